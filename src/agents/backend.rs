@@ -134,7 +134,7 @@ pub struct ChatResponse {
     pub tokens_used: Option<TokenUsage>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TokenUsage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
@@ -152,6 +152,51 @@ pub trait AiBackend: Send + Sync {
 
     /// Check if the backend is available.
     async fn health_check(&self) -> Result<bool, AgentError>;
+
+    /// Forget the response to a request the caller couldn't use, so asking
+    /// again reaches the model. Only caching backends have anything to
+    /// forget.
+    fn invalidate(&self, _request: &ChatRequest) {}
+}
+
+/// Send a request and parse the reply's content with `parse`, returning
+/// the parsed value and the response.
+///
+/// A reply that fails to parse is invalidated, so a cache doesn't serve it
+/// again, and the model is asked once more with the parse error appended
+/// to the conversation. If that reply fails too, its error is returned.
+pub async fn chat_parsed<T>(
+    backend: &dyn AiBackend,
+    request: ChatRequest,
+    parse: impl Fn(&str) -> Result<T, AgentError>,
+) -> Result<(T, ChatResponse), AgentError> {
+    let response = backend.chat(request.clone()).await?;
+    debug!("AI response: {}", response.content);
+    let error = match parse(&response.content) {
+        Ok(parsed) => return Ok((parsed, response)),
+        Err(e) => e,
+    };
+    backend.invalidate(&request);
+    warn!("AI response did not parse ({}); asking again", error);
+
+    let mut corrected = request;
+    corrected
+        .messages
+        .push(ChatMessage::assistant(response.content));
+    corrected.messages.push(ChatMessage::user(format!(
+        "Your reply could not be used: {}. Reply again with only the JSON \
+         requested, following the format exactly.",
+        error
+    )));
+    let response = backend.chat(corrected.clone()).await?;
+    debug!("AI response: {}", response.content);
+    match parse(&response.content) {
+        Ok(parsed) => Ok((parsed, response)),
+        Err(e) => {
+            backend.invalidate(&corrected);
+            Err(e)
+        }
+    }
 }
 
 /// Ollama backend implementation.
@@ -159,6 +204,7 @@ pub struct OllamaBackend {
     client: reqwest::Client,
     base_url: String,
     model: String,
+    num_ctx: Option<u32>,
 }
 
 impl OllamaBackend {
@@ -172,9 +218,16 @@ impl OllamaBackend {
             client,
             base_url,
             model,
+            num_ctx: None,
         }
     }
 
+    /// Builder method to set the context window (`num_ctx`) per request.
+    pub fn with_num_ctx(mut self, num_ctx: Option<u32>) -> Self {
+        self.num_ctx = num_ctx;
+        self
+    }
+
     pub fn from_config(config: &AiBackendConfig) -> Option<Self> {
         match config {
             AiBackendConfig::Ollama {
@@ -186,6 +239,18 @@ impl OllamaBackend {
             _ => None,
         }
     }
+
+    /// Check the server and the configured model, pulling the model when it
+    /// is missing and `pull` is set.
+    pub async fn diagnose(
+        &self,
+        pull: bool,
+        on_progress: impl FnMut(&super::ollama::PullProgress),
+    ) -> super::ollama::OllamaDiagnostics {
+        super::ollama::OllamaAdmin::new(self.base_url.clone())
+            .diagnose(&self.model, pull, on_progress)
+            .await
+    }
 }
 
 /// Ollama API request format.
@@ -211,6 +276,8 @@ struct OllamaOptions {
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     num_predict: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_ctx: Option<u32>,
 }
 
 /// Ollama API response format.
@@ -263,6 +330,7 @@ impl AiBackend for OllamaBackend {
             options: OllamaOptions {
                 temperature: request.temperature,
                 num_predict: request.max_tokens,
+                num_ctx: self.num_ctx,
             },
         };
 
@@ -310,15 +378,13 @@ impl AiBackend for OllamaBackend {
     }
 
     async fn health_check(&self) -> Result<bool, AgentError> {
-        let url = format!("{}/api/tags", self.base_url);
-
-        match self.client.get(&url).send().await {
-            Ok(response) => Ok(response.status().is_success()),
-            Err(e) => {
-                warn!("Ollama health check failed: {}", e);
-                Ok(false)
+        let diagnostics = self.diagnose(false, |_| {}).await;
+        if !diagnostics.is_healthy() {
+            for line in diagnostics.report() {
+                warn!("Ollama health check: {}", line);
             }
         }
+        Ok(diagnostics.is_healthy())
     }
 }
 
@@ -595,6 +661,164 @@ pub fn create_backend(config: &AiBackendConfig) -> Box<dyn AiBackend> {
     }
 }
 
+// --- Response cache ---
+
+/// A cached response, stored as `<key>.json`.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResponse {
+    model: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+    content: String,
+    #[serde(default)]
+    tokens_used: Option<TokenUsage>,
+}
+
+/// Content-addressed store of AI responses (`state/ai_cache/`).
+///
+/// Entries are keyed on a hash of the backend, model and full request, so
+/// re-running an agent on identical input reuses the earlier response.
+/// Entries older than the TTL are ignored and replaced on the next call.
+#[derive(Debug, Clone)]
+pub struct AiCache {
+    dir: std::path::PathBuf,
+    ttl: Option<chrono::Duration>,
+}
+
+impl AiCache {
+    /// A cache in `dir`; `ttl` of None keeps entries forever.
+    pub fn new(dir: impl Into<std::path::PathBuf>, ttl: Option<chrono::Duration>) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl,
+        }
+    }
+
+    /// Cache key for a request to `model` on `backend`.
+    pub fn key(backend: &str, model: &str, request: &ChatRequest) -> String {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        for part in [
+            backend,
+            model,
+            &format!("{:?}", request.temperature),
+            &format!("{:?}", request.max_tokens),
+            &request.json_mode.to_string(),
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        for message in &request.messages {
+            hasher.update(format!("{:?}", message.role).as_bytes());
+            hasher.update([0]);
+            hasher.update(message.content.as_bytes());
+            hasher.update([0]);
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    fn path(&self, key: &str) -> std::path::PathBuf {
+        // Fan out by prefix to keep directories small
+        self.dir.join(&key[..2]).join(format!("{}.json", key))
+    }
+
+    /// The cached response for a key, unless missing or expired.
+    pub fn get(&self, key: &str) -> Option<ChatResponse> {
+        let json = std::fs::read_to_string(self.path(key)).ok()?;
+        let cached: CachedResponse = serde_json::from_str(&json).ok()?;
+        if let Some(ttl) = self.ttl {
+            if chrono::Utc::now() - cached.created_at > ttl {
+                return None;
+            }
+        }
+        Some(ChatResponse {
+            content: cached.content,
+            model: cached.model,
+            tokens_used: cached.tokens_used,
+        })
+    }
+
+    /// Store a response under a key.
+    pub fn put(&self, key: &str, response: &ChatResponse) -> std::io::Result<()> {
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let cached = CachedResponse {
+            model: response.model.clone(),
+            created_at: chrono::Utc::now(),
+            content: response.content.clone(),
+            tokens_used: response.tokens_used.clone(),
+        };
+        // Write then rename, so a concurrent reader never sees half an entry
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec(&cached)?)?;
+        std::fs::rename(tmp, path)
+    }
+
+    /// Drop the entry for a key, if there is one.
+    pub fn remove(&self, key: &str) -> std::io::Result<()> {
+        match std::fs::remove_file(self.path(key)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Backend that answers repeated requests from an [`AiCache`].
+pub struct CachedBackend {
+    inner: std::sync::Arc<dyn AiBackend>,
+    model: String,
+    cache: AiCache,
+}
+
+impl CachedBackend {
+    /// Wrap `inner`, which sends requests to `model`.
+    pub fn new(
+        inner: std::sync::Arc<dyn AiBackend>,
+        model: impl Into<String>,
+        cache: AiCache,
+    ) -> Self {
+        Self {
+            inner,
+            model: model.into(),
+            cache,
+        }
+    }
+}
+
+#[async_trait]
+impl AiBackend for CachedBackend {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    async fn chat(&self, request: ChatRequest) -> Result<ChatResponse, AgentError> {
+        let key = AiCache::key(self.inner.name(), &self.model, &request);
+        if let Some(response) = self.cache.get(&key) {
+            debug!("AI cache hit {}", key);
+            return Ok(response);
+        }
+        let response = self.inner.chat(request).await?;
+        if let Err(e) = self.cache.put(&key, &response) {
+            warn!("Failed to write AI cache entry {}: {}", key, e);
+        }
+        Ok(response)
+    }
+
+    async fn health_check(&self) -> Result<bool, AgentError> {
+        self.inner.health_check().await
+    }
+
+    fn invalidate(&self, request: &ChatRequest) {
+        let key = AiCache::key(self.inner.name(), &self.model, request);
+        if let Err(e) = self.cache.remove(&key) {
+            warn!("Failed to remove AI cache entry {}: {}", key, e);
+        }
+        self.inner.invalidate(request);
+    }
+}
+
 /// Mock backend for testing.
 #[cfg(test)]
 pub struct MockBackend {
@@ -671,6 +895,126 @@ mod tests {
         assert_eq!(request.temperature, Some(0.7));
     }
 
+    /// Counts the requests that reach it.
+    struct CountingBackend(std::sync::atomic::AtomicU32);
+
+    #[async_trait]
+    impl AiBackend for CountingBackend {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        async fn chat(&self, request: ChatRequest) -> Result<ChatResponse, AgentError> {
+            let n = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(ChatResponse {
+                content: format!("{} #{}", request.messages[0].content, n),
+                model: "counting".to_string(),
+                tokens_used: None,
+            })
+        }
+
+        async fn health_check(&self) -> Result<bool, AgentError> {
+            Ok(true)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_backend_reuses_responses() {
+        let tmp = tempfile::tempdir().unwrap();
+        let inner = std::sync::Arc::new(CountingBackend(Default::default()));
+        let cached = CachedBackend::new(inner.clone(), "m1", AiCache::new(tmp.path(), None));
+        let request = |text: &str| ChatRequest::new(vec![ChatMessage::user(text)]);
+
+        let first = cached.chat(request("list")).await.unwrap();
+        let again = cached.chat(request("list")).await.unwrap();
+        assert_eq!(first.content, "list #0");
+        assert_eq!(again.content, "list #0");
+        cached.chat(request("other")).await.unwrap();
+        cached.chat(request("list").with_json_mode()).await.unwrap();
+        assert_eq!(inner.0.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+        // Another model, or an expired entry, goes back to the backend
+        let other_model = CachedBackend::new(inner.clone(), "m2", AiCache::new(tmp.path(), None));
+        other_model.chat(request("list")).await.unwrap();
+        let expired = CachedBackend::new(
+            inner.clone(),
+            "m1",
+            AiCache::new(
+                tmp.path(),
+                Some(chrono::Duration::zero() - chrono::Duration::seconds(1)),
+            ),
+        );
+        expired.chat(request("list")).await.unwrap();
+        assert_eq!(inner.0.load(std::sync::atomic::Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_cached_backend_forgets_unparsable_responses() {
+        let tmp = tempfile::tempdir().unwrap();
+        let inner = std::sync::Arc::new(CountingBackend(Default::default()));
+        let cached = CachedBackend::new(inner.clone(), "m1", AiCache::new(tmp.path(), None));
+        let request = || ChatRequest::new(vec![ChatMessage::user("list")]);
+        // The first two replies are malformed, later ones parse
+        let parse = |content: &str| {
+            if content.ends_with("#0") || content.ends_with("#1") {
+                Err(AgentError::ResponseParseError("bad json".into()))
+            } else {
+                Ok(content.to_string())
+            }
+        };
+
+        // The re-prompt fails too, and neither reply is kept
+        assert!(chat_parsed(&cached, request(), parse).await.is_err());
+        assert_eq!(inner.0.load(std::sync::atomic::Ordering::SeqCst), 2);
+        let (parsed, _) = chat_parsed(&cached, request(), parse).await.unwrap();
+        assert_eq!(parsed, "list #2");
+        assert_eq!(inner.0.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+        // The good reply is cached
+        let (parsed, _) = chat_parsed(&cached, request(), parse).await.unwrap();
+        assert_eq!(parsed, "list #2");
+        assert_eq!(inner.0.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    /// Replies with invalid JSON until it is told why.
+    struct CorrectableBackend;
+
+    #[async_trait]
+    impl AiBackend for CorrectableBackend {
+        fn name(&self) -> &'static str {
+            "correctable"
+        }
+
+        async fn chat(&self, request: ChatRequest) -> Result<ChatResponse, AgentError> {
+            let corrected = request.messages.last().is_some_and(|m| {
+                m.content
+                    .contains("could not be used: AI response unparseable")
+            });
+            Ok(ChatResponse {
+                content: if corrected { "{}" } else { "{" }.to_string(),
+                model: "correctable".to_string(),
+                tokens_used: None,
+            })
+        }
+
+        async fn health_check(&self) -> Result<bool, AgentError> {
+            Ok(true)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chat_parsed_reprompts_with_the_error() {
+        let request = ChatRequest::new(vec![ChatMessage::user("list")]);
+        let (parsed, response) = chat_parsed(&CorrectableBackend, request, |c| {
+            serde_json::from_str::<serde_json::Value>(c)
+                .map_err(|e| AgentError::ResponseParseError(e.to_string()))
+        })
+        .await
+        .unwrap();
+        assert_eq!(parsed, serde_json::json!({}));
+        assert_eq!(response.content, "{}");
+    }
+
     #[tokio::test]
     async fn test_mock_backend() {
         let backend = MockBackend::new(r#"{"result": "test"}"#);
@@ -844,6 +1188,7 @@ mod tests {
             options: OllamaOptions {
                 temperature: Some(0.7),
                 num_predict: Some(1000),
+                num_ctx: Some(8192),
             },
         };
 
@@ -851,6 +1196,7 @@ mod tests {
         assert!(json.contains("llama3.2"));
         assert!(json.contains("Hello"));
         assert!(json.contains("json"));
+        assert!(json.contains("\"num_ctx\":8192"));
     }
 
     #[test]
@@ -1,16 +1,18 @@
 //! Balance Watcher Agent.
 //!
-//! Monitors Warhammer Community for balance updates and edition releases.
+//! Monitors Warhammer Community for balance updates and edition releases,
+//! plus FAQ/errata documents (recorded as non-boundary annotations).
 
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info};
+use tracing::info;
 
-use super::backend::{AiBackend, ChatMessage, ChatRequest};
-use super::{Agent, AgentError, AgentOutput, RetryPolicy};
+use super::backend::{chat_parsed, AiBackend, ChatMessage, ChatRequest};
+use super::{Agent, AgentError, AgentOutput, RetryNotes, RetryPolicy};
+use crate::models::canonical_faction_name;
 use crate::models::{Confidence, SignificantEvent, SignificantEventId, SignificantEventType};
 
 /// Input for the Balance Watcher agent.
@@ -29,21 +31,33 @@ pub struct BalanceWatcherInput {
 /// Output from the Balance Watcher agent.
 #[derive(Debug, Clone)]
 pub struct BalanceWatcherOutput {
-    /// Newly discovered balance updates / edition releases
+    /// Newly discovered balance updates / edition releases / FAQs
     pub events: Vec<AgentOutput<SignificantEvent>>,
 
     /// PDF URLs found (to be downloaded separately)
     pub pdf_urls: Vec<String>,
 }
 
+impl RetryNotes for BalanceWatcherOutput {
+    fn note_retries(&mut self, note: &str) {
+        for event in &mut self.events {
+            event.note_retries(note);
+        }
+    }
+}
+
 /// AI-extracted balance update data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ExtractedBalanceUpdate {
     title: String,
     date: Option<String>,
+    #[serde(default)]
+    effective_date: Option<String>,
     event_type: String,
     pdf_url: Option<String>,
     summary: Option<String>,
+    #[serde(default)]
+    affected_factions: Vec<String>,
     confidence: String,
 }
 
@@ -55,11 +69,21 @@ struct BalanceWatcherResponse {
 /// Balance Watcher agent implementation.
 pub struct BalanceWatcherAgent {
     backend: Arc<dyn AiBackend>,
+    grace_days: u32,
 }
 
 impl BalanceWatcherAgent {
     pub fn new(backend: Arc<dyn AiBackend>) -> Self {
-        Self { backend }
+        Self {
+            backend,
+            grace_days: 0,
+        }
+    }
+
+    /// Grace period given to discovered passes (`[epochs] grace_days`).
+    pub fn with_grace_days(mut self, days: u32) -> Self {
+        self.grace_days = days;
+        self
     }
 
     fn build_prompt(&self, html_content: &str) -> Vec<ChatMessage> {
@@ -93,6 +117,9 @@ impl BalanceWatcherAgent {
                 "edition_release" | "edition" | "new_edition" => {
                     SignificantEventType::EditionRelease
                 }
+                "faq_update" | "faq" | "errata" | "rules_commentary" => {
+                    SignificantEventType::FaqUpdate
+                }
                 _ => SignificantEventType::BalanceUpdate,
             };
 
@@ -107,14 +134,31 @@ impl BalanceWatcherAgent {
                 date,
                 update.title.clone(),
                 source_url.to_string(),
-            );
+            )
+            .with_grace_days(self.grace_days);
 
+            if let Some(effective) = update
+                .effective_date
+                .as_ref()
+                .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            {
+                event = event.with_effective_date(effective);
+            }
             if let Some(pdf_url) = update.pdf_url.clone() {
                 event = event.with_pdf_url(pdf_url);
             }
             if let Some(summary) = update.summary.clone() {
                 event = event.with_summary(summary);
             }
+            let mut factions: Vec<String> = update
+                .affected_factions
+                .iter()
+                .map(|f| canonical_faction_name(f))
+                .filter(|f| !f.is_empty())
+                .collect();
+            factions.sort();
+            factions.dedup();
+            event = event.with_affected_factions(factions);
 
             let confidence = match update.confidence.to_lowercase().as_str() {
                 "high" => Confidence::High,
@@ -137,19 +181,21 @@ impl BalanceWatcherAgent {
     }
 }
 
-const BALANCE_WATCHER_SYSTEM_PROMPT: &str = r#"You are analyzing a Warhammer Community webpage for balance updates and edition releases.
+const BALANCE_WATCHER_SYSTEM_PROMPT: &str = r#"You are analyzing a Warhammer Community webpage for balance updates, edition releases and rules FAQs.
 
 Look for:
 1. "Balance Dataslate" announcements with PDF links
 2. Edition release announcements (e.g., "10th Edition", "Index Update")
-3. Major FAQ updates that affect competitive play
+3. FAQ, errata and Rules Commentary documents that affect competitive play
 
 For each found, extract:
 - title: Exact title as shown on page
 - date: Publication date in YYYY-MM-DD format (null if not found)
-- event_type: "balance_update" or "edition_release"
+- effective_date: Date the changes take effect in YYYY-MM-DD format, if stated separately from publication (null otherwise)
+- event_type: "balance_update", "edition_release", or "faq_update" (FAQ/errata/rules commentary without points changes)
 - pdf_url: Full URL to PDF download (null if not available)
 - summary: Brief summary of key changes (null if unclear)
+- affected_factions: Factions the document applies to, e.g. a faction FAQ (empty list for core rules or if unclear)
 - confidence: "high", "medium", or "low" based on how clearly the info was stated
 
 Return JSON in this exact format:
@@ -158,9 +204,11 @@ Return JSON in this exact format:
     {
       "title": "Balance Dataslate Spring 2025",
       "date": "2025-03-15",
+      "effective_date": "2025-03-20",
       "event_type": "balance_update",
       "pdf_url": "https://...",
       "summary": "Major changes to...",
+      "affected_factions": [],
       "confidence": "high"
     }
   ]
@@ -189,10 +237,10 @@ impl Agent for BalanceWatcherAgent {
         let messages = self.build_prompt(&input.html_content);
         let request = ChatRequest::new(messages).with_json_mode();
 
-        let response = self.backend.chat(request).await?;
-        debug!("AI response: {}", response.content);
-
-        let events = self.parse_response(&response.content, &input.source_url)?;
+        let (events, _) = chat_parsed(self.backend.as_ref(), request, |c| {
+            self.parse_response(c, &input.source_url)
+        })
+        .await?;
 
         // Filter out known events
         let new_events: Vec<_> = events
@@ -328,6 +376,21 @@ mod tests {
         assert_eq!(agent.name(), "balance_watcher");
     }
 
+    #[tokio::test]
+    async fn test_balance_watcher_grace_days() {
+        let backend = Arc::new(MockBackend::new(mock_response()));
+        let agent = BalanceWatcherAgent::new(backend).with_grace_days(3);
+
+        let input = BalanceWatcherInput {
+            html_content: "<html>...</html>".to_string(),
+            source_url: "https://warhammer-community.com/updates".to_string(),
+            known_event_ids: vec![],
+        };
+
+        let output = agent.execute(input).await.unwrap();
+        assert!(output.events.iter().all(|e| e.data.grace_days == 3));
+    }
+
     #[test]
     fn test_balance_watcher_parse_response() {
         let backend: Arc<dyn AiBackend> = Arc::new(MockBackend::new("{}"));
@@ -341,6 +404,34 @@ mod tests {
         assert_eq!(events[0].confidence, Confidence::High);
     }
 
+    #[test]
+    fn test_balance_watcher_detects_faqs() {
+        let backend: Arc<dyn AiBackend> = Arc::new(MockBackend::new("{}"));
+        let agent = BalanceWatcherAgent::new(backend);
+
+        let response = r#"{
+            "updates": [
+                {
+                    "title": "Aeldari and Drukhari FAQ",
+                    "date": "2025-07-02",
+                    "event_type": "errata",
+                    "pdf_url": "https://example.com/aeldari-faq.pdf",
+                    "summary": "Clarifies Fire Dragons",
+                    "affected_factions": ["aeldari", "Drukhari", "Aeldari"],
+                    "confidence": "high"
+                }
+            ]
+        }"#;
+        let events = agent
+            .parse_response(response, "https://example.com")
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        let faq = &events[0].data;
+        assert_eq!(faq.event_type, SignificantEventType::FaqUpdate);
+        assert!(!faq.event_type.is_epoch_boundary());
+        assert_eq!(faq.affected_factions, vec!["Aeldari", "Drukhari"]);
+    }
+
     #[test]
     fn test_balance_watcher_retry_policy() {
         let backend: Arc<dyn AiBackend> = Arc::new(MockBackend::new("{}"));
@@ -0,0 +1,298 @@
+//! Dataslate Parser Agent.
+//!
+//! Downloads a balance dataslate PDF, extracts its text and asks the model
+//! to list every rule change as a structured [`RuleChange`]. Long documents
+//! are sent in line-aligned chunks and the results merged.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tracing::info;
+use url::Url;
+
+use super::backend::{chat_parsed, AiBackend, ChatMessage, ChatRequest};
+use super::{Agent, AgentError, AgentOutput, RetryPolicy};
+use crate::fetch::{pdf, Fetcher};
+use crate::models::canonical_faction_name;
+use crate::models::{Confidence, RuleChange, RuleChangeType};
+
+/// Characters of PDF text sent per request.
+const CHUNK_CHARS: usize = 12_000;
+
+/// Faction name used for changes that apply to every army.
+pub const CORE_RULES: &str = "Core Rules";
+
+/// Input for the Dataslate Parser agent.
+#[derive(Debug, Clone)]
+pub struct DataslateParserInput {
+    /// URL of the dataslate PDF
+    pub pdf_url: String,
+
+    /// Title of the balance pass, for context
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtractedRuleChange {
+    faction: Option<String>,
+    unit: Option<String>,
+    change_type: String,
+    summary: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DataslateParserResponse {
+    changes: Vec<ExtractedRuleChange>,
+}
+
+/// Dataslate Parser agent implementation.
+pub struct DataslateParserAgent {
+    backend: Arc<dyn AiBackend>,
+    fetcher: Arc<Fetcher>,
+}
+
+impl DataslateParserAgent {
+    pub fn new(backend: Arc<dyn AiBackend>, fetcher: Arc<Fetcher>) -> Self {
+        Self { backend, fetcher }
+    }
+
+    fn build_prompt(&self, title: &str, text: &str) -> Vec<ChatMessage> {
+        vec![
+            ChatMessage::system(DATASLATE_PARSER_SYSTEM_PROMPT),
+            ChatMessage::user(format!(
+                "List the rule changes in this excerpt of \"{}\":\n\n{}",
+                title, text
+            )),
+        ]
+    }
+
+    fn parse_response(&self, response: &str) -> Result<Vec<RuleChange>, AgentError> {
+        let trimmed = response.trim();
+        if trimmed.is_empty() {
+            return Ok(Vec::new());
+        }
+        let parsed: DataslateParserResponse = serde_json::from_str(trimmed)
+            .map_err(|e| AgentError::ResponseParseError(format!("Invalid JSON: {}", e)))?;
+
+        Ok(parsed
+            .changes
+            .into_iter()
+            .filter(|c| !c.summary.trim().is_empty())
+            .map(|c| {
+                let change_type = match c.change_type.to_lowercase().as_str() {
+                    "buff" => RuleChangeType::Buff,
+                    "nerf" => RuleChangeType::Nerf,
+                    "clarification" | "errata" => RuleChangeType::Clarification,
+                    _ => RuleChangeType::Rework,
+                };
+                let faction = c
+                    .faction
+                    .map(|f| canonical_faction_name(&f))
+                    .filter(|f| !f.is_empty() && !f.eq_ignore_ascii_case("core"))
+                    .unwrap_or_else(|| CORE_RULES.to_string());
+                RuleChange {
+                    faction,
+                    unit: c.unit.filter(|u| !u.trim().is_empty()),
+                    change_type,
+                    summary: c.summary.trim().to_string(),
+                }
+            })
+            .collect())
+    }
+
+    /// Run the model over already-extracted dataslate text.
+    pub async fn parse_text(
+        &self,
+        title: &str,
+        text: &str,
+    ) -> Result<AgentOutput<Vec<RuleChange>>, AgentError> {
+        let chunks = chunk_lines(text, CHUNK_CHARS);
+        let mut changes = Vec::new();
+        let mut seen = HashSet::new();
+        for chunk in &chunks {
+            let request = ChatRequest::new(self.build_prompt(title, chunk)).with_json_mode();
+            let (parsed, _) =
+                chat_parsed(self.backend.as_ref(), request, |c| self.parse_response(c)).await?;
+            for change in parsed {
+                let key = (
+                    change.faction.clone(),
+                    change.unit.clone(),
+                    change.summary.to_lowercase(),
+                );
+                if seen.insert(key) {
+                    changes.push(change);
+                }
+            }
+        }
+
+        let mut notes = vec![format!(
+            "{} rule changes from {} chunks",
+            changes.len(),
+            chunks.len()
+        )];
+        let confidence = if changes.is_empty() {
+            notes.push("No rule changes found".to_string());
+            Confidence::Low
+        } else {
+            Confidence::Medium
+        };
+        Ok(AgentOutput::new(changes, confidence).with_notes(notes))
+    }
+}
+
+/// Split text into pieces of at most `max_chars`, breaking between lines.
+fn chunk_lines(text: &str, max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for line in text.lines().filter(|l| !l.trim().is_empty()) {
+        if !current.is_empty() && current.len() + line.len() + 1 > max_chars {
+            chunks.push(std::mem::take(&mut current));
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+const DATASLATE_PARSER_SYSTEM_PROMPT: &str = r#"You are reading a Warhammer 40,000 Balance Dataslate and listing every rule change it makes.
+
+For each change, extract:
+- faction: The faction it applies to (null for core rules, missions or changes to every army)
+- unit: The unit it applies to (null for army rules, detachments, enhancements and stratagems)
+- change_type: "buff" (makes the faction stronger), "nerf" (makes it weaker), "rework" (changes how a rule works without a clear direction) or "clarification" (wording fix or FAQ-style ruling)
+- summary: One sentence describing the change
+
+Return JSON in this exact format:
+{
+  "changes": [
+    {
+      "faction": "Aeldari",
+      "unit": "Fire Dragons",
+      "change_type": "nerf",
+      "summary": "Melta bonus no longer applies to re-rolled wound rolls."
+    }
+  ]
+}
+
+If the excerpt contains no rule changes, return: {"changes": []}
+
+IMPORTANT:
+- Only list changes stated in the text
+- Do NOT list points changes; those come from the Munitorum Field Manual
+- Do NOT invent or guess information"#;
+
+#[async_trait]
+impl Agent for DataslateParserAgent {
+    type Input = DataslateParserInput;
+    type Output = AgentOutput<Vec<RuleChange>>;
+
+    fn name(&self) -> &'static str {
+        "dataslate_parser"
+    }
+
+    async fn execute(&self, input: Self::Input) -> Result<Self::Output, AgentError> {
+        info!("Running Dataslate Parser on {}", input.pdf_url);
+
+        let url = Url::parse(&input.pdf_url)
+            .map_err(|e| AgentError::ExtractionRefused(format!("Invalid PDF URL: {}", e)))?;
+        let result = self.fetcher.fetch(&url).await?;
+        let bytes = self.fetcher.read_cached_bytes(&result).await?;
+        let text = pdf::extract_text(&bytes)?;
+
+        let output = self.parse_text(&input.title, &text).await?;
+        info!(
+            "Dataslate Parser found {} rule changes in {}",
+            output.data.len(),
+            input.title
+        );
+        Ok(output)
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            initial_delay_ms: 2000,
+            backoff_multiplier: 2.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::backend::MockBackend;
+    use crate::fetch::{CacheMetadata, FetcherConfig};
+    use chrono::Utc;
+
+    const RESPONSE: &str = r#"{
+        "changes": [
+            {"faction": "aeldari", "unit": "Fire Dragons", "change_type": "nerf", "summary": "Melta bonus reduced."},
+            {"faction": null, "unit": null, "change_type": "rework", "summary": "Devastating Wounds now ends the attack sequence."},
+            {"faction": "Orks", "unit": "", "change_type": "BUFF", "summary": "Waaagh! lasts an extra turn."},
+            {"faction": "Orks", "unit": null, "change_type": "buff", "summary": "  "}
+        ]
+    }"#;
+
+    #[tokio::test]
+    async fn test_dataslate_parser_downloads_and_parses() {
+        let tmp = tempfile::tempdir().unwrap();
+        let fetcher = Fetcher::new(FetcherConfig {
+            cache_dir: tmp.path().to_path_buf(),
+            ..FetcherConfig::default()
+        })
+        .unwrap();
+
+        // Seed the fetcher cache so no network is needed
+        let pdf_url = "https://example.com/dataslate.pdf";
+        let url = Url::parse(pdf_url).unwrap();
+        let bytes = pdf::tests::text_pdf(&["AELDARI", "Fire Dragons: melta bonus reduced."]);
+        let cache_path = fetcher.cache_path_for_url(&url);
+        std::fs::create_dir_all(cache_path.parent().unwrap()).unwrap();
+        std::fs::write(&cache_path, &bytes).unwrap();
+        let meta = CacheMetadata {
+            url: pdf_url.to_string(),
+            fetched_at: Utc::now(),
+            content_type: Some("application/pdf".to_string()),
+            content_length: bytes.len(),
+            etag: None,
+            last_modified: None,
+            expires_at: None,
+        };
+        std::fs::write(
+            fetcher.meta_path_for_url(&url),
+            serde_json::to_string(&meta).unwrap(),
+        )
+        .unwrap();
+
+        let agent =
+            DataslateParserAgent::new(Arc::new(MockBackend::new(RESPONSE)), Arc::new(fetcher));
+        let output = agent
+            .execute(DataslateParserInput {
+                pdf_url: pdf_url.to_string(),
+                title: "Balance Dataslate June 2025".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(output.data.len(), 3);
+        assert_eq!(output.data[0].faction, "Aeldari");
+        assert_eq!(output.data[0].unit.as_deref(), Some("Fire Dragons"));
+        assert_eq!(output.data[0].change_type, RuleChangeType::Nerf);
+        assert_eq!(output.data[1].faction, CORE_RULES);
+        assert_eq!(output.data[2].change_type, RuleChangeType::Buff);
+        assert_eq!(output.data[2].unit, None);
+    }
+
+    #[test]
+    fn test_chunk_lines() {
+        let text = "aaaa\nbbbb\n\ncccc\n";
+        assert_eq!(chunk_lines(text, 10), vec!["aaaa\nbbbb\n", "cccc\n"]);
+        assert_eq!(chunk_lines(text, 100).len(), 1);
+        assert!(chunk_lines("", 10).is_empty());
+    }
+}
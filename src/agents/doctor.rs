@@ -0,0 +1,121 @@
+//! Setup checks for the AI backend (`ai doctor`).
+//!
+//! Runs a sample list through the List Normalizer to measure real latency,
+//! and sizes a context window from the sample's token usage so long lists
+//! aren't silently truncated by Ollama's small default window.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::backend::AiBackend;
+use super::estimate::UsageSample;
+use super::list_normalizer::{ListNormalizerAgent, ListNormalizerInput};
+use super::{Agent, AgentError};
+use crate::models::Confidence;
+
+/// A typical mid-sized tournament list used for the sample extraction.
+pub const SAMPLE_LIST: &str = "\
+Aeldari - Battle Host
+Strike Force (2000 points)
+
+CHARACTERS
+Autarch (85 points)
+  • 1x Star glaive
+Farseer (70 points)
+  • Warlord
+  • 1x Singing spear
+
+BATTLELINE
+Guardian Defenders (100 points)
+  • 10x Guardian Defender
+  • 1x Heavy Weapon Platform
+
+OTHER DATASHEETS
+Fire Prism (170 points)
+  • 1x Prism cannon
+Wave Serpent (125 points)
+  • 1x Twin shuriken cannon
+Wraithguard (170 points)
+  • 5x Wraithguard with wraithcannons
+War Walkers (130 points)
+  • 2x War Walker
+";
+
+/// Faction hint sent with [`SAMPLE_LIST`].
+pub const SAMPLE_FACTION: &str = "Aeldari";
+
+/// Context window sizes to recommend from, smallest first.
+pub const CONTEXT_WINDOWS: &[u32] = &[4096, 8192, 16384, 32768];
+
+/// Largest lists are roughly this many times the sample's size.
+const LARGE_LIST_FACTOR: u32 = 3;
+
+/// Result of the sample extraction.
+#[derive(Debug, Clone)]
+pub struct SampleRun {
+    pub elapsed: Duration,
+    pub units: usize,
+    pub confidence: Confidence,
+    /// Estimated tokens for the sample request
+    pub usage: UsageSample,
+}
+
+/// Normalize [`SAMPLE_LIST`] and time it.
+pub async fn run_sample_extraction(backend: Arc<dyn AiBackend>) -> Result<SampleRun, AgentError> {
+    let usage = ListNormalizerAgent::estimate_usage(SAMPLE_LIST, Some(SAMPLE_FACTION), &[]);
+    let started = Instant::now();
+    let output = ListNormalizerAgent::new(backend)
+        .execute(ListNormalizerInput {
+            raw_text: SAMPLE_LIST.to_string(),
+            faction_hint: Some(SAMPLE_FACTION.to_string()),
+            player_name: "ai doctor".to_string(),
+        })
+        .await?;
+    Ok(SampleRun {
+        elapsed: started.elapsed(),
+        units: output.list.data.units.len(),
+        confidence: output.list.confidence,
+        usage,
+    })
+}
+
+/// Smallest standard context window that fits the largest expected list
+/// request, capped at what the model supports.
+pub fn recommend_context_window(usage: &UsageSample, model_max: Option<u64>) -> u32 {
+    let needed = (usage.prompt_tokens + usage.completion_tokens) * LARGE_LIST_FACTOR;
+    let window = CONTEXT_WINDOWS
+        .iter()
+        .copied()
+        .find(|w| *w >= needed)
+        .unwrap_or(CONTEXT_WINDOWS[CONTEXT_WINDOWS.len() - 1]);
+    match model_max {
+        Some(max) => window.min(max.min(u32::MAX as u64) as u32),
+        None => window,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recommend_context_window() {
+        let small = UsageSample {
+            prompt_tokens: 900,
+            completion_tokens: 400,
+        };
+        assert_eq!(recommend_context_window(&small, None), 4096);
+
+        let sample = ListNormalizerAgent::estimate_usage(SAMPLE_LIST, Some(SAMPLE_FACTION), &[]);
+        let window = recommend_context_window(&sample, None);
+        assert!(CONTEXT_WINDOWS.contains(&window));
+
+        let huge = UsageSample {
+            prompt_tokens: 20_000,
+            completion_tokens: 10_000,
+        };
+        assert_eq!(recommend_context_window(&huge, None), 32768);
+        // Never above the model's trained context
+        assert_eq!(recommend_context_window(&huge, Some(8192)), 8192);
+    }
+}
@@ -0,0 +1,374 @@
+//! Extraction drift between model versions.
+//!
+//! Compares what two models extract from the same raw army lists: unit
+//! counts, confidence, and how often a recognised faction is assigned. A
+//! model upgrade that quietly extracts fewer units or drops factions shows
+//! up as a regression before it is rolled out across a whole epoch.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::list_normalizer::NormalizedArmyList;
+use super::AgentOutput;
+use crate::models::lookup_faction;
+use crate::models::{ArmyList, Confidence};
+
+/// Minimum faction agreement (%) on paired inputs before flagging.
+const MIN_FACTION_AGREEMENT: f64 = 90.0;
+
+/// Largest tolerated drop (percentage points) in recognised-faction rate.
+const MAX_FACTION_RATE_DROP: f64 = 5.0;
+
+/// Largest tolerated relative drop in mean units per list.
+const MAX_UNIT_DROP: f64 = 0.10;
+
+/// Largest tolerated rise (percentage points) in low-confidence share.
+const MAX_LOW_CONFIDENCE_RISE: f64 = 10.0;
+
+/// Largest tolerated rise (percentage points) in lists with no units.
+const MAX_EMPTY_RISE: f64 = 5.0;
+
+/// Unit-count histogram buckets: (label, inclusive upper bound).
+const UNIT_BUCKETS: [(&str, u32); 5] = [
+    ("0", 0),
+    ("1-5", 5),
+    ("6-10", 10),
+    ("11-15", 15),
+    ("16+", u32::MAX),
+];
+
+/// Hash of a raw list, ignoring whitespace differences.
+pub fn raw_hash(raw_text: &str) -> String {
+    let normalized = raw_text.split_whitespace().collect::<Vec<_>>().join(" ");
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
+
+/// One model's extraction of one raw list.
+#[derive(Debug, Clone)]
+pub struct ExtractionSample {
+    pub raw_hash: String,
+    pub model: String,
+    pub faction: String,
+    pub unit_count: u32,
+    pub confidence: Confidence,
+}
+
+impl ExtractionSample {
+    /// Sample from a stored list. `None` if the list records no model.
+    pub fn from_list(list: &ArmyList) -> Option<Self> {
+        Some(Self {
+            raw_hash: raw_hash(&list.raw_text),
+            model: list.extracted_by.clone()?,
+            faction: list.faction.clone(),
+            unit_count: list.units.len() as u32,
+            confidence: list.extraction_confidence,
+        })
+    }
+
+    /// Sample from a fresh normalizer run over `raw_text`.
+    pub fn from_output(
+        raw_text: &str,
+        fallback_model: &str,
+        output: &AgentOutput<NormalizedArmyList>,
+    ) -> Self {
+        Self {
+            raw_hash: raw_hash(raw_text),
+            model: output
+                .model
+                .clone()
+                .unwrap_or_else(|| fallback_model.to_string()),
+            faction: output.data.faction.clone(),
+            unit_count: output.data.units.len() as u32,
+            confidence: output.confidence,
+        }
+    }
+
+    fn has_known_faction(&self) -> bool {
+        lookup_faction(&self.faction).is_some()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UnitBucket {
+    pub units: &'static str,
+    /// Share of lists in this bucket (0-100)
+    pub pct: f64,
+}
+
+/// Distribution summary of one model's extractions.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelProfile {
+    pub model: String,
+    pub lists: u32,
+    pub mean_units: f64,
+    pub unit_counts: Vec<UnitBucket>,
+    /// Confidence mix (0-100 each)
+    pub high_confidence_pct: f64,
+    pub medium_confidence_pct: f64,
+    pub low_confidence_pct: f64,
+    /// Lists assigned a recognised faction (0-100)
+    pub faction_assigned_pct: f64,
+}
+
+/// Comparison of a candidate model against a baseline.
+#[derive(Debug, Clone, Serialize)]
+pub struct DriftReport {
+    pub baseline: ModelProfile,
+    pub candidate: ModelProfile,
+    /// Raw inputs extracted by both models
+    pub paired: u32,
+    /// Paired inputs where both assigned the same faction (0-100)
+    pub faction_agreement_pct: f64,
+    /// Paired inputs with the same unit count (0-100)
+    pub unit_count_match_pct: f64,
+    /// Mean candidate minus baseline unit count on paired inputs
+    pub mean_unit_delta: f64,
+    /// Paired inputs where the candidate reported lower confidence
+    pub confidence_downgrades: u32,
+    /// Human-readable reasons the candidate looks worse
+    pub regressions: Vec<String>,
+}
+
+impl DriftReport {
+    pub fn has_regressions(&self) -> bool {
+        !self.regressions.is_empty()
+    }
+}
+
+fn pct(n: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        (n as f64 / total as f64 * 1000.0).round() / 10.0
+    }
+}
+
+fn confidence_rank(c: Confidence) -> u8 {
+    match c {
+        Confidence::High => 2,
+        Confidence::Medium => 1,
+        Confidence::Low => 0,
+    }
+}
+
+/// Summarise one model's samples.
+pub fn profile(model: &str, samples: &[ExtractionSample]) -> ModelProfile {
+    let n = samples.len();
+    let count = |f: &dyn Fn(&ExtractionSample) -> bool| samples.iter().filter(|s| f(s)).count();
+
+    let mut lower = 0u32;
+    let unit_counts = UNIT_BUCKETS
+        .iter()
+        .map(|&(label, upper)| {
+            let in_bucket = count(&|s| s.unit_count >= lower && s.unit_count <= upper);
+            lower = upper.saturating_add(1);
+            UnitBucket {
+                units: label,
+                pct: pct(in_bucket, n),
+            }
+        })
+        .collect();
+
+    let total_units: u32 = samples.iter().map(|s| s.unit_count).sum();
+    ModelProfile {
+        model: model.to_string(),
+        lists: n as u32,
+        mean_units: if n == 0 {
+            0.0
+        } else {
+            (total_units as f64 / n as f64 * 10.0).round() / 10.0
+        },
+        unit_counts,
+        high_confidence_pct: pct(count(&|s| s.confidence == Confidence::High), n),
+        medium_confidence_pct: pct(count(&|s| s.confidence == Confidence::Medium), n),
+        low_confidence_pct: pct(count(&|s| s.confidence == Confidence::Low), n),
+        faction_assigned_pct: pct(count(&|s| s.has_known_faction()), n),
+    }
+}
+
+/// Compare `candidate` against `baseline` extractions of the same inputs.
+pub fn compare(
+    baseline_model: &str,
+    baseline: &[ExtractionSample],
+    candidate_model: &str,
+    candidate: &[ExtractionSample],
+) -> DriftReport {
+    let base = profile(baseline_model, baseline);
+    let cand = profile(candidate_model, candidate);
+
+    let by_hash: HashMap<&str, &ExtractionSample> =
+        baseline.iter().map(|s| (s.raw_hash.as_str(), s)).collect();
+    let pairs: Vec<(&ExtractionSample, &ExtractionSample)> = candidate
+        .iter()
+        .filter_map(|c| Some((*by_hash.get(c.raw_hash.as_str())?, c)))
+        .collect();
+
+    let n = pairs.len();
+    let agree = pairs
+        .iter()
+        .filter(|(b, c)| b.faction.eq_ignore_ascii_case(&c.faction))
+        .count();
+    let same_units = pairs
+        .iter()
+        .filter(|(b, c)| b.unit_count == c.unit_count)
+        .count();
+    let delta: i64 = pairs
+        .iter()
+        .map(|(b, c)| c.unit_count as i64 - b.unit_count as i64)
+        .sum();
+    let downgrades = pairs
+        .iter()
+        .filter(|(b, c)| confidence_rank(c.confidence) < confidence_rank(b.confidence))
+        .count() as u32;
+
+    let empty_pct = |p: &ModelProfile| p.unit_counts.first().map_or(0.0, |b| b.pct);
+    let mut regressions = Vec::new();
+    if n > 0 && pct(agree, n) < MIN_FACTION_AGREEMENT {
+        regressions.push(format!(
+            "faction agreement {:.1}% on {} paired lists (< {:.0}%)",
+            pct(agree, n),
+            n,
+            MIN_FACTION_AGREEMENT
+        ));
+    }
+    if base.faction_assigned_pct - cand.faction_assigned_pct > MAX_FACTION_RATE_DROP {
+        regressions.push(format!(
+            "recognised faction rate fell {:.1}% -> {:.1}%",
+            base.faction_assigned_pct, cand.faction_assigned_pct
+        ));
+    }
+    if base.mean_units > 0.0 && cand.mean_units < base.mean_units * (1.0 - MAX_UNIT_DROP) {
+        regressions.push(format!(
+            "mean units per list fell {:.1} -> {:.1}",
+            base.mean_units, cand.mean_units
+        ));
+    }
+    if cand.low_confidence_pct - base.low_confidence_pct > MAX_LOW_CONFIDENCE_RISE {
+        regressions.push(format!(
+            "low-confidence share rose {:.1}% -> {:.1}%",
+            base.low_confidence_pct, cand.low_confidence_pct
+        ));
+    }
+    if empty_pct(&cand) - empty_pct(&base) > MAX_EMPTY_RISE {
+        regressions.push(format!(
+            "lists with no units rose {:.1}% -> {:.1}%",
+            empty_pct(&base),
+            empty_pct(&cand)
+        ));
+    }
+
+    DriftReport {
+        baseline: base,
+        candidate: cand,
+        paired: n as u32,
+        faction_agreement_pct: pct(agree, n),
+        unit_count_match_pct: pct(same_units, n),
+        mean_unit_delta: if n == 0 {
+            0.0
+        } else {
+            (delta as f64 / n as f64 * 100.0).round() / 100.0
+        },
+        confidence_downgrades: downgrades,
+        regressions,
+    }
+}
+
+/// Group stored lists by the model that extracted them, most lists first.
+pub fn samples_by_model(lists: &[ArmyList]) -> Vec<(String, Vec<ExtractionSample>)> {
+    let mut groups: HashMap<String, Vec<ExtractionSample>> = HashMap::new();
+    for sample in lists.iter().filter_map(ExtractionSample::from_list) {
+        groups.entry(sample.model.clone()).or_default().push(sample);
+    }
+    let mut out: Vec<_> = groups.into_iter().collect();
+    out.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(&b.0)));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(
+        raw: &str,
+        model: &str,
+        faction: &str,
+        units: u32,
+        c: Confidence,
+    ) -> ExtractionSample {
+        ExtractionSample {
+            raw_hash: raw_hash(raw),
+            model: model.to_string(),
+            faction: faction.to_string(),
+            unit_count: units,
+            confidence: c,
+        }
+    }
+
+    #[test]
+    fn test_raw_hash_ignores_whitespace() {
+        assert_eq!(
+            raw_hash("Necrons\n  Overlord"),
+            raw_hash("Necrons Overlord ")
+        );
+        assert_ne!(raw_hash("Necrons"), raw_hash("Orks"));
+    }
+
+    #[test]
+    fn test_profile_distributions() {
+        let samples = vec![
+            sample("a", "m1", "Necrons", 0, Confidence::Low),
+            sample("b", "m1", "Necrons", 8, Confidence::High),
+            sample("c", "m1", "Unknown", 12, Confidence::High),
+            sample("d", "m1", "Orks", 20, Confidence::Medium),
+        ];
+        let p = profile("m1", &samples);
+        assert_eq!(p.lists, 4);
+        assert_eq!(p.mean_units, 10.0);
+        let buckets: Vec<f64> = p.unit_counts.iter().map(|b| b.pct).collect();
+        assert_eq!(buckets, vec![25.0, 0.0, 25.0, 25.0, 25.0]);
+        assert_eq!(p.high_confidence_pct, 50.0);
+        assert_eq!(p.faction_assigned_pct, 75.0);
+    }
+
+    #[test]
+    fn test_compare_flags_degraded_candidate() {
+        let raws = ["l1", "l2", "l3", "l4"];
+        let baseline: Vec<_> = raws
+            .iter()
+            .map(|r| sample(r, "old", "Necrons", 10, Confidence::High))
+            .collect();
+        let candidate = vec![
+            sample("l1", "new", "Necrons", 10, Confidence::High),
+            sample("l2", "new", "Unknown", 4, Confidence::Low),
+            sample("l3", "new", "Necrons", 0, Confidence::Low),
+            sample("l4", "new", "Necrons", 10, Confidence::High),
+        ];
+
+        let report = compare("old", &baseline, "new", &candidate);
+        assert_eq!(report.paired, 4);
+        assert_eq!(report.faction_agreement_pct, 75.0);
+        assert_eq!(report.unit_count_match_pct, 50.0);
+        assert_eq!(report.mean_unit_delta, -4.0);
+        assert_eq!(report.confidence_downgrades, 2);
+        assert!(report.has_regressions());
+        assert_eq!(report.regressions.len(), 5, "{:?}", report.regressions);
+
+        let same = compare("old", &baseline, "old", &baseline);
+        assert!(!same.has_regressions());
+        assert_eq!(same.faction_agreement_pct, 100.0);
+    }
+
+    #[test]
+    fn test_samples_by_model_skips_untracked_lists() {
+        let mut tracked = ArmyList::new("Necrons".into(), 2000, vec![], "raw".into());
+        tracked.extracted_by = Some("llama3.2:latest".into());
+        let untracked = ArmyList::new("Orks".into(), 2000, vec![], "raw 2".into());
+
+        let groups = samples_by_model(&[tracked, untracked]);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].0, "llama3.2:latest");
+        assert_eq!(groups[0].1.len(), 1);
+    }
+}
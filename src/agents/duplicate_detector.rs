@@ -6,10 +6,10 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info};
+use tracing::info;
 
-use super::backend::{AiBackend, ChatMessage, ChatRequest};
-use super::{Agent, AgentError, RetryPolicy};
+use super::backend::{chat_parsed, AiBackend, ChatMessage, ChatRequest};
+use super::{Agent, AgentError, RetryNotes, RetryPolicy};
 use crate::models::EntityId;
 
 /// Summary of an existing entity for comparison.
@@ -51,6 +51,8 @@ pub struct DuplicateDetectorOutput {
     pub match_reasons: Vec<String>,
 }
 
+impl RetryNotes for DuplicateDetectorOutput {}
+
 /// AI duplicate detection response.
 #[derive(Debug, Deserialize)]
 struct ExtractedDuplicateCheck {
@@ -212,10 +214,10 @@ impl Agent for DuplicateDetectorAgent {
         let messages = self.build_prompt(&input.candidate, &input.existing_entities);
         let request = ChatRequest::new(messages).with_json_mode();
 
-        let response = self.backend.chat(request).await?;
-        debug!("AI response: {}", response.content);
-
-        let output = self.parse_response(&response.content, &input.existing_entities)?;
+        let (output, _) = chat_parsed(self.backend.as_ref(), request, |c| {
+            self.parse_response(c, &input.existing_entities)
+        })
+        .await?;
 
         if output.is_duplicate {
             info!(
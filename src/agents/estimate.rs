@@ -0,0 +1,171 @@
+//! AI usage estimation.
+//!
+//! Projects the number of calls, tokens, and cost of an agent run before
+//! any request is made, so paid backends can be budgeted up front.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::backend::ChatMessage;
+use crate::config::DEFAULT_ANTHROPIC_MODEL;
+
+/// Rough characters-per-token ratio for English text and JSON.
+const CHARS_PER_TOKEN: f64 = 4.0;
+
+/// Estimate the token count of a piece of text.
+pub fn estimate_tokens(text: &str) -> u32 {
+    (text.chars().count() as f64 / CHARS_PER_TOKEN).ceil() as u32
+}
+
+/// Estimate the prompt token count of a full chat request.
+pub fn estimate_prompt_tokens(messages: &[ChatMessage]) -> u32 {
+    // A few tokens of framing per message (role markers, separators)
+    messages
+        .iter()
+        .map(|m| estimate_tokens(&m.content) + 4)
+        .sum()
+}
+
+/// Pick up to `n` evenly spaced indices from `0..len`.
+pub fn sample_indices(len: usize, n: usize) -> Vec<usize> {
+    if len == 0 || n == 0 {
+        return Vec::new();
+    }
+    if len <= n {
+        return (0..len).collect();
+    }
+    let step = len as f64 / n as f64;
+    (0..n).map(|i| (i as f64 * step) as usize).collect()
+}
+
+/// Token sizes measured for one sampled item.
+#[derive(Debug, Clone, Copy)]
+pub struct UsageSample {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+}
+
+/// Rates for a model, in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    pub input_per_mtok: f64,
+    pub output_per_mtok: f64,
+}
+
+impl ModelPricing {
+    /// Local backends cost nothing per token.
+    pub const FREE: ModelPricing = ModelPricing {
+        input_per_mtok: 0.0,
+        output_per_mtok: 0.0,
+    };
+
+    /// Built-in rates by model, used when `[ai.pricing]` doesn't list it.
+    pub fn defaults() -> BTreeMap<String, ModelPricing> {
+        BTreeMap::from([(
+            DEFAULT_ANTHROPIC_MODEL.to_string(),
+            ModelPricing {
+                input_per_mtok: 3.0,
+                output_per_mtok: 15.0,
+            },
+        )])
+    }
+}
+
+/// Projected usage for a batch of agent calls.
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageEstimate {
+    /// Number of items that would be sent to the backend
+    pub calls: u32,
+    /// Number of items the averages were measured from
+    pub sampled: u32,
+    pub avg_prompt_tokens: u32,
+    pub avg_completion_tokens: u32,
+}
+
+impl UsageEstimate {
+    /// Build an estimate from measured samples, projected over `calls` items.
+    pub fn from_samples(calls: u32, samples: &[UsageSample]) -> Self {
+        let (avg_prompt_tokens, avg_completion_tokens) = if samples.is_empty() {
+            (0, 0)
+        } else {
+            let n = samples.len() as u64;
+            let prompt: u64 = samples.iter().map(|s| s.prompt_tokens as u64).sum();
+            let completion: u64 = samples.iter().map(|s| s.completion_tokens as u64).sum();
+            ((prompt / n) as u32, (completion / n) as u32)
+        };
+        Self {
+            calls,
+            sampled: samples.len() as u32,
+            avg_prompt_tokens,
+            avg_completion_tokens,
+        }
+    }
+
+    pub fn total_prompt_tokens(&self) -> u64 {
+        self.calls as u64 * self.avg_prompt_tokens as u64
+    }
+
+    pub fn total_completion_tokens(&self) -> u64 {
+        self.calls as u64 * self.avg_completion_tokens as u64
+    }
+
+    pub fn total_tokens(&self) -> u64 {
+        self.total_prompt_tokens() + self.total_completion_tokens()
+    }
+
+    /// Projected cost in USD at the given rates.
+    pub fn cost(&self, pricing: &ModelPricing) -> f64 {
+        (self.total_prompt_tokens() as f64 * pricing.input_per_mtok
+            + self.total_completion_tokens() as f64 * pricing.output_per_mtok)
+            / 1_000_000.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+
+    #[test]
+    fn test_sample_indices() {
+        assert!(sample_indices(0, 5).is_empty());
+        assert_eq!(sample_indices(3, 5), vec![0, 1, 2]);
+        assert_eq!(sample_indices(10, 5), vec![0, 2, 4, 6, 8]);
+    }
+
+    #[test]
+    fn test_usage_estimate_cost() {
+        let samples = [
+            UsageSample {
+                prompt_tokens: 1000,
+                completion_tokens: 400,
+            },
+            UsageSample {
+                prompt_tokens: 2000,
+                completion_tokens: 600,
+            },
+        ];
+        let est = UsageEstimate::from_samples(1000, &samples);
+        assert_eq!(est.avg_prompt_tokens, 1500);
+        assert_eq!(est.avg_completion_tokens, 500);
+        assert_eq!(est.total_tokens(), 2_000_000);
+
+        assert_eq!(est.cost(&ModelPricing::FREE), 0.0);
+        // 1.5M input @ $3 + 0.5M output @ $15
+        let sonnet = ModelPricing::defaults()[DEFAULT_ANTHROPIC_MODEL];
+        assert!((est.cost(&sonnet) - 12.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_usage_estimate_no_samples() {
+        let est = UsageEstimate::from_samples(10, &[]);
+        assert_eq!(est.total_tokens(), 0);
+    }
+}
@@ -7,14 +7,15 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info};
+use tracing::info;
 
-use super::backend::{AiBackend, ChatMessage, ChatRequest};
-use super::{Agent, AgentError, AgentOutput, RetryPolicy};
+use super::backend::{chat_parsed, AiBackend, ChatMessage, ChatRequest};
+use super::estimate::{estimate_prompt_tokens, UsageSample};
+use super::{Agent, AgentError, AgentOutput, RetryNotes, RetryPolicy};
 use crate::models::Confidence;
 
 /// Stub for a discovered event (before full extraction).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct EventStub {
     /// Event name as written in article
     pub name: String,
@@ -36,6 +37,10 @@ pub struct EventStub {
 
     /// Section of article where this event was found
     pub article_section: Option<String>,
+
+    /// Mission pack or GW season played, if mentioned
+    #[serde(default)]
+    pub mission_pack: Option<String>,
 }
 
 /// Input for the Event Scout agent.
@@ -58,6 +63,14 @@ pub struct EventScoutOutput {
     pub events: Vec<AgentOutput<EventStub>>,
 }
 
+impl RetryNotes for EventScoutOutput {
+    fn note_retries(&mut self, note: &str) {
+        for event in &mut self.events {
+            event.note_retries(note);
+        }
+    }
+}
+
 /// AI-extracted event data.
 #[derive(Debug, Deserialize)]
 struct ExtractedEvent {
@@ -68,6 +81,8 @@ struct ExtractedEvent {
     round_count: Option<u32>,
     event_type: Option<String>,
     article_section: Option<String>,
+    #[serde(default)]
+    mission_pack: Option<String>,
     confidence: String,
 }
 
@@ -86,7 +101,16 @@ impl EventScoutAgent {
         Self { backend }
     }
 
-    fn build_prompt(&self, html_content: &str, article_date: NaiveDate) -> Vec<ChatMessage> {
+    /// Estimate the token usage of scanning one article that covers
+    /// `events` events, without calling the backend.
+    pub fn estimate_usage(article_text: &str, article_date: NaiveDate, events: u32) -> UsageSample {
+        UsageSample {
+            prompt_tokens: estimate_prompt_tokens(&Self::build_prompt(article_text, article_date)),
+            completion_tokens: events * EVENT_STUB_TOKENS,
+        }
+    }
+
+    fn build_prompt(html_content: &str, article_date: NaiveDate) -> Vec<ChatMessage> {
         vec![
             ChatMessage::system(EVENT_SCOUT_SYSTEM_PROMPT),
             ChatMessage::user(format!(
@@ -122,6 +146,7 @@ impl EventScoutAgent {
                 round_count: event.round_count,
                 event_type: event.event_type,
                 article_section: event.article_section,
+                mission_pack: event.mission_pack,
             };
 
             let confidence = match event.confidence.to_lowercase().as_str() {
@@ -145,6 +170,9 @@ impl EventScoutAgent {
     }
 }
 
+/// Rough size of one event stub in the scout's JSON reply.
+const EVENT_STUB_TOKENS: u32 = 120;
+
 const EVENT_SCOUT_SYSTEM_PROMPT: &str = r#"You are extracting tournament information from a Goonhammer Competitive Innovations article.
 
 For each tournament mentioned, extract:
@@ -155,6 +183,7 @@ For each tournament mentioned, extract:
 - round_count: Number of rounds as integer
 - event_type: "GT", "Major", "RTT", "Open", etc. (null if unclear)
 - article_section: Which section of article covers this event (for tracking)
+- mission_pack: Mission pack or GW season played, e.g. "Pariah Nexus", "Leviathan", "Chapter Approved 2025-26" (null if not mentioned)
 - confidence: "high", "medium", or "low"
 
 Tournaments are typically introduced with phrases like:
@@ -173,6 +202,7 @@ Return JSON in this exact format:
       "round_count": 5,
       "event_type": "GT",
       "article_section": "London GT Results",
+      "mission_pack": "Pariah Nexus",
       "confidence": "high"
     }
   ]
@@ -199,13 +229,18 @@ impl Agent for EventScoutAgent {
     async fn execute(&self, input: Self::Input) -> Result<Self::Output, AgentError> {
         info!("Running Event Scout on {}", input.article_url);
 
-        let messages = self.build_prompt(&input.article_html, input.article_date);
+        let messages = Self::build_prompt(&input.article_html, input.article_date);
         let request = ChatRequest::new(messages).with_json_mode();
 
-        let response = self.backend.chat(request).await?;
-        debug!("AI response: {}", response.content);
-
-        let events = self.parse_response(&response.content)?;
+        let (events, response) =
+            chat_parsed(self.backend.as_ref(), request, |c| self.parse_response(c)).await?;
+        let events: Vec<_> = events
+            .into_iter()
+            .map(|e| {
+                e.with_model(&response.model)
+                    .with_agent_version(self.version())
+            })
+            .collect();
 
         info!("Event Scout found {} events", events.len());
 
@@ -237,6 +272,7 @@ mod tests {
                     "round_count": 5,
                     "event_type": "GT",
                     "article_section": "London GT Results",
+                    "mission_pack": "Pariah Nexus",
                     "confidence": "high"
                 },
                 {
@@ -271,10 +307,12 @@ mod tests {
         let london_gt = &output.events[0];
         assert_eq!(london_gt.data.name, "London GT 2025");
         assert_eq!(london_gt.data.player_count, Some(96));
+        assert_eq!(london_gt.data.mission_pack.as_deref(), Some("Pariah Nexus"));
         assert_eq!(london_gt.confidence, Confidence::High);
 
         let birmingham = &output.events[1];
         assert!(birmingham.data.date.is_none());
+        assert!(birmingham.data.mission_pack.is_none());
         assert_eq!(birmingham.confidence, Confidence::Medium);
     }
 
@@ -303,6 +341,7 @@ mod tests {
             round_count: Some(5),
             event_type: Some("GT".to_string()),
             article_section: None,
+            mission_pack: None,
         };
 
         let json = serde_json::to_string(&stub).unwrap();
@@ -329,6 +368,7 @@ mod tests {
             round_count: Some(6),
             event_type: Some("Major".to_string()),
             article_section: Some("Event Recap".to_string()),
+            mission_pack: None,
         };
 
         let json = serde_json::to_string(&stub).unwrap();
@@ -359,4 +399,13 @@ mod tests {
         let policy = agent.retry_policy();
         assert_eq!(policy.max_retries, 3);
     }
+
+    #[test]
+    fn test_event_scout_estimate_usage() {
+        let text = "1st: Alice (Aeldari) 5-0\n".repeat(40);
+        let date = NaiveDate::from_ymd_opt(2025, 6, 16).unwrap();
+        let usage = EventScoutAgent::estimate_usage(&text, date, 3);
+        assert!(usage.prompt_tokens > crate::agents::estimate::estimate_tokens(&text));
+        assert_eq!(usage.completion_tokens, 3 * EVENT_STUB_TOKENS);
+    }
 }
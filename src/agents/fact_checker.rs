@@ -1,16 +1,25 @@
 //! Fact Checker Agent.
 //!
 //! Verifies extracted data against the original source content.
+//!
+//! Two layers: [`FactCheckerAgent`] asks the AI backend to compare a record
+//! with its source, and the rule checks ([`check_placements`],
+//! [`check_list`]) catch results that cannot be right whatever the article
+//! says: unknown factions, ranks past the player count, records longer than
+//! the event, lists far from 2000 points. The sync pipeline runs the rule
+//! checks after extraction and holds records with a major or critical
+//! discrepancy in the review queue instead of storing them.
 
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info, warn};
+use tracing::{info, warn};
 
-use super::backend::{AiBackend, ChatMessage, ChatRequest};
-use super::{Agent, AgentError, RetryPolicy};
-use crate::models::Confidence;
+use super::backend::{chat_parsed, AiBackend, ChatMessage, ChatRequest};
+use super::{Agent, AgentError, RetryNotes, RetryPolicy};
+use crate::models::lookup_faction;
+use crate::models::{ArmyList, Confidence, Placement};
 
 /// Severity of a discrepancy.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -43,6 +52,14 @@ pub struct Discrepancy {
     pub description: String,
 }
 
+impl Severity {
+    /// Whether a record with this discrepancy should wait for review
+    /// rather than be stored.
+    pub fn blocks_storage(&self) -> bool {
+        !matches!(self, Severity::Minor)
+    }
+}
+
 /// Suggested correction for a discrepancy.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Correction {
@@ -95,6 +112,8 @@ pub struct FactCheckerOutput {
     pub overall_confidence: Confidence,
 }
 
+impl RetryNotes for FactCheckerOutput {}
+
 /// AI verification response.
 #[derive(Debug, Deserialize)]
 struct ExtractedVerification {
@@ -282,10 +301,8 @@ impl Agent for FactCheckerAgent {
         );
         let request = ChatRequest::new(messages).with_json_mode();
 
-        let response = self.backend.chat(request).await?;
-        debug!("AI response: {}", response.content);
-
-        let output = self.parse_response(&response.content)?;
+        let (output, _) =
+            chat_parsed(self.backend.as_ref(), request, |c| self.parse_response(c)).await?;
 
         if output.verified {
             info!(
@@ -311,6 +328,158 @@ impl Agent for FactCheckerAgent {
     }
 }
 
+// ── Rule checks ─────────────────────────────────────────────────
+
+/// Points a Strike Force list is built to.
+pub const STRIKE_FORCE_POINTS: u32 = 2000;
+
+/// How far a list total may stray from [`STRIKE_FORCE_POINTS`] before it is
+/// flagged.
+pub const POINTS_TOLERANCE: u32 = 100;
+
+fn discrepancy(
+    field: &str,
+    extracted_value: impl ToString,
+    severity: Severity,
+    description: String,
+) -> Discrepancy {
+    Discrepancy {
+        field: field.to_string(),
+        extracted_value: extracted_value.to_string(),
+        source_evidence: None,
+        severity,
+        description,
+    }
+}
+
+/// Whether any discrepancy should keep the record out of storage.
+pub fn blocks_storage(discrepancies: &[Discrepancy]) -> bool {
+    discrepancies.iter().any(|d| d.severity.blocks_storage())
+}
+
+/// Check one event's extracted placements against the article text and the
+/// event's player and round counts.
+///
+/// Returns the discrepancies of each placement, in input order. A rank
+/// shared by several placements flags all of them; a gap in the ranks is
+/// only noted (articles often list the top few and a handful of others).
+pub fn check_placements(
+    source_content: &str,
+    player_count: Option<u32>,
+    round_count: Option<u32>,
+    placements: &[Placement],
+) -> Vec<Vec<Discrepancy>> {
+    let source = source_content.to_lowercase();
+    let mut ranks: Vec<u32> = placements.iter().map(|p| p.rank).collect();
+    ranks.sort_unstable();
+
+    placements
+        .iter()
+        .map(|p| {
+            let mut found = Vec::new();
+
+            if lookup_faction(&p.faction).is_none() {
+                found.push(discrepancy(
+                    "faction",
+                    &p.faction,
+                    Severity::Major,
+                    format!("'{}' is not a known 40k faction", p.faction),
+                ));
+            }
+
+            if p.rank == 0 {
+                found.push(discrepancy(
+                    "rank",
+                    p.rank,
+                    Severity::Major,
+                    "Ranks start at 1".to_string(),
+                ));
+            } else if let Some(players) = player_count.filter(|&n| p.rank > n) {
+                found.push(discrepancy(
+                    "rank",
+                    p.rank,
+                    Severity::Major,
+                    format!("Rank {} is past the event's {} players", p.rank, players),
+                ));
+            }
+            if ranks.iter().filter(|&&r| r == p.rank).count() > 1 {
+                found.push(discrepancy(
+                    "rank",
+                    p.rank,
+                    Severity::Major,
+                    format!("Rank {} is given to more than one player", p.rank),
+                ));
+            } else if let Some(previous) = ranks.iter().rev().find(|&&r| r < p.rank) {
+                if p.rank - previous > 1 {
+                    found.push(discrepancy(
+                        "rank",
+                        p.rank,
+                        Severity::Minor,
+                        format!("No placements between rank {} and {}", previous, p.rank),
+                    ));
+                }
+            }
+
+            if let (Some(record), Some(rounds)) = (&p.record, round_count) {
+                let games = record.wins + record.losses + record.draws;
+                if games > rounds {
+                    found.push(discrepancy(
+                        "record",
+                        format!("{}-{}-{}", record.wins, record.losses, record.draws),
+                        Severity::Major,
+                        format!("{} games recorded in a {}-round event", games, rounds),
+                    ));
+                }
+            }
+
+            // Names are often shortened or nicknamed, so any part of the
+            // name appearing in the article is enough
+            let named = p
+                .player_name
+                .split_whitespace()
+                .map(|part| part.trim_matches(|c: char| !c.is_alphanumeric()))
+                .filter(|part| part.chars().count() >= 3)
+                .any(|part| source.contains(&part.to_lowercase()));
+            if !named {
+                found.push(discrepancy(
+                    "player_name",
+                    &p.player_name,
+                    Severity::Critical,
+                    format!("'{}' does not appear in the article", p.player_name),
+                ));
+            }
+
+            found
+        })
+        .collect()
+}
+
+/// Check a normalized army list: a known faction and a Strike Force sized
+/// total. A list whose points could not be read (total 0) is not flagged.
+pub fn check_list(list: &ArmyList) -> Vec<Discrepancy> {
+    let mut found = Vec::new();
+    if lookup_faction(&list.faction).is_none() {
+        found.push(discrepancy(
+            "faction",
+            &list.faction,
+            Severity::Major,
+            format!("'{}' is not a known 40k faction", list.faction),
+        ));
+    }
+    if list.total_points > 0 && list.total_points.abs_diff(STRIKE_FORCE_POINTS) > POINTS_TOLERANCE {
+        found.push(discrepancy(
+            "total_points",
+            list.total_points,
+            Severity::Major,
+            format!(
+                "{} points is not a {}-point list",
+                list.total_points, STRIKE_FORCE_POINTS
+            ),
+        ));
+    }
+    found
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -474,4 +643,42 @@ mod tests {
         assert_eq!(parsed.field, "faction");
         assert_eq!(parsed.severity, Severity::Major);
     }
+
+    #[test]
+    fn test_rule_checks_hold_impossible_results() {
+        use crate::models::{EntityId, WinLossRecord};
+
+        let article = "Alice Smith took the event with Aeldari, ahead of Bob Jones on Orks.";
+        let placement = |rank: u32, name: &str, faction: &str| {
+            let mut p = Placement::new(
+                EntityId::from("ev1"),
+                EntityId::from("current"),
+                rank,
+                name.to_string(),
+                faction.to_string(),
+            );
+            p.record = Some(WinLossRecord::new(5, 0, 0));
+            p
+        };
+        let placements = vec![
+            placement(1, "Alice Smith", "Aeldari"),
+            placement(2, "Bob Jones", "Orks"),
+            placement(2, "Bob Jones", "Space Elves"),
+            placement(40, "Carol White", "Necrons"),
+        ];
+
+        let checks = check_placements(article, Some(32), Some(5), &placements);
+        assert!(checks[0].is_empty());
+        assert!(blocks_storage(&checks[1]));
+        assert!(checks[2].iter().any(|d| d.field == "faction"));
+        let fields: Vec<&str> = checks[3].iter().map(|d| d.field.as_str()).collect();
+        assert_eq!(fields, ["rank", "rank", "player_name"]);
+        assert_eq!(checks[3][1].severity, Severity::Minor);
+
+        let list =
+            |points: u32| ArmyList::new("Aeldari".to_string(), points, vec![], String::new());
+        assert!(check_list(&list(1995)).is_empty());
+        assert!(check_list(&list(0)).is_empty());
+        assert!(blocks_storage(&check_list(&list(1500))));
+    }
 }
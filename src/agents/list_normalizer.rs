@@ -2,15 +2,19 @@
 //!
 //! Converts raw army list text to canonical structured format.
 
+use std::collections::BTreeSet;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info};
+use tracing::info;
 
-use super::backend::{AiBackend, ChatMessage, ChatRequest};
-use super::{Agent, AgentError, AgentOutput, RetryPolicy};
-use crate::models::{Confidence, Unit};
+use super::backend::{chat_parsed, AiBackend, ChatMessage, ChatRequest};
+use super::estimate::{estimate_prompt_tokens, estimate_tokens, UsageSample};
+use super::{Agent, AgentError, AgentOutput, RetryNotes, RetryPolicy};
+use crate::models::{ArmyList, Confidence, EntityId, Timestamped, Unit};
+use crate::storage::{StorageConfig, StorageError};
 
 /// Input for the List Normalizer agent.
 #[derive(Debug, Clone)]
@@ -25,6 +29,26 @@ pub struct ListNormalizerInput {
     pub player_name: String,
 }
 
+impl ListNormalizerInput {
+    /// Input for re-normalizing a stored list. Placeholder factions left by
+    /// article extraction ("... presents", event names) are not used as hints.
+    pub fn for_list(list: &ArmyList, player_name: String) -> Self {
+        let faction_hint = if list.faction.is_empty()
+            || list.faction.contains("presents")
+            || list.faction.contains("GT")
+        {
+            None
+        } else {
+            Some(list.faction.clone())
+        };
+        Self {
+            raw_text: list.raw_text.clone(),
+            faction_hint,
+            player_name,
+        }
+    }
+}
+
 /// Normalized army list (intermediate before full ArmyList).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NormalizedArmyList {
@@ -44,6 +68,12 @@ pub struct ListNormalizerOutput {
     pub list: AgentOutput<NormalizedArmyList>,
 }
 
+impl RetryNotes for ListNormalizerOutput {
+    fn note_retries(&mut self, note: &str) {
+        self.list.note_retries(note);
+    }
+}
+
 /// AI-extracted unit data.
 #[derive(Debug, Deserialize)]
 struct ExtractedUnit {
@@ -52,6 +82,8 @@ struct ExtractedUnit {
     points: Option<u32>,
     wargear: Vec<String>,
     keywords: Vec<String>,
+    #[serde(default)]
+    enhancement: Option<String>,
 }
 
 /// AI-extracted army list data.
@@ -84,14 +116,29 @@ impl ListNormalizerAgent {
     }
 
     fn build_prompt(&self, raw_text: &str, faction_hint: Option<&str>) -> Vec<ChatMessage> {
-        let hint_text = faction_hint
-            .map(|f| format!("\nFaction hint: {}", f))
-            .unwrap_or_default();
-
-        vec![
-            ChatMessage::system(LIST_NORMALIZER_SYSTEM_PROMPT),
-            ChatMessage::user(format!("{}Raw army list:\n\n{}", hint_text, raw_text)),
-        ]
+        build_messages(raw_text, faction_hint)
+    }
+
+    /// Estimate the token usage of normalizing one list without calling the backend.
+    ///
+    /// When the list already has parsed units, the completion size is measured
+    /// from their JSON form; otherwise it is approximated from the raw text.
+    pub fn estimate_usage(
+        raw_text: &str,
+        faction_hint: Option<&str>,
+        existing_units: &[Unit],
+    ) -> UsageSample {
+        let prompt_tokens = estimate_prompt_tokens(&build_messages(raw_text, faction_hint));
+        let completion_tokens = if existing_units.is_empty() {
+            // Structured JSON is roughly 1.5x the size of the raw list text
+            estimate_tokens(raw_text) * 3 / 2
+        } else {
+            estimate_tokens(&serde_json::to_string(existing_units).unwrap_or_default())
+        };
+        UsageSample {
+            prompt_tokens,
+            completion_tokens,
+        }
     }
 
     fn parse_response(
@@ -109,10 +156,14 @@ impl ListNormalizerAgent {
             .units
             .into_iter()
             .map(|u| {
-                Unit::new(u.name, u.model_count.unwrap_or(1))
+                let unit = Unit::new(u.name, u.model_count.unwrap_or(1))
                     .with_points(u.points.unwrap_or(0))
                     .with_wargear(u.wargear)
-                    .with_keywords(u.keywords)
+                    .with_keywords(u.keywords);
+                match u.enhancement.filter(|e| !e.trim().is_empty()) {
+                    Some(enhancement) => unit.with_enhancement(enhancement.trim()),
+                    None => unit,
+                }
             })
             .collect();
 
@@ -140,6 +191,17 @@ impl ListNormalizerAgent {
     }
 }
 
+fn build_messages(raw_text: &str, faction_hint: Option<&str>) -> Vec<ChatMessage> {
+    let hint_text = faction_hint
+        .map(|f| format!("\nFaction hint: {}", f))
+        .unwrap_or_default();
+
+    vec![
+        ChatMessage::system(LIST_NORMALIZER_SYSTEM_PROMPT),
+        ChatMessage::user(format!("{}Raw army list:\n\n{}", hint_text, raw_text)),
+    ]
+}
+
 const LIST_NORMALIZER_SYSTEM_PROMPT: &str = r#"You are normalizing a Warhammer 40,000 army list into a structured format.
 
 Given raw list text, extract:
@@ -152,7 +214,8 @@ Given raw list text, extract:
   - name: Unit name (canonical GW name)
   - model_count: Number of models (default 1)
   - points: Points cost
-  - wargear: Array of selected wargear/upgrades
+  - wargear: Array of selected wargear/upgrades, NOT including Enhancements
+  - enhancement: Detachment Enhancement the unit takes, name only without points (null if none)
   - keywords: Array of keywords — MUST include the unit's battlefield role
 - confidence: "high", "medium", or "low"
 - notes: Array of any issues or uncertainties
@@ -291,6 +354,14 @@ Return JSON in this exact format:
         "wargear": ["Wailing Doom"],
         "keywords": ["Epic Hero", "Character", "Monster"]
       },
+      {
+        "name": "Farseer",
+        "model_count": 1,
+        "points": 85,
+        "wargear": ["Singing Spear"],
+        "enhancement": "Psychic Destroyer",
+        "keywords": ["Character", "Infantry", "Psyker"]
+      },
       {
         "name": "Guardians",
         "model_count": 10,
@@ -316,10 +387,84 @@ IMPORTANT:
 - If a unit name is unclear, include as-is with confidence "low"
 - Do NOT add units not mentioned in the source text
 - Include all wargear/upgrades mentioned
+- Enhancements (e.g. "Enhancement: Psychic Destroyer (+15 pts)") go in the carrying unit's "enhancement", never in its wargear
 - Sum points if total not explicitly stated
 - Every unit MUST have at least one role keyword (Character/Battleline/Vehicle/etc.)
 - Note any parsing issues in the notes array"#;
 
+/// Apply a normalization result to a stored list, regenerating its ID from
+/// the new content.
+pub fn apply_normalized(list: &mut ArmyList, result: &AgentOutput<NormalizedArmyList>) {
+    let norm = &result.data;
+    list.faction = norm.faction.clone();
+    list.subfaction = norm.subfaction.clone();
+    list.detachment = norm.detachment.clone();
+    list.total_points = norm.total_points;
+    list.set_units(norm.units.clone());
+    list.extraction_confidence = result.confidence;
+    list.extracted_by = result.model.clone();
+    list.extraction_agent_version = result.agent_version;
+    list.touch();
+
+    let mut unit_names: Vec<_> = list.units.iter().map(|u| u.name.as_str()).collect();
+    unit_names.sort();
+    let units_str = unit_names.join(",");
+    list.id = EntityId::generate(&[
+        &list.faction,
+        list.detachment.as_deref().unwrap_or(""),
+        &units_str,
+        &list.total_points.to_string(),
+    ]);
+}
+
+/// Progress of a `normalize-lists` run, saved after every written batch so
+/// an interrupted run continues where it stopped.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NormalizeCheckpoint {
+    pub epoch_id: String,
+    /// IDs, as written, of the lists already handled
+    pub done: BTreeSet<String>,
+}
+
+impl NormalizeCheckpoint {
+    /// Path of an epoch's checkpoint file.
+    pub fn path(storage: &StorageConfig, epoch_id: &str) -> PathBuf {
+        storage
+            .state_dir()
+            .join(format!("normalize_lists_{}.json", epoch_id))
+    }
+
+    /// Load an epoch's checkpoint, or an empty one if no run was
+    /// interrupted.
+    pub fn load(storage: &StorageConfig, epoch_id: &str) -> Result<Self, StorageError> {
+        let path = Self::path(storage, epoch_id);
+        if !path.exists() {
+            return Ok(Self {
+                epoch_id: epoch_id.to_string(),
+                ..Default::default()
+            });
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, storage: &StorageConfig) -> Result<(), StorageError> {
+        let path = Self::path(storage, &self.epoch_id);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Remove the checkpoint once a run completes.
+    pub fn clear(&self, storage: &StorageConfig) -> Result<(), StorageError> {
+        match std::fs::remove_file(Self::path(storage, &self.epoch_id)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
 #[async_trait]
 impl Agent for ListNormalizerAgent {
     type Input = ListNormalizerInput;
@@ -335,10 +480,13 @@ impl Agent for ListNormalizerAgent {
         let messages = self.build_prompt(&input.raw_text, input.faction_hint.as_deref());
         let request = ChatRequest::new(messages).with_json_mode();
 
-        let response = self.backend.chat(request).await?;
-        debug!("AI response: {}", response.content);
-
-        let list = self.parse_response(&response.content, &input.raw_text)?;
+        let (list, response) = chat_parsed(self.backend.as_ref(), request, |c| {
+            self.parse_response(c, &input.raw_text)
+        })
+        .await?;
+        let list = list
+            .with_model(&response.model)
+            .with_agent_version(self.version());
 
         info!(
             "Normalized list: {} ({} units, {} pts)",
@@ -414,6 +562,13 @@ mod tests {
         let avatar = &output.list.data.units[0];
         assert_eq!(avatar.name, "Avatar of Khaine");
         assert_eq!(avatar.points, Some(335));
+
+        // The producing model is recorded on the stored list
+        assert_eq!(output.list.model.as_deref(), Some("mock"));
+        let mut list = ArmyList::new(String::new(), 0, vec![], String::new());
+        apply_normalized(&mut list, &output.list);
+        assert_eq!(list.extracted_by.as_deref(), Some("mock"));
+        assert_eq!(list.extraction_agent_version, Some(1));
     }
 
     #[tokio::test]
@@ -430,6 +585,7 @@ mod tests {
                         "model_count": 1,
                         "points": 100,
                         "wargear": [],
+                        "enhancement": "Artificer Armour",
                         "keywords": []
                     }
                 ],
@@ -451,6 +607,14 @@ mod tests {
 
         assert_eq!(output.list.confidence, Confidence::Low);
         assert_eq!(output.list.extraction_notes.len(), 2);
+        assert_eq!(
+            output.list.data.units[0].enhancement.as_deref(),
+            Some("Artificer Armour")
+        );
+
+        let mut list = ArmyList::new("Space Marines".to_string(), 0, vec![], String::new());
+        apply_normalized(&mut list, &output.list);
+        assert_eq!(list.enhancements, vec!["Artificer Armour"]);
     }
 
     #[test]
@@ -508,4 +672,42 @@ mod tests {
         let policy = agent.retry_policy();
         assert_eq!(policy.max_retries, 2);
     }
+
+    #[test]
+    fn test_list_normalizer_estimate_usage() {
+        let raw = "Guardians 10x (110 pts)\n".repeat(20);
+        let empty = ListNormalizerAgent::estimate_usage(&raw, Some("Aeldari"), &[]);
+        assert!(empty.prompt_tokens > estimate_tokens(&raw));
+        assert_eq!(empty.completion_tokens, estimate_tokens(&raw) * 3 / 2);
+
+        let units = vec![Unit::new("Guardians".to_string(), 10).with_points(110)];
+        let parsed = ListNormalizerAgent::estimate_usage(&raw, None, &units);
+        assert!(parsed.completion_tokens > 0);
+        assert!(parsed.completion_tokens < empty.completion_tokens);
+    }
+
+    #[test]
+    fn test_normalize_checkpoint_roundtrip() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+
+        let mut checkpoint = NormalizeCheckpoint::load(&storage, "e1").unwrap();
+        assert!(checkpoint.done.is_empty());
+        checkpoint.done.insert("list-a".to_string());
+        checkpoint.save(&storage).unwrap();
+
+        let loaded = NormalizeCheckpoint::load(&storage, "e1").unwrap();
+        assert_eq!(loaded, checkpoint);
+        assert!(NormalizeCheckpoint::load(&storage, "e2")
+            .unwrap()
+            .done
+            .is_empty());
+
+        loaded.clear(&storage).unwrap();
+        loaded.clear(&storage).unwrap();
+        assert!(NormalizeCheckpoint::load(&storage, "e1")
+            .unwrap()
+            .done
+            .is_empty());
+    }
 }
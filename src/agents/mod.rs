@@ -5,14 +5,23 @@
 
 pub mod backend;
 pub mod balance_watcher;
+pub mod dataslate_parser;
+pub mod doctor;
+pub mod drift;
 pub mod duplicate_detector;
+pub mod estimate;
 pub mod event_scout;
 pub mod fact_checker;
 pub mod list_normalizer;
+pub mod ollama;
+pub mod points_watcher;
 pub mod result_harvester;
+pub mod season_summary;
 
 pub use backend::{AiBackend, AiBackendConfig, ChatMessage, ChatRequest, ChatResponse};
 
+use std::time::Duration;
+
 use async_trait::async_trait;
 use thiserror::Error;
 
@@ -38,6 +47,12 @@ pub enum AgentError {
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("Fetch failed: {0}")]
+    Fetch(#[from] crate::fetch::FetchError),
+
+    #[error("Unreadable PDF: {0}")]
+    Pdf(#[from] crate::fetch::pdf::PdfError),
 }
 
 /// Retry policy for agents.
@@ -58,12 +73,36 @@ impl Default for RetryPolicy {
     }
 }
 
+impl RetryPolicy {
+    /// Backoff before retry number `retry` (1-based).
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let factor = self
+            .backoff_multiplier
+            .max(1.0)
+            .powi(retry.saturating_sub(1) as i32);
+        Duration::from_millis((self.initial_delay_ms as f64 * factor) as u64)
+    }
+}
+
+impl AgentError {
+    /// Whether running the agent again may succeed. Parse errors don't
+    /// count: [`backend::chat_parsed`] has already re-prompted the model
+    /// with the error, and the same request would only get the same reply.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, AgentError::RateLimited(_) | AgentError::Timeout(_))
+    }
+}
+
 /// Output wrapper with confidence and metadata.
 #[derive(Debug, Clone)]
 pub struct AgentOutput<T> {
     pub data: T,
     pub confidence: Confidence,
     pub extraction_notes: Vec<String>,
+    /// Model that produced the output, as reported by the backend
+    pub model: Option<String>,
+    /// [`Agent::version`] of the agent that produced the output
+    pub agent_version: Option<u32>,
 }
 
 impl<T> AgentOutput<T> {
@@ -72,6 +111,8 @@ impl<T> AgentOutput<T> {
             data,
             confidence,
             extraction_notes: Vec::new(),
+            model: None,
+            agent_version: None,
         }
     }
 
@@ -79,6 +120,16 @@ impl<T> AgentOutput<T> {
         self.extraction_notes = notes;
         self
     }
+
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub fn with_agent_version(mut self, version: u32) -> Self {
+        self.agent_version = Some(version);
+        self
+    }
 }
 
 /// Core trait for all AI agents.
@@ -90,6 +141,14 @@ pub trait Agent {
     /// Agent identifier for logging and metrics.
     fn name(&self) -> &'static str;
 
+    /// Version of the agent's prompt and response parsing, recorded on the
+    /// records it extracts. Bump it when either changes so older records
+    /// can be found and re-extracted (`meta-agent reprocess
+    /// --older-than-version`).
+    fn version(&self) -> u32 {
+        1
+    }
+
     /// Execute the agent's task.
     async fn execute(&self, input: Self::Input) -> Result<Self::Output, AgentError>;
 
@@ -99,6 +158,68 @@ pub trait Agent {
     }
 }
 
+/// Agent outputs that can record how many retries it took to produce them.
+pub trait RetryNotes {
+    /// Add a note about retries to the output's extraction notes. Outputs
+    /// without notes ignore it.
+    fn note_retries(&mut self, _note: &str) {}
+}
+
+impl<T> RetryNotes for AgentOutput<T> {
+    fn note_retries(&mut self, note: &str) {
+        self.extraction_notes.push(note.to_string());
+    }
+}
+
+/// Run an agent, retrying retryable errors per its [`RetryPolicy`].
+///
+/// Rate limits wait at least as long as the backend asked. When a retry
+/// succeeds the retry count is added to the output's extraction notes.
+pub async fn execute_with_retry<A>(agent: &A, input: A::Input) -> Result<A::Output, AgentError>
+where
+    A: Agent + ?Sized,
+    A::Input: Clone,
+    A::Output: RetryNotes,
+{
+    let policy = agent.retry_policy();
+    let mut retries = 0;
+    let mut last_error = None;
+    loop {
+        let result = agent.execute(input.clone()).await;
+        crate::metrics::Metrics::global().record_agent_call(agent.name(), result.is_ok());
+        match result {
+            Ok(mut output) => {
+                if retries > 0 {
+                    let mut note = format!("Succeeded after {} retries", retries);
+                    if let Some(e) = last_error {
+                        note.push_str(&format!(" (last error: {})", e));
+                    }
+                    output.note_retries(&note);
+                }
+                return Ok(output);
+            }
+            Err(e) if e.is_retryable() && retries < policy.max_retries => {
+                retries += 1;
+                let mut delay = policy.delay_for(retries);
+                if let AgentError::RateLimited(secs) = e {
+                    delay = delay.max(Duration::from_secs(secs));
+                }
+                tracing::warn!(
+                    "{} failed ({}), retry {}/{} in {:?}",
+                    agent.name(),
+                    e,
+                    retries,
+                    policy.max_retries,
+                    delay
+                );
+                last_error = Some(e);
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 /// Extract JSON from an AI response that may be wrapped in markdown code fences.
 ///
 /// Handles responses like:
@@ -186,4 +307,75 @@ mod tests {
         assert_eq!(output.confidence, Confidence::High);
         assert_eq!(output.extraction_notes.len(), 1);
     }
+
+    /// Fails with the queued errors, then succeeds.
+    struct FlakyAgent {
+        errors: std::sync::Mutex<Vec<AgentError>>,
+        calls: std::sync::atomic::AtomicU32,
+    }
+
+    #[async_trait]
+    impl Agent for FlakyAgent {
+        type Input = ();
+        type Output = AgentOutput<u32>;
+
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        async fn execute(&self, _input: ()) -> Result<Self::Output, AgentError> {
+            let calls = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            match self.errors.lock().unwrap().pop() {
+                Some(e) => Err(e),
+                None => Ok(AgentOutput::new(calls, Confidence::High)),
+            }
+        }
+
+        fn retry_policy(&self) -> RetryPolicy {
+            RetryPolicy {
+                max_retries: 2,
+                initial_delay_ms: 1,
+                backoff_multiplier: 2.0,
+            }
+        }
+    }
+
+    fn flaky(errors: Vec<AgentError>) -> FlakyAgent {
+        FlakyAgent {
+            errors: std::sync::Mutex::new(errors),
+            calls: std::sync::atomic::AtomicU32::new(0),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_retry() {
+        let agent = flaky(vec![AgentError::Timeout(30), AgentError::RateLimited(0)]);
+        let output = execute_with_retry(&agent, ()).await.unwrap();
+        assert_eq!(output.data, 2);
+        assert_eq!(output.extraction_notes.len(), 1);
+        assert!(output.extraction_notes[0].starts_with("Succeeded after 2 retries"));
+
+        // Out of retries
+        let agent = flaky(vec![
+            AgentError::Timeout(30),
+            AgentError::Timeout(30),
+            AgentError::Timeout(30),
+        ]);
+        assert!(matches!(
+            execute_with_retry(&agent, ()).await,
+            Err(AgentError::Timeout(30))
+        ));
+
+        // Not retryable
+        let agent = flaky(vec![AgentError::BackendUnavailable("down".into())]);
+        assert!(execute_with_retry(&agent, ()).await.is_err());
+        assert_eq!(agent.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+        let agent = flaky(vec![AgentError::ResponseParseError("bad json".into())]);
+        assert!(execute_with_retry(&agent, ()).await.is_err());
+        assert_eq!(agent.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay_for(1), Duration::from_millis(1000));
+        assert_eq!(policy.delay_for(3), Duration::from_millis(4000));
+    }
 }
@@ -0,0 +1,339 @@
+//! Ollama model management.
+//!
+//! Thin client for the parts of the Ollama API that aren't chat: listing
+//! installed models, reading a model's metadata and pulling a model with
+//! streamed progress. [`OllamaAdmin::diagnose`] combines them into the
+//! startup health check behind `meta-agent debug ai-check`.
+
+use serde::{Deserialize, Serialize};
+
+use super::AgentError;
+
+/// An installed model from `GET /api/tags`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InstalledModel {
+    pub name: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagsResponse {
+    #[serde(default)]
+    models: Vec<InstalledModel>,
+}
+
+/// Model metadata from `POST /api/show`.
+#[derive(Debug, Clone, Default)]
+pub struct ModelDetails {
+    /// e.g. "3.2B"
+    pub parameter_size: Option<String>,
+    /// e.g. "Q4_K_M"
+    pub quantization: Option<String>,
+    /// Maximum context the model was trained for
+    pub context_length: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShowResponse {
+    #[serde(default)]
+    details: ShowDetails,
+    #[serde(default)]
+    model_info: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ShowDetails {
+    parameter_size: Option<String>,
+    quantization_level: Option<String>,
+}
+
+impl From<ShowResponse> for ModelDetails {
+    fn from(show: ShowResponse) -> Self {
+        // Keyed by architecture, e.g. "llama.context_length"
+        let context_length = show
+            .model_info
+            .iter()
+            .find(|(key, _)| key.ends_with(".context_length"))
+            .and_then(|(_, v)| v.as_u64());
+        Self {
+            parameter_size: show.details.parameter_size,
+            quantization: show.details.quantization_level,
+            context_length,
+        }
+    }
+}
+
+/// One progress line from a streamed `POST /api/pull`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PullProgress {
+    pub status: String,
+    #[serde(default)]
+    pub total: Option<u64>,
+    #[serde(default)]
+    pub completed: Option<u64>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ModelRequest<'a> {
+    model: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+}
+
+/// Whether an installed model name satisfies a configured one. Ollama
+/// reports tags explicitly, so "llama3.2" matches "llama3.2:latest".
+pub fn model_matches(installed: &str, wanted: &str) -> bool {
+    let with_tag = |name: &str| {
+        if name.contains(':') {
+            name.to_string()
+        } else {
+            format!("{}:latest", name)
+        }
+    };
+    with_tag(installed) == with_tag(wanted)
+}
+
+/// What a health check found about the server and the configured model.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OllamaDiagnostics {
+    pub base_url: String,
+    pub model: String,
+    /// Why `GET /api/tags` failed, if the server is unreachable
+    pub server_error: Option<String>,
+    /// Installed model names
+    pub installed: Vec<String>,
+    /// Installed name satisfying the configured model
+    pub model_found: Option<String>,
+    /// Whether the check pulled the model
+    pub pulled: bool,
+    pub pull_error: Option<String>,
+}
+
+impl OllamaDiagnostics {
+    /// Server reachable and model installed.
+    pub fn is_healthy(&self) -> bool {
+        self.server_error.is_none() && self.model_found.is_some()
+    }
+
+    /// Findings, one line each, with the fix for each problem.
+    pub fn report(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(ref e) = self.server_error {
+            lines.push(format!("✗ Cannot reach Ollama at {}: {}", self.base_url, e));
+            lines.push("  Start it with `ollama serve` or fix [ai] base_url".to_string());
+            return lines;
+        }
+        lines.push(format!(
+            "✓ Ollama reachable at {} ({} models installed)",
+            self.base_url,
+            self.installed.len()
+        ));
+        match (&self.model_found, &self.pull_error) {
+            (Some(name), _) if self.pulled => lines.push(format!("✓ Pulled model {}", name)),
+            (Some(name), _) => lines.push(format!("✓ Model {} installed", name)),
+            (None, Some(e)) => {
+                lines.push(format!("✗ Pulling model {} failed: {}", self.model, e));
+                lines.push(format!("  Run `ollama pull {}`", self.model));
+            }
+            (None, None) => {
+                lines.push(format!("✗ Model {} is not installed", self.model));
+                lines.push(format!(
+                    "  Run `ollama pull {}`, set [ai] auto_pull = true, or pick one of: {}",
+                    self.model,
+                    if self.installed.is_empty() {
+                        "(none)".to_string()
+                    } else {
+                        self.installed.join(", ")
+                    }
+                ));
+            }
+        }
+        lines
+    }
+}
+
+/// Ollama management client.
+pub struct OllamaAdmin {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl OllamaAdmin {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn unavailable(e: impl std::fmt::Display) -> AgentError {
+        AgentError::BackendUnavailable(e.to_string())
+    }
+
+    /// Installed models.
+    pub async fn list_models(&self) -> Result<Vec<InstalledModel>, AgentError> {
+        let response = self
+            .client
+            .get(format!("{}/api/tags", self.base_url))
+            .send()
+            .await
+            .map_err(Self::unavailable)?
+            .error_for_status()
+            .map_err(Self::unavailable)?;
+        let tags: TagsResponse = response
+            .json()
+            .await
+            .map_err(|e| AgentError::ResponseParseError(e.to_string()))?;
+        Ok(tags.models)
+    }
+
+    /// Metadata for an installed model.
+    pub async fn show_model(&self, model: &str) -> Result<ModelDetails, AgentError> {
+        let response = self
+            .client
+            .post(format!("{}/api/show", self.base_url))
+            .json(&ModelRequest {
+                model,
+                stream: None,
+            })
+            .send()
+            .await
+            .map_err(Self::unavailable)?
+            .error_for_status()
+            .map_err(Self::unavailable)?;
+        let show: ShowResponse = response
+            .json()
+            .await
+            .map_err(|e| AgentError::ResponseParseError(e.to_string()))?;
+        Ok(show.into())
+    }
+
+    /// Check the server and `model`, pulling the model when it is missing
+    /// and `pull` is set. Failures are reported, not returned.
+    pub async fn diagnose(
+        &self,
+        model: &str,
+        pull: bool,
+        on_progress: impl FnMut(&PullProgress),
+    ) -> OllamaDiagnostics {
+        let mut diagnostics = OllamaDiagnostics {
+            base_url: self.base_url.clone(),
+            model: model.to_string(),
+            ..Default::default()
+        };
+        let installed = match self.list_models().await {
+            Ok(models) => models,
+            Err(e) => {
+                diagnostics.server_error = Some(e.to_string());
+                return diagnostics;
+            }
+        };
+        diagnostics.installed = installed.into_iter().map(|m| m.name).collect();
+        diagnostics.model_found = diagnostics
+            .installed
+            .iter()
+            .find(|name| model_matches(name, model))
+            .cloned();
+
+        if diagnostics.model_found.is_none() && pull {
+            match self.pull_model(model, on_progress).await {
+                Ok(()) => {
+                    diagnostics.pulled = true;
+                    diagnostics.model_found = Some(model.to_string());
+                }
+                Err(e) => diagnostics.pull_error = Some(e.to_string()),
+            }
+        }
+        diagnostics
+    }
+
+    /// Pull a model, calling `on_progress` for each status line.
+    pub async fn pull_model(
+        &self,
+        model: &str,
+        mut on_progress: impl FnMut(&PullProgress),
+    ) -> Result<(), AgentError> {
+        let mut response = self
+            .client
+            .post(format!("{}/api/pull", self.base_url))
+            .json(&ModelRequest {
+                model,
+                stream: Some(true),
+            })
+            .send()
+            .await
+            .map_err(Self::unavailable)?
+            .error_for_status()
+            .map_err(Self::unavailable)?;
+
+        // Newline-delimited JSON, which may be split across chunks
+        let mut buf: Vec<u8> = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(Self::unavailable)? {
+            buf.extend_from_slice(&chunk);
+            while let Some(pos) = buf.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = buf.drain(..=pos).collect();
+                if line.iter().all(u8::is_ascii_whitespace) {
+                    continue;
+                }
+                let progress: PullProgress = serde_json::from_slice(&line)
+                    .map_err(|e| AgentError::ResponseParseError(e.to_string()))?;
+                if let Some(error) = progress.error {
+                    return Err(AgentError::BackendUnavailable(format!(
+                        "Pull of {} failed: {}",
+                        model, error
+                    )));
+                }
+                on_progress(&progress);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_matches_default_tag() {
+        assert!(model_matches("llama3.2:latest", "llama3.2"));
+        assert!(model_matches("llama3.2:latest", "llama3.2:latest"));
+        assert!(!model_matches("llama3.2:1b", "llama3.2"));
+        assert!(!model_matches("qwen2.5:14b", "llama3.2"));
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_unreachable_server() {
+        // Nothing listens on the discard port
+        let admin = OllamaAdmin::new("http://127.0.0.1:9/");
+        let diagnostics = admin.diagnose("llama3.2", true, |_| {}).await;
+        assert!(!diagnostics.is_healthy());
+        assert!(diagnostics.server_error.is_some());
+        assert!(!diagnostics.pulled);
+        assert!(diagnostics.report()[0].contains("Cannot reach Ollama at http://127.0.0.1:9"));
+
+        let missing = OllamaDiagnostics {
+            server_error: None,
+            installed: vec!["qwen2.5:14b".to_string()],
+            ..diagnostics
+        };
+        assert!(!missing.is_healthy());
+        assert!(missing.report()[2].contains("qwen2.5:14b"));
+    }
+
+    #[test]
+    fn test_model_details_from_show_response() {
+        let show: ShowResponse = serde_json::from_str(
+            r#"{"details":{"parameter_size":"3.2B","quantization_level":"Q4_K_M"},
+                "model_info":{"general.architecture":"llama","llama.context_length":131072}}"#,
+        )
+        .unwrap();
+        let details = ModelDetails::from(show);
+        assert_eq!(details.parameter_size.as_deref(), Some("3.2B"));
+        assert_eq!(details.quantization.as_deref(), Some("Q4_K_M"));
+        assert_eq!(details.context_length, Some(131072));
+    }
+}
@@ -0,0 +1,311 @@
+//! Points Watcher Agent.
+//!
+//! Reads the text of a Munitorum Field Manual PDF and records every unit's
+//! points cost for the epoch its balance pass starts. The Field Manual is a
+//! fixed table (faction heading, unit name, one `N models ..... X pts` row
+//! per unit size), so it is parsed directly rather than sent to the model.
+
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use regex::Regex;
+use tracing::info;
+
+use super::{Agent, AgentError, AgentOutput};
+use crate::models::canonical_faction_name;
+use crate::models::{Confidence, EpochId, PointsChange, SignificantEventId};
+
+/// Fewer parsed rows than this means the PDF probably isn't a Field Manual.
+const MIN_FIELD_MANUAL_ROWS: usize = 20;
+
+/// Heading prefixes stripped to get the faction name.
+const FACTION_PREFIXES: &[&str] = &["CODEX SUPPLEMENT:", "CODEX:", "INDEX:"];
+
+/// Repeated page furniture, ignored wherever it appears.
+const PAGE_FURNITURE: &[&str] = &["MUNITORUM FIELD MANUAL", "FORGE WORLD"];
+
+/// One unit size's cost as printed in the Field Manual.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldManualEntry {
+    pub faction: String,
+    pub unit_name: String,
+    pub models: u32,
+    pub points: u32,
+}
+
+fn is_heading(line: &str) -> bool {
+    line.chars().filter(|c| c.is_alphabetic()).count() >= 3
+        && !line.chars().any(|c| c.is_lowercase())
+}
+
+/// Parse Field Manual text into per-unit-size costs. Detachment enhancement
+/// sections are skipped; the first cost seen for a unit size wins.
+pub fn parse_field_manual(text: &str) -> Vec<FieldManualEntry> {
+    let size_row =
+        Regex::new(r"^(?P<name>.*?)\s*(?P<models>\d+)\s+models?\b[\s.]*(?P<points>\d+)\s*pts?\b")
+            .expect("valid regex");
+
+    let mut entries = Vec::new();
+    let mut seen: HashSet<(String, String, u32)> = HashSet::new();
+    let mut faction: Option<String> = None;
+    let mut unit: Option<String> = None;
+    let mut in_enhancements = false;
+
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let upper = line.to_uppercase();
+        if PAGE_FURNITURE.iter().any(|f| upper.contains(f))
+            || line.chars().all(|c| c.is_ascii_digit())
+        {
+            continue;
+        }
+
+        if is_heading(line) {
+            if upper.contains("ENHANCEMENTS") {
+                in_enhancements = true;
+            } else if !in_enhancements || FACTION_PREFIXES.iter().any(|p| upper.starts_with(p)) {
+                let name = FACTION_PREFIXES
+                    .iter()
+                    .find_map(|p| upper.strip_prefix(p))
+                    .unwrap_or(&upper)
+                    .trim();
+                faction = Some(canonical_faction_name(name));
+                in_enhancements = false;
+            }
+            // Otherwise a detachment name inside an enhancements section
+            unit = None;
+            continue;
+        }
+        if in_enhancements {
+            continue;
+        }
+
+        match size_row.captures(line) {
+            Some(caps) => {
+                let name = caps["name"].trim_end_matches(['.', ' ']).trim();
+                if !name.is_empty() {
+                    unit = Some(name.to_string());
+                }
+                let (Some(faction), Some(unit)) = (&faction, &unit) else {
+                    continue;
+                };
+                let (Ok(models), Ok(points)) =
+                    (caps["models"].parse::<u32>(), caps["points"].parse::<u32>())
+                else {
+                    continue;
+                };
+                if seen.insert((faction.clone(), unit.to_lowercase(), models)) {
+                    entries.push(FieldManualEntry {
+                        faction: faction.clone(),
+                        unit_name: unit.clone(),
+                        models,
+                        points,
+                    });
+                }
+            }
+            // Single-cost lines ("Name ..... 25 pts") aren't unit sizes
+            None if line.ends_with("pts") => {}
+            None => unit = Some(line.trim_end_matches(['.', ' ']).to_string()),
+        }
+    }
+    entries
+}
+
+/// Input for the Points Watcher agent.
+#[derive(Debug, Clone)]
+pub struct PointsWatcherInput {
+    /// Text extracted from the Field Manual PDF
+    pub pdf_text: String,
+
+    /// URL of the PDF
+    pub source_url: String,
+
+    /// Balance pass the Field Manual belongs to
+    pub significant_event_id: SignificantEventId,
+
+    /// Epoch the balance pass starts
+    pub epoch_id: EpochId,
+
+    /// Date the costs take effect
+    pub effective_date: NaiveDate,
+
+    /// Costs from the previous epoch, for computing changes
+    pub previous: Vec<PointsChange>,
+}
+
+/// Points Watcher agent implementation.
+#[derive(Debug, Default)]
+pub struct PointsWatcherAgent;
+
+impl PointsWatcherAgent {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Agent for PointsWatcherAgent {
+    type Input = PointsWatcherInput;
+    type Output = AgentOutput<Vec<PointsChange>>;
+
+    fn name(&self) -> &'static str {
+        "points_watcher"
+    }
+
+    async fn execute(&self, input: Self::Input) -> Result<Self::Output, AgentError> {
+        info!("Running Points Watcher on {}", input.source_url);
+
+        let entries = parse_field_manual(&input.pdf_text);
+        if entries.is_empty() {
+            return Err(AgentError::ExtractionRefused(
+                "No unit costs found; is this a Munitorum Field Manual?".to_string(),
+            ));
+        }
+
+        let previous: HashMap<(String, String, u32), u32> = input
+            .previous
+            .iter()
+            .map(|p| {
+                (
+                    (p.faction.clone(), p.unit_name.to_lowercase(), p.models),
+                    p.points,
+                )
+            })
+            .collect();
+
+        let mut changed = 0;
+        let records: Vec<PointsChange> = entries
+            .iter()
+            .map(|e| {
+                let record = PointsChange::new(
+                    input.epoch_id.clone(),
+                    input.significant_event_id.clone(),
+                    input.effective_date,
+                    e.faction.clone(),
+                    e.unit_name.clone(),
+                    e.models,
+                    e.points,
+                    input.source_url.clone(),
+                );
+                match previous.get(&(e.faction.clone(), e.unit_name.to_lowercase(), e.models)) {
+                    Some(&prev) => {
+                        if prev != e.points {
+                            changed += 1;
+                        }
+                        record.with_previous_points(prev)
+                    }
+                    None => record,
+                }
+            })
+            .collect();
+
+        let factions: HashSet<&str> = entries.iter().map(|e| e.faction.as_str()).collect();
+        let mut notes = vec![format!(
+            "{} unit sizes across {} factions, {} changed",
+            records.len(),
+            factions.len(),
+            changed
+        )];
+        let confidence = if records.len() >= MIN_FIELD_MANUAL_ROWS {
+            Confidence::High
+        } else {
+            notes.push("Few rows parsed; the PDF may not be a full Field Manual".to_string());
+            Confidence::Low
+        };
+
+        info!(
+            "Points Watcher found {} costs ({} changed)",
+            records.len(),
+            changed
+        );
+        Ok(AgentOutput::new(records, confidence).with_notes(notes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIELD_MANUAL: &str = "\
+MUNITORUM FIELD MANUAL
+CODEX: AELDARI
+Autarch
+1 model ................................................ 85 pts
+Wraithguard
+5 models ............................................. 170 pts
+10 models ............................................ 340 pts
+War Walkers 1 model ...................................... 55 pts
+DETACHMENT ENHANCEMENTS
+BATTLE HOST
+Fate's Messenger ......................................... 15 pts
+CODEX: ORKS
+Boyz
+10 models ............................................. 85 pts
+12
+";
+
+    #[test]
+    fn test_parse_field_manual() {
+        let entries = parse_field_manual(FIELD_MANUAL);
+        let summary: Vec<(&str, &str, u32, u32)> = entries
+            .iter()
+            .map(|e| (e.faction.as_str(), e.unit_name.as_str(), e.models, e.points))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Aeldari", "Autarch", 1, 85),
+                ("Aeldari", "Wraithguard", 5, 170),
+                ("Aeldari", "Wraithguard", 10, 340),
+                ("Aeldari", "War Walkers", 1, 55),
+                ("Orks", "Boyz", 10, 85),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_points_watcher_records_changes() {
+        let date = NaiveDate::from_ymd_opt(2025, 6, 12).unwrap();
+        let previous = PointsChange::new(
+            "old".into(),
+            "old-pass".into(),
+            date,
+            "Aeldari".to_string(),
+            "Wraithguard".to_string(),
+            5,
+            185,
+            "https://example.com/old.pdf".to_string(),
+        );
+        let output = PointsWatcherAgent::new()
+            .execute(PointsWatcherInput {
+                pdf_text: FIELD_MANUAL.to_string(),
+                source_url: "https://example.com/mfm.pdf".to_string(),
+                significant_event_id: "pass".into(),
+                epoch_id: "new".into(),
+                effective_date: date,
+                previous: vec![previous],
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(output.data.len(), 5);
+        // A short sample isn't a full Field Manual
+        assert_eq!(output.confidence, Confidence::Low);
+        let wraithguard = &output.data[1];
+        assert_eq!(wraithguard.previous_points, Some(185));
+        assert_eq!(wraithguard.delta(), Some(-15));
+        assert_eq!(output.data[0].previous_points, None);
+
+        let err = PointsWatcherAgent::new()
+            .execute(PointsWatcherInput {
+                pdf_text: "Balance Dataslate\nRules changes only".to_string(),
+                source_url: String::new(),
+                significant_event_id: "pass".into(),
+                epoch_id: "new".into(),
+                effective_date: date,
+                previous: vec![],
+            })
+            .await;
+        assert!(matches!(err, Err(AgentError::ExtractionRefused(_))));
+    }
+}
@@ -6,11 +6,12 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info};
+use tracing::info;
 
-use super::backend::{AiBackend, ChatMessage, ChatRequest};
+use super::backend::{chat_parsed, AiBackend, ChatMessage, ChatRequest};
+use super::estimate::{estimate_prompt_tokens, estimate_tokens, UsageSample};
 use super::event_scout::EventStub;
-use super::{Agent, AgentError, AgentOutput, RetryPolicy};
+use super::{Agent, AgentError, AgentOutput, RetryNotes, RetryPolicy};
 use crate::models::Confidence;
 
 /// Win/Loss/Draw record.
@@ -44,6 +45,10 @@ pub struct PlacementStub {
 
     /// Battle points if shown
     pub battle_points: Option<u32>,
+
+    /// Team or club if shown
+    #[serde(default)]
+    pub team: Option<String>,
 }
 
 /// Raw army list text for normalization.
@@ -79,6 +84,14 @@ pub struct ResultHarvesterOutput {
     pub raw_lists: Vec<RawListText>,
 }
 
+impl RetryNotes for ResultHarvesterOutput {
+    fn note_retries(&mut self, note: &str) {
+        for placement in &mut self.placements {
+            placement.note_retries(note);
+        }
+    }
+}
+
 /// AI-extracted placement data.
 #[derive(Debug, Deserialize)]
 struct ExtractedPlacement {
@@ -91,6 +104,8 @@ struct ExtractedPlacement {
     losses: Option<u32>,
     draws: Option<u32>,
     battle_points: Option<u32>,
+    #[serde(default)]
+    team: Option<String>,
     army_list: Option<String>,
     confidence: String,
 }
@@ -110,7 +125,20 @@ impl ResultHarvesterAgent {
         Self { backend }
     }
 
-    fn build_prompt(&self, html_content: &str, event: &EventStub) -> Vec<ChatMessage> {
+    /// Estimate the token usage of harvesting one of an article's `events`
+    /// events, without calling the backend. Placements and lists are copied
+    /// out of the text, so the reply is sized as the event's share of it.
+    pub fn estimate_usage(article_text: &str, events: u32) -> UsageSample {
+        UsageSample {
+            prompt_tokens: estimate_prompt_tokens(&Self::build_prompt(
+                article_text,
+                &EventStub::default(),
+            )),
+            completion_tokens: estimate_tokens(article_text) / events.max(1),
+        }
+    }
+
+    fn build_prompt(html_content: &str, event: &EventStub) -> Vec<ChatMessage> {
         vec![
             ChatMessage::system(RESULT_HARVESTER_SYSTEM_PROMPT),
             ChatMessage::user(format!(
@@ -154,6 +182,7 @@ impl ResultHarvesterAgent {
                 detachment: placement.detachment,
                 record,
                 battle_points: placement.battle_points,
+                team: placement.team,
             };
 
             let confidence = match placement.confidence.to_lowercase().as_str() {
@@ -203,6 +232,7 @@ For each placing player, extract:
 - losses: Number of losses (integer, null if not shown)
 - draws: Number of draws (integer, null if not shown)
 - battle_points: Total battle points if shown
+- team: Team or club the player represents if shown (e.g. "Vanguard Tactics"), null otherwise
 - army_list: Full army list text if present (preserve formatting)
 - confidence: "high", "medium", or "low"
 
@@ -260,6 +290,7 @@ Return JSON in this exact format:
       "losses": 0,
       "draws": 0,
       "battle_points": 94,
+      "team": "Vanguard Tactics",
       "army_list": "++ Battalion Detachment...",
       "confidence": "high"
     }
@@ -295,13 +326,15 @@ impl Agent for ResultHarvesterAgent {
     async fn execute(&self, input: Self::Input) -> Result<Self::Output, AgentError> {
         info!("Running Result Harvester for {}", input.event_stub.name);
 
-        let messages = self.build_prompt(&input.article_html, &input.event_stub);
+        let messages = Self::build_prompt(&input.article_html, &input.event_stub);
         let request = ChatRequest::new(messages).with_json_mode();
 
-        let response = self.backend.chat(request).await?;
-        debug!("AI response: {}", response.content);
-
-        let output = self.parse_response(&response.content)?;
+        let (mut output, response) =
+            chat_parsed(self.backend.as_ref(), request, |c| self.parse_response(c)).await?;
+        for placement in &mut output.placements {
+            placement.model = Some(response.model.clone());
+            placement.agent_version = Some(self.version());
+        }
 
         info!(
             "Result Harvester found {} placements, {} lists",
@@ -368,6 +401,7 @@ mod tests {
             round_count: Some(5),
             event_type: Some("GT".to_string()),
             article_section: None,
+            mission_pack: None,
         }
     }
 
@@ -442,12 +476,14 @@ mod tests {
                 draws: 0,
             }),
             battle_points: Some(94),
+            team: Some("Vanguard Tactics".to_string()),
         };
 
         let json = serde_json::to_string(&stub).unwrap();
         let parsed: PlacementStub = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.rank, 1);
         assert_eq!(parsed.battle_points, Some(94));
+        assert_eq!(parsed.team.as_deref(), Some("Vanguard Tactics"));
     }
 
     #[test]
@@ -486,4 +522,16 @@ mod tests {
         let parsed: RawListText = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.placement_rank, 1);
     }
+
+    #[test]
+    fn test_result_harvester_estimate_usage() {
+        let text = "1st: Alice (Aeldari) 5-0\n".repeat(40);
+        let one = ResultHarvesterAgent::estimate_usage(&text, 1);
+        assert!(one.prompt_tokens > estimate_tokens(&text));
+        assert_eq!(one.completion_tokens, estimate_tokens(&text));
+        // Each event's reply covers its share of the article
+        let two = ResultHarvesterAgent::estimate_usage(&text, 2);
+        assert_eq!(two.prompt_tokens, one.prompt_tokens);
+        assert_eq!(two.completion_tokens, one.completion_tokens / 2);
+    }
 }
@@ -0,0 +1,288 @@
+//! Season Summary Agent.
+//!
+//! Goonhammer wraps up each season of Competitive Innovations with an
+//! article of aggregate stats: how many events and players the period
+//! covered and how each faction performed. This agent extracts those
+//! aggregates so [`crate::ingest::season_summary`] can check our own
+//! dataset against them.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use super::backend::{chat_parsed, AiBackend, ChatMessage, ChatRequest};
+use super::{Agent, AgentError, AgentOutput, RetryPolicy};
+use crate::models::canonical_faction_name;
+use crate::models::Confidence;
+
+/// Input for the Season Summary agent.
+#[derive(Debug, Clone)]
+pub struct SeasonSummaryInput {
+    /// HTML content of the article
+    pub article_html: String,
+
+    /// URL of the article
+    pub article_url: String,
+
+    /// Publication date, used when the article states no period end
+    pub article_date: NaiveDate,
+}
+
+/// A faction's performance as published.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublishedFactionStat {
+    /// Canonical faction name
+    pub faction: String,
+
+    /// Win rate (0.0 to 1.0)
+    pub win_rate: f64,
+
+    /// Games the win rate is over, if stated
+    pub games: Option<u32>,
+}
+
+/// Aggregates published in a season summary article.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeasonSummary {
+    pub title: String,
+    pub source_url: String,
+    /// First day of the period covered
+    pub period_start: NaiveDate,
+    /// Last day of the period covered
+    pub period_end: NaiveDate,
+    pub event_count: Option<u32>,
+    pub player_count: Option<u32>,
+    pub factions: Vec<PublishedFactionStat>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtractedFaction {
+    faction: String,
+    win_rate: Option<f64>,
+    #[serde(default)]
+    games: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SeasonSummaryResponse {
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    period_start: Option<String>,
+    #[serde(default)]
+    period_end: Option<String>,
+    #[serde(default)]
+    event_count: Option<u32>,
+    #[serde(default)]
+    player_count: Option<u32>,
+    #[serde(default)]
+    factions: Vec<ExtractedFaction>,
+    confidence: String,
+}
+
+/// Season Summary agent implementation.
+pub struct SeasonSummaryAgent {
+    backend: Arc<dyn AiBackend>,
+}
+
+impl SeasonSummaryAgent {
+    pub fn new(backend: Arc<dyn AiBackend>) -> Self {
+        Self { backend }
+    }
+
+    fn build_prompt(&self, html_content: &str, article_date: NaiveDate) -> Vec<ChatMessage> {
+        vec![
+            ChatMessage::system(SEASON_SUMMARY_SYSTEM_PROMPT),
+            ChatMessage::user(format!(
+                "Article date: {}\n\nArticle content:\n\n{}",
+                article_date, html_content
+            )),
+        ]
+    }
+
+    fn parse_response(
+        &self,
+        response: &str,
+        input: &SeasonSummaryInput,
+    ) -> Result<AgentOutput<SeasonSummary>, AgentError> {
+        let json = super::extract_json(response);
+        let parsed: SeasonSummaryResponse = serde_json::from_str(json)
+            .map_err(|e| AgentError::ResponseParseError(format!("Invalid JSON: {}", e)))?;
+
+        let mut notes = Vec::new();
+        let parse_date = |d: &Option<String>| {
+            d.as_ref()
+                .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        };
+        let period_end = parse_date(&parsed.period_end).unwrap_or_else(|| {
+            notes.push("Period end not stated, using article date".to_string());
+            input.article_date
+        });
+        let Some(period_start) = parse_date(&parsed.period_start) else {
+            return Err(AgentError::ExtractionRefused(
+                "article states no period for its stats".to_string(),
+            ));
+        };
+
+        let mut factions: Vec<PublishedFactionStat> = Vec::new();
+        for f in parsed.factions {
+            let Some(rate) = f.win_rate else {
+                continue;
+            };
+            // Articles quote percentages; accept either scale
+            let win_rate = if rate > 1.0 { rate / 100.0 } else { rate };
+            let faction = canonical_faction_name(&f.faction);
+            if !(0.0..=1.0).contains(&win_rate) || faction.is_empty() {
+                notes.push(format!("Ignored win rate {} for '{}'", rate, f.faction));
+                continue;
+            }
+            if factions.iter().any(|existing| existing.faction == faction) {
+                continue;
+            }
+            factions.push(PublishedFactionStat {
+                faction,
+                win_rate,
+                games: f.games,
+            });
+        }
+
+        let confidence = match parsed.confidence.to_lowercase().as_str() {
+            "high" => Confidence::High,
+            "medium" => Confidence::Medium,
+            _ => Confidence::Low,
+        };
+        let summary = SeasonSummary {
+            title: parsed.title.unwrap_or_else(|| input.article_url.clone()),
+            source_url: input.article_url.clone(),
+            period_start,
+            period_end,
+            event_count: parsed.event_count,
+            player_count: parsed.player_count,
+            factions,
+        };
+        Ok(AgentOutput::new(summary, confidence).with_notes(notes))
+    }
+}
+
+const SEASON_SUMMARY_SYSTEM_PROMPT: &str = r#"You are extracting aggregate statistics from a Goonhammer Competitive Innovations season summary article.
+
+These articles wrap up a period of competitive play (a season, quarter or balance window) with totals across all the events they covered.
+
+Extract:
+- title: The article title
+- period_start: First day of the period the stats cover, YYYY-MM-DD (null if not stated)
+- period_end: Last day of the period the stats cover, YYYY-MM-DD (null if not stated)
+- event_count: Number of events the stats cover, as integer (null if not stated)
+- player_count: Number of players across those events, as integer (null if not stated)
+- factions: Each faction's overall win rate, as published
+  - faction: Faction name as written
+  - win_rate: Win rate as a percentage, e.g. 52.4
+  - games: Number of games behind the win rate (null if not stated)
+- confidence: "high", "medium", or "low"
+
+Return JSON in this exact format:
+{
+  "title": "Competitive Innovations: Season Wrap-Up",
+  "period_start": "2025-06-15",
+  "period_end": "2025-09-30",
+  "event_count": 184,
+  "player_count": 9120,
+  "factions": [
+    {"faction": "Aeldari", "win_rate": 54.1, "games": 3210}
+  ],
+  "confidence": "high"
+}
+
+IMPORTANT:
+- Only extract figures for the whole period, not for single events
+- Do NOT compute or estimate figures the article does not state
+- Use null for any field not explicitly stated
+- Set confidence to "low" if the figures are unclear"#;
+
+#[async_trait]
+impl Agent for SeasonSummaryAgent {
+    type Input = SeasonSummaryInput;
+    type Output = AgentOutput<SeasonSummary>;
+
+    fn name(&self) -> &'static str {
+        "season_summary"
+    }
+
+    async fn execute(&self, input: Self::Input) -> Result<Self::Output, AgentError> {
+        info!("Running Season Summary on {}", input.article_url);
+
+        let messages = self.build_prompt(&input.article_html, input.article_date);
+        let request = ChatRequest::new(messages).with_json_mode();
+
+        let (output, response) = chat_parsed(self.backend.as_ref(), request, |c| {
+            self.parse_response(c, &input)
+        })
+        .await?;
+        let output = output
+            .with_model(&response.model)
+            .with_agent_version(self.version());
+        info!(
+            "Season Summary found {} faction win rates",
+            output.data.factions.len()
+        );
+        Ok(output)
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            initial_delay_ms: 1000,
+            backoff_multiplier: 2.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::backend::MockBackend;
+
+    #[tokio::test]
+    async fn test_season_summary_extraction() {
+        let backend = Arc::new(MockBackend::new(
+            r#"{
+                "title": "Season Wrap-Up",
+                "period_start": "2026-01-01",
+                "period_end": null,
+                "event_count": 120,
+                "player_count": null,
+                "factions": [
+                    {"faction": "Aeldari", "win_rate": 54.5, "games": 900},
+                    {"faction": "Orks", "win_rate": 0.48},
+                    {"faction": "Necrons", "win_rate": 250},
+                    {"faction": "Tau", "win_rate": null}
+                ],
+                "confidence": "high"
+            }"#,
+        ));
+        let agent = SeasonSummaryAgent::new(backend);
+        let output = agent
+            .execute(SeasonSummaryInput {
+                article_html: "<html>...</html>".to_string(),
+                article_url: "https://goonhammer.com/wrap".to_string(),
+                article_date: NaiveDate::from_ymd_opt(2026, 3, 31).unwrap(),
+            })
+            .await
+            .unwrap();
+
+        let summary = &output.data;
+        assert_eq!(
+            summary.period_end,
+            NaiveDate::from_ymd_opt(2026, 3, 31).unwrap()
+        );
+        assert_eq!(summary.event_count, Some(120));
+        assert_eq!(summary.factions.len(), 2);
+        assert!((summary.factions[0].win_rate - 0.545).abs() < 1e-9);
+        assert_eq!(summary.factions[1].faction, "Orks");
+        assert_eq!(output.confidence, Confidence::High);
+        assert_eq!(output.extraction_notes.len(), 2);
+    }
+}
@@ -0,0 +1,171 @@
+//! API token check for mutating routes.
+//!
+//! When `[server] api_token` is set, every request that can change state
+//! (anything but GET, HEAD and OPTIONS) must present the token, either as
+//! `Authorization: Bearer <token>` or in an `X-API-Key` header. Read-only
+//! routes stay open. With no token configured, nothing is checked here and
+//! the per-route localhost guards are the only protection.
+
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, Method};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use sha2::{Digest, Sha256};
+
+use crate::api::state::AppState;
+use crate::api::ApiError;
+
+/// Header carrying the token as an alternative to `Authorization`.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Whether a request with this method can change state.
+fn is_mutating(method: &Method) -> bool {
+    !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// The token a request presents, if any.
+fn presented_token(headers: &HeaderMap) -> Option<&str> {
+    let bearer = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    bearer
+        .or_else(|| headers.get(API_KEY_HEADER).and_then(|v| v.to_str().ok()))
+        .map(str::trim)
+}
+
+/// Compare tokens in constant time, ignoring surrounding whitespace on
+/// either side. Both are hashed first so neither the comparison nor an
+/// early length mismatch leaks how much matched.
+pub fn tokens_match(given: &str, expected: &str) -> bool {
+    let given = Sha256::digest(given.trim().as_bytes());
+    let expected = Sha256::digest(expected.trim().as_bytes());
+    given
+        .iter()
+        .zip(expected.iter())
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+/// Middleware rejecting mutating requests without the configured token.
+pub async fn require_api_token(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if let Some(expected) = state.api_token.as_deref() {
+        if is_mutating(req.method()) {
+            let authorized =
+                presented_token(req.headers()).is_some_and(|t| tokens_match(t, expected));
+            if !authorized {
+                return ApiError::Forbidden("Missing or invalid API token".to_string())
+                    .into_response();
+            }
+        }
+    }
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::build_router;
+    use crate::models::EpochMapper;
+    use crate::storage::StorageConfig;
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use std::sync::Arc;
+    use tower::util::ServiceExt;
+
+    fn state(dir: &std::path::Path, token: Option<&str>) -> AppState {
+        AppState {
+            storage: Arc::new(StorageConfig::new(dir.to_path_buf())),
+            epoch_mapper: Arc::new(tokio::sync::RwLock::new(EpochMapper::new())),
+            refresh_state: Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::refresh::RefreshState::default(),
+            )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: token.map(Arc::from),
+            notifier: None,
+            metrics: Default::default(),
+            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
+            traffic_stats: Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::traffic::TrafficStats::new(),
+            )),
+        }
+    }
+
+    async fn status(
+        app: &axum::Router,
+        method: Method,
+        uri: &str,
+        auth: Option<(&str, &str)>,
+    ) -> StatusCode {
+        let mut req = Request::builder().method(method).uri(uri);
+        if let Some((name, value)) = auth {
+            req = req.header(name, value);
+        }
+        app.clone()
+            .oneshot(req.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_mutating_routes_require_token() {
+        let tmp = tempfile::tempdir().unwrap();
+        let app = build_router(state(tmp.path(), Some("s3cret")));
+
+        let reload = "/api/epochs/reload";
+        assert_eq!(
+            status(&app, Method::POST, reload, None).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status(
+                &app,
+                Method::POST,
+                reload,
+                Some(("authorization", "Bearer wrong"))
+            )
+            .await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status(
+                &app,
+                Method::POST,
+                reload,
+                Some(("authorization", "Bearer s3cret"))
+            )
+            .await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(&app, Method::POST, reload, Some((API_KEY_HEADER, "s3cret"))).await,
+            StatusCode::OK
+        );
+        // Reads stay open
+        assert_eq!(
+            status(&app, Method::GET, "/api/epochs", None).await,
+            StatusCode::OK
+        );
+
+        // No token configured: nothing to check
+        let open = build_router(state(tmp.path(), None));
+        assert_eq!(
+            status(&open, Method::POST, reload, None).await,
+            StatusCode::OK
+        );
+
+        assert!(tokens_match("s3cret", "s3cret"));
+        assert!(!tokens_match("s3cre", "s3cret"));
+        // A configured token padded with whitespace still matches
+        assert!(tokens_match("s3cret", " s3cret\n"));
+    }
+}
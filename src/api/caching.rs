@@ -0,0 +1,230 @@
+//! Conditional GET for analytics responses.
+//!
+//! Analytics handlers fold whole epochs of JSONL into every response, so a
+//! response only changes when those files or the epoch boundaries do.
+//! Routes opt into [`conditional_get`] with `route_layer`; it tags each
+//! successful GET with a weak ETag over:
+//!
+//! - the data directory, request path and query, and `X-Epoch` header
+//! - the epoch boundaries
+//! - size and mtime of every file the handler read, and of the directories
+//!   holding them, so a new file appearing beside them also changes the tag
+//!
+//! plus `Cache-Control: max-age`. The files each URL read are remembered,
+//! so a revalidation whose tag still matches gets a 304 without the handler
+//! running at all. Responses built without reading any JSONL (alternate
+//! store backends, for one) get no tag, since nothing could invalidate it.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::UNIX_EPOCH;
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use sha2::{Digest, Sha256};
+
+use crate::api::state::AppState;
+use crate::api::EPOCH_HEADER;
+
+/// How long clients may reuse a response before revalidating.
+pub const MAX_AGE_SECS: u64 = 60;
+
+/// URLs whose read files are remembered before the map is cleared.
+const REMEMBERED_LIMIT: usize = 10_000;
+
+/// Request key to the files its last response was built from.
+fn remembered() -> &'static Mutex<HashMap<String, Vec<PathBuf>>> {
+    static FILES: OnceLock<Mutex<HashMap<String, Vec<PathBuf>>>> = OnceLock::new();
+    FILES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Weak ETag for a request given the files its response reads.
+pub fn fingerprint(key: &str, epochs: &str, files: &[PathBuf]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(key.as_bytes());
+    hasher.update(epochs.as_bytes());
+    let mut paths: BTreeSet<&Path> = files.iter().map(PathBuf::as_path).collect();
+    paths.extend(files.iter().filter_map(|f| f.parent()));
+    for path in paths {
+        hasher.update(path.to_string_lossy().as_bytes());
+        match std::fs::metadata(path) {
+            Ok(meta) => {
+                hasher.update(meta.len().to_le_bytes());
+                let modified = meta
+                    .modified()
+                    .ok()
+                    .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |d| d.as_nanos());
+                hasher.update(modified.to_le_bytes());
+            }
+            Err(_) => hasher.update(b"missing"),
+        }
+    }
+    format!("W/\"{}\"", &hex::encode(hasher.finalize())[..16])
+}
+
+/// Whether an `If-None-Match` value matches a tag (weak comparison).
+fn matches(if_none_match: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    if_none_match
+        .split(',')
+        .any(|tag| tag.trim() == "*" || opaque(tag) == opaque(etag))
+}
+
+fn set_cache_headers(response: &mut Response, etag: &str) {
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(etag) {
+        headers.insert(header::ETAG, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&format!("max-age={}", MAX_AGE_SECS)) {
+        headers.insert(header::CACHE_CONTROL, value);
+    }
+    headers.append(header::VARY, HeaderValue::from_static(EPOCH_HEADER));
+}
+
+fn not_modified(etag: &str) -> Response {
+    let mut response = StatusCode::NOT_MODIFIED.into_response();
+    set_cache_headers(&mut response, etag);
+    response
+}
+
+/// Middleware adding ETag / Cache-Control to GET responses and answering
+/// matching `If-None-Match` requests with 304 Not Modified.
+pub async fn conditional_get(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    if req.method() != Method::GET {
+        return next.run(req).await;
+    }
+    let key = format!(
+        "{}|{}|{}",
+        state.storage.data_dir.display(),
+        req.uri().path_and_query().map_or("", |p| p.as_str()),
+        req.headers()
+            .get(EPOCH_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+    );
+    let epochs =
+        serde_json::to_string(state.epoch_mapper.read().await.all_epochs()).unwrap_or_default();
+    let if_none_match = req
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    if let Some(given) = &if_none_match {
+        let known = remembered()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&key)
+            .cloned();
+        if let Some(files) = known {
+            let etag = fingerprint(&key, &epochs, &files);
+            if matches(given, &etag) {
+                return not_modified(&etag);
+            }
+        }
+    }
+
+    let (mut response, files) = crate::storage::track_reads(next.run(req)).await;
+    // Pass the reads on to any enclosing tracker (slow request logging)
+    for file in &files {
+        crate::storage::note_read(file);
+    }
+    if !response.status().is_success() || files.is_empty() {
+        return response;
+    }
+
+    let etag = fingerprint(&key, &epochs, &files);
+    {
+        let mut known = remembered().lock().unwrap_or_else(|e| e.into_inner());
+        if known.len() >= REMEMBERED_LIMIT {
+            known.clear();
+        }
+        known.insert(key, files);
+    }
+    if if_none_match.is_some_and(|given| matches(&given, &etag)) {
+        return not_modified(&etag);
+    }
+    set_cache_headers(&mut response, &etag);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::build_router;
+    use crate::api::state::AppState;
+    use crate::models::{EpochMapper, Event};
+    use crate::storage::StorageConfig;
+    use axum::body::Body;
+    use axum::http::{header, Request, StatusCode};
+    use std::sync::Arc;
+    use tower::util::ServiceExt;
+
+    #[tokio::test]
+    async fn test_analytics_etag_revalidation() {
+        let tmp = tempfile::tempdir().unwrap();
+        let epoch_dir = tmp.path().join("normalized").join("current");
+        std::fs::create_dir_all(&epoch_dir).unwrap();
+        let write_events = |names: &[&str]| {
+            let lines: Vec<String> = names
+                .iter()
+                .map(|name| {
+                    let event = Event::new(
+                        name.to_string(),
+                        chrono::NaiveDate::from_ymd_opt(2026, 1, 10).unwrap(),
+                        format!("https://example.com/{}", name),
+                        "test".into(),
+                        "current".into(),
+                    );
+                    serde_json::to_string(&event).unwrap()
+                })
+                .collect();
+            std::fs::write(epoch_dir.join("events.jsonl"), lines.join("\n") + "\n").unwrap();
+        };
+        write_events(&["GT Alpha"]);
+
+        let app = build_router(AppState {
+            storage: Arc::new(StorageConfig::new(tmp.path().to_path_buf())),
+            epoch_mapper: Arc::new(tokio::sync::RwLock::new(EpochMapper::new())),
+            refresh_state: Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::refresh::RefreshState::default(),
+            )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
+            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
+            traffic_stats: Default::default(),
+        });
+        let get = |etag: Option<String>| {
+            let mut req = Request::builder().uri("/api/analytics/overview");
+            if let Some(etag) = etag {
+                req = req.header(header::IF_NONE_MATCH, etag);
+            }
+            app.clone().oneshot(req.body(Body::empty()).unwrap())
+        };
+
+        let resp = get(None).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers()[header::CACHE_CONTROL], "max-age=60");
+        let etag = resp.headers()[header::ETAG].to_str().unwrap().to_string();
+        assert!(etag.starts_with("W/\""));
+
+        let resp = get(Some(etag.clone())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(resp.headers()[header::ETAG], etag.as_str());
+
+        // New data invalidates the tag
+        write_events(&["GT Alpha", "GT Beta"]);
+        let resp = get(Some(etag.clone())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_ne!(resp.headers()[header::ETAG], etag.as_str());
+    }
+}
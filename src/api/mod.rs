@@ -3,43 +3,92 @@
 //! Axum-based HTTP API for querying tournament data,
 //! epoch information, and derived analytics.
 
+pub mod auth;
+pub mod caching;
 pub mod routes;
+pub mod search;
 pub mod state;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use axum::{
-    extract::ConnectInfo,
-    http::StatusCode,
+    async_trait,
+    extract::{ConnectInfo, DefaultBodyLimit, FromRequestParts, MatchedPath, Query},
+    http::{request::Parts, HeaderMap, StatusCode},
     middleware::{self, Next},
     response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::services::ServeDir;
 use tower_http::trace::TraceLayer;
 
 use crate::api::state::AppState;
-use crate::models::EpochMapper;
+use crate::calculate::validation::DEFAULT_GAME_SIZE;
+use crate::ingest::rosters::MAX_ROSTER_BYTES;
+use crate::models::{
+    country_code, Confidence, EpochMapper, Event, Region, SizeBracket, SizeThresholds, Timestamped,
+};
 
 /// Build the full application router.
 pub fn build_router(state: AppState) -> Router {
     let api = Router::new()
+        .route("/api/search", get(search::search))
         .route("/api/events", get(routes::events::list_events))
+        .route("/api/events/upcoming", get(routes::events::upcoming_events))
         .route("/api/events/:id", get(routes::events::get_event))
+        .route("/api/events/:id/full", get(routes::events::get_event_full))
+        .route(
+            "/api/lists/import",
+            post(routes::lists::import_roster_file)
+                .layer(DefaultBodyLimit::max(MAX_ROSTER_BYTES as usize)),
+        )
+        .route("/api/lists/:id", get(routes::lists::get_list))
+        .route("/api/lists/:id/similar", get(routes::lists::similar))
+        .route("/api/export/:entity", get(routes::export::export_entity))
         .route("/api/meta/factions", get(routes::meta::faction_stats))
         .route(
             "/api/meta/factions/:name",
             get(routes::meta::faction_detail),
         )
         .route("/api/meta/allegiances", get(routes::meta::allegiance_stats))
+        .route("/api/meta/snapshot", get(routes::meta::snapshot))
         .route("/api/epochs", get(routes::epochs::list_epochs))
+        .route("/api/epochs/reload", post(routes::epochs::reload_epochs))
         .route("/api/balance", get(routes::epochs::list_balance_passes))
         .route("/api/balance/:id", get(routes::epochs::get_balance_pass))
+        .route("/api/players/:name", get(routes::players::get_player))
+        .route(
+            "/api/units/:name/points-history",
+            get(routes::units::points_history),
+        )
+        .route("/api/units/:name/trend", get(routes::units::unit_trend))
+        .route("/api/series", get(routes::series::list_series))
+        .route("/api/series/:name", get(routes::series::get_series))
+        .route("/api/review", get(routes::review::list_review_items))
+        .route("/api/review/:id", get(routes::review::get_review_item))
+        .route(
+            "/api/review/:id/resolve",
+            post(routes::review::resolve_review_item),
+        )
+        .route("/api/refresh/preview", get(routes::refresh::preview))
+        .route("/api/refresh", post(routes::refresh::start_refresh))
+        .route("/api/refresh/status", get(routes::refresh::status))
+        .route("/api/refresh/stream", get(routes::refresh::stream))
+        .route("/api/issues", get(routes::issues::list_issues))
+        .route("/api/quality/findings", get(routes::quality::list_findings))
+        .route("/api/quality/lists", get(routes::quality::list_quality))
+        .route("/api/sync/history", get(routes::sync::history))
+        .route("/api/traffic", get(routes::traffic::traffic_stats))
+        .route("/api/traffic/geo", get(routes::traffic::geo_lookup));
+
+    // Analytics recompute from JSONL on every request; let clients cache them
+    let analytics = Router::new()
         .route("/api/analytics/overview", get(routes::analytics::overview))
+        .route("/api/analytics/quality", get(routes::analytics::quality))
         .route(
             "/api/analytics/trends",
             get(routes::analytics::faction_trends),
@@ -49,13 +98,18 @@ pub fn build_router(state: AppState) -> Router {
             get(routes::analytics::top_players),
         )
         .route("/api/analytics/units", get(routes::analytics::top_units))
-        .route("/api/refresh/preview", get(routes::refresh::preview))
-        .route("/api/refresh", post(routes::refresh::start_refresh))
-        .route("/api/refresh/status", get(routes::refresh::status))
         .route(
             "/api/analytics/detachments",
             get(routes::analytics::detachment_stats),
         )
+        .route(
+            "/api/analytics/detachments/:faction",
+            get(routes::analytics::faction_detachments),
+        )
+        .route(
+            "/api/analytics/enhancements",
+            get(routes::analytics::enhancement_stats),
+        )
         .route(
             "/api/analytics/unit-performance",
             get(routes::analytics::unit_performance),
@@ -65,10 +119,18 @@ pub fn build_router(state: AppState) -> Router {
             get(routes::analytics::points_efficiency),
         )
         .route("/api/analytics/matchups", get(routes::analytics::matchups))
+        .route(
+            "/api/analytics/matchup-matrix",
+            get(routes::analytics::matchup_matrix),
+        )
+        .route("/api/analytics/sos", get(routes::analytics::sos))
+        .route("/api/analytics/missions", get(routes::analytics::missions))
+        .route("/api/analytics/teams", get(routes::analytics::teams))
         .route(
             "/api/analytics/archetypes",
             get(routes::analytics::archetypes),
         )
+        .route("/api/analytics/combos", get(routes::analytics::combos))
         .route(
             "/api/analytics/win-rates",
             get(routes::analytics::win_rates),
@@ -77,17 +139,60 @@ pub fn build_router(state: AppState) -> Router {
             "/api/analytics/composite-scores",
             get(routes::analytics::composite_scores),
         )
-        .route("/api/traffic", get(routes::traffic::traffic_stats))
-        .route("/api/traffic/geo", get(routes::traffic::geo_lookup));
+        .route(
+            "/api/analytics/placement-curves",
+            get(routes::analytics::placement_curves),
+        )
+        .route(
+            "/api/analytics/positioning",
+            get(routes::analytics::positioning),
+        )
+        .route("/api/analytics/tiers", get(routes::analytics::tiers))
+        .route(
+            "/api/analytics/epoch-diff",
+            get(routes::analytics::epoch_diff),
+        )
+        .route(
+            "/api/analytics/head-to-head",
+            get(routes::analytics::head_to_head),
+        )
+        .route(
+            "/api/analytics/attendance",
+            get(routes::analytics::attendance),
+        )
+        .route(
+            "/api/analytics/conversion",
+            get(routes::analytics::conversion),
+        )
+        .route("/api/analytics/netlists", get(routes::analytics::netlists))
+        .route("/api/analytics/ratings", get(routes::analytics::ratings))
+        .route(
+            "/api/analytics/shift-candidates",
+            get(routes::analytics::shift_candidates),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            caching::conditional_get,
+        ));
+    let api = api.merge(analytics);
+    #[cfg(feature = "duckdb")]
+    let api = api.route("/api/query", post(routes::query::run_query));
+    let api = api.route_layer(middleware::from_fn_with_state(
+        state.clone(),
+        auth::require_api_token,
+    ));
 
     let traffic = state.traffic_stats.clone();
+    let metrics = state.metrics.clone();
 
     Router::new()
         .merge(api)
-        .fallback_service(ServeDir::new("static"))
+        .route("/metrics", get(routes::metrics::metrics))
+        .fallback_service(ServeDir::new(crate::dashboard::DEFAULT_DIR))
         .layer(middleware::from_fn(
             move |req: axum::extract::Request, next: Next| {
                 let stats = traffic.clone();
+                let metrics = metrics.clone();
                 async move {
                     // Try X-Forwarded-For / X-Real-IP first (reverse proxy),
                     // then ConnectInfo (direct connection)
@@ -113,7 +218,48 @@ pub fn build_router(state: AppState) -> Router {
                         let mut s = stats.write().await;
                         s.record(&ip, &path);
                     }
-                    next.run(req).await
+
+                    // Time API handlers; static files have no matched route
+                    let Some(route) = req
+                        .extensions()
+                        .get::<MatchedPath>()
+                        .map(|m| m.as_str().to_string())
+                    else {
+                        return next.run(req).await;
+                    };
+                    let query = req.uri().query().unwrap_or("").to_string();
+                    let epoch_header = req
+                        .headers()
+                        .get(EPOCH_HEADER)
+                        .and_then(|v| v.to_str().ok())
+                        .unwrap_or("")
+                        .to_string();
+                    let method = req.method().to_string();
+                    let started = std::time::Instant::now();
+                    let (response, files) = crate::storage::track_reads(next.run(req)).await;
+                    let elapsed = started.elapsed();
+                    metrics.record_request(&route, &method, response.status().as_u16(), elapsed);
+
+                    let (slow, budget) = {
+                        let mut s = stats.write().await;
+                        (s.record_latency(&route, elapsed), s.slow_request_budget)
+                    };
+                    if slow {
+                        let files: Vec<String> =
+                            files.iter().map(|f| f.display().to_string()).collect();
+                        tracing::warn!(
+                            route = %route,
+                            path = %path,
+                            query = %query,
+                            epoch = %epoch_header,
+                            elapsed_ms = elapsed.as_millis() as u64,
+                            budget_ms = budget.as_millis() as u64,
+                            files_read = files.len(),
+                            files = %files.join(","),
+                            "Slow request"
+                        );
+                    }
+                    response
                 }
             },
         ))
@@ -122,8 +268,23 @@ pub fn build_router(state: AppState) -> Router {
         .with_state(state)
 }
 
+/// Reject requests that come through Cloudflare Tunnel (public domain).
+/// Cloudflare always adds the `CF-Connecting-IP` header to proxied requests.
+/// `feature` names what is refused in the error.
+pub(crate) fn require_local(headers: &HeaderMap, feature: &str) -> Result<(), ApiError> {
+    if headers.contains_key("cf-connecting-ip") {
+        return Err(ApiError::Forbidden(format!(
+            "{} is only available on localhost",
+            feature
+        )));
+    }
+    Ok(())
+}
+
 /// Deduplicate entities by their ID field.
 /// Keeps the first occurrence of each ID.
+///
+/// For timestamped entities use [`merge_by_id`], which keeps the newest.
 pub fn dedup_by_id<T, F>(entities: Vec<T>, id_fn: F) -> Vec<T>
 where
     F: Fn(&T) -> &str,
@@ -135,6 +296,32 @@ where
         .collect()
 }
 
+/// Deduplicate entities by their ID field, last write wins.
+/// Keeps the most recently modified copy of each ID (the first on a tie),
+/// in the position of the ID's first occurrence.
+pub fn merge_by_id<T, F>(entities: Vec<T>, id_fn: F) -> Vec<T>
+where
+    T: Timestamped,
+    F: Fn(&T) -> &str,
+{
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut merged: Vec<T> = Vec::with_capacity(entities.len());
+    for entity in entities {
+        match positions.get(id_fn(&entity)).copied() {
+            Some(pos) => {
+                if entity.last_modified() > merged[pos].last_modified() {
+                    merged[pos] = entity;
+                }
+            }
+            None => {
+                positions.insert(id_fn(&entity).to_string(), merged.len());
+                merged.push(entity);
+            }
+        }
+    }
+    merged
+}
+
 /// Resolve an epoch parameter to an epoch ID string.
 ///
 /// - `None` or `"current"` resolves to the latest epoch from the mapper,
@@ -164,6 +351,284 @@ pub fn resolve_epoch(param: Option<&str>, mapper: &EpochMapper) -> Result<String
     }
 }
 
+/// Header naming the epoch for requests without an `epoch` query param.
+pub const EPOCH_HEADER: &str = "x-epoch";
+
+/// The epoch a request asked for.
+///
+/// Taken from the `epoch` query param, else the `X-Epoch` header, else the
+/// server's `default_epoch`. `None` means none of these were given and the
+/// endpoint applies its own default. Specific epoch IDs are validated here
+/// (`"all"` and `"current"` pass through), so an unknown epoch is rejected
+/// with 404 before the handler runs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EpochParam(pub Option<String>);
+
+impl EpochParam {
+    pub fn as_deref(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EpochQuery {
+    epoch: Option<String>,
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for EpochParam {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let query = Query::<EpochQuery>::try_from_uri(&parts.uri)
+            .ok()
+            .and_then(|q| q.0.epoch);
+        let header = match parts.headers.get(EPOCH_HEADER) {
+            Some(value) => Some(
+                value
+                    .to_str()
+                    .map_err(|_| ApiError::BadRequest("Invalid X-Epoch header".to_string()))?
+                    .trim()
+                    .to_string(),
+            ),
+            None => None,
+        };
+
+        let epoch = query.or(header).or_else(|| state.default_epoch.clone());
+        if let Some(id) = epoch.as_deref().filter(|id| *id != "all") {
+            let mapper = state.epoch_mapper.read().await;
+            resolve_epoch(Some(id), &mapper)?;
+        }
+        Ok(Self(epoch))
+    }
+}
+
+/// Location filter from the `region` and `country` query params.
+///
+/// `region` takes a region code or name (`EU`, `north-america`); `country`
+/// takes an ISO code or country name. Unknown values are rejected with 400
+/// rather than silently matching nothing. With both set, an event must
+/// match both.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GeoFilter {
+    pub region: Option<Region>,
+    pub country: Option<&'static str>,
+}
+
+impl GeoFilter {
+    /// Whether any location filter was given.
+    pub fn is_active(&self) -> bool {
+        self.region.is_some() || self.country.is_some()
+    }
+
+    /// Whether an event passes the filter. Events with no known location
+    /// only pass when the filter is inactive.
+    pub fn matches(&self, event: &Event) -> bool {
+        if let Some(country) = self.country {
+            if event.country_code() != Some(country) {
+                return false;
+            }
+        }
+        self.region
+            .is_none_or(|region| event.region() == Some(region))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GeoQuery {
+    region: Option<String>,
+    country: Option<String>,
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for GeoFilter {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Ok(Query(query)) = Query::<GeoQuery>::try_from_uri(&parts.uri) else {
+            return Ok(Self::default());
+        };
+        let region = match query.region.as_deref().filter(|r| !r.trim().is_empty()) {
+            Some(r) => Some(r.parse::<Region>().map_err(ApiError::BadRequest)?),
+            None => None,
+        };
+        let country = match query.country.as_deref().filter(|c| !c.trim().is_empty()) {
+            Some(c) => Some(
+                country_code(c)
+                    .ok_or_else(|| ApiError::BadRequest(format!("Unknown country '{}'", c)))?,
+            ),
+            None => None,
+        };
+        Ok(Self { region, country })
+    }
+}
+
+/// Event size filter from the `bracket` query param (`small`, `medium`,
+/// `large`, `major`), classified with the server's size thresholds.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SizeFilter {
+    pub bracket: Option<SizeBracket>,
+    pub thresholds: SizeThresholds,
+}
+
+impl SizeFilter {
+    pub fn is_active(&self) -> bool {
+        self.bracket.is_some()
+    }
+
+    /// Whether an event passes the filter. Events with no player count
+    /// only pass when the filter is inactive.
+    pub fn matches(&self, event: &Event) -> bool {
+        self.bracket
+            .is_none_or(|bracket| event.size_bracket(&self.thresholds) == Some(bracket))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SizeQuery {
+    bracket: Option<String>,
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for SizeFilter {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let bracket = Query::<SizeQuery>::try_from_uri(&parts.uri)
+            .ok()
+            .and_then(|q| q.0.bracket)
+            .filter(|b| !b.trim().is_empty());
+        let bracket = match bracket {
+            Some(b) => Some(b.parse::<SizeBracket>().map_err(ApiError::BadRequest)?),
+            None => None,
+        };
+        Ok(Self {
+            bracket,
+            thresholds: state.size_thresholds,
+        })
+    }
+}
+
+/// Points-limit filter from the `game_size` query param. Analytics default
+/// to 2000-point games so Incursion and Combat Patrol events don't skew the
+/// meta; `game_size=any` turns the filter off. Events with no recorded size
+/// are taken to be 2000-point games.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameSizeFilter {
+    pub points: Option<u32>,
+}
+
+impl Default for GameSizeFilter {
+    fn default() -> Self {
+        Self {
+            points: Some(DEFAULT_GAME_SIZE),
+        }
+    }
+}
+
+impl GameSizeFilter {
+    pub fn is_active(&self) -> bool {
+        self.points.is_some()
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        self.points
+            .is_none_or(|points| event.game_size.unwrap_or(DEFAULT_GAME_SIZE) == points)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GameSizeQuery {
+    game_size: Option<String>,
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for GameSizeFilter {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let param = Query::<GameSizeQuery>::try_from_uri(&parts.uri)
+            .ok()
+            .and_then(|q| q.0.game_size)
+            .filter(|g| !g.trim().is_empty());
+        let points = match param.as_deref().map(str::trim) {
+            None => return Ok(Self::default()),
+            Some("any") | Some("all") => None,
+            Some(g) => Some(g.parse::<u32>().map_err(|_| {
+                ApiError::BadRequest(format!(
+                    "Invalid game_size: {} (expected a points limit such as 2000, or any)",
+                    g
+                ))
+            })?),
+        };
+        Ok(Self { points })
+    }
+}
+
+/// Confidence handling from the `min_confidence` and `weighted` query
+/// params: rows extracted below `min_confidence` are dropped, and with
+/// `weighted=true` endpoints that support it count the remaining rows by
+/// [`Confidence::weight`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ConfidenceFilter {
+    pub min: Option<Confidence>,
+    pub weighted: bool,
+}
+
+impl ConfidenceFilter {
+    pub fn keeps(&self, confidence: Confidence) -> bool {
+        self.min.is_none_or(|min| confidence.meets(min))
+    }
+
+    /// Weight of a row: its confidence weight when weighting, else 1.
+    pub fn weight(&self, confidence: Confidence) -> f64 {
+        if self.weighted {
+            confidence.weight()
+        } else {
+            1.0
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ConfidenceQuery {
+    min_confidence: Option<String>,
+    weighted: Option<bool>,
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ConfidenceFilter {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Ok(Query(query)) = Query::<ConfidenceQuery>::try_from_uri(&parts.uri) else {
+            return Ok(Self::default());
+        };
+        let min = match query
+            .min_confidence
+            .as_deref()
+            .filter(|c| !c.trim().is_empty())
+        {
+            Some(c) => Some(c.parse::<Confidence>().map_err(ApiError::BadRequest)?),
+            None => None,
+        };
+        Ok(Self {
+            min,
+            weighted: query.weighted.unwrap_or(false),
+        })
+    }
+}
+
 /// API error types.
 #[derive(Debug, Error)]
 pub enum ApiError {
@@ -274,6 +739,23 @@ impl PaginationMeta {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_require_local_allows_without_cf_header() {
+        let headers = HeaderMap::new();
+        assert!(require_local(&headers, "Refresh").is_ok());
+    }
+
+    #[test]
+    fn test_require_local_blocks_cf_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert("cf-connecting-ip", "1.2.3.4".parse().unwrap());
+        let err = require_local(&headers, "Geo lookup").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Forbidden: Geo lookup is only available on localhost"
+        );
+    }
+
     #[test]
     fn test_pagination_default() {
         let p = Pagination::default();
@@ -358,6 +840,38 @@ mod tests {
         assert_eq!(deduped[1].val, 2);
     }
 
+    #[test]
+    fn test_merge_by_id_keeps_newest() {
+        let placement = |rank: u32| {
+            crate::models::Placement::new(
+                "event".into(),
+                "current".into(),
+                rank,
+                format!("Player {}", rank),
+                "Orks".to_string(),
+            )
+        };
+        let first = placement(1);
+        let mut fixed = first.clone();
+        fixed.faction = "Aeldari".to_string();
+        fixed.set_updated_at(first.created_at + chrono::Duration::minutes(1));
+        let other = placement(2);
+
+        // A fix read before the stale copy, or after it, wins either way
+        for records in [
+            vec![first.clone(), other.clone(), fixed.clone()],
+            vec![fixed.clone(), other.clone(), first.clone()],
+        ] {
+            let merged = merge_by_id(records, |p| p.id.as_str());
+            assert_eq!(merged.len(), 2);
+            assert_eq!(merged[0].faction, "Aeldari");
+            assert_eq!(merged[1].id, other.id);
+        }
+
+        let index = crate::storage::EntityIndex::build(vec![first.clone(), fixed]);
+        assert_eq!(index.get(first.id.as_str()).unwrap().faction, "Aeldari");
+    }
+
     #[test]
     fn test_resolve_epoch_none_empty_mapper() {
         let mapper = crate::models::EpochMapper::new();
@@ -407,6 +921,80 @@ mod tests {
         assert!(err.is_err());
     }
 
+    fn epoch_state(default_epoch: Option<&str>) -> AppState {
+        use crate::models::{SignificantEvent, SignificantEventType};
+        let event = SignificantEvent::new(
+            SignificantEventType::BalanceUpdate,
+            chrono::NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            "Test Balance".to_string(),
+            "https://example.com".to_string(),
+        );
+        AppState {
+            storage: std::sync::Arc::new(crate::storage::StorageConfig::new("/tmp".into())),
+            epoch_mapper: std::sync::Arc::new(tokio::sync::RwLock::new(
+                crate::models::EpochMapper::from_significant_events(&[event]),
+            )),
+            refresh_state: Default::default(),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: default_epoch.map(str::to_string),
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
+            ai_backend: std::sync::Arc::new(crate::agents::backend::MockBackend::new("{}")),
+            traffic_stats: Default::default(),
+        }
+    }
+
+    async fn extract_epoch(
+        state: &AppState,
+        uri: &str,
+        header: Option<&str>,
+    ) -> Result<EpochParam, ApiError> {
+        let mut builder = axum::http::Request::builder().uri(uri);
+        if let Some(h) = header {
+            builder = builder.header(EPOCH_HEADER, h);
+        }
+        let (mut parts, _) = builder.body(()).unwrap().into_parts();
+        EpochParam::from_request_parts(&mut parts, state).await
+    }
+
+    #[tokio::test]
+    async fn test_epoch_param_precedence() {
+        let state = epoch_state(Some("all"));
+        let epoch_id = state.epoch_mapper.read().await.all_epochs()[0]
+            .id
+            .as_str()
+            .to_string();
+
+        let from_query = extract_epoch(&state, "/x?epoch=current", Some(&epoch_id))
+            .await
+            .unwrap();
+        assert_eq!(from_query.as_deref(), Some("current"));
+
+        let from_header = extract_epoch(&state, "/x?limit=5", Some(&epoch_id))
+            .await
+            .unwrap();
+        assert_eq!(from_header.as_deref(), Some(epoch_id.as_str()));
+
+        let from_default = extract_epoch(&state, "/x", None).await.unwrap();
+        assert_eq!(from_default.as_deref(), Some("all"));
+
+        let unset = extract_epoch(&epoch_state(None), "/x", None).await.unwrap();
+        assert!(unset.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_epoch_param_rejects_unknown_epoch() {
+        let state = epoch_state(None);
+        let err = extract_epoch(&state, "/x", Some("nonexistent"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApiError::NotFound(_)));
+    }
+
     #[test]
     fn test_api_error_not_found() {
         use axum::response::IntoResponse;
@@ -1,24 +1,45 @@
 use std::collections::{HashMap, HashSet};
 
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::Json;
 use serde::{Deserialize, Serialize};
 
 use crate::api::state::AppState;
-use crate::api::{dedup_by_id, ApiError};
-use crate::models::{ArmyList, Event, Pairing, Placement};
-use crate::storage::{self, EntityType, JsonlReader};
+use crate::api::{
+    merge_by_id, ApiError, ConfidenceFilter, EpochParam, GameSizeFilter, GeoFilter, SizeFilter,
+};
+use crate::calculate::archetypes::{faction_archetypes, FactionArchetypes};
+use crate::calculate::combos::{faction_combos, FactionCombos};
+use crate::calculate::derive::{
+    compute_placement_curves, load_faction_stats, load_tiers, PlacementCurve, DERIVED_GAME_SIZE,
+    TIER_LIST_MIN_GAMES,
+};
+use crate::calculate::detachments;
+use crate::calculate::enhancements::{self, EnhancementStat};
+use crate::calculate::epoch_diff::{align_faction_stats, biggest_movers, FactionDelta};
+use crate::calculate::matchups::{
+    faction_matchups, matchup_matrix as calculate_matchup_matrix, MatchupMatrix, MATRIX_MIN_GAMES,
+};
+use crate::calculate::quality::{self, EpochQuality, EventQuality};
+use crate::calculate::ratings::{
+    compute_ratings_from_storage, read_ratings, FactionAverage, PlayerRating, RatingConfig,
+};
+use crate::calculate::shifts::{
+    compute_shifts_from_storage, read_shifts, ShiftCandidate, ShiftConfig,
+};
+use crate::calculate::sos::{strength_of_schedule, FactionSchedule, PlayerSchedule};
+use crate::calculate::tiers::FactionTier;
+use crate::calculate::unit_performance::{all_unit_performance, UnitPerformance};
+use crate::calculate::{win_rate_ci, IntervalMethod};
+use crate::models::{
+    faction_allegiance, normalize_faction_name, ArmyList, Event, Pairing, Placement, SizeBracket,
+    Tier,
+};
+use crate::storage::{self, EntityType, IndexedReader};
 use crate::sync::normalize_player_name;
 
-use super::events::{faction_allegiance, normalize_faction_name};
-
 // ── Overview Endpoint ───────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
-pub struct OverviewParams {
-    pub epoch: Option<String>,
-}
-
 #[derive(Debug, Serialize)]
 pub struct FactionHighlight {
     pub name: String,
@@ -47,25 +68,39 @@ pub struct OverviewResponse {
     pub date_range: Option<DateRange>,
     pub most_popular_faction: Option<FactionHighlight>,
     pub highest_win_rate_faction: Option<WinRateHighlight>,
+    /// Events and placements per size bracket (events without a player
+    /// count are left out)
+    pub size_brackets: Vec<SizeBracketBreakdown>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SizeBracketBreakdown {
+    pub bracket: SizeBracket,
+    pub min_players: u32,
+    pub events: u32,
+    pub placements: u32,
 }
 
 pub async fn overview(
     State(state): State<AppState>,
-    Query(params): Query<OverviewParams>,
+    epoch: EpochParam,
+    geo: GeoFilter,
+    size: SizeFilter,
+    game_size: GameSizeFilter,
+    confidence: ConfidenceFilter,
 ) -> Result<Json<OverviewResponse>, ApiError> {
     let mapper = state.epoch_mapper.read().await;
     let epochs = mapper.all_epochs();
 
     // Determine which epochs to scan
-    let epoch_ids: Vec<String> = if params.epoch.as_deref() == Some("all") || params.epoch.is_none()
-    {
+    let epoch_ids: Vec<String> = if epoch.as_deref() == Some("all") || epoch.is_none() {
         if epochs.is_empty() {
             vec!["current".to_string()]
         } else {
             epochs.iter().map(|e| e.id.as_str().to_string()).collect()
         }
     } else {
-        vec![crate::api::resolve_epoch(params.epoch.as_deref(), &mapper)?]
+        vec![crate::api::resolve_epoch(epoch.as_deref(), &mapper)?]
     };
 
     let mut all_placements: Vec<Placement> = Vec::new();
@@ -73,19 +108,37 @@ pub async fn overview(
 
     for epoch_id in &epoch_ids {
         let event_reader =
-            JsonlReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id);
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id);
         if let Ok(events) = event_reader.read_all() {
             all_events.extend(events);
         }
         let placement_reader =
-            JsonlReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id);
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id);
         if let Ok(placements) = placement_reader.read_all() {
             all_placements.extend(placements);
         }
     }
 
-    all_events = dedup_by_id(all_events, |e| e.id.as_str());
-    all_placements = dedup_by_id(all_placements, |p| p.id.as_str());
+    all_events = merge_by_id(all_events, |e| e.id.as_str());
+    state.storage.overrides.retain(&mut all_events);
+    all_placements = merge_by_id(all_placements, |p| p.id.as_str());
+    state.storage.overrides.retain(&mut all_placements);
+    let off_size: HashSet<String> = all_events
+        .iter()
+        .filter(|e| !game_size.matches(e))
+        .map(|e| e.id.as_str().to_string())
+        .collect();
+    all_events.retain(|e| game_size.matches(e));
+    all_placements.retain(|p| !off_size.contains(p.event_id.as_str()));
+    if geo.is_active() || size.is_active() || confidence.min.is_some() {
+        all_events.retain(|e| {
+            geo.matches(e) && size.matches(e) && confidence.keeps(e.extraction_confidence)
+        });
+        let ids: HashSet<&str> = all_events.iter().map(|e| e.id.as_str()).collect();
+        all_placements.retain(|p| {
+            ids.contains(p.event_id.as_str()) && confidence.keeps(p.extraction_confidence)
+        });
+    }
 
     let total_events = all_events.len() as u32;
     let total_placements = all_placements.len() as u32;
@@ -149,6 +202,34 @@ pub async fn overview(
             min_count: count,
         });
 
+    // Size bracket breakdown
+    let thresholds = state.size_thresholds;
+    let mut bracket_counts: HashMap<SizeBracket, (u32, u32)> = HashMap::new();
+    let mut event_brackets: HashMap<&str, SizeBracket> = HashMap::new();
+    for e in &all_events {
+        if let Some(bracket) = e.size_bracket(&thresholds) {
+            bracket_counts.entry(bracket).or_default().0 += 1;
+            event_brackets.insert(e.id.as_str(), bracket);
+        }
+    }
+    for p in &all_placements {
+        if let Some(bracket) = event_brackets.get(p.event_id.as_str()) {
+            bracket_counts.entry(*bracket).or_default().1 += 1;
+        }
+    }
+    let size_brackets = SizeBracket::ALL
+        .iter()
+        .map(|&bracket| {
+            let (events, placements) = bracket_counts.get(&bracket).copied().unwrap_or_default();
+            SizeBracketBreakdown {
+                bracket,
+                min_players: thresholds.min_players(bracket),
+                events,
+                placements,
+            }
+        })
+        .collect();
+
     Ok(Json(OverviewResponse {
         total_events,
         total_placements,
@@ -157,14 +238,58 @@ pub async fn overview(
         date_range,
         most_popular_faction,
         highest_win_rate_faction,
+        size_brackets,
     }))
 }
 
+// ── Data Quality Endpoint ───────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct QualityResponse {
+    pub epochs: Vec<EpochQuality>,
+    /// Events across the requested epochs, worst first
+    pub events: Vec<EventQuality>,
+}
+
+/// Data quality scores per epoch and per event.
+pub async fn quality(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+) -> Result<Json<QualityResponse>, ApiError> {
+    let mapper = state.epoch_mapper.read().await;
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), mapper.all_epochs(), &mapper)?;
+
+    let mut epochs = Vec::new();
+    let mut events = Vec::new();
+    for epoch_id in &epoch_ids {
+        let mut epoch_events =
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id)
+                .read_all()
+                .unwrap_or_default();
+        state.storage.overrides.retain(&mut epoch_events);
+        let (placements, lists) = load_placements_and_lists(&state, std::slice::from_ref(epoch_id));
+        let (epoch_quality, event_quality) =
+            quality::epoch_quality(epoch_id, &epoch_events, &placements, &lists);
+        epochs.push(epoch_quality);
+        events.extend(event_quality);
+    }
+    events.sort_by(|a, b| {
+        a.score
+            .partial_cmp(&b.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(Json(QualityResponse { epochs, events }))
+}
+
 // ── Trends Endpoint ─────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
 pub struct TrendsParams {
     pub factions: Option<String>,
+    /// Only count events with at least this many players
+    pub min_players: Option<u32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -196,15 +321,39 @@ pub struct BalancePassMarker {
     pub id: String,
 }
 
+/// FAQ/errata drop shown as a secondary marker (doesn't start an epoch).
+#[derive(Debug, Clone, Serialize)]
+pub struct FaqMarker {
+    pub date: String,
+    pub title: String,
+    pub id: String,
+    pub affected_factions: Vec<String>,
+}
+
+impl FaqMarker {
+    pub fn from_event(e: &crate::models::SignificantEvent) -> Self {
+        Self {
+            date: e.effective_from().to_string(),
+            title: e.title.clone(),
+            id: e.id.as_str().to_string(),
+            affected_factions: e.affected_factions.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct TrendsResponse {
     pub epochs: Vec<TrendEpoch>,
     pub factions: Vec<FactionTrend>,
     pub balance_passes: Vec<BalancePassMarker>,
+    pub faq_updates: Vec<FaqMarker>,
 }
 
 pub async fn faction_trends(
     State(state): State<AppState>,
+    geo: GeoFilter,
+    size: SizeFilter,
+    game_size: GameSizeFilter,
     Query(params): Query<TrendsParams>,
 ) -> Result<Json<TrendsResponse>, ApiError> {
     let mapper = state.epoch_mapper.read().await;
@@ -215,6 +364,7 @@ pub async fn faction_trends(
             epochs: vec![],
             factions: vec![],
             balance_passes: vec![],
+            faq_updates: vec![],
         }));
     }
 
@@ -226,6 +376,8 @@ pub async fn faction_trends(
             .collect()
     });
 
+    let min_players = params.min_players.unwrap_or(0);
+
     // Collect per-epoch stats
     let mut epoch_infos: Vec<TrendEpoch> = Vec::new();
     // faction -> epoch_id -> (count, wins)
@@ -242,9 +394,18 @@ pub async fn faction_trends(
         });
 
         let reader =
-            JsonlReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id);
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id);
         let placements = reader.read_all().unwrap_or_default();
-        let placements = dedup_by_id(placements, |p| p.id.as_str());
+        let mut placements = merge_by_id(placements, |p| p.id.as_str());
+        state.storage.overrides.retain(&mut placements);
+        let off_size = off_size_event_ids(&state, &[epoch_id.to_string()], &game_size);
+        placements.retain(|p| !off_size.contains(p.event_id.as_str()));
+        if geo.is_active() || size.is_active() || min_players > 0 {
+            let ids = matching_event_ids(&state, &[epoch_id.to_string()], |e| {
+                geo.matches(e) && size.matches(e) && e.player_count.unwrap_or(0) >= min_players
+            });
+            placements.retain(|p| ids.contains(p.event_id.as_str()));
+        }
 
         // Group by faction
         let mut epoch_faction_map: HashMap<String, (u32, u32)> = HashMap::new();
@@ -289,8 +450,9 @@ pub async fn faction_trends(
     for epoch in epochs {
         let epoch_id = epoch.id.as_str();
         let reader =
-            JsonlReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id);
-        let placements = reader.read_all().unwrap_or_default();
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id);
+        let mut placements = reader.read_all().unwrap_or_default();
+        state.storage.overrides.retain(&mut placements);
         epoch_totals.insert(epoch_id.to_string(), placements.len() as u32);
     }
 
@@ -334,7 +496,7 @@ pub async fn faction_trends(
     }
 
     // Balance passes
-    let sig_events = storage::read_significant_events(&state.storage).unwrap_or_default();
+    let sig_events = storage::cached_significant_events(&state.storage).unwrap_or_default();
     let balance_passes: Vec<BalancePassMarker> = sig_events
         .iter()
         .filter(|e| e.event_type == crate::models::SignificantEventType::BalanceUpdate)
@@ -344,11 +506,17 @@ pub async fn faction_trends(
             id: e.id.as_str().to_string(),
         })
         .collect();
+    let faq_updates: Vec<FaqMarker> = sig_events
+        .iter()
+        .filter(|e| e.event_type == crate::models::SignificantEventType::FaqUpdate)
+        .map(FaqMarker::from_event)
+        .collect();
 
     Ok(Json(TrendsResponse {
         epochs: epoch_infos,
         factions: faction_trends,
         balance_passes,
+        faq_updates,
     }))
 }
 
@@ -356,7 +524,6 @@ pub async fn faction_trends(
 
 #[derive(Debug, Deserialize)]
 pub struct PlayersParams {
-    pub epoch: Option<String>,
     pub min_events: Option<u32>,
     pub limit: Option<u32>,
 }
@@ -379,6 +546,13 @@ pub struct PlayerSummary {
     pub top4_rate: f64,
     pub primary_faction: String,
     pub recent_results: Vec<RecentResult>,
+    /// Games with a result in the pairings
+    pub games: u32,
+    /// Mean win rate of the opponents faced, in percent
+    pub strength_of_schedule: Option<f64>,
+    /// Game win rate weighted by the opponent's standing going into each
+    /// round, in percent
+    pub weighted_win_rate: Option<f64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -389,21 +563,22 @@ pub struct PlayersResponse {
 
 pub async fn top_players(
     State(state): State<AppState>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
     Query(params): Query<PlayersParams>,
 ) -> Result<Json<PlayersResponse>, ApiError> {
     let mapper = state.epoch_mapper.read().await;
     let epochs = mapper.all_epochs();
 
     // Determine which epochs to scan
-    let epoch_ids: Vec<String> = if params.epoch.as_deref() == Some("all") || params.epoch.is_none()
-    {
+    let epoch_ids: Vec<String> = if epoch.as_deref() == Some("all") || epoch.is_none() {
         if epochs.is_empty() {
             vec!["current".to_string()]
         } else {
             epochs.iter().map(|e| e.id.as_str().to_string()).collect()
         }
     } else {
-        vec![crate::api::resolve_epoch(params.epoch.as_deref(), &mapper)?]
+        vec![crate::api::resolve_epoch(epoch.as_deref(), &mapper)?]
     };
 
     let mut all_placements: Vec<Placement> = Vec::new();
@@ -411,19 +586,37 @@ pub async fn top_players(
 
     for epoch_id in &epoch_ids {
         let event_reader =
-            JsonlReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id);
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id);
         if let Ok(events) = event_reader.read_all() {
             all_events.extend(events);
         }
         let placement_reader =
-            JsonlReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id);
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id);
         if let Ok(placements) = placement_reader.read_all() {
             all_placements.extend(placements);
         }
     }
 
-    all_events = dedup_by_id(all_events, |e| e.id.as_str());
-    all_placements = dedup_by_id(all_placements, |p| p.id.as_str());
+    all_events = merge_by_id(all_events, |e| e.id.as_str());
+    state.storage.overrides.retain(&mut all_events);
+    all_placements = merge_by_id(all_placements, |p| p.id.as_str());
+    state.storage.overrides.retain(&mut all_placements);
+    let off_size: HashSet<String> = all_events
+        .iter()
+        .filter(|e| !game_size.matches(e))
+        .map(|e| e.id.as_str().to_string())
+        .collect();
+    all_events.retain(|e| game_size.matches(e));
+    all_placements.retain(|p| !off_size.contains(p.event_id.as_str()));
+    let mut pairings = load_pairings(&state, &epoch_ids);
+    pairings.retain(|p| !off_size.contains(p.event_id.as_str()));
+
+    let schedules: HashMap<String, PlayerSchedule> =
+        strength_of_schedule(&pairings, &all_placements, &[])
+            .players
+            .into_iter()
+            .map(|p| (normalize_player_name(&p.player), p))
+            .collect();
 
     // Build event lookup
     let event_map: HashMap<String, &Event> = all_events
@@ -466,7 +659,7 @@ pub async fn top_players(
     // Count unique events per player
     let mut player_summaries: Vec<PlayerSummary> = player_map
         .into_iter()
-        .filter_map(|(_, data)| {
+        .filter_map(|(key, data)| {
             let unique_events: std::collections::HashSet<&str> = data
                 .placements
                 .iter()
@@ -529,6 +722,7 @@ pub async fn top_players(
                 })
                 .collect();
 
+            let schedule = schedules.get(&key);
             Some(PlayerSummary {
                 name: data.display_name,
                 total_events,
@@ -538,6 +732,9 @@ pub async fn top_players(
                 top4_rate: (top4_rate * 10.0).round() / 10.0,
                 primary_faction,
                 recent_results,
+                games: schedule.map_or(0, |s| s.stats.games),
+                strength_of_schedule: schedule.and_then(|s| s.stats.strength_of_schedule),
+                weighted_win_rate: schedule.and_then(|s| s.stats.weighted_win_rate),
             })
         })
         .collect();
@@ -557,11 +754,150 @@ pub async fn top_players(
     }))
 }
 
+// ── Teams Endpoint ──────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct TeamsParams {
+    /// `top4` (default), `wins` (event wins) or `win_rate`
+    pub sort: Option<String>,
+    /// Distinct players a team needs to be listed (default 2)
+    pub min_players: Option<u32>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TeamSummary {
+    pub team: String,
+    pub players: u32,
+    pub events: u32,
+    /// Placements by team members
+    pub entries: u32,
+    pub event_wins: u32,
+    pub top4: u32,
+    #[serde(flatten)]
+    pub record: detachments::GameRecord,
+    pub win_rate: Option<f64>,
+    pub factions: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TeamsResponse {
+    pub teams: Vec<TeamSummary>,
+    pub total_teams: u32,
+}
+
+/// Keys from most to least counted, ties by name.
+fn by_frequency<K: AsRef<str> + Ord>(counts: HashMap<K, u32>) -> Vec<String> {
+    let mut counts: Vec<(K, u32)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+        .into_iter()
+        .map(|(key, _)| key.as_ref().to_string())
+        .collect()
+}
+
+/// Teams ranked by their members' results across events.
+pub async fn teams(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
+    Query(params): Query<TeamsParams>,
+) -> Result<Json<TeamsResponse>, ApiError> {
+    let sort = params.sort.as_deref().unwrap_or("top4");
+    if !matches!(sort, "top4" | "wins" | "win_rate") {
+        return Err(ApiError::BadRequest(format!(
+            "Unknown sort: {} (expected top4, wins or win_rate)",
+            sort
+        )));
+    }
+    let min_players = params.min_players.unwrap_or(2);
+
+    let mapper = state.epoch_mapper.read().await;
+    let epochs = mapper.all_epochs();
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
+    let (mut placements, _) = load_placements_and_lists(&state, &epoch_ids);
+    let off_size = off_size_event_ids(&state, &epoch_ids, &game_size);
+    placements.retain(|p| !off_size.contains(p.event_id.as_str()));
+
+    #[derive(Default)]
+    struct TeamAgg<'a> {
+        /// Spellings seen, with how often
+        names: HashMap<&'a str, u32>,
+        players: HashSet<String>,
+        events: HashSet<&'a str>,
+        entries: u32,
+        event_wins: u32,
+        top4: u32,
+        record: detachments::GameRecord,
+        factions: HashMap<String, u32>,
+    }
+
+    // Teams are matched case-insensitively
+    let mut by_team: HashMap<String, TeamAgg> = HashMap::new();
+    for p in &placements {
+        let Some(team) = p.team.as_deref() else {
+            continue;
+        };
+        let agg = by_team.entry(team.to_lowercase()).or_default();
+        *agg.names.entry(team).or_default() += 1;
+        agg.players.insert(normalize_player_name(&p.player_name));
+        agg.events.insert(p.event_id.as_str());
+        agg.entries += 1;
+        agg.event_wins += p.is_winner() as u32;
+        agg.top4 += p.is_podium() as u32;
+        if let Some(r) = &p.record {
+            agg.record.add(&detachments::GameRecord {
+                wins: r.wins,
+                losses: r.losses,
+                draws: r.draws,
+            });
+        }
+        *agg.factions
+            .entry(normalize_faction_name(&p.faction))
+            .or_default() += 1;
+    }
+
+    let total_teams = by_team.len() as u32;
+    let mut teams: Vec<TeamSummary> = by_team
+        .into_values()
+        .filter(|agg| agg.players.len() as u32 >= min_players)
+        .map(|agg| TeamSummary {
+            team: by_frequency(agg.names).swap_remove(0),
+            players: agg.players.len() as u32,
+            events: agg.events.len() as u32,
+            entries: agg.entries,
+            event_wins: agg.event_wins,
+            top4: agg.top4,
+            win_rate: agg.record.win_rate(),
+            record: agg.record,
+            factions: by_frequency(agg.factions),
+        })
+        .collect();
+
+    teams.sort_by(|a, b| {
+        let by_win_rate = || {
+            b.win_rate
+                .partial_cmp(&a.win_rate)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        };
+        let primary = match sort {
+            "wins" => b.event_wins.cmp(&a.event_wins).then(b.top4.cmp(&a.top4)),
+            "win_rate" => by_win_rate(),
+            _ => b.top4.cmp(&a.top4).then(b.event_wins.cmp(&a.event_wins)),
+        };
+        primary
+            .then_with(by_win_rate)
+            .then_with(|| a.team.cmp(&b.team))
+    });
+    teams.truncate(params.limit.unwrap_or(50));
+
+    Ok(Json(TeamsResponse { teams, total_teams }))
+}
+
 // ── Units Endpoint ──────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
 pub struct UnitsParams {
-    pub epoch: Option<String>,
     pub faction: Option<String>,
     pub limit: Option<u32>,
 }
@@ -593,32 +929,50 @@ pub struct UnitsResponse {
 
 pub async fn top_units(
     State(state): State<AppState>,
+    epoch: EpochParam,
+    geo: GeoFilter,
+    game_size: GameSizeFilter,
+    confidence: ConfidenceFilter,
     Query(params): Query<UnitsParams>,
 ) -> Result<Json<UnitsResponse>, ApiError> {
     let mapper = state.epoch_mapper.read().await;
     let epochs = mapper.all_epochs();
 
-    let epoch_ids: Vec<String> = if params.epoch.as_deref() == Some("all") || params.epoch.is_none()
-    {
+    let epoch_ids: Vec<String> = if epoch.as_deref() == Some("all") || epoch.is_none() {
         if epochs.is_empty() {
             vec!["current".to_string()]
         } else {
             epochs.iter().map(|e| e.id.as_str().to_string()).collect()
         }
     } else {
-        vec![crate::api::resolve_epoch(params.epoch.as_deref(), &mapper)?]
+        vec![crate::api::resolve_epoch(epoch.as_deref(), &mapper)?]
     };
 
     let mut all_lists: Vec<ArmyList> = Vec::new();
     for epoch_id in &epoch_ids {
         let reader =
-            JsonlReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, epoch_id);
+            IndexedReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, epoch_id);
         if let Ok(lists) = reader.read_all() {
             all_lists.extend(lists);
         }
     }
 
-    all_lists = dedup_by_id(all_lists, |l| l.id.as_str());
+    all_lists = merge_by_id(all_lists, |l| l.id.as_str());
+    state.storage.overrides.retain(&mut all_lists);
+    let off_size = off_size_event_ids(&state, &epoch_ids, &game_size);
+    all_lists.retain(|l| {
+        l.event_id
+            .as_ref()
+            .is_none_or(|e| !off_size.contains(e.as_str()))
+    });
+    if let Some(ids) = geo_event_ids(&state, &epoch_ids, &geo) {
+        all_lists.retain(|l| {
+            l.event_id
+                .as_ref()
+                .is_some_and(|e| ids.contains(e.as_str()))
+        });
+    }
+    all_lists.retain(|l| confidence.keeps(l.extraction_confidence));
 
     // Optional faction filter
     if let Some(ref faction_filter) = params.faction {
@@ -773,7 +1127,7 @@ pub async fn top_units(
 
 /// Join army lists to placements via list_id first, then fallback to
 /// (event_id, normalized player name).
-fn join_lists_to_placements(
+pub(crate) fn join_lists_to_placements(
     lists: &[ArmyList],
     placements: &[Placement],
 ) -> Vec<(ArmyList, Placement)> {
@@ -833,27 +1187,93 @@ fn load_placements_and_lists(
 
     for epoch_id in epoch_ids {
         if let Ok(placements) =
-            JsonlReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id)
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id)
                 .read_all()
         {
             all_placements.extend(placements);
         }
         if let Ok(lists) =
-            JsonlReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, epoch_id)
+            IndexedReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, epoch_id)
                 .read_all()
         {
             all_lists.extend(lists);
         }
     }
 
-    all_placements = dedup_by_id(all_placements, |p| p.id.as_str());
-    all_lists = dedup_by_id(all_lists, |l| l.id.as_str());
+    all_placements = merge_by_id(all_placements, |p| p.id.as_str());
+    state.storage.overrides.retain(&mut all_placements);
+    all_lists = merge_by_id(all_lists, |l| l.id.as_str());
+    state.storage.overrides.retain(&mut all_lists);
 
     (all_placements, all_lists)
 }
 
+/// IDs of the events in these epochs that `keep` accepts.
+fn matching_event_ids(
+    state: &AppState,
+    epoch_ids: &[String],
+    keep: impl Fn(&Event) -> bool,
+) -> HashSet<String> {
+    let mut ids = HashSet::new();
+    for epoch_id in epoch_ids {
+        if let Ok(index) =
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id).index()
+        {
+            ids.extend(
+                index
+                    .all()
+                    .iter()
+                    .filter(|e| keep(e))
+                    .map(|e| e.id.as_str().to_string()),
+            );
+        }
+    }
+    ids
+}
+
+/// IDs of the events played at another points limit than `game_size`
+/// asks for. Records are dropped if their event is in the set, so records
+/// with no stored event are kept.
+fn off_size_event_ids(
+    state: &AppState,
+    epoch_ids: &[String],
+    game_size: &GameSizeFilter,
+) -> HashSet<String> {
+    if !game_size.is_active() {
+        return HashSet::new();
+    }
+    matching_event_ids(state, epoch_ids, |e| !game_size.matches(e))
+}
+
+/// IDs of the events passing a location filter, or `None` when the filter
+/// is inactive. Records are then kept only if their event is in the set.
+fn geo_event_ids(
+    state: &AppState,
+    epoch_ids: &[String],
+    geo: &GeoFilter,
+) -> Option<HashSet<String>> {
+    geo.is_active()
+        .then(|| matching_event_ids(state, epoch_ids, |e| geo.matches(e)))
+}
+
+/// Load pairings for a set of epochs, deduplicating.
+fn load_pairings(state: &AppState, epoch_ids: &[String]) -> Vec<Pairing> {
+    let mut all_pairings = Vec::new();
+    for epoch_id in epoch_ids {
+        if let Ok(pairings) =
+            IndexedReader::<Pairing>::for_entity(&state.storage, EntityType::Pairing, epoch_id)
+                .read_all()
+        {
+            all_pairings.extend(pairings);
+        }
+    }
+    let mut pairings = merge_by_id(all_pairings, |p| p.id.as_str());
+    state.storage.overrides.retain(&mut pairings);
+    pairings
+}
+
 /// Resolve epoch IDs from query params.
-fn resolve_epoch_ids(
+pub(crate) fn resolve_epoch_ids(
     epoch_param: Option<&str>,
     epochs: &[crate::models::MetaEpoch],
     mapper: &crate::models::EpochMapper,
@@ -869,13 +1289,48 @@ fn resolve_epoch_ids(
     }
 }
 
+/// `?interval=`: how win-rate confidence intervals are computed (default
+/// wilson).
+fn interval_method(param: Option<&str>) -> Result<IntervalMethod, ApiError> {
+    param.map_or(Ok(IntervalMethod::default()), |s| {
+        s.parse().map_err(ApiError::BadRequest)
+    })
+}
+
+/// Whether `?sort=` asks for the lower confidence bound rather than the
+/// endpoint's usual `default` order.
+fn sorts_by_ci_low(sort: Option<&str>, default: &str) -> Result<bool, ApiError> {
+    match sort {
+        None => Ok(false),
+        Some("ci_low") => Ok(true),
+        Some(s) if s == default => Ok(false),
+        Some(s) => Err(ApiError::BadRequest(format!(
+            "Unknown sort: {} (expected {} or ci_low)",
+            s, default
+        ))),
+    }
+}
+
+/// Highest lower confidence bound first; rows without games last.
+fn sort_by_ci_low<T>(rows: &mut [T], ci_low: impl Fn(&T) -> Option<f64>) {
+    rows.sort_by(|a, b| {
+        ci_low(b)
+            .unwrap_or(-1.0)
+            .partial_cmp(&ci_low(a).unwrap_or(-1.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+}
+
 // ── Detachments Endpoint ────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
 pub struct DetachmentParams {
-    pub epoch: Option<String>,
     pub faction: Option<String>,
     pub min_count: Option<u32>,
+    /// `avg_win_rate` (default) or `ci_low`
+    pub sort: Option<String>,
+    /// `wilson` (default) or `jeffreys`
+    pub interval: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -887,6 +1342,13 @@ pub struct DetachmentStat {
     pub avg_rank: f64,
     pub top4_count: u32,
     pub avg_battle_points: Option<f64>,
+    /// Games played by this detachment's players, from pairings
+    pub games: u32,
+    /// Win rate over those games (draws count half)
+    pub game_win_rate: Option<f64>,
+    /// 95% interval for `game_win_rate`
+    pub ci_low: Option<f64>,
+    pub ci_high: Option<f64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -896,15 +1358,51 @@ pub struct DetachmentResponse {
 
 pub async fn detachment_stats(
     State(state): State<AppState>,
+    epoch: EpochParam,
+    geo: GeoFilter,
+    game_size: GameSizeFilter,
+    confidence: ConfidenceFilter,
     Query(params): Query<DetachmentParams>,
 ) -> Result<Json<DetachmentResponse>, ApiError> {
+    let by_ci_low = sorts_by_ci_low(params.sort.as_deref(), "avg_win_rate")?;
+    let method = interval_method(params.interval.as_deref())?;
     let mapper = state.epoch_mapper.read().await;
     let epochs = mapper.all_epochs();
-    let epoch_ids = resolve_epoch_ids(params.epoch.as_deref(), epochs, &mapper)?;
-    let (placements, lists) = load_placements_and_lists(&state, &epoch_ids);
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
+    let (mut placements, mut lists) = load_placements_and_lists(&state, &epoch_ids);
+    let mut pairings = load_pairings(&state, &epoch_ids);
+    let off_size = off_size_event_ids(&state, &epoch_ids, &game_size);
+    placements.retain(|p| !off_size.contains(p.event_id.as_str()));
+    pairings.retain(|p| !off_size.contains(p.event_id.as_str()));
+    lists.retain(|l| {
+        l.event_id
+            .as_ref()
+            .is_none_or(|e| !off_size.contains(e.as_str()))
+    });
+    if let Some(ids) = geo_event_ids(&state, &epoch_ids, &geo) {
+        placements.retain(|p| ids.contains(p.event_id.as_str()));
+        lists.retain(|l| {
+            l.event_id
+                .as_ref()
+                .is_some_and(|e| ids.contains(e.as_str()))
+        });
+        pairings.retain(|p| ids.contains(p.event_id.as_str()));
+    }
+    placements.retain(|p| confidence.keeps(p.extraction_confidence));
+    lists.retain(|l| confidence.keeps(l.extraction_confidence));
 
     let joined = join_lists_to_placements(&lists, &placements);
 
+    // Game-level records summed across epochs
+    let entries = detachments::detachment_entries(&placements, &lists);
+    let mut game_records: HashMap<(String, String), detachments::GameRecord> = HashMap::new();
+    for ((_, faction, detachment), record) in detachments::game_records(&entries, &pairings) {
+        game_records
+            .entry((faction, detachment))
+            .or_default()
+            .add(&record);
+    }
+
     let min_count = params.min_count.unwrap_or(3);
     let faction_filter = params.faction.as_deref().map(normalize_faction_name);
 
@@ -1021,6 +1519,11 @@ pub async fn detachment_stats(
                         / 10.0,
                 )
             };
+            let record = game_records
+                .get(&(agg.faction.clone(), agg.detachment.clone()))
+                .copied()
+                .unwrap_or_default();
+            let ci = win_rate_ci(record.wins, record.draws, record.games(), method);
             DetachmentStat {
                 faction: agg.faction,
                 detachment: agg.detachment,
@@ -1029,26 +1532,135 @@ pub async fn detachment_stats(
                 avg_rank,
                 top4_count: agg.top4,
                 avg_battle_points,
+                games: record.games(),
+                game_win_rate: record.win_rate(),
+                ci_low: ci.map(|(low, _)| low),
+                ci_high: ci.map(|(_, high)| high),
             }
         })
         .collect();
 
-    detachments.sort_by(|a, b| {
-        b.avg_win_rate
-            .partial_cmp(&a.avg_win_rate)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    if by_ci_low {
+        sort_by_ci_low(&mut detachments, |d| d.ci_low);
+    } else {
+        detachments.sort_by(|a, b| {
+            b.avg_win_rate
+                .partial_cmp(&a.avg_win_rate)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
 
     Ok(Json(DetachmentResponse { detachments }))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct FactionDetachmentsParams {
+    /// Top lists per detachment (default 5)
+    pub top_lists: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FactionDetachmentsResponse {
+    pub faction: String,
+    /// Epochs covered, oldest first (the order of each `trend`)
+    pub epochs: Vec<String>,
+    pub players: u32,
+    pub detachments: Vec<detachments::DetachmentBreakdown>,
+}
+
+/// Per-faction detachment drilldown: meta share, game-level win rate, top
+/// lists and per-epoch trend for each detachment.
+pub async fn faction_detachments(
+    State(state): State<AppState>,
+    Path(faction): Path<String>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
+    Query(params): Query<FactionDetachmentsParams>,
+) -> Result<Json<FactionDetachmentsResponse>, ApiError> {
+    let mapper = state.epoch_mapper.read().await;
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), mapper.all_epochs(), &mapper)?;
+    let (mut placements, lists) = load_placements_and_lists(&state, &epoch_ids);
+    let mut pairings = load_pairings(&state, &epoch_ids);
+    let off_size = off_size_event_ids(&state, &epoch_ids, &game_size);
+    placements.retain(|p| !off_size.contains(p.event_id.as_str()));
+    pairings.retain(|p| !off_size.contains(p.event_id.as_str()));
+
+    let entries = detachments::detachment_entries(&placements, &lists);
+    let faction = normalize_faction_name(&faction);
+    if !entries.iter().any(|e| e.faction == faction) {
+        return Err(ApiError::NotFound(format!(
+            "No detachment data for faction '{}'",
+            faction
+        )));
+    }
+    let records = detachments::game_records(&entries, &pairings);
+    let breakdown = detachments::faction_breakdown(
+        &entries,
+        &records,
+        &faction,
+        &epoch_ids,
+        params.top_lists.unwrap_or(5),
+    );
+
+    Ok(Json(FactionDetachmentsResponse {
+        players: breakdown.iter().map(|d| d.players).sum(),
+        faction,
+        epochs: epoch_ids,
+        detachments: breakdown,
+    }))
+}
+
+// ── Enhancements Endpoint ───────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct EnhancementParams {
+    pub faction: Option<String>,
+    pub min_count: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EnhancementResponse {
+    pub enhancements: Vec<EnhancementStat>,
+}
+
+pub async fn enhancement_stats(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+    geo: GeoFilter,
+    game_size: GameSizeFilter,
+    confidence: ConfidenceFilter,
+    Query(params): Query<EnhancementParams>,
+) -> Result<Json<EnhancementResponse>, ApiError> {
+    let mapper = state.epoch_mapper.read().await;
+    let epochs = mapper.all_epochs();
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
+    let (mut placements, mut lists) = load_placements_and_lists(&state, &epoch_ids);
+    let off_size = off_size_event_ids(&state, &epoch_ids, &game_size);
+    placements.retain(|p| !off_size.contains(p.event_id.as_str()));
+    if let Some(ids) = geo_event_ids(&state, &epoch_ids, &geo) {
+        placements.retain(|p| ids.contains(p.event_id.as_str()));
+    }
+    placements.retain(|p| confidence.keeps(p.extraction_confidence));
+    lists.retain(|l| confidence.keeps(l.extraction_confidence));
+
+    let joined = join_lists_to_placements(&lists, &placements);
+    let min_count = params.min_count.unwrap_or(3);
+    let mut enhancements = enhancements::enhancement_stats(&joined, params.faction.as_deref());
+    enhancements.retain(|e| e.count >= min_count);
+
+    Ok(Json(EnhancementResponse { enhancements }))
+}
+
 // ── Unit Performance Endpoint ───────────────────────────────────
 
 #[derive(Debug, Deserialize)]
 pub struct UnitPerfParams {
-    pub epoch: Option<String>,
     pub faction: Option<String>,
     pub min_appearances: Option<u32>,
+    /// `overrepresentation` (default) or `ci_low`
+    pub sort: Option<String>,
+    /// `wilson` (default) or `jeffreys`
+    pub interval: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1063,6 +1675,18 @@ pub struct UnitPerfStat {
     pub overrepresentation: f64,
     pub avg_rank_when_present: f64,
     pub avg_win_rate_when_present: f64,
+    /// Games played by lists containing the unit, from pairings
+    pub games: u32,
+    /// Win rate over those games (draws count half)
+    pub game_win_rate: Option<f64>,
+    /// 95% interval for `game_win_rate`
+    pub ci_low: Option<f64>,
+    pub ci_high: Option<f64>,
+    /// Game win rate shrunk toward the faction's
+    pub shrunk_win_rate: Option<f64>,
+    pub faction_win_rate: Option<f64>,
+    /// Fewer games than `unit_performance::MIN_UNIT_GAMES`
+    pub low_sample: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -1074,12 +1698,42 @@ pub struct UnitPerfResponse {
 
 pub async fn unit_performance(
     State(state): State<AppState>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
     Query(params): Query<UnitPerfParams>,
 ) -> Result<Json<UnitPerfResponse>, ApiError> {
+    let by_ci_low = sorts_by_ci_low(params.sort.as_deref(), "overrepresentation")?;
+    let method = interval_method(params.interval.as_deref())?;
     let mapper = state.epoch_mapper.read().await;
     let epochs = mapper.all_epochs();
-    let epoch_ids = resolve_epoch_ids(params.epoch.as_deref(), epochs, &mapper)?;
-    let (placements, lists) = load_placements_and_lists(&state, &epoch_ids);
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
+    let (mut placements, mut lists) = load_placements_and_lists(&state, &epoch_ids);
+    let off_size = off_size_event_ids(&state, &epoch_ids, &game_size);
+    placements.retain(|p| !off_size.contains(p.event_id.as_str()));
+    lists.retain(|l| {
+        l.event_id
+            .as_ref()
+            .is_none_or(|e| !off_size.contains(e.as_str()))
+    });
+
+    // Game results: a single epoch is served from its derived artifact
+    let performance = match epoch_ids.as_slice() {
+        [epoch_id] => crate::calculate::derive::load_unit_performance(
+            &state.storage,
+            epoch_id,
+            game_size.points,
+        )
+        .map_err(|e| ApiError::Internal(e.to_string()))?,
+        _ => {
+            let mut pairings = load_pairings(&state, &epoch_ids);
+            pairings.retain(|p| !off_size.contains(p.event_id.as_str()));
+            all_unit_performance(&placements, &lists, &pairings)
+        }
+    };
+    let performance: HashMap<(&str, &str), &UnitPerformance> = performance
+        .iter()
+        .map(|p| ((p.faction.as_str(), p.unit.as_str()), p))
+        .collect();
 
     let faction_filter = params.faction.as_deref().map(normalize_faction_name);
     let min_appearances = params.min_appearances.unwrap_or(3);
@@ -1189,7 +1843,17 @@ pub async fn unit_performance(
                     / 10.0
             };
 
+            let games = performance.get(&(agg.faction.as_str(), name.as_str()));
+            let ci = games
+                .and_then(|g| win_rate_ci(g.record.wins, g.record.draws, g.record.games(), method));
             UnitPerfStat {
+                games: games.map_or(0, |g| g.games),
+                game_win_rate: games.and_then(|g| g.win_rate),
+                ci_low: ci.map(|(low, _)| low),
+                ci_high: ci.map(|(_, high)| high),
+                shrunk_win_rate: games.and_then(|g| g.shrunk_win_rate),
+                faction_win_rate: games.and_then(|g| g.faction_win_rate),
+                low_sample: games.is_none_or(|g| g.low_sample),
                 name,
                 faction: agg.faction,
                 total_lists: agg.total,
@@ -1204,11 +1868,15 @@ pub async fn unit_performance(
         })
         .collect();
 
-    units.sort_by(|a, b| {
-        b.overrepresentation
-            .partial_cmp(&a.overrepresentation)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    if by_ci_low {
+        sort_by_ci_low(&mut units, |u| u.ci_low);
+    } else {
+        units.sort_by(|a, b| {
+            b.overrepresentation
+                .partial_cmp(&a.overrepresentation)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
 
     Ok(Json(UnitPerfResponse {
         units,
@@ -1221,7 +1889,6 @@ pub async fn unit_performance(
 
 #[derive(Debug, Deserialize)]
 pub struct PointsEffParams {
-    pub epoch: Option<String>,
     pub faction: Option<String>,
 }
 
@@ -1242,12 +1909,16 @@ pub struct PointsEffResponse {
 
 pub async fn points_efficiency(
     State(state): State<AppState>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
     Query(params): Query<PointsEffParams>,
 ) -> Result<Json<PointsEffResponse>, ApiError> {
     let mapper = state.epoch_mapper.read().await;
     let epochs = mapper.all_epochs();
-    let epoch_ids = resolve_epoch_ids(params.epoch.as_deref(), epochs, &mapper)?;
-    let (placements, lists) = load_placements_and_lists(&state, &epoch_ids);
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
+    let (mut placements, lists) = load_placements_and_lists(&state, &epoch_ids);
+    let off_size = off_size_event_ids(&state, &epoch_ids, &game_size);
+    placements.retain(|p| !off_size.contains(p.event_id.as_str()));
 
     let faction_filter = params.faction.as_deref().map(normalize_faction_name);
 
@@ -1329,7 +2000,6 @@ pub async fn points_efficiency(
 
 #[derive(Debug, Deserialize)]
 pub struct MatchupsParams {
-    pub epoch: Option<String>,
     pub min_games: Option<u32>,
 }
 
@@ -1352,398 +2022,449 @@ pub struct MatchupsResponse {
 
 pub async fn matchups(
     State(state): State<AppState>,
+    epoch: EpochParam,
+    geo: GeoFilter,
+    game_size: GameSizeFilter,
     Query(params): Query<MatchupsParams>,
 ) -> Result<Json<MatchupsResponse>, ApiError> {
     let mapper = state.epoch_mapper.read().await;
     let epochs = mapper.all_epochs();
-    let epoch_ids = resolve_epoch_ids(params.epoch.as_deref(), epochs, &mapper)?;
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
 
     let min_games = params.min_games.unwrap_or(5);
 
-    // Load pairings
-    let mut all_pairings: Vec<Pairing> = Vec::new();
-    for epoch_id in &epoch_ids {
-        if let Ok(pairings) =
-            JsonlReader::<Pairing>::for_entity(&state.storage, EntityType::Pairing, epoch_id)
-                .read_all()
-        {
-            all_pairings.extend(pairings);
-        }
-    }
-    all_pairings = dedup_by_id(all_pairings, |p| p.id.as_str());
-
-    // Group by (faction1, faction2) — normalize ordering so faction1 < faction2
-    struct MatchupAgg {
-        faction1: String,
-        faction2: String,
-        faction1_wins: u32,
-        faction2_wins: u32,
-        draws: u32,
+    let mut pairings = load_pairings(&state, &epoch_ids);
+    let off_size = off_size_event_ids(&state, &epoch_ids, &game_size);
+    pairings.retain(|p| !off_size.contains(p.event_id.as_str()));
+    if let Some(ids) = geo_event_ids(&state, &epoch_ids, &geo) {
+        pairings.retain(|p| ids.contains(p.event_id.as_str()));
     }
+    let (placements, lists) = load_placements_and_lists(&state, &epoch_ids);
+    let records = faction_matchups(&pairings, &placements, &lists);
 
-    let mut matchup_map: HashMap<(String, String), MatchupAgg> = HashMap::new();
-    let mut all_factions: HashSet<String> = HashSet::new();
-
-    for pairing in &all_pairings {
-        let f1 = match &pairing.player1_faction {
-            Some(f) if !f.is_empty() => normalize_faction_name(f),
-            _ => continue,
-        };
-        let f2 = match &pairing.player2_faction {
-            Some(f) if !f.is_empty() => normalize_faction_name(f),
-            _ => continue,
-        };
-
-        // Skip mirror matches
-        if f1 == f2 {
-            continue;
-        }
-
-        all_factions.insert(f1.clone());
-        all_factions.insert(f2.clone());
-
-        let (key_f1, key_f2, is_swapped) = if f1 <= f2 {
-            (f1.clone(), f2.clone(), false)
-        } else {
-            (f2.clone(), f1.clone(), true)
-        };
-
-        let agg = matchup_map
-            .entry((key_f1.clone(), key_f2.clone()))
-            .or_insert_with(|| MatchupAgg {
-                faction1: key_f1,
-                faction2: key_f2,
-                faction1_wins: 0,
-                faction2_wins: 0,
-                draws: 0,
-            });
-
-        match pairing.player1_result.as_deref() {
-            Some("win") => {
-                if is_swapped {
-                    agg.faction2_wins += 1;
-                } else {
-                    agg.faction1_wins += 1;
-                }
-            }
-            Some("loss") => {
-                if is_swapped {
-                    agg.faction1_wins += 1;
-                } else {
-                    agg.faction2_wins += 1;
-                }
-            }
-            Some("draw") => agg.draws += 1,
-            _ => {}
-        }
-    }
+    let mut factions: Vec<String> = records.keys().map(|(f, _)| f.clone()).collect();
+    factions.dedup();
 
-    let mut matchup_stats: Vec<MatchupStat> = matchup_map
-        .into_values()
-        .filter(|agg| agg.faction1_wins + agg.faction2_wins + agg.draws >= min_games)
-        .map(|agg| {
-            let total = agg.faction1_wins + agg.faction2_wins + agg.draws;
-            let win_rate = if total > 0 {
-                (agg.faction1_wins as f64 / total as f64 * 1000.0).round() / 10.0
-            } else {
-                0.0
-            };
+    // Each matchup once, from the alphabetically first faction's side
+    let mut matchup_stats: Vec<MatchupStat> = records
+        .into_iter()
+        .filter(|((f1, f2), record)| f1 < f2 && record.games() >= min_games)
+        .map(|((faction1, faction2), record)| {
+            let total = record.games();
             MatchupStat {
-                faction1: agg.faction1,
-                faction2: agg.faction2,
-                faction1_wins: agg.faction1_wins,
-                faction2_wins: agg.faction2_wins,
-                draws: agg.draws,
+                faction1,
+                faction2,
+                faction1_wins: record.wins,
+                faction2_wins: record.losses,
+                draws: record.draws,
                 total_games: total,
-                faction1_win_rate: win_rate,
+                faction1_win_rate: (record.wins as f64 / total as f64 * 1000.0).round() / 10.0,
             }
         })
         .collect();
 
     matchup_stats.sort_by(|a, b| b.total_games.cmp(&a.total_games));
 
-    let mut factions: Vec<String> = all_factions.into_iter().collect();
-    factions.sort();
-
     Ok(Json(MatchupsResponse {
         factions,
         matchups: matchup_stats,
     }))
 }
 
-// ── Archetypes Endpoint ─────────────────────────────────────────
+// ── Matchup Matrix Endpoint ─────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
-pub struct ArchetypesParams {
-    pub epoch: Option<String>,
-    pub faction: String,
-}
-
-#[derive(Debug, Serialize)]
-pub struct ArchetypeUnit {
-    pub name: String,
-    pub count: u32,
-    pub points: Option<u32>,
+pub struct MatchupMatrixParams {
+    pub min_games: Option<u32>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct ArchetypeListEntry {
-    pub player_name: String,
-    pub rank: u32,
-    pub event_id: String,
-    pub total_points: u32,
-    pub units: Vec<ArchetypeUnit>,
-}
+/// NxN faction matchup grid from game-level results. Each pairing side's
+/// faction comes from the pairing, the player's placement or their list.
+pub async fn matchup_matrix(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+    geo: GeoFilter,
+    game_size: GameSizeFilter,
+    Query(params): Query<MatchupMatrixParams>,
+) -> Result<Json<MatchupMatrix>, ApiError> {
+    let mapper = state.epoch_mapper.read().await;
+    let epochs = mapper.all_epochs();
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
 
-#[derive(Debug, Serialize)]
-pub struct ArchetypeStat {
-    pub name: String,
-    pub detachment: String,
-    pub defining_units: Vec<String>,
-    pub list_count: u32,
-    pub avg_rank: f64,
-    pub avg_win_rate: f64,
-    pub sample_lists: Vec<ArchetypeListEntry>,
+    let mut pairings = load_pairings(&state, &epoch_ids);
+    let off_size = off_size_event_ids(&state, &epoch_ids, &game_size);
+    pairings.retain(|p| !off_size.contains(p.event_id.as_str()));
+    if let Some(ids) = geo_event_ids(&state, &epoch_ids, &geo) {
+        pairings.retain(|p| ids.contains(p.event_id.as_str()));
+    }
+    let (placements, lists) = load_placements_and_lists(&state, &epoch_ids);
+    let records = faction_matchups(&pairings, &placements, &lists);
+
+    Ok(Json(calculate_matchup_matrix(
+        &records,
+        params.min_games.unwrap_or(MATRIX_MIN_GAMES),
+    )))
 }
 
-#[derive(Debug, Serialize)]
-pub struct ArchetypesResponse {
-    pub faction: String,
-    pub archetypes: Vec<ArchetypeStat>,
-    pub total_lists: u32,
+// ── Strength of Schedule Endpoint ───────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct SosParams {
+    pub min_games: Option<u32>,
+    pub limit: Option<u32>,
+    /// `weighted_win_rate` (default), `strength_of_schedule` or `win_rate`
+    pub sort: Option<String>,
 }
 
-/// Jaccard similarity between two sets of unit names.
-fn jaccard_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
-    if a.is_empty() && b.is_empty() {
-        return 1.0;
-    }
-    let intersection = a.intersection(b).count();
-    let union = a.union(b).count();
-    if union == 0 {
-        0.0
-    } else {
-        intersection as f64 / union as f64
-    }
+#[derive(Debug, Serialize)]
+pub struct SosResponse {
+    pub players: Vec<PlayerSchedule>,
+    pub factions: Vec<FactionSchedule>,
+    pub total_players: u32,
 }
 
-pub async fn archetypes(
+pub async fn sos(
     State(state): State<AppState>,
-    Query(params): Query<ArchetypesParams>,
-) -> Result<Json<ArchetypesResponse>, ApiError> {
+    epoch: EpochParam,
+    geo: GeoFilter,
+    game_size: GameSizeFilter,
+    Query(params): Query<SosParams>,
+) -> Result<Json<SosResponse>, ApiError> {
+    let key: fn(&crate::calculate::sos::ScheduleStats) -> Option<f64> = match params.sort.as_deref()
+    {
+        None | Some("weighted_win_rate") => |s| s.weighted_win_rate,
+        Some("strength_of_schedule") => |s| s.strength_of_schedule,
+        Some("win_rate") => |s| s.win_rate,
+        Some(other) => {
+            return Err(ApiError::BadRequest(format!(
+                "Unknown sort: {} (expected weighted_win_rate, strength_of_schedule or win_rate)",
+                other
+            )))
+        }
+    };
+
     let mapper = state.epoch_mapper.read().await;
     let epochs = mapper.all_epochs();
-    let epoch_ids = resolve_epoch_ids(params.epoch.as_deref(), epochs, &mapper)?;
-    let (placements, lists) = load_placements_and_lists(&state, &epoch_ids);
-
-    let faction_norm = normalize_faction_name(&params.faction);
-
-    // Filter lists to this faction
-    let faction_lists: Vec<&ArmyList> = lists
-        .iter()
-        .filter(|l| normalize_faction_name(&l.faction) == faction_norm && !l.units.is_empty())
-        .collect();
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
 
-    let total_lists = faction_lists.len() as u32;
+    let min_games = params.min_games.unwrap_or(5);
+    let limit = params.limit.unwrap_or(50).min(500) as usize;
 
-    if faction_lists.is_empty() {
-        return Ok(Json(ArchetypesResponse {
-            faction: faction_norm,
-            archetypes: vec![],
-            total_lists: 0,
-        }));
+    let mut pairings = load_pairings(&state, &epoch_ids);
+    let off_size = off_size_event_ids(&state, &epoch_ids, &game_size);
+    pairings.retain(|p| !off_size.contains(p.event_id.as_str()));
+    if let Some(ids) = geo_event_ids(&state, &epoch_ids, &geo) {
+        pairings.retain(|p| ids.contains(p.event_id.as_str()));
     }
+    let (placements, lists) = load_placements_and_lists(&state, &epoch_ids);
+    let schedules = strength_of_schedule(&pairings, &placements, &lists);
+    let total_players = schedules.players.len() as u32;
 
-    // Build unit name sets for each list
-    let list_unit_sets: Vec<HashSet<String>> = faction_lists
-        .iter()
-        .map(|l| l.units.iter().map(|u| u.name.clone()).collect())
+    let by_key = |a: Option<f64>, b: Option<f64>| {
+        b.unwrap_or(-1.0)
+            .partial_cmp(&a.unwrap_or(-1.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    };
+    let mut players: Vec<PlayerSchedule> = schedules
+        .players
+        .into_iter()
+        .filter(|p| p.stats.games >= min_games)
+        .collect();
+    players.sort_by(|a, b| by_key(key(&a.stats), key(&b.stats)));
+    players.truncate(limit);
+    let mut factions: Vec<FactionSchedule> = schedules
+        .factions
+        .into_iter()
+        .filter(|f| f.stats.games >= min_games)
         .collect();
+    factions.sort_by(|a, b| by_key(key(&a.stats), key(&b.stats)));
 
-    // Global unit frequencies across all faction lists
-    let mut global_unit_freq: HashMap<String, u32> = HashMap::new();
-    for set in &list_unit_sets {
-        for unit in set {
-            *global_unit_freq.entry(unit.clone()).or_default() += 1;
-        }
-    }
+    Ok(Json(SosResponse {
+        players,
+        factions,
+        total_players,
+    }))
+}
 
-    // Group by detachment first, then cluster within each detachment
-    let mut detachment_groups: HashMap<String, Vec<usize>> = HashMap::new();
-    for (idx, list) in faction_lists.iter().enumerate() {
-        let det = list.detachment.as_deref().unwrap_or("Unknown").to_string();
-        detachment_groups.entry(det).or_default().push(idx);
-    }
+// ── Head-to-Head Endpoint ───────────────────────────────────────
 
-    // Simple greedy clustering within each detachment group
-    let mut archetypes = Vec::new();
+#[derive(Debug, Deserialize)]
+pub struct HeadToHeadParams {
+    pub faction_a: String,
+    pub faction_b: String,
+}
 
-    for (detachment, indices) in &detachment_groups {
-        let mut assigned: Vec<bool> = vec![false; indices.len()];
-        let mut clusters: Vec<Vec<usize>> = Vec::new();
+#[derive(Debug, Serialize)]
+pub struct HeadToHeadRecord {
+    pub games: u32,
+    pub faction_a_wins: u32,
+    pub faction_b_wins: u32,
+    pub draws: u32,
+    /// Percentage of games won by faction A (0-100)
+    pub faction_a_win_rate: f64,
+    /// 95% Wilson interval for faction A's win rate (0-100)
+    pub ci_low: f64,
+    pub ci_high: f64,
+}
 
-        for i in 0..indices.len() {
-            if assigned[i] {
-                continue;
-            }
-            let mut cluster = vec![indices[i]];
-            assigned[i] = true;
+#[derive(Debug, Serialize)]
+pub struct EpochHeadToHead {
+    pub epoch_id: String,
+    pub epoch_name: Option<String>,
+    #[serde(flatten)]
+    pub record: HeadToHeadRecord,
+}
 
-            for j in (i + 1)..indices.len() {
-                if assigned[j] {
-                    continue;
-                }
-                let sim =
-                    jaccard_similarity(&list_unit_sets[indices[i]], &list_unit_sets[indices[j]]);
-                if sim >= 0.5 {
-                    cluster.push(indices[j]);
-                    assigned[j] = true;
-                }
-            }
+#[derive(Debug, Serialize)]
+pub struct HeadToHeadResponse {
+    pub faction_a: String,
+    pub faction_b: String,
+    #[serde(flatten)]
+    pub overall: HeadToHeadRecord,
+    pub epochs: Vec<EpochHeadToHead>,
+}
 
-            if cluster.len() >= 2 {
-                clusters.push(cluster);
-            }
+#[derive(Debug, Default, Clone, Copy)]
+struct HeadToHeadCounts {
+    a_wins: u32,
+    b_wins: u32,
+    draws: u32,
+}
+
+impl HeadToHeadCounts {
+    fn add(&mut self, other: HeadToHeadCounts) {
+        self.a_wins += other.a_wins;
+        self.b_wins += other.b_wins;
+        self.draws += other.draws;
+    }
+
+    fn record(&self) -> HeadToHeadRecord {
+        let games = self.a_wins + self.b_wins + self.draws;
+        let (low, high) =
+            crate::calculate::wilson_interval(self.a_wins, games, crate::calculate::Z_95);
+        let pct = |x: f64| (x * 1000.0).round() / 10.0;
+        HeadToHeadRecord {
+            games,
+            faction_a_wins: self.a_wins,
+            faction_b_wins: self.b_wins,
+            draws: self.draws,
+            faction_a_win_rate: pct(crate::calculate::calculate_win_rate(
+                self.a_wins,
+                self.b_wins,
+                self.draws,
+            )),
+            ci_low: pct(low),
+            ci_high: pct(high),
         }
+    }
+}
+
+/// Game results between two factions from stored pairings, overall and per
+/// epoch. A pairing's missing faction is filled in from the player's
+/// placement at the same event.
+pub async fn head_to_head(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
+    Query(params): Query<HeadToHeadParams>,
+) -> Result<Json<HeadToHeadResponse>, ApiError> {
+    let faction_a = normalize_faction_name(&params.faction_a);
+    let faction_b = normalize_faction_name(&params.faction_b);
+    if faction_a == faction_b {
+        return Err(ApiError::BadRequest(
+            "faction_a and faction_b must be different factions".to_string(),
+        ));
+    }
+
+    let mapper = state.epoch_mapper.read().await;
+    let epochs = mapper.all_epochs();
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
+    let off_size = off_size_event_ids(&state, &epoch_ids, &game_size);
 
-        // For each cluster, find defining units and compute stats
-        let joined = join_lists_to_placements(&lists, &placements);
-        let placement_by_list_id: HashMap<String, &Placement> = joined
+    let mut epoch_counts: Vec<(String, HeadToHeadCounts)> = Vec::new();
+    for epoch_id in &epoch_ids {
+        let mut pairings: Vec<Pairing> = merge_by_id(
+            IndexedReader::<Pairing>::for_entity(&state.storage, EntityType::Pairing, epoch_id)
+                .read_all()
+                .unwrap_or_default(),
+            |p| p.id.as_str(),
+        );
+        state.storage.overrides.retain(&mut pairings);
+        pairings.retain(|p| !off_size.contains(p.event_id.as_str()));
+        if pairings.is_empty() {
+            continue;
+        }
+        let placements: Vec<Placement> =
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id)
+                .read_all()
+                .unwrap_or_default();
+        let placement_faction: HashMap<(String, String), String> = placements
             .iter()
-            .map(|(l, p)| (l.id.as_str().to_string(), p))
+            .map(|p| {
+                (
+                    (
+                        p.event_id.as_str().to_string(),
+                        normalize_player_name(&p.player_name),
+                    ),
+                    normalize_faction_name(&p.faction),
+                )
+            })
             .collect();
+        let faction_of = |pairing: &Pairing, name: &str, faction: &Option<String>| match faction {
+            Some(f) if !f.is_empty() => Some(normalize_faction_name(f)),
+            _ => placement_faction
+                .get(&(
+                    pairing.event_id.as_str().to_string(),
+                    normalize_player_name(name),
+                ))
+                .cloned(),
+        };
 
-        for cluster in &clusters {
-            // Unit frequency within cluster
-            let mut cluster_unit_freq: HashMap<String, u32> = HashMap::new();
-            for &idx in cluster {
-                for unit in &list_unit_sets[idx] {
-                    *cluster_unit_freq.entry(unit.clone()).or_default() += 1;
-                }
+        let mut counts = HeadToHeadCounts::default();
+        for pairing in &pairings {
+            let f1 = faction_of(pairing, &pairing.player1_name, &pairing.player1_faction);
+            let f2 = faction_of(pairing, &pairing.player2_name, &pairing.player2_faction);
+            let a_is_p1 = match (f1.as_deref(), f2.as_deref()) {
+                (Some(f1), Some(f2)) if f1 == faction_a && f2 == faction_b => true,
+                (Some(f1), Some(f2)) if f1 == faction_b && f2 == faction_a => false,
+                _ => continue,
+            };
+            match (pairing.player1_result.as_deref(), a_is_p1) {
+                (Some("win"), true) | (Some("loss"), false) => counts.a_wins += 1,
+                (Some("loss"), true) | (Some("win"), false) => counts.b_wins += 1,
+                (Some("draw"), _) => counts.draws += 1,
+                _ => {}
             }
+        }
+        if counts.a_wins + counts.b_wins + counts.draws > 0 {
+            epoch_counts.push((epoch_id.clone(), counts));
+        }
+    }
 
-            let cluster_size = cluster.len() as f64;
+    let mut overall = HeadToHeadCounts::default();
+    let epochs_out = epoch_counts
+        .iter()
+        .map(|(epoch_id, counts)| {
+            overall.add(*counts);
+            EpochHeadToHead {
+                epoch_id: epoch_id.clone(),
+                epoch_name: epochs
+                    .iter()
+                    .find(|e| e.id.as_str() == epoch_id)
+                    .map(|e| e.name.clone()),
+                record: counts.record(),
+            }
+        })
+        .collect();
 
-            // Defining units: present in >=60% of cluster, <30% of faction overall
-            let defining_units: Vec<String> = cluster_unit_freq
-                .iter()
-                .filter(|(unit, count)| {
-                    let cluster_rate = **count as f64 / cluster_size;
-                    let global_rate =
-                        *global_unit_freq.get(*unit).unwrap_or(&0) as f64 / total_lists as f64;
-                    cluster_rate >= 0.6 && global_rate < 0.3
-                })
-                .map(|(unit, _)| unit.clone())
-                .collect();
+    Ok(Json(HeadToHeadResponse {
+        faction_a,
+        faction_b,
+        overall: overall.record(),
+        epochs: epochs_out,
+    }))
+}
 
-            // Compute performance stats
-            let mut ranks: Vec<f64> = Vec::new();
-            let mut win_rates: Vec<f64> = Vec::new();
+// ── Archetypes Endpoint ─────────────────────────────────────────
 
-            for &idx in cluster {
-                let list = faction_lists[idx];
-                if let Some(p) = placement_by_list_id.get(list.id.as_str()) {
-                    ranks.push(p.rank as f64);
-                    if let Some(ref record) = p.record {
-                        win_rates.push(record.win_rate());
-                    }
-                }
-            }
+#[derive(Debug, Deserialize)]
+pub struct ArchetypesParams {
+    pub faction: String,
+}
 
-            let avg_rank = if ranks.is_empty() {
-                0.0
-            } else {
-                (ranks.iter().sum::<f64>() / ranks.len() as f64 * 10.0).round() / 10.0
-            };
-            let avg_win_rate = if win_rates.is_empty() {
-                0.0
-            } else {
-                (win_rates.iter().sum::<f64>() / win_rates.len() as f64 * 1000.0).round() / 10.0
-            };
+/// Archetypes of a faction. A single epoch reads the `archetypes` derived
+/// artifact when it's current; several epochs are clustered together on the
+/// fly.
+pub async fn archetypes(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
+    Query(params): Query<ArchetypesParams>,
+) -> Result<Json<FactionArchetypes>, ApiError> {
+    let mapper = state.epoch_mapper.read().await;
+    let epochs = mapper.all_epochs();
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
+    let faction = normalize_faction_name(&params.faction);
+
+    if let [epoch_id] = epoch_ids.as_slice() {
+        let all =
+            crate::calculate::derive::load_archetypes(&state.storage, epoch_id, game_size.points)
+                .map_err(|e| ApiError::Internal(e.to_string()))?;
+        if let Some(found) = all.into_iter().find(|a| a.faction == faction) {
+            return Ok(Json(found));
+        }
+    }
 
-            let name = if defining_units.is_empty() {
-                format!("{} {}", detachment, cluster.len())
-            } else {
-                defining_units
-                    .iter()
-                    .take(3)
-                    .cloned()
-                    .collect::<Vec<_>>()
-                    .join(" + ")
-            };
+    let (mut placements, mut lists) = load_placements_and_lists(&state, &epoch_ids);
+    let mut pairings = load_pairings(&state, &epoch_ids);
+    let off_size = off_size_event_ids(&state, &epoch_ids, &game_size);
+    placements.retain(|p| !off_size.contains(p.event_id.as_str()));
+    pairings.retain(|p| !off_size.contains(p.event_id.as_str()));
+    lists.retain(|l| {
+        l.event_id
+            .as_ref()
+            .is_none_or(|e| !off_size.contains(e.as_str()))
+    });
+    Ok(Json(faction_archetypes(
+        &faction,
+        &placements,
+        &lists,
+        &pairings,
+    )))
+}
 
-            // Collect all lists in the cluster with placement info
-            let mut sample_lists: Vec<ArchetypeListEntry> = Vec::new();
-            for &idx in cluster {
-                let list = faction_lists[idx];
-                let player_name;
-                let rank;
-                let event_id;
-                if let Some(p) = placement_by_list_id.get(list.id.as_str()) {
-                    player_name = p.player_name.clone();
-                    rank = p.rank;
-                    event_id = p.event_id.as_str().to_string();
-                } else {
-                    player_name = list
-                        .player_name
-                        .clone()
-                        .unwrap_or_else(|| "Unknown".to_string());
-                    rank = 0;
-                    event_id = list
-                        .event_id
-                        .as_ref()
-                        .map(|e| e.as_str().to_string())
-                        .unwrap_or_default();
-                }
-                let units = list
-                    .units
-                    .iter()
-                    .map(|u| ArchetypeUnit {
-                        name: u.name.clone(),
-                        count: u.count,
-                        points: u.points,
-                    })
-                    .collect();
-                sample_lists.push(ArchetypeListEntry {
-                    player_name,
-                    rank,
-                    event_id,
-                    total_points: list.total_points,
-                    units,
-                });
-            }
-            sample_lists.sort_by_key(|e| e.rank);
+// ── Combos Endpoint ─────────────────────────────────────────────
 
-            archetypes.push(ArchetypeStat {
-                name,
-                detachment: detachment.clone(),
-                defining_units,
-                list_count: cluster.len() as u32,
-                avg_rank,
-                avg_win_rate,
-                sample_lists,
-            });
+#[derive(Debug, Deserialize)]
+pub struct CombosParams {
+    pub faction: String,
+}
+
+/// Unit pairs and triples a faction's winning lists take together. A
+/// single epoch reads the `combos` derived artifact when it's current;
+/// several epochs are mined together on the fly.
+pub async fn combos(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
+    Query(params): Query<CombosParams>,
+) -> Result<Json<FactionCombos>, ApiError> {
+    let mapper = state.epoch_mapper.read().await;
+    let epochs = mapper.all_epochs();
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
+    let faction = normalize_faction_name(&params.faction);
+
+    if let [epoch_id] = epoch_ids.as_slice() {
+        let all = crate::calculate::derive::load_combos(&state.storage, epoch_id, game_size.points)
+            .map_err(|e| ApiError::Internal(e.to_string()))?;
+        if let Some(found) = all.into_iter().find(|c| c.faction == faction) {
+            return Ok(Json(found));
         }
     }
 
-    archetypes.sort_by(|a, b| b.list_count.cmp(&a.list_count));
-
-    Ok(Json(ArchetypesResponse {
-        faction: faction_norm,
-        archetypes,
-        total_lists,
-    }))
+    let (mut placements, mut lists) = load_placements_and_lists(&state, &epoch_ids);
+    let off_size = off_size_event_ids(&state, &epoch_ids, &game_size);
+    placements.retain(|p| !off_size.contains(p.event_id.as_str()));
+    lists.retain(|l| {
+        l.event_id
+            .as_ref()
+            .is_none_or(|e| !off_size.contains(e.as_str()))
+    });
+    Ok(Json(faction_combos(&faction, &placements, &lists)))
 }
 
 // ── Win Rates Endpoint ──────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
 pub struct WinRatesParams {
-    pub epoch: Option<String>,
     pub from: Option<String>,
     pub to: Option<String>,
     pub min_games: Option<u32>,
     pub min_players: Option<u32>,
+    /// `adjusted_win_rate` (default) or `ci_low`
+    pub sort: Option<String>,
+    /// `wilson` (default) or `jeffreys`
+    pub interval: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1757,6 +2478,9 @@ pub struct FactionWinRate {
     pub losses: u32,
     pub draws: u32,
     pub player_count: u32,
+    /// 95% interval for the unweighted win rate
+    pub ci_low: Option<f64>,
+    pub ci_high: Option<f64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1768,11 +2492,18 @@ pub struct WinRatesResponse {
 
 pub async fn win_rates(
     State(state): State<AppState>,
+    epoch: EpochParam,
+    geo: GeoFilter,
+    game_size: GameSizeFilter,
+    size: SizeFilter,
+    confidence: ConfidenceFilter,
     Query(params): Query<WinRatesParams>,
 ) -> Result<Json<WinRatesResponse>, ApiError> {
+    let by_ci_low = sorts_by_ci_low(params.sort.as_deref(), "adjusted_win_rate")?;
+    let method = interval_method(params.interval.as_deref())?;
     let mapper = state.epoch_mapper.read().await;
     let epochs = mapper.all_epochs();
-    let epoch_ids = resolve_epoch_ids(params.epoch.as_deref(), epochs, &mapper)?;
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
 
     let min_players_filter = params.min_players.unwrap_or(0);
     // Prior weight for regression to the mean: adding K imaginary games at 50%.
@@ -1794,20 +2525,23 @@ pub async fn win_rates(
     let mut all_events = Vec::new();
     for epoch_id in &epoch_ids {
         if let Ok(placements) =
-            JsonlReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id)
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id)
                 .read_all()
         {
             all_placements.extend(placements);
         }
         if let Ok(events) =
-            JsonlReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id).read_all()
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id)
+                .read_all()
         {
             all_events.extend(events);
         }
     }
 
-    all_placements = dedup_by_id(all_placements, |p| p.id.as_str());
-    all_events = dedup_by_id(all_events, |e| e.id.as_str());
+    all_placements = merge_by_id(all_placements, |p| p.id.as_str());
+    state.storage.overrides.retain(&mut all_placements);
+    all_events = merge_by_id(all_events, |e| e.id.as_str());
+    state.storage.overrides.retain(&mut all_events);
 
     // Build event lookup for date filtering and player count filtering
     let event_map: HashMap<String, &Event> = all_events
@@ -1823,6 +2557,21 @@ pub async fn win_rates(
         });
     }
 
+    all_placements.retain(|p| {
+        event_map
+            .get(p.event_id.as_str())
+            .is_none_or(|e| game_size.matches(e))
+    });
+    if geo.is_active() || size.is_active() {
+        all_placements.retain(|p| {
+            event_map
+                .get(p.event_id.as_str())
+                .is_some_and(|e| geo.matches(e) && size.matches(e))
+        });
+    }
+
+    all_placements.retain(|p| confidence.keeps(p.extraction_confidence));
+
     // Filter by min_players (tournament size)
     if min_players_filter > 0 {
         all_placements.retain(|p| match event_map.get(p.event_id.as_str()) {
@@ -1854,11 +2603,14 @@ pub async fn win_rates(
     // Only use placements from events with full standings
     all_placements.retain(|p| full_event_ids.contains(p.event_id.as_str()));
 
-    // Accumulate W/L/D per faction
+    // Accumulate W/L/D per faction. Weighted totals count each game by its
+    // placement's confidence weight (1 unless `weighted=true`).
     struct FactionAgg {
         wins: u32,
         losses: u32,
         draws: u32,
+        weighted_wins: f64,
+        weighted_games: f64,
         players: HashSet<String>,
     }
 
@@ -1874,11 +2626,16 @@ pub async fn win_rates(
             wins: 0,
             losses: 0,
             draws: 0,
+            weighted_wins: 0.0,
+            weighted_games: 0.0,
             players: HashSet::new(),
         });
+        let weight = confidence.weight(p.extraction_confidence);
         agg.wins += record.wins;
         agg.losses += record.losses;
         agg.draws += record.draws;
+        agg.weighted_wins += weight * (record.wins as f64 + 0.5 * record.draws as f64);
+        agg.weighted_games += weight * record.total_games() as f64;
         agg.players.insert(normalize_player_name(&p.player_name));
     }
 
@@ -1887,23 +2644,23 @@ pub async fn win_rates(
         .into_iter()
         .map(|(faction, agg)| {
             let total = agg.wins + agg.losses + agg.draws;
-            let raw_wins = agg.wins as f64 + 0.5 * agg.draws as f64;
-            let win_rate = if total > 0 {
-                (raw_wins / total as f64 * 1000.0).round() / 10.0
+            let (raw_wins, games) = (agg.weighted_wins, agg.weighted_games);
+            let win_rate = if games > 0.0 {
+                (raw_wins / games * 1000.0).round() / 10.0
             } else {
                 0.0
             };
             // Regression to the mean: blend raw rate with 50% prior
             // adjusted = (actual_wins + K * 0.5) / (games + K)
-            let adjusted_win_rate = if total > 0 {
-                ((raw_wins + prior_weight * 0.5) / (total as f64 + prior_weight) * 1000.0).round()
-                    / 10.0
+            let adjusted_win_rate = if games > 0.0 {
+                ((raw_wins + prior_weight * 0.5) / (games + prior_weight) * 1000.0).round() / 10.0
             } else {
                 50.0
             };
             let allegiance = faction_allegiance(&faction)
                 .unwrap_or("Unknown")
                 .to_string();
+            let ci = win_rate_ci(agg.wins, agg.draws, total, method);
             FactionWinRate {
                 faction,
                 allegiance,
@@ -1914,15 +2671,21 @@ pub async fn win_rates(
                 losses: agg.losses,
                 draws: agg.draws,
                 player_count: agg.players.len() as u32,
+                ci_low: ci.map(|(low, _)| low),
+                ci_high: ci.map(|(_, high)| high),
             }
         })
         .collect();
 
-    factions.sort_by(|a, b| {
-        b.adjusted_win_rate
-            .partial_cmp(&a.adjusted_win_rate)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    if by_ci_low {
+        sort_by_ci_low(&mut factions, |f| f.ci_low);
+    } else {
+        factions.sort_by(|a, b| {
+            b.adjusted_win_rate
+                .partial_cmp(&a.adjusted_win_rate)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
 
     let total_games: u32 = factions.iter().map(|f| f.games_played).sum();
     let average_win_rate = if factions.is_empty() {
@@ -1939,11 +2702,174 @@ pub async fn win_rates(
     }))
 }
 
+// ── Missions Endpoint ───────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct MissionsParams {
+    pub min_games: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MissionFactionStat {
+    pub faction: String,
+    pub players: u32,
+    #[serde(flatten)]
+    pub record: detachments::GameRecord,
+    pub games: u32,
+    pub win_rate: Option<f64>,
+    /// The faction's win rate across every event, with or without a pack
+    pub overall_win_rate: Option<f64>,
+    /// Percentage points above (or below) the overall win rate
+    pub delta: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MissionPackSummary {
+    pub mission_pack: String,
+    pub events: u32,
+    pub players: u32,
+    /// Factions with at least `min_games` games, best win rate first
+    pub factions: Vec<MissionFactionStat>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MissionsResponse {
+    pub min_games: u32,
+    pub packs: Vec<MissionPackSummary>,
+    /// Events whose mission pack isn't known
+    pub events_without_pack: u32,
+}
+
+/// Faction win rates split by the mission pack each event played, from
+/// placement records.
+pub async fn missions(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+    geo: GeoFilter,
+    game_size: GameSizeFilter,
+    Query(params): Query<MissionsParams>,
+) -> Result<Json<MissionsResponse>, ApiError> {
+    let mapper = state.epoch_mapper.read().await;
+    let epochs = mapper.all_epochs();
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
+    let min_games = params.min_games.unwrap_or(10);
+
+    let mut events = Vec::new();
+    let mut placements = Vec::new();
+    for epoch_id in &epoch_ids {
+        if let Ok(found) =
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id)
+                .read_all()
+        {
+            events.extend(found);
+        }
+        if let Ok(found) =
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id)
+                .read_all()
+        {
+            placements.extend(found);
+        }
+    }
+    let mut events = merge_by_id(events, |e| e.id.as_str());
+    state.storage.overrides.retain(&mut events);
+    events.retain(|e| geo.matches(e) && game_size.matches(e));
+    let mut placements = merge_by_id(placements, |p| p.id.as_str());
+    state.storage.overrides.retain(&mut placements);
+
+    let pack_of: HashMap<&str, Option<&str>> = events
+        .iter()
+        .map(|e| (e.id.as_str(), e.mission_pack.as_deref()))
+        .collect();
+    let events_without_pack = events.iter().filter(|e| e.mission_pack.is_none()).count() as u32;
+
+    #[derive(Default)]
+    struct Agg {
+        record: detachments::GameRecord,
+        players: HashSet<String>,
+    }
+    let mut overall: HashMap<String, detachments::GameRecord> = HashMap::new();
+    let mut by_pack: HashMap<&str, HashMap<String, Agg>> = HashMap::new();
+    let mut pack_events: HashMap<&str, HashSet<&str>> = HashMap::new();
+
+    for p in &placements {
+        let Some(pack) = pack_of.get(p.event_id.as_str()) else {
+            continue;
+        };
+        let Some(r) = p.record.as_ref().filter(|r| r.total_games() > 0) else {
+            continue;
+        };
+        let record = detachments::GameRecord {
+            wins: r.wins,
+            losses: r.losses,
+            draws: r.draws,
+        };
+        let faction = normalize_faction_name(&p.faction);
+        overall.entry(faction.clone()).or_default().add(&record);
+
+        let Some(pack) = *pack else { continue };
+        pack_events
+            .entry(pack)
+            .or_default()
+            .insert(p.event_id.as_str());
+        let agg = by_pack.entry(pack).or_default().entry(faction).or_default();
+        agg.record.add(&record);
+        agg.players.insert(normalize_player_name(&p.player_name));
+    }
+
+    let mut packs: Vec<MissionPackSummary> = by_pack
+        .into_iter()
+        .map(|(pack, factions)| {
+            let players = factions.values().map(|a| a.players.len() as u32).sum();
+            let mut factions: Vec<MissionFactionStat> = factions
+                .into_iter()
+                .filter(|(_, a)| a.record.games() >= min_games)
+                .map(|(faction, a)| {
+                    let win_rate = a.record.win_rate();
+                    let overall_win_rate = overall.get(&faction).and_then(|r| r.win_rate());
+                    MissionFactionStat {
+                        players: a.players.len() as u32,
+                        games: a.record.games(),
+                        record: a.record,
+                        win_rate,
+                        overall_win_rate,
+                        delta: win_rate
+                            .zip(overall_win_rate)
+                            .map(|(w, o)| ((w - o) * 10.0).round() / 10.0),
+                        faction,
+                    }
+                })
+                .collect();
+            factions.sort_by(|a, b| {
+                b.win_rate
+                    .partial_cmp(&a.win_rate)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a.faction.cmp(&b.faction))
+            });
+            MissionPackSummary {
+                mission_pack: pack.to_string(),
+                events: pack_events.get(pack).map_or(0, |e| e.len() as u32),
+                players,
+                factions,
+            }
+        })
+        .collect();
+    packs.sort_by(|a, b| {
+        b.events
+            .cmp(&a.events)
+            .then_with(|| a.mission_pack.cmp(&b.mission_pack))
+    });
+
+    Ok(Json(MissionsResponse {
+        min_games,
+        packs,
+        events_without_pack,
+    }))
+}
+
 // ── Composite Scores Endpoint ───────────────────────────────────
 
 #[derive(Debug, Deserialize)]
 pub struct CompositeScoresParams {
-    pub epoch: Option<String>,
     pub from: Option<String>,
     pub to: Option<String>,
     pub min_players: Option<u32>,
@@ -1990,11 +2916,14 @@ fn percentile_ranks(values: &[f64]) -> Vec<f64> {
 
 pub async fn composite_scores(
     State(state): State<AppState>,
+    epoch: EpochParam,
+    geo: GeoFilter,
+    game_size: GameSizeFilter,
     Query(params): Query<CompositeScoresParams>,
 ) -> Result<Json<CompositeScoresResponse>, ApiError> {
     let mapper = state.epoch_mapper.read().await;
     let epochs = mapper.all_epochs();
-    let epoch_ids = resolve_epoch_ids(params.epoch.as_deref(), epochs, &mapper)?;
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
 
     let min_players_filter = params.min_players.unwrap_or(0);
     let prior_weight: f64 = 40.0;
@@ -2014,20 +2943,23 @@ pub async fn composite_scores(
     let mut all_events = Vec::new();
     for epoch_id in &epoch_ids {
         if let Ok(placements) =
-            JsonlReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id)
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id)
                 .read_all()
         {
             all_placements.extend(placements);
         }
         if let Ok(events) =
-            JsonlReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id).read_all()
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id)
+                .read_all()
         {
             all_events.extend(events);
         }
     }
 
-    all_placements = dedup_by_id(all_placements, |p| p.id.as_str());
-    all_events = dedup_by_id(all_events, |e| e.id.as_str());
+    all_placements = merge_by_id(all_placements, |p| p.id.as_str());
+    state.storage.overrides.retain(&mut all_placements);
+    all_events = merge_by_id(all_events, |e| e.id.as_str());
+    state.storage.overrides.retain(&mut all_events);
 
     // Build event lookup
     let event_map: HashMap<String, &Event> = all_events
@@ -2043,6 +2975,19 @@ pub async fn composite_scores(
         });
     }
 
+    all_placements.retain(|p| {
+        event_map
+            .get(p.event_id.as_str())
+            .is_none_or(|e| game_size.matches(e))
+    });
+    if geo.is_active() {
+        all_placements.retain(|p| {
+            event_map
+                .get(p.event_id.as_str())
+                .is_some_and(|e| geo.matches(e))
+        });
+    }
+
     // Filter by min_players (tournament size)
     if min_players_filter > 0 {
         all_placements.retain(|p| match event_map.get(p.event_id.as_str()) {
@@ -2222,26 +3167,1114 @@ pub async fn composite_scores(
     }))
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::api::build_router;
-    use crate::api::state::AppState;
-    use crate::models::{EpochMapper, Event, Placement};
-    use crate::storage::StorageConfig;
-    use axum::body::Body;
-    use axum::http::{Request, StatusCode};
-    use serde_json::Value;
-    use std::sync::Arc;
-    use tower::util::ServiceExt;
-
-    fn write_jsonl<T: serde::Serialize>(path: &std::path::Path, items: &[T]) {
-        let mut content = String::new();
-        for item in items {
-            content.push_str(&serde_json::to_string(item).unwrap());
-            content.push('\n');
-        }
-        std::fs::write(path, content).unwrap();
-    }
+// ── Placement Curves Endpoint ───────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct PlacementCurvesParams {
+    pub faction: Option<String>,
+    pub min_placements: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlacementCurvesResponse {
+    pub factions: Vec<PlacementCurve>,
+    pub total_placements: u32,
+}
+
+pub async fn placement_curves(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
+    Query(params): Query<PlacementCurvesParams>,
+) -> Result<Json<PlacementCurvesResponse>, ApiError> {
+    let mapper = state.epoch_mapper.read().await;
+    let epochs = mapper.all_epochs();
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
+
+    let mut all_placements = Vec::new();
+    let mut all_events = Vec::new();
+    for epoch_id in &epoch_ids {
+        if let Ok(placements) =
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id)
+                .read_all()
+        {
+            all_placements.extend(placements);
+        }
+        if let Ok(events) =
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id)
+                .read_all()
+        {
+            all_events.extend(events);
+        }
+    }
+
+    all_placements = merge_by_id(all_placements, |p| p.id.as_str());
+    state.storage.overrides.retain(&mut all_placements);
+    all_events = merge_by_id(all_events, |e| e.id.as_str());
+    state.storage.overrides.retain(&mut all_events);
+    let off_size: HashSet<String> = all_events
+        .iter()
+        .filter(|e| !game_size.matches(e))
+        .map(|e| e.id.as_str().to_string())
+        .collect();
+    all_events.retain(|e| game_size.matches(e));
+    all_placements.retain(|p| !off_size.contains(p.event_id.as_str()));
+
+    let faction_filter = params.faction.as_deref().map(normalize_faction_name);
+    let min_placements = params.min_placements.unwrap_or(0);
+
+    let mut factions = compute_placement_curves(&all_events, &all_placements);
+    factions.retain(|c| {
+        c.placements >= min_placements
+            && faction_filter
+                .as_ref()
+                .is_none_or(|f| c.faction.eq_ignore_ascii_case(f))
+    });
+
+    let total_placements = factions.iter().map(|c| c.placements).sum();
+
+    Ok(Json(PlacementCurvesResponse {
+        factions,
+        total_placements,
+    }))
+}
+
+// ── Positioning Endpoint ────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct PositioningParams {
+    pub min_games: Option<u32>,
+}
+
+/// Where a faction sits relative to the share and win-rate thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Quadrant {
+    /// Popular and winning
+    HighShareHighWinRate,
+    /// Popular but underperforming
+    HighShareLowWinRate,
+    /// Rarely played but winning
+    LowShareHighWinRate,
+    /// Rarely played and underperforming
+    LowShareLowWinRate,
+}
+
+impl Quadrant {
+    fn classify(meta_share: f64, win_rate: f64, share_threshold: f64, win_threshold: f64) -> Self {
+        match (meta_share >= share_threshold, win_rate >= win_threshold) {
+            (true, true) => Quadrant::HighShareHighWinRate,
+            (true, false) => Quadrant::HighShareLowWinRate,
+            (false, true) => Quadrant::LowShareHighWinRate,
+            (false, false) => Quadrant::LowShareLowWinRate,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FactionPosition {
+    pub faction: String,
+    pub allegiance: String,
+    /// Percentage of players in the epoch (0-100)
+    pub meta_share: f64,
+    /// Percentage of games won (0-100)
+    pub win_rate: f64,
+    pub games_played: u32,
+    pub player_count: u32,
+    pub tier: Tier,
+    pub quadrant: Quadrant,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PositioningResponse {
+    pub epoch_id: String,
+    pub factions: Vec<FactionPosition>,
+    /// Meta share dividing high from low share: an even split across the
+    /// factions with results
+    pub share_threshold: f64,
+    /// Win rate dividing high from low (50%)
+    pub win_rate_threshold: f64,
+    pub min_games: u32,
+    pub total_players: u32,
+}
+
+/// Per-faction meta share vs win rate for one epoch, for the positioning
+/// scatterplot. Uses the derived faction stats artifact when current.
+pub async fn positioning(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
+    Query(params): Query<PositioningParams>,
+) -> Result<Json<PositioningResponse>, ApiError> {
+    if epoch.as_deref() == Some("all") {
+        return Err(ApiError::BadRequest(
+            "Positioning is per epoch; epoch=all is not supported".to_string(),
+        ));
+    }
+    let epoch_id = {
+        let mapper = state.epoch_mapper.read().await;
+        crate::api::resolve_epoch(epoch.as_deref(), &mapper)?
+    };
+    let min_games = params.min_games.unwrap_or(TIER_LIST_MIN_GAMES);
+
+    let stats = load_faction_stats(&state.storage, &epoch_id, game_size.points)
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+    let share_threshold = if stats.factions.is_empty() {
+        0.0
+    } else {
+        100.0 / stats.factions.len() as f64
+    };
+    let win_rate_threshold = 50.0;
+
+    let mut factions: Vec<FactionPosition> = stats
+        .factions
+        .iter()
+        .filter(|f| f.games_played >= min_games)
+        .map(|f| {
+            let meta_share = f.meta_share * 100.0;
+            let win_rate = f.win_rate * 100.0;
+            FactionPosition {
+                allegiance: faction_allegiance(&f.name).unwrap_or("Unknown").to_string(),
+                faction: f.name.clone(),
+                meta_share: (meta_share * 10.0).round() / 10.0,
+                win_rate: (win_rate * 10.0).round() / 10.0,
+                games_played: f.games_played,
+                player_count: f.player_count,
+                tier: f.tier,
+                quadrant: Quadrant::classify(
+                    meta_share,
+                    win_rate,
+                    share_threshold,
+                    win_rate_threshold,
+                ),
+            }
+        })
+        .collect();
+    factions.sort_by(|a, b| {
+        b.meta_share
+            .partial_cmp(&a.meta_share)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.faction.cmp(&b.faction))
+    });
+
+    Ok(Json(PositioningResponse {
+        epoch_id,
+        factions,
+        share_threshold: (share_threshold * 10.0).round() / 10.0,
+        win_rate_threshold,
+        min_games,
+        total_players: stats.totals.players,
+    }))
+}
+
+// ── Tiers Endpoint ──────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct TierGroup {
+    pub tier: Tier,
+    pub factions: Vec<FactionTier>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TiersResponse {
+    pub epoch_id: String,
+    /// When the persisted tier list was computed
+    pub computed_at: chrono::DateTime<chrono::Utc>,
+    /// Non-empty tiers, S first; factions within a tier by score
+    pub tiers: Vec<TierGroup>,
+}
+
+/// Composite S–D tier list for one epoch (see `calculate::tiers` for the
+/// methodology). Served from the `composite_tiers` derived artifact, which
+/// is recomputed and persisted only when the epoch's inputs change.
+pub async fn tiers(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
+) -> Result<Json<TiersResponse>, ApiError> {
+    if epoch.as_deref() == Some("all") {
+        return Err(ApiError::BadRequest(
+            "Tiers are per epoch; epoch=all is not supported".to_string(),
+        ));
+    }
+    let epoch_id = {
+        let mapper = state.epoch_mapper.read().await;
+        crate::api::resolve_epoch(epoch.as_deref(), &mapper)?
+    };
+
+    let artifact = load_tiers(&state.storage, &epoch_id, game_size.points)
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+    Ok(Json(TiersResponse {
+        epoch_id,
+        computed_at: artifact.computed_at,
+        tiers: group_tiers(artifact.data),
+    }))
+}
+
+/// Group a tier list, already ordered S first, into its non-empty tiers.
+pub(crate) fn group_tiers(factions: Vec<FactionTier>) -> Vec<TierGroup> {
+    let mut tiers: Vec<TierGroup> = Vec::new();
+    for faction in factions {
+        match tiers.last_mut() {
+            Some(group) if group.tier == faction.tier => group.factions.push(faction),
+            _ => tiers.push(TierGroup {
+                tier: faction.tier,
+                factions: vec![faction],
+            }),
+        }
+    }
+    tiers
+}
+
+// ── Epoch Diff Endpoint ─────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct EpochDiffParams {
+    /// Earlier epoch (default: the epoch before `to`)
+    pub from: Option<String>,
+    /// Later epoch (default: current)
+    pub to: Option<String>,
+    /// Games needed in both epochs to count as a winner or loser
+    pub min_games: Option<u32>,
+    /// Winners and losers to return (default 5)
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EpochDiffResponse {
+    pub from_epoch: String,
+    pub to_epoch: String,
+    pub min_games: u32,
+    /// Every faction seen in either epoch, by name
+    pub factions: Vec<FactionDelta>,
+    /// Largest win-rate gains, biggest first
+    pub winners: Vec<FactionDelta>,
+    /// Largest win-rate drops, biggest first
+    pub losers: Vec<FactionDelta>,
+}
+
+/// Per-faction change in meta share, win rate and tier between two epochs,
+/// typically either side of a balance update.
+pub async fn epoch_diff(
+    State(state): State<AppState>,
+    game_size: GameSizeFilter,
+    Query(params): Query<EpochDiffParams>,
+) -> Result<Json<EpochDiffResponse>, ApiError> {
+    if params.from.as_deref() == Some("all") || params.to.as_deref() == Some("all") {
+        return Err(ApiError::BadRequest(
+            "Epoch diff compares two single epochs; epoch=all is not supported".to_string(),
+        ));
+    }
+    let (from_epoch, to_epoch) = {
+        let mapper = state.epoch_mapper.read().await;
+        let to_epoch = crate::api::resolve_epoch(params.to.as_deref(), &mapper)?;
+        let from_epoch = match params.from.as_deref() {
+            Some(from) => crate::api::resolve_epoch(Some(from), &mapper)?,
+            None => {
+                let epochs = mapper.all_epochs();
+                epochs
+                    .iter()
+                    .position(|e| e.id.as_str() == to_epoch)
+                    .filter(|&i| i > 0)
+                    .map(|i| epochs[i - 1].id.as_str().to_string())
+                    .ok_or_else(|| {
+                        ApiError::BadRequest(format!(
+                            "No epoch before {}; pass from explicitly",
+                            to_epoch
+                        ))
+                    })?
+            }
+        };
+        (from_epoch, to_epoch)
+    };
+    let min_games = params.min_games.unwrap_or(TIER_LIST_MIN_GAMES);
+
+    let before = load_faction_stats(&state.storage, &from_epoch, game_size.points)
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let after = load_faction_stats(&state.storage, &to_epoch, game_size.points)
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+    let factions = align_faction_stats(&before, &after);
+    let (winners, losers) = biggest_movers(&factions, min_games, params.limit.unwrap_or(5));
+
+    Ok(Json(EpochDiffResponse {
+        from_epoch,
+        to_epoch,
+        min_games,
+        factions,
+        winners,
+        losers,
+    }))
+}
+
+// ── Attendance Endpoint ─────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct WeekAttendance {
+    /// Monday of the ISO week
+    pub week_start: String,
+    pub events: u32,
+    pub players: u32,
+    pub avg_players: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RegionAttendance {
+    pub region: String,
+    pub events: u32,
+    pub players: u32,
+    /// Change in event count vs the previous epoch, in percent
+    pub event_growth: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EpochAttendance {
+    pub epoch_id: String,
+    pub epoch_name: Option<String>,
+    pub events: u32,
+    /// Events reporting a player count
+    pub events_with_count: u32,
+    pub total_players: u32,
+    pub avg_players: f64,
+    /// Events per week between the epoch's first and last event
+    pub events_per_week: f64,
+    /// Change in average attendance vs the previous epoch, in percent
+    pub avg_players_change: Option<f64>,
+    pub regions: Vec<RegionAttendance>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AttendanceResponse {
+    pub total_events: u32,
+    pub total_players: u32,
+    pub weeks: Vec<WeekAttendance>,
+    pub epochs: Vec<EpochAttendance>,
+}
+
+/// Region of an event: the last comma-separated part of its location
+/// ("London, UK" -> "UK").
+fn event_region(event: &Event) -> String {
+    event
+        .location
+        .as_deref()
+        .and_then(|l| l.rsplit(',').next())
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .unwrap_or("Unknown")
+        .to_string()
+}
+
+fn avg_players(events: &[&Event]) -> (u32, u32, f64) {
+    let counts: Vec<u32> = events.iter().filter_map(|e| e.player_count).collect();
+    let total: u32 = counts.iter().sum();
+    let avg = if counts.is_empty() {
+        0.0
+    } else {
+        (total as f64 / counts.len() as f64 * 10.0).round() / 10.0
+    };
+    (counts.len() as u32, total, avg)
+}
+
+fn percent_change(prev: f64, current: f64) -> Option<f64> {
+    (prev > 0.0).then(|| ((current - prev) / prev * 1000.0).round() / 10.0)
+}
+
+/// Events per week, attendance and regional growth per epoch, from stored
+/// Event records.
+pub async fn attendance(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
+) -> Result<Json<AttendanceResponse>, ApiError> {
+    use chrono::Datelike;
+
+    let mapper = state.epoch_mapper.read().await;
+    let epochs = mapper.all_epochs();
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
+
+    let mut all_events: Vec<Event> = Vec::new();
+    let mut epoch_out: Vec<EpochAttendance> = Vec::new();
+    let mut prev: Option<(f64, HashMap<String, u32>)> = None;
+
+    for epoch_id in &epoch_ids {
+        let mut events: Vec<Event> = merge_by_id(
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id)
+                .read_all()
+                .unwrap_or_default(),
+            |e| e.id.as_str(),
+        );
+        state.storage.overrides.retain(&mut events);
+        events.retain(|e| game_size.matches(e));
+        if events.is_empty() {
+            continue;
+        }
+        events.sort_by_key(|e| e.date);
+        let refs: Vec<&Event> = events.iter().collect();
+
+        let (events_with_count, total_players, avg) = avg_players(&refs);
+        let span_days = (events[events.len() - 1].date - events[0].date).num_days();
+        let weeks = (span_days / 7 + 1) as f64;
+
+        let mut by_region: HashMap<String, Vec<&Event>> = HashMap::new();
+        for e in &refs {
+            by_region.entry(event_region(e)).or_default().push(e);
+        }
+        let region_counts: HashMap<String, u32> = by_region
+            .iter()
+            .map(|(r, evs)| (r.clone(), evs.len() as u32))
+            .collect();
+        let mut regions: Vec<RegionAttendance> = by_region
+            .into_iter()
+            .map(|(region, evs)| {
+                let event_growth = prev
+                    .as_ref()
+                    .and_then(|(_, counts)| counts.get(&region))
+                    .and_then(|&p| percent_change(p as f64, evs.len() as f64));
+                RegionAttendance {
+                    events: evs.len() as u32,
+                    players: evs.iter().filter_map(|e| e.player_count).sum(),
+                    region,
+                    event_growth,
+                }
+            })
+            .collect();
+        regions.sort_by(|a, b| b.events.cmp(&a.events).then(a.region.cmp(&b.region)));
+
+        epoch_out.push(EpochAttendance {
+            epoch_id: epoch_id.clone(),
+            epoch_name: epochs
+                .iter()
+                .find(|e| e.id.as_str() == epoch_id)
+                .map(|e| e.name.clone()),
+            events: events.len() as u32,
+            events_with_count,
+            total_players,
+            avg_players: avg,
+            events_per_week: (events.len() as f64 / weeks * 10.0).round() / 10.0,
+            avg_players_change: prev.as_ref().and_then(|(p, _)| percent_change(*p, avg)),
+            regions,
+        });
+        prev = Some((avg, region_counts));
+        all_events.extend(events);
+    }
+
+    // Weekly series across the selected epochs
+    let mut all_events = merge_by_id(all_events, |e| e.id.as_str());
+    state.storage.overrides.retain(&mut all_events);
+    let mut by_week: std::collections::BTreeMap<chrono::NaiveDate, Vec<&Event>> =
+        std::collections::BTreeMap::new();
+    for e in &all_events {
+        let week_start = e.date - chrono::Days::new(e.date.weekday().num_days_from_monday() as u64);
+        by_week.entry(week_start).or_default().push(e);
+    }
+    let weeks = by_week
+        .into_iter()
+        .map(|(week_start, evs)| {
+            let (_, players, avg) = avg_players(&evs);
+            WeekAttendance {
+                week_start: week_start.to_string(),
+                events: evs.len() as u32,
+                players,
+                avg_players: avg,
+            }
+        })
+        .collect();
+
+    Ok(Json(AttendanceResponse {
+        total_events: all_events.len() as u32,
+        total_players: all_events.iter().filter_map(|e| e.player_count).sum(),
+        weeks,
+        epochs: epoch_out,
+    }))
+}
+
+// ── Conversion Endpoint ─────────────────────────────────────────
+
+/// Share of an event's field that must have placements for the event to
+/// count. Events with only the top finishers recorded would inflate
+/// conversion.
+const CONVERSION_MIN_COVERAGE: f64 = 0.75;
+
+/// Smallest field that counts (a top 8 of 8 players says nothing).
+const CONVERSION_MIN_FIELD: u32 = 9;
+
+#[derive(Debug, Deserialize)]
+pub struct ConversionParams {
+    pub faction: Option<String>,
+    pub min_players: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArchetypeConversion {
+    pub faction: String,
+    pub detachment: String,
+    pub players: u32,
+    pub top4: u32,
+    pub top8: u32,
+    /// Percentage of players finishing top 4 / top 8 (0-100)
+    pub top4_rate: f64,
+    pub top8_rate: f64,
+    /// Finishes expected if every player had an equal shot at their event's
+    /// cut (sum of cut size / field size over the archetype's placements)
+    pub expected_top4: f64,
+    pub expected_top8: f64,
+    /// Actual / expected finishes (1.0 = par)
+    pub top4_conversion: f64,
+    pub top8_conversion: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EpochConversion {
+    pub epoch_id: String,
+    /// Events with full standings that were counted
+    pub events: u32,
+    /// Events skipped for partial standings or a tiny field
+    pub events_skipped: u32,
+    pub players: u32,
+    pub archetypes: Vec<ArchetypeConversion>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConversionResponse {
+    pub epochs: Vec<EpochConversion>,
+    pub min_players: u32,
+}
+
+#[derive(Default)]
+struct ConversionAgg {
+    players: u32,
+    top4: u32,
+    top8: u32,
+    expected_top4: f64,
+    expected_top8: f64,
+}
+
+fn conversion_ratio(actual: u32, expected: f64) -> f64 {
+    if expected > 0.0 {
+        (actual as f64 / expected * 100.0).round() / 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Top 4 / top 8 conversion per archetype (faction + detachment) for each
+/// epoch, relative to what event sizes alone would predict.
+pub async fn conversion(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
+    Query(params): Query<ConversionParams>,
+) -> Result<Json<ConversionResponse>, ApiError> {
+    let mapper = state.epoch_mapper.read().await;
+    let epochs = mapper.all_epochs();
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
+
+    let min_players = params.min_players.unwrap_or(5);
+    let faction_filter = params.faction.as_deref().map(normalize_faction_name);
+
+    let mut epoch_out = Vec::new();
+    for epoch_id in &epoch_ids {
+        let mut events: Vec<Event> = merge_by_id(
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id)
+                .read_all()
+                .unwrap_or_default(),
+            |e| e.id.as_str(),
+        );
+        state.storage.overrides.retain(&mut events);
+        events.retain(|e| game_size.matches(e));
+        let (placements, lists) = load_placements_and_lists(&state, std::slice::from_ref(epoch_id));
+        if events.is_empty() || placements.is_empty() {
+            continue;
+        }
+
+        let list_detachments: HashMap<&str, &str> = lists
+            .iter()
+            .filter_map(|l| Some((l.id.as_str(), l.detachment.as_deref()?)))
+            .collect();
+        let mut by_event: HashMap<&str, Vec<&Placement>> = HashMap::new();
+        for p in &placements {
+            by_event.entry(p.event_id.as_str()).or_default().push(p);
+        }
+
+        let mut groups: HashMap<(String, String), ConversionAgg> = HashMap::new();
+        let (mut counted, mut skipped, mut players) = (0u32, 0u32, 0u32);
+        for event in &events {
+            let Some(field) = by_event.get(event.id.as_str()) else {
+                continue;
+            };
+            let size = event.player_count.unwrap_or(field.len() as u32);
+            if size < CONVERSION_MIN_FIELD
+                || (field.len() as f64) < size as f64 * CONVERSION_MIN_COVERAGE
+            {
+                skipped += 1;
+                continue;
+            }
+            counted += 1;
+            players += field.len() as u32;
+
+            let top4_share = 4.0_f64.min(size as f64) / size as f64;
+            let top8_share = 8.0_f64.min(size as f64) / size as f64;
+            for p in field {
+                let faction = normalize_faction_name(&p.faction);
+                if faction_filter.as_ref().is_some_and(|f| f != &faction) {
+                    continue;
+                }
+                let detachment = p
+                    .list_id
+                    .as_ref()
+                    .and_then(|id| list_detachments.get(id.as_str()).copied())
+                    .or(p.detachment.as_deref())
+                    .filter(|d| !d.is_empty())
+                    .unwrap_or("Unknown")
+                    .to_string();
+
+                let agg = groups.entry((faction, detachment)).or_default();
+                agg.players += 1;
+                agg.top4 += u32::from(p.rank <= 4);
+                agg.top8 += u32::from(p.rank <= 8);
+                agg.expected_top4 += top4_share;
+                agg.expected_top8 += top8_share;
+            }
+        }
+
+        let mut archetypes: Vec<ArchetypeConversion> = groups
+            .into_iter()
+            .filter(|(_, agg)| agg.players >= min_players)
+            .map(|((faction, detachment), agg)| ArchetypeConversion {
+                faction,
+                detachment,
+                players: agg.players,
+                top4: agg.top4,
+                top8: agg.top8,
+                top4_rate: (agg.top4 as f64 / agg.players as f64 * 1000.0).round() / 10.0,
+                top8_rate: (agg.top8 as f64 / agg.players as f64 * 1000.0).round() / 10.0,
+                expected_top4: (agg.expected_top4 * 100.0).round() / 100.0,
+                expected_top8: (agg.expected_top8 * 100.0).round() / 100.0,
+                top4_conversion: conversion_ratio(agg.top4, agg.expected_top4),
+                top8_conversion: conversion_ratio(agg.top8, agg.expected_top8),
+            })
+            .collect();
+        archetypes.sort_by(|a, b| {
+            b.top4_conversion
+                .partial_cmp(&a.top4_conversion)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(b.players.cmp(&a.players))
+        });
+
+        epoch_out.push(EpochConversion {
+            epoch_id: epoch_id.clone(),
+            events: counted,
+            events_skipped: skipped,
+            players,
+            archetypes,
+        });
+    }
+
+    Ok(Json(ConversionResponse {
+        epochs: epoch_out,
+        min_players,
+    }))
+}
+
+// ── Netlists Endpoint ───────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct NetlistParams {
+    pub faction: Option<String>,
+    /// Minimum placements running the list (default 2)
+    pub min_copies: Option<u32>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NetlistUnit {
+    pub name: String,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NetlistAppearance {
+    pub placement_id: String,
+    pub event_id: String,
+    pub event_name: Option<String>,
+    pub date: Option<String>,
+    pub rank: u32,
+    pub player_name: String,
+    pub list_id: String,
+    pub record: Option<crate::models::WinLossRecord>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Netlist {
+    /// Hash of faction, detachment and unit multiset
+    pub netlist_id: String,
+    pub faction: String,
+    pub detachment: Option<String>,
+    pub total_points: u32,
+    pub units: Vec<NetlistUnit>,
+    /// Placements that ran this list
+    pub copies: u32,
+    /// Placements running the most common exact variant (same list ID)
+    pub exact_copies: u32,
+    /// Distinct exact variants (e.g. re-pointed after a dataslate)
+    pub variants: u32,
+    pub events: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    /// Combined win rate (0-100), from placements with a record
+    pub win_rate: f64,
+    pub first_seen: NetlistAppearance,
+    pub placements: Vec<NetlistAppearance>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NetlistsResponse {
+    pub netlists: Vec<Netlist>,
+    pub min_copies: u32,
+    /// Placements with a linked, parsed list
+    pub placements_considered: u32,
+}
+
+/// Most-copied lists: placements grouped by the unit-multiset hash of their
+/// linked list, with combined record and the first event each appeared at.
+pub async fn netlists(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
+    Query(params): Query<NetlistParams>,
+) -> Result<Json<NetlistsResponse>, ApiError> {
+    let mapper = state.epoch_mapper.read().await;
+    let epochs = mapper.all_epochs();
+    let epoch_ids = resolve_epoch_ids(epoch.as_deref(), epochs, &mapper)?;
+
+    let min_copies = params.min_copies.unwrap_or(2).max(1);
+    let limit = params.limit.unwrap_or(20);
+    let faction_filter = params.faction.as_deref().map(normalize_faction_name);
+
+    let (mut placements, lists) = load_placements_and_lists(&state, &epoch_ids);
+    let mut events = Vec::new();
+    for epoch_id in &epoch_ids {
+        events.extend(
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id)
+                .read_all()
+                .unwrap_or_default(),
+        );
+    }
+    let mut events = merge_by_id(events, |e| e.id.as_str());
+    state.storage.overrides.retain(&mut events);
+    let off_size: HashSet<String> = events
+        .iter()
+        .filter(|e| !game_size.matches(e))
+        .map(|e| e.id.as_str().to_string())
+        .collect();
+    events.retain(|e| game_size.matches(e));
+    placements.retain(|p| !off_size.contains(p.event_id.as_str()));
+    let events_by_id: HashMap<&str, &Event> = events.iter().map(|e| (e.id.as_str(), e)).collect();
+    let lists_by_id: HashMap<&str, &ArmyList> = lists
+        .iter()
+        .filter(|l| !l.units.is_empty())
+        .map(|l| (l.id.as_str(), l))
+        .collect();
+
+    let mut groups: HashMap<String, Vec<(&Placement, &ArmyList)>> = HashMap::new();
+    let mut considered = 0u32;
+    for p in &placements {
+        let Some(list) = p
+            .list_id
+            .as_ref()
+            .and_then(|id| lists_by_id.get(id.as_str()))
+        else {
+            continue;
+        };
+        if faction_filter
+            .as_ref()
+            .is_some_and(|f| f != &normalize_faction_name(&list.faction))
+        {
+            continue;
+        }
+        considered += 1;
+        groups
+            .entry(list.netlist_hash())
+            .or_default()
+            .push((p, list));
+    }
+
+    let mut netlists: Vec<Netlist> = groups
+        .into_iter()
+        .filter(|(_, runs)| runs.len() as u32 >= min_copies)
+        .map(|(netlist_id, runs)| {
+            let mut appearances: Vec<NetlistAppearance> = runs
+                .iter()
+                .map(|(p, list)| {
+                    let event = events_by_id.get(p.event_id.as_str());
+                    NetlistAppearance {
+                        placement_id: p.id.as_str().to_string(),
+                        event_id: p.event_id.as_str().to_string(),
+                        event_name: event.map(|e| e.name.clone()),
+                        date: event.map(|e| e.date.to_string()),
+                        rank: p.rank,
+                        player_name: p.player_name.clone(),
+                        list_id: list.id.as_str().to_string(),
+                        record: p.record.clone(),
+                    }
+                })
+                .collect();
+            // Undated appearances last, then best finish first
+            appearances.sort_by(|a, b| {
+                (a.date.is_none(), &a.date, a.rank).cmp(&(b.date.is_none(), &b.date, b.rank))
+            });
+
+            let mut variant_counts: HashMap<&str, u32> = HashMap::new();
+            for (_, list) in &runs {
+                *variant_counts.entry(list.id.as_str()).or_default() += 1;
+            }
+            let (rep_id, exact_copies) = variant_counts
+                .iter()
+                .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+                .map(|(id, n)| (*id, *n))
+                .unwrap_or_default();
+            let rep = runs
+                .iter()
+                .map(|(_, l)| *l)
+                .find(|l| l.id.as_str() == rep_id)
+                .unwrap_or(runs[0].1);
+
+            let (mut wins, mut losses, mut draws) = (0u32, 0u32, 0u32);
+            for (p, _) in &runs {
+                if let Some(r) = &p.record {
+                    wins += r.wins;
+                    losses += r.losses;
+                    draws += r.draws;
+                }
+            }
+            let games = wins + losses + draws;
+            let event_count = runs
+                .iter()
+                .map(|(p, _)| p.event_id.as_str())
+                .collect::<HashSet<_>>()
+                .len() as u32;
+
+            Netlist {
+                netlist_id,
+                faction: normalize_faction_name(&rep.faction),
+                detachment: rep.detachment.clone(),
+                total_points: rep.total_points,
+                units: rep
+                    .units
+                    .iter()
+                    .map(|u| NetlistUnit {
+                        name: u.name.clone(),
+                        count: u.count,
+                    })
+                    .collect(),
+                copies: runs.len() as u32,
+                exact_copies,
+                variants: variant_counts.len() as u32,
+                events: event_count,
+                wins,
+                losses,
+                draws,
+                win_rate: if games > 0 {
+                    (wins as f64 / games as f64 * 1000.0).round() / 10.0
+                } else {
+                    0.0
+                },
+                first_seen: appearances[0].clone(),
+                placements: appearances,
+            }
+        })
+        .collect();
+
+    netlists.sort_by(|a, b| {
+        b.copies
+            .cmp(&a.copies)
+            .then(b.win_rate.total_cmp(&a.win_rate))
+            .then_with(|| a.netlist_id.cmp(&b.netlist_id))
+    });
+    netlists.truncate(limit);
+
+    Ok(Json(NetlistsResponse {
+        netlists,
+        min_copies,
+        placements_considered: considered,
+    }))
+}
+
+// ── Player Ratings Endpoint ─────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct RatingsParams {
+    /// Rank players by their rating with this faction only
+    pub faction: Option<String>,
+    /// Case-insensitive substring match on player name
+    pub player: Option<String>,
+    pub min_games: Option<u32>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RatingsResponse {
+    pub computed_at: chrono::DateTime<chrono::Utc>,
+    /// "derived" when read from `derived/ratings`, "live" when computed for this request
+    pub source: &'static str,
+    pub epochs: Vec<String>,
+    pub games_rated: u32,
+    pub min_games: u32,
+    pub factions: Vec<FactionAverage>,
+    pub players: Vec<PlayerRating>,
+}
+
+/// Elo ratings per player with per-faction splits. Served from the table
+/// persisted by `derive`, or computed on the fly when none exists yet.
+pub async fn ratings(
+    State(state): State<AppState>,
+    game_size: GameSizeFilter,
+    Query(params): Query<RatingsParams>,
+) -> Result<Json<RatingsResponse>, ApiError> {
+    // The persisted table covers the derived game size only
+    let persisted = if game_size.points == DERIVED_GAME_SIZE {
+        read_ratings(&state.storage)
+            .map_err(|e| ApiError::Internal(format!("Failed to read ratings: {}", e)))?
+    } else {
+        None
+    };
+    let (table, source) = match persisted {
+        Some(table) => (table, "derived"),
+        None => (
+            compute_ratings_from_storage(&state.storage, RatingConfig::default(), game_size.points)
+                .map_err(|e| ApiError::Internal(format!("Failed to compute ratings: {}", e)))?,
+            "live",
+        ),
+    };
+
+    let min_games = params.min_games.unwrap_or(5);
+    let limit = params.limit.unwrap_or(100);
+    let faction_filter = params.faction.as_deref().map(normalize_faction_name);
+    let player_filter = params.player.as_deref().map(normalize_player_name);
+
+    let mut players: Vec<PlayerRating> = table
+        .players
+        .into_iter()
+        .filter(|p| {
+            player_filter
+                .as_ref()
+                .is_none_or(|f| normalize_player_name(&p.player).contains(f.as_str()))
+        })
+        .filter_map(|mut p| match &faction_filter {
+            Some(faction) => {
+                p.factions
+                    .retain(|f| &f.faction == faction && f.games >= min_games);
+                (!p.factions.is_empty()).then_some(p)
+            }
+            None => (p.games >= min_games).then_some(p),
+        })
+        .collect();
+    if faction_filter.is_some() {
+        players.sort_by(|a, b| b.factions[0].rating.total_cmp(&a.factions[0].rating));
+    }
+    players.truncate(limit);
+
+    let factions = table
+        .factions
+        .into_iter()
+        .filter(|f| faction_filter.as_ref().is_none_or(|ff| &f.faction == ff))
+        .collect();
+
+    Ok(Json(RatingsResponse {
+        computed_at: table.computed_at,
+        source,
+        epochs: table.epochs,
+        games_rated: table.games_rated,
+        min_games,
+        factions,
+        players,
+    }))
+}
+
+// ── Shift Candidates Endpoint ───────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct ShiftCandidatesParams {
+    pub faction: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShiftCandidatesResponse {
+    pub computed_at: chrono::DateTime<chrono::Utc>,
+    /// "derived" when read from `derived/shifts`, "live" when computed for this request
+    pub source: &'static str,
+    pub config: ShiftConfig,
+    pub factions_scanned: u32,
+    pub explained_by_boundary: u32,
+    pub candidates: Vec<ShiftCandidate>,
+}
+
+/// Suggested meta shift dates not explained by a registered balance pass
+/// (experimental). Served from the report persisted by `derive`, or computed
+/// on the fly when none exists yet.
+pub async fn shift_candidates(
+    State(state): State<AppState>,
+    game_size: GameSizeFilter,
+    Query(params): Query<ShiftCandidatesParams>,
+) -> Result<Json<ShiftCandidatesResponse>, ApiError> {
+    // The persisted report covers the derived game size only
+    let persisted = if game_size.points == DERIVED_GAME_SIZE {
+        read_shifts(&state.storage)
+            .map_err(|e| ApiError::Internal(format!("Failed to read shift candidates: {}", e)))?
+    } else {
+        None
+    };
+    let (report, source) = match persisted {
+        Some(report) => (report, "derived"),
+        None => (
+            compute_shifts_from_storage(&state.storage, ShiftConfig::default(), game_size.points)
+                .map_err(|e| {
+                ApiError::Internal(format!("Failed to compute shift candidates: {}", e))
+            })?,
+            "live",
+        ),
+    };
+
+    let faction_filter = params.faction.as_deref().map(normalize_faction_name);
+    let candidates = report
+        .candidates
+        .into_iter()
+        .filter(|c| faction_filter.as_ref().is_none_or(|f| &c.faction == f))
+        .collect();
+
+    Ok(Json(ShiftCandidatesResponse {
+        computed_at: report.computed_at,
+        source,
+        config: report.config,
+        factions_scanned: report.factions_scanned,
+        explained_by_boundary: report.explained_by_boundary,
+        candidates,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::build_router;
+    use crate::api::state::AppState;
+    use crate::models::{Confidence, EpochMapper, Event, Pairing, Placement};
+    use crate::storage::StorageConfig;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use serde_json::Value;
+    use std::sync::Arc;
+    use tower::util::ServiceExt;
+
+    fn write_jsonl<T: serde::Serialize>(path: &std::path::Path, items: &[T]) {
+        let mut content = String::new();
+        for item in items {
+            content.push_str(&serde_json::to_string(item).unwrap());
+            content.push('\n');
+        }
+        std::fs::write(path, content).unwrap();
+    }
 
     async fn get_json(app: axum::Router, uri: &str) -> (StatusCode, Value) {
         let resp = app
@@ -2256,179 +4289,984 @@ mod tests {
         (status, json)
     }
 
-    fn make_event(name: &str, date: &str, source_url: &str) -> Event {
-        Event::new(
-            name.to_string(),
-            chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
-            source_url.to_string(),
-            "test".to_string(),
-            "current".into(),
+    fn make_event(name: &str, date: &str, source_url: &str) -> Event {
+        Event::new(
+            name.to_string(),
+            chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            source_url.to_string(),
+            "test".to_string(),
+            "current".into(),
+        )
+    }
+
+    fn make_placement(event: &Event, rank: u32, player: &str, faction: &str) -> Placement {
+        Placement::new(
+            event.id.clone(),
+            "current".into(),
+            rank,
+            player.to_string(),
+            faction.to_string(),
+        )
+    }
+
+    fn setup_test_state(dir: &std::path::Path) -> AppState {
+        let storage = StorageConfig::new(dir.to_path_buf());
+        let epoch_dir = dir.join("normalized").join("current");
+        std::fs::create_dir_all(&epoch_dir).unwrap();
+        AppState {
+            storage: Arc::new(storage),
+            epoch_mapper: Arc::new(tokio::sync::RwLock::new(EpochMapper::new())),
+            refresh_state: Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::refresh::RefreshState::default(),
+            )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
+            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
+            traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::traffic::TrafficStats::new(),
+            )),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_analytics_overview() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let e2 = make_event("GT Beta", "2026-01-22", "https://example.com/b");
+        let p1 = make_placement(&e1, 1, "Alice", "Aeldari");
+        let p2 = make_placement(&e1, 2, "Bob", "Necrons");
+        let p3 = make_placement(&e2, 1, "Alice", "Aeldari");
+        let p4 = make_placement(&e2, 2, "Charlie", "Orks");
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1, &e2]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2, &p3, &p4]);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/overview").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["total_events"], 2);
+        assert_eq!(json["total_placements"], 4);
+        assert_eq!(json["total_unique_players"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_analytics_overview_size_brackets() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let rtt = make_event("RTT", "2026-01-15", "https://example.com/a").with_player_count(16);
+        let gt = make_event("GT", "2026-01-22", "https://example.com/b").with_player_count(180);
+        let p1 = make_placement(&rtt, 1, "Alice", "Aeldari");
+        let p2 = make_placement(&gt, 1, "Bob", "Necrons");
+        let p3 = make_placement(&gt, 2, "Cara", "Orks");
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&rtt, &gt]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2, &p3]);
+
+        let app = build_router(state);
+        let (_, json) = get_json(app.clone(), "/api/analytics/overview").await;
+        let brackets = json["size_brackets"].as_array().unwrap();
+        assert_eq!(brackets.len(), 4);
+        assert_eq!(brackets[0]["bracket"], "small");
+        assert_eq!(brackets[0]["events"], 1);
+        assert_eq!(brackets[3]["bracket"], "major");
+        assert_eq!(brackets[3]["placements"], 2);
+
+        let (status, json) = get_json(app.clone(), "/api/analytics/overview?bracket=major").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["total_events"], 1);
+        assert_eq!(json["total_placements"], 2);
+
+        let (status, _) = get_json(app, "/api/analytics/overview?bracket=huge").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_analytics_game_size_filter() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let gt = make_event("GT", "2026-01-15", "https://example.com/a");
+        let incursion = make_event("Incursion RTT", "2026-01-22", "https://example.com/b")
+            .with_game_size(Some(1000));
+        let p1 = make_placement(&gt, 1, "Alice", "Aeldari");
+        let p2 = make_placement(&incursion, 1, "Bob", "Necrons");
+        let p3 = make_placement(&incursion, 2, "Cara", "Orks");
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&gt, &incursion]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2, &p3]);
+
+        let app = build_router(state);
+        // Unsized events count as 2000pt, the default
+        let (_, json) = get_json(app.clone(), "/api/analytics/overview").await;
+        assert_eq!(json["total_events"], 1);
+        assert_eq!(json["total_placements"], 1);
+
+        let (_, json) = get_json(app.clone(), "/api/analytics/overview?game_size=1000").await;
+        assert_eq!(json["total_placements"], 2);
+
+        let (_, json) = get_json(app.clone(), "/api/analytics/overview?game_size=any").await;
+        assert_eq!(json["total_events"], 2);
+
+        // Every analytics route leaves the 1000pt event out by default
+        let (_, json) = get_json(app.clone(), "/api/analytics/attendance").await;
+        assert_eq!(json["total_events"], 1);
+        let (_, json) = get_json(app.clone(), "/api/meta/allegiances").await;
+        assert_eq!(json["total_placements"], 1);
+        let (_, json) = get_json(app.clone(), "/api/analytics/tiers").await;
+        let tiered: Vec<&str> = json["tiers"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|t| t["factions"].as_array().unwrap())
+            .filter_map(|f| f["faction"].as_str())
+            .collect();
+        assert!(!tiered.contains(&"Necrons"));
+        let (_, json) = get_json(app.clone(), "/api/meta/allegiances?game_size=1000").await;
+        assert_eq!(json["total_placements"], 2);
+
+        let (status, _) = get_json(app, "/api/analytics/overview?game_size=big").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_analytics_overrides_exclusions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let gt = make_event("GT", "2026-01-15", "https://example.com/a");
+        let narrative = make_event("Narrative Weekend", "2026-01-22", "https://example.com/b");
+        let p1 = make_placement(&gt, 1, "Alice", "Aeldari");
+        let p2 = make_placement(&gt, 2, "Test Account", "Orks");
+        let p3 = make_placement(&narrative, 1, "Bob", "Necrons");
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&gt, &narrative]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2, &p3]);
+
+        let overrides = crate::storage::Overrides::parse(&format!(
+            "[exclude]\nevents = [\"{}\"]\nplayers = [\"test account\"]\n",
+            narrative.id.as_str()
+        ))
+        .unwrap();
+        state.storage = Arc::new(
+            StorageConfig::new(tmp.path().to_path_buf()).with_overrides(Arc::new(overrides)),
+        );
+
+        let app = build_router(state);
+        let (_, json) = get_json(app, "/api/analytics/overview").await;
+        assert_eq!(json["total_events"], 1);
+        assert_eq!(json["total_placements"], 1);
+        assert_eq!(json["most_popular_faction"]["name"], "Aeldari");
+    }
+
+    #[tokio::test]
+    async fn test_analytics_quality_and_min_confidence() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a")
+            .with_confidence(Confidence::High);
+        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_confidence(Confidence::High);
+        let p2 = make_placement(&e1, 2, "Bob", "Necrons").with_confidence(Confidence::Low);
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2]);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app.clone(), "/api/analytics/quality").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["epochs"][0]["placements"], 2);
+        assert_eq!(json["events"][0]["name"], "GT Alpha");
+        assert_eq!(json["events"][0]["list_link_rate"], 0.0);
+
+        let (_, json) = get_json(app.clone(), "/api/analytics/overview?min_confidence=high").await;
+        assert_eq!(json["total_placements"], 1);
+
+        let (status, _) = get_json(app, "/api/analytics/overview?min_confidence=sure").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_analytics_overview_empty() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        write_jsonl::<Event>(&epoch_dir.join("events.jsonl"), &[]);
+        write_jsonl::<Placement>(&epoch_dir.join("placements.jsonl"), &[]);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/overview").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["total_events"], 0);
+        assert_eq!(json["total_placements"], 0);
+        assert_eq!(json["total_unique_players"], 0);
+        assert!(json["most_popular_faction"].is_null());
+    }
+
+    fn setup_test_state_with_epoch(dir: &std::path::Path) -> AppState {
+        use crate::models::{SignificantEvent, SignificantEventType};
+        let storage = StorageConfig::new(dir.to_path_buf());
+        let sig_event = SignificantEvent::new(
+            SignificantEventType::BalanceUpdate,
+            chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            "Jan 2025 Balance".to_string(),
+            "https://example.com".to_string(),
+        );
+        let mapper = crate::models::EpochMapper::from_significant_events(&[sig_event]);
+        // Create the epoch directory using the first epoch id
+        let epoch_id = mapper.all_epochs()[0].id.as_str();
+        let epoch_dir = dir.join("normalized").join(epoch_id);
+        std::fs::create_dir_all(&epoch_dir).unwrap();
+        AppState {
+            storage: Arc::new(storage),
+            epoch_mapper: Arc::new(tokio::sync::RwLock::new(mapper)),
+            refresh_state: Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::refresh::RefreshState::default(),
+            )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
+            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
+            traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::traffic::TrafficStats::new(),
+            )),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_analytics_trends() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state_with_epoch(tmp.path());
+        let epoch_id = state.epoch_mapper.read().await.all_epochs()[0]
+            .id
+            .as_str()
+            .to_string();
+        let epoch_dir = tmp.path().join("normalized").join(&epoch_id);
+
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let p1 = make_placement(&e1, 1, "Alice", "Aeldari");
+        let p2 = make_placement(&e1, 2, "Bob", "Necrons");
+        let p3 = make_placement(&e1, 3, "Charlie", "Aeldari");
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2, &p3]);
+
+        let mut sig = vec![crate::models::SignificantEvent::new(
+            crate::models::SignificantEventType::FaqUpdate,
+            chrono::NaiveDate::from_ymd_opt(2026, 1, 10).unwrap(),
+            "Necrons FAQ".to_string(),
+            "https://example.com/faq".to_string(),
+        )
+        .with_affected_factions(vec!["Necrons".to_string()])];
+        crate::storage::write_significant_events(&state.storage, &mut sig).unwrap();
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/trends").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let factions = json["factions"].as_array().unwrap();
+        assert!(!factions.is_empty());
+        for f in factions {
+            assert!(!f["data_points"].as_array().unwrap().is_empty());
+            assert!(!f["faction"].as_str().unwrap().is_empty());
+            assert!(!f["allegiance"].as_str().unwrap().is_empty());
+        }
+        assert!(json["balance_passes"].as_array().unwrap().is_empty());
+        let faqs = json["faq_updates"].as_array().unwrap();
+        assert_eq!(faqs.len(), 1);
+        assert_eq!(faqs[0]["affected_factions"][0], "Necrons");
+    }
+
+    #[tokio::test]
+    async fn test_analytics_trends_with_faction_filter() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state_with_epoch(tmp.path());
+        let epoch_id = state.epoch_mapper.read().await.all_epochs()[0]
+            .id
+            .as_str()
+            .to_string();
+        let epoch_dir = tmp.path().join("normalized").join(&epoch_id);
+
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let p1 = make_placement(&e1, 1, "Alice", "Aeldari");
+        let p2 = make_placement(&e1, 2, "Bob", "Necrons");
+        let p3 = make_placement(&e1, 3, "Charlie", "Orks");
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2, &p3]);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/trends?factions=Aeldari,Necrons").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let factions = json["factions"].as_array().unwrap();
+        assert_eq!(factions.len(), 2);
+        let names: Vec<&str> = factions
+            .iter()
+            .map(|f| f["faction"].as_str().unwrap())
+            .collect();
+        assert!(names.contains(&"Aeldari"));
+        assert!(names.contains(&"Necrons"));
+    }
+
+    #[tokio::test]
+    async fn test_analytics_trends_empty() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        write_jsonl::<Event>(&epoch_dir.join("events.jsonl"), &[]);
+        write_jsonl::<Placement>(&epoch_dir.join("placements.jsonl"), &[]);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/trends").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(json["factions"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_analytics_units() {
+        use crate::models::{ArmyList, Unit};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let u1 = Unit::new("Leman Russ".to_string(), 2).with_points(160);
+        let u2 = Unit::new("Infantry Squad".to_string(), 10).with_points(65);
+        let list1 = ArmyList::new(
+            "Astra Militarum".to_string(),
+            2000,
+            vec![u1.clone(), u2.clone()],
+            "raw".to_string(),
+        );
+
+        let u3 = Unit::new("Leman Russ".to_string(), 1).with_points(160);
+        let list2 = ArmyList::new(
+            "Astra Militarum".to_string(),
+            2000,
+            vec![u3],
+            "raw".to_string(),
+        );
+
+        write_jsonl(&epoch_dir.join("army_lists.jsonl"), &[&list1, &list2]);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/units").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["total_lists_analysed"], 2);
+
+        let top = json["top_units"].as_array().unwrap();
+        assert!(!top.is_empty());
+        // Leman Russ appears in 2 lists, Infantry Squad in 1
+        assert_eq!(top[0]["name"], "Leman Russ");
+        assert_eq!(top[0]["lists_containing"], 2);
+        assert_eq!(top[0]["avg_points"], 160);
+    }
+
+    #[tokio::test]
+    async fn test_analytics_units_with_faction_filter() {
+        use crate::models::{ArmyList, Unit};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let list1 = ArmyList::new(
+            "Astra Militarum".to_string(),
+            2000,
+            vec![Unit::new("Leman Russ".to_string(), 1)],
+            "raw".to_string(),
+        );
+        let list2 = ArmyList::new(
+            "Aeldari".to_string(),
+            2000,
+            vec![Unit::new("Wraithlord".to_string(), 1)],
+            "raw".to_string(),
+        );
+
+        write_jsonl(&epoch_dir.join("army_lists.jsonl"), &[&list1, &list2]);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/units?faction=Astra%20Militarum").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["total_lists_analysed"], 1);
+        let top = json["top_units"].as_array().unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0]["name"], "Leman Russ");
+    }
+
+    #[tokio::test]
+    async fn test_analytics_units_empty() {
+        use crate::models::ArmyList;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        write_jsonl::<ArmyList>(&epoch_dir.join("army_lists.jsonl"), &[]);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/units").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["total_lists_analysed"], 0);
+        assert!(json["top_units"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_analytics_players_min_events_filter() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let p1 = make_placement(&e1, 1, "Alice", "Aeldari");
+        let p2 = make_placement(&e1, 2, "Bob", "Necrons");
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2]);
+
+        let app = build_router(state);
+        // min_events=2 means nobody qualifies (1 event each)
+        let (status, json) = get_json(app, "/api/analytics/players?min_events=2").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(json["players"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_analytics_players() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let e2 = make_event("GT Beta", "2026-01-22", "https://example.com/b");
+        let p1 = make_placement(&e1, 1, "Alice", "Aeldari");
+        let p2 = make_placement(&e1, 2, "Bob", "Necrons");
+        let p3 = make_placement(&e2, 1, "Alice", "Aeldari");
+        let p4 = make_placement(&e2, 3, "Bob", "Necrons");
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1, &e2]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2, &p3, &p4]);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/players?min_events=2").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let players = json["players"].as_array().unwrap();
+        assert_eq!(players.len(), 2);
+        // Alice should be first (2 wins)
+        assert_eq!(players[0]["name"], "Alice");
+        assert_eq!(players[0]["total_wins"], 2);
+        assert_eq!(players[0]["primary_faction"], "Aeldari");
+    }
+
+    // ── Win Rates Tests ─────────────────────────────────────────
+
+    /// Create filler placements to make an event look like full standings
+    /// (max rank > 20), so the win rate endpoint doesn't filter it out.
+    fn fill_event(event: &Event, start_rank: u32, count: u32) -> Vec<Placement> {
+        (0..count)
+            .map(|i| {
+                let rank = start_rank + i;
+                make_placement(event, rank, &format!("Filler{}", rank), "Orks").with_record(2, 3, 0)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_win_rates_basic() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_record(5, 1, 0);
+        let p2 = make_placement(&e1, 2, "Bob", "Necrons").with_record(3, 3, 0);
+        let p3 = make_placement(&e1, 3, "Charlie", "Aeldari").with_record(4, 2, 0);
+        let mut all_p: Vec<Placement> = vec![p1, p2, p3];
+        all_p.extend(fill_event(&e1, 4, 20));
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(
+            &epoch_dir.join("placements.jsonl"),
+            &all_p.iter().collect::<Vec<_>>(),
+        );
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/win-rates?min_games=0").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let factions = json["factions"].as_array().unwrap();
+        // Aeldari, Necrons, and Orks (filler)
+        assert!(factions.len() >= 2);
+        // Aeldari: (5+4) wins, (1+2) losses = 9/12 = 75%
+        let aeldari = factions.iter().find(|f| f["faction"] == "Aeldari").unwrap();
+        assert_eq!(aeldari["wins"], 9);
+        assert_eq!(aeldari["losses"], 3);
+        assert_eq!(aeldari["games_played"], 12);
+        assert_eq!(aeldari["win_rate"], 75.0);
+        // Necrons: 3/6 = 50%
+        let necrons = factions.iter().find(|f| f["faction"] == "Necrons").unwrap();
+        assert_eq!(necrons["win_rate"], 50.0);
+    }
+
+    #[tokio::test]
+    async fn test_win_rates_sort_by_ci_low() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        // A perfect record over 5 games vs 75% over 40
+        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_record(5, 0, 0);
+        let p2 = make_placement(&e1, 2, "Bob", "Necrons").with_record(30, 10, 0);
+        let mut all_p = vec![p1, p2];
+        all_p.extend(fill_event(&e1, 3, 20));
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(
+            &epoch_dir.join("placements.jsonl"),
+            &all_p.iter().collect::<Vec<_>>(),
+        );
+
+        let app = build_router(state);
+        let (_, json) = get_json(app.clone(), "/api/analytics/win-rates?min_games=0").await;
+        assert_eq!(json["factions"][0]["faction"], "Aeldari");
+        assert!(json["factions"][0]["ci_high"].as_f64().unwrap() <= 100.0);
+
+        let (status, json) = get_json(
+            app.clone(),
+            "/api/analytics/win-rates?min_games=0&sort=ci_low",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let factions = json["factions"].as_array().unwrap();
+        assert_eq!(factions[0]["faction"], "Necrons");
+        assert_eq!(factions[1]["faction"], "Aeldari");
+        // Wilson: 5/5 -> [56.6, 100]
+        assert_eq!(factions[1]["ci_low"], 56.6);
+
+        let (_, json) = get_json(
+            app.clone(),
+            "/api/analytics/win-rates?min_games=0&sort=ci_low&interval=jeffreys",
         )
+        .await;
+        let aeldari = json["factions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|f| f["faction"] == "Aeldari")
+            .unwrap();
+        assert!(aeldari["ci_low"].as_f64().unwrap() > 56.6);
+
+        let (status, _) = get_json(app.clone(), "/api/analytics/win-rates?interval=bayes").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = get_json(app, "/api/analytics/win-rates?sort=games").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
     }
 
-    fn make_placement(event: &Event, rank: u32, player: &str, faction: &str) -> Placement {
-        Placement::new(
-            event.id.clone(),
-            "current".into(),
-            rank,
-            player.to_string(),
-            faction.to_string(),
-        )
+    #[tokio::test]
+    async fn test_win_rates_draws_counted_half() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        // 3 wins, 1 loss, 2 draws = (3 + 0.5*2) / 6 = 4/6 = 66.7%
+        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_record(3, 1, 2);
+        let mut all_p = vec![p1];
+        all_p.extend(fill_event(&e1, 2, 22));
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(
+            &epoch_dir.join("placements.jsonl"),
+            &all_p.iter().collect::<Vec<_>>(),
+        );
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/win-rates?min_games=0").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let factions = json["factions"].as_array().unwrap();
+        let f = factions.iter().find(|f| f["faction"] == "Aeldari").unwrap();
+        assert_eq!(f["wins"], 3);
+        assert_eq!(f["draws"], 2);
+        assert_eq!(f["games_played"], 6);
+        // (3 + 0.5*2) / 6 * 100 = 66.7
+        assert_eq!(f["win_rate"], 66.7);
     }
 
-    fn setup_test_state(dir: &std::path::Path) -> AppState {
-        let storage = StorageConfig::new(dir.to_path_buf());
-        let epoch_dir = dir.join("normalized").join("current");
-        std::fs::create_dir_all(&epoch_dir).unwrap();
-        AppState {
-            storage: Arc::new(storage),
-            epoch_mapper: Arc::new(tokio::sync::RwLock::new(EpochMapper::new())),
-            refresh_state: Arc::new(tokio::sync::RwLock::new(
-                crate::api::routes::refresh::RefreshState::default(),
-            )),
-            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
-            traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
-                crate::api::routes::traffic::TrafficStats::new(),
-            )),
-        }
+    #[tokio::test]
+    async fn test_win_rates_skips_no_record() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_record(5, 1, 0);
+        let p2 = make_placement(&e1, 2, "Bob", "Necrons"); // no record
+        let mut all_p = vec![p1, p2];
+        all_p.extend(fill_event(&e1, 3, 20));
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(
+            &epoch_dir.join("placements.jsonl"),
+            &all_p.iter().collect::<Vec<_>>(),
+        );
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/win-rates?min_games=0").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let factions = json["factions"].as_array().unwrap();
+        // Necrons should NOT appear (no record), but Aeldari and Orks (filler) should
+        assert!(!factions.iter().any(|f| f["faction"] == "Necrons"));
+        assert!(factions.iter().any(|f| f["faction"] == "Aeldari"));
+    }
+
+    #[tokio::test]
+    async fn test_win_rates_empty() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        write_jsonl::<Event>(&epoch_dir.join("events.jsonl"), &[]);
+        write_jsonl::<Placement>(&epoch_dir.join("placements.jsonl"), &[]);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/win-rates").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(json["factions"].as_array().unwrap().is_empty());
+        assert_eq!(json["total_games"], 0);
+        assert_eq!(json["average_win_rate"], 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_win_rates_regression_to_mean() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        // Small sample: 5W 1L = 83.3% raw, but adjusted should be pulled toward 50%
+        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_record(5, 1, 0);
+        let mut all_p = vec![p1];
+        all_p.extend(fill_event(&e1, 2, 22));
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(
+            &epoch_dir.join("placements.jsonl"),
+            &all_p.iter().collect::<Vec<_>>(),
+        );
+
+        let app = build_router(state);
+        // min_games=40 (prior weight) — 6 real games + 40 imaginary at 50%
+        // adjusted = (5 + 40*0.5) / (6 + 40) = 25/46 = 54.3%
+        let (status, json) = get_json(app, "/api/analytics/win-rates").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let factions = json["factions"].as_array().unwrap();
+        let f = factions.iter().find(|f| f["faction"] == "Aeldari").unwrap();
+        assert_eq!(f["win_rate"], 83.3); // raw rate unchanged
+        let adjusted = f["adjusted_win_rate"].as_f64().unwrap();
+        // Should be much closer to 50% than the raw 83.3%
+        assert!(
+            adjusted > 50.0 && adjusted < 60.0,
+            "adjusted={adjusted} should be 50-60"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_win_rates_excludes_top_only_events() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        // Event with only top 4 placements (top-only source, should be excluded)
+        let e1 = make_event("Small GT", "2026-01-15", "https://example.com/a");
+        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_record(5, 0, 0);
+        let p2 = make_placement(&e1, 2, "Bob", "Aeldari").with_record(4, 1, 0);
+        let p3 = make_placement(&e1, 3, "Charlie", "Necrons").with_record(3, 2, 0);
+        let p4 = make_placement(&e1, 4, "Dave", "Necrons").with_record(3, 2, 0);
+
+        // Event with full standings (should be included)
+        let e2 = make_event("Big GT", "2026-01-22", "https://example.com/b");
+        let q1 = make_placement(&e2, 1, "Eve", "Necrons").with_record(4, 1, 0);
+        let mut all_p = vec![p1, p2, p3, p4, q1];
+        all_p.extend(fill_event(&e2, 2, 22));
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1, &e2]);
+        write_jsonl(
+            &epoch_dir.join("placements.jsonl"),
+            &all_p.iter().collect::<Vec<_>>(),
+        );
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/win-rates?min_games=0").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let factions = json["factions"].as_array().unwrap();
+        // Aeldari should NOT appear — they only exist in the top-only event
+        assert!(!factions.iter().any(|f| f["faction"] == "Aeldari"));
+        // Necrons should appear from the full event only (4W 1L from Eve)
+        let necrons = factions.iter().find(|f| f["faction"] == "Necrons").unwrap();
+        assert_eq!(necrons["wins"], 4);
+        assert_eq!(necrons["games_played"], 5);
+    }
+
+    // ── Composite Scores Tests ──────────────────────────────────
+
+    #[tokio::test]
+    async fn test_composite_scores_basic() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let e1 = make_event("Big GT", "2026-01-15", "https://example.com/a");
+        // Aeldari: strong win record
+        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_record(5, 0, 0);
+        let p2 = make_placement(&e1, 3, "Charlie", "Aeldari").with_record(3, 2, 0);
+        // Necrons: mediocre
+        let p3 = make_placement(&e1, 2, "Bob", "Necrons").with_record(3, 2, 0);
+        let p4 = make_placement(&e1, 5, "Dave", "Necrons").with_record(2, 3, 0);
+        let mut all_p: Vec<Placement> = vec![p1, p2, p3, p4];
+        all_p.extend(fill_event(&e1, 6, 20));
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(
+            &epoch_dir.join("placements.jsonl"),
+            &all_p.iter().collect::<Vec<_>>(),
+        );
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/composite-scores").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let factions = json["factions"].as_array().unwrap();
+        assert!(factions.len() >= 2);
+
+        let aeldari = factions.iter().find(|f| f["faction"] == "Aeldari").unwrap();
+        let necrons = factions.iter().find(|f| f["faction"] == "Necrons").unwrap();
+
+        // Aeldari should have higher meta_threat than Necrons
+        assert!(
+            aeldari["meta_threat"].as_f64().unwrap() > necrons["meta_threat"].as_f64().unwrap()
+        );
+        // Both should have non-zero power_index
+        assert!(aeldari["power_index"].as_f64().unwrap() > 0.0);
+        assert!(necrons["power_index"].as_f64().unwrap() >= 0.0);
+        // expected_podiums should be non-negative
+        assert!(aeldari["expected_podiums"].as_f64().unwrap() >= 0.0);
+        // Check total_placements and total_games are populated
+        assert!(json["total_placements"].as_u64().unwrap() > 0);
+        assert!(json["total_games"].as_u64().unwrap() > 0);
     }
 
     #[tokio::test]
-    async fn test_analytics_overview() {
+    async fn test_composite_scores_empty() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
-        let e2 = make_event("GT Beta", "2026-01-22", "https://example.com/b");
-        let p1 = make_placement(&e1, 1, "Alice", "Aeldari");
-        let p2 = make_placement(&e1, 2, "Bob", "Necrons");
-        let p3 = make_placement(&e2, 1, "Alice", "Aeldari");
-        let p4 = make_placement(&e2, 2, "Charlie", "Orks");
-
-        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1, &e2]);
-        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2, &p3, &p4]);
+        write_jsonl::<Event>(&epoch_dir.join("events.jsonl"), &[]);
+        write_jsonl::<Placement>(&epoch_dir.join("placements.jsonl"), &[]);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/overview").await;
+        let (status, json) = get_json(app, "/api/analytics/composite-scores").await;
 
         assert_eq!(status, StatusCode::OK);
-        assert_eq!(json["total_events"], 2);
-        assert_eq!(json["total_placements"], 4);
-        assert_eq!(json["total_unique_players"], 3);
+        assert!(json["factions"].as_array().unwrap().is_empty());
+        assert_eq!(json["total_placements"], 0);
+        assert_eq!(json["total_games"], 0);
     }
 
     #[tokio::test]
-    async fn test_analytics_overview_empty() {
+    async fn test_composite_scores_balance_deviation_sign() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        write_jsonl::<Event>(&epoch_dir.join("events.jsonl"), &[]);
-        write_jsonl::<Placement>(&epoch_dir.join("placements.jsonl"), &[]);
+        let e1 = make_event("GT Balance", "2026-01-15", "https://example.com/a");
+        // Strong faction (above 50%)
+        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_record(5, 0, 0);
+        // Weak faction (below 50%)
+        let p2 = make_placement(&e1, 10, "Bob", "Necrons").with_record(1, 4, 0);
+        let mut all_p: Vec<Placement> = vec![p1, p2];
+        all_p.extend(fill_event(&e1, 2, 22));
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(
+            &epoch_dir.join("placements.jsonl"),
+            &all_p.iter().collect::<Vec<_>>(),
+        );
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/overview").await;
+        let (status, json) = get_json(app, "/api/analytics/composite-scores").await;
 
         assert_eq!(status, StatusCode::OK);
-        assert_eq!(json["total_events"], 0);
-        assert_eq!(json["total_placements"], 0);
-        assert_eq!(json["total_unique_players"], 0);
-        assert!(json["most_popular_faction"].is_null());
-    }
+        let factions = json["factions"].as_array().unwrap();
 
-    fn setup_test_state_with_epoch(dir: &std::path::Path) -> AppState {
-        use crate::models::{SignificantEvent, SignificantEventType};
-        let storage = StorageConfig::new(dir.to_path_buf());
-        let sig_event = SignificantEvent::new(
-            SignificantEventType::BalanceUpdate,
-            chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
-            "Jan 2025 Balance".to_string(),
-            "https://example.com".to_string(),
+        let aeldari = factions.iter().find(|f| f["faction"] == "Aeldari").unwrap();
+        let necrons = factions.iter().find(|f| f["faction"] == "Necrons").unwrap();
+
+        // Aeldari: above 50% → positive balance_deviation
+        assert!(
+            aeldari["balance_deviation"].as_f64().unwrap() > 0.0,
+            "Strong faction should have positive balance deviation"
+        );
+        // Necrons: below 50% → negative balance_deviation
+        assert!(
+            necrons["balance_deviation"].as_f64().unwrap() < 0.0,
+            "Weak faction should have negative balance deviation"
         );
-        let mapper = crate::models::EpochMapper::from_significant_events(&[sig_event]);
-        // Create the epoch directory using the first epoch id
-        let epoch_id = mapper.all_epochs()[0].id.as_str();
-        let epoch_dir = dir.join("normalized").join(epoch_id);
-        std::fs::create_dir_all(&epoch_dir).unwrap();
-        AppState {
-            storage: Arc::new(storage),
-            epoch_mapper: Arc::new(tokio::sync::RwLock::new(mapper)),
-            refresh_state: Arc::new(tokio::sync::RwLock::new(
-                crate::api::routes::refresh::RefreshState::default(),
-            )),
-            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
-            traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
-                crate::api::routes::traffic::TrafficStats::new(),
-            )),
-        }
     }
 
-    #[tokio::test]
-    async fn test_analytics_trends() {
-        let tmp = tempfile::tempdir().unwrap();
-        let state = setup_test_state_with_epoch(tmp.path());
-        let epoch_id = state.epoch_mapper.read().await.all_epochs()[0]
-            .id
-            .as_str()
-            .to_string();
-        let epoch_dir = tmp.path().join("normalized").join(&epoch_id);
+    // ── Jaccard Similarity Tests ─────────────────────────────────
 
-        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
-        let p1 = make_placement(&e1, 1, "Alice", "Aeldari");
-        let p2 = make_placement(&e1, 2, "Bob", "Necrons");
-        let p3 = make_placement(&e1, 3, "Charlie", "Aeldari");
+    #[test]
+    fn test_jaccard_similarity_identical() {
+        use crate::calculate::archetypes::jaccard_similarity;
+        use std::collections::HashSet;
+        let a: HashSet<String> = ["Wraithguard", "Wave Serpent"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert!((jaccard_similarity(&a, &a) - 1.0).abs() < f64::EPSILON);
+    }
 
-        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
-        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2, &p3]);
+    #[test]
+    fn test_jaccard_similarity_disjoint() {
+        use crate::calculate::archetypes::jaccard_similarity;
+        use std::collections::HashSet;
+        let a: HashSet<String> = ["Wraithguard"].iter().map(|s| s.to_string()).collect();
+        let b: HashSet<String> = ["Wave Serpent"].iter().map(|s| s.to_string()).collect();
+        assert!((jaccard_similarity(&a, &b)).abs() < f64::EPSILON);
+    }
 
-        let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/trends").await;
+    #[test]
+    fn test_jaccard_similarity_empty() {
+        use crate::calculate::archetypes::jaccard_similarity;
+        use std::collections::HashSet;
+        let empty: HashSet<String> = HashSet::new();
+        assert!((jaccard_similarity(&empty, &empty) - 1.0).abs() < f64::EPSILON);
+    }
 
-        assert_eq!(status, StatusCode::OK);
-        let factions = json["factions"].as_array().unwrap();
-        assert!(!factions.is_empty());
-        for f in factions {
-            assert!(!f["data_points"].as_array().unwrap().is_empty());
-            assert!(!f["faction"].as_str().unwrap().is_empty());
-            assert!(!f["allegiance"].as_str().unwrap().is_empty());
-        }
+    #[test]
+    fn test_jaccard_similarity_partial() {
+        use crate::calculate::archetypes::jaccard_similarity;
+        use std::collections::HashSet;
+        let a: HashSet<String> = ["A", "B", "C"].iter().map(|s| s.to_string()).collect();
+        let b: HashSet<String> = ["B", "C", "D"].iter().map(|s| s.to_string()).collect();
+        // intersection = {B,C} = 2, union = {A,B,C,D} = 4 → 0.5
+        assert!((jaccard_similarity(&a, &b) - 0.5).abs() < f64::EPSILON);
     }
 
+    // ── Detachment Stats Tests ──────────────────────────────────
+
     #[tokio::test]
-    async fn test_analytics_trends_with_faction_filter() {
+    async fn test_detachment_stats_basic() {
+        use crate::models::{ArmyList, Unit};
+
         let tmp = tempfile::tempdir().unwrap();
-        let state = setup_test_state_with_epoch(tmp.path());
-        let epoch_id = state.epoch_mapper.read().await.all_epochs()[0]
-            .id
-            .as_str()
-            .to_string();
-        let epoch_dir = tmp.path().join("normalized").join(&epoch_id);
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
 
         let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
-        let p1 = make_placement(&e1, 1, "Alice", "Aeldari");
-        let p2 = make_placement(&e1, 2, "Bob", "Necrons");
-        let p3 = make_placement(&e1, 3, "Charlie", "Orks");
+        let list1 = ArmyList::new(
+            "Aeldari".to_string(),
+            2000,
+            vec![Unit::new("Wraithguard".to_string(), 5)],
+            "raw".to_string(),
+        )
+        .with_detachment("Seer Council".to_string())
+        .with_player_name("Alice".to_string())
+        .with_event_id(e1.id.clone());
+
+        let list2 = ArmyList::new(
+            "Aeldari".to_string(),
+            2000,
+            vec![Unit::new("Fire Prism".to_string(), 1)],
+            "raw".to_string(),
+        )
+        .with_detachment("Seer Council".to_string())
+        .with_player_name("Bob".to_string())
+        .with_event_id(e1.id.clone());
+
+        let list3 = ArmyList::new(
+            "Aeldari".to_string(),
+            2000,
+            vec![Unit::new("Wraithknight".to_string(), 1)],
+            "raw".to_string(),
+        )
+        .with_detachment("Seer Council".to_string())
+        .with_player_name("Charlie".to_string())
+        .with_event_id(e1.id.clone());
+
+        let mut p1 = make_placement(&e1, 1, "Alice", "Aeldari");
+        p1.list_id = Some(list1.id.clone());
+        p1 = p1.with_record(5, 0, 0);
+        let mut p2 = make_placement(&e1, 2, "Bob", "Aeldari");
+        p2.list_id = Some(list2.id.clone());
+        p2 = p2.with_record(4, 1, 0);
+        let mut p3 = make_placement(&e1, 3, "Charlie", "Aeldari");
+        p3.list_id = Some(list3.id.clone());
+        p3 = p3.with_record(3, 2, 0);
 
         write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
         write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2, &p3]);
+        write_jsonl(
+            &epoch_dir.join("army_lists.jsonl"),
+            &[&list1, &list2, &list3],
+        );
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/trends?factions=Aeldari,Necrons").await;
+        let (status, json) = get_json(
+            app,
+            "/api/analytics/detachments?faction=Aeldari&min_count=1",
+        )
+        .await;
 
         assert_eq!(status, StatusCode::OK);
-        let factions = json["factions"].as_array().unwrap();
-        assert_eq!(factions.len(), 2);
-        let names: Vec<&str> = factions
+        let detachments = json["detachments"].as_array().unwrap();
+        assert!(!detachments.is_empty());
+        let sc = detachments
             .iter()
-            .map(|f| f["faction"].as_str().unwrap())
-            .collect();
-        assert!(names.contains(&"Aeldari"));
-        assert!(names.contains(&"Necrons"));
+            .find(|d| d["detachment"] == "Seer Council")
+            .unwrap();
+        assert_eq!(sc["count"], 3);
+        assert_eq!(sc["faction"], "Aeldari");
     }
 
     #[tokio::test]
-    async fn test_analytics_trends_empty() {
+    async fn test_detachment_stats_empty() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
@@ -2437,504 +5275,622 @@ mod tests {
         write_jsonl::<Placement>(&epoch_dir.join("placements.jsonl"), &[]);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/trends").await;
+        let (status, json) = get_json(app, "/api/analytics/detachments").await;
 
         assert_eq!(status, StatusCode::OK);
-        assert!(json["factions"].as_array().unwrap().is_empty());
+        assert!(json["detachments"].as_array().unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn test_analytics_units() {
-        use crate::models::{ArmyList, Unit};
+    async fn test_faction_detachments_uses_pairings() {
+        use crate::models::Pairing;
 
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        let u1 = Unit::new("Leman Russ".to_string(), 2).with_points(160);
-        let u2 = Unit::new("Infantry Squad".to_string(), 10).with_points(65);
-        let list1 = ArmyList::new(
-            "Astra Militarum".to_string(),
-            2000,
-            vec![u1.clone(), u2.clone()],
-            "raw".to_string(),
-        );
-
-        let u3 = Unit::new("Leman Russ".to_string(), 1).with_points(160);
-        let list2 = ArmyList::new(
-            "Astra Militarum".to_string(),
-            2000,
-            vec![u3],
-            "raw".to_string(),
-        );
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_detachment("Seer Council".into());
+        let p2 =
+            make_placement(&e1, 2, "Bob", "Necrons").with_detachment("Awakened Dynasty".into());
+        let p3 = make_placement(&e1, 3, "Cara", "Aeldari").with_detachment("Battle Host".into());
+        let mut pairings = Vec::new();
+        for (round, winner, loser) in [
+            (1, "Alice", "Bob"),
+            (2, "Alice", "Cara"),
+            (3, "Bob", "Cara"),
+        ] {
+            let mut p = Pairing::new(
+                e1.id.clone(),
+                "current".into(),
+                round,
+                winner.to_string(),
+                loser.to_string(),
+            );
+            p.player1_result = Some("win".to_string());
+            pairings.push(p);
+        }
 
-        write_jsonl(&epoch_dir.join("army_lists.jsonl"), &[&list1, &list2]);
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2, &p3]);
+        write_jsonl(&epoch_dir.join("pairings.jsonl"), &pairings);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/units").await;
-
+        let (status, json) = get_json(app.clone(), "/api/analytics/detachments/Aeldari").await;
         assert_eq!(status, StatusCode::OK);
-        assert_eq!(json["total_lists_analysed"], 2);
+        assert_eq!(json["players"], 2);
+        let detachments = json["detachments"].as_array().unwrap();
+        let seer = detachments
+            .iter()
+            .find(|d| d["detachment"] == "Seer Council")
+            .unwrap();
+        assert_eq!(seer["games"], 2);
+        assert_eq!(seer["wins"], 2);
+        assert_eq!(seer["game_win_rate"], 100.0);
+        assert_eq!(seer["meta_share"], 50.0);
+        assert_eq!(seer["trend"][0]["epoch_id"], "current");
+
+        let (_, json) = get_json(app.clone(), "/api/analytics/detachments?min_count=1").await;
+        let host = json["detachments"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|d| d["detachment"] == "Battle Host")
+            .unwrap();
+        assert_eq!(host["games"], 2);
+        assert_eq!(host["game_win_rate"], 0.0);
 
-        let top = json["top_units"].as_array().unwrap();
-        assert!(!top.is_empty());
-        // Leman Russ appears in 2 lists, Infantry Squad in 1
-        assert_eq!(top[0]["name"], "Leman Russ");
-        assert_eq!(top[0]["lists_containing"], 2);
-        assert_eq!(top[0]["avg_points"], 160);
+        let (status, _) = get_json(app, "/api/analytics/detachments/Orks").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
     }
 
+    // ── Unit Performance Tests ──────────────────────────────────
+
     #[tokio::test]
-    async fn test_analytics_units_with_faction_filter() {
+    async fn test_unit_performance_basic() {
         use crate::models::{ArmyList, Unit};
 
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+
         let list1 = ArmyList::new(
-            "Astra Militarum".to_string(),
+            "Aeldari".to_string(),
             2000,
-            vec![Unit::new("Leman Russ".to_string(), 1)],
+            vec![
+                Unit::new("Wraithguard".to_string(), 5).with_points(180),
+                Unit::new("Wave Serpent".to_string(), 1).with_points(120),
+            ],
             "raw".to_string(),
-        );
+        )
+        .with_player_name("Alice".to_string())
+        .with_event_id(e1.id.clone());
+
         let list2 = ArmyList::new(
             "Aeldari".to_string(),
             2000,
-            vec![Unit::new("Wraithlord".to_string(), 1)],
+            vec![
+                Unit::new("Wraithguard".to_string(), 5).with_points(180),
+                Unit::new("Fire Prism".to_string(), 1).with_points(150),
+            ],
             "raw".to_string(),
-        );
+        )
+        .with_player_name("Bob".to_string())
+        .with_event_id(e1.id.clone());
+
+        let mut p1 = make_placement(&e1, 1, "Alice", "Aeldari");
+        p1.list_id = Some(list1.id.clone());
+        p1 = p1.with_record(5, 0, 0);
+        let mut p2 = make_placement(&e1, 8, "Bob", "Aeldari");
+        p2.list_id = Some(list2.id.clone());
+        p2 = p2.with_record(2, 3, 0);
 
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2]);
         write_jsonl(&epoch_dir.join("army_lists.jsonl"), &[&list1, &list2]);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/units?faction=Astra%20Militarum").await;
+        let (status, json) = get_json(
+            app,
+            "/api/analytics/unit-performance?faction=Aeldari&min_appearances=1",
+        )
+        .await;
 
         assert_eq!(status, StatusCode::OK);
-        assert_eq!(json["total_lists_analysed"], 1);
-        let top = json["top_units"].as_array().unwrap();
-        assert_eq!(top.len(), 1);
-        assert_eq!(top[0]["name"], "Leman Russ");
+        let units = json["units"].as_array().unwrap();
+        assert!(!units.is_empty());
+        // Wraithguard appears in both lists
+        let wg = units.iter().find(|u| u["name"] == "Wraithguard");
+        assert!(wg.is_some());
+        assert_eq!(wg.unwrap()["total_lists"], 2);
     }
 
     #[tokio::test]
-    async fn test_analytics_units_empty() {
-        use crate::models::ArmyList;
-
+    async fn test_unit_performance_empty() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        write_jsonl::<ArmyList>(&epoch_dir.join("army_lists.jsonl"), &[]);
+        write_jsonl::<Event>(&epoch_dir.join("events.jsonl"), &[]);
+        write_jsonl::<Placement>(&epoch_dir.join("placements.jsonl"), &[]);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/units").await;
+        let (status, json) = get_json(app, "/api/analytics/unit-performance").await;
 
         assert_eq!(status, StatusCode::OK);
-        assert_eq!(json["total_lists_analysed"], 0);
-        assert!(json["top_units"].as_array().unwrap().is_empty());
+        assert!(json["units"].as_array().unwrap().is_empty());
     }
 
+    // ── Points Efficiency Tests ─────────────────────────────────
+
     #[tokio::test]
-    async fn test_analytics_players_min_events_filter() {
+    async fn test_points_efficiency_basic() {
+        use crate::models::{ArmyList, Unit};
+
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
         let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
-        let p1 = make_placement(&e1, 1, "Alice", "Aeldari");
-        let p2 = make_placement(&e1, 2, "Bob", "Necrons");
+
+        // Need >=3 appearances per unit for the filter
+        let mut lists = Vec::new();
+        let mut placements_vec = Vec::new();
+        for (i, name) in ["Alice", "Bob", "Charlie"].iter().enumerate() {
+            let list = ArmyList::new(
+                "Aeldari".to_string(),
+                2000,
+                vec![Unit::new("Wraithguard".to_string(), 5).with_points(180)],
+                format!("raw{}", i),
+            )
+            .with_player_name(name.to_string())
+            .with_event_id(e1.id.clone());
+
+            let mut p = make_placement(&e1, (i + 1) as u32, name, "Aeldari");
+            p.list_id = Some(list.id.clone());
+            p = p.with_record(4, 1, 0);
+            placements_vec.push(p);
+            lists.push(list);
+        }
 
         write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
-        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2]);
+        write_jsonl(
+            &epoch_dir.join("placements.jsonl"),
+            &placements_vec.iter().collect::<Vec<_>>(),
+        );
+        write_jsonl(
+            &epoch_dir.join("army_lists.jsonl"),
+            &lists.iter().collect::<Vec<_>>(),
+        );
 
         let app = build_router(state);
-        // min_events=2 means nobody qualifies (1 event each)
-        let (status, json) = get_json(app, "/api/analytics/players?min_events=2").await;
+        let (status, json) =
+            get_json(app, "/api/analytics/points-efficiency?faction=Aeldari").await;
 
         assert_eq!(status, StatusCode::OK);
-        assert!(json["players"].as_array().unwrap().is_empty());
+        let units = json["units"].as_array().unwrap();
+        assert!(!units.is_empty());
+        assert_eq!(units[0]["unit_name"], "Wraithguard");
+        assert_eq!(units[0]["avg_points"], 180);
+        assert_eq!(units[0]["appearances"], 3);
     }
 
     #[tokio::test]
-    async fn test_analytics_players() {
+    async fn test_points_efficiency_empty() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
-        let e2 = make_event("GT Beta", "2026-01-22", "https://example.com/b");
-        let p1 = make_placement(&e1, 1, "Alice", "Aeldari");
-        let p2 = make_placement(&e1, 2, "Bob", "Necrons");
-        let p3 = make_placement(&e2, 1, "Alice", "Aeldari");
-        let p4 = make_placement(&e2, 3, "Bob", "Necrons");
-
-        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1, &e2]);
-        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2, &p3, &p4]);
+        write_jsonl::<Event>(&epoch_dir.join("events.jsonl"), &[]);
+        write_jsonl::<Placement>(&epoch_dir.join("placements.jsonl"), &[]);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/players?min_events=2").await;
+        let (status, json) = get_json(app, "/api/analytics/points-efficiency").await;
 
         assert_eq!(status, StatusCode::OK);
-        let players = json["players"].as_array().unwrap();
-        assert_eq!(players.len(), 2);
-        // Alice should be first (2 wins)
-        assert_eq!(players[0]["name"], "Alice");
-        assert_eq!(players[0]["total_wins"], 2);
-        assert_eq!(players[0]["primary_faction"], "Aeldari");
+        assert!(json["units"].as_array().unwrap().is_empty());
     }
 
-    // ── Win Rates Tests ─────────────────────────────────────────
-
-    /// Create filler placements to make an event look like full standings
-    /// (max rank > 20), so the win rate endpoint doesn't filter it out.
-    fn fill_event(event: &Event, start_rank: u32, count: u32) -> Vec<Placement> {
-        (0..count)
-            .map(|i| {
-                let rank = start_rank + i;
-                make_placement(event, rank, &format!("Filler{}", rank), "Orks").with_record(2, 3, 0)
-            })
-            .collect()
-    }
+    // ── Teams Tests ─────────────────────────────────────────────
 
     #[tokio::test]
-    async fn test_win_rates_basic() {
+    async fn test_teams_ranked_by_top4() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
         let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
-        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_record(5, 1, 0);
-        let p2 = make_placement(&e1, 2, "Bob", "Necrons").with_record(3, 3, 0);
-        let p3 = make_placement(&e1, 3, "Charlie", "Aeldari").with_record(4, 2, 0);
-        let mut all_p: Vec<Placement> = vec![p1, p2, p3];
-        all_p.extend(fill_event(&e1, 4, 20));
-
-        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
-        write_jsonl(
-            &epoch_dir.join("placements.jsonl"),
-            &all_p.iter().collect::<Vec<_>>(),
-        );
+        let e2 = make_event("GT Beta", "2026-01-22", "https://example.com/b");
+        let placements = vec![
+            make_placement(&e1, 1, "Alice", "Aeldari")
+                .with_record(5, 0, 0)
+                .with_team(Some("Vanguard Tactics")),
+            make_placement(&e1, 2, "Bob", "Orks")
+                .with_record(4, 1, 0)
+                .with_team(Some("Article 40k")),
+            make_placement(&e1, 3, "Carol", "Orks")
+                .with_record(3, 2, 0)
+                .with_team(Some("article 40k")),
+            make_placement(&e2, 4, "Bob", "Orks")
+                .with_record(3, 2, 0)
+                .with_team(Some("Article 40k")),
+            make_placement(&e2, 9, "Dave", "Necrons")
+                .with_record(1, 4, 0)
+                .with_team(Some("Vanguard Tactics")),
+            // Solo team: below min_players
+            make_placement(&e2, 1, "Eve", "Necrons")
+                .with_record(5, 0, 0)
+                .with_team(Some("Lone Wolves")),
+            make_placement(&e2, 2, "Frank", "Orks").with_record(4, 1, 0),
+        ];
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1, &e2]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/win-rates?min_games=0").await;
-
+        let (status, json) = get_json(app.clone(), "/api/analytics/teams").await;
         assert_eq!(status, StatusCode::OK);
-        let factions = json["factions"].as_array().unwrap();
-        // Aeldari, Necrons, and Orks (filler)
-        assert!(factions.len() >= 2);
-        // Aeldari: (5+4) wins, (1+2) losses = 9/12 = 75%
-        let aeldari = factions.iter().find(|f| f["faction"] == "Aeldari").unwrap();
-        assert_eq!(aeldari["wins"], 9);
-        assert_eq!(aeldari["losses"], 3);
-        assert_eq!(aeldari["games_played"], 12);
-        assert_eq!(aeldari["win_rate"], 75.0);
-        // Necrons: 3/6 = 50%
-        let necrons = factions.iter().find(|f| f["faction"] == "Necrons").unwrap();
-        assert_eq!(necrons["win_rate"], 50.0);
+        assert_eq!(json["total_teams"], 3);
+        let teams = json["teams"].as_array().unwrap();
+        assert_eq!(teams.len(), 2);
+        assert_eq!(teams[0]["team"], "Article 40k");
+        assert_eq!(teams[0]["top4"], 3);
+        assert_eq!(teams[0]["players"], 2);
+        assert_eq!(teams[0]["events"], 2);
+        assert_eq!(teams[0]["factions"], serde_json::json!(["Orks"]));
+
+        let (_, json) = get_json(app.clone(), "/api/analytics/teams?sort=wins").await;
+        assert_eq!(json["teams"][0]["team"], "Vanguard Tactics");
+
+        let (status, _) = get_json(app, "/api/analytics/teams?sort=elo").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
     }
 
+    // ── Missions Tests ──────────────────────────────────────────
+
     #[tokio::test]
-    async fn test_win_rates_draws_counted_half() {
+    async fn test_missions_splits_faction_records_by_pack() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
-        // 3 wins, 1 loss, 2 draws = (3 + 0.5*2) / 6 = 4/6 = 66.7%
-        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_record(3, 1, 2);
-        let mut all_p = vec![p1];
-        all_p.extend(fill_event(&e1, 2, 22));
-
-        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        let pariah = make_event("GT Alpha", "2026-01-15", "https://example.com/a")
+            .with_mission_pack(Some("Pariah Nexus".into()));
+        let leviathan = make_event("GT Beta", "2026-01-22", "https://example.com/b")
+            .with_mission_pack(Some("leviathan missions".into()));
+        let unknown = make_event("GT Gamma", "2026-01-29", "https://example.com/c");
+
+        let placements = vec![
+            make_placement(&pariah, 1, "Alice", "Aeldari").with_record(5, 0, 0),
+            make_placement(&pariah, 2, "Bob", "Orks").with_record(3, 2, 0),
+            make_placement(&leviathan, 1, "Carol", "Orks").with_record(4, 1, 0),
+            make_placement(&leviathan, 2, "Dave", "Aeldari").with_record(1, 4, 0),
+            make_placement(&unknown, 1, "Eve", "Aeldari").with_record(4, 1, 0),
+        ];
         write_jsonl(
-            &epoch_dir.join("placements.jsonl"),
-            &all_p.iter().collect::<Vec<_>>(),
+            &epoch_dir.join("events.jsonl"),
+            &[&pariah, &leviathan, &unknown],
         );
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/win-rates?min_games=0").await;
+        let (status, json) = get_json(app, "/api/analytics/missions?min_games=5").await;
 
         assert_eq!(status, StatusCode::OK);
-        let factions = json["factions"].as_array().unwrap();
-        let f = factions.iter().find(|f| f["faction"] == "Aeldari").unwrap();
-        assert_eq!(f["wins"], 3);
-        assert_eq!(f["draws"], 2);
-        assert_eq!(f["games_played"], 6);
-        // (3 + 0.5*2) / 6 * 100 = 66.7
-        assert_eq!(f["win_rate"], 66.7);
+        assert_eq!(json["events_without_pack"], 1);
+        let packs = json["packs"].as_array().unwrap();
+        assert_eq!(packs.len(), 2);
+        let leviathan = packs
+            .iter()
+            .find(|p| p["mission_pack"] == "Leviathan")
+            .unwrap();
+        assert_eq!(leviathan["events"], 1);
+        let factions = leviathan["factions"].as_array().unwrap();
+        assert_eq!(factions[0]["faction"], "Orks");
+        assert_eq!(factions[0]["win_rate"], 80.0);
+        // Aeldari went 10-5 overall but 1-4 on Leviathan
+        assert_eq!(factions[1]["overall_win_rate"], 66.7);
+        assert_eq!(factions[1]["delta"], -46.7);
     }
 
+    // ── Matchups Tests ──────────────────────────────────────────
+
     #[tokio::test]
-    async fn test_win_rates_skips_no_record() {
+    async fn test_matchups_basic() {
+        use crate::models::Pairing;
+
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
         let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
-        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_record(5, 1, 0);
-        let p2 = make_placement(&e1, 2, "Bob", "Necrons"); // no record
-        let mut all_p = vec![p1, p2];
-        all_p.extend(fill_event(&e1, 3, 20));
-
-        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
-        write_jsonl(
-            &epoch_dir.join("placements.jsonl"),
-            &all_p.iter().collect::<Vec<_>>(),
-        );
-
-        let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/win-rates?min_games=0").await;
-
-        assert_eq!(status, StatusCode::OK);
-        let factions = json["factions"].as_array().unwrap();
-        // Necrons should NOT appear (no record), but Aeldari and Orks (filler) should
-        assert!(!factions.iter().any(|f| f["faction"] == "Necrons"));
-        assert!(factions.iter().any(|f| f["faction"] == "Aeldari"));
-    }
 
-    #[tokio::test]
-    async fn test_win_rates_empty() {
-        let tmp = tempfile::tempdir().unwrap();
-        let state = setup_test_state(tmp.path());
-        let epoch_dir = tmp.path().join("normalized").join("current");
+        // Create pairings: Aeldari vs Necrons, 3 wins for Aeldari, 2 for Necrons
+        let mut pairings = Vec::new();
+        for i in 0..3u32 {
+            let mut p = Pairing::new(
+                e1.id.clone(),
+                "current".into(),
+                i + 1,
+                format!("Aeldari{}", i),
+                format!("Necron{}", i),
+            );
+            p.player1_faction = Some("Aeldari".to_string());
+            p.player2_faction = Some("Necrons".to_string());
+            p.player1_result = Some("win".to_string());
+            pairings.push(p);
+        }
+        for i in 0..2u32 {
+            let mut p = Pairing::new(
+                e1.id.clone(),
+                "current".into(),
+                i + 4,
+                format!("NecronWin{}", i),
+                format!("AeldariLoss{}", i),
+            );
+            p.player1_faction = Some("Necrons".to_string());
+            p.player2_faction = Some("Aeldari".to_string());
+            p.player1_result = Some("win".to_string());
+            pairings.push(p);
+        }
 
-        write_jsonl::<Event>(&epoch_dir.join("events.jsonl"), &[]);
-        write_jsonl::<Placement>(&epoch_dir.join("placements.jsonl"), &[]);
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(
+            &epoch_dir.join("pairings.jsonl"),
+            &pairings.iter().collect::<Vec<_>>(),
+        );
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/win-rates").await;
+        let (status, json) = get_json(app, "/api/analytics/matchups?min_games=1").await;
 
         assert_eq!(status, StatusCode::OK);
-        assert!(json["factions"].as_array().unwrap().is_empty());
-        assert_eq!(json["total_games"], 0);
-        assert_eq!(json["average_win_rate"], 0.0);
+        let matchups = json["matchups"].as_array().unwrap();
+        assert!(!matchups.is_empty());
+        assert_eq!(json["factions"].as_array().unwrap().len(), 2);
+        // Total games should be 5
+        assert_eq!(matchups[0]["total_games"], 5);
     }
 
     #[tokio::test]
-    async fn test_win_rates_regression_to_mean() {
+    async fn test_matchup_matrix_marks_small_samples() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
         let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
-        // Small sample: 5W 1L = 83.3% raw, but adjusted should be pulled toward 50%
-        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_record(5, 1, 0);
-        let mut all_p = vec![p1];
-        all_p.extend(fill_event(&e1, 2, 22));
-
-        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
-        write_jsonl(
-            &epoch_dir.join("placements.jsonl"),
-            &all_p.iter().collect::<Vec<_>>(),
+        let mut pairings = Vec::new();
+        for round in 1..=4u32 {
+            let mut p = Pairing::new(
+                e1.id.clone(),
+                "current".into(),
+                round,
+                "Alice".to_string(),
+                "Bob".to_string(),
+            );
+            p.player1_result = Some(if round == 4 { "loss" } else { "win" }.to_string());
+            pairings.push(p);
+        }
+        let mut other = Pairing::new(
+            e1.id.clone(),
+            "current".into(),
+            5,
+            "Carol".to_string(),
+            "Bob".to_string(),
         );
+        other.player1_faction = Some("Orks".to_string());
+        other.player1_result = Some("draw".to_string());
+        pairings.push(other);
+
+        let placements = vec![
+            make_placement(&e1, 1, "Alice", "Aeldari"),
+            make_placement(&e1, 2, "Bob", "Necrons"),
+        ];
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(&epoch_dir.join("pairings.jsonl"), &pairings);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
 
         let app = build_router(state);
-        // min_games=40 (prior weight) — 6 real games + 40 imaginary at 50%
-        // adjusted = (5 + 40*0.5) / (6 + 40) = 25/46 = 54.3%
-        let (status, json) = get_json(app, "/api/analytics/win-rates").await;
+        let (status, json) = get_json(app, "/api/analytics/matchup-matrix?min_games=3").await;
 
         assert_eq!(status, StatusCode::OK);
-        let factions = json["factions"].as_array().unwrap();
-        let f = factions.iter().find(|f| f["faction"] == "Aeldari").unwrap();
-        assert_eq!(f["win_rate"], 83.3); // raw rate unchanged
-        let adjusted = f["adjusted_win_rate"].as_f64().unwrap();
-        // Should be much closer to 50% than the raw 83.3%
-        assert!(
-            adjusted > 50.0 && adjusted < 60.0,
-            "adjusted={adjusted} should be 50-60"
+        assert_eq!(
+            json["factions"],
+            serde_json::json!(["Aeldari", "Necrons", "Orks"])
         );
+        assert!(json["cells"][0][0].is_null());
+        assert_eq!(json["cells"][0][1]["wins"], 3);
+        assert_eq!(json["cells"][0][1]["win_rate"], 75.0);
+        assert_eq!(json["cells"][1][0]["win_rate"], 25.0);
+        assert_eq!(json["cells"][2][1]["draws"], 1);
+        assert_eq!(json["cells"][2][1]["insufficient_data"], true);
+        assert!(json["cells"][2][1]["win_rate"].is_null());
     }
 
     #[tokio::test]
-    async fn test_win_rates_excludes_top_only_events() {
+    async fn test_sos_and_players_schedule() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        // Event with only top 4 placements (top-only source, should be excluded)
-        let e1 = make_event("Small GT", "2026-01-15", "https://example.com/a");
-        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_record(5, 0, 0);
-        let p2 = make_placement(&e1, 2, "Bob", "Aeldari").with_record(4, 1, 0);
-        let p3 = make_placement(&e1, 3, "Charlie", "Necrons").with_record(3, 2, 0);
-        let p4 = make_placement(&e1, 4, "Dave", "Necrons").with_record(3, 2, 0);
-
-        // Event with full standings (should be included)
-        let e2 = make_event("Big GT", "2026-01-22", "https://example.com/b");
-        let q1 = make_placement(&e2, 1, "Eve", "Necrons").with_record(4, 1, 0);
-        let mut all_p = vec![p1, p2, p3, p4, q1];
-        all_p.extend(fill_event(&e2, 2, 22));
-
-        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1, &e2]);
-        write_jsonl(
-            &epoch_dir.join("placements.jsonl"),
-            &all_p.iter().collect::<Vec<_>>(),
-        );
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let mut pairings = Vec::new();
+        for round in 1..=4u32 {
+            let mut p = Pairing::new(
+                e1.id.clone(),
+                "current".into(),
+                round,
+                "Alice".to_string(),
+                "Bob".to_string(),
+            );
+            p.player1_result = Some(if round == 4 { "loss" } else { "win" }.to_string());
+            pairings.push(p);
+        }
+        let placements = vec![
+            make_placement(&e1, 1, "Alice", "Aeldari"),
+            make_placement(&e1, 2, "Bob", "Necrons"),
+        ];
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(&epoch_dir.join("pairings.jsonl"), &pairings);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/win-rates?min_games=0").await;
-
+        let (status, json) = get_json(app.clone(), "/api/analytics/sos?min_games=4").await;
         assert_eq!(status, StatusCode::OK);
-        let factions = json["factions"].as_array().unwrap();
-        // Aeldari should NOT appear — they only exist in the top-only event
-        assert!(!factions.iter().any(|f| f["faction"] == "Aeldari"));
-        // Necrons should appear from the full event only (4W 1L from Eve)
-        let necrons = factions.iter().find(|f| f["faction"] == "Necrons").unwrap();
-        assert_eq!(necrons["wins"], 4);
-        assert_eq!(necrons["games_played"], 5);
-    }
+        assert_eq!(json["total_players"], 2);
+        assert_eq!(json["players"][0]["player"], "Alice");
+        // Round 1 weighs 1, later rounds 0.5 against a winless Bob
+        assert_eq!(json["players"][0]["weighted_win_rate"], 80.0);
+        assert_eq!(json["players"][0]["win_rate"], 75.0);
+        assert_eq!(json["factions"][0]["faction"], "Aeldari");
+        assert_eq!(json["factions"][0]["rounds"][3]["losses"], 1);
+
+        let (_, json) = get_json(app.clone(), "/api/analytics/players?min_events=1").await;
+        let alice = json["players"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|p| p["name"] == "Alice")
+            .unwrap();
+        assert_eq!(alice["games"], 4);
+        assert_eq!(alice["weighted_win_rate"], 80.0);
 
-    // ── Composite Scores Tests ──────────────────────────────────
+        let (status, _) = get_json(app, "/api/analytics/sos?sort=rank").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
 
     #[tokio::test]
-    async fn test_composite_scores_basic() {
+    async fn test_head_to_head_joins_placement_factions() {
+        use crate::models::Pairing;
+
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        let e1 = make_event("Big GT", "2026-01-15", "https://example.com/a");
-        // Aeldari: strong win record
-        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_record(5, 0, 0);
-        let p2 = make_placement(&e1, 3, "Charlie", "Aeldari").with_record(3, 2, 0);
-        // Necrons: mediocre
-        let p3 = make_placement(&e1, 2, "Bob", "Necrons").with_record(3, 2, 0);
-        let p4 = make_placement(&e1, 5, "Dave", "Necrons").with_record(2, 3, 0);
-        let mut all_p: Vec<Placement> = vec![p1, p2, p3, p4];
-        all_p.extend(fill_event(&e1, 6, 20));
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let pairing = |round: u32, p1: &str, p2: &str, result: &str| {
+            let mut p = Pairing::new(
+                e1.id.clone(),
+                "current".into(),
+                round,
+                p1.to_string(),
+                p2.to_string(),
+            );
+            p.player1_result = Some(result.to_string());
+            p
+        };
+
+        let mut pairings = Vec::new();
+        for i in 0..3u32 {
+            let mut p = pairing(i + 1, &format!("Eldar{}", i), &format!("Crons{}", i), "win");
+            p.player1_faction = Some("Aeldari".to_string());
+            p.player2_faction = Some("Necrons".to_string());
+            pairings.push(p);
+        }
+        // No factions on the pairing: resolved from placements
+        pairings.push(pairing(4, "Alice", "Bob", "loss"));
+        let mut draw = pairing(5, "Carol", "Dave", "draw");
+        draw.player1_faction = Some("Necrons".to_string());
+        draw.player2_faction = Some("Aeldari".to_string());
+        pairings.push(draw);
+        // Unrelated matchup
+        let mut other = pairing(6, "Eve", "Frank", "win");
+        other.player1_faction = Some("Orks".to_string());
+        other.player2_faction = Some("Aeldari".to_string());
+        pairings.push(other);
+
+        let placements = vec![
+            make_placement(&e1, 1, "Bob", "Necrons"),
+            make_placement(&e1, 2, "alice", "Aeldari"),
+        ];
 
         write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
-        write_jsonl(
-            &epoch_dir.join("placements.jsonl"),
-            &all_p.iter().collect::<Vec<_>>(),
-        );
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
+        write_jsonl(&epoch_dir.join("pairings.jsonl"), &pairings);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/composite-scores").await;
+        let (status, json) = get_json(
+            app.clone(),
+            "/api/analytics/head-to-head?faction_a=necrons&faction_b=Aeldari",
+        )
+        .await;
 
         assert_eq!(status, StatusCode::OK);
-        let factions = json["factions"].as_array().unwrap();
-        assert!(factions.len() >= 2);
-
-        let aeldari = factions.iter().find(|f| f["faction"] == "Aeldari").unwrap();
-        let necrons = factions.iter().find(|f| f["faction"] == "Necrons").unwrap();
-
-        // Aeldari should have higher meta_threat than Necrons
-        assert!(
-            aeldari["meta_threat"].as_f64().unwrap() > necrons["meta_threat"].as_f64().unwrap()
+        assert_eq!(json["faction_a"], "Necrons");
+        assert_eq!(json["games"], 5);
+        assert_eq!(json["faction_a_wins"], 1);
+        assert_eq!(json["faction_b_wins"], 3);
+        assert_eq!(json["draws"], 1);
+        assert_eq!(json["faction_a_win_rate"], 20.0);
+        let (low, high) = (
+            json["ci_low"].as_f64().unwrap(),
+            json["ci_high"].as_f64().unwrap(),
         );
-        // Both should have non-zero power_index
-        assert!(aeldari["power_index"].as_f64().unwrap() > 0.0);
-        assert!(necrons["power_index"].as_f64().unwrap() >= 0.0);
-        // expected_podiums should be non-negative
-        assert!(aeldari["expected_podiums"].as_f64().unwrap() >= 0.0);
-        // Check total_placements and total_games are populated
-        assert!(json["total_placements"].as_u64().unwrap() > 0);
-        assert!(json["total_games"].as_u64().unwrap() > 0);
+        assert!(low > 0.0 && low < 20.0);
+        assert!(high > 60.0 && high < 70.0);
+        let epochs = json["epochs"].as_array().unwrap();
+        assert_eq!(epochs.len(), 1);
+        assert_eq!(epochs[0]["epoch_id"], "current");
+        assert_eq!(epochs[0]["games"], 5);
+
+        let (status, _) = get_json(
+            app,
+            "/api/analytics/head-to-head?faction_a=Orks&faction_b=orks",
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
     }
 
     #[tokio::test]
-    async fn test_composite_scores_empty() {
+    async fn test_matchups_empty() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
         write_jsonl::<Event>(&epoch_dir.join("events.jsonl"), &[]);
-        write_jsonl::<Placement>(&epoch_dir.join("placements.jsonl"), &[]);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/composite-scores").await;
+        let (status, json) = get_json(app, "/api/analytics/matchups").await;
 
         assert_eq!(status, StatusCode::OK);
-        assert!(json["factions"].as_array().unwrap().is_empty());
-        assert_eq!(json["total_placements"], 0);
-        assert_eq!(json["total_games"], 0);
+        assert!(json["matchups"].as_array().unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn test_composite_scores_balance_deviation_sign() {
+    async fn test_matchups_skips_mirror() {
+        use crate::models::Pairing;
+
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        let e1 = make_event("GT Balance", "2026-01-15", "https://example.com/a");
-        // Strong faction (above 50%)
-        let p1 = make_placement(&e1, 1, "Alice", "Aeldari").with_record(5, 0, 0);
-        // Weak faction (below 50%)
-        let p2 = make_placement(&e1, 10, "Bob", "Necrons").with_record(1, 4, 0);
-        let mut all_p: Vec<Placement> = vec![p1, p2];
-        all_p.extend(fill_event(&e1, 2, 22));
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
 
-        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
-        write_jsonl(
-            &epoch_dir.join("placements.jsonl"),
-            &all_p.iter().collect::<Vec<_>>(),
+        let mut p = Pairing::new(
+            e1.id.clone(),
+            "current".into(),
+            1,
+            "Alice".to_string(),
+            "Bob".to_string(),
         );
+        p.player1_faction = Some("Aeldari".to_string());
+        p.player2_faction = Some("Aeldari".to_string());
+        p.player1_result = Some("win".to_string());
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(&epoch_dir.join("pairings.jsonl"), &[&p]);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/composite-scores").await;
+        let (status, json) = get_json(app, "/api/analytics/matchups?min_games=0").await;
 
         assert_eq!(status, StatusCode::OK);
-        let factions = json["factions"].as_array().unwrap();
-
-        let aeldari = factions.iter().find(|f| f["faction"] == "Aeldari").unwrap();
-        let necrons = factions.iter().find(|f| f["faction"] == "Necrons").unwrap();
-
-        // Aeldari: above 50% → positive balance_deviation
-        assert!(
-            aeldari["balance_deviation"].as_f64().unwrap() > 0.0,
-            "Strong faction should have positive balance deviation"
-        );
-        // Necrons: below 50% → negative balance_deviation
-        assert!(
-            necrons["balance_deviation"].as_f64().unwrap() < 0.0,
-            "Weak faction should have negative balance deviation"
-        );
-    }
-
-    // ── Jaccard Similarity Tests ─────────────────────────────────
-
-    #[test]
-    fn test_jaccard_similarity_identical() {
-        use super::jaccard_similarity;
-        use std::collections::HashSet;
-        let a: HashSet<String> = ["Wraithguard", "Wave Serpent"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        assert!((jaccard_similarity(&a, &a) - 1.0).abs() < f64::EPSILON);
-    }
-
-    #[test]
-    fn test_jaccard_similarity_disjoint() {
-        use super::jaccard_similarity;
-        use std::collections::HashSet;
-        let a: HashSet<String> = ["Wraithguard"].iter().map(|s| s.to_string()).collect();
-        let b: HashSet<String> = ["Wave Serpent"].iter().map(|s| s.to_string()).collect();
-        assert!((jaccard_similarity(&a, &b)).abs() < f64::EPSILON);
-    }
-
-    #[test]
-    fn test_jaccard_similarity_empty() {
-        use super::jaccard_similarity;
-        use std::collections::HashSet;
-        let empty: HashSet<String> = HashSet::new();
-        assert!((jaccard_similarity(&empty, &empty) - 1.0).abs() < f64::EPSILON);
-    }
-
-    #[test]
-    fn test_jaccard_similarity_partial() {
-        use super::jaccard_similarity;
-        use std::collections::HashSet;
-        let a: HashSet<String> = ["A", "B", "C"].iter().map(|s| s.to_string()).collect();
-        let b: HashSet<String> = ["B", "C", "D"].iter().map(|s| s.to_string()).collect();
-        // intersection = {B,C} = 2, union = {A,B,C,D} = 4 → 0.5
-        assert!((jaccard_similarity(&a, &b) - 0.5).abs() < f64::EPSILON);
+        assert!(json["matchups"].as_array().unwrap().is_empty());
     }
 
-    // ── Detachment Stats Tests ──────────────────────────────────
+    // ── Archetypes Tests ────────────────────────────────────────
 
     #[tokio::test]
-    async fn test_detachment_stats_basic() {
+    async fn test_archetypes_basic() {
         use crate::models::{ArmyList, Unit};
 
         let tmp = tempfile::tempdir().unwrap();
@@ -2942,10 +5898,16 @@ mod tests {
         let epoch_dir = tmp.path().join("normalized").join("current");
 
         let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+
+        // Two similar lists (same units) → should cluster together
         let list1 = ArmyList::new(
             "Aeldari".to_string(),
             2000,
-            vec![Unit::new("Wraithguard".to_string(), 5)],
+            vec![
+                Unit::new("Wraithguard".to_string(), 5).with_points(180),
+                Unit::new("Wave Serpent".to_string(), 1).with_points(120),
+                Unit::new("Farseer".to_string(), 1).with_points(90),
+            ],
             "raw".to_string(),
         )
         .with_detachment("Seer Council".to_string())
@@ -2954,79 +5916,140 @@ mod tests {
 
         let list2 = ArmyList::new(
             "Aeldari".to_string(),
-            2000,
-            vec![Unit::new("Fire Prism".to_string(), 1)],
+            1990,
+            vec![
+                Unit::new("Wraithguard".to_string(), 5).with_points(180),
+                Unit::new("Wave Serpent".to_string(), 2).with_points(240),
+                Unit::new("Farseer".to_string(), 1).with_points(90),
+            ],
             "raw".to_string(),
         )
         .with_detachment("Seer Council".to_string())
         .with_player_name("Bob".to_string())
         .with_event_id(e1.id.clone());
 
-        let list3 = ArmyList::new(
-            "Aeldari".to_string(),
-            2000,
-            vec![Unit::new("Wraithknight".to_string(), 1)],
-            "raw".to_string(),
-        )
-        .with_detachment("Seer Council".to_string())
-        .with_player_name("Charlie".to_string())
-        .with_event_id(e1.id.clone());
-
-        let mut p1 = make_placement(&e1, 1, "Alice", "Aeldari");
+        let mut p1 = make_placement(&e1, 2, "Alice", "Aeldari");
         p1.list_id = Some(list1.id.clone());
-        p1 = p1.with_record(5, 0, 0);
-        let mut p2 = make_placement(&e1, 2, "Bob", "Aeldari");
+        p1 = p1.with_record(4, 1, 0);
+        let mut p2 = make_placement(&e1, 5, "Bob", "Aeldari");
         p2.list_id = Some(list2.id.clone());
-        p2 = p2.with_record(4, 1, 0);
-        let mut p3 = make_placement(&e1, 3, "Charlie", "Aeldari");
-        p3.list_id = Some(list3.id.clone());
-        p3 = p3.with_record(3, 2, 0);
+        p2 = p2.with_record(3, 2, 0);
 
         write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
-        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2, &p3]);
-        write_jsonl(
-            &epoch_dir.join("army_lists.jsonl"),
-            &[&list1, &list2, &list3],
-        );
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2]);
+        write_jsonl(&epoch_dir.join("army_lists.jsonl"), &[&list1, &list2]);
 
         let app = build_router(state);
-        let (status, json) = get_json(
-            app,
-            "/api/analytics/detachments?faction=Aeldari&min_count=1",
-        )
-        .await;
+        let (status, json) = get_json(app, "/api/analytics/archetypes?faction=Aeldari").await;
 
         assert_eq!(status, StatusCode::OK);
-        let detachments = json["detachments"].as_array().unwrap();
-        assert!(!detachments.is_empty());
-        let sc = detachments
-            .iter()
-            .find(|d| d["detachment"] == "Seer Council")
-            .unwrap();
-        assert_eq!(sc["count"], 3);
-        assert_eq!(sc["faction"], "Aeldari");
+        assert_eq!(json["faction"], "Aeldari");
+        assert_eq!(json["total_lists"], 2);
+
+        let archetypes = json["archetypes"].as_array().unwrap();
+        assert!(!archetypes.is_empty());
+
+        // Should have a cluster with 2 lists
+        let a = &archetypes[0];
+        assert_eq!(a["list_count"], 2);
+        assert_eq!(a["detachment"], "Seer Council");
+
+        // sample_lists should be populated
+        let sample_lists = a["sample_lists"].as_array().unwrap();
+        assert_eq!(sample_lists.len(), 2);
+        // Sorted by rank: Alice (#2) first, then Bob (#5)
+        assert_eq!(sample_lists[0]["player_name"], "Alice");
+        assert_eq!(sample_lists[0]["rank"], 2);
+        assert_eq!(sample_lists[1]["player_name"], "Bob");
+        assert_eq!(sample_lists[1]["rank"], 5);
+
+        // Each entry should have units
+        let units = sample_lists[0]["units"].as_array().unwrap();
+        assert!(!units.is_empty());
+        let names: Vec<&str> = units.iter().map(|u| u["name"].as_str().unwrap()).collect();
+        assert!(names.contains(&"Wraithguard"));
+        assert!(names.contains(&"Wave Serpent"));
     }
 
     #[tokio::test]
-    async fn test_detachment_stats_empty() {
+    async fn test_archetypes_empty_faction() {
+        use crate::models::ArmyList;
+
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        write_jsonl::<Event>(&epoch_dir.join("events.jsonl"), &[]);
+        write_jsonl::<ArmyList>(&epoch_dir.join("army_lists.jsonl"), &[]);
         write_jsonl::<Placement>(&epoch_dir.join("placements.jsonl"), &[]);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/detachments").await;
+        let (status, json) = get_json(app, "/api/analytics/archetypes?faction=Tyranids").await;
 
         assert_eq!(status, StatusCode::OK);
-        assert!(json["detachments"].as_array().unwrap().is_empty());
+        assert_eq!(json["total_lists"], 0);
+        assert!(json["archetypes"].as_array().unwrap().is_empty());
     }
 
-    // ── Unit Performance Tests ──────────────────────────────────
+    #[tokio::test]
+    async fn test_combos_from_winning_lists() {
+        use crate::models::{ArmyList, Unit};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let mut lists = Vec::new();
+        let mut placements = Vec::new();
+        for (rank, (player, units)) in [
+            ("Alice", vec!["Wraithguard", "Wave Serpent"]),
+            ("Bob", vec!["Wraithguard", "Wave Serpent", "Farseer"]),
+            ("Cara", vec!["Wraithguard", "Wave Serpent"]),
+            ("Dan", vec!["Fire Dragons", "Falcon"]),
+            ("Eve", vec!["Fire Dragons", "Farseer"]),
+            ("Finn", vec!["Falcon", "Farseer"]),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let list = ArmyList::new(
+                "Aeldari".to_string(),
+                2000,
+                units
+                    .into_iter()
+                    .map(|u| Unit::new(u.to_string(), 1))
+                    .collect(),
+                format!("raw {}", player),
+            )
+            .with_player_name(player.to_string())
+            .with_event_id(e1.id.clone());
+            let mut p =
+                make_placement(&e1, rank as u32 + 1, player, "Aeldari").with_record(4, 1, 0);
+            p.list_id = Some(list.id.clone());
+            lists.push(list);
+            placements.push(p);
+        }
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
+        write_jsonl(&epoch_dir.join("army_lists.jsonl"), &lists);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/combos?faction=aeldari").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["faction"], "Aeldari");
+        assert_eq!(json["winning_lists"], 6);
+        let pairs = json["pairs"].as_array().unwrap();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0]["units"][0], "Wave Serpent");
+        assert_eq!(pairs[0]["units"][1], "Wraithguard");
+        assert_eq!(pairs[0]["lists"], 3);
+        assert_eq!(pairs[0]["lift"], 2.0);
+    }
 
     #[tokio::test]
-    async fn test_unit_performance_basic() {
+    async fn test_archetypes_no_cluster_when_dissimilar() {
         use crate::models::{ArmyList, Unit};
 
         let tmp = tempfile::tempdir().unwrap();
@@ -3035,15 +6058,17 @@ mod tests {
 
         let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
 
+        // Two completely different lists → no cluster (jaccard < 0.5)
         let list1 = ArmyList::new(
             "Aeldari".to_string(),
             2000,
             vec![
-                Unit::new("Wraithguard".to_string(), 5).with_points(180),
-                Unit::new("Wave Serpent".to_string(), 1).with_points(120),
+                Unit::new("Wraithguard".to_string(), 5),
+                Unit::new("Wave Serpent".to_string(), 1),
             ],
             "raw".to_string(),
         )
+        .with_detachment("Seer Council".to_string())
         .with_player_name("Alice".to_string())
         .with_event_id(e1.id.clone());
 
@@ -3051,385 +6076,624 @@ mod tests {
             "Aeldari".to_string(),
             2000,
             vec![
-                Unit::new("Wraithguard".to_string(), 5).with_points(180),
-                Unit::new("Fire Prism".to_string(), 1).with_points(150),
+                Unit::new("Fire Prism".to_string(), 1),
+                Unit::new("Night Spinner".to_string(), 1),
             ],
             "raw".to_string(),
         )
+        .with_detachment("Seer Council".to_string())
         .with_player_name("Bob".to_string())
         .with_event_id(e1.id.clone());
 
         let mut p1 = make_placement(&e1, 1, "Alice", "Aeldari");
         p1.list_id = Some(list1.id.clone());
-        p1 = p1.with_record(5, 0, 0);
-        let mut p2 = make_placement(&e1, 8, "Bob", "Aeldari");
+        let mut p2 = make_placement(&e1, 2, "Bob", "Aeldari");
         p2.list_id = Some(list2.id.clone());
-        p2 = p2.with_record(2, 3, 0);
 
         write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
         write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2]);
         write_jsonl(&epoch_dir.join("army_lists.jsonl"), &[&list1, &list2]);
 
         let app = build_router(state);
-        let (status, json) = get_json(
-            app,
-            "/api/analytics/unit-performance?faction=Aeldari&min_appearances=1",
-        )
-        .await;
+        let (status, json) = get_json(app, "/api/analytics/archetypes?faction=Aeldari").await;
 
         assert_eq!(status, StatusCode::OK);
-        let units = json["units"].as_array().unwrap();
-        assert!(!units.is_empty());
-        // Wraithguard appears in both lists
-        let wg = units.iter().find(|u| u["name"] == "Wraithguard");
-        assert!(wg.is_some());
-        assert_eq!(wg.unwrap()["total_lists"], 2);
+        // No clusters because lists are completely different (0% jaccard)
+        assert!(json["archetypes"].as_array().unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn test_unit_performance_empty() {
+    async fn test_archetypes_clones_not_independent() {
+        use crate::models::{ArmyList, EntityId, Unit};
+
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        write_jsonl::<Event>(&epoch_dir.join("events.jsonl"), &[]);
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let units = vec![
+            Unit::new("Wraithguard".to_string(), 5),
+            Unit::new("Wave Serpent".to_string(), 1),
+            Unit::new("Farseer".to_string(), 1),
+        ];
+        let make = |player: &str| {
+            let mut list = ArmyList::new("Aeldari".to_string(), 2000, units.clone(), "raw".into())
+                .with_detachment("Seer Council".to_string())
+                .with_player_name(player.to_string())
+                .with_event_id(e1.id.clone());
+            list.id = EntityId::generate(&[player]);
+            list
+        };
+
+        let original = make("Alice");
+        let similar = make("Bob");
+        let mut clone = make("Carol");
+        clone.clone_of = Some(original.id.clone());
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
         write_jsonl::<Placement>(&epoch_dir.join("placements.jsonl"), &[]);
+        write_jsonl(
+            &epoch_dir.join("army_lists.jsonl"),
+            &[&original, &similar, &clone],
+        );
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/unit-performance").await;
+        let (status, json) = get_json(app, "/api/analytics/archetypes?faction=Aeldari").await;
 
         assert_eq!(status, StatusCode::OK);
-        assert!(json["units"].as_array().unwrap().is_empty());
+        assert_eq!(json["total_lists"], 2);
+        assert_eq!(json["clone_lists"], 1);
+        let a = &json["archetypes"][0];
+        assert_eq!(a["list_count"], 2);
+        assert_eq!(a["clone_count"], 1);
     }
 
-    // ── Points Efficiency Tests ─────────────────────────────────
-
     #[tokio::test]
-    async fn test_points_efficiency_basic() {
-        use crate::models::{ArmyList, Unit};
+    async fn test_placement_curves_basic() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
 
+        let e1 =
+            make_event("GT Alpha", "2026-01-15", "https://example.com/a").with_player_count(10);
+        let placements: Vec<Placement> = (1..=10)
+            .map(|rank| {
+                let faction = if rank % 2 == 1 { "Aeldari" } else { "Orks" };
+                make_placement(&e1, rank, &format!("Player {}", rank), faction)
+            })
+            .collect();
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, "/api/analytics/placement-curves").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["total_placements"], 10);
+        let factions = json["factions"].as_array().unwrap();
+        assert_eq!(factions.len(), 2);
+        assert_eq!(factions[0]["faction"], "Aeldari");
+        assert_eq!(factions[0]["top_10_conversion"], 0.2);
+        assert_eq!(factions[1]["top_10_conversion"], 0.0);
+        assert!(factions[0]["median_finish"].as_f64() < factions[1]["median_finish"].as_f64());
+    }
+
+    #[tokio::test]
+    async fn test_placement_curves_faction_filter() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let e1 =
+            make_event("GT Alpha", "2026-01-15", "https://example.com/a").with_player_count(10);
+        let placements: Vec<Placement> = (1..=10)
+            .map(|rank| {
+                let faction = if rank <= 3 { "Aeldari" } else { "Orks" };
+                make_placement(&e1, rank, &format!("Player {}", rank), faction)
+            })
+            .collect();
 
-        // Need >=3 appearances per unit for the filter
-        let mut lists = Vec::new();
-        let mut placements_vec = Vec::new();
-        for (i, name) in ["Alice", "Bob", "Charlie"].iter().enumerate() {
-            let list = ArmyList::new(
-                "Aeldari".to_string(),
-                2000,
-                vec![Unit::new("Wraithguard".to_string(), 5).with_points(180)],
-                format!("raw{}", i),
-            )
-            .with_player_name(name.to_string())
-            .with_event_id(e1.id.clone());
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
 
-            let mut p = make_placement(&e1, (i + 1) as u32, name, "Aeldari");
-            p.list_id = Some(list.id.clone());
-            p = p.with_record(4, 1, 0);
-            placements_vec.push(p);
-            lists.push(list);
-        }
+        let app = build_router(state);
+        let (status, json) = get_json(
+            app,
+            "/api/analytics/placement-curves?faction=orks&min_placements=5",
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        let factions = json["factions"].as_array().unwrap();
+        assert_eq!(factions.len(), 1);
+        assert_eq!(factions[0]["faction"], "Orks");
+        assert_eq!(factions[0]["placements"], 7);
+    }
+
+    #[tokio::test]
+    async fn test_positioning_quadrants() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let e1 =
+            make_event("GT Alpha", "2026-01-15", "https://example.com/a").with_player_count(10);
+        let placements: Vec<Placement> = (1..=10)
+            .map(|rank| {
+                let name = format!("Player {}", rank);
+                match rank {
+                    1..=5 => make_placement(&e1, rank, &name, "Aeldari").with_record(4, 1, 0),
+                    6 => make_placement(&e1, rank, &name, "Necrons").with_record(3, 2, 0),
+                    _ => make_placement(&e1, rank, &name, "Orks").with_record(1, 4, 0),
+                }
+            })
+            .collect();
 
         write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
-        write_jsonl(
-            &epoch_dir.join("placements.jsonl"),
-            &placements_vec.iter().collect::<Vec<_>>(),
-        );
-        write_jsonl(
-            &epoch_dir.join("army_lists.jsonl"),
-            &lists.iter().collect::<Vec<_>>(),
-        );
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
 
         let app = build_router(state);
-        let (status, json) =
-            get_json(app, "/api/analytics/points-efficiency?faction=Aeldari").await;
+        let (status, json) = get_json(app, "/api/analytics/positioning?min_games=5").await;
 
         assert_eq!(status, StatusCode::OK);
-        let units = json["units"].as_array().unwrap();
-        assert!(!units.is_empty());
-        assert_eq!(units[0]["unit_name"], "Wraithguard");
-        assert_eq!(units[0]["avg_points"], 180);
-        assert_eq!(units[0]["appearances"], 3);
+        assert_eq!(json["epoch_id"], "current");
+        assert_eq!(json["share_threshold"], 33.3);
+        assert_eq!(json["total_players"], 10);
+        let factions = json["factions"].as_array().unwrap();
+        assert_eq!(factions.len(), 3);
+        assert_eq!(factions[0]["faction"], "Aeldari");
+        assert_eq!(factions[0]["meta_share"], 50.0);
+        assert_eq!(factions[0]["win_rate"], 80.0);
+        assert_eq!(factions[0]["quadrant"], "high_share_high_win_rate");
+        assert_eq!(factions[1]["faction"], "Orks");
+        assert_eq!(factions[1]["quadrant"], "high_share_low_win_rate");
+        assert_eq!(factions[2]["faction"], "Necrons");
+        assert_eq!(factions[2]["quadrant"], "low_share_high_win_rate");
+    }
+
+    #[tokio::test]
+    async fn test_positioning_min_games_and_all_epoch() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let placements = vec![
+            make_placement(&e1, 1, "Alice", "Aeldari").with_record(4, 1, 0),
+            make_placement(&e1, 2, "Bob", "Orks").with_record(3, 2, 0),
+        ];
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app.clone(), "/api/analytics/positioning").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["min_games"], 20);
+        assert!(json["factions"].as_array().unwrap().is_empty());
+
+        let (status, _) = get_json(app, "/api/analytics/positioning?epoch=all").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
     }
 
     #[tokio::test]
-    async fn test_points_efficiency_empty() {
+    async fn test_tiers_persisted_per_epoch() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        write_jsonl::<Event>(&epoch_dir.join("events.jsonl"), &[]);
-        write_jsonl::<Placement>(&epoch_dir.join("placements.jsonl"), &[]);
+        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let placements = vec![
+            make_placement(&e1, 1, "Alice", "Aeldari"),
+            make_placement(&e1, 2, "Bob", "Orks"),
+        ];
+        let pairings: Vec<Pairing> = (1..=30)
+            .map(|round| {
+                let mut p = Pairing::new(
+                    e1.id.clone(),
+                    "current".into(),
+                    round,
+                    format!("A{}", round),
+                    format!("B{}", round),
+                );
+                p.player1_faction = Some("Aeldari".into());
+                p.player2_faction = Some("Orks".into());
+                p.player1_result = Some(if round <= 25 { "win" } else { "loss" }.into());
+                p
+            })
+            .collect();
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
+        write_jsonl(&epoch_dir.join("pairings.jsonl"), &pairings);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/points-efficiency").await;
-
+        let (status, json) = get_json(app.clone(), "/api/analytics/tiers").await;
         assert_eq!(status, StatusCode::OK);
-        assert!(json["units"].as_array().unwrap().is_empty());
+        assert_eq!(json["epoch_id"], "current");
+        let tiers = json["tiers"].as_array().unwrap();
+        assert_eq!(tiers.first().unwrap()["factions"][0]["faction"], "Aeldari");
+        assert_eq!(tiers.last().unwrap()["factions"][0]["faction"], "Orks");
+        assert!(tmp
+            .path()
+            .join("derived/composite_tiers/epoch=current/composite_tiers.json")
+            .exists());
+
+        // Unchanged inputs serve the same persisted list
+        let (_, again) = get_json(app.clone(), "/api/analytics/tiers").await;
+        assert_eq!(again["computed_at"], json["computed_at"]);
+
+        let (status, _) = get_json(app, "/api/analytics/tiers?epoch=all").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
     }
 
-    // ── Matchups Tests ──────────────────────────────────────────
-
     #[tokio::test]
-    async fn test_matchups_basic() {
-        use crate::models::Pairing;
-
+    async fn test_epoch_diff_between_epochs() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
-        let epoch_dir = tmp.path().join("normalized").join("current");
-
-        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
 
-        // Create pairings: Aeldari vs Necrons, 3 wins for Aeldari, 2 for Necrons
-        let mut pairings = Vec::new();
-        for i in 0..3u32 {
-            let mut p = Pairing::new(
-                e1.id.clone(),
-                "current".into(),
-                i + 1,
-                format!("Aeldari{}", i),
-                format!("Necron{}", i),
-            );
-            p.player1_faction = Some("Aeldari".to_string());
-            p.player2_faction = Some("Necrons".to_string());
-            p.player1_result = Some("win".to_string());
-            pairings.push(p);
-        }
-        for i in 0..2u32 {
-            let mut p = Pairing::new(
-                e1.id.clone(),
-                "current".into(),
-                i + 4,
-                format!("NecronWin{}", i),
-                format!("AeldariLoss{}", i),
-            );
-            p.player1_faction = Some("Necrons".to_string());
-            p.player2_faction = Some("Aeldari".to_string());
-            p.player1_result = Some("win".to_string());
-            pairings.push(p);
+        for (epoch, aeldari, orks) in [("e1", (4, 1), (1, 4)), ("e2", (2, 3), (3, 2))] {
+            let dir = tmp.path().join("normalized").join(epoch);
+            std::fs::create_dir_all(&dir).unwrap();
+            let mut e = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+            e.epoch_id = epoch.into();
+            let placements = vec![
+                make_placement(&e, 1, "Alice", "Aeldari").with_record(aeldari.0, aeldari.1, 0),
+                make_placement(&e, 2, "Bob", "Orks").with_record(orks.0, orks.1, 0),
+            ];
+            write_jsonl(&dir.join("events.jsonl"), &[&e]);
+            write_jsonl(&dir.join("placements.jsonl"), &placements);
         }
 
-        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
-        write_jsonl(
-            &epoch_dir.join("pairings.jsonl"),
-            &pairings.iter().collect::<Vec<_>>(),
-        );
-
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/matchups?min_games=1").await;
-
+        let (status, json) = get_json(
+            app.clone(),
+            "/api/analytics/epoch-diff?from=e1&to=e2&min_games=5",
+        )
+        .await;
         assert_eq!(status, StatusCode::OK);
-        let matchups = json["matchups"].as_array().unwrap();
-        assert!(!matchups.is_empty());
-        assert_eq!(json["factions"].as_array().unwrap().len(), 2);
-        // Total games should be 5
-        assert_eq!(matchups[0]["total_games"], 5);
+        assert_eq!(json["from_epoch"], "e1");
+        let factions = json["factions"].as_array().unwrap();
+        assert_eq!(factions.len(), 2);
+        assert_eq!(factions[0]["faction"], "Aeldari");
+        assert_eq!(factions[0]["win_rate_delta"], -0.4);
+        assert_eq!(factions[0]["tier_change"], -4);
+        assert_eq!(json["winners"][0]["faction"], "Orks");
+        assert_eq!(json["losers"][0]["faction"], "Aeldari");
+
+        // Without epochs configured there's no previous epoch to default to
+        let (status, _) = get_json(app, "/api/analytics/epoch-diff?to=e2").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
     }
 
     #[tokio::test]
-    async fn test_matchups_empty() {
+    async fn test_attendance_weeks_and_regions() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        write_jsonl::<Event>(&epoch_dir.join("events.jsonl"), &[]);
+        // 2026-01-05 is a Monday
+        let events = vec![
+            make_event("GT Alpha", "2026-01-05", "https://example.com/a")
+                .with_player_count(40)
+                .with_location("London, UK".to_string()),
+            make_event("RTT Beta", "2026-01-10", "https://example.com/b")
+                .with_player_count(20)
+                .with_location("Leeds, UK".to_string()),
+            make_event("GT Gamma", "2026-01-20", "https://example.com/c")
+                .with_location("Austin, USA".to_string()),
+        ];
+        write_jsonl(&epoch_dir.join("events.jsonl"), &events);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/matchups").await;
+        let (status, json) = get_json(app, "/api/analytics/attendance").await;
 
         assert_eq!(status, StatusCode::OK);
-        assert!(json["matchups"].as_array().unwrap().is_empty());
+        assert_eq!(json["total_events"], 3);
+        assert_eq!(json["total_players"], 60);
+
+        let weeks = json["weeks"].as_array().unwrap();
+        assert_eq!(weeks.len(), 2);
+        assert_eq!(weeks[0]["week_start"], "2026-01-05");
+        assert_eq!(weeks[0]["events"], 2);
+        assert_eq!(weeks[0]["avg_players"], 30.0);
+        assert_eq!(weeks[1]["week_start"], "2026-01-19");
+
+        let epoch = &json["epochs"][0];
+        assert_eq!(epoch["events"], 3);
+        assert_eq!(epoch["events_with_count"], 2);
+        assert_eq!(epoch["avg_players"], 30.0);
+        // 15 days between first and last event = 3 weeks
+        assert_eq!(epoch["events_per_week"], 1.0);
+        assert!(epoch["avg_players_change"].is_null());
+        assert_eq!(epoch["regions"][0]["region"], "UK");
+        assert_eq!(epoch["regions"][0]["events"], 2);
     }
 
     #[tokio::test]
-    async fn test_matchups_skips_mirror() {
-        use crate::models::Pairing;
-
+    async fn test_conversion_against_field_size() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
-
-        let mut p = Pairing::new(
-            e1.id.clone(),
-            "current".into(),
-            1,
-            "Alice".to_string(),
-            "Bob".to_string(),
-        );
-        p.player1_faction = Some("Aeldari".to_string());
-        p.player2_faction = Some("Aeldari".to_string());
-        p.player1_result = Some("win".to_string());
-
-        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
-        write_jsonl(&epoch_dir.join("pairings.jsonl"), &[&p]);
+        let full =
+            make_event("GT Alpha", "2026-01-10", "https://example.com/a").with_player_count(16);
+        // Only the top 4 reported: must not count
+        let partial =
+            make_event("GT Beta", "2026-01-17", "https://example.com/b").with_player_count(20);
+
+        let mut placements = Vec::new();
+        for rank in 1..=16u32 {
+            let p = if matches!(rank, 1 | 2 | 3 | 9) {
+                make_placement(&full, rank, &format!("P{}", rank), "Aeldari")
+                    .with_detachment("Aspect Host".to_string())
+            } else {
+                make_placement(&full, rank, &format!("P{}", rank), "Necrons")
+            };
+            placements.push(p);
+        }
+        for rank in 1..=4u32 {
+            placements.push(make_placement(
+                &partial,
+                rank,
+                &format!("Q{}", rank),
+                "Necrons",
+            ));
+        }
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&full, &partial]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/matchups?min_games=0").await;
-
+        let (status, json) = get_json(app, "/api/analytics/conversion?min_players=3").await;
         assert_eq!(status, StatusCode::OK);
-        assert!(json["matchups"].as_array().unwrap().is_empty());
-    }
 
-    // ── Archetypes Tests ────────────────────────────────────────
+        let epoch = &json["epochs"][0];
+        assert_eq!(epoch["events"], 1);
+        assert_eq!(epoch["events_skipped"], 1);
+        assert_eq!(epoch["players"], 16);
+
+        let archetypes = epoch["archetypes"].as_array().unwrap();
+        assert_eq!(archetypes.len(), 2);
+        let aeldari = &archetypes[0];
+        assert_eq!(aeldari["faction"], "Aeldari");
+        assert_eq!(aeldari["detachment"], "Aspect Host");
+        assert_eq!(aeldari["top4"], 3);
+        assert_eq!(aeldari["top4_rate"], 75.0);
+        // 4 players x 4/16 = 1 expected top 4
+        assert_eq!(aeldari["expected_top4"], 1.0);
+        assert_eq!(aeldari["top4_conversion"], 3.0);
+        assert_eq!(aeldari["top8_conversion"], 1.5);
+
+        let necrons = &archetypes[1];
+        assert_eq!(necrons["detachment"], "Unknown");
+        assert_eq!(necrons["players"], 12);
+        assert_eq!(necrons["top4_conversion"], 0.33);
+    }
 
     #[tokio::test]
-    async fn test_archetypes_basic() {
-        use crate::models::{ArmyList, Unit};
-
+    async fn test_shift_candidates_live() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
-
-        // Two similar lists (same units) → should cluster together
-        let list1 = ArmyList::new(
-            "Aeldari".to_string(),
-            2000,
-            vec![
-                Unit::new("Wraithguard".to_string(), 5).with_points(180),
-                Unit::new("Wave Serpent".to_string(), 1).with_points(120),
-                Unit::new("Farseer".to_string(), 1).with_points(90),
-            ],
-            "raw".to_string(),
-        )
-        .with_detachment("Seer Council".to_string())
-        .with_player_name("Alice".to_string())
-        .with_event_id(e1.id.clone());
+        // Aeldari jump from 45% to 65% in week 7; Orks stay flat
+        let start = chrono::NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+        let mut events = Vec::new();
+        let mut placements = Vec::new();
+        for week in 0..12 {
+            let date = (start + chrono::Duration::weeks(week)).to_string();
+            let event = make_event(
+                &format!("Weekly {}", week),
+                &date,
+                &format!("https://example.com/{}", week),
+            );
+            let (wins, losses) = if week < 6 { (18, 22) } else { (26, 14) };
+            placements
+                .push(make_placement(&event, 1, "Alice", "Aeldari").with_record(wins, losses, 0));
+            placements.push(make_placement(&event, 2, "Bob", "Orks").with_record(20, 20, 0));
+            events.push(event);
+        }
+        write_jsonl(&epoch_dir.join("events.jsonl"), &events);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
 
-        let list2 = ArmyList::new(
-            "Aeldari".to_string(),
-            1990,
-            vec![
-                Unit::new("Wraithguard".to_string(), 5).with_points(180),
-                Unit::new("Wave Serpent".to_string(), 2).with_points(240),
-                Unit::new("Farseer".to_string(), 1).with_points(90),
-            ],
-            "raw".to_string(),
-        )
-        .with_detachment("Seer Council".to_string())
-        .with_player_name("Bob".to_string())
-        .with_event_id(e1.id.clone());
+        let app = build_router(state);
+        let (status, json) = get_json(app.clone(), "/api/analytics/shift-candidates").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["source"], "live");
+        assert_eq!(json["factions_scanned"], 2);
+        let candidates = json["candidates"].as_array().unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0]["faction"], "Aeldari");
+        assert_eq!(candidates[0]["date"], "2026-02-16");
+        assert_eq!(candidates[0]["delta"], 20.0);
+
+        let (_, json) = get_json(app, "/api/analytics/shift-candidates?faction=orks").await;
+        assert!(json["candidates"].as_array().unwrap().is_empty());
+    }
 
-        let mut p1 = make_placement(&e1, 2, "Alice", "Aeldari");
-        p1.list_id = Some(list1.id.clone());
-        p1 = p1.with_record(4, 1, 0);
-        let mut p2 = make_placement(&e1, 5, "Bob", "Aeldari");
-        p2.list_id = Some(list2.id.clone());
-        p2 = p2.with_record(3, 2, 0);
+    #[tokio::test]
+    async fn test_ratings_from_pairings() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
 
-        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
-        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2]);
-        write_jsonl(&epoch_dir.join("army_lists.jsonl"), &[&list1, &list2]);
+        let gt = make_event("GT Alpha", "2026-01-10", "https://example.com/a");
+        let mut pairings = Vec::new();
+        for (round, (p1, f1, p2, f2, result)) in [
+            ("Alice", "Aeldari", "Bob", "Orks", "win"),
+            ("Alice", "Necrons", "Carol", "Orks", "win"),
+            ("Bob", "Orks", "Carol", "Orks", "loss"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let mut p = crate::models::Pairing::new(
+                gt.id.clone(),
+                crate::models::EntityId::from("current"),
+                round as u32 + 1,
+                p1.to_string(),
+                p2.to_string(),
+            );
+            p.player1_faction = Some(f1.to_string());
+            p.player2_faction = Some(f2.to_string());
+            p.player1_result = Some(result.to_string());
+            pairings.push(p);
+        }
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&gt]);
+        write_jsonl(&epoch_dir.join("pairings.jsonl"), &pairings);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/archetypes?faction=Aeldari").await;
-
+        let (status, json) = get_json(app.clone(), "/api/analytics/ratings?min_games=1").await;
         assert_eq!(status, StatusCode::OK);
-        assert_eq!(json["faction"], "Aeldari");
-        assert_eq!(json["total_lists"], 2);
-
-        let archetypes = json["archetypes"].as_array().unwrap();
-        assert!(!archetypes.is_empty());
-
-        // Should have a cluster with 2 lists
-        let a = &archetypes[0];
-        assert_eq!(a["list_count"], 2);
-        assert_eq!(a["detachment"], "Seer Council");
+        assert_eq!(json["source"], "live");
+        assert_eq!(json["games_rated"], 3);
+        let players = json["players"].as_array().unwrap();
+        assert_eq!(players.len(), 3);
+        assert_eq!(players[0]["player"], "Alice");
+        assert_eq!(players[0]["factions"].as_array().unwrap().len(), 2);
 
-        // sample_lists should be populated
-        let sample_lists = a["sample_lists"].as_array().unwrap();
-        assert_eq!(sample_lists.len(), 2);
-        // Sorted by rank: Alice (#2) first, then Bob (#5)
-        assert_eq!(sample_lists[0]["player_name"], "Alice");
-        assert_eq!(sample_lists[0]["rank"], 2);
-        assert_eq!(sample_lists[1]["player_name"], "Bob");
-        assert_eq!(sample_lists[1]["rank"], 5);
+        let (_, json) = get_json(
+            app.clone(),
+            "/api/analytics/ratings?min_games=1&faction=orks",
+        )
+        .await;
+        let players = json["players"].as_array().unwrap();
+        assert_eq!(players.len(), 2);
+        assert_eq!(players[0]["player"], "Carol");
+        assert_eq!(players[0]["factions"][0]["faction"], "Orks");
+        assert_eq!(json["factions"].as_array().unwrap().len(), 1);
 
-        // Each entry should have units
-        let units = sample_lists[0]["units"].as_array().unwrap();
-        assert!(!units.is_empty());
-        let names: Vec<&str> = units.iter().map(|u| u["name"].as_str().unwrap()).collect();
-        assert!(names.contains(&"Wraithguard"));
-        assert!(names.contains(&"Wave Serpent"));
+        let (_, json) = get_json(app, "/api/analytics/ratings").await;
+        assert!(json["players"].as_array().unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn test_archetypes_empty_faction() {
-        use crate::models::ArmyList;
-
+    async fn test_ratings_respect_game_size() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        write_jsonl::<ArmyList>(&epoch_dir.join("army_lists.jsonl"), &[]);
-        write_jsonl::<Placement>(&epoch_dir.join("placements.jsonl"), &[]);
+        let gt = make_event("GT Alpha", "2026-01-10", "https://example.com/a");
+        let small = make_event("Incursion Night", "2026-01-11", "https://example.com/b")
+            .with_game_size(Some(1000));
+        let mut pairings = Vec::new();
+        for (event, p1, p2) in [(&gt, "Alice", "Bob"), (&small, "Carol", "Dave")] {
+            let mut p = crate::models::Pairing::new(
+                event.id.clone(),
+                crate::models::EntityId::from("current"),
+                1,
+                p1.to_string(),
+                p2.to_string(),
+            );
+            p.player1_faction = Some("Aeldari".to_string());
+            p.player2_faction = Some("Orks".to_string());
+            p.player1_result = Some("win".to_string());
+            pairings.push(p);
+        }
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&gt, &small]);
+        write_jsonl(&epoch_dir.join("pairings.jsonl"), &pairings);
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/archetypes?faction=Tyranids").await;
+        let players = |json: &serde_json::Value| {
+            let mut names: Vec<String> = json["players"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|p| p["player"].as_str().unwrap().to_string())
+                .collect();
+            names.sort();
+            names
+        };
 
+        let (status, json) = get_json(app.clone(), "/api/analytics/ratings?min_games=1").await;
         assert_eq!(status, StatusCode::OK);
-        assert_eq!(json["total_lists"], 0);
-        assert!(json["archetypes"].as_array().unwrap().is_empty());
+        assert_eq!(json["games_rated"], 1);
+        assert_eq!(players(&json), ["Alice", "Bob"]);
+
+        let (_, json) = get_json(
+            app.clone(),
+            "/api/analytics/ratings?min_games=1&game_size=1000",
+        )
+        .await;
+        assert_eq!(json["source"], "live");
+        assert_eq!(players(&json), ["Carol", "Dave"]);
+
+        let (_, json) = get_json(app, "/api/analytics/ratings?min_games=1&game_size=any").await;
+        assert_eq!(json["games_rated"], 2);
     }
 
     #[tokio::test]
-    async fn test_archetypes_no_cluster_when_dissimilar() {
-        use crate::models::{ArmyList, Unit};
-
+    async fn test_netlists_groups_near_exact_copies() {
         let tmp = tempfile::tempdir().unwrap();
         let state = setup_test_state(tmp.path());
         let epoch_dir = tmp.path().join("normalized").join("current");
 
-        let e1 = make_event("GT Alpha", "2026-01-15", "https://example.com/a");
+        let early = make_event("GT Alpha", "2026-01-10", "https://example.com/a");
+        let late = make_event("GT Beta", "2026-01-24", "https://example.com/b");
 
-        // Two completely different lists → no cluster (jaccard < 0.5)
-        let list1 = ArmyList::new(
-            "Aeldari".to_string(),
-            2000,
+        use crate::models::{ArmyList, Unit};
+        let units = |serpent_pts: u32| {
             vec![
-                Unit::new("Wraithguard".to_string(), 5),
-                Unit::new("Wave Serpent".to_string(), 1),
-            ],
-            "raw".to_string(),
-        )
-        .with_detachment("Seer Council".to_string())
-        .with_player_name("Alice".to_string())
-        .with_event_id(e1.id.clone());
-
-        let list2 = ArmyList::new(
-            "Aeldari".to_string(),
+                Unit::new("Wave Serpent".to_string(), 1).with_points(serpent_pts),
+                Unit::new("Wraithguard".to_string(), 5).with_points(180),
+            ]
+        };
+        let original = ArmyList::new("Aeldari".to_string(), 2000, units(120), "a".to_string())
+            .with_detachment("Aspect Host".to_string());
+        // Same units, re-pointed: a near-exact copy with a different list ID
+        let repointed = ArmyList::new("Aeldari".to_string(), 1990, units(110), "b".to_string())
+            .with_detachment("Aspect Host".to_string());
+        let one_off = ArmyList::new(
+            "Necrons".to_string(),
             2000,
-            vec![
-                Unit::new("Fire Prism".to_string(), 1),
-                Unit::new("Night Spinner".to_string(), 1),
-            ],
-            "raw".to_string(),
-        )
-        .with_detachment("Seer Council".to_string())
-        .with_player_name("Bob".to_string())
-        .with_event_id(e1.id.clone());
-
-        let mut p1 = make_placement(&e1, 1, "Alice", "Aeldari");
-        p1.list_id = Some(list1.id.clone());
-        let mut p2 = make_placement(&e1, 2, "Bob", "Aeldari");
-        p2.list_id = Some(list2.id.clone());
+            vec![Unit::new("Overlord".to_string(), 1)],
+            "c".to_string(),
+        );
 
-        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1]);
-        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&p1, &p2]);
-        write_jsonl(&epoch_dir.join("army_lists.jsonl"), &[&list1, &list2]);
+        let link = |p: Placement, l: &ArmyList| p.with_list_id(l.id.clone());
+        let placements = vec![
+            link(
+                make_placement(&late, 1, "Alice", "Aeldari").with_record(5, 0, 0),
+                &repointed,
+            ),
+            link(
+                make_placement(&early, 3, "Bob", "Aeldari").with_record(3, 2, 0),
+                &original,
+            ),
+            link(
+                make_placement(&late, 2, "Carol", "Aeldari").with_record(4, 1, 0),
+                &original,
+            ),
+            link(make_placement(&early, 1, "Dan", "Necrons"), &one_off),
+        ];
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&early, &late]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
+        write_jsonl(
+            &epoch_dir.join("army_lists.jsonl"),
+            &[&original, &repointed, &one_off],
+        );
 
         let app = build_router(state);
-        let (status, json) = get_json(app, "/api/analytics/archetypes?faction=Aeldari").await;
-
+        let (status, json) = get_json(app.clone(), "/api/analytics/netlists").await;
         assert_eq!(status, StatusCode::OK);
-        // No clusters because lists are completely different (0% jaccard)
-        assert!(json["archetypes"].as_array().unwrap().is_empty());
+        assert_eq!(json["placements_considered"], 4);
+
+        let netlists = json["netlists"].as_array().unwrap();
+        assert_eq!(netlists.len(), 1, "the one-off list is below min_copies");
+        let n = &netlists[0];
+        assert_eq!(n["faction"], "Aeldari");
+        assert_eq!(n["copies"], 3);
+        assert_eq!(n["exact_copies"], 2);
+        assert_eq!(n["variants"], 2);
+        assert_eq!(n["events"], 2);
+        assert_eq!(n["wins"], 12);
+        assert_eq!(n["win_rate"], 80.0);
+        assert_eq!(n["total_points"], 2000);
+        assert_eq!(n["first_seen"]["player_name"], "Bob");
+        assert_eq!(n["first_seen"]["event_name"], "GT Alpha");
+        assert_eq!(n["placements"].as_array().unwrap().len(), 3);
+
+        let (_, json) = get_json(app, "/api/analytics/netlists?min_copies=1&faction=necrons").await;
+        assert_eq!(json["netlists"].as_array().unwrap().len(), 1);
+        assert_eq!(json["netlists"][0]["copies"], 1);
     }
 }
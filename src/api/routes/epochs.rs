@@ -1,23 +1,32 @@
+use std::time::{Duration, SystemTime};
+
 use axum::extract::{Path, State};
+use axum::http::HeaderMap;
 use axum::Json;
 use serde::Serialize;
 
+use super::analytics::FaqMarker;
 use crate::api::state::AppState;
-use crate::api::ApiError;
-use crate::models::{BalanceChanges, Event, Placement, SignificantEventType};
-use crate::storage::{self, EntityType, JsonlReader};
+use crate::api::{require_local, ApiError};
+use crate::models::{
+    BalanceChanges, Event, Placement, RuleChange, RuleChangeType, SignificantEventType,
+};
+use crate::storage::{self, EntityType, IndexedReader};
 
 #[derive(Debug, Serialize)]
 pub struct Epoch {
     pub id: String,
     pub label: String,
     pub is_current: bool,
+    pub is_provisional: bool,
     pub start_date: Option<String>,
     pub end_date: Option<String>,
     pub event_count: u32,
     pub placement_count: u32,
     pub balance_pass_id: Option<String>,
     pub balance_pass_title: Option<String>,
+    /// FAQ/errata drops that took effect during this epoch
+    pub faq_updates: Vec<FaqMarker>,
 }
 
 #[derive(Debug, Serialize)]
@@ -29,29 +38,39 @@ pub async fn list_epochs(State(state): State<AppState>) -> Result<Json<EpochsRes
     let mapper = state.epoch_mapper.read().await;
 
     // Load significant events for balance pass info
-    let sig_events = storage::read_significant_events(&state.storage).unwrap_or_default();
+    let sig_events = storage::cached_significant_events(&state.storage).unwrap_or_default();
+    let faqs: Vec<_> = sig_events
+        .iter()
+        .filter(|e| e.event_type == SignificantEventType::FaqUpdate)
+        .collect();
 
     if mapper.all_epochs().is_empty() {
-        let count = JsonlReader::<Event>::for_entity(&state.storage, EntityType::Event, "current")
-            .read_all()
-            .map(|v| v.len() as u32)
-            .unwrap_or(0);
-        let pcount =
-            JsonlReader::<Placement>::for_entity(&state.storage, EntityType::Placement, "current")
+        let count =
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, "current")
                 .read_all()
                 .map(|v| v.len() as u32)
                 .unwrap_or(0);
+        let pcount = IndexedReader::<Placement>::for_entity(
+            &state.storage,
+            EntityType::Placement,
+            "current",
+        )
+        .read_all()
+        .map(|v| v.len() as u32)
+        .unwrap_or(0);
         return Ok(Json(EpochsResponse {
             epochs: vec![Epoch {
                 id: "current".to_string(),
                 label: "Current Meta".to_string(),
                 is_current: true,
+                is_provisional: false,
                 start_date: None,
                 end_date: None,
                 event_count: count,
                 placement_count: pcount,
                 balance_pass_id: None,
                 balance_pass_title: None,
+                faq_updates: faqs.iter().map(|f| FaqMarker::from_event(f)).collect(),
             }],
         }));
     }
@@ -62,11 +81,11 @@ pub async fn list_epochs(State(state): State<AppState>) -> Result<Json<EpochsRes
         .map(|e| {
             let epoch_id = e.id.as_str();
             let count =
-                JsonlReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id)
+                IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id)
                     .read_all()
                     .map(|v| v.len() as u32)
                     .unwrap_or(0);
-            let pcount = JsonlReader::<Placement>::for_entity(
+            let pcount = IndexedReader::<Placement>::for_entity(
                 &state.storage,
                 EntityType::Placement,
                 epoch_id,
@@ -77,17 +96,28 @@ pub async fn list_epochs(State(state): State<AppState>) -> Result<Json<EpochsRes
 
             // Find the balance pass that started this epoch
             let balance_pass = sig_events.iter().find(|se| se.id == e.start_event_id);
+            let faq_updates = faqs
+                .iter()
+                .filter(|f| {
+                    mapper
+                        .get_epoch_for_date(f.effective_from())
+                        .is_some_and(|fe| fe.id == e.id)
+                })
+                .map(|f| FaqMarker::from_event(f))
+                .collect();
 
             Epoch {
                 id: epoch_id.to_string(),
                 label: e.name.clone(),
                 is_current: e.is_current,
+                is_provisional: e.is_provisional,
                 start_date: Some(e.start_date.to_string()),
                 end_date: e.end_date.map(|d| d.to_string()),
                 event_count: count,
                 placement_count: pcount,
                 balance_pass_id: balance_pass.map(|bp| bp.id.as_str().to_string()),
                 balance_pass_title: balance_pass.map(|bp| bp.title.clone()),
+                faq_updates,
             }
         })
         .collect();
@@ -95,6 +125,63 @@ pub async fn list_epochs(State(state): State<AppState>) -> Result<Json<EpochsRes
     Ok(Json(EpochsResponse { epochs }))
 }
 
+// ── Epoch Reload ────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct EpochReloadResponse {
+    pub epochs: usize,
+    pub current_epoch: Option<String>,
+}
+
+/// Rebuild the epoch mapper from `significant_events.jsonl` so balance
+/// passes added by the CLI or a sync take effect without a restart.
+pub async fn reload_epochs(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<EpochReloadResponse>, ApiError> {
+    require_local(&headers, "Epoch reload")?;
+    super::refresh::reload_epoch_mapper(&state.storage, &state.epoch_mapper).await;
+
+    let mapper = state.epoch_mapper.read().await;
+    tracing::info!(
+        "Reloaded epoch mapper: {} epochs",
+        mapper.all_epochs().len()
+    );
+    Ok(Json(EpochReloadResponse {
+        epochs: mapper.all_epochs().len(),
+        current_epoch: mapper.current_epoch().map(|e| e.id.as_str().to_string()),
+    }))
+}
+
+fn significant_events_mtime(storage: &storage::StorageConfig) -> Option<SystemTime> {
+    std::fs::metadata(storage.significant_events_path())
+        .and_then(|m| m.modified())
+        .ok()
+}
+
+/// Poll `significant_events.jsonl` and reload the epoch mapper whenever
+/// its modification time changes. Runs until the server shuts down.
+pub async fn watch_significant_events(state: AppState, poll: Duration) {
+    let mut last_seen = significant_events_mtime(&state.storage);
+    let mut ticker = tokio::time::interval(poll);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    ticker.tick().await;
+
+    loop {
+        ticker.tick().await;
+        let mtime = significant_events_mtime(&state.storage);
+        if mtime == last_seen {
+            continue;
+        }
+        last_seen = mtime;
+        super::refresh::reload_epoch_mapper(&state.storage, &state.epoch_mapper).await;
+        tracing::info!(
+            "significant_events.jsonl changed; reloaded {} epochs",
+            state.epoch_mapper.read().await.all_epochs().len()
+        );
+    }
+}
+
 // ── Balance Pass Endpoints ──────────────────────────────────────
 
 #[derive(Debug, Serialize)]
@@ -115,7 +202,7 @@ pub struct BalancePassListResponse {
 pub async fn list_balance_passes(
     State(state): State<AppState>,
 ) -> Result<Json<BalancePassListResponse>, ApiError> {
-    let sig_events = storage::read_significant_events(&state.storage)
+    let sig_events = storage::cached_significant_events(&state.storage)
         .map_err(|e| ApiError::Internal(format!("Failed to read significant events: {}", e)))?;
 
     let balance_passes = sig_events
@@ -127,7 +214,7 @@ pub async fn list_balance_passes(
             date: e.date.to_string(),
             source_url: e.source_url.clone(),
             summary: e.summary.clone(),
-            has_details: e.changes.is_some(),
+            has_details: e.changes.is_some() || !e.rule_changes.is_empty(),
         })
         .collect();
 
@@ -143,13 +230,73 @@ pub struct BalancePassDetail {
     pub pdf_url: Option<String>,
     pub summary: Option<String>,
     pub changes: Option<BalanceChanges>,
+    /// Rule changes parsed from the dataslate PDF
+    pub rule_changes: Vec<RuleChange>,
+    /// Per-faction tally of `rule_changes`, most changed first
+    pub factions: Vec<FactionRuleSummary>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FactionRuleSummary {
+    pub faction: String,
+    /// "buff", "nerf", "mixed" or "rework"
+    pub direction: String,
+    pub buffs: u32,
+    pub nerfs: u32,
+    pub reworks: u32,
+    pub clarifications: u32,
+}
+
+/// Tally rule changes per faction. Direction follows the buffs and nerfs;
+/// factions with only reworks or clarifications are "rework".
+fn faction_rule_summary(changes: &[RuleChange]) -> Vec<FactionRuleSummary> {
+    let mut factions: Vec<FactionRuleSummary> = Vec::new();
+    for change in changes {
+        let idx = match factions.iter().position(|f| f.faction == change.faction) {
+            Some(idx) => idx,
+            None => {
+                factions.push(FactionRuleSummary {
+                    faction: change.faction.clone(),
+                    direction: String::new(),
+                    buffs: 0,
+                    nerfs: 0,
+                    reworks: 0,
+                    clarifications: 0,
+                });
+                factions.len() - 1
+            }
+        };
+        let summary = &mut factions[idx];
+        match change.change_type {
+            RuleChangeType::Buff => summary.buffs += 1,
+            RuleChangeType::Nerf => summary.nerfs += 1,
+            RuleChangeType::Rework => summary.reworks += 1,
+            RuleChangeType::Clarification => summary.clarifications += 1,
+        }
+    }
+    for summary in &mut factions {
+        summary.direction = match (summary.buffs > 0, summary.nerfs > 0) {
+            (true, true) => "mixed",
+            (true, false) => "buff",
+            (false, true) => "nerf",
+            (false, false) => "rework",
+        }
+        .to_string();
+    }
+    let total = |f: &FactionRuleSummary| f.buffs + f.nerfs + f.reworks + f.clarifications;
+    factions.sort_by(|a, b| {
+        total(b)
+            .cmp(&total(a))
+            .then_with(|| a.faction.cmp(&b.faction))
+    });
+    factions
 }
 
 pub async fn get_balance_pass(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<Json<BalancePassDetail>, ApiError> {
-    let sig_events = storage::read_significant_events(&state.storage)
+    let sig_events = storage::cached_significant_events(&state.storage)
         .map_err(|e| ApiError::Internal(format!("Failed to read significant events: {}", e)))?;
 
     let event = sig_events
@@ -165,6 +312,8 @@ pub async fn get_balance_pass(
         pdf_url: event.pdf_url.clone(),
         summary: event.summary.clone(),
         changes: event.changes.clone(),
+        rule_changes: event.rule_changes.clone(),
+        factions: faction_rule_summary(&event.rule_changes),
     }))
 }
 
@@ -173,7 +322,8 @@ mod tests {
     use crate::api::build_router;
     use crate::api::state::AppState;
     use crate::models::{
-        BalanceChanges, EpochMapper, Event, FactionChange, SignificantEvent, SignificantEventType,
+        BalanceChanges, EpochMapper, Event, FactionChange, RuleChange, RuleChangeType,
+        SignificantEvent, SignificantEventType,
     };
     use crate::storage::StorageConfig;
     use axum::body::Body;
@@ -254,6 +404,14 @@ mod tests {
             refresh_state: Arc::new(tokio::sync::RwLock::new(
                 crate::api::routes::refresh::RefreshState::default(),
             )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
             ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
             traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
                 crate::api::routes::traffic::TrafficStats::new(),
@@ -304,6 +462,43 @@ mod tests {
         assert_eq!(changes["faction_changes"][0]["direction"], "nerf");
     }
 
+    #[tokio::test]
+    async fn test_get_balance_pass_rule_changes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let change = |faction: &str, unit: Option<&str>, change_type| RuleChange {
+            faction: faction.to_string(),
+            unit: unit.map(str::to_string),
+            change_type,
+            summary: "Changed".to_string(),
+        };
+        let pass =
+            make_balance_pass("Dataslate June 2025", "2025-06-12", false).with_rule_changes(vec![
+                change("Aeldari", Some("Fire Dragons"), RuleChangeType::Nerf),
+                change("Aeldari", None, RuleChangeType::Nerf),
+                change("Orks", None, RuleChangeType::Buff),
+                change("Orks", Some("Boyz"), RuleChangeType::Nerf),
+                change("Core Rules", None, RuleChangeType::Clarification),
+            ]);
+        let pass_id = pass.id.as_str().to_string();
+        let state = setup_with_balance_passes(tmp.path(), &[pass]);
+        let app = build_router(state);
+
+        let (_, list) = get_json(app.clone(), "/api/balance").await;
+        assert_eq!(list["balance_passes"][0]["has_details"], true);
+
+        let (status, json) = get_json(app, &format!("/api/balance/{}", pass_id)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["rule_changes"].as_array().unwrap().len(), 5);
+        assert_eq!(json["rule_changes"][0]["unit"], "Fire Dragons");
+        let factions = json["factions"].as_array().unwrap();
+        assert_eq!(factions.len(), 3);
+        assert_eq!(factions[0]["faction"], "Aeldari");
+        assert_eq!(factions[0]["direction"], "nerf");
+        assert_eq!(factions[1]["faction"], "Orks");
+        assert_eq!(factions[1]["direction"], "mixed");
+        assert_eq!(factions[2]["direction"], "rework");
+    }
+
     #[tokio::test]
     async fn test_get_balance_pass_not_found() {
         let tmp = tempfile::tempdir().unwrap();
@@ -366,6 +561,137 @@ mod tests {
         assert_eq!(epochs[1]["is_current"], true);
     }
 
+    #[tokio::test]
+    async fn test_list_epochs_attaches_faq_updates() {
+        let tmp = tempfile::tempdir().unwrap();
+        let faq = SignificantEvent::new(
+            SignificantEventType::FaqUpdate,
+            chrono::NaiveDate::from_ymd_opt(2025, 12, 20).unwrap(),
+            "Aeldari FAQ".to_string(),
+            "https://example.com/faq".to_string(),
+        )
+        .with_affected_factions(vec!["Aeldari".to_string()]);
+        let passes = vec![
+            make_balance_pass("Dataslate December 2025", "2025-12-11", false),
+            faq,
+            make_balance_pass("Dataslate January 2026", "2026-01-07", false),
+        ];
+        let state = setup_with_balance_passes(tmp.path(), &passes);
+        let app = build_router(state);
+
+        let (status, json) = get_json(app.clone(), "/api/epochs").await;
+        assert_eq!(status, StatusCode::OK);
+        let epochs = json["epochs"].as_array().unwrap();
+        // The FAQ annotates the December epoch rather than starting one
+        assert_eq!(epochs.len(), 2);
+        let faqs = epochs[0]["faq_updates"].as_array().unwrap();
+        assert_eq!(faqs.len(), 1);
+        assert_eq!(faqs[0]["title"], "Aeldari FAQ");
+        assert_eq!(faqs[0]["affected_factions"][0], "Aeldari");
+        assert!(epochs[1]["faq_updates"].as_array().unwrap().is_empty());
+
+        // Not listed as a balance pass
+        let (_, json) = get_json(app, "/api/balance").await;
+        assert_eq!(json["balance_passes"].as_array().unwrap().len(), 2);
+    }
+
+    // ── Epoch Reload Tests ───────────────────────────────────────
+
+    async fn post_reload(app: axum::Router, via_tunnel: bool) -> (StatusCode, Value) {
+        let mut req = Request::builder().method("POST").uri("/api/epochs/reload");
+        if via_tunnel {
+            req = req.header("cf-connecting-ip", "203.0.113.7");
+        }
+        let resp = app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap();
+        let status = resp.status();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+
+    #[tokio::test]
+    async fn test_reload_epochs_picks_up_new_balance_pass() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut passes = vec![make_balance_pass(
+            "Dataslate December 2025",
+            "2025-12-11",
+            false,
+        )];
+        let state = setup_with_balance_passes(tmp.path(), &passes);
+        let app = build_router(state.clone());
+
+        // Registered out-of-process, e.g. via `add-balance-pass`
+        passes.push(make_balance_pass(
+            "Dataslate January 2026",
+            "2026-01-07",
+            false,
+        ));
+        write_jsonl(
+            &tmp.path()
+                .join("normalized")
+                .join("significant_events.jsonl"),
+            &passes,
+        );
+        assert_eq!(state.epoch_mapper.read().await.all_epochs().len(), 1);
+
+        let (status, _) = post_reload(app.clone(), true).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(state.epoch_mapper.read().await.all_epochs().len(), 1);
+
+        let (status, json) = post_reload(app, false).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["epochs"], 2);
+        let mapper = state.epoch_mapper.read().await;
+        assert_eq!(
+            json["current_epoch"],
+            mapper.current_epoch().unwrap().id.as_str()
+        );
+        assert_eq!(
+            mapper.current_epoch().unwrap().name,
+            "Post Dataslate January 2026"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_watcher_reloads_on_file_change() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut passes = vec![make_balance_pass(
+            "Dataslate December 2025",
+            "2025-12-11",
+            false,
+        )];
+        let state = setup_with_balance_passes(tmp.path(), &passes);
+        let watcher = tokio::spawn(super::watch_significant_events(
+            state.clone(),
+            std::time::Duration::from_millis(20),
+        ));
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        passes.push(make_balance_pass(
+            "Dataslate January 2026",
+            "2026-01-07",
+            false,
+        ));
+        write_jsonl(
+            &tmp.path()
+                .join("normalized")
+                .join("significant_events.jsonl"),
+            &passes,
+        );
+
+        let mut reloaded = false;
+        for _ in 0..50 {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            if state.epoch_mapper.read().await.all_epochs().len() == 2 {
+                reloaded = true;
+                break;
+            }
+        }
+        watcher.abort();
+        assert!(reloaded, "watcher should rebuild the mapper");
+    }
+
     #[tokio::test]
     async fn test_list_epochs_empty_falls_back() {
         let tmp = tempfile::tempdir().unwrap();
@@ -380,6 +706,14 @@ mod tests {
             refresh_state: Arc::new(tokio::sync::RwLock::new(
                 crate::api::routes::refresh::RefreshState::default(),
             )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
             ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
             traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
                 crate::api::routes::traffic::TrafficStats::new(),
@@ -1,532 +1,20 @@
-use std::collections::HashMap;
-use std::sync::LazyLock;
+use std::collections::{BTreeMap, HashMap};
 
 use axum::extract::{Path, Query, State};
 use axum::Json;
 use serde::{Deserialize, Serialize};
 
 use crate::api::state::AppState;
-use crate::api::{dedup_by_id, resolve_epoch, ApiError, Pagination, PaginationMeta};
-use crate::models::{ArmyList, Event, Placement};
-use crate::storage::{EntityType, JsonlReader};
-
-// ── Faction Taxonomy ─────────────────────────────────────────────
-
-/// Information about a canonical faction.
-#[derive(Debug, Clone)]
-pub struct FactionInfo {
-    pub canonical_name: &'static str,
-    pub allegiance: &'static str,
-    pub allegiance_sub: &'static str,
-}
-
-/// Result of resolving a raw faction string.
-#[derive(Debug, Clone)]
-pub struct ResolvedFaction {
-    pub faction: String,
-    pub subfaction: Option<String>,
-    pub allegiance: String,
-    pub allegiance_sub: String,
-}
-
-static FACTION_MAP: LazyLock<HashMap<&'static str, FactionInfo>> = LazyLock::new(|| {
-    let entries: Vec<(&str, FactionInfo)> = vec![
-        // Space Marines chapters (distinct factions with codex supplements)
-        (
-            "space marines",
-            FactionInfo {
-                canonical_name: "Space Marines",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        (
-            "blood angels",
-            FactionInfo {
-                canonical_name: "Blood Angels",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        (
-            "dark angels",
-            FactionInfo {
-                canonical_name: "Dark Angels",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        (
-            "space wolves",
-            FactionInfo {
-                canonical_name: "Space Wolves",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        (
-            "black templars",
-            FactionInfo {
-                canonical_name: "Black Templars",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        (
-            "deathwatch",
-            FactionInfo {
-                canonical_name: "Deathwatch",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        (
-            "grey knights",
-            FactionInfo {
-                canonical_name: "Grey Knights",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        // Other chapters → each is its own faction
-        (
-            "adeptus astartes",
-            FactionInfo {
-                canonical_name: "Space Marines",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        (
-            "ultramarines",
-            FactionInfo {
-                canonical_name: "Ultramarines",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        (
-            "iron hands",
-            FactionInfo {
-                canonical_name: "Iron Hands",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        (
-            "raven guard",
-            FactionInfo {
-                canonical_name: "Raven Guard",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        (
-            "salamanders",
-            FactionInfo {
-                canonical_name: "Salamanders",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        (
-            "imperial fists",
-            FactionInfo {
-                canonical_name: "Imperial Fists",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        (
-            "white scars",
-            FactionInfo {
-                canonical_name: "White Scars",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        (
-            "crimson fists",
-            FactionInfo {
-                canonical_name: "Crimson Fists",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        (
-            "black dragons",
-            FactionInfo {
-                canonical_name: "Black Dragons",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        (
-            "flesh tearers",
-            FactionInfo {
-                canonical_name: "Flesh Tearers",
-                allegiance: "Imperium",
-                allegiance_sub: "Space Marines",
-            },
-        ),
-        // Armies of the Imperium
-        (
-            "adepta sororitas",
-            FactionInfo {
-                canonical_name: "Adepta Sororitas",
-                allegiance: "Imperium",
-                allegiance_sub: "Armies of the Imperium",
-            },
-        ),
-        (
-            "sisters of battle",
-            FactionInfo {
-                canonical_name: "Adepta Sororitas",
-                allegiance: "Imperium",
-                allegiance_sub: "Armies of the Imperium",
-            },
-        ),
-        (
-            "adeptus custodes",
-            FactionInfo {
-                canonical_name: "Adeptus Custodes",
-                allegiance: "Imperium",
-                allegiance_sub: "Armies of the Imperium",
-            },
-        ),
-        (
-            "adeptus mechanicus",
-            FactionInfo {
-                canonical_name: "Adeptus Mechanicus",
-                allegiance: "Imperium",
-                allegiance_sub: "Armies of the Imperium",
-            },
-        ),
-        (
-            "astra militarum",
-            FactionInfo {
-                canonical_name: "Astra Militarum",
-                allegiance: "Imperium",
-                allegiance_sub: "Armies of the Imperium",
-            },
-        ),
-        (
-            "imperial guard",
-            FactionInfo {
-                canonical_name: "Astra Militarum",
-                allegiance: "Imperium",
-                allegiance_sub: "Armies of the Imperium",
-            },
-        ),
-        (
-            "imperial knights",
-            FactionInfo {
-                canonical_name: "Imperial Knights",
-                allegiance: "Imperium",
-                allegiance_sub: "Armies of the Imperium",
-            },
-        ),
-        (
-            "agents of the imperium",
-            FactionInfo {
-                canonical_name: "Agents of the Imperium",
-                allegiance: "Imperium",
-                allegiance_sub: "Armies of the Imperium",
-            },
-        ),
-        // Forces of Chaos
-        (
-            "chaos space marines",
-            FactionInfo {
-                canonical_name: "Chaos Space Marines",
-                allegiance: "Chaos",
-                allegiance_sub: "Forces of Chaos",
-            },
-        ),
-        (
-            "death guard",
-            FactionInfo {
-                canonical_name: "Death Guard",
-                allegiance: "Chaos",
-                allegiance_sub: "Forces of Chaos",
-            },
-        ),
-        (
-            "thousand sons",
-            FactionInfo {
-                canonical_name: "Thousand Sons",
-                allegiance: "Chaos",
-                allegiance_sub: "Forces of Chaos",
-            },
-        ),
-        (
-            "chaos thousand sons",
-            FactionInfo {
-                canonical_name: "Thousand Sons",
-                allegiance: "Chaos",
-                allegiance_sub: "Forces of Chaos",
-            },
-        ),
-        (
-            "world eaters",
-            FactionInfo {
-                canonical_name: "World Eaters",
-                allegiance: "Chaos",
-                allegiance_sub: "Forces of Chaos",
-            },
-        ),
-        (
-            "emperor's children",
-            FactionInfo {
-                canonical_name: "Emperor's Children",
-                allegiance: "Chaos",
-                allegiance_sub: "Forces of Chaos",
-            },
-        ),
-        (
-            "chaos daemons",
-            FactionInfo {
-                canonical_name: "Chaos Daemons",
-                allegiance: "Chaos",
-                allegiance_sub: "Forces of Chaos",
-            },
-        ),
-        (
-            "daemons of chaos",
-            FactionInfo {
-                canonical_name: "Chaos Daemons",
-                allegiance: "Chaos",
-                allegiance_sub: "Forces of Chaos",
-            },
-        ),
-        (
-            "chaos knights",
-            FactionInfo {
-                canonical_name: "Chaos Knights",
-                allegiance: "Chaos",
-                allegiance_sub: "Forces of Chaos",
-            },
-        ),
-        // Xenos
-        (
-            "aeldari",
-            FactionInfo {
-                canonical_name: "Aeldari",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "craftworlds",
-            FactionInfo {
-                canonical_name: "Aeldari",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "craftworld",
-            FactionInfo {
-                canonical_name: "Aeldari",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "harlequins",
-            FactionInfo {
-                canonical_name: "Aeldari",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "drukhari",
-            FactionInfo {
-                canonical_name: "Drukhari",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "dark eldar",
-            FactionInfo {
-                canonical_name: "Drukhari",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "tyranids",
-            FactionInfo {
-                canonical_name: "Tyranids",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "genestealer cults",
-            FactionInfo {
-                canonical_name: "Genestealer Cults",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "genestealer cult",
-            FactionInfo {
-                canonical_name: "Genestealer Cults",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "leagues of votann",
-            FactionInfo {
-                canonical_name: "Leagues of Votann",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "votann",
-            FactionInfo {
-                canonical_name: "Leagues of Votann",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "necrons",
-            FactionInfo {
-                canonical_name: "Necrons",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "orks",
-            FactionInfo {
-                canonical_name: "Orks",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "t'au empire",
-            FactionInfo {
-                canonical_name: "T'au Empire",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "t'au",
-            FactionInfo {
-                canonical_name: "T'au Empire",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "tau",
-            FactionInfo {
-                canonical_name: "T'au Empire",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-        (
-            "tau empire",
-            FactionInfo {
-                canonical_name: "T'au Empire",
-                allegiance: "Xenos",
-                allegiance_sub: "Xenos",
-            },
-        ),
-    ];
-    entries.into_iter().collect()
-});
-
-/// Chapters that should be promoted from subfaction to faction.
-/// When faction is "Space Marines" and subfaction matches one of these,
-/// the subfaction becomes the faction.
-const CHAPTER_FACTIONS: &[&str] = &[
-    "Blood Angels",
-    "Dark Angels",
-    "Space Wolves",
-    "Black Templars",
-    "Deathwatch",
-    "Grey Knights",
-    "Ultramarines",
-    "Iron Hands",
-    "Raven Guard",
-    "Salamanders",
-    "Imperial Fists",
-    "White Scars",
-    "Crimson Fists",
-    "Black Dragons",
-    "Flesh Tearers",
-];
-
-/// Look up faction info from the taxonomy map.
-pub fn lookup_faction(name: &str) -> Option<&'static FactionInfo> {
-    FACTION_MAP.get(name.trim().to_lowercase().as_str())
-}
-
-/// Get the allegiance for a faction name. Returns None if not found.
-pub fn faction_allegiance(name: &str) -> Option<&'static str> {
-    lookup_faction(name).map(|info| info.allegiance)
-}
-
-/// Resolve a raw faction + subfaction into canonical faction, subfaction, and allegiance.
-///
-/// Handles cases like:
-/// - `faction: "Space Marines", subfaction: "Blood Angels"` → `faction: "Blood Angels", subfaction: None`
-/// - `faction: "Ultramarines"` → `faction: "Space Marines", subfaction: "Ultramarines"`
-/// - `faction: "Adeptus Astartes"` → `faction: "Space Marines"`
-/// - `faction: "Blood Angels"` → `faction: "Blood Angels"`
-pub fn resolve_faction(faction: &str, subfaction: Option<&str>) -> ResolvedFaction {
-    let trimmed = faction.trim();
-    let lower = trimmed.to_lowercase();
-
-    // Step 1: If subfaction is a chapter-level faction, promote it
-    if let Some(sub) = subfaction {
-        let sub_lower = sub.trim().to_lowercase();
-        // Check if subfaction is a codex-supplement chapter
-        if CHAPTER_FACTIONS
-            .iter()
-            .any(|c| c.to_lowercase() == sub_lower)
-        {
-            if let Some(info) = FACTION_MAP.get(sub_lower.as_str()) {
-                return ResolvedFaction {
-                    faction: info.canonical_name.to_string(),
-                    subfaction: None,
-                    allegiance: info.allegiance.to_string(),
-                    allegiance_sub: info.allegiance_sub.to_string(),
-                };
-            }
-        }
-    }
-
-    // Step 2: Look up the faction itself
-    if let Some(info) = FACTION_MAP.get(lower.as_str()) {
-        return ResolvedFaction {
-            faction: info.canonical_name.to_string(),
-            subfaction: subfaction.map(|s| s.to_string()),
-            allegiance: info.allegiance.to_string(),
-            allegiance_sub: info.allegiance_sub.to_string(),
-        };
-    }
-
-    // Step 3: Unknown faction — return as-is with no allegiance
-    ResolvedFaction {
-        faction: trimmed.to_string(),
-        subfaction: subfaction.map(|s| s.to_string()),
-        allegiance: "Unknown".to_string(),
-        allegiance_sub: "Unknown".to_string(),
-    }
-}
+use crate::api::{
+    merge_by_id, resolve_epoch, ApiError, EpochParam, GeoFilter, Pagination, PaginationMeta,
+};
+use crate::calculate::detachments::GameRecord;
+use crate::models::{
+    lookup_faction, normalize_faction_name, ArmyList, BracketMatch, BracketStage, Event,
+    EventStatus, Pairing, Placement, Region,
+};
+use crate::storage::{EntityType, IndexedReader};
+use crate::sync::normalize_player_name;
 
 #[derive(Debug, Deserialize)]
 pub struct ListEventsParams {
@@ -534,7 +22,6 @@ pub struct ListEventsParams {
     pub page_size: Option<u32>,
     pub from: Option<String>,
     pub to: Option<String>,
-    pub epoch: Option<String>,
     pub has_results: Option<bool>,
     pub q: Option<String>,
     pub min_players: Option<u32>,
@@ -554,12 +41,17 @@ pub struct EventSummary {
     pub name: String,
     pub date: String,
     pub location: Option<String>,
+    pub city: Option<String>,
+    /// ISO country code, stored or geocoded from `location`
+    pub country: Option<String>,
+    pub region: Option<Region>,
     pub player_count: Option<u32>,
     pub round_count: Option<u32>,
     pub source_url: String,
     pub winner: Option<WinnerSummary>,
     pub has_lists: bool,
     pub completed: bool,
+    pub status: EventStatus,
 }
 
 #[derive(Debug, Serialize)]
@@ -570,12 +62,14 @@ pub struct EventListResponse {
 
 pub async fn list_events(
     State(state): State<AppState>,
+    epoch: EpochParam,
+    geo: GeoFilter,
     Query(params): Query<ListEventsParams>,
 ) -> Result<Json<EventListResponse>, ApiError> {
     let mapper = state.epoch_mapper.read().await;
 
     // Support epoch=all to load events from every epoch (used by calendar)
-    let epoch_ids: Vec<String> = if params.epoch.as_deref() == Some("all") {
+    let epoch_ids: Vec<String> = if epoch.as_deref() == Some("all") {
         let epochs = mapper.all_epochs();
         if epochs.is_empty() {
             vec!["current".to_string()]
@@ -583,7 +77,7 @@ pub async fn list_events(
             epochs.iter().map(|e| e.id.as_str().to_string()).collect()
         }
     } else {
-        vec![resolve_epoch(params.epoch.as_deref(), &mapper)?]
+        vec![resolve_epoch(epoch.as_deref(), &mapper)?]
     };
 
     let mut events: Vec<Event> = Vec::new();
@@ -591,26 +85,27 @@ pub async fn list_events(
     let mut lists: Vec<ArmyList> = Vec::new();
 
     for epoch_id in &epoch_ids {
-        let reader = JsonlReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id);
+        let reader =
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id);
         if let Ok(mut epoch_events) = reader.read_all() {
             events.append(&mut epoch_events);
         }
 
         let p_reader =
-            JsonlReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id);
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id);
         if let Ok(mut epoch_placements) = p_reader.read_all() {
             placements.append(&mut epoch_placements);
         }
 
         let l_reader =
-            JsonlReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, epoch_id);
+            IndexedReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, epoch_id);
         if let Ok(mut epoch_lists) = l_reader.read_all() {
             lists.append(&mut epoch_lists);
         }
     }
 
-    events = dedup_by_id(events, |e| e.id.as_str());
-    let placements = dedup_by_id(placements, |p| p.id.as_str());
+    events = merge_by_id(events, |e| e.id.as_str());
+    let placements = merge_by_id(placements, |p| p.id.as_str());
 
     // Filter by date range
     if let Some(ref from) = params.from {
@@ -635,6 +130,10 @@ pub async fn list_events(
         });
     }
 
+    if geo.is_active() {
+        events.retain(|e| geo.matches(e));
+    }
+
     // Filter by player count
     if let Some(min) = params.min_players {
         events.retain(|e| e.player_count.unwrap_or(0) >= min);
@@ -711,7 +210,7 @@ pub async fn list_events(
             let too_recent = event.date >= today - chrono::Days::new(3);
             let completed = if has_placements {
                 true
-            } else if event.date > today {
+            } else if event.is_upcoming() || event.date > today {
                 false // future
             } else if too_small || too_recent {
                 true // don't show as missing — too small/recent
@@ -724,12 +223,16 @@ pub async fn list_events(
                 name: event.name.clone(),
                 date: event.date.to_string(),
                 location: event.location.clone(),
+                city: event.city.clone(),
+                country: event.country_code().map(String::from),
+                region: event.region(),
                 player_count: event.player_count,
                 round_count: event.round_count,
                 source_url: event.source_url.clone(),
                 winner,
                 has_lists: events_with_lists.contains(event.id.as_str()),
                 completed,
+                status: event.status,
             }
         })
         .collect();
@@ -740,6 +243,119 @@ pub async fn list_events(
     }))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct UpcomingEventsParams {
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+    /// Earliest date (default today)
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub q: Option<String>,
+    pub min_players: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpcomingEvent {
+    pub id: String,
+    pub name: String,
+    pub date: String,
+    pub days_until: i64,
+    pub location: Option<String>,
+    pub city: Option<String>,
+    /// ISO country code, stored or geocoded from `location`
+    pub country: Option<String>,
+    pub region: Option<Region>,
+    /// Registered so far
+    pub player_count: Option<u32>,
+    pub source_url: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpcomingEventsResponse {
+    pub events: Vec<UpcomingEvent>,
+    pub pagination: PaginationMeta,
+}
+
+/// Events not yet played, soonest first, from every epoch. Stored by
+/// `sync --upcoming`; each drops out once a sync marks it completed.
+pub async fn upcoming_events(
+    State(state): State<AppState>,
+    geo: GeoFilter,
+    Query(params): Query<UpcomingEventsParams>,
+) -> Result<Json<UpcomingEventsResponse>, ApiError> {
+    let today = chrono::Utc::now().date_naive();
+    let parse = |name: &str, value: &Option<String>| {
+        value
+            .as_deref()
+            .map(|v| {
+                v.parse::<chrono::NaiveDate>().map_err(|_| {
+                    ApiError::BadRequest(format!("Invalid {} date (expected YYYY-MM-DD)", name))
+                })
+            })
+            .transpose()
+    };
+    let from = parse("from", &params.from)?.unwrap_or(today);
+    let to = parse("to", &params.to)?;
+
+    let mut epoch_ids = crate::storage::jsonl::list_epochs(&state.storage)
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    if epoch_ids.is_empty() {
+        epoch_ids.push("current".to_string());
+    }
+    let mut events: Vec<Event> = Vec::new();
+    for epoch_id in &epoch_ids {
+        let reader =
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id);
+        if let Ok(mut epoch_events) = reader.read_all() {
+            events.append(&mut epoch_events);
+        }
+    }
+    let mut events = merge_by_id(events, |e| e.id.as_str());
+
+    let q = params.q.as_deref().map(str::to_lowercase);
+    events.retain(|e| {
+        e.is_upcoming()
+            && e.date >= from
+            && to.is_none_or(|to| e.date <= to)
+            && q.as_ref().is_none_or(|q| {
+                e.name.to_lowercase().contains(q)
+                    || e.location
+                        .as_ref()
+                        .is_some_and(|l| l.to_lowercase().contains(q))
+            })
+            && params
+                .min_players
+                .is_none_or(|min| e.player_count.unwrap_or(0) >= min)
+            && geo.matches(e)
+    });
+    events.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.name.cmp(&b.name)));
+
+    let pagination = Pagination::new(params.page, params.page_size);
+    let meta = PaginationMeta::new(&pagination, events.len() as u32);
+    let events = events
+        .into_iter()
+        .skip(pagination.offset() as usize)
+        .take(pagination.page_size as usize)
+        .map(|e| UpcomingEvent {
+            id: e.id.as_str().to_string(),
+            days_until: (e.date - today).num_days(),
+            date: e.date.to_string(),
+            country: e.country_code().map(String::from),
+            region: e.region(),
+            name: e.name,
+            location: e.location,
+            city: e.city,
+            player_count: e.player_count,
+            source_url: e.source_url,
+        })
+        .collect();
+
+    Ok(Json(UpcomingEventsResponse {
+        events,
+        pagination: meta,
+    }))
+}
+
 #[derive(Debug, Serialize)]
 pub struct PlacementDetail {
     pub rank: u32,
@@ -748,6 +364,9 @@ pub struct PlacementDetail {
     pub subfaction: Option<String>,
     pub detachment: Option<String>,
     pub record: Option<RecordDetail>,
+    /// Furthest top-cut stage reached (record is then Swiss only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bracket_stage: Option<BracketStage>,
     pub army_list: Option<ArmyListDetail>,
 }
 
@@ -795,9 +414,24 @@ pub struct EventDetailResponse {
     pub round_count: Option<u32>,
     pub source_url: String,
     pub placements: Vec<PlacementDetail>,
+    /// Top-cut games after Swiss, by round (empty if no cut)
+    pub bracket: Vec<BracketMatchDetail>,
     pub unmatched_lists: Vec<UnmatchedEventList>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct BracketMatchDetail {
+    pub round: u32,
+    pub stage: BracketStage,
+    pub player1_name: String,
+    pub player1_faction: Option<String>,
+    pub player2_name: String,
+    pub player2_faction: Option<String>,
+    pub winner_name: Option<String>,
+    pub player1_game_points: Option<u32>,
+    pub player2_game_points: Option<u32>,
+}
+
 /// Parse the actual faction name from an army list's raw_text.
 pub fn parse_faction_from_raw(raw: &str) -> Option<String> {
     for line in raw.lines() {
@@ -965,18 +599,6 @@ pub fn unit_to_detail(u: &crate::models::Unit) -> UnitDetail {
     }
 }
 
-/// Normalize faction names to canonical forms.
-/// Handles common variants and abbreviations found in tournament data.
-/// Uses the FACTION_MAP taxonomy for consistent resolution.
-pub fn normalize_faction_name(name: &str) -> String {
-    let trimmed = name.trim();
-    if let Some(info) = lookup_faction(trimmed) {
-        info.canonical_name.to_string()
-    } else {
-        trimmed.to_string()
-    }
-}
-
 /// Score how well two faction names match.
 /// Returns: 3 = exact match, 2 = one contains the other, 0 = no match.
 /// Applies faction name normalization before comparing.
@@ -1142,28 +764,24 @@ pub async fn get_event(
 ) -> Result<Json<EventDetailResponse>, ApiError> {
     let mapper = state.epoch_mapper.read().await;
     let epoch = resolve_epoch(params.epoch.as_deref(), &mapper)?;
-    let reader = JsonlReader::<Event>::for_entity(&state.storage, EntityType::Event, &epoch);
-    let events = reader
-        .read_all()
-        .map_err(|e| ApiError::Internal(e.to_string()))?;
-    let events = dedup_by_id(events, |e| e.id.as_str());
+    Ok(Json(event_detail(&state, &epoch, &id)?))
+}
 
-    let event = events
-        .into_iter()
-        .find(|e| e.id.as_str() == id)
+/// An event's standings (with matched lists) and top-cut bracket.
+fn event_detail(state: &AppState, epoch: &str, id: &str) -> Result<EventDetailResponse, ApiError> {
+    let event = IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch)
+        .get(id)
+        .map_err(|e| ApiError::Internal(e.to_string()))?
         .ok_or_else(|| ApiError::NotFound(format!("Event not found: {}", id)))?;
 
     // Read placements for this event
-    let placement_reader =
-        JsonlReader::<Placement>::for_entity(&state.storage, EntityType::Placement, &epoch);
-    let placements = placement_reader
-        .read_all()
-        .map_err(|e| ApiError::Internal(e.to_string()))?;
-    let placements = dedup_by_id(placements, |p| p.id.as_str());
+    let placements =
+        IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch)
+            .for_event(event.id.as_str())
+            .map_err(|e| ApiError::Internal(e.to_string()))?;
 
     let mut event_placements: Vec<PlacementDetail> = placements
         .into_iter()
-        .filter(|p| p.event_id == event.id)
         .map(|p| PlacementDetail {
             rank: p.rank,
             player_name: p.player_name,
@@ -1175,18 +793,40 @@ pub async fn get_event(
                 losses: r.losses,
                 draws: r.draws,
             }),
+            bracket_stage: p.bracket_stage,
             army_list: None,
         })
         .collect();
     event_placements.sort_by_key(|p| p.rank);
 
+    // Read top-cut bracket matches
+    let bracket_matches =
+        IndexedReader::<BracketMatch>::for_entity(&state.storage, EntityType::BracketMatch, epoch)
+            .for_event(event.id.as_str())
+            .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let mut bracket: Vec<BracketMatchDetail> = bracket_matches
+        .into_iter()
+        .map(|m| BracketMatchDetail {
+            round: m.round,
+            stage: m.stage,
+            player1_name: m.player1_name,
+            player1_faction: m.player1_faction,
+            player2_name: m.player2_name,
+            player2_faction: m.player2_faction,
+            winner_name: m.winner_name,
+            player1_game_points: m.player1_game_points,
+            player2_game_points: m.player2_game_points,
+        })
+        .collect();
+    bracket.sort_by_key(|m| m.round);
+
     // Read army lists and match to placements
     let list_reader =
-        JsonlReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, &epoch);
+        IndexedReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, epoch);
     let lists = list_reader
         .read_all()
         .map_err(|e| ApiError::Internal(e.to_string()))?;
-    let lists = dedup_by_id(lists, |l| l.id.as_str());
+    let lists = merge_by_id(lists, |l| l.id.as_str());
 
     let unmatched_lists = match_lists_to_placements(
         &mut event_placements,
@@ -1195,7 +835,7 @@ pub async fn get_event(
         event.id.as_str(),
     );
 
-    Ok(Json(EventDetailResponse {
+    Ok(EventDetailResponse {
         id: event.id.as_str().to_string(),
         name: event.name,
         date: event.date.to_string(),
@@ -1204,16 +844,239 @@ pub async fn get_event(
         round_count: event.round_count,
         source_url: event.source_url,
         placements: event_placements,
+        bracket,
         unmatched_lists,
+    })
+}
+
+#[derive(Debug, Serialize)]
+pub struct EventFullResponse {
+    pub id: String,
+    pub name: String,
+    pub date: String,
+    pub location: Option<String>,
+    pub player_count: Option<u32>,
+    pub round_count: Option<u32>,
+    pub source_url: String,
+    pub standings: Vec<StandingEntry>,
+    /// Games by round, with each round's faction results
+    pub rounds: Vec<RoundDetail>,
+    /// Top-cut games after Swiss, by round (empty if no cut)
+    pub bracket: Vec<BracketMatchDetail>,
+    /// Faction results over every round
+    pub faction_results: Vec<FactionGameResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StandingEntry {
+    pub rank: u32,
+    pub player_name: String,
+    pub faction: String,
+    pub detachment: Option<String>,
+    pub record: Option<RecordDetail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bracket_stage: Option<BracketStage>,
+    pub list: Option<ListSummary>,
+}
+
+/// A list without its raw text.
+#[derive(Debug, Serialize)]
+pub struct ListSummary {
+    pub id: String,
+    pub faction: Option<String>,
+    pub detachment: Option<String>,
+    pub total_points: u32,
+    /// Models or units across all entries
+    pub unit_count: u32,
+    /// Distinct unit names, in list order
+    pub units: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RoundDetail {
+    pub round: u32,
+    pub games: Vec<RoundGame>,
+    pub faction_results: Vec<FactionGameResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RoundGame {
+    pub player1_name: String,
+    pub player1_faction: Option<String>,
+    pub player2_name: String,
+    pub player2_faction: Option<String>,
+    /// "win", "loss" or "draw" for player 1; `None` if not reported
+    pub player1_result: Option<String>,
+    pub player1_game_points: Option<u32>,
+    pub player2_game_points: Option<u32>,
+}
+
+/// A faction's games at the event. Mirror games are left out.
+#[derive(Debug, Serialize)]
+pub struct FactionGameResult {
+    pub faction: String,
+    #[serde(flatten)]
+    pub record: GameRecord,
+    /// Percent, draws counting half
+    pub win_rate: Option<f64>,
+}
+
+/// Everything the event page shows in one call: standings with list
+/// summaries, pairings by round with per-round faction results, and the
+/// top-cut bracket.
+pub async fn get_event_full(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<GetEventParams>,
+) -> Result<Json<EventFullResponse>, ApiError> {
+    let mapper = state.epoch_mapper.read().await;
+    let epoch = resolve_epoch(params.epoch.as_deref(), &mapper)?;
+    let detail = event_detail(&state, &epoch, &id)?;
+
+    let pairings =
+        IndexedReader::<Pairing>::for_entity(&state.storage, EntityType::Pairing, &epoch)
+            .index()
+            .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let pairings: Vec<Pairing> = merge_by_id(
+        pairings
+            .for_event(&detail.id)
+            .into_iter()
+            .cloned()
+            .collect(),
+        |p| p.id.as_str(),
+    );
+
+    let player_factions: HashMap<String, String> = detail
+        .placements
+        .iter()
+        .map(|p| {
+            (
+                normalize_player_name(&p.player_name),
+                normalize_faction_name(&p.faction),
+            )
+        })
+        .collect();
+    let faction_of = |name: &str, faction: &Option<String>| match faction {
+        Some(f) if !f.is_empty() => Some(normalize_faction_name(f)),
+        _ => player_factions.get(&normalize_player_name(name)).cloned(),
+    };
+
+    let mut by_round: BTreeMap<u32, Vec<RoundGame>> = BTreeMap::new();
+    let mut round_records: BTreeMap<u32, HashMap<String, GameRecord>> = BTreeMap::new();
+    let mut event_records: HashMap<String, GameRecord> = HashMap::new();
+    for pairing in pairings {
+        let f1 = faction_of(&pairing.player1_name, &pairing.player1_faction);
+        let f2 = faction_of(&pairing.player2_name, &pairing.player2_faction);
+        let result = |wins, losses, draws| GameRecord {
+            wins,
+            losses,
+            draws,
+        };
+        let sides = match pairing.player1_result.as_deref() {
+            Some("win") => Some((result(1, 0, 0), result(0, 1, 0))),
+            Some("loss") => Some((result(0, 1, 0), result(1, 0, 0))),
+            Some("draw") => Some((result(0, 0, 1), result(0, 0, 1))),
+            _ => None,
+        };
+        if let Some((r1, r2)) = sides.filter(|_| f1 != f2) {
+            let records = round_records.entry(pairing.round).or_default();
+            for (faction, r) in [(&f1, r1), (&f2, r2)] {
+                if let Some(faction) = faction {
+                    records.entry(faction.clone()).or_default().add(&r);
+                    event_records.entry(faction.clone()).or_default().add(&r);
+                }
+            }
+        }
+        by_round.entry(pairing.round).or_default().push(RoundGame {
+            player1_name: pairing.player1_name,
+            player1_faction: f1,
+            player2_name: pairing.player2_name,
+            player2_faction: f2,
+            player1_result: pairing.player1_result,
+            player1_game_points: pairing.player1_game_points,
+            player2_game_points: pairing.player2_game_points,
+        });
+    }
+
+    let rounds = by_round
+        .into_iter()
+        .map(|(round, games)| RoundDetail {
+            round,
+            games,
+            faction_results: faction_results(round_records.remove(&round).unwrap_or_default()),
+        })
+        .collect();
+
+    let standings = detail
+        .placements
+        .into_iter()
+        .map(|p| StandingEntry {
+            rank: p.rank,
+            player_name: p.player_name,
+            faction: p.faction,
+            detachment: p.detachment,
+            record: p.record,
+            bracket_stage: p.bracket_stage,
+            list: p.army_list.map(|l| {
+                let mut units: Vec<String> = Vec::new();
+                for unit in &l.units {
+                    if !units.contains(&unit.name) {
+                        units.push(unit.name.clone());
+                    }
+                }
+                ListSummary {
+                    id: l.id,
+                    faction: l.parsed_faction,
+                    detachment: l.parsed_detachment,
+                    total_points: l.total_points,
+                    unit_count: l.units.iter().map(|u| u.count).sum(),
+                    units,
+                }
+            }),
+        })
+        .collect();
+
+    Ok(Json(EventFullResponse {
+        id: detail.id,
+        name: detail.name,
+        date: detail.date,
+        location: detail.location,
+        player_count: detail.player_count,
+        round_count: detail.round_count,
+        source_url: detail.source_url,
+        standings,
+        rounds,
+        bracket: detail.bracket,
+        faction_results: faction_results(event_records),
     }))
 }
 
+/// Faction records as results, most games first.
+fn faction_results(records: HashMap<String, GameRecord>) -> Vec<FactionGameResult> {
+    let mut results: Vec<FactionGameResult> = records
+        .into_iter()
+        .map(|(faction, record)| FactionGameResult {
+            faction,
+            win_rate: record.win_rate(),
+            record,
+        })
+        .collect();
+    results.sort_by(|a, b| {
+        b.record
+            .games()
+            .cmp(&a.record.games())
+            .then_with(|| a.faction.cmp(&b.faction))
+    });
+    results
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::api::build_router;
     use crate::api::state::AppState;
-    use crate::models::{ArmyList, EpochMapper, Unit};
+    use crate::models::{faction_allegiance, resolve_faction};
+    use crate::models::{ArmyList, EpochMapper, Timestamped, Unit};
     use crate::storage::StorageConfig;
     use axum::body::Body;
     use axum::http::{Request, StatusCode};
@@ -1240,6 +1103,14 @@ mod tests {
             refresh_state: Arc::new(tokio::sync::RwLock::new(
                 crate::api::routes::refresh::RefreshState::default(),
             )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
             ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
             traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
                 crate::api::routes::traffic::TrafficStats::new(),
@@ -1345,6 +1216,49 @@ mod tests {
         assert_eq!(events[1]["completed"], true);
     }
 
+    #[tokio::test]
+    async fn test_upcoming_events() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let upcoming = |name: &str, date: &str| {
+            make_event(name, date, "https://example.com/u").with_status(EventStatus::Upcoming)
+        };
+        let later = upcoming("Summer GT", "2099-06-01");
+        let sooner = upcoming("Spring GT", "2099-03-01");
+        let stale = upcoming("Old GT", "2020-03-01");
+        let played = upcoming("Played GT", "2099-04-01");
+        // A later sync marked it completed
+        let mut completed = played.clone().with_status(EventStatus::Completed);
+        completed.touch();
+        write_jsonl(
+            &epoch_dir.join("events.jsonl"),
+            &[&later, &sooner, &stale, &played, &completed],
+        );
+
+        let app = build_router(state);
+        let (status, json) = get_json(app.clone(), "/api/events/upcoming").await;
+        assert_eq!(status, StatusCode::OK);
+        let names: Vec<&str> = json["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["Spring GT", "Summer GT"]);
+        assert!(json["events"][0]["days_until"].as_i64().unwrap() > 0);
+
+        let (_, json) = get_json(app.clone(), "/api/events/upcoming?to=2099-04-01").await;
+        assert_eq!(json["pagination"]["total_items"], 1);
+        let (status, _) = get_json(app.clone(), "/api/events/upcoming?from=soon").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (_, json) = get_json(app, "/api/events?q=summer").await;
+        assert_eq!(json["events"][0]["status"], "upcoming");
+        assert_eq!(json["events"][0]["completed"], false);
+    }
+
     #[tokio::test]
     async fn test_list_events_date_filter() {
         let tmp = tempfile::tempdir().unwrap();
@@ -1748,6 +1662,130 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_event_includes_bracket() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let event = make_event("Cut GT", "2025-03-01", "https://example.com/cut");
+        let winner = make_placement(&event, 1, "Alice", "Necrons")
+            .with_record(4, 1, 0)
+            .with_bracket_stage(BracketStage::Final);
+        let runner_up = make_placement(&event, 2, "Bob", "Orks")
+            .with_record(5, 0, 0)
+            .with_bracket_stage(BracketStage::Final);
+        let mut final_match = BracketMatch::new(
+            event.id.clone(),
+            "current".into(),
+            6,
+            BracketStage::Final,
+            "Alice".to_string(),
+            "Bob".to_string(),
+        );
+        final_match.winner_name = Some("Alice".to_string());
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&event]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&winner, &runner_up]);
+        write_jsonl(&epoch_dir.join("bracket_matches.jsonl"), &[&final_match]);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app, &format!("/api/events/{}", event.id.as_str())).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["placements"][0]["bracket_stage"], "final");
+        assert_eq!(json["placements"][1]["record"]["wins"], 5);
+        let bracket = json["bracket"].as_array().unwrap();
+        assert_eq!(bracket.len(), 1);
+        assert_eq!(bracket[0]["stage"], "final");
+        assert_eq!(bracket[0]["winner_name"], "Alice");
+    }
+
+    #[tokio::test]
+    async fn test_get_event_full() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let event = make_event("Full GT", "2025-04-05", "https://example.com/full");
+        let alice = make_placement(&event, 1, "Alice", "Necrons").with_record(2, 0, 0);
+        let bob = make_placement(&event, 2, "Bob", "Orks").with_record(1, 1, 0);
+        let carol = make_placement(&event, 3, "Carol", "Orks").with_record(0, 2, 0);
+        let mut list = ArmyList::new(
+            "Necrons".to_string(),
+            2000,
+            vec![
+                Unit::new("Necron Warriors".to_string(), 2),
+                Unit::new("Overlord".to_string(), 1),
+                Unit::new("Necron Warriors".to_string(), 1),
+            ],
+            "Necrons list".to_string(),
+        )
+        .with_player_name("Alice".to_string());
+        list.event_id = Some(event.id.clone());
+
+        let game = |round: u32, p1: &str, p2: &str, result: &str| {
+            let mut pairing = Pairing::new(
+                event.id.clone(),
+                "current".into(),
+                round,
+                p1.to_string(),
+                p2.to_string(),
+            );
+            pairing.player1_result = Some(result.to_string());
+            pairing
+        };
+        let pairings = [
+            game(1, "Alice", "Bob", "win"),
+            game(1, "Carol", "Dave", "loss"),
+            game(2, "Alice", "Carol", "win"),
+            // Mirror: listed, but not in the faction results
+            game(2, "Bob", "Carol", "win"),
+        ];
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&event]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &[&alice, &bob, &carol]);
+        write_jsonl(&epoch_dir.join("army_lists.jsonl"), &[&list]);
+        write_jsonl(&epoch_dir.join("pairings.jsonl"), &pairings);
+
+        let app = build_router(state);
+        let uri = format!("/api/events/{}/full", event.id.as_str());
+        let (status, json) = get_json(app.clone(), &uri).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let winner = &json["standings"][0];
+        assert_eq!(winner["player_name"], "Alice");
+        assert_eq!(winner["list"]["unit_count"], 4);
+        assert_eq!(
+            winner["list"]["units"],
+            serde_json::json!(["Necron Warriors", "Overlord"])
+        );
+        assert!(winner["list"].get("raw_text").is_none());
+        assert!(json["standings"][1]["list"].is_null());
+
+        let rounds = json["rounds"].as_array().unwrap();
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(rounds[1]["games"].as_array().unwrap().len(), 2);
+        // Dave has no placement or faction: his game counts for Carol only
+        let round1 = &rounds[0]["faction_results"];
+        assert_eq!(round1[0]["faction"], "Orks");
+        assert_eq!(
+            (&round1[0]["wins"], &round1[0]["losses"]),
+            (&0.into(), &2.into())
+        );
+        assert_eq!(round1[0]["win_rate"], 0.0);
+        assert_eq!(round1[1]["win_rate"], 100.0);
+        let necrons = json["faction_results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|r| r["faction"] == "Necrons")
+            .unwrap();
+        assert_eq!(necrons["wins"], 2);
+
+        let (status, _) = get_json(app, "/api/events/missing/full").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
     // ── Detachment Consistency Integration Tests ─────────────────
 
     /// Helper: cross-check placement detachments against army list raw_text.
@@ -2005,4 +2043,43 @@ mod tests {
         assert_eq!(events.len(), 1);
         assert_eq!(events[0]["name"], "Medium");
     }
+
+    #[tokio::test]
+    async fn test_list_events_region_and_country_filters() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let e1 = make_event("LVO", "2025-01-15", "https://example.com/a")
+            .with_location("Las Vegas, NV".to_string());
+        let e2 = make_event("London GT", "2025-01-22", "https://example.com/b")
+            .with_location("London, UK".to_string());
+        let e3 = make_event("Berlin GT", "2025-01-29", "https://example.com/c")
+            .with_location("Berlin, Germany".to_string());
+        let e4 = make_event("Nowhere GT", "2025-02-05", "https://example.com/d");
+
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&e1, &e2, &e3, &e4]);
+        write_jsonl::<Placement>(&epoch_dir.join("placements.jsonl"), &[]);
+        write_jsonl::<ArmyList>(&epoch_dir.join("army_lists.jsonl"), &[]);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app.clone(), "/api/events?region=EU").await;
+        assert_eq!(status, StatusCode::OK);
+        let names: Vec<&str> = json["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["Berlin GT", "London GT"]);
+
+        let (_, json) = get_json(app.clone(), "/api/events?country=usa").await;
+        let events = json["events"].as_array().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["country"], "US");
+        assert_eq!(events[0]["region"], "NA");
+
+        let (status, _) = get_json(app, "/api/events?region=moon").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
 }
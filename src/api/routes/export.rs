@@ -0,0 +1,180 @@
+//! Data export endpoint.
+//!
+//! `GET /api/export/:entity?format=csv&epoch=all` returns every stored
+//! record of an entity as a file download. CSV and JSONL are streamed a
+//! record at a time; JSON and Parquet are built in memory first.
+
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+
+use crate::api::state::AppState;
+use crate::api::{ApiError, EpochParam};
+use crate::export::{self, cell_text, csv_record, ExportEntity, ExportFormat, Table};
+
+#[derive(Debug, Deserialize)]
+pub struct ExportParams {
+    /// csv (default), json, jsonl or parquet
+    pub format: Option<String>,
+}
+
+pub async fn export_entity(
+    State(state): State<AppState>,
+    Path(entity): Path<String>,
+    epoch: EpochParam,
+    Query(params): Query<ExportParams>,
+) -> Result<Response, ApiError> {
+    let entity: ExportEntity = entity
+        .parse()
+        .map_err(|e: export::ExportError| ApiError::NotFound(e.to_string()))?;
+    let format: ExportFormat = params
+        .format
+        .as_deref()
+        .unwrap_or("csv")
+        .parse()
+        .map_err(|e: export::ExportError| ApiError::BadRequest(e.to_string()))?;
+    let epoch = epoch.as_deref().unwrap_or("all").to_string();
+
+    let records = export::load_records(
+        &state.storage,
+        entity,
+        &export::export_epochs(&state.storage, &epoch),
+    )
+    .map_err(|e| ApiError::Internal(format!("Failed to read {}: {}", entity, e)))?;
+
+    let body = match format {
+        ExportFormat::Csv => {
+            let Table { columns, rows } = Table::from_records(&records);
+            let chunks = std::iter::once(csv_record(&columns)).chain(
+                rows.into_iter()
+                    .map(|row| csv_record(row.iter().map(cell_text))),
+            );
+            Body::from_stream(tokio_stream::iter(chunks))
+        }
+        ExportFormat::Jsonl => {
+            let chunks = records.into_iter().map(|record| {
+                let mut line = serde_json::to_vec(&record)?;
+                line.push(b'\n');
+                Ok::<_, serde_json::Error>(line)
+            });
+            Body::from_stream(tokio_stream::iter(chunks))
+        }
+        ExportFormat::Json | ExportFormat::Parquet => {
+            let mut buf = Vec::new();
+            export::write_records(&records, format, &mut buf)
+                .map_err(|e| ApiError::Internal(format!("Export failed: {}", e)))?;
+            Body::from(buf)
+        }
+    };
+
+    let filename = format!("{}_{}.{}", entity, epoch, format.extension());
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::build_router;
+    use crate::api::state::AppState;
+    use crate::models::{EpochMapper, Placement};
+    use crate::storage::{EntityType, JsonlWriter, StorageConfig};
+    use axum::body::Body;
+    use axum::http::{header, Request, StatusCode};
+    use std::sync::Arc;
+    use tower::util::ServiceExt;
+
+    fn setup(dir: &std::path::Path) -> AppState {
+        let storage = StorageConfig::new(dir.to_path_buf());
+        let placements: Vec<Placement> = ["Alice", "Bob"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                Placement::new(
+                    "evt".into(),
+                    "current".into(),
+                    i as u32 + 1,
+                    name.to_string(),
+                    "Aeldari".into(),
+                )
+            })
+            .collect();
+        JsonlWriter::for_entity(&storage, EntityType::Placement, "current")
+            .append_batch(&placements)
+            .unwrap();
+        AppState {
+            storage: Arc::new(storage),
+            epoch_mapper: Arc::new(tokio::sync::RwLock::new(EpochMapper::new())),
+            refresh_state: Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::refresh::RefreshState::default(),
+            )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
+            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
+            traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::traffic::TrafficStats::new(),
+            )),
+        }
+    }
+
+    async fn get(app: axum::Router, uri: &str) -> (StatusCode, Option<String>, String) {
+        let resp = app
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = resp.status();
+        let disposition = resp
+            .headers()
+            .get(header::CONTENT_DISPOSITION)
+            .map(|v| v.to_str().unwrap().to_string());
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            disposition,
+            String::from_utf8_lossy(&body).into_owned(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_export_placements_csv_and_jsonl() {
+        let tmp = tempfile::tempdir().unwrap();
+        let app = build_router(setup(tmp.path()));
+
+        let (status, disposition, body) = get(app.clone(), "/api/export/placements").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            disposition.as_deref(),
+            Some("attachment; filename=\"placements_all.csv\"")
+        );
+        let mut lines = body.lines();
+        assert!(lines.next().unwrap().starts_with("id,"));
+        assert_eq!(lines.count(), 2);
+
+        let (_, _, body) = get(app.clone(), "/api/export/placements?format=jsonl").await;
+        let first: serde_json::Value = serde_json::from_str(body.lines().next().unwrap()).unwrap();
+        assert_eq!(first["player_name"], "Alice");
+
+        let (status, _, _) = get(app.clone(), "/api/export/widgets").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _, _) = get(app, "/api/export/placements?format=xlsx").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
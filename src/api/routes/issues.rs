@@ -0,0 +1,163 @@
+use axum::extract::{Query, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::api::state::AppState;
+use crate::api::ApiError;
+use crate::models::{IngestIssue, IngestStage};
+use crate::sync::issues::read_issues;
+
+#[derive(Debug, Deserialize)]
+pub struct IssueListParams {
+    /// "open" (default), "resolved" or "all"
+    pub status: Option<String>,
+    /// Source key (`bcp`, `goonhammer`, `t3`)
+    pub source: Option<String>,
+    /// Pipeline stage (`fetch`, `extract`, `standings`, `backfill`)
+    pub stage: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IssueListResponse {
+    /// Most recently seen first
+    pub issues: Vec<IngestIssue>,
+    pub total: usize,
+}
+
+/// Ingest issues from the ledger.
+pub async fn list_issues(
+    State(state): State<AppState>,
+    Query(params): Query<IssueListParams>,
+) -> Result<Json<IssueListResponse>, ApiError> {
+    let open = match params.status.as_deref().unwrap_or("open") {
+        "open" => Some(true),
+        "resolved" => Some(false),
+        "all" => None,
+        other => {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid status '{}': expected open, resolved or all",
+                other
+            )))
+        }
+    };
+    let stage = params
+        .stage
+        .as_deref()
+        .map(str::parse::<IngestStage>)
+        .transpose()
+        .map_err(ApiError::BadRequest)?;
+
+    let issues: Vec<IngestIssue> = read_issues(&state.storage)
+        .map_err(|e| ApiError::Internal(format!("Failed to read ingest issues: {}", e)))?
+        .into_iter()
+        .filter(|i| open.is_none_or(|open| i.is_open() == open))
+        .filter(|i| params.source.as_deref().is_none_or(|s| i.source == s))
+        .filter(|i| stage.is_none_or(|s| i.stage == s))
+        .collect();
+    let total = issues.len();
+    Ok(Json(IssueListResponse { issues, total }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::build_router;
+    use crate::models::{EpochMapper, IssueKind, RetryTarget};
+    use crate::storage::StorageConfig;
+    use crate::sync::issues::{record_updates, IssueUpdate};
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use chrono::{NaiveDate, Utc};
+    use std::sync::Arc;
+    use tower::util::ServiceExt;
+
+    #[tokio::test]
+    async fn test_list_issues_filters() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        let now = Utc::now();
+        let issue = |source: &str, url: &str, stage: IngestStage| {
+            IssueUpdate::Failed(Box::new(IngestIssue {
+                id: IngestIssue::issue_id(source, url, stage),
+                source: source.to_string(),
+                source_url: url.to_string(),
+                stage,
+                kind: IssueKind::Network,
+                error: "HTTP 502: bad gateway".to_string(),
+                retryable: true,
+                raw_path: None,
+                target: RetryTarget::Article {
+                    url: url.to_string(),
+                    date: NaiveDate::from_ymd_opt(2026, 2, 1).unwrap(),
+                },
+                attempts: 1,
+                first_seen: now,
+                last_seen: now,
+                run_started_at: Some(now),
+                resolved_at: None,
+            }))
+        };
+        record_updates(
+            &storage,
+            vec![
+                issue("goonhammer", "https://gh/a", IngestStage::Fetch),
+                issue("goonhammer", "https://gh/b", IngestStage::Extract),
+                issue("bcp", "https://bcp/1", IngestStage::Standings),
+                IssueUpdate::Succeeded {
+                    source: "goonhammer".to_string(),
+                    source_url: "https://gh/a".to_string(),
+                },
+            ],
+            now,
+        )
+        .unwrap();
+
+        let app = build_router(AppState {
+            storage: Arc::new(storage),
+            epoch_mapper: Arc::new(tokio::sync::RwLock::new(EpochMapper::new())),
+            refresh_state: Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::refresh::RefreshState::default(),
+            )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
+            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
+            traffic_stats: Default::default(),
+        });
+        let get = |uri: &str| {
+            app.clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        };
+        let json = |resp: axum::response::Response| async {
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+
+        let body = json(get("/api/issues").await.unwrap()).await;
+        assert_eq!(body["total"], 2);
+
+        let body = json(
+            get("/api/issues?source=goonhammer&stage=extract")
+                .await
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["issues"][0]["source_url"], "https://gh/b");
+        assert_eq!(body["issues"][0]["target"]["type"], "article");
+
+        let body = json(get("/api/issues?status=resolved").await.unwrap()).await;
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["issues"][0]["source_url"], "https://gh/a");
+
+        let resp = get("/api/issues?stage=nope").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+}
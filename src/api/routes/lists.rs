@@ -0,0 +1,440 @@
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::HeaderMap;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::analytics::join_lists_to_placements;
+use crate::api::state::AppState;
+use crate::api::{require_local, resolve_epoch, ApiError};
+use crate::calculate::archetypes::{diff_against_archetype, reference_archetype, ArchetypeDiff};
+use crate::calculate::derive::{load_archetypes, DERIVED_GAME_SIZE};
+use crate::calculate::similarity::{
+    similar_lists, SimilarList, SimilarityMetric, SimilarityOptions,
+};
+use crate::ingest::rosters::{import_roster, RosterError};
+use crate::models::{normalize_faction_name, ArmyList, Confidence, Event, Placement};
+use crate::storage::{EntityType, IndexedReader};
+
+use super::events::{army_list_to_detail, ArmyListDetail, RecordDetail};
+
+#[derive(Debug, Deserialize)]
+pub struct GetListParams {
+    pub epoch: Option<String>,
+}
+
+/// The placement a list was played to.
+#[derive(Debug, Serialize)]
+pub struct ListPlacement {
+    pub rank: u32,
+    pub player_name: String,
+    pub record: Option<RecordDetail>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListEvent {
+    pub id: String,
+    pub name: String,
+    pub date: String,
+    pub player_count: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListDetailResponse {
+    pub epoch_id: String,
+    /// Normalized faction name
+    pub faction: String,
+    pub detachment: Option<String>,
+    pub player_name: Option<String>,
+    pub confidence: Confidence,
+    /// Original list when this one is a cloned submission
+    pub clone_of: Option<String>,
+    #[serde(flatten)]
+    pub list: ArmyListDetail,
+    pub placement: Option<ListPlacement>,
+    pub event: Option<ListEvent>,
+    /// Differences from the faction's most common archetype for the list's
+    /// detachment (`None` when the faction has no archetypes yet)
+    pub archetype_diff: Option<ArchetypeDiff>,
+}
+
+/// One army list with its placement, event and how it differs from the
+/// reference archetype.
+pub async fn get_list(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<GetListParams>,
+) -> Result<Json<ListDetailResponse>, ApiError> {
+    let epoch_id = {
+        let mapper = state.epoch_mapper.read().await;
+        resolve_epoch(params.epoch.as_deref(), &mapper)?
+    };
+    let list =
+        IndexedReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, &epoch_id)
+            .get(&id)
+            .map_err(|e| ApiError::Internal(e.to_string()))?
+            .ok_or_else(|| ApiError::NotFound(format!("List not found: {}", id)))?;
+
+    let placements =
+        IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, &epoch_id);
+    let candidates: Vec<Placement> = match &list.event_id {
+        Some(event_id) => placements.for_event(event_id.as_str()),
+        None => placements.read_all(),
+    }
+    .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let placement = join_lists_to_placements(std::slice::from_ref(&list), &candidates)
+        .into_iter()
+        .next()
+        .map(|(_, p)| p);
+
+    let event_id = placement
+        .as_ref()
+        .map(|p| p.event_id.as_str().to_string())
+        .or_else(|| list.event_id.as_ref().map(|e| e.as_str().to_string()));
+    let event = match event_id {
+        Some(event_id) => {
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, &epoch_id)
+                .get(&event_id)
+                .map_err(|e| ApiError::Internal(e.to_string()))?
+                .map(|e| ListEvent {
+                    id: e.id.as_str().to_string(),
+                    name: e.name.clone(),
+                    date: e.date.to_string(),
+                    player_count: e.player_count,
+                })
+        }
+        None => None,
+    };
+
+    let faction = normalize_faction_name(&list.faction);
+    let archetypes = load_archetypes(&state.storage, &epoch_id, DERIVED_GAME_SIZE)
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let archetype_diff = archetypes
+        .iter()
+        .find(|a| a.faction == faction)
+        .and_then(|a| reference_archetype(a, list.detachment.as_deref()))
+        .map(|archetype| diff_against_archetype(&list, archetype));
+
+    Ok(Json(ListDetailResponse {
+        epoch_id,
+        faction,
+        detachment: list.detachment.clone(),
+        player_name: list.player_name.clone(),
+        confidence: list.extraction_confidence,
+        clone_of: list.clone_of.as_ref().map(|c| c.as_str().to_string()),
+        list: army_list_to_detail(&list),
+        placement: placement.map(|p| ListPlacement {
+            rank: p.rank,
+            player_name: p.player_name,
+            record: p.record.map(|r| RecordDetail {
+                wins: r.wins,
+                losses: r.losses,
+                draws: r.draws,
+            }),
+        }),
+        event,
+        archetype_diff,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SimilarListsParams {
+    pub epoch: Option<String>,
+    /// `jaccard` (default) or `cosine`
+    pub metric: Option<String>,
+    /// Compare against every faction instead of the list's own
+    #[serde(default)]
+    pub all_factions: bool,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SimilarListsResponse {
+    pub list_id: String,
+    pub faction: String,
+    pub metric: SimilarityMetric,
+    pub lists: Vec<SimilarList>,
+    /// Similar lists with a known record
+    pub with_results: u32,
+    /// Win rate in percent across those lists' games
+    pub win_rate: Option<f64>,
+}
+
+/// Lists in the same epoch most like this one, with how they did.
+pub async fn similar(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<SimilarListsParams>,
+) -> Result<Json<SimilarListsResponse>, ApiError> {
+    let metric = params
+        .metric
+        .as_deref()
+        .map(str::parse::<SimilarityMetric>)
+        .transpose()
+        .map_err(ApiError::BadRequest)?
+        .unwrap_or_default();
+    let epoch_id = {
+        let mapper = state.epoch_mapper.read().await;
+        resolve_epoch(params.epoch.as_deref(), &mapper)?
+    };
+    let lists =
+        IndexedReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, &epoch_id)
+            .index()
+            .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let target = lists
+        .get(&id)
+        .ok_or_else(|| ApiError::NotFound(format!("List not found: {}", id)))?;
+    let placements =
+        IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, &epoch_id)
+            .read_all()
+            .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+    let options = SimilarityOptions {
+        metric,
+        same_faction: !params.all_factions,
+        limit: params.limit.unwrap_or(10).clamp(1, 100),
+    };
+    let found = similar_lists(target, lists.all(), &placements, options);
+
+    let (mut wins, mut games, mut with_results) = (0u32, 0u32, 0u32);
+    for record in found.iter().filter_map(|l| l.record.as_ref()) {
+        with_results += 1;
+        wins += record.wins;
+        games += record.wins + record.losses + record.draws;
+    }
+
+    Ok(Json(SimilarListsResponse {
+        list_id: id,
+        faction: normalize_faction_name(&target.faction),
+        metric,
+        lists: found,
+        with_results,
+        win_rate: (games > 0).then(|| (wins as f64 / games as f64 * 1000.0).round() / 10.0),
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportRosterParams {
+    pub event_id: String,
+    pub player: String,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportRosterResponse {
+    pub epoch_id: String,
+    /// False if the list was already stored, or on a dry run
+    pub stored: bool,
+    #[serde(flatten)]
+    pub list: ArmyListDetail,
+}
+
+/// Import an uploaded roster file (.ros, .rosz or New Recruit JSON, as the
+/// raw request body) as a player's list at an event. The router caps bodies
+/// at [`MAX_ROSTER_BYTES`](crate::ingest::rosters::MAX_ROSTER_BYTES).
+pub async fn import_roster_file(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Query(params): Query<ImportRosterParams>,
+    body: Bytes,
+) -> Result<Json<ImportRosterResponse>, ApiError> {
+    require_local(&headers, "Roster import")?;
+    let import = import_roster(
+        &state.storage,
+        &body,
+        &params.player,
+        &params.event_id,
+        params.dry_run,
+    )
+    .map_err(|e| match e {
+        RosterError::EventNotFound(_) => ApiError::NotFound(e.to_string()),
+        RosterError::Storage(_) | RosterError::Io(_) => ApiError::Internal(e.to_string()),
+        _ => ApiError::BadRequest(e.to_string()),
+    })?;
+    if import.stored {
+        tracing::info!(
+            "Imported roster {} for {} at {}",
+            import.list.id,
+            params.player,
+            import.event.name
+        );
+    }
+
+    Ok(Json(ImportRosterResponse {
+        epoch_id: import.event.epoch_id.to_string(),
+        stored: import.stored,
+        list: army_list_to_detail(&import.list),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::build_router;
+    use crate::models::{EntityId, EpochMapper, Timestamped, Unit};
+    use crate::storage::StorageConfig;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use chrono::NaiveDate;
+    use serde_json::Value;
+    use std::sync::Arc;
+    use tower::util::ServiceExt;
+
+    fn write_jsonl<T: serde::Serialize>(path: &std::path::Path, items: &[T]) {
+        let mut content = String::new();
+        for item in items {
+            content.push_str(&serde_json::to_string(item).unwrap());
+            content.push('\n');
+        }
+        std::fs::write(path, content).unwrap();
+    }
+
+    async fn get_json(app: axum::Router, uri: &str) -> (StatusCode, Value) {
+        let resp = app
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = resp.status();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+        (status, json)
+    }
+
+    fn setup_test_state(dir: &std::path::Path) -> AppState {
+        AppState {
+            storage: Arc::new(StorageConfig::new(dir.to_path_buf())),
+            epoch_mapper: Arc::new(tokio::sync::RwLock::new(EpochMapper::new())),
+            refresh_state: Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::refresh::RefreshState::default(),
+            )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
+            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
+            traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::traffic::TrafficStats::new(),
+            )),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_list_with_placement_and_archetype_diff() {
+        let tmp = tempfile::tempdir().unwrap();
+        let epoch_dir = tmp.path().join("normalized").join("current");
+        std::fs::create_dir_all(&epoch_dir).unwrap();
+
+        let event = Event::new(
+            "London GT".into(),
+            NaiveDate::from_ymd_opt(2026, 1, 10).unwrap(),
+            "https://example.com/a".into(),
+            "bcp".into(),
+            "current".into(),
+        );
+        let list = |player: &str, units: &[&str]| {
+            ArmyList::new(
+                "Space Marines".into(),
+                2000,
+                units.iter().map(|u| Unit::new(u.to_string(), 3)).collect(),
+                format!("{} raw list", player),
+            )
+            .with_detachment("Gladius Task Force".into())
+            .with_player_name(player.into())
+            .with_event_id(EntityId::from(event.id.as_str()))
+        };
+        let mut lists = vec![
+            list(
+                "Alice",
+                &["Captain", "Eradicators", "Eradicators", "Gladiator"],
+            ),
+            list(
+                "Bob",
+                &["Captain", "Eradicators", "Eradicators", "Gladiator"],
+            ),
+            list("Cara", &["Captain", "Eradicators", "Eradicators"]),
+            list("Dan", &["Captain", "Eradicators", "Redemptor Dreadnought"]),
+        ];
+        // Alice's and Bob's lists share an ID; Alice's copy is the newer
+        lists[0].touch();
+        let placements: Vec<Placement> = ["Alice", "Bob", "Cara", "Dan"]
+            .iter()
+            .enumerate()
+            .map(|(i, player)| {
+                Placement::new(
+                    event.id.clone(),
+                    "current".into(),
+                    i as u32 + 1,
+                    player.to_string(),
+                    "Space Marines".into(),
+                )
+                .with_record(4 - i as u32, i as u32, 0)
+            })
+            .collect();
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&event]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
+        write_jsonl(&epoch_dir.join("army_lists.jsonl"), &lists);
+
+        let app = build_router(setup_test_state(tmp.path()));
+        let uri = format!("/api/lists/{}", lists[3].id.as_str());
+        let (status, json) = get_json(app.clone(), &uri).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["faction"], "Space Marines");
+        assert_eq!(json["raw_text"], "Dan raw list");
+        assert_eq!(json["units"][0]["count"], 3);
+        assert_eq!(json["placement"]["rank"], 4);
+        assert_eq!(json["event"]["name"], "London GT");
+        assert_eq!(
+            json["archetype_diff"]["summary"],
+            "drops 1x Eradicators; adds 1x Redemptor Dreadnought"
+        );
+
+        let (status, _) = get_json(app.clone(), "/api/lists/missing").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let uri = format!("/api/lists/{}/similar?limit=2", lists[2].id.as_str());
+        let (status, json) = get_json(app.clone(), &uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["metric"], "jaccard");
+        let similar = json["lists"].as_array().unwrap();
+        assert_eq!(similar.len(), 2);
+        assert_eq!(similar[0]["similarity"], 0.75);
+        assert_eq!(similar[0]["rank"], 1);
+        assert_eq!(json["with_results"], 2);
+        assert_eq!(json["win_rate"], 62.5);
+
+        let uri = format!("/api/lists/{}/similar?metric=euclid", lists[2].id.as_str());
+        let (status, _) = get_json(app, &uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_import_roster_body_limit() {
+        let tmp = tempfile::tempdir().unwrap();
+        let app = build_router(setup_test_state(tmp.path()));
+        let post = |len: usize| {
+            Request::builder()
+                .method("POST")
+                .uri("/api/lists/import?event_id=missing&player=Alice")
+                .body(Body::from(vec![b' '; len]))
+                .unwrap()
+        };
+
+        // Past axum's 2 MB default, within the roster limit
+        let resp = app.clone().oneshot(post(4 * 1024 * 1024)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let resp = app
+            .oneshot(post(crate::ingest::rosters::MAX_ROSTER_BYTES as usize + 1))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
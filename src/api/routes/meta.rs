@@ -1,31 +1,34 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use axum::extract::{Path, Query, State};
 use axum::Json;
 use serde::{Deserialize, Serialize};
 
 use crate::api::state::AppState;
-use crate::api::{dedup_by_id, resolve_epoch, ApiError};
-use crate::models::{ArmyList, Event, Placement};
-use crate::storage::{EntityType, JsonlReader};
-
-use super::events::{
-    army_list_to_detail, faction_allegiance, normalize_faction_name, ArmyListDetail,
+use crate::api::{
+    merge_by_id, resolve_epoch, ApiError, ConfidenceFilter, EpochParam, GameSizeFilter, GeoFilter,
+    SizeFilter,
+};
+use crate::calculate::derive::{
+    load_epoch_derived, load_faction_stats, UnitFrequency, TIER_LIST_MIN_GAMES,
+};
+use crate::calculate::epoch_diff::{align_faction_stats, biggest_movers, FactionDelta};
+use crate::models::{
+    faction_allegiance, lookup_faction, normalize_faction_name, ArmyList, DateRange, EpochMapper,
+    EpochTotals, Event, Placement, Tier,
 };
+use crate::storage::{EntityType, IndexedReader, StorageConfig, StorageError};
+
+use super::analytics::{group_tiers, TierGroup};
+use super::events::{army_list_to_detail, ArmyListDetail};
 
 #[derive(Debug, Deserialize)]
 pub struct FactionStatsParams {
     pub min_players: Option<u32>,
-    pub epoch: Option<String>,
     pub from: Option<String>,
     pub to: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct FactionDetailParams {
-    pub epoch: Option<String>,
-}
-
 #[derive(Debug, Serialize)]
 pub struct UnitCount {
     pub name: String,
@@ -61,12 +64,17 @@ pub struct FactionStatsResponse {
 
 pub async fn faction_stats(
     State(state): State<AppState>,
+    epoch: EpochParam,
+    geo: GeoFilter,
+    size: SizeFilter,
+    game_size: GameSizeFilter,
+    confidence: ConfidenceFilter,
     Query(params): Query<FactionStatsParams>,
 ) -> Result<Json<FactionStatsResponse>, ApiError> {
     let mapper = state.epoch_mapper.read().await;
 
     // Support epoch=all to load from every epoch
-    let epoch_ids: Vec<String> = if params.epoch.as_deref() == Some("all") {
+    let epoch_ids: Vec<String> = if epoch.as_deref() == Some("all") {
         let epochs = mapper.all_epochs();
         if epochs.is_empty() {
             vec!["current".to_string()]
@@ -74,7 +82,7 @@ pub async fn faction_stats(
             epochs.iter().map(|e| e.id.as_str().to_string()).collect()
         }
     } else {
-        vec![resolve_epoch(params.epoch.as_deref(), &mapper)?]
+        vec![resolve_epoch(epoch.as_deref(), &mapper)?]
     };
 
     // Parse optional date range filters
@@ -87,39 +95,50 @@ pub async fn faction_stats(
         .as_deref()
         .and_then(|s| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
 
+    let filter_by_event = from_date.is_some()
+        || to_date.is_some()
+        || geo.is_active()
+        || size.is_active()
+        || game_size.is_active();
+
     let mut all_placements = Vec::new();
     let mut all_events = Vec::new();
     for epoch_id in &epoch_ids {
         let reader =
-            JsonlReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id);
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id);
         if let Ok(mut p) = reader.read_all() {
             all_placements.append(&mut p);
         }
-        if from_date.is_some() || to_date.is_some() {
+        if filter_by_event {
             let event_reader =
-                JsonlReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id);
+                IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id);
             if let Ok(mut e) = event_reader.read_all() {
                 all_events.append(&mut e);
             }
         }
     }
-    let placements = dedup_by_id(all_placements, |p| p.id.as_str());
+    let mut placements = merge_by_id(all_placements, |p| p.id.as_str());
+    state.storage.overrides.retain(&mut placements);
+    placements.retain(|p| confidence.keeps(p.extraction_confidence));
 
-    // If date filtering, filter placements by event date
-    let placements = if from_date.is_some() || to_date.is_some() {
-        let event_dates: HashMap<String, chrono::NaiveDate> = all_events
-            .into_iter()
-            .map(|e| (e.id.as_str().to_string(), e.date))
-            .collect();
+    // If date, location, size or game size filtering, filter placements by
+    // their event. Placements with no known event can't be located or sized, so
+    // those filters drop them.
+    let placements = if filter_by_event {
+        let events_by_id: HashMap<&str, &Event> =
+            all_events.iter().map(|e| (e.id.as_str(), e)).collect();
 
         placements
             .into_iter()
-            .filter(|p| {
-                let event_date = event_dates.get(p.event_id.as_str());
-                match event_date {
-                    Some(d) => from_date.is_none_or(|f| *d >= f) && to_date.is_none_or(|t| *d <= t),
-                    None => true,
+            .filter(|p| match events_by_id.get(p.event_id.as_str()) {
+                Some(e) => {
+                    from_date.is_none_or(|f| e.date >= f)
+                        && to_date.is_none_or(|t| e.date <= t)
+                        && geo.matches(e)
+                        && size.matches(e)
+                        && game_size.matches(e)
                 }
+                None => !geo.is_active() && !size.is_active(),
             })
             .collect()
     } else {
@@ -130,12 +149,13 @@ pub async fn faction_stats(
     let mut all_lists_raw = Vec::new();
     for epoch_id in &epoch_ids {
         let list_reader =
-            JsonlReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, epoch_id);
+            IndexedReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, epoch_id);
         if let Ok(mut lists) = list_reader.read_all() {
             all_lists_raw.append(&mut lists);
         }
     }
-    let all_lists = dedup_by_id(all_lists_raw, |l| l.id.as_str());
+    let mut all_lists = merge_by_id(all_lists_raw, |l| l.id.as_str());
+    state.storage.overrides.retain(&mut all_lists);
 
     // Index army lists by normalized faction name
     let mut lists_by_faction: HashMap<String, Vec<&ArmyList>> = HashMap::new();
@@ -224,7 +244,7 @@ pub async fn faction_stats(
             top_units.sort_by(|a, b| b.count.cmp(&a.count));
             top_units.truncate(5);
 
-            let info = super::events::lookup_faction(&faction);
+            let info = lookup_faction(&faction);
             FactionStat {
                 faction,
                 allegiance: info.map(|i| i.allegiance.to_string()),
@@ -290,18 +310,36 @@ pub struct FactionDetailResponse {
 
 pub async fn faction_detail(
     State(state): State<AppState>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
     Path(faction_name): Path<String>,
-    Query(params): Query<FactionDetailParams>,
 ) -> Result<Json<FactionDetailResponse>, ApiError> {
     let mapper = state.epoch_mapper.read().await;
-    let epoch = resolve_epoch(params.epoch.as_deref(), &mapper)?;
+    let epoch = resolve_epoch(epoch.as_deref(), &mapper)?;
     // Read placements for this faction (winners and top-4)
     let placement_reader =
-        JsonlReader::<Placement>::for_entity(&state.storage, EntityType::Placement, &epoch);
+        IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, &epoch);
     let placements = placement_reader
         .read_all()
         .map_err(|e| ApiError::Internal(e.to_string()))?;
-    let placements = dedup_by_id(placements, |p| p.id.as_str());
+    let mut placements = merge_by_id(placements, |p| p.id.as_str());
+    state.storage.overrides.retain(&mut placements);
+
+    // Read events to get names/dates, and drop other game sizes
+    let event_reader =
+        IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, &epoch);
+    let events = event_reader
+        .read_all()
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let mut events = merge_by_id(events, |e| e.id.as_str());
+    state.storage.overrides.retain(&mut events);
+    let off_size: HashSet<String> = events
+        .iter()
+        .filter(|e| !game_size.matches(e))
+        .map(|e| e.id.as_str().to_string())
+        .collect();
+    events.retain(|e| game_size.matches(e));
+    placements.retain(|p| !off_size.contains(p.event_id.as_str()));
 
     let normalized_query = normalize_faction_name(&faction_name);
     let faction_placements: Vec<_> = placements
@@ -319,20 +357,19 @@ pub async fn faction_detail(
         )));
     }
 
-    // Read events to get names/dates
-    let event_reader = JsonlReader::<Event>::for_entity(&state.storage, EntityType::Event, &epoch);
-    let events = event_reader
-        .read_all()
-        .map_err(|e| ApiError::Internal(e.to_string()))?;
-    let events = dedup_by_id(events, |e| e.id.as_str());
-
     // Read army lists
     let list_reader =
-        JsonlReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, &epoch);
+        IndexedReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, &epoch);
     let all_lists = list_reader
         .read_all()
         .map_err(|e| ApiError::Internal(e.to_string()))?;
-    let all_lists = dedup_by_id(all_lists, |l| l.id.as_str());
+    let mut all_lists = merge_by_id(all_lists, |l| l.id.as_str());
+    state.storage.overrides.retain(&mut all_lists);
+    all_lists.retain(|l| {
+        l.event_id
+            .as_ref()
+            .is_none_or(|e| !off_size.contains(e.as_str()))
+    });
 
     let normalize_name = |s: &str| -> String {
         s.split_whitespace()
@@ -342,7 +379,7 @@ pub async fn faction_detail(
     };
 
     // Track which list IDs have been claimed to prevent double-matching
-    let mut claimed_list_ids: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut claimed_list_ids: HashSet<String> = HashSet::new();
 
     let mut winners: Vec<FactionWinner> = Vec::new();
     for p in faction_placements {
@@ -485,11 +522,6 @@ pub async fn faction_detail(
 
 // ── Allegiance Stats ─────────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
-pub struct AllegianceStatsParams {
-    pub epoch: Option<String>,
-}
-
 #[derive(Debug, Serialize)]
 pub struct AllegianceFaction {
     pub faction: String,
@@ -514,17 +546,30 @@ pub struct AllegianceStatsResponse {
 
 pub async fn allegiance_stats(
     State(state): State<AppState>,
-    Query(params): Query<AllegianceStatsParams>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
 ) -> Result<Json<AllegianceStatsResponse>, ApiError> {
     let mapper = state.epoch_mapper.read().await;
-    let epoch = resolve_epoch(params.epoch.as_deref(), &mapper)?;
+    let epoch = resolve_epoch(epoch.as_deref(), &mapper)?;
 
     let reader =
-        JsonlReader::<Placement>::for_entity(&state.storage, EntityType::Placement, &epoch);
+        IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, &epoch);
     let placements = reader
         .read_all()
         .map_err(|e| ApiError::Internal(e.to_string()))?;
-    let placements = dedup_by_id(placements, |p| p.id.as_str());
+    let mut placements = merge_by_id(placements, |p| p.id.as_str());
+    state.storage.overrides.retain(&mut placements);
+    if game_size.is_active() {
+        let events = IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, &epoch)
+            .read_all()
+            .map_err(|e| ApiError::Internal(e.to_string()))?;
+        let off_size: HashSet<&str> = events
+            .iter()
+            .filter(|e| !game_size.matches(e))
+            .map(|e| e.id.as_str())
+            .collect();
+        placements.retain(|p| !off_size.contains(p.event_id.as_str()));
+    }
 
     let total = placements.len() as u32;
 
@@ -591,6 +636,211 @@ pub async fn allegiance_stats(
     }))
 }
 
+// ── Meta Snapshot ───────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct SnapshotParams {
+    /// Entries in each top list (default 5)
+    pub limit: Option<usize>,
+}
+
+/// A faction's headline numbers.
+#[derive(Debug, Serialize)]
+pub struct SnapshotFaction {
+    pub faction: String,
+    pub tier: Tier,
+    pub win_rate: f64,
+    pub meta_share: f64,
+    pub player_count: u32,
+    pub games_played: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SnapshotDetachment {
+    pub faction: String,
+    pub detachment: String,
+    pub count: u32,
+    pub win_rate: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SnapshotEvent {
+    pub id: String,
+    pub name: String,
+    pub date: String,
+    pub player_count: Option<u32>,
+    pub winner: Option<String>,
+    pub winner_faction: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MetaSnapshotResponse {
+    pub epoch_id: String,
+    /// Epoch the movers are measured from, if there is one
+    pub previous_epoch: Option<String>,
+    pub date_range: DateRange,
+    pub totals: EpochTotals,
+    pub tiers: Vec<TierGroup>,
+    /// Highest win rates among factions with enough games for the tier list
+    pub top_factions: Vec<SnapshotFaction>,
+    /// Most played detachments
+    pub top_detachments: Vec<SnapshotDetachment>,
+    /// Units in the largest share of their faction's lists
+    pub top_units: Vec<UnitFrequency>,
+    pub winners: Vec<FactionDelta>,
+    pub losers: Vec<FactionDelta>,
+    /// Latest events first
+    pub recent_events: Vec<SnapshotEvent>,
+}
+
+/// Everything a landing page shows for one epoch, in one request. Read
+/// from the derived artifacts where they are current.
+pub async fn snapshot(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+    game_size: GameSizeFilter,
+    Query(params): Query<SnapshotParams>,
+) -> Result<Json<MetaSnapshotResponse>, ApiError> {
+    if epoch.as_deref() == Some("all") {
+        return Err(ApiError::BadRequest(
+            "The snapshot is per epoch; epoch=all is not supported".to_string(),
+        ));
+    }
+    let (epoch_id, previous_epoch) = {
+        let mapper = state.epoch_mapper.read().await;
+        let epoch_id = resolve_epoch(epoch.as_deref(), &mapper)?;
+        let previous = previous_epoch(&mapper, &epoch_id);
+        (epoch_id, previous)
+    };
+    let snapshot = meta_snapshot(
+        &state.storage,
+        epoch_id,
+        previous_epoch,
+        game_size.points,
+        params.limit.unwrap_or(5),
+    )
+    .map_err(|e| ApiError::Internal(e.to_string()))?;
+    Ok(Json(snapshot))
+}
+
+/// The epoch before `epoch_id`, if there is one.
+pub fn previous_epoch(mapper: &EpochMapper, epoch_id: &str) -> Option<String> {
+    let epochs = mapper.all_epochs();
+    epochs
+        .iter()
+        .position(|e| e.id.as_str() == epoch_id)
+        .filter(|&i| i > 0)
+        .map(|i| epochs[i - 1].id.as_str().to_string())
+}
+
+/// Build the meta snapshot for an epoch's games at `game_size` points, with
+/// `limit` entries in each top list and movers measured from
+/// `previous_epoch`.
+pub fn meta_snapshot(
+    storage: &StorageConfig,
+    epoch_id: String,
+    previous_epoch: Option<String>,
+    game_size: Option<u32>,
+    limit: usize,
+) -> Result<MetaSnapshotResponse, StorageError> {
+    let derived = load_epoch_derived(storage, &epoch_id, game_size)?;
+    let stats = &derived.faction_stats;
+
+    let mut top_factions: Vec<SnapshotFaction> = stats
+        .factions
+        .iter()
+        .filter(|f| f.games_played >= TIER_LIST_MIN_GAMES)
+        .map(|f| SnapshotFaction {
+            faction: f.name.clone(),
+            tier: f.tier,
+            win_rate: f.win_rate,
+            meta_share: f.meta_share,
+            player_count: f.player_count,
+            games_played: f.games_played,
+        })
+        .collect();
+    top_factions.sort_by(|a, b| {
+        b.win_rate
+            .partial_cmp(&a.win_rate)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    top_factions.truncate(limit);
+
+    let mut top_detachments: Vec<SnapshotDetachment> = stats
+        .factions
+        .iter()
+        .flat_map(|f| {
+            f.top_detachments.iter().map(|d| SnapshotDetachment {
+                faction: f.name.clone(),
+                detachment: d.name.clone(),
+                count: d.count,
+                win_rate: d.win_rate,
+            })
+        })
+        .collect();
+    top_detachments.sort_by(|a, b| b.count.cmp(&a.count).then(a.detachment.cmp(&b.detachment)));
+    top_detachments.truncate(limit);
+
+    let mut top_units = derived.unit_frequency.clone();
+    top_units.sort_by(|a, b| {
+        b.list_share
+            .partial_cmp(&a.list_share)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(b.lists_containing.cmp(&a.lists_containing))
+    });
+    top_units.truncate(limit);
+
+    let (winners, losers) = match &previous_epoch {
+        Some(previous) => {
+            let before = load_faction_stats(storage, previous, game_size)?;
+            biggest_movers(
+                &align_faction_stats(&before, stats),
+                TIER_LIST_MIN_GAMES,
+                limit,
+            )
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+
+    let champions: HashMap<&str, &Placement> = derived
+        .placements
+        .iter()
+        .filter(|p| p.rank == 1)
+        .map(|p| (p.event_id.as_str(), p))
+        .collect();
+    let mut events: Vec<&Event> = derived.events.iter().collect();
+    events.sort_by(|a, b| b.date.cmp(&a.date).then(a.name.cmp(&b.name)));
+    let recent_events = events
+        .into_iter()
+        .take(limit)
+        .map(|e| {
+            let champion = champions.get(e.id.as_str());
+            SnapshotEvent {
+                id: e.id.as_str().to_string(),
+                name: e.name.clone(),
+                date: e.date.to_string(),
+                player_count: e.player_count,
+                winner: champion.map(|p| p.player_name.clone()),
+                winner_faction: champion.map(|p| normalize_faction_name(&p.faction)),
+            }
+        })
+        .collect();
+
+    Ok(MetaSnapshotResponse {
+        epoch_id,
+        previous_epoch,
+        date_range: stats.date_range.clone(),
+        totals: stats.totals.clone(),
+        tiers: group_tiers(derived.tiers),
+        top_factions,
+        top_detachments,
+        top_units,
+        winners,
+        losers,
+        recent_events,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::api::build_router;
@@ -616,6 +866,14 @@ mod tests {
             refresh_state: Arc::new(tokio::sync::RwLock::new(
                 crate::api::routes::refresh::RefreshState::default(),
             )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
             ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
             traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
                 crate::api::routes::traffic::TrafficStats::new(),
@@ -688,6 +946,57 @@ mod tests {
         (status, json)
     }
 
+    #[tokio::test]
+    async fn test_meta_snapshot() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let epoch_dir = tmp.path().join("normalized").join("current");
+
+        let early = make_event("GT Alpha", "2026-01-15", "https://example.com/gt-alpha");
+        let late = make_event("GT Beta", "2026-02-15", "https://example.com/gt-beta");
+        let mut placements = Vec::new();
+        for event in [&early, &late] {
+            for (rank, (player, faction, wins)) in [
+                ("Alice", "Aeldari", 5),
+                ("Bob", "Orks", 3),
+                ("Cara", "Necrons", 1),
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                let mut p = make_placement(event, rank as u32 + 1, player, faction).with_record(
+                    wins,
+                    5 - wins,
+                    0,
+                );
+                p.detachment = Some(format!("{} Detachment", faction));
+                placements.push(p);
+            }
+        }
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[&early, &late]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
+        let list = make_list("Aeldari", "Battle Host", Some("Alice"), None);
+        write_jsonl(&epoch_dir.join("army_lists.jsonl"), &[&list]);
+
+        let app = build_router(state);
+        let (status, json) = get_json(app.clone(), "/api/meta/snapshot?limit=2").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["epoch_id"], "current");
+        assert_eq!(json["totals"]["events"], 2);
+        assert_eq!(json["recent_events"][0]["name"], "GT Beta");
+        assert_eq!(json["recent_events"][0]["winner"], "Alice");
+        assert_eq!(json["recent_events"][0]["winner_faction"], "Aeldari");
+        assert_eq!(json["top_detachments"].as_array().unwrap().len(), 2);
+        assert_eq!(json["top_units"][0]["unit"], "Test Unit");
+        assert!(json["tiers"].is_array());
+        // Ten games each is under the tier list minimum
+        assert!(json["top_factions"].as_array().unwrap().is_empty());
+        assert!(json["previous_epoch"].is_null());
+
+        let (status, _) = get_json(app, "/api/meta/snapshot?epoch=all").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
     // ── Matching Logic Tests ──────────────────────────────────────
 
     #[tokio::test]
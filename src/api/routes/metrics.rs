@@ -0,0 +1,76 @@
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+
+use crate::api::state::AppState;
+use crate::metrics::CONTENT_TYPE;
+
+/// Prometheus scrape endpoint.
+pub async fn metrics(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, CONTENT_TYPE)],
+        state.metrics.render(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::build_router;
+    use crate::api::state::AppState;
+    use crate::models::EpochMapper;
+    use crate::storage::StorageConfig;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use std::sync::Arc;
+    use tower::util::ServiceExt;
+
+    #[tokio::test]
+    async fn test_metrics_counts_routed_requests() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = AppState {
+            storage: Arc::new(StorageConfig::new(tmp.path().to_path_buf())),
+            epoch_mapper: Arc::new(tokio::sync::RwLock::new(EpochMapper::new())),
+            refresh_state: Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::refresh::RefreshState::default(),
+            )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
+            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
+            traffic_stats: Default::default(),
+        };
+        let app = build_router(state);
+
+        for _ in 0..2 {
+            let req = Request::builder()
+                .uri("/api/epochs")
+                .body(Body::empty())
+                .unwrap();
+            app.clone().oneshot(req).await.unwrap();
+        }
+
+        let req = Request::builder()
+            .uri("/metrics")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers()[axum::http::header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain; version=0.0.4"));
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(text.contains(
+            "meta_http_requests_total{route=\"/api/epochs\",method=\"GET\",status=\"200\"} 2"
+        ));
+        assert!(text.contains("# TYPE meta_sync_duration_seconds histogram"));
+    }
+}
@@ -1,6 +1,18 @@
 pub mod analytics;
 pub mod epochs;
 pub mod events;
+pub mod export;
+pub mod issues;
+pub mod lists;
 pub mod meta;
+pub mod metrics;
+pub mod players;
+pub mod quality;
+#[cfg(feature = "duckdb")]
+pub mod query;
 pub mod refresh;
+pub mod review;
+pub mod series;
+pub mod sync;
 pub mod traffic;
+pub mod units;
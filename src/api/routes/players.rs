@@ -0,0 +1,585 @@
+use std::collections::{BTreeSet, HashMap};
+
+use axum::extract::{Path, State};
+use axum::Json;
+use serde::Serialize;
+
+use crate::api::state::AppState;
+use crate::api::{merge_by_id, ApiError};
+use crate::models::{normalize_faction_name, ArmyList, Event, Pairing, Placement, WinLossRecord};
+use crate::storage::{EntityType, IndexedReader};
+use crate::sync::normalize_player_name;
+
+/// Everything stored across every known epoch.
+struct AllData {
+    events: Vec<Event>,
+    placements: Vec<Placement>,
+    pairings: Vec<Pairing>,
+    lists: Vec<ArmyList>,
+}
+
+async fn load_all(state: &AppState) -> AllData {
+    let mapper = state.epoch_mapper.read().await;
+    let epoch_ids: Vec<String> = if mapper.all_epochs().is_empty() {
+        vec!["current".to_string()]
+    } else {
+        mapper
+            .all_epochs()
+            .iter()
+            .map(|e| e.id.as_str().to_string())
+            .collect()
+    };
+
+    let mut data = AllData {
+        events: Vec::new(),
+        placements: Vec::new(),
+        pairings: Vec::new(),
+        lists: Vec::new(),
+    };
+    for epoch_id in &epoch_ids {
+        if let Ok(e) =
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id)
+                .read_all()
+        {
+            data.events.extend(e);
+        }
+        if let Ok(p) =
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id)
+                .read_all()
+        {
+            data.placements.extend(p);
+        }
+        if let Ok(p) =
+            IndexedReader::<Pairing>::for_entity(&state.storage, EntityType::Pairing, epoch_id)
+                .read_all()
+        {
+            data.pairings.extend(p);
+        }
+        if let Ok(l) =
+            IndexedReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, epoch_id)
+                .read_all()
+        {
+            data.lists.extend(l);
+        }
+    }
+
+    let mut data = AllData {
+        events: merge_by_id(data.events, |e| e.id.as_str()),
+        placements: merge_by_id(data.placements, |p| p.id.as_str()),
+        pairings: merge_by_id(data.pairings, |p| p.id.as_str()),
+        lists: merge_by_id(data.lists, |l| l.id.as_str()),
+    };
+    let overrides = &state.storage.overrides;
+    overrides.retain(&mut data.events);
+    overrides.retain(&mut data.placements);
+    overrides.retain(&mut data.pairings);
+    overrides.retain(&mut data.lists);
+    data
+}
+
+fn rate(part: u32, total: u32) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        (part as f64 / total as f64 * 1000.0).round() / 10.0
+    }
+}
+
+// ── Player Profile Endpoint ─────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct PlayerPlacement {
+    pub event_id: String,
+    pub event_name: String,
+    pub event_date: String,
+    pub epoch_id: String,
+    pub rank: u32,
+    pub player_count: Option<u32>,
+    pub faction: String,
+    pub detachment: Option<String>,
+    pub record: Option<WinLossRecord>,
+    pub list_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlayerGameRecord {
+    /// Games with a reported result
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    /// Percentage of games won (0-100)
+    pub win_rate: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FactionHistoryEntry {
+    pub faction: String,
+    pub events: u32,
+    pub best_rank: u32,
+    pub first_played: String,
+    pub last_played: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlayerArchetype {
+    pub faction: String,
+    pub detachment: String,
+    pub lists: u32,
+    pub list_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HeadToHead {
+    pub opponent: String,
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    /// Factions the opponent used in these games
+    pub opponent_factions: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlayerProfileResponse {
+    /// Name as written in the most recent placement
+    pub name: String,
+    /// Name used for matching (lowercase, collapsed whitespace)
+    pub normalized_name: String,
+    pub total_events: u32,
+    pub event_wins: u32,
+    pub top4_finishes: u32,
+    /// Game record from pairings
+    pub record: PlayerGameRecord,
+    pub placements: Vec<PlayerPlacement>,
+    pub factions: Vec<FactionHistoryEntry>,
+    pub archetypes: Vec<PlayerArchetype>,
+    pub opponents: Vec<HeadToHead>,
+}
+
+/// A player's full history across all epochs. The name is matched after
+/// normalization, so case and spacing differences resolve to one player.
+pub async fn get_player(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<PlayerProfileResponse>, ApiError> {
+    let key = normalize_player_name(&name);
+    if key.is_empty() {
+        return Err(ApiError::BadRequest("Player name is empty".to_string()));
+    }
+    if state.storage.overrides.excludes_player(&name) {
+        return Err(ApiError::NotFound(format!("Player not found: {}", name)));
+    }
+
+    let data = load_all(&state).await;
+    let event_map: HashMap<&str, &Event> = data.events.iter().map(|e| (e.id.as_str(), e)).collect();
+    let event_date = |id: &str| {
+        event_map
+            .get(id)
+            .map(|e| e.date.to_string())
+            .unwrap_or_default()
+    };
+
+    let mut placements: Vec<&Placement> = data
+        .placements
+        .iter()
+        .filter(|p| normalize_player_name(&p.player_name) == key)
+        .collect();
+    placements.sort_by(|a, b| {
+        event_date(b.event_id.as_str())
+            .cmp(&event_date(a.event_id.as_str()))
+            .then(a.rank.cmp(&b.rank))
+    });
+
+    // (pairing, player is player1)
+    let games: Vec<(&Pairing, bool)> = data
+        .pairings
+        .iter()
+        .filter_map(|p| {
+            if normalize_player_name(&p.player1_name) == key {
+                Some((p, true))
+            } else if normalize_player_name(&p.player2_name) == key {
+                Some((p, false))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if placements.is_empty() && games.is_empty() {
+        return Err(ApiError::NotFound(format!("Player not found: {}", name)));
+    }
+
+    let display_name = placements
+        .first()
+        .map(|p| p.player_name.clone())
+        .or_else(|| {
+            games.first().map(|(p, is_p1)| {
+                if *is_p1 {
+                    p.player1_name.clone()
+                } else {
+                    p.player2_name.clone()
+                }
+            })
+        })
+        .unwrap_or(name);
+
+    // Placement history and faction history
+    let mut factions: HashMap<String, FactionHistoryEntry> = HashMap::new();
+    let mut event_ids: BTreeSet<&str> = BTreeSet::new();
+    let mut history = Vec::with_capacity(placements.len());
+    for p in &placements {
+        let event = event_map.get(p.event_id.as_str());
+        let date = event_date(p.event_id.as_str());
+        let faction = normalize_faction_name(&p.faction);
+        event_ids.insert(p.event_id.as_str());
+
+        let entry = factions
+            .entry(faction.clone())
+            .or_insert_with(|| FactionHistoryEntry {
+                faction: faction.clone(),
+                events: 0,
+                best_rank: p.rank,
+                first_played: date.clone(),
+                last_played: date.clone(),
+            });
+        entry.events += 1;
+        entry.best_rank = entry.best_rank.min(p.rank);
+        if date < entry.first_played {
+            entry.first_played = date.clone();
+        }
+        if date > entry.last_played {
+            entry.last_played = date.clone();
+        }
+
+        history.push(PlayerPlacement {
+            event_id: p.event_id.as_str().to_string(),
+            event_name: event.map(|e| e.name.clone()).unwrap_or_default(),
+            event_date: date,
+            epoch_id: p.epoch_id.as_str().to_string(),
+            rank: p.rank,
+            player_count: event.and_then(|e| e.player_count),
+            faction,
+            detachment: p.detachment.clone(),
+            record: p.record.clone(),
+            list_id: p.list_id.as_ref().map(|id| id.as_str().to_string()),
+        });
+    }
+    let mut factions: Vec<FactionHistoryEntry> = factions.into_values().collect();
+    factions.sort_by(|a, b| {
+        b.events
+            .cmp(&a.events)
+            .then_with(|| b.last_played.cmp(&a.last_played))
+    });
+
+    // Lists played: linked from placements, or attributed by name
+    let linked: BTreeSet<&str> = placements
+        .iter()
+        .filter_map(|p| p.list_id.as_ref().map(|id| id.as_str()))
+        .collect();
+    let mut archetypes: HashMap<(String, String), Vec<String>> = HashMap::new();
+    for list in data.lists.iter().filter(|l| {
+        linked.contains(l.id.as_str())
+            || l.player_name
+                .as_deref()
+                .is_some_and(|n| normalize_player_name(n) == key)
+    }) {
+        let detachment = list
+            .detachment
+            .clone()
+            .unwrap_or_else(|| "Unknown".to_string());
+        archetypes
+            .entry((normalize_faction_name(&list.faction), detachment))
+            .or_default()
+            .push(list.id.as_str().to_string());
+    }
+    let mut archetypes: Vec<PlayerArchetype> = archetypes
+        .into_iter()
+        .map(|((faction, detachment), list_ids)| PlayerArchetype {
+            faction,
+            detachment,
+            lists: list_ids.len() as u32,
+            list_ids,
+        })
+        .collect();
+    archetypes.sort_by(|a, b| {
+        b.lists
+            .cmp(&a.lists)
+            .then_with(|| a.faction.cmp(&b.faction))
+            .then_with(|| a.detachment.cmp(&b.detachment))
+    });
+
+    // Game record and head-to-head from pairings
+    let (mut wins, mut losses, mut draws) = (0u32, 0u32, 0u32);
+    let mut opponents: HashMap<String, (HeadToHead, BTreeSet<String>)> = HashMap::new();
+    for (pairing, is_p1) in &games {
+        let (opponent, opponent_faction) = if *is_p1 {
+            (&pairing.player2_name, &pairing.player2_faction)
+        } else {
+            (&pairing.player1_name, &pairing.player1_faction)
+        };
+        let result = match (pairing.player1_result.as_deref(), is_p1) {
+            (Some("win"), true) | (Some("loss"), false) => Some("win"),
+            (Some("loss"), true) | (Some("win"), false) => Some("loss"),
+            (Some("draw"), _) => Some("draw"),
+            _ => None,
+        };
+
+        let (h2h, opp_factions) = opponents
+            .entry(normalize_player_name(opponent))
+            .or_insert_with(|| {
+                (
+                    HeadToHead {
+                        opponent: opponent.clone(),
+                        games: 0,
+                        wins: 0,
+                        losses: 0,
+                        draws: 0,
+                        opponent_factions: Vec::new(),
+                    },
+                    BTreeSet::new(),
+                )
+            });
+        h2h.games += 1;
+        if let Some(f) = opponent_faction {
+            opp_factions.insert(normalize_faction_name(f));
+        }
+        match result {
+            Some("win") => {
+                wins += 1;
+                h2h.wins += 1;
+            }
+            Some("loss") => {
+                losses += 1;
+                h2h.losses += 1;
+            }
+            Some(_) => {
+                draws += 1;
+                h2h.draws += 1;
+            }
+            None => {}
+        }
+    }
+    let mut opponents: Vec<HeadToHead> = opponents
+        .into_values()
+        .map(|(mut h2h, factions)| {
+            h2h.opponent_factions = factions.into_iter().collect();
+            h2h
+        })
+        .collect();
+    opponents.sort_by(|a, b| {
+        b.games
+            .cmp(&a.games)
+            .then_with(|| a.opponent.cmp(&b.opponent))
+    });
+
+    let decided = wins + losses + draws;
+    Ok(Json(PlayerProfileResponse {
+        name: display_name,
+        normalized_name: key,
+        total_events: event_ids.len() as u32,
+        event_wins: placements.iter().filter(|p| p.rank == 1).count() as u32,
+        top4_finishes: placements.iter().filter(|p| p.rank <= 4).count() as u32,
+        record: PlayerGameRecord {
+            games: decided,
+            wins,
+            losses,
+            draws,
+            win_rate: rate(wins, decided),
+        },
+        placements: history,
+        factions,
+        archetypes,
+        opponents,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::build_router;
+    use crate::api::state::AppState;
+    use crate::models::{ArmyList, EpochMapper, Event, Pairing, Placement};
+    use crate::storage::StorageConfig;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use serde_json::Value;
+    use std::sync::Arc;
+    use tower::util::ServiceExt;
+
+    fn write_jsonl<T: serde::Serialize>(path: &std::path::Path, items: &[T]) {
+        let mut content = String::new();
+        for item in items {
+            content.push_str(&serde_json::to_string(item).unwrap());
+            content.push('\n');
+        }
+        std::fs::write(path, content).unwrap();
+    }
+
+    async fn get_json(app: axum::Router, uri: &str) -> (StatusCode, Value) {
+        let resp = app
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = resp.status();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+        (status, json)
+    }
+
+    fn make_event(name: &str, date: &str) -> Event {
+        Event::new(
+            name.to_string(),
+            chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            format!("https://example.com/{}", date),
+            "test".to_string(),
+            "current".into(),
+        )
+    }
+
+    fn make_pairing(event: &Event, round: u32, p1: &str, p2: &str, result: &str) -> Pairing {
+        let mut p = Pairing::new(
+            event.id.clone(),
+            "current".into(),
+            round,
+            p1.to_string(),
+            p2.to_string(),
+        );
+        p.player2_faction = Some("Orks".to_string());
+        p.player1_result = Some(result.to_string());
+        p
+    }
+
+    fn setup(dir: &std::path::Path) -> AppState {
+        let epoch_dir = dir.join("normalized").join("current");
+        std::fs::create_dir_all(&epoch_dir).unwrap();
+
+        let gt = make_event("GT Alpha", "2026-01-15");
+        let rtt = make_event("RTT Beta", "2026-02-20");
+        let list = ArmyList::new("Aeldari".to_string(), 2000, vec![], "raw".to_string())
+            .with_detachment("Warhost".to_string());
+        let placements = vec![
+            Placement::new(
+                gt.id.clone(),
+                "current".into(),
+                1,
+                "Alice Smith".into(),
+                "Aeldari".into(),
+            )
+            .with_list_id(list.id.clone()),
+            Placement::new(
+                rtt.id.clone(),
+                "current".into(),
+                3,
+                "alice  smith".into(),
+                "Necrons".into(),
+            ),
+            Placement::new(
+                rtt.id.clone(),
+                "current".into(),
+                1,
+                "Bob".into(),
+                "Orks".into(),
+            ),
+        ];
+        let pairings = vec![
+            make_pairing(&gt, 1, "Alice Smith", "Bob", "win"),
+            make_pairing(&rtt, 1, "Bob", "ALICE SMITH", "win"),
+            make_pairing(&rtt, 2, "Alice Smith", "Carol", "draw"),
+        ];
+        write_jsonl(&epoch_dir.join("events.jsonl"), &[gt, rtt]);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), &placements);
+        write_jsonl(&epoch_dir.join("pairings.jsonl"), &pairings);
+        write_jsonl(&epoch_dir.join("army_lists.jsonl"), &[list]);
+
+        AppState {
+            storage: Arc::new(StorageConfig::new(dir.to_path_buf())),
+            epoch_mapper: Arc::new(tokio::sync::RwLock::new(EpochMapper::new())),
+            refresh_state: Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::refresh::RefreshState::default(),
+            )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
+            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
+            traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::traffic::TrafficStats::new(),
+            )),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_player_profile() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup(tmp.path());
+
+        let (status, json) = get_json(build_router(state), "/api/players/ALICE%20SMITH").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["name"], "alice  smith");
+        assert_eq!(json["normalized_name"], "alice smith");
+        assert_eq!(json["total_events"], 2);
+        assert_eq!(json["event_wins"], 1);
+
+        let placements = json["placements"].as_array().unwrap();
+        assert_eq!(placements.len(), 2);
+        assert_eq!(placements[0]["event_name"], "RTT Beta");
+        assert_eq!(json["factions"].as_array().unwrap().len(), 2);
+
+        assert_eq!(json["record"]["games"], 3);
+        assert_eq!(json["record"]["wins"], 1);
+        assert_eq!(json["record"]["losses"], 1);
+        assert_eq!(json["record"]["draws"], 1);
+        assert_eq!(json["record"]["win_rate"], 33.3);
+
+        let opponents = json["opponents"].as_array().unwrap();
+        assert_eq!(opponents[0]["opponent"], "Bob");
+        assert_eq!(opponents[0]["games"], 2);
+        assert_eq!(opponents[0]["wins"], 1);
+        assert_eq!(opponents[0]["losses"], 1);
+
+        let archetypes = json["archetypes"].as_array().unwrap();
+        assert_eq!(archetypes.len(), 1);
+        assert_eq!(archetypes[0]["detachment"], "Warhost");
+    }
+
+    #[tokio::test]
+    async fn test_player_not_found() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup(tmp.path());
+        let (status, _) = get_json(build_router(state), "/api/players/nobody").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_player_profile_overrides_exclusions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut state = setup(tmp.path());
+        let rtt = make_event("RTT Beta", "2026-02-20");
+        let overrides = crate::storage::Overrides::parse(&format!(
+            "[exclude]\nevents = [\"{}\"]\nplayers = [\"carol\"]\n",
+            rtt.id.as_str()
+        ))
+        .unwrap();
+        state.storage = Arc::new(
+            StorageConfig::new(tmp.path().to_path_buf()).with_overrides(Arc::new(overrides)),
+        );
+        let app = build_router(state);
+
+        let (status, json) = get_json(app.clone(), "/api/players/Alice%20Smith").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["total_events"], 1);
+        assert_eq!(json["placements"].as_array().unwrap().len(), 1);
+        assert_eq!(json["record"]["games"], 1);
+        assert_eq!(json["opponents"].as_array().unwrap().len(), 1);
+
+        let (status, _) = get_json(app, "/api/players/Carol").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
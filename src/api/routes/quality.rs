@@ -0,0 +1,180 @@
+use std::collections::{BTreeMap, HashMap};
+
+use axum::extract::{Query, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::analytics::resolve_epoch_ids;
+use crate::api::state::AppState;
+use crate::api::{merge_by_id, ApiError, EpochParam};
+use crate::calculate::validation::points_anomalies;
+use crate::ingest::season_summary::read_findings;
+use crate::models::{normalize_faction_name, ArmyList, Event, QualityFinding, QualityFlag};
+use crate::storage::{EntityType, IndexedReader};
+
+#[derive(Debug, Deserialize)]
+pub struct FindingListParams {
+    /// Only findings from this season summary article
+    pub source_url: Option<String>,
+    /// Only findings about this faction
+    pub faction: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FindingListResponse {
+    /// Most recent first
+    pub findings: Vec<QualityFinding>,
+    pub total: usize,
+}
+
+/// Data-quality findings from cross-checking season summaries.
+pub async fn list_findings(
+    State(state): State<AppState>,
+    Query(params): Query<FindingListParams>,
+) -> Result<Json<FindingListResponse>, ApiError> {
+    let findings: Vec<QualityFinding> = read_findings(&state.storage)
+        .map_err(|e| ApiError::Internal(format!("Failed to read quality findings: {}", e)))?
+        .into_iter()
+        .filter(|f| {
+            params
+                .source_url
+                .as_deref()
+                .is_none_or(|u| f.source_url == u)
+        })
+        .filter(|f| {
+            params
+                .faction
+                .as_deref()
+                .is_none_or(|faction| f.subject.as_deref() == Some(faction))
+        })
+        .collect();
+    let total = findings.len();
+    Ok(Json(FindingListResponse { findings, total }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListQualityParams {
+    /// Only lists with a flag of this kind (e.g. `total_mismatch`)
+    pub kind: Option<String>,
+    pub faction: Option<String>,
+    /// Lists returned (default 100)
+    pub limit: Option<usize>,
+}
+
+/// A list with points problems.
+#[derive(Debug, Serialize)]
+pub struct FlaggedList {
+    pub list_id: String,
+    pub epoch_id: String,
+    pub faction: String,
+    pub detachment: Option<String>,
+    pub player_name: Option<String>,
+    pub event_id: Option<String>,
+    pub event_name: Option<String>,
+    /// The event's points limit, if it gives one
+    pub game_size: Option<u32>,
+    pub total_points: u32,
+    pub unit_points: u32,
+    pub flags: Vec<QualityFlag>,
+    /// The flags, readably
+    pub messages: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListQualityResponse {
+    /// Lists validated
+    pub checked: usize,
+    /// Lists with at least one flag (before `kind`/`faction` filters)
+    pub flagged: usize,
+    /// Flags of each kind across all lists
+    pub by_kind: BTreeMap<String, usize>,
+    /// Most flags first
+    pub lists: Vec<FlaggedList>,
+}
+
+/// Lists whose points don't add up or don't fit their event's game size.
+///
+/// Validated on every request, so the report follows the current rules
+/// even for lists `maintain` hasn't re-flagged yet.
+pub async fn list_quality(
+    State(state): State<AppState>,
+    epoch: EpochParam,
+    Query(params): Query<ListQualityParams>,
+) -> Result<Json<ListQualityResponse>, ApiError> {
+    let epoch_ids = {
+        let mapper = state.epoch_mapper.read().await;
+        resolve_epoch_ids(epoch.as_deref(), mapper.all_epochs(), &mapper)?
+    };
+    let faction = params.faction.as_deref().map(normalize_faction_name);
+
+    let (mut checked, mut flagged) = (0, 0);
+    let mut by_kind: BTreeMap<String, usize> = BTreeMap::new();
+    let mut lists = Vec::new();
+    for epoch_id in &epoch_ids {
+        let events: HashMap<String, Event> =
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id)
+                .read_all()
+                .map_err(|e| ApiError::Internal(e.to_string()))?
+                .into_iter()
+                .map(|e| (e.id.as_str().to_string(), e))
+                .collect();
+        let epoch_lists = merge_by_id(
+            IndexedReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, epoch_id)
+                .read_all()
+                .map_err(|e| ApiError::Internal(e.to_string()))?,
+            |l| l.id.as_str(),
+        );
+
+        for list in epoch_lists {
+            checked += 1;
+            let event = list
+                .event_id
+                .as_ref()
+                .and_then(|id| events.get(id.as_str()));
+            let flags = points_anomalies(&list, event.and_then(|e| e.game_size));
+            if flags.is_empty() {
+                continue;
+            }
+            flagged += 1;
+            let kinds: Vec<String> = flags.iter().map(|f| f.kind().to_string()).collect();
+            for kind in &kinds {
+                *by_kind.entry(kind.clone()).or_default() += 1;
+            }
+            if params.kind.as_ref().is_some_and(|k| !kinds.contains(k))
+                || faction
+                    .as_ref()
+                    .is_some_and(|f| normalize_faction_name(&list.faction) != *f)
+            {
+                continue;
+            }
+            lists.push(FlaggedList {
+                list_id: list.id.as_str().to_string(),
+                epoch_id: epoch_id.clone(),
+                faction: list.faction.clone(),
+                detachment: list.detachment.clone(),
+                player_name: list.player_name.clone(),
+                event_id: list.event_id.as_ref().map(|id| id.as_str().to_string()),
+                event_name: event.map(|e| e.name.clone()),
+                game_size: event.and_then(|e| e.game_size),
+                total_points: list.total_points,
+                unit_points: list.units.iter().filter_map(|u| u.points).sum(),
+                messages: flags.iter().map(ToString::to_string).collect(),
+                flags,
+            });
+        }
+    }
+    lists.sort_by(|a, b| {
+        b.flags
+            .len()
+            .cmp(&a.flags.len())
+            .then_with(|| a.list_id.cmp(&b.list_id))
+    });
+    lists.truncate(params.limit.unwrap_or(100));
+
+    Ok(Json(ListQualityResponse {
+        checked,
+        flagged,
+        by_kind,
+        lists,
+    }))
+}
@@ -0,0 +1,38 @@
+//! Ad-hoc SQL endpoint (`duckdb` feature).
+//!
+//! `POST /api/query` runs a read-only query over the Parquet lake. See
+//! [`crate::storage::duckdb`] for what queries may do.
+
+use axum::extract::State;
+use axum::Json;
+use serde::Deserialize;
+
+use crate::api::state::AppState;
+use crate::api::ApiError;
+use crate::storage::duckdb::{self, QueryError, QueryResult};
+
+#[derive(Debug, Deserialize)]
+pub struct QueryRequest {
+    pub sql: String,
+    /// Maximum rows to return (default 1000, capped at 10000)
+    pub limit: Option<usize>,
+}
+
+pub async fn run_query(
+    State(state): State<AppState>,
+    Json(request): Json<QueryRequest>,
+) -> Result<Json<QueryResult>, ApiError> {
+    let query = duckdb::validate_query(&request.sql, request.limit)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let parquet_dir = state.storage.parquet_dir();
+
+    let result = tokio::task::spawn_blocking(move || duckdb::run_query(&parquet_dir, &query))
+        .await
+        .map_err(|e| ApiError::Internal(format!("Query task failed: {}", e)))?;
+    match result {
+        Ok(result) => Ok(Json(result)),
+        // Bad SQL is the caller's problem, not a server fault
+        Err(e @ QueryError::Engine(_)) => Err(ApiError::BadRequest(e.to_string())),
+        Err(e) => Err(ApiError::Internal(e.to_string())),
+    }
+}
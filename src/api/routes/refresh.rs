@@ -1,27 +1,21 @@
+use std::convert::Infallible;
 use std::sync::Arc;
 
 use axum::extract::{Query, State};
 use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event as SseEvent, KeepAlive, Sse};
 use axum::response::IntoResponse;
 use axum::Json;
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::api::state::AppState;
-use crate::api::ApiError;
+use crate::api::{require_local, ApiError};
 use crate::models::{Event, Placement};
-use crate::storage::{EntityType, JsonlReader};
-
-/// Reject requests that come through Cloudflare Tunnel (public domain).
-/// Cloudflare always adds the `CF-Connecting-IP` header to proxied requests.
-fn require_local(headers: &HeaderMap) -> Result<(), ApiError> {
-    if headers.contains_key("cf-connecting-ip") {
-        return Err(ApiError::Forbidden(
-            "Refresh is only available on localhost".to_string(),
-        ));
-    }
-    Ok(())
-}
+use crate::storage::{EntityType, IndexedReader, JsonlReader};
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
 
 // ── Types ────────────────────────────────────────────────────────
 
@@ -110,6 +104,19 @@ pub struct EventProgress {
     pub detail: String,
 }
 
+/// Sender for live refresh updates; each message is a full state snapshot.
+pub type RefreshUpdates = tokio::sync::broadcast::Sender<RefreshState>;
+
+/// Create the channel that carries refresh snapshots to stream clients.
+pub fn refresh_channel() -> RefreshUpdates {
+    tokio::sync::broadcast::channel(64).0
+}
+
+/// Push a snapshot to stream clients. Having no subscribers is not an error.
+fn publish(updates: &RefreshUpdates, state: &RefreshState) {
+    let _ = updates.send(state.clone());
+}
+
 // ── Preview ──────────────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
@@ -171,7 +178,7 @@ pub async fn preview(
     State(state): State<AppState>,
     Query(params): Query<PreviewParams>,
 ) -> Result<Json<PreviewResponse>, ApiError> {
-    require_local(&headers)?;
+    require_local(&headers, "Refresh")?;
     let today = Utc::now().date_naive();
     let date_from = parse_date_or(params.date_from.as_deref(), today - chrono::Days::new(30));
     // Clamp date_to to today
@@ -198,12 +205,13 @@ pub async fn preview(
     let mut all_placements: Vec<Placement> = Vec::new();
 
     for epoch_id in &epoch_ids {
-        let reader = JsonlReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id);
+        let reader =
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id);
         if let Ok(events) = reader.read_all() {
             all_events.extend(events);
         }
         let reader =
-            JsonlReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id);
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id);
         if let Ok(placements) = reader.read_all() {
             all_placements.extend(placements);
         }
@@ -239,7 +247,7 @@ pub async fn start_refresh(
     State(state): State<AppState>,
     Json(params): Json<StartParams>,
 ) -> Result<impl IntoResponse, ApiError> {
-    require_local(&headers)?;
+    require_local(&headers, "Refresh")?;
     let today = Utc::now().date_naive();
     let date_from = parse_date_or(params.date_from.as_deref(), today - chrono::Days::new(30));
     let date_to_raw = parse_date_or(params.date_to.as_deref(), today);
@@ -269,24 +277,13 @@ pub async fn start_refresh(
             progress: RefreshProgress::default(),
             errors: Vec::new(),
         };
+        publish(&state.refresh_updates, &refresh);
     }
 
     // Spawn background task
-    let refresh_state = state.refresh_state.clone();
-    let storage = state.storage.clone();
-    let epoch_mapper = state.epoch_mapper.clone();
-    let ai_backend = state.ai_backend.clone();
-
+    let app = state.clone();
     tokio::spawn(async move {
-        run_refresh_pipeline(
-            refresh_state,
-            storage,
-            epoch_mapper,
-            ai_backend,
-            date_from,
-            date_to,
-        )
-        .await;
+        run_refresh_pipeline(app, date_from, date_to).await;
     });
 
     // Return 202 with current state
@@ -301,16 +298,54 @@ pub async fn status(State(state): State<AppState>) -> Json<RefreshState> {
     Json(current.clone())
 }
 
+// ── Stream ───────────────────────────────────────────────────────
+
+/// Server-sent events for live refresh progress.
+///
+/// Each `refresh` event carries a full [`RefreshState`] (the same shape as
+/// `/api/refresh/status`), sent first on connect and then whenever the
+/// pipeline reports progress, including per-event calendar updates.
+pub async fn stream(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<SseEvent, Infallible>>> {
+    // Subscribe before reading the snapshot so no update falls in between
+    let rx = state.refresh_updates.subscribe();
+    let initial = state.refresh_state.read().await.clone();
+
+    let updates = BroadcastStream::new(rx).filter_map(|msg| match msg {
+        Ok(snapshot) => Some(snapshot),
+        // A slow client missed some snapshots; the next one supersedes them
+        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+            tracing::debug!("Refresh stream client lagged by {} updates", skipped);
+            None
+        }
+    });
+    let events = tokio_stream::once(initial)
+        .chain(updates)
+        .map(|snapshot| Ok(sse_event(&snapshot)));
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+fn sse_event(snapshot: &RefreshState) -> SseEvent {
+    let data = serde_json::to_string(snapshot).unwrap_or_default();
+    SseEvent::default().event("refresh").data(data)
+}
+
 // ── Background Pipeline ──────────────────────────────────────────
 
-async fn run_refresh_pipeline(
-    refresh_state: Arc<tokio::sync::RwLock<RefreshState>>,
-    storage: Arc<crate::storage::StorageConfig>,
-    epoch_mapper: Arc<tokio::sync::RwLock<crate::models::EpochMapper>>,
-    ai_backend: Arc<dyn crate::agents::backend::AiBackend>,
-    date_from: NaiveDate,
-    _date_to: NaiveDate,
-) {
+async fn run_refresh_pipeline(app: AppState, date_from: NaiveDate, _date_to: NaiveDate) {
+    let AppState {
+        refresh_state,
+        refresh_updates: updates,
+        syncs,
+        storage,
+        epoch_mapper,
+        ai_backend,
+        notifier,
+        grace_days,
+        ..
+    } = app;
     let today = Utc::now().date_naive();
     let mut errors: Vec<String> = Vec::new();
 
@@ -319,10 +354,11 @@ async fn run_refresh_pipeline(
         let mut state = refresh_state.write().await;
         state.phase = RefreshPhase::CheckingBalance;
         state.progress.message = "Checking Warhammer Community for balance updates...".to_string();
+        publish(&updates, &state);
     }
 
     let mut new_balance_passes = 0u32;
-    match run_balance_check(&storage, &ai_backend).await {
+    match run_balance_check(&storage, &ai_backend, grace_days).await {
         Ok(count) => {
             new_balance_passes = count;
             let mut state = refresh_state.write().await;
@@ -332,6 +368,7 @@ async fn run_refresh_pipeline(
             } else {
                 "No new balance changes".to_string()
             };
+            publish(&updates, &state);
         }
         Err(e) => {
             let msg = format!("Balance check failed: {}", e);
@@ -339,6 +376,7 @@ async fn run_refresh_pipeline(
             errors.push(msg);
             let mut state = refresh_state.write().await;
             state.progress.message = "Balance check failed, continuing...".to_string();
+            publish(&updates, &state);
         }
     }
 
@@ -347,14 +385,17 @@ async fn run_refresh_pipeline(
         let mut state = refresh_state.write().await;
         state.phase = RefreshPhase::SyncingResults;
         state.progress.message = "Discovering BCP events and fetching results...".to_string();
+        publish(&updates, &state);
     }
 
     match run_sync(
+        &syncs,
         &storage,
         &ai_backend,
+        notifier,
         date_from,
         today,
-        refresh_state.clone(),
+        progress_callback(refresh_state.clone(), updates.clone()),
     )
     .await
     {
@@ -367,6 +408,7 @@ async fn run_refresh_pipeline(
                 "Synced {} events, {} placements, {} lists",
                 events, placements, lists
             );
+            publish(&updates, &state);
         }
         Err(e) => {
             let msg = format!("Sync failed: {}", e);
@@ -374,17 +416,13 @@ async fn run_refresh_pipeline(
             errors.push(msg);
             let mut state = refresh_state.write().await;
             state.progress.message = "Sync failed, continuing...".to_string();
+            publish(&updates, &state);
         }
     }
 
     // Compute cumulative totals across all epochs in the database
     {
-        let mapper = crate::storage::read_significant_events(&storage).unwrap_or_default();
-        let epoch_mapper = if mapper.is_empty() {
-            crate::models::EpochMapper::new()
-        } else {
-            crate::models::EpochMapper::from_significant_events(&mapper)
-        };
+        let epoch_mapper = crate::storage::cached_epoch_mapper(&storage);
 
         let epoch_ids: Vec<String> = {
             let epochs = epoch_mapper.all_epochs();
@@ -401,16 +439,16 @@ async fn run_refresh_pipeline(
 
         for epoch_id in &epoch_ids {
             let event_reader =
-                JsonlReader::<Event>::for_entity(&storage, EntityType::Event, epoch_id);
+                IndexedReader::<Event>::for_entity(&storage, EntityType::Event, epoch_id);
             if let Ok(events) = event_reader.read_all() {
                 total_events += events.len() as u32;
             }
             let placement_reader =
-                JsonlReader::<Placement>::for_entity(&storage, EntityType::Placement, epoch_id);
+                IndexedReader::<Placement>::for_entity(&storage, EntityType::Placement, epoch_id);
             if let Ok(placements) = placement_reader.read_all() {
                 total_placements += placements.len() as u32;
             }
-            let list_reader = JsonlReader::<crate::models::ArmyList>::for_entity(
+            let list_reader = IndexedReader::<crate::models::ArmyList>::for_entity(
                 &storage,
                 EntityType::ArmyList,
                 epoch_id,
@@ -424,6 +462,7 @@ async fn run_refresh_pipeline(
         state.progress.total_events = total_events;
         state.progress.total_placements = total_placements;
         state.progress.total_lists = total_lists;
+        publish(&updates, &state);
     }
 
     // Step 3: Discover future events
@@ -431,6 +470,7 @@ async fn run_refresh_pipeline(
         let mut state = refresh_state.write().await;
         state.phase = RefreshPhase::DiscoveringFuture;
         state.progress.message = "Discovering upcoming BCP events...".to_string();
+        publish(&updates, &state);
     }
 
     let future_end = today + chrono::Days::new(60);
@@ -439,6 +479,7 @@ async fn run_refresh_pipeline(
             let mut state = refresh_state.write().await;
             state.progress.future_events_found = count;
             state.progress.message = format!("Found {} upcoming events", count);
+            publish(&updates, &state);
         }
         Err(e) => {
             let msg = format!("Future discovery failed: {}", e);
@@ -446,6 +487,7 @@ async fn run_refresh_pipeline(
             errors.push(msg);
             let mut state = refresh_state.write().await;
             state.progress.message = "Future discovery failed".to_string();
+            publish(&updates, &state);
         }
     }
 
@@ -454,6 +496,7 @@ async fn run_refresh_pipeline(
         {
             let mut state = refresh_state.write().await;
             state.phase = RefreshPhase::Repartitioning;
+            publish(&updates, &state);
         }
 
         match crate::sync::repartition::repartition(&storage, "current", false, false) {
@@ -472,16 +515,7 @@ async fn run_refresh_pipeline(
     }
 
     // Step 5: Rebuild epoch mapper
-    {
-        let sig_events = crate::storage::read_significant_events(&storage).unwrap_or_default();
-        let new_mapper = if sig_events.is_empty() {
-            crate::models::EpochMapper::new()
-        } else {
-            crate::models::EpochMapper::from_significant_events(&sig_events)
-        };
-        let mut mapper = epoch_mapper.write().await;
-        *mapper = new_mapper;
-    }
+    reload_epoch_mapper(&storage, &epoch_mapper).await;
 
     // Final state — only mark as Failed if the core sync step had errors.
     // Balance check and future discovery errors are non-critical warnings.
@@ -496,12 +530,14 @@ async fn run_refresh_pipeline(
         } else {
             RefreshStatus::Completed
         };
+        publish(&updates, &state);
     }
 }
 
 async fn run_balance_check(
     storage: &crate::storage::StorageConfig,
     backend: &Arc<dyn crate::agents::backend::AiBackend>,
+    grace_days: u32,
 ) -> Result<u32, anyhow::Error> {
     let fetcher = crate::fetch::Fetcher::new(crate::fetch::FetcherConfig {
         cache_dir: storage.raw_dir(),
@@ -514,18 +550,18 @@ async fn run_balance_check(
     let html = fetcher.read_cached_text(&fetch_result).await?;
 
     use crate::agents::balance_watcher::{BalanceWatcherAgent, BalanceWatcherInput};
-    use crate::agents::Agent;
+    use crate::agents::execute_with_retry;
     let existing = crate::storage::read_significant_events(storage).unwrap_or_default();
     let known_ids = existing.iter().map(|e| e.id.clone()).collect();
 
-    let watcher = BalanceWatcherAgent::new(backend.clone());
+    let watcher = BalanceWatcherAgent::new(backend.clone()).with_grace_days(grace_days);
     let input = BalanceWatcherInput {
         html_content: html,
         source_url: wh_url.to_string(),
         known_event_ids: known_ids,
     };
 
-    let output = watcher.execute(input).await?;
+    let output = execute_with_retry(&watcher, input).await?;
     let new_count = output.events.len() as u32;
 
     if new_count > 0 {
@@ -544,11 +580,13 @@ async fn run_balance_check(
 }
 
 async fn run_sync(
+    syncs: &crate::sync::ActiveSyncs,
     storage: &crate::storage::StorageConfig,
     backend: &Arc<dyn crate::agents::backend::AiBackend>,
+    notifier: Option<Arc<crate::notify::Notifier>>,
     date_from: NaiveDate,
     date_to: NaiveDate,
-    refresh_state: Arc<tokio::sync::RwLock<RefreshState>>,
+    progress: impl Fn(crate::sync::SyncProgress) + Send + Sync + 'static,
 ) -> Result<(u32, u32, u32), anyhow::Error> {
     let fetcher = crate::fetch::Fetcher::new(crate::fetch::FetcherConfig {
         cache_dir: storage.raw_dir(),
@@ -556,58 +594,25 @@ async fn run_sync(
     })?;
 
     let sync_config = crate::sync::SyncConfig {
-        sources: vec![crate::sync::SyncSource::default()],
+        sources: vec![crate::sync::SyncSource::default().into_source()],
         interval: std::time::Duration::from_secs(3600),
         date_from: Some(date_from),
         date_to: Some(date_to),
         dry_run: false,
+        full: false,
+        upcoming: false,
+        // BCP only; balance passes come from the balance check
+        grace_days: 0,
         storage: storage.clone(),
     };
 
-    let rs = refresh_state.clone();
-    let orchestrator = crate::sync::SyncOrchestrator::new(sync_config, fetcher, backend.clone())
-        .with_progress_callback(move |progress| {
-            // Update refresh state in a blocking fashion (callback is sync)
-            if let Ok(mut state) = rs.try_write() {
-                state.progress.events_synced = progress.events_synced;
-                state.progress.placements_synced = progress.placements_synced;
-                state.progress.lists_normalized = progress.lists_normalized;
-                state.progress.events_discovered = progress.events_discovered;
-                state.progress.current_event_index = progress.current_event_index;
-                state.progress.message = progress.message.clone();
-                // Only update discovered_events when the sync sends a non-empty list;
-                // inner calls (e.g. from sync_bcp_standings) send Vec::new() and
-                // should NOT wipe the existing per-event progress.
-                if !progress.discovered_events.is_empty() {
-                    state.progress.discovered_events = progress
-                        .discovered_events
-                        .iter()
-                        .map(|sep| EventProgress {
-                            name: sep.name.clone(),
-                            date: sep.date.clone(),
-                            player_count: sep.player_count,
-                            status: match sep.status {
-                                crate::sync::SyncEventStatus::Pending => EventSyncStatus::Pending,
-                                crate::sync::SyncEventStatus::Syncing => EventSyncStatus::Syncing,
-                                crate::sync::SyncEventStatus::Done => EventSyncStatus::Done,
-                                crate::sync::SyncEventStatus::Skipped => EventSyncStatus::Skipped,
-                            },
-                            placements_found: sep.placements_found,
-                            lists_found: sep.lists_found,
-                            detail: sep.detail.clone(),
-                        })
-                        .collect();
-                } else if !progress.message.is_empty() {
-                    // Update the detail of the currently-syncing event with the message
-                    for ev in &mut state.progress.discovered_events {
-                        if matches!(ev.status, EventSyncStatus::Syncing) {
-                            ev.detail = progress.message.clone();
-                        }
-                    }
-                }
-            }
-        });
-    let result = orchestrator.sync_once().await?;
+    let mut orchestrator =
+        crate::sync::SyncOrchestrator::new(sync_config, fetcher, backend.clone())
+            .with_progress_callback(progress);
+    if let Some(notifier) = notifier {
+        orchestrator = orchestrator.with_post_sync_hook(notifier);
+    }
+    let result = syncs.run(orchestrator).await?;
 
     Ok((
         result.events_synced,
@@ -616,6 +621,65 @@ async fn run_sync(
     ))
 }
 
+/// Mirror sync progress into the shared refresh state so
+/// `/api/refresh/status` and the SSE stream show it.
+fn progress_callback(
+    rs: Arc<tokio::sync::RwLock<RefreshState>>,
+    updates: RefreshUpdates,
+) -> impl Fn(crate::sync::SyncProgress) + Send + Sync + 'static {
+    move |progress| {
+        // Update refresh state in a blocking fashion (callback is sync)
+        if let Ok(mut state) = rs.try_write() {
+            state.progress.events_synced = progress.events_synced;
+            state.progress.placements_synced = progress.placements_synced;
+            state.progress.lists_normalized = progress.lists_normalized;
+            state.progress.events_discovered = progress.events_discovered;
+            state.progress.current_event_index = progress.current_event_index;
+            state.progress.message = progress.message.clone();
+            // Only update discovered_events when the sync sends a non-empty list;
+            // inner calls (e.g. from sync_bcp_standings) send Vec::new() and
+            // should NOT wipe the existing per-event progress.
+            if !progress.discovered_events.is_empty() {
+                state.progress.discovered_events = progress
+                    .discovered_events
+                    .iter()
+                    .map(|sep| EventProgress {
+                        name: sep.name.clone(),
+                        date: sep.date.clone(),
+                        player_count: sep.player_count,
+                        status: match sep.status {
+                            crate::sync::SyncEventStatus::Pending => EventSyncStatus::Pending,
+                            crate::sync::SyncEventStatus::Syncing => EventSyncStatus::Syncing,
+                            crate::sync::SyncEventStatus::Done => EventSyncStatus::Done,
+                            crate::sync::SyncEventStatus::Skipped => EventSyncStatus::Skipped,
+                        },
+                        placements_found: sep.placements_found,
+                        lists_found: sep.lists_found,
+                        detail: sep.detail.clone(),
+                    })
+                    .collect();
+            } else if !progress.message.is_empty() {
+                // Update the detail of the currently-syncing event with the message
+                for ev in &mut state.progress.discovered_events {
+                    if matches!(ev.status, EventSyncStatus::Syncing) {
+                        ev.detail = progress.message.clone();
+                    }
+                }
+            }
+            publish(&updates, &state);
+        }
+    }
+}
+
+/// Rebuild the shared epoch mapper from the significant events on disk.
+pub async fn reload_epoch_mapper(
+    storage: &crate::storage::StorageConfig,
+    epoch_mapper: &tokio::sync::RwLock<crate::models::EpochMapper>,
+) {
+    let new_mapper = crate::storage::cached_epoch_mapper(storage);
+    *epoch_mapper.write().await = crate::models::EpochMapper::clone(&new_mapper);
+}
+
 async fn run_future_discovery(
     storage: &crate::storage::StorageConfig,
     _backend: &Arc<dyn crate::agents::backend::AiBackend>,
@@ -639,12 +703,7 @@ async fn run_future_discovery(
     let new_event_count = bcp_events.len() as u32;
 
     // Determine epoch for future events
-    let sig_events = crate::storage::read_significant_events(storage).unwrap_or_default();
-    let epoch_mapper = if sig_events.is_empty() {
-        crate::models::EpochMapper::new()
-    } else {
-        crate::models::EpochMapper::from_significant_events(&sig_events)
-    };
+    let epoch_mapper = crate::storage::cached_epoch_mapper(storage);
 
     let mut stored = 0u32;
     for bcp_event in &bcp_events {
@@ -691,6 +750,111 @@ async fn run_future_discovery(
     Ok(stored)
 }
 
+// ── Embedded Background Sync ─────────────────────────────────────
+
+/// Run the sync orchestrator every `sync_config.interval` inside the server
+/// process (`serve --with-sync`). Progress is reported through the shared
+/// refresh state and the epoch mapper is reloaded after each run.
+pub async fn run_background_sync(
+    state: AppState,
+    sync_config: crate::sync::SyncConfig,
+    fetcher_config: crate::fetch::FetcherConfig,
+) {
+    let mut ticker = tokio::time::interval(sync_config.interval);
+    tracing::info!(
+        "Background sync every {:?} ({} source(s))",
+        sync_config.interval,
+        sync_config.sources.len()
+    );
+    loop {
+        ticker.tick().await;
+        if state.syncs.is_shutting_down() {
+            return;
+        }
+        background_sync_once(&state, &sync_config, &fetcher_config).await;
+    }
+}
+
+/// One background sync run. Returns false if skipped because a refresh was
+/// already running.
+async fn background_sync_once(
+    state: &AppState,
+    sync_config: &crate::sync::SyncConfig,
+    fetcher_config: &crate::fetch::FetcherConfig,
+) -> bool {
+    {
+        let mut refresh = state.refresh_state.write().await;
+        if refresh.status == RefreshStatus::Running {
+            tracing::info!("Background sync skipped: a refresh is already running");
+            return false;
+        }
+        *refresh = RefreshState {
+            status: RefreshStatus::Running,
+            phase: RefreshPhase::SyncingResults,
+            started_at: Some(Utc::now()),
+            completed_at: None,
+            progress: RefreshProgress {
+                message: "Background sync running...".to_string(),
+                ..Default::default()
+            },
+            errors: Vec::new(),
+        };
+        publish(&state.refresh_updates, &refresh);
+    }
+
+    let result = match crate::fetch::Fetcher::new(fetcher_config.clone()) {
+        Ok(fetcher) => {
+            let mut orchestrator = crate::sync::SyncOrchestrator::new(
+                sync_config.clone(),
+                fetcher,
+                state.ai_backend.clone(),
+            )
+            .with_progress_callback(progress_callback(
+                state.refresh_state.clone(),
+                state.refresh_updates.clone(),
+            ));
+            if let Some(notifier) = state.notifier.clone() {
+                orchestrator = orchestrator.with_post_sync_hook(notifier);
+            }
+            state.syncs.run(orchestrator).await
+        }
+        Err(e) => Err(e.into()),
+    };
+
+    // New balance passes may have been registered during the sync
+    reload_epoch_mapper(&state.storage, &state.epoch_mapper).await;
+
+    let mut refresh = state.refresh_state.write().await;
+    refresh.phase = RefreshPhase::Done;
+    refresh.completed_at = Some(Utc::now());
+    match result {
+        Ok(result) => {
+            refresh.progress.events_synced = result.events_synced;
+            refresh.progress.placements_synced = result.placements_synced;
+            refresh.progress.lists_normalized = result.lists_normalized;
+            refresh.progress.message = format!(
+                "Background sync: {} events, {} placements, {} lists",
+                result.events_synced, result.placements_synced, result.lists_normalized
+            );
+            refresh.status = if result.errors.is_empty() {
+                RefreshStatus::Completed
+            } else {
+                RefreshStatus::Failed
+            };
+            refresh.errors = result.errors;
+        }
+        Err(e) => {
+            let msg = format!("Sync failed: {}", e);
+            tracing::warn!("Background {}", msg);
+            refresh.progress.message = "Background sync failed".to_string();
+            refresh.status = RefreshStatus::Failed;
+            refresh.errors = vec![msg];
+        }
+    }
+    publish(&state.refresh_updates, &refresh);
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -721,6 +885,14 @@ mod tests {
             storage: Arc::new(storage),
             epoch_mapper: Arc::new(tokio::sync::RwLock::new(EpochMapper::new())),
             refresh_state: Arc::new(tokio::sync::RwLock::new(RefreshState::default())),
+            refresh_updates: refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
             ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
             traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
                 crate::api::routes::traffic::TrafficStats::new(),
@@ -781,6 +953,15 @@ mod tests {
         (status, json)
     }
 
+    async fn next_frame(body: &mut axum::body::BodyDataStream) -> String {
+        let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), body.next())
+            .await
+            .expect("stream stalled")
+            .unwrap()
+            .unwrap();
+        String::from_utf8(chunk.to_vec()).unwrap()
+    }
+
     // ── Unit Tests ───────────────────────────────────────────────
 
     #[test]
@@ -1026,6 +1207,50 @@ mod tests {
         assert_eq!(json["progress"]["events_synced"], 3);
     }
 
+    #[tokio::test]
+    async fn test_stream_sends_snapshot_then_updates() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let updates = state.refresh_updates.clone();
+
+        let app = build_router(state);
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/refresh/stream")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers()["content-type"], "text/event-stream");
+
+        let mut body = resp.into_body().into_data_stream();
+        let first = next_frame(&mut body).await;
+        assert!(first.starts_with("event: refresh\n"));
+        assert!(first.contains("\"status\":\"idle\""));
+
+        let mut running = RefreshState {
+            status: RefreshStatus::Running,
+            ..Default::default()
+        };
+        running.progress.discovered_events.push(EventProgress {
+            name: "London GT".to_string(),
+            date: "2026-01-10".to_string(),
+            player_count: 96,
+            status: EventSyncStatus::Syncing,
+            placements_found: 0,
+            lists_found: 0,
+            detail: "Fetching standings".to_string(),
+        });
+        publish(&updates, &running);
+
+        let second = next_frame(&mut body).await;
+        assert!(second.contains("\"status\":\"running\""));
+        assert!(second.contains("London GT"));
+    }
+
     #[tokio::test]
     async fn test_preview_with_custom_dates() {
         let tmp = tempfile::tempdir().unwrap();
@@ -1046,4 +1271,47 @@ mod tests {
         assert_eq!(json["events_in_range"], 0);
         assert_eq!(json["scheduled_without_data"], 0);
     }
+
+    #[tokio::test]
+    async fn test_background_sync_reports_status_and_reloads_mapper() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let mut sig = vec![crate::models::SignificantEvent::new(
+            crate::models::SignificantEventType::BalanceUpdate,
+            chrono::NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            "June Update".to_string(),
+            "https://example.com".to_string(),
+        )];
+        crate::storage::write_significant_events(&state.storage, &mut sig).unwrap();
+
+        // No sources: the sync fails fast without touching the network
+        let sync_config = crate::sync::SyncConfig {
+            sources: vec![],
+            storage: (*state.storage).clone(),
+            ..Default::default()
+        };
+        let fetcher_config = crate::fetch::FetcherConfig {
+            cache_dir: state.storage.raw_dir(),
+            ..Default::default()
+        };
+
+        assert!(background_sync_once(&state, &sync_config, &fetcher_config).await);
+        let (status, json) = get_json(build_router(state.clone()), "/api/refresh/status").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["status"], "failed");
+        assert_eq!(json["phase"], "done");
+        assert!(json["errors"][0]
+            .as_str()
+            .unwrap()
+            .starts_with("Sync failed"));
+        assert_eq!(state.epoch_mapper.read().await.all_epochs().len(), 1);
+
+        // A manual refresh in progress is left alone
+        state.refresh_state.write().await.status = RefreshStatus::Running;
+        assert!(!background_sync_once(&state, &sync_config, &fetcher_config).await);
+        assert_eq!(
+            state.refresh_state.read().await.status,
+            RefreshStatus::Running
+        );
+    }
 }
@@ -0,0 +1,358 @@
+use axum::extract::{Path, Query, State};
+use axum::http::HeaderMap;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::api::state::AppState;
+use crate::api::{require_local, ApiError};
+use crate::models::{EntityType, ResolutionAction, ReviewQueueItem};
+use crate::storage;
+
+fn load_items(state: &AppState) -> Result<Vec<ReviewQueueItem>, ApiError> {
+    storage::read_review_items(&state.storage)
+        .map_err(|e| ApiError::Internal(format!("Failed to read review queue: {}", e)))
+}
+
+// ── Review List Endpoint ────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewListParams {
+    /// "pending" (default), "resolved", or "all"
+    pub status: Option<String>,
+    pub entity_type: Option<String>,
+    pub reason: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReviewListResponse {
+    pub items: Vec<ReviewQueueItem>,
+    pub total: usize,
+    pub pending: usize,
+}
+
+pub async fn list_review_items(
+    State(state): State<AppState>,
+    Query(params): Query<ReviewListParams>,
+) -> Result<Json<ReviewListResponse>, ApiError> {
+    let all = load_items(&state)?;
+    let pending = all.iter().filter(|i| i.is_pending()).count();
+
+    let status = params.status.as_deref().unwrap_or("pending");
+    if !matches!(status, "pending" | "resolved" | "all") {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid status '{}': expected pending, resolved, or all",
+            status
+        )));
+    }
+
+    let mut items: Vec<ReviewQueueItem> = all
+        .into_iter()
+        .filter(|i| match status {
+            "pending" => i.is_pending(),
+            "resolved" => !i.is_pending(),
+            _ => true,
+        })
+        .filter(|i| {
+            params
+                .entity_type
+                .as_deref()
+                .is_none_or(|t| i.entity_type.to_string().eq_ignore_ascii_case(t))
+        })
+        .filter(|i| {
+            params
+                .reason
+                .as_deref()
+                .is_none_or(|r| i.reason.to_string().eq_ignore_ascii_case(r))
+        })
+        .collect();
+
+    // Oldest first, so the queue is worked in arrival order
+    items.sort_by_key(|i| i.created_at);
+    let total = items.len();
+    if let Some(limit) = params.limit {
+        items.truncate(limit);
+    }
+
+    Ok(Json(ReviewListResponse {
+        items,
+        total,
+        pending,
+    }))
+}
+
+// ── Review Detail Endpoint ──────────────────────────────────────
+
+pub async fn get_review_item(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ReviewQueueItem>, ApiError> {
+    load_items(&state)?
+        .into_iter()
+        .find(|i| i.id == id)
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("Review item {} not found", id)))
+}
+
+// ── Review Resolve Endpoint ─────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct ResolveParams {
+    /// "accept", "reject", or "fix"
+    pub action: String,
+    pub reason: Option<String>,
+}
+
+pub async fn resolve_review_item(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(params): Json<ResolveParams>,
+) -> Result<Json<ReviewQueueItem>, ApiError> {
+    require_local(&headers, "Review resolution")?;
+    let action: ResolutionAction = params.action.parse().map_err(ApiError::BadRequest)?;
+
+    let mut items = load_items(&state)?;
+    let item = items
+        .iter_mut()
+        .find(|i| i.id == id)
+        .ok_or_else(|| ApiError::NotFound(format!("Review item {} not found", id)))?;
+    if !item.is_pending() {
+        return Err(ApiError::Conflict(format!(
+            "Review item {} is already resolved",
+            id
+        )));
+    }
+    if action == ResolutionAction::Accept {
+        store_held(&state, item)?;
+    }
+    item.resolve_with(action, params.reason);
+    let resolved = item.clone();
+
+    storage::write_review_items(&state.storage, &items)
+        .map_err(|e| ApiError::Internal(format!("Failed to write review queue: {}", e)))?;
+
+    tracing::info!("Resolved review item {} ({})", id, action);
+    Ok(Json(resolved))
+}
+
+/// Store the record an accepted item held back from storage, if any.
+fn store_held(state: &AppState, item: &ReviewQueueItem) -> Result<(), ApiError> {
+    let Some(held) = &item.held else {
+        return Ok(());
+    };
+    let entity = match item.entity_type {
+        EntityType::Event => storage::EntityType::Event,
+        EntityType::Placement => storage::EntityType::Placement,
+        EntityType::ArmyList => storage::EntityType::ArmyList,
+        EntityType::SignificantEvent => {
+            return Err(ApiError::BadRequest(
+                "Held significant events cannot be stored from the review queue".to_string(),
+            ))
+        }
+    };
+    storage::JsonlWriter::for_source(&state.storage, entity, &held.epoch, &held.source)
+        .append(&held.record)
+        .map_err(|e| ApiError::Internal(format!("Failed to store held record: {}", e)))?;
+    tracing::info!("Stored held {} {}", item.entity_type, item.entity_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::build_router;
+    use crate::api::state::AppState;
+    use crate::models::{EntityId, EntityType, EpochMapper, ReviewQueueItem, ReviewReason};
+    use crate::storage::{self, StorageConfig};
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use serde_json::Value;
+    use std::sync::Arc;
+    use tower::util::ServiceExt;
+
+    fn setup_test_state(dir: &std::path::Path) -> AppState {
+        AppState {
+            storage: Arc::new(StorageConfig::new(dir.to_path_buf())),
+            epoch_mapper: Arc::new(tokio::sync::RwLock::new(EpochMapper::new())),
+            refresh_state: Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::refresh::RefreshState::default(),
+            )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
+            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
+            traffic_stats: Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::traffic::TrafficStats::new(),
+            )),
+        }
+    }
+
+    fn seed(state: &AppState) -> Vec<ReviewQueueItem> {
+        let mut resolved = ReviewQueueItem::new(
+            EntityType::Event,
+            EntityId::from("e1"),
+            ReviewReason::DuplicateSuspected,
+            "Possible duplicate".to_string(),
+        );
+        resolved.resolve(None);
+        let items = vec![
+            ReviewQueueItem::new(
+                EntityType::ArmyList,
+                EntityId::from("l1"),
+                ReviewReason::LowConfidence,
+                "Faction not recognized".to_string(),
+            ),
+            ReviewQueueItem::new(
+                EntityType::Placement,
+                EntityId::from("p1"),
+                ReviewReason::FactCheckFailed,
+                "Points missing".to_string(),
+            ),
+            resolved,
+        ];
+        storage::write_review_items(&state.storage, &items).unwrap();
+        items
+    }
+
+    async fn send(app: axum::Router, req: Request<Body>) -> (StatusCode, Value) {
+        let resp = app.oneshot(req).await.unwrap();
+        let status = resp.status();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+        (status, json)
+    }
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::builder().uri(uri).body(Body::empty()).unwrap()
+    }
+
+    fn resolve(id: &str, body: &str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(format!("/api/review/{}/resolve", id))
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_review_items() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        seed(&state);
+
+        let (status, json) = send(build_router(state.clone()), get("/api/review")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["total"], 2);
+        assert_eq!(json["pending"], 2);
+
+        let (_, json) = send(
+            build_router(state.clone()),
+            get("/api/review?status=all&entity_type=army_list"),
+        )
+        .await;
+        assert_eq!(json["total"], 1);
+        assert_eq!(json["items"][0]["entity_id"], "l1");
+
+        let (status, _) = send(build_router(state), get("/api/review?status=bogus")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_review_item() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let items = seed(&state);
+
+        let uri = format!("/api/review/{}", items[1].id);
+        let (status, json) = send(build_router(state.clone()), get(&uri)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["reason"], "fact_check_failed");
+
+        let (status, _) = send(build_router(state), get("/api/review/missing")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_review_item() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let items = seed(&state);
+        let id = &items[0].id;
+
+        let (status, json) = send(
+            build_router(state.clone()),
+            resolve(id, r#"{"action":"reject","reason":"Wrong faction"}"#),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["resolved"], true);
+        assert_eq!(json["resolution_action"], "reject");
+
+        let stored = storage::read_review_items(&state.storage).unwrap();
+        assert!(!stored.iter().find(|i| &i.id == id).unwrap().is_pending());
+
+        // Resolving twice conflicts
+        let (status, _) = send(
+            build_router(state.clone()),
+            resolve(id, r#"{"action":"accept"}"#),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let (status, _) = send(
+            build_router(state),
+            resolve(&items[1].id, r#"{"action":"delete"}"#),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_accept_stores_held_record() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup_test_state(tmp.path());
+        let placement = crate::models::Placement::new(
+            EntityId::from("e1"),
+            EntityId::from("current"),
+            1,
+            "Alice".to_string(),
+            "Aeldari".to_string(),
+        );
+        let item = ReviewQueueItem::new(
+            EntityType::Placement,
+            placement.id.clone(),
+            ReviewReason::FactCheckFailed,
+            "Rank 1 is given to more than one player".to_string(),
+        )
+        .with_held(crate::models::HeldRecord {
+            epoch: "current".to_string(),
+            source: "goonhammer".to_string(),
+            record: serde_json::to_value(&placement).unwrap(),
+        });
+        storage::write_review_items(&state.storage, std::slice::from_ref(&item)).unwrap();
+
+        let (status, _) = send(
+            build_router(state.clone()),
+            resolve(&item.id, r#"{"action":"accept"}"#),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let stored: Vec<crate::models::Placement> = storage::JsonlReader::for_entity(
+            &state.storage,
+            storage::EntityType::Placement,
+            "current",
+        )
+        .read_all()
+        .unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].id, placement.id);
+    }
+}
@@ -0,0 +1,396 @@
+use std::collections::HashMap;
+
+use axum::extract::{Path, State};
+use axum::Json;
+use chrono::Datelike;
+use serde::Serialize;
+
+use crate::api::state::AppState;
+use crate::api::{merge_by_id, ApiError};
+use crate::models::{normalize_faction_name, Event, Placement, SeriesMatcher};
+use crate::storage::{self, EntityType, IndexedReader};
+
+/// Load all events and placements across every known epoch.
+async fn load_all(state: &AppState) -> (Vec<Event>, Vec<Placement>) {
+    let mapper = state.epoch_mapper.read().await;
+    let epoch_ids: Vec<String> = if mapper.all_epochs().is_empty() {
+        vec!["current".to_string()]
+    } else {
+        mapper
+            .all_epochs()
+            .iter()
+            .map(|e| e.id.as_str().to_string())
+            .collect()
+    };
+
+    let mut events = Vec::new();
+    let mut placements = Vec::new();
+    for epoch_id in &epoch_ids {
+        if let Ok(e) =
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, epoch_id)
+                .read_all()
+        {
+            events.extend(e);
+        }
+        if let Ok(p) =
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, epoch_id)
+                .read_all()
+        {
+            placements.extend(p);
+        }
+    }
+
+    (
+        merge_by_id(events, |e| e.id.as_str()),
+        merge_by_id(placements, |p| p.id.as_str()),
+    )
+}
+
+/// Series name for an event: the linked series, else a match on the event name
+/// (covers events stored before series linking existed).
+fn series_of(event: &Event, matcher: &SeriesMatcher) -> Option<String> {
+    event
+        .series
+        .clone()
+        .or_else(|| matcher.match_event(&event.name).map(|s| s.name.clone()))
+}
+
+// ── Series List Endpoint ────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct SeriesSummary {
+    pub name: String,
+    pub patterns: Vec<String>,
+    pub edition_count: u32,
+    pub latest_edition: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeriesListResponse {
+    pub series: Vec<SeriesSummary>,
+}
+
+pub async fn list_series(
+    State(state): State<AppState>,
+) -> Result<Json<SeriesListResponse>, ApiError> {
+    let definitions = storage::read_series(&state.storage)
+        .map_err(|e| ApiError::Internal(format!("Failed to read series: {}", e)))?;
+    let matcher = SeriesMatcher::new(definitions);
+    let (events, _) = load_all(&state).await;
+
+    let mut editions: HashMap<String, Vec<&Event>> = HashMap::new();
+    for event in &events {
+        if let Some(name) = series_of(event, &matcher) {
+            editions.entry(name).or_default().push(event);
+        }
+    }
+
+    let series = matcher
+        .all()
+        .map(|s| {
+            let evs = editions.get(&s.name);
+            SeriesSummary {
+                name: s.name.clone(),
+                patterns: s.patterns.clone(),
+                edition_count: evs.map(|v| v.len() as u32).unwrap_or(0),
+                latest_edition: evs
+                    .and_then(|v| v.iter().map(|e| e.date).max())
+                    .map(|d| d.to_string()),
+            }
+        })
+        .collect();
+
+    Ok(Json(SeriesListResponse { series }))
+}
+
+// ── Series Detail Endpoint ──────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct SeriesWinner {
+    pub player_name: String,
+    pub faction: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeriesFactionShare {
+    pub faction: String,
+    pub count: u32,
+    pub meta_share: f64,
+    pub best_rank: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeriesEdition {
+    pub event_id: String,
+    pub event_name: String,
+    pub date: String,
+    pub year: i32,
+    pub location: Option<String>,
+    pub player_count: u32,
+    /// Attendance change vs the previous edition, in percent
+    pub attendance_change: Option<f64>,
+    pub winner: Option<SeriesWinner>,
+    pub top_factions: Vec<SeriesFactionShare>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WinningFaction {
+    pub faction: String,
+    pub wins: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeriesDetailResponse {
+    pub name: String,
+    pub patterns: Vec<String>,
+    pub editions: Vec<SeriesEdition>,
+    pub winning_factions: Vec<WinningFaction>,
+}
+
+pub async fn get_series(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<SeriesDetailResponse>, ApiError> {
+    let definitions = storage::read_series(&state.storage)
+        .map_err(|e| ApiError::Internal(format!("Failed to read series: {}", e)))?;
+    let matcher = SeriesMatcher::new(definitions);
+    let series = matcher
+        .get(&name)
+        .ok_or_else(|| ApiError::NotFound(format!("Series not found: {}", name)))?;
+
+    let (events, placements) = load_all(&state).await;
+
+    let mut series_events: Vec<&Event> = events
+        .iter()
+        .filter(|e| series_of(e, &matcher).as_deref() == Some(series.name.as_str()))
+        .collect();
+    series_events.sort_by_key(|e| e.date);
+
+    let mut by_event: HashMap<&str, Vec<&Placement>> = HashMap::new();
+    for p in &placements {
+        by_event.entry(p.event_id.as_str()).or_default().push(p);
+    }
+
+    let mut editions = Vec::new();
+    let mut faction_wins: HashMap<String, u32> = HashMap::new();
+    let mut prev_attendance: Option<u32> = None;
+
+    for event in series_events {
+        let event_placements = by_event.get(event.id.as_str()).cloned().unwrap_or_default();
+        let player_count = event.player_count.unwrap_or(event_placements.len() as u32);
+
+        let winner = event_placements
+            .iter()
+            .find(|p| p.rank == 1)
+            .map(|p| SeriesWinner {
+                player_name: p.player_name.clone(),
+                faction: normalize_faction_name(&p.faction),
+            });
+        if let Some(ref w) = winner {
+            *faction_wins.entry(w.faction.clone()).or_default() += 1;
+        }
+
+        // Meta snapshot: top factions by representation at this edition
+        let mut counts: HashMap<String, (u32, u32)> = HashMap::new();
+        for p in &event_placements {
+            let entry = counts
+                .entry(normalize_faction_name(&p.faction))
+                .or_insert((0, u32::MAX));
+            entry.0 += 1;
+            entry.1 = entry.1.min(p.rank);
+        }
+        let total = event_placements.len() as f64;
+        let mut top_factions: Vec<SeriesFactionShare> = counts
+            .into_iter()
+            .map(|(faction, (count, best_rank))| SeriesFactionShare {
+                faction,
+                count,
+                meta_share: (count as f64 / total * 1000.0).round() / 10.0,
+                best_rank,
+            })
+            .collect();
+        top_factions.sort_by(|a, b| b.count.cmp(&a.count).then(a.best_rank.cmp(&b.best_rank)));
+        top_factions.truncate(5);
+
+        let attendance_change = prev_attendance
+            .filter(|prev| *prev > 0 && player_count > 0)
+            .map(|prev| {
+                ((player_count as f64 - prev as f64) / prev as f64 * 1000.0).round() / 10.0
+            });
+        if player_count > 0 {
+            prev_attendance = Some(player_count);
+        }
+
+        editions.push(SeriesEdition {
+            event_id: event.id.as_str().to_string(),
+            event_name: event.name.clone(),
+            date: event.date.to_string(),
+            year: event.date.year(),
+            location: event.location.clone(),
+            player_count,
+            attendance_change,
+            winner,
+            top_factions,
+        });
+    }
+
+    let mut winning_factions: Vec<WinningFaction> = faction_wins
+        .into_iter()
+        .map(|(faction, wins)| WinningFaction { faction, wins })
+        .collect();
+    winning_factions.sort_by(|a, b| b.wins.cmp(&a.wins).then(a.faction.cmp(&b.faction)));
+
+    Ok(Json(SeriesDetailResponse {
+        name: series.name.clone(),
+        patterns: series.patterns.clone(),
+        editions,
+        winning_factions,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::build_router;
+    use crate::api::state::AppState;
+    use crate::models::{EpochMapper, Event, Placement};
+    use crate::storage::StorageConfig;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use serde_json::Value;
+    use std::sync::Arc;
+    use tower::util::ServiceExt;
+
+    fn write_jsonl<T: serde::Serialize>(path: &std::path::Path, items: &[T]) {
+        let mut content = String::new();
+        for item in items {
+            content.push_str(&serde_json::to_string(item).unwrap());
+            content.push('\n');
+        }
+        std::fs::write(path, content).unwrap();
+    }
+
+    async fn get_json(app: axum::Router, uri: &str) -> (StatusCode, Value) {
+        let resp = app
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = resp.status();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+        (status, json)
+    }
+
+    fn make_event(name: &str, date: &str, players: u32) -> Event {
+        Event::new(
+            name.to_string(),
+            chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            format!("https://example.com/{}", date),
+            "test".to_string(),
+            "current".into(),
+        )
+        .with_player_count(players)
+    }
+
+    fn setup(dir: &std::path::Path, events: &[Event], placements: &[Placement]) -> AppState {
+        let epoch_dir = dir.join("normalized").join("current");
+        std::fs::create_dir_all(&epoch_dir).unwrap();
+        write_jsonl(&epoch_dir.join("events.jsonl"), events);
+        write_jsonl(&epoch_dir.join("placements.jsonl"), placements);
+        AppState {
+            storage: Arc::new(StorageConfig::new(dir.to_path_buf())),
+            epoch_mapper: Arc::new(tokio::sync::RwLock::new(EpochMapper::new())),
+            refresh_state: Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::refresh::RefreshState::default(),
+            )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
+            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
+            traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::traffic::TrafficStats::new(),
+            )),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_series_detail_year_over_year() {
+        let tmp = tempfile::tempdir().unwrap();
+        let lvo25 = make_event("LVO 2025", "2025-01-31", 800);
+        let lvo26 = make_event("Las Vegas Open 2026", "2026-01-30", 1000)
+            .with_series(Some("Las Vegas Open".to_string()));
+        let other = make_event("Brighton Doubles", "2025-06-01", 40);
+        let placements = vec![
+            Placement::new(
+                lvo25.id.clone(),
+                "current".into(),
+                1,
+                "A".into(),
+                "Aeldari".into(),
+            ),
+            Placement::new(
+                lvo25.id.clone(),
+                "current".into(),
+                2,
+                "B".into(),
+                "Orks".into(),
+            ),
+            Placement::new(
+                lvo26.id.clone(),
+                "current".into(),
+                1,
+                "C".into(),
+                "Aeldari".into(),
+            ),
+        ];
+        let state = setup(tmp.path(), &[lvo25, lvo26, other], &placements);
+
+        let (status, json) = get_json(build_router(state), "/api/series/las%20vegas%20open").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["name"], "Las Vegas Open");
+        let editions = json["editions"].as_array().unwrap();
+        assert_eq!(editions.len(), 2);
+        assert_eq!(editions[0]["year"], 2025);
+        assert!(editions[0]["attendance_change"].is_null());
+        assert_eq!(editions[1]["attendance_change"], 25.0);
+        assert_eq!(editions[0]["winner"]["faction"], "Aeldari");
+        assert_eq!(editions[0]["top_factions"].as_array().unwrap().len(), 2);
+        assert_eq!(json["winning_factions"][0]["faction"], "Aeldari");
+        assert_eq!(json["winning_factions"][0]["wins"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_series_not_found() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup(tmp.path(), &[], &[]);
+        let (status, _) = get_json(build_router(state), "/api/series/nonexistent").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_list_series() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = setup(
+            tmp.path(),
+            &[make_event("LVO 2025", "2025-01-31", 800)],
+            &[],
+        );
+        let (status, json) = get_json(build_router(state), "/api/series").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let series = json["series"].as_array().unwrap();
+        let lvo = series
+            .iter()
+            .find(|s| s["name"] == "Las Vegas Open")
+            .unwrap();
+        assert_eq!(lvo["edition_count"], 1);
+        assert_eq!(lvo["latest_edition"], "2025-01-31");
+    }
+}
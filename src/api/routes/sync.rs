@@ -0,0 +1,170 @@
+use axum::extract::{Query, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::api::state::AppState;
+use crate::api::ApiError;
+use crate::sync::history::{read_history, SyncRun, HISTORY_LIMIT};
+use crate::sync::SyncStatus;
+
+/// Runs returned when the request names no limit.
+const DEFAULT_LIMIT: usize = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct SyncHistoryParams {
+    /// Only runs that included this source (`bcp`, `goonhammer`, `t3`, ...)
+    pub source: Option<String>,
+    /// "completed" or "failed"
+    pub status: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// A recorded run with its totals across sources.
+#[derive(Debug, Serialize)]
+pub struct SyncRunSummary {
+    #[serde(flatten)]
+    pub run: SyncRun,
+    pub events_synced: u32,
+    pub placements_synced: u32,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncHistoryResponse {
+    /// Newest first
+    pub runs: Vec<SyncRunSummary>,
+    /// Matching runs before the limit
+    pub total: usize,
+}
+
+/// Recorded sync runs, newest first.
+pub async fn history(
+    State(state): State<AppState>,
+    Query(params): Query<SyncHistoryParams>,
+) -> Result<Json<SyncHistoryResponse>, ApiError> {
+    let status = match params.status.as_deref() {
+        None => None,
+        Some("completed") => Some(SyncStatus::Completed),
+        Some("failed") => Some(SyncStatus::Failed),
+        Some(other) => {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid status '{}': expected completed or failed",
+                other
+            )))
+        }
+    };
+    let runs = read_history(&state.storage)
+        .map_err(|e| ApiError::Internal(format!("Failed to read sync history: {}", e)))?;
+
+    let matching: Vec<SyncRun> = runs
+        .into_iter()
+        .filter(|r| status.is_none_or(|s| r.status == s))
+        .filter(|r| {
+            params
+                .source
+                .as_deref()
+                .is_none_or(|source| r.sources.iter().any(|s| s.source == source))
+        })
+        .collect();
+    let total = matching.len();
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_LIMIT)
+        .clamp(1, HISTORY_LIMIT);
+
+    let runs = matching
+        .into_iter()
+        .take(limit)
+        .map(|run| SyncRunSummary {
+            events_synced: run.events_synced(),
+            placements_synced: run.placements_synced(),
+            duration_ms: run.duration_ms(),
+            run,
+        })
+        .collect();
+    Ok(Json(SyncHistoryResponse { runs, total }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::build_router;
+    use crate::models::EpochMapper;
+    use crate::storage::StorageConfig;
+    use crate::sync::history::{record_run, SourceRun};
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use chrono::Utc;
+    use std::sync::Arc;
+    use tower::util::ServiceExt;
+
+    #[tokio::test]
+    async fn test_sync_history_filters() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        let now = Utc::now();
+        for (source, status) in [
+            ("bcp", SyncStatus::Completed),
+            ("t3", SyncStatus::Failed),
+            ("bcp", SyncStatus::Completed),
+        ] {
+            let run = SyncRun {
+                started_at: now,
+                finished_at: now + chrono::Duration::seconds(5),
+                status,
+                cancelled: false,
+                sources: vec![SourceRun {
+                    source: source.to_string(),
+                    events_synced: 3,
+                    ..Default::default()
+                }],
+                errors: Vec::new(),
+            };
+            record_run(&storage, &run).unwrap();
+        }
+
+        let app = build_router(AppState {
+            storage: Arc::new(storage),
+            epoch_mapper: Arc::new(tokio::sync::RwLock::new(EpochMapper::new())),
+            refresh_state: Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::refresh::RefreshState::default(),
+            )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
+            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
+            traffic_stats: Default::default(),
+        });
+        let get = |uri: &str| {
+            app.clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        };
+
+        let resp = get("/api/sync/history?source=bcp&limit=1").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["total"], 2);
+        assert_eq!(json["runs"].as_array().unwrap().len(), 1);
+        assert_eq!(json["runs"][0]["events_synced"], 3);
+        assert_eq!(json["runs"][0]["duration_ms"], 5000);
+
+        let resp = get("/api/sync/history?status=failed").await.unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["total"], 1);
+        assert_eq!(json["runs"][0]["sources"][0]["source"], "t3");
+
+        let resp = get("/api/sync/history?status=odd").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+}
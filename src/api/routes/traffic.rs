@@ -1,5 +1,6 @@
 use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::extract::{Query, State};
 use axum::http::HeaderMap;
@@ -9,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
 use crate::api::state::AppState;
-use crate::api::ApiError;
+use crate::api::{require_local, ApiError};
 
 // ── Time-series bucket ──────────────────────────────────────────
 
@@ -30,6 +31,35 @@ pub struct GeoInfo {
     pub country_code: String,
 }
 
+// ── Route latency ───────────────────────────────────────────────
+
+/// Handler times kept per route for percentiles.
+pub const LATENCY_WINDOW: usize = 1000;
+
+/// Budget used when `[server] slow_request_ms` isn't set.
+pub const DEFAULT_SLOW_REQUEST_BUDGET: Duration = Duration::from_millis(500);
+
+/// Rolling handler times for one route.
+#[derive(Debug, Clone, Default)]
+pub struct RouteTimings {
+    /// Most recent [`LATENCY_WINDOW`] handler times
+    pub samples: VecDeque<Duration>,
+    /// Requests since server start
+    pub requests: u64,
+    /// Requests over the slow-request budget since server start
+    pub slow: u64,
+}
+
+/// Nearest-rank percentile of sorted samples, in milliseconds.
+fn percentile_ms(sorted: &[Duration], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    let d = sorted[rank.clamp(1, sorted.len()) - 1];
+    (d.as_secs_f64() * 10_000.0).round() / 10.0
+}
+
 // ── TrafficStats ────────────────────────────────────────────────
 
 /// In-memory traffic stats, reset on server restart.
@@ -49,6 +79,10 @@ pub struct TrafficStats {
     pub time_series: VecDeque<TimeBucket>,
     /// Cached geo lookups for IPs
     pub geo_cache: HashMap<String, GeoInfo>,
+    /// Handler times per matched route (e.g. "/api/events/:id")
+    pub route_timings: HashMap<String, RouteTimings>,
+    /// Requests slower than this are logged
+    pub slow_request_budget: Duration,
 }
 
 impl Default for TrafficStats {
@@ -61,6 +95,8 @@ impl Default for TrafficStats {
             started_at: Utc::now(),
             time_series: VecDeque::with_capacity(1440),
             geo_cache: HashMap::new(),
+            route_timings: HashMap::new(),
+            slow_request_budget: DEFAULT_SLOW_REQUEST_BUDGET,
         }
     }
 }
@@ -136,6 +172,56 @@ impl TrafficStats {
         }
     }
 
+    /// Builder method to set the slow-request budget.
+    pub fn with_slow_request_budget(mut self, budget: Duration) -> Self {
+        self.slow_request_budget = budget;
+        self
+    }
+
+    /// Record a handler time for a route. Returns true when it was over
+    /// the slow-request budget.
+    pub fn record_latency(&mut self, route: &str, elapsed: Duration) -> bool {
+        let timings = self.route_timings.entry(route.to_string()).or_default();
+        timings.requests += 1;
+        if timings.samples.len() == LATENCY_WINDOW {
+            timings.samples.pop_front();
+        }
+        timings.samples.push_back(elapsed);
+        let slow = elapsed > self.slow_request_budget;
+        if slow {
+            timings.slow += 1;
+        }
+        slow
+    }
+
+    /// p50/p95/p99 per route over the rolling window, slowest p95 first.
+    pub fn latency_summary(&self) -> Vec<RouteLatency> {
+        let mut summary: Vec<RouteLatency> = self
+            .route_timings
+            .iter()
+            .map(|(route, timings)| {
+                let mut sorted: Vec<Duration> = timings.samples.iter().copied().collect();
+                sorted.sort_unstable();
+                RouteLatency {
+                    route: route.clone(),
+                    requests: timings.requests,
+                    slow_requests: timings.slow,
+                    p50_ms: percentile_ms(&sorted, 50.0),
+                    p95_ms: percentile_ms(&sorted, 95.0),
+                    p99_ms: percentile_ms(&sorted, 99.0),
+                    max_ms: percentile_ms(&sorted, 100.0),
+                }
+            })
+            .collect();
+        summary.sort_by(|a, b| {
+            b.p95_ms
+                .partial_cmp(&a.p95_ms)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.route.cmp(&b.route))
+        });
+        summary
+    }
+
     pub fn unique_ips(&self) -> usize {
         self.requests_by_ip.len()
     }
@@ -165,6 +251,21 @@ pub struct TrafficResponse {
     pub paths: Vec<PathSummary>,
     pub started_at: String,
     pub time_series: Vec<TimeSeriesPoint>,
+    /// Handler latency per API route over the last requests
+    pub latency: Vec<RouteLatency>,
+    pub slow_request_budget_ms: u64,
+}
+
+/// Handler latency percentiles for one route.
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteLatency {
+    pub route: String,
+    pub requests: u64,
+    pub slow_requests: u64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
 }
 
 #[derive(Debug, Serialize)]
@@ -257,25 +358,17 @@ pub async fn traffic_stats(State(state): State<AppState>) -> Json<TrafficRespons
         paths,
         started_at: stats.started_at.to_rfc3339(),
         time_series,
+        latency: stats.latency_summary(),
+        slow_request_budget_ms: stats.slow_request_budget.as_millis() as u64,
     })
 }
 
-/// Reject requests that come through Cloudflare Tunnel (public domain).
-fn require_local(headers: &HeaderMap) -> Result<(), ApiError> {
-    if headers.contains_key("cf-connecting-ip") {
-        return Err(ApiError::Forbidden(
-            "Geo lookup is only available on localhost".to_string(),
-        ));
-    }
-    Ok(())
-}
-
 pub async fn geo_lookup(
     headers: HeaderMap,
     State(state): State<AppState>,
     Query(params): Query<GeoQuery>,
 ) -> Result<Json<Vec<GeoResult>>, ApiError> {
-    require_local(&headers)?;
+    require_local(&headers, "Geo lookup")?;
 
     let ips: Vec<String> = params
         .ips
@@ -538,20 +631,6 @@ mod tests {
         assert_eq!(entry.country, "Germany");
     }
 
-    #[test]
-    fn test_require_local_allows_without_cf_header() {
-        let headers = HeaderMap::new();
-        assert!(require_local(&headers).is_ok());
-    }
-
-    #[test]
-    fn test_require_local_blocks_cf_header() {
-        let mut headers = HeaderMap::new();
-        headers.insert("cf-connecting-ip", "1.2.3.4".parse().unwrap());
-        let result = require_local(&headers);
-        assert!(result.is_err());
-    }
-
     #[test]
     fn test_traffic_response_serialization() {
         let resp = TrafficResponse {
@@ -575,6 +654,8 @@ mod tests {
                 page_views: 3,
                 api_requests: 2,
             }],
+            latency: vec![],
+            slow_request_budget_ms: 500,
         };
         let json = serde_json::to_value(&resp).unwrap();
         assert_eq!(json["total_requests"], 100);
@@ -597,4 +678,69 @@ mod tests {
         assert_eq!(stats.unique_ips(), 3);
         assert_eq!(stats.external_ips().len(), 2); // excludes 127.0.0.1
     }
+
+    #[test]
+    fn test_latency_percentiles_and_slow_budget() {
+        let mut stats = TrafficStats::new().with_slow_request_budget(Duration::from_millis(50));
+        for ms in 1..=100 {
+            let slow = stats.record_latency("/api/events", Duration::from_millis(ms));
+            assert_eq!(slow, ms > 50);
+        }
+        stats.record_latency("/api/epochs", Duration::from_millis(2));
+
+        let summary = stats.latency_summary();
+        assert_eq!(summary[0].route, "/api/events");
+        assert_eq!(summary[0].requests, 100);
+        assert_eq!(summary[0].slow_requests, 50);
+        assert_eq!(summary[0].p50_ms, 50.0);
+        assert_eq!(summary[0].p95_ms, 95.0);
+        assert_eq!(summary[0].p99_ms, 99.0);
+        assert_eq!(summary[0].max_ms, 100.0);
+        assert_eq!(summary[1].p50_ms, 2.0);
+
+        // Window keeps only the most recent samples
+        for _ in 0..LATENCY_WINDOW {
+            stats.record_latency("/api/events", Duration::from_millis(1));
+        }
+        assert_eq!(stats.latency_summary()[0].route, "/api/epochs");
+    }
+
+    #[tokio::test]
+    async fn test_middleware_times_matched_routes() {
+        use crate::api::build_router;
+        use axum::body::Body;
+        use axum::http::Request;
+        use tower::util::ServiceExt;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let traffic: SharedTrafficStats = Default::default();
+        let state = AppState {
+            storage: Arc::new(crate::storage::StorageConfig::new(tmp.path().to_path_buf())),
+            epoch_mapper: Default::default(),
+            refresh_state: Default::default(),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
+            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
+            traffic_stats: traffic.clone(),
+        };
+        let app = build_router(state);
+        for uri in ["/api/events/abc123", "/api/events/def456", "/index.html"] {
+            app.clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+        }
+
+        let stats = traffic.read().await;
+        let summary = stats.latency_summary();
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].route, "/api/events/:id");
+        assert_eq!(summary[0].requests, 2);
+    }
 }
@@ -0,0 +1,617 @@
+use std::collections::{BTreeMap, HashSet};
+
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use super::analytics::join_lists_to_placements;
+use crate::api::state::AppState;
+use crate::api::{dedup_by_id, merge_by_id, ApiError, GameSizeFilter};
+use crate::calculate::detachments::GameRecord;
+use crate::models::{
+    normalize_faction_name, ArmyList, Event, Placement, PointsChange, RuleChange,
+    SignificantEventType,
+};
+use crate::storage::{self, EntityType, IndexedReader};
+
+/// Minimum epochs with both a cost and lists before a correlation is reported.
+const MIN_CORRELATION_POINTS: usize = 3;
+
+#[derive(Debug, Deserialize)]
+pub struct PointsHistoryParams {
+    pub faction: Option<String>,
+}
+
+/// A unit size's cost in one epoch, with how much the unit was played.
+#[derive(Debug, Serialize)]
+pub struct PointsHistoryEntry {
+    pub epoch_id: String,
+    pub epoch_name: String,
+    pub effective_date: String,
+    pub models: u32,
+    pub points: u32,
+    pub previous_points: Option<u32>,
+    pub delta: Option<i64>,
+    /// Percent of the faction's lists in the epoch that include the unit
+    pub play_rate: Option<f64>,
+    pub lists_with_unit: u32,
+    pub faction_lists: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FactionPointsHistory {
+    pub faction: String,
+    pub history: Vec<PointsHistoryEntry>,
+    /// Pearson correlation between the unit's cheapest size and its play
+    /// rate across epochs; negative means it is played more when cheaper
+    pub correlation: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PointsHistoryResponse {
+    pub unit_name: String,
+    pub factions: Vec<FactionPointsHistory>,
+}
+
+/// Pearson correlation coefficient, or None when either series is constant.
+fn pearson(pairs: &[(f64, f64)]) -> Option<f64> {
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in pairs {
+        cov += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x).powi(2);
+        var_y += (y - mean_y).powi(2);
+    }
+    if var_x == 0.0 || var_y == 0.0 {
+        return None;
+    }
+    Some((cov / (var_x * var_y).sqrt() * 1000.0).round() / 1000.0)
+}
+
+pub async fn points_history(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Query(params): Query<PointsHistoryParams>,
+) -> Result<Json<PointsHistoryResponse>, ApiError> {
+    let mapper = state.epoch_mapper.read().await;
+    let epochs: Vec<(String, String)> = if mapper.all_epochs().is_empty() {
+        vec![("current".to_string(), "current".to_string())]
+    } else {
+        mapper
+            .all_epochs()
+            .iter()
+            .map(|e| (e.id.as_str().to_string(), e.name.clone()))
+            .collect()
+    };
+    let faction_filter = params.faction.as_deref().map(normalize_faction_name);
+
+    // faction -> epoch index -> costs
+    let mut costs: BTreeMap<String, BTreeMap<usize, Vec<PointsChange>>> = BTreeMap::new();
+    let mut unit_name = name.clone();
+    for (i, (epoch_id, _)) in epochs.iter().enumerate() {
+        let records = IndexedReader::<PointsChange>::for_entity(
+            &state.storage,
+            EntityType::PointsChange,
+            epoch_id,
+        )
+        .read_all()
+        .unwrap_or_default();
+        for record in dedup_by_id(records, |p| p.id.as_str()) {
+            if !record.unit_name.eq_ignore_ascii_case(&name)
+                || faction_filter
+                    .as_ref()
+                    .is_some_and(|f| *f != record.faction)
+            {
+                continue;
+            }
+            unit_name = record.unit_name.clone();
+            costs
+                .entry(record.faction.clone())
+                .or_default()
+                .entry(i)
+                .or_default()
+                .push(record);
+        }
+    }
+    if costs.is_empty() {
+        return Err(ApiError::NotFound(format!(
+            "No points history for unit: {}",
+            name
+        )));
+    }
+
+    let mut factions = Vec::new();
+    for (faction, by_epoch) in costs {
+        let mut history = Vec::new();
+        let mut pairs = Vec::new();
+        for (i, mut records) in by_epoch {
+            let (epoch_id, epoch_name) = &epochs[i];
+            let lists: Vec<ArmyList> = IndexedReader::<ArmyList>::for_entity(
+                &state.storage,
+                EntityType::ArmyList,
+                epoch_id,
+            )
+            .read_all()
+            .unwrap_or_default();
+            let lists: Vec<ArmyList> = merge_by_id(lists, |l| l.id.as_str())
+                .into_iter()
+                .filter(|l| normalize_faction_name(&l.faction) == faction)
+                .collect();
+            let with_unit = lists
+                .iter()
+                .filter(|l| l.units.iter().any(|u| u.name.eq_ignore_ascii_case(&name)))
+                .count() as u32;
+            let play_rate = (!lists.is_empty())
+                .then(|| (with_unit as f64 / lists.len() as f64 * 1000.0).round() / 10.0);
+
+            records.sort_by_key(|r| r.models);
+            if let (Some(cheapest), Some(rate)) = (records.first(), play_rate) {
+                pairs.push((cheapest.points as f64, rate));
+            }
+            for record in records {
+                history.push(PointsHistoryEntry {
+                    epoch_id: epoch_id.clone(),
+                    epoch_name: epoch_name.clone(),
+                    effective_date: record.effective_date.to_string(),
+                    models: record.models,
+                    points: record.points,
+                    previous_points: record.previous_points,
+                    delta: record.delta(),
+                    play_rate,
+                    lists_with_unit: with_unit,
+                    faction_lists: lists.len() as u32,
+                });
+            }
+        }
+        let correlation = if pairs.len() >= MIN_CORRELATION_POINTS {
+            pearson(&pairs)
+        } else {
+            None
+        };
+        factions.push(FactionPointsHistory {
+            faction,
+            history,
+            correlation,
+        });
+    }
+
+    Ok(Json(PointsHistoryResponse {
+        unit_name,
+        factions,
+    }))
+}
+
+// ── Unit Trend ──────────────────────────────────────────────────
+
+/// A unit cost set by the balance pass that started an epoch.
+#[derive(Debug, Serialize)]
+pub struct TrendPointsChange {
+    pub models: u32,
+    pub points: u32,
+    pub previous_points: Option<u32>,
+    pub delta: Option<i64>,
+}
+
+/// The balance pass (or other boundary) that started an epoch, with what
+/// it changed for the unit.
+#[derive(Debug, Serialize)]
+pub struct BalanceMarker {
+    pub id: String,
+    pub title: String,
+    pub event_type: SignificantEventType,
+    pub date: String,
+    pub points_changes: Vec<TrendPointsChange>,
+    pub rule_changes: Vec<RuleChange>,
+}
+
+/// A unit's place in one epoch's meta.
+#[derive(Debug, Serialize)]
+pub struct UnitTrendPoint {
+    pub epoch_id: String,
+    pub epoch_name: String,
+    pub faction_lists: u32,
+    pub lists_with_unit: u32,
+    /// Percent of the faction's lists that include the unit
+    pub inclusion_rate: Option<f64>,
+    /// Copies per list that includes the unit
+    pub avg_count: Option<f64>,
+    /// Win rate of players whose lists include the unit, from their final
+    /// records (percent, draws count half)
+    pub win_rate: Option<f64>,
+    /// The same over all the faction's listed players, for comparison
+    pub faction_win_rate: Option<f64>,
+    pub games: u32,
+    /// Absent when the event that started the epoch isn't recorded
+    pub balance_pass: Option<BalanceMarker>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FactionUnitTrend {
+    pub faction: String,
+    pub epochs: Vec<UnitTrendPoint>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnitTrendResponse {
+    pub unit_name: String,
+    pub factions: Vec<FactionUnitTrend>,
+}
+
+/// Lists, placements and costs of one epoch.
+struct EpochData {
+    id: String,
+    name: String,
+    start_event_id: Option<String>,
+    lists: Vec<ArmyList>,
+    placements: Vec<Placement>,
+    costs: Vec<PointsChange>,
+}
+
+pub async fn unit_trend(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    game_size: GameSizeFilter,
+    Query(params): Query<PointsHistoryParams>,
+) -> Result<Json<UnitTrendResponse>, ApiError> {
+    let mapper = state.epoch_mapper.read().await;
+    let epochs: Vec<(String, String, Option<String>)> = if mapper.all_epochs().is_empty() {
+        vec![("current".to_string(), "current".to_string(), None)]
+    } else {
+        mapper
+            .all_epochs()
+            .iter()
+            .map(|e| {
+                (
+                    e.id.as_str().to_string(),
+                    e.name.clone(),
+                    Some(e.start_event_id.as_str().to_string()),
+                )
+            })
+            .collect()
+    };
+    drop(mapper);
+    let faction_filter = params.faction.as_deref().map(normalize_faction_name);
+    let sig_events = storage::cached_significant_events(&state.storage)
+        .map_err(|e| ApiError::Internal(format!("Failed to read significant events: {}", e)))?;
+
+    let is_unit = |unit: &str| unit.eq_ignore_ascii_case(&name);
+    let mut unit_name = name.clone();
+    let mut factions: Vec<String> = Vec::new();
+    let mut data = Vec::new();
+    for (id, epoch_name, start_event_id) in epochs {
+        let off_size: HashSet<String> = if game_size.is_active() {
+            IndexedReader::<Event>::for_entity(&state.storage, EntityType::Event, &id)
+                .read_all()
+                .unwrap_or_default()
+                .iter()
+                .filter(|e| !game_size.matches(e))
+                .map(|e| e.id.as_str().to_string())
+                .collect()
+        } else {
+            HashSet::new()
+        };
+        let lists =
+            IndexedReader::<ArmyList>::for_entity(&state.storage, EntityType::ArmyList, &id)
+                .read_all()
+                .unwrap_or_default();
+        let mut lists = merge_by_id(lists, |l| l.id.as_str());
+        lists.retain(|l| {
+            l.event_id
+                .as_ref()
+                .is_none_or(|e| !off_size.contains(e.as_str()))
+        });
+        state.storage.overrides.retain(&mut lists);
+        for unit in lists.iter().flat_map(|l| &l.units) {
+            if is_unit(&unit.name) {
+                unit_name = unit.name.clone();
+            }
+        }
+        for list in &lists {
+            let faction = normalize_faction_name(&list.faction);
+            if list.units.iter().any(|u| is_unit(&u.name))
+                && faction_filter.as_ref().is_none_or(|f| *f == faction)
+                && !factions.contains(&faction)
+            {
+                factions.push(faction);
+            }
+        }
+        let mut placements =
+            IndexedReader::<Placement>::for_entity(&state.storage, EntityType::Placement, &id)
+                .read_all()
+                .unwrap_or_default();
+        placements.retain(|p| !off_size.contains(p.event_id.as_str()));
+        state.storage.overrides.retain(&mut placements);
+        let costs = IndexedReader::<PointsChange>::for_entity(
+            &state.storage,
+            EntityType::PointsChange,
+            &id,
+        )
+        .read_all()
+        .unwrap_or_default();
+        data.push(EpochData {
+            id,
+            name: epoch_name,
+            start_event_id,
+            lists,
+            placements: merge_by_id(placements, |p| p.id.as_str()),
+            costs: dedup_by_id(costs, |p| p.id.as_str()),
+        });
+    }
+    if factions.is_empty() {
+        return Err(ApiError::NotFound(format!(
+            "No lists include unit: {}",
+            name
+        )));
+    }
+    factions.sort();
+
+    let percent = |part: u32, whole: u32| {
+        (whole > 0).then(|| (part as f64 / whole as f64 * 1000.0).round() / 10.0)
+    };
+    let mut trends = Vec::new();
+    for faction in factions {
+        let mut points = Vec::new();
+        for epoch in &data {
+            let lists: Vec<ArmyList> = epoch
+                .lists
+                .iter()
+                .filter(|l| normalize_faction_name(&l.faction) == faction)
+                .cloned()
+                .collect();
+            let copies: Vec<u32> = lists
+                .iter()
+                .map(|l| l.units.iter().filter(|u| is_unit(&u.name)).count() as u32)
+                .filter(|c| *c > 0)
+                .collect();
+            let with_unit = copies.len() as u32;
+
+            let (mut unit_record, mut faction_record) =
+                (GameRecord::default(), GameRecord::default());
+            for (list, placement) in join_lists_to_placements(&lists, &epoch.placements) {
+                let Some(r) = &placement.record else {
+                    continue;
+                };
+                let record = GameRecord {
+                    wins: r.wins,
+                    losses: r.losses,
+                    draws: r.draws,
+                };
+                faction_record.add(&record);
+                if list.units.iter().any(|u| is_unit(&u.name)) {
+                    unit_record.add(&record);
+                }
+            }
+
+            let balance_pass = epoch
+                .start_event_id
+                .as_ref()
+                .and_then(|id| sig_events.iter().find(|e| e.id.as_str() == id))
+                .map(|event| {
+                    let mut points_changes: Vec<TrendPointsChange> = epoch
+                        .costs
+                        .iter()
+                        .filter(|c| {
+                            is_unit(&c.unit_name) && normalize_faction_name(&c.faction) == faction
+                        })
+                        .map(|c| TrendPointsChange {
+                            models: c.models,
+                            points: c.points,
+                            previous_points: c.previous_points,
+                            delta: c.delta(),
+                        })
+                        .collect();
+                    points_changes.sort_by_key(|c| c.models);
+                    BalanceMarker {
+                        id: event.id.as_str().to_string(),
+                        title: event.title.clone(),
+                        event_type: event.event_type.clone(),
+                        date: event.date.to_string(),
+                        points_changes,
+                        rule_changes: event
+                            .rule_changes
+                            .iter()
+                            .filter(|c| {
+                                c.unit.as_deref().is_some_and(is_unit)
+                                    && normalize_faction_name(&c.faction) == faction
+                            })
+                            .cloned()
+                            .collect(),
+                    }
+                });
+
+            points.push(UnitTrendPoint {
+                epoch_id: epoch.id.clone(),
+                epoch_name: epoch.name.clone(),
+                faction_lists: lists.len() as u32,
+                lists_with_unit: with_unit,
+                inclusion_rate: percent(with_unit, lists.len() as u32),
+                avg_count: (with_unit > 0).then(|| {
+                    (copies.iter().sum::<u32>() as f64 / with_unit as f64 * 100.0).round() / 100.0
+                }),
+                win_rate: unit_record.win_rate(),
+                faction_win_rate: faction_record.win_rate(),
+                games: unit_record.games(),
+                balance_pass,
+            });
+        }
+        trends.push(FactionUnitTrend {
+            faction,
+            epochs: points,
+        });
+    }
+
+    Ok(Json(UnitTrendResponse {
+        unit_name,
+        factions: trends,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::build_router;
+    use crate::models::{EpochMapper, Unit};
+    use crate::storage::StorageConfig;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use chrono::NaiveDate;
+    use serde_json::Value;
+    use std::sync::Arc;
+    use tower::util::ServiceExt;
+
+    fn write_jsonl<T: serde::Serialize>(path: &std::path::Path, items: &[T]) {
+        let mut content = String::new();
+        for item in items {
+            content.push_str(&serde_json::to_string(item).unwrap());
+            content.push('\n');
+        }
+        std::fs::write(path, content).unwrap();
+    }
+
+    async fn get_json(app: axum::Router, uri: &str) -> (StatusCode, Value) {
+        let resp = app
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = resp.status();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+        (status, json)
+    }
+
+    fn setup(dir: &std::path::Path, costs: &[PointsChange], lists: &[ArmyList]) -> AppState {
+        let epoch_dir = dir.join("normalized").join("current");
+        std::fs::create_dir_all(&epoch_dir).unwrap();
+        write_jsonl(&epoch_dir.join("points_changes.jsonl"), costs);
+        write_jsonl(&epoch_dir.join("army_lists.jsonl"), lists);
+        AppState {
+            storage: Arc::new(StorageConfig::new(dir.to_path_buf())),
+            epoch_mapper: Arc::new(tokio::sync::RwLock::new(EpochMapper::new())),
+            refresh_state: Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::refresh::RefreshState::default(),
+            )),
+            refresh_updates: crate::api::routes::refresh::refresh_channel(),
+            syncs: Default::default(),
+            default_epoch: None,
+            size_thresholds: Default::default(),
+            grace_days: 0,
+            api_token: None,
+            notifier: None,
+            metrics: Default::default(),
+            ai_backend: Arc::new(crate::agents::backend::MockBackend::new("{}")),
+            traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
+                crate::api::routes::traffic::TrafficStats::new(),
+            )),
+        }
+    }
+
+    fn make_list(units: &[&str], points: u32) -> ArmyList {
+        let units = units.iter().map(|u| Unit::new(u.to_string(), 5)).collect();
+        ArmyList::new("Aeldari".to_string(), points, units, String::new())
+    }
+
+    #[tokio::test]
+    async fn test_points_history_with_play_rate() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cost = PointsChange::new(
+            "current".into(),
+            "pass".into(),
+            NaiveDate::from_ymd_opt(2025, 6, 12).unwrap(),
+            "Aeldari".to_string(),
+            "Wraithguard".to_string(),
+            5,
+            170,
+            "https://example.com/mfm.pdf".to_string(),
+        )
+        .with_previous_points(185);
+        let lists = vec![
+            make_list(&["Wraithguard", "Autarch"], 2000),
+            make_list(&["Autarch"], 1995),
+            make_list(&["Autarch", "War Walkers"], 1990),
+            make_list(&["wraithguard"], 1985),
+        ];
+        let app = build_router(setup(tmp.path(), &[cost], &lists));
+
+        let (status, json) = get_json(app.clone(), "/api/units/wraithguard/points-history").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["unit_name"], "Wraithguard");
+        let entry = &json["factions"][0]["history"][0];
+        assert_eq!(entry["points"], 170);
+        assert_eq!(entry["delta"], -15);
+        assert_eq!(entry["lists_with_unit"], 2);
+        assert_eq!(entry["play_rate"], 50.0);
+        // One epoch is too few to correlate
+        assert!(json["factions"][0]["correlation"].is_null());
+
+        let (status, _) = get_json(app, "/api/units/Autarch/points-history?faction=Orks").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let pairs = [(185.0, 10.0), (170.0, 20.0), (150.0, 35.0)];
+        assert!(pearson(&pairs).unwrap() < -0.9);
+    }
+
+    #[tokio::test]
+    async fn test_unit_trend() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut lists = vec![
+            make_list(&["Wraithguard", "Wraithguard", "Autarch"], 2000),
+            make_list(&["Autarch"], 1995),
+            make_list(&["Wraithguard"], 1985),
+            make_list(&["Autarch", "War Walkers"], 1990),
+        ];
+        let event = crate::models::EntityId::from("gt");
+        let mut placements = Vec::new();
+        for (i, (list, wins)) in lists.iter_mut().zip([4, 2, 3, 1]).enumerate() {
+            let player = format!("Player {}", i);
+            list.player_name = Some(player.clone());
+            list.event_id = Some(event.clone());
+            placements.push(
+                Placement::new(
+                    event.clone(),
+                    "current".into(),
+                    i as u32 + 1,
+                    player,
+                    "Aeldari".to_string(),
+                )
+                .with_record(wins, 5 - wins, 0),
+            );
+        }
+        let state = setup(tmp.path(), &[], &lists);
+        write_jsonl(
+            &tmp.path().join("normalized/current/placements.jsonl"),
+            &placements,
+        );
+        let app = build_router(state);
+
+        let (status, json) = get_json(app.clone(), "/api/units/wraithguard/trend").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["unit_name"], "Wraithguard");
+        let point = &json["factions"][0]["epochs"][0];
+        assert_eq!(json["factions"][0]["faction"], "Aeldari");
+        assert_eq!(point["lists_with_unit"], 2);
+        assert_eq!(point["inclusion_rate"], 50.0);
+        assert_eq!(point["avg_count"], 1.5);
+        assert_eq!(point["win_rate"], 70.0);
+        assert_eq!(point["faction_win_rate"], 50.0);
+        assert!(point["balance_pass"].is_null());
+
+        let (status, _) = get_json(app, "/api/units/Warboss/trend").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // Excluded players drop out of both the lists and the records
+        let overrides =
+            crate::storage::Overrides::parse("[exclude]\nplayers = [\"player 0\"]\n").unwrap();
+        let mut state = setup(tmp.path(), &[], &lists);
+        state.storage = Arc::new(
+            StorageConfig::new(tmp.path().to_path_buf()).with_overrides(Arc::new(overrides)),
+        );
+        let (_, json) = get_json(build_router(state), "/api/units/wraithguard/trend").await;
+        let point = &json["factions"][0]["epochs"][0];
+        assert_eq!(point["lists_with_unit"], 1);
+        assert_eq!(point["win_rate"], 60.0);
+    }
+}
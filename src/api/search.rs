@@ -0,0 +1,490 @@
+//! Full-text search over events, players, factions, detachments and units.
+//!
+//! Every epoch's entities are folded into one inverted index of lowercase
+//! word tokens. A query matches a document when each of its tokens is a
+//! prefix of one of the document's tokens, so `/api/search?q=wave ser`
+//! finds Wave Serpents as the user types.
+//!
+//! The index is built on the first search and cached. Its inputs are the
+//! per-epoch [`EntityIndex`](crate::storage::EntityIndex)es, which are
+//! themselves rebuilt when a file changes; the search index is rebuilt
+//! whenever any of those changed since it was built.
+
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use axum::extract::{Query, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::api::state::AppState;
+use crate::api::ApiError;
+use crate::models::normalize_faction_name;
+use crate::models::{ArmyList, Event, Placement};
+use crate::storage::{EntityType, IndexedReader, StorageConfig, StorageError};
+use crate::sync::normalize_player_name;
+
+/// Hits returned when the request names no limit.
+const DEFAULT_LIMIT: usize = 20;
+
+/// Upper bound on `limit`.
+const MAX_LIMIT: usize = 100;
+
+/// What a search hit refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HitKind {
+    Event,
+    Player,
+    Faction,
+    Detachment,
+    Unit,
+}
+
+impl std::str::FromStr for HitKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "event" | "events" => Ok(HitKind::Event),
+            "player" | "players" => Ok(HitKind::Player),
+            "faction" | "factions" => Ok(HitKind::Faction),
+            "detachment" | "detachments" => Ok(HitKind::Detachment),
+            "unit" | "units" => Ok(HitKind::Unit),
+            other => Err(format!(
+                "Unknown kind: {} (expected event, player, faction, detachment or unit)",
+                other
+            )),
+        }
+    }
+}
+
+/// One search result.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub kind: HitKind,
+    pub title: String,
+    /// Context such as the event date or the unit's faction
+    pub subtitle: Option<String>,
+    /// API path with details for the hit
+    pub link: String,
+    /// Epoch of an event; `None` for entities spanning epochs
+    pub epoch_id: Option<String>,
+    /// Placements, lists or events the hit stands for
+    pub occurrences: u32,
+}
+
+/// An inverted index of search documents.
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    docs: Vec<SearchHit>,
+    /// Token -> documents containing it (ascending, no repeats)
+    postings: BTreeMap<String, Vec<usize>>,
+}
+
+/// Split text into lowercase word tokens. Apostrophes join rather than
+/// split, so "T'au" is one token.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .replace(['\'', '’'], "")
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn path_link(segments: &[&str]) -> String {
+    let mut url = url::Url::parse("http://localhost").expect("static base URL");
+    url.path_segments_mut()
+        .expect("base URL has a path")
+        .extend(segments);
+    url.path().to_string()
+}
+
+fn query_link(path: &str, key: &str, value: &str) -> String {
+    let value: String = url::form_urlencoded::byte_serialize(value.as_bytes()).collect();
+    format!("{}?{}={}", path, key, value)
+}
+
+/// One epoch's entities to index.
+pub struct EpochEntities<'a> {
+    pub epoch_id: &'a str,
+    pub events: &'a [Event],
+    pub placements: &'a [Placement],
+    pub lists: &'a [ArmyList],
+}
+
+impl SearchIndex {
+    /// Build an index from every epoch's events, placements and lists.
+    pub fn build(epochs: &[EpochEntities<'_>]) -> Self {
+        let mut index = Self::default();
+        let mut players: HashMap<String, (String, u32)> = HashMap::new();
+        let mut factions: HashMap<String, u32> = HashMap::new();
+        let mut detachments: HashMap<(String, String), u32> = HashMap::new();
+        let mut units: HashMap<(String, String), u32> = HashMap::new();
+
+        for epoch in epochs {
+            for event in epoch.events {
+                index.add(SearchHit {
+                    kind: HitKind::Event,
+                    title: event.name.clone(),
+                    subtitle: Some(match &event.city {
+                        Some(city) => format!("{} · {}", event.date, city),
+                        None => event.date.to_string(),
+                    }),
+                    link: path_link(&["api", "events", event.id.as_str()]),
+                    epoch_id: Some(epoch.epoch_id.to_string()),
+                    occurrences: event.player_count.unwrap_or(0),
+                });
+            }
+            for p in epoch.placements {
+                let key = normalize_player_name(&p.player_name);
+                if !key.is_empty() {
+                    let entry = players
+                        .entry(key)
+                        .or_insert_with(|| (p.player_name.trim().to_string(), 0));
+                    entry.1 += 1;
+                }
+                *factions
+                    .entry(normalize_faction_name(&p.faction))
+                    .or_default() += 1;
+                if let Some(d) = p.detachment.as_deref().filter(|d| !d.is_empty()) {
+                    *detachments
+                        .entry((normalize_faction_name(&p.faction), d.to_string()))
+                        .or_default() += 1;
+                }
+            }
+            for list in epoch.lists {
+                let faction = normalize_faction_name(&list.faction);
+                for unit in &list.units {
+                    *units
+                        .entry((faction.clone(), unit.name.clone()))
+                        .or_default() += 1;
+                }
+                if let Some(d) = list.detachment.as_deref().filter(|d| !d.is_empty()) {
+                    detachments
+                        .entry((faction.clone(), d.to_string()))
+                        .or_default();
+                }
+            }
+        }
+
+        for (name, count) in players.into_values() {
+            index.add(SearchHit {
+                kind: HitKind::Player,
+                link: path_link(&["api", "players", &name]),
+                title: name,
+                subtitle: Some(format!("{} placements", count)),
+                epoch_id: None,
+                occurrences: count,
+            });
+        }
+        for (faction, count) in factions {
+            index.add(SearchHit {
+                kind: HitKind::Faction,
+                link: path_link(&["api", "meta", "factions", &faction]),
+                title: faction,
+                subtitle: None,
+                epoch_id: None,
+                occurrences: count,
+            });
+        }
+        for ((faction, detachment), count) in detachments {
+            index.add(SearchHit {
+                kind: HitKind::Detachment,
+                title: detachment,
+                link: path_link(&["api", "analytics", "detachments", &faction]),
+                subtitle: Some(faction),
+                epoch_id: None,
+                occurrences: count,
+            });
+        }
+        for ((faction, unit), count) in units {
+            index.add(SearchHit {
+                kind: HitKind::Unit,
+                title: unit,
+                link: query_link("/api/analytics/units", "faction", &faction),
+                subtitle: Some(faction),
+                epoch_id: None,
+                occurrences: count,
+            });
+        }
+        index
+    }
+
+    fn add(&mut self, hit: SearchHit) {
+        let id = self.docs.len();
+        let mut text = hit.title.clone();
+        // Units and detachments are also found through their faction
+        if matches!(hit.kind, HitKind::Detachment | HitKind::Unit) {
+            if let Some(faction) = &hit.subtitle {
+                text.push(' ');
+                text.push_str(faction);
+            }
+        }
+        for token in tokenize(&text) {
+            let posting = self.postings.entry(token).or_default();
+            if posting.last() != Some(&id) {
+                posting.push(id);
+            }
+        }
+        self.docs.push(hit);
+    }
+
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+
+    /// Documents matching every token of `query` as a prefix, best first:
+    /// whole-title matches, then exact token matches, then by occurrences.
+    pub fn search(&self, query: &str, kind: Option<HitKind>, limit: usize) -> Vec<SearchHit> {
+        let tokens = tokenize(query);
+        if tokens.is_empty() {
+            return Vec::new();
+        }
+
+        // doc -> exact token matches, for docs matching every token so far
+        let mut matches: Option<HashMap<usize, u32>> = None;
+        for token in &tokens {
+            let mut found: HashMap<usize, u32> = HashMap::new();
+            for (term, docs) in self
+                .postings
+                .range(token.clone()..)
+                .take_while(|(term, _)| term.starts_with(token.as_str()))
+            {
+                let exact = u32::from(term == token);
+                for &doc in docs {
+                    let entry = found.entry(doc).or_default();
+                    *entry = (*entry).max(exact);
+                }
+            }
+            matches = Some(match matches {
+                None => found,
+                Some(prev) => prev
+                    .into_iter()
+                    .filter_map(|(doc, exact)| found.get(&doc).map(|e| (doc, exact + e)))
+                    .collect(),
+            });
+        }
+
+        let wanted = tokens.join(" ");
+        let mut ranked: Vec<(bool, u32, &SearchHit)> = matches
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(doc, exact)| (doc, exact, &self.docs[doc]))
+            .filter(|(_, _, hit)| kind.is_none_or(|k| hit.kind == k))
+            .map(|(_, exact, hit)| (tokenize(&hit.title).join(" ") == wanted, exact, hit))
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.0.cmp(&a.0)
+                .then_with(|| b.1.cmp(&a.1))
+                .then_with(|| b.2.occurrences.cmp(&a.2.occurrences))
+                .then_with(|| a.2.title.cmp(&b.2.title))
+        });
+        ranked
+            .into_iter()
+            .take(limit)
+            .map(|(_, _, hit)| hit.clone())
+            .collect()
+    }
+}
+
+/// A built index and the entity indexes it was built from.
+struct CachedIndex {
+    sources: Vec<Arc<dyn Any + Send + Sync>>,
+    index: Arc<SearchIndex>,
+}
+
+fn cache() -> &'static Mutex<HashMap<PathBuf, CachedIndex>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, CachedIndex>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The search index for a data directory, rebuilt when any epoch's events,
+/// placements or lists changed since the last build.
+pub fn search_index(storage: &StorageConfig) -> Result<Arc<SearchIndex>, StorageError> {
+    let epochs = crate::storage::jsonl::list_epochs(storage)?;
+    let mut events = Vec::with_capacity(epochs.len());
+    let mut placements = Vec::with_capacity(epochs.len());
+    let mut lists = Vec::with_capacity(epochs.len());
+    for epoch_id in &epochs {
+        events.push(
+            IndexedReader::<Event>::for_entity(storage, EntityType::Event, epoch_id).index()?,
+        );
+        placements.push(
+            IndexedReader::<Placement>::for_entity(storage, EntityType::Placement, epoch_id)
+                .index()?,
+        );
+        lists.push(
+            IndexedReader::<ArmyList>::for_entity(storage, EntityType::ArmyList, epoch_id)
+                .index()?,
+        );
+    }
+
+    let sources: Vec<Arc<dyn Any + Send + Sync>> = events
+        .iter()
+        .map(|i| i.clone() as Arc<dyn Any + Send + Sync>)
+        .chain(
+            placements
+                .iter()
+                .map(|i| i.clone() as Arc<dyn Any + Send + Sync>),
+        )
+        .chain(
+            lists
+                .iter()
+                .map(|i| i.clone() as Arc<dyn Any + Send + Sync>),
+        )
+        .collect();
+
+    let key = storage.data_dir.clone();
+    {
+        let cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(cached) = cache.get(&key) {
+            let unchanged = cached.sources.len() == sources.len()
+                && cached
+                    .sources
+                    .iter()
+                    .zip(&sources)
+                    .all(|(a, b)| Arc::ptr_eq(a, b));
+            if unchanged {
+                return Ok(cached.index.clone());
+            }
+        }
+    }
+
+    let inputs: Vec<EpochEntities> = epochs
+        .iter()
+        .enumerate()
+        .map(|(i, epoch_id)| EpochEntities {
+            epoch_id,
+            events: events[i].all(),
+            placements: placements[i].all(),
+            lists: lists[i].all(),
+        })
+        .collect();
+    let index = Arc::new(SearchIndex::build(&inputs));
+    tracing::debug!(docs = index.len(), "Built search index");
+
+    cache().lock().unwrap_or_else(|e| e.into_inner()).insert(
+        key,
+        CachedIndex {
+            sources,
+            index: index.clone(),
+        },
+    );
+    Ok(index)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    pub q: String,
+    pub kind: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResponse {
+    pub query: String,
+    pub hits: Vec<SearchHit>,
+}
+
+/// Search event names, player names, factions, detachments and units
+/// across every epoch.
+pub async fn search(
+    State(state): State<AppState>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<SearchResponse>, ApiError> {
+    let query = params.q.trim().to_string();
+    if tokenize(&query).is_empty() {
+        return Err(ApiError::BadRequest(
+            "q must contain at least one letter or digit".to_string(),
+        ));
+    }
+    let kind = params
+        .kind
+        .as_deref()
+        .map(str::parse::<HitKind>)
+        .transpose()
+        .map_err(ApiError::BadRequest)?;
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let index = search_index(&state.storage).map_err(|e| ApiError::Internal(e.to_string()))?;
+    let hits = index.search(&query, kind, limit);
+    Ok(Json(SearchResponse { query, hits }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EntityId, Unit};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_search_index_prefix_and_kinds() {
+        let event = Event::new(
+            "London GT".into(),
+            NaiveDate::from_ymd_opt(2026, 1, 10).unwrap(),
+            "https://example.com/a".into(),
+            "bcp".into(),
+            "current".into(),
+        );
+        let placements = vec![
+            Placement::new(
+                event.id.clone(),
+                "current".into(),
+                1,
+                "Alice Smith".into(),
+                "Aeldari".into(),
+            )
+            .with_detachment("Seer Council".into()),
+            Placement::new(
+                event.id.clone(),
+                "current".into(),
+                2,
+                "Bob".into(),
+                "T'au Empire".into(),
+            ),
+        ];
+        let lists = vec![ArmyList::new(
+            "Aeldari".into(),
+            2000,
+            vec![Unit::new("Wave Serpent".into(), 1)],
+            "raw".into(),
+        )
+        .with_event_id(EntityId::from(event.id.as_str()))];
+
+        let index = SearchIndex::build(&[EpochEntities {
+            epoch_id: "current",
+            events: std::slice::from_ref(&event),
+            placements: &placements,
+            lists: &lists,
+        }]);
+
+        let hits = index.search("lond", None, 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, HitKind::Event);
+        assert_eq!(hits[0].link, format!("/api/events/{}", event.id.as_str()));
+
+        let hits = index.search("alice sm", None, 10);
+        assert_eq!(hits[0].kind, HitKind::Player);
+        assert_eq!(hits[0].link, "/api/players/Alice%20Smith");
+
+        assert_eq!(index.search("tau", None, 10)[0].title, "T'au Empire");
+
+        // Units are found through their faction too; the faction ranks first
+        let hits = index.search("aeldari", None, 10);
+        assert_eq!(hits[0].kind, HitKind::Faction);
+        assert!(hits.iter().any(|h| h.title == "Wave Serpent"));
+        let units = index.search("aeldari", Some(HitKind::Unit), 10);
+        assert_eq!(units.len(), 1);
+        assert_eq!(units[0].link, "/api/analytics/units?faction=Aeldari");
+
+        assert!(index.search("seer zzz", None, 10).is_empty());
+    }
+}
@@ -1,16 +1,35 @@
 use std::sync::Arc;
 
 use crate::agents::backend::AiBackend;
-use crate::api::routes::refresh::RefreshState;
+use crate::api::routes::refresh::{RefreshState, RefreshUpdates};
 use crate::api::routes::traffic::SharedTrafficStats;
-use crate::models::EpochMapper;
+use crate::metrics::Metrics;
+use crate::models::{EpochMapper, SizeThresholds};
+use crate::notify::Notifier;
 use crate::storage::StorageConfig;
+use crate::sync::ActiveSyncs;
 
 #[derive(Clone)]
 pub struct AppState {
     pub storage: Arc<StorageConfig>,
     pub epoch_mapper: Arc<tokio::sync::RwLock<EpochMapper>>,
     pub refresh_state: Arc<tokio::sync::RwLock<RefreshState>>,
+    /// Broadcasts refresh state changes to `/api/refresh/stream` clients
+    pub refresh_updates: RefreshUpdates,
+    /// Syncs started by the server, cancelled on shutdown
+    pub syncs: ActiveSyncs,
+    /// Epoch used when a request names none (`[server] default_epoch`)
+    pub default_epoch: Option<String>,
+    /// Player counts separating event size brackets (`[analytics.size_brackets]`)
+    pub size_thresholds: SizeThresholds,
+    /// Grace period given to balance passes a refresh discovers (`[epochs] grace_days`)
+    pub grace_days: u32,
+    /// Token required on mutating routes (`[server] api_token`)
+    pub api_token: Option<Arc<str>>,
+    /// Watchlist alerts sent after server-run syncs (`[notifications]`)
+    pub notifier: Option<Arc<Notifier>>,
     pub ai_backend: Arc<dyn AiBackend>,
     pub traffic_stats: SharedTrafficStats,
+    /// Registry rendered by `GET /metrics`
+    pub metrics: Arc<Metrics>,
 }
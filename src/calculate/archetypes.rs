@@ -0,0 +1,738 @@
+//! Army list archetypes.
+//!
+//! Lists are clustered within each (faction, detachment) by the Jaccard
+//! similarity of their unit names, using average-linkage agglomerative
+//! clustering: the two most similar clusters are merged until no pair is at
+//! least [`SIMILARITY_THRESHOLD`] similar on average. Unlike a greedy pass
+//! this doesn't depend on which list happens to be read first.
+//!
+//! Each cluster is labelled with its defining units (common in the cluster,
+//! uncommon across the faction) and scored both on its players' final
+//! records and on the games they played from pairings.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::detachments::GameRecord;
+use crate::api::routes::analytics::join_lists_to_placements;
+use crate::models::normalize_faction_name;
+use crate::models::{ArmyList, Pairing, Placement};
+use crate::sync::normalize_player_name;
+
+/// Minimum average similarity for two clusters to be merged.
+pub const SIMILARITY_THRESHOLD: f64 = 0.5;
+
+/// Smallest cluster reported as an archetype.
+pub const MIN_CLUSTER_SIZE: usize = 2;
+
+/// A defining unit appears in at least this share of the cluster's lists...
+const DEFINING_CLUSTER_SHARE: f64 = 0.6;
+
+/// ...and in less than this share of the faction's lists.
+const DEFINING_FACTION_SHARE: f64 = 0.3;
+
+/// Defining units used in an archetype's name.
+const NAME_UNITS: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchetypeUnit {
+    pub name: String,
+    pub count: u32,
+    pub points: Option<u32>,
+}
+
+/// A list belonging to an archetype.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchetypeListEntry {
+    pub player_name: String,
+    /// Final rank, 0 when the list isn't joined to a placement
+    pub rank: u32,
+    pub event_id: String,
+    pub total_points: u32,
+    pub units: Vec<ArchetypeUnit>,
+}
+
+/// One cluster of similar lists.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchetypeStat {
+    pub name: String,
+    pub detachment: String,
+    /// Most distinctive first
+    pub defining_units: Vec<String>,
+    pub list_count: u32,
+    /// Cloned submissions of lists in this archetype (not counted in list_count)
+    pub clone_count: u32,
+    /// Average pairwise similarity of the cluster's lists (0.0 to 1.0)
+    pub cohesion: f64,
+    pub avg_rank: f64,
+    /// Average final-record win rate in percent
+    pub avg_win_rate: f64,
+    /// Games from pairings; mirrors against the same archetype are skipped
+    #[serde(flatten)]
+    pub record: GameRecord,
+    pub games: u32,
+    pub game_win_rate: Option<f64>,
+    /// Every list in the cluster, best rank first
+    pub sample_lists: Vec<ArchetypeListEntry>,
+}
+
+/// Archetypes of one faction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactionArchetypes {
+    pub faction: String,
+    /// Largest first
+    pub archetypes: Vec<ArchetypeStat>,
+    pub total_lists: u32,
+    /// Lists excluded from clustering because they clone another list
+    pub clone_lists: u32,
+    /// Lists not similar enough to any other to form an archetype
+    pub unclustered_lists: u32,
+}
+
+impl FactionArchetypes {
+    fn empty(faction: String, clone_lists: u32) -> Self {
+        Self {
+            faction,
+            archetypes: Vec::new(),
+            total_lists: 0,
+            clone_lists,
+            unclustered_lists: 0,
+        }
+    }
+}
+
+/// Jaccard similarity between two sets of unit names.
+pub fn jaccard_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let intersection = a.intersection(b).count();
+    let union = a.union(b).count();
+    if union == 0 {
+        0.0
+    } else {
+        intersection as f64 / union as f64
+    }
+}
+
+/// Average-linkage clustering of unit sets. Returns clusters of indices into
+/// `sets`, each sorted, in order of their first member.
+pub fn cluster_unit_sets(sets: &[HashSet<String>], threshold: f64) -> Vec<Vec<usize>> {
+    let mut clusters: Vec<Vec<usize>> = (0..sets.len()).map(|i| vec![i]).collect();
+    let mut sim: Vec<Vec<f64>> = (0..sets.len())
+        .map(|i| {
+            (0..sets.len())
+                .map(|j| jaccard_similarity(&sets[i], &sets[j]))
+                .collect()
+        })
+        .collect();
+    let mut alive: Vec<bool> = vec![true; sets.len()];
+
+    loop {
+        let mut best: Option<(usize, usize, f64)> = None;
+        for i in 0..clusters.len() {
+            if !alive[i] {
+                continue;
+            }
+            for j in (i + 1)..clusters.len() {
+                if alive[j] && sim[i][j] >= threshold && best.is_none_or(|(_, _, s)| sim[i][j] > s)
+                {
+                    best = Some((i, j, sim[i][j]));
+                }
+            }
+        }
+        let Some((a, b, _)) = best else { break };
+
+        // Lance-Williams update for average linkage
+        let (size_a, size_b) = (clusters[a].len() as f64, clusters[b].len() as f64);
+        for k in 0..clusters.len() {
+            if alive[k] && k != a && k != b {
+                let merged = (size_a * sim[a][k] + size_b * sim[b][k]) / (size_a + size_b);
+                sim[a][k] = merged;
+                sim[k][a] = merged;
+            }
+        }
+        let moved = std::mem::take(&mut clusters[b]);
+        clusters[a].extend(moved);
+        alive[b] = false;
+    }
+
+    let mut out: Vec<Vec<usize>> = clusters
+        .into_iter()
+        .zip(alive)
+        .filter(|(c, alive)| *alive && !c.is_empty())
+        .map(|(mut c, _)| {
+            c.sort_unstable();
+            c
+        })
+        .collect();
+    out.sort_by_key(|c| c[0]);
+    out
+}
+
+fn unit_set(list: &ArmyList) -> HashSet<String> {
+    list.units.iter().map(|u| u.name.clone()).collect()
+}
+
+fn round1(v: f64) -> f64 {
+    (v * 10.0).round() / 10.0
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+/// Cluster one faction's lists into archetypes.
+pub fn faction_archetypes(
+    faction: &str,
+    placements: &[Placement],
+    lists: &[ArmyList],
+    pairings: &[Pairing],
+) -> FactionArchetypes {
+    let faction = normalize_faction_name(faction);
+    let faction_lists: Vec<&ArmyList> = lists
+        .iter()
+        .filter(|l| normalize_faction_name(&l.faction) == faction && !l.units.is_empty())
+        .collect();
+
+    // Clones are counted against their original, not as independent lists
+    let mut clone_counts: HashMap<&str, u32> = HashMap::new();
+    for list in &faction_lists {
+        if let Some(ref original) = list.clone_of {
+            *clone_counts.entry(original.as_str()).or_default() += 1;
+        }
+    }
+    let clone_lists: u32 = clone_counts.values().sum();
+    let (clones, faction_lists): (Vec<&ArmyList>, Vec<&ArmyList>) = faction_lists
+        .into_iter()
+        .partition(|l| l.clone_of.is_some());
+    if faction_lists.is_empty() {
+        return FactionArchetypes::empty(faction, clone_lists);
+    }
+    let total_lists = faction_lists.len();
+
+    let sets: Vec<HashSet<String>> = faction_lists.iter().map(|l| unit_set(l)).collect();
+    let mut faction_unit_freq: HashMap<&str, u32> = HashMap::new();
+    for set in &sets {
+        for unit in set {
+            *faction_unit_freq.entry(unit.as_str()).or_default() += 1;
+        }
+    }
+
+    let mut by_detachment: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (idx, list) in faction_lists.iter().enumerate() {
+        by_detachment
+            .entry(list.detachment.as_deref().unwrap_or("Unknown"))
+            .or_default()
+            .push(idx);
+    }
+    let mut detachments: Vec<&str> = by_detachment.keys().copied().collect();
+    detachments.sort_unstable();
+
+    // Cluster within each detachment; clusters hold indices into faction_lists
+    let mut clusters: Vec<(&str, Vec<usize>)> = Vec::new();
+    for detachment in detachments {
+        let indices = &by_detachment[detachment];
+        let group_sets: Vec<HashSet<String>> = indices.iter().map(|&i| sets[i].clone()).collect();
+        for cluster in cluster_unit_sets(&group_sets, SIMILARITY_THRESHOLD) {
+            if cluster.len() >= MIN_CLUSTER_SIZE {
+                clusters.push((
+                    detachment,
+                    cluster.into_iter().map(|i| indices[i]).collect(),
+                ));
+            }
+        }
+    }
+    let clustered: usize = clusters.iter().map(|(_, c)| c.len()).sum();
+
+    let joined = join_lists_to_placements(lists, placements);
+    let placement_by_list: HashMap<&str, &Placement> =
+        joined.iter().map(|(l, p)| (l.id.as_str(), p)).collect();
+
+    // Which archetype each player was on at each event (clones play their
+    // original's list), for scoring pairings
+    let mut cluster_of_list: HashMap<&str, usize> = HashMap::new();
+    for (cluster_idx, (_, members)) in clusters.iter().enumerate() {
+        for &idx in members {
+            cluster_of_list.insert(faction_lists[idx].id.as_str(), cluster_idx);
+        }
+    }
+    for clone in &clones {
+        if let Some(&c) = clone
+            .clone_of
+            .as_ref()
+            .and_then(|o| cluster_of_list.get(o.as_str()))
+        {
+            cluster_of_list.insert(clone.id.as_str(), c);
+        }
+    }
+    let mut cluster_of_player: HashMap<(String, String), usize> = HashMap::new();
+    for list in faction_lists.iter().chain(clones.iter()) {
+        let Some(&c) = cluster_of_list.get(list.id.as_str()) else {
+            continue;
+        };
+        let (event_id, player) = match placement_by_list.get(list.id.as_str()) {
+            Some(p) => (p.event_id.as_str().to_string(), p.player_name.clone()),
+            None => match (&list.event_id, &list.player_name) {
+                (Some(e), Some(name)) => (e.as_str().to_string(), name.clone()),
+                _ => continue,
+            },
+        };
+        cluster_of_player.insert((event_id, normalize_player_name(&player)), c);
+    }
+    let records = archetype_records(&cluster_of_player, pairings, clusters.len());
+
+    let mut archetypes: Vec<ArchetypeStat> = clusters
+        .iter()
+        .zip(records)
+        .map(|((detachment, members), record)| {
+            let size = members.len() as f64;
+            let mut cluster_unit_freq: HashMap<&str, u32> = HashMap::new();
+            for &idx in members {
+                for unit in &sets[idx] {
+                    *cluster_unit_freq.entry(unit.as_str()).or_default() += 1;
+                }
+            }
+
+            // Ranked by lift: how much more common in the cluster than the faction
+            let mut defining: Vec<(&str, f64)> = cluster_unit_freq
+                .iter()
+                .filter_map(|(&unit, &count)| {
+                    let cluster_share = count as f64 / size;
+                    let faction_share = faction_unit_freq.get(unit).copied().unwrap_or(0) as f64
+                        / total_lists as f64;
+                    (cluster_share >= DEFINING_CLUSTER_SHARE
+                        && faction_share < DEFINING_FACTION_SHARE)
+                        .then_some((unit, cluster_share / faction_share))
+                })
+                .collect();
+            defining.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+            let defining_units: Vec<String> = defining.iter().map(|(u, _)| u.to_string()).collect();
+
+            let name = if defining_units.is_empty() {
+                format!("{} {}", detachment, members.len())
+            } else {
+                defining_units
+                    .iter()
+                    .take(NAME_UNITS)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(" + ")
+            };
+
+            let mut pair_sims = Vec::new();
+            for (i, &a) in members.iter().enumerate() {
+                for &b in &members[i + 1..] {
+                    pair_sims.push(jaccard_similarity(&sets[a], &sets[b]));
+                }
+            }
+
+            let mut ranks = Vec::new();
+            let mut win_rates = Vec::new();
+            let mut sample_lists = Vec::new();
+            for &idx in members {
+                let list = faction_lists[idx];
+                let placement = placement_by_list.get(list.id.as_str());
+                if let Some(p) = placement {
+                    ranks.push(p.rank as f64);
+                    if let Some(ref record) = p.record {
+                        win_rates.push(record.win_rate());
+                    }
+                }
+                sample_lists.push(ArchetypeListEntry {
+                    player_name: placement
+                        .map(|p| p.player_name.clone())
+                        .or_else(|| list.player_name.clone())
+                        .unwrap_or_else(|| "Unknown".to_string()),
+                    rank: placement.map(|p| p.rank).unwrap_or(0),
+                    event_id: placement
+                        .map(|p| p.event_id.as_str().to_string())
+                        .or_else(|| list.event_id.as_ref().map(|e| e.as_str().to_string()))
+                        .unwrap_or_default(),
+                    total_points: list.total_points,
+                    units: list
+                        .units
+                        .iter()
+                        .map(|u| ArchetypeUnit {
+                            name: u.name.clone(),
+                            count: u.count,
+                            points: u.points,
+                        })
+                        .collect(),
+                });
+            }
+            sample_lists.sort_by_key(|e| e.rank);
+
+            ArchetypeStat {
+                name,
+                detachment: detachment.to_string(),
+                defining_units,
+                list_count: members.len() as u32,
+                clone_count: members
+                    .iter()
+                    .map(|&idx| {
+                        clone_counts
+                            .get(faction_lists[idx].id.as_str())
+                            .copied()
+                            .unwrap_or(0)
+                    })
+                    .sum(),
+                cohesion: (mean(&pair_sims).unwrap_or(1.0) * 1000.0).round() / 1000.0,
+                avg_rank: mean(&ranks).map(round1).unwrap_or(0.0),
+                avg_win_rate: mean(&win_rates).map(|r| round1(r * 100.0)).unwrap_or(0.0),
+                games: record.games(),
+                game_win_rate: record.win_rate(),
+                record,
+                sample_lists,
+            }
+        })
+        .collect();
+
+    archetypes.sort_by(|a, b| {
+        b.list_count
+            .cmp(&a.list_count)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    FactionArchetypes {
+        faction,
+        archetypes,
+        total_lists: total_lists as u32,
+        clone_lists,
+        unclustered_lists: (total_lists - clustered) as u32,
+    }
+}
+
+/// Game records per cluster, keyed by (event_id, normalized player name).
+fn archetype_records(
+    cluster_of_player: &HashMap<(String, String), usize>,
+    pairings: &[Pairing],
+    clusters: usize,
+) -> Vec<GameRecord> {
+    let mut records = vec![GameRecord::default(); clusters];
+    for pairing in pairings {
+        let (p1_won, p2_won) = match pairing.player1_result.as_deref() {
+            Some("win") => (Some(true), Some(false)),
+            Some("loss") => (Some(false), Some(true)),
+            Some("draw") => (None, None),
+            _ => continue,
+        };
+        let event_id = pairing.event_id.as_str().to_string();
+        let lookup =
+            |name: &str| cluster_of_player.get(&(event_id.clone(), normalize_player_name(name)));
+        let c1 = lookup(&pairing.player1_name);
+        let c2 = lookup(&pairing.player2_name);
+        if c1.is_some() && c1 == c2 {
+            continue;
+        }
+        for (cluster, won) in [(c1, p1_won), (c2, p2_won)] {
+            let Some(&cluster) = cluster else { continue };
+            let record = &mut records[cluster];
+            match won {
+                Some(true) => record.wins += 1,
+                Some(false) => record.losses += 1,
+                None => record.draws += 1,
+            }
+        }
+    }
+    records
+}
+
+/// Archetypes for every faction with lists, alphabetically.
+pub fn all_archetypes(
+    placements: &[Placement],
+    lists: &[ArmyList],
+    pairings: &[Pairing],
+) -> Vec<FactionArchetypes> {
+    let factions: HashSet<String> = lists
+        .iter()
+        .map(|l| normalize_faction_name(&l.faction))
+        .collect();
+    let mut factions: Vec<String> = factions.into_iter().collect();
+    factions.sort();
+    factions
+        .iter()
+        .map(|f| faction_archetypes(f, placements, lists, pairings))
+        .filter(|a| a.total_lists > 0)
+        .collect()
+}
+
+/// A unit whose copies differ between a list and an archetype.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UnitChange {
+    pub name: String,
+    /// Copies in the archetype's typical list
+    pub archetype: u32,
+    /// Copies in this list
+    pub list: u32,
+}
+
+/// How a list differs from its faction and detachment's most common
+/// archetype.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchetypeDiff {
+    pub archetype: String,
+    pub detachment: String,
+    pub archetype_lists: u32,
+    /// Units the list has more copies of than the typical list
+    pub added: Vec<UnitChange>,
+    /// Units the list has fewer copies of than the typical list
+    pub dropped: Vec<UnitChange>,
+    /// e.g. "drops 2x Eradicators, adds 1x Redemptor Dreadnought"
+    pub summary: String,
+}
+
+/// The archetype a list is compared against: the largest with the list's
+/// detachment, else the faction's largest.
+pub fn reference_archetype<'a>(
+    archetypes: &'a FactionArchetypes,
+    detachment: Option<&str>,
+) -> Option<&'a ArchetypeStat> {
+    archetypes
+        .archetypes
+        .iter()
+        .find(|a| detachment.is_some_and(|d| a.detachment.eq_ignore_ascii_case(d)))
+        .or_else(|| archetypes.archetypes.first())
+}
+
+fn copies_by_name<'a>(units: impl Iterator<Item = &'a str>) -> HashMap<&'a str, u32> {
+    let mut copies = HashMap::new();
+    for name in units {
+        *copies.entry(name).or_default() += 1;
+    }
+    copies
+}
+
+/// Copies of each unit in an archetype's typical list: the median over its
+/// lists, counting lists without the unit as zero.
+fn typical_copies(archetype: &ArchetypeStat) -> HashMap<&str, u32> {
+    let per_list: Vec<HashMap<&str, u32>> = archetype
+        .sample_lists
+        .iter()
+        .map(|l| copies_by_name(l.units.iter().map(|u| u.name.as_str())))
+        .collect();
+    let names: HashSet<&str> = per_list.iter().flat_map(|c| c.keys().copied()).collect();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let mut counts: Vec<u32> = per_list
+                .iter()
+                .map(|c| c.get(name).copied().unwrap_or(0))
+                .collect();
+            counts.sort_unstable();
+            let median = counts[(counts.len() - 1) / 2];
+            (median > 0).then_some((name, median))
+        })
+        .collect()
+}
+
+/// Compare a list's units with an archetype's typical list.
+pub fn diff_against_archetype(list: &ArmyList, archetype: &ArchetypeStat) -> ArchetypeDiff {
+    let typical = typical_copies(archetype);
+    let ours = copies_by_name(list.units.iter().map(|u| u.name.as_str()));
+
+    let mut names: Vec<&str> = typical.keys().chain(ours.keys()).copied().collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let (mut added, mut dropped) = (Vec::new(), Vec::new());
+    for name in names {
+        let change = UnitChange {
+            name: name.to_string(),
+            archetype: typical.get(name).copied().unwrap_or(0),
+            list: ours.get(name).copied().unwrap_or(0),
+        };
+        if change.list > change.archetype {
+            added.push(change);
+        } else if change.list < change.archetype {
+            dropped.push(change);
+        }
+    }
+
+    let describe = |verb: &str, changes: &[UnitChange]| {
+        let units: Vec<String> = changes
+            .iter()
+            .map(|c| format!("{}x {}", c.list.abs_diff(c.archetype), c.name))
+            .collect();
+        (!units.is_empty()).then(|| format!("{} {}", verb, units.join(", ")))
+    };
+    let parts: Vec<String> = [describe("drops", &dropped), describe("adds", &added)]
+        .into_iter()
+        .flatten()
+        .collect();
+    let summary = if parts.is_empty() {
+        "matches the archetype".to_string()
+    } else {
+        parts.join("; ")
+    };
+
+    ArchetypeDiff {
+        archetype: archetype.name.clone(),
+        detachment: archetype.detachment.clone(),
+        archetype_lists: archetype.list_count,
+        added,
+        dropped,
+        summary,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EntityId, Unit};
+
+    fn set(units: &[&str]) -> HashSet<String> {
+        units.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_cluster_unit_sets_is_order_independent() {
+        let sets = vec![
+            set(&["A", "B", "C"]),
+            set(&["X", "Y", "Z"]),
+            set(&["A", "B", "C", "D"]),
+            set(&["X", "Y", "Z", "W"]),
+            set(&["Q"]),
+        ];
+        let clusters = cluster_unit_sets(&sets, SIMILARITY_THRESHOLD);
+        assert_eq!(clusters, vec![vec![0, 2], vec![1, 3], vec![4]]);
+
+        let reversed: Vec<HashSet<String>> = sets.iter().rev().cloned().collect();
+        let clusters = cluster_unit_sets(&reversed, SIMILARITY_THRESHOLD);
+        assert_eq!(clusters, vec![vec![0], vec![1, 3], vec![2, 4]]);
+    }
+
+    #[test]
+    fn test_faction_archetypes_labels_and_scores_games() {
+        let list = |player: &str, units: &[&str]| {
+            let mut list = ArmyList::new(
+                "Aeldari".to_string(),
+                2000,
+                units.iter().map(|u| Unit::new(u.to_string(), 1)).collect(),
+                "raw".into(),
+            )
+            .with_detachment("Battle Host".to_string())
+            .with_player_name(player.to_string())
+            .with_event_id(EntityId::from("evt"));
+            list.id = EntityId::generate(&[player]);
+            list
+        };
+        let lists = vec![
+            list("Alice", &["Farseer", "Wraithguard", "Wave Serpent"]),
+            list("Bob", &["Farseer", "Wraithguard", "Wave Serpent", "Avatar"]),
+            list("Cara", &["Farseer", "Fire Dragons", "Falcon"]),
+            list("Dan", &["Farseer", "Fire Dragons", "Falcon", "Autarch"]),
+            list("Eve", &["Farseer", "Fire Dragons", "Falcon"]),
+            list("Finn", &["Farseer", "Warp Spiders"]),
+            list("Gus", &["Farseer", "Shining Spears"]),
+            list("Hal", &["Farseer", "Windriders"]),
+            list("Ivy", &["Farseer", "Wraithknight"]),
+            list("Jo", &["Farseer", "Night Spinner"]),
+            list("Kit", &["Farseer", "Hemlock"]),
+        ];
+        let pairing = |p1: &str, p2: &str, result: &str| {
+            let mut p = Pairing::new(
+                EntityId::from("evt"),
+                EntityId::from("current"),
+                1,
+                p1.to_string(),
+                p2.to_string(),
+            );
+            p.player1_result = Some(result.to_string());
+            p
+        };
+        let pairings = vec![
+            pairing("Alice", "Cara", "win"),
+            pairing("Bob", "Dan", "draw"),
+            // Mirror within the Fire Dragons archetype: skipped
+            pairing("Cara", "Eve", "win"),
+            pairing("Finn", "Eve", "loss"),
+        ];
+
+        let result = faction_archetypes("aeldari", &[], &lists, &pairings);
+        assert_eq!(result.faction, "Aeldari");
+        assert_eq!(result.total_lists, 11);
+        assert_eq!(result.unclustered_lists, 6);
+        assert_eq!(result.archetypes.len(), 2);
+
+        let dragons = &result.archetypes[0];
+        assert_eq!(dragons.list_count, 3);
+        // Farseer is in every list, so it doesn't define anything
+        assert_eq!(dragons.defining_units, vec!["Falcon", "Fire Dragons"]);
+        assert_eq!(dragons.name, "Falcon + Fire Dragons");
+        assert_eq!(
+            dragons.record,
+            GameRecord {
+                wins: 1,
+                losses: 1,
+                draws: 1
+            }
+        );
+        assert_eq!(dragons.game_win_rate, Some(50.0));
+
+        let wraiths = &result.archetypes[1];
+        assert_eq!(wraiths.defining_units, vec!["Wave Serpent", "Wraithguard"]);
+        assert_eq!(wraiths.games, 2);
+        assert_eq!(wraiths.game_win_rate, Some(75.0));
+        assert!((wraiths.cohesion - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_diff_against_archetype() {
+        let entry = |units: &[&str]| ArchetypeListEntry {
+            player_name: "P".into(),
+            rank: 1,
+            event_id: "evt".into(),
+            total_points: 2000,
+            units: units
+                .iter()
+                .map(|u| ArchetypeUnit {
+                    name: u.to_string(),
+                    count: 1,
+                    points: None,
+                })
+                .collect(),
+        };
+        let archetype = ArchetypeStat {
+            name: "Eradicators + Gladiator".into(),
+            detachment: "Gladius Task Force".into(),
+            defining_units: vec!["Eradicators".into()],
+            list_count: 3,
+            clone_count: 0,
+            cohesion: 0.8,
+            avg_rank: 2.0,
+            avg_win_rate: 60.0,
+            record: GameRecord::default(),
+            games: 0,
+            game_win_rate: None,
+            sample_lists: vec![
+                entry(&["Captain", "Eradicators", "Eradicators", "Gladiator"]),
+                entry(&["Captain", "Eradicators", "Eradicators"]),
+                entry(&["Captain", "Eradicators", "Gladiator", "Scouts"]),
+            ],
+        };
+        let list = ArmyList::new(
+            "Space Marines".into(),
+            2000,
+            ["Captain", "Gladiator", "Redemptor Dreadnought"]
+                .iter()
+                .map(|u| Unit::new(u.to_string(), 1))
+                .collect(),
+            "raw".into(),
+        );
+
+        let diff = diff_against_archetype(&list, &archetype);
+        assert_eq!(diff.dropped.len(), 1);
+        assert_eq!(diff.dropped[0].archetype, 2);
+        assert_eq!(diff.added[0].name, "Redemptor Dreadnought");
+        assert_eq!(
+            diff.summary,
+            "drops 2x Eradicators; adds 1x Redemptor Dreadnought"
+        );
+    }
+}
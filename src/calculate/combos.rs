@@ -0,0 +1,278 @@
+//! Unit combo detection.
+//!
+//! Finds unit pairs and triples that winning lists of a faction take
+//! together more often than chance, scored like association rules:
+//!
+//! - `support`: share of the faction's winning lists containing the combo
+//! - `confidence`: of the winning lists taking the combo's least common
+//!   unit, the share that also take the rest
+//! - `lift`: support over what independent picks would give (the product
+//!   of each unit's support); above 1.0 the units are taken together
+//!
+//! A winning list is one joined to a placement with more wins than losses,
+//! or a top-4 finish when the record is unknown. Triples are only counted
+//! when all three of their pairs are frequent.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::analytics::join_lists_to_placements;
+use crate::models::normalize_faction_name;
+use crate::models::{ArmyList, Placement};
+
+/// Smallest number of winning lists a combo must appear in.
+pub const MIN_COMBO_LISTS: u32 = 3;
+
+/// Smallest support for a combo to be reported.
+pub const MIN_COMBO_SUPPORT: f64 = 0.1;
+
+/// Combos reported per faction for each size.
+const MAX_COMBOS: usize = 25;
+
+/// A set of units taken together.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnitCombo {
+    /// Sorted by name
+    pub units: Vec<String>,
+    /// Winning lists containing every unit
+    pub lists: u32,
+    pub support: f64,
+    pub confidence: f64,
+    pub lift: f64,
+}
+
+/// Combos found in one faction's winning lists.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactionCombos {
+    pub faction: String,
+    pub winning_lists: u32,
+    /// Strongest first (lift, then lists)
+    pub pairs: Vec<UnitCombo>,
+    pub triples: Vec<UnitCombo>,
+}
+
+fn round3(v: f64) -> f64 {
+    (v * 1000.0).round() / 1000.0
+}
+
+fn is_winning(p: &Placement) -> bool {
+    match &p.record {
+        Some(r) => r.wins > r.losses,
+        None => p.rank <= 4,
+    }
+}
+
+/// Unit sets of every winning list, by normalized faction. Cloned lists
+/// count once, through their original.
+fn winning_unit_sets(
+    placements: &[Placement],
+    lists: &[ArmyList],
+) -> HashMap<String, Vec<BTreeSet<String>>> {
+    let mut out: HashMap<String, Vec<BTreeSet<String>>> = HashMap::new();
+    for (list, placement) in join_lists_to_placements(lists, placements) {
+        if list.clone_of.is_some() || list.units.is_empty() || !is_winning(&placement) {
+            continue;
+        }
+        out.entry(normalize_faction_name(&list.faction))
+            .or_default()
+            .push(list.units.iter().map(|u| u.name.clone()).collect());
+    }
+    out
+}
+
+/// Count how many sets contain each combo of `size` units drawn from
+/// `units`. Triples are only counted when all their pairs are in `pairs`.
+fn count_combos<'a>(
+    sets: &'a [BTreeSet<String>],
+    size: usize,
+    units: &HashMap<&str, u32>,
+    pairs: &HashSet<Vec<&str>>,
+) -> HashMap<Vec<&'a str>, u32> {
+    let mut counts: HashMap<Vec<&str>, u32> = HashMap::new();
+    for set in sets {
+        let taken: Vec<&str> = set
+            .iter()
+            .map(String::as_str)
+            .filter(|u| units.contains_key(u))
+            .collect();
+        for (i, &a) in taken.iter().enumerate() {
+            for (j, &b) in taken.iter().enumerate().skip(i + 1) {
+                if size == 2 {
+                    *counts.entry(vec![a, b]).or_default() += 1;
+                    continue;
+                }
+                for &c in taken.iter().skip(j + 1) {
+                    let all_frequent = [[a, b], [a, c], [b, c]]
+                        .iter()
+                        .all(|pair| pairs.contains(pair.as_slice()));
+                    if all_frequent {
+                        *counts.entry(vec![a, b, c]).or_default() += 1;
+                    }
+                }
+            }
+        }
+    }
+    counts
+}
+
+fn is_frequent(count: u32, total: usize) -> bool {
+    count >= MIN_COMBO_LISTS && count as f64 / total as f64 >= MIN_COMBO_SUPPORT
+}
+
+/// Score frequent combos, keeping those taken together more than chance.
+fn score_combos(
+    counts: &HashMap<Vec<&str>, u32>,
+    units: &HashMap<&str, u32>,
+    total: usize,
+) -> Vec<UnitCombo> {
+    let mut combos: Vec<UnitCombo> = counts
+        .iter()
+        .filter(|(_, &n)| is_frequent(n, total))
+        .map(|(combo, &n)| {
+            let share = |count: u32| count as f64 / total as f64;
+            let support = share(n);
+            let unit_supports: Vec<f64> = combo.iter().map(|u| share(units[u])).collect();
+            let expected: f64 = unit_supports.iter().product();
+            let rarest = unit_supports.iter().cloned().fold(f64::INFINITY, f64::min);
+            UnitCombo {
+                units: combo.iter().map(|u| u.to_string()).collect(),
+                lists: n,
+                support: round3(support),
+                confidence: round3(support / rarest),
+                lift: round3(support / expected),
+            }
+        })
+        .filter(|c| c.lift > 1.0)
+        .collect();
+    combos.sort_by(|a, b| {
+        b.lift
+            .partial_cmp(&a.lift)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| b.lists.cmp(&a.lists))
+            .then_with(|| a.units.cmp(&b.units))
+    });
+    combos.truncate(MAX_COMBOS);
+    combos
+}
+
+fn combos_for_sets(faction: String, sets: &[BTreeSet<String>]) -> FactionCombos {
+    let total = sets.len();
+    let mut units: HashMap<&str, u32> = HashMap::new();
+    for set in sets {
+        for unit in set {
+            *units.entry(unit.as_str()).or_default() += 1;
+        }
+    }
+    // A combo is never more frequent than its rarest unit
+    units.retain(|_, n| is_frequent(*n, total));
+
+    let pair_counts = count_combos(sets, 2, &units, &HashSet::new());
+    let frequent_pairs: HashSet<Vec<&str>> = pair_counts
+        .iter()
+        .filter(|(_, &n)| is_frequent(n, total))
+        .map(|(pair, _)| pair.clone())
+        .collect();
+    let triple_counts = count_combos(sets, 3, &units, &frequent_pairs);
+
+    FactionCombos {
+        faction,
+        winning_lists: total as u32,
+        pairs: score_combos(&pair_counts, &units, total),
+        triples: score_combos(&triple_counts, &units, total),
+    }
+}
+
+/// Combos in one faction's winning lists.
+pub fn faction_combos(
+    faction: &str,
+    placements: &[Placement],
+    lists: &[ArmyList],
+) -> FactionCombos {
+    let faction = normalize_faction_name(faction);
+    let sets = winning_unit_sets(placements, lists)
+        .remove(&faction)
+        .unwrap_or_default();
+    combos_for_sets(faction, &sets)
+}
+
+/// Combos for every faction with winning lists, by faction name.
+pub fn all_combos(placements: &[Placement], lists: &[ArmyList]) -> Vec<FactionCombos> {
+    let mut by_faction: Vec<(String, Vec<BTreeSet<String>>)> =
+        winning_unit_sets(placements, lists).into_iter().collect();
+    by_faction.sort_by(|a, b| a.0.cmp(&b.0));
+    by_faction
+        .into_iter()
+        .map(|(faction, sets)| combos_for_sets(faction, &sets))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EntityId, Unit};
+
+    #[test]
+    fn test_faction_combos_finds_associated_units() {
+        let event = EntityId::from("evt");
+        let mut placements = Vec::new();
+        let mut lists = Vec::new();
+        let rosters: [&[&str]; 8] = [
+            &["Farseer", "Wraithguard", "Wave Serpent", "Rangers"],
+            &["Farseer", "Wraithguard", "Wave Serpent"],
+            &["Farseer", "Wraithguard", "Wave Serpent", "Avatar"],
+            &["Autarch", "Fire Dragons", "Falcon", "Rangers"],
+            &["Autarch", "Fire Dragons", "Falcon"],
+            &["Autarch", "Fire Dragons", "Falcon", "Rangers"],
+            &["Farseer", "Rangers"],
+            // Losing list: ignored
+            &["Farseer", "Avatar", "Rangers"],
+        ];
+        for (i, units) in rosters.iter().enumerate() {
+            let player = format!("Player {}", i);
+            let (wins, losses) = if i == 7 { (1, 4) } else { (4, 1) };
+            placements.push(
+                Placement::new(
+                    event.clone(),
+                    "current".into(),
+                    i as u32 + 1,
+                    player.clone(),
+                    "Aeldari".into(),
+                )
+                .with_record(wins, losses, 0),
+            );
+            lists.push(
+                ArmyList::new(
+                    "Aeldari".into(),
+                    2000,
+                    units.iter().map(|u| Unit::new(u.to_string(), 1)).collect(),
+                    format!("raw {}", i),
+                )
+                .with_player_name(player)
+                .with_event_id(event.clone()),
+            );
+        }
+
+        let combos = faction_combos("aeldari", &placements, &lists);
+        assert_eq!(combos.faction, "Aeldari");
+        assert_eq!(combos.winning_lists, 7);
+
+        let top = &combos.pairs[0];
+        assert_eq!(top.lists, 3);
+        assert_eq!(top.confidence, 1.0);
+        assert!(top.lift > 2.0);
+        // Farseer and Rangers are taken together less often than chance
+        assert!(combos
+            .pairs
+            .iter()
+            .all(|c| c.units != vec!["Farseer".to_string(), "Rangers".to_string()]));
+
+        let triples: Vec<&Vec<String>> = combos.triples.iter().map(|c| &c.units).collect();
+        assert_eq!(triples.len(), 2);
+        assert!(triples.contains(&&vec![
+            "Farseer".to_string(),
+            "Wave Serpent".to_string(),
+            "Wraithguard".to_string()
+        ]));
+    }
+}
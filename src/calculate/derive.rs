@@ -0,0 +1,1208 @@
+//! Derived analytics pipeline.
+//!
+//! Computes datasets from normalized JSONL and persists them under
+//! `derived/{derivation}/epoch={epoch_id}/{derivation}.json`, so consumers
+//! can read precomputed results instead of re-aggregating on every request.
+//! Each artifact records a hash of its inputs' file stamps; unchanged
+//! inputs are skipped unless a recompute is forced. Like the analytics
+//! routes, artifacts cover 2000-point games only; the loaders compute other
+//! game sizes on the fly.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::models::normalize_faction_name;
+use crate::models::{
+    ArmyList, DateRange, DetachmentStats, EpochTotals, Event, FactionStat, FactionStats, Pairing,
+    Placement, Tier,
+};
+use crate::storage::store;
+use crate::storage::{
+    EntityType, IndexedReader, Overrides, StorageBackend, StorageConfig, StorageError,
+};
+
+use super::aggregate_placements;
+use super::archetypes::{all_archetypes, FactionArchetypes};
+use super::combos::{all_combos, FactionCombos};
+use super::matchups::faction_matchups;
+use super::tiers::{compute_tiers, FactionTier};
+use super::unit_performance::{all_unit_performance, UnitPerformance};
+use super::validation::DEFAULT_GAME_SIZE;
+
+/// Points limit of the games persisted artifacts cover.
+pub const DERIVED_GAME_SIZE: Option<u32> = Some(DEFAULT_GAME_SIZE);
+
+/// Minimum games for a faction to be placed on the derived tier list.
+pub const TIER_LIST_MIN_GAMES: u32 = 20;
+
+/// Smallest event (in players) included in placement curves.
+const PLACEMENT_CURVE_MIN_EVENT_SIZE: u32 = 10;
+
+/// Percentiles reported on each placement curve.
+const PLACEMENT_CURVE_PERCENTILES: [u32; 5] = [10, 25, 50, 75, 90];
+
+/// Errors that can occur while deriving analytics.
+#[derive(Debug, Error)]
+pub enum DeriveError {
+    #[error("Unknown derivation: {0}")]
+    UnknownDerivation(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// A derived dataset that can be computed for an epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Derivation {
+    FactionStats,
+    UnitFrequency,
+    Matchups,
+    /// Factions bucketed by the win-rate tier in their faction stats. Not
+    /// served by the API
+    TierList,
+    PlacementCurves,
+    Archetypes,
+    /// The composite score tier list (see `calculate::tiers`) served by
+    /// `/api/analytics/tiers` and the meta snapshot
+    CompositeTiers,
+    Combos,
+    UnitPerformance,
+}
+
+impl Derivation {
+    /// All derivations, in dependency-free run order.
+    pub fn all() -> Vec<Derivation> {
+        vec![
+            Derivation::FactionStats,
+            Derivation::UnitFrequency,
+            Derivation::Matchups,
+            Derivation::TierList,
+            Derivation::PlacementCurves,
+            Derivation::Archetypes,
+            Derivation::CompositeTiers,
+            Derivation::Combos,
+            Derivation::UnitPerformance,
+        ]
+    }
+
+    /// Directory/file name for this derivation.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Derivation::FactionStats => "faction_stats",
+            Derivation::UnitFrequency => "unit_frequency",
+            Derivation::Matchups => "matchups",
+            Derivation::TierList => "tier_list",
+            Derivation::PlacementCurves => "placement_curves",
+            Derivation::Archetypes => "archetypes",
+            Derivation::CompositeTiers => "composite_tiers",
+            Derivation::Combos => "combos",
+            Derivation::UnitPerformance => "unit_performance",
+        }
+    }
+
+    /// Parse a comma-separated `--run` value. `None` selects all derivations.
+    pub fn parse_list(spec: Option<&str>) -> Result<Vec<Derivation>, DeriveError> {
+        let Some(spec) = spec else {
+            return Ok(Self::all());
+        };
+        let mut out = Vec::new();
+        for name in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let d = name.parse::<Derivation>()?;
+            if !out.contains(&d) {
+                out.push(d);
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl std::str::FromStr for Derivation {
+    type Err = DeriveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "faction_stats" => Ok(Derivation::FactionStats),
+            "unit_frequency" => Ok(Derivation::UnitFrequency),
+            "matchups" => Ok(Derivation::Matchups),
+            "tier_list" => Ok(Derivation::TierList),
+            "placement_curves" => Ok(Derivation::PlacementCurves),
+            "archetypes" => Ok(Derivation::Archetypes),
+            "composite_tiers" => Ok(Derivation::CompositeTiers),
+            "combos" => Ok(Derivation::Combos),
+            "unit_performance" => Ok(Derivation::UnitPerformance),
+            _ => Err(DeriveError::UnknownDerivation(s.to_string())),
+        }
+    }
+}
+
+/// A persisted derived dataset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivedArtifact<T> {
+    pub derivation: String,
+    pub epoch_id: String,
+    pub computed_at: DateTime<Utc>,
+    /// Hash of the normalized inputs this artifact was computed from
+    pub source_hash: String,
+    pub data: T,
+}
+
+/// Unit frequency within a faction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnitFrequency {
+    pub faction: String,
+    pub unit: String,
+    pub lists_containing: u32,
+    /// Share of the faction's lists containing this unit (0.0 to 1.0)
+    pub list_share: f64,
+    pub avg_points: Option<u32>,
+}
+
+/// Head-to-head record of one faction against another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchupCell {
+    pub faction: String,
+    pub opponent: String,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub games: u32,
+    pub win_rate: f64,
+}
+
+/// Factions grouped into one tier.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TierEntry {
+    pub tier: Tier,
+    pub factions: Vec<String>,
+}
+
+/// Finishing position at one percentile of a faction's placements.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurvePoint {
+    pub percentile: u32,
+    /// Normalized finish (0.0 = winner, 1.0 = last place)
+    pub finish: f64,
+}
+
+/// Distribution of a faction's finishing positions, normalized by event size.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlacementCurve {
+    pub faction: String,
+    pub placements: u32,
+    pub events: u32,
+    pub median_finish: f64,
+    pub mean_finish: f64,
+    /// Share of placements finishing in the top 10% of their event
+    pub top_10_conversion: f64,
+    pub curve: Vec<CurvePoint>,
+    /// Placement counts per decile of the field (index 0 = top 10%)
+    pub deciles: Vec<u32>,
+}
+
+/// Outcome of running one derivation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeriveOutcome {
+    Computed {
+        path: PathBuf,
+    },
+    /// Inputs unchanged since the last run
+    Skipped,
+}
+
+/// Path of a derived artifact.
+pub fn derived_path(storage: &StorageConfig, derivation: Derivation, epoch_id: &str) -> PathBuf {
+    storage
+        .derived_dir()
+        .join(derivation.name())
+        .join(format!("epoch={}", epoch_id))
+        .join(format!("{}.json", derivation.name()))
+}
+
+/// Read a previously persisted artifact, if any.
+pub fn read_derived<T: DeserializeOwned>(
+    storage: &StorageConfig,
+    derivation: Derivation,
+    epoch_id: &str,
+) -> Result<Option<DerivedArtifact<T>>, StorageError> {
+    let path = derived_path(storage, derivation, epoch_id);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)?;
+    Ok(Some(serde_json::from_str(&content)?))
+}
+
+/// Normalized inputs for one epoch.
+struct EpochInputs {
+    events: Vec<Event>,
+    placements: Vec<Placement>,
+    lists: Vec<ArmyList>,
+    pairings: Vec<Pairing>,
+}
+
+/// Entity files the derivations read.
+const INPUT_ENTITIES: [EntityType; 4] = [
+    EntityType::Event,
+    EntityType::Placement,
+    EntityType::ArmyList,
+    EntityType::Pairing,
+];
+
+/// Hash of what an epoch's artifacts are computed from: each input file's
+/// modification time and size (each key's stamp in other store backends),
+/// the local overrides and the game size. Cheap enough to check on every
+/// read; the inputs themselves are only loaded when an artifact is stale.
+fn source_hash(
+    storage: &StorageConfig,
+    epoch_id: &str,
+    game_size: Option<u32>,
+) -> Result<String, StorageError> {
+    let store = match storage.backend {
+        StorageBackend::Jsonl => None,
+        backend => Some(store::open(storage, backend)?),
+    };
+    let mut hasher = Sha256::new();
+    for entity in INPUT_ENTITIES {
+        hasher.update(entity.filename().as_bytes());
+        match &store {
+            // Database writes don't touch the JSONL files
+            Some(store) => {
+                for key in store::entity_keys(store.as_ref(), entity, epoch_id)? {
+                    hasher.update(key.as_bytes());
+                    hasher.update(store.stamp(&key)?.as_bytes());
+                }
+            }
+            None => {
+                let root = storage.normalized_dir();
+                for path in crate::storage::jsonl::entity_paths(storage, entity, epoch_id) {
+                    let meta = std::fs::metadata(&path)?;
+                    let modified = meta
+                        .modified()?
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap_or_default();
+                    let relative = path.strip_prefix(&root).unwrap_or(&path);
+                    hasher.update(relative.to_string_lossy().as_bytes());
+                    hasher.update(format!("{}:{}", modified.as_nanos(), meta.len()).as_bytes());
+                }
+            }
+        }
+    }
+    // Derived results follow the local overrides and the game size too
+    if *storage.overrides != Overrides::default() {
+        hasher.update(serde_json::to_vec(&*storage.overrides)?);
+    }
+    hasher.update(format!("game_size={:?}", game_size).as_bytes());
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Inputs for an epoch, limited to games at `game_size` points (events
+/// that don't say count as 2000) unless it is `None`. Read through the
+/// entity index cache, so unchanged files aren't parsed again.
+fn load_inputs(
+    storage: &StorageConfig,
+    epoch_id: &str,
+    game_size: Option<u32>,
+) -> Result<EpochInputs, StorageError> {
+    let mut inputs = EpochInputs {
+        events: IndexedReader::<Event>::for_entity(storage, EntityType::Event, epoch_id)
+            .read_all()?,
+        placements: IndexedReader::<Placement>::for_entity(
+            storage,
+            EntityType::Placement,
+            epoch_id,
+        )
+        .read_all()?,
+        lists: IndexedReader::<ArmyList>::for_entity(storage, EntityType::ArmyList, epoch_id)
+            .read_all()?,
+        pairings: IndexedReader::<Pairing>::for_entity(storage, EntityType::Pairing, epoch_id)
+            .read_all()?,
+    };
+    let overrides = &storage.overrides;
+    overrides.retain(&mut inputs.events);
+    overrides.retain(&mut inputs.placements);
+    overrides.retain(&mut inputs.lists);
+    overrides.retain(&mut inputs.pairings);
+
+    if let Some(points) = game_size {
+        let off_size: HashSet<String> = inputs
+            .events
+            .iter()
+            .filter(|e| e.game_size.unwrap_or(DEFAULT_GAME_SIZE) != points)
+            .map(|e| e.id.as_str().to_string())
+            .collect();
+        if !off_size.is_empty() {
+            inputs.events.retain(|e| !off_size.contains(e.id.as_str()));
+            inputs
+                .placements
+                .retain(|p| !off_size.contains(p.event_id.as_str()));
+            inputs
+                .pairings
+                .retain(|p| !off_size.contains(p.event_id.as_str()));
+            inputs.lists.retain(|l| {
+                l.event_id
+                    .as_ref()
+                    .is_none_or(|e| !off_size.contains(e.as_str()))
+            });
+        }
+    }
+    Ok(inputs)
+}
+
+/// Run the selected derivations for an epoch.
+///
+/// Returns the outcome of each derivation in the order requested.
+pub fn run_derivations(
+    storage: &StorageConfig,
+    epoch_id: &str,
+    derivations: &[Derivation],
+    force: bool,
+) -> Result<Vec<(Derivation, DeriveOutcome)>, DeriveError> {
+    let source_hash = source_hash(storage, epoch_id, DERIVED_GAME_SIZE)?;
+    // Read only if some artifact is stale
+    let mut loaded = None;
+    let mut results = Vec::new();
+
+    for &derivation in derivations {
+        if !force {
+            let existing: Option<DerivedArtifact<serde_json::Value>> =
+                read_derived(storage, derivation, epoch_id).unwrap_or(None);
+            if existing.is_some_and(|a| a.source_hash == source_hash) {
+                results.push((derivation, DeriveOutcome::Skipped));
+                continue;
+            }
+        }
+        let inputs = match &mut loaded {
+            Some(inputs) => &*inputs,
+            slot => slot.insert(load_inputs(storage, epoch_id, DERIVED_GAME_SIZE)?),
+        };
+
+        let data = match derivation {
+            Derivation::FactionStats => {
+                serde_json::to_value(compute_faction_stats(epoch_id, inputs))
+            }
+            Derivation::UnitFrequency => {
+                serde_json::to_value(compute_unit_frequency(&inputs.lists))
+            }
+            Derivation::Matchups => serde_json::to_value(compute_matchups(
+                &inputs.pairings,
+                &inputs.placements,
+                &inputs.lists,
+            )),
+            Derivation::TierList => {
+                serde_json::to_value(compute_tier_list(&compute_faction_stats(epoch_id, inputs)))
+            }
+            Derivation::PlacementCurves => {
+                serde_json::to_value(compute_placement_curves(&inputs.events, &inputs.placements))
+            }
+            Derivation::Archetypes => serde_json::to_value(all_archetypes(
+                &inputs.placements,
+                &inputs.lists,
+                &inputs.pairings,
+            )),
+            Derivation::CompositeTiers => {
+                serde_json::to_value(compute_tiers(&inputs.placements, &inputs.pairings))
+            }
+            Derivation::Combos => {
+                serde_json::to_value(all_combos(&inputs.placements, &inputs.lists))
+            }
+            Derivation::UnitPerformance => serde_json::to_value(all_unit_performance(
+                &inputs.placements,
+                &inputs.lists,
+                &inputs.pairings,
+            )),
+        }
+        .map_err(StorageError::from)?;
+
+        let artifact = DerivedArtifact {
+            derivation: derivation.name().to_string(),
+            epoch_id: epoch_id.to_string(),
+            computed_at: Utc::now(),
+            source_hash: source_hash.clone(),
+            data,
+        };
+
+        let path = derived_path(storage, derivation, epoch_id);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(StorageError::from)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(
+            &tmp,
+            serde_json::to_string_pretty(&artifact).map_err(StorageError::from)?,
+        )
+        .map_err(StorageError::from)?;
+        std::fs::rename(&tmp, &path).map_err(StorageError::from)?;
+
+        results.push((derivation, DeriveOutcome::Computed { path }));
+    }
+
+    Ok(results)
+}
+
+/// Faction stats for an epoch: the persisted artifact if it was computed
+/// from the current inputs, otherwise computed on the fly (not persisted).
+pub fn load_faction_stats(
+    storage: &StorageConfig,
+    epoch_id: &str,
+    game_size: Option<u32>,
+) -> Result<FactionStats, StorageError> {
+    let source_hash = source_hash(storage, epoch_id, game_size)?;
+    if let Some(data) = current_artifact(storage, Derivation::FactionStats, epoch_id, &source_hash)
+    {
+        return Ok(data);
+    }
+    let inputs = load_inputs(storage, epoch_id, game_size)?;
+    Ok(compute_faction_stats(epoch_id, &inputs))
+}
+
+/// Archetypes for an epoch: the persisted artifact if it was computed from
+/// the current inputs, otherwise clustered on the fly (not persisted).
+pub fn load_archetypes(
+    storage: &StorageConfig,
+    epoch_id: &str,
+    game_size: Option<u32>,
+) -> Result<Vec<FactionArchetypes>, StorageError> {
+    let source_hash = source_hash(storage, epoch_id, game_size)?;
+    if let Some(data) = current_artifact(storage, Derivation::Archetypes, epoch_id, &source_hash) {
+        return Ok(data);
+    }
+    let inputs = load_inputs(storage, epoch_id, game_size)?;
+    Ok(all_archetypes(
+        &inputs.placements,
+        &inputs.lists,
+        &inputs.pairings,
+    ))
+}
+
+/// Unit combos for an epoch: the persisted artifact if it was computed from
+/// the current inputs, otherwise mined on the fly (not persisted).
+pub fn load_combos(
+    storage: &StorageConfig,
+    epoch_id: &str,
+    game_size: Option<u32>,
+) -> Result<Vec<FactionCombos>, StorageError> {
+    let source_hash = source_hash(storage, epoch_id, game_size)?;
+    if let Some(data) = current_artifact(storage, Derivation::Combos, epoch_id, &source_hash) {
+        return Ok(data);
+    }
+    let inputs = load_inputs(storage, epoch_id, game_size)?;
+    Ok(all_combos(&inputs.placements, &inputs.lists))
+}
+
+/// Unit game results for an epoch: the persisted artifact if it was
+/// computed from the current inputs, otherwise computed on the fly (not
+/// persisted).
+pub fn load_unit_performance(
+    storage: &StorageConfig,
+    epoch_id: &str,
+    game_size: Option<u32>,
+) -> Result<Vec<UnitPerformance>, StorageError> {
+    let source_hash = source_hash(storage, epoch_id, game_size)?;
+    if let Some(data) =
+        current_artifact(storage, Derivation::UnitPerformance, epoch_id, &source_hash)
+    {
+        return Ok(data);
+    }
+    let inputs = load_inputs(storage, epoch_id, game_size)?;
+    Ok(all_unit_performance(
+        &inputs.placements,
+        &inputs.lists,
+        &inputs.pairings,
+    ))
+}
+
+/// An epoch's derived datasets, with the events and placements they came
+/// from.
+#[derive(Debug, Clone)]
+pub struct EpochDerived {
+    pub faction_stats: FactionStats,
+    pub tiers: Vec<FactionTier>,
+    pub unit_frequency: Vec<UnitFrequency>,
+    pub events: Vec<Event>,
+    pub placements: Vec<Placement>,
+}
+
+/// Faction stats, tiers and unit frequency for an epoch in one pass: the
+/// inputs are stamped and read once, and each persisted artifact is used
+/// when it was computed from them, otherwise it is computed on the fly
+/// (not persisted).
+pub fn load_epoch_derived(
+    storage: &StorageConfig,
+    epoch_id: &str,
+    game_size: Option<u32>,
+) -> Result<EpochDerived, StorageError> {
+    let source_hash = source_hash(storage, epoch_id, game_size)?;
+    let inputs = load_inputs(storage, epoch_id, game_size)?;
+    let faction_stats = current_artifact(storage, Derivation::FactionStats, epoch_id, &source_hash)
+        .unwrap_or_else(|| compute_faction_stats(epoch_id, &inputs));
+    let tiers = current_artifact(storage, Derivation::CompositeTiers, epoch_id, &source_hash)
+        .unwrap_or_else(|| compute_tiers(&inputs.placements, &inputs.pairings));
+    let unit_frequency =
+        current_artifact(storage, Derivation::UnitFrequency, epoch_id, &source_hash)
+            .unwrap_or_else(|| compute_unit_frequency(&inputs.lists));
+    Ok(EpochDerived {
+        faction_stats,
+        tiers,
+        unit_frequency,
+        events: inputs.events,
+        placements: inputs.placements,
+    })
+}
+
+/// A persisted artifact's data, if it was computed from the inputs
+/// `source_hash` describes.
+fn current_artifact<T: DeserializeOwned>(
+    storage: &StorageConfig,
+    derivation: Derivation,
+    epoch_id: &str,
+    source_hash: &str,
+) -> Option<T> {
+    let artifact: DerivedArtifact<T> = read_derived(storage, derivation, epoch_id).ok()??;
+    (artifact.source_hash == source_hash).then_some(artifact.data)
+}
+
+/// Composite tiers for an epoch. Unlike the other loaders this persists a
+/// fresh artifact when the stored one is missing or stale, so repeated
+/// reads return the same list until the inputs change. Other game sizes
+/// than the persisted one are computed on the fly.
+pub fn load_tiers(
+    storage: &StorageConfig,
+    epoch_id: &str,
+    game_size: Option<u32>,
+) -> Result<DerivedArtifact<Vec<FactionTier>>, DeriveError> {
+    if game_size != DERIVED_GAME_SIZE {
+        let inputs = load_inputs(storage, epoch_id, game_size)?;
+        return Ok(DerivedArtifact {
+            derivation: Derivation::CompositeTiers.name().to_string(),
+            epoch_id: epoch_id.to_string(),
+            computed_at: Utc::now(),
+            data: compute_tiers(&inputs.placements, &inputs.pairings),
+            source_hash: source_hash(storage, epoch_id, game_size)?,
+        });
+    }
+    run_derivations(storage, epoch_id, &[Derivation::CompositeTiers], false)?;
+    read_derived(storage, Derivation::CompositeTiers, epoch_id)?.ok_or_else(|| {
+        StorageError::PathNotFound(derived_path(storage, Derivation::CompositeTiers, epoch_id))
+            .into()
+    })
+}
+
+fn compute_faction_stats(epoch_id: &str, inputs: &EpochInputs) -> FactionStats {
+    let event_players: HashMap<&str, u32> = {
+        let mut max_rank: HashMap<&str, u32> = HashMap::new();
+        for p in &inputs.placements {
+            let entry = max_rank.entry(p.event_id.as_str()).or_default();
+            *entry = (*entry).max(p.rank);
+        }
+        inputs
+            .events
+            .iter()
+            .map(|e| {
+                let id = e.id.as_str();
+                let count = e
+                    .player_count
+                    .unwrap_or_else(|| max_rank.get(id).copied().unwrap_or(0));
+                (id, count)
+            })
+            .collect()
+    };
+
+    let mut by_faction: HashMap<String, Vec<&Placement>> = HashMap::new();
+    for p in &inputs.placements {
+        by_faction
+            .entry(normalize_faction_name(&p.faction))
+            .or_default()
+            .push(p);
+    }
+
+    let total_players = inputs.placements.len() as u32;
+    let total_top_4 = inputs.placements.iter().filter(|p| p.rank <= 4).count() as u32;
+    let total_games: u32 = inputs
+        .placements
+        .iter()
+        .filter_map(|p| p.record.as_ref())
+        .map(|r| r.total_games())
+        .sum();
+
+    let mut factions: Vec<FactionStat> = by_faction
+        .into_iter()
+        .map(|(name, ps)| {
+            let ranks: Vec<u32> = ps.iter().map(|p| p.rank).collect();
+            let totals: Vec<u32> = ps
+                .iter()
+                .map(|p| event_players.get(p.event_id.as_str()).copied().unwrap_or(0))
+                .collect();
+            let counts = aggregate_placements(&ranks, &totals);
+
+            let (mut wins, mut losses, mut draws) = (0, 0, 0);
+            for r in ps.iter().filter_map(|p| p.record.as_ref()) {
+                wins += r.wins;
+                losses += r.losses;
+                draws += r.draws;
+            }
+            let events: HashSet<&str> = ps.iter().map(|p| p.event_id.as_str()).collect();
+
+            let mut stat = FactionStat::new(
+                name,
+                ps.len() as u32,
+                wins + losses + draws,
+                events.len() as u32,
+                wins,
+                losses,
+                draws,
+                counts,
+                total_players,
+                total_top_4,
+            );
+
+            let percentiles: Vec<f64> = ranks
+                .iter()
+                .zip(&totals)
+                .filter(|(_, t)| **t > 0)
+                .map(|(r, t)| 1.0 - (*r as f64 - 1.0) / *t as f64)
+                .collect();
+            if !percentiles.is_empty() {
+                stat.average_placement_percentile =
+                    percentiles.iter().sum::<f64>() / percentiles.len() as f64;
+            }
+            stat.four_zero_starts = ps
+                .iter()
+                .filter_map(|p| p.record.as_ref())
+                .filter(|r| r.wins >= 4 && r.losses == 0)
+                .count() as u32;
+            stat.five_zero_starts = ps
+                .iter()
+                .filter_map(|p| p.record.as_ref())
+                .filter(|r| r.wins >= 5 && r.losses == 0)
+                .count() as u32;
+
+            let mut detachments: HashMap<String, (u32, u32, u32)> = HashMap::new();
+            for p in &ps {
+                let Some(det) = p.detachment.as_deref().filter(|d| !d.is_empty()) else {
+                    continue;
+                };
+                let entry = detachments.entry(det.to_string()).or_default();
+                entry.0 += 1;
+                if let Some(r) = &p.record {
+                    entry.1 += r.wins;
+                    entry.2 += r.total_games();
+                }
+            }
+            let mut top_detachments: Vec<DetachmentStats> = detachments
+                .into_iter()
+                .map(|(name, (count, w, g))| DetachmentStats {
+                    name,
+                    count,
+                    win_rate: if g > 0 { w as f64 / g as f64 } else { 0.0 },
+                })
+                .collect();
+            top_detachments.sort_by(|a, b| b.count.cmp(&a.count).then(a.name.cmp(&b.name)));
+            top_detachments.truncate(5);
+            stat.top_detachments = top_detachments;
+
+            stat
+        })
+        .collect();
+
+    factions.sort_by(|a, b| {
+        b.player_count
+            .cmp(&a.player_count)
+            .then(a.name.cmp(&b.name))
+    });
+
+    let today = Utc::now().date_naive();
+    let date_range = DateRange {
+        from: inputs.events.iter().map(|e| e.date).min().unwrap_or(today),
+        to: inputs.events.iter().map(|e| e.date).max().unwrap_or(today),
+    };
+    let totals = EpochTotals {
+        events: inputs.events.len() as u32,
+        players: total_players,
+        games: total_games / 2,
+    };
+
+    FactionStats::new(
+        epoch_id.into(),
+        epoch_id.to_string(),
+        date_range,
+        totals,
+        factions,
+    )
+}
+
+fn compute_unit_frequency(lists: &[ArmyList]) -> Vec<UnitFrequency> {
+    // faction -> unit -> (lists containing, points total, points samples)
+    let mut agg: HashMap<String, HashMap<String, (u32, u64, u32)>> = HashMap::new();
+    let mut faction_lists: HashMap<String, u32> = HashMap::new();
+
+    for list in lists.iter().filter(|l| !l.units.is_empty()) {
+        let faction = normalize_faction_name(&list.faction);
+        *faction_lists.entry(faction.clone()).or_default() += 1;
+        let units = agg.entry(faction).or_default();
+        let mut seen = HashSet::new();
+        for unit in &list.units {
+            let entry = units.entry(unit.name.clone()).or_default();
+            if seen.insert(unit.name.as_str()) {
+                entry.0 += 1;
+            }
+            if let Some(pts) = unit.points.filter(|p| *p > 0) {
+                entry.1 += pts as u64;
+                entry.2 += 1;
+            }
+        }
+    }
+
+    let mut out: Vec<UnitFrequency> = agg
+        .into_iter()
+        .flat_map(|(faction, units)| {
+            let total = faction_lists.get(&faction).copied().unwrap_or(0);
+            units
+                .into_iter()
+                .map(move |(unit, (count, pts, samples))| UnitFrequency {
+                    faction: faction.clone(),
+                    unit,
+                    lists_containing: count,
+                    list_share: if total > 0 {
+                        count as f64 / total as f64
+                    } else {
+                        0.0
+                    },
+                    avg_points: (samples > 0).then(|| (pts / samples as u64) as u32),
+                })
+        })
+        .collect();
+
+    out.sort_by(|a, b| {
+        a.faction
+            .cmp(&b.faction)
+            .then(b.lists_containing.cmp(&a.lists_containing))
+            .then(a.unit.cmp(&b.unit))
+    });
+    out
+}
+
+fn compute_matchups(
+    pairings: &[Pairing],
+    placements: &[Placement],
+    lists: &[ArmyList],
+) -> Vec<MatchupCell> {
+    // Already sorted by faction, then opponent
+    faction_matchups(pairings, placements, lists)
+        .into_iter()
+        .map(|((faction, opponent), record)| {
+            let games = record.games();
+            MatchupCell {
+                faction,
+                opponent,
+                wins: record.wins,
+                losses: record.losses,
+                draws: record.draws,
+                games,
+                win_rate: if games > 0 {
+                    (record.wins as f64 + 0.5 * record.draws as f64) / games as f64
+                } else {
+                    0.0
+                },
+            }
+        })
+        .collect()
+}
+
+fn compute_tier_list(stats: &FactionStats) -> Vec<TierEntry> {
+    [Tier::S, Tier::A, Tier::B, Tier::C, Tier::D]
+        .into_iter()
+        .map(|tier| {
+            let mut factions: Vec<&FactionStat> = stats
+                .factions
+                .iter()
+                .filter(|f| f.tier == tier && f.games_played >= TIER_LIST_MIN_GAMES)
+                .collect();
+            factions.sort_by(|a, b| {
+                b.win_rate
+                    .partial_cmp(&a.win_rate)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            TierEntry {
+                tier,
+                factions: factions.into_iter().map(|f| f.name.clone()).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Linearly interpolated percentile of sorted values.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let pos = pct / 100.0 * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (pos - lower as f64)
+}
+
+fn round3(v: f64) -> f64 {
+    (v * 1000.0).round() / 1000.0
+}
+
+/// Compute per-faction placement curves.
+///
+/// Each finish is normalized as `(rank - 1) / (event_size - 1)`, so 0.0 is
+/// the winner and 1.0 is last place regardless of event size. Events smaller
+/// than `PLACEMENT_CURVE_MIN_EVENT_SIZE`, or with less than half their field
+/// recorded (top-only sources), are excluded to avoid survivorship bias.
+pub fn compute_placement_curves(events: &[Event], placements: &[Placement]) -> Vec<PlacementCurve> {
+    let mut recorded: HashMap<&str, (u32, u32)> = HashMap::new();
+    for p in placements {
+        let entry = recorded.entry(p.event_id.as_str()).or_default();
+        entry.0 += 1;
+        entry.1 = entry.1.max(p.rank);
+    }
+
+    let declared: HashMap<&str, u32> = events
+        .iter()
+        .filter_map(|e| e.player_count.map(|c| (e.id.as_str(), c)))
+        .collect();
+
+    let event_sizes: HashMap<&str, u32> = recorded
+        .iter()
+        .filter_map(|(&event_id, &(count, max_rank))| {
+            let size = declared.get(event_id).copied().unwrap_or(0).max(max_rank);
+            (size >= PLACEMENT_CURVE_MIN_EVENT_SIZE && count * 2 >= size)
+                .then_some((event_id, size))
+        })
+        .collect();
+
+    struct CurveAgg<'a> {
+        finishes: Vec<f64>,
+        top_10: u32,
+        events: HashSet<&'a str>,
+    }
+
+    let mut by_faction: HashMap<String, CurveAgg> = HashMap::new();
+    for p in placements {
+        let Some(&size) = event_sizes.get(p.event_id.as_str()) else {
+            continue;
+        };
+        if p.rank == 0 {
+            continue;
+        }
+        let agg = by_faction
+            .entry(normalize_faction_name(&p.faction))
+            .or_insert_with(|| CurveAgg {
+                finishes: Vec::new(),
+                top_10: 0,
+                events: HashSet::new(),
+            });
+        let finish = (p.rank.min(size) - 1) as f64 / (size - 1) as f64;
+        agg.finishes.push(finish);
+        let top_cut = ((size as f64) * 0.1).ceil().max(1.0) as u32;
+        if p.rank <= top_cut {
+            agg.top_10 += 1;
+        }
+        agg.events.insert(p.event_id.as_str());
+    }
+
+    let mut out: Vec<PlacementCurve> = by_faction
+        .into_iter()
+        .map(|(faction, mut agg)| {
+            agg.finishes
+                .sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            let n = agg.finishes.len();
+            let mut deciles = vec![0u32; 10];
+            for &f in &agg.finishes {
+                deciles[((f * 10.0) as usize).min(9)] += 1;
+            }
+            PlacementCurve {
+                faction,
+                placements: n as u32,
+                events: agg.events.len() as u32,
+                median_finish: round3(percentile(&agg.finishes, 50.0)),
+                mean_finish: round3(agg.finishes.iter().sum::<f64>() / n as f64),
+                top_10_conversion: round3(agg.top_10 as f64 / n as f64),
+                curve: PLACEMENT_CURVE_PERCENTILES
+                    .iter()
+                    .map(|&pct| CurvePoint {
+                        percentile: pct,
+                        finish: round3(percentile(&agg.finishes, pct as f64)),
+                    })
+                    .collect(),
+                deciles,
+            }
+        })
+        .collect();
+
+    out.sort_by(|a, b| {
+        a.median_finish
+            .partial_cmp(&b.median_finish)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.faction.cmp(&b.faction))
+    });
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::WinLossRecord;
+    use crate::storage::JsonlWriter;
+    use tempfile::TempDir;
+
+    fn seed(storage: &StorageConfig) {
+        let event = Event::new(
+            "Test GT".to_string(),
+            chrono::NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            "https://example.com".to_string(),
+            "test".to_string(),
+            "current".into(),
+        )
+        .with_player_count(4);
+
+        let mut placements = Vec::new();
+        for (rank, faction, w, l) in [
+            (1, "Aeldari", 5, 0),
+            (2, "Orks", 4, 1),
+            (3, "Aeldari", 3, 2),
+            (4, "Necrons", 0, 5),
+        ] {
+            let mut p = Placement::new(
+                event.id.clone(),
+                "current".into(),
+                rank,
+                format!("Player {}", rank),
+                faction.to_string(),
+            );
+            p.record = Some(WinLossRecord::new(w, l, 0));
+            placements.push(p);
+        }
+
+        JsonlWriter::for_entity(storage, EntityType::Event, "current")
+            .write_all(&[event])
+            .unwrap();
+        JsonlWriter::for_entity(storage, EntityType::Placement, "current")
+            .write_all(&placements)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_parse_derivation_list() {
+        assert_eq!(Derivation::parse_list(None).unwrap().len(), 9);
+        assert_eq!(
+            Derivation::parse_list(Some("matchups, tier-list,matchups")).unwrap(),
+            vec![Derivation::Matchups, Derivation::TierList]
+        );
+        assert!(matches!(
+            Derivation::parse_list(Some("bogus")),
+            Err(DeriveError::UnknownDerivation(_))
+        ));
+    }
+
+    #[test]
+    fn test_run_derivations_persists_and_skips() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = StorageConfig::new(temp_dir.path().to_path_buf());
+        seed(&storage);
+
+        let results = run_derivations(&storage, "current", &Derivation::all(), false).unwrap();
+        assert!(results
+            .iter()
+            .all(|(_, o)| matches!(o, DeriveOutcome::Computed { .. })));
+
+        let stats: DerivedArtifact<FactionStats> =
+            read_derived(&storage, Derivation::FactionStats, "current")
+                .unwrap()
+                .unwrap();
+        let aeldari = stats.data.get_faction("Aeldari").unwrap();
+        assert_eq!(aeldari.player_count, 2);
+        assert_eq!(aeldari.placement_counts.first, 1);
+        assert_eq!(aeldari.five_zero_starts, 1);
+
+        // Unchanged inputs are skipped, unless forced
+        let again = run_derivations(&storage, "current", &[Derivation::Matchups], false).unwrap();
+        assert_eq!(again[0].1, DeriveOutcome::Skipped);
+        let forced = run_derivations(&storage, "current", &[Derivation::Matchups], true).unwrap();
+        assert!(matches!(forced[0].1, DeriveOutcome::Computed { .. }));
+
+        // Appending to an input file changes its stamp
+        let placement = Placement::new(
+            "late-event".into(),
+            "current".into(),
+            1,
+            "Player 9".to_string(),
+            "Orks".to_string(),
+        );
+        JsonlWriter::for_entity(&storage, EntityType::Placement, "current")
+            .append(&placement)
+            .unwrap();
+        let stale = run_derivations(&storage, "current", &[Derivation::Matchups], false).unwrap();
+        assert!(matches!(stale[0].1, DeriveOutcome::Computed { .. }));
+        let stats = load_faction_stats(&storage, "current", DERIVED_GAME_SIZE).unwrap();
+        assert_eq!(stats.get_faction("Orks").unwrap().player_count, 2);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_run_derivations_sees_sqlite_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage =
+            StorageConfig::new(temp_dir.path().to_path_buf()).with_backend(StorageBackend::Sqlite);
+        seed(&storage);
+
+        let run = || run_derivations(&storage, "current", &[Derivation::FactionStats], false);
+        assert!(matches!(
+            run().unwrap()[0].1,
+            DeriveOutcome::Computed { .. }
+        ));
+        assert_eq!(run().unwrap()[0].1, DeriveOutcome::Skipped);
+
+        let placement = Placement::new(
+            "late-event".into(),
+            "current".into(),
+            1,
+            "Player 9".to_string(),
+            "Orks".to_string(),
+        );
+        store::open(&storage, StorageBackend::Sqlite)
+            .unwrap()
+            .append_lines(
+                "current/placements.jsonl",
+                &[serde_json::to_string(&placement).unwrap()],
+            )
+            .unwrap();
+        assert!(matches!(
+            run().unwrap()[0].1,
+            DeriveOutcome::Computed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_derived_stats_skip_other_game_sizes() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = StorageConfig::new(temp_dir.path().to_path_buf());
+        seed(&storage);
+        let incursion = Event::new(
+            "Incursion RTT".to_string(),
+            chrono::NaiveDate::from_ymd_opt(2025, 6, 8).unwrap(),
+            "https://example.com/incursion".to_string(),
+            "test".to_string(),
+            "current".into(),
+        )
+        .with_game_size(Some(1000));
+        let placement = Placement::new(
+            incursion.id.clone(),
+            "current".into(),
+            1,
+            "Player 5".to_string(),
+            "Orks".to_string(),
+        );
+        JsonlWriter::for_entity(&storage, EntityType::Event, "current")
+            .append(&incursion)
+            .unwrap();
+        JsonlWriter::for_entity(&storage, EntityType::Placement, "current")
+            .append(&placement)
+            .unwrap();
+
+        run_derivations(&storage, "current", &[Derivation::FactionStats], false).unwrap();
+        let stats = load_faction_stats(&storage, "current", DERIVED_GAME_SIZE).unwrap();
+        assert_eq!(stats.get_faction("Orks").unwrap().player_count, 1);
+
+        let incursion_stats = load_faction_stats(&storage, "current", Some(1000)).unwrap();
+        assert_eq!(incursion_stats.get_faction("Orks").unwrap().player_count, 1);
+        assert!(incursion_stats.get_faction("Aeldari").is_none());
+
+        let everything = load_faction_stats(&storage, "current", None).unwrap();
+        assert_eq!(everything.get_faction("Orks").unwrap().player_count, 2);
+    }
+
+    #[test]
+    fn test_compute_matchups_both_perspectives() {
+        let mut p = Pairing::new(
+            "event".into(),
+            "current".into(),
+            1,
+            "A".to_string(),
+            "B".to_string(),
+        );
+        p.player1_faction = Some("Aeldari".to_string());
+        p.player2_faction = Some("Orks".to_string());
+        p.player1_result = Some("win".to_string());
+
+        let cells = compute_matchups(&[p], &[], &[]);
+        assert_eq!(cells.len(), 2);
+        let aeldari = cells.iter().find(|c| c.faction == "Aeldari").unwrap();
+        assert_eq!((aeldari.wins, aeldari.losses), (1, 0));
+        let orks = cells.iter().find(|c| c.faction == "Orks").unwrap();
+        assert_eq!((orks.wins, orks.losses), (0, 1));
+    }
+
+    #[test]
+    fn test_compute_placement_curves() {
+        let date = chrono::NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+        let full = Event::new(
+            "Full GT".to_string(),
+            date,
+            "https://example.com/full".to_string(),
+            "test".to_string(),
+            "current".into(),
+        )
+        .with_player_count(11);
+        let top_only = Event::new(
+            "Top Cut Report".to_string(),
+            date,
+            "https://example.com/top".to_string(),
+            "test".to_string(),
+            "current".into(),
+        )
+        .with_player_count(100);
+
+        let mut placements = Vec::new();
+        for rank in 1..=11 {
+            let faction = if rank <= 2 || rank == 6 {
+                "Aeldari"
+            } else {
+                "Orks"
+            };
+            placements.push(Placement::new(
+                full.id.clone(),
+                "current".into(),
+                rank,
+                format!("Player {}", rank),
+                faction.to_string(),
+            ));
+        }
+        // Top-only standings are excluded entirely
+        placements.push(Placement::new(
+            top_only.id.clone(),
+            "current".into(),
+            1,
+            "Winner".to_string(),
+            "Necrons".to_string(),
+        ));
+
+        let curves = compute_placement_curves(&[full, top_only], &placements);
+        assert_eq!(curves.len(), 2);
+
+        let aeldari = &curves[0];
+        assert_eq!(aeldari.faction, "Aeldari");
+        assert_eq!(aeldari.placements, 3);
+        assert_eq!(aeldari.events, 1);
+        // Finishes 0.0, 0.1, 0.5
+        assert_eq!(aeldari.median_finish, 0.1);
+        // Top 10% of 11 players is the top 2
+        assert_eq!(aeldari.top_10_conversion, 0.667);
+        assert_eq!(aeldari.deciles[0], 1);
+        assert_eq!(aeldari.deciles[1], 1);
+        assert_eq!(aeldari.deciles[5], 1);
+        assert_eq!(aeldari.curve.len(), 5);
+
+        let orks = &curves[1];
+        assert_eq!(orks.top_10_conversion, 0.0);
+        assert_eq!(orks.curve.last().unwrap().finish, 0.93);
+    }
+}
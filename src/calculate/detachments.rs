@@ -0,0 +1,386 @@
+//! Detachment analytics from game-level results.
+//!
+//! Each player's detachment is resolved from their army list, falling back
+//! to the placement. Detachments are then scored on the games their players
+//! actually played (from pairings) rather than on averaged final records,
+//! so a 3-2 player who dropped after five rounds counts as five games, not
+//! one 60% data point.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::analytics::join_lists_to_placements;
+use crate::models::normalize_faction_name;
+use crate::models::{ArmyList, Pairing, Placement};
+use crate::sync::normalize_player_name;
+
+/// One player's result at one event, with their detachment resolved.
+#[derive(Debug, Clone)]
+pub struct DetachmentEntry {
+    pub epoch_id: String,
+    pub event_id: String,
+    pub player_name: String,
+    /// Normalized faction name
+    pub faction: String,
+    pub detachment: String,
+    pub rank: u32,
+    pub list_id: Option<String>,
+    pub total_points: Option<u32>,
+}
+
+/// Resolve detachments for placements. Placements joined to a list use the
+/// list's detachment (or the placement's, or "Unknown"); placements without
+/// a list are only included when they carry a detachment themselves.
+pub fn detachment_entries(placements: &[Placement], lists: &[ArmyList]) -> Vec<DetachmentEntry> {
+    let mut entries = Vec::new();
+    let mut seen: HashSet<&str> = HashSet::new();
+
+    let entry = |p: &Placement, list: Option<&ArmyList>, detachment: String| DetachmentEntry {
+        epoch_id: p.epoch_id.as_str().to_string(),
+        event_id: p.event_id.as_str().to_string(),
+        player_name: p.player_name.clone(),
+        faction: normalize_faction_name(&p.faction),
+        detachment,
+        rank: p.rank,
+        list_id: list.map(|l| l.id.as_str().to_string()),
+        total_points: list.map(|l| l.total_points),
+    };
+
+    let joined = join_lists_to_placements(lists, placements);
+    for (list, placement) in &joined {
+        let detachment = list
+            .detachment
+            .as_deref()
+            .or(placement.detachment.as_deref())
+            .unwrap_or("Unknown")
+            .to_string();
+        entries.push(entry(placement, Some(list), detachment));
+    }
+    seen.extend(joined.iter().map(|(_, p)| p.id.as_str()));
+
+    for placement in placements {
+        if seen.contains(placement.id.as_str()) {
+            continue;
+        }
+        if let Some(det) = placement.detachment.as_ref().filter(|d| !d.is_empty()) {
+            entries.push(entry(placement, None, det.clone()));
+        }
+    }
+    entries
+}
+
+/// Game-level wins, losses and draws.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+}
+
+impl GameRecord {
+    pub fn games(&self) -> u32 {
+        self.wins + self.losses + self.draws
+    }
+
+    /// Win rate in percent with draws counted as half a win, to one decimal.
+    pub fn win_rate(&self) -> Option<f64> {
+        let games = self.games();
+        if games == 0 {
+            return None;
+        }
+        let rate = (self.wins as f64 + self.draws as f64 * 0.5) / games as f64;
+        Some((rate * 1000.0).round() / 10.0)
+    }
+
+    pub fn add(&mut self, other: &GameRecord) {
+        self.wins += other.wins;
+        self.losses += other.losses;
+        self.draws += other.draws;
+    }
+}
+
+/// (epoch, faction, detachment)
+pub type DetachmentKey = (String, String, String);
+
+/// Game records per (epoch, faction, detachment) from pairings.
+///
+/// Each side of a pairing is credited to the detachment its player used at
+/// that event. Mirror games (same faction and detachment on both sides)
+/// are skipped since they always net to 50%.
+pub fn game_records(
+    entries: &[DetachmentEntry],
+    pairings: &[Pairing],
+) -> HashMap<DetachmentKey, GameRecord> {
+    let by_player: HashMap<(&str, String), &DetachmentEntry> = entries
+        .iter()
+        .map(|e| {
+            (
+                (e.event_id.as_str(), normalize_player_name(&e.player_name)),
+                e,
+            )
+        })
+        .collect();
+
+    let mut records: HashMap<DetachmentKey, GameRecord> = HashMap::new();
+    for pairing in pairings {
+        let (p1_won, p2_won) = match pairing.player1_result.as_deref() {
+            Some("win") => (Some(true), Some(false)),
+            Some("loss") => (Some(false), Some(true)),
+            Some("draw") => (None, None),
+            _ => continue,
+        };
+        let event_id = pairing.event_id.as_str();
+        let p1 = by_player.get(&(event_id, normalize_player_name(&pairing.player1_name)));
+        let p2 = by_player.get(&(event_id, normalize_player_name(&pairing.player2_name)));
+        if let (Some(a), Some(b)) = (p1, p2) {
+            if a.faction == b.faction && a.detachment == b.detachment {
+                continue;
+            }
+        }
+
+        for (entry, won) in [(p1, p1_won), (p2, p2_won)] {
+            let Some(entry) = entry else { continue };
+            let record = records
+                .entry((
+                    entry.epoch_id.clone(),
+                    entry.faction.clone(),
+                    entry.detachment.clone(),
+                ))
+                .or_default();
+            match won {
+                Some(true) => record.wins += 1,
+                Some(false) => record.losses += 1,
+                None => record.draws += 1,
+            }
+        }
+    }
+    records
+}
+
+/// A high-placing list for a detachment.
+#[derive(Debug, Clone, Serialize)]
+pub struct TopList {
+    pub list_id: String,
+    pub player_name: String,
+    pub event_id: String,
+    pub epoch_id: String,
+    pub rank: u32,
+    pub total_points: Option<u32>,
+}
+
+/// A detachment's numbers in one epoch.
+#[derive(Debug, Clone, Serialize)]
+pub struct DetachmentTrendPoint {
+    pub epoch_id: String,
+    pub players: u32,
+    /// Percent of the faction's players in this epoch
+    pub meta_share: f64,
+    pub games: u32,
+    pub game_win_rate: Option<f64>,
+}
+
+/// One detachment of a faction.
+#[derive(Debug, Clone, Serialize)]
+pub struct DetachmentBreakdown {
+    pub detachment: String,
+    pub players: u32,
+    /// Percent of the faction's players
+    pub meta_share: f64,
+    #[serde(flatten)]
+    pub record: GameRecord,
+    pub games: u32,
+    pub game_win_rate: Option<f64>,
+    pub top4_count: u32,
+    pub top_lists: Vec<TopList>,
+    /// One point per epoch in `epoch_order` where the detachment was played
+    pub trend: Vec<DetachmentTrendPoint>,
+}
+
+fn percent(part: u32, whole: u32) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        (part as f64 / whole as f64 * 1000.0).round() / 10.0
+    }
+}
+
+/// Break a faction down by detachment, most played first. `epoch_order`
+/// orders the trend points; `top_lists` caps lists per detachment.
+pub fn faction_breakdown(
+    entries: &[DetachmentEntry],
+    records: &HashMap<DetachmentKey, GameRecord>,
+    faction: &str,
+    epoch_order: &[String],
+    top_lists: usize,
+) -> Vec<DetachmentBreakdown> {
+    let faction = normalize_faction_name(faction);
+    let entries: Vec<&DetachmentEntry> = entries.iter().filter(|e| e.faction == faction).collect();
+    let faction_players = entries.len() as u32;
+    let mut players_per_epoch: HashMap<&str, u32> = HashMap::new();
+    for e in &entries {
+        *players_per_epoch.entry(e.epoch_id.as_str()).or_default() += 1;
+    }
+
+    let mut by_detachment: HashMap<&str, Vec<&DetachmentEntry>> = HashMap::new();
+    for e in &entries {
+        by_detachment
+            .entry(e.detachment.as_str())
+            .or_default()
+            .push(e);
+    }
+
+    let mut breakdown: Vec<DetachmentBreakdown> = by_detachment
+        .into_iter()
+        .map(|(detachment, group)| {
+            let record_for = |epoch: &str| {
+                records
+                    .get(&(epoch.to_string(), faction.clone(), detachment.to_string()))
+                    .copied()
+                    .unwrap_or_default()
+            };
+
+            let trend: Vec<DetachmentTrendPoint> = epoch_order
+                .iter()
+                .filter_map(|epoch| {
+                    let players = group.iter().filter(|e| e.epoch_id == *epoch).count() as u32;
+                    if players == 0 {
+                        return None;
+                    }
+                    let record = record_for(epoch);
+                    Some(DetachmentTrendPoint {
+                        epoch_id: epoch.clone(),
+                        players,
+                        meta_share: percent(
+                            players,
+                            players_per_epoch.get(epoch.as_str()).copied().unwrap_or(0),
+                        ),
+                        games: record.games(),
+                        game_win_rate: record.win_rate(),
+                    })
+                })
+                .collect();
+
+            let mut record = GameRecord::default();
+            let epochs: HashSet<&str> = group.iter().map(|e| e.epoch_id.as_str()).collect();
+            for epoch in epochs {
+                record.add(&record_for(epoch));
+            }
+
+            let mut with_lists: Vec<&&DetachmentEntry> =
+                group.iter().filter(|e| e.list_id.is_some()).collect();
+            with_lists.sort_by_key(|e| e.rank);
+            let top = with_lists
+                .into_iter()
+                .take(top_lists)
+                .map(|e| TopList {
+                    list_id: e.list_id.clone().unwrap_or_default(),
+                    player_name: e.player_name.clone(),
+                    event_id: e.event_id.clone(),
+                    epoch_id: e.epoch_id.clone(),
+                    rank: e.rank,
+                    total_points: e.total_points,
+                })
+                .collect();
+
+            DetachmentBreakdown {
+                detachment: detachment.to_string(),
+                players: group.len() as u32,
+                meta_share: percent(group.len() as u32, faction_players),
+                games: record.games(),
+                game_win_rate: record.win_rate(),
+                record,
+                top4_count: group.iter().filter(|e| e.rank <= 4).count() as u32,
+                top_lists: top,
+                trend,
+            }
+        })
+        .collect();
+
+    breakdown.sort_by(|a, b| {
+        b.players
+            .cmp(&a.players)
+            .then_with(|| a.detachment.cmp(&b.detachment))
+    });
+    breakdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EntityId;
+
+    fn entry(
+        epoch: &str,
+        player: &str,
+        faction: &str,
+        detachment: &str,
+        rank: u32,
+    ) -> DetachmentEntry {
+        DetachmentEntry {
+            epoch_id: epoch.to_string(),
+            event_id: "evt".to_string(),
+            player_name: player.to_string(),
+            faction: faction.to_string(),
+            detachment: detachment.to_string(),
+            rank,
+            list_id: Some(format!("list-{}", player)),
+            total_points: Some(2000),
+        }
+    }
+
+    fn pairing(round: u32, p1: &str, p2: &str, result: &str) -> Pairing {
+        let mut p = Pairing::new(
+            EntityId::from("evt"),
+            EntityId::from("e1"),
+            round,
+            p1.to_string(),
+            p2.to_string(),
+        );
+        p.player1_result = Some(result.to_string());
+        p
+    }
+
+    #[test]
+    fn test_game_records_and_breakdown() {
+        let entries = vec![
+            entry("e1", "Alice", "Aeldari", "Seer Council", 1),
+            entry("e1", "Bob", "Aeldari", "Battle Host", 3),
+            entry("e1", "Cara", "Aeldari", "Seer Council", 2),
+            entry("e1", "Dan", "Orks", "Waaagh! Tribe", 4),
+        ];
+        let pairings = vec![
+            pairing(1, "Alice", "Dan", "win"),
+            pairing(2, "Alice", "Bob", "win"),
+            pairing(3, "Cara", "Alice", "draw"),
+            // Mirror: skipped
+            pairing(4, "Alice", "Cara", "win"),
+            pairing(5, "Dan", "Cara", "win"),
+        ];
+        let records = game_records(&entries, &pairings);
+        let seer = records[&(
+            "e1".to_string(),
+            "Aeldari".to_string(),
+            "Seer Council".to_string(),
+        )];
+        assert_eq!(
+            seer,
+            GameRecord {
+                wins: 2,
+                losses: 1,
+                draws: 0
+            }
+        );
+
+        let breakdown = faction_breakdown(&entries, &records, "aeldari", &["e1".to_string()], 1);
+        assert_eq!(breakdown.len(), 2);
+        assert_eq!(breakdown[0].detachment, "Seer Council");
+        assert_eq!(breakdown[0].players, 2);
+        assert!((breakdown[0].meta_share - 66.7).abs() < 1e-9);
+        assert_eq!(breakdown[0].game_win_rate, Some(66.7));
+        assert_eq!(breakdown[0].top_lists.len(), 1);
+        assert_eq!(breakdown[0].top_lists[0].player_name, "Alice");
+        assert_eq!(breakdown[0].trend.len(), 1);
+        assert_eq!(breakdown[1].game_win_rate, Some(0.0));
+    }
+}
@@ -0,0 +1,140 @@
+//! Enhancement popularity and win rates.
+//!
+//! Each Enhancement is scored within its faction: how many of the faction's
+//! lists take it, and how the players who took it did over their final
+//! records. Lists stored before Enhancements were tracked count as taking
+//! none until they are parsed or normalized again.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::calculate_win_rate;
+use crate::models::{normalize_faction_name, ArmyList, Placement};
+
+/// How one Enhancement performed.
+#[derive(Debug, Clone, Serialize)]
+pub struct EnhancementStat {
+    pub faction: String,
+    pub enhancement: String,
+    /// Lists taking it
+    pub count: u32,
+    /// Share of the faction's lists taking it (0.0 to 1.0)
+    pub popularity: f64,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    /// Win rate over the players' records, `None` without any
+    pub win_rate: Option<f64>,
+    pub top4_count: u32,
+}
+
+/// Score Enhancements over lists joined to their placements, most taken
+/// first. Factions are normalized; `faction` keeps only that one.
+pub fn enhancement_stats(
+    joined: &[(ArmyList, Placement)],
+    faction: Option<&str>,
+) -> Vec<EnhancementStat> {
+    let faction = faction.map(normalize_faction_name);
+    let mut faction_lists: HashMap<String, u32> = HashMap::new();
+    let mut stats: HashMap<(String, String), EnhancementStat> = HashMap::new();
+
+    for (list, placement) in joined {
+        let list_faction = normalize_faction_name(&placement.faction);
+        if faction.as_ref().is_some_and(|f| f != &list_faction) {
+            continue;
+        }
+        *faction_lists.entry(list_faction.clone()).or_default() += 1;
+
+        for enhancement in &list.enhancements {
+            let stat = stats
+                .entry((list_faction.clone(), enhancement.clone()))
+                .or_insert_with(|| EnhancementStat {
+                    faction: list_faction.clone(),
+                    enhancement: enhancement.clone(),
+                    count: 0,
+                    popularity: 0.0,
+                    wins: 0,
+                    losses: 0,
+                    draws: 0,
+                    win_rate: None,
+                    top4_count: 0,
+                });
+            stat.count += 1;
+            if let Some(record) = &placement.record {
+                stat.wins += record.wins;
+                stat.losses += record.losses;
+                stat.draws += record.draws;
+            }
+            if placement.rank <= 4 {
+                stat.top4_count += 1;
+            }
+        }
+    }
+
+    let mut stats: Vec<EnhancementStat> = stats
+        .into_values()
+        .map(|mut stat| {
+            stat.popularity = stat.count as f64 / faction_lists[&stat.faction] as f64;
+            if stat.wins + stat.losses + stat.draws > 0 {
+                stat.win_rate = Some(calculate_win_rate(stat.wins, stat.losses, stat.draws));
+            }
+            stat
+        })
+        .collect();
+    stats.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.faction.cmp(&b.faction))
+            .then_with(|| a.enhancement.cmp(&b.enhancement))
+    });
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EntityId, Unit};
+
+    #[test]
+    fn test_enhancement_stats() {
+        let entry = |rank: u32, faction: &str, enhancement: Option<&str>, wins: u32| {
+            let mut unit = Unit::new("Farseer".to_string(), 1);
+            if let Some(enhancement) = enhancement {
+                unit = unit.with_enhancement(enhancement);
+            }
+            let list = ArmyList::new(faction.to_string(), 2000, vec![unit], String::new());
+            let placement = Placement::new(
+                EntityId::from("gt"),
+                EntityId::from("current"),
+                rank,
+                format!("Player {}", rank),
+                faction.to_string(),
+            )
+            .with_record(wins, 5 - wins, 0);
+            (list, placement)
+        };
+        let joined = vec![
+            entry(1, "Aeldari", Some("Psychic Destroyer"), 5),
+            entry(2, "Aeldari", Some("Psychic Destroyer"), 3),
+            entry(7, "Aeldari", Some("Lucky Stone"), 2),
+            entry(9, "Aeldari", None, 1),
+            entry(3, "Necrons", Some("Veil of Darkness"), 4),
+        ];
+
+        let stats = enhancement_stats(&joined, None);
+        let names: Vec<&str> = stats.iter().map(|s| s.enhancement.as_str()).collect();
+        assert_eq!(
+            names,
+            ["Psychic Destroyer", "Lucky Stone", "Veil of Darkness"]
+        );
+        let destroyer = &stats[0];
+        assert_eq!((destroyer.count, destroyer.top4_count), (2, 2));
+        assert!((destroyer.popularity - 0.5).abs() < 1e-9);
+        assert!((destroyer.win_rate.unwrap() - 0.8).abs() < 1e-9);
+
+        let necrons = enhancement_stats(&joined, Some("Necrons"));
+        assert_eq!(necrons.len(), 1);
+        assert!((necrons[0].popularity - 1.0).abs() < 1e-9);
+    }
+}
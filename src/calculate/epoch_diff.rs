@@ -0,0 +1,210 @@
+//! Epoch-over-epoch faction deltas.
+//!
+//! Aligns two epochs' faction stats by normalized faction name, so a
+//! faction that was renamed or aliased between extractions still lines up,
+//! and reports how its meta share, win rate and tier moved.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::models::normalize_faction_name;
+use crate::models::{FactionStat, FactionStats, Tier};
+
+/// A faction's headline numbers in one epoch.
+#[derive(Debug, Clone, Serialize)]
+pub struct FactionSnapshot {
+    pub tier: Tier,
+    pub meta_share: f64,
+    pub win_rate: f64,
+    pub games_played: u32,
+    pub player_count: u32,
+}
+
+impl From<&FactionStat> for FactionSnapshot {
+    fn from(stat: &FactionStat) -> Self {
+        Self {
+            tier: stat.tier,
+            meta_share: stat.meta_share,
+            win_rate: stat.win_rate,
+            games_played: stat.games_played,
+            player_count: stat.player_count,
+        }
+    }
+}
+
+/// How one faction changed between two epochs. Deltas are `None` when the
+/// faction is missing from either side.
+#[derive(Debug, Clone, Serialize)]
+pub struct FactionDelta {
+    pub faction: String,
+    pub from: Option<FactionSnapshot>,
+    pub to: Option<FactionSnapshot>,
+    pub meta_share_delta: Option<f64>,
+    pub win_rate_delta: Option<f64>,
+    /// Tiers moved up (positive) or down (negative)
+    pub tier_change: Option<i32>,
+}
+
+impl FactionDelta {
+    /// Whether both epochs have at least `min_games` games for the faction.
+    pub fn has_games(&self, min_games: u32) -> bool {
+        [&self.from, &self.to]
+            .iter()
+            .all(|s| s.as_ref().is_some_and(|s| s.games_played >= min_games))
+    }
+}
+
+fn tier_level(tier: Tier) -> i32 {
+    match tier {
+        Tier::S => 4,
+        Tier::A => 3,
+        Tier::B => 2,
+        Tier::C => 1,
+        Tier::D => 0,
+    }
+}
+
+fn round4(v: f64) -> f64 {
+    (v * 10_000.0).round() / 10_000.0
+}
+
+fn by_faction(stats: &FactionStats) -> BTreeMap<String, &FactionStat> {
+    let mut out: BTreeMap<String, &FactionStat> = BTreeMap::new();
+    for stat in &stats.factions {
+        let entry = out
+            .entry(normalize_faction_name(&stat.name))
+            .or_insert(stat);
+        // Two raw names folding into one faction: keep the larger sample
+        if stat.games_played > entry.games_played {
+            *entry = stat;
+        }
+    }
+    out
+}
+
+/// Align two epochs' faction stats, sorted by faction name. Factions
+/// present in only one epoch are included with the other side empty.
+pub fn align_faction_stats(from: &FactionStats, to: &FactionStats) -> Vec<FactionDelta> {
+    let before = by_faction(from);
+    let after = by_faction(to);
+
+    let mut names: Vec<&String> = before.keys().chain(after.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let b = before.get(name).copied();
+            let a = after.get(name).copied();
+            let both = b.zip(a);
+            FactionDelta {
+                faction: name.clone(),
+                from: b.map(FactionSnapshot::from),
+                to: a.map(FactionSnapshot::from),
+                meta_share_delta: both.map(|(b, a)| round4(a.meta_share - b.meta_share)),
+                win_rate_delta: both.map(|(b, a)| round4(a.win_rate - b.win_rate)),
+                tier_change: both.map(|(b, a)| tier_level(a.tier) - tier_level(b.tier)),
+            }
+        })
+        .collect()
+}
+
+/// The biggest win-rate gains and drops among factions with at least
+/// `min_games` games in both epochs, at most `limit` of each.
+pub fn biggest_movers(
+    deltas: &[FactionDelta],
+    min_games: u32,
+    limit: usize,
+) -> (Vec<FactionDelta>, Vec<FactionDelta>) {
+    let mut moved: Vec<(&FactionDelta, f64)> = deltas
+        .iter()
+        .filter(|d| d.has_games(min_games))
+        .filter_map(|d| d.win_rate_delta.map(|w| (d, w)))
+        .collect();
+    moved.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.0.faction.cmp(&b.0.faction))
+    });
+
+    let winners = moved
+        .iter()
+        .filter(|(_, w)| *w > 0.0)
+        .take(limit)
+        .map(|(d, _)| (*d).clone())
+        .collect();
+    let losers = moved
+        .iter()
+        .rev()
+        .filter(|(_, w)| *w < 0.0)
+        .take(limit)
+        .map(|(d, _)| (*d).clone())
+        .collect();
+    (winners, losers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DateRange, EntityId, EpochTotals, PlacementCounts};
+    use chrono::NaiveDate;
+
+    fn stats(epoch: &str, factions: &[(&str, u32, u32)]) -> FactionStats {
+        let total_players = factions.len() as u32 * 10;
+        let factions = factions
+            .iter()
+            .map(|&(name, wins, losses)| {
+                FactionStat::new(
+                    name.to_string(),
+                    10,
+                    wins + losses,
+                    1,
+                    wins,
+                    losses,
+                    0,
+                    PlacementCounts::default(),
+                    total_players,
+                    4,
+                )
+            })
+            .collect();
+        let day = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        FactionStats::new(
+            EntityId::from(epoch),
+            epoch.to_string(),
+            DateRange { from: day, to: day },
+            EpochTotals::default(),
+            factions,
+        )
+    }
+
+    #[test]
+    fn test_align_and_movers() {
+        let before = stats(
+            "e1",
+            &[("Aeldari", 60, 40), ("Orks", 45, 55), ("Tyranids", 50, 50)],
+        );
+        let after = stats(
+            "e2",
+            &[("Aeldari", 48, 52), ("Orks", 56, 44), ("Necrons", 5, 5)],
+        );
+
+        let deltas = align_faction_stats(&before, &after);
+        let names: Vec<&str> = deltas.iter().map(|d| d.faction.as_str()).collect();
+        assert_eq!(names, vec!["Aeldari", "Necrons", "Orks", "Tyranids"]);
+
+        let aeldari = &deltas[0];
+        assert_eq!(aeldari.win_rate_delta, Some(-0.12));
+        assert_eq!(aeldari.tier_change, Some(-2));
+        assert!(deltas[1].from.is_none() && deltas[1].win_rate_delta.is_none());
+        assert!(deltas[3].to.is_none());
+
+        let (winners, losers) = biggest_movers(&deltas, 20, 5);
+        assert_eq!(winners.len(), 1);
+        assert_eq!(winners[0].faction, "Orks");
+        assert_eq!(winners[0].tier_change, Some(3));
+        assert_eq!(losers[0].faction, "Aeldari");
+    }
+}
@@ -0,0 +1,250 @@
+//! Faction matchups from game-level results.
+//!
+//! Each side of a pairing is credited to its player's faction: the one on
+//! the pairing when present, else the player's placement at the event, else
+//! their army list. Games are recorded from both perspectives, so the
+//! record of A against B is the mirror of B against A. Mirror games are
+//! skipped since they always net to 50%.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::Serialize;
+
+use crate::calculate::detachments::GameRecord;
+use crate::models::normalize_faction_name;
+use crate::models::{ArmyList, Pairing, Placement};
+use crate::sync::normalize_player_name;
+
+/// Default games a matrix cell needs before its win rate is reported.
+pub const MATRIX_MIN_GAMES: u32 = 10;
+
+/// (faction, opponent) to the faction's record in that matchup.
+pub type MatchupRecords = BTreeMap<(String, String), GameRecord>;
+
+/// Resolves a pairing player's faction from placements and army lists.
+pub struct FactionResolver {
+    /// (event, normalized player) to normalized faction
+    by_player: HashMap<(String, String), String>,
+}
+
+impl FactionResolver {
+    pub fn new(placements: &[Placement], lists: &[ArmyList]) -> Self {
+        let mut by_player = HashMap::new();
+        // Lists first so placements, the reported faction, take precedence
+        for list in lists {
+            if let (Some(event), Some(player)) = (&list.event_id, &list.player_name) {
+                by_player.insert(
+                    (event.as_str().to_string(), normalize_player_name(player)),
+                    normalize_faction_name(&list.faction),
+                );
+            }
+        }
+        for p in placements.iter().filter(|p| !p.faction.is_empty()) {
+            by_player.insert(
+                (
+                    p.event_id.as_str().to_string(),
+                    normalize_player_name(&p.player_name),
+                ),
+                normalize_faction_name(&p.faction),
+            );
+        }
+        Self { by_player }
+    }
+
+    /// The faction of one side of a pairing, if it can be identified.
+    pub fn faction(
+        &self,
+        pairing: &Pairing,
+        player: &str,
+        faction: Option<&str>,
+    ) -> Option<String> {
+        match faction.filter(|f| !f.is_empty()) {
+            Some(f) => Some(normalize_faction_name(f)),
+            None => self
+                .by_player
+                .get(&(
+                    pairing.event_id.as_str().to_string(),
+                    normalize_player_name(player),
+                ))
+                .cloned(),
+        }
+    }
+}
+
+/// Game records for every faction pairing seen, from both perspectives.
+pub fn faction_matchups(
+    pairings: &[Pairing],
+    placements: &[Placement],
+    lists: &[ArmyList],
+) -> MatchupRecords {
+    let resolver = FactionResolver::new(placements, lists);
+    let mut records = MatchupRecords::new();
+
+    for pairing in pairings {
+        let f1 = resolver.faction(
+            pairing,
+            &pairing.player1_name,
+            pairing.player1_faction.as_deref(),
+        );
+        let f2 = resolver.faction(
+            pairing,
+            &pairing.player2_name,
+            pairing.player2_faction.as_deref(),
+        );
+        let (Some(f1), Some(f2)) = (f1, f2) else {
+            continue;
+        };
+        if f1 == f2 {
+            continue;
+        }
+
+        let (r1, r2) = match pairing.player1_result.as_deref() {
+            Some("win") => ((1, 0, 0), (0, 1, 0)),
+            Some("loss") => ((0, 1, 0), (1, 0, 0)),
+            Some("draw") => ((0, 0, 1), (0, 0, 1)),
+            _ => continue,
+        };
+        for (key, (wins, losses, draws)) in [((f1.clone(), f2.clone()), r1), ((f2, f1), r2)] {
+            records.entry(key).or_default().add(&GameRecord {
+                wins,
+                losses,
+                draws,
+            });
+        }
+    }
+    records
+}
+
+/// One cell of the matchup matrix: the row faction's record against the
+/// column faction.
+#[derive(Debug, Clone, Serialize)]
+pub struct MatrixCell {
+    #[serde(flatten)]
+    pub record: GameRecord,
+    pub games: u32,
+    /// Percent with draws as half a win; `None` below the sample threshold
+    pub win_rate: Option<f64>,
+    pub insufficient_data: bool,
+}
+
+/// An NxN grid of faction matchups. `cells[i][j]` is `factions[i]` against
+/// `factions[j]`; the diagonal (mirror games) is `None`.
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchupMatrix {
+    pub factions: Vec<String>,
+    pub min_games: u32,
+    pub cells: Vec<Vec<Option<MatrixCell>>>,
+}
+
+/// Lay matchup records out as a matrix over every faction seen. Cells with
+/// fewer than `min_games` games keep their counts but no win rate.
+pub fn matchup_matrix(records: &MatchupRecords, min_games: u32) -> MatchupMatrix {
+    let factions: Vec<String> = records
+        .keys()
+        .map(|(faction, _)| faction.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let cells = factions
+        .iter()
+        .map(|row| {
+            factions
+                .iter()
+                .map(|col| {
+                    if row == col {
+                        return None;
+                    }
+                    let record = records
+                        .get(&(row.clone(), col.clone()))
+                        .copied()
+                        .unwrap_or_default();
+                    let games = record.games();
+                    let insufficient_data = games == 0 || games < min_games;
+                    Some(MatrixCell {
+                        record,
+                        games,
+                        win_rate: if insufficient_data {
+                            None
+                        } else {
+                            record.win_rate()
+                        },
+                        insufficient_data,
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    MatchupMatrix {
+        factions,
+        min_games,
+        cells,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EntityId;
+
+    #[test]
+    fn test_matchup_matrix_resolves_factions_from_placements() {
+        let event = EntityId::from("evt");
+        let pairing = |round: u32, p1: &str, p2: &str, result: &str| {
+            let mut p = Pairing::new(
+                event.clone(),
+                "current".into(),
+                round,
+                p1.to_string(),
+                p2.to_string(),
+            );
+            p.player1_result = Some(result.to_string());
+            p
+        };
+        let mut tagged = pairing(1, "Ann", "Ben", "win");
+        tagged.player1_faction = Some("Aeldari".to_string());
+        tagged.player2_faction = Some("Orks".to_string());
+        let pairings = vec![
+            tagged,
+            // Factions resolved from placements
+            pairing(2, "Ann", "Ben", "draw"),
+            pairing(3, "Ben", "cal", "loss"),
+            // Unknown player: skipped
+            pairing(4, "Ann", "Zed", "win"),
+        ];
+        let placement = |rank: u32, player: &str, faction: &str| {
+            Placement::new(
+                event.clone(),
+                "current".into(),
+                rank,
+                player.to_string(),
+                faction.to_string(),
+            )
+        };
+        let placements = vec![
+            placement(1, "Ann", "Aeldari"),
+            placement(2, "Ben", "Orks"),
+            placement(3, "Cal", "tau"),
+        ];
+
+        let records = faction_matchups(&pairings, &placements, &[]);
+        let aeldari_orks = records[&("Aeldari".to_string(), "Orks".to_string())];
+        assert_eq!((aeldari_orks.wins, aeldari_orks.draws), (1, 1));
+        let orks_aeldari = records[&("Orks".to_string(), "Aeldari".to_string())];
+        assert_eq!((orks_aeldari.losses, orks_aeldari.draws), (1, 1));
+
+        let matrix = matchup_matrix(&records, 2);
+        assert_eq!(matrix.factions, vec!["Aeldari", "Orks", "T'au Empire"]);
+        assert!(matrix.cells[0][0].is_none());
+        let cell = matrix.cells[0][1].as_ref().unwrap();
+        assert_eq!(cell.win_rate, Some(75.0));
+        assert!(!cell.insufficient_data);
+        // One game between Orks and T'au: below the threshold
+        let cell = matrix.cells[2][1].as_ref().unwrap();
+        assert_eq!(cell.record.wins, 1);
+        assert!(cell.insufficient_data && cell.win_rate.is_none());
+        // Never played
+        assert_eq!(matrix.cells[0][2].as_ref().unwrap().games, 0);
+    }
+}
@@ -5,6 +5,38 @@
 //! - Unit frequency analysis
 //! - Common combo detection
 //! - Trend analysis across epochs
+//! - Player ratings from pairings
+//! - Detachment win rates from pairings
+//! - Enhancement popularity and win rates
+//! - List archetypes clustered by unit composition
+//! - Meta shift candidates from win-rate breakpoints (experimental)
+//! - Data quality scores per event and epoch
+//! - Composite faction tier lists
+//! - Epoch-over-epoch faction deltas
+//! - Army list similarity search
+//! - Faction matchup matrices from pairings
+//! - Points validation for army lists
+//! - Unit win rates from pairings, shrunk toward the faction
+//! - Wilson and Jeffreys confidence intervals for win rates
+//! - Strength of schedule and round-weighted results from pairings
+
+pub mod archetypes;
+pub mod combos;
+pub mod derive;
+pub mod detachments;
+pub mod enhancements;
+pub mod epoch_diff;
+pub mod matchups;
+pub mod quality;
+pub mod ratings;
+pub mod shifts;
+pub mod similarity;
+pub mod sos;
+pub mod tiers;
+pub mod unit_performance;
+pub mod validation;
+
+use serde::{Deserialize, Serialize};
 
 use crate::models::{PlacementCounts, Tier};
 
@@ -23,6 +55,202 @@ pub fn calculate_win_rate(wins: u32, losses: u32, draws: u32) -> f64 {
     }
 }
 
+/// z-score for a two-sided 95% confidence interval.
+pub const Z_95: f64 = 1.959_964;
+
+/// Wilson score interval for a proportion of `successes` out of `n`.
+///
+/// Returns `(low, high)` as fractions. Unlike the normal approximation it
+/// stays inside [0, 1] and stays wide for small samples, so a 3-0 record is
+/// not reported as a certain 100%. Returns `(0.0, 1.0)` when `n` is 0.
+pub fn wilson_interval(successes: u32, n: u32, z: f64) -> (f64, f64) {
+    wilson(successes as f64, n, z)
+}
+
+/// Wilson interval for a possibly fractional success count (draws as half).
+fn wilson(successes: f64, n: u32, z: f64) -> (f64, f64) {
+    if n == 0 {
+        return (0.0, 1.0);
+    }
+    let n = n as f64;
+    let p = successes / n;
+    let z2 = z * z;
+    let denom = 1.0 + z2 / n;
+    let centre = (p + z2 / (2.0 * n)) / denom;
+    let margin = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denom;
+    ((centre - margin).max(0.0), (centre + margin).min(1.0))
+}
+
+/// Jeffreys interval for `successes` out of `n`: the central `level` (e.g.
+/// 0.95) of the Beta(successes + ½, n − successes + ½) posterior, with the
+/// bound pinned to 0 or 1 when every trial failed or succeeded.
+///
+/// Slightly narrower than Wilson for small samples with good coverage.
+/// Returns `(0.0, 1.0)` when `n` is 0.
+pub fn jeffreys_interval(successes: f64, n: u32, level: f64) -> (f64, f64) {
+    if n == 0 {
+        return (0.0, 1.0);
+    }
+    let failures = n as f64 - successes;
+    let tail = (1.0 - level) / 2.0;
+    let low = if successes <= 0.0 {
+        0.0
+    } else {
+        beta_quantile(tail, successes + 0.5, failures + 0.5)
+    };
+    let high = if failures <= 0.0 {
+        1.0
+    } else {
+        beta_quantile(1.0 - tail, successes + 0.5, failures + 0.5)
+    };
+    (low, high)
+}
+
+/// How win-rate confidence intervals are computed (`?interval=`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IntervalMethod {
+    #[default]
+    Wilson,
+    Jeffreys,
+}
+
+impl std::str::FromStr for IntervalMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "wilson" => Ok(IntervalMethod::Wilson),
+            "jeffreys" => Ok(IntervalMethod::Jeffreys),
+            other => Err(format!(
+                "Unknown interval: {} (expected wilson or jeffreys)",
+                other
+            )),
+        }
+    }
+}
+
+/// 95% interval for a win rate over `games` with draws counting half, in
+/// percent to one decimal. `None` without games.
+pub fn win_rate_ci(
+    wins: u32,
+    draws: u32,
+    games: u32,
+    method: IntervalMethod,
+) -> Option<(f64, f64)> {
+    if games == 0 {
+        return None;
+    }
+    let successes = wins as f64 + draws as f64 * 0.5;
+    let (low, high) = match method {
+        IntervalMethod::Wilson => wilson(successes, games, Z_95),
+        IntervalMethod::Jeffreys => jeffreys_interval(successes, games, 0.95),
+    };
+    let pct = |x: f64| (x * 1000.0).round() / 10.0;
+    Some((pct(low), pct(high)))
+}
+
+/// Natural log of the gamma function (Lanczos approximation, g = 7).
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection formula
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let mut sum = COEFFICIENTS[0];
+    for (i, c) in COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += c / (x + i as f64);
+    }
+    let t = x + 7.5;
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Continued fraction for the incomplete beta function (modified Lentz).
+fn beta_continued_fraction(x: f64, a: f64, b: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for m in 1..=300 {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+        let even = m * (b - m) * x / ((a + m2 - 1.0) * (a + m2));
+        d = 1.0 + even * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + even / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        h *= d * c;
+        let odd = -(a + m) * (a + b + m) * x / ((a + m2) * (a + m2 + 1.0));
+        d = 1.0 + odd * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + odd / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let step = d * c;
+        h *= step;
+        if (step - 1.0).abs() < 1e-12 {
+            break;
+        }
+    }
+    h
+}
+
+/// Regularized incomplete beta function I_x(a, b).
+fn incomplete_beta(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(x, a, b) / a
+    } else {
+        1.0 - front * beta_continued_fraction(1.0 - x, b, a) / b
+    }
+}
+
+/// Quantile `p` of Beta(a, b), by bisection on the CDF.
+fn beta_quantile(p: f64, a: f64, b: f64) -> f64 {
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..100 {
+        let mid = (low + high) / 2.0;
+        if incomplete_beta(mid, a, b) < p {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    (low + high) / 2.0
+}
+
 /// Calculate over-representation ratio.
 /// A ratio > 1.0 means the faction is over-represented in top placements.
 pub fn calculate_over_representation(
@@ -76,6 +304,43 @@ pub fn aggregate_placements(ranks: &[u32], total_players_per_event: &[u32]) -> P
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_wilson_interval() {
+        let (low, high) = wilson_interval(3, 3, Z_95);
+        assert!(low > 0.4 && low < 0.5);
+        assert!((high - 1.0).abs() < 1e-9);
+
+        let (low, high) = wilson_interval(300, 500, Z_95);
+        assert!(low > 0.55 && low < 0.6);
+        assert!(high > 0.6 && high < 0.65);
+
+        assert_eq!(wilson_interval(0, 0, Z_95), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_jeffreys_interval() {
+        // Beta(1.5, 1.5) is symmetric
+        let (low, high) = jeffreys_interval(1.0, 2, 0.95);
+        assert!((low + high - 1.0).abs() < 1e-6);
+
+        // Beta(6.5, 4.5): 2.5% and 97.5% quantiles
+        let (low, high) = jeffreys_interval(6.0, 10, 0.95);
+        assert!((low - 0.3037).abs() < 0.001, "low {}", low);
+        assert!((high - 0.8469).abs() < 0.001, "high {}", high);
+
+        let (low, high) = jeffreys_interval(3.0, 3, 0.95);
+        assert!(low > 0.4 && low < 0.6);
+        assert_eq!(high, 1.0);
+
+        // A 3-0 faction's lower bound sits well below a 60% faction's over 500 games
+        let (small, _) = win_rate_ci(3, 0, 3, IntervalMethod::Wilson).unwrap();
+        let (large, _) = win_rate_ci(300, 0, 500, IntervalMethod::Jeffreys).unwrap();
+        assert!(small < large);
+        assert_eq!(win_rate_ci(0, 0, 0, IntervalMethod::Wilson), None);
+        assert_eq!("jeffreys".parse(), Ok(IntervalMethod::Jeffreys));
+        assert!("normal".parse::<IntervalMethod>().is_err());
+    }
+
     #[test]
     fn test_calculate_tier() {
         assert_eq!(calculate_tier(0.60), Tier::S);
@@ -0,0 +1,250 @@
+//! Data quality scoring.
+//!
+//! Extractions carry a [`Confidence`], and not every placement has a list
+//! or a known detachment. These scores summarise how much of an event (or
+//! an epoch) analytics can actually rely on:
+//!
+//! - `high_confidence_list_rate`: share of army lists extracted with high
+//!   confidence
+//! - `list_link_rate`: share of placements joined to an army list
+//! - `detachment_coverage`: share of placements whose detachment is known
+//!
+//! The quality `score` is the mean of the three, from 0 to 100.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::api::routes::analytics::join_lists_to_placements;
+use crate::models::{ArmyList, Confidence, Event, Placement};
+
+/// Raw counts behind a quality score.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct QualityCounts {
+    placements: u32,
+    lists: u32,
+    high_confidence_lists: u32,
+    linked_placements: u32,
+    placements_with_detachment: u32,
+}
+
+impl QualityCounts {
+    fn add(&mut self, other: &QualityCounts) {
+        self.placements += other.placements;
+        self.lists += other.lists;
+        self.high_confidence_lists += other.high_confidence_lists;
+        self.linked_placements += other.linked_placements;
+        self.placements_with_detachment += other.placements_with_detachment;
+    }
+
+    fn rates(&self) -> (f64, f64, f64, f64) {
+        let high = ratio(self.high_confidence_lists, self.lists);
+        let linked = ratio(self.linked_placements, self.placements);
+        let detachments = ratio(self.placements_with_detachment, self.placements);
+        let score = (high + linked + detachments) / 3.0 * 100.0;
+        (
+            round(high),
+            round(linked),
+            round(detachments),
+            (score * 10.0).round() / 10.0,
+        )
+    }
+}
+
+fn ratio(part: u32, whole: u32) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+fn round(rate: f64) -> f64 {
+    (rate * 1000.0).round() / 1000.0
+}
+
+/// Quality of one event's data.
+#[derive(Debug, Clone, Serialize)]
+pub struct EventQuality {
+    pub event_id: String,
+    pub name: String,
+    pub date: String,
+    pub confidence: Confidence,
+    pub placements: u32,
+    pub lists: u32,
+    pub high_confidence_list_rate: f64,
+    pub list_link_rate: f64,
+    pub detachment_coverage: f64,
+    pub score: f64,
+}
+
+/// Quality of one epoch's data, from the totals across its events.
+#[derive(Debug, Clone, Serialize)]
+pub struct EpochQuality {
+    pub epoch_id: String,
+    pub events: u32,
+    pub low_confidence_events: u32,
+    pub placements: u32,
+    pub lists: u32,
+    pub high_confidence_list_rate: f64,
+    pub list_link_rate: f64,
+    pub detachment_coverage: f64,
+    pub score: f64,
+}
+
+/// Count quality inputs for a set of placements and lists.
+fn counts(placements: &[Placement], lists: &[ArmyList]) -> QualityCounts {
+    let joined = join_lists_to_placements(lists, placements);
+    let list_detachment: HashMap<&str, bool> = joined
+        .iter()
+        .map(|(list, p)| (p.id.as_str(), list.detachment.is_some()))
+        .collect();
+    let linked: HashSet<&str> = list_detachment.keys().copied().collect();
+
+    QualityCounts {
+        placements: placements.len() as u32,
+        lists: lists.len() as u32,
+        high_confidence_lists: lists
+            .iter()
+            .filter(|l| l.extraction_confidence == Confidence::High)
+            .count() as u32,
+        linked_placements: placements
+            .iter()
+            .filter(|p| linked.contains(p.id.as_str()))
+            .count() as u32,
+        placements_with_detachment: placements
+            .iter()
+            .filter(|p| {
+                p.detachment.is_some()
+                    || list_detachment.get(p.id.as_str()).copied().unwrap_or(false)
+            })
+            .count() as u32,
+    }
+}
+
+/// Score every event in an epoch and the epoch as a whole. Events are
+/// returned worst first.
+pub fn epoch_quality(
+    epoch_id: &str,
+    events: &[Event],
+    placements: &[Placement],
+    lists: &[ArmyList],
+) -> (EpochQuality, Vec<EventQuality>) {
+    let mut placements_by_event: HashMap<&str, Vec<Placement>> = HashMap::new();
+    for p in placements {
+        placements_by_event
+            .entry(p.event_id.as_str())
+            .or_default()
+            .push(p.clone());
+    }
+    let mut lists_by_event: HashMap<&str, Vec<ArmyList>> = HashMap::new();
+    for l in lists {
+        if let Some(event_id) = &l.event_id {
+            lists_by_event
+                .entry(event_id.as_str())
+                .or_default()
+                .push(l.clone());
+        }
+    }
+
+    let mut total = QualityCounts::default();
+    let mut scored = Vec::with_capacity(events.len());
+    for event in events {
+        let id = event.id.as_str();
+        let c = counts(
+            placements_by_event.get(id).map_or(&[][..], Vec::as_slice),
+            lists_by_event.get(id).map_or(&[][..], Vec::as_slice),
+        );
+        total.add(&c);
+        let (high, linked, detachments, score) = c.rates();
+        scored.push(EventQuality {
+            event_id: id.to_string(),
+            name: event.name.clone(),
+            date: event.date.to_string(),
+            confidence: event.extraction_confidence,
+            placements: c.placements,
+            lists: c.lists,
+            high_confidence_list_rate: high,
+            list_link_rate: linked,
+            detachment_coverage: detachments,
+            score,
+        });
+    }
+    scored.sort_by(|a, b| {
+        a.score
+            .partial_cmp(&b.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let (high, linked, detachments, score) = total.rates();
+    let epoch = EpochQuality {
+        epoch_id: epoch_id.to_string(),
+        events: events.len() as u32,
+        low_confidence_events: events
+            .iter()
+            .filter(|e| e.extraction_confidence == Confidence::Low)
+            .count() as u32,
+        placements: total.placements,
+        lists: total.lists,
+        high_confidence_list_rate: high,
+        list_link_rate: linked,
+        detachment_coverage: detachments,
+        score,
+    };
+    (epoch, scored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_epoch_quality() {
+        let good = Event::new(
+            "Good GT".into(),
+            NaiveDate::from_ymd_opt(2026, 1, 10).unwrap(),
+            "https://example.com/a".into(),
+            "bcp".into(),
+            "current".into(),
+        );
+        let bare = Event::new(
+            "Bare GT".into(),
+            NaiveDate::from_ymd_opt(2026, 1, 17).unwrap(),
+            "https://example.com/b".into(),
+            "bcp".into(),
+            "current".into(),
+        );
+        let placement = |event: &Event, rank: u32, player: &str| {
+            Placement::new(
+                event.id.clone(),
+                "current".into(),
+                rank,
+                player.to_string(),
+                "Necrons".to_string(),
+            )
+        };
+        let mut list = ArmyList::new("Necrons".into(), 2000, vec![], "raw".into())
+            .with_confidence(Confidence::High);
+        list.player_name = Some("Alice".into());
+        list.event_id = Some(good.id.clone());
+        list.detachment = Some("Awakened Dynasty".into());
+
+        let placements = vec![
+            placement(&good, 1, "Alice"),
+            placement(&good, 2, "Bob"),
+            placement(&bare, 1, "Cara"),
+        ];
+        let (epoch, events) = epoch_quality("current", &[good.clone(), bare], &placements, &[list]);
+
+        assert_eq!(epoch.placements, 3);
+        assert_eq!(epoch.lists, 1);
+        assert!((epoch.list_link_rate - 0.333).abs() < 1e-9);
+        assert_eq!(events[0].name, "Bare GT");
+        assert_eq!(events[0].score, 0.0);
+        // Good GT: all lists high, 1 of 2 linked, 1 of 2 with detachment
+        assert_eq!(events[1].event_id, good.id.as_str());
+        assert!((events[1].score - 66.7).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,610 @@
+//! Player ratings computed from pairings.
+//!
+//! Every rated game updates an Elo rating for both players, processed in
+//! event-date and round order across all epochs. At each epoch boundary
+//! ratings regress part of the way back to the initial rating, since a
+//! balance pass reshuffles which armies are good. Each player also gets a
+//! per-faction rating, updated only by games played with that faction
+//! against the opponent's overall rating, so a strong player can be told
+//! apart from a player riding a strong faction.
+//!
+//! The table is persisted to `derived/ratings/ratings.json` and, like the
+//! derived artifacts, covers 2000-point games.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::merge_by_id;
+use crate::models::normalize_faction_name;
+use crate::models::{Event, Pairing};
+use crate::storage::{cached_epoch_mapper, EntityType, JsonlReader, StorageConfig, StorageError};
+use crate::sync::normalize_player_name;
+
+use super::validation::DEFAULT_GAME_SIZE;
+
+/// Rating every player starts from.
+pub const INITIAL_RATING: f64 = 1500.0;
+
+/// Maximum rating change from a single game.
+pub const K_FACTOR: f64 = 32.0;
+
+/// Fraction of the distance to [`INITIAL_RATING`] removed at each epoch boundary.
+pub const EPOCH_DECAY: f64 = 0.25;
+
+/// Tunables for [`compute_ratings`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RatingConfig {
+    pub initial: f64,
+    pub k_factor: f64,
+    pub epoch_decay: f64,
+}
+
+impl Default for RatingConfig {
+    fn default() -> Self {
+        Self {
+            initial: INITIAL_RATING,
+            k_factor: K_FACTOR,
+            epoch_decay: EPOCH_DECAY,
+        }
+    }
+}
+
+impl RatingConfig {
+    pub fn with_k_factor(mut self, k_factor: f64) -> Self {
+        self.k_factor = k_factor;
+        self
+    }
+
+    pub fn with_epoch_decay(mut self, epoch_decay: f64) -> Self {
+        self.epoch_decay = epoch_decay.clamp(0.0, 1.0);
+        self
+    }
+}
+
+/// A player's rating with one faction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactionRating {
+    pub faction: String,
+    pub rating: f64,
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    /// Rating minus the average rating of everyone playing this faction
+    pub vs_faction_average: f64,
+}
+
+/// A player's overall rating.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerRating {
+    pub player: String,
+    pub rating: f64,
+    pub peak_rating: f64,
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub last_played: Option<NaiveDate>,
+    /// Per-faction splits, most-played first
+    pub factions: Vec<FactionRating>,
+}
+
+/// Average per-faction rating across all players of a faction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactionAverage {
+    pub faction: String,
+    pub players: u32,
+    pub average_rating: f64,
+}
+
+/// Ratings for every player, highest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatingsTable {
+    pub computed_at: DateTime<Utc>,
+    pub config: RatingConfig,
+    /// Epochs processed, oldest first
+    pub epochs: Vec<String>,
+    pub games_rated: u32,
+    pub players: Vec<PlayerRating>,
+    pub factions: Vec<FactionAverage>,
+}
+
+/// Events and pairings of one epoch.
+pub struct EpochGames {
+    pub epoch_id: String,
+    pub events: Vec<Event>,
+    pub pairings: Vec<Pairing>,
+}
+
+#[derive(Default)]
+struct Record {
+    rating: f64,
+    peak: f64,
+    games: u32,
+    wins: u32,
+    losses: u32,
+    draws: u32,
+}
+
+impl Record {
+    fn new(initial: f64) -> Self {
+        Self {
+            rating: initial,
+            peak: initial,
+            ..Default::default()
+        }
+    }
+
+    fn apply(&mut self, score: f64, expected: f64, k: f64) {
+        self.rating += k * (score - expected);
+        self.peak = self.peak.max(self.rating);
+        self.games += 1;
+        match score {
+            s if s > 0.5 => self.wins += 1,
+            s if s < 0.5 => self.losses += 1,
+            _ => self.draws += 1,
+        }
+    }
+
+    fn decay(&mut self, initial: f64, decay: f64) {
+        self.rating += (initial - self.rating) * decay;
+    }
+}
+
+#[derive(Default)]
+struct PlayerState {
+    name: String,
+    overall: Record,
+    last_played: Option<NaiveDate>,
+    factions: HashMap<String, Record>,
+}
+
+/// Elo expected score for a player rated `rating` against `opponent`.
+pub fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+/// Player 1's score (1 win, 0.5 draw, 0 loss), from the recorded result
+/// or, failing that, the game points. `None` when the game is unscored.
+fn player1_score(p: &Pairing) -> Option<f64> {
+    match p
+        .player1_result
+        .as_deref()
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        Some("win") => return Some(1.0),
+        Some("loss") => return Some(0.0),
+        Some("draw") => return Some(0.5),
+        _ => {}
+    }
+    let (a, b) = (p.player1_game_points?, p.player2_game_points?);
+    if a == 0 && b == 0 {
+        return None;
+    }
+    Some(match a.cmp(&b) {
+        std::cmp::Ordering::Greater => 1.0,
+        std::cmp::Ordering::Less => 0.0,
+        std::cmp::Ordering::Equal => 0.5,
+    })
+}
+
+fn round1(x: f64) -> f64 {
+    (x * 10.0).round() / 10.0
+}
+
+/// Compute ratings over `epochs`, which must be ordered oldest first.
+pub fn compute_ratings(epochs: &[EpochGames], config: RatingConfig) -> RatingsTable {
+    let mut players: HashMap<String, PlayerState> = HashMap::new();
+    let mut games_rated = 0u32;
+    let mut epoch_ids = Vec::new();
+
+    for epoch in epochs {
+        if !epoch_ids.is_empty() {
+            for state in players.values_mut() {
+                state.overall.decay(config.initial, config.epoch_decay);
+                for record in state.factions.values_mut() {
+                    record.decay(config.initial, config.epoch_decay);
+                }
+            }
+        }
+        epoch_ids.push(epoch.epoch_id.clone());
+
+        let event_dates: HashMap<&str, NaiveDate> = epoch
+            .events
+            .iter()
+            .map(|e| (e.id.as_str(), e.date))
+            .collect();
+        let mut games: Vec<(Option<NaiveDate>, &Pairing)> = epoch
+            .pairings
+            .iter()
+            .map(|p| (event_dates.get(p.event_id.as_str()).copied(), p))
+            .collect();
+        // Undated events sort last
+        games.sort_by(|(da, a), (db, b)| {
+            (da.is_none(), da, a.event_id.as_str(), a.round).cmp(&(
+                db.is_none(),
+                db,
+                b.event_id.as_str(),
+                b.round,
+            ))
+        });
+
+        for (date, pairing) in games {
+            let Some(score1) = player1_score(pairing) else {
+                continue;
+            };
+            let key1 = normalize_player_name(&pairing.player1_name);
+            let key2 = normalize_player_name(&pairing.player2_name);
+            if key1.is_empty() || key2.is_empty() || key1 == key2 || key2 == "bye" {
+                continue;
+            }
+
+            for (key, name) in [
+                (&key1, &pairing.player1_name),
+                (&key2, &pairing.player2_name),
+            ] {
+                players.entry(key.clone()).or_insert_with(|| PlayerState {
+                    name: name.trim().to_string(),
+                    overall: Record::new(config.initial),
+                    ..Default::default()
+                });
+            }
+            let r1 = players[&key1].overall.rating;
+            let r2 = players[&key2].overall.rating;
+
+            for (key, faction, score, own, opponent) in [
+                (&key1, &pairing.player1_faction, score1, r1, r2),
+                (&key2, &pairing.player2_faction, 1.0 - score1, r2, r1),
+            ] {
+                let state = players.get_mut(key).expect("inserted above");
+                state
+                    .overall
+                    .apply(score, expected_score(own, opponent), config.k_factor);
+                state.last_played = state.last_played.max(date);
+
+                if let Some(faction) = faction.as_deref().filter(|f| !f.trim().is_empty()) {
+                    let record = state
+                        .factions
+                        .entry(normalize_faction_name(faction))
+                        .or_insert_with(|| Record::new(config.initial));
+                    let expected = expected_score(record.rating, opponent);
+                    record.apply(score, expected, config.k_factor);
+                }
+            }
+            games_rated += 1;
+        }
+    }
+
+    // Faction averages for the "vs faction" comparison
+    let mut sums: HashMap<&str, (f64, u32)> = HashMap::new();
+    for state in players.values() {
+        for (faction, record) in &state.factions {
+            let entry = sums.entry(faction.as_str()).or_default();
+            entry.0 += record.rating;
+            entry.1 += 1;
+        }
+    }
+    let averages: HashMap<&str, f64> = sums
+        .iter()
+        .map(|(f, (sum, n))| (*f, sum / *n as f64))
+        .collect();
+
+    let mut factions: Vec<FactionAverage> = sums
+        .iter()
+        .map(|(f, (_, n))| FactionAverage {
+            faction: f.to_string(),
+            players: *n,
+            average_rating: round1(averages[f]),
+        })
+        .collect();
+    factions.sort_by(|a, b| {
+        b.average_rating
+            .total_cmp(&a.average_rating)
+            .then_with(|| a.faction.cmp(&b.faction))
+    });
+
+    let mut out: Vec<PlayerRating> = players
+        .values()
+        .map(|state| {
+            let mut splits: Vec<FactionRating> = state
+                .factions
+                .iter()
+                .map(|(faction, r)| FactionRating {
+                    faction: faction.clone(),
+                    rating: round1(r.rating),
+                    games: r.games,
+                    wins: r.wins,
+                    losses: r.losses,
+                    draws: r.draws,
+                    vs_faction_average: round1(r.rating - averages[faction.as_str()]),
+                })
+                .collect();
+            splits.sort_by(|a, b| b.games.cmp(&a.games).then(a.faction.cmp(&b.faction)));
+            PlayerRating {
+                player: state.name.clone(),
+                rating: round1(state.overall.rating),
+                peak_rating: round1(state.overall.peak),
+                games: state.overall.games,
+                wins: state.overall.wins,
+                losses: state.overall.losses,
+                draws: state.overall.draws,
+                last_played: state.last_played,
+                factions: splits,
+            }
+        })
+        .collect();
+    out.sort_by(|a, b| {
+        b.rating
+            .total_cmp(&a.rating)
+            .then_with(|| a.player.cmp(&b.player))
+    });
+
+    RatingsTable {
+        computed_at: Utc::now(),
+        config,
+        epochs: epoch_ids,
+        games_rated,
+        players: out,
+        factions,
+    }
+}
+
+/// Epoch IDs in chronological order: registered epochs first, then any
+/// other epoch directories (e.g. "current") by name.
+fn ordered_epochs(storage: &StorageConfig) -> Result<Vec<String>, StorageError> {
+    let mapper = cached_epoch_mapper(storage);
+    let mut ordered: Vec<String> = mapper
+        .all_epochs()
+        .iter()
+        .map(|e| e.id.as_str().to_string())
+        .collect();
+    let mut extra: Vec<String> = crate::storage::jsonl::list_epochs(storage)?
+        .into_iter()
+        .filter(|id| !ordered.contains(id))
+        .collect();
+    extra.sort();
+    ordered.extend(extra);
+    Ok(ordered)
+}
+
+/// Load every epoch's events and pairings and compute ratings, rating only
+/// games at `game_size` points (events that don't say count as 2000)
+/// unless it is `None`. Excluded events and players are left out.
+pub fn compute_ratings_from_storage(
+    storage: &StorageConfig,
+    config: RatingConfig,
+    game_size: Option<u32>,
+) -> Result<RatingsTable, StorageError> {
+    let mut epochs = Vec::new();
+    for epoch_id in ordered_epochs(storage)? {
+        let pairings = JsonlReader::<Pairing>::for_entity(storage, EntityType::Pairing, &epoch_id)
+            .read_all()?;
+        if pairings.is_empty() {
+            continue;
+        }
+        let events =
+            JsonlReader::<Event>::for_entity(storage, EntityType::Event, &epoch_id).read_all()?;
+        let mut events = merge_by_id(events, |e| e.id.as_str());
+        let mut pairings = merge_by_id(pairings, |p| p.id.as_str());
+        storage.overrides.retain(&mut events);
+        storage.overrides.retain(&mut pairings);
+        if let Some(points) = game_size {
+            let off_size: HashSet<String> = events
+                .iter()
+                .filter(|e| e.game_size.unwrap_or(DEFAULT_GAME_SIZE) != points)
+                .map(|e| e.id.as_str().to_string())
+                .collect();
+            events.retain(|e| !off_size.contains(e.id.as_str()));
+            pairings.retain(|p| !off_size.contains(p.event_id.as_str()));
+        }
+        epochs.push(EpochGames {
+            epoch_id,
+            events,
+            pairings,
+        });
+    }
+    Ok(compute_ratings(&epochs, config))
+}
+
+/// Path of the persisted ratings table.
+pub fn ratings_path(storage: &StorageConfig) -> PathBuf {
+    storage.derived_dir().join("ratings").join("ratings.json")
+}
+
+/// Persist a ratings table.
+pub fn write_ratings(
+    storage: &StorageConfig,
+    table: &RatingsTable,
+) -> Result<PathBuf, StorageError> {
+    let path = ratings_path(storage);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string_pretty(table)?)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(path)
+}
+
+/// Read the persisted ratings table, if one has been computed.
+pub fn read_ratings(storage: &StorageConfig) -> Result<Option<RatingsTable>, StorageError> {
+    let path = ratings_path(storage);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)?;
+    Ok(Some(serde_json::from_str(&content)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EntityId;
+
+    fn event(name: &str, date: &str) -> Event {
+        Event::new(
+            name.to_string(),
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            format!("https://example.com/{}", name),
+            "bcp".to_string(),
+            EntityId::from("e1"),
+        )
+    }
+
+    fn game(
+        event: &Event,
+        round: u32,
+        p1: (&str, &str),
+        p2: (&str, &str),
+        result: &str,
+    ) -> Pairing {
+        let mut p = Pairing::new(
+            event.id.clone(),
+            EntityId::from("e1"),
+            round,
+            p1.0.to_string(),
+            p2.0.to_string(),
+        );
+        p.player1_faction = Some(p1.1.to_string());
+        p.player2_faction = Some(p2.1.to_string());
+        p.player1_result = Some(result.to_string());
+        p
+    }
+
+    fn find<'a>(table: &'a RatingsTable, name: &str) -> &'a PlayerRating {
+        table.players.iter().find(|p| p.player == name).unwrap()
+    }
+
+    #[test]
+    fn test_expected_score_symmetry() {
+        assert!((expected_score(1500.0, 1500.0) - 0.5).abs() < 1e-9);
+        let e = expected_score(1700.0, 1500.0);
+        assert!(e > 0.75 && e < 0.77);
+        assert!((e + expected_score(1500.0, 1700.0) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ratings_update_and_faction_splits() {
+        let gt = event("GT", "2025-03-01");
+        let pairings = vec![
+            game(&gt, 1, ("Alice", "Aeldari"), ("Bob", "Orks"), "win"),
+            game(&gt, 2, ("Alice", "Aeldari"), ("Carol", "Orks"), "win"),
+            game(&gt, 3, ("bob", "Orks"), ("Carol", "Orks"), "draw"),
+            // Unscored games are ignored
+            Pairing::new(
+                gt.id.clone(),
+                EntityId::from("e1"),
+                4,
+                "Alice".to_string(),
+                "Bob".to_string(),
+            ),
+        ];
+        let table = compute_ratings(
+            &[EpochGames {
+                epoch_id: "e1".to_string(),
+                events: vec![gt],
+                pairings,
+            }],
+            RatingConfig::default(),
+        );
+
+        assert_eq!(table.games_rated, 3);
+        assert_eq!(table.players[0].player, "Alice");
+        let alice = find(&table, "Alice");
+        assert_eq!((alice.wins, alice.losses, alice.games), (2, 0, 2));
+        assert!(alice.rating > INITIAL_RATING);
+        assert_eq!(alice.peak_rating, alice.rating);
+        assert_eq!(alice.factions[0].faction, "Aeldari");
+
+        // "bob" and "Bob" are the same player
+        let bob = find(&table, "Bob");
+        assert_eq!((bob.games, bob.draws), (2, 1));
+        assert!(bob.rating < INITIAL_RATING);
+
+        let orks = table.factions.iter().find(|f| f.faction == "Orks").unwrap();
+        assert_eq!(orks.players, 2);
+        assert_eq!(table.factions[0].faction, "Aeldari");
+    }
+
+    #[test]
+    fn test_epoch_decay_pulls_toward_initial() {
+        let gt = event("GT", "2025-03-01");
+        let later = event("Later GT", "2025-07-01");
+        let epochs = vec![
+            EpochGames {
+                epoch_id: "a".to_string(),
+                events: vec![gt.clone()],
+                pairings: vec![game(&gt, 1, ("Alice", "Aeldari"), ("Bob", "Orks"), "win")],
+            },
+            EpochGames {
+                epoch_id: "b".to_string(),
+                events: vec![later.clone()],
+                pairings: vec![game(
+                    &later,
+                    1,
+                    ("Carol", "Necrons"),
+                    ("Dan", "Orks"),
+                    "win",
+                )],
+            },
+        ];
+
+        let no_decay = compute_ratings(&epochs, RatingConfig::default().with_epoch_decay(0.0));
+        let full = compute_ratings(&epochs, RatingConfig::default().with_epoch_decay(1.0));
+
+        assert_eq!(no_decay.epochs, vec!["a", "b"]);
+        assert_eq!(find(&no_decay, "Alice").rating, 1516.0);
+        assert_eq!(find(&full, "Alice").rating, INITIAL_RATING);
+        assert_eq!(find(&full, "Alice").peak_rating, 1516.0);
+        assert_eq!(
+            find(&full, "Alice").last_played,
+            NaiveDate::from_ymd_opt(2025, 3, 1)
+        );
+    }
+
+    #[test]
+    fn test_ratings_roundtrip_from_storage() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        let gt = event("GT", "2025-03-01");
+        let dir = storage.normalized_dir().join("current");
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |file: &str, lines: Vec<String>| {
+            std::fs::write(dir.join(file), lines.join("\n") + "\n").unwrap();
+        };
+        write("events.jsonl", vec![serde_json::to_string(&gt).unwrap()]);
+        write(
+            "pairings.jsonl",
+            vec![serde_json::to_string(&game(
+                &gt,
+                1,
+                ("Alice", "Aeldari"),
+                ("Bob", "Orks"),
+                "loss",
+            ))
+            .unwrap()],
+        );
+
+        assert!(read_ratings(&storage).unwrap().is_none());
+        let table =
+            compute_ratings_from_storage(&storage, RatingConfig::default(), Some(2000)).unwrap();
+        assert_eq!(table.epochs, vec!["current"]);
+        write_ratings(&storage, &table).unwrap();
+
+        let read = read_ratings(&storage).unwrap().unwrap();
+        assert_eq!(read.players[0].player, "Bob");
+        assert_eq!(read.games_rated, 1);
+
+        let overrides =
+            crate::storage::Overrides::parse("[exclude]\nplayers = [\"alice\"]\n").unwrap();
+        let storage = storage.with_overrides(std::sync::Arc::new(overrides));
+        let table =
+            compute_ratings_from_storage(&storage, RatingConfig::default(), Some(2000)).unwrap();
+        assert_eq!(table.games_rated, 0);
+        assert!(table.players.is_empty());
+    }
+}
@@ -0,0 +1,452 @@
+//! Meta shift candidates (experimental).
+//!
+//! Builds a weekly win-rate series per faction from placement records and
+//! runs binary segmentation over it: the split with the largest two-sample
+//! z-score is kept if it clears [`ShiftConfig::min_z`], then both halves are
+//! searched again. Breakpoints within a few days of a registered balance pass
+//! or edition are explained by it and dropped; what's left are dates where
+//! the meta moved on its own (a breakout list, a new codex) and that may be
+//! worth an annotation.
+//!
+//! The report is persisted to `derived/shifts/shift_candidates.json` and,
+//! like the derived artifacts, covers 2000-point games.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::detachments::GameRecord;
+use super::validation::DEFAULT_GAME_SIZE;
+use crate::api::merge_by_id;
+use crate::models::normalize_faction_name;
+use crate::models::EntityType as ReviewEntityType;
+use crate::models::{EntityId, Event, Placement, ReviewQueueItem, ReviewReason, SignificantEvent};
+use crate::storage::{
+    cached_significant_events, EntityType, JsonlReader, StorageConfig, StorageError,
+};
+
+/// Tunables for [`detect_shifts`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ShiftConfig {
+    /// Weeks of data required on each side of a breakpoint
+    pub min_segment_weeks: usize,
+    /// Games required on each side of a breakpoint
+    pub min_games: u32,
+    /// Smallest |z| accepted as a breakpoint
+    pub min_z: f64,
+    /// Breakpoints this close to a registered balance pass are explained by it
+    pub boundary_window_days: i64,
+}
+
+impl Default for ShiftConfig {
+    fn default() -> Self {
+        Self {
+            min_segment_weeks: 3,
+            min_games: 30,
+            min_z: 3.0,
+            boundary_window_days: 14,
+        }
+    }
+}
+
+/// A faction's games in one week (Monday start).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WeeklyPoint {
+    pub week_start: NaiveDate,
+    #[serde(flatten)]
+    pub record: GameRecord,
+}
+
+/// A suggested meta shift date for one faction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShiftCandidate {
+    /// Stable ID (faction + date), used for the review queue
+    pub id: String,
+    pub faction: String,
+    /// First week at the new level
+    pub date: NaiveDate,
+    /// Percent, draws counted as half a win
+    pub win_rate_before: f64,
+    pub win_rate_after: f64,
+    pub delta: f64,
+    pub z_score: f64,
+    pub games_before: u32,
+    pub games_after: u32,
+    /// Closest registered balance pass or edition, if any
+    pub nearest_boundary: Option<NearestBoundary>,
+}
+
+/// A registered epoch boundary near a candidate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NearestBoundary {
+    pub title: String,
+    pub date: NaiveDate,
+    /// Candidate date minus boundary date
+    pub days: i64,
+}
+
+/// Output of a detection run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShiftReport {
+    pub computed_at: DateTime<Utc>,
+    pub config: ShiftConfig,
+    pub factions_scanned: u32,
+    /// Breakpoints dropped because a registered boundary explains them
+    pub explained_by_boundary: u32,
+    /// Unexplained breakpoints, newest first
+    pub candidates: Vec<ShiftCandidate>,
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Weekly game records per faction from placement records, oldest week first.
+pub fn weekly_series(
+    events: &[Event],
+    placements: &[Placement],
+) -> BTreeMap<String, Vec<WeeklyPoint>> {
+    let dates: HashMap<&str, NaiveDate> = events.iter().map(|e| (e.id.as_str(), e.date)).collect();
+    let mut weeks: BTreeMap<String, BTreeMap<NaiveDate, GameRecord>> = BTreeMap::new();
+    for p in placements {
+        let (Some(record), Some(date)) = (&p.record, dates.get(p.event_id.as_str())) else {
+            continue;
+        };
+        weeks
+            .entry(normalize_faction_name(&p.faction))
+            .or_default()
+            .entry(week_start(*date))
+            .or_default()
+            .add(&GameRecord {
+                wins: record.wins,
+                losses: record.losses,
+                draws: record.draws,
+            });
+    }
+    weeks
+        .into_iter()
+        .map(|(faction, weeks)| {
+            let points = weeks
+                .into_iter()
+                .map(|(week_start, record)| WeeklyPoint { week_start, record })
+                .collect();
+            (faction, points)
+        })
+        .collect()
+}
+
+fn score(r: &GameRecord) -> f64 {
+    r.wins as f64 + r.draws as f64 * 0.5
+}
+
+/// Two-proportion z-score of `after` against `before`.
+fn z_score(before: &GameRecord, after: &GameRecord) -> f64 {
+    let (n1, n2) = (before.games() as f64, after.games() as f64);
+    if n1 == 0.0 || n2 == 0.0 {
+        return 0.0;
+    }
+    let pooled = (score(before) + score(after)) / (n1 + n2);
+    let variance = pooled * (1.0 - pooled) * (1.0 / n1 + 1.0 / n2);
+    if variance <= 0.0 {
+        return 0.0;
+    }
+    (score(after) / n2 - score(before) / n1) / variance.sqrt()
+}
+
+fn sum(points: &[WeeklyPoint]) -> GameRecord {
+    let mut total = GameRecord::default();
+    for p in points {
+        total.add(&p.record);
+    }
+    total
+}
+
+/// Breakpoints in a weekly series as `(index, z)`, where `index` is the
+/// first week after the shift. Sorted by index.
+pub fn detect_shifts(series: &[WeeklyPoint], config: &ShiftConfig) -> Vec<(usize, f64)> {
+    fn segment(
+        series: &[WeeklyPoint],
+        lo: usize,
+        hi: usize,
+        config: &ShiftConfig,
+        out: &mut Vec<(usize, f64)>,
+    ) {
+        let min_weeks = config.min_segment_weeks.max(1);
+        if hi - lo < min_weeks * 2 {
+            return;
+        }
+        let mut best: Option<(usize, f64)> = None;
+        for k in (lo + min_weeks)..=(hi - min_weeks) {
+            let (before, after) = (sum(&series[lo..k]), sum(&series[k..hi]));
+            if before.games() < config.min_games || after.games() < config.min_games {
+                continue;
+            }
+            let z = z_score(&before, &after);
+            if best.is_none_or(|(_, b)| z.abs() > b.abs()) {
+                best = Some((k, z));
+            }
+        }
+        if let Some((k, z)) = best.filter(|(_, z)| z.abs() >= config.min_z) {
+            out.push((k, z));
+            segment(series, lo, k, config, out);
+            segment(series, k, hi, config, out);
+        }
+    }
+
+    let mut out = Vec::new();
+    segment(series, 0, series.len(), config, &mut out);
+    out.sort_by_key(|(k, _)| *k);
+    out
+}
+
+fn round1(x: f64) -> f64 {
+    (x * 10.0).round() / 10.0
+}
+
+/// Candidates from weekly series. Breakpoints within the boundary window of
+/// a `(title, date)` boundary are counted as explained and left out.
+pub fn shift_candidates(
+    series: &BTreeMap<String, Vec<WeeklyPoint>>,
+    boundaries: &[(String, NaiveDate)],
+    config: ShiftConfig,
+) -> ShiftReport {
+    let mut candidates = Vec::new();
+    let mut explained = 0;
+    for (faction, points) in series {
+        let breaks = detect_shifts(points, &config);
+        // Before/after levels are measured between neighbouring breakpoints
+        let mut edges: Vec<usize> = vec![0];
+        edges.extend(breaks.iter().map(|(k, _)| *k));
+        edges.push(points.len());
+
+        for (i, (k, z)) in breaks.iter().enumerate() {
+            let date = points[*k].week_start;
+            let nearest = boundaries
+                .iter()
+                .map(|(title, d)| NearestBoundary {
+                    title: title.clone(),
+                    date: *d,
+                    days: (date - *d).num_days(),
+                })
+                .min_by_key(|b| b.days.abs());
+            if nearest
+                .as_ref()
+                .is_some_and(|b| b.days.abs() <= config.boundary_window_days)
+            {
+                explained += 1;
+                continue;
+            }
+
+            let before = sum(&points[edges[i]..*k]);
+            let after = sum(&points[*k..edges[i + 2]]);
+            let (rate_before, rate_after) = (
+                before.win_rate().unwrap_or(0.0),
+                after.win_rate().unwrap_or(0.0),
+            );
+            let date_str = date.to_string();
+            candidates.push(ShiftCandidate {
+                id: EntityId::generate(&["meta_shift", faction, &date_str])
+                    .as_str()
+                    .to_string(),
+                faction: faction.clone(),
+                date,
+                win_rate_before: rate_before,
+                win_rate_after: rate_after,
+                delta: round1(rate_after - rate_before),
+                z_score: (z * 100.0).round() / 100.0,
+                games_before: before.games(),
+                games_after: after.games(),
+                nearest_boundary: nearest,
+            });
+        }
+    }
+    candidates.sort_by(|a, b| {
+        b.date
+            .cmp(&a.date)
+            .then_with(|| b.z_score.abs().total_cmp(&a.z_score.abs()))
+    });
+
+    ShiftReport {
+        computed_at: Utc::now(),
+        config,
+        factions_scanned: series.len() as u32,
+        explained_by_boundary: explained,
+        candidates,
+    }
+}
+
+/// Boundary dates of registered balance passes and editions.
+pub fn epoch_boundaries(events: &[SignificantEvent]) -> Vec<(String, NaiveDate)> {
+    events
+        .iter()
+        .filter(|e| e.event_type.is_epoch_boundary())
+        .map(|e| (e.title.clone(), e.boundary_date()))
+        .collect()
+}
+
+/// Load every epoch's events and placements and detect shift candidates,
+/// counting only games at `game_size` points (events that don't say count
+/// as 2000) unless it is `None`. Excluded events and players are left out.
+pub fn compute_shifts_from_storage(
+    storage: &StorageConfig,
+    config: ShiftConfig,
+    game_size: Option<u32>,
+) -> Result<ShiftReport, StorageError> {
+    let mut events = Vec::new();
+    let mut placements = Vec::new();
+    for epoch_id in crate::storage::jsonl::list_epochs(storage)? {
+        events.extend(
+            JsonlReader::<Event>::for_entity(storage, EntityType::Event, &epoch_id).read_all()?,
+        );
+        placements.extend(
+            JsonlReader::<Placement>::for_entity(storage, EntityType::Placement, &epoch_id)
+                .read_all()?,
+        );
+    }
+    let mut events = merge_by_id(events, |e| e.id.as_str());
+    let mut placements = merge_by_id(placements, |p| p.id.as_str());
+    storage.overrides.retain(&mut events);
+    storage.overrides.retain(&mut placements);
+    if let Some(points) = game_size {
+        let off_size: HashSet<String> = events
+            .iter()
+            .filter(|e| e.game_size.unwrap_or(DEFAULT_GAME_SIZE) != points)
+            .map(|e| e.id.as_str().to_string())
+            .collect();
+        events.retain(|e| !off_size.contains(e.id.as_str()));
+        placements.retain(|p| !off_size.contains(p.event_id.as_str()));
+    }
+    let boundaries = epoch_boundaries(&cached_significant_events(storage)?);
+    Ok(shift_candidates(
+        &weekly_series(&events, &placements),
+        &boundaries,
+        config,
+    ))
+}
+
+/// Path of the persisted shift report.
+pub fn shifts_path(storage: &StorageConfig) -> PathBuf {
+    storage
+        .derived_dir()
+        .join("shifts")
+        .join("shift_candidates.json")
+}
+
+/// Persist a shift report.
+pub fn write_shifts(
+    storage: &StorageConfig,
+    report: &ShiftReport,
+) -> Result<PathBuf, StorageError> {
+    let path = shifts_path(storage);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string_pretty(report)?)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(path)
+}
+
+/// Read the persisted shift report, if one has been computed.
+pub fn read_shifts(storage: &StorageConfig) -> Result<Option<ShiftReport>, StorageError> {
+    let path = shifts_path(storage);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)?;
+    Ok(Some(serde_json::from_str(&content)?))
+}
+
+/// Add candidates not already in the review queue as potential timeline
+/// annotations. Returns the number queued.
+pub fn queue_for_review(
+    storage: &StorageConfig,
+    report: &ShiftReport,
+) -> Result<usize, StorageError> {
+    let mut items = crate::storage::read_review_items(storage)?;
+    let queued: HashSet<String> = items
+        .iter()
+        .filter(|i| i.reason == ReviewReason::MetaShiftCandidate)
+        .map(|i| i.entity_id.as_str().to_string())
+        .collect();
+
+    let mut added = 0;
+    for c in &report.candidates {
+        if queued.contains(&c.id) {
+            continue;
+        }
+        items.push(ReviewQueueItem::new(
+            ReviewEntityType::SignificantEvent,
+            EntityId::from(c.id.as_str()),
+            ReviewReason::MetaShiftCandidate,
+            format!(
+                "{} win rate moved {:+.1} points ({:.1}% -> {:.1}%, z = {:.2}) from the week \
+                 of {}, with no balance pass nearby",
+                c.faction, c.delta, c.win_rate_before, c.win_rate_after, c.z_score, c.date
+            ),
+        ));
+        added += 1;
+    }
+    if added > 0 {
+        crate::storage::write_review_items(storage, &items)?;
+    }
+    Ok(added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn week(i: i64, wins: u32, losses: u32) -> WeeklyPoint {
+        WeeklyPoint {
+            week_start: NaiveDate::from_ymd_opt(2026, 1, 5).unwrap() + Duration::weeks(i),
+            record: GameRecord {
+                wins,
+                losses,
+                draws: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_detects_shift_and_drops_explained_ones() {
+        // 45% for six weeks, then 65%
+        let points: Vec<WeeklyPoint> = (0..6)
+            .map(|i| week(i, 18, 22))
+            .chain((6..12).map(|i| week(i, 26, 14)))
+            .collect();
+        let flat: Vec<WeeklyPoint> = (0..12).map(|i| week(i, 20, 20)).collect();
+        let config = ShiftConfig::default();
+
+        let breaks = detect_shifts(&points, &config);
+        assert_eq!(breaks.len(), 1);
+        assert_eq!(breaks[0].0, 6);
+        assert!(breaks[0].1 > 3.0);
+        assert!(detect_shifts(&flat, &config).is_empty());
+
+        let series: BTreeMap<String, Vec<WeeklyPoint>> = [
+            ("Aeldari".to_string(), points.clone()),
+            ("Orks".to_string(), flat),
+        ]
+        .into_iter()
+        .collect();
+        let report = shift_candidates(&series, &[], config);
+        assert_eq!(report.factions_scanned, 2);
+        assert_eq!(report.candidates.len(), 1);
+        let c = &report.candidates[0];
+        assert_eq!(c.faction, "Aeldari");
+        assert_eq!(c.date, points[6].week_start);
+        assert_eq!(c.win_rate_before, 45.0);
+        assert_eq!(c.win_rate_after, 65.0);
+        assert_eq!(c.delta, 20.0);
+        assert_eq!(c.games_before, 240);
+
+        // A balance pass the Friday before explains it
+        let pass = points[6].week_start - Duration::days(3);
+        let report = shift_candidates(&series, &[("Balance Dataslate".to_string(), pass)], config);
+        assert!(report.candidates.is_empty());
+        assert_eq!(report.explained_by_boundary, 1);
+    }
+}
@@ -0,0 +1,239 @@
+//! Army list similarity.
+//!
+//! Lists are compared as multisets of unit names, so two lists that both
+//! take three Eradicator squads are closer than one taking three and one
+//! taking a single squad. Two measures are offered:
+//!
+//! - Jaccard: copies in common over copies in either (sum of minimums over
+//!   sum of maximums)
+//! - Cosine: the angle between the lists' copy-count vectors, which cares
+//!   less about list size
+//!
+//! Both run from 0.0 (nothing in common) to 1.0 (the same units).
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::analytics::join_lists_to_placements;
+use crate::models::normalize_faction_name;
+use crate::models::{ArmyList, Placement, WinLossRecord};
+
+/// How two lists' units are compared.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SimilarityMetric {
+    #[default]
+    Jaccard,
+    Cosine,
+}
+
+impl std::str::FromStr for SimilarityMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "jaccard" => Ok(SimilarityMetric::Jaccard),
+            "cosine" => Ok(SimilarityMetric::Cosine),
+            other => Err(format!(
+                "Unknown metric: {} (expected jaccard or cosine)",
+                other
+            )),
+        }
+    }
+}
+
+/// Copies of each unit in a list.
+pub fn unit_multiset(list: &ArmyList) -> HashMap<&str, u32> {
+    let mut copies = HashMap::new();
+    for unit in &list.units {
+        *copies.entry(unit.name.as_str()).or_default() += 1;
+    }
+    copies
+}
+
+/// Similarity of two unit multisets.
+pub fn multiset_similarity(
+    a: &HashMap<&str, u32>,
+    b: &HashMap<&str, u32>,
+    metric: SimilarityMetric,
+) -> f64 {
+    let names: HashSet<&str> = a.keys().chain(b.keys()).copied().collect();
+    let count = |m: &HashMap<&str, u32>, n: &str| m.get(n).copied().unwrap_or(0) as f64;
+    match metric {
+        SimilarityMetric::Jaccard => {
+            let (mut shared, mut either) = (0.0, 0.0);
+            for name in names {
+                shared += count(a, name).min(count(b, name));
+                either += count(a, name).max(count(b, name));
+            }
+            if either == 0.0 {
+                0.0
+            } else {
+                shared / either
+            }
+        }
+        SimilarityMetric::Cosine => {
+            let dot: f64 = names.iter().map(|n| count(a, n) * count(b, n)).sum();
+            let norm = |m: &HashMap<&str, u32>| {
+                m.values().map(|&c| (c as f64).powi(2)).sum::<f64>().sqrt()
+            };
+            let denom = norm(a) * norm(b);
+            if denom == 0.0 {
+                0.0
+            } else {
+                dot / denom
+            }
+        }
+    }
+}
+
+/// Options for [`similar_lists`].
+#[derive(Debug, Clone, Copy)]
+pub struct SimilarityOptions {
+    pub metric: SimilarityMetric,
+    /// Only compare against lists of the target's faction
+    pub same_faction: bool,
+    pub limit: usize,
+}
+
+impl Default for SimilarityOptions {
+    fn default() -> Self {
+        Self {
+            metric: SimilarityMetric::default(),
+            same_faction: true,
+            limit: 10,
+        }
+    }
+}
+
+/// A list similar to the target, with how it did.
+#[derive(Debug, Clone, Serialize)]
+pub struct SimilarList {
+    pub list_id: String,
+    pub similarity: f64,
+    pub faction: String,
+    pub detachment: Option<String>,
+    pub player_name: Option<String>,
+    pub event_id: Option<String>,
+    /// Final rank, when the list is joined to a placement
+    pub rank: Option<u32>,
+    pub record: Option<WinLossRecord>,
+}
+
+/// The lists most similar to `target`, best first. The target itself and
+/// cloned submissions of it are skipped, as are lists sharing no units.
+pub fn similar_lists(
+    target: &ArmyList,
+    lists: &[ArmyList],
+    placements: &[Placement],
+    options: SimilarityOptions,
+) -> Vec<SimilarList> {
+    let faction = normalize_faction_name(&target.faction);
+    let target_units = unit_multiset(target);
+    let same_submission = |l: &ArmyList| {
+        l.id == target.id
+            || l.clone_of.as_ref() == Some(&target.id)
+            || target.clone_of.as_ref() == Some(&l.id)
+    };
+
+    let mut scored: Vec<(&ArmyList, f64)> = lists
+        .iter()
+        .filter(|l| !same_submission(l))
+        .filter(|l| !options.same_faction || normalize_faction_name(&l.faction) == faction)
+        .map(|l| {
+            let score = multiset_similarity(&target_units, &unit_multiset(l), options.metric);
+            (l, score)
+        })
+        .filter(|(_, score)| *score > 0.0)
+        .collect();
+    scored.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.0.id.as_str().cmp(b.0.id.as_str()))
+    });
+    scored.truncate(options.limit);
+
+    let top: Vec<ArmyList> = scored.iter().map(|(l, _)| (*l).clone()).collect();
+    let placed: HashMap<String, Placement> = join_lists_to_placements(&top, placements)
+        .into_iter()
+        .map(|(l, p)| (l.id.as_str().to_string(), p))
+        .collect();
+
+    scored
+        .into_iter()
+        .map(|(list, score)| {
+            let placement = placed.get(list.id.as_str());
+            SimilarList {
+                list_id: list.id.as_str().to_string(),
+                similarity: (score * 1000.0).round() / 1000.0,
+                faction: normalize_faction_name(&list.faction),
+                detachment: list.detachment.clone(),
+                player_name: list.player_name.clone(),
+                event_id: placement
+                    .map(|p| p.event_id.as_str().to_string())
+                    .or_else(|| list.event_id.as_ref().map(|e| e.as_str().to_string())),
+                rank: placement.map(|p| p.rank),
+                record: placement.and_then(|p| p.record.clone()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Unit;
+
+    fn list(faction: &str, units: &[&str]) -> ArmyList {
+        ArmyList::new(
+            faction.to_string(),
+            2000,
+            units.iter().map(|u| Unit::new(u.to_string(), 1)).collect(),
+            format!("{} {}", faction, units.join(",")),
+        )
+    }
+
+    #[test]
+    fn test_similar_lists_ranks_by_unit_overlap() {
+        let target = list("Space Marines", &["Captain", "Eradicators", "Eradicators"]);
+        let close = list(
+            "Space Marines",
+            &["Captain", "Eradicators", "Eradicators", "Scouts"],
+        );
+        let far = list("Space Marines", &["Captain", "Eradicators", "Gladiator"]);
+        let other_faction = list("Dark Angels", &["Captain", "Eradicators", "Eradicators"]);
+        let unrelated = list("Space Marines", &["Scouts"]);
+        let lists = vec![
+            target.clone(),
+            far.clone(),
+            close.clone(),
+            other_faction.clone(),
+            unrelated,
+        ];
+
+        let a = unit_multiset(&target);
+        let b = unit_multiset(&close);
+        assert!((multiset_similarity(&a, &b, SimilarityMetric::Jaccard) - 0.75).abs() < 1e-9);
+        assert!(
+            (multiset_similarity(&a, &b, SimilarityMetric::Cosine)
+                - 5.0 / (5.0f64.sqrt() * 6.0f64.sqrt()))
+            .abs()
+                < 1e-9
+        );
+
+        let found = similar_lists(&target, &lists, &[], SimilarityOptions::default());
+        let ids: Vec<&str> = found.iter().map(|s| s.list_id.as_str()).collect();
+        assert_eq!(ids, vec![close.id.as_str(), far.id.as_str()]);
+        assert_eq!(found[0].similarity, 0.75);
+        assert!(found[0].rank.is_none());
+
+        let options = SimilarityOptions {
+            same_faction: false,
+            ..Default::default()
+        };
+        let found = similar_lists(&target, &lists, &[], options);
+        assert_eq!(found[0].list_id, other_faction.id.as_str());
+        assert_eq!(found[0].similarity, 1.0);
+    }
+}
@@ -0,0 +1,329 @@
+//! Strength of schedule and round-weighted results from pairings.
+//!
+//! Each event's rounds are replayed in order. A player's strength of
+//! schedule is the mean final win rate of the opponents they met at those
+//! events, leaving out each opponent's game against them. Every result is
+//! also weighted by how the opponent stood going into the round: a game
+//! against a player on 4-0 weighs 1.5, against one on 0-4 0.5, and a first
+//! round game 1. A weighted win rate above the plain one means the wins
+//! came on the top tables. Factions are credited the same way through
+//! their players, with a per-round breakdown so late fades show up.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::calculate::detachments::GameRecord;
+use crate::calculate::matchups::FactionResolver;
+use crate::models::{ArmyList, Pairing, Placement};
+use crate::sync::normalize_player_name;
+
+/// A side's record in one round.
+#[derive(Debug, Clone, Serialize)]
+pub struct RoundRecord {
+    pub round: u32,
+    #[serde(flatten)]
+    pub record: GameRecord,
+    pub win_rate: Option<f64>,
+}
+
+/// Schedule-aware results for a player or faction.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleStats {
+    #[serde(flatten)]
+    pub record: GameRecord,
+    pub games: u32,
+    /// Percent with draws as half a win
+    pub win_rate: Option<f64>,
+    /// Mean win rate of the opponents faced, in percent
+    pub strength_of_schedule: Option<f64>,
+    /// Win rate with each game weighted by the opponent's standing going
+    /// into the round, in percent
+    pub weighted_win_rate: Option<f64>,
+    pub rounds: Vec<RoundRecord>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlayerSchedule {
+    pub player: String,
+    pub events: u32,
+    #[serde(flatten)]
+    pub stats: ScheduleStats,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FactionSchedule {
+    pub faction: String,
+    pub players: u32,
+    #[serde(flatten)]
+    pub stats: ScheduleStats,
+}
+
+/// Schedules for every player and faction seen in the pairings, highest
+/// weighted win rate first.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Schedules {
+    pub players: Vec<PlayerSchedule>,
+    pub factions: Vec<FactionSchedule>,
+}
+
+fn percent(fraction: f64) -> f64 {
+    (fraction * 1000.0).round() / 10.0
+}
+
+#[derive(Default)]
+struct Accumulator {
+    record: GameRecord,
+    opponent_rates: f64,
+    opponents: u32,
+    weighted_score: f64,
+    weight: f64,
+    rounds: BTreeMap<u32, GameRecord>,
+}
+
+impl Accumulator {
+    fn add(&mut self, round: u32, score: f64, opponent_before: f64, opponent_rate: Option<f64>) {
+        let result = if score == 1.0 {
+            GameRecord {
+                wins: 1,
+                ..Default::default()
+            }
+        } else if score == 0.0 {
+            GameRecord {
+                losses: 1,
+                ..Default::default()
+            }
+        } else {
+            GameRecord {
+                draws: 1,
+                ..Default::default()
+            }
+        };
+        self.record.add(&result);
+        self.rounds.entry(round).or_default().add(&result);
+        if let Some(rate) = opponent_rate {
+            self.opponent_rates += rate;
+            self.opponents += 1;
+        }
+        let weight = 0.5 + opponent_before;
+        self.weighted_score += weight * score;
+        self.weight += weight;
+    }
+
+    fn finish(self) -> ScheduleStats {
+        ScheduleStats {
+            games: self.record.games(),
+            win_rate: self.record.win_rate(),
+            strength_of_schedule: (self.opponents > 0)
+                .then(|| percent(self.opponent_rates / self.opponents as f64)),
+            weighted_win_rate: (self.weight > 0.0)
+                .then(|| percent(self.weighted_score / self.weight)),
+            record: self.record,
+            rounds: self
+                .rounds
+                .into_iter()
+                .map(|(round, record)| RoundRecord {
+                    round,
+                    win_rate: record.win_rate(),
+                    record,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Points and games, for a running or final event score.
+type Score = (f64, u32);
+
+fn rate((points, games): Score) -> Option<f64> {
+    (games > 0).then(|| points / games as f64)
+}
+
+/// Strength of schedule and weighted results for every player and faction.
+pub fn strength_of_schedule(
+    pairings: &[Pairing],
+    placements: &[Placement],
+    lists: &[ArmyList],
+) -> Schedules {
+    let resolver = FactionResolver::new(placements, lists);
+
+    let mut by_event: BTreeMap<&str, Vec<&Pairing>> = BTreeMap::new();
+    for pairing in pairings {
+        by_event
+            .entry(pairing.event_id.as_str())
+            .or_default()
+            .push(pairing);
+    }
+
+    let mut players: HashMap<String, (String, HashSet<String>, Accumulator)> = HashMap::new();
+    let mut factions: HashMap<String, (HashSet<String>, Accumulator)> = HashMap::new();
+
+    for (event_id, mut games) in by_event {
+        games.sort_by_key(|p| p.round);
+        // (pairing, player 1 key, player 2 key, player 1 score)
+        let games: Vec<(&Pairing, String, String, f64)> = games
+            .into_iter()
+            .filter_map(|p| {
+                let score = match p.player1_result.as_deref() {
+                    Some("win") => 1.0,
+                    Some("loss") => 0.0,
+                    Some("draw") => 0.5,
+                    _ => return None,
+                };
+                Some((
+                    p,
+                    normalize_player_name(&p.player1_name),
+                    normalize_player_name(&p.player2_name),
+                    score,
+                ))
+            })
+            .collect();
+
+        let mut finals: HashMap<&str, Score> = HashMap::new();
+        for (_, p1, p2, score) in &games {
+            for (key, points) in [(p1, *score), (p2, 1.0 - score)] {
+                let total = finals.entry(key.as_str()).or_default();
+                total.0 += points;
+                total.1 += 1;
+            }
+        }
+
+        let mut standing: HashMap<&str, Score> = HashMap::new();
+        for round in games.chunk_by(|a, b| a.0.round == b.0.round) {
+            let mut results = Vec::new();
+            for (pairing, p1, p2, score) in round {
+                let sides = [
+                    (
+                        p1,
+                        &pairing.player1_name,
+                        pairing.player1_faction.as_deref(),
+                        p2,
+                        *score,
+                    ),
+                    (
+                        p2,
+                        &pairing.player2_name,
+                        pairing.player2_faction.as_deref(),
+                        p1,
+                        1.0 - score,
+                    ),
+                ];
+                for (key, name, faction, opponent, score) in sides {
+                    let before = standing
+                        .get(opponent.as_str())
+                        .copied()
+                        .and_then(rate)
+                        .unwrap_or(0.5);
+                    let (points, played) = finals[opponent.as_str()];
+                    let opponent_rate = rate((points - (1.0 - score), played - 1));
+
+                    let player = players
+                        .entry(key.clone())
+                        .or_insert_with(|| (name.clone(), HashSet::new(), Accumulator::default()));
+                    player.1.insert(event_id.to_string());
+                    player.2.add(pairing.round, score, before, opponent_rate);
+
+                    if let Some(faction) = resolver.faction(pairing, name, faction) {
+                        let entry = factions.entry(faction).or_default();
+                        entry.0.insert(key.clone());
+                        entry.1.add(pairing.round, score, before, opponent_rate);
+                    }
+                    results.push((key.as_str(), score));
+                }
+            }
+            for (key, score) in results {
+                let total = standing.entry(key).or_default();
+                total.0 += score;
+                total.1 += 1;
+            }
+        }
+    }
+
+    let by_weighted = |a: &ScheduleStats, b: &ScheduleStats| {
+        b.weighted_win_rate
+            .partial_cmp(&a.weighted_win_rate)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    };
+    let mut players: Vec<PlayerSchedule> = players
+        .into_values()
+        .map(|(player, events, acc)| PlayerSchedule {
+            player,
+            events: events.len() as u32,
+            stats: acc.finish(),
+        })
+        .collect();
+    players.sort_by(|a, b| by_weighted(&a.stats, &b.stats).then(a.player.cmp(&b.player)));
+    let mut factions: Vec<FactionSchedule> = factions
+        .into_iter()
+        .map(|(faction, (players, acc))| FactionSchedule {
+            faction,
+            players: players.len() as u32,
+            stats: acc.finish(),
+        })
+        .collect();
+    factions.sort_by(|a, b| by_weighted(&a.stats, &b.stats).then(a.faction.cmp(&b.faction)));
+
+    Schedules { players, factions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EntityId;
+
+    fn game(round: u32, p1: &str, f1: &str, p2: &str, f2: &str, result: &str) -> Pairing {
+        let mut p = Pairing::new(
+            EntityId::from("gt"),
+            EntityId::from("current"),
+            round,
+            p1.to_string(),
+            p2.to_string(),
+        );
+        p.player1_faction = Some(f1.to_string());
+        p.player2_faction = Some(f2.to_string());
+        p.player1_result = Some(result.to_string());
+        p
+    }
+
+    #[test]
+    fn test_strength_of_schedule() {
+        // Alice beats Bob in round 1, then Carol (1-0 after beating Dave) in
+        // round 2; Dave beats Bob in round 2, a game between 0-1 players.
+        let pairings = vec![
+            game(1, "Alice", "Aeldari", "Bob", "Orks", "win"),
+            game(1, "Carol", "Necrons", "Dave", "Orks", "win"),
+            game(2, "Alice", "Aeldari", "Carol", "Necrons", "win"),
+            game(2, "Dave", "Orks", "Bob", "Orks", "win"),
+        ];
+        let schedules = strength_of_schedule(&pairings, &[], &[]);
+
+        let player = |name: &str| {
+            schedules
+                .players
+                .iter()
+                .find(|p| p.player == name)
+                .unwrap()
+                .stats
+                .clone()
+        };
+        let alice = player("Alice");
+        assert_eq!(alice.win_rate, Some(100.0));
+        // Bob's other game a loss (0%), Carol's a win (100%)
+        assert_eq!(alice.strength_of_schedule, Some(50.0));
+        let dave = player("Dave");
+        // Weighted: loss to Carol at weight 1, win over 0-1 Bob at 0.5
+        assert_eq!(dave.weighted_win_rate, Some(33.3));
+        assert_eq!(dave.win_rate, Some(50.0));
+        assert_eq!(schedules.players[0].player, "Alice");
+
+        let orks = schedules
+            .factions
+            .iter()
+            .find(|f| f.faction == "Orks")
+            .unwrap();
+        assert_eq!(orks.players, 2);
+        assert_eq!(orks.stats.games, 4);
+        assert_eq!(orks.stats.rounds.len(), 2);
+        assert_eq!(orks.stats.rounds[1].win_rate, Some(50.0));
+    }
+}
@@ -0,0 +1,272 @@
+//! Composite faction tier list.
+//!
+//! Win rate alone over-rewards factions with a handful of lucky games and
+//! ignores whether a faction actually converts into top finishes. Each
+//! faction's tier here comes from a weighted composite of three signals,
+//! each shrunk toward neutral by its sample size:
+//!
+//! | Signal | Source | Neutral | Scale | Weight |
+//! |--------|--------|---------|-------|--------|
+//! | Win rate | non-mirror games from pairings | 50% | 5 points | 0.5 |
+//! | Title over-representation | share of event wins vs meta share ([`calculate_over_representation`]) | 1.0 | 0.5 | 0.25 |
+//! | Podium rate | top-4 finishes per player ([`calculate_podium_rate`]) vs the field's | field rate | ±50% of it | 0.25 |
+//!
+//! Shrinkage blends each signal with its neutral value as if
+//! [`WIN_RATE_PRIOR_GAMES`] games (or [`PLACEMENT_PRIOR_PLAYERS`] players)
+//! at the neutral value had been added, so small samples sit near the
+//! middle. Each shrunk signal is divided by its scale and the weighted sum
+//! is the `score`: S from 1.0, A from 0.35, B from -0.35, C from -1.0, D
+//! below. Factions with fewer than [`TIER_LIST_MIN_GAMES`] games are
+//! marked `provisional`.
+//!
+//! [`calculate_over_representation`]: super::calculate_over_representation
+//! [`calculate_podium_rate`]: super::calculate_podium_rate
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::normalize_faction_name;
+use crate::models::{Pairing, Placement, Tier};
+
+use super::derive::TIER_LIST_MIN_GAMES;
+use super::{calculate_over_representation, calculate_podium_rate};
+
+/// Imaginary 50% games added to each faction's pairing record.
+pub const WIN_RATE_PRIOR_GAMES: f64 = 40.0;
+
+/// Imaginary neutral players added to each faction's placement signals.
+pub const PLACEMENT_PRIOR_PLAYERS: f64 = 30.0;
+
+const WIN_RATE_WEIGHT: f64 = 0.5;
+const OVER_REPRESENTATION_WEIGHT: f64 = 0.25;
+const PODIUM_WEIGHT: f64 = 0.25;
+
+const WIN_RATE_SCALE: f64 = 0.05;
+const OVER_REPRESENTATION_SCALE: f64 = 0.5;
+
+/// One faction's place on the composite tier list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactionTier {
+    pub faction: String,
+    pub tier: Tier,
+    /// Weighted composite (0 = average)
+    pub score: f64,
+    /// Non-mirror games from pairings
+    pub games: u32,
+    /// Raw win rate from pairings (0.0 to 1.0, draws count half)
+    pub win_rate: f64,
+    /// Win rate after shrinkage toward 50%
+    pub adjusted_win_rate: f64,
+    pub players: u32,
+    pub event_wins: u32,
+    /// Share of event wins over share of players (1.0 = proportional)
+    pub over_representation: f64,
+    /// Top-4 finishes per player
+    pub podium_rate: f64,
+    /// Below the minimum sample size
+    pub provisional: bool,
+}
+
+/// Blend `value` (over `n` samples) with `neutral` as if `prior` neutral
+/// samples had been added.
+fn shrink(value: f64, n: f64, neutral: f64, prior: f64) -> f64 {
+    (value * n + neutral * prior) / (n + prior)
+}
+
+fn tier_for_score(score: f64) -> Tier {
+    if score >= 1.0 {
+        Tier::S
+    } else if score >= 0.35 {
+        Tier::A
+    } else if score >= -0.35 {
+        Tier::B
+    } else if score >= -1.0 {
+        Tier::C
+    } else {
+        Tier::D
+    }
+}
+
+fn round3(v: f64) -> f64 {
+    (v * 1000.0).round() / 1000.0
+}
+
+/// Build the composite tier list, best score first.
+pub fn compute_tiers(placements: &[Placement], pairings: &[Pairing]) -> Vec<FactionTier> {
+    // Pairing records: faction -> (win points, games)
+    let mut records: HashMap<String, (f64, u32)> = HashMap::new();
+    for p in pairings {
+        let (Some(f1), Some(f2)) = (
+            p.player1_faction.as_deref().filter(|f| !f.is_empty()),
+            p.player2_faction.as_deref().filter(|f| !f.is_empty()),
+        ) else {
+            continue;
+        };
+        let (f1, f2) = (normalize_faction_name(f1), normalize_faction_name(f2));
+        if f1 == f2 {
+            continue;
+        }
+        let (s1, s2) = match p.player1_result.as_deref() {
+            Some("win") => (1.0, 0.0),
+            Some("loss") => (0.0, 1.0),
+            Some("draw") => (0.5, 0.5),
+            _ => continue,
+        };
+        for (faction, points) in [(f1, s1), (f2, s2)] {
+            let entry = records.entry(faction).or_default();
+            entry.0 += points;
+            entry.1 += 1;
+        }
+    }
+
+    // Placement counts: faction -> (players, event wins, top 4)
+    let mut finishes: HashMap<String, (u32, u32, u32)> = HashMap::new();
+    for p in placements {
+        let entry = finishes
+            .entry(normalize_faction_name(&p.faction))
+            .or_default();
+        entry.0 += 1;
+        if p.rank == 1 {
+            entry.1 += 1;
+        }
+        if p.rank <= 4 {
+            entry.2 += 1;
+        }
+    }
+    let total_players: u32 = finishes.values().map(|f| f.0).sum();
+    let total_wins: u32 = finishes.values().map(|f| f.1).sum();
+    let total_top_4: u32 = finishes.values().map(|f| f.2).sum();
+    let field_podium = calculate_podium_rate(total_top_4, total_players);
+
+    let factions: HashSet<&String> = records.keys().chain(finishes.keys()).collect();
+    let mut tiers: Vec<FactionTier> = factions
+        .into_iter()
+        .map(|faction| {
+            let (points, games) = records.get(faction).copied().unwrap_or_default();
+            let (players, event_wins, top_4) = finishes.get(faction).copied().unwrap_or_default();
+
+            let win_rate = if games > 0 {
+                points / games as f64
+            } else {
+                0.5
+            };
+            let adjusted_win_rate = shrink(win_rate, games as f64, 0.5, WIN_RATE_PRIOR_GAMES);
+
+            let over_representation = if total_wins > 0 && players > 0 {
+                calculate_over_representation(event_wins, total_wins, players, total_players)
+            } else {
+                1.0
+            };
+            let podium_rate = calculate_podium_rate(top_4, players);
+            let shrunk_over_rep = shrink(
+                over_representation,
+                players as f64,
+                1.0,
+                PLACEMENT_PRIOR_PLAYERS,
+            );
+            let shrunk_podium = if players > 0 {
+                shrink(
+                    podium_rate,
+                    players as f64,
+                    field_podium,
+                    PLACEMENT_PRIOR_PLAYERS,
+                )
+            } else {
+                field_podium
+            };
+
+            let podium_component = if field_podium > 0.0 {
+                (shrunk_podium - field_podium) / (field_podium * 0.5)
+            } else {
+                0.0
+            };
+            let score = WIN_RATE_WEIGHT * (adjusted_win_rate - 0.5) / WIN_RATE_SCALE
+                + OVER_REPRESENTATION_WEIGHT * (shrunk_over_rep - 1.0) / OVER_REPRESENTATION_SCALE
+                + PODIUM_WEIGHT * podium_component;
+
+            FactionTier {
+                faction: faction.clone(),
+                tier: tier_for_score(score),
+                score: round3(score),
+                games,
+                win_rate: round3(win_rate),
+                adjusted_win_rate: round3(adjusted_win_rate),
+                players,
+                event_wins,
+                over_representation: round3(over_representation),
+                podium_rate: round3(podium_rate),
+                provisional: games < TIER_LIST_MIN_GAMES,
+            }
+        })
+        .collect();
+
+    tiers.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.faction.cmp(&b.faction))
+    });
+    tiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(f1: &str, f2: &str, result: &str, n: u32) -> Vec<Pairing> {
+        (0..n)
+            .map(|i| {
+                let mut p = Pairing::new(
+                    "event".into(),
+                    "current".into(),
+                    i + 1,
+                    format!("{}-{}", f1, i),
+                    format!("{}-{}", f2, i),
+                );
+                p.player1_faction = Some(f1.to_string());
+                p.player2_faction = Some(f2.to_string());
+                p.player1_result = Some(result.to_string());
+                p
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compute_tiers_shrinks_small_samples() {
+        let mut pairings = game("Aeldari", "Orks", "win", 60);
+        pairings.extend(game("Aeldari", "Orks", "loss", 20));
+        // A perfect record over three games stays near the middle
+        pairings.extend(game("Leagues of Votann", "Orks", "win", 3));
+
+        let placements: Vec<Placement> = (1..=8)
+            .map(|rank| {
+                let faction = if rank <= 2 { "Aeldari" } else { "Orks" };
+                Placement::new(
+                    "event".into(),
+                    "current".into(),
+                    rank,
+                    format!("P{}", rank),
+                    faction.to_string(),
+                )
+            })
+            .collect();
+
+        let tiers = compute_tiers(&placements, &pairings);
+        let aeldari = tiers.iter().find(|t| t.faction == "Aeldari").unwrap();
+        let votann = tiers
+            .iter()
+            .find(|t| t.faction == "Leagues of Votann")
+            .unwrap();
+        let orks = tiers.iter().find(|t| t.faction == "Orks").unwrap();
+
+        assert_eq!(tiers[0].faction, "Aeldari");
+        assert_eq!(aeldari.tier, Tier::S);
+        assert_eq!(aeldari.win_rate, 0.75);
+        assert!(!aeldari.provisional);
+        assert_eq!(votann.win_rate, 1.0);
+        assert!(votann.adjusted_win_rate < 0.55);
+        assert!(votann.provisional);
+        assert!(orks.score < votann.score);
+    }
+}
@@ -0,0 +1,246 @@
+//! Unit win rates from game results.
+//!
+//! A unit's record is built from the games actually played by the players
+//! whose lists contain it: unit → lists → placements → pairings. Each side
+//! of a pairing is credited to every unit in that player's list, once per
+//! unit however many copies they took. Mirror games (same faction on both
+//! sides) are skipped, since they always net to 50% for the faction.
+//!
+//! Rarely taken units have few games and extreme win rates, so each rate
+//! is also reported shrunk toward its faction's rate over the same
+//! population, weighted as if the faction had played
+//! [`SHRINKAGE_GAMES`] games with the unit. Units with fewer than
+//! [`MIN_UNIT_GAMES`] games are marked `low_sample`.
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::detachments::GameRecord;
+use crate::api::routes::analytics::join_lists_to_placements;
+use crate::models::{normalize_faction_name, ArmyList, Pairing, Placement};
+use crate::sync::normalize_player_name;
+
+/// Weight, in games, of the faction rate a unit's rate is shrunk toward.
+pub const SHRINKAGE_GAMES: f64 = 30.0;
+
+/// Fewest games for a unit's rate not to be marked `low_sample`.
+pub const MIN_UNIT_GAMES: u32 = 20;
+
+/// A unit's game results within its faction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnitPerformance {
+    pub faction: String,
+    pub unit: String,
+    /// Lists containing the unit
+    pub lists: u32,
+    #[serde(flatten)]
+    pub record: GameRecord,
+    pub games: u32,
+    /// Win rate in percent, draws counting half
+    pub win_rate: Option<f64>,
+    /// Win rate of the faction's listed players over the same games
+    pub faction_win_rate: Option<f64>,
+    /// `win_rate` shrunk toward `faction_win_rate`
+    pub shrunk_win_rate: Option<f64>,
+    pub low_sample: bool,
+}
+
+/// Unit game results for every faction, by faction then most games.
+pub fn all_unit_performance(
+    placements: &[Placement],
+    lists: &[ArmyList],
+    pairings: &[Pairing],
+) -> Vec<UnitPerformance> {
+    // (event, player) → (faction, distinct unit names)
+    let mut players: HashMap<(String, String), (String, BTreeSet<String>)> = HashMap::new();
+    let mut list_counts: HashMap<(String, String), u32> = HashMap::new();
+    for (list, placement) in join_lists_to_placements(lists, placements) {
+        let faction = normalize_faction_name(&placement.faction);
+        let units: BTreeSet<String> = list.units.iter().map(|u| u.name.clone()).collect();
+        for unit in &units {
+            *list_counts
+                .entry((faction.clone(), unit.clone()))
+                .or_default() += 1;
+        }
+        players.insert(
+            (
+                placement.event_id.as_str().to_string(),
+                normalize_player_name(&placement.player_name),
+            ),
+            (faction, units),
+        );
+    }
+
+    let mut faction_records: HashMap<String, GameRecord> = HashMap::new();
+    let mut unit_records: HashMap<(String, String), GameRecord> = HashMap::new();
+    for pairing in pairings {
+        let (p1, p2) = match pairing.player1_result.as_deref() {
+            Some("win") => (
+                GameRecord {
+                    wins: 1,
+                    ..Default::default()
+                },
+                GameRecord {
+                    losses: 1,
+                    ..Default::default()
+                },
+            ),
+            Some("loss") => (
+                GameRecord {
+                    losses: 1,
+                    ..Default::default()
+                },
+                GameRecord {
+                    wins: 1,
+                    ..Default::default()
+                },
+            ),
+            Some("draw") => {
+                let draw = GameRecord {
+                    draws: 1,
+                    ..Default::default()
+                };
+                (draw, draw)
+            }
+            _ => continue,
+        };
+        let event_id = pairing.event_id.as_str().to_string();
+        let side1 = players.get(&(
+            event_id.clone(),
+            normalize_player_name(&pairing.player1_name),
+        ));
+        let side2 = players.get(&(event_id, normalize_player_name(&pairing.player2_name)));
+        if let (Some(a), Some(b)) = (side1, side2) {
+            if a.0 == b.0 {
+                continue;
+            }
+        }
+
+        for (side, result) in [(side1, p1), (side2, p2)] {
+            let Some((faction, units)) = side else {
+                continue;
+            };
+            faction_records
+                .entry(faction.clone())
+                .or_default()
+                .add(&result);
+            for unit in units {
+                unit_records
+                    .entry((faction.clone(), unit.clone()))
+                    .or_default()
+                    .add(&result);
+            }
+        }
+    }
+
+    let mut out: Vec<UnitPerformance> = list_counts
+        .into_iter()
+        .map(|((faction, unit), lists)| {
+            let record = unit_records
+                .get(&(faction.clone(), unit.clone()))
+                .copied()
+                .unwrap_or_default();
+            let faction_win_rate = faction_records.get(&faction).and_then(|r| r.win_rate());
+            UnitPerformance {
+                lists,
+                games: record.games(),
+                win_rate: record.win_rate(),
+                shrunk_win_rate: faction_win_rate.map(|prior| shrink(&record, prior)),
+                faction_win_rate,
+                low_sample: record.games() < MIN_UNIT_GAMES,
+                record,
+                faction,
+                unit,
+            }
+        })
+        .collect();
+    out.sort_by(|a, b| {
+        a.faction
+            .cmp(&b.faction)
+            .then_with(|| b.games.cmp(&a.games))
+            .then_with(|| a.unit.cmp(&b.unit))
+    });
+    out
+}
+
+/// A record's win rate in percent, shrunk toward `prior` (also percent).
+fn shrink(record: &GameRecord, prior: f64) -> f64 {
+    let points = record.wins as f64 + record.draws as f64 * 0.5;
+    let rate =
+        (points + SHRINKAGE_GAMES * prior / 100.0) / (record.games() as f64 + SHRINKAGE_GAMES);
+    (rate * 1000.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EntityId, Unit};
+
+    #[test]
+    fn test_unit_performance_shrinks_small_samples() {
+        let event = EntityId::from("gt");
+        let epoch = EntityId::from("current");
+        let rosters: [(&str, &[&str]); 4] = [
+            ("Aeldari", &["Farseer", "Wraithguard", "Wraithguard"]),
+            ("Aeldari", &["Farseer", "Fire Dragons"]),
+            ("Orks", &["Warboss"]),
+            ("Aeldari", &["Farseer"]),
+        ];
+        let mut placements = Vec::new();
+        let mut lists = Vec::new();
+        for (i, (faction, units)) in rosters.iter().enumerate() {
+            let player = format!("Player {}", i);
+            placements.push(Placement::new(
+                event.clone(),
+                epoch.clone(),
+                i as u32 + 1,
+                player.clone(),
+                faction.to_string(),
+            ));
+            let units = units.iter().map(|u| Unit::new(u.to_string(), 1)).collect();
+            let mut list = ArmyList::new(faction.to_string(), 2000, units, format!("list {}", i))
+                .with_player_name(player);
+            list.event_id = Some(event.clone());
+            lists.push(list);
+        }
+        let game = |round: u32, p1: usize, p2: usize, result: &str| {
+            let mut pairing = Pairing::new(
+                event.clone(),
+                epoch.clone(),
+                round,
+                format!("Player {}", p1),
+                format!("Player {}", p2),
+            );
+            pairing.player1_result = Some(result.to_string());
+            pairing
+        };
+        let pairings = vec![
+            game(1, 0, 2, "win"),
+            game(2, 1, 2, "loss"),
+            game(3, 3, 2, "draw"),
+            // Mirror: skipped
+            game(4, 0, 1, "win"),
+        ];
+
+        let stats = all_unit_performance(&placements, &lists, &pairings);
+        let find = |unit: &str| stats.iter().find(|s| s.unit == unit).unwrap();
+
+        let farseer = find("Farseer");
+        assert_eq!((farseer.lists, farseer.games), (3, 3));
+        assert_eq!(farseer.win_rate, Some(50.0));
+        assert_eq!(farseer.faction_win_rate, Some(50.0));
+        assert_eq!(farseer.shrunk_win_rate, Some(50.0));
+        assert!(farseer.low_sample);
+
+        // One list, one game won: the shrunk rate stays near the faction's
+        let wraithguard = find("Wraithguard");
+        assert_eq!((wraithguard.lists, wraithguard.games), (1, 1));
+        assert_eq!(wraithguard.win_rate, Some(100.0));
+        assert_eq!(wraithguard.shrunk_win_rate, Some(51.6));
+
+        let warboss = find("Warboss");
+        assert_eq!((warboss.record.wins, warboss.record.losses), (1, 1));
+        assert_eq!(stats[0].faction, "Aeldari");
+    }
+}
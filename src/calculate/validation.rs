@@ -0,0 +1,147 @@
+//! Points validation for army lists.
+//!
+//! Extraction sometimes gets points wrong: the AI invents a unit cost, a
+//! list is submitted for the wrong game size, or the army total is read as
+//! a unit. A list's points are checked two ways:
+//!
+//! - its `total_points` against the sum of its unit points, allowing for
+//!   Enhancements, which lists often cost outside their units
+//! - its `total_points` against the event's points limit (`game_size`, or
+//!   [`DEFAULT_GAME_SIZE`] when the event doesn't say)
+//!
+//! Problems are stored on the list as [`QualityFlag`]s rather than fixed,
+//! since either side of a mismatch may be the wrong one.
+
+use std::collections::HashMap;
+
+use crate::models::{ArmyList, Event, QualityFlag, Timestamped};
+
+/// Points limit assumed when an event doesn't give one (Strike Force).
+pub const DEFAULT_GAME_SIZE: u32 = 2000;
+
+/// Largest gap between the total and the unit points left unflagged; room
+/// for a few Enhancements costed separately.
+pub const TOTAL_TOLERANCE: u32 = 60;
+
+/// Lists under this share of the limit are flagged as the wrong game size.
+pub const UNDER_LIMIT_RATIO: f64 = 0.75;
+
+/// No single unit costs this much.
+pub const MAX_UNIT_POINTS: u32 = 800;
+
+/// Points problems in a list played at an event with the given limit.
+pub fn points_anomalies(list: &ArmyList, game_size: Option<u32>) -> Vec<QualityFlag> {
+    let mut flags = Vec::new();
+    let total = list.total_points;
+
+    let unit_points: u32 = list.units.iter().filter_map(|u| u.points).sum();
+    // Lists with no unit costs can't be checked against their total
+    if unit_points > 0 && total.abs_diff(unit_points) > TOTAL_TOLERANCE {
+        flags.push(QualityFlag::TotalMismatch { total, unit_points });
+    }
+
+    let limit = game_size.unwrap_or(DEFAULT_GAME_SIZE);
+    if total > limit {
+        flags.push(QualityFlag::OverLimit { total, limit });
+    } else if total > 0 && (total as f64) < limit as f64 * UNDER_LIMIT_RATIO {
+        flags.push(QualityFlag::UnderLimit { total, limit });
+    }
+
+    for unit in &list.units {
+        if let Some(points) = unit.points.filter(|p| *p >= MAX_UNIT_POINTS) {
+            flags.push(QualityFlag::UnitCost {
+                unit: unit.name.clone(),
+                points,
+            });
+        }
+    }
+    flags
+}
+
+/// Re-validate lists against their events' points limits, replacing their
+/// flags. Returns the number of lists whose flags changed.
+pub fn flag_lists(events: &[Event], lists: &mut [ArmyList]) -> usize {
+    let game_sizes: HashMap<&str, Option<u32>> = events
+        .iter()
+        .map(|e| (e.id.as_str(), e.game_size))
+        .collect();
+    let mut changed = 0;
+    for list in lists {
+        let game_size = list
+            .event_id
+            .as_ref()
+            .and_then(|id| game_sizes.get(id.as_str()).copied())
+            .flatten();
+        let flags = points_anomalies(list, game_size);
+        if flags != list.quality_flags {
+            list.quality_flags = flags;
+            list.touch();
+            changed += 1;
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EntityId, Unit};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_flag_lists() {
+        let list = |total: u32, costs: &[u32]| {
+            let units = costs
+                .iter()
+                .enumerate()
+                .map(|(i, p)| Unit::new(format!("Unit {}", i), 1).with_points(*p))
+                .collect();
+            ArmyList::new("Orks".to_string(), total, units, String::new())
+        };
+        let mut event = Event::new(
+            "Incursion GT".to_string(),
+            NaiveDate::from_ymd_opt(2026, 5, 1).unwrap(),
+            "https://example.com/gt".to_string(),
+            "bcp".to_string(),
+            EntityId::from("current"),
+        );
+        event.game_size = Some(1000);
+
+        let mut lists = vec![
+            // Consistent, with an Enhancement costed outside the units
+            list(1995, &[700, 600, 650]),
+            list(2000, &[500, 500]),
+            list(3000, &[1000, 1000, 1000]),
+            list(1000, &[]),
+            list(1000, &[]),
+        ];
+        lists[4].event_id = Some(event.id.clone());
+
+        assert_eq!(flag_lists(std::slice::from_ref(&event), &mut lists), 3);
+        assert!(lists[0].quality_flags.is_empty());
+        assert_eq!(
+            lists[1].quality_flags,
+            vec![QualityFlag::TotalMismatch {
+                total: 2000,
+                unit_points: 1000
+            }]
+        );
+        let kinds: Vec<&str> = lists[2].quality_flags.iter().map(|f| f.kind()).collect();
+        assert_eq!(kinds, ["over_limit", "unit_cost", "unit_cost", "unit_cost"]);
+        assert_eq!(
+            serde_json::to_value(&lists[2].quality_flags[0]).unwrap()["kind"],
+            "over_limit"
+        );
+        // 1000 points is the wrong size for an unknown event, right for the
+        // Incursion one
+        assert!(matches!(
+            lists[3].quality_flags[..],
+            [QualityFlag::UnderLimit { limit: 2000, .. }]
+        ));
+        assert!(lists[4].quality_flags.is_empty());
+        assert!(lists[3].updated_at.is_some());
+
+        // Unchanged flags aren't counted again
+        assert_eq!(flag_lists(&[event], &mut lists), 0);
+    }
+}
@@ -1,9 +1,26 @@
 //! Configuration loading and validation.
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 
+use crate::agents::estimate::ModelPricing;
+use crate::fetch::FetcherConfig;
+use crate::models::SizeThresholds;
+use crate::storage::{Overrides, StorageBackend, StorageConfig};
+use crate::sync::cursor::cursor_key;
+use crate::sync::{Source, SyncSource};
+
+/// Config file read when `--config` is not given (optional).
+pub const DEFAULT_CONFIG_PATH: &str = "./config.toml";
+
+/// Model used for the Anthropic backend when `[ai] model` is left at the
+/// Ollama default.
+pub const DEFAULT_ANTHROPIC_MODEL: &str = "claude-sonnet-4-20250514";
+
 /// Configuration errors.
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -39,6 +56,36 @@ pub struct AiConfig {
     /// Max retries
     #[serde(default = "default_max_retries")]
     pub max_retries: u32,
+
+    /// Ollama context window (`num_ctx`); unset uses the server default.
+    /// `meta-agent ai doctor` recommends a value.
+    #[serde(default)]
+    pub num_ctx: Option<u32>,
+
+    /// Pull the Ollama model at startup if it is not installed
+    #[serde(default)]
+    pub auto_pull: bool,
+
+    /// Reuse responses to identical requests from `state/ai_cache/`
+    #[serde(default = "default_cache")]
+    pub cache: bool,
+
+    /// Days a cached response stays valid (0 = forever)
+    #[serde(default = "default_cache_ttl_days")]
+    pub cache_ttl_days: u32,
+
+    /// Rates by model for `--estimate`, in USD per million tokens; add to
+    /// or override the built-in rates
+    #[serde(default)]
+    pub pricing: BTreeMap<String, ModelPricing>,
+}
+
+fn default_cache() -> bool {
+    true
+}
+
+fn default_cache_ttl_days() -> u32 {
+    30
 }
 
 fn default_backend() -> String {
@@ -69,21 +116,244 @@ impl Default for AiConfig {
             model: default_model(),
             timeout_seconds: default_timeout(),
             max_retries: default_max_retries(),
+            num_ctx: None,
+            auto_pull: false,
+            cache: default_cache(),
+            cache_ttl_days: default_cache_ttl_days(),
+            pricing: BTreeMap::new(),
         }
     }
 }
 
-/// Source configuration.
+impl AiConfig {
+    /// Model to request, resolving the Ollama default to a sensible model
+    /// for the Anthropic backend.
+    pub fn model_for_backend(&self) -> &str {
+        if self.backend == "anthropic" && self.model == default_model() {
+            DEFAULT_ANTHROPIC_MODEL
+        } else {
+            &self.model
+        }
+    }
+
+    /// Rates for the configured model: `[ai.pricing]`, then the built-in
+    /// rates. Ollama runs locally, so its models are free unless priced.
+    /// `None` when no rate is known.
+    pub fn pricing(&self) -> Option<ModelPricing> {
+        let model = self.model_for_backend();
+        self.pricing
+            .get(model)
+            .copied()
+            .or_else(|| ModelPricing::defaults().get(model).copied())
+            .or((self.backend == "ollama").then_some(ModelPricing::FREE))
+    }
+}
+
+/// Goonhammer source configuration (`[sources.goonhammer]`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SourceConfig {
+#[serde(default)]
+pub struct GoonhammerSourceConfig {
     pub enabled: bool,
+
+    /// Competitive Innovations tag page
     pub base_url: String,
-    #[serde(default = "default_rate_limit")]
-    pub rate_limit_ms: u64,
+
+    /// Delay between requests in milliseconds (unset = fetcher default)
+    pub rate_limit_ms: Option<u64>,
+}
+
+impl Default for GoonhammerSourceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base_url: "https://www.goonhammer.com/tag/competitive-innovations-in-10th/".to_string(),
+            rate_limit_ms: None,
+        }
+    }
+}
+
+/// Best Coast Pairings source configuration (`[sources.bcp]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BcpSourceConfig {
+    pub enabled: bool,
+
+    /// Base URL for the BCP API
+    pub api_base_url: String,
+
+    /// Game type ID (1 = Warhammer 40k)
+    pub game_type: u32,
+
+    /// Delay between requests in milliseconds (unset = fetcher default)
+    pub rate_limit_ms: Option<u64>,
+
+    /// Split listings hosting several game sizes into per-bracket events
+    pub split_brackets: bool,
 }
 
-fn default_rate_limit() -> u64 {
-    2000
+impl Default for BcpSourceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            api_base_url: "https://newprod-api.bestcoastpairings.com/v1".to_string(),
+            game_type: 1,
+            rate_limit_ms: None,
+            split_brackets: false,
+        }
+    }
+}
+
+/// Warhammer Community source configuration
+/// (`[sources.warhammer_community]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WarhammerCommunitySourceConfig {
+    pub enabled: bool,
+
+    /// Downloads page to monitor for balance dataslates
+    pub url: String,
+
+    /// Delay between requests in milliseconds (unset = fetcher default)
+    pub rate_limit_ms: Option<u64>,
+}
+
+impl Default for WarhammerCommunitySourceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: "https://www.warhammer-community.com/en-gb/downloads/warhammer-40000/".to_string(),
+            rate_limit_ms: None,
+        }
+    }
+}
+
+/// Tabletop Tournaments source configuration (`[sources.t3]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct T3SourceConfig {
+    pub enabled: bool,
+
+    /// T3 site root
+    pub base_url: String,
+
+    /// Only calendar entries whose game system contains this text
+    pub game_system: String,
+
+    /// Delay between requests in milliseconds (unset = fetcher default)
+    pub rate_limit_ms: Option<u64>,
+}
+
+impl Default for T3SourceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base_url: crate::sync::t3::T3_BASE_URL.to_string(),
+            game_system: "Warhammer 40".to_string(),
+            rate_limit_ms: None,
+        }
+    }
+}
+
+/// Sync source configuration (`[sources]`). Mirrors [`SyncSource`]; only
+/// BCP is enabled by default.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourcesConfig {
+    pub goonhammer: GoonhammerSourceConfig,
+    pub bcp: BcpSourceConfig,
+    pub warhammer_community: WarhammerCommunitySourceConfig,
+    pub t3: T3SourceConfig,
+
+    /// Sections of registered third-party sources (`[sources.<name>]`),
+    /// read with [`SourcesConfig::section`]
+    #[serde(flatten)]
+    pub other: BTreeMap<String, toml::Value>,
+}
+
+impl SourcesConfig {
+    /// All sources with their configured URLs, enabled or not.
+    pub fn all(&self) -> Vec<SyncSource> {
+        vec![
+            SyncSource::Goonhammer {
+                base_url: self.goonhammer.base_url.clone(),
+            },
+            SyncSource::Bcp {
+                api_base_url: self.bcp.api_base_url.clone(),
+                game_type: self.bcp.game_type,
+                split_brackets: self.bcp.split_brackets,
+            },
+            SyncSource::WarhammerCommunity {
+                url: self.warhammer_community.url.clone(),
+            },
+            SyncSource::TabletopTournaments {
+                base_url: self.t3.base_url.clone(),
+                game_system: self.t3.game_system.clone(),
+            },
+        ]
+    }
+
+    /// Sources enabled in the config.
+    pub fn enabled(&self) -> Vec<SyncSource> {
+        self.all()
+            .into_iter()
+            .filter(|s| self.is_enabled(s))
+            .collect()
+    }
+
+    /// Look up a source by its CLI name ("goonhammer", "bcp",
+    /// "warhammer-community", "t3"), whether or not it is enabled.
+    pub fn by_name(&self, name: &str) -> Option<SyncSource> {
+        self.all().into_iter().find(|s| cursor_key(s) == name)
+    }
+
+    fn is_enabled(&self, source: &SyncSource) -> bool {
+        self.source_enabled(cursor_key(source))
+    }
+
+    /// Whether the source registered under `name` is enabled. Third-party
+    /// sources are off unless their section sets `enabled = true`.
+    pub fn source_enabled(&self, name: &str) -> bool {
+        match name {
+            "goonhammer" => self.goonhammer.enabled,
+            "bcp" => self.bcp.enabled,
+            "warhammer-community" => self.warhammer_community.enabled,
+            "t3" => self.t3.enabled,
+            _ => self
+                .section(name)
+                .and_then(|s| s.get("enabled"))
+                .and_then(toml::Value::as_bool)
+                .unwrap_or(false),
+        }
+    }
+
+    /// The `[sources.<name>]` section of a third-party source.
+    pub fn section(&self, name: &str) -> Option<&toml::Value> {
+        self.other.get(name)
+    }
+
+    fn rate_limit_ms(&self, name: &str) -> Option<u64> {
+        match name {
+            "goonhammer" => self.goonhammer.rate_limit_ms,
+            "bcp" => self.bcp.rate_limit_ms,
+            "warhammer-community" => self.warhammer_community.rate_limit_ms,
+            "t3" => self.t3.rate_limit_ms,
+            _ => self
+                .section(name)
+                .and_then(|s| s.get("rate_limit_ms"))
+                .and_then(toml::Value::as_integer)
+                .and_then(|ms| u64::try_from(ms).ok()),
+        }
+    }
+
+    /// Request delay for a run over `sources`: the slowest configured rate
+    /// limit among them, or None to keep the fetcher default.
+    pub fn request_delay(&self, sources: &[Arc<dyn Source>]) -> Option<Duration> {
+        sources
+            .iter()
+            .filter_map(|s| self.rate_limit_ms(s.name()))
+            .max()
+            .map(Duration::from_millis)
+    }
 }
 
 /// Server configuration.
@@ -97,6 +367,35 @@ pub struct ServerConfig {
 
     #[serde(default = "default_cors_origin")]
     pub cors_origin: String,
+
+    /// Epoch served by analytics endpoints when a request gives neither an
+    /// `epoch` query param nor an `X-Epoch` header (unset = each endpoint's
+    /// own default)
+    #[serde(default)]
+    pub default_epoch: Option<String>,
+
+    /// API requests slower than this are logged with their parameters and
+    /// the files they read
+    #[serde(default = "default_slow_request_ms")]
+    pub slow_request_ms: u64,
+
+    /// Token mutating API routes require, as `Authorization: Bearer` or
+    /// `X-API-Key` (unset = only the localhost checks apply)
+    #[serde(default)]
+    pub api_token: Option<String>,
+
+    /// Seconds `serve` waits on SIGINT/SIGTERM for open connections and
+    /// running syncs before exiting
+    #[serde(default = "default_shutdown_grace_secs")]
+    pub shutdown_grace_secs: u64,
+}
+
+fn default_slow_request_ms() -> u64 {
+    500
+}
+
+fn default_shutdown_grace_secs() -> u64 {
+    10
 }
 
 fn default_host() -> String {
@@ -104,7 +403,7 @@ fn default_host() -> String {
 }
 
 fn default_port() -> u16 {
-    8080
+    3000
 }
 
 fn default_cors_origin() -> String {
@@ -117,10 +416,168 @@ impl Default for ServerConfig {
             host: default_host(),
             port: default_port(),
             cors_origin: default_cors_origin(),
+            default_epoch: None,
+            slow_request_ms: default_slow_request_ms(),
+            shutdown_grace_secs: default_shutdown_grace_secs(),
+            api_token: None,
         }
     }
 }
 
+/// Epoch boundary configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EpochConfig {
+    /// Days after a balance pass takes effect during which tournaments still
+    /// count toward the previous epoch. Applied to newly registered passes.
+    #[serde(default)]
+    pub grace_days: u32,
+}
+
+/// Job schedules for `meta-agent schedule` and `serve --scheduler`
+/// (`[schedule]`).
+///
+/// Each job takes an interval ("30m", "6h", "1d"), "daily HH:MM",
+/// "weekly <day> HH:MM" (UTC) or "off".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScheduleConfig {
+    /// Incremental sync over the enabled sources
+    pub sync: String,
+
+    /// Check Warhammer Community for new balance passes
+    pub balance_discovery: String,
+
+    /// Move data from the `current` epoch into per-epoch directories
+    pub repartition: String,
+
+    /// AI-normalize unparsed army lists in the current epoch (off by default
+    /// since it spends AI calls)
+    pub normalize_lists: String,
+
+    /// Recompute derived artifacts for every epoch
+    pub derive: String,
+
+    /// Maximum lists normalized per run
+    pub normalize_limit: usize,
+
+    /// Start the scheduler inside `serve` without `--scheduler`
+    pub run_in_serve: bool,
+}
+
+impl Default for ScheduleConfig {
+    fn default() -> Self {
+        Self {
+            sync: "6h".to_string(),
+            balance_discovery: "daily 06:00".to_string(),
+            repartition: "daily 06:30".to_string(),
+            normalize_lists: "off".to_string(),
+            derive: "1h".to_string(),
+            normalize_limit: 50,
+            run_in_serve: false,
+        }
+    }
+}
+
+/// Storage settings (`[storage]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageSettings {
+    /// `.bak` generations kept when a JSONL file is rewritten in place
+    /// (`x.jsonl.bak`, `x.jsonl.bak.2`, ...); 0 disables backups
+    pub backups: usize,
+
+    /// Backend for per-epoch records: "jsonl" or "sqlite" (needs the
+    /// `sqlite` feature)
+    pub backend: StorageBackend,
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self {
+            backups: 1,
+            backend: StorageBackend::Jsonl,
+        }
+    }
+}
+
+/// Analytics settings (`[analytics]`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnalyticsSettings {
+    /// Player counts at which events become medium, large and major
+    pub size_brackets: SizeThresholds,
+}
+
+/// Kind of service a webhook posts to, which decides the payload shape.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookKind {
+    /// Discord channel webhook (`{"content": ...}`)
+    Discord,
+    /// Slack incoming webhook (`{"text": ...}`)
+    Slack,
+    /// Any endpoint accepting the alerts as JSON
+    #[default]
+    Generic,
+}
+
+/// A webhook notified of watchlist hits (`[[notifications.webhooks]]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+
+    #[serde(default)]
+    pub kind: WebhookKind,
+}
+
+/// What to watch for (`[[notifications.watch]]`). Every field set must
+/// match: `faction` against the placement's faction or subfaction, `player`
+/// against the player name and `unit` against the units of the linked list,
+/// all case-insensitively.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchRule {
+    pub faction: Option<String>,
+    pub player: Option<String>,
+    pub unit: Option<String>,
+}
+
+impl WatchRule {
+    /// Whether the rule names nothing to match on.
+    pub fn is_empty(&self) -> bool {
+        self.faction.is_none() && self.player.is_none() && self.unit.is_none()
+    }
+}
+
+/// Watchlist notifications sent after each sync run (`[notifications]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationsConfig {
+    pub webhooks: Vec<WebhookConfig>,
+
+    pub watch: Vec<WatchRule>,
+
+    /// Placements at or above this rank notify
+    pub top: u32,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            webhooks: Vec::new(),
+            watch: Vec::new(),
+            top: 4,
+        }
+    }
+}
+
+impl NotificationsConfig {
+    /// Whether there is anything to send and anything to send it to.
+    pub fn enabled(&self) -> bool {
+        !self.webhooks.is_empty() && !self.watch.is_empty()
+    }
+}
+
 /// Main application configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -135,6 +592,24 @@ pub struct AppConfig {
 
     #[serde(default)]
     pub server: ServerConfig,
+
+    #[serde(default)]
+    pub epochs: EpochConfig,
+
+    #[serde(default)]
+    pub sources: SourcesConfig,
+
+    #[serde(default)]
+    pub schedule: ScheduleConfig,
+
+    #[serde(default)]
+    pub storage: StorageSettings,
+
+    #[serde(default)]
+    pub analytics: AnalyticsSettings,
+
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
 }
 
 fn default_data_dir() -> PathBuf {
@@ -152,6 +627,12 @@ impl Default for AppConfig {
             log_level: default_log_level(),
             ai: AiConfig::default(),
             server: ServerConfig::default(),
+            epochs: EpochConfig::default(),
+            sources: SourcesConfig::default(),
+            schedule: ScheduleConfig::default(),
+            storage: StorageSettings::default(),
+            analytics: AnalyticsSettings::default(),
+            notifications: NotificationsConfig::default(),
         }
     }
 }
@@ -165,6 +646,49 @@ impl AppConfig {
         Ok(config)
     }
 
+    /// Load the config from `path`, or from [`DEFAULT_CONFIG_PATH`] if that
+    /// file exists, falling back to defaults. An explicitly given path must
+    /// exist.
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        match path {
+            Some(path) => Self::from_file(&path.to_path_buf()),
+            None => {
+                let default_path = PathBuf::from(DEFAULT_CONFIG_PATH);
+                if default_path.exists() {
+                    Self::from_file(&default_path)
+                } else {
+                    Ok(Self::default())
+                }
+            }
+        }
+    }
+
+    /// Storage rooted at the configured data directory.
+    pub fn storage(&self) -> StorageConfig {
+        StorageConfig::new(self.data_dir.clone())
+            .with_backups(self.storage.backups)
+            .with_backend(self.storage.backend)
+            .with_overrides(Overrides::shared())
+    }
+
+    /// Fetcher settings for a run over `sources`, caching into the raw
+    /// directory of `storage`.
+    pub fn fetcher_config(
+        &self,
+        storage: &StorageConfig,
+        sources: &[Arc<dyn Source>],
+    ) -> FetcherConfig {
+        let defaults = FetcherConfig::default();
+        FetcherConfig {
+            cache_dir: storage.raw_dir(),
+            request_delay: self
+                .sources
+                .request_delay(sources)
+                .unwrap_or(defaults.request_delay),
+            ..defaults
+        }
+    }
+
     /// Validate the configuration.
     pub fn validate(&self) -> Result<(), ConfigError> {
         if self.ai.timeout_seconds == 0 {
@@ -173,12 +697,75 @@ impl AppConfig {
             ));
         }
 
+        if !matches!(self.ai.backend.as_str(), "ollama" | "anthropic") {
+            return Err(ConfigError::ValidationError(format!(
+                "Unknown AI backend '{}' (expected 'ollama' or 'anthropic')",
+                self.ai.backend
+            )));
+        }
+
+        if let Some((model, _)) = self.ai.pricing.iter().find(|(_, p)| {
+            [p.input_per_mtok, p.output_per_mtok]
+                .iter()
+                .any(|rate| !rate.is_finite() || *rate < 0.0)
+        }) {
+            return Err(ConfigError::ValidationError(format!(
+                "AI pricing for '{}' must be a non-negative number",
+                model
+            )));
+        }
+
         if self.server.port == 0 {
             return Err(ConfigError::ValidationError(
                 "Server port must be greater than 0".to_string(),
             ));
         }
 
+        if self
+            .server
+            .api_token
+            .as_deref()
+            .is_some_and(|t| t.trim().is_empty())
+        {
+            return Err(ConfigError::ValidationError(
+                "[server] api_token must not be empty".to_string(),
+            ));
+        }
+
+        if !self.analytics.size_brackets.is_ordered() {
+            return Err(ConfigError::ValidationError(
+                "[analytics.size_brackets] thresholds must increase: 0 < medium < large < major"
+                    .to_string(),
+            ));
+        }
+
+        for webhook in &self.notifications.webhooks {
+            if url::Url::parse(&webhook.url).is_err() {
+                return Err(ConfigError::ValidationError(format!(
+                    "[notifications] invalid webhook url '{}'",
+                    webhook.url
+                )));
+            }
+        }
+
+        if self.notifications.watch.iter().any(WatchRule::is_empty) {
+            return Err(ConfigError::ValidationError(
+                "[notifications] each watch rule needs a faction, player or unit".to_string(),
+            ));
+        }
+
+        if self.notifications.top == 0 {
+            return Err(ConfigError::ValidationError(
+                "[notifications] top must be greater than 0".to_string(),
+            ));
+        }
+
+        for job in crate::scheduler::Job::all() {
+            crate::scheduler::Schedule::parse(job.spec(&self.schedule)).map_err(|e| {
+                ConfigError::ValidationError(format!("[schedule] {}: {}", job.name(), e))
+            })?;
+        }
+
         Ok(())
     }
 }
@@ -194,7 +781,7 @@ mod tests {
         assert_eq!(config.data_dir, PathBuf::from("./data"));
         assert_eq!(config.log_level, "info");
         assert_eq!(config.ai.backend, "ollama");
-        assert_eq!(config.server.port, 8080);
+        assert_eq!(config.server.port, 3000);
     }
 
     #[test]
@@ -229,6 +816,235 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_epoch_config_parse() {
+        let config: AppConfig = toml::from_str("[epochs]\ngrace_days = 3\n").unwrap();
+        assert_eq!(config.epochs.grace_days, 3);
+
+        let config: AppConfig = toml::from_str("").unwrap();
+        assert_eq!(config.epochs.grace_days, 0);
+    }
+
+    #[test]
+    fn test_default_epoch_parse() {
+        let config: AppConfig =
+            toml::from_str("[server]\ndefault_epoch = \"balance-2025-06\"\n").unwrap();
+        assert_eq!(
+            config.server.default_epoch.as_deref(),
+            Some("balance-2025-06")
+        );
+        assert_eq!(config.server.port, 3000);
+
+        let config: AppConfig = toml::from_str("").unwrap();
+        assert!(config.server.default_epoch.is_none());
+    }
+
+    #[test]
+    fn test_config_validation_unknown_backend() {
+        let mut config = AppConfig::default();
+        config.ai.backend = "openai".to_string();
+
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_sources_default_bcp_only() {
+        let config: AppConfig = toml::from_str("").unwrap();
+        let enabled = config.sources.enabled();
+        assert_eq!(enabled.len(), 1);
+        assert!(matches!(enabled[0], SyncSource::Bcp { game_type: 1, .. }));
+        let enabled: Vec<_> = enabled.into_iter().map(SyncSource::into_source).collect();
+        assert!(config.sources.request_delay(&enabled).is_none());
+    }
+
+    #[test]
+    fn test_sources_parse() {
+        let config: AppConfig = toml::from_str(
+            r#"
+[sources.goonhammer]
+enabled = true
+base_url = "https://example.com/ci/"
+rate_limit_ms = 2000
+
+[sources.bcp]
+enabled = false
+
+[sources.warhammer_community]
+rate_limit_ms = 3000
+
+[sources.example]
+enabled = true
+feed = "https://example.com/results.json"
+"#,
+        )
+        .unwrap();
+
+        let enabled = config.sources.enabled();
+        assert_eq!(enabled.len(), 1);
+        match &enabled[0] {
+            SyncSource::Goonhammer { base_url } => assert_eq!(base_url, "https://example.com/ci/"),
+            other => panic!("unexpected source {:?}", other),
+        }
+
+        // Disabled sources are still reachable by name (CLI --source override)
+        assert!(matches!(
+            config.sources.by_name("bcp"),
+            Some(SyncSource::Bcp { .. })
+        ));
+        assert!(matches!(
+            config.sources.by_name("t3"),
+            Some(SyncSource::TabletopTournaments { ref game_system, .. }) if game_system == "Warhammer 40"
+        ));
+        assert!(config.sources.by_name("nope").is_none());
+
+        // Third-party sections are kept for registered sources to read
+        assert!(config.sources.source_enabled("example"));
+        assert!(!config.sources.source_enabled("nope"));
+        assert_eq!(
+            config.sources.section("example").unwrap()["feed"].as_str(),
+            Some("https://example.com/results.json")
+        );
+
+        let all: Vec<_> = config
+            .sources
+            .all()
+            .into_iter()
+            .map(SyncSource::into_source)
+            .collect();
+        assert_eq!(
+            config.sources.request_delay(&all),
+            Some(Duration::from_millis(3000))
+        );
+    }
+
+    #[test]
+    fn test_load_and_fetcher_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "data_dir = \"/srv/meta\"\n[sources.bcp]\nrate_limit_ms = 1500\n",
+        )
+        .unwrap();
+
+        let config = AppConfig::load(Some(&path)).unwrap();
+        let storage = config.storage();
+        assert_eq!(storage.raw_dir(), PathBuf::from("/srv/meta/raw"));
+
+        let enabled: Vec<_> = config
+            .sources
+            .enabled()
+            .into_iter()
+            .map(SyncSource::into_source)
+            .collect();
+        let fetcher = config.fetcher_config(&storage, &enabled);
+        assert_eq!(fetcher.cache_dir, storage.raw_dir());
+        assert_eq!(fetcher.request_delay, Duration::from_millis(1500));
+
+        assert!(AppConfig::load(Some(&tmp.path().join("missing.toml"))).is_err());
+    }
+
+    #[test]
+    fn test_schedule_config_parse() {
+        let config: AppConfig = toml::from_str("").unwrap();
+        assert_eq!(config.schedule.sync, "6h");
+        assert_eq!(config.schedule.normalize_lists, "off");
+        assert!(!config.schedule.run_in_serve);
+
+        let mut config: AppConfig = toml::from_str(
+            "[schedule]\nsync = \"2h\"\nderive = \"weekly sun 04:00\"\nrun_in_serve = true\n",
+        )
+        .unwrap();
+        assert_eq!(config.schedule.sync, "2h");
+        assert_eq!(config.schedule.repartition, "daily 06:30");
+        assert!(config.schedule.run_in_serve);
+        assert!(config.validate().is_ok());
+
+        config.schedule.derive = "whenever".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_notifications_config_parse() {
+        let config: AppConfig = toml::from_str("").unwrap();
+        assert!(!config.notifications.enabled());
+        assert_eq!(config.notifications.top, 4);
+
+        let mut config: AppConfig = toml::from_str(
+            r#"
+[[notifications.webhooks]]
+kind = "discord"
+url = "https://discord.com/api/webhooks/1/abc"
+
+[[notifications.webhooks]]
+url = "https://example.com/hook"
+
+[[notifications.watch]]
+faction = "Aeldari"
+
+[[notifications.watch]]
+player = "Alice Smith"
+unit = "Wraithknight"
+"#,
+        )
+        .unwrap();
+        assert!(config.notifications.enabled());
+        assert_eq!(config.notifications.webhooks[0].kind, WebhookKind::Discord);
+        assert_eq!(config.notifications.webhooks[1].kind, WebhookKind::Generic);
+        assert_eq!(
+            config.notifications.watch[1].unit.as_deref(),
+            Some("Wraithknight")
+        );
+        assert!(config.validate().is_ok());
+
+        config.notifications.watch.push(WatchRule::default());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_anthropic_model_resolution() {
+        let mut ai = AiConfig::default();
+        assert_eq!(ai.model_for_backend(), "llama3.2");
+        ai.backend = "anthropic".to_string();
+        assert_eq!(ai.model_for_backend(), DEFAULT_ANTHROPIC_MODEL);
+        ai.model = "claude-haiku".to_string();
+        assert_eq!(ai.model_for_backend(), "claude-haiku");
+    }
+
+    #[test]
+    fn test_ai_pricing() {
+        let mut config: AppConfig = toml::from_str(
+            r#"
+[ai]
+backend = "anthropic"
+
+[ai.pricing."claude-haiku"]
+input_per_mtok = 0.8
+output_per_mtok = 4.0
+"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        // Built-in rate for the default model
+        assert_eq!(config.ai.pricing().unwrap().output_per_mtok, 15.0);
+        config.ai.model = "claude-haiku".to_string();
+        assert_eq!(config.ai.pricing().unwrap().input_per_mtok, 0.8);
+        config.ai.model = "claude-unpriced".to_string();
+        assert!(config.ai.pricing().is_none());
+
+        // Ollama models run locally
+        config.ai.backend = "ollama".to_string();
+        assert_eq!(config.ai.pricing(), Some(ModelPricing::FREE));
+
+        config
+            .ai
+            .pricing
+            .get_mut("claude-haiku")
+            .unwrap()
+            .input_per_mtok = -1.0;
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = AppConfig::default();
@@ -0,0 +1,361 @@
+//! The web dashboard bundled with the crate.
+//!
+//! `serve` hands anything that isn't an API route to the `static` directory.
+//! The dashboard's files are embedded in the binary so [`init`] can write
+//! them there on a fresh install. [`export_site`] renders the meta snapshot
+//! for one epoch into a standalone page that needs no server.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::api::routes::meta::{meta_snapshot, previous_epoch, MetaSnapshotResponse};
+use crate::calculate::derive::DERIVED_GAME_SIZE;
+use crate::storage::{cached_epoch_mapper, current_epoch_id, StorageConfig, StorageError};
+
+/// Directory `serve` serves the dashboard from.
+pub const DEFAULT_DIR: &str = "static";
+
+/// Entries in each top list of an exported page.
+const EXPORT_LIMIT: usize = 10;
+
+/// Dashboard files, relative to the static directory.
+const ASSETS: &[(&str, &str)] = &[
+    ("index.html", include_str!("../static/index.html")),
+    ("css/style.css", include_str!("../static/css/style.css")),
+    ("js/api.js", include_str!("../static/js/api.js")),
+    ("js/app.js", include_str!("../static/js/app.js")),
+    ("js/chart.js", include_str!("../static/js/chart.js")),
+    ("js/events.js", include_str!("../static/js/events.js")),
+    ("js/lib/api.js", include_str!("../static/js/lib/api.js")),
+    (
+        "js/lib/factions.js",
+        include_str!("../static/js/lib/factions.js"),
+    ),
+    ("js/lib/tiers.js", include_str!("../static/js/lib/tiers.js")),
+];
+
+/// Errors writing the dashboard.
+#[derive(Debug, Error)]
+pub enum DashboardError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Unknown epoch: {0}")]
+    UnknownEpoch(String),
+}
+
+/// Files written, and those left alone because they already existed.
+#[derive(Debug, Default)]
+pub struct InitReport {
+    pub written: Vec<PathBuf>,
+    pub skipped: Vec<PathBuf>,
+}
+
+/// Write the dashboard into `dir`. Existing files are kept unless `force`,
+/// so local edits survive a re-run.
+pub fn init(dir: &Path, force: bool) -> Result<InitReport, DashboardError> {
+    let mut report = InitReport::default();
+    for (name, contents) in ASSETS {
+        let path = dir.join(name);
+        if path.exists() && !force {
+            report.skipped.push(path);
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, contents)?;
+        report.written.push(path);
+    }
+    Ok(report)
+}
+
+/// Render the meta snapshot of `epoch` (the current epoch if `None` or
+/// "current") into `dir` as `index.html`, with the data beside it as
+/// `snapshot.json`. Returns the page's path.
+pub fn export_site(
+    storage: &StorageConfig,
+    epoch: Option<&str>,
+    dir: &Path,
+) -> Result<PathBuf, DashboardError> {
+    let epoch_id = resolve_epoch(storage, epoch)?;
+    let previous = previous_epoch(&cached_epoch_mapper(storage), &epoch_id);
+    let snapshot = meta_snapshot(storage, epoch_id, previous, DERIVED_GAME_SIZE, EXPORT_LIMIT)?;
+
+    fs::create_dir_all(dir)?;
+    fs::write(
+        dir.join("snapshot.json"),
+        serde_json::to_string_pretty(&snapshot)?,
+    )?;
+    let page = dir.join("index.html");
+    fs::write(&page, render_snapshot(&snapshot))?;
+    Ok(page)
+}
+
+/// The epoch ID `epoch` names: the current epoch for `None` or "current",
+/// otherwise a known epoch.
+pub(crate) fn resolve_epoch(
+    storage: &StorageConfig,
+    epoch: Option<&str>,
+) -> Result<String, DashboardError> {
+    match epoch {
+        None | Some("current") => Ok(current_epoch_id(storage)),
+        Some(id) => {
+            let mapper = cached_epoch_mapper(storage);
+            if !mapper.all_epochs().is_empty()
+                && mapper
+                    .get_epoch(&crate::models::EntityId::from(id))
+                    .is_none()
+            {
+                return Err(DashboardError::UnknownEpoch(id.to_string()));
+            }
+            Ok(id.to_string())
+        }
+    }
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub(crate) fn percent(fraction: f64) -> String {
+    format!("{:.1}%", fraction * 100.0)
+}
+
+/// A titled table; rows are already escaped.
+pub(crate) fn table(title: &str, headers: &[&str], rows: Vec<Vec<String>>) -> String {
+    if rows.is_empty() {
+        return String::new();
+    }
+    let head: String = headers.iter().map(|h| format!("<th>{}</th>", h)).collect();
+    let body: String = rows
+        .into_iter()
+        .map(|cells| {
+            let cells: String = cells
+                .into_iter()
+                .map(|c| format!("<td>{}</td>", c))
+                .collect();
+            format!("<tr>{}</tr>", cells)
+        })
+        .collect();
+    format!(
+        "<section><h2>{}</h2><table><thead><tr>{}</tr></thead><tbody>{}</tbody></table></section>\n",
+        title, head, body
+    )
+}
+
+/// Standalone HTML page for a meta snapshot.
+pub fn render_snapshot(snapshot: &MetaSnapshotResponse) -> String {
+    page(
+        &format!("40k Meta Snapshot — {}", snapshot.epoch_id),
+        &snapshot_body(snapshot),
+    )
+}
+
+/// Page content for a meta snapshot.
+pub(crate) fn snapshot_body(snapshot: &MetaSnapshotResponse) -> String {
+    let mut sections = String::new();
+
+    sections.push_str(&table(
+        "Tiers",
+        &["Tier", "Factions"],
+        snapshot
+            .tiers
+            .iter()
+            .map(|group| {
+                let factions: Vec<String> =
+                    group.factions.iter().map(|f| escape(&f.faction)).collect();
+                vec![group.tier.to_string(), factions.join(", ")]
+            })
+            .collect(),
+    ));
+    sections.push_str(&table(
+        "Top factions",
+        &["Faction", "Tier", "Win rate", "Meta share", "Players"],
+        snapshot
+            .top_factions
+            .iter()
+            .map(|f| {
+                vec![
+                    escape(&f.faction),
+                    f.tier.to_string(),
+                    percent(f.win_rate),
+                    percent(f.meta_share),
+                    f.player_count.to_string(),
+                ]
+            })
+            .collect(),
+    ));
+    sections.push_str(&table(
+        "Popular detachments",
+        &["Detachment", "Faction", "Players", "Win rate"],
+        snapshot
+            .top_detachments
+            .iter()
+            .map(|d| {
+                vec![
+                    escape(&d.detachment),
+                    escape(&d.faction),
+                    d.count.to_string(),
+                    percent(d.win_rate),
+                ]
+            })
+            .collect(),
+    ));
+    sections.push_str(&table(
+        "Most taken units",
+        &["Unit", "Faction", "Share of lists"],
+        snapshot
+            .top_units
+            .iter()
+            .map(|u| vec![escape(&u.unit), escape(&u.faction), percent(u.list_share)])
+            .collect(),
+    ));
+    let movers = snapshot.winners.iter().chain(&snapshot.losers);
+    sections.push_str(&table(
+        "Movers since the last balance pass",
+        &["Faction", "Win rate change"],
+        movers
+            .map(|m| {
+                let delta = m
+                    .win_rate_delta
+                    .map(|d| format!("{:+.1} pts", d * 100.0))
+                    .unwrap_or_default();
+                vec![escape(&m.faction), delta]
+            })
+            .collect(),
+    ));
+    sections.push_str(&table(
+        "Recent events",
+        &["Date", "Event", "Players", "Winner"],
+        snapshot
+            .recent_events
+            .iter()
+            .map(|e| {
+                let winner = match (&e.winner, &e.winner_faction) {
+                    (Some(player), Some(faction)) => {
+                        format!("{} ({})", escape(player), escape(faction))
+                    }
+                    (Some(player), None) => escape(player),
+                    _ => String::new(),
+                };
+                vec![
+                    escape(&e.date),
+                    escape(&e.name),
+                    e.player_count.map(|n| n.to_string()).unwrap_or_default(),
+                    winner,
+                ]
+            })
+            .collect(),
+    ));
+
+    format!(
+        "<h1>40k Meta Snapshot</h1>\n<p class=\"muted\">Epoch {}, {} to {}: {} events, {} players, {} games</p>\n{}<p class=\"muted\">Data: <a href=\"snapshot.json\">snapshot.json</a></p>\n",
+        escape(&snapshot.epoch_id),
+        snapshot.date_range.from,
+        snapshot.date_range.to,
+        snapshot.totals.events,
+        snapshot.totals.players,
+        snapshot.totals.games,
+        sections,
+    )
+}
+
+/// A complete HTML document around `body`, with the shared styling.
+pub(crate) fn page(title: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="UTF-8">
+<meta name="viewport" content="width=device-width, initial-scale=1.0">
+<title>{title}</title>
+<style>
+body {{ font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 960px; padding: 0 1rem; color: #1d1d1f; }}
+table {{ border-collapse: collapse; width: 100%; margin-bottom: 1.5rem; }}
+th, td {{ text-align: left; padding: 0.35rem 0.6rem; border-bottom: 1px solid #ddd; }}
+th {{ background: #f4f4f6; }}
+nav a {{ margin-right: 1rem; }}
+.muted {{ color: #555; }}
+</style>
+</head>
+<body>
+{body}</body>
+</html>
+"#,
+        title = escape(title),
+        body = body,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EntityId, Event, Placement};
+    use crate::storage::{EntityType, JsonlWriter};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_init_and_export_site() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("static");
+
+        let report = init(&dir, false).unwrap();
+        assert_eq!(report.written.len(), ASSETS.len());
+        assert!(dir.join("js/lib/tiers.js").exists());
+        fs::write(dir.join("index.html"), "custom").unwrap();
+        let report = init(&dir, false).unwrap();
+        assert_eq!(report.skipped.len(), ASSETS.len());
+        assert_eq!(
+            fs::read_to_string(dir.join("index.html")).unwrap(),
+            "custom"
+        );
+        init(&dir, true).unwrap();
+        assert!(fs::read_to_string(dir.join("index.html"))
+            .unwrap()
+            .starts_with("<!DOCTYPE html>"));
+
+        let storage = StorageConfig::new(tmp.path().join("data"));
+        let epoch = EntityId::from("current");
+        let event = Event::new(
+            "Guns & Glory GT".to_string(),
+            NaiveDate::from_ymd_opt(2026, 5, 2).unwrap(),
+            "https://example.com/guns".to_string(),
+            "bcp".to_string(),
+            epoch.clone(),
+        );
+        let placement = Placement::new(
+            event.id.clone(),
+            epoch.clone(),
+            1,
+            "Alice".to_string(),
+            "Aeldari".to_string(),
+        );
+        JsonlWriter::<Event>::for_entity(&storage, EntityType::Event, "current")
+            .write_all(std::slice::from_ref(&event))
+            .unwrap();
+        JsonlWriter::<Placement>::for_entity(&storage, EntityType::Placement, "current")
+            .write_all(&[placement])
+            .unwrap();
+
+        let site = tmp.path().join("site");
+        let page = export_site(&storage, None, &site).unwrap();
+        let html = fs::read_to_string(page).unwrap();
+        assert!(html.contains("Guns &amp; Glory GT"));
+        assert!(html.contains("Alice (Aeldari)"));
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(site.join("snapshot.json")).unwrap()).unwrap();
+        assert_eq!(json["epoch_id"], "current");
+    }
+}
@@ -0,0 +1,559 @@
+//! Bundled demo dataset.
+//!
+//! Seeds a data directory with a small, anonymized sample of tournament
+//! data (events, placements, pairings and army lists across two epochs) so
+//! the API and dashboard can be explored without a first sync or an AI
+//! backend. The dataset is generated from a fixed seed, so every run
+//! produces the same records.
+
+use chrono::NaiveDate;
+use std::fs;
+use thiserror::Error;
+
+use crate::models::{
+    ArmyList, EntityId, EpochMapper, Event, Pairing, Placement, SignificantEvent,
+    SignificantEventType, Unit,
+};
+use crate::storage::{
+    self, jsonl::list_epochs, EntityType, JsonlWriter, StorageConfig, StorageError,
+};
+
+/// Source name recorded on every demo event.
+pub const DEMO_SOURCE: &str = "demo";
+
+const DEMO_SEED: u64 = 0x5eed_40c0;
+const PLAYER_POOL: usize = 40;
+const PLAYERS_PER_EVENT: usize = 24;
+const ROUNDS: u32 = 5;
+const POINTS_LIMIT: u32 = 2000;
+
+/// Errors that can occur while seeding demo data.
+#[derive(Debug, Error)]
+pub enum DemoError {
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+
+    #[error("Data directory already contains normalized data; use --force to replace it")]
+    AlreadySeeded,
+}
+
+/// Counts of the records written by [`seed_demo_data`].
+#[derive(Debug, Default)]
+pub struct DemoSummary {
+    pub epochs: Vec<String>,
+    pub events: usize,
+    pub placements: usize,
+    pub pairings: usize,
+    pub army_lists: usize,
+}
+
+struct DemoFaction {
+    name: &'static str,
+    detachments: [&'static str; 2],
+    units: &'static [(&'static str, u32)],
+    /// Win-probability offset per epoch (before and after the second balance pass)
+    strength: [f64; 2],
+}
+
+const FACTIONS: &[DemoFaction] = &[
+    DemoFaction {
+        name: "Aeldari",
+        detachments: ["Battle Host", "Aspect Host"],
+        units: &[
+            ("Avatar of Khaine", 335),
+            ("Wraithknight", 435),
+            ("Fire Dragons", 120),
+            ("Guardian Defenders", 100),
+            ("Farseer", 70),
+            ("Wave Serpent", 125),
+            ("Dark Reapers", 90),
+            ("Warlock Conclave", 55),
+        ],
+        strength: [0.12, 0.02],
+    },
+    DemoFaction {
+        name: "Space Marines",
+        detachments: ["Gladius Task Force", "Firestorm Assault Force"],
+        units: &[
+            ("Captain", 80),
+            ("Intercessor Squad", 80),
+            ("Terminator Squad", 170),
+            ("Redemptor Dreadnought", 210),
+            ("Gladiator Lancer", 160),
+            ("Lieutenant", 65),
+            ("Eradicator Squad", 95),
+            ("Scout Squad", 70),
+        ],
+        strength: [0.0, 0.04],
+    },
+    DemoFaction {
+        name: "Necrons",
+        detachments: ["Awakened Dynasty", "Hypercrypt Legion"],
+        units: &[
+            ("Overlord", 85),
+            ("Necron Warriors", 90),
+            ("Immortals", 70),
+            ("Lokhust Heavy Destroyers", 55),
+            ("Canoptek Doomstalker", 145),
+            ("Skorpekh Destroyers", 90),
+            ("Technomancer", 80),
+            ("Doom Scythe", 230),
+        ],
+        strength: [0.05, 0.08],
+    },
+    DemoFaction {
+        name: "Orks",
+        detachments: ["Waaagh! Tribe", "Bully Boyz"],
+        units: &[
+            ("Warboss", 65),
+            ("Boyz", 85),
+            ("Meganobz", 105),
+            ("Ghazghkull Thraka", 235),
+            ("Gretchin", 40),
+            ("Trukk", 65),
+            ("Lootas", 55),
+            ("Beastboss", 80),
+        ],
+        strength: [-0.04, -0.02],
+    },
+    DemoFaction {
+        name: "Chaos Space Marines",
+        detachments: ["Veterans of the Long War", "Pactbound Zealots"],
+        units: &[
+            ("Chaos Lord", 90),
+            ("Legionaries", 90),
+            ("Chosen", 125),
+            ("Chaos Terminators", 180),
+            ("Forgefiend", 130),
+            ("Cultist Mob", 50),
+            ("Havocs", 125),
+            ("Dark Apostle", 75),
+        ],
+        strength: [-0.02, 0.10],
+    },
+    DemoFaction {
+        name: "T'au Empire",
+        detachments: ["Kauyon", "Mont'ka"],
+        units: &[
+            ("Commander in Coldstar Battlesuit", 95),
+            ("Strike Team", 75),
+            ("Crisis Sunforge Battlesuits", 130),
+            ("Riptide Battlesuit", 190),
+            ("Broadside Battlesuits", 80),
+            ("Pathfinder Team", 90),
+            ("Hammerhead Gunship", 145),
+            ("Ghostkeel Battlesuit", 160),
+        ],
+        strength: [-0.08, -0.06],
+    },
+    DemoFaction {
+        name: "Tyranids",
+        detachments: ["Invasion Fleet", "Crusher Stampede"],
+        units: &[
+            ("Hive Tyrant", 195),
+            ("Termagants", 60),
+            ("Hormagaunts", 65),
+            ("Screamer-Killer", 125),
+            ("Norn Emissary", 260),
+            ("Zoanthropes", 100),
+            ("Neurotyrant", 105),
+            ("Tyranid Warriors with Ranged Bio-weapons", 65),
+        ],
+        strength: [0.03, -0.05],
+    },
+    DemoFaction {
+        name: "Adepta Sororitas",
+        detachments: ["Hallowed Martyrs", "Bringers of Flame"],
+        units: &[
+            ("Canoness", 50),
+            ("Battle Sister Squad", 115),
+            ("Seraphim Squad", 80),
+            ("Repentia Squad", 75),
+            ("Castigator", 140),
+            ("Paragon Warsuits", 170),
+            ("Morvenn Vahl", 170),
+            ("Immolator", 115),
+        ],
+        strength: [-0.06, -0.09],
+    },
+];
+
+struct DemoEvent {
+    name: &'static str,
+    location: &'static str,
+    date: (i32, u32, u32),
+    /// Index into each faction's `strength`
+    epoch: usize,
+}
+
+const EVENTS: &[DemoEvent] = &[
+    DemoEvent {
+        name: "Demo Summer Open",
+        location: "Northtown",
+        date: (2025, 7, 12),
+        epoch: 0,
+    },
+    DemoEvent {
+        name: "Demo Harvest GT",
+        location: "Southport",
+        date: (2025, 8, 9),
+        epoch: 0,
+    },
+    DemoEvent {
+        name: "Demo Autumn Classic",
+        location: "Eastbridge",
+        date: (2025, 10, 4),
+        epoch: 1,
+    },
+    DemoEvent {
+        name: "Demo Winter Invitational",
+        location: "Westfield",
+        date: (2025, 11, 8),
+        epoch: 1,
+    },
+];
+
+/// Small deterministic linear congruential generator, so the dataset is
+/// reproducible without pulling in an RNG dependency.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        self.0 >> 11
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 42) as f64
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    fn range(&mut self, lo: u32, hi: u32) -> u32 {
+        lo + self.below((hi - lo + 1) as usize) as u32
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+fn date(ymd: (i32, u32, u32)) -> NaiveDate {
+    NaiveDate::from_ymd_opt(ymd.0, ymd.1, ymd.2).expect("valid demo date")
+}
+
+fn balance_passes() -> Vec<SignificantEvent> {
+    [
+        ((2025, 6, 15), "Demo Balance Dataslate (Summer)"),
+        ((2025, 9, 15), "Demo Balance Dataslate (Autumn)"),
+    ]
+    .into_iter()
+    .map(|(d, title)| {
+        SignificantEvent::new(
+            SignificantEventType::BalanceUpdate,
+            date(d),
+            title.to_string(),
+            "https://example.com/demo/balance".to_string(),
+        )
+        .with_summary("Sample balance pass bundled with the demo dataset".to_string())
+    })
+    .collect()
+}
+
+/// Build a legal-sized list by drawing units from the faction pool.
+fn build_list(rng: &mut Lcg, faction: &DemoFaction, detachment: &str) -> ArmyList {
+    let mut units: Vec<Unit> = Vec::new();
+    let mut total = 0;
+    for _ in 0..40 {
+        let (name, points) = faction.units[rng.below(faction.units.len())];
+        if total + points > POINTS_LIMIT {
+            continue;
+        }
+        total += points;
+        match units.iter_mut().find(|u| u.name == name) {
+            Some(unit) => {
+                unit.count += 1;
+                unit.points = unit.points.map(|p| p + points);
+            }
+            None => units.push(Unit::new(name.to_string(), 1).with_points(points)),
+        }
+    }
+
+    let mut raw_text = format!(
+        "{}\n{}\nStrike Force ({} points)\n\n",
+        faction.name, detachment, POINTS_LIMIT
+    );
+    for unit in &units {
+        raw_text.push_str(&format!(
+            "{}x {} ({} pts)\n",
+            unit.count,
+            unit.name,
+            unit.points.unwrap_or(0)
+        ));
+    }
+
+    ArmyList::new(faction.name.to_string(), total, units, raw_text)
+        .with_detachment(detachment.to_string())
+}
+
+#[derive(Default, Clone, Copy)]
+struct Standing {
+    wins: u32,
+    losses: u32,
+    draws: u32,
+    battle_points: u32,
+}
+
+struct EventData {
+    event: Event,
+    placements: Vec<Placement>,
+    pairings: Vec<Pairing>,
+    lists: Vec<ArmyList>,
+}
+
+/// Simulate a Swiss-paired event and derive its placements from the final standings.
+fn simulate_event(rng: &mut Lcg, spec: &DemoEvent, epoch_id: &EntityId) -> EventData {
+    let event_date = date(spec.date);
+    let event = Event::new(
+        spec.name.to_string(),
+        event_date,
+        format!(
+            "https://example.com/demo/{}",
+            spec.name.to_lowercase().replace(' ', "-")
+        ),
+        DEMO_SOURCE.to_string(),
+        epoch_id.clone(),
+    )
+    .with_location(spec.location.to_string())
+    .with_player_count(PLAYERS_PER_EVENT as u32)
+    .with_round_count(ROUNDS);
+
+    let mut pool: Vec<usize> = (0..PLAYER_POOL).collect();
+    rng.shuffle(&mut pool);
+    pool.truncate(PLAYERS_PER_EVENT);
+
+    let players: Vec<(String, &DemoFaction, &str)> = pool
+        .iter()
+        .map(|&p| {
+            let faction = &FACTIONS[p % FACTIONS.len()];
+            let detachment = faction.detachments[rng.below(2)];
+            (format!("Player {:02}", p + 1), faction, detachment)
+        })
+        .collect();
+
+    let mut standings = vec![Standing::default(); players.len()];
+    let mut pairings = Vec::new();
+    for round in 1..=ROUNDS {
+        let mut order: Vec<usize> = (0..players.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (sa, sb) = (standings[a], standings[b]);
+            (sb.wins, sb.battle_points, a).cmp(&(sa.wins, sa.battle_points, b))
+        });
+
+        for pair in order.chunks(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let edge = players[a].1.strength[spec.epoch] - players[b].1.strength[spec.epoch];
+            let roll = rng.next_f64();
+            let (result, gp_a, gp_b) = if roll < 0.05 {
+                let gp = rng.range(60, 80);
+                ("draw", gp, gp)
+            } else if roll < 0.525 + edge {
+                ("win", rng.range(70, 95), rng.range(30, 65))
+            } else {
+                ("loss", rng.range(30, 65), rng.range(70, 95))
+            };
+
+            match result {
+                "win" => {
+                    standings[a].wins += 1;
+                    standings[b].losses += 1;
+                }
+                "loss" => {
+                    standings[a].losses += 1;
+                    standings[b].wins += 1;
+                }
+                _ => {
+                    standings[a].draws += 1;
+                    standings[b].draws += 1;
+                }
+            }
+            standings[a].battle_points += gp_a;
+            standings[b].battle_points += gp_b;
+
+            let mut pairing = Pairing::new(
+                event.id.clone(),
+                epoch_id.clone(),
+                round,
+                players[a].0.clone(),
+                players[b].0.clone(),
+            );
+            pairing.player1_faction = Some(players[a].1.name.to_string());
+            pairing.player2_faction = Some(players[b].1.name.to_string());
+            pairing.player1_result = Some(result.to_string());
+            pairing.player1_game_points = Some(gp_a);
+            pairing.player2_game_points = Some(gp_b);
+            pairings.push(pairing);
+        }
+    }
+
+    let mut final_order: Vec<usize> = (0..players.len()).collect();
+    final_order.sort_by(|&a, &b| {
+        let (sa, sb) = (standings[a], standings[b]);
+        (sb.wins, sb.draws, sb.battle_points, a).cmp(&(sa.wins, sa.draws, sa.battle_points, b))
+    });
+
+    let mut placements = Vec::new();
+    let mut lists = Vec::new();
+    for (pos, &idx) in final_order.iter().enumerate() {
+        let (ref player, faction, detachment) = players[idx];
+        let s = standings[idx];
+
+        let mut list = build_list(rng, faction, detachment)
+            .with_player_name(player.clone())
+            .with_event_id(event.id.clone())
+            .with_event_date(event_date);
+        // One list per player per event, even if two players drew the same units
+        list.id = EntityId::generate(&[event.id.as_str(), player, "demo-list"]);
+
+        placements.push(
+            Placement::new(
+                event.id.clone(),
+                epoch_id.clone(),
+                pos as u32 + 1,
+                player.clone(),
+                faction.name.to_string(),
+            )
+            .with_detachment(detachment.to_string())
+            .with_record(s.wins, s.losses, s.draws)
+            .with_battle_points(s.battle_points)
+            .with_list_id(list.id.clone()),
+        );
+        lists.push(list);
+    }
+
+    EventData {
+        event,
+        placements,
+        pairings,
+        lists,
+    }
+}
+
+/// Write the demo dataset into `storage`.
+///
+/// Refuses to touch a data directory that already holds normalized data
+/// unless `force` is set, in which case the existing normalized directory
+/// (including significant events) is replaced.
+pub fn seed_demo_data(storage: &StorageConfig, force: bool) -> Result<DemoSummary, DemoError> {
+    let has_data = !list_epochs(storage)?.is_empty() || storage.significant_events_path().exists();
+    if has_data {
+        if !force {
+            return Err(DemoError::AlreadySeeded);
+        }
+        fs::remove_dir_all(storage.normalized_dir()).map_err(StorageError::from)?;
+    }
+
+    let mut significant_events = balance_passes();
+    storage::write_significant_events(storage, &mut significant_events)?;
+    let mapper = EpochMapper::from_significant_events(&significant_events);
+
+    let mut rng = Lcg(DEMO_SEED);
+    let mut by_epoch: Vec<(String, Vec<EventData>)> = Vec::new();
+    for spec in EVENTS {
+        let epoch_id = mapper.get_epoch_id_for_date(date(spec.date));
+        let data = simulate_event(&mut rng, spec, &epoch_id);
+        match by_epoch.iter_mut().find(|(id, _)| id == epoch_id.as_str()) {
+            Some((_, events)) => events.push(data),
+            None => by_epoch.push((epoch_id.as_str().to_string(), vec![data])),
+        }
+    }
+
+    let mut summary = DemoSummary::default();
+    for (epoch_id, events) in by_epoch {
+        let event_rows: Vec<Event> = events.iter().map(|e| e.event.clone()).collect();
+        let placements: Vec<Placement> = events.iter().flat_map(|e| e.placements.clone()).collect();
+        let pairings: Vec<Pairing> = events.iter().flat_map(|e| e.pairings.clone()).collect();
+        let lists: Vec<ArmyList> = events.iter().flat_map(|e| e.lists.clone()).collect();
+
+        summary.events += JsonlWriter::<Event>::for_entity(storage, EntityType::Event, &epoch_id)
+            .write_all(&event_rows)?;
+        summary.placements +=
+            JsonlWriter::<Placement>::for_entity(storage, EntityType::Placement, &epoch_id)
+                .write_all(&placements)?;
+        summary.pairings +=
+            JsonlWriter::<Pairing>::for_entity(storage, EntityType::Pairing, &epoch_id)
+                .write_all(&pairings)?;
+        summary.army_lists +=
+            JsonlWriter::<ArmyList>::for_entity(storage, EntityType::ArmyList, &epoch_id)
+                .write_all(&lists)?;
+        summary.epochs.push(epoch_id);
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::validate::validate_storage;
+    use crate::storage::JsonlReader;
+
+    #[test]
+    fn test_seed_demo_data() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+
+        let summary = seed_demo_data(&storage, false).unwrap();
+        assert_eq!(summary.epochs.len(), 2);
+        assert_eq!(summary.events, EVENTS.len());
+        assert_eq!(summary.placements, EVENTS.len() * PLAYERS_PER_EVENT);
+        assert_eq!(summary.army_lists, summary.placements);
+        assert_eq!(
+            summary.pairings,
+            EVENTS.len() * ROUNDS as usize * PLAYERS_PER_EVENT / 2
+        );
+
+        let report = validate_storage(&storage).unwrap();
+        assert!(report.is_ok(), "{:?}", report.issues);
+
+        let lists: Vec<ArmyList> =
+            JsonlReader::for_entity(&storage, EntityType::ArmyList, &summary.epochs[0])
+                .read_all()
+                .unwrap();
+        assert!(lists.iter().all(|l| l.total_points <= POINTS_LIMIT));
+    }
+
+    #[test]
+    fn test_seed_demo_data_is_deterministic_and_guarded() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        seed_demo_data(&storage, false).unwrap();
+        let path = storage
+            .normalized_dir()
+            .join(&list_epochs(&storage).unwrap()[0])
+            .join("placements.jsonl");
+        let ranks = |p: &std::path::Path| -> Vec<(String, u32)> {
+            JsonlReader::<Placement>::new(p.to_path_buf())
+                .read_all()
+                .unwrap()
+                .into_iter()
+                .map(|pl| (pl.player_name, pl.rank))
+                .collect()
+        };
+        let first = ranks(&path);
+
+        assert!(matches!(
+            seed_demo_data(&storage, false),
+            Err(DemoError::AlreadySeeded)
+        ));
+        seed_demo_data(&storage, true).unwrap();
+        assert_eq!(ranks(&path), first);
+    }
+}
@@ -0,0 +1,503 @@
+//! Export stored entities to CSV, JSON, JSONL or Parquet.
+//!
+//! Reads the normalized JSONL for one epoch or all of them, deduplicates by
+//! ID and writes a single file per entity, so data can be pulled into
+//! spreadsheets or notebooks without knowing the data-lake layout.
+//!
+//! JSON and JSONL keep records as stored. CSV and Parquet flatten nested
+//! objects into `parent_child` columns (a placement's `record.wins` becomes
+//! `record_wins`); arrays such as a list's units are written as JSON text.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::storage::jsonl::list_epochs;
+use crate::storage::{
+    current_epoch_id, read_significant_events, EntityType, JsonlReader, StorageConfig, StorageError,
+};
+
+/// Errors from exporting.
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Parquet error: {0}")]
+    Parquet(String),
+
+    #[error("Unknown entity '{0}' (expected events, placements, lists, pairings, bracket_matches or significant_events)")]
+    UnknownEntity(String),
+
+    #[error("Unknown format '{0}' (expected csv, json, jsonl or parquet)")]
+    UnknownFormat(String),
+}
+
+/// An exportable entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportEntity {
+    Events,
+    Placements,
+    Lists,
+    Pairings,
+    BracketMatches,
+    SignificantEvents,
+}
+
+impl ExportEntity {
+    /// Storage entity type.
+    pub fn entity_type(self) -> EntityType {
+        match self {
+            ExportEntity::Events => EntityType::Event,
+            ExportEntity::Placements => EntityType::Placement,
+            ExportEntity::Lists => EntityType::ArmyList,
+            ExportEntity::Pairings => EntityType::Pairing,
+            ExportEntity::BracketMatches => EntityType::BracketMatch,
+            ExportEntity::SignificantEvents => EntityType::SignificantEvent,
+        }
+    }
+}
+
+impl fmt::Display for ExportEntity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ExportEntity::Events => "events",
+            ExportEntity::Placements => "placements",
+            ExportEntity::Lists => "lists",
+            ExportEntity::Pairings => "pairings",
+            ExportEntity::BracketMatches => "bracket_matches",
+            ExportEntity::SignificantEvents => "significant_events",
+        })
+    }
+}
+
+impl FromStr for ExportEntity {
+    type Err = ExportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "events" | "event" => Ok(ExportEntity::Events),
+            "placements" | "placement" => Ok(ExportEntity::Placements),
+            "lists" | "list" | "army_lists" => Ok(ExportEntity::Lists),
+            "pairings" | "pairing" => Ok(ExportEntity::Pairings),
+            "bracket_matches" | "brackets" => Ok(ExportEntity::BracketMatches),
+            "significant_events" | "balance" => Ok(ExportEntity::SignificantEvents),
+            _ => Err(ExportError::UnknownEntity(s.to_string())),
+        }
+    }
+}
+
+/// Output file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Jsonl,
+    Parquet,
+}
+
+impl ExportFormat {
+    /// File extension (without the dot).
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Parquet => "parquet",
+        }
+    }
+
+    /// HTTP content type.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Json => "application/json",
+            ExportFormat::Jsonl => "application/x-ndjson",
+            ExportFormat::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = ExportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            "jsonl" | "ndjson" => Ok(ExportFormat::Jsonl),
+            "parquet" => Ok(ExportFormat::Parquet),
+            _ => Err(ExportError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+/// Epoch directories to export: every stored epoch for `"all"`, the
+/// current epoch for `"current"`, else the given ID.
+pub fn export_epochs(storage: &StorageConfig, epoch: &str) -> Vec<String> {
+    match epoch {
+        "all" => {
+            let epochs = list_epochs(storage).unwrap_or_default();
+            if epochs.is_empty() {
+                vec!["current".to_string()]
+            } else {
+                epochs
+            }
+        }
+        "current" => vec![current_epoch_id(storage)],
+        id => vec![id.to_string()],
+    }
+}
+
+/// Load records for an entity across epochs, keeping the first record
+/// for each ID. Significant events are global and ignore `epochs`.
+pub fn load_records(
+    storage: &StorageConfig,
+    entity: ExportEntity,
+    epochs: &[String],
+) -> Result<Vec<Value>, StorageError> {
+    if entity == ExportEntity::SignificantEvents {
+        return read_significant_events(storage)?
+            .iter()
+            .map(|e| serde_json::to_value(e).map_err(StorageError::from))
+            .collect();
+    }
+
+    let mut seen = HashSet::new();
+    let mut records = Vec::new();
+    for epoch in epochs {
+        let rows = JsonlReader::<Value>::for_entity(storage, entity.entity_type(), epoch)
+            .read_all()
+            .unwrap_or_default();
+        for row in rows {
+            let id = row.get("id").and_then(Value::as_str).map(String::from);
+            if id.is_none_or(|id| seen.insert(id)) {
+                records.push(row);
+            }
+        }
+    }
+    Ok(records)
+}
+
+/// Records flattened into named columns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub columns: Vec<String>,
+    /// One cell per column; `Value::Null` where a record lacks the column
+    pub rows: Vec<Vec<Value>>,
+}
+
+fn flatten(prefix: &str, value: &Value, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, v) in map {
+                let name = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}_{}", prefix, key)
+                };
+                flatten(&name, v, out);
+            }
+        }
+        Value::Object(_) => out.push((prefix.to_string(), Value::Null)),
+        other => out.push((prefix.to_string(), other.clone())),
+    }
+}
+
+impl Table {
+    /// Flatten records. Columns appear in first-seen order, `id` first.
+    pub fn from_records(records: &[Value]) -> Self {
+        let mut columns: Vec<String> = Vec::new();
+        let mut known = HashSet::new();
+        let flat: Vec<Map<String, Value>> = records
+            .iter()
+            .map(|record| {
+                let mut cells = Vec::new();
+                flatten("", record, &mut cells);
+                cells.sort_by_key(|(name, _)| name != "id");
+                for (name, _) in &cells {
+                    if known.insert(name.clone()) {
+                        columns.push(name.clone());
+                    }
+                }
+                cells.into_iter().collect()
+            })
+            .collect();
+
+        let rows = flat
+            .into_iter()
+            .map(|mut cells| {
+                columns
+                    .iter()
+                    .map(|c| cells.remove(c).unwrap_or(Value::Null))
+                    .collect()
+            })
+            .collect();
+        Self { columns, rows }
+    }
+}
+
+/// Text for a CSV cell: strings unquoted, null empty, arrays as JSON.
+pub fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// One CSV record, newline-terminated.
+pub fn csv_record<I, S>(fields: I) -> Result<Vec<u8>, ExportError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<[u8]>,
+{
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(fields)?;
+    writer
+        .into_inner()
+        .map_err(|e| ExportError::Io(e.into_error()))
+}
+
+/// Write a table as CSV with a header row.
+pub fn write_csv(table: &Table, out: impl Write) -> Result<(), ExportError> {
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(&table.columns)?;
+    for row in &table.rows {
+        writer.write_record(row.iter().map(cell_text))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Arrow type for a column: integer, float or boolean when every non-null
+/// cell agrees, else text.
+fn column_type(table: &Table, col: usize) -> DataType {
+    let mut cells = table.rows.iter().map(|r| &r[col]).filter(|v| !v.is_null());
+    let first = match cells.clone().next() {
+        Some(v) => v,
+        None => return DataType::Utf8,
+    };
+    if first.is_boolean() && cells.clone().all(Value::is_boolean) {
+        DataType::Boolean
+    } else if cells
+        .clone()
+        .all(|v| v.is_i64() || v.is_u64() && v.as_i64().is_some())
+    {
+        DataType::Int64
+    } else if cells.all(Value::is_number) {
+        DataType::Float64
+    } else {
+        DataType::Utf8
+    }
+}
+
+/// Write a table as a single Parquet row group.
+pub fn write_parquet<W: Write + Send>(table: &Table, out: W) -> Result<(), ExportError> {
+    let parquet_err = |e: &dyn fmt::Display| ExportError::Parquet(e.to_string());
+
+    let types: Vec<DataType> = (0..table.columns.len())
+        .map(|c| column_type(table, c))
+        .collect();
+    let schema = Arc::new(Schema::new(
+        table
+            .columns
+            .iter()
+            .zip(&types)
+            .map(|(name, ty)| Field::new(name, ty.clone(), true))
+            .collect::<Vec<_>>(),
+    ));
+    let arrays: Vec<ArrayRef> = types
+        .iter()
+        .enumerate()
+        .map(|(c, ty)| {
+            let cells = table.rows.iter().map(|r| &r[c]);
+            match ty {
+                DataType::Boolean => Arc::new(BooleanArray::from(
+                    cells.map(Value::as_bool).collect::<Vec<_>>(),
+                )) as ArrayRef,
+                DataType::Int64 => Arc::new(Int64Array::from(
+                    cells.map(Value::as_i64).collect::<Vec<_>>(),
+                )),
+                DataType::Float64 => Arc::new(Float64Array::from(
+                    cells.map(Value::as_f64).collect::<Vec<_>>(),
+                )),
+                _ => Arc::new(StringArray::from(
+                    cells
+                        .map(|v| (!v.is_null()).then(|| cell_text(v)))
+                        .collect::<Vec<_>>(),
+                )),
+            }
+        })
+        .collect();
+
+    let mut writer =
+        ArrowWriter::try_new(out, schema.clone(), None).map_err(|e| parquet_err(&e))?;
+    if !table.rows.is_empty() {
+        let batch = RecordBatch::try_new(schema, arrays).map_err(|e| parquet_err(&e))?;
+        writer.write(&batch).map_err(|e| parquet_err(&e))?;
+    }
+    writer.close().map_err(|e| parquet_err(&e))?;
+    Ok(())
+}
+
+/// Write records in the given format.
+pub fn write_records(
+    records: &[Value],
+    format: ExportFormat,
+    mut out: impl Write + Send,
+) -> Result<(), ExportError> {
+    match format {
+        ExportFormat::Csv => write_csv(&Table::from_records(records), out),
+        ExportFormat::Parquet => write_parquet(&Table::from_records(records), out),
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, records)?;
+            out.flush()?;
+            Ok(())
+        }
+        ExportFormat::Jsonl => {
+            for record in records {
+                serde_json::to_writer(&mut out, record)?;
+                out.write_all(b"\n")?;
+            }
+            out.flush()?;
+            Ok(())
+        }
+    }
+}
+
+/// Summary of an export written to disk.
+#[derive(Debug, Clone)]
+pub struct ExportOutcome {
+    pub path: PathBuf,
+    pub records: usize,
+}
+
+/// Export one entity to `<out_dir>/<entity>_<epoch>.<ext>`.
+pub fn export_to_dir(
+    storage: &StorageConfig,
+    entity: ExportEntity,
+    epoch: &str,
+    format: ExportFormat,
+    out_dir: &Path,
+) -> Result<ExportOutcome, ExportError> {
+    let records = load_records(storage, entity, &export_epochs(storage, epoch))?;
+    fs::create_dir_all(out_dir)?;
+    let path = out_dir.join(format!("{}_{}.{}", entity, epoch, format.extension()));
+    write_records(&records, format, BufWriter::new(File::create(&path)?))?;
+    Ok(ExportOutcome {
+        path,
+        records: records.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EntityId, Placement};
+    use crate::storage::JsonlWriter;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_table_flattens_nested_records() {
+        let records = vec![
+            json!({"id": "a", "rank": 1, "record": {"wins": 5, "losses": 0}, "units": [{"name": "X"}]}),
+            json!({"id": "b", "rank": 2, "detachment": "Gladius"}),
+        ];
+        let table = Table::from_records(&records);
+        assert_eq!(
+            table.columns,
+            vec![
+                "id",
+                "rank",
+                "record_losses",
+                "record_wins",
+                "units",
+                "detachment"
+            ]
+        );
+        assert_eq!(table.rows[1][2], Value::Null);
+
+        let mut buf = Vec::new();
+        write_csv(&table, &mut buf).unwrap();
+        let csv = String::from_utf8(buf).unwrap();
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            r#"a,1,0,5,"[{""name"":""X""}]","#
+        );
+        assert_eq!(column_type(&table, 1), DataType::Int64);
+        assert_eq!(column_type(&table, 5), DataType::Utf8);
+    }
+
+    #[test]
+    fn test_export_all_epochs_dedups_and_writes_parquet() {
+        let temp = TempDir::new().unwrap();
+        let storage = StorageConfig::new(temp.path().to_path_buf());
+        let placement = |rank, name: &str| {
+            Placement::new(
+                EntityId::from("evt"),
+                EntityId::from("e1"),
+                rank,
+                name.to_string(),
+                "Aeldari".to_string(),
+            )
+            .with_record(4, 1, 0)
+        };
+        let a = placement(1, "Alice");
+        JsonlWriter::for_entity(&storage, EntityType::Placement, "e1")
+            .append_batch(&[a.clone(), a.clone()])
+            .unwrap();
+        JsonlWriter::for_entity(&storage, EntityType::Placement, "e2")
+            .append(&placement(2, "Bob"))
+            .unwrap();
+
+        let out = temp.path().join("export");
+        let outcome = export_to_dir(
+            &storage,
+            ExportEntity::Placements,
+            "all",
+            ExportFormat::Parquet,
+            &out,
+        )
+        .unwrap();
+        assert_eq!(outcome.records, 2);
+        assert!(outcome.path.ends_with("placements_all.parquet"));
+
+        let reader = SerializedFileReader::new(File::open(&outcome.path).unwrap()).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 2);
+
+        let one = export_to_dir(
+            &storage,
+            ExportEntity::Placements,
+            "e2",
+            ExportFormat::Csv,
+            &out,
+        )
+        .unwrap();
+        let csv = fs::read_to_string(one.path).unwrap();
+        assert_eq!(csv.lines().count(), 2);
+        assert!(csv.contains("Bob"));
+    }
+}
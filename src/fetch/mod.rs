@@ -4,20 +4,27 @@
 //! All fetched content is stored in the raw data directory for re-processing.
 
 use std::collections::HashMap;
+use std::hash::BuildHasher;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 use chrono::{DateTime, Utc};
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+    USER_AGENT,
+};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use thiserror::Error;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use url::Url;
 
+pub mod pdf;
+
 /// Errors that can occur during fetching.
 #[derive(Debug, Error)]
 pub enum FetchError {
@@ -106,6 +113,12 @@ pub struct FetcherConfig {
 
     /// Extra headers to include in every request (e.g., API keys)
     pub extra_headers: HashMap<String, String>,
+
+    /// Times to retry a request answered with 429 Too Many Requests
+    pub rate_limit_retries: u32,
+
+    /// Longest Retry-After to wait out; longer ones fail immediately
+    pub max_retry_after: Duration,
 }
 
 impl Default for FetcherConfig {
@@ -118,10 +131,52 @@ impl Default for FetcherConfig {
             user_agent: "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36".to_string(),
             request_delay: Duration::from_millis(500),
             extra_headers: HashMap::new(),
+            rate_limit_retries: 3,
+            max_retry_after: Duration::from_secs(120),
         }
     }
 }
 
+/// Earliest time the next request to each host may start. Shared by every
+/// `Fetcher` in the process, so separate clients for one host (e.g. BCP's
+/// authenticated and anonymous fetchers) stay polite together.
+static HOST_SLOTS: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
+
+fn host_slots() -> std::sync::MutexGuard<'static, HashMap<String, Instant>> {
+    HOST_SLOTS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Reserve the next request slot for `host` and return how long to wait
+/// for it. Each reservation pushes the following one `delay` later, so
+/// concurrent callers queue up instead of bursting.
+fn reserve_slot(host: &str, delay: Duration) -> Duration {
+    let now = Instant::now();
+    let mut slots = host_slots();
+    let start = slots.get(host).map_or(now, |&next| next.max(now));
+    slots.insert(host.to_string(), start + delay);
+    start - now
+}
+
+/// Hold off every request to `host` until `until` (after a 429).
+fn defer_host(host: &str, until: Instant) {
+    let mut slots = host_slots();
+    let next = slots.entry(host.to_string()).or_insert(until);
+    *next = (*next).max(until);
+}
+
+/// Wait before retrying after a 429: the server's Retry-After or an
+/// exponential backoff (1s, 2s, 4s, ...), whichever is longer, plus up to
+/// 25% jitter so parallel syncs don't retry in lockstep.
+fn rate_limit_backoff(retry_after_secs: u64, attempt: u32) -> Duration {
+    let exponential = 1u64 << attempt.saturating_sub(1).min(10);
+    let base = Duration::from_secs(retry_after_secs.max(exponential));
+    let jitter = std::collections::hash_map::RandomState::new().hash_one(Instant::now()) % 1000;
+    base + base.mul_f64(jitter as f64 / 4000.0)
+}
+
 /// HTTP fetcher with local caching.
 pub struct Fetcher {
     client: Client,
@@ -160,25 +215,44 @@ impl Fetcher {
         Self::new(FetcherConfig::default())
     }
 
+    /// Configuration this fetcher was built with.
+    pub fn config(&self) -> &FetcherConfig {
+        &self.config
+    }
+
     /// Fetch a URL, using cache if available and fresh.
     pub async fn fetch(&self, url: &Url) -> Result<FetchResult, FetchError> {
         let cache_path = self.cache_path_for_url(url);
         let meta_path = self.meta_path_for_url(url);
 
         // Check cache
-        if let Some(result) = self.check_cache(url, &cache_path, &meta_path).await? {
+        let cached = self.check_cache(url, &cache_path, &meta_path).await?;
+        crate::metrics::Metrics::global().record_cache_lookup(cached.is_some());
+        if let Some(result) = cached {
             return Ok(result);
         }
 
+        // Expired entries with validators are revalidated rather than
+        // re-downloaded
+        let stale = if cache_path.exists() {
+            Self::read_meta(&meta_path)
+                .await
+                .filter(|m| m.etag.is_some() || m.last_modified.is_some())
+        } else {
+            None
+        };
+
         // Fetch from network
-        self.fetch_and_cache(url, &cache_path, &meta_path).await
+        self.fetch_politely(url, &cache_path, &meta_path, stale.as_ref())
+            .await
     }
 
     /// Force fetch from network, ignoring cache.
     pub async fn fetch_fresh(&self, url: &Url) -> Result<FetchResult, FetchError> {
         let cache_path = self.cache_path_for_url(url);
         let meta_path = self.meta_path_for_url(url);
-        self.fetch_and_cache(url, &cache_path, &meta_path).await
+        self.fetch_politely(url, &cache_path, &meta_path, None)
+            .await
     }
 
     /// Get content from cache without network fallback.
@@ -191,6 +265,11 @@ impl Fetcher {
             .flatten()
     }
 
+    /// Read a URL's cached body whatever its age, without network fallback.
+    pub async fn read_cached_any_age(&self, url: &Url) -> Option<String> {
+        fs::read_to_string(self.cache_path_for_url(url)).await.ok()
+    }
+
     /// Check if content is cached and fresh.
     async fn check_cache(
         &self,
@@ -228,18 +307,107 @@ impl Fetcher {
         }))
     }
 
-    /// Fetch from network and cache the result.
+    /// Read cache metadata, if present and parseable.
+    async fn read_meta(meta_path: &Path) -> Option<CacheMetadata> {
+        let content = fs::read_to_string(meta_path).await.ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Fetch from network, waiting for the host's request slot and retrying
+    /// 429 responses with backoff. `stale` is expired cache metadata to
+    /// revalidate with conditional headers.
+    async fn fetch_politely(
+        &self,
+        url: &Url,
+        cache_path: &Path,
+        meta_path: &Path,
+        stale: Option<&CacheMetadata>,
+    ) -> Result<FetchResult, FetchError> {
+        let host = url.host_str().unwrap_or("unknown").to_string();
+        let mut attempt = 0;
+        loop {
+            let wait = reserve_slot(&host, self.config.request_delay);
+            if !wait.is_zero() {
+                debug!("Waiting {:?} before requesting {}", wait, url);
+                tokio::time::sleep(wait).await;
+            }
+
+            match self
+                .fetch_and_cache(url, cache_path, meta_path, stale)
+                .await
+            {
+                Err(FetchError::RateLimited {
+                    retry_after_secs, ..
+                }) if attempt < self.config.rate_limit_retries
+                    && Duration::from_secs(retry_after_secs) <= self.config.max_retry_after =>
+                {
+                    attempt += 1;
+                    let backoff = rate_limit_backoff(retry_after_secs, attempt);
+                    warn!(
+                        "Rate limited by {}, retry {}/{} in {:?}",
+                        host, attempt, self.config.rate_limit_retries, backoff
+                    );
+                    defer_host(&host, Instant::now() + backoff);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Fetch from network and cache the result. With `stale` metadata the
+    /// request is conditional, and a 304 only refreshes the metadata.
     async fn fetch_and_cache(
         &self,
         url: &Url,
         cache_path: &Path,
         meta_path: &Path,
+        stale: Option<&CacheMetadata>,
     ) -> Result<FetchResult, FetchError> {
         info!("Fetching {}", url);
 
-        let response = self.client.get(url.as_str()).send().await?;
+        let mut request = self.client.get(url.as_str());
+        if let Some(meta) = stale {
+            if let Some(etag) = &meta.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(modified) = &meta.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, modified);
+            }
+        }
+        let response = request.send().await?;
 
         let status = response.status();
+        if let (reqwest::StatusCode::NOT_MODIFIED, Some(meta)) = (status, stale) {
+            info!("{} not modified, keeping cached copy", url);
+            let fetched_at = Utc::now();
+            let header = |name| {
+                response
+                    .headers()
+                    .get(name)
+                    .and_then(|v: &HeaderValue| v.to_str().ok())
+                    .map(|s| s.to_string())
+            };
+            let meta = CacheMetadata {
+                fetched_at,
+                etag: header(ETAG).or_else(|| meta.etag.clone()),
+                last_modified: header(LAST_MODIFIED).or_else(|| meta.last_modified.clone()),
+                expires_at: Some(
+                    fetched_at + chrono::Duration::seconds(self.config.cache_ttl.as_secs() as i64),
+                ),
+                ..meta.clone()
+            };
+            fs::write(meta_path, serde_json::to_string_pretty(&meta)?).await?;
+            return Ok(FetchResult {
+                url: url.clone(),
+                cache_path: cache_path.to_path_buf(),
+                content_type: meta.content_type,
+                content_length: meta.content_length,
+                fetched_at,
+                from_cache: true,
+                etag: meta.etag,
+                last_modified: meta.last_modified,
+            });
+        }
         if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
             let retry_after = response
                 .headers()
@@ -337,7 +505,7 @@ impl Fetcher {
     }
 
     /// Generate a cache path for a URL.
-    fn cache_path_for_url(&self, url: &Url) -> PathBuf {
+    pub(crate) fn cache_path_for_url(&self, url: &Url) -> PathBuf {
         let hash = Self::url_hash(url);
         let host = url.host_str().unwrap_or("unknown");
         let extension = Self::extension_for_url(url);
@@ -349,7 +517,7 @@ impl Fetcher {
     }
 
     /// Generate a metadata path for a URL.
-    fn meta_path_for_url(&self, url: &Url) -> PathBuf {
+    pub(crate) fn meta_path_for_url(&self, url: &Url) -> PathBuf {
         let hash = Self::url_hash(url);
         let host = url.host_str().unwrap_or("unknown");
 
@@ -406,6 +574,8 @@ mod tests {
             user_agent: "test-agent".to_string(),
             request_delay: Duration::from_millis(0),
             extra_headers: HashMap::new(),
+            rate_limit_retries: 0,
+            max_retry_after: Duration::from_secs(0),
         }
     }
 
@@ -546,6 +716,85 @@ mod tests {
         assert_eq!(bytes, b"\x00\x01\x02\x03");
     }
 
+    #[test]
+    fn test_reserve_slot_spaces_requests() {
+        let delay = Duration::from_millis(200);
+        assert!(reserve_slot("slots.test", delay).is_zero());
+        let second = reserve_slot("slots.test", delay);
+        let third = reserve_slot("slots.test", delay);
+        assert!(second > Duration::from_millis(150) && second <= delay);
+        assert!(third > Duration::from_millis(350) && third <= delay * 2);
+        // Other hosts are unaffected
+        assert!(reserve_slot("other-slots.test", delay).is_zero());
+
+        defer_host("slots.test", Instant::now() + Duration::from_secs(5));
+        assert!(reserve_slot("slots.test", delay) > Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_rate_limit_backoff() {
+        // Retry-After wins over the exponential floor
+        let wait = rate_limit_backoff(10, 1);
+        assert!(wait >= Duration::from_secs(10) && wait <= Duration::from_millis(12_500));
+        // No Retry-After: 1s, 2s, 4s...
+        let wait = rate_limit_backoff(0, 3);
+        assert!(wait >= Duration::from_secs(4) && wait <= Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_expired_cache_revalidates_with_etag() {
+        use axum::http::{header, HeaderMap as AxumHeaders, StatusCode};
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+
+        // Serves "v1" with an ETag, then 304 to matching revalidations
+        let downloads = Arc::new(AtomicU32::new(0));
+        let counter = downloads.clone();
+        let app = axum::Router::new().route(
+            "/page",
+            axum::routing::get(move |headers: AxumHeaders| {
+                let counter = counter.clone();
+                async move {
+                    if headers.get(header::IF_NONE_MATCH).map(|v| v.as_bytes()) == Some(b"\"v1\"") {
+                        return (StatusCode::NOT_MODIFIED, [(header::ETAG, "\"v1\"")], "");
+                    }
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    (StatusCode::OK, [(header::ETAG, "\"v1\"")], "page body")
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let temp_dir = TempDir::new().unwrap();
+        let fetcher = Fetcher::new(FetcherConfig {
+            cache_ttl: Duration::from_secs(0),
+            ..test_config(&temp_dir)
+        })
+        .unwrap();
+        let url = Url::parse(&format!("http://{}/page", addr)).unwrap();
+
+        let first = fetcher.fetch(&url).await.unwrap();
+        assert!(!first.from_cache);
+        assert_eq!(first.etag.as_deref(), Some("\"v1\""));
+
+        // Cache age is compared in whole seconds
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        let second = fetcher.fetch(&url).await.unwrap();
+        assert!(second.from_cache);
+        assert!(second.fetched_at > first.fetched_at);
+        assert_eq!(downloads.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            fetcher.read_cached_text(&second).await.unwrap(),
+            "page body"
+        );
+
+        // A forced fetch downloads again
+        fetcher.fetch_fresh(&url).await.unwrap();
+        assert_eq!(downloads.load(Ordering::SeqCst), 2);
+    }
+
     #[test]
     fn test_meta_path_for_url() {
         let temp_dir = TempDir::new().unwrap();
@@ -570,6 +819,8 @@ mod tests {
             user_agent: "test-agent".to_string(),
             request_delay: Duration::from_millis(0),
             extra_headers: HashMap::new(),
+            rate_limit_retries: 0,
+            max_retry_after: Duration::from_secs(0),
         };
         let fetcher = Fetcher::new(config).unwrap();
 
@@ -0,0 +1,911 @@
+//! Plain-text extraction from PDF documents.
+//!
+//! Covers what the Munitorum Field Manual and balance dataslates need:
+//! objects (including compressed object streams), Flate-encoded content
+//! streams, the text-showing operators and `ToUnicode` CMaps for subset
+//! fonts. Layout is approximated: a vertical move starts a new line, a
+//! horizontal gap inserts a space, and pages are separated by a blank line.
+
+use std::collections::HashMap;
+use std::io::Read;
+
+use flate2::read::ZlibDecoder;
+use thiserror::Error;
+
+/// Errors from text extraction.
+#[derive(Debug, Error)]
+pub enum PdfError {
+    #[error("Not a PDF document")]
+    NotPdf,
+
+    #[error("No pages found")]
+    NoPages,
+
+    #[error("No extractable text (scanned or image-only PDF?)")]
+    NoText,
+}
+
+/// TJ adjustment (thousandths of an em) treated as a word gap.
+const TJ_SPACE_THRESHOLD: f64 = -200.0;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Null,
+    Num(f64),
+    Name(String),
+    Str(Vec<u8>),
+    Array(Vec<Value>),
+    Dict(HashMap<String, Value>),
+    Ref(u32),
+    /// Bare keyword: a content stream operator or `stream`/`endobj`
+    Op(String),
+}
+
+impl Value {
+    fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Dict(d) => d.get(key),
+            _ => None,
+        }
+    }
+
+    fn as_name(&self) -> Option<&str> {
+        match self {
+            Value::Name(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    fn as_num(&self) -> Option<f64> {
+        match self {
+            Value::Num(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+fn is_whitespace(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r' | b'\x0c' | b'\0')
+}
+
+fn is_delimiter(b: u8) -> bool {
+    matches!(
+        b,
+        b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%'
+    )
+}
+
+struct Lexer<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn at(data: &'a [u8], pos: usize) -> Self {
+        Self { data, pos }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while let Some(b) = self.peek() {
+            if is_whitespace(b) {
+                self.pos += 1;
+            } else if b == b'%' {
+                while let Some(b) = self.peek() {
+                    if b == b'\n' || b == b'\r' {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn word(&mut self) -> &'a [u8] {
+        let start = self.pos;
+        while let Some(b) = self.peek() {
+            if is_whitespace(b) || is_delimiter(b) {
+                break;
+            }
+            self.pos += 1;
+        }
+        &self.data[start..self.pos]
+    }
+
+    fn next(&mut self) -> Option<Value> {
+        self.skip_ws();
+        let b = self.peek()?;
+        match b {
+            b'/' => {
+                self.pos += 1;
+                let raw = self.word();
+                Some(Value::Name(decode_name(raw)))
+            }
+            b'(' => Some(Value::Str(self.literal_string())),
+            b'<' if self.data.get(self.pos + 1) == Some(&b'<') => {
+                self.pos += 2;
+                let mut dict = HashMap::new();
+                loop {
+                    self.skip_ws();
+                    match self.peek() {
+                        None => break,
+                        Some(b'>') => {
+                            self.pos += 2;
+                            break;
+                        }
+                        _ => {}
+                    }
+                    let Some(Value::Name(key)) = self.next() else {
+                        continue;
+                    };
+                    let value = self.next().unwrap_or(Value::Null);
+                    dict.insert(key, value);
+                }
+                Some(Value::Dict(dict))
+            }
+            b'<' => Some(Value::Str(self.hex_string())),
+            b'[' => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    self.skip_ws();
+                    match self.peek() {
+                        None => break,
+                        Some(b']') => {
+                            self.pos += 1;
+                            break;
+                        }
+                        _ => {}
+                    }
+                    match self.next() {
+                        Some(v) => items.push(v),
+                        None => break,
+                    }
+                }
+                Some(Value::Array(items))
+            }
+            b'0'..=b'9' | b'+' | b'-' | b'.' => {
+                let raw = self.word();
+                let text = std::str::from_utf8(raw).unwrap_or("0");
+                let num: f64 = text.parse().unwrap_or(0.0);
+                // `12 0 R` is a reference
+                if !text.contains('.') {
+                    let save = self.pos;
+                    self.skip_ws();
+                    let generation = self.word();
+                    self.skip_ws();
+                    if !generation.is_empty()
+                        && generation.iter().all(u8::is_ascii_digit)
+                        && self.peek() == Some(b'R')
+                        && self
+                            .data
+                            .get(self.pos + 1)
+                            .is_none_or(|b| is_whitespace(*b) || is_delimiter(*b))
+                    {
+                        self.pos += 1;
+                        return Some(Value::Ref(num as u32));
+                    }
+                    self.pos = save;
+                }
+                Some(Value::Num(num))
+            }
+            b')' | b'>' | b']' | b'{' | b'}' => {
+                // Stray delimiter; skip it
+                self.pos += 1;
+                self.next()
+            }
+            _ => {
+                let raw = self.word();
+                Some(match raw {
+                    b"null" => Value::Null,
+                    _ => Value::Op(String::from_utf8_lossy(raw).into_owned()),
+                })
+            }
+        }
+    }
+
+    fn literal_string(&mut self) -> Vec<u8> {
+        self.pos += 1;
+        let mut out = Vec::new();
+        let mut depth = 1;
+        while let Some(b) = self.peek() {
+            self.pos += 1;
+            match b {
+                b'\\' => {
+                    let Some(e) = self.peek() else { break };
+                    self.pos += 1;
+                    match e {
+                        b'n' => out.push(b'\n'),
+                        b'r' => out.push(b'\r'),
+                        b't' => out.push(b'\t'),
+                        b'b' => out.push(8),
+                        b'f' => out.push(12),
+                        b'0'..=b'7' => {
+                            let mut code = (e - b'0') as u32;
+                            for _ in 0..2 {
+                                match self.peek() {
+                                    Some(d @ b'0'..=b'7') => {
+                                        code = code * 8 + (d - b'0') as u32;
+                                        self.pos += 1;
+                                    }
+                                    _ => break,
+                                }
+                            }
+                            out.push(code as u8);
+                        }
+                        // Line continuation
+                        b'\r' => {
+                            if self.peek() == Some(b'\n') {
+                                self.pos += 1;
+                            }
+                        }
+                        b'\n' => {}
+                        other => out.push(other),
+                    }
+                }
+                b'(' => {
+                    depth += 1;
+                    out.push(b);
+                }
+                b')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                    out.push(b);
+                }
+                _ => out.push(b),
+            }
+        }
+        out
+    }
+
+    fn hex_string(&mut self) -> Vec<u8> {
+        self.pos += 1;
+        let mut digits = Vec::new();
+        while let Some(b) = self.peek() {
+            self.pos += 1;
+            if b == b'>' {
+                break;
+            }
+            if let Some(d) = (b as char).to_digit(16) {
+                digits.push(d as u8);
+            }
+        }
+        if digits.len() % 2 == 1 {
+            digits.push(0);
+        }
+        digits.chunks(2).map(|p| p[0] << 4 | p[1]).collect()
+    }
+}
+
+fn decode_name(raw: &[u8]) -> String {
+    let mut out = Vec::with_capacity(raw.len());
+    let mut i = 0;
+    while i < raw.len() {
+        if raw[i] == b'#' && i + 2 < raw.len() {
+            if let Ok(b) = u8::from_str_radix(&String::from_utf8_lossy(&raw[i + 1..i + 3]), 16) {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(raw[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+struct Object {
+    value: Value,
+    /// Decoded stream data, for stream objects
+    stream: Option<Vec<u8>>,
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|p| p + from)
+}
+
+/// Decode a stream's data according to its `/Filter`. `None` for filters
+/// that don't carry text (images) or data that fails to inflate.
+fn decode_stream(dict: &Value, raw: &[u8]) -> Option<Vec<u8>> {
+    let filters: Vec<&str> = match dict.get("Filter") {
+        None => Vec::new(),
+        Some(Value::Name(n)) => vec![n.as_str()],
+        Some(Value::Array(items)) => items.iter().filter_map(Value::as_name).collect(),
+        Some(_) => return None,
+    };
+    let mut data = raw.to_vec();
+    for filter in filters {
+        match filter {
+            "FlateDecode" | "Fl" => {
+                let mut out = Vec::new();
+                // Truncated streams still yield what inflated cleanly
+                let _ = ZlibDecoder::new(data.as_slice()).read_to_end(&mut out);
+                if out.is_empty() {
+                    return None;
+                }
+                data = out;
+            }
+            _ => return None,
+        }
+    }
+    Some(data)
+}
+
+/// Find every `N G obj ... endobj` in the file, then unpack object streams.
+/// Later definitions win, as with incremental updates.
+fn parse_objects(data: &[u8]) -> HashMap<u32, Object> {
+    let mut objects = HashMap::new();
+    let mut pos = 0;
+    while let Some(at) = find(data, b"obj", pos) {
+        pos = at + 3;
+        if data
+            .get(pos)
+            .is_some_and(|b| !is_whitespace(*b) && !is_delimiter(*b))
+        {
+            continue;
+        }
+        // Walk back over "N G "
+        let mut i = at;
+        let mut numbers = Vec::new();
+        for _ in 0..2 {
+            while i > 0 && is_whitespace(data[i - 1]) {
+                i -= 1;
+            }
+            let end = i;
+            while i > 0 && data[i - 1].is_ascii_digit() {
+                i -= 1;
+            }
+            if i == end {
+                break;
+            }
+            numbers.push(String::from_utf8_lossy(&data[i..end]).into_owned());
+        }
+        let [_generation, num] = numbers.as_slice() else {
+            continue;
+        };
+        let Ok(num) = num.parse::<u32>() else {
+            continue;
+        };
+
+        let mut lexer = Lexer::at(data, pos);
+        let Some(value) = lexer.next() else { continue };
+        let after_value = lexer.pos;
+        let mut stream = None;
+        if let Some(Value::Op(op)) = lexer.next() {
+            if op == "stream" {
+                let mut start = lexer.pos;
+                if data.get(start) == Some(&b'\r') {
+                    start += 1;
+                }
+                if data.get(start) == Some(&b'\n') {
+                    start += 1;
+                }
+                let declared = value
+                    .get("Length")
+                    .and_then(Value::as_num)
+                    .map(|n| start + n as usize)
+                    .filter(|end| data.get(*end..).is_some_and(|rest| rest.len() >= 9));
+                let end = declared
+                    .filter(|end| find(data, b"endstream", *end).is_some_and(|e| e - end <= 2))
+                    .or_else(|| find(data, b"endstream", start));
+                if let Some(end) = end {
+                    stream = decode_stream(&value, &data[start..end]);
+                    pos = end;
+                }
+            } else {
+                pos = after_value;
+            }
+        }
+        objects.insert(num, Object { value, stream });
+    }
+
+    // Compressed object streams (PDF 1.5+)
+    let containers: Vec<(Vec<u8>, usize, usize)> = objects
+        .values()
+        .filter(|o| o.value.get("Type").and_then(Value::as_name) == Some("ObjStm"))
+        .filter_map(|o| {
+            let n = o.value.get("N")?.as_num()? as usize;
+            let first = o.value.get("First")?.as_num()? as usize;
+            Some((o.stream.clone()?, n, first))
+        })
+        .collect();
+    for (data, n, first) in containers {
+        let mut header = Lexer::new(&data[..first.min(data.len())]);
+        for _ in 0..n {
+            let (Some(Value::Num(num)), Some(Value::Num(offset))) = (header.next(), header.next())
+            else {
+                break;
+            };
+            let mut lexer = Lexer::at(&data, first + offset as usize);
+            if let Some(value) = lexer.next() {
+                objects.entry(num as u32).or_insert(Object {
+                    value,
+                    stream: None,
+                });
+            }
+        }
+    }
+    objects
+}
+
+/// Code-to-Unicode mapping from a font's `/ToUnicode` CMap.
+#[derive(Debug, Default)]
+struct CMap {
+    /// Bytes per character code
+    code_len: usize,
+    map: HashMap<u32, String>,
+}
+
+fn code_of(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0u32, |acc, b| acc << 8 | *b as u32)
+}
+
+fn utf16(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks(2)
+        .map(|c| (c[0] as u16) << 8 | *c.get(1).unwrap_or(&0) as u16)
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+fn parse_cmap(data: &[u8]) -> CMap {
+    let mut lexer = Lexer::new(data);
+    let mut cmap = CMap::default();
+    let mut operands: Vec<Value> = Vec::new();
+    let mut mode = "";
+    while let Some(value) = lexer.next() {
+        let Value::Op(op) = value else {
+            operands.push(value);
+            continue;
+        };
+        match op.as_str() {
+            "begincodespacerange" | "beginbfchar" | "beginbfrange" => {
+                mode = match op.as_str() {
+                    "begincodespacerange" => "space",
+                    "beginbfchar" => "char",
+                    _ => "range",
+                };
+                operands.clear();
+            }
+            "endcodespacerange" => {
+                if let Some(Value::Str(lo)) = operands.first() {
+                    cmap.code_len = lo.len();
+                }
+                mode = "";
+                operands.clear();
+            }
+            "endbfchar" => {
+                for pair in operands.chunks(2) {
+                    if let [Value::Str(src), Value::Str(dst)] = pair {
+                        cmap.code_len = cmap.code_len.max(src.len());
+                        cmap.map.insert(code_of(src), utf16(dst));
+                    }
+                }
+                mode = "";
+                operands.clear();
+            }
+            "endbfrange" => {
+                for triple in operands.chunks(3) {
+                    let [Value::Str(lo), Value::Str(hi), dst] = triple else {
+                        continue;
+                    };
+                    cmap.code_len = cmap.code_len.max(lo.len());
+                    let (lo_code, hi_code) = (code_of(lo), code_of(hi));
+                    for (i, code) in (lo_code..=hi_code.min(lo_code + 0xffff)).enumerate() {
+                        let text = match dst {
+                            Value::Str(base) if !base.is_empty() => {
+                                let mut bytes = base.clone();
+                                let last = bytes.len() - 1;
+                                bytes[last] = bytes[last].wrapping_add(i as u8);
+                                utf16(&bytes)
+                            }
+                            Value::Array(items) => match items.get(i) {
+                                Some(Value::Str(s)) => utf16(s),
+                                _ => continue,
+                            },
+                            _ => continue,
+                        };
+                        cmap.map.insert(code, text);
+                    }
+                }
+                mode = "";
+                operands.clear();
+            }
+            _ if mode.is_empty() => operands.clear(),
+            _ => {}
+        }
+    }
+    if cmap.code_len == 0 {
+        cmap.code_len = 1;
+    }
+    cmap
+}
+
+struct Document {
+    objects: HashMap<u32, Object>,
+}
+
+impl Document {
+    fn resolve<'a>(&'a self, value: &'a Value) -> &'a Value {
+        let mut current = value;
+        for _ in 0..8 {
+            match current {
+                Value::Ref(num) => match self.objects.get(num) {
+                    Some(obj) => current = &obj.value,
+                    None => return &Value::Null,
+                },
+                _ => return current,
+            }
+        }
+        current
+    }
+
+    fn stream(&self, value: &Value) -> Option<&[u8]> {
+        match value {
+            Value::Ref(num) => self.objects.get(num)?.stream.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Page dictionaries in document order.
+    fn pages(&self) -> Vec<&Value> {
+        let root = self
+            .objects
+            .values()
+            .find(|o| o.value.get("Type").and_then(Value::as_name) == Some("Catalog"))
+            .and_then(|o| o.value.get("Pages"));
+        let mut pages = Vec::new();
+        if let Some(root) = root {
+            self.collect_pages(root, &mut pages, 0);
+        }
+        if pages.is_empty() {
+            // No usable page tree: fall back to object order
+            let mut nums: Vec<&u32> = self
+                .objects
+                .iter()
+                .filter(|(_, o)| o.value.get("Type").and_then(Value::as_name) == Some("Page"))
+                .map(|(n, _)| n)
+                .collect();
+            nums.sort();
+            pages = nums.into_iter().map(|n| &self.objects[n].value).collect();
+        }
+        pages
+    }
+
+    fn collect_pages<'a>(&'a self, node: &'a Value, out: &mut Vec<&'a Value>, depth: usize) {
+        if depth > 32 {
+            return;
+        }
+        let node = self.resolve(node);
+        match node.get("Type").and_then(Value::as_name) {
+            Some("Page") => out.push(node),
+            _ => {
+                if let Some(Value::Array(kids)) = node.get("Kids").map(|k| self.resolve(k)) {
+                    for kid in kids {
+                        self.collect_pages(kid, out, depth + 1);
+                    }
+                }
+            }
+        }
+    }
+
+    /// A page attribute, inherited from ancestors in the page tree.
+    fn inherited<'a>(&'a self, page: &'a Value, key: &str) -> Option<&'a Value> {
+        let mut node = page;
+        for _ in 0..32 {
+            if let Some(v) = node.get(key) {
+                return Some(self.resolve(v));
+            }
+            node = self.resolve(node.get("Parent")?);
+        }
+        None
+    }
+
+    fn fonts(&self, page: &Value) -> HashMap<String, CMap> {
+        let mut fonts = HashMap::new();
+        let Some(Value::Dict(font_dict)) = self
+            .inherited(page, "Resources")
+            .and_then(|r| r.get("Font"))
+            .map(|f| self.resolve(f))
+        else {
+            return fonts;
+        };
+        for (name, font) in font_dict {
+            let font = self.resolve(font);
+            if let Some(data) = font.get("ToUnicode").and_then(|t| self.stream(t)) {
+                fonts.insert(name.clone(), parse_cmap(data));
+            }
+        }
+        fonts
+    }
+
+    fn contents(&self, page: &Value) -> Vec<u8> {
+        let mut out = Vec::new();
+        let refs: Vec<&Value> = match page.get("Contents") {
+            Some(v @ Value::Ref(_)) => match self.resolve(v) {
+                Value::Array(items) => items.iter().collect(),
+                _ => vec![v],
+            },
+            Some(Value::Array(items)) => items.iter().collect(),
+            _ => Vec::new(),
+        };
+        for r in refs {
+            if let Some(data) = self.stream(r) {
+                out.extend_from_slice(data);
+                out.push(b'\n');
+            }
+        }
+        out
+    }
+}
+
+/// Accumulates shown text into lines.
+#[derive(Default)]
+struct TextWriter {
+    out: String,
+    line: String,
+}
+
+impl TextWriter {
+    fn newline(&mut self) {
+        let line = self.line.trim();
+        if !line.is_empty() {
+            self.out.push_str(line);
+            self.out.push('\n');
+        }
+        self.line.clear();
+    }
+
+    fn space(&mut self) {
+        if !self.line.is_empty() && !self.line.ends_with(' ') {
+            self.line.push(' ');
+        }
+    }
+
+    fn show(&mut self, bytes: &[u8], cmap: Option<&CMap>) {
+        match cmap {
+            Some(cmap) => {
+                for code in bytes.chunks(cmap.code_len.max(1)) {
+                    if let Some(text) = cmap.map.get(&code_of(code)) {
+                        self.line.push_str(text);
+                    }
+                }
+            }
+            // Simple fonts: treat codes as Latin-1
+            None => self.line.extend(bytes.iter().map(|b| *b as char)),
+        }
+    }
+}
+
+fn page_text(content: &[u8], fonts: &HashMap<String, CMap>, writer: &mut TextWriter) {
+    let mut lexer = Lexer::new(content);
+    let mut operands: Vec<Value> = Vec::new();
+    let mut font: Option<&CMap> = None;
+    let mut last_y: Option<f64> = None;
+
+    while let Some(value) = lexer.next() {
+        let Value::Op(op) = value else {
+            operands.push(value);
+            continue;
+        };
+        let num = |i: usize| operands.get(i).and_then(Value::as_num).unwrap_or(0.0);
+        match op.as_str() {
+            "Tf" => {
+                font = operands
+                    .first()
+                    .and_then(Value::as_name)
+                    .and_then(|n| fonts.get(n));
+            }
+            "Td" | "TD" => {
+                if num(1).abs() > 0.01 {
+                    writer.newline();
+                } else if num(0) > 0.0 {
+                    writer.space();
+                }
+            }
+            "Tm" => {
+                let y = num(5);
+                if last_y.is_some_and(|last| (last - y).abs() > 0.01) {
+                    writer.newline();
+                } else {
+                    writer.space();
+                }
+                last_y = Some(y);
+            }
+            "T*" => writer.newline(),
+            "Tj" | "'" | "\"" => {
+                if op != "Tj" {
+                    writer.newline();
+                }
+                if let Some(Value::Str(s)) = operands.last() {
+                    writer.show(s, font);
+                }
+            }
+            "TJ" => {
+                if let Some(Value::Array(items)) = operands.last() {
+                    for item in items {
+                        match item {
+                            Value::Str(s) => writer.show(s, font),
+                            Value::Num(n) if *n < TJ_SPACE_THRESHOLD => writer.space(),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            "ET" => writer.space(),
+            // Inline image data is binary; skip to its end marker
+            "ID" => {
+                let end = find(lexer.data, b"EI", lexer.pos)
+                    .filter(|at| lexer.data.get(at - 1).is_some_and(|b| is_whitespace(*b)));
+                lexer.pos = end.map(|e| e + 2).unwrap_or(lexer.data.len());
+            }
+            _ => {}
+        }
+        operands.clear();
+    }
+    writer.newline();
+}
+
+/// Extract the text of every page, pages separated by a blank line.
+pub fn extract_text(bytes: &[u8]) -> Result<String, PdfError> {
+    if find(&bytes[..bytes.len().min(1024)], b"%PDF-", 0).is_none() {
+        return Err(PdfError::NotPdf);
+    }
+    let doc = Document {
+        objects: parse_objects(bytes),
+    };
+    let pages = doc.pages();
+    if pages.is_empty() {
+        return Err(PdfError::NoPages);
+    }
+
+    let mut text = String::new();
+    for page in pages {
+        let mut writer = TextWriter::default();
+        page_text(&doc.contents(page), &doc.fonts(page), &mut writer);
+        if !writer.out.is_empty() {
+            text.push_str(&writer.out);
+            text.push('\n');
+        }
+    }
+    if text.trim().is_empty() {
+        return Err(PdfError::NoText);
+    }
+    Ok(text.trim_end().to_string())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    /// Assemble a PDF from `(number, body)` objects; stream bodies are
+    /// given as `(dict, data)` and compressed when `compress` is set.
+    pub(crate) fn build_pdf(objects: &[(u32, String, Option<Vec<u8>>)], compress: bool) -> Vec<u8> {
+        let mut out = b"%PDF-1.5\n".to_vec();
+        for (num, dict, stream) in objects {
+            out.extend_from_slice(format!("{} 0 obj\n", num).as_bytes());
+            match stream {
+                Some(data) => {
+                    let (data, filter) = if compress {
+                        let mut enc = ZlibEncoder::new(Vec::new(), Compression::default());
+                        enc.write_all(data).unwrap();
+                        (enc.finish().unwrap(), " /Filter /FlateDecode")
+                    } else {
+                        (data.clone(), "")
+                    };
+                    out.extend_from_slice(
+                        format!("<< {} /Length {}{} >>\nstream\n", dict, data.len(), filter)
+                            .as_bytes(),
+                    );
+                    out.extend_from_slice(&data);
+                    out.extend_from_slice(b"\nendstream\n");
+                }
+                None => out.extend_from_slice(dict.as_bytes()),
+            }
+            out.extend_from_slice(b"\nendobj\n");
+        }
+        out.extend_from_slice(b"trailer\n<< /Root 1 0 R >>\n%%EOF\n");
+        out
+    }
+
+    /// A one-page PDF showing `lines` in a simple font.
+    pub(crate) fn text_pdf(lines: &[&str]) -> Vec<u8> {
+        let mut content = String::from("BT /F1 10 Tf 72 720 Td\n");
+        for line in lines {
+            let escaped = line
+                .replace('\\', "\\\\")
+                .replace('(', "\\(")
+                .replace(')', "\\)");
+            content.push_str(&format!("({}) Tj 0 -12 Td\n", escaped));
+        }
+        content.push_str("ET");
+        build_pdf(
+            &[
+                (1, "<< /Type /Catalog /Pages 2 0 R >>".into(), None),
+                (2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".into(), None),
+                (
+                    3,
+                    "<< /Type /Page /Parent 2 0 R /Resources << /Font << /F1 4 0 R >> >> \
+                     /Contents 5 0 R >>"
+                        .into(),
+                    None,
+                ),
+                (
+                    4,
+                    "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".into(),
+                    None,
+                ),
+                (5, String::new(), Some(content.into_bytes())),
+            ],
+            true,
+        )
+    }
+
+    #[test]
+    fn test_extract_text_simple_font() {
+        let pdf = text_pdf(&["Wraithguard (5 models)", "170 pts"]);
+        assert_eq!(
+            extract_text(&pdf).unwrap(),
+            "Wraithguard (5 models)\n170 pts"
+        );
+        assert!(matches!(extract_text(b"<html>"), Err(PdfError::NotPdf)));
+    }
+
+    #[test]
+    fn test_extract_text_tounicode_and_kerning() {
+        // Two-byte codes: 0001 = 'F', 0002..0004 = "ire", 0005 = ' '
+        let cmap = "/CIDInit /ProcSet findresource begin 12 dict begin begincmap \
+                    1 begincodespacerange <0000> <FFFF> endcodespacerange \
+                    2 beginbfchar <0001> <0046> <0005> <0020> endbfchar \
+                    1 beginbfrange <0002> <0004> [<0069> <0072> <0065>] endbfrange \
+                    1 beginbfrange <0010> <0011> <0044> endbfrange \
+                    endcmap";
+        let content = "BT /F2 9 Tf 1 0 0 1 50 700 Tm [<0001000200030004>-300<00100011>] TJ \
+                       1 0 0 1 50 688 Tm <00100011> Tj ET";
+        let pdf = build_pdf(
+            &[
+                (1, "<< /Type /Catalog /Pages 2 0 R >>".into(), None),
+                (
+                    2,
+                    "<< /Type /Pages /Kids [3 0 R] /Count 1 \
+                     /Resources << /Font << /F2 4 0 R >> >> >>"
+                        .into(),
+                    None,
+                ),
+                (
+                    3,
+                    "<< /Type /Page /Parent 2 0 R /Contents [5 0 R] >>".into(),
+                    None,
+                ),
+                (
+                    4,
+                    "<< /Type /Font /Subtype /Type0 /ToUnicode 6 0 R >>".into(),
+                    None,
+                ),
+                (5, String::new(), Some(content.as_bytes().to_vec())),
+                (6, String::new(), Some(cmap.as_bytes().to_vec())),
+            ],
+            true,
+        );
+        // Resources are inherited from the page tree
+        assert_eq!(extract_text(&pdf).unwrap(), "Fire DE\nDE");
+    }
+}
@@ -0,0 +1,517 @@
+//! Generic CSV import for events, placements and army lists.
+//!
+//! Tournament organisers often keep results in spreadsheets. Each entity
+//! has a set of fields with common header aliases ("Pos", "Player",
+//! "Army", ...); a [`ColumnMapping`] loaded from TOML can point fields at
+//! any other header. Rows are validated into model records (whose IDs come
+//! from `EntityId::generate`, so re-importing is idempotent) and rows that
+//! fail validation are reported with their line number instead of aborting
+//! the import.
+//!
+//! Mapping file format:
+//!
+//! ```toml
+//! [columns]
+//! rank = "Final Position"
+//! player_name = "Name"
+//! faction = "Army"
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::models::{ArmyList, Confidence, EntityId, Event, Placement};
+use crate::parsing::armylist::parse_units_from_raw_text;
+
+/// Errors that abort a CSV import.
+#[derive(Debug, Error)]
+pub enum CsvImportError {
+    #[error("Invalid CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("Failed to read mapping file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid mapping file: {0}")]
+    Mapping(#[from] toml::de::Error),
+
+    #[error("Mapping refers to unknown {entity} field '{field}'")]
+    UnknownField { entity: CsvEntity, field: String },
+
+    #[error("No column found for required field '{0}'")]
+    MissingColumn(&'static str),
+
+    #[error("Unknown entity '{0}' (expected events, placements or lists)")]
+    UnknownEntity(String),
+}
+
+/// Entity a CSV file holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvEntity {
+    Events,
+    Placements,
+    Lists,
+}
+
+impl CsvEntity {
+    /// Fields for this entity: (name, required, header aliases).
+    fn fields(self) -> &'static [FieldSpec] {
+        match self {
+            CsvEntity::Events => EVENT_FIELDS,
+            CsvEntity::Placements => PLACEMENT_FIELDS,
+            CsvEntity::Lists => LIST_FIELDS,
+        }
+    }
+}
+
+impl fmt::Display for CsvEntity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CsvEntity::Events => "events",
+            CsvEntity::Placements => "placements",
+            CsvEntity::Lists => "lists",
+        })
+    }
+}
+
+impl FromStr for CsvEntity {
+    type Err = CsvImportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "events" | "event" => Ok(CsvEntity::Events),
+            "placements" | "placement" | "results" => Ok(CsvEntity::Placements),
+            "lists" | "list" | "army_lists" | "army-lists" => Ok(CsvEntity::Lists),
+            other => Err(CsvImportError::UnknownEntity(other.to_string())),
+        }
+    }
+}
+
+struct FieldSpec {
+    name: &'static str,
+    required: bool,
+    aliases: &'static [&'static str],
+}
+
+const fn field(name: &'static str, required: bool, aliases: &'static [&'static str]) -> FieldSpec {
+    FieldSpec {
+        name,
+        required,
+        aliases,
+    }
+}
+
+const EVENT_FIELDS: &[FieldSpec] = &[
+    field("name", true, &["event", "eventname", "tournament", "title"]),
+    field("date", true, &["eventdate", "startdate", "day"]),
+    field("location", false, &["venue", "city", "where"]),
+    field(
+        "player_count",
+        false,
+        &["players", "playercount", "attendance"],
+    ),
+    field("round_count", false, &["rounds", "roundcount"]),
+    field("source_url", false, &["url", "link", "source"]),
+];
+
+const PLACEMENT_FIELDS: &[FieldSpec] = &[
+    field(
+        "rank",
+        true,
+        &["placing", "place", "position", "pos", "standing", "#"],
+    ),
+    field("player_name", true, &["player", "name", "playername"]),
+    field("faction", true, &["army", "race", "codex"]),
+    field("subfaction", false, &["chapter", "subfactionname"]),
+    field("detachment", false, &[]),
+    field("wins", false, &["w", "won"]),
+    field("losses", false, &["l", "lost"]),
+    field("draws", false, &["d", "drawn", "ties"]),
+    field(
+        "battle_points",
+        false,
+        &["bp", "points", "vp", "score", "totalpoints"],
+    ),
+];
+
+const LIST_FIELDS: &[FieldSpec] = &[
+    field("player_name", false, &["player", "name", "playername"]),
+    field("faction", true, &["army", "race", "codex"]),
+    field("detachment", false, &[]),
+    field("total_points", false, &["points", "pts", "listpoints"]),
+    field("raw_text", true, &["list", "armylist", "listtext", "text"]),
+];
+
+/// Field -> CSV header overrides.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ColumnMapping {
+    #[serde(default)]
+    pub columns: HashMap<String, String>,
+}
+
+impl ColumnMapping {
+    /// Parse a mapping from TOML.
+    pub fn from_toml(content: &str) -> Result<Self, CsvImportError> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// Load a mapping file.
+    pub fn load(path: &Path) -> Result<Self, CsvImportError> {
+        Self::from_toml(&std::fs::read_to_string(path)?)
+    }
+
+    /// Check every mapped field exists for the entity.
+    fn validate(&self, entity: CsvEntity) -> Result<(), CsvImportError> {
+        for field in self.columns.keys() {
+            if !entity.fields().iter().any(|f| f.name == field) {
+                return Err(CsvImportError::UnknownField {
+                    entity,
+                    field: field.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A row that failed validation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowError {
+    /// 1-based line in the file (the header is line 1)
+    pub line: u64,
+    pub message: String,
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Valid records and per-row errors from one file.
+#[derive(Debug, Clone)]
+pub struct CsvImport<T> {
+    pub records: Vec<T>,
+    pub errors: Vec<RowError>,
+    /// Field -> header actually used
+    pub columns: Vec<(&'static str, String)>,
+}
+
+fn normalize_header(h: &str) -> String {
+    h.chars()
+        .filter(|c| c.is_alphanumeric() || *c == '#')
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Resolved column positions for one file.
+struct Columns {
+    positions: HashMap<&'static str, usize>,
+    used: Vec<(&'static str, String)>,
+}
+
+impl Columns {
+    fn resolve(
+        headers: &csv::StringRecord,
+        entity: CsvEntity,
+        mapping: &ColumnMapping,
+    ) -> Result<Self, CsvImportError> {
+        mapping.validate(entity)?;
+        let normalized: Vec<String> = headers.iter().map(normalize_header).collect();
+        let mut positions = HashMap::new();
+        let mut used = Vec::new();
+
+        for spec in entity.fields() {
+            let position = match mapping.columns.get(spec.name) {
+                Some(header) => {
+                    let wanted = normalize_header(header);
+                    normalized.iter().position(|h| *h == wanted)
+                }
+                None => normalized.iter().position(|h| {
+                    *h == normalize_header(spec.name) || spec.aliases.contains(&h.as_str())
+                }),
+            };
+            match position {
+                Some(i) => {
+                    positions.insert(spec.name, i);
+                    used.push((spec.name, headers.get(i).unwrap_or_default().to_string()));
+                }
+                None if spec.required => return Err(CsvImportError::MissingColumn(spec.name)),
+                None => {}
+            }
+        }
+        Ok(Self { positions, used })
+    }
+}
+
+/// Accessor for one row's fields.
+struct Row<'a> {
+    record: &'a csv::StringRecord,
+    columns: &'a Columns,
+}
+
+impl Row<'_> {
+    fn get(&self, field: &str) -> Option<String> {
+        self.columns
+            .positions
+            .get(field)
+            .and_then(|&i| self.record.get(i))
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    }
+
+    fn required(&self, field: &str) -> Result<String, String> {
+        self.get(field).ok_or_else(|| format!("missing {}", field))
+    }
+
+    fn number(&self, field: &str) -> Result<Option<u32>, String> {
+        self.get(field)
+            .map(|v| {
+                v.trim_end_matches('.')
+                    .replace(',', "")
+                    .parse::<u32>()
+                    .map_err(|_| format!("{} '{}' is not a whole number", field, v))
+            })
+            .transpose()
+    }
+}
+
+fn parse_rows<T>(
+    content: &str,
+    entity: CsvEntity,
+    mapping: &ColumnMapping,
+    mut build: impl FnMut(&Row) -> Result<T, String>,
+) -> Result<CsvImport<T>, CsvImportError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.trim_start_matches('\u{feff}').as_bytes());
+    let headers = reader.headers()?.clone();
+    let columns = Columns::resolve(&headers, entity, mapping)?;
+
+    let mut records = Vec::new();
+    let mut errors = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        if record.iter().all(|v| v.trim().is_empty()) {
+            continue;
+        }
+        let line = record.position().map(|p| p.line()).unwrap_or(0);
+        let row = Row {
+            record: &record,
+            columns: &columns,
+        };
+        match build(&row) {
+            Ok(r) => records.push(r),
+            Err(message) => errors.push(RowError { line, message }),
+        }
+    }
+    Ok(CsvImport {
+        records,
+        errors,
+        columns: columns.used,
+    })
+}
+
+pub(crate) fn parse_date(s: &str) -> Result<NaiveDate, String> {
+    ["%Y-%m-%d", "%d/%m/%Y", "%d.%m.%Y"]
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(s, fmt).ok())
+        .ok_or_else(|| format!("date '{}' is not YYYY-MM-DD, DD/MM/YYYY or DD.MM.YYYY", s))
+}
+
+/// Parse events. `epoch_for` assigns each event's epoch from its date;
+/// rows without a `source_url` column get `default_source_url`.
+pub fn parse_events(
+    content: &str,
+    mapping: &ColumnMapping,
+    default_source_url: &str,
+    epoch_for: impl Fn(NaiveDate) -> EntityId,
+) -> Result<CsvImport<Event>, CsvImportError> {
+    parse_rows(content, CsvEntity::Events, mapping, |row| {
+        let name = row.required("name")?;
+        let date = parse_date(&row.required("date")?)?;
+        let url = row
+            .get("source_url")
+            .unwrap_or_else(|| default_source_url.to_string());
+        let mut event = Event::new(name, date, url, "csv".to_string(), epoch_for(date))
+            .with_confidence(Confidence::High);
+        if let Some(location) = row.get("location") {
+            event = event.with_location(location);
+        }
+        if let Some(count) = row.number("player_count")? {
+            event = event.with_player_count(count);
+        }
+        if let Some(count) = row.number("round_count")? {
+            event = event.with_round_count(count);
+        }
+        Ok(event)
+    })
+}
+
+/// Parse placements for an existing event.
+pub fn parse_placements(
+    content: &str,
+    mapping: &ColumnMapping,
+    event: &Event,
+) -> Result<CsvImport<Placement>, CsvImportError> {
+    parse_rows(content, CsvEntity::Placements, mapping, |row| {
+        let rank = row.number("rank")?.ok_or("missing rank")?;
+        if rank == 0 {
+            return Err("rank must be 1 or higher".to_string());
+        }
+        let mut placement = Placement::new(
+            event.id.clone(),
+            event.epoch_id.clone(),
+            rank,
+            row.required("player_name")?,
+            row.required("faction")?,
+        )
+        .with_confidence(Confidence::High);
+        if let Some(sub) = row.get("subfaction") {
+            placement = placement.with_subfaction(sub);
+        }
+        if let Some(det) = row.get("detachment") {
+            placement = placement.with_detachment(det);
+        }
+        let (w, l, d) = (
+            row.number("wins")?,
+            row.number("losses")?,
+            row.number("draws")?,
+        );
+        if w.is_some() || l.is_some() || d.is_some() {
+            placement = placement.with_record(w.unwrap_or(0), l.unwrap_or(0), d.unwrap_or(0));
+        }
+        if let Some(bp) = row.number("battle_points")? {
+            placement = placement.with_battle_points(bp);
+        }
+        Ok(placement)
+    })
+}
+
+/// Parse army lists for an existing event. Units are parsed from the list
+/// text with the same regex parser as BCP lists; total points fall back to
+/// the sum of unit points.
+pub fn parse_lists(
+    content: &str,
+    mapping: &ColumnMapping,
+    event: &Event,
+) -> Result<CsvImport<ArmyList>, CsvImportError> {
+    parse_rows(content, CsvEntity::Lists, mapping, |row| {
+        let raw_text = row.required("raw_text")?;
+        let units = parse_units_from_raw_text(&raw_text);
+        let total_points = match row.number("total_points")? {
+            Some(p) => p,
+            None => units.iter().filter_map(|u| u.points).sum(),
+        };
+        let confidence = if units.is_empty() {
+            Confidence::Low
+        } else {
+            Confidence::High
+        };
+        let mut list = ArmyList::new(row.required("faction")?, total_points, units, raw_text)
+            .with_event_id(event.id.clone())
+            .with_event_date(event.date)
+            .with_source_url(event.source_url.clone())
+            .with_confidence(confidence);
+        if let Some(det) = row.get("detachment") {
+            list = list.with_detachment(det);
+        }
+        if let Some(name) = row.get("player_name") {
+            list = list.with_player_name(name);
+        }
+        Ok(list)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> Event {
+        Event::new(
+            "Spreadsheet GT".to_string(),
+            NaiveDate::from_ymd_opt(2025, 6, 14).unwrap(),
+            "file://results.csv".to_string(),
+            "csv".to_string(),
+            EntityId::from("current"),
+        )
+    }
+
+    #[test]
+    fn test_parse_placements_with_aliases_and_row_errors() {
+        let csv = "Pos,Player,Army,W,L,D,BP\n\
+                   1,Alice,Aeldari,5,0,0,94\n\
+                   2,Bob,,4,1,0,80\n\
+                   x,Cara,Orks,3,2,0,70\n\
+                   ,,,,,,\n\
+                   4,Dan,Necrons,,,,\n";
+        let import = parse_placements(csv, &ColumnMapping::default(), &event()).unwrap();
+
+        assert_eq!(import.records.len(), 2);
+        assert_eq!(import.records[0].player_name, "Alice");
+        assert_eq!(import.records[0].battle_points, Some(94));
+        assert_eq!(import.records[0].record.as_ref().unwrap().wins, 5);
+        assert!(import.records[1].record.is_none());
+
+        assert_eq!(import.errors.len(), 2);
+        assert_eq!(import.errors[0].line, 3);
+        assert_eq!(import.errors[0].message, "missing faction");
+        assert!(import.errors[1]
+            .message
+            .contains("'x' is not a whole number"));
+
+        // Same rows again produce the same IDs
+        let again = parse_placements(csv, &ColumnMapping::default(), &event()).unwrap();
+        assert_eq!(again.records[0].id, import.records[0].id);
+    }
+
+    #[test]
+    fn test_mapping_file_overrides_headers() {
+        let mapping = ColumnMapping::from_toml(
+            "[columns]\nrank = \"Final Position\"\nplayer_name = \"Nom\"\nfaction = \"Armée\"\n",
+        )
+        .unwrap();
+        let csv = "Final Position,Nom,Armée\n1,Alice,Aeldari\n";
+        let import = parse_placements(csv, &mapping, &event()).unwrap();
+        assert_eq!(import.records[0].faction, "Aeldari");
+        assert!(import
+            .columns
+            .contains(&("rank", "Final Position".to_string())));
+
+        let bad = ColumnMapping::from_toml("[columns]\nelo = \"Rating\"\n").unwrap();
+        assert!(matches!(
+            parse_placements(csv, &bad, &event()),
+            Err(CsvImportError::UnknownField { .. })
+        ));
+        assert!(matches!(
+            parse_placements(
+                "Name,Army\nAlice,Aeldari\n",
+                &ColumnMapping::default(),
+                &event()
+            ),
+            Err(CsvImportError::MissingColumn("rank"))
+        ));
+    }
+
+    #[test]
+    fn test_parse_events_assigns_epoch_by_date() {
+        let csv = "Event,Date,Players\nSpring RTT,12/04/2025,24\nSummer GT,2025-07-19,80\n";
+        let cutoff = NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+        let import = parse_events(csv, &ColumnMapping::default(), "file://events.csv", |d| {
+            EntityId::from(if d < cutoff { "spring" } else { "summer" })
+        })
+        .unwrap();
+        assert_eq!(import.records.len(), 2);
+        assert_eq!(import.records[0].epoch_id.as_str(), "spring");
+        assert_eq!(import.records[0].player_count, Some(24));
+        assert_eq!(import.records[1].epoch_id.as_str(), "summer");
+        assert_eq!(import.records[1].source_name, "csv");
+    }
+}
@@ -1,6 +1,18 @@
-//! Ingestion pipeline for testing.
+//! Ingestion pipeline for testing, and file-based imports.
 //!
 //! Provides functions to test the full ingestion flow with real or mock AI backends.
+//! [`ttb`] imports event exports from the Tabletop Battles and Stats and
+//! Ladders apps; [`csv_import`] imports spreadsheet CSVs of events,
+//! placements and army lists; [`statcheck`] reads and writes the Stat Check
+//! community dataset format; [`season_summary`] checks our aggregates
+//! against Goonhammer's published season summaries; [`rosters`] imports
+//! BattleScribe and New Recruit roster files as army lists.
+
+pub mod csv_import;
+pub mod rosters;
+pub mod season_summary;
+pub mod statcheck;
+pub mod ttb;
 
 use std::sync::Arc;
 
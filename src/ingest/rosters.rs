@@ -0,0 +1,714 @@
+//! BattleScribe and New Recruit roster import.
+//!
+//! Rosters are structured: every unit is a selection with its points,
+//! model count and wargear, and the force names its faction catalogue. So
+//! unlike pasted list text they convert straight into an [`ArmyList`]
+//! without AI. Three formats are read, detected from content:
+//!
+//! - `.ros`: BattleScribe roster XML
+//! - `.rosz`: the same XML in a zip archive
+//! - `.json`: New Recruit's JSON export, which mirrors the XML structure
+
+use std::io::{Cursor, Read};
+
+use quick_xml::events::{BytesStart, Event as XmlEvent};
+use quick_xml::Reader;
+use serde_json::Value;
+use thiserror::Error;
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+use crate::models::{lookup_faction, resolve_faction};
+use crate::models::{ArmyList, Confidence, Event, Unit};
+use crate::storage::jsonl::list_epochs;
+use crate::storage::{EntityType, IndexedReader, JsonlWriter, StorageConfig, StorageError};
+
+/// Source name for imported lists.
+pub const SOURCE_NAME: &str = "roster";
+
+/// Largest roster read: the upload limit of `POST /api/lists/import`, and
+/// of a `.ros` file inflated out of a `.rosz` archive. Real rosters are a
+/// few hundred kilobytes.
+pub const MAX_ROSTER_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Errors that abort a roster import.
+#[derive(Debug, Error)]
+pub enum RosterError {
+    #[error("Unrecognised roster format (expected .ros, .rosz or New Recruit JSON)")]
+    UnknownFormat,
+
+    #[error("Invalid .rosz archive: {0}")]
+    Archive(String),
+
+    #[error("Invalid roster XML: {0}")]
+    Xml(String),
+
+    #[error("Invalid roster JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Roster has no units")]
+    Empty,
+
+    #[error("Event {0} not found")]
+    EventNotFound(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// Roster file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RosterFormat {
+    Ros,
+    Rosz,
+    Json,
+}
+
+impl RosterFormat {
+    /// Detect the format from a file's content.
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"PK\x03\x04") {
+            return Some(RosterFormat::Rosz);
+        }
+        let start = bytes
+            .iter()
+            .skip_while(|b| b.is_ascii_whitespace() || **b == 0xEF || **b == 0xBB || **b == 0xBF)
+            .copied()
+            .next();
+        match start {
+            Some(b'<') => Some(RosterFormat::Ros),
+            Some(b'{') => Some(RosterFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// A selection (unit, model or upgrade) in a roster.
+#[derive(Debug, Clone, Default)]
+struct Selection {
+    name: String,
+    /// `unit`, `model` or `upgrade`
+    kind: String,
+    number: u32,
+    /// Points of this selection alone, excluding its children
+    points: f64,
+    /// Selection group the entry was picked from, e.g. "Enhancements"
+    group: Option<String>,
+    categories: Vec<String>,
+    children: Vec<Selection>,
+}
+
+impl Selection {
+    /// Points of the selection and everything under it.
+    fn total_points(&self) -> f64 {
+        self.points
+            + self
+                .children
+                .iter()
+                .map(Selection::total_points)
+                .sum::<f64>()
+    }
+
+    /// Models under the selection (the selection itself for a model).
+    fn model_count(&self) -> u32 {
+        if self.kind == "model" {
+            return self.number;
+        }
+        self.children.iter().map(Selection::model_count).sum()
+    }
+
+    /// Whether the selection is a detachment Enhancement.
+    fn is_enhancement(&self) -> bool {
+        self.group
+            .iter()
+            .chain(&self.categories)
+            .any(|g| g.to_lowercase().starts_with("enhancement"))
+    }
+
+    /// Upgrade names under the selection, first seen first, leaving out
+    /// Enhancements.
+    fn collect_wargear(&self, out: &mut Vec<String>) {
+        for child in &self.children {
+            if child.kind == "upgrade"
+                && !child.name.is_empty()
+                && !child.is_enhancement()
+                && !out.contains(&child.name)
+            {
+                out.push(child.name.clone());
+            }
+            child.collect_wargear(out);
+        }
+    }
+
+    /// The first Enhancement under the selection.
+    fn enhancement(&self) -> Option<&str> {
+        self.children.iter().find_map(|child| {
+            if child.is_enhancement() && !child.name.is_empty() {
+                Some(child.name.as_str())
+            } else {
+                child.enhancement()
+            }
+        })
+    }
+}
+
+/// A force: a faction catalogue and its top-level selections.
+#[derive(Debug, Clone, Default)]
+struct Force {
+    catalogue: String,
+    selections: Vec<Selection>,
+}
+
+/// A parsed roster, before conversion.
+#[derive(Debug, Clone, Default)]
+struct Roster {
+    name: Option<String>,
+    points: Option<f64>,
+    forces: Vec<Force>,
+}
+
+/// Whether a cost entry is the points cost.
+fn is_points(name: &str) -> bool {
+    matches!(name.trim().to_lowercase().as_str(), "pts" | "points")
+}
+
+/// Parse a roster file into an army list. The list has no player or event
+/// yet.
+pub fn parse_roster(bytes: &[u8]) -> Result<ArmyList, RosterError> {
+    let roster = match RosterFormat::detect(bytes).ok_or(RosterError::UnknownFormat)? {
+        RosterFormat::Rosz => roster_from_xml(&parse_xml(&unzip_ros(bytes)?)?),
+        RosterFormat::Ros => roster_from_xml(&parse_xml(&String::from_utf8_lossy(bytes))?),
+        RosterFormat::Json => roster_from_json(&serde_json::from_slice(bytes)?),
+    };
+    to_army_list(roster)
+}
+
+fn to_army_list(roster: Roster) -> Result<ArmyList, RosterError> {
+    let selections: Vec<&Selection> = roster
+        .forces
+        .iter()
+        .flat_map(|f| f.selections.iter())
+        .collect();
+
+    // "Detachment" / "Detachment Choice" holds the chosen detachment
+    let detachment = selections
+        .iter()
+        .find(|s| s.kind == "upgrade" && s.name.to_lowercase().contains("detachment"))
+        .and_then(|s| {
+            s.children.first().map(|c| c.name.clone()).or_else(|| {
+                s.name
+                    .split_once(':')
+                    .map(|(_, name)| name.trim().to_string())
+            })
+        })
+        .filter(|d| !d.is_empty());
+
+    let units: Vec<Unit> = selections
+        .iter()
+        .filter(|s| s.kind == "unit" || s.kind == "model")
+        .map(|s| {
+            let mut wargear = Vec::new();
+            s.collect_wargear(&mut wargear);
+            let unit = Unit::new(s.name.clone(), s.model_count().max(1))
+                .with_points(s.total_points().round() as u32)
+                .with_wargear(wargear)
+                .with_keywords(s.categories.clone());
+            match s.enhancement() {
+                Some(enhancement) => unit.with_enhancement(enhancement),
+                None => unit,
+            }
+        })
+        .collect();
+    if units.is_empty() {
+        return Err(RosterError::Empty);
+    }
+    let total_points = roster
+        .points
+        .filter(|p| *p > 0.0)
+        .map(|p| p.round() as u32)
+        .unwrap_or_else(|| units.iter().filter_map(|u| u.points).sum());
+
+    // Catalogues read "Xenos - Aeldari" or "Imperium - Space Marines -
+    // Ultramarines"; the most specific known segment is the faction
+    let catalogue = roster
+        .forces
+        .first()
+        .map(|f| f.catalogue.clone())
+        .unwrap_or_default();
+    let segments: Vec<&str> = catalogue.split(" - ").map(str::trim).collect();
+    let known = segments.iter().rev().find(|s| lookup_faction(s).is_some());
+    let (resolved, confidence) = match known {
+        Some(name) => (resolve_faction(name, None), Confidence::High),
+        None => (
+            resolve_faction(segments.last().copied().unwrap_or(""), None),
+            Confidence::Medium,
+        ),
+    };
+
+    let raw_text = render(
+        &roster,
+        &catalogue,
+        detachment.as_deref(),
+        total_points,
+        &units,
+    );
+    let mut list = ArmyList::new(resolved.faction, total_points, units, raw_text)
+        .with_allegiance(resolved.allegiance)
+        .with_confidence(confidence);
+    if let Some(sub) = resolved.subfaction {
+        list = list.with_subfaction(sub);
+    }
+    if let Some(detachment) = detachment {
+        list = list.with_detachment(detachment);
+    }
+    Ok(list)
+}
+
+/// A readable copy of the roster, kept as the list's raw text.
+fn render(
+    roster: &Roster,
+    catalogue: &str,
+    detachment: Option<&str>,
+    total_points: u32,
+    units: &[Unit],
+) -> String {
+    let mut lines = Vec::new();
+    if let Some(name) = &roster.name {
+        lines.push(name.clone());
+    }
+    lines.push(match detachment {
+        Some(d) => format!("{} - {}", catalogue, d),
+        None => catalogue.to_string(),
+    });
+    lines.push(format!("{} points", total_points));
+    lines.push(String::new());
+    for unit in units {
+        let count = if unit.count > 1 {
+            format!("{}x ", unit.count)
+        } else {
+            String::new()
+        };
+        lines.push(format!(
+            "{}{} ({} points)",
+            count,
+            unit.name,
+            unit.points.unwrap_or(0)
+        ));
+        for wargear in &unit.wargear {
+            lines.push(format!("  • {}", wargear));
+        }
+        if let Some(enhancement) = &unit.enhancement {
+            lines.push(format!("  • Enhancement: {}", enhancement));
+        }
+    }
+    lines.join("\n")
+}
+
+// ── New Recruit JSON ─────────────────────────────────────────────
+
+fn roster_from_json(value: &Value) -> Roster {
+    let roster = value.get("roster").unwrap_or(value);
+    let mut forces = Vec::new();
+    if let Some(list) = roster.get("forces").and_then(Value::as_array) {
+        for force in list {
+            forces_from_json(force, &mut forces);
+        }
+    }
+    Roster {
+        name: str_field(roster, "name"),
+        points: json_points(roster),
+        forces,
+    }
+}
+
+fn forces_from_json(force: &Value, out: &mut Vec<Force>) {
+    out.push(Force {
+        catalogue: str_field(force, "catalogueName").unwrap_or_default(),
+        selections: json_array(force, "selections")
+            .map(selection_from_json)
+            .collect(),
+    });
+    for nested in json_array(force, "forces") {
+        forces_from_json(nested, out);
+    }
+}
+
+fn selection_from_json(value: &Value) -> Selection {
+    Selection {
+        name: str_field(value, "name").unwrap_or_default(),
+        kind: str_field(value, "type").unwrap_or_else(|| "upgrade".to_string()),
+        number: value
+            .get("number")
+            .and_then(Value::as_u64)
+            .map(|n| n as u32)
+            .unwrap_or(1),
+        points: json_points(value).unwrap_or(0.0),
+        group: str_field(value, "group"),
+        categories: json_array(value, "categories")
+            .filter_map(|c| str_field(c, "name"))
+            .collect(),
+        children: json_array(value, "selections")
+            .map(selection_from_json)
+            .collect(),
+    }
+}
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(|s| s.trim().to_string())
+}
+
+fn json_array<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+fn json_points(value: &Value) -> Option<f64> {
+    json_array(value, "costs")
+        .filter(|c| c.get("name").and_then(Value::as_str).is_some_and(is_points))
+        .filter_map(|c| c.get("value").and_then(Value::as_f64))
+        .reduce(|a, b| a + b)
+}
+
+// ── BattleScribe XML ─────────────────────────────────────────────
+
+fn roster_from_xml(root: &XmlElement) -> Roster {
+    let mut forces = Vec::new();
+    for force in root.children_named("forces", "force") {
+        forces_from_xml(force, &mut forces);
+    }
+    Roster {
+        name: root.attr("name").map(str::to_string),
+        points: xml_points(root),
+        forces,
+    }
+}
+
+fn forces_from_xml(force: &XmlElement, out: &mut Vec<Force>) {
+    out.push(Force {
+        catalogue: force.attr("catalogueName").unwrap_or_default().to_string(),
+        selections: force
+            .children_named("selections", "selection")
+            .map(selection_from_xml)
+            .collect(),
+    });
+    for nested in force.children_named("forces", "force") {
+        forces_from_xml(nested, out);
+    }
+}
+
+fn selection_from_xml(el: &XmlElement) -> Selection {
+    Selection {
+        name: el.attr("name").unwrap_or_default().trim().to_string(),
+        kind: el.attr("type").unwrap_or("upgrade").to_string(),
+        number: el.attr("number").and_then(|n| n.parse().ok()).unwrap_or(1),
+        points: xml_points(el).unwrap_or(0.0),
+        group: el.attr("group").map(str::to_string),
+        categories: el
+            .children_named("categories", "category")
+            .filter_map(|c| c.attr("name"))
+            .map(str::to_string)
+            .collect(),
+        children: el
+            .children_named("selections", "selection")
+            .map(selection_from_xml)
+            .collect(),
+    }
+}
+
+fn xml_points(el: &XmlElement) -> Option<f64> {
+    el.children_named("costs", "cost")
+        .filter(|c| c.attr("name").is_some_and(is_points))
+        .filter_map(|c| c.attr("value").and_then(|v| v.parse::<f64>().ok()))
+        .reduce(|a, b| a + b)
+}
+
+/// An XML element; text content is not kept, rosters carry everything
+/// needed in attributes.
+#[derive(Debug, Clone, Default)]
+struct XmlElement {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<XmlElement>,
+}
+
+impl XmlElement {
+    fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Elements named `item` inside this element's `list` child, e.g. the
+    /// `selection`s in `<selections>`.
+    fn children_named<'a>(
+        &'a self,
+        list: &'a str,
+        item: &'a str,
+    ) -> impl Iterator<Item = &'a XmlElement> {
+        self.children
+            .iter()
+            .filter(move |c| c.name == list)
+            .flat_map(|c| c.children.iter())
+            .filter(move |c| c.name == item)
+    }
+}
+
+fn parse_xml(text: &str) -> Result<XmlElement, RosterError> {
+    let xml_error = |e: quick_xml::Error| RosterError::Xml(e.to_string());
+    let mut reader = Reader::from_str(text);
+    let mut stack: Vec<XmlElement> = Vec::new();
+    let mut root: Option<XmlElement> = None;
+    loop {
+        let element = match reader.read_event().map_err(xml_error)? {
+            XmlEvent::Start(tag) => {
+                stack.push(element_from(&tag)?);
+                continue;
+            }
+            XmlEvent::Empty(tag) => element_from(&tag)?,
+            // Mismatched end tags are rejected by the reader
+            XmlEvent::End(_) => stack
+                .pop()
+                .ok_or_else(|| RosterError::Xml("unexpected end tag".to_string()))?,
+            XmlEvent::Eof => break,
+            _ => continue,
+        };
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(element),
+            None => root = Some(element),
+        }
+    }
+    if let Some(open) = stack.last() {
+        return Err(RosterError::Xml(format!("<{}> is never closed", open.name)));
+    }
+    root.ok_or_else(|| RosterError::Xml("no root element".to_string()))
+}
+
+/// An element with the attributes of a start tag, entities decoded.
+fn element_from(tag: &BytesStart) -> Result<XmlElement, RosterError> {
+    let mut element = XmlElement {
+        name: String::from_utf8_lossy(tag.name().as_ref()).into_owned(),
+        ..Default::default()
+    };
+    for attr in tag.attributes() {
+        let attr = attr.map_err(|e| RosterError::Xml(e.to_string()))?;
+        let value = attr
+            .unescape_value()
+            .map_err(|e| RosterError::Xml(e.to_string()))?;
+        element.attrs.push((
+            String::from_utf8_lossy(attr.key.as_ref()).into_owned(),
+            value.into_owned(),
+        ));
+    }
+    Ok(element)
+}
+
+// ── .rosz archives ───────────────────────────────────────────────
+
+/// The `.ros` entry of a `.rosz` zip archive. Entries inflating past
+/// [`MAX_ROSTER_BYTES`] are rejected rather than read into memory.
+fn unzip_ros(bytes: &[u8]) -> Result<String, RosterError> {
+    let archive_error = |e: ZipError| RosterError::Archive(e.to_string());
+    let too_large = || {
+        RosterError::Archive(format!(
+            ".ros file is larger than {} bytes",
+            MAX_ROSTER_BYTES
+        ))
+    };
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(archive_error)?;
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i).map_err(archive_error)?;
+        if !entry.name().to_lowercase().ends_with(".ros") {
+            continue;
+        }
+        if entry.size() > MAX_ROSTER_BYTES {
+            return Err(too_large());
+        }
+        // The declared size can lie, so the read is capped too
+        let mut out = Vec::new();
+        entry.take(MAX_ROSTER_BYTES + 1).read_to_end(&mut out)?;
+        if out.len() as u64 > MAX_ROSTER_BYTES {
+            return Err(too_large());
+        }
+        return Ok(String::from_utf8_lossy(&out).into_owned());
+    }
+    Err(RosterError::Archive("no .ros file inside".to_string()))
+}
+
+// ── Storing ──────────────────────────────────────────────────────
+
+/// An imported roster list.
+#[derive(Debug, Clone)]
+pub struct RosterImport {
+    pub list: ArmyList,
+    pub event: Event,
+    /// False if the same list was already stored (or on a dry run)
+    pub stored: bool,
+}
+
+/// Parse a roster for a player at a stored event and, unless `dry_run`,
+/// append it to the event's epoch under the `roster` source.
+pub fn import_roster(
+    storage: &StorageConfig,
+    bytes: &[u8],
+    player: &str,
+    event_id: &str,
+    dry_run: bool,
+) -> Result<RosterImport, RosterError> {
+    let mut event = None;
+    for epoch in list_epochs(storage)? {
+        let reader = IndexedReader::<Event>::for_entity(storage, EntityType::Event, &epoch);
+        if let Some(found) = reader.index()?.get(event_id) {
+            event = Some(found.clone());
+            break;
+        }
+    }
+    let event = event.ok_or_else(|| RosterError::EventNotFound(event_id.to_string()))?;
+
+    let list = parse_roster(bytes)?
+        .with_player_name(player.trim().to_string())
+        .with_event_id(event.id.clone())
+        .with_event_date(event.date)
+        .with_source_url(event.source_url.clone());
+
+    let epoch = event.epoch_id.as_str();
+    let exists = IndexedReader::<ArmyList>::for_entity(storage, EntityType::ArmyList, epoch)
+        .index()?
+        .get(list.id.as_str())
+        .is_some_and(|l| l.player_name == list.player_name);
+    let stored = !dry_run && !exists;
+    if stored {
+        JsonlWriter::for_source(storage, EntityType::ArmyList, epoch, SOURCE_NAME).append(&list)?;
+    }
+    Ok(RosterImport {
+        list,
+        event,
+        stored,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const ROS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<roster id="r1" name="Aeldari &amp; Friends" gameSystemName="Warhammer 40,000 10th Edition">
+  <costs><cost name="pts" typeId="51b2" value="2000.0"/></costs>
+  <forces>
+    <force id="f1" name="Army Roster" catalogueName="Xenos - Aeldari">
+      <selections>
+        <selection id="s0" name="Battle Size" type="upgrade" number="1">
+          <selections><selection id="s0a" name="Strike Force (2000 Point limit)" type="upgrade" number="1"/></selections>
+        </selection>
+        <selection id="s1" name="Detachment" type="upgrade" number="1">
+          <selections><selection id="s1a" name="Battle Host" type="upgrade" number="1"/></selections>
+        </selection>
+        <selection id="s2" name="Farseer" type="model" number="1">
+          <costs><cost name="pts" typeId="51b2" value="70.0"/></costs>
+          <categories><category name="Character" primary="false"/><category name="Psyker" primary="false"/></categories>
+          <selections>
+            <selection id="s2a" name="Singing spear" type="upgrade" number="1"/>
+            <selection id="s2b" name="Lucky Stone" type="upgrade" number="1" group="Enhancements"/>
+          </selections>
+        </selection>
+        <!-- a ten-model unit -->
+        <selection id="s3" name="Guardian Defenders" type="unit" number="1">
+          <costs><cost name="pts" typeId="51b2" value="100.0"/></costs>
+          <selections>
+            <selection id="s3a" name="Guardian Defender" type="model" number="10">
+              <selections><selection id="s3b" name="Shuriken catapult" type="upgrade" number="10"/></selections>
+            </selection>
+            <selection id="s3c" name="Heavy Weapon Platform" type="model" number="1">
+              <costs><cost name="pts" typeId="51b2" value="0.0"/></costs>
+            </selection>
+          </selections>
+        </selection>
+      </selections>
+    </force>
+  </forces>
+</roster>"#;
+
+    /// A one-file zip archive holding `contents` as `list.ros`, deflated.
+    fn rosz(contents: &[u8]) -> Vec<u8> {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        zip.start_file("list.ros", options).unwrap();
+        zip.write_all(contents).unwrap();
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_parse_roster_formats() {
+        let json = r#"{"roster": {"name": "Aeldari & Friends",
+            "costs": [{"name": "pts", "value": 2000}],
+            "forces": [{"catalogueName": "Xenos - Aeldari", "selections": [
+                {"name": "Detachment", "type": "upgrade", "selections": [{"name": "Battle Host", "type": "upgrade"}]},
+                {"name": "Farseer", "type": "model", "number": 1,
+                 "costs": [{"name": "pts", "value": 70}],
+                 "categories": [{"name": "Character"}, {"name": "Psyker"}],
+                 "selections": [{"name": "Singing spear", "type": "upgrade", "number": 1},
+                    {"name": "Lucky Stone", "type": "upgrade", "number": 1, "group": "Enhancements"}]},
+                {"name": "Guardian Defenders", "type": "unit", "number": 1,
+                 "costs": [{"name": "pts", "value": 100}],
+                 "selections": [
+                    {"name": "Guardian Defender", "type": "model", "number": 10,
+                     "selections": [{"name": "Shuriken catapult", "type": "upgrade", "number": 10}]},
+                    {"name": "Heavy Weapon Platform", "type": "model", "number": 1}]}
+            ]}]}}"#;
+
+        let from_ros = parse_roster(ROS.as_bytes()).unwrap();
+        let from_rosz = parse_roster(&rosz(ROS.as_bytes())).unwrap();
+        let from_json = parse_roster(json.as_bytes()).unwrap();
+
+        for list in [&from_ros, &from_rosz, &from_json] {
+            assert_eq!(list.faction, "Aeldari");
+            assert_eq!(list.detachment.as_deref(), Some("Battle Host"));
+            assert_eq!(list.total_points, 2000);
+            assert_eq!(list.units.len(), 2);
+            assert_eq!(list.units[0].name, "Farseer");
+            assert_eq!(list.units[0].points, Some(70));
+            assert_eq!(list.units[0].wargear, vec!["Singing spear"]);
+            assert_eq!(list.enhancements, vec!["Lucky Stone"]);
+            assert_eq!(list.units[0].keywords, vec!["Character", "Psyker"]);
+            assert_eq!(list.units[1].count, 11);
+            assert_eq!(list.units[1].points, Some(100));
+            assert_eq!(list.extraction_confidence, Confidence::High);
+            assert!(list.extracted_by.is_none());
+        }
+        assert_eq!(from_ros.id, from_json.id);
+        assert!(from_ros.raw_text.starts_with("Aeldari & Friends\n"));
+        assert!(from_ros
+            .raw_text
+            .contains("11x Guardian Defenders (100 points)"));
+
+        assert!(matches!(
+            parse_roster(b"Farseer 70pts"),
+            Err(RosterError::UnknownFormat)
+        ));
+        assert!(matches!(
+            parse_roster(b"<roster><forces></roster>"),
+            Err(RosterError::Xml(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_roster_rejects_oversized_archive_entry() {
+        let bomb = rosz(&vec![b' '; MAX_ROSTER_BYTES as usize + 1]);
+        assert!(bomb.len() < 64 * 1024);
+        match parse_roster(&bomb) {
+            Err(RosterError::Archive(message)) => assert!(message.contains("larger than")),
+            other => panic!("expected an archive error, got {:?}", other),
+        }
+    }
+}
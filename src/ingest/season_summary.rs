@@ -0,0 +1,345 @@
+//! Cross-checking our aggregates against published season summaries.
+//!
+//! [`SeasonSummaryAgent`] reads a Goonhammer season wrap-up; [`cross_check`]
+//! recomputes the same figures from our events and placements over the
+//! period it covers and turns every gap beyond tolerance into a
+//! [`QualityFinding`]. A large gap usually means missed events or
+//! misattributed factions on our side, though the publication can be
+//! wrong too, so findings are for a person to look at, not fixes.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chrono::{NaiveDate, Utc};
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::agents::backend::AiBackend;
+use crate::agents::season_summary::{SeasonSummary, SeasonSummaryAgent, SeasonSummaryInput};
+use crate::agents::{execute_with_retry, AgentError};
+use crate::calculate::calculate_win_rate;
+use crate::fetch::{FetchError, Fetcher};
+use crate::models::{canonical_faction_name, Event, FindingKind, Placement, QualityFinding};
+use crate::storage::jsonl::list_epochs;
+use crate::storage::{
+    EntityType, IndexedReader, JsonlReader, JsonlWriter, StorageConfig, StorageError,
+};
+
+/// Largest tolerated relative gap in event and player counts.
+pub const COUNT_TOLERANCE: f64 = 0.10;
+
+/// Largest tolerated gap in a faction's win rate (0.0 to 1.0).
+pub const WIN_RATE_TOLERANCE: f64 = 0.03;
+
+/// Fewest local games before a faction's win rate is compared.
+pub const MIN_FACTION_GAMES: u32 = 30;
+
+/// Errors that abort a season summary check.
+#[derive(Debug, Error)]
+pub enum SeasonSummaryError {
+    #[error("Fetch error: {0}")]
+    Fetch(#[from] FetchError),
+
+    #[error("Agent error: {0}")]
+    Agent(#[from] AgentError),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// Result of checking one article.
+#[derive(Debug, Clone, Serialize)]
+pub struct SeasonCheck {
+    pub summary: SeasonSummary,
+    /// Our events in the period
+    pub local_events: usize,
+    /// Published factions whose win rate was compared
+    pub factions_compared: usize,
+    pub findings: Vec<QualityFinding>,
+}
+
+/// Compare a published summary with our events and placements, which may
+/// span any dates; only those in the summary's period count.
+pub fn cross_check(
+    summary: &SeasonSummary,
+    events: &[Event],
+    placements: &[Placement],
+) -> SeasonCheck {
+    let in_period = |date: NaiveDate| date >= summary.period_start && date <= summary.period_end;
+    let events: Vec<&Event> = events.iter().filter(|e| in_period(e.date)).collect();
+    let mut placements_per_event: HashMap<&str, u32> = HashMap::new();
+    let mut records: HashMap<String, (u32, u32, u32)> = HashMap::new();
+    for p in placements {
+        if !events.iter().any(|e| e.id == p.event_id) {
+            continue;
+        }
+        *placements_per_event.entry(p.event_id.as_str()).or_default() += 1;
+        if let Some(record) = &p.record {
+            let entry = records
+                .entry(canonical_faction_name(&p.faction))
+                .or_default();
+            entry.0 += record.wins;
+            entry.1 += record.losses;
+            entry.2 += record.draws;
+        }
+    }
+
+    let found_at = Utc::now();
+    let finding = |kind: FindingKind, subject: Option<&str>, published: f64, local: Option<f64>| {
+        let message = match (kind, local) {
+            (FindingKind::FactionMissing, _) => format!(
+                "{} is published at {:.1}% but we have no results for it",
+                subject.unwrap_or("?"),
+                published * 100.0
+            ),
+            (FindingKind::FactionWinRate, Some(local)) => format!(
+                "{} win rate: published {:.1}%, ours {:.1}%",
+                subject.unwrap_or("?"),
+                published * 100.0,
+                local * 100.0
+            ),
+            (_, local) => format!(
+                "{}: published {}, ours {}",
+                kind,
+                published,
+                local.unwrap_or(0.0)
+            ),
+        };
+        QualityFinding {
+            id: QualityFinding::finding_id(&summary.source_url, kind, subject),
+            kind,
+            subject: subject.map(str::to_string),
+            published,
+            local,
+            message,
+            source_url: summary.source_url.clone(),
+            source_title: summary.title.clone(),
+            period_start: summary.period_start,
+            period_end: summary.period_end,
+            found_at,
+        }
+    };
+    let count_gap = |published: u32, local: u32| {
+        published > 0
+            && (local as f64 - published as f64).abs() / published as f64 > COUNT_TOLERANCE
+    };
+
+    let mut findings = Vec::new();
+    if let Some(published) = summary.event_count {
+        let local = events.len() as u32;
+        if count_gap(published, local) {
+            findings.push(finding(
+                FindingKind::EventCount,
+                None,
+                published as f64,
+                Some(local as f64),
+            ));
+        }
+    }
+    if let Some(published) = summary.player_count {
+        let local: u32 = events
+            .iter()
+            .map(|e| {
+                e.player_count.unwrap_or_else(|| {
+                    placements_per_event
+                        .get(e.id.as_str())
+                        .copied()
+                        .unwrap_or(0)
+                })
+            })
+            .sum();
+        if count_gap(published, local) {
+            findings.push(finding(
+                FindingKind::PlayerCount,
+                None,
+                published as f64,
+                Some(local as f64),
+            ));
+        }
+    }
+
+    let mut factions_compared = 0;
+    for published in &summary.factions {
+        let subject = Some(published.faction.as_str());
+        match records.get(&published.faction) {
+            None => findings.push(finding(
+                FindingKind::FactionMissing,
+                subject,
+                published.win_rate,
+                None,
+            )),
+            Some(&(w, l, d)) if w + l + d >= MIN_FACTION_GAMES => {
+                factions_compared += 1;
+                let local = calculate_win_rate(w, l, d);
+                if (local - published.win_rate).abs() > WIN_RATE_TOLERANCE {
+                    findings.push(finding(
+                        FindingKind::FactionWinRate,
+                        subject,
+                        published.win_rate,
+                        Some(local),
+                    ));
+                }
+            }
+            // Too few local games to say anything
+            Some(_) => {}
+        }
+    }
+
+    SeasonCheck {
+        summary: summary.clone(),
+        local_events: events.len(),
+        factions_compared,
+        findings,
+    }
+}
+
+/// Path of the findings file.
+pub fn findings_path(storage: &StorageConfig) -> PathBuf {
+    storage.state_dir().join("quality_findings.jsonl")
+}
+
+/// Every recorded finding, most recent first.
+pub fn read_findings(storage: &StorageConfig) -> Result<Vec<QualityFinding>, StorageError> {
+    let mut findings: Vec<QualityFinding> = JsonlReader::new(findings_path(storage)).read_all()?;
+    findings.sort_by_key(|f| Reverse(f.found_at));
+    Ok(findings)
+}
+
+/// Replace an article's findings with those of a new check.
+pub fn record_findings(
+    storage: &StorageConfig,
+    source_url: &str,
+    findings: &[QualityFinding],
+) -> Result<(), StorageError> {
+    let path = findings_path(storage);
+    let mut all: Vec<QualityFinding> = JsonlReader::new(path.clone()).read_all()?;
+    all.retain(|f| f.source_url != source_url);
+    all.extend(findings.iter().cloned());
+    JsonlWriter::new(path).write_all(&all)?;
+    Ok(())
+}
+
+/// Fetch a season summary article, extract its aggregates and cross-check
+/// them against every epoch. Unless `dry_run`, the findings replace the
+/// article's previous ones.
+pub async fn check_season_summary(
+    fetcher: &Fetcher,
+    backend: Arc<dyn AiBackend>,
+    storage: &StorageConfig,
+    article_url: &str,
+    dry_run: bool,
+) -> Result<SeasonCheck, SeasonSummaryError> {
+    let url = url::Url::parse(article_url)
+        .map_err(|e| FetchError::InvalidUrl(format!("{}: {}", article_url, e)))?;
+    let fetch_result = fetcher.fetch(&url).await?;
+    let html = fetcher.read_cached_text(&fetch_result).await?;
+
+    // The publication date isn't known up front; today bounds the period
+    let input = SeasonSummaryInput {
+        article_html: html,
+        article_url: article_url.to_string(),
+        article_date: Utc::now().date_naive(),
+    };
+    let output = execute_with_retry(&SeasonSummaryAgent::new(backend), input).await?;
+
+    let mut events: Vec<Event> = Vec::new();
+    let mut placements: Vec<Placement> = Vec::new();
+    for epoch in list_epochs(storage)? {
+        events.extend(
+            IndexedReader::<Event>::for_entity(storage, EntityType::Event, &epoch).read_all()?,
+        );
+        placements.extend(
+            IndexedReader::<Placement>::for_entity(storage, EntityType::Placement, &epoch)
+                .read_all()?,
+        );
+    }
+
+    let check = cross_check(&output.data, &events, &placements);
+    if !dry_run {
+        record_findings(storage, article_url, &check.findings)?;
+    }
+    Ok(check)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::season_summary::PublishedFactionStat;
+    use crate::models::EntityId;
+
+    #[test]
+    fn test_cross_check_flags_gaps() {
+        let date = |d: u32| NaiveDate::from_ymd_opt(2026, 2, d).unwrap();
+        let event = |name: &str, day: u32| {
+            Event::new(
+                name.to_string(),
+                date(day),
+                format!("https://example.com/{}", name),
+                "bcp".to_string(),
+                EntityId::from("current"),
+            )
+            .with_player_count(40)
+        };
+        let events = vec![event("In GT", 10), event("Out GT", 28)];
+        let placements: Vec<Placement> = [("Aeldari", 25, 15), ("Orks", 20, 20), ("Necrons", 2, 1)]
+            .iter()
+            .enumerate()
+            .map(|(i, (faction, w, l))| {
+                Placement::new(
+                    events[0].id.clone(),
+                    EntityId::from("current"),
+                    i as u32 + 1,
+                    format!("Player {}", i),
+                    faction.to_string(),
+                )
+                .with_record(*w, *l, 0)
+            })
+            .collect();
+        let faction = |name: &str, win_rate: f64| PublishedFactionStat {
+            faction: name.to_string(),
+            win_rate,
+            games: None,
+        };
+        let summary = SeasonSummary {
+            title: "Wrap-Up".to_string(),
+            source_url: "https://goonhammer.com/wrap".to_string(),
+            period_start: date(1),
+            period_end: date(20),
+            event_count: Some(2),
+            player_count: Some(42),
+            factions: vec![
+                faction("Aeldari", 0.55),
+                faction("Orks", 0.51),
+                faction("Necrons", 0.40),
+                faction("Tyranids", 0.50),
+            ],
+        };
+
+        let check = cross_check(&summary, &events, &placements);
+        assert_eq!((check.local_events, check.factions_compared), (1, 2));
+        let kinds: Vec<(FindingKind, Option<&str>)> = check
+            .findings
+            .iter()
+            .map(|f| (f.kind, f.subject.as_deref()))
+            .collect();
+        // One event of two is flagged but 40 players of 42 is within
+        // tolerance; Aeldari at 62.5% is flagged, Orks at 50% is not and
+        // Necrons on 3 games aren't compared
+        assert_eq!(
+            kinds,
+            vec![
+                (FindingKind::EventCount, None),
+                (FindingKind::FactionWinRate, Some("Aeldari")),
+                (FindingKind::FactionMissing, Some("Tyranids")),
+            ]
+        );
+
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        record_findings(&storage, &summary.source_url, &check.findings).unwrap();
+        record_findings(&storage, &summary.source_url, &check.findings[..1]).unwrap();
+        assert_eq!(read_findings(&storage).unwrap().len(), 1);
+    }
+}
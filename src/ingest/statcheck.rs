@@ -0,0 +1,447 @@
+//! Stat Check community dataset format.
+//!
+//! Stat Check aggregates tournament results as one CSV row per player
+//! result, with the event's details repeated on every row. This module
+//! maps our Event / Placement / ArmyList records to and from that layout
+//! so locally tracked events can be contributed upstream and published
+//! datasets can be imported.
+//!
+//! Headers default to the ones in [`FIELDS`]; a [`ColumnMapping`] (the
+//! same TOML format as the generic CSV import) renames any of them:
+//!
+//! ```toml
+//! [columns]
+//! placing = "Rank"
+//! army_list = "List Text"
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+
+use chrono::NaiveDate;
+use thiserror::Error;
+
+use super::csv_import::{parse_date, ColumnMapping, RowError};
+use crate::models::{ArmyList, Confidence, EntityId, Event, Placement};
+use crate::parsing::armylist::parse_units_from_raw_text;
+
+/// Source name for imported records.
+pub const SOURCE_NAME: &str = "statcheck";
+
+/// Dataset fields and their default headers, in column order.
+pub const FIELDS: &[(&str, &str)] = &[
+    ("event_name", "Event Name"),
+    ("event_date", "Event Date"),
+    ("location", "Location"),
+    ("player_count", "Players"),
+    ("rounds", "Rounds"),
+    ("placing", "Placing"),
+    ("player_name", "Player Name"),
+    ("faction", "Faction"),
+    ("detachment", "Detachment"),
+    ("wins", "Wins"),
+    ("losses", "Losses"),
+    ("draws", "Draws"),
+    ("battle_points", "Battle Points"),
+    ("list_points", "List Points"),
+    ("army_list", "Army List"),
+    ("source_url", "Source URL"),
+];
+
+/// Fields every imported row needs.
+const REQUIRED: &[&str] = &[
+    "event_name",
+    "event_date",
+    "placing",
+    "player_name",
+    "faction",
+];
+
+/// Errors that abort a Stat Check import or export.
+#[derive(Debug, Error)]
+pub enum StatCheckError {
+    #[error("Invalid CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Mapping refers to unknown Stat Check field '{0}'")]
+    UnknownField(String),
+
+    #[error("No column found for required field '{0}'")]
+    MissingColumn(&'static str),
+}
+
+/// Field -> header, with mapping overrides applied.
+fn headers(mapping: &ColumnMapping) -> Result<Vec<(&'static str, String)>, StatCheckError> {
+    if let Some(field) = mapping
+        .columns
+        .keys()
+        .find(|f| !FIELDS.iter().any(|(name, _)| name == f))
+    {
+        return Err(StatCheckError::UnknownField(field.clone()));
+    }
+    Ok(FIELDS
+        .iter()
+        .map(|(field, default)| {
+            let header = mapping
+                .columns
+                .get(*field)
+                .cloned()
+                .unwrap_or_else(|| default.to_string());
+            (*field, header)
+        })
+        .collect())
+}
+
+fn normalize_header(h: &str) -> String {
+    h.chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Write placements (with their event and army list) as a Stat Check
+/// dataset, ordered by event date then placing. Placements whose event is
+/// not in `events` are skipped. Returns the number of rows written.
+pub fn write_dataset<W: Write>(
+    events: &[Event],
+    placements: &[Placement],
+    lists: &[ArmyList],
+    mapping: &ColumnMapping,
+    writer: W,
+) -> Result<usize, StatCheckError> {
+    let headers = headers(mapping)?;
+    let events: HashMap<&str, &Event> = events.iter().map(|e| (e.id.as_str(), e)).collect();
+    let lists_by_id: HashMap<&str, &ArmyList> = lists.iter().map(|l| (l.id.as_str(), l)).collect();
+    // Lists not linked from a placement are matched by event and player
+    let lists_by_player: HashMap<(&str, String), &ArmyList> = lists
+        .iter()
+        .filter_map(|l| {
+            let event_id = l.event_id.as_ref()?.as_str();
+            let player = l.player_name.as_ref()?.to_lowercase();
+            Some(((event_id, player), l))
+        })
+        .collect();
+
+    let mut rows: Vec<(&Event, &Placement)> = placements
+        .iter()
+        .filter_map(|p| Some((*events.get(p.event_id.as_str())?, p)))
+        .collect();
+    rows.sort_by(|(ea, pa), (eb, pb)| {
+        (ea.date, &ea.name, pa.rank).cmp(&(eb.date, &eb.name, pb.rank))
+    });
+
+    let mut out = csv::Writer::from_writer(writer);
+    out.write_record(headers.iter().map(|(_, h)| h))?;
+    for (event, placement) in &rows {
+        let list = placement
+            .list_id
+            .as_ref()
+            .and_then(|id| lists_by_id.get(id.as_str()))
+            .or_else(|| {
+                lists_by_player.get(&(
+                    placement.event_id.as_str(),
+                    placement.player_name.to_lowercase(),
+                ))
+            });
+        let record = placement.record.as_ref();
+        let number = |n: Option<u32>| n.map(|n| n.to_string()).unwrap_or_default();
+        let row: Vec<String> = headers
+            .iter()
+            .map(|(field, _)| match *field {
+                "event_name" => event.name.clone(),
+                "event_date" => event.date.to_string(),
+                "location" => event.location.clone().unwrap_or_default(),
+                "player_count" => number(event.player_count),
+                "rounds" => number(event.round_count),
+                "placing" => placement.rank.to_string(),
+                "player_name" => placement.player_name.clone(),
+                "faction" => placement.faction.clone(),
+                "detachment" => placement
+                    .detachment
+                    .clone()
+                    .or_else(|| list.and_then(|l| l.detachment.clone()))
+                    .unwrap_or_default(),
+                "wins" => number(record.map(|r| r.wins)),
+                "losses" => number(record.map(|r| r.losses)),
+                "draws" => number(record.map(|r| r.draws)),
+                "battle_points" => number(placement.battle_points),
+                "list_points" => number(list.map(|l| l.total_points)),
+                "army_list" => list.map(|l| l.raw_text.clone()).unwrap_or_default(),
+                "source_url" => event.source_url.clone(),
+                _ => String::new(),
+            })
+            .collect();
+        out.write_record(&row)?;
+    }
+    out.flush()?;
+    Ok(rows.len())
+}
+
+/// Records parsed from a Stat Check dataset.
+#[derive(Debug, Clone, Default)]
+pub struct StatCheckImport {
+    pub events: Vec<Event>,
+    pub placements: Vec<Placement>,
+    pub lists: Vec<ArmyList>,
+    pub errors: Vec<RowError>,
+}
+
+/// Parse a Stat Check dataset. Rows sharing an event name and date become
+/// one event; `epoch_for` assigns each event's epoch from its date and rows
+/// without a source URL get `default_source_url`.
+pub fn parse_dataset(
+    content: &str,
+    mapping: &ColumnMapping,
+    default_source_url: &str,
+    epoch_for: impl Fn(NaiveDate) -> EntityId,
+) -> Result<StatCheckImport, StatCheckError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.trim_start_matches('\u{feff}').as_bytes());
+    let file_headers: Vec<String> = reader.headers()?.iter().map(normalize_header).collect();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    for (field, header) in headers(mapping)? {
+        let wanted = [normalize_header(&header), normalize_header(field)];
+        if let Some(i) = file_headers.iter().position(|h| wanted.contains(h)) {
+            positions.insert(field, i);
+        }
+    }
+    if let Some(missing) = REQUIRED.iter().find(|f| !positions.contains_key(**f)) {
+        return Err(StatCheckError::MissingColumn(missing));
+    }
+
+    let mut import = StatCheckImport::default();
+    // (name, date) -> index into import.events, in first-seen order
+    let mut event_index: BTreeMap<(String, NaiveDate), usize> = BTreeMap::new();
+    let mut rows_per_event: HashMap<usize, u32> = HashMap::new();
+
+    for record in reader.records() {
+        let record = record?;
+        if record.iter().all(|v| v.trim().is_empty()) {
+            continue;
+        }
+        let line = record.position().map(|p| p.line()).unwrap_or(0);
+        let get = |field: &str| {
+            positions
+                .get(field)
+                .and_then(|&i| record.get(i))
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(String::from)
+        };
+        let number = |field: &str| -> Result<Option<u32>, String> {
+            get(field)
+                .map(|v| {
+                    v.replace(',', "")
+                        .parse::<u32>()
+                        .map_err(|_| format!("{} '{}' is not a whole number", field, v))
+                })
+                .transpose()
+        };
+
+        let row = (|| -> Result<(), String> {
+            let required = |field: &str| get(field).ok_or_else(|| format!("missing {}", field));
+            let name = required("event_name")?;
+            let date = parse_date(&required("event_date")?)?;
+            let rank = number("placing")?.ok_or("missing placing")?;
+            if rank == 0 {
+                return Err("placing must be 1 or higher".to_string());
+            }
+            let player_name = required("player_name")?;
+            let faction = required("faction")?;
+
+            let index = match event_index.get(&(name.clone(), date)) {
+                Some(&i) => i,
+                None => {
+                    let url = get("source_url").unwrap_or_else(|| default_source_url.to_string());
+                    let mut event = Event::new(
+                        name.clone(),
+                        date,
+                        url,
+                        SOURCE_NAME.to_string(),
+                        epoch_for(date),
+                    )
+                    .with_confidence(Confidence::High);
+                    if let Some(location) = get("location") {
+                        event = event.with_location(location);
+                    }
+                    if let Some(count) = number("player_count")? {
+                        event = event.with_player_count(count);
+                    }
+                    if let Some(rounds) = number("rounds")? {
+                        event = event.with_round_count(rounds);
+                    }
+                    import.events.push(event);
+                    event_index.insert((name, date), import.events.len() - 1);
+                    import.events.len() - 1
+                }
+            };
+            let event = &import.events[index];
+
+            let mut placement = Placement::new(
+                event.id.clone(),
+                event.epoch_id.clone(),
+                rank,
+                player_name.clone(),
+                faction.clone(),
+            )
+            .with_confidence(Confidence::High);
+            if let Some(det) = get("detachment") {
+                placement = placement.with_detachment(det);
+            }
+            let (w, l, d) = (number("wins")?, number("losses")?, number("draws")?);
+            if w.is_some() || l.is_some() || d.is_some() {
+                placement = placement.with_record(w.unwrap_or(0), l.unwrap_or(0), d.unwrap_or(0));
+            }
+            if let Some(bp) = number("battle_points")? {
+                placement = placement.with_battle_points(bp);
+            }
+
+            if let Some(raw_text) = get("army_list") {
+                let units = parse_units_from_raw_text(&raw_text);
+                let total_points = match number("list_points")? {
+                    Some(p) => p,
+                    None => units.iter().filter_map(|u| u.points).sum(),
+                };
+                let confidence = if units.is_empty() {
+                    Confidence::Low
+                } else {
+                    Confidence::High
+                };
+                let mut list = ArmyList::new(faction, total_points, units, raw_text)
+                    .with_event_id(event.id.clone())
+                    .with_event_date(event.date)
+                    .with_source_url(event.source_url.clone())
+                    .with_player_name(player_name)
+                    .with_confidence(confidence);
+                if let Some(det) = get("detachment") {
+                    list = list.with_detachment(det);
+                }
+                placement = placement.with_list_id(list.id.clone());
+                import.lists.push(list);
+            }
+
+            import.placements.push(placement);
+            *rows_per_event.entry(index).or_default() += 1;
+            Ok(())
+        })();
+        if let Err(message) = row {
+            import.errors.push(RowError { line, message });
+        }
+    }
+
+    // Datasets without a player count column still know how many rows
+    for (index, rows) in rows_per_event {
+        let event = &mut import.events[index];
+        if event.player_count.is_none() {
+            event.player_count = Some(rows);
+        }
+    }
+    Ok(import)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dataset_round_trip() {
+        let date = NaiveDate::from_ymd_opt(2025, 6, 14).unwrap();
+        let event = Event::new(
+            "Northern GT".to_string(),
+            date,
+            "https://example.com/gt".to_string(),
+            "bcp".to_string(),
+            EntityId::from("current"),
+        )
+        .with_player_count(40);
+        let list = ArmyList::new(
+            "Aeldari".to_string(),
+            1995,
+            vec![],
+            "Farseer (70 points)".to_string(),
+        )
+        .with_event_id(event.id.clone())
+        .with_player_name("Alice".to_string());
+        let placements = vec![
+            Placement::new(
+                event.id.clone(),
+                "current".into(),
+                2,
+                "Bob".to_string(),
+                "Orks".to_string(),
+            ),
+            Placement::new(
+                event.id.clone(),
+                "current".into(),
+                1,
+                "Alice".to_string(),
+                "Aeldari".to_string(),
+            )
+            .with_record(5, 0, 0),
+        ];
+
+        let mut buf = Vec::new();
+        let written = write_dataset(
+            std::slice::from_ref(&event),
+            &placements,
+            &[list],
+            &ColumnMapping::default(),
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!(written, 2);
+        let csv = String::from_utf8(buf).unwrap();
+        assert!(csv.starts_with("Event Name,Event Date,"));
+
+        let import = parse_dataset(&csv, &ColumnMapping::default(), "file://x.csv", |_| {
+            EntityId::from("current")
+        })
+        .unwrap();
+        assert!(import.errors.is_empty());
+        assert_eq!(import.events.len(), 1);
+        assert_eq!(import.events[0].id, event.id);
+        assert_eq!(import.events[0].player_count, Some(40));
+        assert_eq!(import.events[0].source_name, SOURCE_NAME);
+        assert_eq!(import.placements[0].player_name, "Alice");
+        assert_eq!(import.placements[0].record.as_ref().unwrap().wins, 5);
+        assert_eq!(import.lists.len(), 1);
+        assert_eq!(import.lists[0].total_points, 1995);
+        assert_eq!(
+            import.placements[0].list_id.as_ref(),
+            Some(&import.lists[0].id)
+        );
+    }
+
+    #[test]
+    fn test_parse_dataset_with_mapping_and_row_errors() {
+        let mapping = ColumnMapping::from_toml("[columns]\nplacing = \"Rank\"\n").unwrap();
+        let csv = "Event Name,Event Date,Rank,Player Name,Faction\n\
+                   Spring RTT,12/04/2025,1,Alice,Aeldari\n\
+                   Spring RTT,12/04/2025,2,Bob,\n\
+                   Summer GT,2025-07-19,1,Cara,Orks\n";
+        let import =
+            parse_dataset(csv, &mapping, "file://x.csv", |_| EntityId::from("current")).unwrap();
+        assert_eq!(import.events.len(), 2);
+        assert_eq!(import.placements.len(), 2);
+        assert_eq!(import.events[0].player_count, Some(1));
+        assert_eq!(import.errors.len(), 1);
+        assert_eq!(import.errors[0].line, 3);
+
+        let bad = ColumnMapping::from_toml("[columns]\nelo = \"Rating\"\n").unwrap();
+        assert!(matches!(
+            parse_dataset(csv, &bad, "", |_| EntityId::from("current")),
+            Err(StatCheckError::UnknownField(_))
+        ));
+        assert!(matches!(
+            parse_dataset(csv, &ColumnMapping::default(), "", |_| {
+                EntityId::from("current")
+            }),
+            Err(StatCheckError::MissingColumn("placing"))
+        ));
+    }
+}
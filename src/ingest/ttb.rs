@@ -0,0 +1,543 @@
+//! Tabletop Battles / Stats and Ladders export import.
+//!
+//! Private events run on the Tabletop Battles (TTB) app or Stats and
+//! Ladders never reach BCP. Both apps export an event's games as JSON or
+//! CSV; this module parses either into a flat list of games and converts
+//! them into [`Pairing`] and [`Placement`] records, computing standings the
+//! same way as for BCP events (wins, then battle points).
+//!
+//! JSON exports may list games flat (`games`/`matches`/`pairings`) or
+//! grouped under `rounds`; players may be plain names or objects with a
+//! `faction`. CSV exports need one row per game with round, player and
+//! score columns; header names are matched loosely ("Player 1",
+//! "player1_name", "P1 Score", ...).
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::models::{Confidence, EntityId, EventId, Pairing, Placement};
+use crate::sync::normalize_player_name;
+
+/// Errors that can occur while reading an export.
+#[derive(Debug, Error)]
+pub enum TtbError {
+    #[error("Invalid JSON export: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Invalid CSV export: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("CSV export is missing a {0} column")]
+    MissingColumn(&'static str),
+
+    #[error("Export contains no games")]
+    Empty,
+}
+
+/// One game from an export. `player2` is None for a bye.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TtbGame {
+    pub round: u32,
+    pub player1: String,
+    pub player1_faction: Option<String>,
+    pub player1_score: Option<u32>,
+    pub player2: Option<String>,
+    pub player2_faction: Option<String>,
+    pub player2_score: Option<u32>,
+    /// Explicit result for player 1 ("win", "loss", "draw") when the
+    /// export gives one; otherwise derived from the scores
+    pub player1_result: Option<String>,
+}
+
+/// A parsed export.
+#[derive(Debug, Clone, Default)]
+pub struct TtbExport {
+    /// Event name from the export, if present
+    pub event_name: Option<String>,
+    /// Event date from the export, if present
+    pub event_date: Option<NaiveDate>,
+    pub games: Vec<TtbGame>,
+    /// Player name -> faction, from the player list and game rows
+    pub factions: HashMap<String, String>,
+}
+
+/// Parse an export, detecting JSON vs CSV from the content.
+pub fn parse_export(content: &str) -> Result<TtbExport, TtbError> {
+    let trimmed = content.trim_start_matches('\u{feff}').trim_start();
+    let export = if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        parse_json(trimmed)?
+    } else {
+        parse_csv(trimmed)?
+    };
+    if export.games.is_empty() {
+        return Err(TtbError::Empty);
+    }
+    Ok(export)
+}
+
+// ── JSON ────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum JsonExport {
+    Event(JsonEvent),
+    Games(Vec<JsonGame>),
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct JsonEvent {
+    #[serde(alias = "eventName", alias = "event_name", alias = "title")]
+    name: Option<String>,
+    #[serde(alias = "startDate", alias = "start_date", alias = "eventDate")]
+    date: Option<String>,
+    #[serde(alias = "event")]
+    info: Option<Box<JsonEvent>>,
+    #[serde(alias = "participants", alias = "teams")]
+    players: Vec<JsonPlayer>,
+    #[serde(alias = "matches", alias = "pairings")]
+    games: Vec<JsonGame>,
+    rounds: Vec<JsonRound>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRound {
+    #[serde(alias = "number", alias = "roundNumber")]
+    round: u32,
+    #[serde(alias = "matches", alias = "pairings")]
+    games: Vec<JsonGame>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum JsonPlayer {
+    Name(String),
+    Player {
+        #[serde(alias = "playerName", alias = "player_name", alias = "displayName")]
+        name: String,
+        #[serde(default, alias = "army", alias = "armyName", alias = "race")]
+        faction: Option<String>,
+    },
+}
+
+impl JsonPlayer {
+    fn name(&self) -> &str {
+        match self {
+            JsonPlayer::Name(n) => n,
+            JsonPlayer::Player { name, .. } => name,
+        }
+    }
+
+    fn faction(&self) -> Option<&str> {
+        match self {
+            JsonPlayer::Name(_) => None,
+            JsonPlayer::Player { faction, .. } => faction.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonGame {
+    #[serde(default, alias = "roundNumber")]
+    round: Option<u32>,
+    #[serde(
+        alias = "player_one",
+        alias = "playerOne",
+        alias = "p1",
+        alias = "home"
+    )]
+    player1: JsonPlayer,
+    #[serde(
+        default,
+        alias = "player_two",
+        alias = "playerTwo",
+        alias = "p2",
+        alias = "away"
+    )]
+    player2: Option<JsonPlayer>,
+    #[serde(
+        default,
+        alias = "player1_score",
+        alias = "player1Score",
+        alias = "p1_score",
+        alias = "score_one"
+    )]
+    score1: Option<u32>,
+    #[serde(
+        default,
+        alias = "player2_score",
+        alias = "player2Score",
+        alias = "p2_score",
+        alias = "score_two"
+    )]
+    score2: Option<u32>,
+    #[serde(default, alias = "player1_result", alias = "player1Result")]
+    result: Option<String>,
+}
+
+fn parse_json(content: &str) -> Result<TtbExport, TtbError> {
+    let parsed: JsonExport = serde_json::from_str(content)?;
+    let mut export = TtbExport::default();
+
+    let (games, rounds) = match parsed {
+        JsonExport::Games(games) => (games, Vec::new()),
+        JsonExport::Event(mut event) => {
+            if let Some(info) = event.info.take() {
+                event.name = event.name.or(info.name);
+                event.date = event.date.or(info.date);
+            }
+            export.event_name = event.name;
+            export.event_date = event.date.as_deref().and_then(parse_date);
+            for p in &event.players {
+                if let Some(f) = p.faction() {
+                    export
+                        .factions
+                        .insert(p.name().trim().to_string(), f.to_string());
+                }
+            }
+            (event.games, event.rounds)
+        }
+    };
+
+    let flat = games.into_iter().map(|g| (None, g));
+    let grouped = rounds
+        .into_iter()
+        .flat_map(|r| r.games.into_iter().map(move |g| (Some(r.round), g)));
+    for (round, game) in flat.chain(grouped) {
+        let player2 = game
+            .player2
+            .as_ref()
+            .map(|p| p.name().trim().to_string())
+            .filter(|n| !is_bye(n));
+        let game_row = TtbGame {
+            round: round.or(game.round).unwrap_or(0),
+            player1: game.player1.name().trim().to_string(),
+            player1_faction: game.player1.faction().map(str::to_string),
+            player1_score: game.score1,
+            player2_faction: player2
+                .as_ref()
+                .and(game.player2.as_ref())
+                .and_then(|p| p.faction().map(str::to_string)),
+            player2,
+            player2_score: game.score2,
+            player1_result: game.result.as_deref().and_then(normalize_result),
+        };
+        export.push_game(game_row);
+    }
+    Ok(export)
+}
+
+// ── CSV ─────────────────────────────────────────────────────────────────────
+
+/// Header aliases per column, compared after lowercasing and dropping
+/// everything but letters and digits.
+const ROUND_COLUMNS: &[&str] = &["round", "roundnumber", "rnd"];
+const P1_COLUMNS: &[&str] = &["player1", "player1name", "p1", "playerone", "home"];
+const P2_COLUMNS: &[&str] = &["player2", "player2name", "p2", "playertwo", "away"];
+const P1_FACTION_COLUMNS: &[&str] = &["faction1", "player1faction", "p1faction", "army1"];
+const P2_FACTION_COLUMNS: &[&str] = &["faction2", "player2faction", "p2faction", "army2"];
+const P1_SCORE_COLUMNS: &[&str] = &["score1", "player1score", "p1score", "vp1"];
+const P2_SCORE_COLUMNS: &[&str] = &["score2", "player2score", "p2score", "vp2"];
+const RESULT_COLUMNS: &[&str] = &["result", "player1result", "p1result"];
+
+fn normalize_header(h: &str) -> String {
+    h.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn parse_csv(content: &str) -> Result<TtbExport, TtbError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+    let headers: Vec<String> = reader.headers()?.iter().map(normalize_header).collect();
+    let find = |aliases: &[&str]| headers.iter().position(|h| aliases.contains(&h.as_str()));
+
+    let p1_col = find(P1_COLUMNS).ok_or(TtbError::MissingColumn("player 1"))?;
+    let p2_col = find(P2_COLUMNS).ok_or(TtbError::MissingColumn("player 2"))?;
+    let round_col = find(ROUND_COLUMNS);
+    let (f1_col, f2_col) = (find(P1_FACTION_COLUMNS), find(P2_FACTION_COLUMNS));
+    let (s1_col, s2_col) = (find(P1_SCORE_COLUMNS), find(P2_SCORE_COLUMNS));
+    let result_col = find(RESULT_COLUMNS);
+
+    let mut export = TtbExport::default();
+    for record in reader.records() {
+        let record = record?;
+        let field = |col: Option<usize>| {
+            col.and_then(|i| record.get(i))
+                .map(str::to_string)
+                .filter(|v| !v.is_empty())
+        };
+        let number = |col: Option<usize>| field(col).and_then(|v| v.parse::<u32>().ok());
+
+        let Some(player1) = field(Some(p1_col)) else {
+            continue;
+        };
+        let player2 = field(Some(p2_col)).filter(|n| !is_bye(n));
+        export.push_game(TtbGame {
+            round: number(round_col).unwrap_or(0),
+            player1,
+            player1_faction: field(f1_col),
+            player1_score: number(s1_col),
+            player2_faction: player2.as_ref().and(field(f2_col)),
+            player2,
+            player2_score: number(s2_col),
+            player1_result: field(result_col).as_deref().and_then(normalize_result),
+        });
+    }
+    Ok(export)
+}
+
+// ── Shared helpers ──────────────────────────────────────────────────────────
+
+impl TtbExport {
+    fn push_game(&mut self, game: TtbGame) {
+        if let Some(ref f) = game.player1_faction {
+            self.factions
+                .entry(game.player1.clone())
+                .or_insert_with(|| f.clone());
+        }
+        if let (Some(p2), Some(f)) = (&game.player2, &game.player2_faction) {
+            self.factions.entry(p2.clone()).or_insert_with(|| f.clone());
+        }
+        self.games.push(game);
+    }
+}
+
+fn is_bye(name: &str) -> bool {
+    let lower = name.trim().to_lowercase();
+    lower.is_empty() || lower == "bye" || lower == "freilos"
+}
+
+fn normalize_result(result: &str) -> Option<String> {
+    match result.trim().to_lowercase().as_str() {
+        "win" | "w" | "won" | "1" => Some("win".to_string()),
+        "loss" | "l" | "lost" | "0" => Some("loss".to_string()),
+        "draw" | "d" | "tie" | "0.5" => Some("draw".to_string()),
+        _ => None,
+    }
+}
+
+fn parse_date(s: &str) -> Option<NaiveDate> {
+    let date_part = s.get(..10).unwrap_or(s);
+    NaiveDate::parse_from_str(date_part, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(s.trim(), "%d/%m/%Y"))
+        .ok()
+}
+
+/// Player 1's result for a game: the explicit result if given, otherwise
+/// by score. Byes count as wins; unscored games have no result.
+fn player1_result(game: &TtbGame) -> Option<&'static str> {
+    if game.player2.is_none() {
+        return Some("win");
+    }
+    if let Some(ref r) = game.player1_result {
+        return match r.as_str() {
+            "win" => Some("win"),
+            "loss" => Some("loss"),
+            _ => Some("draw"),
+        };
+    }
+    let (s1, s2) = (game.player1_score?, game.player2_score?);
+    Some(match s1.cmp(&s2) {
+        std::cmp::Ordering::Greater => "win",
+        std::cmp::Ordering::Less => "loss",
+        std::cmp::Ordering::Equal => "draw",
+    })
+}
+
+// ── Conversion ──────────────────────────────────────────────────────────────
+
+/// Convert an export's games into pairings (byes are skipped).
+pub fn pairings_from_export(
+    export: &TtbExport,
+    event_id: &EventId,
+    epoch_id: &EntityId,
+) -> Vec<Pairing> {
+    export
+        .games
+        .iter()
+        .filter_map(|g| {
+            let player2 = g.player2.clone()?;
+            let mut pairing = Pairing::new(
+                event_id.clone(),
+                epoch_id.clone(),
+                g.round,
+                g.player1.clone(),
+                player2,
+            );
+            pairing.player1_faction = faction_for(export, &g.player1, &g.player1_faction);
+            pairing.player2_faction = g
+                .player2
+                .as_ref()
+                .and_then(|p2| faction_for(export, p2, &g.player2_faction));
+            pairing.player1_result = player1_result(g).map(str::to_string);
+            pairing.player1_game_points = g.player1_score;
+            pairing.player2_game_points = g.player2_score;
+            Some(pairing)
+        })
+        .collect()
+}
+
+fn faction_for(export: &TtbExport, player: &str, game_faction: &Option<String>) -> Option<String> {
+    game_faction
+        .clone()
+        .or_else(|| export.factions.get(player).cloned())
+}
+
+#[derive(Default)]
+struct Tally {
+    name: String,
+    wins: u32,
+    losses: u32,
+    draws: u32,
+    battle_points: u32,
+}
+
+/// Compute final standings: wins desc, then battle points desc, then name.
+pub fn placements_from_export(
+    export: &TtbExport,
+    event_id: &EventId,
+    epoch_id: &EntityId,
+) -> Vec<Placement> {
+    let mut tallies: HashMap<String, Tally> = HashMap::new();
+    let mut record = |name: &str, result: Option<&str>, score: Option<u32>| {
+        let t = tallies
+            .entry(normalize_player_name(name))
+            .or_insert_with(|| Tally {
+                name: name.to_string(),
+                ..Default::default()
+            });
+        match result {
+            Some("win") => t.wins += 1,
+            Some("loss") => t.losses += 1,
+            Some(_) => t.draws += 1,
+            None => {}
+        }
+        t.battle_points += score.unwrap_or(0);
+    };
+
+    for game in &export.games {
+        let result = player1_result(game);
+        record(&game.player1, result, game.player1_score);
+        if let Some(ref p2) = game.player2 {
+            let p2_result = result.map(|r| match r {
+                "win" => "loss",
+                "loss" => "win",
+                other => other,
+            });
+            record(p2, p2_result, game.player2_score);
+        }
+    }
+
+    let mut tallies: Vec<Tally> = tallies.into_values().collect();
+    tallies.sort_by(|a, b| {
+        b.wins
+            .cmp(&a.wins)
+            .then(b.battle_points.cmp(&a.battle_points))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    tallies
+        .into_iter()
+        .enumerate()
+        .map(|(i, t)| {
+            let faction = export
+                .factions
+                .get(&t.name)
+                .cloned()
+                .unwrap_or_else(|| "Unknown".to_string());
+            Placement::new(
+                event_id.clone(),
+                epoch_id.clone(),
+                i as u32 + 1,
+                t.name,
+                faction,
+            )
+            .with_record(t.wins, t.losses, t.draws)
+            .with_battle_points(t.battle_points)
+            .with_confidence(Confidence::High)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json_rounds_and_standings() {
+        let json = r#"{
+            "event": {"name": "Club Night League", "startDate": "2025-05-03T09:00:00Z"},
+            "players": [
+                {"name": "Alice", "faction": "Aeldari"},
+                {"name": "Bob", "army": "Necrons"},
+                {"name": "Cara", "faction": "Orks"}
+            ],
+            "rounds": [
+                {"number": 1, "matches": [
+                    {"player1": "Alice", "player2": "Bob", "score1": 85, "score2": 60},
+                    {"player1": "Cara", "player2": "BYE"}
+                ]},
+                {"number": 2, "matches": [
+                    {"player1": {"name": "Cara"}, "player2": {"name": "Alice"}, "score1": 70, "score2": 70}
+                ]}
+            ]
+        }"#;
+        let export = parse_export(json).unwrap();
+        assert_eq!(export.event_name.as_deref(), Some("Club Night League"));
+        assert_eq!(export.event_date, NaiveDate::from_ymd_opt(2025, 5, 3));
+        assert_eq!(export.games.len(), 3);
+        assert_eq!(export.games[1].player2, None);
+
+        let event_id = EntityId::from("event-1");
+        let epoch_id = EntityId::from("current");
+        let pairings = pairings_from_export(&export, &event_id, &epoch_id);
+        assert_eq!(pairings.len(), 2, "byes are not stored as pairings");
+        assert_eq!(pairings[0].player1_result.as_deref(), Some("win"));
+        assert_eq!(pairings[0].player2_faction.as_deref(), Some("Necrons"));
+        assert_eq!(pairings[1].round, 2);
+        assert_eq!(pairings[1].player1_result.as_deref(), Some("draw"));
+
+        let placements = placements_from_export(&export, &event_id, &epoch_id);
+        assert_eq!(placements.len(), 3);
+        // Alice and Cara both 1-0-1; Alice has more battle points
+        assert_eq!(placements[0].player_name, "Alice");
+        assert_eq!(placements[0].faction, "Aeldari");
+        assert_eq!(placements[0].battle_points, Some(155));
+        assert_eq!(placements[1].player_name, "Cara");
+        assert_eq!(placements[2].player_name, "Bob");
+        assert_eq!(placements[2].record.as_ref().unwrap().losses, 1);
+    }
+
+    #[test]
+    fn test_parse_csv_with_loose_headers() {
+        let csv = "Round,Player 1,Faction 1,P1 Score,Player 2,Faction 2,P2 Score\n\
+                   1,Alice,Aeldari,85,Bob,Necrons,60\n\
+                   2,Bob,Necrons,72,Alice,Aeldari,40\n";
+        let export = parse_export(csv).unwrap();
+        assert_eq!(export.games.len(), 2);
+        assert_eq!(export.games[1].round, 2);
+        assert_eq!(
+            export.factions.get("Bob").map(String::as_str),
+            Some("Necrons")
+        );
+
+        let placements =
+            placements_from_export(&export, &EntityId::from("e"), &EntityId::from("current"));
+        // 1-1 each; Bob has 132 battle points to Alice's 125
+        assert_eq!(placements[0].player_name, "Bob");
+    }
+
+    #[test]
+    fn test_csv_missing_player_column() {
+        let err = parse_export("round,home\n1,Alice\n").unwrap_err();
+        assert!(matches!(err, TtbError::MissingColumn("player 2")));
+        assert!(matches!(parse_export("[]"), Err(TtbError::Empty)));
+    }
+}
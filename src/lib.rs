@@ -10,14 +10,34 @@
 //! - **api**: REST API endpoints
 //! - **calculate**: Statistics and derived metrics computation
 //! - **config**: Configuration loading and validation
+//! - **dashboard**: The bundled web UI and static meta snapshot export
+//! - **demo**: Bundled sample dataset for exploring the API
+//! - **export**: CSV/JSON/Parquet exports of any stored entity
+//! - **maintain**: Consistency repair across all stored epochs
+//! - **metrics**: Prometheus counters and histograms for a running process
+//! - **notify**: Watchlist webhook notifications sent after sync runs
+//! - **parsing**: Army list text parsing shared by every source
+//! - **progress**: Progress bars and timing summaries for long-running CLI commands
+//! - **publish**: Static HTML/JSON site of an epoch's analytics
+//! - **scheduler**: Recurring sync, repartition, normalization and derive jobs
 
 pub mod agents;
 pub mod api;
 pub mod calculate;
 pub mod config;
+pub mod dashboard;
+pub mod demo;
+pub mod export;
 pub mod fetch;
 pub mod ingest;
+pub mod maintain;
+pub mod metrics;
 pub mod models;
+pub mod notify;
+pub mod parsing;
+pub mod progress;
+pub mod publish;
+pub mod scheduler;
 pub mod storage;
 pub mod sync;
 
@@ -25,14 +45,16 @@ pub use models::*;
 
 use std::time::Duration;
 
-/// Parse a human-friendly duration string (e.g., "6h", "30m", "90s").
+/// Parse a human-friendly duration string (e.g., "1d", "6h", "30m", "90s").
 pub fn parse_duration(s: &str) -> Option<Duration> {
     let s = s.trim();
     if s.is_empty() {
         return None;
     }
 
-    let (num_str, multiplier) = if let Some(n) = s.strip_suffix('h') {
+    let (num_str, multiplier) = if let Some(n) = s.strip_suffix('d') {
+        (n, 86400)
+    } else if let Some(n) = s.strip_suffix('h') {
         (n, 3600)
     } else if let Some(n) = s.strip_suffix('m') {
         (n, 60)
@@ -56,6 +78,11 @@ mod tests {
         assert_eq!(parse_duration("6h"), Some(Duration::from_secs(21600)));
     }
 
+    #[test]
+    fn test_parse_duration_days() {
+        assert_eq!(parse_duration("1d"), Some(Duration::from_secs(86400)));
+    }
+
     #[test]
     fn test_parse_duration_minutes() {
         assert_eq!(parse_duration("30m"), Some(Duration::from_secs(1800)));
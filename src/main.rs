@@ -7,45 +7,81 @@ use clap::{Parser, Subcommand};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use meta_agent::agents::backend::{AiBackend, OllamaBackend};
-use meta_agent::agents::list_normalizer::{ListNormalizerAgent, ListNormalizerInput};
-use meta_agent::agents::Agent;
-use meta_agent::api::dedup_by_id;
+use meta_agent::agents::dataslate_parser::{DataslateParserAgent, DataslateParserInput};
+use meta_agent::agents::list_normalizer::{
+    apply_normalized, ListNormalizerAgent, ListNormalizerInput,
+};
+use meta_agent::agents::points_watcher::{PointsWatcherAgent, PointsWatcherInput};
+use meta_agent::agents::{execute_with_retry, Agent};
+use meta_agent::api::merge_by_id;
+use meta_agent::config::{AiConfig, AppConfig};
 use meta_agent::fetch::{Fetcher, FetcherConfig};
+use meta_agent::ingest::csv_import::CsvEntity;
 use meta_agent::ingest::{self, TestMockBackend};
 use meta_agent::models::{
-    ArmyList, Confidence, EpochMapper, SignificantEvent, SignificantEventType,
+    ArmyList, Confidence, EpochMapper, PointsChange, SignificantEvent, SignificantEventType,
+    Timestamped,
 };
+use meta_agent::notify::Notifier;
+use meta_agent::progress::{Progress, ProgressMode};
+use meta_agent::scheduler::{discover_balance_passes, Job, Scheduler};
 use meta_agent::storage::{
-    read_significant_events, write_significant_events, EntityType, JsonlReader, JsonlWriter,
-    StorageConfig,
+    cached_epoch_mapper, current_epoch_id, read_significant_events, write_significant_events,
+    EntityType, JsonlReader, JsonlWriter, StorageConfig,
 };
-use meta_agent::sync::{SyncConfig, SyncOrchestrator, SyncSource};
+use meta_agent::sync::{ActiveSyncs, Source, SyncConfig, SyncOrchestrator, SyncSource};
 
 #[derive(Parser)]
 #[command(name = "meta-agent")]
 #[command(about = "Warhammer 40k meta tracker with AI-powered extraction")]
 #[command(version)]
 struct Cli {
-    /// Path to configuration file
-    #[arg(long, default_value = "./config.toml")]
-    config: String,
+    /// Path to configuration file (default: ./config.toml, if present)
+    #[arg(long)]
+    config: Option<std::path::PathBuf>,
 
-    /// Data directory path
-    #[arg(long, default_value = "./data")]
-    data_dir: String,
+    /// Data directory path (overrides `data_dir` from the config file)
+    #[arg(long)]
+    data_dir: Option<std::path::PathBuf>,
 
-    /// Log level (trace, debug, info, warn, error)
-    #[arg(long, default_value = "info")]
-    log_level: String,
+    /// Log level: trace, debug, info, warn, error (overrides `log_level`)
+    #[arg(long)]
+    log_level: Option<String>,
 
     /// Output logs as JSON
     #[arg(long)]
     json_logs: bool,
 
+    /// Command output format. `json` prints a machine-readable summary and
+    /// disables progress bars (which are also off when stderr is not a TTY).
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text, global = true)]
+    output: OutputFormat,
+
+    /// Write new normalized data to per-source partitions
+    /// (normalized/<epoch>/<source>/). Existing files stay readable.
+    #[arg(long)]
+    partition_by_source: bool,
+
+    /// Backend for per-epoch records: jsonl or sqlite (overrides
+    /// `[storage] backend`; sqlite needs the `sqlite` feature)
+    #[arg(long)]
+    storage_backend: Option<meta_agent::storage::StorageBackend>,
+
+    /// Send every AI request to the backend instead of reusing cached
+    /// responses from state/ai_cache/
+    #[arg(long, global = true)]
+    no_ai_cache: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Sync tournament data from sources
@@ -81,21 +117,62 @@ enum Commands {
         /// Process a single article URL directly (bypasses discovery)
         #[arg(long)]
         url: Option<String>,
+
+        /// Ignore the incremental sync cursor and rescan the full date range
+        #[arg(long)]
+        full: bool,
+
+        /// Store upcoming BCP events (today to --to, default 90 days ahead)
+        /// instead of syncing results
+        #[arg(long, conflicts_with_all = ["url", "source"])]
+        upcoming: bool,
     },
 
     /// Start the API server
     Serve {
-        /// Bind address
-        #[arg(long, default_value = "127.0.0.1")]
-        host: String,
+        /// Bind address (overrides `[server] host`)
+        #[arg(long)]
+        host: Option<String>,
 
-        /// Port number
-        #[arg(long, default_value = "3000")]
-        port: u16,
+        /// Port number (overrides `[server] port`)
+        #[arg(long)]
+        port: Option<u16>,
 
         /// Log all HTTP requests
         #[arg(long)]
         access_log: bool,
+
+        /// Run the `[schedule]` jobs in the background while serving
+        #[arg(long)]
+        scheduler: bool,
+
+        /// Sync the enabled sources in the background while serving;
+        /// progress shows in `/api/refresh/status`
+        #[arg(long)]
+        with_sync: bool,
+
+        /// Background sync interval (e.g., "6h", "30m")
+        #[arg(long, default_value = "6h", requires = "with_sync")]
+        interval: String,
+    },
+
+    /// Seed the data dir with a bundled sample dataset and start the server
+    Demo {
+        /// Bind address (overrides `[server] host`)
+        #[arg(long)]
+        host: Option<String>,
+
+        /// Port number (overrides `[server] port`)
+        #[arg(long)]
+        port: Option<u16>,
+
+        /// Replace existing normalized data in the data dir
+        #[arg(long)]
+        force: bool,
+
+        /// Seed the data without starting the server
+        #[arg(long)]
+        seed_only: bool,
     },
 
     /// Rebuild Parquet files from JSONL
@@ -115,11 +192,14 @@ enum Commands {
 
     /// Compute derived analytics
     Derive {
-        /// Epoch to analyze
+        /// Epoch to analyze (default: current epoch, "all" for every epoch)
         #[arg(long)]
         epoch: Option<String>,
 
-        /// Derivations to run (comma-separated)
+        /// Derivations to run (comma-separated: faction_stats, unit_frequency,
+        /// matchups, tier_list, placement_curves, archetypes, composite_tiers,
+        /// combos, unit_performance; default: all, plus player ratings across
+        /// every epoch)
         #[arg(long)]
         run: Option<String>,
 
@@ -134,6 +214,47 @@ enum Commands {
         action: ReviewAction,
     },
 
+    /// List or retry articles and events a sync failed to import
+    Issues {
+        #[command(subcommand)]
+        action: IssuesAction,
+    },
+
+    /// Cross-check our aggregates against Goonhammer season summaries
+    SeasonSummary {
+        #[command(subcommand)]
+        action: SeasonSummaryAction,
+    },
+
+    /// Re-run extraction over cached articles without fetching, e.g. after
+    /// a prompt change or switching AI backend
+    Reprocess {
+        /// Article URL
+        #[arg(
+            long,
+            conflicts_with_all = ["all_failed", "older_than_version"],
+            required_unless_present_any = ["all_failed", "older_than_version"]
+        )]
+        url: Option<String>,
+
+        /// Article date, YYYY-MM-DD (default: the cached post's date, else today)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Every article with an open ingest issue
+        #[arg(long, conflicts_with = "older_than_version")]
+        all_failed: bool,
+
+        /// Every article with records extracted by an agent older than this
+        /// version; their records are replaced (snapshotted first)
+        #[arg(long)]
+        older_than_version: Option<u32>,
+
+        /// Project AI calls, tokens, and cost without reprocessing anything
+        #[arg(long)]
+        estimate: bool,
+    },
+
     /// Debug utilities
     Debug {
         #[command(subcommand)]
@@ -161,14 +282,61 @@ enum Commands {
         /// Only process lists matching this faction (e.g. "Space Marines")
         #[arg(long)]
         faction: Option<String>,
+
+        /// Project AI calls, tokens, and cost without normalizing anything
+        #[arg(long)]
+        estimate: bool,
+
+        /// Lists normalized at once (request starts stay 500ms apart)
+        #[arg(long, default_value_t = 1)]
+        concurrency: usize,
+
+        /// Lists per incremental write
+        #[arg(long, default_value_t = 50)]
+        batch_size: usize,
+
+        /// Ignore the checkpoint of an interrupted run and start over
+        #[arg(long)]
+        restart: bool,
+    },
+
+    /// Compare list extraction between AI model versions
+    DriftReport {
+        /// Epoch to read lists from (default: current epoch)
+        #[arg(long)]
+        epoch: Option<String>,
+
+        /// Model the stored lists were extracted with (default: the model
+        /// behind the most stored lists)
+        #[arg(long)]
+        baseline: Option<String>,
+
+        /// Model to re-extract a sample of the baseline's raw lists with.
+        /// Without it, compares the models already recorded in storage.
+        #[arg(long)]
+        candidate: Option<String>,
+
+        /// Number of raw lists to re-extract with the candidate
+        #[arg(long, default_value = "25")]
+        sample: usize,
     },
 
     /// Register a balance pass / significant event
     AddBalancePass {
-        /// Date of the balance pass (YYYY-MM-DD)
+        /// Date the balance pass was announced (YYYY-MM-DD)
         #[arg(long)]
         date: String,
 
+        /// Date the changes take effect, if later than the announcement
+        /// (YYYY-MM-DD)
+        #[arg(long)]
+        effective_date: Option<String>,
+
+        /// Days after the effective date that still count toward the previous
+        /// epoch (default: `[epochs] grace_days` from the config file)
+        #[arg(long)]
+        grace_days: Option<u32>,
+
         /// Title (e.g. "Balance Dataslate Q4 2025")
         #[arg(long)]
         title: String,
@@ -181,9 +349,14 @@ enum Commands {
         #[arg(long)]
         pdf_url: Option<String>,
 
-        /// Event type: "balance" or "edition"
+        /// Event type: "balance", "edition" or "faq" (FAQ/errata; annotates
+        /// the timeline without starting an epoch)
         #[arg(long, default_value = "balance")]
         event_type: String,
+
+        /// Factions a FAQ applies to (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        factions: Vec<String>,
     },
 
     /// Discover balance passes from Warhammer Community
@@ -197,6 +370,42 @@ enum Commands {
         url: Option<String>,
     },
 
+    /// Record unit points costs from the Munitorum Field Manual PDFs linked
+    /// to balance passes
+    SyncPoints {
+        /// Only process this balance pass (significant event ID)
+        #[arg(long)]
+        event: Option<String>,
+
+        /// Field Manual URL to use instead of the balance pass's PDF link
+        /// (requires --event)
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Print what would be recorded without writing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Re-parse epochs that already have points recorded
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Parse balance dataslate PDFs into per-faction rule changes
+    ParseDataslates {
+        /// Only parse this balance pass (significant event ID)
+        #[arg(long)]
+        event: Option<String>,
+
+        /// Print what would be recorded without writing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Re-parse passes that already have rule changes
+        #[arg(long)]
+        force: bool,
+    },
+
     /// Weekly update: fetch new results, check for balance passes, update epochs
     WeeklyUpdate {
         /// Print what would happen without writing
@@ -234,7 +443,8 @@ enum Commands {
         dry_run: bool,
     },
 
-    /// Link army lists to placements by player name (retroactive fix)
+    /// Link army lists to placements by player name and flag cloned
+    /// list submissions (retroactive fix)
     LinkLists {
         /// Epoch to process (default: current)
         #[arg(long)]
@@ -245,6 +455,42 @@ enum Commands {
         dry_run: bool,
     },
 
+    /// Run all consistency repairs (dedup, faction taxonomy, list linking,
+    /// game sizes, reference validation) over every epoch
+    Maintain {
+        /// Report what would change without writing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Also fail on validation warnings
+        #[arg(long)]
+        strict: bool,
+
+        /// Run continuously at interval (e.g. nightly with "24h")
+        #[arg(long)]
+        watch: bool,
+
+        /// Maintenance interval when watching
+        #[arg(long, default_value = "24h")]
+        interval: String,
+    },
+
+    /// Rewrite entity files with duplicate records removed (keeping the
+    /// latest update) and sorted
+    Compact {
+        /// Epoch to compact, or "all"
+        #[arg(long, default_value = "all")]
+        epoch: String,
+
+        /// Report what would be reclaimed without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Repartition data by epoch
     Repartition {
         /// Show what would happen without writing
@@ -259,169 +505,556 @@ enum Commands {
         #[arg(long)]
         keep_originals: bool,
     },
-}
 
-#[derive(Subcommand)]
-enum ReviewAction {
-    /// List pending review items
-    List {
+    /// Correct an event's date, moving it and its placements, lists and
+    /// pairings to the epoch the new date belongs to
+    FixEvent {
+        /// Event ID
         #[arg(long)]
-        entity_type: Option<String>,
+        id: String,
 
-        #[arg(long, default_value = "10")]
-        limit: usize,
+        /// Correct event date (YYYY-MM-DD)
+        #[arg(long)]
+        date: String,
+
+        /// Show what would move without writing
+        #[arg(long)]
+        dry_run: bool,
     },
 
-    /// Show review item details
-    Show { id: String },
+    /// Merge a duplicate event into another, re-pointing its placements,
+    /// lists and pairings
+    MergeEvents {
+        /// Event to keep
+        #[arg(long, requires = "remove", required_unless_present = "suggest")]
+        keep: Option<String>,
 
-    /// Resolve a review item
-    Resolve {
-        id: String,
+        /// Duplicate event to remove
+        #[arg(long, requires = "keep")]
+        remove: Option<String>,
 
+        /// List likely duplicate pairs instead of merging
+        #[arg(long, conflicts_with_all = ["keep", "remove"])]
+        suggest: bool,
+
+        /// Show what would change without writing
         #[arg(long)]
-        action: String,
+        dry_run: bool,
+    },
 
+    /// Run sync, balance discovery, repartition, list normalization and
+    /// derive on the schedules in `[schedule]`
+    Schedule {
+        /// Run one job now and exit (sync, balance-discovery, repartition,
+        /// normalize-lists, derive)
         #[arg(long)]
-        reason: Option<String>,
+        run: Option<String>,
+
+        /// Print the enabled jobs and their next run times, then exit
+        #[arg(long)]
+        list: bool,
     },
-}
 
-#[derive(Subcommand)]
-enum DebugAction {
-    /// Parse a fixture file
-    ParseFixture { path: String },
+    /// AI backend setup utilities
+    Ai {
+        #[command(subcommand)]
+        action: AiAction,
+    },
+
+    /// Render an epoch's analytics into a static HTML/JSON site
+    Publish {
+        /// Epoch ID or "current"
+        #[arg(long, default_value = "current")]
+        epoch: String,
 
-    /// Validate storage integrity
-    ValidateStorage,
+        /// Output directory
+        #[arg(long, default_value = "./site")]
+        out: std::path::PathBuf,
+    },
 
-    /// Show epoch timeline
-    Epochs,
+    /// Bundled web dashboard
+    Dashboard {
+        #[command(subcommand)]
+        action: DashboardAction,
+    },
 
-    /// Check army list matching coverage
-    CheckLists {
-        /// Epoch to check (default: current)
+    /// Watchlist webhook notifications (`[notifications]`)
+    Notify {
+        #[command(subcommand)]
+        action: NotifyAction,
+    },
+
+    /// Export stored data to CSV, JSON, JSONL or Parquet files
+    Export {
+        /// Entity to export: events, placements, lists, pairings,
+        /// bracket_matches or significant_events
         #[arg(long)]
-        epoch: Option<String>,
+        entity: meta_agent::export::ExportEntity,
+
+        /// Epoch ID, "current" or "all"
+        #[arg(long, default_value = "all")]
+        epoch: String,
+
+        /// Output format: csv, json, jsonl or parquet
+        #[arg(long, default_value = "csv")]
+        format: meta_agent::export::ExportFormat,
+
+        /// Output directory
+        #[arg(long, default_value = "./export")]
+        out: std::path::PathBuf,
     },
 
-    /// Check detachment consistency between placements and army lists
-    CheckDetachments {
-        /// Epoch to check (default: current)
+    /// Export results as a Stat Check community dataset CSV
+    ExportStatcheck {
+        /// Epoch ID, "current" or "all"
+        #[arg(long, default_value = "all")]
+        epoch: String,
+
+        /// Output directory
+        #[arg(long, default_value = "./export")]
+        out: std::path::PathBuf,
+
+        /// TOML file renaming dataset columns
         #[arg(long)]
-        epoch: Option<String>,
+        mapping: Option<std::path::PathBuf>,
     },
 
-    /// Re-parse army list units from raw_text using the regex parser
-    /// to update keywords and wargear fields
-    ReparseUnits {
-        /// Epoch to check (default: current)
+    /// Import events and results from files
+    Import {
+        #[command(subcommand)]
+        action: ImportAction,
+    },
+
+    /// Snapshot normalized/ and state/ into backups/, or list snapshots
+    Backup {
+        /// Label for the snapshot
+        #[arg(long, default_value = "manual")]
+        tag: String,
+
+        /// List existing snapshots instead of creating one
         #[arg(long)]
-        epoch: Option<String>,
+        list: bool,
+    },
 
-        /// Dry run — don't write changes
+    /// Copy per-epoch records between storage backends (JSONL files and
+    /// the SQLite database); the source is left untouched
+    ConvertStorage {
+        /// Backend to read from
         #[arg(long)]
-        dry_run: bool,
+        from: meta_agent::storage::StorageBackend,
+
+        /// Backend to write to
+        #[arg(long)]
+        to: meta_agent::storage::StorageBackend,
     },
 
-    /// Test ingestion from a fixture file
-    TestIngest {
-        /// Path to HTML fixture
-        path: String,
+    /// Replace normalized/ and state/ with a snapshot (the current data is
+    /// snapshotted as "pre-restore" first)
+    Restore {
+        /// Snapshot ID, tag (newest match) or "latest"
+        snapshot: String,
 
-        /// Type: "events" or "balance"
-        #[arg(long, default_value = "events")]
-        ingest_type: String,
+        /// Restore without asking for confirmation
+        #[arg(long)]
+        yes: bool,
+    },
+}
 
-        /// Use real Ollama backend (requires Ollama running)
+#[derive(Subcommand)]
+enum AiAction {
+    /// Check the backend, model and context window, pulling the model if
+    /// it is missing
+    Doctor {
+        /// Pull a missing model without asking
         #[arg(long)]
-        use_ollama: bool,
+        yes: bool,
     },
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
+#[derive(Subcommand)]
+enum DashboardAction {
+    /// Write the dashboard into the directory `serve` serves, or with
+    /// --output render the meta snapshot as a standalone static site
+    Init {
+        /// Dashboard directory
+        #[arg(long, default_value = meta_agent::dashboard::DEFAULT_DIR)]
+        dir: std::path::PathBuf,
+
+        /// Overwrite files that already exist
+        #[arg(long)]
+        force: bool,
 
-    // Initialize tracing
-    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(&cli.log_level));
+        /// Export the meta snapshot as a static page into this directory
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
 
-    tracing_subscriber::registry()
-        .with(filter)
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+        /// Epoch for --output (default: current)
+        #[arg(long, requires = "output")]
+        epoch: Option<String>,
+    },
+}
 
-    tracing::info!("Starting meta-agent v{}", env!("CARGO_PKG_VERSION"));
+#[derive(Subcommand)]
+enum NotifyAction {
+    /// Send a sample alert to every configured webhook
+    Test,
+}
 
-    match cli.command {
-        Commands::Sync {
-            once,
-            watch,
-            interval: interval_str,
-            from,
-            to,
-            source,
-            dry_run,
-            url: direct_url,
-        } => {
-            // Parse date range
-            let date_from = from.map(|s| {
-                NaiveDate::parse_from_str(&s, "%Y-%m-%d")
-                    .unwrap_or_else(|_| panic!("Invalid --from date (expected YYYY-MM-DD): {}", s))
-            });
-            let date_to = to.map(|s| {
-                NaiveDate::parse_from_str(&s, "%Y-%m-%d")
-                    .unwrap_or_else(|_| panic!("Invalid --to date (expected YYYY-MM-DD): {}", s))
-            });
+#[derive(Subcommand)]
+enum ImportAction {
+    /// Import a private event from a Tabletop Battles / Stats and Ladders export
+    Ttb {
+        /// Path to the exported file (JSON or CSV)
+        #[arg(long)]
+        file: std::path::PathBuf,
 
-            // Build source list
-            let sources = match source.as_deref() {
-                Some("goonhammer") => vec![SyncSource::Goonhammer {
-                    base_url: "https://www.goonhammer.com/tag/competitive-innovations-in-10th/"
-                        .to_string(),
-                }],
-                Some("bcp") => vec![SyncSource::Bcp {
-                    api_base_url: "https://newprod-api.bestcoastpairings.com/v1".to_string(),
-                    game_type: 1,
-                }],
-                Some("warhammer-community") => vec![SyncSource::WarhammerCommunity {
-                    url: "https://www.warhammer-community.com/en-gb/downloads/warhammer-40000/"
-                        .to_string(),
-                }],
-                None => vec![SyncSource::default()],
-                Some(other) => {
-                    eprintln!(
-                        "Unknown source: {}. Use 'goonhammer', 'bcp', or 'warhammer-community'.",
-                        other
-                    );
-                    return Ok(());
-                }
-            };
+        /// Event name (overrides the name in the export)
+        #[arg(long)]
+        event_name: Option<String>,
 
-            // Select backend
-            let backend: Arc<dyn AiBackend> = select_backend();
+        /// Event date, YYYY-MM-DD (required if the export has none)
+        #[arg(long)]
+        date: Option<String>,
 
-            // Storage config
-            let storage = StorageConfig::new(std::path::PathBuf::from(&cli.data_dir));
+        /// Event location
+        #[arg(long)]
+        location: Option<String>,
 
-            // Create fetcher with cache dir from storage config
-            let fetcher = Fetcher::new(FetcherConfig {
-                cache_dir: storage.raw_dir(),
-                ..Default::default()
-            })
-            .expect("Failed to create fetcher");
+        /// Show what would be imported without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
 
-            // Parse interval
-            let sync_interval =
-                meta_agent::parse_duration(&interval_str).unwrap_or(Duration::from_secs(6 * 3600));
+    /// Import events, placements or army lists from a spreadsheet CSV
+    Csv {
+        /// Entity in the file: events, placements or lists
+        #[arg(long)]
+        entity: CsvEntity,
 
-            let sync_config = SyncConfig {
+        /// Path to the CSV file
+        #[arg(long)]
+        file: std::path::PathBuf,
+
+        /// Event the rows belong to (required for placements and lists)
+        #[arg(long)]
+        event_id: Option<String>,
+
+        /// TOML file mapping fields to CSV headers
+        #[arg(long)]
+        mapping: Option<std::path::PathBuf>,
+
+        /// Show what would be imported without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Import a published Stat Check dataset CSV
+    Statcheck {
+        /// Path to the dataset CSV
+        #[arg(long)]
+        file: std::path::PathBuf,
+
+        /// TOML file mapping dataset fields to CSV headers
+        #[arg(long)]
+        mapping: Option<std::path::PathBuf>,
+
+        /// Show what would be imported without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Import a BattleScribe / New Recruit roster (.ros, .rosz or JSON) as
+    /// a player's army list
+    Roster {
+        /// Path to the roster file
+        #[arg(long)]
+        file: std::path::PathBuf,
+
+        /// Player who ran the list
+        #[arg(long)]
+        player: String,
+
+        /// Event the list was played at
+        #[arg(long)]
+        event_id: String,
+
+        /// Show what would be imported without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReviewAction {
+    /// List pending review items
+    List {
+        #[arg(long)]
+        entity_type: Option<String>,
+
+        #[arg(long, default_value = "10")]
+        limit: usize,
+    },
+
+    /// Show review item details
+    Show { id: String },
+
+    /// Resolve a review item
+    Resolve {
+        id: String,
+
+        #[arg(long)]
+        action: String,
+
+        #[arg(long)]
+        reason: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum IssuesAction {
+    /// List open ingest issues
+    List {
+        /// Include resolved issues
+        #[arg(long)]
+        all: bool,
+
+        /// Only issues from this source: goonhammer, bcp or t3
+        #[arg(long)]
+        source: Option<String>,
+    },
+
+    /// Re-run the articles or events behind issues
+    Retry {
+        /// Issue IDs
+        ids: Vec<String>,
+
+        /// Retry every open, retryable issue
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum SeasonSummaryAction {
+    /// Extract a season summary article's stats and record where ours differ
+    Check {
+        /// Article URL
+        url: String,
+
+        /// Show findings without recording them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// List recorded data-quality findings
+    Findings,
+}
+
+#[derive(Subcommand)]
+enum DebugAction {
+    /// Parse a saved Goonhammer, BCP or Warhammer Community page without AI
+    ParseFixture {
+        path: String,
+
+        /// Print the parsed entities as JSON (for regression fixtures)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Validate storage integrity (exits 1 on errors)
+    ValidateStorage {
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Also fail on warnings (duplicates, orphans)
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Check the Ollama server and the configured model
+    AiCheck {
+        /// Pull the model if it is not installed
+        #[arg(long)]
+        pull: bool,
+
+        /// Print the diagnostics as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show epoch timeline
+    Epochs,
+
+    /// Check army list matching coverage
+    CheckLists {
+        /// Epoch to check (default: current)
+        #[arg(long)]
+        epoch: Option<String>,
+    },
+
+    /// Check detachment consistency between placements and army lists
+    CheckDetachments {
+        /// Epoch to check (default: current)
+        #[arg(long)]
+        epoch: Option<String>,
+    },
+
+    /// Re-parse army list units from raw_text using the regex parser
+    /// to update keywords and wargear fields
+    ReparseUnits {
+        /// Epoch to check (default: current)
+        #[arg(long)]
+        epoch: Option<String>,
+
+        /// Dry run — don't write changes
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Test ingestion from a fixture file
+    TestIngest {
+        /// Path to HTML fixture
+        path: String,
+
+        /// Type: "events" or "balance"
+        #[arg(long, default_value = "events")]
+        ingest_type: String,
+
+        /// Use real Ollama backend (requires Ollama running)
+        #[arg(long)]
+        use_ollama: bool,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    // Load config; CLI flags take precedence over the file
+    let mut config = AppConfig::load(cli.config.as_deref())?;
+    if let Some(data_dir) = &cli.data_dir {
+        config.data_dir = data_dir.clone();
+    }
+    if let Some(log_level) = &cli.log_level {
+        config.log_level = log_level.clone();
+    }
+    if let Some(backend) = cli.storage_backend {
+        config.storage.backend = backend;
+    }
+    if cli.no_ai_cache {
+        config.ai.cache = false;
+    }
+
+    // Initialize tracing
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(&config.log_level));
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    tracing::info!("Starting meta-agent v{}", env!("CARGO_PKG_VERSION"));
+
+    // Local house rules: faction aliases and groups, excluded events/players
+    let overrides = meta_agent::storage::Overrides::load(&config.data_dir).unwrap_or_else(|e| {
+        tracing::warn!("Ignoring {}: {}", meta_agent::storage::OVERRIDES_FILE, e);
+        Default::default()
+    });
+
+    // Faction taxonomy: the embedded default plus any local additions
+    let mut taxonomy = match meta_agent::models::FactionTaxonomy::load(&config.data_dir) {
+        Ok(taxonomy) => taxonomy,
+        Err(e) => {
+            tracing::warn!("Ignoring local faction taxonomy: {}", e);
+            meta_agent::models::FactionTaxonomy::embedded().clone()
+        }
+    };
+    if let Err(e) = taxonomy.add_aliases(&overrides.aliases) {
+        tracing::warn!("Ignoring faction aliases in overrides: {}", e);
+    }
+    taxonomy.add_groups(&overrides.groups);
+    tracing::debug!(
+        "Faction taxonomy v{}: {} factions",
+        taxonomy.version,
+        taxonomy.factions.len()
+    );
+    taxonomy.install();
+    overrides.install();
+
+    match cli.command {
+        Commands::Sync {
+            once,
+            watch,
+            interval: interval_str,
+            from,
+            to,
+            source,
+            dry_run,
+            url: direct_url,
+            full,
+            upcoming,
+        } => {
+            // Parse date range
+            let date_from = from.map(|s| {
+                NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+                    .unwrap_or_else(|_| panic!("Invalid --from date (expected YYYY-MM-DD): {}", s))
+            });
+            let date_to = to.map(|s| {
+                NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+                    .unwrap_or_else(|_| panic!("Invalid --to date (expected YYYY-MM-DD): {}", s))
+            });
+
+            // Build source list: --source picks one source (enabled or not),
+            // otherwise every source enabled under [sources]. Upcoming
+            // events come from BCP only.
+            let registry = meta_agent::sync::source::registry();
+            let sources = match source.as_deref().or(upcoming.then_some("bcp")) {
+                Some(name) => match registry.build(name, &config.sources) {
+                    Some(source) => vec![source],
+                    None => {
+                        eprintln!(
+                            "Unknown source: {}. Use one of: {}.",
+                            name,
+                            registry.names().join(", ")
+                        );
+                        return Ok(());
+                    }
+                },
+                None => registry.enabled(&config.sources),
+            };
+            if sources.is_empty() {
+                eprintln!("No sources enabled. Enable one under [sources] or pass --source.");
+                return Ok(());
+            }
+
+            // Select backend
+            check_ollama_at_startup(&config.ai).await;
+            let backend: Arc<dyn AiBackend> = select_backend(&config);
+
+            // Storage config
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+
+            // Create fetcher with cache dir from storage config
+            let fetcher = Fetcher::new(config.fetcher_config(&storage, &sources))
+                .expect("Failed to create fetcher");
+
+            // Parse interval
+            let sync_interval =
+                meta_agent::parse_duration(&interval_str).unwrap_or(Duration::from_secs(6 * 3600));
+
+            let sync_config = SyncConfig {
                 sources,
                 interval: sync_interval,
                 date_from,
                 date_to,
                 dry_run,
+                full,
+                upcoming,
+                grace_days: config.epochs.grace_days,
                 storage,
             };
 
@@ -457,7 +1090,10 @@ async fn main() -> Result<()> {
                 return Ok(());
             }
 
-            let orchestrator = SyncOrchestrator::new(sync_config, fetcher, backend);
+            let mut orchestrator = SyncOrchestrator::new(sync_config, fetcher, backend);
+            if let Some(notifier) = Notifier::from_config(&config.notifications) {
+                orchestrator = orchestrator.with_post_sync_hook(notifier);
+            }
 
             if once {
                 tracing::info!("Running one-time sync...");
@@ -490,49 +1126,202 @@ async fn main() -> Result<()> {
                 eprintln!("Specify --once or --watch");
             }
         }
-        Commands::Serve { host, port, .. } => {
-            let storage = StorageConfig::new(std::path::PathBuf::from(&cli.data_dir));
-            let epoch_mapper = match read_significant_events(&storage) {
-                Ok(events) if !events.is_empty() => {
-                    tracing::info!(
-                        "Loaded {} significant events for epoch mapping",
-                        events.len()
-                    );
-                    EpochMapper::from_significant_events(&events)
-                }
-                _ => EpochMapper::new(),
-            };
-            let backend: Arc<dyn AiBackend> = select_backend();
-            let state = meta_agent::api::state::AppState {
-                storage: Arc::new(storage),
-                epoch_mapper: Arc::new(tokio::sync::RwLock::new(epoch_mapper)),
-                refresh_state: Arc::new(tokio::sync::RwLock::new(
-                    meta_agent::api::routes::refresh::RefreshState::default(),
-                )),
-                ai_backend: backend,
-                traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
-                    meta_agent::api::routes::traffic::TrafficStats::new(),
-                )),
+        Commands::Serve {
+            host,
+            port,
+            scheduler,
+            with_sync,
+            interval,
+            ..
+        } => {
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+            let host = host.unwrap_or_else(|| config.server.host.clone());
+            let port = port.unwrap_or(config.server.port);
+            let syncs = ActiveSyncs::default();
+            if !std::path::Path::new(meta_agent::dashboard::DEFAULT_DIR)
+                .join("index.html")
+                .exists()
+            {
+                tracing::warn!(
+                    "No dashboard in ./{}; run `meta-agent dashboard init` to write one",
+                    meta_agent::dashboard::DEFAULT_DIR
+                );
+            }
+            if scheduler || config.schedule.run_in_serve || with_sync {
+                check_ollama_at_startup(&config.ai).await;
+            }
+            if scheduler || config.schedule.run_in_serve {
+                let backend = select_backend(&config);
+                let scheduler = Scheduler::new(config.clone(), storage.clone(), backend)?
+                    .with_active_syncs(syncs.clone());
+                Arc::new(scheduler).spawn();
+            }
+            let background_sync = if with_sync {
+                let interval = meta_agent::parse_duration(&interval)
+                    .filter(|d| !d.is_zero())
+                    .ok_or_else(|| anyhow::anyhow!("Invalid --interval: {}", interval))?;
+                let sources = meta_agent::sync::source::registry().enabled(&config.sources);
+                let fetcher_config = config.fetcher_config(&storage, &sources);
+                let sync_config = SyncConfig {
+                    sources,
+                    interval,
+                    grace_days: config.epochs.grace_days,
+                    storage: storage.clone(),
+                    ..Default::default()
+                };
+                Some((sync_config, fetcher_config))
+            } else {
+                None
             };
-            let app = meta_agent::api::build_router(state);
-            let addr = format!("{}:{}", host, port);
-            let listener = tokio::net::TcpListener::bind(&addr).await?;
-            tracing::info!("Dashboard: http://{}", addr);
-            axum::serve(
-                listener,
-                app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+            serve(
+                storage,
+                &host,
+                port,
+                config.server.default_epoch.clone(),
+                &config,
+                background_sync,
+                syncs,
             )
             .await?;
         }
+        Commands::Demo {
+            host,
+            port,
+            force,
+            seed_only,
+        } => {
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+            let summary = match meta_agent::demo::seed_demo_data(&storage, force) {
+                Ok(summary) => summary,
+                Err(e) => {
+                    eprintln!("Demo seeding failed: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            println!("Seeded demo data into {}", config.data_dir.display());
+            println!("  Epochs:      {}", summary.epochs.join(", "));
+            println!("  Events:      {}", summary.events);
+            println!("  Placements:  {}", summary.placements);
+            println!("  Pairings:    {}", summary.pairings);
+            println!("  Army lists:  {}", summary.army_lists);
+            if !seed_only {
+                let host = host.unwrap_or_else(|| config.server.host.clone());
+                let port = port.unwrap_or(config.server.port);
+                serve(
+                    storage,
+                    &host,
+                    port,
+                    None,
+                    &config,
+                    None,
+                    ActiveSyncs::default(),
+                )
+                .await?;
+            }
+        }
         Commands::BuildParquet { .. } => {
             tracing::info!("Rebuilding Parquet files...");
             // TODO: Implement build-parquet command
             tracing::warn!("BuildParquet command not yet implemented");
         }
-        Commands::Derive { .. } => {
+        Commands::Derive { epoch, run, force } => {
+            use meta_agent::calculate::derive::{run_derivations, Derivation, DeriveOutcome};
+
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+
+            let derivations = match Derivation::parse_list(run.as_deref()) {
+                Ok(d) => d,
+                Err(e) => {
+                    eprintln!(
+                        "{}. Available: faction_stats, unit_frequency, matchups, tier_list, \
+                         placement_curves, archetypes, composite_tiers, combos, unit_performance",
+                        e
+                    );
+                    return Ok(());
+                }
+            };
+
+            // Resolve epochs: "all" = every epoch directory, default = current epoch
+            let epoch_ids: Vec<String> = match epoch.as_deref() {
+                Some("all") => meta_agent::storage::jsonl::list_epochs(&storage)?,
+                Some(e) => vec![e.to_string()],
+                None => vec![current_epoch_id(&storage)],
+            };
+
             tracing::info!("Computing derived analytics...");
-            // TODO: Implement derive command
-            tracing::warn!("Derive command not yet implemented");
+            let mut computed = 0u32;
+            let mut skipped = 0u32;
+            for epoch_id in &epoch_ids {
+                println!("Epoch {}:", epoch_id);
+                for (derivation, outcome) in
+                    run_derivations(&storage, epoch_id, &derivations, force)?
+                {
+                    match outcome {
+                        DeriveOutcome::Computed { path } => {
+                            println!("  {:<16} computed -> {}", derivation.name(), path.display());
+                            computed += 1;
+                        }
+                        DeriveOutcome::Skipped => {
+                            println!(
+                                "  {:<16} up to date (use --force to recompute)",
+                                derivation.name()
+                            );
+                            skipped += 1;
+                        }
+                    }
+                }
+            }
+
+            // Ratings span every epoch, so they're refreshed whenever all
+            // derivations run
+            if run.is_none() {
+                use meta_agent::calculate::ratings::{
+                    compute_ratings_from_storage, write_ratings, RatingConfig,
+                };
+                let table = compute_ratings_from_storage(
+                    &storage,
+                    RatingConfig::default(),
+                    meta_agent::calculate::derive::DERIVED_GAME_SIZE,
+                )?;
+                let path = write_ratings(&storage, &table)?;
+                println!(
+                    "Ratings:          {} players from {} games -> {}",
+                    table.players.len(),
+                    table.games_rated,
+                    path.display()
+                );
+
+                use meta_agent::calculate::derive::DERIVED_GAME_SIZE;
+                use meta_agent::calculate::shifts::{
+                    compute_shifts_from_storage, queue_for_review, write_shifts, ShiftConfig,
+                };
+                let report = compute_shifts_from_storage(
+                    &storage,
+                    ShiftConfig::default(),
+                    DERIVED_GAME_SIZE,
+                )?;
+                let path = write_shifts(&storage, &report)?;
+                let queued = queue_for_review(&storage, &report)?;
+                println!(
+                    "Shifts:           {} candidates ({} new for review, {} explained by \
+                     balance passes) -> {}",
+                    report.candidates.len(),
+                    queued,
+                    report.explained_by_boundary,
+                    path.display()
+                );
+            }
+
+            println!("\n=== Derive Results ===");
+            println!("Epochs:     {}", epoch_ids.len());
+            println!("Computed:   {}", computed);
+            println!("Skipped:    {}", skipped);
         }
         Commands::Review { action } => {
             match action {
@@ -549,58 +1338,290 @@ async fn main() -> Result<()> {
             // TODO: Implement review commands
             tracing::warn!("Review commands not yet implemented");
         }
-        Commands::NormalizeLists {
-            only_empty,
-            dry_run,
-            limit,
-            epoch,
-            faction,
-        } => {
-            let storage = StorageConfig::new(std::path::PathBuf::from(&cli.data_dir));
+        Commands::Issues { action } => {
+            use meta_agent::sync::issues::read_issues;
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+            let issues = read_issues(&storage)?;
+            match action {
+                IssuesAction::List { all, source } => {
+                    let shown: Vec<_> = issues
+                        .iter()
+                        .filter(|i| all || i.is_open())
+                        .filter(|i| source.as_deref().is_none_or(|s| i.source == s))
+                        .collect();
+                    if shown.is_empty() {
+                        println!("No ingest issues");
+                    }
+                    for i in shown {
+                        println!(
+                            "{}  {:<10} {:<9} {:<12} x{:<3} {}{}",
+                            i.id,
+                            i.source,
+                            i.stage,
+                            i.kind,
+                            i.attempts,
+                            i.source_url,
+                            if i.is_open() { "" } else { "  (resolved)" }
+                        );
+                        println!(
+                            "    {} (last seen {}{})",
+                            i.error,
+                            i.last_seen.format("%Y-%m-%d %H:%M UTC"),
+                            if i.retryable { "" } else { ", not retryable" }
+                        );
+                        if let Some(raw) = &i.raw_path {
+                            println!("    raw: {}", raw);
+                        }
+                    }
+                }
+                IssuesAction::Retry { ids, all } => {
+                    let selected: Vec<_> = if all {
+                        issues
+                            .into_iter()
+                            .filter(|i| i.is_open() && i.retryable)
+                            .collect()
+                    } else {
+                        let mut selected = Vec::new();
+                        for id in &ids {
+                            match issues.iter().find(|i| i.id.as_str() == id) {
+                                Some(issue) => selected.push(issue.clone()),
+                                None => eprintln!("Unknown issue: {}", id),
+                            }
+                        }
+                        selected
+                    };
+                    if selected.is_empty() {
+                        println!("Nothing to retry. Pass issue IDs or --all.");
+                        return Ok(());
+                    }
 
-            // Resolve epoch: use provided, or find the current one
-            let epoch_id = epoch.unwrap_or_else(|| {
-                let sig = read_significant_events(&storage).unwrap_or_default();
-                if sig.is_empty() {
-                    "current".to_string()
-                } else {
-                    let mapper = EpochMapper::from_significant_events(&sig);
-                    mapper
-                        .current_epoch()
-                        .map(|e| e.id.as_str().to_string())
-                        .unwrap_or_else(|| "current".to_string())
+                    // Retries reuse each issue's source as configured, even
+                    // if it is disabled for scheduled syncs
+                    let registry = meta_agent::sync::source::registry();
+                    let mut sources: Vec<Arc<dyn Source>> = Vec::new();
+                    for issue in &selected {
+                        if sources.iter().any(|s| s.name() == issue.source) {
+                            continue;
+                        }
+                        if let Some(source) = registry.build(&issue.source, &config.sources) {
+                            sources.push(source);
+                        }
+                    }
+                    let fetcher = Fetcher::new(config.fetcher_config(&storage, &sources))
+                        .expect("Failed to create fetcher");
+                    let sync_config = SyncConfig {
+                        sources,
+                        interval: Duration::from_secs(3600),
+                        date_from: None,
+                        date_to: None,
+                        dry_run: false,
+                        full: false,
+                        upcoming: false,
+                        grace_days: config.epochs.grace_days,
+                        storage,
+                    };
+                    let orchestrator =
+                        SyncOrchestrator::new(sync_config, fetcher, select_backend(&config));
+
+                    let mut fixed = 0;
+                    for issue in &selected {
+                        match orchestrator.retry_issue(issue).await {
+                            Ok((events, placements, lists)) => {
+                                fixed += 1;
+                                println!(
+                                    "{} resolved: {} events, {} placements, {} lists",
+                                    issue.id, events, placements, lists
+                                );
+                            }
+                            Err(e) => println!("{} failed again: {}", issue.id, e),
+                        }
+                    }
+                    println!("\n{}/{} issues resolved", fixed, selected.len());
                 }
+            }
+        }
+        Commands::Reprocess {
+            url,
+            date,
+            all_failed,
+            older_than_version,
+            estimate,
+        } => {
+            use meta_agent::models::RetryTarget;
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+            let date = date.map(|s| {
+                NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+                    .unwrap_or_else(|_| panic!("Invalid --date (expected YYYY-MM-DD): {}", s))
             });
+            let issues = meta_agent::sync::issues::read_issues(&storage)?;
+
+            // (url, date, raw payload) of each article to reprocess
+            let mut articles: Vec<(String, Option<NaiveDate>, Option<String>)> = Vec::new();
+            // Stored (events, lists) of each stale article, for --estimate
+            let mut stored_counts = std::collections::HashMap::new();
+            if let Some(min_version) = older_than_version {
+                for stale in meta_agent::sync::reprocess::stale_articles(&storage, min_version)? {
+                    println!(
+                        "{} ({}): {} events, {} placements, {} lists older than v{}",
+                        stale.url,
+                        stale.date,
+                        stale.events,
+                        stale.placements,
+                        stale.lists,
+                        min_version
+                    );
+                    stored_counts
+                        .insert(stale.url.clone(), (stale.events as u32, stale.lists as u32));
+                    articles.push((stale.url, Some(stale.date), None));
+                }
+                if !articles.is_empty() && !estimate {
+                    let snapshot =
+                        meta_agent::storage::backup::create_snapshot(&storage, "pre-reprocess")?;
+                    println!("Snapshotted current data as {}", snapshot.id);
+                }
+            }
+            for issue in issues.iter().filter(|i| i.is_open()) {
+                if let RetryTarget::Article { url: target, date } = &issue.target {
+                    if all_failed || url.as_deref() == Some(target.as_str()) {
+                        articles.push((target.clone(), Some(*date), issue.raw_path.clone()));
+                    }
+                }
+            }
+            let mut seen = std::collections::HashSet::new();
+            articles.retain(|a| seen.insert(a.0.clone()));
+            if let Some(url) = url {
+                if articles.is_empty() {
+                    articles.push((url, None, None));
+                }
+            }
+            if articles.is_empty() {
+                println!("No articles to reprocess");
+                return Ok(());
+            }
+
+            let sources: Vec<Arc<dyn Source>> = config
+                .sources
+                .by_name("goonhammer")
+                .map(SyncSource::into_source)
+                .into_iter()
+                .collect();
+            let fetcher = Fetcher::new(config.fetcher_config(&storage, &sources))
+                .expect("Failed to create fetcher");
+            let sync_config = SyncConfig {
+                sources,
+                interval: Duration::from_secs(3600),
+                date_from: None,
+                date_to: None,
+                dry_run: false,
+                full: false,
+                upcoming: false,
+                grace_days: config.epochs.grace_days,
+                storage,
+            };
+            let orchestrator = SyncOrchestrator::new(sync_config, fetcher, select_backend(&config));
+
+            if estimate {
+                print_reprocess_estimate(
+                    &orchestrator,
+                    &articles,
+                    date,
+                    &stored_counts,
+                    &config.ai,
+                )
+                .await;
+                return Ok(());
+            }
+
+            let (mut events, mut placements, mut lists) = (0, 0, 0);
+            for (article_url, issue_date, raw_path) in &articles {
+                let parsed = match url::Url::parse(article_url) {
+                    Ok(parsed) => parsed,
+                    Err(e) => {
+                        eprintln!("Invalid URL {}: {}", article_url, e);
+                        continue;
+                    }
+                };
+                let article_date = date
+                    .or(*issue_date)
+                    .unwrap_or_else(|| chrono::Utc::now().date_naive());
+                match orchestrator
+                    .reprocess_article(
+                        &parsed,
+                        article_date,
+                        raw_path.as_deref().map(std::path::Path::new),
+                        older_than_version.is_some(),
+                    )
+                    .await
+                {
+                    Ok((e, p, l)) => {
+                        println!(
+                            "{}: {} new events, {} placements, {} lists",
+                            article_url, e, p, l
+                        );
+                        events += e;
+                        placements += p;
+                        lists += l;
+                    }
+                    Err(e) => println!("{}: {}", article_url, e),
+                }
+            }
+            println!("\n=== Reprocess Results ===");
+            println!("Articles:         {}", articles.len());
+            println!("Events added:     {}", events);
+            println!("Placements added: {}", placements);
+            println!("Lists added:      {}", lists);
+        }
+        Commands::NormalizeLists {
+            only_empty,
+            dry_run,
+            limit,
+            epoch,
+            faction,
+            estimate,
+            concurrency,
+            batch_size,
+            restart,
+        } => {
+            use meta_agent::agents::list_normalizer::NormalizeCheckpoint;
+
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+
+            // Resolve epoch: use provided, or find the current one
+            let epoch_id = epoch.unwrap_or_else(|| current_epoch_id(&storage));
             tracing::info!("Normalizing lists in epoch: {}", epoch_id);
 
             // Read all army lists
             let reader =
                 JsonlReader::<ArmyList>::for_entity(&storage, EntityType::ArmyList, &epoch_id);
             let lists = reader.read_all().expect("Failed to read army lists");
-            let mut lists = dedup_by_id(lists, |l| l.id.as_str());
+            let mut lists = merge_by_id(lists, |l| l.id.as_str());
+            // Normalized lists are validated against their event's game size
+            let events: Vec<meta_agent::models::Event> =
+                JsonlReader::for_entity(&storage, EntityType::Event, &epoch_id)
+                    .read_all()
+                    .unwrap_or_default();
 
             let total = lists.len();
             tracing::info!("Loaded {} army lists", total);
 
-            // Back up the file
+            // Writing rotates the current file to the newest backup
             let src_path = storage
                 .normalized_dir()
                 .join(&epoch_id)
                 .join("army_lists.jsonl");
-            let bak_path = src_path.with_extension("jsonl.bak");
-            if src_path.exists() && !dry_run {
-                std::fs::copy(&src_path, &bak_path).expect("Failed to create backup");
-                tracing::info!("Backed up to {:?}", bak_path);
-            }
-
-            // Select backend
-            let backend: Arc<dyn AiBackend> = select_backend();
-            let agent = ListNormalizerAgent::new(backend);
+            let bak_path = (storage.backup_generations > 0)
+                .then(|| meta_agent::storage::backup_path(&src_path, 1));
 
             // Normalize the faction filter for comparison
             let faction_filter = faction
                 .as_deref()
-                .map(meta_agent::api::routes::events::normalize_faction_name);
+                .map(meta_agent::models::normalize_faction_name);
 
             // Determine which lists to process
             let indices: Vec<usize> = lists
@@ -608,7 +1629,7 @@ async fn main() -> Result<()> {
                 .enumerate()
                 .filter(|(_, l)| !only_empty || l.units.is_empty())
                 .filter(|(_, l)| match &faction_filter {
-                    Some(ff) => meta_agent::api::routes::events::normalize_faction_name(&l.faction)
+                    Some(ff) => meta_agent::models::normalize_faction_name(&l.faction)
                         .eq_ignore_ascii_case(ff),
                     None => true,
                 })
@@ -616,6 +1637,36 @@ async fn main() -> Result<()> {
                 .take(limit.unwrap_or(usize::MAX))
                 .collect();
 
+            // Continue an interrupted run: lists it wrote are skipped
+            let mut checkpoint = if dry_run || restart {
+                NormalizeCheckpoint {
+                    epoch_id: epoch_id.clone(),
+                    ..Default::default()
+                }
+            } else {
+                NormalizeCheckpoint::load(&storage, &epoch_id)?
+            };
+            let resumed = checkpoint.done.len();
+            let indices: Vec<usize> = indices
+                .into_iter()
+                .filter(|&i| !checkpoint.done.contains(lists[i].id.as_str()))
+                .collect();
+            if resumed > 0 {
+                tracing::info!(
+                    "Resuming: {} lists done by an interrupted run (--restart to redo them)",
+                    resumed
+                );
+            }
+
+            if estimate {
+                print_normalize_estimate(&lists, &indices, &config.ai);
+                return Ok(());
+            }
+
+            // Select backend
+            let backend: Arc<dyn AiBackend> = select_backend(&config);
+            let agent = Arc::new(ListNormalizerAgent::new(backend));
+
             let to_process = indices.len();
             tracing::info!(
                 "Will normalize {} of {} lists{}",
@@ -626,124 +1677,489 @@ async fn main() -> Result<()> {
 
             let mut normalized_count = 0u32;
             let mut error_count = 0u32;
+            let mut skipped_count = 0u32;
+            let mut progress = Progress::new(
+                to_process as u64,
+                "lists",
+                ProgressMode::detect(cli.output == OutputFormat::Json),
+            );
 
-            for (progress, &idx) in indices.iter().enumerate() {
-                let list = &lists[idx];
+            // Up to `concurrency` lists in flight, request starts 500ms
+            // apart across all of them; agents back off on rate limits
+            let concurrency = concurrency.max(1);
+            let batch_size = batch_size.max(1);
+            let next_start = Arc::new(tokio::sync::Mutex::new(tokio::time::Instant::now()));
+            let mut tasks = tokio::task::JoinSet::new();
+            let mut pending = indices.iter().copied();
+            let mut completed = 0usize;
+            let mut unwritten = 0usize;
+            let mut written_once = false;
+
+            // Rewrite the epoch file and checkpoint. Only the first write of
+            // the run rotates backups, so the backup is the pre-run data.
+            let mut write_batch = |lists: &[ArmyList],
+                                   checkpoint: &NormalizeCheckpoint|
+             -> Result<()> {
+                let writer =
+                    JsonlWriter::<ArmyList>::for_entity(&storage, EntityType::ArmyList, &epoch_id);
+                let writer = if written_once {
+                    writer.with_backups(0)
+                } else {
+                    writer
+                };
+                writer.write_all(lists)?;
+                checkpoint.save(&storage)?;
+                written_once = true;
+                Ok(())
+            };
 
-                if list.raw_text.trim().is_empty() {
-                    tracing::warn!(
-                        "[{}/{}] Skipping list with empty raw_text",
-                        progress + 1,
-                        to_process
-                    );
-                    continue;
+            loop {
+                while tasks.len() < concurrency {
+                    let Some(idx) = pending.next() else {
+                        break;
+                    };
+                    let list = &lists[idx];
+                    if list.raw_text.trim().is_empty() {
+                        completed += 1;
+                        progress.println(format!(
+                            "[{}/{}] Skipping list with empty raw_text",
+                            completed, to_process
+                        ));
+                        skipped_count += 1;
+                        checkpoint.done.insert(list.id.as_str().to_string());
+                        progress.inc();
+                        continue;
+                    }
+                    progress.set_message(list.faction.clone());
+                    progress.record_ai_call();
+
+                    let input = ListNormalizerInput::for_list(list, format!("list-{}", idx));
+                    let agent = agent.clone();
+                    let next_start = next_start.clone();
+                    tasks.spawn(async move {
+                        {
+                            let mut next = next_start.lock().await;
+                            tokio::time::sleep_until(*next).await;
+                            *next = tokio::time::Instant::now() + Duration::from_millis(500);
+                        }
+                        (idx, execute_with_retry(agent.as_ref(), input).await)
+                    });
                 }
 
-                let input = ListNormalizerInput {
-                    raw_text: list.raw_text.clone(),
-                    faction_hint: if list.faction.is_empty()
-                        || list.faction.contains("presents")
-                        || list.faction.contains("GT")
-                    {
-                        None
-                    } else {
-                        Some(list.faction.clone())
-                    },
-                    player_name: format!("list-{}", idx),
+                let Some(joined) = tasks.join_next().await else {
+                    break;
                 };
-
-                match agent.execute(input).await {
+                let (idx, result) = joined?;
+                completed += 1;
+                match result {
                     Ok(output) => {
                         let result = output.list;
                         let norm = &result.data;
 
-                        println!(
+                        progress.println(format!(
                             "[{}/{}] Normalized: {} - {} ({} units, {}pts)",
-                            progress + 1,
+                            completed,
                             to_process,
                             norm.faction,
                             norm.detachment.as_deref().unwrap_or("(none)"),
                             norm.units.len(),
                             norm.total_points,
-                        );
+                        ));
 
                         if !dry_run {
-                            let l = &mut lists[idx];
-                            l.faction = norm.faction.clone();
-                            l.subfaction = norm.subfaction.clone();
-                            l.detachment = norm.detachment.clone();
-                            l.total_points = norm.total_points;
-                            l.units = norm.units.clone();
-                            l.extraction_confidence = result.confidence;
-
-                            // Regenerate ID based on new data
-                            let mut unit_names: Vec<_> =
-                                l.units.iter().map(|u| u.name.as_str()).collect();
-                            unit_names.sort();
-                            let units_str = unit_names.join(",");
-                            l.id = meta_agent::models::EntityId::generate(&[
-                                &l.faction,
-                                l.detachment.as_deref().unwrap_or(""),
-                                &units_str,
-                                &l.total_points.to_string(),
-                            ]);
+                            apply_normalized(&mut lists[idx], &result);
+                            meta_agent::calculate::validation::flag_lists(
+                                &events,
+                                std::slice::from_mut(&mut lists[idx]),
+                            );
+                            checkpoint.done.insert(lists[idx].id.as_str().to_string());
+                            unwritten += 1;
                         }
 
                         normalized_count += 1;
                     }
                     Err(e) => {
-                        tracing::error!(
+                        progress.println(format!(
                             "[{}/{}] Failed to normalize: {}",
-                            progress + 1,
-                            to_process,
-                            e
-                        );
+                            completed, to_process, e
+                        ));
                         error_count += 1;
                     }
                 }
+                progress.inc();
 
-                // Rate limiting: 500ms delay between API calls
-                tokio::time::sleep(Duration::from_millis(500)).await;
+                if unwritten >= batch_size {
+                    write_batch(&lists, &checkpoint)?;
+                    unwritten = 0;
+                }
             }
+            let timing = progress.finish();
 
-            // Write results
+            // Write the last batch; a finished run needs no checkpoint
             if !dry_run {
-                let writer =
-                    JsonlWriter::<ArmyList>::for_entity(&storage, EntityType::ArmyList, &epoch_id);
-                writer
-                    .write_all(&lists)
-                    .expect("Failed to write normalized lists");
+                if unwritten > 0 {
+                    write_batch(&lists, &checkpoint)?;
+                }
+                checkpoint.clear(&storage)?;
+            }
+
+            if cli.output == OutputFormat::Json {
+                let summary = serde_json::json!({
+                    "epoch": epoch_id,
+                    "total": total,
+                    "resumed": resumed,
+                    "processed": to_process,
+                    "normalized": normalized_count,
+                    "skipped": skipped_count,
+                    "errors": error_count,
+                    "dry_run": dry_run,
+                    "backup": bak_path.as_ref().filter(|_| !dry_run).map(|p| p.display().to_string()),
+                    "timing": timing,
+                });
+                println!("{}", serde_json::to_string_pretty(&summary)?);
+                return Ok(());
             }
 
             println!("\n=== Normalization Results ===");
             println!("Total lists:      {}", total);
+            if resumed > 0 {
+                println!("Resumed after:    {}", resumed);
+            }
             println!("Processed:        {}", to_process);
             println!("Normalized:       {}", normalized_count);
+            println!("Skipped:          {}", skipped_count);
             println!("Errors:           {}", error_count);
-            if !dry_run {
+            println!("Elapsed:          {}", timing);
+            if dry_run {
+                println!("(dry run - no data written to disk)");
+            } else if let Some(bak_path) = &bak_path {
                 println!("Backed up to:     {:?}", bak_path);
+            }
+        }
+        Commands::DriftReport {
+            epoch,
+            baseline,
+            candidate,
+            sample,
+        } => {
+            use meta_agent::agents::drift::{compare, samples_by_model, ExtractionSample};
+            use meta_agent::agents::estimate::sample_indices;
+
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+            let epoch_id = epoch.unwrap_or_else(|| current_epoch_id(&storage));
+            let json_output = cli.output == OutputFormat::Json;
+
+            let lists: Vec<ArmyList> =
+                JsonlReader::for_entity(&storage, EntityType::ArmyList, &epoch_id)
+                    .read_all()
+                    .unwrap_or_default();
+            let lists = merge_by_id(lists, |l| l.id.as_str());
+            let groups = samples_by_model(&lists);
+
+            let Some(baseline) = baseline.or_else(|| groups.first().map(|(m, _)| m.clone())) else {
+                println!(
+                    "No lists in {} record an extraction model yet; run normalize-lists first.",
+                    epoch_id
+                );
+                return Ok(());
+            };
+            let baseline_samples = groups
+                .iter()
+                .find(|(m, _)| *m == baseline)
+                .map(|(_, s)| s.clone())
+                .unwrap_or_default();
+            if baseline_samples.is_empty() {
+                anyhow::bail!("No lists in {} were extracted by {}", epoch_id, baseline);
+            }
+
+            let reports = match candidate {
+                Some(candidate) => {
+                    // Re-extract a sample of the baseline's raw lists
+                    let baseline_lists: Vec<&ArmyList> = lists
+                        .iter()
+                        .filter(|l| l.extracted_by.as_deref() == Some(baseline.as_str()))
+                        .collect();
+                    let picked: Vec<&ArmyList> = sample_indices(baseline_lists.len(), sample)
+                        .into_iter()
+                        .map(|i| baseline_lists[i])
+                        .collect();
+
+                    let ai = AiConfig {
+                        model: candidate.clone(),
+                        ..config.ai.clone()
+                    };
+                    let agent = ListNormalizerAgent::new(select_backend(&AppConfig {
+                        ai,
+                        ..config.clone()
+                    }));
+                    let mut progress = Progress::new(
+                        picked.len() as u64,
+                        "lists",
+                        ProgressMode::detect(json_output),
+                    );
+
+                    let mut base = Vec::new();
+                    let mut cand = Vec::new();
+                    for (idx, list) in picked.iter().enumerate() {
+                        progress.record_ai_call();
+                        let input = ListNormalizerInput::for_list(list, format!("drift-{}", idx));
+                        match agent.execute(input).await {
+                            Ok(output) => {
+                                base.extend(ExtractionSample::from_list(list));
+                                cand.push(ExtractionSample::from_output(
+                                    &list.raw_text,
+                                    &candidate,
+                                    &output.list,
+                                ));
+                            }
+                            Err(e) => progress.println(format!(
+                                "[{}/{}] {} failed: {}",
+                                idx + 1,
+                                picked.len(),
+                                candidate,
+                                e
+                            )),
+                        }
+                        progress.inc();
+                        tokio::time::sleep(Duration::from_millis(500)).await;
+                    }
+                    let timing = progress.finish();
+                    if !json_output {
+                        println!("Re-extracted {} lists in {}", cand.len(), timing);
+                    }
+                    vec![compare(&baseline, &base, &candidate, &cand)]
+                }
+                None => groups
+                    .iter()
+                    .filter(|(m, _)| *m != baseline)
+                    .map(|(m, samples)| compare(&baseline, &baseline_samples, m, samples))
+                    .collect(),
+            };
+
+            if json_output {
+                println!("{}", serde_json::to_string_pretty(&reports)?);
+            } else if reports.is_empty() {
+                println!(
+                    "All tracked lists in {} were extracted by {}; pass --candidate <model> \
+                     to re-extract a sample with another model.",
+                    epoch_id, baseline
+                );
             } else {
-                println!("(dry run - no data written to disk)");
+                for r in &reports {
+                    print_drift_report(r);
+                }
+            }
+
+            let regressed = reports.iter().filter(|r| r.has_regressions()).count();
+            if regressed > 0 {
+                anyhow::bail!(
+                    "{} model comparison(s) show extraction regressions",
+                    regressed
+                );
             }
         }
         Commands::Debug { action } => {
             match action {
-                DebugAction::ParseFixture { path } => {
-                    tracing::info!("Parsing fixture: {}", path);
+                DebugAction::ParseFixture { path, json } => {
+                    use meta_agent::sync::fixture::parse_fixture;
+
+                    let content = std::fs::read_to_string(&path)?;
+                    let parsed = match parse_fixture(&content, &path) {
+                        Ok(parsed) => parsed,
+                        Err(e) => {
+                            eprintln!("Failed to parse {}: {}", path, e);
+                            std::process::exit(1);
+                        }
+                    };
+
+                    if json {
+                        println!("{}", serde_json::to_string_pretty(&parsed)?);
+                    } else {
+                        println!("=== Fixture: {} ({}) ===", path, parsed.kind);
+                        if !parsed.articles.is_empty() {
+                            println!("\nArticles ({}):", parsed.articles.len());
+                            println!("  {:<12} Title", "Date");
+                            for article in &parsed.articles {
+                                let date = article.date.map(|d| d.to_string()).unwrap_or_default();
+                                println!("  {:<12} {}", date, article.title);
+                            }
+                        }
+                        if !parsed.significant_events.is_empty() {
+                            println!(
+                                "\nSignificant events ({}):",
+                                parsed.significant_events.len()
+                            );
+                            for sig in &parsed.significant_events {
+                                println!("  {} [{}] {}", sig.date, sig.event_type, sig.title);
+                                if let Some(ref pdf) = sig.pdf_url {
+                                    println!("    PDF: {}", pdf);
+                                }
+                            }
+                        }
+                        if !parsed.events.is_empty() {
+                            println!("\nEvents ({}):", parsed.events.len());
+                            println!(
+                                "  {:<40} {:<12} {:>7} {:>6}  Location",
+                                "Name", "Date", "Players", "Rounds"
+                            );
+                            for event in &parsed.events {
+                                println!(
+                                    "  {:<40} {:<12} {:>7} {:>6}  {}",
+                                    event.name,
+                                    event.date.to_string(),
+                                    event
+                                        .player_count
+                                        .map(|c| c.to_string())
+                                        .unwrap_or_default(),
+                                    event.round_count.map(|c| c.to_string()).unwrap_or_default(),
+                                    event.location.as_deref().unwrap_or("")
+                                );
+                            }
+                        }
+                        if !parsed.placements.is_empty() {
+                            println!("\nPlacements ({}):", parsed.placements.len());
+                            println!(
+                                "  {:>4}  {:<24} {:<24} {:<28} {:<7} List",
+                                "Rank", "Player", "Faction", "Detachment", "Record"
+                            );
+                            for p in &parsed.placements {
+                                let record = p
+                                    .record
+                                    .as_ref()
+                                    .map(|r| format!("{}-{}-{}", r.wins, r.losses, r.draws))
+                                    .unwrap_or_default();
+                                println!(
+                                    "  {:>4}  {:<24} {:<24} {:<28} {:<7} {}",
+                                    p.rank,
+                                    p.player_name,
+                                    p.faction,
+                                    p.detachment.as_deref().unwrap_or(""),
+                                    record,
+                                    if p.list_id.is_some() { "yes" } else { "" }
+                                );
+                            }
+                        }
+                        if !parsed.army_lists.is_empty() {
+                            println!("\nArmy lists ({}):", parsed.army_lists.len());
+                            for list in &parsed.army_lists {
+                                println!(
+                                    "  {} - {} ({}) {}pts, {} units parsed",
+                                    list.player_name.as_deref().unwrap_or("Unknown"),
+                                    list.faction,
+                                    list.detachment.as_deref().unwrap_or("no detachment"),
+                                    list.total_points,
+                                    list.units.len()
+                                );
+                            }
+                        }
+                        if !parsed.pairings.is_empty() {
+                            println!("\nPairings: {}", parsed.pairings.len());
+                        }
+                    }
+                }
+                DebugAction::ValidateStorage { json, strict } => {
+                    use meta_agent::storage::validate::{validate_storage, Severity};
+
+                    let storage = config
+                        .storage()
+                        .with_source_partitioning(cli.partition_by_source);
+                    let report = validate_storage(&storage)?;
+
+                    if json {
+                        println!("{}", serde_json::to_string_pretty(&report)?);
+                    } else {
+                        println!("=== Storage Validation ===\n");
+                        for epoch in &report.epochs {
+                            println!(
+                                "  {}: {} events, {} placements, {} lists, {} pairings",
+                                epoch.epoch_id,
+                                epoch.events,
+                                epoch.placements,
+                                epoch.army_lists,
+                                epoch.pairings
+                            );
+                        }
+                        if !report.issues.is_empty() {
+                            println!();
+                        }
+                        for issue in &report.issues {
+                            let level = match issue.severity {
+                                Severity::Error => "ERROR",
+                                Severity::Warning => "WARN ",
+                            };
+                            let target = issue
+                                .location
+                                .clone()
+                                .or_else(|| issue.id.clone())
+                                .unwrap_or_default();
+                            println!(
+                                "  {} [{}] {} {} {}: {}",
+                                level,
+                                issue.epoch_id,
+                                issue.entity,
+                                serde_json::to_string(&issue.kind)?.trim_matches('"'),
+                                target,
+                                issue.message
+                            );
+                        }
+                        println!("\nErrors: {}  Warnings: {}", report.errors, report.warnings);
+                    }
+
+                    let code = report.exit_code(strict);
+                    if code != 0 {
+                        std::process::exit(code);
+                    }
                 }
-                DebugAction::ValidateStorage => {
-                    tracing::info!("Validating storage...");
+                DebugAction::AiCheck { pull, json } => {
+                    if config.ai.backend != "ollama" {
+                        println!(
+                            "[ai] backend is {}; use `meta-agent ai doctor` to check it",
+                            config.ai.backend
+                        );
+                        return Ok(());
+                    }
+                    let backend = OllamaBackend::new(
+                        config.ai.base_url.clone(),
+                        config.ai.model.clone(),
+                        config.ai.timeout_seconds,
+                    );
+                    let bar = (!json).then(pull_progress_bar);
+                    let diagnostics = backend
+                        .diagnose(pull || config.ai.auto_pull, |p| {
+                            if let Some(ref bar) = bar {
+                                update_pull_progress(bar, p);
+                            }
+                        })
+                        .await;
+                    if let Some(bar) = bar {
+                        bar.finish_and_clear();
+                    }
+
+                    if json {
+                        println!("{}", serde_json::to_string_pretty(&diagnostics)?);
+                    } else {
+                        println!("=== AI check ===");
+                        for line in diagnostics.report() {
+                            println!("  {}", line);
+                        }
+                    }
+                    if !diagnostics.is_healthy() {
+                        std::process::exit(1);
+                    }
                 }
                 DebugAction::Epochs => {
-                    let storage = StorageConfig::new(std::path::PathBuf::from(&cli.data_dir));
-                    let events = read_significant_events(&storage).unwrap_or_default();
-                    if events.is_empty() {
+                    let storage = config
+                        .storage()
+                        .with_source_partitioning(cli.partition_by_source);
+                    let mapper = cached_epoch_mapper(&storage);
+                    if mapper.all_epochs().is_empty() {
                         println!("No significant events registered.");
                         println!(
                             "Use `add-balance-pass` or `discover-balance-passes` to register epoch boundaries."
                         );
                     } else {
-                        let mapper = EpochMapper::from_significant_events(&events);
                         println!(
                             "=== Epoch Timeline ({} epochs) ===\n",
                             mapper.all_epochs().len()
@@ -753,7 +2169,13 @@ async fn main() -> Result<()> {
                                 .end_date
                                 .map(|d| d.to_string())
                                 .unwrap_or_else(|| "now".to_string());
-                            let current = if epoch.is_current { " [CURRENT]" } else { "" };
+                            let current = if epoch.is_current {
+                                " [CURRENT]"
+                            } else if epoch.is_provisional {
+                                " [PROVISIONAL]"
+                            } else {
+                                ""
+                            };
                             println!(
                                 "  {} — {} to {}{}",
                                 epoch.name, epoch.start_date, end, current
@@ -763,34 +2185,31 @@ async fn main() -> Result<()> {
                     }
                 }
                 DebugAction::CheckLists { epoch } => {
-                    use meta_agent::api::routes::events::{
-                        faction_match_score, normalize_faction_name,
-                    };
+                    use meta_agent::api::routes::events::faction_match_score;
+                    use meta_agent::models::normalize_faction_name;
 
-                    let storage = StorageConfig::new(std::path::PathBuf::from(&cli.data_dir));
-                    let sig_events = read_significant_events(&storage).unwrap_or_default();
-                    let mapper = EpochMapper::from_significant_events(&sig_events);
-                    let epoch_id = epoch
-                        .or_else(|| mapper.current_epoch().map(|e| e.id.as_str().to_string()))
-                        .unwrap_or_else(|| "current".to_string());
+                    let storage = config
+                        .storage()
+                        .with_source_partitioning(cli.partition_by_source);
+                    let epoch_id = epoch.unwrap_or_else(|| current_epoch_id(&storage));
 
                     let events: Vec<meta_agent::models::Event> =
                         JsonlReader::for_entity(&storage, EntityType::Event, &epoch_id)
                             .read_all()
                             .unwrap_or_default();
-                    let events = dedup_by_id(events, |e| e.id.as_str());
+                    let events = merge_by_id(events, |e| e.id.as_str());
 
                     let placements: Vec<meta_agent::models::Placement> =
                         JsonlReader::for_entity(&storage, EntityType::Placement, &epoch_id)
                             .read_all()
                             .unwrap_or_default();
-                    let placements = dedup_by_id(placements, |p| p.id.as_str());
+                    let placements = merge_by_id(placements, |p| p.id.as_str());
 
                     let lists: Vec<ArmyList> =
                         JsonlReader::for_entity(&storage, EntityType::ArmyList, &epoch_id)
                             .read_all()
                             .unwrap_or_default();
-                    let lists = dedup_by_id(lists, |l| l.id.as_str());
+                    let lists = merge_by_id(lists, |l| l.id.as_str());
 
                     let event_urls: std::collections::HashMap<String, String> = events
                         .iter()
@@ -912,35 +2331,32 @@ async fn main() -> Result<()> {
                     }
                 }
                 DebugAction::CheckDetachments { epoch } => {
-                    use meta_agent::api::routes::events::{
-                        normalize_faction_name, parse_detachment_from_raw,
-                    };
+                    use meta_agent::api::routes::events::parse_detachment_from_raw;
+                    use meta_agent::models::normalize_faction_name;
                     use meta_agent::sync::normalize_player_name;
 
-                    let storage = StorageConfig::new(std::path::PathBuf::from(&cli.data_dir));
-                    let sig_events = read_significant_events(&storage).unwrap_or_default();
-                    let mapper = EpochMapper::from_significant_events(&sig_events);
-                    let epoch_id = epoch
-                        .or_else(|| mapper.current_epoch().map(|e| e.id.as_str().to_string()))
-                        .unwrap_or_else(|| "current".to_string());
+                    let storage = config
+                        .storage()
+                        .with_source_partitioning(cli.partition_by_source);
+                    let epoch_id = epoch.unwrap_or_else(|| current_epoch_id(&storage));
 
                     let events: Vec<meta_agent::models::Event> =
                         JsonlReader::for_entity(&storage, EntityType::Event, &epoch_id)
                             .read_all()
                             .unwrap_or_default();
-                    let events = dedup_by_id(events, |e| e.id.as_str());
+                    let events = merge_by_id(events, |e| e.id.as_str());
 
                     let placements: Vec<meta_agent::models::Placement> =
                         JsonlReader::for_entity(&storage, EntityType::Placement, &epoch_id)
                             .read_all()
                             .unwrap_or_default();
-                    let placements = dedup_by_id(placements, |p| p.id.as_str());
+                    let placements = merge_by_id(placements, |p| p.id.as_str());
 
                     let lists: Vec<ArmyList> =
                         JsonlReader::for_entity(&storage, EntityType::ArmyList, &epoch_id)
                             .read_all()
                             .unwrap_or_default();
-                    let lists = dedup_by_id(lists, |l| l.id.as_str());
+                    let lists = merge_by_id(lists, |l| l.id.as_str());
 
                     // Build name→lists map (one player can have lists from multiple events)
                     let mut name_to_lists: std::collections::HashMap<String, Vec<&ArmyList>> =
@@ -1129,16 +2545,14 @@ async fn main() -> Result<()> {
                     }
                 }
                 DebugAction::ReparseUnits { epoch, dry_run } => {
-                    use meta_agent::sync::bcp::{
+                    use meta_agent::parsing::armylist::{
                         detect_chapter_from_raw_text, parse_units_from_raw_text,
                     };
 
-                    let storage = StorageConfig::new(std::path::PathBuf::from(&cli.data_dir));
-                    let sig_events = read_significant_events(&storage).unwrap_or_default();
-                    let mapper = EpochMapper::from_significant_events(&sig_events);
-                    let epoch_id = epoch
-                        .or_else(|| mapper.current_epoch().map(|e| e.id.as_str().to_string()))
-                        .unwrap_or_else(|| "current".to_string());
+                    let storage = config
+                        .storage()
+                        .with_source_partitioning(cli.partition_by_source);
+                    let epoch_id = epoch.unwrap_or_else(|| current_epoch_id(&storage));
 
                     let reader = JsonlReader::<ArmyList>::for_entity(
                         &storage,
@@ -1146,7 +2560,7 @@ async fn main() -> Result<()> {
                         &epoch_id,
                     );
                     let mut lists = reader.read_all().expect("Failed to read army lists");
-                    lists = dedup_by_id(lists, |l| l.id.as_str());
+                    lists = merge_by_id(lists, |l| l.id.as_str());
 
                     // Also load placements to fix factions there too
                     let p_reader = JsonlReader::<meta_agent::models::Placement>::for_entity(
@@ -1155,7 +2569,7 @@ async fn main() -> Result<()> {
                         &epoch_id,
                     );
                     let mut placements = p_reader.read_all().unwrap_or_default();
-                    placements = dedup_by_id(placements, |p| p.id.as_str());
+                    placements = merge_by_id(placements, |p| p.id.as_str());
 
                     println!(
                         "=== Re-parsing units from raw_text (epoch: {}) ===",
@@ -1212,10 +2626,11 @@ async fn main() -> Result<()> {
                         if has_new_data || list.units.is_empty() {
                             let new_total: u32 = new_units.iter().filter_map(|u| u.points).sum();
                             if !dry_run {
-                                list.units = new_units;
+                                list.set_units(new_units);
                                 if new_total > 0 {
                                     list.total_points = new_total;
                                 }
+                                list.touch();
                             }
                             updated += 1;
                         }
@@ -1232,6 +2647,7 @@ async fn main() -> Result<()> {
                             if let Some(chapter) = player_chapter.get(&norm_name) {
                                 if !dry_run {
                                     p.faction = chapter.clone();
+                                    p.touch();
                                 }
                                 placements_fixed += 1;
                             }
@@ -1239,16 +2655,7 @@ async fn main() -> Result<()> {
                     }
 
                     if !dry_run {
-                        // Back up and write army lists
-                        let src_path = storage
-                            .normalized_dir()
-                            .join(&epoch_id)
-                            .join("army_lists.jsonl");
-                        let bak_path = src_path.with_extension("jsonl.pre-reparse.bak");
-                        if src_path.exists() {
-                            std::fs::copy(&src_path, &bak_path).expect("Failed to create backup");
-                            println!("Backed up lists to {:?}", bak_path);
-                        }
+                        // Write army lists (the writer keeps the .bak)
                         let writer = JsonlWriter::<ArmyList>::for_entity(
                             &storage,
                             EntityType::ArmyList,
@@ -1258,15 +2665,6 @@ async fn main() -> Result<()> {
 
                         // Write placements if any were fixed
                         if placements_fixed > 0 {
-                            let p_src = storage
-                                .normalized_dir()
-                                .join(&epoch_id)
-                                .join("placements.jsonl");
-                            let p_bak = p_src.with_extension("jsonl.pre-reparse.bak");
-                            if p_src.exists() {
-                                std::fs::copy(&p_src, &p_bak).expect("Failed to backup placements");
-                                println!("Backed up placements to {:?}", p_bak);
-                            }
                             let p_writer = JsonlWriter::<meta_agent::models::Placement>::for_entity(
                                 &storage,
                                 EntityType::Placement,
@@ -1297,9 +2695,9 @@ async fn main() -> Result<()> {
                     let backend: Arc<dyn AiBackend> = if use_ollama {
                         tracing::info!("Using Ollama backend...");
                         let ollama = OllamaBackend::new(
-                            "http://localhost:11434".to_string(),
-                            "llama3.2".to_string(),
-                            120,
+                            config.ai.base_url.clone(),
+                            config.ai.model.clone(),
+                            config.ai.timeout_seconds,
                         );
 
                         if !ingest::check_backend(&ollama).await {
@@ -1341,22 +2739,47 @@ async fn main() -> Result<()> {
         }
         Commands::AddBalancePass {
             date,
+            effective_date,
+            grace_days,
             title,
             source_url,
             pdf_url,
             event_type,
+            factions,
         } => {
-            let storage = StorageConfig::new(std::path::PathBuf::from(&cli.data_dir));
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
             let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
                 .unwrap_or_else(|_| panic!("Invalid --date (expected YYYY-MM-DD): {}", date));
 
             let evt_type = match event_type.as_str() {
                 "edition" => SignificantEventType::EditionRelease,
+                "faq" => SignificantEventType::FaqUpdate,
                 _ => SignificantEventType::BalanceUpdate,
             };
 
+            let grace_days = grace_days.unwrap_or(config.epochs.grace_days);
+
             let mut event = SignificantEvent::new(evt_type, date, title.clone(), source_url)
-                .with_confidence(Confidence::High);
+                .with_confidence(Confidence::High)
+                .with_grace_days(grace_days)
+                .with_affected_factions(
+                    factions
+                        .iter()
+                        .map(|f| meta_agent::models::normalize_faction_name(f))
+                        .collect(),
+                );
+            if let Some(effective) = effective_date {
+                let effective =
+                    NaiveDate::parse_from_str(&effective, "%Y-%m-%d").unwrap_or_else(|_| {
+                        panic!(
+                            "Invalid --effective-date (expected YYYY-MM-DD): {}",
+                            effective
+                        )
+                    });
+                event = event.with_effective_date(effective);
+            }
             if let Some(url) = pdf_url {
                 event = event.with_pdf_url(url);
             }
@@ -1369,11 +2792,16 @@ async fn main() -> Result<()> {
                 return Ok(());
             }
 
+            let kind = if event.event_type.is_epoch_boundary() {
+                "balance pass"
+            } else {
+                "FAQ annotation"
+            };
             existing.push(event);
             write_significant_events(&storage, &mut existing)?;
 
             let mapper = EpochMapper::from_significant_events(&existing);
-            println!("Registered balance pass: {} ({})", title, date);
+            println!("Registered {}: {} ({})", kind, title, date);
             println!(
                 "\n=== Epoch Timeline ({} epochs) ===\n",
                 mapper.all_epochs().len()
@@ -1383,7 +2811,13 @@ async fn main() -> Result<()> {
                     .end_date
                     .map(|d| d.to_string())
                     .unwrap_or_else(|| "now".to_string());
-                let current = if epoch.is_current { " [CURRENT]" } else { "" };
+                let current = if epoch.is_current {
+                    " [CURRENT]"
+                } else if epoch.is_provisional {
+                    " [PROVISIONAL]"
+                } else {
+                    ""
+                };
                 println!(
                     "  {} — {} to {}{}",
                     epoch.name, epoch.start_date, end, current
@@ -1391,61 +2825,40 @@ async fn main() -> Result<()> {
             }
         }
         Commands::DiscoverBalancePasses { dry_run, url } => {
-            let storage = StorageConfig::new(std::path::PathBuf::from(&cli.data_dir));
-            let page_url = url.unwrap_or_else(|| {
-                "https://www.warhammer-community.com/en-gb/downloads/warhammer-40000/".to_string()
-            });
-
-            let backend: Arc<dyn AiBackend> = select_backend();
-
-            let fetcher = Fetcher::new(FetcherConfig {
-                cache_dir: storage.raw_dir(),
-                ..Default::default()
-            })
-            .expect("Failed to create fetcher");
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+            let page_url = url.unwrap_or_else(|| config.sources.warhammer_community.url.clone());
 
-            let page_url =
-                url::Url::parse(&page_url).unwrap_or_else(|e| panic!("Invalid URL: {}", e));
+            let backend: Arc<dyn AiBackend> = select_backend(&config);
 
-            // Fetch and run BalanceWatcherAgent
-            let fetch_result = fetcher.fetch(&page_url).await?;
-            let html = fetcher.read_cached_text(&fetch_result).await?;
-
-            use meta_agent::agents::balance_watcher::{BalanceWatcherAgent, BalanceWatcherInput};
-            let existing = read_significant_events(&storage).unwrap_or_default();
-            let known_ids: Vec<_> = existing.iter().map(|e| e.id.clone()).collect();
-
-            let watcher = BalanceWatcherAgent::new(backend);
-            let input = BalanceWatcherInput {
-                html_content: html,
-                source_url: page_url.to_string(),
-                known_event_ids: known_ids,
+            let watch_source = SyncSource::WarhammerCommunity {
+                url: page_url.clone(),
             };
+            let fetcher =
+                Fetcher::new(config.fetcher_config(&storage, &[watch_source.into_source()]))
+                    .expect("Failed to create fetcher");
 
-            let output = watcher.execute(input).await?;
-            println!("Discovered {} balance events", output.events.len());
-
-            if !output.events.is_empty() {
-                let mut merged = existing;
-                let existing_ids: std::collections::HashSet<String> =
-                    merged.iter().map(|e| e.id.as_str().to_string()).collect();
-
-                let mut new_count = 0;
-                for evt in &output.events {
-                    if !existing_ids.contains(evt.data.id.as_str()) {
-                        merged.push(evt.data.clone());
-                        new_count += 1;
-                    }
-                }
-
-                if !dry_run && new_count > 0 {
-                    write_significant_events(&storage, &mut merged)?;
-                    println!("Added {} new events ({} total)", new_count, merged.len());
-                } else if dry_run {
-                    println!("(dry run — {} new events would be added)", new_count);
-                } else {
-                    println!("No new events to add.");
-                }
+            let added = discover_balance_passes(
+                &fetcher,
+                backend,
+                &storage,
+                &page_url,
+                config.epochs.grace_days,
+                dry_run,
+            )
+            .await?;
+            println!("Discovered {} new balance events", added.len());
+
+            if !added.is_empty() {
+                let mut merged = read_significant_events(&storage).unwrap_or_default();
+                if dry_run {
+                    println!("(dry run — {} new events would be added)", added.len());
+                    merged.extend(added);
+                    merged.sort_by_key(|e| e.date);
+                } else {
+                    println!("Added {} new events ({} total)", added.len(), merged.len());
+                }
 
                 let mapper = EpochMapper::from_significant_events(&merged);
                 println!(
@@ -1457,7 +2870,13 @@ async fn main() -> Result<()> {
                         .end_date
                         .map(|d| d.to_string())
                         .unwrap_or_else(|| "now".to_string());
-                    let current = if epoch.is_current { " [CURRENT]" } else { "" };
+                    let current = if epoch.is_current {
+                        " [CURRENT]"
+                    } else if epoch.is_provisional {
+                        " [PROVISIONAL]"
+                    } else {
+                        ""
+                    };
                     println!(
                         "  {} — {} to {}{}",
                         epoch.name, epoch.start_date, end, current
@@ -1465,15 +2884,214 @@ async fn main() -> Result<()> {
                 }
             }
         }
+        Commands::SyncPoints {
+            event,
+            url,
+            dry_run,
+            force,
+        } => {
+            if url.is_some() && event.is_none() {
+                anyhow::bail!("--url needs --event to say which balance pass it belongs to");
+            }
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+            let events = read_significant_events(&storage).unwrap_or_default();
+            let mapper = EpochMapper::from_significant_events(&events);
+
+            let watch_source = SyncSource::WarhammerCommunity {
+                url: config.sources.warhammer_community.url.clone(),
+            };
+            let fetcher =
+                Fetcher::new(config.fetcher_config(&storage, &[watch_source.into_source()]))
+                    .expect("Failed to create fetcher");
+            let agent = PointsWatcherAgent::new();
+
+            // Walk epochs in order so each Field Manual is diffed against the last
+            let mut previous: Vec<PointsChange> = Vec::new();
+            for epoch in mapper.all_epochs() {
+                let existing: Vec<PointsChange> =
+                    JsonlReader::for_entity(&storage, EntityType::PointsChange, epoch.id.as_str())
+                        .read_all()
+                        .unwrap_or_default();
+                let Some(pass) = events.iter().find(|e| e.id == epoch.start_event_id) else {
+                    continue;
+                };
+                let selected = event.as_deref().is_none_or(|id| id == pass.id.as_str());
+                let pdf_url = url.clone().or_else(|| pass.pdf_url.clone());
+                let skip = !selected || (!existing.is_empty() && !force) || pdf_url.is_none();
+                if skip {
+                    if !existing.is_empty() {
+                        previous = existing;
+                    }
+                    continue;
+                }
+                let pdf_url = pdf_url.unwrap_or_default();
+
+                println!("{} ({}): {}", epoch.name, pass.title, pdf_url);
+                let parsed = match url::Url::parse(&pdf_url) {
+                    Ok(parsed) => parsed,
+                    Err(e) => {
+                        eprintln!("  Invalid PDF URL: {}", e);
+                        continue;
+                    }
+                };
+                let text = match fetcher.fetch(&parsed).await {
+                    Ok(result) => match fetcher.read_cached_bytes(&result).await {
+                        Ok(bytes) => meta_agent::fetch::pdf::extract_text(&bytes),
+                        Err(e) => {
+                            eprintln!("  Failed to read PDF: {}", e);
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        eprintln!("  Failed to fetch PDF: {}", e);
+                        continue;
+                    }
+                };
+                let text = match text {
+                    Ok(text) => text,
+                    Err(e) => {
+                        eprintln!("  Failed to extract text: {}", e);
+                        continue;
+                    }
+                };
+
+                let output = match agent
+                    .execute(PointsWatcherInput {
+                        pdf_text: text,
+                        source_url: pdf_url.clone(),
+                        significant_event_id: pass.id.clone(),
+                        epoch_id: epoch.id.clone(),
+                        effective_date: pass.boundary_date(),
+                        previous: previous.clone(),
+                    })
+                    .await
+                {
+                    Ok(output) => output,
+                    Err(e) => {
+                        eprintln!("  {}", e);
+                        continue;
+                    }
+                };
+                for note in &output.extraction_notes {
+                    println!("  {}", note);
+                }
+                let changes: Vec<&PointsChange> = output
+                    .data
+                    .iter()
+                    .filter(|p| p.delta().is_some_and(|d| d != 0))
+                    .collect();
+                for change in changes.iter().take(10) {
+                    println!(
+                        "    {} / {} ({} models): {} -> {}",
+                        change.faction,
+                        change.unit_name,
+                        change.models,
+                        change.previous_points.unwrap_or_default(),
+                        change.points
+                    );
+                }
+                if changes.len() > 10 {
+                    println!("    ... and {} more", changes.len() - 10);
+                }
+
+                if dry_run {
+                    println!("  (dry run — {} costs not written)", output.data.len());
+                } else {
+                    JsonlWriter::for_entity(&storage, EntityType::PointsChange, epoch.id.as_str())
+                        .write_all(&output.data)?;
+                    println!("  Wrote {} costs", output.data.len());
+                }
+                previous = output.data;
+            }
+        }
+        Commands::ParseDataslates {
+            event,
+            dry_run,
+            force,
+        } => {
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+            let mut events = read_significant_events(&storage).unwrap_or_default();
+
+            let backend: Arc<dyn AiBackend> = select_backend(&config);
+            let watch_source = SyncSource::WarhammerCommunity {
+                url: config.sources.warhammer_community.url.clone(),
+            };
+            let fetcher =
+                Fetcher::new(config.fetcher_config(&storage, &[watch_source.into_source()]))
+                    .expect("Failed to create fetcher");
+            let agent = DataslateParserAgent::new(backend, Arc::new(fetcher));
+
+            let mut parsed = 0;
+            for pass in events.iter_mut() {
+                if pass.event_type != SignificantEventType::BalanceUpdate
+                    || event.as_deref().is_some_and(|id| id != pass.id.as_str())
+                    || (!pass.rule_changes.is_empty() && !force)
+                {
+                    continue;
+                }
+                let Some(pdf_url) = pass.pdf_url.clone() else {
+                    if event.is_some() {
+                        eprintln!("{} has no PDF URL", pass.title);
+                    }
+                    continue;
+                };
+
+                println!("{} ({})", pass.title, pdf_url);
+                let input = DataslateParserInput {
+                    pdf_url,
+                    title: pass.title.clone(),
+                };
+                let output = match execute_with_retry(&agent, input).await {
+                    Ok(output) => output,
+                    Err(e) => {
+                        eprintln!("  {}", e);
+                        continue;
+                    }
+                };
+                for note in &output.extraction_notes {
+                    println!("  {}", note);
+                }
+                for change in &output.data {
+                    println!(
+                        "    [{:?}] {}{}: {}",
+                        change.change_type,
+                        change.faction,
+                        change
+                            .unit
+                            .as_ref()
+                            .map(|u| format!(" / {}", u))
+                            .unwrap_or_default(),
+                        change.summary
+                    );
+                }
+                if !output.data.is_empty() {
+                    pass.rule_changes = output.data;
+                    parsed += 1;
+                }
+            }
+
+            if dry_run {
+                println!("(dry run — {} balance passes not updated)", parsed);
+            } else if parsed > 0 {
+                write_significant_events(&storage, &mut events)?;
+                println!("Updated {} balance passes", parsed);
+            } else {
+                println!("No balance passes to update");
+            }
+        }
         Commands::WeeklyUpdate { dry_run, days } => {
-            let storage = StorageConfig::new(std::path::PathBuf::from(&cli.data_dir));
-            let backend: Arc<dyn AiBackend> = select_backend();
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+            let backend: Arc<dyn AiBackend> = select_backend(&config);
 
-            let fetcher = Fetcher::new(FetcherConfig {
-                cache_dir: storage.raw_dir(),
-                ..Default::default()
-            })
-            .expect("Failed to create fetcher");
+            let sources = meta_agent::sync::source::registry().enabled(&config.sources);
+            let fetcher = Fetcher::new(config.fetcher_config(&storage, &sources))
+                .expect("Failed to create fetcher");
 
             let today = chrono::Utc::now().date_naive();
             let from_date = today - chrono::Days::new(days as u64);
@@ -1482,73 +3100,29 @@ async fn main() -> Result<()> {
 
             // ── Step 1: Check for balance passes ──
             println!("Step 1: Checking for balance passes...");
-            let wh_url = "https://www.warhammer-community.com/en-gb/downloads/warhammer-40000/";
-            let balance_page_url =
-                url::Url::parse(wh_url).expect("Invalid Warhammer Community URL");
-
             let mut new_balance_passes = 0u32;
-            match fetcher.fetch(&balance_page_url).await {
-                Ok(fetch_result) => match fetcher.read_cached_text(&fetch_result).await {
-                    Ok(html) => {
-                        use meta_agent::agents::balance_watcher::{
-                            BalanceWatcherAgent, BalanceWatcherInput,
-                        };
-                        let watcher = BalanceWatcherAgent::new(backend.clone());
-                        let existing = read_significant_events(&storage).unwrap_or_default();
-                        let known_ids: Vec<meta_agent::models::EntityId> =
-                            existing.iter().map(|e| e.id.clone()).collect();
-                        let input = BalanceWatcherInput {
-                            html_content: html,
-                            source_url: wh_url.to_string(),
-                            known_event_ids: known_ids,
-                        };
-                        match watcher.execute(input).await {
-                            Ok(output) => {
-                                new_balance_passes = output.events.len() as u32;
-                                if new_balance_passes > 0 {
-                                    println!(
-                                        "  Found {} new balance pass(es)!",
-                                        new_balance_passes
-                                    );
-                                    if !dry_run {
-                                        let mut all_events = existing;
-                                        let existing_ids: std::collections::HashSet<String> =
-                                            all_events
-                                                .iter()
-                                                .map(|e| e.id.as_str().to_string())
-                                                .collect();
-                                        for event_output in &output.events {
-                                            println!(
-                                                "    - {} ({})",
-                                                event_output.data.title, event_output.data.date
-                                            );
-                                            if !existing_ids.contains(event_output.data.id.as_str())
-                                            {
-                                                all_events.push(event_output.data.clone());
-                                            }
-                                        }
-                                        if let Err(e) =
-                                            write_significant_events(&storage, &mut all_events)
-                                        {
-                                            tracing::error!(
-                                                "Failed to write significant events: {}",
-                                                e
-                                            );
-                                        }
-                                    }
-                                } else {
-                                    println!("  No new balance passes found.");
-                                }
-                            }
-                            Err(e) => {
-                                tracing::warn!("Balance watcher failed: {}", e);
-                                println!("  Balance watcher failed: {}", e);
-                            }
-                        }
+            match discover_balance_passes(
+                &fetcher,
+                backend.clone(),
+                &storage,
+                &config.sources.warhammer_community.url,
+                config.epochs.grace_days,
+                dry_run,
+            )
+            .await
+            {
+                Ok(added) if !added.is_empty() => {
+                    new_balance_passes = added.len() as u32;
+                    println!("  Found {} new balance pass(es)!", new_balance_passes);
+                    for pass in &added {
+                        println!("    - {} ({})", pass.title, pass.date);
                     }
-                    Err(e) => println!("  Failed to read page: {}", e),
-                },
-                Err(e) => println!("  Failed to fetch Warhammer Community: {}", e),
+                }
+                Ok(_) => println!("  No new balance passes found."),
+                Err(e) => {
+                    tracing::warn!("Balance discovery failed: {}", e);
+                    println!("  Balance discovery failed: {}", e);
+                }
             }
 
             // ── Step 2: Sync new tournament results ──
@@ -1558,15 +3132,21 @@ async fn main() -> Result<()> {
             );
 
             let sync_config = SyncConfig {
-                sources: vec![SyncSource::default()],
+                sources,
                 interval: Duration::from_secs(3600),
                 date_from: Some(from_date),
                 date_to: Some(today),
                 dry_run,
+                full: false,
+                upcoming: false,
+                grace_days: config.epochs.grace_days,
                 storage: storage.clone(),
             };
 
-            let orchestrator = SyncOrchestrator::new(sync_config, fetcher, backend);
+            let mut orchestrator = SyncOrchestrator::new(sync_config, fetcher, backend);
+            if let Some(notifier) = Notifier::from_config(&config.notifications) {
+                orchestrator = orchestrator.with_post_sync_hook(notifier);
+            }
             match orchestrator.sync_once().await {
                 Ok(result) => {
                     println!("  Events:     {}", result.events_synced);
@@ -1625,9 +3205,11 @@ async fn main() -> Result<()> {
             all,
             dry_run,
         } => {
-            use meta_agent::api::routes::events::resolve_faction;
+            use meta_agent::maintain::{reclassify_lists, reclassify_placements};
 
-            let storage = StorageConfig::new(std::path::PathBuf::from(&cli.data_dir));
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
 
             // Build list of epoch IDs to process
             let epoch_ids: Vec<String> = if all {
@@ -1646,16 +3228,7 @@ async fn main() -> Result<()> {
                 ids.sort();
                 ids
             } else if epoch == "current" {
-                let sig = read_significant_events(&storage).unwrap_or_default();
-                let resolved = if sig.is_empty() {
-                    "current".to_string()
-                } else {
-                    let mapper = EpochMapper::from_significant_events(&sig);
-                    mapper
-                        .current_epoch()
-                        .map(|e| e.id.as_str().to_string())
-                        .unwrap_or_else(|| "current".to_string())
-                };
+                let resolved = current_epoch_id(&storage);
                 // Include both the resolved epoch and literal "current" if they differ
                 let norm_dir = storage.normalized_dir();
                 let mut ids = vec![resolved.clone()];
@@ -1688,48 +3261,14 @@ async fn main() -> Result<()> {
                         Vec::new()
                     }
                 };
-                let mut placements = dedup_by_id(placements, |p| p.id.as_str());
-
-                let placement_path = storage
-                    .normalized_dir()
-                    .join(epoch_id)
-                    .join("placements.jsonl");
-                if placement_path.exists() && !dry_run && !placements.is_empty() {
-                    let bak = placement_path.with_extension("jsonl.pre-reclassify.bak");
-                    std::fs::copy(&placement_path, &bak).expect("Failed to backup placements");
-                }
+                let mut placements = merge_by_id(placements, |p| p.id.as_str());
 
-                let mut p_changed = 0u32;
                 let p_total = placements.len() as u32;
-                for p in &mut placements {
-                    let resolved = resolve_faction(&p.faction, p.subfaction.as_deref());
-                    let mut changed = false;
-                    if p.faction != resolved.faction {
-                        if dry_run {
-                            println!(
-                                "  [placement] #{} {} — faction: \"{}\" → \"{}\"",
-                                p.rank, p.player_name, p.faction, resolved.faction
-                            );
-                        }
-                        p.faction = resolved.faction.clone();
-                        changed = true;
-                    }
-                    if p.subfaction != resolved.subfaction {
-                        if dry_run && (p.subfaction.is_some() || resolved.subfaction.is_some()) {
-                            println!(
-                                "  [placement] #{} {} — subfaction: {:?} → {:?}",
-                                p.rank, p.player_name, p.subfaction, resolved.subfaction
-                            );
-                        }
-                        p.subfaction = resolved.subfaction.clone();
-                        changed = true;
-                    }
-                    if p.allegiance.as_deref() != Some(&resolved.allegiance) {
-                        p.allegiance = Some(resolved.allegiance.clone());
-                        changed = true;
-                    }
-                    if changed {
-                        p_changed += 1;
+                let (p_changed, items) = reclassify_placements(&mut placements);
+                let p_changed = p_changed as u32;
+                if dry_run {
+                    for item in &items {
+                        println!("  {}", item);
                     }
                 }
 
@@ -1754,44 +3293,14 @@ async fn main() -> Result<()> {
                         Vec::new()
                     }
                 };
-                let mut lists = dedup_by_id(lists, |l| l.id.as_str());
-
-                let list_path = storage
-                    .normalized_dir()
-                    .join(epoch_id)
-                    .join("army_lists.jsonl");
-                if list_path.exists() && !dry_run && !lists.is_empty() {
-                    let bak = list_path.with_extension("jsonl.pre-reclassify.bak");
-                    std::fs::copy(&list_path, &bak).expect("Failed to backup army lists");
-                }
+                let mut lists = merge_by_id(lists, |l| l.id.as_str());
 
-                let mut l_changed = 0u32;
                 let l_total = lists.len() as u32;
-                for l in &mut lists {
-                    let resolved = resolve_faction(&l.faction, l.subfaction.as_deref());
-                    let mut changed = false;
-                    if l.faction != resolved.faction {
-                        if dry_run {
-                            println!(
-                                "  [list] {} — faction: \"{}\" → \"{}\"",
-                                l.player_name.as_deref().unwrap_or("?"),
-                                l.faction,
-                                resolved.faction
-                            );
-                        }
-                        l.faction = resolved.faction.clone();
-                        changed = true;
-                    }
-                    if l.subfaction != resolved.subfaction {
-                        l.subfaction = resolved.subfaction.clone();
-                        changed = true;
-                    }
-                    if l.allegiance.as_deref() != Some(&resolved.allegiance) {
-                        l.allegiance = Some(resolved.allegiance.clone());
-                        changed = true;
-                    }
-                    if changed {
-                        l_changed += 1;
+                let (l_changed, items) = reclassify_lists(&mut lists);
+                let l_changed = l_changed as u32;
+                if dry_run {
+                    for item in &items {
+                        println!("  {}", item);
                     }
                 }
 
@@ -1829,38 +3338,34 @@ async fn main() -> Result<()> {
             }
         }
         Commands::FetchPairings { epoch, dry_run } => {
-            let storage = StorageConfig::new(std::path::PathBuf::from(&cli.data_dir));
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
 
-            let epoch_id = epoch.unwrap_or_else(|| {
-                let sig = read_significant_events(&storage).unwrap_or_default();
-                if sig.is_empty() {
-                    "current".to_string()
-                } else {
-                    let mapper = EpochMapper::from_significant_events(&sig);
-                    mapper
-                        .current_epoch()
-                        .map(|e| e.id.as_str().to_string())
-                        .unwrap_or_else(|| "current".to_string())
-                }
-            });
+            let epoch_id = epoch.unwrap_or_else(|| current_epoch_id(&storage));
 
-            println!("=== Fetch Pairings (epoch: {}) ===\n", epoch_id);
+            let json_output = cli.output == OutputFormat::Json;
+            if !json_output {
+                println!("=== Fetch Pairings (epoch: {}) ===\n", epoch_id);
+            }
 
             // Load events
             let events: Vec<meta_agent::models::Event> =
                 JsonlReader::for_entity(&storage, EntityType::Event, &epoch_id)
                     .read_all()
                     .unwrap_or_default();
-            let events = dedup_by_id(events, |e| e.id.as_str());
+            let events = merge_by_id(events, |e| e.id.as_str());
 
             // Filter to BCP events only
             let bcp_events: Vec<&meta_agent::models::Event> =
                 events.iter().filter(|e| e.source_name == "bcp").collect();
 
-            println!("Total events: {}", events.len());
-            println!("BCP events:   {}\n", bcp_events.len());
+            if !json_output {
+                println!("Total events: {}", events.len());
+                println!("BCP events:   {}\n", bcp_events.len());
+            }
 
-            if bcp_events.is_empty() {
+            if bcp_events.is_empty() && !json_output {
                 println!("No BCP events found. Pairings can only be fetched for BCP events.");
                 return Ok(());
             }
@@ -1876,51 +3381,69 @@ async fn main() -> Result<()> {
                 .collect();
 
             // Create BCP client
+            let bcp = &config.sources.bcp;
+            let bcp_source = SyncSource::Bcp {
+                api_base_url: bcp.api_base_url.clone(),
+                game_type: bcp.game_type,
+                split_brackets: bcp.split_brackets,
+            };
             let bcp_fetcher = Fetcher::new(FetcherConfig {
-                cache_dir: storage.raw_dir(),
                 extra_headers: meta_agent::sync::bcp::bcp_headers_authenticated().await,
-                ..Default::default()
+                ..config.fetcher_config(&storage, &[bcp_source.into_source()])
             })
             .expect("Failed to create BCP fetcher");
             let bcp_client = meta_agent::sync::bcp::BcpClient::new(
                 bcp_fetcher,
-                "https://newprod-api.bestcoastpairings.com/v1".to_string(),
-                1,
+                bcp.api_base_url.clone(),
+                bcp.game_type,
             );
 
             let mut total_pairings = 0u32;
             let mut events_processed = 0u32;
+            let mut events_skipped = 0u32;
+            let mut events_failed = 0u32;
+            let mut progress = Progress::new(
+                bcp_events.len() as u64,
+                "events",
+                ProgressMode::detect(json_output),
+            );
 
             for (idx, event) in bcp_events.iter().enumerate() {
+                progress.set_message(event.name.clone());
+
                 // Skip events that already have pairings
                 if existing_event_ids.contains(event.id.as_str()) {
-                    println!(
+                    progress.println(format!(
                         "[{}/{}] Skipping {} (pairings already exist)",
                         idx + 1,
                         bcp_events.len(),
                         event.name
-                    );
+                    ));
+                    events_skipped += 1;
+                    progress.inc();
                     continue;
                 }
 
                 // Extract BCP event ID from source_url
                 let bcp_event_id = event.source_url.rsplit('/').next().unwrap_or("");
                 if bcp_event_id.is_empty() {
-                    println!(
+                    progress.println(format!(
                         "[{}/{}] Skipping {} (no BCP event ID in URL)",
                         idx + 1,
                         bcp_events.len(),
                         event.name
-                    );
+                    ));
+                    events_skipped += 1;
+                    progress.inc();
                     continue;
                 }
 
-                println!(
+                progress.println(format!(
                     "[{}/{}] Fetching pairings for {}...",
                     idx + 1,
                     bcp_events.len(),
                     event.name
-                );
+                ));
 
                 // Rate limit
                 tokio::time::sleep(Duration::from_secs(2)).await;
@@ -1935,11 +3458,11 @@ async fn main() -> Result<()> {
                             epoch_entity_id,
                         );
 
-                        println!(
+                        progress.println(format!(
                             "  Got {} pairings ({} converted)",
                             bcp_pairings.len(),
                             model_pairings.len()
-                        );
+                        ));
 
                         if !dry_run && !model_pairings.is_empty() {
                             let writer = JsonlWriter::<meta_agent::models::Pairing>::for_entity(
@@ -1955,33 +3478,45 @@ async fn main() -> Result<()> {
                         events_processed += 1;
                     }
                     Err(e) => {
-                        println!("  Error: {}", e);
+                        progress.println(format!("  Error: {}", e));
+                        events_failed += 1;
                     }
                 }
+                progress.inc();
+            }
+            let timing = progress.finish();
+
+            if json_output {
+                let summary = serde_json::json!({
+                    "epoch": epoch_id,
+                    "bcp_events": bcp_events.len(),
+                    "events_processed": events_processed,
+                    "events_skipped": events_skipped,
+                    "events_failed": events_failed,
+                    "pairings_fetched": total_pairings,
+                    "dry_run": dry_run,
+                    "timing": timing,
+                });
+                println!("{}", serde_json::to_string_pretty(&summary)?);
+                return Ok(());
             }
 
             println!("\n=== Results ===");
             println!("Events processed: {}", events_processed);
+            println!("Events skipped:   {}", events_skipped);
+            println!("Events failed:    {}", events_failed);
             println!("Pairings fetched: {}", total_pairings);
+            println!("Elapsed:          {}", timing);
             if dry_run {
                 println!("(dry run — no data written to disk)");
             }
         }
         Commands::LinkLists { epoch, dry_run } => {
-            let storage = StorageConfig::new(std::path::PathBuf::from(&cli.data_dir));
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
 
-            let epoch_id = epoch.unwrap_or_else(|| {
-                let sig = read_significant_events(&storage).unwrap_or_default();
-                if sig.is_empty() {
-                    "current".to_string()
-                } else {
-                    let mapper = EpochMapper::from_significant_events(&sig);
-                    mapper
-                        .current_epoch()
-                        .map(|e| e.id.as_str().to_string())
-                        .unwrap_or_else(|| "current".to_string())
-                }
-            });
+            let epoch_id = epoch.unwrap_or_else(|| current_epoch_id(&storage));
 
             println!("=== Link Lists (epoch: {}) ===\n", epoch_id);
 
@@ -1990,94 +3525,31 @@ async fn main() -> Result<()> {
                 JsonlReader::for_entity(&storage, EntityType::Event, &epoch_id)
                     .read_all()
                     .unwrap_or_default();
-            let events = dedup_by_id(events, |e| e.id.as_str());
+            let events = merge_by_id(events, |e| e.id.as_str());
 
             let placements: Vec<meta_agent::models::Placement> =
                 JsonlReader::for_entity(&storage, EntityType::Placement, &epoch_id)
                     .read_all()
                     .unwrap_or_default();
-            let mut placements = dedup_by_id(placements, |p| p.id.as_str());
+            let mut placements = merge_by_id(placements, |p| p.id.as_str());
 
             let lists: Vec<ArmyList> =
                 JsonlReader::for_entity(&storage, EntityType::ArmyList, &epoch_id)
                     .read_all()
                     .unwrap_or_default();
-            let mut lists = dedup_by_id(lists, |l| l.id.as_str());
+            let mut lists = merge_by_id(lists, |l| l.id.as_str());
 
             println!("Events:     {}", events.len());
             println!("Placements: {}", placements.len());
             println!("Lists:      {}\n", lists.len());
 
-            // Build lookup: event source_url → event_id
-            let url_to_event_id: std::collections::HashMap<String, meta_agent::models::EventId> =
-                events
-                    .iter()
-                    .map(|e| (e.source_url.clone(), e.id.clone()))
-                    .collect();
+            let outcome = meta_agent::maintain::link_lists(&events, &mut placements, &mut lists);
 
-            // 1. Set event_id on lists that don't have one
-            let mut lists_linked = 0u32;
-            for list in &mut lists {
-                if list.event_id.is_none() {
-                    if let Some(ref source_url) = list.source_url {
-                        if let Some(event_id) = url_to_event_id.get(source_url) {
-                            list.event_id = Some(event_id.clone());
-                            lists_linked += 1;
-                        }
-                    }
-                }
-            }
-
-            // 2. Build per-event name→list_id map, then set list_id on placements
-            let mut placements_linked = 0u32;
-            let name_to_list: std::collections::HashMap<
-                (String, String),
-                meta_agent::models::ArmyListId,
-            > = lists
-                .iter()
-                .filter_map(|l| {
-                    let event_id = l.event_id.as_ref()?.as_str().to_string();
-                    let name = meta_agent::sync::normalize_player_name(l.player_name.as_deref()?);
-                    Some(((event_id, name), l.id.clone()))
-                })
-                .collect();
-
-            for p in &mut placements {
-                if p.list_id.is_none() {
-                    let key = (
-                        p.event_id.as_str().to_string(),
-                        meta_agent::sync::normalize_player_name(&p.player_name),
-                    );
-                    if let Some(list_id) = name_to_list.get(&key) {
-                        p.list_id = Some(list_id.clone());
-                        placements_linked += 1;
-                    }
-                }
-            }
-
-            println!("Lists with event_id set:     {}", lists_linked);
-            println!("Placements with list_id set: {}", placements_linked);
+            println!("Lists with event_id set:     {}", outcome.lists_linked);
+            println!("Placements with list_id set: {}", outcome.placements_linked);
+            println!("Lists flagged as clones:     {}", outcome.clones_flagged);
 
             if !dry_run {
-                // Back up existing files
-                let placement_path = storage
-                    .normalized_dir()
-                    .join(&epoch_id)
-                    .join("placements.jsonl");
-                let list_path = storage
-                    .normalized_dir()
-                    .join(&epoch_id)
-                    .join("army_lists.jsonl");
-
-                if placement_path.exists() {
-                    let bak = placement_path.with_extension("jsonl.pre-link.bak");
-                    std::fs::copy(&placement_path, &bak).ok();
-                }
-                if list_path.exists() {
-                    let bak = list_path.with_extension("jsonl.pre-link.bak");
-                    std::fs::copy(&list_path, &bak).ok();
-                }
-
                 let p_writer = JsonlWriter::<meta_agent::models::Placement>::for_entity(
                     &storage,
                     EntityType::Placement,
@@ -2098,12 +3570,235 @@ async fn main() -> Result<()> {
                 println!("\n(dry run — no data written to disk)");
             }
         }
+        Commands::SeasonSummary { action } => {
+            use meta_agent::ingest::season_summary::{check_season_summary, read_findings};
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+            let findings = match action {
+                SeasonSummaryAction::Check { url, dry_run } => {
+                    let backend: Arc<dyn AiBackend> = select_backend(&config);
+                    let source = SyncSource::Goonhammer {
+                        base_url: config.sources.goonhammer.base_url.clone(),
+                    };
+                    let fetcher =
+                        Fetcher::new(config.fetcher_config(&storage, &[source.into_source()]))
+                            .expect("Failed to create fetcher");
+                    let check =
+                        check_season_summary(&fetcher, backend, &storage, &url, dry_run).await?;
+                    if cli.output == OutputFormat::Json {
+                        println!("{}", serde_json::to_string_pretty(&check)?);
+                        return Ok(());
+                    }
+                    println!(
+                        "{} ({} to {})",
+                        check.summary.title, check.summary.period_start, check.summary.period_end
+                    );
+                    println!(
+                        "  {} local events in the period, {} of {} published factions compared{}\n",
+                        check.local_events,
+                        check.factions_compared,
+                        check.summary.factions.len(),
+                        if dry_run {
+                            " (dry run, not recorded)"
+                        } else {
+                            ""
+                        }
+                    );
+                    check.findings
+                }
+                SeasonSummaryAction::Findings => {
+                    let findings = read_findings(&storage)?;
+                    if cli.output == OutputFormat::Json {
+                        println!("{}", serde_json::to_string_pretty(&findings)?);
+                        return Ok(());
+                    }
+                    findings
+                }
+            };
+            if findings.is_empty() {
+                println!("No data-quality findings");
+            }
+            for f in &findings {
+                println!("  [{}] {}", f.kind, f.message);
+                println!(
+                    "      {} ({} to {})",
+                    f.source_url, f.period_start, f.period_end
+                );
+            }
+        }
+        Commands::Compact { epoch, dry_run } => {
+            use meta_agent::storage::compact::compact;
+
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+            let epochs = (epoch != "all").then(|| vec![epoch]);
+            let report = compact(&storage, epochs.as_deref(), dry_run)?;
+
+            if cli.output == OutputFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+            println!(
+                "=== Compaction{} ===\n",
+                if dry_run { " (dry run)" } else { "" }
+            );
+            for file in report.files.iter().filter(|f| f.rewritten) {
+                println!(
+                    "  {}: {} -> {} lines ({} reclaimed)",
+                    file.path,
+                    file.lines_before,
+                    file.lines_after,
+                    file.reclaimed()
+                );
+            }
+            println!(
+                "\n{} of {} files {}, {} duplicate lines reclaimed",
+                report.files_rewritten(),
+                report.files.len(),
+                if dry_run { "to rewrite" } else { "rewritten" },
+                report.reclaimed()
+            );
+        }
+        Commands::Maintain {
+            dry_run,
+            json,
+            strict,
+            watch,
+            interval,
+        } => {
+            use meta_agent::maintain::run_maintenance;
+            use meta_agent::storage::validate::Severity;
+
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+
+            if watch {
+                let every =
+                    meta_agent::parse_duration(&interval).unwrap_or(Duration::from_secs(24 * 3600));
+                tracing::info!("Running periodic maintenance (interval: {})...", interval);
+                let mut ticker = tokio::time::interval(every);
+                loop {
+                    ticker.tick().await;
+                    match run_maintenance(&storage, dry_run) {
+                        Ok(report) => tracing::info!(
+                            "Maintenance: {} changes across {} epochs, {} validation errors, {} warnings",
+                            report.changes(),
+                            report.epochs.len(),
+                            report.validation.errors,
+                            report.validation.warnings
+                        ),
+                        Err(e) => tracing::error!("Maintenance failed: {}", e),
+                    }
+                }
+            }
+
+            let report = run_maintenance(&storage, dry_run)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                println!(
+                    "=== Maintenance{} ===\n",
+                    if dry_run { " (dry run)" } else { "" }
+                );
+                for epoch in &report.epochs {
+                    println!("Epoch {}:", epoch.epoch_id);
+                    println!("  Duplicates removed:       {}", epoch.duplicates_removed);
+                    println!(
+                        "  Placements reclassified:  {}",
+                        epoch.placements_reclassified
+                    );
+                    println!("  Lists reclassified:       {}", epoch.lists_reclassified);
+                    println!("  Lists linked to events:   {}", epoch.links.lists_linked);
+                    println!(
+                        "  Placements linked:        {}",
+                        epoch.links.placements_linked
+                    );
+                    println!("  Clone flags updated:      {}", epoch.clone_changes);
+                    println!("  Points flags updated:     {}", epoch.points_flag_changes);
+                    println!("  Game sizes detected:      {}", epoch.game_sizes_detected);
+                    if dry_run {
+                        for item in &epoch.items {
+                            println!("    {}", item);
+                        }
+                    }
+                    for file in &epoch.files_written {
+                        println!("  Wrote {}", file);
+                    }
+                    println!();
+                }
+                for epoch_id in &report.skipped {
+                    println!("Skipped epoch {}: unreadable entity file", epoch_id);
+                }
+
+                println!(
+                    "Validation: {} errors, {} warnings",
+                    report.validation.errors, report.validation.warnings
+                );
+                for issue in report.validation.issues.iter().take(20) {
+                    let level = match issue.severity {
+                        Severity::Error => "ERROR",
+                        Severity::Warning => "WARN ",
+                    };
+                    println!("  {} {}: {}", level, issue.epoch_id, issue.message);
+                }
+                if report.validation.issues.len() > 20 {
+                    println!("  ... and {} more", report.validation.issues.len() - 20);
+                }
+
+                let status = report.exit_code(strict);
+                println!(
+                    "\nSummary: {} changes{}, status {}",
+                    report.changes(),
+                    if dry_run { " pending" } else { " applied" },
+                    if status == 0 { "OK" } else { "FAILED" }
+                );
+            }
+
+            let status = report.exit_code(strict);
+            if status != 0 {
+                std::process::exit(status);
+            }
+        }
+        Commands::Schedule { run, list } => {
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+            let backend = select_backend(&config);
+            let scheduler = Arc::new(Scheduler::new(config.clone(), storage, backend)?);
+
+            if let Some(name) = run {
+                let job: Job = name.parse()?;
+                let summary = scheduler.run_job(job).await?;
+                println!("{}: {}", job.name(), summary);
+            } else if list {
+                let now = chrono::Utc::now();
+                for (job, schedule) in scheduler.jobs() {
+                    println!(
+                        "  {:<18} {:<22} next: {}",
+                        job.name(),
+                        schedule.to_string(),
+                        schedule.next_after(now).format("%Y-%m-%d %H:%M UTC")
+                    );
+                }
+            } else {
+                println!(
+                    "Running {} scheduled job(s); Ctrl-C to stop",
+                    scheduler.jobs().len()
+                );
+                scheduler.run().await;
+            }
+        }
         Commands::Repartition {
             dry_run,
             source,
             keep_originals,
         } => {
-            let storage = StorageConfig::new(std::path::PathBuf::from(&cli.data_dir));
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
             match meta_agent::sync::repartition::repartition(
                 &storage,
                 &source,
@@ -2132,32 +3827,1371 @@ async fn main() -> Result<()> {
                 }
             }
         }
+        Commands::FixEvent { id, date, dry_run } => {
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+            let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                .unwrap_or_else(|_| panic!("Invalid --date (expected YYYY-MM-DD): {}", date));
+            match meta_agent::sync::fix_event::fix_event_date(&storage, &id, date, dry_run) {
+                Ok(fix) => {
+                    println!("\n=== Fix Event: {} ===", fix.name);
+                    println!("  Date:  {} -> {}", fix.old_date, fix.new_date);
+                    if fix.moved() {
+                        println!("  Epoch: {} -> {}", fix.from_epoch, fix.to_epoch);
+                    } else {
+                        println!("  Epoch: {} (unchanged)", fix.from_epoch);
+                    }
+                    println!(
+                        "  {} placements, {} lists, {} pairings",
+                        fix.placements, fix.lists, fix.pairings
+                    );
+                    if dry_run {
+                        println!("\n(dry run — no data written to disk)");
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Fix event failed: {}", e);
+                }
+            }
+        }
+        Commands::MergeEvents {
+            keep,
+            remove,
+            suggest,
+            dry_run,
+        } => {
+            use meta_agent::sync::merge_events::{merge_events, suggest_duplicates};
+
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+            if suggest {
+                let suggestions = suggest_duplicates(&storage)?;
+                if suggestions.is_empty() {
+                    println!("No likely duplicate events found.");
+                }
+                for s in &suggestions {
+                    println!(
+                        "\n{:.2}  keep   {}  {} ({}, {}, {} placements)",
+                        s.score,
+                        s.keep.id,
+                        s.keep.name,
+                        s.keep.date,
+                        s.keep.source_name,
+                        s.keep.placements
+                    );
+                    println!(
+                        "      remove {}  {} ({}, {}, {} placements)",
+                        s.remove.id,
+                        s.remove.name,
+                        s.remove.date,
+                        s.remove.source_name,
+                        s.remove.placements
+                    );
+                }
+            } else if let (Some(keep), Some(remove)) = (keep, remove) {
+                match merge_events(&storage, &keep, &remove, dry_run) {
+                    Ok(merge) => {
+                        println!("\n=== Merge {} into {} ===", merge.remove_id, merge.keep_id);
+                        println!(
+                            "  Placements: {} moved, {} reconciled",
+                            merge.placements_moved, merge.placements_reconciled
+                        );
+                        println!(
+                            "  Lists:      {} moved, {} dropped",
+                            merge.lists_moved, merge.lists_dropped
+                        );
+                        println!(
+                            "  Pairings:   {} moved, {} dropped",
+                            merge.pairings_moved, merge.pairings_dropped
+                        );
+                        if dry_run {
+                            println!("\n(dry run — no data written to disk)");
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Merge failed: {}", e);
+                    }
+                }
+            }
+        }
+        Commands::Ai { action } => match action {
+            AiAction::Doctor { yes } => ai_doctor(&config.ai, yes).await?,
+        },
+        Commands::Publish { epoch, out } => {
+            let storage = config.storage();
+            let report = meta_agent::publish::publish(&storage, Some(&epoch), &out)?;
+            println!(
+                "Published {} pages for {} to {}",
+                report.pages,
+                report.epoch_id,
+                out.display()
+            );
+            println!("Open {}", report.index.display());
+        }
+        Commands::Dashboard { action } => match action {
+            DashboardAction::Init {
+                dir,
+                force,
+                output,
+                epoch,
+            } => {
+                if let Some(output) = output {
+                    let storage = config.storage();
+                    let page =
+                        meta_agent::dashboard::export_site(&storage, epoch.as_deref(), &output)?;
+                    println!("Wrote meta snapshot to {}", page.display());
+                } else {
+                    let report = meta_agent::dashboard::init(&dir, force)?;
+                    println!(
+                        "Wrote {} dashboard files to {}",
+                        report.written.len(),
+                        dir.display()
+                    );
+                    if !report.skipped.is_empty() {
+                        println!(
+                            "Kept {} existing files (use --force to overwrite)",
+                            report.skipped.len()
+                        );
+                    }
+                }
+            }
+        },
+        Commands::Notify { action } => match action {
+            NotifyAction::Test => {
+                if config.notifications.webhooks.is_empty() {
+                    eprintln!("No webhooks configured under [[notifications.webhooks]].");
+                    return Ok(());
+                }
+                let notifier = Notifier::new(config.notifications.clone());
+                let alerts = [meta_agent::notify::sample_alert()];
+                for webhook in notifier.webhooks() {
+                    match notifier.post(webhook, &alerts).await {
+                        Ok(()) => println!("  ok      {} ({:?})", webhook.url, webhook.kind),
+                        Err(e) => println!("  failed  {}: {}", webhook.url, e),
+                    }
+                }
+            }
+        },
+        Commands::Export {
+            entity,
+            epoch,
+            format,
+            out,
+        } => {
+            let storage = config.storage();
+            let outcome =
+                meta_agent::export::export_to_dir(&storage, entity, &epoch, format, &out)?;
+            println!(
+                "Exported {} {} to {}",
+                outcome.records,
+                entity,
+                outcome.path.display()
+            );
+        }
+        Commands::ExportStatcheck {
+            epoch,
+            out,
+            mapping,
+        } => {
+            let storage = config.storage();
+            export_statcheck(&storage, &epoch, &out, mapping.as_deref())?;
+        }
+        Commands::Backup { tag, list } => {
+            use meta_agent::storage::backup;
+            let storage = config.storage();
+            if list {
+                let snapshots = backup::list_snapshots(&storage)?;
+                if snapshots.is_empty() {
+                    println!("No snapshots in {:?}", storage.backups_dir());
+                }
+                for s in snapshots {
+                    println!(
+                        "{:<40} {:>6} files {:>10} bytes  {}",
+                        s.id,
+                        s.files,
+                        s.bytes,
+                        s.created_at.format("%Y-%m-%d %H:%M:%S UTC")
+                    );
+                }
+            } else {
+                let snapshot = backup::create_snapshot(&storage, &tag)?;
+                println!(
+                    "Created snapshot {} ({} files, {} bytes)",
+                    snapshot.id, snapshot.files, snapshot.bytes
+                );
+            }
+        }
+        Commands::ConvertStorage { from, to } => {
+            let storage = config.storage();
+            let (files, records) = meta_agent::storage::store::convert(&storage, from, to)?;
+            println!(
+                "Copied {} records in {} files from {} to {}",
+                records, files, from, to
+            );
+            if to != config.storage.backend {
+                println!(
+                    "Set [storage] backend = \"{}\" or pass --storage-backend {} to use it",
+                    to, to
+                );
+            }
+        }
+        Commands::Restore { snapshot, yes } => {
+            use meta_agent::storage::backup;
+            let storage = config.storage();
+            let found = backup::find_snapshot(&storage, &snapshot)?;
+            if !yes {
+                print!(
+                    "Replace normalized/ and state/ with snapshot {}? [y/N] ",
+                    found.id
+                );
+                std::io::Write::flush(&mut std::io::stdout())?;
+                let mut answer = String::new();
+                std::io::stdin().read_line(&mut answer)?;
+                if !matches!(answer.trim(), "y" | "Y" | "yes") {
+                    println!("Aborted");
+                    return Ok(());
+                }
+            }
+            let restored = backup::restore_snapshot(&storage, &found.id)?;
+            println!(
+                "Restored snapshot {} ({} files); previous data saved as \"{}\"",
+                restored.id,
+                restored.files,
+                backup::PRE_RESTORE_TAG
+            );
+        }
+        Commands::Import { action } => {
+            let storage = config
+                .storage()
+                .with_source_partitioning(cli.partition_by_source);
+            match action {
+                ImportAction::Ttb {
+                    file,
+                    event_name,
+                    date,
+                    location,
+                    dry_run,
+                } => import_ttb(
+                    &storage,
+                    &file,
+                    event_name,
+                    date.as_deref(),
+                    location,
+                    dry_run,
+                )?,
+                ImportAction::Csv {
+                    entity,
+                    file,
+                    event_id,
+                    mapping,
+                    dry_run,
+                } => import_csv(
+                    &storage,
+                    entity,
+                    &file,
+                    event_id.as_deref(),
+                    mapping.as_deref(),
+                    dry_run,
+                )?,
+                ImportAction::Statcheck {
+                    file,
+                    mapping,
+                    dry_run,
+                } => import_statcheck(&storage, &file, mapping.as_deref(), dry_run)?,
+                ImportAction::Roster {
+                    file,
+                    player,
+                    event_id,
+                    dry_run,
+                } => import_roster(&storage, &file, &player, &event_id, dry_run)?,
+            }
+        }
     }
 
     Ok(())
 }
 
-/// Select the best available AI backend.
-///
-/// When the `remote-ai` feature is active and `ANTHROPIC_API_KEY` is set,
-/// uses AnthropicBackend. Otherwise falls back to OllamaBackend.
-fn select_backend() -> Arc<dyn AiBackend> {
-    #[cfg(feature = "remote-ai")]
+/// Write every stored placement in the chosen epochs as a Stat Check
+/// dataset to `<out>/statcheck_<epoch>.csv`.
+fn export_statcheck(
+    storage: &StorageConfig,
+    epoch: &str,
+    out: &std::path::Path,
+    mapping: Option<&std::path::Path>,
+) -> Result<()> {
+    use anyhow::Context;
+    use meta_agent::api::merge_by_id;
+    use meta_agent::ingest::csv_import::ColumnMapping;
+    use meta_agent::ingest::statcheck;
+    use meta_agent::models::{ArmyList, Event, Placement};
+
+    let mapping = match mapping {
+        Some(path) => ColumnMapping::load(path)
+            .with_context(|| format!("Failed to load mapping {}", path.display()))?,
+        None => ColumnMapping::default(),
+    };
+
+    let mut events: Vec<Event> = Vec::new();
+    let mut placements: Vec<Placement> = Vec::new();
+    let mut lists: Vec<ArmyList> = Vec::new();
+    for epoch in meta_agent::export::export_epochs(storage, epoch) {
+        events.extend(
+            JsonlReader::<Event>::for_entity(storage, EntityType::Event, &epoch)
+                .read_all()
+                .unwrap_or_default(),
+        );
+        placements.extend(
+            JsonlReader::<Placement>::for_entity(storage, EntityType::Placement, &epoch)
+                .read_all()
+                .unwrap_or_default(),
+        );
+        lists.extend(
+            JsonlReader::<ArmyList>::for_entity(storage, EntityType::ArmyList, &epoch)
+                .read_all()
+                .unwrap_or_default(),
+        );
+    }
+    let events = merge_by_id(events, |e| e.id.as_str());
+    let placements = merge_by_id(placements, |p| p.id.as_str());
+    let lists = merge_by_id(lists, |l| l.id.as_str());
+
+    std::fs::create_dir_all(out)?;
+    let path = out.join(format!("statcheck_{}.csv", epoch));
+    let file = std::fs::File::create(&path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let rows = statcheck::write_dataset(&events, &placements, &lists, &mapping, file)?;
+    println!(
+        "Exported {} results from {} events to {}",
+        rows,
+        events.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Import a Stat Check dataset. Events already tracked locally (matched by
+/// name and date) are skipped along with their rows.
+fn import_statcheck(
+    storage: &StorageConfig,
+    file: &std::path::Path,
+    mapping: Option<&std::path::Path>,
+    dry_run: bool,
+) -> Result<()> {
+    use anyhow::Context;
+    use meta_agent::ingest::csv_import::{ColumnMapping, RowError};
+    use meta_agent::ingest::statcheck;
+    use meta_agent::models::{EntityId, Event};
+
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let mapping = match mapping {
+        Some(path) => ColumnMapping::load(path)
+            .with_context(|| format!("Failed to load mapping {}", path.display()))?,
+        None => ColumnMapping::default(),
+    };
+    let mapper = cached_epoch_mapper(storage);
+    let source_url = format!(
+        "file://{}",
+        file.canonicalize()
+            .unwrap_or_else(|_| file.to_path_buf())
+            .display()
+    );
+    let import = statcheck::parse_dataset(&content, &mapping, &source_url, |date| {
+        if mapper.all_epochs().is_empty() {
+            EntityId::from("current")
+        } else {
+            mapper.get_epoch_id_for_date(date)
+        }
+    })?;
+
+    println!("=== Import Stat Check dataset {} ===", file.display());
+    println!(
+        "  {} events, {} placements, {} army lists, {} rows rejected",
+        import.events.len(),
+        import.placements.len(),
+        import.lists.len(),
+        import.errors.len()
+    );
+    for RowError { line, message } in import.errors.iter().take(20) {
+        println!("    line {}: {}", line, message);
+    }
+
+    let mut written = (0, 0, 0);
+    let mut skipped = 0;
+    for event in &import.events {
+        let epoch = event.epoch_id.as_str();
+        let existing: Vec<Event> = JsonlReader::for_entity(storage, EntityType::Event, epoch)
+            .read_all()
+            .unwrap_or_default();
+        if let Some(id) = meta_agent::sync::convert::find_duplicate_event(event, &existing) {
+            println!(
+                "  {}  {:<40} already tracked ({})",
+                event.date, event.name, id
+            );
+            skipped += 1;
+            continue;
+        }
+        let placements: Vec<_> = import
+            .placements
+            .iter()
+            .filter(|p| p.event_id == event.id)
+            .collect();
+        let lists: Vec<_> = import
+            .lists
+            .iter()
+            .filter(|l| l.event_id.as_ref() == Some(&event.id))
+            .collect();
+        println!(
+            "  {}  {:<40} {} results, {} lists (epoch {})",
+            event.date,
+            event.name,
+            placements.len(),
+            lists.len(),
+            epoch
+        );
+        if dry_run {
+            continue;
+        }
+        JsonlWriter::for_source(storage, EntityType::Event, epoch, statcheck::SOURCE_NAME)
+            .append(event)?;
+        JsonlWriter::for_source(
+            storage,
+            EntityType::Placement,
+            epoch,
+            statcheck::SOURCE_NAME,
+        )
+        .append_batch(&placements)?;
+        JsonlWriter::for_source(storage, EntityType::ArmyList, epoch, statcheck::SOURCE_NAME)
+            .append_batch(&lists)?;
+        written.0 += 1;
+        written.1 += placements.len();
+        written.2 += lists.len();
+    }
+
+    if dry_run {
+        println!("\n(dry run - no data written to disk)");
+        return Ok(());
+    }
+    println!(
+        "\nWrote {} events, {} placements, {} army lists ({} events already tracked)",
+        written.0, written.1, written.2, skipped
+    );
+    Ok(())
+}
+
+/// Import a roster file as a player's army list at a stored event.
+fn import_roster(
+    storage: &StorageConfig,
+    file: &std::path::Path,
+    player: &str,
+    event_id: &str,
+    dry_run: bool,
+) -> Result<()> {
+    use anyhow::Context;
+    use meta_agent::ingest::rosters;
+
+    let bytes =
+        std::fs::read(file).with_context(|| format!("Failed to read {}", file.display()))?;
+    let import = rosters::import_roster(storage, &bytes, player, event_id, dry_run)?;
+    let list = &import.list;
+
+    println!("=== Import roster {} ===", file.display());
+    println!(
+        "  Event:      {} ({})",
+        import.event.name, import.event.date
+    );
+    println!("  Player:     {}", player);
+    println!("  Faction:    {}", list.faction);
+    if let Some(detachment) = &list.detachment {
+        println!("  Detachment: {}", detachment);
+    }
+    println!("  Points:     {}", list.total_points);
+    for unit in &list.units {
+        println!(
+            "    {:>2}x {:<36} {:>4} pts",
+            unit.count,
+            unit.name,
+            unit.points.unwrap_or(0)
+        );
+    }
+
+    if dry_run {
+        println!("\n(dry run - no data written to disk)");
+    } else if import.stored {
+        println!("\nWrote army list {}", list.id);
+    } else {
+        println!("\nArmy list {} already stored", list.id);
+    }
+    Ok(())
+}
+
+/// Import a Tabletop Battles / Stats and Ladders export as one event with
+/// its pairings and computed standings.
+fn import_ttb(
+    storage: &StorageConfig,
+    file: &std::path::Path,
+    event_name: Option<String>,
+    date: Option<&str>,
+    location: Option<String>,
+    dry_run: bool,
+) -> Result<()> {
+    use anyhow::Context;
+    use meta_agent::ingest::ttb;
+    use meta_agent::models::{Event, Pairing, Placement};
+
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let export = ttb::parse_export(&content)?;
+
+    let name = event_name
+        .or_else(|| export.event_name.clone())
+        .context("Export has no event name; pass --event-name")?;
+    let date = match date {
+        Some(d) => NaiveDate::parse_from_str(d, "%Y-%m-%d")
+            .with_context(|| format!("Invalid --date '{}', expected YYYY-MM-DD", d))?,
+        None => export
+            .event_date
+            .context("Export has no event date; pass --date")?,
+    };
+
+    let mapper = cached_epoch_mapper(storage);
+    let epoch_id = if mapper.all_epochs().is_empty() {
+        meta_agent::models::EntityId::from("current")
+    } else {
+        mapper.get_epoch_id_for_date(date)
+    };
+    let epoch_str = epoch_id.as_str().to_string();
+
+    let source_url = format!(
+        "file://{}",
+        file.canonicalize()
+            .unwrap_or_else(|_| file.to_path_buf())
+            .display()
+    );
+    let mut event = Event::new(name, date, source_url, "ttb".to_string(), epoch_id.clone())
+        .with_confidence(Confidence::High);
+    if let Some(loc) = location {
+        event = event.with_location(loc);
+    }
+
+    let existing_events: Vec<Event> =
+        JsonlReader::for_entity(storage, EntityType::Event, &epoch_str)
+            .read_all()
+            .unwrap_or_default();
+    let duplicate = meta_agent::sync::convert::find_duplicate_event(&event, &existing_events);
+    let event_id = duplicate.clone().unwrap_or_else(|| event.id.clone());
+
+    let placements = ttb::placements_from_export(&export, &event_id, &epoch_id);
+    let pairings = ttb::pairings_from_export(&export, &event_id, &epoch_id);
+    event = event.with_player_count(placements.len() as u32);
+    if let Some(rounds) = export.games.iter().map(|g| g.round).max() {
+        event = event.with_round_count(rounds);
+    }
+
+    println!(
+        "=== Import {} ({}, epoch {}) ===",
+        event.name, date, epoch_str
+    );
+    if let Some(ref id) = duplicate {
+        println!("  Matches existing event {}, adding results to it", id);
+    }
+    println!(
+        "  {} games, {} pairings, {} players",
+        export.games.len(),
+        pairings.len(),
+        placements.len()
+    );
+    for p in placements.iter().take(10) {
+        let record = p
+            .record
+            .as_ref()
+            .map(|r| format!("{}-{}-{}", r.wins, r.losses, r.draws))
+            .unwrap_or_default();
+        println!(
+            "  {:>3}. {:<24} {:<24} {}",
+            p.rank, p.player_name, p.faction, record
+        );
+    }
+
+    if dry_run {
+        println!("\n(dry run - no data written to disk)");
+        return Ok(());
+    }
+
+    if duplicate.is_none() {
+        JsonlWriter::for_source(storage, EntityType::Event, &epoch_str, &event.source_name)
+            .append(&event)?;
+    }
+    let existing_placements: std::collections::HashSet<String> =
+        JsonlReader::<Placement>::for_entity(storage, EntityType::Placement, &epoch_str)
+            .read_all()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|p| p.id.as_str().to_string())
+            .collect();
+    let new_placements: Vec<&Placement> = placements
+        .iter()
+        .filter(|p| !existing_placements.contains(p.id.as_str()))
+        .collect();
+    let existing_pairings: std::collections::HashSet<String> =
+        JsonlReader::<Pairing>::for_entity(storage, EntityType::Pairing, &epoch_str)
+            .read_all()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|p| p.id.as_str().to_string())
+            .collect();
+    let new_pairings: Vec<&Pairing> = pairings
+        .iter()
+        .filter(|p| !existing_pairings.contains(p.id.as_str()))
+        .collect();
+
+    JsonlWriter::for_source(storage, EntityType::Placement, &epoch_str, "ttb")
+        .append_batch(&new_placements)?;
+    JsonlWriter::for_source(storage, EntityType::Pairing, &epoch_str, "ttb")
+        .append_batch(&new_pairings)?;
+    println!(
+        "\nWrote {} placements, {} pairings ({} already stored)",
+        new_placements.len(),
+        new_pairings.len(),
+        placements.len() - new_placements.len() + pairings.len() - new_pairings.len()
+    );
+    Ok(())
+}
+
+/// Import a spreadsheet CSV of events, or of placements / army lists for an
+/// existing event. Rows whose IDs are already stored are skipped.
+fn import_csv(
+    storage: &StorageConfig,
+    entity: CsvEntity,
+    file: &std::path::Path,
+    event_id: Option<&str>,
+    mapping: Option<&std::path::Path>,
+    dry_run: bool,
+) -> Result<()> {
+    use anyhow::Context;
+    use meta_agent::ingest::csv_import::{self, ColumnMapping, CsvImport, RowError};
+    use meta_agent::models::{EntityId, Event, Placement};
+
+    fn print_summary<T>(import: &CsvImport<T>) {
+        let columns: Vec<String> = import
+            .columns
+            .iter()
+            .map(|(field, header)| format!("{} <- \"{}\"", field, header))
+            .collect();
+        println!("  Columns: {}", columns.join(", "));
+        println!(
+            "  {} valid rows, {} rejected",
+            import.records.len(),
+            import.errors.len()
+        );
+        for RowError { line, message } in import.errors.iter().take(20) {
+            println!("    line {}: {}", line, message);
+        }
+    }
+
+    /// Append records whose IDs are not yet in the epoch.
+    fn append_new<T>(
+        storage: &StorageConfig,
+        entity_type: EntityType,
+        epoch: &str,
+        records: &[T],
+        id: impl Fn(&T) -> &EntityId,
+    ) -> Result<usize>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
     {
-        if let Ok(api_key) = std::env::var("ANTHROPIC_API_KEY") {
-            tracing::info!("Using Anthropic backend (claude-sonnet-4-20250514)");
-            return Arc::new(meta_agent::agents::backend::AnthropicBackend::new(
-                api_key,
-                "claude-sonnet-4-20250514".to_string(),
-                120,
-            ));
+        let existing: std::collections::HashSet<String> =
+            JsonlReader::<serde_json::Value>::for_entity(storage, entity_type, epoch)
+                .read_all()
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|v| v.get("id").and_then(|i| i.as_str()).map(String::from))
+                .collect();
+        let new: Vec<&T> = records
+            .iter()
+            .filter(|r| !existing.contains(id(r).as_str()))
+            .collect();
+        JsonlWriter::for_source(storage, entity_type, epoch, "csv").append_batch(&new)?;
+        Ok(new.len())
+    }
+
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let mapping = match mapping {
+        Some(path) => ColumnMapping::load(path)
+            .with_context(|| format!("Failed to load mapping {}", path.display()))?,
+        None => ColumnMapping::default(),
+    };
+
+    if entity == CsvEntity::Events {
+        let mapper = cached_epoch_mapper(storage);
+        let source_url = format!(
+            "file://{}",
+            file.canonicalize()
+                .unwrap_or_else(|_| file.to_path_buf())
+                .display()
+        );
+        let import = csv_import::parse_events(&content, &mapping, &source_url, |date| {
+            if mapper.all_epochs().is_empty() {
+                EntityId::from("current")
+            } else {
+                mapper.get_epoch_id_for_date(date)
+            }
+        })?;
+
+        println!("=== Import events from {} ===", file.display());
+        print_summary(&import);
+        for e in import.records.iter().take(10) {
+            println!("  {}  {:<40} epoch {}", e.date, e.name, e.epoch_id);
+        }
+        if dry_run {
+            println!("\n(dry run - no data written to disk)");
+            return Ok(());
+        }
+
+        let mut by_epoch: std::collections::BTreeMap<String, Vec<Event>> = Default::default();
+        for event in import.records {
+            by_epoch
+                .entry(event.epoch_id.as_str().to_string())
+                .or_default()
+                .push(event);
+        }
+        let mut written = 0;
+        let mut total = 0;
+        for (epoch, events) in by_epoch {
+            let existing: Vec<Event> = JsonlReader::for_entity(storage, EntityType::Event, &epoch)
+                .read_all()
+                .unwrap_or_default();
+            let new: Vec<&Event> = events
+                .iter()
+                .filter(|e| meta_agent::sync::convert::find_duplicate_event(e, &existing).is_none())
+                .collect();
+            JsonlWriter::for_source(storage, EntityType::Event, &epoch, "csv")
+                .append_batch(&new)?;
+            written += new.len();
+            total += events.len();
         }
+        println!(
+            "\nWrote {} events ({} already stored)",
+            written,
+            total - written
+        );
+        return Ok(());
     }
 
-    tracing::info!("Using Ollama backend (llama3.2)");
-    Arc::new(OllamaBackend::new(
-        "http://localhost:11434".to_string(),
-        "llama3.2".to_string(),
-        120,
-    ))
+    let event_id = event_id.context("--event-id is required for placements and lists")?;
+    let event = meta_agent::storage::jsonl::list_epochs(storage)
+        .unwrap_or_default()
+        .into_iter()
+        .chain(std::iter::once("current".to_string()))
+        .flat_map(|epoch| {
+            JsonlReader::<Event>::for_entity(storage, EntityType::Event, &epoch)
+                .read_all()
+                .unwrap_or_default()
+        })
+        .find(|e| e.id.as_str() == event_id)
+        .with_context(|| format!("Event {} not found", event_id))?;
+    let epoch = event.epoch_id.as_str().to_string();
+
+    println!(
+        "=== Import {} for {} ({}, epoch {}) ===",
+        entity, event.name, event.date, epoch
+    );
+    match entity {
+        CsvEntity::Placements => {
+            let import = csv_import::parse_placements(&content, &mapping, &event)?;
+            print_summary(&import);
+            for p in import.records.iter().take(10) {
+                println!("  {:>3}. {:<24} {}", p.rank, p.player_name, p.faction);
+            }
+            if dry_run {
+                println!("\n(dry run - no data written to disk)");
+                return Ok(());
+            }
+            let written = append_new(
+                storage,
+                EntityType::Placement,
+                &epoch,
+                &import.records,
+                |p: &Placement| &p.id,
+            )?;
+            println!(
+                "\nWrote {} placements ({} already stored)",
+                written,
+                import.records.len() - written
+            );
+        }
+        CsvEntity::Lists => {
+            let import = csv_import::parse_lists(&content, &mapping, &event)?;
+            print_summary(&import);
+
+            // Preview how many lists will link to this event's placements
+            let names: std::collections::HashSet<String> =
+                JsonlReader::<Placement>::for_entity(storage, EntityType::Placement, &epoch)
+                    .read_all()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|p| p.event_id == event.id)
+                    .map(|p| meta_agent::sync::normalize_player_name(&p.player_name))
+                    .collect();
+            let matched = import
+                .records
+                .iter()
+                .filter_map(|l| l.player_name.as_deref())
+                .filter(|n| names.contains(&meta_agent::sync::normalize_player_name(n)))
+                .count();
+            for l in import.records.iter().take(10) {
+                println!(
+                    "  {:<24} {:<24} {} pts, {} units",
+                    l.player_name.as_deref().unwrap_or("-"),
+                    l.faction,
+                    l.total_points,
+                    l.units.len()
+                );
+            }
+            println!("  {} lists match a placement by player name", matched);
+            if dry_run {
+                println!("\n(dry run - no data written to disk)");
+                return Ok(());
+            }
+            let written = append_new(
+                storage,
+                EntityType::ArmyList,
+                &epoch,
+                &import.records,
+                |l: &ArmyList| &l.id,
+            )?;
+            println!(
+                "\nWrote {} lists ({} already stored)",
+                written,
+                import.records.len() - written
+            );
+            if matched > 0 {
+                println!("Run `meta-agent maintain` to link them to placements.");
+            }
+        }
+        CsvEntity::Events => unreachable!("handled above"),
+    }
+    Ok(())
+}
+
+/// Number of candidate lists measured when estimating AI usage.
+const ESTIMATE_SAMPLE_SIZE: usize = 10;
+
+/// Print projected AI usage for normalizing the given lists.
+fn print_normalize_estimate(lists: &[ArmyList], indices: &[usize], ai: &AiConfig) {
+    use meta_agent::agents::estimate::{sample_indices, UsageEstimate};
+
+    // Lists with empty raw_text are skipped by the normalizer, so never cost a call
+    let candidates: Vec<&ArmyList> = indices
+        .iter()
+        .map(|&i| &lists[i])
+        .filter(|l| !l.raw_text.trim().is_empty())
+        .collect();
+
+    let samples: Vec<_> = sample_indices(candidates.len(), ESTIMATE_SAMPLE_SIZE)
+        .into_iter()
+        .map(|i| {
+            let list = candidates[i];
+            ListNormalizerAgent::estimate_usage(&list.raw_text, Some(&list.faction), &list.units)
+        })
+        .collect();
+
+    let est = UsageEstimate::from_samples(candidates.len() as u32, &samples);
+
+    println!("=== AI Usage Estimate (normalize-lists) ===");
+    println!("Candidate lists:      {}", indices.len());
+    print_usage_estimate(&est, ai);
+}
+
+/// Print projected AI usage for reprocessing the given articles. Articles
+/// without stored records are counted as one event and no lists.
+async fn print_reprocess_estimate(
+    orchestrator: &SyncOrchestrator,
+    articles: &[(String, Option<NaiveDate>, Option<String>)],
+    date: Option<NaiveDate>,
+    stored_counts: &std::collections::HashMap<String, (u32, u32)>,
+    ai: &AiConfig,
+) {
+    use meta_agent::agents::estimate::{sample_indices, UsageEstimate};
+
+    let counts = |url: &str| stored_counts.get(url).copied().unwrap_or((1, 0));
+    // One Event Scout call per article, then one per event and list
+    let calls: u32 = articles
+        .iter()
+        .map(|(url, _, _)| {
+            let (events, lists) = counts(url);
+            1 + events + lists
+        })
+        .sum();
+
+    let mut samples = Vec::new();
+    let mut uncached = 0;
+    for i in sample_indices(articles.len(), ESTIMATE_SAMPLE_SIZE) {
+        let (article_url, issue_date, raw_path) = &articles[i];
+        let Ok(parsed) = url::Url::parse(article_url) else {
+            continue;
+        };
+        let article_date = date
+            .or(*issue_date)
+            .unwrap_or_else(|| chrono::Utc::now().date_naive());
+        let (events, lists) = counts(article_url);
+        match orchestrator
+            .estimate_reprocess(
+                &parsed,
+                article_date,
+                raw_path.as_deref().map(std::path::Path::new),
+                events,
+                lists,
+            )
+            .await
+        {
+            Some(article_samples) => samples.extend(article_samples),
+            None => uncached += 1,
+        }
+    }
+
+    let est = UsageEstimate::from_samples(calls, &samples);
+
+    println!("=== AI Usage Estimate (reprocess) ===");
+    println!("Articles:             {}", articles.len());
+    if uncached > 0 {
+        println!(
+            "Sampled, not cached:  {} (these fail to reprocess)",
+            uncached
+        );
+    }
+    print_usage_estimate(&est, ai);
+}
+
+/// Print projected calls, tokens, and cost at the configured model's rates.
+fn print_usage_estimate(est: &meta_agent::agents::estimate::UsageEstimate, ai: &AiConfig) {
+    println!("Projected calls:      {}", est.calls);
+    println!("Sampled:              {}", est.sampled);
+    println!("Avg prompt tokens:    {}", est.avg_prompt_tokens);
+    println!("Avg completion:       {}", est.avg_completion_tokens);
+    println!("Total tokens:         {}", est.total_tokens());
+    // Matches the fixed 500ms delay between calls
+    println!(
+        "Min. wall time:       {}m",
+        (est.calls as u64 * 500).div_ceil(60_000)
+    );
+    println!();
+    let model = ai.model_for_backend();
+    println!("Backend:              {} ({})", ai.backend, model);
+    match ai.pricing() {
+        Some(pricing) => println!("Projected cost:       ${:.2}", est.cost(&pricing)),
+        None => println!(
+            "Projected cost:       unknown - no rate for this model; set input_per_mtok \
+             and output_per_mtok under [ai.pricing.\"{}\"]",
+            model
+        ),
+    }
+    println!("\n(estimate only - no AI calls made, nothing written)");
+}
+
+/// Build the application state from storage and run the API server,
+/// optionally with a background sync loop sharing its state.
+///
+/// On SIGINT/SIGTERM the server stops accepting connections and cancels the
+/// syncs in `syncs`, gives both up to `[server] shutdown_grace_secs` to
+/// finish, then flushes the storage backends before returning.
+async fn serve(
+    storage: StorageConfig,
+    host: &str,
+    port: u16,
+    default_epoch: Option<String>,
+    config: &AppConfig,
+    background_sync: Option<(SyncConfig, FetcherConfig)>,
+    syncs: ActiveSyncs,
+) -> Result<()> {
+    let epoch_mapper = EpochMapper::clone(&cached_epoch_mapper(&storage));
+    if !epoch_mapper.all_epochs().is_empty() {
+        tracing::info!(
+            "Loaded {} epochs for epoch mapping",
+            epoch_mapper.all_epochs().len()
+        );
+    }
+    if let Some(epoch) = default_epoch.as_deref() {
+        meta_agent::api::resolve_epoch(Some(epoch), &epoch_mapper)
+            .map_err(|e| anyhow::anyhow!("Invalid [server] default_epoch: {}", e))?;
+        tracing::info!("Default epoch for API requests: {}", epoch);
+    }
+    // Parse the epoch files once up front; routes then read from the index
+    if let Err(e) = meta_agent::storage::index::warm(&storage) {
+        tracing::warn!("Failed to warm storage indexes: {}", e);
+    }
+    let backend: Arc<dyn AiBackend> = select_backend(config);
+    let state = meta_agent::api::state::AppState {
+        storage: Arc::new(storage),
+        epoch_mapper: Arc::new(tokio::sync::RwLock::new(epoch_mapper)),
+        refresh_state: Arc::new(tokio::sync::RwLock::new(
+            meta_agent::api::routes::refresh::RefreshState::default(),
+        )),
+        refresh_updates: meta_agent::api::routes::refresh::refresh_channel(),
+        syncs: syncs.clone(),
+        default_epoch,
+        size_thresholds: config.analytics.size_brackets,
+        grace_days: config.epochs.grace_days,
+        api_token: config.server.api_token.as_deref().map(Arc::from),
+        notifier: Notifier::from_config(&config.notifications),
+        metrics: meta_agent::metrics::Metrics::global(),
+        ai_backend: backend,
+        traffic_stats: std::sync::Arc::new(tokio::sync::RwLock::new(
+            meta_agent::api::routes::traffic::TrafficStats::new()
+                .with_slow_request_budget(Duration::from_millis(config.server.slow_request_ms)),
+        )),
+    };
+    // Pick up balance passes registered by the CLI without a restart
+    tokio::spawn(meta_agent::api::routes::epochs::watch_significant_events(
+        state.clone(),
+        Duration::from_secs(10),
+    ));
+    if let Some((sync_config, fetcher_config)) = background_sync {
+        tokio::spawn(meta_agent::api::routes::refresh::run_background_sync(
+            state.clone(),
+            sync_config,
+            fetcher_config,
+        ));
+    }
+    let app = meta_agent::api::build_router(state);
+    let addr = format!("{}:{}", host, port);
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    tracing::info!("Dashboard: http://{}", addr);
+
+    let grace = Duration::from_secs(config.server.shutdown_grace_secs);
+    let deadline = Arc::new(std::sync::OnceLock::new());
+    let stopping = Arc::new(tokio::sync::Notify::new());
+    let server = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .with_graceful_shutdown({
+        let (deadline, stopping, syncs) = (deadline.clone(), stopping.clone(), syncs.clone());
+        async move {
+            shutdown_signal().await;
+            let _ = deadline.set(tokio::time::Instant::now() + grace);
+            tracing::info!("Shutting down: draining connections for up to {:?}", grace);
+            // Refuse new syncs and cancel running ones; waited on below
+            syncs.shutdown(Duration::ZERO).await;
+            stopping.notify_one();
+        }
+    });
+    tokio::select! {
+        result = server => result?,
+        _ = async {
+            stopping.notified().await;
+            tokio::time::sleep(grace).await;
+        } => tracing::warn!("Connections still open after {:?}; closing them", grace),
+    }
+
+    let remaining = deadline
+        .get()
+        .map(|d| d.saturating_duration_since(tokio::time::Instant::now()))
+        .unwrap_or_default();
+    if !syncs.shutdown(remaining).await {
+        tracing::warn!("Exiting with a sync still running; rerun it to pick up where it stopped");
+    }
+    if let Err(e) = meta_agent::storage::store::flush_all() {
+        tracing::warn!("Failed to flush storage: {}", e);
+    }
+    tracing::info!("Shutdown complete");
+    Ok(())
+}
+
+/// Resolve on Ctrl-C, or SIGTERM on Unix.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Print a model drift comparison as a side-by-side table.
+fn print_drift_report(r: &meta_agent::agents::drift::DriftReport) {
+    let (b, c) = (&r.baseline, &r.candidate);
+    println!("\n=== Extraction Drift: {} -> {} ===", b.model, c.model);
+    println!("{:<24} {:>12} {:>12}", "", "baseline", "candidate");
+    println!("{:<24} {:>12} {:>12}", "Lists", b.lists, c.lists);
+    println!(
+        "{:<24} {:>12.1} {:>12.1}",
+        "Mean units", b.mean_units, c.mean_units
+    );
+    for (bb, cb) in b.unit_counts.iter().zip(&c.unit_counts) {
+        println!(
+            "{:<24} {:>11.1}% {:>11.1}%",
+            format!("  {} units", bb.units),
+            bb.pct,
+            cb.pct
+        );
+    }
+    println!(
+        "{:<24} {:>11.1}% {:>11.1}%",
+        "High confidence", b.high_confidence_pct, c.high_confidence_pct
+    );
+    println!(
+        "{:<24} {:>11.1}% {:>11.1}%",
+        "Low confidence", b.low_confidence_pct, c.low_confidence_pct
+    );
+    println!(
+        "{:<24} {:>11.1}% {:>11.1}%",
+        "Recognised faction", b.faction_assigned_pct, c.faction_assigned_pct
+    );
+    if r.paired > 0 {
+        println!("\nSame raw input ({} lists):", r.paired);
+        println!("  Faction agreement:     {:.1}%", r.faction_agreement_pct);
+        println!("  Same unit count:       {:.1}%", r.unit_count_match_pct);
+        println!("  Mean unit delta:       {:+.2}", r.mean_unit_delta);
+        println!("  Confidence downgrades: {}", r.confidence_downgrades);
+    } else {
+        println!("\n(no shared raw inputs; distributions compare different lists)");
+    }
+    if r.regressions.is_empty() {
+        println!("\nNo regressions detected.");
+    } else {
+        println!("\nRegressions:");
+        for reason in &r.regressions {
+            println!("  - {}", reason);
+        }
+    }
+}
+
+/// Build the AI backend named by `[ai] backend`, answering repeated
+/// requests from `state/ai_cache/` unless `[ai] cache` is off or
+/// `--no-ai-cache` was given.
+fn select_backend(config: &AppConfig) -> Arc<dyn AiBackend> {
+    use meta_agent::agents::backend::{AiCache, CachedBackend};
+
+    let (backend, model) = build_backend(&config.ai);
+    if !config.ai.cache {
+        return backend;
+    }
+    let ttl = (config.ai.cache_ttl_days > 0)
+        .then(|| chrono::Duration::days(i64::from(config.ai.cache_ttl_days)));
+    let cache = AiCache::new(config.storage().state_dir().join("ai_cache"), ttl);
+    Arc::new(CachedBackend::new(backend, model, cache))
+}
+
+/// The uncached AI backend named by `[ai] backend`, with the model it uses.
+///
+/// "anthropic" needs the `remote-ai` feature and `ANTHROPIC_API_KEY`;
+/// without either it falls back to Ollama with a warning.
+fn build_backend(ai: &AiConfig) -> (Arc<dyn AiBackend>, String) {
+    if ai.backend == "anthropic" {
+        #[cfg(feature = "remote-ai")]
+        {
+            if let Ok(api_key) = std::env::var("ANTHROPIC_API_KEY") {
+                let model = ai.model_for_backend().to_string();
+                tracing::info!("Using Anthropic backend ({})", model);
+                let backend = meta_agent::agents::backend::AnthropicBackend::new(
+                    api_key,
+                    model.clone(),
+                    ai.timeout_seconds,
+                );
+                return (Arc::new(backend), model);
+            }
+            tracing::warn!("ANTHROPIC_API_KEY not set; falling back to Ollama");
+        }
+        #[cfg(not(feature = "remote-ai"))]
+        tracing::warn!("Built without the remote-ai feature; falling back to Ollama");
+    }
+
+    let model = if ai.backend == "ollama" {
+        ai.model.clone()
+    } else {
+        AiConfig::default().model
+    };
+    tracing::info!("Using Ollama backend ({})", model);
+    let backend = OllamaBackend::new(ai.base_url.clone(), model.clone(), ai.timeout_seconds)
+        .with_num_ctx(ai.num_ctx);
+    (Arc::new(backend), model)
+}
+
+/// Progress bar for an Ollama model pull.
+fn pull_progress_bar() -> indicatif::ProgressBar {
+    let bar = indicatif::ProgressBar::new(0);
+    bar.set_style(
+        indicatif::ProgressStyle::with_template(
+            "    {msg:24} [{bar:40}] {bytes}/{total_bytes} ({eta})",
+        )
+        .unwrap_or_else(|_| indicatif::ProgressStyle::default_bar())
+        .progress_chars("=> "),
+    );
+    bar
+}
+
+fn update_pull_progress(
+    bar: &indicatif::ProgressBar,
+    progress: &meta_agent::agents::ollama::PullProgress,
+) {
+    bar.set_message(progress.status.clone());
+    if let (Some(total), Some(done)) = (progress.total, progress.completed) {
+        bar.set_length(total);
+        bar.set_position(done);
+    }
+}
+
+/// Startup health check for the Ollama backend. Logs what is wrong and how
+/// to fix it instead of letting agents fail later, pulling a missing model
+/// first when `[ai] auto_pull` is set.
+async fn check_ollama_at_startup(ai: &AiConfig) {
+    if ai.backend != "ollama" {
+        return;
+    }
+    let backend = OllamaBackend::new(ai.base_url.clone(), ai.model.clone(), ai.timeout_seconds);
+    let mut last_status = String::new();
+    let diagnostics = backend
+        .diagnose(ai.auto_pull, |p| {
+            if p.status != last_status {
+                tracing::info!("Pulling {}: {}", ai.model, p.status);
+                last_status = p.status.clone();
+            }
+        })
+        .await;
+    if diagnostics.is_healthy() {
+        if diagnostics.pulled {
+            tracing::info!("Pulled Ollama model {}", ai.model);
+        }
+        return;
+    }
+    for line in diagnostics.report() {
+        tracing::warn!("{}", line);
+    }
+}
+
+/// Check the configured AI backend end to end: connectivity, model
+/// presence (offering to pull it), a timed sample extraction and a context
+/// window recommendation.
+async fn ai_doctor(ai: &AiConfig, assume_yes: bool) -> Result<()> {
+    use meta_agent::agents::doctor;
+    use meta_agent::agents::ollama::{model_matches, OllamaAdmin};
+    use std::io::{IsTerminal, Write};
+
+    println!("=== AI doctor ===");
+    println!("  Backend: {} ({})", ai.backend, ai.model_for_backend());
+
+    let mut model_max = None;
+    if ai.backend == "ollama" {
+        let admin = OllamaAdmin::new(ai.base_url.clone());
+        let models = match admin.list_models().await {
+            Ok(models) => {
+                println!("  ✓ Ollama reachable at {}", ai.base_url);
+                models
+            }
+            Err(e) => {
+                println!("  ✗ Cannot reach Ollama at {}: {}", ai.base_url, e);
+                println!("    Start it with `ollama serve` or fix [ai] base_url");
+                anyhow::bail!("AI backend unreachable");
+            }
+        };
+
+        match models.iter().find(|m| model_matches(&m.name, &ai.model)) {
+            Some(m) => println!(
+                "  ✓ Model {} installed ({:.1} GB)",
+                m.name,
+                m.size as f64 / 1e9
+            ),
+            None => {
+                println!("  ✗ Model {} is not installed", ai.model);
+                let confirmed = assume_yes
+                    || (std::io::stdin().is_terminal() && {
+                        print!("    Pull {} now? [y/N] ", ai.model);
+                        std::io::stdout().flush()?;
+                        let mut answer = String::new();
+                        std::io::stdin().read_line(&mut answer)?;
+                        matches!(answer.trim(), "y" | "Y" | "yes")
+                    });
+                if !confirmed {
+                    println!("    Run `ollama pull {}` or re-run with --yes", ai.model);
+                    anyhow::bail!("Model {} missing", ai.model);
+                }
+
+                let bar = pull_progress_bar();
+                admin
+                    .pull_model(&ai.model, |p| update_pull_progress(&bar, p))
+                    .await?;
+                bar.finish_and_clear();
+                println!("  ✓ Pulled {}", ai.model);
+            }
+        }
+
+        match admin.show_model(&ai.model).await {
+            Ok(details) => {
+                println!(
+                    "    {} parameters, {} quantization, {} token trained context",
+                    details.parameter_size.as_deref().unwrap_or("?"),
+                    details.quantization.as_deref().unwrap_or("?"),
+                    details
+                        .context_length
+                        .map(|c| c.to_string())
+                        .unwrap_or_else(|| "?".to_string())
+                );
+                model_max = details.context_length;
+            }
+            Err(e) => println!("    (could not read model details: {})", e),
+        }
+    }
+
+    println!("  Running sample extraction...");
+    let run = match doctor::run_sample_extraction(build_backend(ai).0).await {
+        Ok(run) => run,
+        Err(e) => {
+            println!("  ✗ Sample extraction failed: {}", e);
+            anyhow::bail!("Sample extraction failed");
+        }
+    };
+    println!(
+        "  ✓ Sample list normalized in {:.1}s ({} units, {:?} confidence, ~{} tokens)",
+        run.elapsed.as_secs_f64(),
+        run.units,
+        run.confidence,
+        run.usage.prompt_tokens + run.usage.completion_tokens
+    );
+    if run.elapsed.as_secs() > ai.timeout_seconds / 2 {
+        println!(
+            "    Close to the {}s timeout; consider a smaller model or a higher [ai] timeout_seconds",
+            ai.timeout_seconds
+        );
+    }
+
+    if ai.backend == "ollama" {
+        let recommended = doctor::recommend_context_window(&run.usage, model_max);
+        match ai.num_ctx {
+            Some(n) if n >= recommended => {
+                println!("  ✓ Context window num_ctx = {}", n)
+            }
+            Some(n) => println!(
+                "  ! num_ctx = {} may truncate long lists; recommended [ai] num_ctx = {}",
+                n, recommended
+            ),
+            None => println!(
+                "  ! num_ctx unset (server default); recommended [ai] num_ctx = {}",
+                recommended
+            ),
+        }
+    }
+    Ok(())
 }
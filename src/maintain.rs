@@ -0,0 +1,550 @@
+//! Consistency repair for normalized data.
+//!
+//! Bundles the retroactive fixes that used to be separate commands
+//! (`link-lists`, `reclassify-factions`, duplicate compaction and
+//! `debug validate-storage`) into one pass over every epoch, along with
+//! points validation of every list and game-size detection for events
+//! stored before it was recorded, producing an itemized report and a
+//! single exit status.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::api::merge_by_id;
+use crate::calculate::validation::flag_lists;
+use crate::models::{game_size_from_name, resolve_faction};
+use crate::models::{ArmyList, ArmyListId, Event, EventId, Pairing, Placement, Timestamped};
+use crate::storage::jsonl::{entity_path, entity_paths, list_epochs};
+use crate::storage::validate::{validate_storage, ValidationReport};
+use crate::storage::{EntityType, JsonlReader, JsonlWriter, StorageConfig, StorageError};
+use crate::sync::brackets::infer_game_size;
+use crate::sync::{clones, normalize_player_name};
+
+/// Counts from linking lists to events and placements.
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+pub struct LinkOutcome {
+    /// Lists given an `event_id` via their source URL
+    pub lists_linked: usize,
+    /// Placements given a `list_id` by player name
+    pub placements_linked: usize,
+    /// Lists flagged as clones of another submission
+    pub clones_flagged: usize,
+}
+
+/// Link army lists to events (by source URL) and placements (by player name
+/// within an event), then flag cloned submissions.
+pub fn link_lists(
+    events: &[Event],
+    placements: &mut [Placement],
+    lists: &mut [ArmyList],
+) -> LinkOutcome {
+    let mut outcome = LinkOutcome::default();
+
+    // 1. Set event_id on lists that don't have one
+    let url_to_event_id: HashMap<&str, &EventId> = events
+        .iter()
+        .map(|e| (e.source_url.as_str(), &e.id))
+        .collect();
+    for list in lists.iter_mut().filter(|l| l.event_id.is_none()) {
+        if let Some(event_id) = list
+            .source_url
+            .as_deref()
+            .and_then(|url| url_to_event_id.get(url))
+        {
+            list.event_id = Some((*event_id).clone());
+            list.touch();
+            outcome.lists_linked += 1;
+        }
+    }
+
+    // 2. Set list_id on placements from a per-event name → list map
+    let name_to_list: HashMap<(String, String), ArmyListId> = lists
+        .iter()
+        .filter_map(|l| {
+            let event_id = l.event_id.as_ref()?.as_str().to_string();
+            let name = normalize_player_name(l.player_name.as_deref()?);
+            Some(((event_id, name), l.id.clone()))
+        })
+        .collect();
+    for p in placements.iter_mut().filter(|p| p.list_id.is_none()) {
+        let key = (
+            p.event_id.as_str().to_string(),
+            normalize_player_name(&p.player_name),
+        );
+        if let Some(list_id) = name_to_list.get(&key) {
+            p.list_id = Some(list_id.clone());
+            p.touch();
+            outcome.placements_linked += 1;
+        }
+    }
+
+    // 3. Flag cloned lists (near-identical raw text within an event)
+    outcome.clones_flagged = clones::flag_clones(lists);
+    outcome
+}
+
+/// Apply the canonical faction taxonomy to placements.
+///
+/// Returns the number of placements changed and a description of each
+/// faction/subfaction change.
+pub fn reclassify_placements(placements: &mut [Placement]) -> (usize, Vec<String>) {
+    let mut changed_count = 0;
+    let mut items = Vec::new();
+    for p in placements {
+        let resolved = resolve_faction(&p.faction, p.subfaction.as_deref());
+        let mut changed = false;
+        if p.faction != resolved.faction {
+            items.push(format!(
+                "[placement] #{} {} — faction: \"{}\" → \"{}\"",
+                p.rank, p.player_name, p.faction, resolved.faction
+            ));
+            p.faction = resolved.faction.clone();
+            changed = true;
+        }
+        if p.subfaction != resolved.subfaction {
+            if p.subfaction.is_some() || resolved.subfaction.is_some() {
+                items.push(format!(
+                    "[placement] #{} {} — subfaction: {:?} → {:?}",
+                    p.rank, p.player_name, p.subfaction, resolved.subfaction
+                ));
+            }
+            p.subfaction = resolved.subfaction.clone();
+            changed = true;
+        }
+        if p.allegiance.as_deref() != Some(&resolved.allegiance) {
+            p.allegiance = Some(resolved.allegiance);
+            changed = true;
+        }
+        if changed {
+            p.touch();
+            changed_count += 1;
+        }
+    }
+    (changed_count, items)
+}
+
+/// Apply the canonical faction taxonomy to army lists.
+///
+/// Returns the number of lists changed and a description of each faction
+/// change.
+pub fn reclassify_lists(lists: &mut [ArmyList]) -> (usize, Vec<String>) {
+    let mut changed_count = 0;
+    let mut items = Vec::new();
+    for l in lists {
+        let resolved = resolve_faction(&l.faction, l.subfaction.as_deref());
+        let mut changed = false;
+        if l.faction != resolved.faction {
+            items.push(format!(
+                "[list] {} — faction: \"{}\" → \"{}\"",
+                l.player_name.as_deref().unwrap_or("?"),
+                l.faction,
+                resolved.faction
+            ));
+            l.faction = resolved.faction.clone();
+            changed = true;
+        }
+        if l.subfaction != resolved.subfaction {
+            l.subfaction = resolved.subfaction.clone();
+            changed = true;
+        }
+        if l.allegiance.as_deref() != Some(&resolved.allegiance) {
+            l.allegiance = Some(resolved.allegiance);
+            changed = true;
+        }
+        if changed {
+            l.touch();
+            changed_count += 1;
+        }
+    }
+    (changed_count, items)
+}
+
+/// Set the points limit of events that have none, from the event name or
+/// else the totals of the event's lists.
+///
+/// Returns the number of events changed and a description of each.
+pub fn fill_game_sizes(events: &mut [Event], lists: &[ArmyList]) -> (usize, Vec<String>) {
+    let mut points: HashMap<&str, Vec<u32>> = HashMap::new();
+    for list in lists {
+        if let Some(event_id) = &list.event_id {
+            points
+                .entry(event_id.as_str())
+                .or_default()
+                .push(list.total_points);
+        }
+    }
+    let mut changed = 0;
+    let mut items = Vec::new();
+    for event in events.iter_mut().filter(|e| e.game_size.is_none()) {
+        let game_size = game_size_from_name(&event.name).or_else(|| {
+            points
+                .get(event.id.as_str())
+                .and_then(|p| infer_game_size(p))
+        });
+        if let Some(game_size) = game_size {
+            items.push(format!("[game size] {} — {}pts", event.name, game_size));
+            event.game_size = Some(game_size);
+            event.touch();
+            changed += 1;
+        }
+    }
+    (changed, items)
+}
+
+/// What maintenance found (and fixed, unless a dry run) in one epoch.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct EpochMaintenance {
+    pub epoch_id: String,
+    pub duplicates_removed: usize,
+    pub placements_reclassified: usize,
+    pub lists_reclassified: usize,
+    #[serde(flatten)]
+    pub links: LinkOutcome,
+    /// Lists whose `clone_of` changed
+    pub clone_changes: usize,
+    /// Lists whose points `quality_flags` changed
+    pub points_flag_changes: usize,
+    /// Events given a `game_size`
+    pub game_sizes_detected: usize,
+    /// Itemized changes and problems
+    pub items: Vec<String>,
+    /// Entity files rewritten
+    pub files_written: Vec<String>,
+}
+
+impl EpochMaintenance {
+    /// Total number of changed records.
+    pub fn changes(&self) -> usize {
+        self.duplicates_removed
+            + self.placements_reclassified
+            + self.lists_reclassified
+            + self.links.lists_linked
+            + self.links.placements_linked
+            + self.clone_changes
+            + self.points_flag_changes
+            + self.game_sizes_detected
+    }
+}
+
+/// Result of a full maintenance run.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct MaintainReport {
+    pub dry_run: bool,
+    pub epochs: Vec<EpochMaintenance>,
+    /// Epochs skipped because a file could not be read
+    pub skipped: Vec<String>,
+    /// Reference validation after the repairs (before them on a dry run)
+    pub validation: ValidationReport,
+}
+
+impl MaintainReport {
+    /// Total number of changed records across all epochs.
+    pub fn changes(&self) -> usize {
+        self.epochs.iter().map(|e| e.changes()).sum()
+    }
+
+    /// Process exit code: 1 if validation failed or an epoch was unreadable,
+    /// or on validation warnings when `strict`.
+    pub fn exit_code(&self, strict: bool) -> i32 {
+        if !self.skipped.is_empty() {
+            1
+        } else {
+            self.validation.exit_code(strict)
+        }
+    }
+}
+
+fn read_entity<T: DeserializeOwned>(
+    config: &StorageConfig,
+    entity: EntityType,
+    epoch_id: &str,
+) -> Result<Vec<T>, StorageError> {
+    JsonlReader::for_entity(config, entity, epoch_id).read_all()
+}
+
+/// Replace an entity's files with `entities` in the shared epoch file.
+///
+/// Every existing file (shared and per-source partitions) is first copied
+/// to `*.pre-maintain.bak`; partitions are then removed, since their rows are
+/// now in the shared file.
+fn rewrite_entity<T: Serialize>(
+    config: &StorageConfig,
+    entity: EntityType,
+    epoch_id: &str,
+    entities: &[T],
+) -> Result<(), StorageError> {
+    let shared = entity_path(config, entity, epoch_id);
+    for path in entity_paths(config, entity, epoch_id) {
+        fs::copy(&path, path.with_extension("jsonl.pre-maintain.bak"))?;
+        if path != shared {
+            fs::remove_file(&path)?;
+        }
+    }
+    JsonlWriter::for_entity(config, entity, epoch_id).write_all(entities)?;
+    Ok(())
+}
+
+fn file_label(config: &StorageConfig, entity: EntityType, epoch_id: &str) -> String {
+    let path = entity_path(config, entity, epoch_id);
+    path.strip_prefix(config.normalized_dir())
+        .unwrap_or(Path::new(entity.filename()))
+        .display()
+        .to_string()
+}
+
+/// Repair one epoch. Returns `None` if any of its files could not be read,
+/// in which case nothing is written.
+fn maintain_epoch(
+    config: &StorageConfig,
+    epoch_id: &str,
+    dry_run: bool,
+) -> Result<Option<EpochMaintenance>, StorageError> {
+    let (events, placements, lists, pairings) = match (
+        read_entity::<Event>(config, EntityType::Event, epoch_id),
+        read_entity::<Placement>(config, EntityType::Placement, epoch_id),
+        read_entity::<ArmyList>(config, EntityType::ArmyList, epoch_id),
+        read_entity::<Pairing>(config, EntityType::Pairing, epoch_id),
+    ) {
+        (Ok(e), Ok(p), Ok(l), Ok(pr)) => (e, p, l, pr),
+        _ => return Ok(None),
+    };
+
+    let mut report = EpochMaintenance {
+        epoch_id: epoch_id.to_string(),
+        ..Default::default()
+    };
+
+    // 1. Duplicate compaction
+    let counts = (events.len(), placements.len(), lists.len(), pairings.len());
+    let mut events = merge_by_id(events, |e| e.id.as_str());
+    let mut placements = merge_by_id(placements, |p| p.id.as_str());
+    let mut lists = merge_by_id(lists, |l| l.id.as_str());
+    let pairings = merge_by_id(pairings, |p| p.id.as_str());
+    let dupes = [
+        ("events", counts.0 - events.len()),
+        ("placements", counts.1 - placements.len()),
+        ("army lists", counts.2 - lists.len()),
+        ("pairings", counts.3 - pairings.len()),
+    ];
+    for (name, removed) in dupes.iter().filter(|(_, n)| *n > 0) {
+        report
+            .items
+            .push(format!("[dedup] {} duplicate {} removed", removed, name));
+    }
+    report.duplicates_removed = dupes.iter().map(|(_, n)| n).sum();
+
+    // 2. Faction taxonomy
+    let (changed, items) = reclassify_placements(&mut placements);
+    report.placements_reclassified = changed;
+    report.items.extend(items);
+    let (changed, items) = reclassify_lists(&mut lists);
+    report.lists_reclassified = changed;
+    report.items.extend(items);
+
+    // 3. List linking and clone flags
+    let clone_before: Vec<Option<ArmyListId>> = lists.iter().map(|l| l.clone_of.clone()).collect();
+    report.links = link_lists(&events, &mut placements, &mut lists);
+    report.clone_changes = lists
+        .iter()
+        .zip(&clone_before)
+        .filter(|(l, before)| &l.clone_of != *before)
+        .count();
+    if report.links.lists_linked > 0 {
+        report.items.push(format!(
+            "[link] {} lists linked to events",
+            report.links.lists_linked
+        ));
+    }
+    if report.links.placements_linked > 0 {
+        report.items.push(format!(
+            "[link] {} placements linked to lists",
+            report.links.placements_linked
+        ));
+    }
+    if report.clone_changes > 0 {
+        report.items.push(format!(
+            "[clones] {} clone flags updated",
+            report.clone_changes
+        ));
+    }
+
+    // 4. Points validation
+    report.points_flag_changes = flag_lists(&events, &mut lists);
+    if report.points_flag_changes > 0 {
+        let flagged = lists.iter().filter(|l| !l.quality_flags.is_empty()).count();
+        report.items.push(format!(
+            "[points] {} lists re-flagged ({} with points problems)",
+            report.points_flag_changes, flagged
+        ));
+    }
+
+    // 5. Game sizes
+    let (changed, items) = fill_game_sizes(&mut events, &lists);
+    report.game_sizes_detected = changed;
+    report.items.extend(items);
+
+    if dry_run {
+        return Ok(Some(report));
+    }
+
+    let placements_changed =
+        dupes[1].1 > 0 || report.placements_reclassified > 0 || report.links.placements_linked > 0;
+    let lists_changed = dupes[2].1 > 0
+        || report.lists_reclassified > 0
+        || report.links.lists_linked > 0
+        || report.clone_changes > 0
+        || report.points_flag_changes > 0;
+
+    if dupes[0].1 > 0 || report.game_sizes_detected > 0 {
+        rewrite_entity(config, EntityType::Event, epoch_id, &events)?;
+        report
+            .files_written
+            .push(file_label(config, EntityType::Event, epoch_id));
+    }
+    if placements_changed {
+        rewrite_entity(config, EntityType::Placement, epoch_id, &placements)?;
+        report
+            .files_written
+            .push(file_label(config, EntityType::Placement, epoch_id));
+    }
+    if lists_changed {
+        rewrite_entity(config, EntityType::ArmyList, epoch_id, &lists)?;
+        report
+            .files_written
+            .push(file_label(config, EntityType::ArmyList, epoch_id));
+    }
+    if dupes[3].1 > 0 {
+        rewrite_entity(config, EntityType::Pairing, epoch_id, &pairings)?;
+        report
+            .files_written
+            .push(file_label(config, EntityType::Pairing, epoch_id));
+    }
+
+    Ok(Some(report))
+}
+
+/// Run every repair over all epochs, then validate references.
+///
+/// With `dry_run`, nothing is written and validation reflects the data as
+/// it is on disk.
+pub fn run_maintenance(
+    config: &StorageConfig,
+    dry_run: bool,
+) -> Result<MaintainReport, StorageError> {
+    let mut report = MaintainReport {
+        dry_run,
+        ..Default::default()
+    };
+
+    for epoch_id in list_epochs(config)? {
+        if epoch_id.ends_with(".bak") {
+            continue;
+        }
+        match maintain_epoch(config, &epoch_id, dry_run)? {
+            Some(epoch) => report.epochs.push(epoch),
+            None => {
+                tracing::warn!("Skipping epoch {}: unreadable entity file", epoch_id);
+                report.skipped.push(epoch_id);
+            }
+        }
+    }
+
+    report.validation = validate_storage(config)?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EntityId;
+    use chrono::NaiveDate;
+
+    fn setup(storage: &StorageConfig) {
+        let epoch = EntityId::from("e1");
+        let event = Event::new(
+            "Test GT".to_string(),
+            NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            "https://example.com/gt".to_string(),
+            "bcp".to_string(),
+            epoch.clone(),
+        );
+        let placement = Placement::new(
+            event.id.clone(),
+            epoch.clone(),
+            1,
+            "Alice".to_string(),
+            "Adeptus Astartes".to_string(),
+        );
+        let list = ArmyList::new(
+            "Space Marines".to_string(),
+            2000,
+            vec![],
+            "list".to_string(),
+        )
+        .with_player_name("alice".to_string())
+        .with_source_url("https://example.com/gt".to_string());
+
+        JsonlWriter::for_entity(storage, EntityType::Event, "e1")
+            .write_all(&[event.clone(), event])
+            .unwrap();
+        JsonlWriter::for_entity(storage, EntityType::Placement, "e1")
+            .write_all(&[placement])
+            .unwrap();
+        JsonlWriter::for_entity(storage, EntityType::ArmyList, "e1")
+            .write_all(&[list])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_dry_run_reports_without_writing() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        setup(&storage);
+
+        let report = run_maintenance(&storage, true).unwrap();
+        let epoch = &report.epochs[0];
+        assert_eq!(epoch.duplicates_removed, 1);
+        assert_eq!(epoch.placements_reclassified, 1);
+        assert_eq!(epoch.links.lists_linked, 1);
+        assert_eq!(epoch.links.placements_linked, 1);
+        assert!(epoch.files_written.is_empty());
+        // Validation sees the duplicate still on disk
+        assert!(report.validation.warnings > 0);
+
+        let events: Vec<Event> = read_entity(&storage, EntityType::Event, "e1").unwrap();
+        assert_eq!(events.len(), 2);
+    }
+
+    #[test]
+    fn test_maintenance_repairs_and_is_idempotent() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        setup(&storage);
+
+        let report = run_maintenance(&storage, false).unwrap();
+        assert_eq!(report.epochs[0].files_written.len(), 3);
+        assert_eq!(report.exit_code(true), 0);
+
+        let placements: Vec<Placement> =
+            read_entity(&storage, EntityType::Placement, "e1").unwrap();
+        assert_eq!(placements[0].faction, "Space Marines");
+        assert_eq!(placements[0].allegiance.as_deref(), Some("Imperium"));
+        assert!(placements[0].list_id.is_some());
+        // Game size comes from the newly linked list's total
+        assert_eq!(report.epochs[0].game_sizes_detected, 1);
+        let events: Vec<Event> = read_entity(&storage, EntityType::Event, "e1").unwrap();
+        assert_eq!(events[0].game_size, Some(2000));
+        assert!(storage
+            .normalized_dir()
+            .join("e1/events.jsonl.pre-maintain.bak")
+            .exists());
+
+        let again = run_maintenance(&storage, false).unwrap();
+        assert_eq!(again.changes(), 0);
+        assert!(again.epochs[0].files_written.is_empty());
+    }
+}
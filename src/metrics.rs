@@ -0,0 +1,296 @@
+//! Process metrics in the Prometheus text exposition format.
+//!
+//! One [`Metrics`] registry lives for the whole process. The API server
+//! shares it through `AppState` and records every routed request; the sync
+//! orchestrator, agents and fetcher have no server state and record into
+//! [`Metrics::global`], which is the same instance. `GET /metrics` renders
+//! it for scraping.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+/// Upper bounds, in seconds, of the request latency buckets.
+const REQUEST_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Upper bounds, in seconds, of the sync run duration buckets.
+const SYNC_BUCKETS: &[f64] = &[
+    1.0, 5.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1800.0, 3600.0,
+];
+
+/// Content type of the text exposition format.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Cumulative histogram over fixed buckets.
+#[derive(Debug, Clone)]
+struct Histogram {
+    bounds: &'static [f64],
+    /// Observations at or below each bound (not yet cumulative)
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            counts: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        if let Some(i) = self.bounds.iter().position(|&b| value <= b) {
+            self.counts[i] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        let sep = if labels.is_empty() { "" } else { "," };
+        let mut cumulative = 0;
+        for (bound, count) in self.bounds.iter().zip(&self.counts) {
+            cumulative += count;
+            let _ = writeln!(
+                out,
+                "{}_bucket{{{}{}le=\"{}\"}} {}",
+                name, labels, sep, bound, cumulative
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{}_bucket{{{}{}le=\"+Inf\"}} {}",
+            name, labels, sep, self.count
+        );
+        let braces = |s: &str| {
+            if s.is_empty() {
+                String::new()
+            } else {
+                format!("{{{}}}", s)
+            }
+        };
+        let _ = writeln!(out, "{}_sum{} {}", name, braces(labels), self.sum);
+        let _ = writeln!(out, "{}_count{} {}", name, braces(labels), self.count);
+    }
+}
+
+#[derive(Debug)]
+struct Registry {
+    /// (route, method, status) to requests served
+    requests: BTreeMap<(String, String, u16), u64>,
+    /// Route to request latency
+    latency: BTreeMap<String, Histogram>,
+    /// Outcome to sync runs
+    sync_runs: BTreeMap<&'static str, u64>,
+    sync_duration: Histogram,
+    /// (agent, outcome) to calls
+    agent_calls: BTreeMap<(String, &'static str), u64>,
+    cache_hits: u64,
+    cache_misses: u64,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self {
+            requests: BTreeMap::new(),
+            latency: BTreeMap::new(),
+            sync_runs: BTreeMap::new(),
+            sync_duration: Histogram::new(SYNC_BUCKETS),
+            agent_calls: BTreeMap::new(),
+            cache_hits: 0,
+            cache_misses: 0,
+        }
+    }
+}
+
+/// Counters and histograms for a running process.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    registry: Mutex<Registry>,
+}
+
+/// Escape a label value for the exposition format.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+impl Metrics {
+    /// The process-wide registry.
+    pub fn global() -> Arc<Metrics> {
+        static GLOBAL: OnceLock<Arc<Metrics>> = OnceLock::new();
+        GLOBAL.get_or_init(|| Arc::new(Metrics::default())).clone()
+    }
+
+    fn registry(&self) -> std::sync::MutexGuard<'_, Registry> {
+        self.registry.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record an API request against its route pattern.
+    pub fn record_request(&self, route: &str, method: &str, status: u16, elapsed: Duration) {
+        let mut r = self.registry();
+        *r.requests
+            .entry((route.to_string(), method.to_string(), status))
+            .or_default() += 1;
+        r.latency
+            .entry(route.to_string())
+            .or_insert_with(|| Histogram::new(REQUEST_BUCKETS))
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Record a finished sync run. `outcome` is `ok`, `partial` (some
+    /// sources failed) or `cancelled`.
+    pub fn record_sync_run(&self, outcome: &'static str, elapsed: Duration) {
+        let mut r = self.registry();
+        *r.sync_runs.entry(outcome).or_default() += 1;
+        r.sync_duration.observe(elapsed.as_secs_f64());
+    }
+
+    /// Record one agent call attempt; retries count as separate calls.
+    pub fn record_agent_call(&self, agent: &str, ok: bool) {
+        let outcome = if ok { "ok" } else { "error" };
+        *self
+            .registry()
+            .agent_calls
+            .entry((agent.to_string(), outcome))
+            .or_default() += 1;
+    }
+
+    /// Record whether a fetch was served from the HTTP cache.
+    pub fn record_cache_lookup(&self, hit: bool) {
+        let mut r = self.registry();
+        if hit {
+            r.cache_hits += 1;
+        } else {
+            r.cache_misses += 1;
+        }
+    }
+
+    /// Render every metric in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let r = self.registry();
+        let mut out = String::new();
+
+        out.push_str("# HELP meta_http_requests_total API requests served.\n");
+        out.push_str("# TYPE meta_http_requests_total counter\n");
+        for ((route, method, status), count) in &r.requests {
+            let _ = writeln!(
+                out,
+                "meta_http_requests_total{{route=\"{}\",method=\"{}\",status=\"{}\"}} {}",
+                escape(route),
+                escape(method),
+                status,
+                count
+            );
+        }
+
+        out.push_str("# HELP meta_http_request_duration_seconds API request latency.\n");
+        out.push_str("# TYPE meta_http_request_duration_seconds histogram\n");
+        for (route, histogram) in &r.latency {
+            histogram.render(
+                &mut out,
+                "meta_http_request_duration_seconds",
+                &format!("route=\"{}\"", escape(route)),
+            );
+        }
+
+        out.push_str("# HELP meta_sync_runs_total Sync runs by outcome.\n");
+        out.push_str("# TYPE meta_sync_runs_total counter\n");
+        for (outcome, count) in &r.sync_runs {
+            let _ = writeln!(
+                out,
+                "meta_sync_runs_total{{outcome=\"{}\"}} {}",
+                outcome, count
+            );
+        }
+
+        out.push_str("# HELP meta_sync_duration_seconds Sync run duration.\n");
+        out.push_str("# TYPE meta_sync_duration_seconds histogram\n");
+        r.sync_duration
+            .render(&mut out, "meta_sync_duration_seconds", "");
+
+        out.push_str("# HELP meta_agent_calls_total AI agent calls by outcome.\n");
+        out.push_str("# TYPE meta_agent_calls_total counter\n");
+        for ((agent, outcome), count) in &r.agent_calls {
+            let _ = writeln!(
+                out,
+                "meta_agent_calls_total{{agent=\"{}\",outcome=\"{}\"}} {}",
+                escape(agent),
+                outcome,
+                count
+            );
+        }
+
+        out.push_str("# HELP meta_fetch_cache_lookups_total Fetches by cache result.\n");
+        out.push_str("# TYPE meta_fetch_cache_lookups_total counter\n");
+        let _ = writeln!(
+            out,
+            "meta_fetch_cache_lookups_total{{result=\"hit\"}} {}",
+            r.cache_hits
+        );
+        let _ = writeln!(
+            out,
+            "meta_fetch_cache_lookups_total{{result=\"miss\"}} {}",
+            r.cache_misses
+        );
+
+        let lookups = r.cache_hits + r.cache_misses;
+        out.push_str("# HELP meta_fetch_cache_hit_ratio Share of fetches served from cache.\n");
+        out.push_str("# TYPE meta_fetch_cache_hit_ratio gauge\n");
+        let ratio = if lookups == 0 {
+            0.0
+        } else {
+            r.cache_hits as f64 / lookups as f64
+        };
+        let _ = writeln!(out, "meta_fetch_cache_hit_ratio {}", ratio);
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_exposition_format() {
+        let metrics = Metrics::default();
+        metrics.record_request("/api/events", "GET", 200, Duration::from_millis(30));
+        metrics.record_request("/api/events", "GET", 200, Duration::from_secs(20));
+        metrics.record_sync_run("ok", Duration::from_secs(42));
+        metrics.record_agent_call("event_scout", true);
+        metrics.record_agent_call("event_scout", false);
+        metrics.record_cache_lookup(true);
+        metrics.record_cache_lookup(true);
+        metrics.record_cache_lookup(false);
+
+        let text = metrics.render();
+        assert!(text.contains(
+            "meta_http_requests_total{route=\"/api/events\",method=\"GET\",status=\"200\"} 2"
+        ));
+        // 30ms lands in the 0.05 bucket; 20s only in +Inf
+        assert!(text.contains(
+            "meta_http_request_duration_seconds_bucket{route=\"/api/events\",le=\"0.025\"} 0"
+        ));
+        assert!(text.contains(
+            "meta_http_request_duration_seconds_bucket{route=\"/api/events\",le=\"0.05\"} 1"
+        ));
+        assert!(text.contains(
+            "meta_http_request_duration_seconds_bucket{route=\"/api/events\",le=\"+Inf\"} 2"
+        ));
+        assert!(text.contains("meta_sync_duration_seconds_bucket{le=\"60\"} 1"));
+        assert!(text.contains("meta_sync_duration_seconds_count 1"));
+        assert!(text.contains("meta_sync_runs_total{outcome=\"ok\"} 1"));
+        assert!(text.contains("meta_agent_calls_total{agent=\"event_scout\",outcome=\"error\"} 1"));
+        assert!(text.contains("meta_fetch_cache_hit_ratio 0.6666666666666666"));
+        assert_eq!(escape("a\"b\\"), "a\\\"b\\\\");
+    }
+}
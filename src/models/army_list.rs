@@ -23,6 +23,10 @@ pub struct Unit {
 
     /// Keywords (if known)
     pub keywords: Vec<String>,
+
+    /// Detachment Enhancement this character takes, kept out of `wargear`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enhancement: Option<String>,
 }
 
 impl Unit {
@@ -34,6 +38,7 @@ impl Unit {
             points: None,
             wargear: Vec::new(),
             keywords: Vec::new(),
+            enhancement: None,
         }
     }
 
@@ -54,6 +59,71 @@ impl Unit {
         self.keywords = keywords;
         self
     }
+
+    /// Builder method to set the Enhancement.
+    pub fn with_enhancement(mut self, enhancement: impl Into<String>) -> Self {
+        self.enhancement = Some(enhancement.into());
+        self
+    }
+}
+
+/// A points problem found by [`crate::calculate::validation`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum QualityFlag {
+    /// Unit points don't add up to the list's total
+    TotalMismatch { total: u32, unit_points: u32 },
+    /// Total is over the event's points limit
+    OverLimit { total: u32, limit: u32 },
+    /// Total is far under the event's points limit, e.g. a 1000 point list
+    /// at a 2000 point event
+    UnderLimit { total: u32, limit: u32 },
+    /// A unit costs more than any single unit can
+    UnitCost { unit: String, points: u32 },
+}
+
+impl QualityFlag {
+    /// The serialized `kind` tag.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            QualityFlag::TotalMismatch { .. } => "total_mismatch",
+            QualityFlag::OverLimit { .. } => "over_limit",
+            QualityFlag::UnderLimit { .. } => "under_limit",
+            QualityFlag::UnitCost { .. } => "unit_cost",
+        }
+    }
+}
+
+impl std::fmt::Display for QualityFlag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QualityFlag::TotalMismatch { total, unit_points } => write!(
+                f,
+                "units add up to {} pts but the list totals {}",
+                unit_points, total
+            ),
+            QualityFlag::OverLimit { total, limit } => {
+                write!(f, "{} pts is over the {} pt limit", total, limit)
+            }
+            QualityFlag::UnderLimit { total, limit } => {
+                write!(f, "{} pts is far under the {} pt limit", total, limit)
+            }
+            QualityFlag::UnitCost { unit, points } => {
+                write!(f, "{} costs an implausible {} pts", unit, points)
+            }
+        }
+    }
+}
+
+/// Enhancements carried by units, each once, in unit order.
+fn enhancements_of(units: &[Unit]) -> Vec<String> {
+    let mut enhancements: Vec<String> = Vec::new();
+    for name in units.iter().filter_map(|u| u.enhancement.as_ref()) {
+        if !enhancements.contains(name) {
+            enhancements.push(name.clone());
+        }
+    }
+    enhancements
 }
 
 /// A normalized army list.
@@ -81,6 +151,11 @@ pub struct ArmyList {
     /// Units in the list
     pub units: Vec<Unit>,
 
+    /// Enhancements taken, in unit order; kept in step with the units'
+    /// by [`ArmyList::new`] and [`ArmyList::set_units`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enhancements: Vec<String>,
+
     /// Original raw text (for audit)
     pub raw_text: String,
 
@@ -100,6 +175,10 @@ pub struct ArmyList {
     /// When this record was created
     pub created_at: DateTime<Utc>,
 
+    /// When this record was last changed (unset = not since it was created)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+
     /// Confidence level of the extraction
     pub extraction_confidence: Confidence,
 
@@ -108,6 +187,26 @@ pub struct ArmyList {
 
     /// Path to the raw source file
     pub raw_source_path: Option<PathBuf>,
+
+    /// Original list this one was copied from (same event, near-identical raw text)
+    #[serde(default)]
+    pub clone_of: Option<ArmyListId>,
+
+    /// AI model that extracted this record (e.g. "llama3.2:latest");
+    /// `None` for records parsed without AI
+    #[serde(default)]
+    pub extracted_by: Option<String>,
+
+    /// Version of the extracting agent ([`crate::agents::Agent::version`]);
+    /// `None` for records parsed without AI or extracted before versions
+    /// were recorded
+    #[serde(default)]
+    pub extraction_agent_version: Option<u32>,
+
+    /// Points problems from the last validation; empty when the list is
+    /// consistent or hasn't been validated
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quality_flags: Vec<QualityFlag>,
 }
 
 impl ArmyList {
@@ -132,6 +231,7 @@ impl ArmyList {
             allegiance: None,
             detachment: None,
             total_points,
+            enhancements: enhancements_of(&units),
             units,
             raw_text,
             player_name: None,
@@ -139,9 +239,14 @@ impl ArmyList {
             event_id: None,
             source_url: None,
             created_at: Utc::now(),
+            updated_at: None,
             extraction_confidence: Confidence::default(),
             needs_review: false,
             raw_source_path: None,
+            clone_of: None,
+            extracted_by: None,
+            extraction_agent_version: None,
+            quality_flags: Vec::new(),
         }
     }
 
@@ -163,6 +268,12 @@ impl ArmyList {
         self
     }
 
+    /// Builder method to record the AI model that extracted this record.
+    pub fn with_extracted_by(mut self, model: impl Into<String>) -> Self {
+        self.extracted_by = Some(model.into());
+        self
+    }
+
     /// Builder method to set subfaction.
     pub fn with_subfaction(mut self, subfaction: String) -> Self {
         self.subfaction = Some(subfaction);
@@ -212,6 +323,12 @@ impl ArmyList {
         self
     }
 
+    /// Replace the units, and the Enhancements they carry.
+    pub fn set_units(&mut self, units: Vec<Unit>) {
+        self.enhancements = enhancements_of(&units);
+        self.units = units;
+    }
+
     /// Get unit names for analysis.
     pub fn unit_names(&self) -> Vec<&str> {
         self.units.iter().map(|u| u.name.as_str()).collect()
@@ -230,6 +347,41 @@ impl ArmyList {
             .map(|u| u.count)
             .sum()
     }
+
+    /// Hash of the faction, detachment and unit multiset (names and model
+    /// counts), ignoring points, wargear and entry order.
+    ///
+    /// Lists with the same hash are the same "netlist" even if points
+    /// changed between dataslates or the units were entered differently.
+    pub fn netlist_hash(&self) -> String {
+        let mut counts: std::collections::BTreeMap<String, u32> = Default::default();
+        for unit in &self.units {
+            let name = unit
+                .name
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase();
+            *counts.entry(name).or_default() += unit.count.max(1);
+        }
+        let units = counts
+            .iter()
+            .map(|(name, count)| format!("{}x{}", count, name))
+            .collect::<Vec<_>>()
+            .join(",");
+        EntityId::generate(&[
+            &self.faction.trim().to_lowercase(),
+            &self
+                .detachment
+                .as_deref()
+                .unwrap_or("")
+                .trim()
+                .to_lowercase(),
+            &units,
+        ])
+        .as_str()
+        .to_string()
+    }
 }
 
 #[cfg(test)]
@@ -258,6 +410,29 @@ mod tests {
         assert_eq!(unit.keywords.len(), 2);
     }
 
+    #[test]
+    fn test_netlist_hash_ignores_points_and_order() {
+        let list = ArmyList::new(
+            "Aeldari".to_string(),
+            2000,
+            create_test_units(),
+            String::new(),
+        );
+        let mut reordered: Vec<Unit> = create_test_units().into_iter().rev().collect();
+        reordered[0].points = Some(135);
+        reordered[1].name = "WRAITHGUARD ".to_string();
+        let copy = ArmyList::new("aeldari".to_string(), 1985, reordered, String::new());
+        assert_eq!(list.netlist_hash(), copy.netlist_hash());
+        assert_ne!(list.id, copy.id);
+
+        let other_detachment = list.clone().with_detachment("Seer Council".to_string());
+        assert_ne!(list.netlist_hash(), other_detachment.netlist_hash());
+
+        let mut fewer = copy.clone();
+        fewer.units[1].count = 3;
+        assert_ne!(list.netlist_hash(), fewer.netlist_hash());
+    }
+
     #[test]
     fn test_army_list_creation() {
         let units = create_test_units();
@@ -0,0 +1,160 @@
+//! Single-elimination cut (bracket) model.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{EntityId, EpochId, EventId};
+
+/// Type alias for bracket match IDs.
+pub type BracketMatchId = EntityId;
+
+/// Stage of a single-elimination cut, counted back from the final.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BracketStage {
+    Final,
+    Semifinal,
+    Quarterfinal,
+    RoundOf16,
+    /// Round of 32 or earlier
+    RoundOf32,
+}
+
+impl BracketStage {
+    /// Stage for a cut round, given how many rounds remain after it
+    /// (0 = the final).
+    pub fn from_rounds_remaining(remaining: u32) -> Self {
+        match remaining {
+            0 => BracketStage::Final,
+            1 => BracketStage::Semifinal,
+            2 => BracketStage::Quarterfinal,
+            3 => BracketStage::RoundOf16,
+            _ => BracketStage::RoundOf32,
+        }
+    }
+
+    /// Human-readable label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            BracketStage::Final => "Final",
+            BracketStage::Semifinal => "Semifinal",
+            BracketStage::Quarterfinal => "Quarterfinal",
+            BracketStage::RoundOf16 => "Round of 16",
+            BracketStage::RoundOf32 => "Round of 32",
+        }
+    }
+}
+
+/// A single game in the top cut played after the Swiss rounds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BracketMatch {
+    /// Unique identifier
+    pub id: BracketMatchId,
+
+    /// Event this match belongs to
+    pub event_id: EventId,
+
+    /// Epoch this match belongs to
+    pub epoch_id: EpochId,
+
+    /// Overall round number (continuing on from the Swiss rounds)
+    pub round: u32,
+
+    /// Bracket stage
+    pub stage: BracketStage,
+
+    /// Player 1 name
+    pub player1_name: String,
+
+    /// Player 1 faction
+    pub player1_faction: Option<String>,
+
+    /// Player 2 name
+    pub player2_name: String,
+
+    /// Player 2 faction
+    pub player2_faction: Option<String>,
+
+    /// Name of the player who advanced (None if not reported)
+    pub winner_name: Option<String>,
+
+    /// Player 1 game points
+    pub player1_game_points: Option<u32>,
+
+    /// Player 2 game points
+    pub player2_game_points: Option<u32>,
+
+    /// When this record was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl BracketMatch {
+    /// Create a new BracketMatch with auto-generated ID.
+    pub fn new(
+        event_id: EventId,
+        epoch_id: EpochId,
+        round: u32,
+        stage: BracketStage,
+        player1_name: String,
+        player2_name: String,
+    ) -> Self {
+        let id = EntityId::generate(&[
+            event_id.as_str(),
+            "bracket",
+            &round.to_string(),
+            &player1_name,
+            &player2_name,
+        ]);
+
+        Self {
+            id,
+            event_id,
+            epoch_id,
+            round,
+            stage,
+            player1_name,
+            player1_faction: None,
+            player2_name,
+            player2_faction: None,
+            winner_name: None,
+            player1_game_points: None,
+            player2_game_points: None,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bracket_stage_from_rounds_remaining() {
+        assert_eq!(BracketStage::from_rounds_remaining(0), BracketStage::Final);
+        assert_eq!(
+            BracketStage::from_rounds_remaining(2),
+            BracketStage::Quarterfinal
+        );
+        assert_eq!(
+            BracketStage::from_rounds_remaining(6),
+            BracketStage::RoundOf32
+        );
+        assert!(BracketStage::Final < BracketStage::Semifinal);
+    }
+
+    #[test]
+    fn test_bracket_match_serialization() {
+        let m = BracketMatch::new(
+            EntityId::from("event-1"),
+            EntityId::from("epoch-1"),
+            6,
+            BracketStage::Quarterfinal,
+            "Alice".to_string(),
+            "Bob".to_string(),
+        );
+        let json = serde_json::to_string(&m).unwrap();
+        assert!(json.contains("\"stage\":\"quarterfinal\""));
+        let back: BracketMatch = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.id, m.id);
+    }
+}
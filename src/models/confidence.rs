@@ -25,6 +25,45 @@ impl Confidence {
     pub fn needs_review(&self) -> bool {
         matches!(self, Confidence::Low)
     }
+
+    /// Ordinal level (low = 0, high = 2).
+    fn level(&self) -> u8 {
+        match self {
+            Confidence::High => 2,
+            Confidence::Medium => 1,
+            Confidence::Low => 0,
+        }
+    }
+
+    /// Returns true if this is at least `min`.
+    pub fn meets(&self, min: Confidence) -> bool {
+        self.level() >= min.level()
+    }
+
+    /// Weight given to a row of this confidence when down-weighting.
+    pub fn weight(&self) -> f64 {
+        match self {
+            Confidence::High => 1.0,
+            Confidence::Medium => 0.7,
+            Confidence::Low => 0.3,
+        }
+    }
+}
+
+impl std::str::FromStr for Confidence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "high" => Ok(Confidence::High),
+            "medium" => Ok(Confidence::Medium),
+            "low" => Ok(Confidence::Low),
+            _ => Err(format!(
+                "Unknown confidence '{}' (expected high, medium or low)",
+                s
+            )),
+        }
+    }
 }
 
 impl std::fmt::Display for Confidence {
@@ -55,6 +94,15 @@ mod tests {
         assert!(Confidence::Low.needs_review());
     }
 
+    #[test]
+    fn test_confidence_meets() {
+        assert!(Confidence::High.meets(Confidence::Medium));
+        assert!(Confidence::Medium.meets(Confidence::Medium));
+        assert!(!Confidence::Low.meets(Confidence::Medium));
+        assert_eq!("HIGH".parse::<Confidence>().unwrap(), Confidence::High);
+        assert!("certain".parse::<Confidence>().is_err());
+    }
+
     #[test]
     fn test_confidence_serialization() {
         let high = Confidence::High;
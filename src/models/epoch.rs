@@ -1,6 +1,6 @@
 //! Meta epochs - time periods between significant events.
 
-use chrono::NaiveDate;
+use chrono::{NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 use super::{EntityId, EpochId, SignificantEvent, SignificantEventId};
@@ -20,9 +20,14 @@ pub struct MetaEpoch {
     /// ID of the significant event that started this epoch
     pub start_event_id: SignificantEventId,
 
-    /// Start date of the epoch
+    /// Start date of the epoch (effective date of its significant event)
     pub start_date: NaiveDate,
 
+    /// Days after `start_date` during which tournaments still belong to the
+    /// previous epoch
+    #[serde(default)]
+    pub grace_days: u32,
+
     /// End date of the epoch (None if current)
     pub end_date: Option<NaiveDate>,
 
@@ -31,6 +36,10 @@ pub struct MetaEpoch {
 
     /// Whether this is the current active epoch
     pub is_current: bool,
+
+    /// Announced but not yet in effect
+    #[serde(default)]
+    pub is_provisional: bool,
 }
 
 impl MetaEpoch {
@@ -43,10 +52,12 @@ impl MetaEpoch {
             id,
             name,
             start_event_id: event.id.clone(),
-            start_date: event.date,
+            start_date: event.effective_from(),
+            grace_days: event.grace_days,
             end_date: None,
             end_event_id: None,
             is_current: true,
+            is_provisional: false,
         }
     }
 
@@ -57,23 +68,30 @@ impl MetaEpoch {
             name: "Pre-Tracking".to_string(),
             start_event_id: EntityId::from("genesis"),
             start_date: NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+            grace_days: 0,
             end_date: None,
             end_event_id: None,
             is_current: false,
+            is_provisional: false,
         }
     }
 
+    /// First date on which tournaments are assigned to this epoch.
+    pub fn boundary_date(&self) -> NaiveDate {
+        self.start_date + chrono::Days::new(self.grace_days as u64)
+    }
+
     /// Close this epoch with the next significant event.
     pub fn close_with(&mut self, next_event: &SignificantEvent) {
-        // End date is the day before the next epoch starts
-        self.end_date = next_event.date.pred_opt();
+        // End date is the day before the next epoch takes over
+        self.end_date = next_event.boundary_date().pred_opt();
         self.end_event_id = Some(next_event.id.clone());
         self.is_current = false;
     }
 
     /// Check if a date falls within this epoch.
     pub fn contains_date(&self, date: NaiveDate) -> bool {
-        if date < self.start_date {
+        if date < self.boundary_date() {
             return false;
         }
         match self.end_date {
@@ -84,7 +102,7 @@ impl MetaEpoch {
 }
 
 /// Manager for epoch mapping operations.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct EpochMapper {
     epochs: Vec<MetaEpoch>,
 }
@@ -98,15 +116,27 @@ impl EpochMapper {
     /// Create an EpochMapper from a list of significant events.
     /// Events should be sorted by date in ascending order.
     pub fn from_significant_events(events: &[SignificantEvent]) -> Self {
+        Self::from_significant_events_as_of(events, Utc::now().date_naive())
+    }
+
+    /// Create an EpochMapper, treating epochs that take effect after `today`
+    /// as provisional. The current epoch is the latest one already in effect.
+    pub fn from_significant_events_as_of(events: &[SignificantEvent], today: NaiveDate) -> Self {
         let mut mapper = Self::new();
 
         if events.is_empty() {
             return mapper;
         }
 
-        // Sort events by date
-        let mut sorted_events: Vec<_> = events.iter().collect();
-        sorted_events.sort_by_key(|e| e.date);
+        // Sort boundary events by effective date (FAQs only annotate)
+        let mut sorted_events: Vec<_> = events
+            .iter()
+            .filter(|e| e.event_type.is_epoch_boundary())
+            .collect();
+        if sorted_events.is_empty() {
+            return mapper;
+        }
+        sorted_events.sort_by_key(|e| e.effective_from());
 
         // Create epochs from events
         for (i, event) in sorted_events.iter().enumerate() {
@@ -119,21 +149,31 @@ impl EpochMapper {
                 }
             }
 
-            // Only the last epoch is current
-            epoch.is_current = i == sorted_events.len() - 1;
+            epoch.is_current = false;
+            epoch.is_provisional = epoch.start_date > today;
 
             mapper.epochs.push(epoch);
         }
 
+        // Only the latest epoch in effect is current (the last one if all
+        // are provisional)
+        let current = mapper
+            .epochs
+            .iter()
+            .rposition(|e| !e.is_provisional)
+            .unwrap_or(mapper.epochs.len() - 1);
+        mapper.epochs[current].is_current = true;
+
         mapper
     }
 
     /// Get the epoch for a given date.
     pub fn get_epoch_for_date(&self, date: NaiveDate) -> Option<&MetaEpoch> {
-        // Find the most recent epoch that starts on or before the date
+        // Find the most recent epoch whose boundary (start plus grace) is on
+        // or before the date
         self.epochs
             .iter()
-            .filter(|e| e.start_date <= date)
+            .filter(|e| e.boundary_date() <= date)
             .max_by_key(|e| e.start_date)
     }
 
@@ -161,6 +201,10 @@ impl EpochMapper {
 
     /// Add a new significant event and update epochs.
     pub fn add_significant_event(&mut self, event: &SignificantEvent) {
+        if !event.event_type.is_epoch_boundary() {
+            return;
+        }
+
         // Close current epoch if any
         if let Some(current) = self.epochs.iter_mut().find(|e| e.is_current) {
             current.close_with(event);
@@ -286,6 +330,34 @@ mod tests {
         assert!(epochs[1].end_date.is_some());
     }
 
+    #[test]
+    fn test_epoch_mapper_ignores_faq_updates() {
+        let faq = SignificantEvent::new(
+            SignificantEventType::FaqUpdate,
+            NaiveDate::from_ymd_opt(2025, 7, 1).unwrap(),
+            "Rules Commentary July 2025".to_string(),
+            "https://example.com".to_string(),
+        );
+        let events = vec![
+            create_test_event(
+                NaiveDate::from_ymd_opt(2025, 6, 15).unwrap(),
+                "Balance Dataslate June 2025",
+            ),
+            faq.clone(),
+        ];
+
+        let mut mapper = EpochMapper::from_significant_events(&events);
+        assert_eq!(mapper.all_epochs().len(), 1);
+        assert!(mapper.all_epochs()[0].end_date.is_none());
+
+        mapper.add_significant_event(&faq);
+        assert_eq!(mapper.all_epochs().len(), 1);
+
+        assert!(EpochMapper::from_significant_events(&[faq])
+            .all_epochs()
+            .is_empty());
+    }
+
     #[test]
     fn test_epoch_mapper_date_lookup() {
         let events = vec![
@@ -353,4 +425,58 @@ mod tests {
         assert_eq!(epoch.start_date, deserialized.start_date);
         assert_eq!(epoch.is_current, deserialized.is_current);
     }
+
+    #[test]
+    fn test_effective_date_and_grace_period() {
+        let announced = NaiveDate::from_ymd_opt(2025, 6, 10).unwrap();
+        let effective = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        let events = vec![
+            create_test_event(NaiveDate::from_ymd_opt(2025, 3, 15).unwrap(), "March"),
+            create_test_event(announced, "June")
+                .with_effective_date(effective)
+                .with_grace_days(2),
+        ];
+
+        let mapper = EpochMapper::from_significant_events(&events);
+        let june = &mapper.all_epochs()[1];
+        assert_eq!(june.start_date, effective);
+        assert_eq!(
+            mapper.all_epochs()[0].end_date,
+            NaiveDate::from_ymd_opt(2025, 6, 16)
+        );
+
+        // Between announcement and effective date: still the old epoch
+        let epoch = mapper.get_epoch_for_date(NaiveDate::from_ymd_opt(2025, 6, 12).unwrap());
+        assert!(epoch.unwrap().name.contains("March"));
+
+        // Boundary weekend inside the grace period: still the old epoch
+        let epoch = mapper.get_epoch_for_date(NaiveDate::from_ymd_opt(2025, 6, 16).unwrap());
+        assert!(epoch.unwrap().name.contains("March"));
+        assert!(mapper.all_epochs()[0].contains_date(NaiveDate::from_ymd_opt(2025, 6, 16).unwrap()));
+
+        let epoch = mapper.get_epoch_for_date(NaiveDate::from_ymd_opt(2025, 6, 17).unwrap());
+        assert!(epoch.unwrap().name.contains("June"));
+    }
+
+    #[test]
+    fn test_provisional_epoch() {
+        let today = NaiveDate::from_ymd_opt(2025, 6, 12).unwrap();
+        let events = vec![
+            create_test_event(NaiveDate::from_ymd_opt(2025, 3, 15).unwrap(), "March"),
+            create_test_event(NaiveDate::from_ymd_opt(2025, 6, 10).unwrap(), "June")
+                .with_effective_date(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap()),
+        ];
+
+        let mapper = EpochMapper::from_significant_events_as_of(&events, today);
+        let epochs = mapper.all_epochs();
+        assert!(epochs[1].is_provisional);
+        assert!(!epochs[1].is_current);
+        assert!(mapper.current_epoch().unwrap().name.contains("March"));
+
+        // Once in effect, the new epoch becomes current
+        let later = NaiveDate::from_ymd_opt(2025, 6, 20).unwrap();
+        let mapper = EpochMapper::from_significant_events_as_of(&events, later);
+        assert!(!mapper.all_epochs()[1].is_provisional);
+        assert!(mapper.current_epoch().unwrap().name.contains("June"));
+    }
 }
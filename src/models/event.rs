@@ -2,9 +2,198 @@
 
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use super::{
+    country_from_parts, geocode_location, region_for_country, Confidence, EntityId, EpochId,
+    EventId, Region,
+};
+
+/// Size class of an event by player count (an RTT plays very differently
+/// from a 200-player GT).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SizeBracket {
+    Small,
+    Medium,
+    Large,
+    Major,
+}
+
+impl SizeBracket {
+    pub const ALL: [SizeBracket; 4] = [
+        SizeBracket::Small,
+        SizeBracket::Medium,
+        SizeBracket::Large,
+        SizeBracket::Major,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SizeBracket::Small => "small",
+            SizeBracket::Medium => "medium",
+            SizeBracket::Large => "large",
+            SizeBracket::Major => "major",
+        }
+    }
+}
+
+impl fmt::Display for SizeBracket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SizeBracket {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "small" | "rtt" => Ok(SizeBracket::Small),
+            "medium" => Ok(SizeBracket::Medium),
+            "large" | "gt" => Ok(SizeBracket::Large),
+            "major" => Ok(SizeBracket::Major),
+            _ => Err(format!(
+                "Unknown size bracket '{}' (expected small, medium, large or major)",
+                s
+            )),
+        }
+    }
+}
+
+/// Minimum player count for each bracket above small (`[analytics.size_brackets]`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SizeThresholds {
+    pub medium: u32,
+    pub large: u32,
+    pub major: u32,
+}
+
+impl Default for SizeThresholds {
+    fn default() -> Self {
+        Self {
+            medium: 24,
+            large: 50,
+            major: 100,
+        }
+    }
+}
+
+impl SizeThresholds {
+    pub fn classify(&self, players: u32) -> SizeBracket {
+        if players >= self.major {
+            SizeBracket::Major
+        } else if players >= self.large {
+            SizeBracket::Large
+        } else if players >= self.medium {
+            SizeBracket::Medium
+        } else {
+            SizeBracket::Small
+        }
+    }
+
+    /// Smallest player count in a bracket.
+    pub fn min_players(&self, bracket: SizeBracket) -> u32 {
+        match bracket {
+            SizeBracket::Small => 0,
+            SizeBracket::Medium => self.medium,
+            SizeBracket::Large => self.large,
+            SizeBracket::Major => self.major,
+        }
+    }
+
+    /// Whether the thresholds are strictly increasing.
+    pub fn is_ordered(&self) -> bool {
+        0 < self.medium && self.medium < self.large && self.large < self.major
+    }
+}
 
-use super::{Confidence, EntityId, EpochId, EventId};
+/// Whether an event has been played. Events discovered ahead of time are
+/// stored as upcoming and flipped to completed once a sync sees them end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventStatus {
+    Upcoming,
+    #[default]
+    Completed,
+}
+
+impl EventStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EventStatus::Upcoming => "upcoming",
+            EventStatus::Completed => "completed",
+        }
+    }
+}
+
+impl fmt::Display for EventStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Known mission packs, with the lowercase phrases that name them.
+const MISSION_PACKS: &[(&str, &[&str])] = &[
+    ("Pariah Nexus", &["pariah nexus", "pariah"]),
+    ("Leviathan", &["leviathan"]),
+    (
+        "Chapter Approved 2025-26",
+        &["chapter approved 2025", "ca 2025", "ca25"],
+    ),
+    (
+        "Chapter Approved 2024",
+        &["chapter approved 2024", "ca 2024", "ca24"],
+    ),
+    (
+        "Chapter Approved 2023",
+        &["chapter approved 2023", "ca 2023", "ca23"],
+    ),
+];
+
+/// Canonical name for a mission pack as written by a source. Known packs
+/// are matched by phrase ("GW Pariah Nexus missions" is "Pariah Nexus");
+/// anything else is kept as trimmed. Blank input gives `None`.
+pub fn normalize_mission_pack(raw: &str) -> Option<String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let lower = trimmed.to_lowercase();
+    let known = MISSION_PACKS
+        .iter()
+        .find(|(_, phrases)| phrases.iter().any(|p| lower.contains(p)))
+        .map(|(name, _)| name.to_string());
+    Some(known.unwrap_or_else(|| trimmed.to_string()))
+}
+
+/// Battle sizes as event names spell them, with their points limits.
+const BATTLE_SIZES: &[(&str, u32)] = &[
+    ("combat patrol", 500),
+    ("incursion", 1000),
+    ("strike force", 2000),
+    ("onslaught", 3000),
+];
+
+/// Points limit stated in an event name: a battle size ("Incursion",
+/// "Combat Patrol") or an explicit limit ("1000pts", "1,000 points").
+pub fn game_size_from_name(name: &str) -> Option<u32> {
+    static POINTS: OnceLock<Regex> = OnceLock::new();
+    let lower = name.to_lowercase();
+    if let Some((_, size)) = BATTLE_SIZES.iter().find(|(size, _)| lower.contains(size)) {
+        return Some(*size);
+    }
+    POINTS
+        .get_or_init(|| Regex::new(r"\b(\d,?\d{3}|\d{3})\s*(?:pts?|points?)\b").unwrap())
+        .captures(&lower)
+        .and_then(|c| c[1].replace(',', "").parse().ok())
+}
 
 /// A tournament event.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +228,10 @@ pub struct Event {
     /// When this record was created
     pub created_at: DateTime<Utc>,
 
+    /// When this record was last changed (unset = not since it was created)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+
     /// Confidence level of the extraction
     pub extraction_confidence: Confidence,
 
@@ -47,6 +240,56 @@ pub struct Event {
 
     /// Path to the raw source file
     pub raw_source_path: Option<PathBuf>,
+
+    /// Name of the recurring series this event belongs to (e.g., "Las Vegas Open")
+    #[serde(default)]
+    pub series: Option<String>,
+
+    /// AI model that extracted this record (e.g. "llama3.2:latest");
+    /// `None` for records parsed without AI
+    #[serde(default)]
+    pub extracted_by: Option<String>,
+
+    /// Version of the extracting agent ([`crate::agents::Agent::version`]);
+    /// `None` for records parsed without AI or extracted before versions
+    /// were recorded
+    #[serde(default)]
+    pub extraction_agent_version: Option<u32>,
+
+    /// Listing this event was split from, when one listing hosted several
+    /// game-size brackets (each bracket is stored as its own event)
+    #[serde(default)]
+    pub parent_id: Option<EventId>,
+
+    /// Points limit played (e.g. 2000), when known
+    #[serde(default)]
+    pub game_size: Option<u32>,
+
+    /// Venue name, when the source gives it separately
+    #[serde(default)]
+    pub venue: Option<String>,
+
+    /// City
+    #[serde(default)]
+    pub city: Option<String>,
+
+    /// State / province
+    #[serde(default)]
+    pub state: Option<String>,
+
+    /// ISO 3166-1 alpha-2 country code (e.g. "US", "GB")
+    #[serde(default)]
+    pub country: Option<String>,
+
+    /// Mission pack or GW season played (e.g. "Pariah Nexus"), when the
+    /// source names it
+    #[serde(default)]
+    pub mission_pack: Option<String>,
+
+    /// Upcoming until played; events stored before this was tracked are
+    /// completed
+    #[serde(default)]
+    pub status: EventStatus,
 }
 
 impl Event {
@@ -72,19 +315,87 @@ impl Event {
             source_name,
             epoch_id,
             created_at: Utc::now(),
+            updated_at: None,
             extraction_confidence: Confidence::default(),
             needs_review: false,
             raw_source_path: None,
+            series: None,
+            extracted_by: None,
+            extraction_agent_version: None,
+            parent_id: None,
+            game_size: None,
+            venue: None,
+            city: None,
+            state: None,
+            country: None,
+            mission_pack: None,
+            status: EventStatus::Completed,
         }
     }
 
-    /// Regenerate ID with location included.
+    /// Builder method to set whether the event has been played.
+    pub fn with_status(mut self, status: EventStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn is_upcoming(&self) -> bool {
+        self.status == EventStatus::Upcoming
+    }
+
+    /// Builder method to record the AI model that extracted this record.
+    pub fn with_extracted_by(mut self, model: impl Into<String>) -> Self {
+        self.extracted_by = Some(model.into());
+        self
+    }
+
+    /// Regenerate ID with location included. The country is geocoded from
+    /// the location text unless already set.
     pub fn with_location(mut self, location: String) -> Self {
+        if self.country.is_none() {
+            self.country = geocode_location(&location).map(String::from);
+        }
         self.location = Some(location.clone());
         self.id = EntityId::generate(&[&self.name, &self.date.to_string(), &location]);
         self
     }
 
+    /// Builder method to set structured place fields. The country may be a
+    /// name or code; it (or failing that, the state) is normalized to an
+    /// ISO code.
+    pub fn with_place(
+        mut self,
+        venue: Option<String>,
+        city: Option<String>,
+        state: Option<String>,
+        country: Option<String>,
+    ) -> Self {
+        let code = country_from_parts(state.as_deref(), country.as_deref());
+        self.country = code.map(String::from).or(self.country);
+        self.venue = venue;
+        self.city = city;
+        self.state = state;
+        self
+    }
+
+    /// ISO country code: the stored one, else geocoded from the location
+    /// (events stored before place fields existed have only the text).
+    pub fn country_code(&self) -> Option<&str> {
+        self.country
+            .as_deref()
+            .or_else(|| self.location.as_deref().and_then(geocode_location))
+    }
+
+    /// Size bracket, when the player count is known.
+    pub fn size_bracket(&self, thresholds: &SizeThresholds) -> Option<SizeBracket> {
+        self.player_count.map(|n| thresholds.classify(n))
+    }
+
+    /// Broad region the event was held in.
+    pub fn region(&self) -> Option<Region> {
+        self.country_code().and_then(region_for_country)
+    }
+
     /// Builder method to set player count.
     pub fn with_player_count(mut self, count: u32) -> Self {
         self.player_count = Some(count);
@@ -109,6 +420,44 @@ impl Event {
         self.raw_source_path = Some(path);
         self
     }
+
+    /// Builder method to set the mission pack, normalized with
+    /// [`normalize_mission_pack`].
+    pub fn with_mission_pack(mut self, pack: Option<String>) -> Self {
+        self.mission_pack = pack.as_deref().and_then(normalize_mission_pack);
+        self
+    }
+
+    /// Builder method to set the points limit played.
+    pub fn with_game_size(mut self, game_size: Option<u32>) -> Self {
+        self.game_size = game_size;
+        self
+    }
+
+    /// Builder method to set the series this event belongs to.
+    pub fn with_series(mut self, series: Option<String>) -> Self {
+        self.series = series;
+        self
+    }
+
+    /// Create the event for one bracket of a multi-bracket listing.
+    ///
+    /// The ID is derived from the parent ID and the bracket index so it is
+    /// stable across syncs; the game size only affects the name.
+    pub fn bracket(&self, index: usize, game_size: Option<u32>) -> Self {
+        let label = match game_size {
+            Some(points) => format!("{}pts", points),
+            None => format!("Bracket {}", index + 1),
+        };
+        let mut event = self.clone();
+        event.id = EntityId::generate(&[self.id.as_str(), "bracket", &index.to_string()]);
+        event.name = format!("{} ({})", self.name, label);
+        event.player_count = None;
+        event.parent_id = Some(self.id.clone());
+        event.game_size = game_size;
+        event.created_at = Utc::now();
+        event
+    }
 }
 
 #[cfg(test)]
@@ -144,6 +493,74 @@ mod tests {
         assert_eq!(event.location, Some("London, UK".to_string()));
     }
 
+    #[test]
+    fn test_normalize_mission_pack() {
+        assert_eq!(
+            normalize_mission_pack("GW Pariah Nexus missions").as_deref(),
+            Some("Pariah Nexus")
+        );
+        assert_eq!(
+            normalize_mission_pack("Chapter Approved 2025-2026").as_deref(),
+            Some("Chapter Approved 2025-26")
+        );
+        assert_eq!(
+            normalize_mission_pack(" WTC Missions ").as_deref(),
+            Some("WTC Missions")
+        );
+        assert!(normalize_mission_pack("  ").is_none());
+    }
+
+    #[test]
+    fn test_game_size_from_name() {
+        assert_eq!(game_size_from_name("Brum Incursion RTT"), Some(1000));
+        assert_eq!(game_size_from_name("Combat Patrol Night"), Some(500));
+        assert_eq!(
+            game_size_from_name("Winter 1,000pts Escalation"),
+            Some(1000)
+        );
+        assert_eq!(game_size_from_name("London GT 2000 points"), Some(2000));
+        assert_eq!(game_size_from_name("London GT 2026"), None);
+    }
+
+    #[test]
+    fn test_size_brackets() {
+        let thresholds = SizeThresholds::default();
+        assert_eq!(thresholds.classify(12), SizeBracket::Small);
+        assert_eq!(thresholds.classify(24), SizeBracket::Medium);
+        assert_eq!(thresholds.classify(60), SizeBracket::Large);
+        assert_eq!(thresholds.classify(400), SizeBracket::Major);
+        assert!(thresholds.is_ordered());
+        assert_eq!("GT".parse::<SizeBracket>().unwrap(), SizeBracket::Large);
+        assert!("huge".parse::<SizeBracket>().is_err());
+    }
+
+    #[test]
+    fn test_event_country_and_region() {
+        let event = Event::new(
+            "LVO 2026".to_string(),
+            NaiveDate::from_ymd_opt(2026, 1, 30).unwrap(),
+            "https://example.com".to_string(),
+            "bcp".to_string(),
+            EntityId::from("epoch-123"),
+        )
+        .with_place(
+            None,
+            Some("Las Vegas".to_string()),
+            Some("NV".to_string()),
+            Some("United States".to_string()),
+        );
+        assert_eq!(event.country.as_deref(), Some("US"));
+        assert_eq!(event.region(), Some(Region::NorthAmerica));
+
+        // Older records only have the location text
+        let mut legacy: Event =
+            serde_json::from_value(serde_json::to_value(&event).unwrap()).unwrap();
+        legacy.country = None;
+        legacy.location = Some("Manchester, England".to_string());
+        assert_eq!(legacy.country_code(), Some("GB"));
+        assert_eq!(legacy.region(), Some(Region::Europe));
+    }
+
     #[test]
     fn test_event_id_includes_location() {
         let event1 = Event::new(
@@ -168,6 +585,29 @@ mod tests {
         assert_ne!(event1.id, event2.id);
     }
 
+    #[test]
+    fn test_event_bracket() {
+        let parent = Event::new(
+            "Club Weekender".to_string(),
+            NaiveDate::from_ymd_opt(2025, 7, 12).unwrap(),
+            "https://example.com".to_string(),
+            "bcp".to_string(),
+            EntityId::from("epoch-123"),
+        )
+        .with_player_count(40);
+
+        let big = parent.bracket(0, Some(2000));
+        let small = parent.bracket(1, None);
+        assert_eq!(big.name, "Club Weekender (2000pts)");
+        assert_eq!(small.name, "Club Weekender (Bracket 2)");
+        assert_eq!(big.parent_id.as_ref(), Some(&parent.id));
+        assert_eq!(big.game_size, Some(2000));
+        assert!(big.player_count.is_none());
+        assert_ne!(big.id, small.id);
+        // Size doesn't change the ID
+        assert_eq!(parent.bracket(0, None).id, big.id);
+    }
+
     #[test]
     fn test_event_builder() {
         let event = Event::new(
@@ -202,4 +642,26 @@ mod tests {
         assert_eq!(event.id, deserialized.id);
         assert_eq!(event.name, deserialized.name);
     }
+
+    #[test]
+    fn test_event_status_defaults_to_completed() {
+        let event = Event::new(
+            "Test GT".to_string(),
+            NaiveDate::from_ymd_opt(2025, 7, 12).unwrap(),
+            "https://example.com".to_string(),
+            "bcp".to_string(),
+            EntityId::from("current"),
+        );
+        let mut json = serde_json::to_value(&event).unwrap();
+        json.as_object_mut().unwrap().remove("status");
+        let stored: Event = serde_json::from_value(json).unwrap();
+        assert_eq!(stored.status, EventStatus::Completed);
+
+        let upcoming = event.with_status(EventStatus::Upcoming);
+        assert!(upcoming.is_upcoming());
+        assert_eq!(
+            serde_json::to_value(&upcoming).unwrap()["status"],
+            "upcoming"
+        );
+    }
 }
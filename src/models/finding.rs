@@ -0,0 +1,70 @@
+//! Data-quality findings from cross-checking published aggregates.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::EntityId;
+
+/// What a data-quality finding compares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingKind {
+    /// Events held in the period
+    EventCount,
+    /// Players across those events
+    PlayerCount,
+    /// A faction's win rate
+    FactionWinRate,
+    /// A faction the publication reports that we have no results for
+    FactionMissing,
+}
+
+impl std::fmt::Display for FindingKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FindingKind::EventCount => write!(f, "event_count"),
+            FindingKind::PlayerCount => write!(f, "player_count"),
+            FindingKind::FactionWinRate => write!(f, "faction_win_rate"),
+            FindingKind::FactionMissing => write!(f, "faction_missing"),
+        }
+    }
+}
+
+/// Where a published aggregate and our own disagree by more than the
+/// tolerance for its kind.
+///
+/// Findings live in `state/quality_findings.jsonl`. Checking the same
+/// article again replaces its findings, so a fixed gap disappears.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityFinding {
+    pub id: EntityId,
+    pub kind: FindingKind,
+    /// Faction, for per-faction findings
+    #[serde(default)]
+    pub subject: Option<String>,
+    /// Value the publication reports (win rates as 0.0 to 1.0)
+    pub published: f64,
+    /// Our value over the same period, if we have one
+    #[serde(default)]
+    pub local: Option<f64>,
+    pub message: String,
+    /// Article the published value came from
+    pub source_url: String,
+    pub source_title: String,
+    /// Period the publication covers
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub found_at: DateTime<Utc>,
+}
+
+impl QualityFinding {
+    /// ID of the finding for an article, kind and subject.
+    pub fn finding_id(source_url: &str, kind: FindingKind, subject: Option<&str>) -> EntityId {
+        EntityId::generate(&[source_url, &kind.to_string(), subject.unwrap_or("")])
+    }
+
+    /// Local minus published, if we have a local value.
+    pub fn difference(&self) -> Option<f64> {
+        self.local.map(|local| local - self.published)
+    }
+}
@@ -0,0 +1,434 @@
+//! Offline country and region lookup for event locations.
+//!
+//! Sources give locations as free text ("Las Vegas, NV", "Manchester,
+//! England", "Berlin, Deutschland") or as separate city/state/country
+//! fields. This maps them to ISO 3166-1 alpha-2 country codes and broad
+//! regions without calling a geocoding service.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Broad region used to compare metas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Region {
+    #[serde(rename = "NA")]
+    NorthAmerica,
+    #[serde(rename = "EU")]
+    Europe,
+    #[serde(rename = "OC")]
+    Oceania,
+    #[serde(rename = "AS")]
+    Asia,
+    #[serde(rename = "SA")]
+    SouthAmerica,
+    #[serde(rename = "AF")]
+    Africa,
+}
+
+impl Region {
+    /// Two-letter code used in the API (`?region=EU`).
+    pub fn code(self) -> &'static str {
+        match self {
+            Region::NorthAmerica => "NA",
+            Region::Europe => "EU",
+            Region::Oceania => "OC",
+            Region::Asia => "AS",
+            Region::SouthAmerica => "SA",
+            Region::Africa => "AF",
+        }
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Region {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace(['-', '_'], " ").as_str() {
+            "na" | "north america" | "americas" => Ok(Region::NorthAmerica),
+            "eu" | "europe" => Ok(Region::Europe),
+            "oc" | "oceania" | "anz" => Ok(Region::Oceania),
+            "as" | "asia" | "apac" => Ok(Region::Asia),
+            "sa" | "south america" | "latam" => Ok(Region::SouthAmerica),
+            "af" | "africa" => Ok(Region::Africa),
+            _ => Err(format!(
+                "Unknown region '{}' (expected NA, EU, OC, AS, SA or AF)",
+                s
+            )),
+        }
+    }
+}
+
+/// (ISO code, region, names and aliases in lowercase).
+const COUNTRIES: &[(&str, Region, &[&str])] = &[
+    (
+        "US",
+        Region::NorthAmerica,
+        &[
+            "united states",
+            "united states of america",
+            "usa",
+            "us",
+            "america",
+        ],
+    ),
+    ("CA", Region::NorthAmerica, &["canada", "can"]),
+    ("MX", Region::NorthAmerica, &["mexico", "méxico", "mex"]),
+    ("PR", Region::NorthAmerica, &["puerto rico"]),
+    (
+        "GB",
+        Region::Europe,
+        &[
+            "united kingdom",
+            "uk",
+            "gb",
+            "gbr",
+            "great britain",
+            "england",
+            "scotland",
+            "wales",
+            "northern ireland",
+        ],
+    ),
+    (
+        "IE",
+        Region::Europe,
+        &["ireland", "republic of ireland", "irl", "éire"],
+    ),
+    (
+        "DE",
+        Region::Europe,
+        &["germany", "deutschland", "deu", "ger"],
+    ),
+    ("FR", Region::Europe, &["france", "fra"]),
+    ("ES", Region::Europe, &["spain", "españa", "espana", "esp"]),
+    ("PT", Region::Europe, &["portugal", "prt"]),
+    ("IT", Region::Europe, &["italy", "italia", "ita"]),
+    (
+        "NL",
+        Region::Europe,
+        &[
+            "netherlands",
+            "the netherlands",
+            "holland",
+            "nederland",
+            "nld",
+        ],
+    ),
+    (
+        "BE",
+        Region::Europe,
+        &["belgium", "belgië", "belgique", "bel"],
+    ),
+    ("LU", Region::Europe, &["luxembourg", "lux"]),
+    (
+        "CH",
+        Region::Europe,
+        &["switzerland", "schweiz", "suisse", "che"],
+    ),
+    (
+        "AT",
+        Region::Europe,
+        &["austria", "österreich", "osterreich", "aut"],
+    ),
+    ("DK", Region::Europe, &["denmark", "danmark", "dnk"]),
+    ("SE", Region::Europe, &["sweden", "sverige", "swe"]),
+    ("NO", Region::Europe, &["norway", "norge", "nor"]),
+    ("FI", Region::Europe, &["finland", "suomi", "fin"]),
+    ("IS", Region::Europe, &["iceland", "isl"]),
+    ("PL", Region::Europe, &["poland", "polska", "pol"]),
+    (
+        "CZ",
+        Region::Europe,
+        &["czech republic", "czechia", "česko", "cze"],
+    ),
+    ("SK", Region::Europe, &["slovakia", "svk"]),
+    ("HU", Region::Europe, &["hungary", "magyarország", "hun"]),
+    ("SI", Region::Europe, &["slovenia", "svn"]),
+    ("HR", Region::Europe, &["croatia", "hrvatska", "hrv"]),
+    ("RS", Region::Europe, &["serbia", "srb"]),
+    ("RO", Region::Europe, &["romania", "rou"]),
+    ("BG", Region::Europe, &["bulgaria", "bgr"]),
+    ("GR", Region::Europe, &["greece", "grc"]),
+    ("EE", Region::Europe, &["estonia", "est"]),
+    ("LV", Region::Europe, &["latvia", "lva"]),
+    ("LT", Region::Europe, &["lithuania", "ltu"]),
+    ("UA", Region::Europe, &["ukraine", "ukr"]),
+    ("MT", Region::Europe, &["malta", "mlt"]),
+    ("CY", Region::Europe, &["cyprus", "cyp"]),
+    ("AU", Region::Oceania, &["australia", "aus"]),
+    ("NZ", Region::Oceania, &["new zealand", "nzl"]),
+    ("JP", Region::Asia, &["japan", "jpn"]),
+    ("KR", Region::Asia, &["south korea", "korea", "kor"]),
+    ("CN", Region::Asia, &["china", "chn"]),
+    ("HK", Region::Asia, &["hong kong", "hkg"]),
+    ("TW", Region::Asia, &["taiwan", "twn"]),
+    ("SG", Region::Asia, &["singapore", "sgp"]),
+    ("MY", Region::Asia, &["malaysia", "mys"]),
+    ("TH", Region::Asia, &["thailand", "tha"]),
+    ("PH", Region::Asia, &["philippines", "phl"]),
+    ("IN", Region::Asia, &["india", "ind"]),
+    ("AE", Region::Asia, &["united arab emirates", "uae", "are"]),
+    ("IL", Region::Asia, &["israel", "isr"]),
+    ("TR", Region::Asia, &["turkey", "türkiye", "turkiye", "tur"]),
+    ("BR", Region::SouthAmerica, &["brazil", "brasil", "bra"]),
+    ("AR", Region::SouthAmerica, &["argentina", "arg"]),
+    ("CL", Region::SouthAmerica, &["chile", "chl"]),
+    ("CO", Region::SouthAmerica, &["colombia", "col"]),
+    ("PE", Region::SouthAmerica, &["peru", "per"]),
+    ("ZA", Region::Africa, &["south africa", "zaf", "rsa"]),
+];
+
+/// US states and territories, full names then postal codes.
+const US_STATES: &[&str] = &[
+    "alabama",
+    "alaska",
+    "arizona",
+    "arkansas",
+    "california",
+    "colorado",
+    "connecticut",
+    "delaware",
+    "florida",
+    "georgia",
+    "hawaii",
+    "idaho",
+    "illinois",
+    "indiana",
+    "iowa",
+    "kansas",
+    "kentucky",
+    "louisiana",
+    "maine",
+    "maryland",
+    "massachusetts",
+    "michigan",
+    "minnesota",
+    "mississippi",
+    "missouri",
+    "montana",
+    "nebraska",
+    "nevada",
+    "new hampshire",
+    "new jersey",
+    "new mexico",
+    "new york",
+    "north carolina",
+    "north dakota",
+    "ohio",
+    "oklahoma",
+    "oregon",
+    "pennsylvania",
+    "rhode island",
+    "south carolina",
+    "south dakota",
+    "tennessee",
+    "texas",
+    "utah",
+    "vermont",
+    "virginia",
+    "washington",
+    "west virginia",
+    "wisconsin",
+    "wyoming",
+    "district of columbia",
+    "al",
+    "ak",
+    "az",
+    "ar",
+    "ca",
+    "co",
+    "ct",
+    "de",
+    "fl",
+    "ga",
+    "hi",
+    "id",
+    "il",
+    "in",
+    "ia",
+    "ks",
+    "ky",
+    "la",
+    "me",
+    "md",
+    "ma",
+    "mi",
+    "mn",
+    "ms",
+    "mo",
+    "mt",
+    "ne",
+    "nv",
+    "nh",
+    "nj",
+    "nm",
+    "ny",
+    "nc",
+    "nd",
+    "oh",
+    "ok",
+    "or",
+    "pa",
+    "ri",
+    "sc",
+    "sd",
+    "tn",
+    "tx",
+    "ut",
+    "vt",
+    "va",
+    "wa",
+    "wv",
+    "wi",
+    "wy",
+    "dc",
+];
+
+/// Canadian provinces and territories, full names then postal codes.
+const CA_PROVINCES: &[&str] = &[
+    "alberta",
+    "british columbia",
+    "manitoba",
+    "new brunswick",
+    "newfoundland and labrador",
+    "nova scotia",
+    "ontario",
+    "prince edward island",
+    "quebec",
+    "québec",
+    "saskatchewan",
+    "yukon",
+    "nunavut",
+    "northwest territories",
+    "ab",
+    "bc",
+    "mb",
+    "nb",
+    "nl",
+    "ns",
+    "on",
+    "pe",
+    "qc",
+    "sk",
+    "yt",
+    "nu",
+    "nt",
+];
+
+/// Australian states and territories.
+const AU_STATES: &[&str] = &[
+    "new south wales",
+    "victoria",
+    "queensland",
+    "western australia",
+    "south australia",
+    "tasmania",
+    "australian capital territory",
+    "northern territory",
+    "nsw",
+    "vic",
+    "qld",
+    "wa",
+    "tas",
+    "act",
+];
+
+/// ISO code for a country name, alias or code.
+pub fn country_code(name: &str) -> Option<&'static str> {
+    let name = name.trim().trim_end_matches('.').to_lowercase();
+    if name.is_empty() {
+        return None;
+    }
+    COUNTRIES
+        .iter()
+        .find(|(code, _, aliases)| {
+            code.eq_ignore_ascii_case(&name) || aliases.contains(&name.as_str())
+        })
+        .map(|(code, _, _)| *code)
+}
+
+/// Region a country code belongs to.
+pub fn region_for_country(code: &str) -> Option<Region> {
+    COUNTRIES
+        .iter()
+        .find(|(c, _, _)| c.eq_ignore_ascii_case(code))
+        .map(|(_, region, _)| *region)
+}
+
+/// Country implied by a state or province name or postal code.
+fn country_for_state(state: &str) -> Option<&'static str> {
+    let state = state.trim().trim_end_matches('.').to_lowercase();
+    if US_STATES.contains(&state.as_str()) {
+        Some("US")
+    } else if CA_PROVINCES.contains(&state.as_str()) {
+        Some("CA")
+    } else if AU_STATES.contains(&state.as_str()) {
+        Some("AU")
+    } else {
+        None
+    }
+}
+
+/// Country code for structured fields: the country when recognised, else
+/// whatever the state implies.
+pub fn country_from_parts(state: Option<&str>, country: Option<&str>) -> Option<&'static str> {
+    country
+        .and_then(country_code)
+        .or_else(|| state.and_then(country_for_state))
+}
+
+/// Best-effort country code for a free-text location, reading its
+/// comma-separated parts from the end ("Venue, City, State, Country").
+pub fn geocode_location(location: &str) -> Option<&'static str> {
+    let parts: Vec<&str> = location
+        .split([',', '|', '/'])
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect();
+    // Country names first, so "Perth, WA, Australia" isn't read as Washington
+    if let Some(code) = parts.iter().rev().find_map(|p| country_code(p)) {
+        // Two-letter parts are often state codes ("Columbus, OH")
+        let last = parts.last().copied().unwrap_or_default();
+        if last.len() == 2 && parts.len() > 1 {
+            if let Some(state_country) = country_for_state(last) {
+                return Some(state_country);
+            }
+        }
+        return Some(code);
+    }
+    parts.iter().rev().find_map(|p| {
+        // "Columbus OH 43215": try the words too
+        country_for_state(p).or_else(|| p.split_whitespace().rev().find_map(country_for_state))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geocode_location() {
+        assert_eq!(geocode_location("Las Vegas, NV"), Some("US"));
+        assert_eq!(geocode_location("Manchester, England"), Some("GB"));
+        assert_eq!(geocode_location("Berlin, Deutschland"), Some("DE"));
+        assert_eq!(
+            geocode_location("Convention Centre, Toronto, Ontario, Canada"),
+            Some("CA")
+        );
+        assert_eq!(geocode_location("Sydney, NSW"), Some("AU"));
+        assert_eq!(geocode_location("Columbus OH 43215"), Some("US"));
+        assert_eq!(geocode_location("Somewhere"), None);
+        assert_eq!(region_for_country("gb"), Some(Region::Europe));
+        assert_eq!(country_from_parts(Some("TX"), None), Some("US"));
+        assert_eq!("europe".parse::<Region>().unwrap(), Region::Europe);
+        assert!("mars".parse::<Region>().is_err());
+    }
+}
@@ -74,6 +74,9 @@ pub type PlacementId = EntityId;
 /// Type alias for army list IDs
 pub type ArmyListId = EntityId;
 
+/// Type alias for event series IDs
+pub type SeriesId = EntityId;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,173 @@
+//! Ingest issues: articles and events a sync failed to import.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{EntityId, EventId};
+
+/// Pipeline step an ingest issue happened in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestStage {
+    /// Downloading the article or page
+    Fetch,
+    /// AI extraction of events, placements and lists from an article
+    Extract,
+    /// Fetching and storing an event's standings and lists
+    Standings,
+    /// Filling in missing lists for an already stored event
+    Backfill,
+}
+
+impl std::fmt::Display for IngestStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IngestStage::Fetch => write!(f, "fetch"),
+            IngestStage::Extract => write!(f, "extract"),
+            IngestStage::Standings => write!(f, "standings"),
+            IngestStage::Backfill => write!(f, "backfill"),
+        }
+    }
+}
+
+impl std::str::FromStr for IngestStage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "fetch" => Ok(IngestStage::Fetch),
+            "extract" => Ok(IngestStage::Extract),
+            "standings" => Ok(IngestStage::Standings),
+            "backfill" => Ok(IngestStage::Backfill),
+            other => Err(format!("Unknown ingest stage: {}", other)),
+        }
+    }
+}
+
+/// Broad class of an ingest failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// Connection failure, timeout or HTTP error status
+    Network,
+    /// The source or AI backend asked us to slow down
+    RateLimited,
+    /// The payload could not be parsed
+    Parse,
+    /// The AI backend failed or refused to extract
+    Ai,
+    /// Writing the results failed
+    Storage,
+    Other,
+}
+
+impl std::fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IssueKind::Network => write!(f, "network"),
+            IssueKind::RateLimited => write!(f, "rate_limited"),
+            IssueKind::Parse => write!(f, "parse"),
+            IssueKind::Ai => write!(f, "ai"),
+            IssueKind::Storage => write!(f, "storage"),
+            IssueKind::Other => write!(f, "other"),
+        }
+    }
+}
+
+/// What to re-run to retry an issue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RetryTarget {
+    /// A Goonhammer article
+    Article { url: String, date: NaiveDate },
+    /// Standings of a BCP event
+    BcpEvent {
+        /// BCP object ID
+        bcp_id: String,
+        name: String,
+        date: NaiveDate,
+        /// Stored event the placements belong to
+        event_id: EventId,
+        #[serde(default)]
+        player_count: Option<u32>,
+        #[serde(default)]
+        round_count: Option<u32>,
+    },
+    /// Standings of a Tabletop Tournaments event
+    T3Event {
+        /// T3 tournament ID
+        t3_id: String,
+        name: String,
+        date: NaiveDate,
+        /// Stored event the placements belong to
+        event_id: EventId,
+    },
+}
+
+/// An article or event a sync failed to import.
+///
+/// Issues live in `state/ingest_issues.jsonl`, one per source URL and
+/// stage. A later failure of the same item bumps `attempts`; a later
+/// success (in a sync or through `meta-agent issues retry`) resolves it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IngestIssue {
+    pub id: EntityId,
+    /// Source key, as in the sync cursor (`bcp`, `goonhammer`, `t3`)
+    pub source: String,
+    pub source_url: String,
+    pub stage: IngestStage,
+    pub kind: IssueKind,
+    /// Error from the most recent attempt
+    pub error: String,
+    /// Whether retrying can help (transient network, AI or storage errors)
+    pub retryable: bool,
+    /// Cached raw payload, relative to the data directory, if one was fetched
+    #[serde(default)]
+    pub raw_path: Option<String>,
+    pub target: RetryTarget,
+    pub attempts: u32,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    /// Sync run that last hit this issue (its `started_at` in sync history),
+    /// or None for a manual retry
+    #[serde(default)]
+    pub run_started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl IngestIssue {
+    /// ID of the issue for an item and stage.
+    pub fn issue_id(source: &str, source_url: &str, stage: IngestStage) -> EntityId {
+        EntityId::generate(&[source, source_url, &stage.to_string()])
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.resolved_at.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_target_roundtrip() {
+        let target = RetryTarget::BcpEvent {
+            bcp_id: "abc123".to_string(),
+            name: "London GT".to_string(),
+            date: NaiveDate::from_ymd_opt(2026, 3, 14).unwrap(),
+            event_id: EntityId::from("ev1"),
+            player_count: Some(96),
+            round_count: None,
+        };
+        let json = serde_json::to_value(&target).unwrap();
+        assert_eq!(json["type"], "bcp_event");
+        assert_eq!(serde_json::from_value::<RetryTarget>(json).unwrap(), target);
+
+        let a = IngestIssue::issue_id("bcp", "https://x/event/1", IngestStage::Standings);
+        let b = IngestIssue::issue_id("bcp", "https://x/event/1", IngestStage::Backfill);
+        assert_ne!(a, b);
+        assert_eq!("backfill".parse::<IngestStage>(), Ok(IngestStage::Backfill));
+    }
+}
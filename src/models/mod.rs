@@ -1,23 +1,39 @@
 //! Core data models for the meta agent.
 
 mod army_list;
+mod bracket;
 mod confidence;
 mod epoch;
 mod event;
+mod finding;
+mod geo;
 mod ids;
+mod issue;
 mod pairing;
 mod placement;
+mod points;
 mod review;
+mod series;
 mod significant_event;
 mod stats;
+mod taxonomy;
+mod timestamps;
 
 pub use army_list::*;
+pub use bracket::*;
 pub use confidence::*;
 pub use epoch::*;
 pub use event::*;
+pub use finding::*;
+pub use geo::*;
 pub use ids::*;
+pub use issue::*;
 pub use pairing::*;
 pub use placement::*;
+pub use points::*;
 pub use review::*;
+pub use series::*;
 pub use significant_event::*;
 pub use stats::*;
+pub use taxonomy::*;
+pub use timestamps::*;
@@ -46,6 +46,10 @@ pub struct Pairing {
 
     /// When this record was created
     pub created_at: DateTime<Utc>,
+
+    /// When this record was last changed (unset = not since it was created)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl Pairing {
@@ -77,6 +81,7 @@ impl Pairing {
             player1_game_points: None,
             player2_game_points: None,
             created_at: Utc::now(),
+            updated_at: None,
         }
     }
 }
@@ -3,7 +3,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use super::{ArmyListId, Confidence, EntityId, EpochId, EventId, PlacementId};
+use super::{ArmyListId, BracketStage, Confidence, EntityId, EpochId, EventId, PlacementId};
 
 /// Win/loss/draw record.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -39,6 +39,17 @@ impl WinLossRecord {
     }
 }
 
+/// Clean up a team name as entered: whitespace is collapsed, and blank or
+/// placeholder entries ("none", "n/a", "-") give `None`.
+pub fn normalize_team_name(raw: &str) -> Option<String> {
+    let name = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    let placeholder = matches!(
+        name.to_lowercase().as_str(),
+        "" | "-" | "none" | "n/a" | "na" | "no team" | "independent"
+    );
+    (!placeholder).then_some(name)
+}
+
 /// A player's placement in a tournament.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Placement {
@@ -76,17 +87,41 @@ pub struct Placement {
     /// Battle points (if available)
     pub battle_points: Option<u32>,
 
+    /// Furthest top-cut stage reached, for events with a bracket after Swiss
+    /// (`record` is then the Swiss record only)
+    #[serde(default)]
+    pub bracket_stage: Option<BracketStage>,
+
     /// Link to army list
     pub list_id: Option<ArmyListId>,
 
+    /// Team or club the player entered under (e.g. "Vanguard Tactics")
+    #[serde(default)]
+    pub team: Option<String>,
+
     /// When this record was created
     pub created_at: DateTime<Utc>,
 
+    /// When this record was last changed (unset = not since it was created)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+
     /// Confidence level of the extraction
     pub extraction_confidence: Confidence,
 
     /// Whether this needs manual review
     pub needs_review: bool,
+
+    /// AI model that extracted this record (e.g. "llama3.2:latest");
+    /// `None` for records parsed without AI
+    #[serde(default)]
+    pub extracted_by: Option<String>,
+
+    /// Version of the extracting agent ([`crate::agents::Agent::version`]);
+    /// `None` for records parsed without AI or extracted before versions
+    /// were recorded
+    #[serde(default)]
+    pub extraction_agent_version: Option<u32>,
 }
 
 impl Placement {
@@ -112,13 +147,24 @@ impl Placement {
             detachment: None,
             record: None,
             battle_points: None,
+            bracket_stage: None,
             list_id: None,
+            team: None,
             created_at: Utc::now(),
+            updated_at: None,
             extraction_confidence: Confidence::default(),
             needs_review: false,
+            extracted_by: None,
+            extraction_agent_version: None,
         }
     }
 
+    /// Builder method to record the AI model that extracted this record.
+    pub fn with_extracted_by(mut self, model: impl Into<String>) -> Self {
+        self.extracted_by = Some(model.into());
+        self
+    }
+
     /// Builder method to set subfaction.
     pub fn with_subfaction(mut self, subfaction: String) -> Self {
         self.subfaction = Some(subfaction);
@@ -149,12 +195,24 @@ impl Placement {
         self
     }
 
+    /// Builder method to set bracket stage.
+    pub fn with_bracket_stage(mut self, stage: BracketStage) -> Self {
+        self.bracket_stage = Some(stage);
+        self
+    }
+
     /// Builder method to set list ID.
     pub fn with_list_id(mut self, list_id: ArmyListId) -> Self {
         self.list_id = Some(list_id);
         self
     }
 
+    /// Builder method to set the team, cleaned with [`normalize_team_name`].
+    pub fn with_team(mut self, team: Option<&str>) -> Self {
+        self.team = team.and_then(normalize_team_name);
+        self
+    }
+
     /// Builder method to set confidence.
     pub fn with_confidence(mut self, confidence: Confidence) -> Self {
         self.extraction_confidence = confidence;
@@ -191,6 +249,16 @@ mod tests {
         assert_eq!(record.win_rate(), 0.0);
     }
 
+    #[test]
+    fn test_normalize_team_name() {
+        assert_eq!(
+            normalize_team_name("  Vanguard   Tactics ").as_deref(),
+            Some("Vanguard Tactics")
+        );
+        assert!(normalize_team_name("N/A").is_none());
+        assert!(normalize_team_name(" ").is_none());
+    }
+
     #[test]
     fn test_placement_creation() {
         let placement = Placement::new(
@@ -0,0 +1,120 @@
+//! Unit points costs from the Munitorum Field Manual.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{EntityId, EpochId, SignificantEventId};
+
+/// Type alias for points change IDs.
+pub type PointsChangeId = EntityId;
+
+/// A unit's points cost for one unit size in one epoch, as published in the
+/// Field Manual that came with the epoch's balance pass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PointsChange {
+    /// Unique identifier
+    pub id: PointsChangeId,
+
+    /// Epoch this cost applies to
+    pub epoch_id: EpochId,
+
+    /// Balance pass the Field Manual was published with
+    pub significant_event_id: SignificantEventId,
+
+    /// Date the cost takes effect
+    pub effective_date: NaiveDate,
+
+    /// Faction (canonical name)
+    pub faction: String,
+
+    /// Unit name as printed in the Field Manual
+    pub unit_name: String,
+
+    /// Number of models this cost buys
+    pub models: u32,
+
+    /// Points cost
+    pub points: u32,
+
+    /// Cost in the previous Field Manual (None if the unit size is new)
+    pub previous_points: Option<u32>,
+
+    /// URL of the Field Manual PDF
+    pub source_url: String,
+
+    /// When this record was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl PointsChange {
+    /// Create a new PointsChange with auto-generated ID.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        epoch_id: EpochId,
+        significant_event_id: SignificantEventId,
+        effective_date: NaiveDate,
+        faction: String,
+        unit_name: String,
+        models: u32,
+        points: u32,
+        source_url: String,
+    ) -> Self {
+        let id = EntityId::generate(&[
+            epoch_id.as_str(),
+            &faction,
+            &unit_name.to_lowercase(),
+            &models.to_string(),
+        ]);
+
+        Self {
+            id,
+            epoch_id,
+            significant_event_id,
+            effective_date,
+            faction,
+            unit_name,
+            models,
+            points,
+            previous_points: None,
+            source_url,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Builder method to set the previous cost.
+    pub fn with_previous_points(mut self, points: u32) -> Self {
+        self.previous_points = Some(points);
+        self
+    }
+
+    /// Change from the previous cost (None if the unit size is new).
+    pub fn delta(&self) -> Option<i64> {
+        self.previous_points
+            .map(|prev| self.points as i64 - prev as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_points_change_id_and_delta() {
+        let make = |unit: &str| {
+            PointsChange::new(
+                "epoch-1".into(),
+                "pass-1".into(),
+                NaiveDate::from_ymd_opt(2025, 6, 12).unwrap(),
+                "Aeldari".to_string(),
+                unit.to_string(),
+                5,
+                170,
+                "https://example.com/mfm.pdf".to_string(),
+            )
+        };
+        let a = make("Wraithguard");
+        assert_eq!(a.id, make("WRAITHGUARD").id);
+        assert_eq!(a.delta(), None);
+        assert_eq!(a.with_previous_points(185).delta(), Some(-15));
+    }
+}
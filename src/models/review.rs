@@ -19,6 +19,8 @@ pub enum ReviewReason {
     DuplicateSuspected,
     /// Manual flag by user
     ManualFlag,
+    /// Win rates shifted with no balance pass to explain it
+    MetaShiftCandidate,
 }
 
 impl std::fmt::Display for ReviewReason {
@@ -28,6 +30,42 @@ impl std::fmt::Display for ReviewReason {
             ReviewReason::FactCheckFailed => write!(f, "fact_check_failed"),
             ReviewReason::DuplicateSuspected => write!(f, "duplicate_suspected"),
             ReviewReason::ManualFlag => write!(f, "manual_flag"),
+            ReviewReason::MetaShiftCandidate => write!(f, "meta_shift_candidate"),
+        }
+    }
+}
+
+/// How a review item was resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolutionAction {
+    /// Extraction is correct as stored
+    Accept,
+    /// Extraction is wrong and should be discarded
+    Reject,
+    /// Extraction was corrected by hand
+    Fix,
+}
+
+impl std::fmt::Display for ResolutionAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResolutionAction::Accept => write!(f, "accept"),
+            ResolutionAction::Reject => write!(f, "reject"),
+            ResolutionAction::Fix => write!(f, "fix"),
+        }
+    }
+}
+
+impl std::str::FromStr for ResolutionAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "accept" => Ok(ResolutionAction::Accept),
+            "reject" => Ok(ResolutionAction::Reject),
+            "fix" => Ok(ResolutionAction::Fix),
+            other => Err(format!("Unknown resolution action: {}", other)),
         }
     }
 }
@@ -53,6 +91,17 @@ impl std::fmt::Display for EntityType {
     }
 }
 
+/// A record kept out of storage until its review item is accepted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeldRecord {
+    /// Epoch partition the record belongs in
+    pub epoch: String,
+    /// Source partition (`goonhammer`, ...)
+    pub source: String,
+    /// The record as it would have been stored
+    pub record: serde_json::Value,
+}
+
 /// An item in the review queue.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReviewQueueItem {
@@ -85,6 +134,14 @@ pub struct ReviewQueueItem {
 
     /// Notes about the resolution
     pub resolution_notes: Option<String>,
+
+    /// Action taken when resolving
+    #[serde(default)]
+    pub resolution_action: Option<ResolutionAction>,
+
+    /// Record held back from storage; accepting the item stores it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub held: Option<HeldRecord>,
 }
 
 impl ReviewQueueItem {
@@ -106,6 +163,8 @@ impl ReviewQueueItem {
             resolved: false,
             resolved_at: None,
             resolution_notes: None,
+            resolution_action: None,
+            held: None,
         }
     }
 
@@ -115,6 +174,12 @@ impl ReviewQueueItem {
         self
     }
 
+    /// Builder method to hold a record back from storage until accepted.
+    pub fn with_held(mut self, held: HeldRecord) -> Self {
+        self.held = Some(held);
+        self
+    }
+
     /// Mark as resolved.
     pub fn resolve(&mut self, notes: Option<String>) {
         self.resolved = true;
@@ -122,6 +187,12 @@ impl ReviewQueueItem {
         self.resolution_notes = notes;
     }
 
+    /// Mark as resolved with the action taken.
+    pub fn resolve_with(&mut self, action: ResolutionAction, notes: Option<String>) {
+        self.resolve(notes);
+        self.resolution_action = Some(action);
+    }
+
     /// Check if this is pending (not resolved).
     pub fn is_pending(&self) -> bool {
         !self.resolved
@@ -178,6 +249,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolution_action_parse() {
+        assert_eq!(
+            "Reject".parse::<ResolutionAction>(),
+            Ok(ResolutionAction::Reject)
+        );
+        assert!("delete".parse::<ResolutionAction>().is_err());
+    }
+
     #[test]
     fn test_entity_type_display() {
         assert_eq!(format!("{}", EntityType::Event), "event");
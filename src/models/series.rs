@@ -0,0 +1,143 @@
+//! Recurring event series (LVO, WCW, GW Grand Tournaments).
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::{EntityId, SeriesId};
+
+/// A recurring tournament series, matched against event names.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Series {
+    /// Unique identifier (derived from name)
+    pub id: SeriesId,
+
+    /// Display name (e.g., "Las Vegas Open")
+    pub name: String,
+
+    /// Case-insensitive regex patterns matched against event names
+    pub patterns: Vec<String>,
+}
+
+impl Series {
+    /// Create a new Series with auto-generated ID.
+    pub fn new(name: String, patterns: Vec<String>) -> Self {
+        let id = EntityId::generate(&[&name]);
+        Self { id, name, patterns }
+    }
+
+    /// Built-in series used when no series file has been configured.
+    pub fn defaults() -> Vec<Series> {
+        let series = |name: &str, patterns: &[&str]| {
+            Series::new(
+                name.to_string(),
+                patterns.iter().map(|p| p.to_string()).collect(),
+            )
+        };
+        vec![
+            series("Las Vegas Open", &[r"\blvo\b", r"las vegas open"]),
+            series(
+                "World Championships of Warhammer",
+                &[r"\bwcw\b", r"world championships? of warhammer"],
+            ),
+            series("London GT", &[r"london (gt|grand tournament)"]),
+            series(
+                "Warhammer World GT",
+                &[
+                    r"warhammer world (gt|grand tournament)",
+                    r"\bgw\b.*(gt|grand tournament)",
+                ],
+            ),
+            series("Adepticon", &[r"adepticon"]),
+            series("NOVA Open", &[r"\bnova\b"]),
+            series("SoCal Open", &[r"socal open"]),
+            series("Texas Warhammer Open", &[r"texas warhammer open"]),
+        ]
+    }
+}
+
+/// Compiled matcher assigning events to series by name.
+#[derive(Debug, Default)]
+pub struct SeriesMatcher {
+    rules: Vec<(Series, Vec<Regex>)>,
+}
+
+impl SeriesMatcher {
+    /// Compile the patterns for each series. Invalid patterns are skipped.
+    pub fn new(series: Vec<Series>) -> Self {
+        let rules = series
+            .into_iter()
+            .map(|s| {
+                let regexes = s
+                    .patterns
+                    .iter()
+                    .filter_map(|p| match Regex::new(&format!("(?i){}", p)) {
+                        Ok(re) => Some(re),
+                        Err(e) => {
+                            tracing::warn!("Invalid pattern for series {}: {} ({})", s.name, p, e);
+                            None
+                        }
+                    })
+                    .collect();
+                (s, regexes)
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Find the first series whose patterns match the event name.
+    pub fn match_event(&self, event_name: &str) -> Option<&Series> {
+        self.rules
+            .iter()
+            .find(|(_, regexes)| regexes.iter().any(|re| re.is_match(event_name)))
+            .map(|(s, _)| s)
+    }
+
+    /// Look up a series by name (case-insensitive).
+    pub fn get(&self, name: &str) -> Option<&Series> {
+        self.rules
+            .iter()
+            .map(|(s, _)| s)
+            .find(|s| s.name.eq_ignore_ascii_case(name))
+    }
+
+    /// All configured series.
+    pub fn all(&self) -> impl Iterator<Item = &Series> {
+        self.rules.iter().map(|(s, _)| s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_series_matching() {
+        let matcher = SeriesMatcher::new(Series::defaults());
+
+        let lvo = matcher.match_event("LVO 2025 - Warhammer 40k Championship");
+        assert_eq!(lvo.map(|s| s.name.as_str()), Some("Las Vegas Open"));
+
+        let london = matcher.match_event("London Grand Tournament 2025");
+        assert_eq!(london.map(|s| s.name.as_str()), Some("London GT"));
+
+        assert!(matcher.match_event("Brighton Doubles").is_none());
+        // Word boundaries avoid matching inside other words
+        assert!(matcher.match_event("Salvo Showdown").is_none());
+    }
+
+    #[test]
+    fn test_invalid_pattern_skipped() {
+        let matcher = SeriesMatcher::new(vec![Series::new(
+            "Broken".to_string(),
+            vec!["(unclosed".to_string(), "broken cup".to_string()],
+        )]);
+        assert!(matcher.match_event("The Broken Cup").is_some());
+    }
+
+    #[test]
+    fn test_get_by_name() {
+        let matcher = SeriesMatcher::new(Series::defaults());
+        assert!(matcher.get("las vegas open").is_some());
+        assert!(matcher.get("Unknown Series").is_none());
+    }
+}
@@ -6,7 +6,7 @@ use std::path::PathBuf;
 
 use super::{Confidence, EntityId, SignificantEventId};
 
-/// Type of significant event that marks an epoch boundary.
+/// Type of significant event.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SignificantEventType {
@@ -14,6 +14,16 @@ pub enum SignificantEventType {
     BalanceUpdate,
     /// Edition release (new edition launch)
     EditionRelease,
+    /// Rules FAQ or errata document. Annotates the timeline without
+    /// starting a new epoch.
+    FaqUpdate,
+}
+
+impl SignificantEventType {
+    /// Whether events of this type start a new epoch.
+    pub fn is_epoch_boundary(&self) -> bool {
+        !matches!(self, SignificantEventType::FaqUpdate)
+    }
 }
 
 impl std::fmt::Display for SignificantEventType {
@@ -21,13 +31,15 @@ impl std::fmt::Display for SignificantEventType {
         match self {
             SignificantEventType::BalanceUpdate => write!(f, "balance_update"),
             SignificantEventType::EditionRelease => write!(f, "edition_release"),
+            SignificantEventType::FaqUpdate => write!(f, "faq_update"),
         }
     }
 }
 
-/// A single unit points change within a balance update.
+/// A single unit points change summarised in a balance update (the full
+/// per-epoch costs are recorded as [`PointsChange`](super::PointsChange)).
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PointsChange {
+pub struct BalancePointsChange {
     pub unit: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub old_points: Option<i32>,
@@ -44,7 +56,7 @@ pub struct FactionChange {
     pub direction: String,
     pub summary: String,
     #[serde(default)]
-    pub points_changes: Vec<PointsChange>,
+    pub points_changes: Vec<BalancePointsChange>,
     #[serde(default)]
     pub rules_changes: Vec<String>,
     #[serde(default)]
@@ -60,7 +72,35 @@ pub struct BalanceChanges {
     pub faction_changes: Vec<FactionChange>,
 }
 
-/// A significant event that marks an epoch boundary.
+/// Direction of a single rule change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleChangeType {
+    Buff,
+    Nerf,
+    Rework,
+    Clarification,
+}
+
+/// One rule change parsed from a balance dataslate PDF.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleChange {
+    /// Faction (canonical name), or "Core Rules" for changes that apply to
+    /// every army
+    pub faction: String,
+
+    /// Unit the change applies to (None for army rules, detachments, etc.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+
+    pub change_type: RuleChangeType,
+
+    /// One-sentence description of the change
+    pub summary: String,
+}
+
+/// A significant event: an epoch boundary (balance pass, edition) or a
+/// timeline annotation (FAQ/errata).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignificantEvent {
     /// Unique identifier (derived from type + date + title)
@@ -69,9 +109,18 @@ pub struct SignificantEvent {
     /// Type of significant event
     pub event_type: SignificantEventType,
 
-    /// Date of the event
+    /// Date the event was announced
     pub date: NaiveDate,
 
+    /// Date the changes take effect (None = same as the announcement)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_date: Option<NaiveDate>,
+
+    /// Days after the effective date during which tournaments still count
+    /// toward the previous epoch (lists were locked before the change)
+    #[serde(default)]
+    pub grace_days: u32,
+
     /// Title of the event
     pub title: String,
 
@@ -88,6 +137,14 @@ pub struct SignificantEvent {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub changes: Option<BalanceChanges>,
 
+    /// Rule changes parsed from the PDF (`parse-dataslates`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rule_changes: Vec<RuleChange>,
+
+    /// Factions the document touches, when detectable (canonical names)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub affected_factions: Vec<String>,
+
     /// When this record was created
     pub created_at: DateTime<Utc>,
 
@@ -115,11 +172,15 @@ impl SignificantEvent {
             id,
             event_type,
             date,
+            effective_date: None,
+            grace_days: 0,
             title,
             source_url,
             pdf_url: None,
             summary: None,
             changes: None,
+            rule_changes: Vec::new(),
+            affected_factions: Vec::new(),
             created_at: Utc::now(),
             extraction_confidence: Confidence::default(),
             needs_review: false,
@@ -127,6 +188,29 @@ impl SignificantEvent {
         }
     }
 
+    /// Builder method to set the effective date.
+    pub fn with_effective_date(mut self, date: NaiveDate) -> Self {
+        self.effective_date = Some(date);
+        self
+    }
+
+    /// Builder method to set the boundary grace period.
+    pub fn with_grace_days(mut self, days: u32) -> Self {
+        self.grace_days = days;
+        self
+    }
+
+    /// Date the changes take effect, falling back to the announcement date.
+    pub fn effective_from(&self) -> NaiveDate {
+        self.effective_date.unwrap_or(self.date)
+    }
+
+    /// First date on which tournaments are assigned to the epoch this event
+    /// starts (effective date plus grace period).
+    pub fn boundary_date(&self) -> NaiveDate {
+        self.effective_from() + chrono::Days::new(self.grace_days as u64)
+    }
+
     /// Builder method to set PDF URL.
     pub fn with_pdf_url(mut self, url: String) -> Self {
         self.pdf_url = Some(url);
@@ -139,6 +223,18 @@ impl SignificantEvent {
         self
     }
 
+    /// Builder method to set the affected factions.
+    pub fn with_affected_factions(mut self, factions: Vec<String>) -> Self {
+        self.affected_factions = factions;
+        self
+    }
+
+    /// Builder method to set the parsed rule changes.
+    pub fn with_rule_changes(mut self, changes: Vec<RuleChange>) -> Self {
+        self.rule_changes = changes;
+        self
+    }
+
     /// Builder method to set confidence.
     pub fn with_confidence(mut self, confidence: Confidence) -> Self {
         self.extraction_confidence = confidence;
@@ -223,6 +319,26 @@ mod tests {
             format!("{}", SignificantEventType::EditionRelease),
             "edition_release"
         );
+        assert_eq!(format!("{}", SignificantEventType::FaqUpdate), "faq_update");
+    }
+
+    #[test]
+    fn test_faq_update_is_not_a_boundary() {
+        assert!(SignificantEventType::BalanceUpdate.is_epoch_boundary());
+        assert!(SignificantEventType::EditionRelease.is_epoch_boundary());
+        assert!(!SignificantEventType::FaqUpdate.is_epoch_boundary());
+
+        let faq = SignificantEvent::new(
+            SignificantEventType::FaqUpdate,
+            NaiveDate::from_ymd_opt(2025, 7, 2).unwrap(),
+            "Aeldari FAQ".to_string(),
+            "https://example.com".to_string(),
+        )
+        .with_affected_factions(vec!["Aeldari".to_string()]);
+        let json = serde_json::to_string(&faq).unwrap();
+        assert!(json.contains("\"event_type\":\"faq_update\""));
+        let back: SignificantEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.affected_factions, vec!["Aeldari"]);
     }
 
     #[test]
@@ -258,7 +374,7 @@ mod tests {
                 faction: "Aeldari".to_string(),
                 direction: "nerf".to_string(),
                 summary: "Major points increases".to_string(),
-                points_changes: vec![PointsChange {
+                points_changes: vec![BalancePointsChange {
                     unit: "Fire Dragons".to_string(),
                     old_points: Some(85),
                     new_points: Some(100),
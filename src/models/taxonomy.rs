@@ -0,0 +1,424 @@
+//! Faction taxonomy: canonical factions, their aliases and allegiances, and
+//! the keywords that identify Space Marine chapters.
+//!
+//! The default taxonomy is data, embedded from `faction_taxonomy.toml`. A
+//! `state/faction_taxonomy.toml` in the data directory extends it (see
+//! [`FactionTaxonomy::load`]), so a new faction or a rename needs no code
+//! change. The CLI installs the merged taxonomy at startup; code that runs
+//! before that, or without a data directory, sees the embedded default.
+//! Aliases and faction groups from the local `overrides.toml` are added on
+//! top (see [`FactionTaxonomy::add_aliases`] and
+//! [`FactionTaxonomy::add_groups`]).
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The default taxonomy.
+pub const DEFAULT_TAXONOMY: &str = include_str!("faction_taxonomy.toml");
+
+/// File name of local taxonomy additions, under `state/`.
+pub const TAXONOMY_FILE: &str = "faction_taxonomy.toml";
+
+static INSTALLED: OnceLock<FactionTaxonomy> = OnceLock::new();
+static DEFAULT: OnceLock<FactionTaxonomy> = OnceLock::new();
+
+/// Errors from loading a taxonomy.
+#[derive(Debug, Error)]
+pub enum TaxonomyError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Parse error: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("Invalid taxonomy: {0}")]
+    Invalid(String),
+}
+
+/// A canonical faction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FactionInfo {
+    /// Canonical name, as stored and shown
+    #[serde(rename = "name")]
+    pub canonical_name: String,
+    /// "Imperium", "Chaos" or "Xenos"
+    pub allegiance: String,
+    /// Grouping within the allegiance (e.g. "Space Marines")
+    pub allegiance_sub: String,
+    /// Other spellings and old names, matched case-insensitively
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// A Space Marine chapter with its own codex supplement: "Space Marines"
+    /// with this as the subfaction resolves to this faction
+    #[serde(default)]
+    pub chapter: bool,
+}
+
+/// Keywords that identify a chapter played from the Space Marines codex.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChapterKeywords {
+    pub name: String,
+    /// Detachments only this chapter can take
+    #[serde(default)]
+    pub detachments: Vec<String>,
+    /// Named characters unique to this chapter
+    #[serde(default)]
+    pub characters: Vec<String>,
+}
+
+/// Result of resolving a raw faction string.
+#[derive(Debug, Clone)]
+pub struct ResolvedFaction {
+    pub faction: String,
+    pub subfaction: Option<String>,
+    pub allegiance: String,
+    pub allegiance_sub: String,
+}
+
+/// The faction taxonomy.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FactionTaxonomy {
+    /// Bumped when the default data changes
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default, rename = "faction")]
+    pub factions: Vec<FactionInfo>,
+    #[serde(default, rename = "chapter")]
+    pub chapters: Vec<ChapterKeywords>,
+    /// Lowercased names and aliases to their index in `factions`
+    #[serde(skip)]
+    index: HashMap<String, usize>,
+    /// Lowercased canonical names to the group reported in their place
+    #[serde(skip)]
+    groups: HashMap<String, String>,
+}
+
+impl FactionTaxonomy {
+    /// Parse a taxonomy from TOML.
+    pub fn parse(toml_text: &str) -> Result<Self, TaxonomyError> {
+        let mut taxonomy: FactionTaxonomy = toml::from_str(toml_text)?;
+        taxonomy.build_index()?;
+        Ok(taxonomy)
+    }
+
+    /// The embedded default taxonomy.
+    pub fn embedded() -> &'static FactionTaxonomy {
+        DEFAULT.get_or_init(|| {
+            Self::parse(DEFAULT_TAXONOMY).expect("embedded faction taxonomy is valid")
+        })
+    }
+
+    /// The default taxonomy extended with `state/faction_taxonomy.toml`
+    /// under `data_dir`, if that file exists.
+    ///
+    /// A faction or chapter in the local file replaces the default one with
+    /// the same name (case-insensitively); others are added. The local
+    /// version wins when it is higher.
+    pub fn load(data_dir: &Path) -> Result<Self, TaxonomyError> {
+        let mut taxonomy = Self::embedded().clone();
+        let path = data_dir.join("state").join(TAXONOMY_FILE);
+        if path.exists() {
+            let local: FactionTaxonomy = toml::from_str(&std::fs::read_to_string(&path)?)?;
+            taxonomy.merge(local);
+            taxonomy.build_index()?;
+        }
+        Ok(taxonomy)
+    }
+
+    /// Make this the taxonomy used by the lookup functions. Only the first
+    /// install takes effect; returns false if one was already installed.
+    pub fn install(self) -> bool {
+        INSTALLED.set(self).is_ok()
+    }
+
+    /// The installed taxonomy, or the embedded default.
+    pub fn global() -> &'static FactionTaxonomy {
+        INSTALLED.get().unwrap_or_else(|| Self::embedded())
+    }
+
+    fn merge(&mut self, local: FactionTaxonomy) {
+        self.version = self.version.max(local.version);
+        for faction in local.factions {
+            match self.factions.iter_mut().find(|f| {
+                f.canonical_name
+                    .eq_ignore_ascii_case(&faction.canonical_name)
+            }) {
+                Some(existing) => *existing = faction,
+                None => self.factions.push(faction),
+            }
+        }
+        for chapter in local.chapters {
+            match self
+                .chapters
+                .iter_mut()
+                .find(|c| c.name.eq_ignore_ascii_case(&chapter.name))
+            {
+                Some(existing) => *existing = chapter,
+                None => self.chapters.push(chapter),
+            }
+        }
+    }
+
+    fn build_index(&mut self) -> Result<(), TaxonomyError> {
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for (i, faction) in self.factions.iter().enumerate() {
+            let names = std::iter::once(&faction.canonical_name).chain(&faction.aliases);
+            for name in names {
+                let key = name.trim().to_lowercase();
+                if let Some(&other) = index.get(&key) {
+                    if other != i {
+                        return Err(TaxonomyError::Invalid(format!(
+                            "'{}' names both {} and {}",
+                            name, self.factions[other].canonical_name, faction.canonical_name
+                        )));
+                    }
+                }
+                index.insert(key, i);
+            }
+        }
+        self.index = index;
+        Ok(())
+    }
+
+    /// Add aliases (alias to faction name) to known factions. Nothing is
+    /// added if an alias names an unknown faction or clashes with another.
+    pub fn add_aliases(&mut self, aliases: &BTreeMap<String, String>) -> Result<(), TaxonomyError> {
+        let mut factions = self.factions.clone();
+        for (alias, faction) in aliases {
+            let Some(&i) = self.index.get(faction.trim().to_lowercase().as_str()) else {
+                return Err(TaxonomyError::Invalid(format!(
+                    "alias '{}' names unknown faction {}",
+                    alias, faction
+                )));
+            };
+            factions[i].aliases.push(alias.clone());
+        }
+        let previous = std::mem::replace(&mut self.factions, factions);
+        if let Err(e) = self.build_index() {
+            self.factions = previous;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Report factions (by name or alias; unknown names as written) under
+    /// a group name instead, e.g. both Knights factions as "Knights".
+    pub fn add_groups(&mut self, groups: &BTreeMap<String, String>) {
+        for (faction, group) in groups {
+            let key = match self.lookup(faction) {
+                Some(info) => info.canonical_name.to_lowercase(),
+                None => faction.trim().to_lowercase(),
+            };
+            self.groups.insert(key, group.trim().to_string());
+        }
+    }
+
+    /// A faction's canonical name, ignoring groups. Unknown names are kept,
+    /// trimmed.
+    pub fn canonical<'a>(&'a self, name: &'a str) -> &'a str {
+        let trimmed = name.trim();
+        self.lookup(trimmed)
+            .map(|info| info.canonical_name.as_str())
+            .unwrap_or(trimmed)
+    }
+
+    /// The name a faction is reported under: its canonical name, or its
+    /// group if it has one. Unknown names are kept, trimmed.
+    pub fn normalize(&self, name: &str) -> String {
+        let name = self.canonical(name);
+        if !self.groups.is_empty() {
+            if let Some(group) = self.groups.get(&name.to_lowercase()) {
+                return group.clone();
+            }
+        }
+        name.to_string()
+    }
+
+    /// Look up a faction by name or alias.
+    pub fn lookup(&self, name: &str) -> Option<&FactionInfo> {
+        self.index
+            .get(name.trim().to_lowercase().as_str())
+            .map(|&i| &self.factions[i])
+    }
+
+    /// Resolve a raw faction + subfaction into canonical faction, subfaction,
+    /// and allegiance.
+    pub fn resolve(&self, faction: &str, subfaction: Option<&str>) -> ResolvedFaction {
+        let resolved = |info: &FactionInfo, subfaction: Option<String>| ResolvedFaction {
+            faction: info.canonical_name.clone(),
+            subfaction,
+            allegiance: info.allegiance.clone(),
+            allegiance_sub: info.allegiance_sub.clone(),
+        };
+
+        // A subfaction that is a chapter with its own codex is the faction
+        if let Some(info) = subfaction
+            .and_then(|sub| self.lookup(sub))
+            .filter(|info| info.chapter)
+        {
+            return resolved(info, None);
+        }
+
+        match self.lookup(faction) {
+            Some(info) => resolved(info, subfaction.map(|s| s.to_string())),
+            None => ResolvedFaction {
+                faction: faction.trim().to_string(),
+                subfaction: subfaction.map(|s| s.to_string()),
+                allegiance: "Unknown".to_string(),
+                allegiance_sub: "Unknown".to_string(),
+            },
+        }
+    }
+
+    /// Detect a chapter from army list text that names only "Space Marines".
+    ///
+    /// Looks for the chapter after the faction ("Space Marines\nChapter",
+    /// "Adeptus Astartes - Chapter", "Space Marines (Chapter)"), then for a
+    /// chapter-only detachment, then for a chapter's named character.
+    pub fn detect_chapter(&self, raw_text: &str) -> Option<&str> {
+        let named = |candidate: &str| {
+            self.chapters
+                .iter()
+                .find(|c| c.name.eq_ignore_ascii_case(candidate.trim()))
+                .map(|c| c.name.as_str())
+        };
+
+        let patterns = [
+            r"(?m)Space Marines\n(\w[\w\s]+)\n",
+            r"(?i)Adeptus Astartes\s*-\s*(\w[\w\s]+?)(?:\s*-|\s*\n|\s*\[)",
+            r"(?i)Space Marines\s*\((\w[\w\s]+?)\)",
+        ];
+        for pattern in patterns {
+            let re = Regex::new(pattern).expect("chapter pattern is valid");
+            if let Some(chapter) = re.captures(raw_text).and_then(|caps| named(&caps[1])) {
+                return Some(chapter);
+            }
+        }
+
+        let lower = raw_text.to_lowercase();
+        if let Some(chapter) = self.chapters.iter().find(|c| {
+            c.detachments
+                .iter()
+                .any(|d| lower.contains(&d.to_lowercase()))
+        }) {
+            return Some(&chapter.name);
+        }
+
+        self.chapters
+            .iter()
+            .find(|c| {
+                c.characters
+                    .iter()
+                    .any(|name| raw_text.contains(name.as_str()))
+            })
+            .map(|c| c.name.as_str())
+    }
+}
+
+/// Look up faction info from the installed taxonomy.
+pub fn lookup_faction(name: &str) -> Option<&'static FactionInfo> {
+    FactionTaxonomy::global().lookup(name)
+}
+
+/// Get the allegiance for a faction name. Returns None if not found.
+pub fn faction_allegiance(name: &str) -> Option<&'static str> {
+    lookup_faction(name).map(|info| info.allegiance.as_str())
+}
+
+/// Resolve a raw faction + subfaction into canonical faction, subfaction, and allegiance.
+///
+/// Handles cases like:
+/// - `faction: "Space Marines", subfaction: "Blood Angels"` → `faction: "Blood Angels", subfaction: None`
+/// - `faction: "Ultramarines"` → `faction: "Ultramarines"`
+/// - `faction: "Adeptus Astartes"` → `faction: "Space Marines"`
+/// - `faction: "Blood Angels"` → `faction: "Blood Angels"`
+pub fn resolve_faction(faction: &str, subfaction: Option<&str>) -> ResolvedFaction {
+    FactionTaxonomy::global().resolve(faction, subfaction)
+}
+
+/// Normalize faction names to canonical forms.
+/// Handles common variants and abbreviations found in tournament data, and
+/// reports grouped factions under their group. For analytics and reporting;
+/// names that get stored use [`canonical_faction_name`].
+pub fn normalize_faction_name(name: &str) -> String {
+    FactionTaxonomy::global().normalize(name)
+}
+
+/// The canonical name for a faction, without local groups applied, so
+/// stored records don't depend on `overrides.toml`.
+pub fn canonical_faction_name(name: &str) -> String {
+    FactionTaxonomy::global().canonical(name).to_string()
+}
+
+/// Detect a specific Space Marine chapter from army list raw text.
+///
+/// BCP often returns "Space Marines" or "Space Marines (Astartes)" as the faction,
+/// but the raw text contains the actual chapter (Ultramarines, Salamanders, etc.).
+/// Returns `Some("Ultramarines")` etc. if detected, `None` if truly generic.
+pub fn detect_chapter_from_raw_text(raw_text: &str) -> Option<&'static str> {
+    FactionTaxonomy::global().detect_chapter(raw_text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_file_extends_default() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("state")).unwrap();
+        std::fs::write(
+            tmp.path().join("state").join(TAXONOMY_FILE),
+            r#"
+version = 2
+
+[[faction]]
+name = "Aeldari"
+allegiance = "Xenos"
+allegiance_sub = "Xenos"
+aliases = ["eldar", "craftworlds"]
+
+[[faction]]
+name = "Squats"
+allegiance = "Xenos"
+allegiance_sub = "Xenos"
+
+[[chapter]]
+name = "Flesh Tearers"
+characters = ["Gabriel Seth"]
+"#,
+        )
+        .unwrap();
+
+        let taxonomy = FactionTaxonomy::load(tmp.path()).unwrap();
+        assert_eq!(taxonomy.version, 2);
+        assert_eq!(taxonomy.lookup("Eldar").unwrap().canonical_name, "Aeldari");
+        // Replaced, not merged: the default "harlequins" alias is gone
+        assert!(taxonomy.lookup("harlequins").is_none());
+        assert_eq!(taxonomy.lookup("squats").unwrap().allegiance, "Xenos");
+        assert_eq!(
+            taxonomy.detect_chapter("Captain\nGabriel Seth"),
+            Some("Flesh Tearers")
+        );
+        assert_eq!(
+            FactionTaxonomy::embedded()
+                .lookup("tau")
+                .unwrap()
+                .canonical_name,
+            "T'au Empire"
+        );
+
+        let clash = "[[faction]]\nname = \"A\"\nallegiance = \"X\"\nallegiance_sub = \"X\"\n\
+                     [[faction]]\nname = \"B\"\nallegiance = \"X\"\nallegiance_sub = \"X\"\n\
+                     aliases = [\"a\"]\n";
+        assert!(matches!(
+            FactionTaxonomy::parse(clash),
+            Err(TaxonomyError::Invalid(_))
+        ));
+    }
+}
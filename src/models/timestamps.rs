@@ -0,0 +1,86 @@
+//! Record timestamps for last-write-wins merging.
+
+use chrono::{DateTime, Utc};
+
+use super::{ArmyList, Event, Pairing, Placement};
+
+/// A record that tracks when it was created and last changed.
+///
+/// Writes append, so the same ID can appear more than once across an
+/// epoch's files. Readers keep the copy with the latest
+/// [`last_modified`](Timestamped::last_modified), and anything that rewrites
+/// an existing record calls [`touch`](Timestamped::touch) so its fix wins
+/// over stale copies.
+pub trait Timestamped {
+    fn created_at(&self) -> DateTime<Utc>;
+
+    fn updated_at(&self) -> Option<DateTime<Utc>>;
+
+    fn set_updated_at(&mut self, at: DateTime<Utc>);
+
+    /// When the record was last written.
+    fn last_modified(&self) -> DateTime<Utc> {
+        self.updated_at().unwrap_or(self.created_at())
+    }
+
+    /// Mark the record as changed now.
+    fn touch(&mut self) {
+        self.set_updated_at(Utc::now());
+    }
+}
+
+impl Timestamped for Event {
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn updated_at(&self) -> Option<DateTime<Utc>> {
+        self.updated_at
+    }
+
+    fn set_updated_at(&mut self, at: DateTime<Utc>) {
+        self.updated_at = Some(at);
+    }
+}
+
+impl Timestamped for Placement {
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn updated_at(&self) -> Option<DateTime<Utc>> {
+        self.updated_at
+    }
+
+    fn set_updated_at(&mut self, at: DateTime<Utc>) {
+        self.updated_at = Some(at);
+    }
+}
+
+impl Timestamped for ArmyList {
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn updated_at(&self) -> Option<DateTime<Utc>> {
+        self.updated_at
+    }
+
+    fn set_updated_at(&mut self, at: DateTime<Utc>) {
+        self.updated_at = Some(at);
+    }
+}
+
+impl Timestamped for Pairing {
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn updated_at(&self) -> Option<DateTime<Utc>> {
+        self.updated_at
+    }
+
+    fn set_updated_at(&mut self, at: DateTime<Utc>) {
+        self.updated_at = Some(at);
+    }
+}
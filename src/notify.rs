@@ -0,0 +1,378 @@
+//! Watchlist notifications.
+//!
+//! After a sync run, placements it stored at or above `[notifications] top`
+//! are checked against the watch rules, and any hits are posted to each
+//! configured webhook, shaped for Discord, Slack or as plain JSON.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::config::{NotificationsConfig, WatchRule, WebhookConfig, WebhookKind};
+use crate::models::{ArmyList, Event, Placement};
+use crate::storage::jsonl::list_epochs;
+use crate::storage::{EntityType, IndexedReader, StorageConfig, StorageError};
+use crate::sync::{normalize_player_name, PostSyncHook, SyncResult};
+
+/// Alerts spelled out in a chat message; the rest are counted.
+const MAX_LISTED: usize = 20;
+
+/// Errors delivering a notification.
+#[derive(Debug, Error)]
+pub enum NotifyError {
+    #[error("Request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Webhook {url} returned {status}")]
+    Status { url: String, status: u16 },
+}
+
+/// A watched faction, player or unit placing at an event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Alert {
+    pub event_id: String,
+    pub event_name: String,
+    pub event_date: NaiveDate,
+    pub epoch_id: String,
+    pub rank: u32,
+    pub player_name: String,
+    pub faction: String,
+    pub detachment: Option<String>,
+    /// The rules that matched, e.g. "faction Aeldari"
+    pub watches: Vec<String>,
+}
+
+impl Alert {
+    /// One line of a chat message.
+    fn line(&self) -> String {
+        let detachment = self
+            .detachment
+            .as_deref()
+            .map(|d| format!(", {}", d))
+            .unwrap_or_default();
+        format!(
+            "#{} {} ({}{}) at {} on {} [{}]",
+            self.rank,
+            self.player_name,
+            self.faction,
+            detachment,
+            self.event_name,
+            self.event_date,
+            self.watches.join("; ")
+        )
+    }
+}
+
+/// Short description of a rule for alert text.
+fn describe(rule: &WatchRule) -> String {
+    [
+        ("faction", &rule.faction),
+        ("player", &rule.player),
+        ("unit", &rule.unit),
+    ]
+    .into_iter()
+    .filter_map(|(field, value)| value.as_ref().map(|v| format!("{} {}", field, v)))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// Whether a placement, with its list if known, satisfies every field of a
+/// rule.
+fn matches(rule: &WatchRule, placement: &Placement, list: Option<&ArmyList>) -> bool {
+    let faction_ok = rule.faction.as_deref().is_none_or(|f| {
+        placement.faction.eq_ignore_ascii_case(f)
+            || placement
+                .subfaction
+                .as_deref()
+                .is_some_and(|s| s.eq_ignore_ascii_case(f))
+    });
+    let player_ok = rule
+        .player
+        .as_deref()
+        .is_none_or(|p| normalize_player_name(&placement.player_name) == normalize_player_name(p));
+    let unit_ok = rule.unit.as_deref().is_none_or(|u| {
+        list.is_some_and(|l| l.units.iter().any(|unit| unit.name.eq_ignore_ascii_case(u)))
+    });
+    faction_ok && player_ok && unit_ok
+}
+
+/// Placements stored since `since` that rank `top` or better and match a
+/// rule, best first within each event.
+pub fn find_alerts(
+    storage: &StorageConfig,
+    config: &NotificationsConfig,
+    since: DateTime<Utc>,
+) -> Result<Vec<Alert>, StorageError> {
+    let mut alerts = Vec::new();
+    for epoch_id in list_epochs(storage)? {
+        let placements =
+            IndexedReader::<Placement>::for_entity(storage, EntityType::Placement, &epoch_id)
+                .index()?;
+        let mut fresh: Vec<&Placement> = placements
+            .all()
+            .iter()
+            .filter(|p| p.created_at >= since && p.rank <= config.top)
+            .collect();
+        if fresh.is_empty() {
+            continue;
+        }
+        fresh.sort_by(|a, b| (a.event_id.as_str(), a.rank).cmp(&(b.event_id.as_str(), b.rank)));
+
+        let events =
+            IndexedReader::<Event>::for_entity(storage, EntityType::Event, &epoch_id).index()?;
+        let lists = IndexedReader::<ArmyList>::for_entity(storage, EntityType::ArmyList, &epoch_id)
+            .index()?;
+        for placement in fresh {
+            let Some(event) = events.get(placement.event_id.as_str()) else {
+                continue;
+            };
+            let list = match &placement.list_id {
+                Some(id) => lists.get(id.as_str()),
+                None => lists
+                    .for_event(placement.event_id.as_str())
+                    .into_iter()
+                    .find(|l| {
+                        l.player_name.as_deref().map(normalize_player_name)
+                            == Some(normalize_player_name(&placement.player_name))
+                    }),
+            };
+            let watches: Vec<String> = config
+                .watch
+                .iter()
+                .filter(|rule| matches(rule, placement, list))
+                .map(describe)
+                .collect();
+            if watches.is_empty() {
+                continue;
+            }
+            alerts.push(Alert {
+                event_id: event.id.as_str().to_string(),
+                event_name: event.name.clone(),
+                event_date: event.date,
+                epoch_id: epoch_id.clone(),
+                rank: placement.rank,
+                player_name: placement.player_name.clone(),
+                faction: placement.faction.clone(),
+                detachment: placement.detachment.clone(),
+                watches,
+            });
+        }
+    }
+    Ok(alerts)
+}
+
+/// Request body for a webhook of `kind`.
+pub fn payload(kind: WebhookKind, alerts: &[Alert]) -> Value {
+    if kind == WebhookKind::Generic {
+        return json!({ "alerts": alerts });
+    }
+    let mut lines = vec![format!("Watchlist: {} new top placements", alerts.len())];
+    lines.extend(alerts.iter().take(MAX_LISTED).map(Alert::line));
+    if alerts.len() > MAX_LISTED {
+        lines.push(format!("...and {} more", alerts.len() - MAX_LISTED));
+    }
+    let text = lines.join("\n");
+    match kind {
+        WebhookKind::Discord => json!({ "content": text }),
+        _ => json!({ "text": text }),
+    }
+}
+
+/// An alert for `meta-agent notify test`.
+pub fn sample_alert() -> Alert {
+    Alert {
+        event_id: "test".to_string(),
+        event_name: "Test Event".to_string(),
+        event_date: Utc::now().date_naive(),
+        epoch_id: "current".to_string(),
+        rank: 1,
+        player_name: "Test Player".to_string(),
+        faction: "Aeldari".to_string(),
+        detachment: None,
+        watches: vec!["test notification".to_string()],
+    }
+}
+
+/// Sends watchlist alerts to the configured webhooks.
+pub struct Notifier {
+    config: NotificationsConfig,
+    client: reqwest::Client,
+}
+
+impl Notifier {
+    pub fn new(config: NotificationsConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self { config, client }
+    }
+
+    /// A notifier for `[notifications]`, unless it has no webhooks or no
+    /// watch rules.
+    pub fn from_config(config: &NotificationsConfig) -> Option<Arc<Self>> {
+        config
+            .enabled()
+            .then(|| Arc::new(Self::new(config.clone())))
+    }
+
+    pub fn webhooks(&self) -> &[WebhookConfig] {
+        &self.config.webhooks
+    }
+
+    /// Post alerts to one webhook.
+    pub async fn post(&self, webhook: &WebhookConfig, alerts: &[Alert]) -> Result<(), NotifyError> {
+        let response = self
+            .client
+            .post(&webhook.url)
+            .json(&payload(webhook.kind, alerts))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(NotifyError::Status {
+                url: webhook.url.clone(),
+                status: response.status().as_u16(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Post alerts to every webhook, logging failures. Returns how many
+    /// webhooks accepted them.
+    pub async fn send(&self, alerts: &[Alert]) -> usize {
+        let mut sent = 0;
+        for webhook in &self.config.webhooks {
+            match self.post(webhook, alerts).await {
+                Ok(()) => sent += 1,
+                Err(e) => warn!("Notification to {} failed: {}", webhook.url, e),
+            }
+        }
+        sent
+    }
+}
+
+#[async_trait::async_trait]
+impl PostSyncHook for Notifier {
+    async fn after_sync(
+        &self,
+        storage: &StorageConfig,
+        started_at: DateTime<Utc>,
+        result: &SyncResult,
+    ) {
+        if result.placements_synced == 0 {
+            return;
+        }
+        let alerts = match find_alerts(storage, &self.config, started_at) {
+            Ok(alerts) => alerts,
+            Err(e) => {
+                warn!("Could not check watchlist: {}", e);
+                return;
+            }
+        };
+        if alerts.is_empty() {
+            return;
+        }
+        let sent = self.send(&alerts).await;
+        info!(
+            "Sent {} watchlist alerts to {} of {} webhooks",
+            alerts.len(),
+            sent,
+            self.config.webhooks.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EntityId, Unit};
+    use crate::storage::JsonlWriter;
+
+    #[test]
+    fn test_find_alerts_and_payloads() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        let epoch = EntityId::from("current");
+        let since = Utc::now() - chrono::Duration::minutes(5);
+
+        let event = Event::new(
+            "London GT".to_string(),
+            NaiveDate::from_ymd_opt(2026, 5, 2).unwrap(),
+            "https://example.com/london".to_string(),
+            "bcp".to_string(),
+            epoch.clone(),
+        );
+        let placement = |rank: u32, player: &str, faction: &str| {
+            Placement::new(
+                event.id.clone(),
+                epoch.clone(),
+                rank,
+                player.to_string(),
+                faction.to_string(),
+            )
+        };
+        let mut list = ArmyList::new(
+            "Orks".to_string(),
+            2000,
+            vec![Unit::new("Ghazghkull Thraka".to_string(), 1)],
+            String::new(),
+        );
+        list.event_id = Some(event.id.clone());
+        list.player_name = Some("Bob".to_string());
+        let mut stale = placement(3, "Carol", "Aeldari");
+        stale.created_at = since - chrono::Duration::days(1);
+        let placements = [
+            placement(1, "Alice  Smith", "Aeldari"),
+            placement(2, "Bob", "Orks"),
+            stale,
+            placement(5, "Dave", "Aeldari"),
+        ];
+        JsonlWriter::<Event>::for_entity(&storage, EntityType::Event, epoch.as_str())
+            .write_all(std::slice::from_ref(&event))
+            .unwrap();
+        JsonlWriter::<Placement>::for_entity(&storage, EntityType::Placement, epoch.as_str())
+            .write_all(&placements)
+            .unwrap();
+        JsonlWriter::<ArmyList>::for_entity(&storage, EntityType::ArmyList, epoch.as_str())
+            .write_all(&[list])
+            .unwrap();
+
+        let config = NotificationsConfig {
+            watch: vec![
+                WatchRule {
+                    faction: Some("aeldari".to_string()),
+                    ..Default::default()
+                },
+                WatchRule {
+                    player: Some("alice smith".to_string()),
+                    ..Default::default()
+                },
+                WatchRule {
+                    unit: Some("Ghazghkull Thraka".to_string()),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let alerts = find_alerts(&storage, &config, since).unwrap();
+        let hits: Vec<_> = alerts
+            .iter()
+            .map(|a| (a.rank, a.player_name.as_str(), a.watches.len()))
+            .collect();
+        assert_eq!(hits, vec![(1, "Alice  Smith", 2), (2, "Bob", 1)]);
+        assert_eq!(alerts[1].watches, vec!["unit Ghazghkull Thraka"]);
+
+        let discord = payload(WebhookKind::Discord, &alerts);
+        let content = discord["content"].as_str().unwrap();
+        assert!(content.contains("#2 Bob (Orks) at London GT on 2026-05-02"));
+        assert!(payload(WebhookKind::Slack, &alerts)["text"].is_string());
+        assert_eq!(
+            payload(WebhookKind::Generic, &alerts)["alerts"][0]["faction"],
+            "Aeldari"
+        );
+    }
+}
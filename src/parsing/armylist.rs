@@ -0,0 +1,1376 @@
+//! Army list text parsing.
+//!
+//! Lists reach us as free text in a handful of export formats (see
+//! [`ListFormat`]). [`parse_units_from_raw_text`] is the regex fast path
+//! used before any AI normalization: it pulls out units with their points,
+//! counts, wargear and keywords, and returns nothing when it can't, so the
+//! caller falls back to the AI. [`parse_list`] adds what the unit lines
+//! don't carry: format, detachment, total points, Enhancements and which
+//! characters lead which units.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::models::Unit;
+
+pub use crate::models::detect_chapter_from_raw_text;
+
+/// Compiled patterns, shared across calls; lists are parsed in bulk.
+struct Patterns {
+    enhancement: Regex,
+    attachment: Regex,
+    detachment: Regex,
+    total: Regex,
+    declared: Regex,
+    battle_size: Regex,
+    nr_header: Regex,
+    line_prefix: Regex,
+    item: Regex,
+    parens: Regex,
+    bracket: Regex,
+    dash: Regex,
+    wargear: Regex,
+    n_with: Regex,
+    name_count: Regex,
+    upgrade_cost: Regex,
+}
+
+fn patterns() -> &'static Patterns {
+    static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| Patterns {
+        enhancement: Regex::new(r"(?i)\benhancements?\s*:\s*([^,(\[]+?)\s*(?:[\(\[]\+?\s*(\d+)\s*(?:pts?|points?)[\)\]])?\s*(?:\(on\s+([^)]+)\))?\s*(?:,|$)").unwrap(),
+        attachment: Regex::new(r"(?i)^[\u{2022}\u{25e6}*+\-\s]*(?:attached to|leading|leads|leader of)\s*:?\s+(.+?)\s*$").unwrap(),
+        detachment: Regex::new(r"(?i)^[+\s]*detachment(?:\s+rule)?\s*:\s*(.+?)\s*$").unwrap(),
+        total: Regex::new(r"(?i)\btotal[^:]*:\s*(\d[\d,]*)\s*(?:pts?|points?)?").unwrap(),
+        declared: Regex::new(r"(?i)[\(\[](\d[\d,]*)\s*(?:pts?|points?)[\)\]]").unwrap(),
+        battle_size: Regex::new(r"(?i)^(?:strike force|incursion|onslaught)\b").unwrap(),
+        nr_header: Regex::new(r"^\+\s+[A-Z][A-Z ]+:").unwrap(),
+        line_prefix: Regex::new(r"^(?:(Char|EH|CH|BL|IN|VE|MO|BE|DT)\d+:\s*)").unwrap(),
+        item: Regex::new(r"(\d+)x\s+([^,]+)").unwrap(),
+        parens: Regex::new(r"(?i)^(?:(\d+)x?\s+)?(.+?)\s*\((\d+)\s*(?:pts?|points?)\)(.*)").unwrap(),
+        bracket: Regex::new(r"(?i)^(?:\((\d+)\)\s+)?(.+?)\s*\[(\d+)\s*(?:pts?|points?)\](.*)").unwrap(),
+        dash: Regex::new(r"(?i)^(\d+)\s+(.+?)\s*[-–]\s*(\d+)\s*(?:pts?|points?)(.*)").unwrap(),
+        wargear: Regex::new(r"(?:^[\u{2022}\u{25e6}\u{2013}*\-]\s*)?(\d+)x\s+(.+)").unwrap(),
+        n_with: Regex::new(r"^\d+ with ").unwrap(),
+        name_count: Regex::new(r"^(.+?)\s*\((\d+)\)\s*$").unwrap(),
+        upgrade_cost: Regex::new(r"(?i)\s*[\(\[]\+\s*\d+\s*(?:pts?|points?)[\)\]]").unwrap(),
+    })
+}
+
+/// Export format a list's text was written in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListFormat {
+    /// Warhammer 40,000 app: `Unit (95 Points)` with `•` gear bullets under
+    /// `CHARACTERS` / `BATTLELINE` / `OTHER DATASHEETS` headers
+    GwApp,
+    /// New Recruit's text export: a `+ FACTION KEYWORD:` header block and
+    /// `Char1: 1x Unit (95 pts): gear, gear` lines
+    NewRecruit,
+    /// Free text pasted into BCP: parenthesised, bracket or dash unit lines
+    Bcp,
+    /// No units could be read
+    #[default]
+    Unknown,
+}
+
+/// An Enhancement taken in a list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ParsedEnhancement {
+    pub name: String,
+    /// Character carrying it, if the list says
+    pub unit: Option<String>,
+    /// Points cost, if listed separately from the unit's
+    pub points: Option<u32>,
+}
+
+/// A character leading a unit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Attachment {
+    pub leader: String,
+    pub unit: String,
+}
+
+/// Everything read from a list's text.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ParsedList {
+    pub format: ListFormat,
+    pub detachment: Option<String>,
+    /// Total the list declares for itself, not the sum of its units
+    pub total_points: Option<u32>,
+    pub units: Vec<Unit>,
+    pub enhancements: Vec<ParsedEnhancement>,
+    pub attachments: Vec<Attachment>,
+}
+
+/// Parse a list's text. Units are as from [`parse_units_from_raw_text`],
+/// each with the Enhancement it carries; the rest is best effort and left
+/// empty when the text doesn't say.
+pub fn parse_list(raw_text: &str) -> ParsedList {
+    let re_enhancement = &patterns().enhancement;
+    let re_attachment = &patterns().attachment;
+    let re_detachment = &patterns().detachment;
+    let re_total = &patterns().total;
+    let re_declared = &patterns().declared;
+    let re_battle_size = &patterns().battle_size;
+    let parse_points = |s: &str| s.replace(',', "").parse::<u32>().ok();
+
+    let (units, unit_lines) = parse_units_and_lines(raw_text);
+    let lines: Vec<&str> = raw_text.lines().collect();
+    // Unit whose block a line falls in
+    let owner = |line: usize| {
+        unit_lines
+            .iter()
+            .rposition(|&start| start <= line)
+            .map(|i| units[i].name.clone())
+    };
+    // A unit named in free text, e.g. "Char2: Autarch" or "Fire Dragons"
+    let find_unit = |text: &str| {
+        let (text, _) = strip_line_prefix(text.trim());
+        let lower = text.trim().to_lowercase();
+        units
+            .iter()
+            .find(|u| u.name.to_lowercase() == lower)
+            .or_else(|| {
+                units
+                    .iter()
+                    .find(|u| lower.contains(&u.name.to_lowercase()))
+            })
+            .map(|u| u.name.clone())
+            .unwrap_or_else(|| text.trim().to_string())
+    };
+    let first_unit_line = unit_lines.first().copied().unwrap_or(lines.len());
+
+    let mut parsed = ParsedList {
+        format: detect_format(&lines, !units.is_empty()),
+        ..Default::default()
+    };
+    let mut after_battle_size = false;
+    for (i, raw_line) in lines.iter().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if i < first_unit_line {
+            if let Some(caps) = re_detachment.captures(line) {
+                parsed.detachment.get_or_insert_with(|| caps[1].to_string());
+            } else if after_battle_size
+                && parsed.detachment.is_none()
+                && !re_declared.is_match(line)
+                && line.chars().any(|c| c.is_lowercase())
+            {
+                // GW app: the detachment follows the battle size line
+                parsed.detachment = Some(line.to_string());
+            }
+            after_battle_size = re_battle_size.is_match(line);
+
+            if parsed.total_points.is_none() {
+                // The list's title line, "My List (1995 Points)"
+                let is_title = lines[..i].iter().all(|l| l.trim().is_empty())
+                    && !re_battle_size.is_match(line);
+                parsed.total_points = re_total
+                    .captures(line)
+                    .or_else(|| re_declared.captures(line).filter(|_| is_title))
+                    .and_then(|caps| parse_points(&caps[1]));
+            }
+        }
+
+        for caps in re_enhancement.captures_iter(line) {
+            let name = caps[1].trim().to_string();
+            let unit = caps.get(3).map(|m| find_unit(m.as_str())).or_else(|| {
+                if i >= first_unit_line {
+                    owner(i)
+                } else {
+                    None
+                }
+            });
+            let points = caps.get(2).and_then(|m| parse_points(m.as_str()));
+            match parsed.enhancements.iter_mut().find(|e| e.name == name) {
+                Some(existing) => {
+                    existing.unit = existing.unit.take().or(unit);
+                    existing.points = existing.points.or(points);
+                }
+                None => parsed
+                    .enhancements
+                    .push(ParsedEnhancement { name, unit, points }),
+            }
+        }
+
+        if let Some(caps) = re_attachment.captures(line) {
+            if let Some(leader) = owner(i) {
+                let unit = find_unit(&caps[1]);
+                if unit != leader {
+                    parsed.attachments.push(Attachment { leader, unit });
+                }
+            }
+        }
+    }
+    parsed.units = units;
+    // Carriers take their Enhancement off the list, not their wargear
+    for enhancement in &parsed.enhancements {
+        let carrier = enhancement.unit.as_ref().and_then(|name| {
+            parsed
+                .units
+                .iter_mut()
+                .find(|u| &u.name == name && u.enhancement.is_none())
+        });
+        if let Some(unit) = carrier {
+            unit.enhancement = Some(enhancement.name.clone());
+        }
+    }
+    parsed
+}
+
+/// Guess a list's export format from its lines.
+fn detect_format(lines: &[&str], has_units: bool) -> ListFormat {
+    if !has_units {
+        return ListFormat::Unknown;
+    }
+    let re_nr_header = &patterns().nr_header;
+    if lines.iter().any(|l| re_nr_header.is_match(l.trim())) {
+        return ListFormat::NewRecruit;
+    }
+    let exported = lines
+        .iter()
+        .any(|l| l.trim().starts_with("Exported with App Version"));
+    let bulleted = lines.iter().any(|l| l.trim().starts_with('\u{2022}'));
+    let headed = lines
+        .iter()
+        .any(|l| matches!(l.trim(), "CHARACTERS" | "BATTLELINE" | "OTHER DATASHEETS"));
+    if exported || (bulleted && headed) {
+        return ListFormat::GwApp;
+    }
+    ListFormat::Bcp
+}
+
+/// Strip common line prefixes used in BCP army list formats.
+///
+/// Handles: `Char1:`, `EH1:`, `CH2:`, `BL3:`, `IN4:`, `VE1:`, `MO1:`, `BE1:`, `DT1:`.
+/// Returns `(stripped_line, prefix_hint)` where prefix_hint helps classify unit roles.
+fn strip_line_prefix(line: &str) -> (&str, Option<&str>) {
+    let re = &patterns().line_prefix;
+    match re.captures(line) {
+        Some(caps) => {
+            let prefix = caps.get(1).map(|m| m.as_str());
+            (&line[caps.get(0).unwrap().end()..], prefix)
+        }
+        None => (line, None),
+    }
+}
+
+/// Map a line prefix or section header to unit keywords.
+fn keywords_for_section(section: &str) -> Vec<String> {
+    match section {
+        "CHARACTERS" | "CHARACTER" | "Char" | "EH" | "CH" => {
+            vec!["Character".to_string()]
+        }
+        "BATTLELINE" | "BL" | "BE" => vec!["Battleline".to_string()],
+        "OTHER DATASHEETS" => vec!["Other".to_string()],
+        "IN" => vec!["Infantry".to_string()],
+        "DEDICATED TRANSPORTS" | "DT" => vec!["Dedicated Transport".to_string()],
+        "VE" => vec!["Vehicle".to_string()],
+        "MO" => vec!["Monster".to_string()],
+        "ALLIED UNITS" => vec!["Allied".to_string()],
+        "FORTIFICATIONS" => vec!["Fortification".to_string()],
+        _ => Vec::new(),
+    }
+}
+
+/// Process buffered gear lines for a unit, classifying model types vs weapons.
+///
+/// Uses indentation depth to distinguish:
+/// - Shallow indent (≤ min+1): model components → sum into unit count
+/// - Deeper indent: weapons → add to wargear with "Nx" prefix when qty > 1
+///
+/// If all lines are at the same indent (flat), treats them all as weapons.
+fn flush_gear_buffer(buffer: &mut Vec<(usize, u32, String)>, units: &mut [Unit]) {
+    if buffer.is_empty() || units.is_empty() {
+        buffer.clear();
+        return;
+    }
+    let unit = units.last_mut().unwrap();
+
+    let min_indent = buffer.iter().map(|g| g.0).min().unwrap_or(0);
+    let max_indent = buffer.iter().map(|g| g.0).max().unwrap_or(0);
+    let has_sub_levels = max_indent > min_indent + 1;
+
+    if has_sub_levels {
+        // Multi-level: top-level items are model components, deeper are weapons
+        let mut model_count = 0u32;
+        for (indent, qty, name) in buffer.iter() {
+            if *indent <= min_indent + 1 {
+                // Model component → contributes to squad count
+                model_count += qty;
+            } else {
+                // Weapon line
+                let formatted = if *qty > 1 {
+                    format!("{}x {}", qty, name)
+                } else {
+                    name.clone()
+                };
+                unit.wargear.push(formatted);
+            }
+        }
+        if model_count > 0 {
+            unit.count = model_count;
+        }
+    } else {
+        // Flat: all lines are direct weapons/gear
+        for (_, qty, name) in buffer.iter() {
+            if name.starts_with("Enhancement") || name.starts_with("Warlord") {
+                continue;
+            }
+            let formatted = if *qty > 1 {
+                format!("{}x {}", qty, name)
+            } else {
+                name.clone()
+            };
+            unit.wargear.push(formatted);
+        }
+    }
+    buffer.clear();
+}
+
+/// Parse inline wargear from text after the points value.
+///
+/// Handles: `Unit (120 pts) 4x Multi-melta, 1x Inferno pistol`
+/// Returns wargear items like `["4x Multi-melta", "Inferno pistol"]`.
+///
+/// New Recruit and bracket lists separate the gear with `:` or `,`
+/// (`Farseer (70 pts): Warlord, Singing spear`); there every item counts,
+/// with or without a quantity, except Warlord and Enhancement entries.
+fn parse_inline_wargear(text_after_points: &str) -> Vec<String> {
+    let re_item = &patterns().item;
+    let mut wargear = Vec::new();
+
+    let trimmed = text_after_points.trim_start();
+    if let Some(items) = trimmed
+        .strip_prefix(':')
+        .or_else(|| trimmed.strip_prefix(','))
+    {
+        for item in items.split(',').map(str::trim) {
+            let lower = item.to_lowercase();
+            if item.is_empty() || lower == "warlord" || lower.starts_with("enhancement") {
+                continue;
+            }
+            match re_item.captures(item) {
+                Some(caps) if caps.get(0).is_some_and(|m| m.start() == 0) => {
+                    let qty: u32 = caps[1].parse().unwrap_or(1);
+                    let name = caps[2].trim();
+                    wargear.push(if qty > 1 {
+                        format!("{}x {}", qty, name)
+                    } else {
+                        name.to_string()
+                    });
+                }
+                _ => wargear.push(item.to_string()),
+            }
+        }
+        return wargear;
+    }
+
+    for caps in re_item.captures_iter(text_after_points) {
+        let qty: u32 = caps[1].parse().unwrap_or(1);
+        let name = caps[2].trim().to_string();
+        if !name.is_empty() {
+            let formatted = if qty > 1 {
+                format!("{}x {}", qty, name)
+            } else {
+                name
+            };
+            wargear.push(formatted);
+        }
+    }
+    wargear
+}
+
+/// Parse units from army list raw text using regex.
+///
+/// Extracts unit names, points, counts, wargear (from bullet/indented lines
+/// and inline comma-separated gear), Enhancements (see [`parse_list`]) and
+/// keywords (from section headers / line prefixes).
+///
+/// Handles three BCP list formats:
+/// 1. **Parenthesized**: `Unit Name (XXpts)` — most common (~60%)
+/// 2. **Bracket**: `Unit Name [XXX pts]` — Russian tournament template (~20%)
+/// 3. **Dash**: `1 Unit Name - XXpts` — a few tournament organizers (~1%)
+///
+/// Returns an empty Vec if no units could be parsed (signals AI fallback).
+pub fn parse_units_from_raw_text(raw_text: &str) -> Vec<Unit> {
+    parse_list(raw_text).units
+}
+
+/// [`parse_units_from_raw_text`], plus the index of the line each unit
+/// starts on.
+fn parse_units_and_lines(raw_text: &str) -> (Vec<Unit>, Vec<usize>) {
+    // Format 1: "Unit Name (XXpts)" or "2x Unit Name (XX points)"
+    // Captures optional trailing text after the closing paren for inline wargear
+    let re_parens = &patterns().parens;
+    // Format 2: "Unit Name [XXX pts]" (bracket format, Russian template)
+    let re_bracket = &patterns().bracket;
+    // Format 3: "1 Unit Name - XXpts" (dash format)
+    let re_dash = &patterns().dash;
+    // Wargear line: "• 1x Storm bolter" or "  1x Bolt rifle" or "- 1x Weapon"
+    let re_wargear = &patterns().wargear;
+    // Skip "N with ..." wargear description lines
+    let re_n_with = &patterns().n_with;
+    // Extract (N) model count from name, e.g. "Retributor Squad (5)" → count=5
+    let re_name_count = &patterns().name_count;
+
+    let section_headers: std::collections::HashSet<&str> = [
+        "CHARACTERS",
+        "BATTLELINE",
+        "OTHER DATASHEETS",
+        "ALLIED UNITS",
+        "CHARACTER",
+        "DEDICATED TRANSPORTS",
+        "FORTIFICATIONS",
+    ]
+    .into_iter()
+    .collect();
+
+    let skip_names: std::collections::HashSet<&str> =
+        ["strike force", "incursion", "onslaught", "army roster"]
+            .into_iter()
+            .collect();
+
+    // Upgrade costs: "Enhancement: X (+15 pts)", "[+20 pts]"
+    let re_upgrade_cost = &patterns().upgrade_cost;
+
+    let mut units: Vec<Unit> = Vec::new();
+    let mut unit_lines: Vec<usize> = Vec::new();
+    let mut current_section = String::new();
+    // Buffer for gear lines: (indent, qty, name)
+    let mut gear_buffer: Vec<(usize, u32, String)> = Vec::new();
+
+    for (line_index, raw_line) in raw_text.lines().enumerate() {
+        let indent = raw_line.len() - raw_line.trim_start().len();
+        let line = raw_line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        // Track section headers for keyword assignment
+        if section_headers.contains(line) {
+            flush_gear_buffer(&mut gear_buffer, &mut units);
+            current_section = line.to_string();
+            continue;
+        }
+        // Also handle "Epic Hero:", "Character:", "Battleline:" labels
+        let stripped_colon = line.trim_end_matches(':');
+        if line.ends_with(':') && line.len() < 25 && !line.contains('[') && !line.contains('(') {
+            flush_gear_buffer(&mut gear_buffer, &mut units);
+            match stripped_colon {
+                "Epic Hero" | "Character" | "Characters" => {
+                    current_section = "CHARACTER".to_string();
+                }
+                "Battleline" => current_section = "BATTLELINE".to_string(),
+                "Other Datasheets" | "Other" => {
+                    current_section = "OTHER DATASHEETS".to_string();
+                }
+                "Dedicated Transports" => {
+                    current_section = "DEDICATED TRANSPORTS".to_string();
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        // Gear line detection: bullet-prefixed OR indented Nx lines (without points)
+        let starts_with_bullet = line.starts_with('\u{2022}')
+            || line.starts_with('\u{25e6}')
+            || line.starts_with('\u{2013}')
+            || line.starts_with('*')
+            || line.starts_with("- ");
+        let has_points = line.contains("pts") || line.contains("points") || line.contains("Points");
+        let is_indented_nx =
+            indent >= 2 && !units.is_empty() && !has_points && re_wargear.is_match(line);
+
+        if starts_with_bullet || is_indented_nx {
+            if !units.is_empty() {
+                if let Some(caps) = re_wargear.captures(line) {
+                    let qty: u32 = caps[1].parse().unwrap_or(1);
+                    let name = caps[2].trim().to_string();
+                    if !name.is_empty() {
+                        gear_buffer.push((indent, qty, name));
+                    }
+                }
+            }
+            continue;
+        }
+
+        // Skip non-unit lines
+        if line.starts_with("Enhancement:") || line.starts_with("Warlord") {
+            continue;
+        }
+        if line.starts_with("Exported with") || line == "undefined" {
+            continue;
+        }
+        if line.starts_with('+') || line.starts_with('#') {
+            continue;
+        }
+        // Upgrade costs would read as the unit's points; drop them, and
+        // lines that are nothing but an upgrade
+        let without_upgrades = re_upgrade_cost.replace_all(line, "");
+        if without_upgrades.len() != line.len()
+            && ![re_parens, re_bracket, re_dash]
+                .iter()
+                .any(|re| re.is_match(&without_upgrades))
+        {
+            continue;
+        }
+        let line = without_upgrades.as_ref();
+        if line.starts_with("==") {
+            continue;
+        }
+        if re_n_with.is_match(line) {
+            continue;
+        }
+
+        // Strip common line prefixes (Char1:, EH1:, CH2:, etc.)
+        let (stripped, prefix_hint) = strip_line_prefix(line);
+
+        // Try parenthesized format first, then bracket, then dash
+        let parsed = re_parens
+            .captures(stripped)
+            .map(|caps| {
+                let count: u32 = caps.get(1).map_or(1, |m| m.as_str().parse().unwrap_or(1));
+                let name = caps[2].trim().to_string();
+                let points: u32 = caps[3].parse().unwrap_or(0);
+                let trailing = caps.get(4).map_or("", |m| m.as_str());
+                (count, name, points, trailing.to_string())
+            })
+            .or_else(|| {
+                re_bracket.captures(stripped).map(|caps| {
+                    let count: u32 = caps.get(1).map_or(1, |m| m.as_str().parse().unwrap_or(1));
+                    let raw_name = caps[2].trim();
+                    let name = raw_name
+                        .split(',')
+                        .next()
+                        .unwrap_or(raw_name)
+                        .trim()
+                        .to_string();
+                    let points: u32 = caps[3].parse().unwrap_or(0);
+                    let trailing = caps.get(4).map_or("", |m| m.as_str());
+                    (count, name, points, trailing.to_string())
+                })
+            })
+            .or_else(|| {
+                re_dash.captures(stripped).map(|caps| {
+                    let count: u32 = caps[1].parse().unwrap_or(1);
+                    let name = caps[2].trim().to_string();
+                    let points: u32 = caps[3].parse().unwrap_or(0);
+                    let trailing = caps.get(4).map_or("", |m| m.as_str());
+                    (count, name, points, trailing.to_string())
+                })
+            });
+
+        if let Some((count, name, points, trailing)) = parsed {
+            if skip_names.contains(name.to_lowercase().as_str()) {
+                continue;
+            }
+            if name.is_empty() || points == 0 {
+                continue;
+            }
+            // Skip army total lines (no single unit costs 800+ pts)
+            if points >= 800 {
+                continue;
+            }
+            if name.starts_with("ENHANCEMENT") {
+                continue;
+            }
+
+            let (clean_name, count) = if let Some(nc) = re_name_count.captures(&name) {
+                let n = nc[1].trim().to_string();
+                let c: u32 = nc[2].parse().unwrap_or(count);
+                (n, c)
+            } else {
+                (
+                    name.replace(": Warlord", "")
+                        .replace(": ENHANCEMENT", "")
+                        .trim()
+                        .to_string(),
+                    count,
+                )
+            };
+
+            // Determine keywords from section header or line prefix
+            let keywords = if let Some(prefix) = prefix_hint {
+                keywords_for_section(prefix)
+            } else if !current_section.is_empty() {
+                keywords_for_section(&current_section)
+            } else {
+                Vec::new()
+            };
+
+            // Flush gear buffer for the previous unit before pushing new one
+            flush_gear_buffer(&mut gear_buffer, &mut units);
+
+            // Parse inline wargear from trailing text after points
+            let inline_wargear = parse_inline_wargear(&trailing);
+
+            let mut unit = Unit::new(clean_name, count)
+                .with_points(points)
+                .with_keywords(keywords);
+            if !inline_wargear.is_empty() {
+                unit = unit.with_wargear(inline_wargear);
+            }
+            units.push(unit);
+            unit_lines.push(line_index);
+        }
+    }
+
+    // Final flush for the last unit's gear lines
+    flush_gear_buffer(&mut gear_buffer, &mut units);
+
+    (units, unit_lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_standard_bcp_format() {
+        let raw = r#"GK Army (1975 points)
+
+Grey Knights
+Strike Force (2000 points)
+Teleport Strike Force
+
+
+CHARACTERS
+
+Brotherhood Librarian (150 points)
+  • 1x Combi-weapon
+    1x Nemesis force weapon
+  • Enhancement: Sigil of Exigence
+
+Castellan Crowe (90 points)
+  • 1x Black Blade of Antwyr
+    1x Storm bolter
+
+Grand Master Voldus (95 points)
+  • Warlord
+  • 1x Malleus Argyrum
+
+BATTLELINE
+
+Brotherhood Terminator Squad (200 points)
+  • 4x Brotherhood Terminator
+
+Brotherhood Terminator Squad (200 points)
+  • 4x Brotherhood Terminator
+
+OTHER DATASHEETS
+
+Nemesis Dreadknight (245 points)
+  • 1x Heavy psycannon
+
+Nemesis Dreadknight (245 points)
+  • 1x Heavy psycannon
+"#;
+        let units = parse_units_from_raw_text(raw);
+        // Should parse: Librarian(150), Crowe(90), Voldus(95), 2x Terminator(200),
+        //               2x Dreadknight(245) = 7 units minimum
+        // "Strike Force" and army name lines should be skipped
+        assert!(units.len() >= 7, "Expected >= 7 units, got {}", units.len());
+
+        // Check specific units
+        let librarian = units
+            .iter()
+            .find(|u| u.name.contains("Brotherhood Librarian"));
+        assert!(librarian.is_some(), "Should find Brotherhood Librarian");
+        assert_eq!(librarian.unwrap().points, Some(150));
+
+        let crowe = units.iter().find(|u| u.name.contains("Crowe"));
+        assert!(crowe.is_some(), "Should find Castellan Crowe");
+        assert_eq!(crowe.unwrap().points, Some(90));
+
+        // Strike Force should be skipped
+        let sf = units
+            .iter()
+            .find(|u| u.name.to_lowercase().contains("strike force"));
+        assert!(sf.is_none(), "Strike Force should be skipped");
+    }
+
+    #[test]
+    fn test_parse_char_prefix_pts_format() {
+        let raw = r#"+++++++++++++++++++++++++++++++++++++++++++++++
++ FACTION KEYWORD: Xenos - Tyranids
++ DETACHMENT: Crusher Stampede
++ TOTAL ARMY POINTS: 2000pts
++++++++++++++++++++++++++++++++++++++++++++++++
+
+CHARACTER
+
+Char1: 1x Old One Eye (150 pts)
+1 with Old One Eye's claws and talons
+
+Char2: 1x Hive Tyrant (235 pts)
+1 with Monstrous Bonesword and Lash Whip
+  • Warlord
+
+Char3: 1x Neurotyrant (105 pts)
+
+BATTLELINE
+
+10x Gargoyles (85 pts)
+
+OTHER DATASHEETS
+
+1x Biovores (50 pts)
+3x Screamer-Killers (375 pts)
+2x Carnifexes (180 pts)
+"#;
+        let units = parse_units_from_raw_text(raw);
+        assert!(units.len() >= 7, "Expected >= 7 units, got {}", units.len());
+
+        // Old One Eye with Char prefix
+        let ooe = units.iter().find(|u| u.name.contains("Old One Eye"));
+        assert!(ooe.is_some(), "Should find Old One Eye");
+        assert_eq!(ooe.unwrap().points, Some(150));
+        assert_eq!(ooe.unwrap().count, 1);
+
+        // 10x Gargoyles
+        let gargs = units.iter().find(|u| u.name.contains("Gargoyles"));
+        assert!(gargs.is_some(), "Should find Gargoyles");
+        assert_eq!(gargs.unwrap().count, 10);
+        assert_eq!(gargs.unwrap().points, Some(85));
+
+        // 3x Screamer-Killers
+        let sk = units.iter().find(|u| u.name.contains("Screamer-Killers"));
+        assert!(sk.is_some(), "Should find Screamer-Killers");
+        assert_eq!(sk.unwrap().count, 3);
+        assert_eq!(sk.unwrap().points, Some(375));
+    }
+
+    #[test]
+    fn test_parse_bracket_format() {
+        let raw = r#"+++++++++++++++++++++++++++++++++++++++++++++++
++FACTION KEYWORD: Imperium - Adeptus Custodes
++DETACHMENT: Lions of the Emperor
++TOTAL ARMY POINTS: 2000 pts
++++++++++++++++++++++++++++++++++++++++++++++++
+EH1: Trajann Valoris [140 pts]
+BL1: (4) Custodian Guard, Guardian Spear [150 pts]
+CH1: Blade Champion [120 pts]
+CH2: Shield-captain In Allarus Terminator Armour, Castellan axe, Admonimortis [140 pts]
+IN1: (2) Allarus Custodians, Guardian Spear [110 pts]
+IN2: (4) Custodian Wardens, Guardian Spear, Vexilla [210 pts]
+IN3: (5) Witchseekers, Witchseeker Flamer [55 pts]
+VE1: Venerable Land Raider, Hunter-killer missile [220 pts]
+"#;
+        let units = parse_units_from_raw_text(raw);
+        assert_eq!(units.len(), 8, "Expected 8 units, got {:?}", units);
+
+        // Trajann Valoris (EH prefix, no count)
+        let trajann = units.iter().find(|u| u.name.contains("Trajann Valoris"));
+        assert!(trajann.is_some(), "Should find Trajann Valoris");
+        assert_eq!(trajann.unwrap().points, Some(140));
+        assert_eq!(trajann.unwrap().count, 1);
+
+        // Custodian Guard with (4) count prefix
+        let guard = units.iter().find(|u| u.name.contains("Custodian Guard"));
+        assert!(guard.is_some(), "Should find Custodian Guard");
+        assert_eq!(guard.unwrap().count, 4);
+        assert_eq!(guard.unwrap().points, Some(150));
+
+        // Shield-captain — should strip wargear after comma
+        let sc = units.iter().find(|u| u.name.contains("Shield-captain"));
+        assert!(sc.is_some(), "Should find Shield-captain");
+        assert_eq!(sc.unwrap().points, Some(140));
+
+        // Allarus Custodians with (2) count
+        let allarus = units.iter().find(|u| u.name.contains("Allarus Custodians"));
+        assert!(allarus.is_some(), "Should find Allarus Custodians");
+        assert_eq!(allarus.unwrap().count, 2);
+    }
+
+    #[test]
+    fn test_parse_dash_format() {
+        let raw = r#"Sneaky Beaky Bois
+Detachment: Vanguard Spearhead
+
+1 Kayvaan Shrike - 100 pts
+- 1x Blackout
+- 1x The Raven's Talons
+
+1 Captain - 80 pts
+- 1x Master-crafted power sword
+
+1 Librarian in Phobos armour - 70 pts
+- 1x Smite
+- 1x Force weapon
+
+5 Vanguard Veterans - 110 pts
+- 5x Vanguard Veteran Weapon
+"#;
+        let units = parse_units_from_raw_text(raw);
+        assert_eq!(units.len(), 4, "Expected 4 units, got {:?}", units);
+
+        let shrike = units
+            .iter()
+            .find(|u| u.name.contains("Kayvaan Shrike"))
+            .unwrap();
+        assert_eq!(shrike.points, Some(100));
+        assert_eq!(shrike.count, 1);
+        assert_eq!(shrike.wargear, vec!["Blackout", "The Raven's Talons"]);
+
+        let captain = units.iter().find(|u| u.name == "Captain").unwrap();
+        assert_eq!(captain.wargear, vec!["Master-crafted power sword"]);
+
+        let librarian = units.iter().find(|u| u.name.contains("Librarian")).unwrap();
+        assert_eq!(librarian.wargear, vec!["Smite", "Force weapon"]);
+
+        let vets = units
+            .iter()
+            .find(|u| u.name.contains("Vanguard Veterans"))
+            .unwrap();
+        assert_eq!(vets.count, 5);
+        assert_eq!(vets.points, Some(110));
+        assert_eq!(vets.wargear, vec!["5x Vanguard Veteran Weapon"]);
+    }
+
+    #[test]
+    fn test_parse_bracket_format_with_warlord_suffix() {
+        // Some bracket-format lists have ": Warlord" after the unit name
+        let raw = r#"EH1: Mortarion: Warlord [380 pts]
+EH2: Typhus [100 pts]
+CH1: Daemon Prince of Nurgle [215 pts]
+IN1: Blightlord Terminators (10), 2x blight launcher [370 pts]
+IN2: Deathshroud Terminators (3), 1x Icon of Despair [160 pts]
+BL1: Poxwalkers (10) [65 pts]
+"#;
+        let units = parse_units_from_raw_text(raw);
+        assert_eq!(units.len(), 6, "Expected 6 units, got {:?}", units);
+
+        // Mortarion should have Warlord stripped from name
+        let mort = units.iter().find(|u| u.name.contains("Mortarion"));
+        assert!(mort.is_some(), "Should find Mortarion");
+        assert_eq!(mort.unwrap().points, Some(380));
+        assert!(
+            !mort.unwrap().name.contains("Warlord"),
+            "Warlord should be stripped from name"
+        );
+    }
+
+    #[test]
+    fn test_parse_inline_pts_on_same_line_as_wargear() {
+        // Russian format: unit name + model count in parens + points, wargear on same line
+        let raw = r#"Bjorn the Fell-Handed (160 points) 1x Multi-melta
+Iron Priest (80 points) Warlord Enhancement: Chariots of the Storm
+Logan Grimnar (110 points)
+Ragnar Blackmane (100 points)
+Blood Claws (20) (285 points) 1x Power weapon
+Intercessor Squad (5) (80 points) 1x Astartes grenade launcher
+Fenrisian Wolves (40 points)
+Gladiator Lancer (160 points) 2x Storm bolter
+"#;
+        let units = parse_units_from_raw_text(raw);
+        assert!(units.len() >= 6, "Expected >= 6 units, got {}", units.len());
+
+        let bjorn = units.iter().find(|u| u.name.contains("Bjorn"));
+        assert!(bjorn.is_some(), "Should find Bjorn");
+        assert_eq!(bjorn.unwrap().points, Some(160));
+
+        // Fenrisian Wolves (40 points) — number looks like count but it's points
+        let wolves = units.iter().find(|u| u.name.contains("Fenrisian Wolves"));
+        assert!(wolves.is_some(), "Should find Fenrisian Wolves");
+        assert_eq!(wolves.unwrap().points, Some(40));
+    }
+
+    #[test]
+    fn test_parse_skips_enhancement_lines() {
+        let raw = r#"CHARACTERS
+
+Brotherhood Librarian (150 points)
+  • Enhancement: Sigil of Exigence
+  • (+20 pts)
+
+OTHER DATASHEETS
+
+Nemesis Dreadknight (245 points)
+"#;
+        let units = parse_units_from_raw_text(raw);
+        // Should only find 2 units, not the enhancement line
+        assert_eq!(units.len(), 2, "Expected 2 units, got {:?}", units);
+        assert_eq!(units[0].keywords, vec!["Character"]);
+        assert_eq!(units[1].keywords, vec!["Other"]);
+    }
+
+    #[test]
+    fn test_parse_skips_wargear_bullet_lines() {
+        let raw = r#"Castellan Crowe (90 points)
+  • 1x Black Blade of Antwyr
+  - 1x Storm bolter
+Nemesis Dreadknight (245 points)
+  • 1x Heavy psycannon
+"#;
+        let units = parse_units_from_raw_text(raw);
+        // Should only find 2 units (Crowe, Dreadknight), not wargear lines
+        assert_eq!(units.len(), 2, "Expected 2 units, got {:?}", units);
+        // Wargear should be attached to the correct units
+        assert_eq!(
+            units[0].wargear,
+            vec!["Black Blade of Antwyr", "Storm bolter"]
+        );
+        assert_eq!(units[1].wargear, vec!["Heavy psycannon"]);
+    }
+
+    #[test]
+    fn test_parse_skips_header_lines() {
+        let raw = r#"+++++++++++++++++++++++++++++++++++++++++++++++
++ FACTION KEYWORD: Space Marines
++ DETACHMENT: Ironstorm
++ TOTAL ARMY POINTS: 2000pts
++++++++++++++++++++++++++++++++++++++++++++++++
+
+# Army Roster
+
+## Character [200 pts]
+
+== Ironstorm Army Roster ==
+
+Captain (80 points)
+"#;
+        let units = parse_units_from_raw_text(raw);
+        assert_eq!(units.len(), 1, "Expected 1 unit, got {:?}", units);
+        assert_eq!(units[0].name, "Captain");
+    }
+
+    #[test]
+    fn test_parse_returns_empty_for_no_points_format() {
+        // Lists without per-unit points should return empty (needs AI)
+        let raw = r#"Aeldari - Warhost
+2000 Points
+
+Maugan Ra
+- Maugetar
+
+5x Swooping Hawks
+- 4x Lasblasters
+
+10x Dark Reapers
+- 10x Reaper Launchers
+"#;
+        let units = parse_units_from_raw_text(raw);
+        assert!(
+            units.is_empty(),
+            "Should return empty for no-points format, got {:?}",
+            units
+        );
+    }
+
+    #[test]
+    fn test_parse_returns_empty_for_whitespace_tabulated() {
+        // Whitespace-aligned columns — impossible to parse with simple regex
+        let raw = r#"GREY KNIGHTS
+
+Brotherhood Strike
+
+GrandMaster                                                                95
+       Purity of purpose                                                   15
+
+Paladin Squad  (10)                                                       450
+
+Librarians  x2                                                 80        160
+"#;
+        let units = parse_units_from_raw_text(raw);
+        assert!(
+            units.is_empty(),
+            "Should return empty for whitespace-tabulated format, got {:?}",
+            units
+        );
+    }
+
+    #[test]
+    fn test_parse_total_points_accuracy() {
+        // Test that total points sum makes sense for a standard list
+        let raw = r#"CHARACTERS
+
+Captain (80 points)
+Librarian (70 points)
+
+BATTLELINE
+
+Intercessor Squad (75 points)
+Intercessor Squad (75 points)
+
+OTHER DATASHEETS
+
+Redemptor Dreadnought (210 points)
+Gladiator Lancer (160 points)
+"#;
+        let units = parse_units_from_raw_text(raw);
+        let total: u32 = units.iter().filter_map(|u| u.points).sum();
+        assert_eq!(total, 670, "Total should be 670pts, got {}", total);
+        assert_eq!(units.len(), 6);
+        // Verify section-based keyword assignment
+        assert_eq!(units[0].keywords, vec!["Character"]);
+        assert_eq!(units[1].keywords, vec!["Character"]);
+        assert_eq!(units[2].keywords, vec!["Battleline"]);
+        assert_eq!(units[3].keywords, vec!["Battleline"]);
+        assert_eq!(units[4].keywords, vec!["Other"]);
+        assert_eq!(units[5].keywords, vec!["Other"]);
+    }
+
+    #[test]
+    fn test_parse_mixed_count_formats() {
+        // Various ways counts appear
+        let raw = r#"1x Old One Eye (150 pts)
+3x Screamer-Killers (375 pts)
+10x Gargoyles (85 pts)
+Neurotyrant (105 pts)
+"#;
+        let units = parse_units_from_raw_text(raw);
+        assert_eq!(units.len(), 4);
+        assert_eq!(units[0].count, 1); // 1x
+        assert_eq!(units[1].count, 3); // 3x
+        assert_eq!(units[2].count, 10); // 10x
+        assert_eq!(units[3].count, 1); // no prefix = 1
+    }
+
+    #[test]
+    fn test_parse_bracket_count_in_parens() {
+        // Bracket format uses (N) before unit name for count
+        let raw = r#"EH1: Trajann Valoris [140 pts]
+BL1: (4) Custodian Guard, Guardian Spear [150 pts]
+IN1: (2) Allarus Custodians [110 pts]
+VE1: Venerable Land Raider [220 pts]
+"#;
+        let units = parse_units_from_raw_text(raw);
+        assert_eq!(units.len(), 4);
+
+        let trajann = units.iter().find(|u| u.name.contains("Trajann")).unwrap();
+        assert_eq!(trajann.count, 1);
+        assert_eq!(trajann.keywords, vec!["Character"]); // EH prefix
+
+        let guard = units
+            .iter()
+            .find(|u| u.name.contains("Custodian Guard"))
+            .unwrap();
+        assert_eq!(guard.count, 4);
+        assert_eq!(guard.keywords, vec!["Battleline"]); // BL prefix
+
+        let allarus = units.iter().find(|u| u.name.contains("Allarus")).unwrap();
+        assert_eq!(allarus.count, 2);
+        assert_eq!(allarus.keywords, vec!["Infantry"]); // IN prefix
+
+        let raider = units
+            .iter()
+            .find(|u| u.name.contains("Land Raider"))
+            .unwrap();
+        assert_eq!(raider.keywords, vec!["Vehicle"]); // VE prefix
+    }
+
+    #[test]
+    fn test_parse_does_not_include_game_size() {
+        let raw = r#"Strike Force (2000 points)
+Incursion (1000 points)
+Onslaught (3000 points)
+Captain (80 points)
+"#;
+        let units = parse_units_from_raw_text(raw);
+        assert_eq!(units.len(), 1);
+        assert_eq!(units[0].name, "Captain");
+    }
+
+    #[test]
+    fn test_strip_line_prefix() {
+        assert_eq!(
+            strip_line_prefix("Char1: 1x Old One Eye (150 pts)"),
+            ("1x Old One Eye (150 pts)", Some("Char"))
+        );
+        assert_eq!(
+            strip_line_prefix("EH1: Trajann Valoris [140 pts]"),
+            ("Trajann Valoris [140 pts]", Some("EH"))
+        );
+        assert_eq!(
+            strip_line_prefix("CH2: Shield-captain [140 pts]"),
+            ("Shield-captain [140 pts]", Some("CH"))
+        );
+        assert_eq!(
+            strip_line_prefix("BL1: (4) Custodian Guard [150 pts]"),
+            ("(4) Custodian Guard [150 pts]", Some("BL"))
+        );
+        assert_eq!(
+            strip_line_prefix("IN3: Poxwalkers (10) [65 pts]"),
+            ("Poxwalkers (10) [65 pts]", Some("IN"))
+        );
+        assert_eq!(
+            strip_line_prefix("VE1: Venerable Land Raider [220 pts]"),
+            ("Venerable Land Raider [220 pts]", Some("VE"))
+        );
+        assert_eq!(
+            strip_line_prefix("MO1: Carnifex [90 pts]"),
+            ("Carnifex [90 pts]", Some("MO"))
+        );
+        assert_eq!(
+            strip_line_prefix("Captain (80 points)"),
+            ("Captain (80 points)", None)
+        );
+    }
+
+    #[test]
+    fn test_detect_chapter_line_after_sm() {
+        let raw = "Army Name (2000 Points)\n\nSpace Marines\nUltramarines\nStrike Force (2000 Points)\nBlade of Ultramar\n";
+        assert_eq!(detect_chapter_from_raw_text(raw), Some("Ultramarines"));
+    }
+
+    #[test]
+    fn test_detect_chapter_astartes_dash() {
+        let raw = "+ FACTION KEYWORD: Imperium - Adeptus Astartes - Iron Hands\n+ DETACHMENT: Anvil Siege Force\n";
+        assert_eq!(detect_chapter_from_raw_text(raw), Some("Iron Hands"));
+    }
+
+    #[test]
+    fn test_detect_chapter_parens() {
+        let raw =
+            "Army Faction Used: Space Marines (Ultramarines)\nDetachment: Blade of Ultramar\n";
+        assert_eq!(detect_chapter_from_raw_text(raw), Some("Ultramarines"));
+    }
+
+    #[test]
+    fn test_detect_chapter_from_detachment() {
+        let raw = "FACTION KEYWORD: Adeptus Astartes\nDETACHMENT: Firestorm Assault Force\nCaptain (80 points)\n";
+        assert_eq!(detect_chapter_from_raw_text(raw), Some("Salamanders"));
+    }
+
+    #[test]
+    fn test_detect_chapter_from_character() {
+        let raw = "1 Kayvaan Shrike - 100 pts\n1 Captain - 80 pts\n";
+        assert_eq!(detect_chapter_from_raw_text(raw), Some("Raven Guard"));
+    }
+
+    #[test]
+    fn test_detect_chapter_generic_sm() {
+        // Truly generic SM list with no chapter markers
+        let raw = "FACTION KEYWORD: Space Marines\nDETACHMENT: Ironstorm Spearhead\nTechmarine (65 points)\n";
+        assert_eq!(detect_chapter_from_raw_text(raw), None);
+    }
+
+    #[test]
+    fn test_parse_multi_level_wargear_retributors() {
+        // BCP app format with model-type lines and sub-weapon lines
+        let raw = "OTHER DATASHEETS\n\nRetributor Squad (120 points)\n  \u{2022} 1x Retributor Superior\n    \u{2022} 1x Bolt pistol\n      1x Close combat weapon\n      1x Condemnor boltgun\n      1x Power weapon\n  \u{2022} 4x Retributor\n    \u{2022} 4x Bolt pistol\n      4x Close combat weapon\n      4x Multi-melta\n";
+        let units = parse_units_from_raw_text(raw);
+        assert_eq!(units.len(), 1, "Expected 1 unit, got {:?}", units);
+        let ret = &units[0];
+        assert_eq!(ret.name, "Retributor Squad");
+        assert_eq!(ret.count, 5, "Squad should be 5 models (1 superior + 4)");
+        assert!(
+            ret.wargear.iter().any(|w| w.contains("Multi-melta")),
+            "Should have Multi-melta in wargear: {:?}",
+            ret.wargear
+        );
+        assert!(
+            ret.wargear.iter().any(|w| w == "4x Multi-melta"),
+            "Should show 4x Multi-melta: {:?}",
+            ret.wargear
+        );
+        assert!(
+            ret.wargear.iter().any(|w| w.contains("Power weapon")),
+            "Should have Power weapon: {:?}",
+            ret.wargear
+        );
+    }
+
+    #[test]
+    fn test_parse_multi_level_wargear_paragons() {
+        let raw = "Paragon Warsuits (210 points)\n  \u{2022} 1x Paragon Superior\n    \u{2022} 1x Bolt pistol\n      1x Multi-melta\n      1x Paragon grenade launchers\n      1x Paragon war mace\n  \u{2022} 2x Paragon\n    \u{2022} 2x Bolt pistol\n      2x Multi-melta\n      2x Paragon grenade launchers\n      2x Paragon war mace\n";
+        let units = parse_units_from_raw_text(raw);
+        assert_eq!(units.len(), 1);
+        let para = &units[0];
+        assert_eq!(para.name, "Paragon Warsuits");
+        assert_eq!(para.count, 3, "Should be 3 models (1 superior + 2)");
+        assert!(
+            para.wargear.iter().any(|w| w.contains("war mace")),
+            "Should show Paragon war mace: {:?}",
+            para.wargear
+        );
+        assert!(
+            para.wargear.iter().any(|w| w.contains("Multi-melta")),
+            "Should show Multi-melta: {:?}",
+            para.wargear
+        );
+    }
+
+    #[test]
+    fn test_parse_inline_wargear_after_points() {
+        // Russian format with wargear on same line after points
+        let raw = "Retributor Squad (5) (120 pts) 4x Multi-melta, 1x Inferno pistol, 1x Power weapon\nParagon Warsuits (3) (210 pts) 3x Multi-melta, 3x Paragon war mace\n";
+        let units = parse_units_from_raw_text(raw);
+        assert_eq!(units.len(), 2, "Expected 2 units, got {:?}", units);
+
+        let ret = &units[0];
+        assert_eq!(ret.name, "Retributor Squad");
+        assert_eq!(ret.count, 5, "Count from (5) in name");
+        assert!(
+            ret.wargear.iter().any(|w| w == "4x Multi-melta"),
+            "Should have 4x Multi-melta: {:?}",
+            ret.wargear
+        );
+        assert!(
+            ret.wargear.iter().any(|w| w == "Inferno pistol"),
+            "Should have Inferno pistol: {:?}",
+            ret.wargear
+        );
+
+        let para = &units[1];
+        assert_eq!(para.name, "Paragon Warsuits");
+        assert_eq!(para.count, 3, "Count from (3) in name");
+        assert!(
+            para.wargear.iter().any(|w| w == "3x Multi-melta"),
+            "Should have 3x Multi-melta: {:?}",
+            para.wargear
+        );
+        assert!(
+            para.wargear.iter().any(|w| w.contains("war mace")),
+            "Should have war mace: {:?}",
+            para.wargear
+        );
+    }
+
+    #[test]
+    fn test_parse_flat_wargear_single_model() {
+        // Flat format: single-model unit, all gear at same indent
+        let raw = "Castellan Crowe (90 points)\n  \u{2022} 1x Black Blade of Antwyr\n  \u{2022} 1x Storm bolter\n";
+        let units = parse_units_from_raw_text(raw);
+        assert_eq!(units.len(), 1);
+        assert_eq!(units[0].count, 1, "Single model, count should stay 1");
+        assert_eq!(
+            units[0].wargear,
+            vec!["Black Blade of Antwyr", "Storm bolter"]
+        );
+    }
+
+    // ── parse_list ─────────────────────────────────────────────────────────
+
+    const GW_APP: &str = include_str!("../../tests/fixtures/armylists/gw_app.txt");
+    const NEW_RECRUIT: &str = include_str!("../../tests/fixtures/armylists/new_recruit.txt");
+    const BCP_BRACKET: &str = include_str!("../../tests/fixtures/armylists/bcp_bracket.txt");
+
+    fn enhancement(name: &str, unit: &str, points: Option<u32>) -> ParsedEnhancement {
+        ParsedEnhancement {
+            name: name.to_string(),
+            unit: Some(unit.to_string()),
+            points,
+        }
+    }
+
+    #[test]
+    fn test_parse_list_fixtures() {
+        let gw = parse_list(GW_APP);
+        assert_eq!(gw.format, ListFormat::GwApp);
+        assert_eq!(gw.detachment.as_deref(), Some("Gladius Task Force"));
+        assert_eq!(gw.total_points, Some(1990));
+        assert_eq!(gw.units.len(), 11);
+        assert_eq!(gw.units[3].count, 5, "Sergeant + 4 Intercessors");
+        assert_eq!(
+            gw.enhancements,
+            vec![
+                enhancement("Artificer Armour", "Captain in Terminator Armour", None),
+                enhancement(
+                    "The Honour Vehement",
+                    "Librarian in Terminator Armour",
+                    None
+                ),
+            ]
+        );
+
+        let nr = parse_list(NEW_RECRUIT);
+        assert_eq!(nr.format, ListFormat::NewRecruit);
+        assert_eq!(nr.detachment.as_deref(), Some("Battle Host"));
+        assert_eq!(nr.total_points, Some(1985));
+        assert_eq!(nr.units.len(), 12, "{:?}", nr.units);
+        let autarch = &nr.units[1];
+        assert_eq!(
+            (autarch.name.as_str(), autarch.points),
+            ("Autarch", Some(105))
+        );
+        assert_eq!(autarch.wargear, vec!["Dragon fusion pistol", "Star glaive"]);
+        assert_eq!(nr.units[0].wargear, vec!["Singing spear"]);
+        assert_eq!(
+            nr.units[3].wargear,
+            vec!["10x Shuriken catapult", "Heavy Weapon Platform"]
+        );
+        assert_eq!(
+            nr.enhancements,
+            vec![enhancement("Psychic Destroyer", "Autarch", Some(15))]
+        );
+        assert_eq!(autarch.enhancement.as_deref(), Some("Psychic Destroyer"));
+        let list = crate::models::ArmyList::new(
+            "Aeldari".to_string(),
+            1985,
+            parse_units_from_raw_text(NEW_RECRUIT),
+            NEW_RECRUIT.to_string(),
+        );
+        assert_eq!(list.enhancements, vec!["Psychic Destroyer"]);
+        assert_eq!(
+            nr.attachments,
+            vec![Attachment {
+                leader: "Autarch".to_string(),
+                unit: "Fire Dragons".to_string(),
+            }]
+        );
+
+        let bcp = parse_list(BCP_BRACKET);
+        assert_eq!(bcp.format, ListFormat::Bcp);
+        assert_eq!(bcp.detachment.as_deref(), Some("Awakened Dynasty"));
+        assert_eq!(bcp.total_points, Some(1740));
+        assert_eq!(bcp.units.len(), 11);
+        assert_eq!(bcp.units[0].wargear, vec!["Voidreaper", "Tachyon arrow"]);
+        assert_eq!(
+            bcp.enhancements,
+            vec![enhancement("Enaegic Dermal Bond", "Overlord", Some(20))]
+        );
+        assert_eq!(bcp.attachments[0].unit, "Lychguard");
+
+        for parsed in [&gw, &nr, &bcp] {
+            let sum: u32 = parsed.units.iter().filter_map(|u| u.points).sum();
+            assert_eq!(Some(sum), parsed.total_points);
+        }
+        assert_eq!(parse_list("no list here").format, ListFormat::Unknown);
+    }
+
+    /// Every truncation, dropped line and doubled line of the fixtures
+    /// parses without panicking into units that make sense.
+    #[test]
+    fn test_parse_list_mangled_fixtures() {
+        for fixture in [GW_APP, NEW_RECRUIT, BCP_BRACKET] {
+            let lines: Vec<&str> = fixture.lines().collect();
+            let mut inputs: Vec<String> = fixture
+                .char_indices()
+                .step_by(5)
+                .map(|(i, _)| fixture[..i].to_string())
+                .collect();
+            for i in 0..lines.len() {
+                let mut dropped = lines.clone();
+                dropped.remove(i);
+                inputs.push(dropped.join("\n"));
+                let mut doubled = lines.clone();
+                doubled.insert(i, lines[i]);
+                inputs.push(doubled.join("\n"));
+            }
+
+            for input in &inputs {
+                let parsed = parse_list(input);
+                for unit in &parsed.units {
+                    assert!(!unit.name.is_empty() && unit.count > 0, "{:?}", unit);
+                    assert!(matches!(unit.points, Some(p) if p > 0 && p < 800));
+                }
+                for e in &parsed.enhancements {
+                    assert!(!e.name.is_empty());
+                }
+                assert_eq!(
+                    parsed.units.is_empty(),
+                    parsed.format == ListFormat::Unknown
+                );
+            }
+        }
+    }
+}
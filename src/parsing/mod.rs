@@ -0,0 +1,6 @@
+//! Parsing of free-text formats shared by several sources.
+//!
+//! [`armylist`] reads army list text as exported by the GW app, BCP and
+//! New Recruit.
+
+pub mod armylist;
@@ -0,0 +1,218 @@
+//! Terminal progress reporting for long-running CLI commands.
+//!
+//! Commands such as `normalize-lists` and `fetch-pairings` can run for
+//! tens of minutes. [`Progress`] renders an indicatif bar with ETA and
+//! throughput when stderr is a terminal, falls back to the plain
+//! line-by-line log otherwise, and stays silent when the command's
+//! output is machine-readable JSON.
+
+use std::fmt;
+use std::io::IsTerminal;
+use std::time::{Duration, Instant};
+
+use indicatif::{ProgressBar, ProgressState, ProgressStyle};
+use serde::Serialize;
+
+/// How a [`Progress`] reports per-item updates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressMode {
+    /// Animated bar on stderr with ETA and rates.
+    Bar,
+    /// One line per item on stdout (non-TTY, e.g. cron or piped output).
+    Lines,
+    /// No per-item output (`--output json`).
+    Quiet,
+}
+
+impl ProgressMode {
+    /// Pick a mode: JSON output is always quiet, a terminal gets a bar,
+    /// anything else keeps the line-by-line log.
+    pub fn detect(json_output: bool) -> Self {
+        if json_output {
+            Self::Quiet
+        } else if std::io::stderr().is_terminal() {
+            Self::Bar
+        } else {
+            Self::Lines
+        }
+    }
+}
+
+/// Progress tracker for a fixed number of items.
+pub struct Progress {
+    mode: ProgressMode,
+    bar: Option<ProgressBar>,
+    unit: &'static str,
+    started: Instant,
+    items: u64,
+    ai_calls: u64,
+}
+
+impl Progress {
+    /// Create a tracker for `len` items, labelled with `unit` (e.g. "lists").
+    pub fn new(len: u64, unit: &'static str, mode: ProgressMode) -> Self {
+        let bar = (mode == ProgressMode::Bar).then(|| {
+            let bar = ProgressBar::new(len);
+            bar.set_style(bar_style(unit));
+            bar.enable_steady_tick(Duration::from_millis(250));
+            bar
+        });
+        Self {
+            mode,
+            bar,
+            unit,
+            started: Instant::now(),
+            items: 0,
+            ai_calls: 0,
+        }
+    }
+
+    /// Print a log line without tearing the bar. Dropped in quiet mode.
+    pub fn println(&self, msg: impl AsRef<str>) {
+        match (&self.bar, self.mode) {
+            (Some(bar), _) => bar.println(msg.as_ref()),
+            (None, ProgressMode::Quiet) => {}
+            (None, _) => println!("{}", msg.as_ref()),
+        }
+    }
+
+    /// Show what is currently being worked on next to the bar.
+    pub fn set_message(&self, msg: impl Into<String>) {
+        if let Some(bar) = &self.bar {
+            bar.set_message(msg.into());
+        }
+    }
+
+    /// Mark one item as done.
+    pub fn inc(&mut self) {
+        self.items += 1;
+        if let Some(bar) = &self.bar {
+            bar.inc(1);
+        }
+    }
+
+    /// Record a call to the AI backend, for the AI calls/min rate.
+    pub fn record_ai_call(&mut self) {
+        self.ai_calls += 1;
+    }
+
+    /// Clear the bar and return the timing summary.
+    pub fn finish(self) -> ProgressSummary {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+        ProgressSummary {
+            unit: self.unit,
+            items: self.items,
+            ai_calls: self.ai_calls,
+            elapsed: self.started.elapsed(),
+        }
+    }
+}
+
+fn bar_style(unit: &'static str) -> ProgressStyle {
+    ProgressStyle::with_template(
+        "{spinner} [{elapsed_precise}] {wide_bar} {pos}/{len} {unit} ({rate}, ETA {eta}) {msg}",
+    )
+    .unwrap_or_else(|_| ProgressStyle::default_bar())
+    .with_key("unit", move |_: &ProgressState, w: &mut dyn fmt::Write| {
+        let _ = w.write_str(unit);
+    })
+    .with_key("rate", |state: &ProgressState, w: &mut dyn fmt::Write| {
+        let _ = write!(w, "{:.1}/min", state.per_sec() * 60.0);
+    })
+}
+
+/// Final timing summary for a finished [`Progress`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ProgressSummary {
+    pub unit: &'static str,
+    pub items: u64,
+    pub ai_calls: u64,
+    #[serde(rename = "elapsed_secs", serialize_with = "serialize_secs")]
+    pub elapsed: Duration,
+}
+
+impl ProgressSummary {
+    /// Completed items per minute.
+    pub fn items_per_minute(&self) -> f64 {
+        per_minute(self.items, self.elapsed)
+    }
+
+    /// AI backend calls per minute.
+    pub fn ai_calls_per_minute(&self) -> f64 {
+        per_minute(self.ai_calls, self.elapsed)
+    }
+}
+
+impl fmt::Display for ProgressSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.elapsed.as_secs();
+        write!(
+            f,
+            "{}m {:02}s ({:.1} {}/min",
+            secs / 60,
+            secs % 60,
+            self.items_per_minute(),
+            self.unit
+        )?;
+        if self.ai_calls > 0 {
+            write!(f, ", {:.1} AI calls/min", self.ai_calls_per_minute())?;
+        }
+        write!(f, ")")
+    }
+}
+
+fn per_minute(count: u64, elapsed: Duration) -> f64 {
+    let minutes = elapsed.as_secs_f64() / 60.0;
+    if minutes <= 0.0 {
+        return 0.0;
+    }
+    count as f64 / minutes
+}
+
+fn serialize_secs<S: serde::Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_f64((d.as_secs_f64() * 10.0).round() / 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_output_is_quiet() {
+        assert_eq!(ProgressMode::detect(true), ProgressMode::Quiet);
+    }
+
+    #[test]
+    fn test_summary_rates_and_display() {
+        let summary = ProgressSummary {
+            unit: "lists",
+            items: 30,
+            ai_calls: 45,
+            elapsed: Duration::from_secs(90),
+        };
+        assert!((summary.items_per_minute() - 20.0).abs() < 1e-9);
+        assert!((summary.ai_calls_per_minute() - 30.0).abs() < 1e-9);
+        assert_eq!(
+            summary.to_string(),
+            "1m 30s (20.0 lists/min, 30.0 AI calls/min)"
+        );
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["elapsed_secs"], 90.0);
+        assert_eq!(json["items"], 30);
+    }
+
+    #[test]
+    fn test_quiet_progress_counts_items() {
+        let mut progress = Progress::new(2, "events", ProgressMode::Quiet);
+        progress.println("not shown");
+        progress.inc();
+        progress.inc();
+        let summary = progress.finish();
+        assert_eq!(summary.items, 2);
+        assert_eq!(summary.ai_calls, 0);
+        assert!(!summary.to_string().contains("AI calls"));
+    }
+}
@@ -0,0 +1,477 @@
+//! Static site export of an epoch's analytics.
+//!
+//! [`publish`] writes a directory of HTML pages, each with its data beside
+//! it as JSON, that any static host (GitHub Pages, S3) can serve without
+//! the API:
+//!
+//! - `index.html`: the meta snapshot, with links to every faction
+//! - `tiers.html`: the full tier list
+//! - `trends.html`: win rate and meta share per epoch, one column per
+//!   balance pass
+//! - `factions/<slug>.html`: a faction's stats, detachments, most taken
+//!   units and trend
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::api::routes::analytics::group_tiers;
+use crate::api::routes::meta::{meta_snapshot, previous_epoch};
+use crate::calculate::derive::{
+    load_epoch_derived, load_faction_stats, UnitFrequency, DERIVED_GAME_SIZE,
+};
+use crate::calculate::tiers::FactionTier;
+use crate::dashboard::{
+    escape, page, percent, resolve_epoch, snapshot_body, table, DashboardError,
+};
+use crate::models::FactionStat;
+use crate::storage::{cached_epoch_mapper, StorageConfig};
+
+/// Entries in each top list of the index page.
+const TOP_LIMIT: usize = 10;
+
+/// Units listed on a faction page.
+const FACTION_UNITS: usize = 15;
+
+/// What a publish wrote.
+#[derive(Debug)]
+pub struct PublishReport {
+    pub epoch_id: String,
+    /// HTML pages written (each has a JSON twin)
+    pub pages: usize,
+    pub index: PathBuf,
+}
+
+/// An epoch on the trend axis; each starts with a balance pass.
+#[derive(Debug, Clone, Serialize)]
+pub struct TrendEpoch {
+    pub epoch_id: String,
+    pub label: String,
+    pub start_date: String,
+}
+
+/// A faction's numbers in one epoch.
+#[derive(Debug, Clone, Serialize)]
+pub struct TrendPoint {
+    pub epoch_id: String,
+    pub win_rate: f64,
+    pub meta_share: f64,
+    pub games: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FactionTrend {
+    pub faction: String,
+    /// One entry per epoch, `None` where the faction wasn't played
+    pub points: Vec<Option<TrendPoint>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Trends {
+    pub epochs: Vec<TrendEpoch>,
+    pub factions: Vec<FactionTrend>,
+}
+
+#[derive(Debug, Serialize)]
+struct FactionPage<'a> {
+    epoch_id: &'a str,
+    stats: &'a FactionStat,
+    tier: Option<&'a FactionTier>,
+    units: Vec<&'a UnitFrequency>,
+    trend: Vec<Option<TrendPoint>>,
+}
+
+/// File-name form of a faction name: "Adeptus Custodes" -> "adeptus-custodes".
+fn slug(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Links to the top-level pages; `root` is the path back to the site root.
+fn nav(root: &str) -> String {
+    format!(
+        "<nav><a href=\"{root}index.html\">Snapshot</a><a href=\"{root}tiers.html\">Tiers</a><a href=\"{root}trends.html\">Trends</a></nav>\n"
+    )
+}
+
+/// Write `name.html` and `name.json` into `dir`.
+fn write_page(
+    dir: &Path,
+    name: &str,
+    html: String,
+    data: &impl Serialize,
+) -> Result<PathBuf, DashboardError> {
+    fs::create_dir_all(dir)?;
+    fs::write(
+        dir.join(format!("{}.json", name)),
+        serde_json::to_string_pretty(data)?,
+    )?;
+    let path = dir.join(format!("{}.html", name));
+    fs::write(&path, html)?;
+    Ok(path)
+}
+
+/// Win rate and meta share per faction for every epoch up to and
+/// including `epoch_id`.
+fn trends(storage: &StorageConfig, epoch_id: &str) -> Result<Trends, DashboardError> {
+    let mapper = cached_epoch_mapper(storage);
+    let mut epochs: Vec<TrendEpoch> = Vec::new();
+    for epoch in mapper.all_epochs() {
+        epochs.push(TrendEpoch {
+            epoch_id: epoch.id.as_str().to_string(),
+            label: epoch.name.clone(),
+            start_date: epoch.start_date.to_string(),
+        });
+        if epoch.id.as_str() == epoch_id {
+            break;
+        }
+    }
+    if epochs.is_empty() {
+        epochs.push(TrendEpoch {
+            epoch_id: epoch_id.to_string(),
+            label: epoch_id.to_string(),
+            start_date: String::new(),
+        });
+    }
+
+    let mut by_faction: HashMap<String, Vec<Option<TrendPoint>>> = HashMap::new();
+    for (i, epoch) in epochs.iter().enumerate() {
+        let stats = load_faction_stats(storage, &epoch.epoch_id, DERIVED_GAME_SIZE)?;
+        for faction in stats.factions {
+            let points = by_faction
+                .entry(faction.name)
+                .or_insert_with(|| vec![None; epochs.len()]);
+            points[i] = Some(TrendPoint {
+                epoch_id: epoch.epoch_id.clone(),
+                win_rate: faction.win_rate,
+                meta_share: faction.meta_share,
+                games: faction.games_played,
+            });
+        }
+    }
+
+    // Most played in the published epoch first
+    let share = |points: &[Option<TrendPoint>]| {
+        points
+            .last()
+            .and_then(|p| p.as_ref())
+            .map_or(0.0, |p| p.meta_share)
+    };
+    let mut factions: Vec<FactionTrend> = by_faction
+        .into_iter()
+        .map(|(faction, points)| FactionTrend { faction, points })
+        .collect();
+    factions.sort_by(|a, b| {
+        share(&b.points)
+            .partial_cmp(&share(&a.points))
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.faction.cmp(&b.faction))
+    });
+    Ok(Trends { epochs, factions })
+}
+
+fn trend_cell(point: &Option<TrendPoint>) -> String {
+    point
+        .as_ref()
+        .map(|p| format!("{} ({})", percent(p.win_rate), percent(p.meta_share)))
+        .unwrap_or_else(|| "–".to_string())
+}
+
+fn render_trends(trends: &Trends) -> String {
+    let mut headers = vec!["Faction".to_string()];
+    headers.extend(trends.epochs.iter().map(|e| {
+        if e.start_date.is_empty() {
+            escape(&e.label)
+        } else {
+            format!("{}<br><small>{}</small>", escape(&e.label), e.start_date)
+        }
+    }));
+    let headers: Vec<&str> = headers.iter().map(String::as_str).collect();
+    let rows = trends
+        .factions
+        .iter()
+        .map(|f| {
+            let mut cells = vec![format!(
+                "<a href=\"factions/{}.html\">{}</a>",
+                slug(&f.faction),
+                escape(&f.faction)
+            )];
+            cells.extend(f.points.iter().map(trend_cell));
+            cells
+        })
+        .collect();
+    let body = format!(
+        "{}<h1>Trends</h1>\n<p class=\"muted\">Win rate (meta share) per epoch. Each column starts at a balance pass.</p>\n{}",
+        nav(""),
+        table("Factions by epoch", &headers, rows)
+    );
+    page("40k Meta Trends", &body)
+}
+
+fn render_tiers(epoch_id: &str, tiers: &[FactionTier]) -> String {
+    let rows = tiers
+        .iter()
+        .map(|t| {
+            vec![
+                t.tier.to_string(),
+                format!(
+                    "<a href=\"factions/{}.html\">{}</a>",
+                    slug(&t.faction),
+                    escape(&t.faction)
+                ),
+                format!("{:.2}", t.score),
+                percent(t.win_rate),
+                t.games.to_string(),
+                t.event_wins.to_string(),
+            ]
+        })
+        .collect();
+    let body = format!(
+        "{}<h1>Tier List</h1>\n<p class=\"muted\">Epoch {}</p>\n{}",
+        nav(""),
+        escape(epoch_id),
+        table(
+            "Factions",
+            &[
+                "Tier",
+                "Faction",
+                "Score",
+                "Win rate",
+                "Games",
+                "Event wins"
+            ],
+            rows
+        )
+    );
+    page(&format!("40k Tier List — {}", epoch_id), &body)
+}
+
+fn render_faction(data: &FactionPage<'_>, epochs: &[TrendEpoch]) -> String {
+    let stats = data.stats;
+    let tier = data
+        .tier
+        .map(|t| t.tier.to_string())
+        .unwrap_or_else(|| stats.tier.to_string());
+    let mut body = format!(
+        "{}<h1>{}</h1>\n<p class=\"muted\">Epoch {}: tier {}, {} win rate over {} games ({}-{}-{}), {} meta share, {} players</p>\n",
+        nav("../"),
+        escape(&stats.name),
+        escape(data.epoch_id),
+        tier,
+        percent(stats.win_rate),
+        stats.games_played,
+        stats.wins,
+        stats.losses,
+        stats.draws,
+        percent(stats.meta_share),
+        stats.player_count,
+    );
+    body.push_str(&table(
+        "Detachments",
+        &["Detachment", "Players", "Win rate"],
+        stats
+            .top_detachments
+            .iter()
+            .map(|d| vec![escape(&d.name), d.count.to_string(), percent(d.win_rate)])
+            .collect(),
+    ));
+    body.push_str(&table(
+        "Most taken units",
+        &["Unit", "Lists", "Share of lists"],
+        data.units
+            .iter()
+            .map(|u| {
+                vec![
+                    escape(&u.unit),
+                    u.lists_containing.to_string(),
+                    percent(u.list_share),
+                ]
+            })
+            .collect(),
+    ));
+    body.push_str(&table(
+        "Trend",
+        &["Epoch", "Starts", "Win rate (meta share)"],
+        epochs
+            .iter()
+            .zip(&data.trend)
+            .map(|(e, p)| vec![escape(&e.label), e.start_date.clone(), trend_cell(p)])
+            .collect(),
+    ));
+    page(&format!("{} — 40k Meta", stats.name), &body)
+}
+
+/// Render the analytics of `epoch` (the current epoch if `None` or
+/// "current") into a static site under `out`.
+pub fn publish(
+    storage: &StorageConfig,
+    epoch: Option<&str>,
+    out: &Path,
+) -> Result<PublishReport, DashboardError> {
+    let epoch_id = resolve_epoch(storage, epoch)?;
+    let previous = previous_epoch(&cached_epoch_mapper(storage), &epoch_id);
+    let snapshot = meta_snapshot(
+        storage,
+        epoch_id.clone(),
+        previous,
+        DERIVED_GAME_SIZE,
+        TOP_LIMIT,
+    )?;
+    let derived = load_epoch_derived(storage, &epoch_id, DERIVED_GAME_SIZE)?;
+    let trends = trends(storage, &epoch_id)?;
+    let mut pages = 0;
+
+    let mut factions: Vec<&FactionStat> = derived.faction_stats.factions.iter().collect();
+    factions.sort_by(|a, b| a.name.cmp(&b.name));
+    let links: Vec<String> = factions
+        .iter()
+        .map(|f| {
+            format!(
+                "<a href=\"factions/{}.html\">{}</a>",
+                slug(&f.name),
+                escape(&f.name)
+            )
+        })
+        .collect();
+    let index_body = format!(
+        "{}{}<section><h2>Factions</h2><p>{}</p></section>\n",
+        nav(""),
+        snapshot_body(&snapshot),
+        links.join(" · ")
+    );
+    let index = write_page(
+        out,
+        "index",
+        page(&format!("40k Meta — {}", epoch_id), &index_body),
+        &snapshot,
+    )?;
+    // The snapshot page links its data as snapshot.json
+    fs::copy(out.join("index.json"), out.join("snapshot.json"))?;
+    pages += 1;
+
+    write_page(
+        out,
+        "tiers",
+        render_tiers(&epoch_id, &derived.tiers),
+        &group_tiers(derived.tiers.clone()),
+    )?;
+    write_page(out, "trends", render_trends(&trends), &trends)?;
+    pages += 2;
+
+    let faction_dir = out.join("factions");
+    for stats in factions {
+        let mut units: Vec<&UnitFrequency> = derived
+            .unit_frequency
+            .iter()
+            .filter(|u| u.faction == stats.name)
+            .collect();
+        units.sort_by(|a, b| {
+            b.list_share
+                .partial_cmp(&a.list_share)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.unit.cmp(&b.unit))
+        });
+        units.truncate(FACTION_UNITS);
+        let trend = trends
+            .factions
+            .iter()
+            .find(|f| f.faction == stats.name)
+            .map(|f| f.points.clone())
+            .unwrap_or_default();
+        let data = FactionPage {
+            epoch_id: &epoch_id,
+            stats,
+            tier: derived.tiers.iter().find(|t| t.faction == stats.name),
+            units,
+            trend,
+        };
+        write_page(
+            &faction_dir,
+            &slug(&stats.name),
+            render_faction(&data, &trends.epochs),
+            &data,
+        )?;
+        pages += 1;
+    }
+
+    Ok(PublishReport {
+        epoch_id,
+        pages,
+        index,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EntityId, Event, Placement};
+    use crate::storage::{EntityType, JsonlWriter};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_publish_site() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().join("data"));
+        let epoch = EntityId::from("current");
+        let event = Event::new(
+            "Spring GT".to_string(),
+            NaiveDate::from_ymd_opt(2026, 5, 2).unwrap(),
+            "https://example.com/spring".to_string(),
+            "bcp".to_string(),
+            epoch.clone(),
+        );
+        let placements = [
+            Placement::new(
+                event.id.clone(),
+                epoch.clone(),
+                1,
+                "Alice".to_string(),
+                "Adeptus Custodes".to_string(),
+            )
+            .with_record(5, 0, 0),
+            Placement::new(
+                event.id.clone(),
+                epoch.clone(),
+                2,
+                "Bob".to_string(),
+                "Orks".to_string(),
+            )
+            .with_record(4, 1, 0),
+        ];
+        JsonlWriter::<Event>::for_entity(&storage, EntityType::Event, "current")
+            .write_all(std::slice::from_ref(&event))
+            .unwrap();
+        JsonlWriter::<Placement>::for_entity(&storage, EntityType::Placement, "current")
+            .write_all(&placements)
+            .unwrap();
+
+        let out = tmp.path().join("site");
+        let report = publish(&storage, None, &out).unwrap();
+        assert_eq!(report.epoch_id, "current");
+        assert_eq!(report.pages, 5);
+        for file in [
+            "index.html",
+            "snapshot.json",
+            "tiers.json",
+            "trends.html",
+            "factions/orks.json",
+        ] {
+            assert!(out.join(file).exists(), "missing {}", file);
+        }
+        let custodes = fs::read_to_string(out.join("factions/adeptus-custodes.html")).unwrap();
+        assert!(custodes.contains("<h1>Adeptus Custodes</h1>"));
+        assert!(custodes.contains("href=\"../tiers.html\""));
+        let index = fs::read_to_string(&report.index).unwrap();
+        assert!(index.contains("href=\"factions/orks.html\""));
+
+        let trends: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(out.join("trends.json")).unwrap()).unwrap();
+        assert_eq!(trends["epochs"].as_array().unwrap().len(), 1);
+        assert_eq!(trends["factions"].as_array().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,593 @@
+//! Job scheduler.
+//!
+//! Runs sync, balance-pass discovery, repartition, list normalization and
+//! derivations on independent schedules taken from `[schedule]` in
+//! config.toml. Used by `meta-agent schedule` and embedded in
+//! `serve --scheduler`.
+//!
+//! Jobs never overlap: each waits for its next due time, then takes a shared
+//! lock so one job's writes can't interleave with another's.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Days, NaiveTime, Utc, Weekday};
+use thiserror::Error;
+use tracing::{error, info, warn};
+
+use crate::agents::backend::AiBackend;
+use crate::agents::balance_watcher::{BalanceWatcherAgent, BalanceWatcherInput};
+use crate::agents::list_normalizer::{apply_normalized, ListNormalizerAgent, ListNormalizerInput};
+use crate::agents::{execute_with_retry, AgentError};
+use crate::api::merge_by_id;
+use crate::calculate::derive::{
+    run_derivations, Derivation, DeriveError, DeriveOutcome, DERIVED_GAME_SIZE,
+};
+use crate::calculate::ratings::{compute_ratings_from_storage, write_ratings, RatingConfig};
+use crate::calculate::validation::flag_lists;
+use crate::config::{AppConfig, ScheduleConfig};
+use crate::fetch::{FetchError, Fetcher};
+use crate::models::{ArmyList, Event, SignificantEvent};
+use crate::notify::Notifier;
+use crate::storage::jsonl::list_epochs;
+use crate::storage::{
+    cached_significant_events, current_epoch_id, read_significant_events, write_significant_events,
+    EntityType, JsonlReader, JsonlWriter, StorageConfig, StorageError,
+};
+use crate::sync::{ActiveSyncs, Source, SyncConfig, SyncError, SyncOrchestrator, SyncSource};
+
+/// Delay between AI calls when normalizing lists.
+const NORMALIZE_DELAY: Duration = Duration::from_millis(500);
+
+/// Scheduler errors.
+#[derive(Debug, Error)]
+pub enum SchedulerError {
+    #[error("Invalid schedule '{0}': {1}")]
+    InvalidSchedule(String, String),
+
+    #[error("Unknown job: {0}")]
+    UnknownJob(String),
+
+    #[error("Sync error: {0}")]
+    Sync(#[from] SyncError),
+
+    #[error("Fetch error: {0}")]
+    Fetch(#[from] FetchError),
+
+    #[error("Agent error: {0}")]
+    Agent(#[from] AgentError),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+
+    #[error("Derive error: {0}")]
+    Derive(#[from] DeriveError),
+
+    #[error("Repartition failed: {0}")]
+    Repartition(String),
+}
+
+/// When a job runs. Times are UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schedule {
+    /// Every interval, starting one interval after the scheduler starts
+    Every(Duration),
+    /// Once a day at the given time
+    Daily(NaiveTime),
+    /// Once a week on the given day and time
+    Weekly(Weekday, NaiveTime),
+}
+
+impl Schedule {
+    /// Parse a schedule spec. Returns None for "off" (or an empty spec).
+    ///
+    /// Accepted forms: an interval ("30m", "6h", "1d"), "daily HH:MM" and
+    /// "weekly <day> HH:MM".
+    pub fn parse(spec: &str) -> Result<Option<Self>, SchedulerError> {
+        let spec = spec.trim();
+        if spec.is_empty() || spec.eq_ignore_ascii_case("off") {
+            return Ok(None);
+        }
+        spec.parse().map(Some)
+    }
+
+    /// The first due time strictly after `now`.
+    pub fn next_after(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        match *self {
+            Schedule::Every(interval) => {
+                now + chrono::Duration::from_std(interval).unwrap_or(chrono::Duration::MAX)
+            }
+            Schedule::Daily(time) => {
+                let today = now.date_naive().and_time(time).and_utc();
+                if today > now {
+                    today
+                } else {
+                    today + Days::new(1)
+                }
+            }
+            Schedule::Weekly(weekday, time) => {
+                let ahead =
+                    (7 + weekday.num_days_from_monday() - now.weekday().num_days_from_monday()) % 7;
+                let candidate = (now.date_naive() + Days::new(ahead as u64))
+                    .and_time(time)
+                    .and_utc();
+                if candidate > now {
+                    candidate
+                } else {
+                    candidate + Days::new(7)
+                }
+            }
+        }
+    }
+}
+
+fn parse_time(spec: &str, s: &str) -> Result<NaiveTime, SchedulerError> {
+    NaiveTime::parse_from_str(s, "%H:%M")
+        .map_err(|_| SchedulerError::InvalidSchedule(spec.to_string(), format!("bad time '{}'", s)))
+}
+
+impl FromStr for Schedule {
+    type Err = SchedulerError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = spec.split_whitespace().collect();
+        match parts.as_slice() {
+            ["daily", time] => Ok(Schedule::Daily(parse_time(spec, time)?)),
+            ["weekly", day, time] => {
+                let weekday = day.parse::<Weekday>().map_err(|_| {
+                    SchedulerError::InvalidSchedule(spec.to_string(), format!("bad day '{}'", day))
+                })?;
+                Ok(Schedule::Weekly(weekday, parse_time(spec, time)?))
+            }
+            [interval] => match crate::parse_duration(interval) {
+                Some(d) if !d.is_zero() => Ok(Schedule::Every(d)),
+                _ => Err(SchedulerError::InvalidSchedule(
+                    spec.to_string(),
+                    "expected an interval like 6h, \"daily HH:MM\" or \"weekly <day> HH:MM\""
+                        .to_string(),
+                )),
+            },
+            _ => Err(SchedulerError::InvalidSchedule(
+                spec.to_string(),
+                "expected an interval like 6h, \"daily HH:MM\" or \"weekly <day> HH:MM\""
+                    .to_string(),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Schedule::Every(d) => write!(f, "every {}s", d.as_secs()),
+            Schedule::Daily(t) => write!(f, "daily {}", t.format("%H:%M")),
+            Schedule::Weekly(day, t) => write!(f, "weekly {} {}", day, t.format("%H:%M")),
+        }
+    }
+}
+
+/// A scheduled job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Job {
+    Sync,
+    BalanceDiscovery,
+    Repartition,
+    NormalizeLists,
+    Derive,
+}
+
+impl Job {
+    /// All jobs, in the order they run when due together.
+    pub fn all() -> [Job; 5] {
+        [
+            Job::BalanceDiscovery,
+            Job::Sync,
+            Job::Repartition,
+            Job::NormalizeLists,
+            Job::Derive,
+        ]
+    }
+
+    /// CLI name of this job.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Job::Sync => "sync",
+            Job::BalanceDiscovery => "balance-discovery",
+            Job::Repartition => "repartition",
+            Job::NormalizeLists => "normalize-lists",
+            Job::Derive => "derive",
+        }
+    }
+
+    /// Schedule spec for this job from the config.
+    pub fn spec<'a>(&self, config: &'a ScheduleConfig) -> &'a str {
+        match self {
+            Job::Sync => &config.sync,
+            Job::BalanceDiscovery => &config.balance_discovery,
+            Job::Repartition => &config.repartition,
+            Job::NormalizeLists => &config.normalize_lists,
+            Job::Derive => &config.derive,
+        }
+    }
+}
+
+impl FromStr for Job {
+    type Err = SchedulerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Job::all()
+            .into_iter()
+            .find(|j| j.name() == s.trim().replace('_', "-"))
+            .ok_or_else(|| SchedulerError::UnknownJob(s.to_string()))
+    }
+}
+
+/// Runs jobs on their schedules.
+pub struct Scheduler {
+    config: AppConfig,
+    storage: StorageConfig,
+    backend: Arc<dyn AiBackend>,
+    jobs: Vec<(Job, Schedule)>,
+    running: tokio::sync::Mutex<()>,
+    syncs: ActiveSyncs,
+}
+
+impl Scheduler {
+    /// Build a scheduler from `[schedule]`; jobs set to "off" are dropped.
+    pub fn new(
+        config: AppConfig,
+        storage: StorageConfig,
+        backend: Arc<dyn AiBackend>,
+    ) -> Result<Self, SchedulerError> {
+        let mut jobs = Vec::new();
+        for job in Job::all() {
+            if let Some(schedule) = Schedule::parse(job.spec(&config.schedule))? {
+                jobs.push((job, schedule));
+            }
+        }
+        Ok(Self {
+            config,
+            storage,
+            backend,
+            jobs,
+            running: tokio::sync::Mutex::new(()),
+            syncs: ActiveSyncs::default(),
+        })
+    }
+
+    /// Track sync jobs in `syncs`, so whoever owns it can cancel them.
+    pub fn with_active_syncs(mut self, syncs: ActiveSyncs) -> Self {
+        self.syncs = syncs;
+        self
+    }
+
+    /// Enabled jobs and their schedules.
+    pub fn jobs(&self) -> &[(Job, Schedule)] {
+        &self.jobs
+    }
+
+    /// Run one job now, waiting for any running job to finish first.
+    ///
+    /// Returns a one-line summary.
+    pub async fn run_job(&self, job: Job) -> Result<String, SchedulerError> {
+        let _guard = self.running.lock().await;
+        info!("Scheduler: running {}", job.name());
+        match job {
+            Job::Sync => self.sync().await,
+            Job::BalanceDiscovery => self.balance_discovery().await,
+            Job::Repartition => self.repartition(),
+            Job::NormalizeLists => self.normalize_lists().await,
+            Job::Derive => self.derive(),
+        }
+    }
+
+    /// Run every enabled job on its schedule until the task is dropped.
+    pub async fn run(self: Arc<Self>) {
+        if self.jobs.is_empty() {
+            warn!("Scheduler: no jobs enabled under [schedule]");
+            return;
+        }
+        let loops: Vec<_> = self
+            .jobs
+            .iter()
+            .map(|&(job, schedule)| {
+                let scheduler = self.clone();
+                tokio::spawn(async move { scheduler.job_loop(job, schedule).await })
+            })
+            .collect();
+        for handle in loops {
+            let _ = handle.await;
+        }
+    }
+
+    /// Spawn [`Scheduler::run`] as a background task.
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn job_loop(&self, job: Job, schedule: Schedule) {
+        loop {
+            let now = Utc::now();
+            let next = schedule.next_after(now);
+            info!("Scheduler: next {} at {}", job.name(), next);
+            tokio::time::sleep((next - now).to_std().unwrap_or_default()).await;
+
+            match self.run_job(job).await {
+                Ok(summary) => info!("Scheduler: {} done: {}", job.name(), summary),
+                Err(e) => error!("Scheduler: {} failed: {}", job.name(), e),
+            }
+        }
+    }
+
+    fn fetcher(&self, sources: &[Arc<dyn Source>]) -> Result<Fetcher, SchedulerError> {
+        Ok(Fetcher::new(
+            self.config.fetcher_config(&self.storage, sources),
+        )?)
+    }
+
+    async fn sync(&self) -> Result<String, SchedulerError> {
+        let sources = crate::sync::source::registry().enabled(&self.config.sources);
+        let fetcher = self.fetcher(&sources)?;
+        let sync_config = SyncConfig {
+            sources,
+            grace_days: self.config.epochs.grace_days,
+            storage: self.storage.clone(),
+            ..Default::default()
+        };
+        let mut orchestrator = SyncOrchestrator::new(sync_config, fetcher, self.backend.clone());
+        if let Some(notifier) = Notifier::from_config(&self.config.notifications) {
+            orchestrator = orchestrator.with_post_sync_hook(notifier);
+        }
+        let result = self.syncs.run(orchestrator).await?;
+        Ok(format!(
+            "{} events, {} placements, {} errors",
+            result.events_synced,
+            result.placements_synced,
+            result.errors.len()
+        ))
+    }
+
+    async fn balance_discovery(&self) -> Result<String, SchedulerError> {
+        let url = self.config.sources.warhammer_community.url.clone();
+        let fetcher =
+            self.fetcher(&[SyncSource::WarhammerCommunity { url: url.clone() }.into_source()])?;
+        let added = discover_balance_passes(
+            &fetcher,
+            self.backend.clone(),
+            &self.storage,
+            &url,
+            self.config.epochs.grace_days,
+            false,
+        )
+        .await?;
+        Ok(format!("{} new balance pass(es)", added.len()))
+    }
+
+    fn repartition(&self) -> Result<String, SchedulerError> {
+        let sig = cached_significant_events(&self.storage)?;
+        if !sig.iter().any(|e| e.event_type.is_epoch_boundary()) {
+            return Ok("skipped (no balance passes registered)".to_string());
+        }
+        let current = self.storage.normalized_dir().join("current");
+        if !current.exists() {
+            return Ok("skipped (nothing in 'current')".to_string());
+        }
+        let result = crate::sync::repartition::repartition(&self.storage, "current", false, false)
+            .map_err(|e| SchedulerError::Repartition(e.to_string()))?;
+        Ok(format!(
+            "{} events across {} epochs",
+            result.events_by_epoch.values().sum::<u32>(),
+            result.events_by_epoch.len()
+        ))
+    }
+
+    async fn normalize_lists(&self) -> Result<String, SchedulerError> {
+        let epoch_id = current_epoch_id(&self.storage);
+        let mut lists = merge_by_id(
+            JsonlReader::<ArmyList>::for_entity(&self.storage, EntityType::ArmyList, &epoch_id)
+                .read_all()?,
+            |l| l.id.as_str(),
+        );
+        let indices: Vec<usize> = lists
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| l.units.is_empty() && !l.raw_text.trim().is_empty())
+            .map(|(i, _)| i)
+            .take(self.config.schedule.normalize_limit)
+            .collect();
+        if indices.is_empty() {
+            return Ok(format!("no unparsed lists in {}", epoch_id));
+        }
+
+        let events = JsonlReader::<Event>::for_entity(&self.storage, EntityType::Event, &epoch_id)
+            .read_all()?;
+
+        let agent = ListNormalizerAgent::new(self.backend.clone());
+        let (mut normalized, mut failed) = (0u32, 0u32);
+        for &idx in &indices {
+            let input = ListNormalizerInput::for_list(&lists[idx], format!("list-{}", idx));
+            match execute_with_retry(&agent, input).await {
+                Ok(output) => {
+                    apply_normalized(&mut lists[idx], &output.list);
+                    flag_lists(&events, std::slice::from_mut(&mut lists[idx]));
+                    normalized += 1;
+                }
+                Err(e) => {
+                    warn!("Scheduler: failed to normalize list {}: {}", idx, e);
+                    failed += 1;
+                }
+            }
+            tokio::time::sleep(NORMALIZE_DELAY).await;
+        }
+
+        if normalized > 0 {
+            JsonlWriter::<ArmyList>::for_entity(&self.storage, EntityType::ArmyList, &epoch_id)
+                .write_all(&lists)?;
+        }
+        Ok(format!(
+            "{} normalized, {} failed in {}",
+            normalized, failed, epoch_id
+        ))
+    }
+
+    fn derive(&self) -> Result<String, SchedulerError> {
+        let (mut computed, mut skipped) = (0u32, 0u32);
+        for epoch_id in list_epochs(&self.storage)? {
+            for (_, outcome) in
+                run_derivations(&self.storage, &epoch_id, &Derivation::all(), false)?
+            {
+                match outcome {
+                    DeriveOutcome::Computed { .. } => computed += 1,
+                    DeriveOutcome::Skipped => skipped += 1,
+                }
+            }
+        }
+        let ratings = compute_ratings_from_storage(
+            &self.storage,
+            RatingConfig::default(),
+            DERIVED_GAME_SIZE,
+        )?;
+        write_ratings(&self.storage, &ratings)?;
+        Ok(format!(
+            "{} computed, {} up to date, {} players rated",
+            computed,
+            skipped,
+            ratings.players.len()
+        ))
+    }
+}
+
+/// Fetch the Warhammer Community downloads page, extract balance passes and
+/// register any not already known.
+///
+/// Returns the newly discovered passes (written unless `dry_run`), each
+/// given `grace_days`.
+pub async fn discover_balance_passes(
+    fetcher: &Fetcher,
+    backend: Arc<dyn AiBackend>,
+    storage: &StorageConfig,
+    page_url: &str,
+    grace_days: u32,
+    dry_run: bool,
+) -> Result<Vec<SignificantEvent>, SchedulerError> {
+    let url = url::Url::parse(page_url)
+        .map_err(|e| FetchError::InvalidUrl(format!("{}: {}", page_url, e)))?;
+    let fetch_result = fetcher.fetch(&url).await?;
+    let html = fetcher.read_cached_text(&fetch_result).await?;
+
+    let existing = read_significant_events(storage)?;
+    let input = BalanceWatcherInput {
+        html_content: html,
+        source_url: page_url.to_string(),
+        known_event_ids: existing.iter().map(|e| e.id.clone()).collect(),
+    };
+    let output = execute_with_retry(
+        &BalanceWatcherAgent::new(backend).with_grace_days(grace_days),
+        input,
+    )
+    .await?;
+
+    let mut added: Vec<SignificantEvent> = Vec::new();
+    for event in output.events {
+        let known = existing
+            .iter()
+            .chain(added.iter())
+            .any(|e| e.id == event.data.id);
+        if !known {
+            added.push(event.data);
+        }
+    }
+
+    if !dry_run && !added.is_empty() {
+        let mut merged = existing;
+        merged.extend(added.iter().cloned());
+        write_significant_events(storage, &mut merged)?;
+    }
+    Ok(added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::backend::MockBackend;
+    use chrono::TimeZone;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_schedule_parse() {
+        assert_eq!(
+            Schedule::parse("6h").unwrap(),
+            Some(Schedule::Every(Duration::from_secs(6 * 3600)))
+        );
+        assert_eq!(Schedule::parse("off").unwrap(), None);
+        assert_eq!(
+            Schedule::parse("daily 03:30").unwrap(),
+            Some(Schedule::Daily(NaiveTime::from_hms_opt(3, 30, 0).unwrap()))
+        );
+        assert!(matches!(
+            Schedule::parse("weekly mon 06:00").unwrap(),
+            Some(Schedule::Weekly(Weekday::Mon, _))
+        ));
+        assert!(Schedule::parse("daily 25:00").is_err());
+        assert!(Schedule::parse("sometimes").is_err());
+        assert!(Schedule::parse("0h").is_err());
+    }
+
+    #[test]
+    fn test_schedule_next_after() {
+        // 2026-03-04 is a Wednesday
+        let now = at(2026, 3, 4, 10, 0);
+
+        let daily = Schedule::parse("daily 06:00").unwrap().unwrap();
+        assert_eq!(daily.next_after(now), at(2026, 3, 5, 6, 0));
+        let daily = Schedule::parse("daily 12:00").unwrap().unwrap();
+        assert_eq!(daily.next_after(now), at(2026, 3, 4, 12, 0));
+
+        let weekly = Schedule::parse("weekly mon 06:00").unwrap().unwrap();
+        assert_eq!(weekly.next_after(now), at(2026, 3, 9, 6, 0));
+        let weekly = Schedule::parse("weekly wed 10:00").unwrap().unwrap();
+        assert_eq!(weekly.next_after(now), at(2026, 3, 11, 10, 0));
+
+        let every = Schedule::parse("30m").unwrap().unwrap();
+        assert_eq!(every.next_after(now), at(2026, 3, 4, 10, 30));
+    }
+
+    #[test]
+    fn test_job_names_roundtrip() {
+        for job in Job::all() {
+            assert_eq!(job.name().parse::<Job>().unwrap(), job);
+        }
+        assert_eq!(
+            "normalize_lists".parse::<Job>().unwrap(),
+            Job::NormalizeLists
+        );
+        assert!("nope".parse::<Job>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_jobs_and_offline_runs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        let mut config = AppConfig::default();
+        config.schedule.sync = "off".to_string();
+        let scheduler = Scheduler::new(config, storage, Arc::new(MockBackend::new("{}"))).unwrap();
+
+        let jobs: Vec<Job> = scheduler.jobs().iter().map(|(j, _)| *j).collect();
+        assert!(!jobs.contains(&Job::Sync));
+        // normalize-lists is off by default (it spends AI calls)
+        assert!(!jobs.contains(&Job::NormalizeLists));
+        assert!(jobs.contains(&Job::Derive));
+
+        let summary = scheduler.run_job(Job::Repartition).await.unwrap();
+        assert!(summary.starts_with("skipped"));
+        let summary = scheduler.run_job(Job::Derive).await.unwrap();
+        assert_eq!(summary, "0 computed, 0 up to date, 0 players rated");
+        let summary = scheduler.run_job(Job::NormalizeLists).await.unwrap();
+        assert_eq!(summary, "no unparsed lists in current");
+    }
+}
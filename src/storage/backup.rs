@@ -0,0 +1,213 @@
+//! Data lake snapshots.
+//!
+//! A snapshot is a directory copy of `normalized/` and `state/` under
+//! `backups/<timestamp>-<tag>/`, with a `snapshot.json` manifest. Raw
+//! content and derived/Parquet output are left out: the former is only a
+//! fetch cache and the latter can be rebuilt from the normalized data.
+
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use super::{invalidate_cache, StorageConfig, StorageError};
+
+/// Manifest file written into every snapshot.
+const MANIFEST: &str = "snapshot.json";
+
+/// Data directories included in a snapshot.
+const SNAPSHOT_DIRS: &[&str] = &["normalized", "state"];
+
+/// Tag of the snapshot taken automatically before a restore.
+pub const PRE_RESTORE_TAG: &str = "pre-restore";
+
+/// Metadata for one snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotInfo {
+    /// Directory name: `<YYYYmmddTHHMMSSZ>-<tag>`
+    pub id: String,
+    pub tag: String,
+    pub created_at: DateTime<Utc>,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// Reduce a tag to characters that are safe in a directory name.
+fn sanitize_tag(tag: &str) -> String {
+    let tag: String = tag
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let tag = tag.trim_matches('-');
+    if tag.is_empty() {
+        "manual".to_string()
+    } else {
+        tag.to_string()
+    }
+}
+
+/// Recursively copy `from` into `to`, returning (files, bytes) copied.
+/// Temp files from interrupted writes are skipped.
+fn copy_dir(from: &Path, to: &Path) -> Result<(usize, u64), StorageError> {
+    fs::create_dir_all(to)?;
+    let (mut files, mut bytes) = (0, 0);
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            let (f, b) = copy_dir(&path, &target)?;
+            files += f;
+            bytes += b;
+        } else if !entry.file_name().to_string_lossy().ends_with(".tmp") {
+            bytes += fs::copy(&path, &target)?;
+            files += 1;
+        }
+    }
+    Ok((files, bytes))
+}
+
+/// Snapshot `normalized/` and `state/` under `tag`.
+pub fn create_snapshot(config: &StorageConfig, tag: &str) -> Result<SnapshotInfo, StorageError> {
+    let tag = sanitize_tag(tag);
+    let created_at = Utc::now();
+    let mut id = format!("{}-{}", created_at.format("%Y%m%dT%H%M%SZ"), tag);
+    let mut suffix = 2;
+    while config.backups_dir().join(&id).exists() {
+        id = format!("{}-{}-{}", created_at.format("%Y%m%dT%H%M%SZ"), tag, suffix);
+        suffix += 1;
+    }
+    let dir = config.backups_dir().join(&id);
+    fs::create_dir_all(&dir)?;
+
+    let (mut files, mut bytes) = (0, 0);
+    for name in SNAPSHOT_DIRS {
+        let source = config.data_dir.join(name);
+        if source.is_dir() {
+            let (f, b) = copy_dir(&source, &dir.join(name))?;
+            files += f;
+            bytes += b;
+        }
+    }
+
+    let info = SnapshotInfo {
+        id,
+        tag,
+        created_at,
+        files,
+        bytes,
+    };
+    fs::write(dir.join(MANIFEST), serde_json::to_string_pretty(&info)?)?;
+    info!("Created snapshot {} ({} files)", info.id, info.files);
+    Ok(info)
+}
+
+/// All snapshots, oldest first. Directories without a manifest are ignored.
+pub fn list_snapshots(config: &StorageConfig) -> Result<Vec<SnapshotInfo>, StorageError> {
+    let dir = config.backups_dir();
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut snapshots = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let manifest = entry?.path().join(MANIFEST);
+        if let Ok(content) = fs::read_to_string(&manifest) {
+            snapshots.push(serde_json::from_str::<SnapshotInfo>(&content)?);
+        }
+    }
+    snapshots.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+    Ok(snapshots)
+}
+
+/// Find a snapshot by exact ID, else the newest with a matching tag
+/// ("latest" picks the newest overall).
+pub fn find_snapshot(config: &StorageConfig, name: &str) -> Result<SnapshotInfo, StorageError> {
+    let snapshots = list_snapshots(config)?;
+    let found = if name == "latest" {
+        snapshots.last()
+    } else {
+        snapshots.iter().find(|s| s.id == name).or_else(|| {
+            let tag = sanitize_tag(name);
+            snapshots.iter().rev().find(|s| s.tag == tag)
+        })
+    };
+    found
+        .cloned()
+        .ok_or_else(|| StorageError::SnapshotNotFound(name.to_string()))
+}
+
+/// Replace `normalized/` and `state/` with the contents of a snapshot.
+///
+/// The current data is snapshotted under [`PRE_RESTORE_TAG`] first, so a
+/// restore can itself be undone. Returns the restored snapshot.
+pub fn restore_snapshot(config: &StorageConfig, name: &str) -> Result<SnapshotInfo, StorageError> {
+    let snapshot = find_snapshot(config, name)?;
+    let source = config.backups_dir().join(&snapshot.id);
+    create_snapshot(config, PRE_RESTORE_TAG)?;
+
+    for dir in SNAPSHOT_DIRS {
+        let target = config.data_dir.join(dir);
+        if target.exists() {
+            fs::remove_dir_all(&target)?;
+        }
+        let saved = source.join(dir);
+        if saved.is_dir() {
+            copy_dir(&saved, &target)?;
+        }
+    }
+    invalidate_cache(config);
+    info!("Restored snapshot {}", snapshot.id);
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = StorageConfig::new(tmp.path().to_path_buf());
+        let events = config.normalized_dir().join("current").join("events.jsonl");
+        fs::create_dir_all(events.parent().unwrap()).unwrap();
+        fs::create_dir_all(config.state_dir()).unwrap();
+        fs::write(&events, "{\"id\":\"a\"}\n").unwrap();
+        fs::write(config.state_dir().join("cursor.json"), "{}").unwrap();
+
+        let snapshot = create_snapshot(&config, "Before Reclassify!").unwrap();
+        assert_eq!(snapshot.tag, "before-reclassify");
+        assert_eq!(snapshot.files, 2);
+
+        fs::write(&events, "{\"id\":\"b\"}\n").unwrap();
+        fs::write(config.normalized_dir().join("stray.jsonl"), "").unwrap();
+
+        let restored = restore_snapshot(&config, "before-reclassify").unwrap();
+        assert_eq!(restored.id, snapshot.id);
+        assert_eq!(fs::read_to_string(&events).unwrap(), "{\"id\":\"a\"}\n");
+        assert!(!config.normalized_dir().join("stray.jsonl").exists());
+
+        let tags: Vec<String> = list_snapshots(&config)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.tag)
+            .collect();
+        assert_eq!(tags, vec!["before-reclassify", PRE_RESTORE_TAG]);
+        assert_eq!(
+            find_snapshot(&config, "latest").unwrap().tag,
+            PRE_RESTORE_TAG
+        );
+        assert!(matches!(
+            find_snapshot(&config, "nope"),
+            Err(StorageError::SnapshotNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+//! Read-through cache for significant events and the epoch mapper.
+//!
+//! Almost every command and several API routes need the epoch timeline.
+//! Rather than re-reading `significant_events.jsonl` and rebuilding the
+//! [`EpochMapper`] each time, callers go through this cache. Entries are
+//! keyed by file path and invalidated when the file's modification time or
+//! size changes, or explicitly by [`write_significant_events`]. The mapper
+//! is also rebuilt when the date changes, since provisional epochs become
+//! current as their start date passes.
+//!
+//! [`write_significant_events`]: super::write_significant_events
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+use chrono::{NaiveDate, Utc};
+
+use super::{jsonl, StorageConfig, StorageError};
+use crate::models::{EpochMapper, SignificantEvent};
+
+/// Modification time and size of the file when it was read; `None` when
+/// the file did not exist.
+type FileStamp = Option<(SystemTime, u64)>;
+
+struct Entry {
+    stamp: FileStamp,
+    events: Arc<Vec<SignificantEvent>>,
+    mapper: Option<(NaiveDate, Arc<EpochMapper>)>,
+}
+
+fn cache() -> &'static Mutex<HashMap<PathBuf, Entry>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Entry>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn stamp(path: &Path) -> FileStamp {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+/// Significant events, read from disk only when the file has changed
+/// since the last call.
+pub fn cached_significant_events(
+    config: &StorageConfig,
+) -> Result<Arc<Vec<SignificantEvent>>, StorageError> {
+    let path = config.significant_events_path();
+    let current = stamp(&path);
+    let mut cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(entry) = cache.get(&path) {
+        if entry.stamp == current {
+            return Ok(entry.events.clone());
+        }
+    }
+
+    let events = Arc::new(jsonl::read_significant_events(config)?);
+    cache.insert(
+        path,
+        Entry {
+            stamp: current,
+            events: events.clone(),
+            mapper: None,
+        },
+    );
+    Ok(events)
+}
+
+/// Epoch mapper built from the cached significant events. Empty when no
+/// events are registered or the file cannot be read.
+pub fn cached_epoch_mapper(config: &StorageConfig) -> Arc<EpochMapper> {
+    let events = cached_significant_events(config).unwrap_or_default();
+    let today = Utc::now().date_naive();
+    let path = config.significant_events_path();
+
+    let mut cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(entry) = cache.get_mut(&path) {
+        if let Some((built_on, mapper)) = &entry.mapper {
+            if *built_on == today && Arc::ptr_eq(&entry.events, &events) {
+                return mapper.clone();
+            }
+        }
+        let mapper = Arc::new(EpochMapper::from_significant_events_as_of(&events, today));
+        if Arc::ptr_eq(&entry.events, &events) {
+            entry.mapper = Some((today, mapper.clone()));
+        }
+        return mapper;
+    }
+    Arc::new(EpochMapper::from_significant_events_as_of(&events, today))
+}
+
+/// The current epoch's ID, or "current" when no epochs are registered.
+pub fn current_epoch_id(config: &StorageConfig) -> String {
+    cached_epoch_mapper(config)
+        .current_epoch()
+        .map(|e| e.id.as_str().to_string())
+        .unwrap_or_else(|| "current".to_string())
+}
+
+/// Drop the cached entry for this storage root so the next read goes to disk.
+pub fn invalidate_cache(config: &StorageConfig) {
+    let mut cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+    cache.remove(&config.significant_events_path());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SignificantEventType;
+
+    fn pass(title: &str, date: &str) -> SignificantEvent {
+        SignificantEvent::new(
+            SignificantEventType::BalanceUpdate,
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            title.to_string(),
+            "https://example.com".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_cache_reuses_and_invalidates_on_write() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = StorageConfig::new(tmp.path().to_path_buf());
+
+        assert!(cached_significant_events(&config).unwrap().is_empty());
+        assert_eq!(current_epoch_id(&config), "current");
+
+        let mut events = vec![pass("January Dataslate", "2025-01-10")];
+        super::super::write_significant_events(&config, &mut events).unwrap();
+
+        let first = cached_significant_events(&config).unwrap();
+        assert_eq!(first.len(), 1);
+        assert!(Arc::ptr_eq(
+            &first,
+            &cached_significant_events(&config).unwrap()
+        ));
+
+        let mapper = cached_epoch_mapper(&config);
+        assert_eq!(mapper.all_epochs().len(), 1);
+        assert!(Arc::ptr_eq(&mapper, &cached_epoch_mapper(&config)));
+        assert_ne!(current_epoch_id(&config), "current");
+
+        events.push(pass("June Dataslate", "2025-06-10"));
+        super::super::write_significant_events(&config, &mut events).unwrap();
+        assert_eq!(cached_significant_events(&config).unwrap().len(), 2);
+        assert_eq!(cached_epoch_mapper(&config).all_epochs().len(), 2);
+    }
+}
@@ -0,0 +1,261 @@
+//! Compaction of the JSONL entity files.
+//!
+//! Writes append, so a record rewritten by a later sync or repair leaves
+//! its older copy behind and every reader dedups at runtime. Compaction
+//! rewrites each file (the shared epoch file and each source partition)
+//! with one record per ID, keeping the most recently updated copy, sorted
+//! in reading order: events by date, placements by event and rank, lists
+//! by event date, pairings by event and round.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::index::Indexed;
+use super::jsonl::{entity_paths, list_epochs, EntityType, JsonlReader, JsonlWriter};
+use super::store::StorageBackend;
+use super::{invalidate_indexes, StorageConfig, StorageError};
+use crate::models::{ArmyList, Event, Pairing, Placement, Timestamped};
+
+/// Records a sync must store before it compacts the files afterwards.
+pub const AUTO_COMPACT_MIN_RECORDS: u32 = 1000;
+
+/// An entity whose files can be compacted.
+pub trait Compactable: Indexed + Timestamped + Serialize {
+    const ENTITY: EntityType;
+
+    /// Order of records in a compacted file.
+    fn compare(&self, other: &Self) -> Ordering;
+}
+
+impl Compactable for Event {
+    const ENTITY: EntityType = EntityType::Event;
+
+    fn compare(&self, other: &Self) -> Ordering {
+        (self.date, &self.name).cmp(&(other.date, &other.name))
+    }
+}
+
+impl Compactable for Placement {
+    const ENTITY: EntityType = EntityType::Placement;
+
+    fn compare(&self, other: &Self) -> Ordering {
+        (self.event_id.as_str(), self.rank).cmp(&(other.event_id.as_str(), other.rank))
+    }
+}
+
+impl Compactable for ArmyList {
+    const ENTITY: EntityType = EntityType::ArmyList;
+
+    fn compare(&self, other: &Self) -> Ordering {
+        (self.event_date, self.id.as_str()).cmp(&(other.event_date, other.id.as_str()))
+    }
+}
+
+impl Compactable for Pairing {
+    const ENTITY: EntityType = EntityType::Pairing;
+
+    fn compare(&self, other: &Self) -> Ordering {
+        (self.event_id.as_str(), self.round, self.id.as_str()).cmp(&(
+            other.event_id.as_str(),
+            other.round,
+            other.id.as_str(),
+        ))
+    }
+}
+
+/// Outcome of compacting one file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileCompaction {
+    /// Relative to the normalized directory
+    pub path: String,
+    pub lines_before: usize,
+    pub lines_after: usize,
+    /// Whether the file was (or, on a dry run, would be) rewritten
+    pub rewritten: bool,
+}
+
+impl FileCompaction {
+    pub fn reclaimed(&self) -> usize {
+        self.lines_before - self.lines_after
+    }
+}
+
+/// Outcome of a compaction run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompactReport {
+    pub dry_run: bool,
+    pub files: Vec<FileCompaction>,
+}
+
+impl CompactReport {
+    /// Duplicate lines removed across all files.
+    pub fn reclaimed(&self) -> usize {
+        self.files.iter().map(FileCompaction::reclaimed).sum()
+    }
+
+    pub fn files_rewritten(&self) -> usize {
+        self.files.iter().filter(|f| f.rewritten).count()
+    }
+}
+
+/// One record per ID (the most recently updated; the later line on a
+/// tie), in compacted order.
+pub fn compact_records<T: Compactable>(records: Vec<T>) -> Vec<T> {
+    let mut latest: HashMap<String, usize> = HashMap::new();
+    let mut kept: Vec<Option<T>> = Vec::with_capacity(records.len());
+    for record in records {
+        let id = record.index_id().to_string();
+        match latest.get(&id) {
+            Some(&idx) => {
+                let existing = kept[idx].as_ref().expect("kept record");
+                if record.last_modified() >= existing.last_modified() {
+                    kept[idx] = Some(record);
+                }
+            }
+            None => {
+                latest.insert(id, kept.len());
+                kept.push(Some(record));
+            }
+        }
+    }
+    let mut kept: Vec<T> = kept.into_iter().flatten().collect();
+    kept.sort_by(T::compare);
+    kept
+}
+
+fn compact_file<T: Compactable>(
+    config: &StorageConfig,
+    path: &Path,
+    dry_run: bool,
+) -> Result<FileCompaction, StorageError> {
+    let records: Vec<T> = JsonlReader::new(path.to_path_buf()).read_all()?;
+    let lines_before = std::fs::read_to_string(path)?
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .count();
+    let ids_before: Vec<String> = records.iter().map(|r| r.index_id().to_string()).collect();
+    let compacted = compact_records(records);
+    let rewritten = lines_before != compacted.len()
+        || compacted
+            .iter()
+            .map(|r| r.index_id())
+            .ne(ids_before.iter().map(String::as_str));
+
+    if rewritten && !dry_run {
+        JsonlWriter::new(path.to_path_buf())
+            .with_backups(config.backup_generations)
+            .write_all(&compacted)?;
+    }
+    Ok(FileCompaction {
+        path: relative(config, path),
+        lines_before,
+        lines_after: compacted.len(),
+        rewritten,
+    })
+}
+
+fn relative(config: &StorageConfig, path: &Path) -> String {
+    path.strip_prefix(config.normalized_dir())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|_| PathBuf::from(path))
+        .display()
+        .to_string()
+}
+
+fn compact_entity<T: Compactable>(
+    config: &StorageConfig,
+    epoch_id: &str,
+    dry_run: bool,
+    report: &mut CompactReport,
+) -> Result<(), StorageError> {
+    for path in entity_paths(config, T::ENTITY, epoch_id) {
+        report
+            .files
+            .push(compact_file::<T>(config, &path, dry_run)?);
+    }
+    Ok(())
+}
+
+/// Compact the entity files of the given epochs, or of every epoch when
+/// `epochs` is None. Only the JSONL backend has files to compact.
+pub fn compact(
+    config: &StorageConfig,
+    epochs: Option<&[String]>,
+    dry_run: bool,
+) -> Result<CompactReport, StorageError> {
+    let mut report = CompactReport {
+        dry_run,
+        ..Default::default()
+    };
+    if config.backend != StorageBackend::Jsonl {
+        return Ok(report);
+    }
+    let epochs = match epochs {
+        Some(epochs) => epochs.to_vec(),
+        None => list_epochs(config)?,
+    };
+    for epoch_id in epochs.iter().filter(|e| !e.ends_with(".bak")) {
+        compact_entity::<Event>(config, epoch_id, dry_run, &mut report)?;
+        compact_entity::<Placement>(config, epoch_id, dry_run, &mut report)?;
+        compact_entity::<ArmyList>(config, epoch_id, dry_run, &mut report)?;
+        compact_entity::<Pairing>(config, epoch_id, dry_run, &mut report)?;
+    }
+    if report.files_rewritten() > 0 && !dry_run {
+        invalidate_indexes(config);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EntityId;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_compact_keeps_latest_and_sorts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = StorageConfig::new(tmp.path().to_path_buf());
+        let event = |name: &str, day: u32| {
+            Event::new(
+                name.to_string(),
+                NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+                format!("https://example.com/{}", name),
+                "bcp".to_string(),
+                EntityId::from("current"),
+            )
+        };
+        let late = event("Late GT", 20);
+        let early = event("Early GT", 5);
+        let mut fixed = early.clone();
+        fixed.player_count = Some(64);
+        fixed.updated_at = Some(early.created_at + chrono::Duration::minutes(5));
+        // A stale copy appended after the fix must not win
+        let stale = early.clone();
+
+        JsonlWriter::for_entity(&config, EntityType::Event, "current")
+            .write_all(&[late.clone(), early, fixed, stale])
+            .unwrap();
+
+        let report = compact(&config, None, true).unwrap();
+        assert_eq!((report.reclaimed(), report.files_rewritten()), (2, 1));
+        assert_eq!(report.files[0].path, "current/events.jsonl");
+
+        let report = compact(&config, Some(&["current".to_string()]), false).unwrap();
+        assert_eq!(report.reclaimed(), 2);
+        let events: Vec<Event> = JsonlReader::for_entity(&config, EntityType::Event, "current")
+            .read_all()
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].name, "Early GT");
+        assert_eq!(events[0].player_count, Some(64));
+        assert_eq!(events[1].id, late.id);
+
+        // Already compact: nothing to rewrite
+        let report = compact(&config, None, false).unwrap();
+        assert_eq!((report.reclaimed(), report.files_rewritten()), (0, 0));
+    }
+}
@@ -0,0 +1,576 @@
+//! Ad-hoc SQL over the Parquet lake.
+//!
+//! Queries are checked here before they reach DuckDB: a single `SELECT` or
+//! `WITH` statement, no file-reading table functions, and only allow-listed
+//! tables. Execution (behind the `duckdb` feature) loads each table's
+//! Parquet files into an in-memory database, then switches off external
+//! access so the query itself can't touch the filesystem.
+
+use serde::Serialize;
+use thiserror::Error;
+
+use super::TableType;
+
+/// Tables a query may read, one per Parquet table type.
+pub const QUERY_TABLES: &[TableType] = &[
+    TableType::Events,
+    TableType::Placements,
+    TableType::FactionStats,
+];
+
+/// Rows returned when the caller doesn't ask for a limit.
+pub const DEFAULT_ROW_LIMIT: usize = 1_000;
+
+/// Hard cap on returned rows.
+pub const MAX_ROW_LIMIT: usize = 10_000;
+
+/// Keywords that write, change settings or reach outside the lake.
+const FORBIDDEN_KEYWORDS: &[&str] = &[
+    "insert",
+    "update",
+    "delete",
+    "merge",
+    "create",
+    "drop",
+    "alter",
+    "truncate",
+    "copy",
+    "attach",
+    "detach",
+    "install",
+    "load",
+    "pragma",
+    "set",
+    "reset",
+    "export",
+    "import",
+    "call",
+    "checkpoint",
+    "vacuum",
+    "grant",
+];
+
+/// Table functions that read files or run nested queries.
+const FORBIDDEN_FUNCTIONS: &[&str] = &[
+    "glob",
+    "parquet_scan",
+    "parquet_metadata",
+    "parquet_schema",
+    "query",
+    "query_table",
+    "sniff_csv",
+    "getenv",
+];
+
+/// Errors from query validation or execution.
+#[derive(Debug, Error)]
+pub enum QueryError {
+    #[error("Query is empty")]
+    Empty,
+
+    #[error("Only a single statement is allowed")]
+    MultipleStatements,
+
+    #[error("Only SELECT queries are allowed")]
+    NotReadOnly,
+
+    #[error("Unbalanced parentheses")]
+    UnbalancedParentheses,
+
+    #[error("'{0}' is not allowed in queries")]
+    Forbidden(String),
+
+    #[error("Unknown table '{0}' (available: {1})")]
+    UnknownTable(String, String),
+
+    #[error("Query failed: {0}")]
+    Engine(String),
+}
+
+/// A query that passed validation, wrapped with its row limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidatedQuery {
+    /// SQL to execute; fetches one row past `limit` to detect truncation
+    pub sql: String,
+    pub limit: usize,
+}
+
+/// Query output as JSON-friendly rows.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+    pub row_count: usize,
+    /// More rows matched than `limit`
+    pub truncated: bool,
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Lowercased keyword or identifier (quoted identifiers unquoted)
+    Word(String),
+    /// A string literal; the contents don't matter
+    Str,
+    Punct(char),
+}
+
+/// SQL with `--` and `/* */` comments replaced by whitespace. Quoted
+/// strings and identifiers are copied as they are.
+fn strip_comments(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '-' if chars.peek() == Some(&'-') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+                out.push('\n');
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                out.push(' ');
+            }
+            '\'' | '"' => {
+                out.push(c);
+                while let Some(next) = chars.next() {
+                    out.push(next);
+                    if next == c {
+                        // Doubled quote is an escaped quote
+                        if chars.peek() == Some(&c) {
+                            out.extend(chars.next());
+                        } else {
+                            break;
+                        }
+                    }
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Split comment-free SQL into words, string literals and punctuation.
+fn tokenize(sql: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '\'' | '"' => {
+                let mut text = String::new();
+                while let Some(next) = chars.next() {
+                    if next == c {
+                        // Doubled quote is an escaped quote
+                        if chars.peek() == Some(&c) {
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                    text.push(next);
+                }
+                tokens.push(if c == '"' {
+                    Token::Word(text.to_lowercase())
+                } else {
+                    Token::Str
+                });
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut word = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_alphanumeric() || next == '_' {
+                        word.push(next);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Word(word.to_lowercase()));
+            }
+            c => tokens.push(Token::Punct(c)),
+        }
+    }
+    tokens
+}
+
+/// Keywords that end a FROM list.
+const FROM_LIST_END: &[&str] = &[
+    "where",
+    "group",
+    "having",
+    "qualify",
+    "window",
+    "order",
+    "limit",
+    "offset",
+    "union",
+    "except",
+    "intersect",
+    "select",
+];
+
+/// Check every table reference in the FROM list starting at `start`, joins
+/// and comma-separated tables alike. DuckDB reads string literals there as
+/// file paths, so none may appear outside a join condition.
+fn check_from_list(
+    tokens: &[Token],
+    start: usize,
+    tables: &[&str],
+    ctes: &[&str],
+) -> Result<(), QueryError> {
+    let mut depth = 0usize;
+    // Depth of the innermost table function's arguments
+    let mut function_depth: Option<usize> = None;
+    let mut expect_table = true;
+    let mut in_condition = false;
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        match token {
+            Token::Punct('(') => {
+                depth += 1;
+                expect_table = false;
+            }
+            Token::Punct(')') => {
+                if depth == 0 {
+                    return Ok(());
+                }
+                if function_depth == Some(depth) {
+                    function_depth = None;
+                }
+                depth -= 1;
+            }
+            Token::Str if function_depth.is_some() || (depth == 0 && !in_condition) => {
+                return Err(QueryError::Forbidden("file paths".into()));
+            }
+            // Subqueries are checked on their own FROM
+            _ if depth > 0 => {}
+            Token::Punct(',') => {
+                expect_table = true;
+                in_condition = false;
+            }
+            Token::Word(w) if FROM_LIST_END.contains(&w.as_str()) => return Ok(()),
+            Token::Word(w) if w == "join" => {
+                expect_table = true;
+                in_condition = false;
+            }
+            Token::Word(w) if w == "on" || w == "using" => in_condition = true,
+            Token::Word(table) if expect_table => {
+                expect_table = false;
+                if tokens.get(i + 1) == Some(&Token::Punct('(')) {
+                    function_depth = Some(depth + 1);
+                } else if !tables.contains(&table.as_str()) && !ctes.contains(&table.as_str()) {
+                    return Err(QueryError::UnknownTable(table.clone(), tables.join(", ")));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn table_names() -> Vec<&'static str> {
+    QUERY_TABLES.iter().map(|t| t.name()).collect()
+}
+
+/// Check a user query and wrap it with a row limit.
+///
+/// Comments are stripped first, so none can hide the wrapper's closing
+/// parenthesis or limit. `limit` defaults to [`DEFAULT_ROW_LIMIT`] and is
+/// capped at [`MAX_ROW_LIMIT`].
+pub fn validate_query(sql: &str, limit: Option<usize>) -> Result<ValidatedQuery, QueryError> {
+    let sql = strip_comments(sql);
+    let sql = sql
+        .trim()
+        .trim_end_matches(|c: char| c == ';' || c.is_whitespace());
+    let tokens = tokenize(sql);
+    if tokens.is_empty() {
+        return Err(QueryError::Empty);
+    }
+    if tokens.contains(&Token::Punct(';')) {
+        return Err(QueryError::MultipleStatements);
+    }
+    // A stray `)` would close the wrapper and leave the limit behind
+    let mut depth = 0i32;
+    for token in &tokens {
+        match token {
+            Token::Punct('(') => depth += 1,
+            Token::Punct(')') => depth -= 1,
+            _ => {}
+        }
+        if depth < 0 {
+            return Err(QueryError::UnbalancedParentheses);
+        }
+    }
+    if depth != 0 {
+        return Err(QueryError::UnbalancedParentheses);
+    }
+    if !matches!(&tokens[0], Token::Word(w) if w == "select" || w == "with") {
+        return Err(QueryError::NotReadOnly);
+    }
+
+    // CTE names: `name AS (`
+    let ctes: Vec<&str> = tokens
+        .windows(3)
+        .filter_map(|w| match w {
+            [Token::Word(name), Token::Word(kw), Token::Punct('(')] if kw == "as" => {
+                Some(name.as_str())
+            }
+            _ => None,
+        })
+        .collect();
+    let tables = table_names();
+
+    for (i, token) in tokens.iter().enumerate() {
+        let Token::Word(word) = token else { continue };
+        if FORBIDDEN_KEYWORDS.contains(&word.as_str()) {
+            return Err(QueryError::Forbidden(word.clone()));
+        }
+        let next = tokens.get(i + 1);
+        if next == Some(&Token::Punct('('))
+            && (word.starts_with("read_") || FORBIDDEN_FUNCTIONS.contains(&word.as_str()))
+        {
+            return Err(QueryError::Forbidden(format!("{}()", word)));
+        }
+        if word == "from" {
+            check_from_list(&tokens, i + 1, &tables, &ctes)?;
+        }
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_ROW_LIMIT).clamp(1, MAX_ROW_LIMIT);
+    Ok(ValidatedQuery {
+        sql: format!("SELECT * FROM (\n{}\n) AS q LIMIT {}", sql, limit + 1),
+        limit,
+    })
+}
+
+/// Run a validated query against the Parquet files under `parquet_dir`
+/// (every epoch's files are combined per table).
+#[cfg(feature = "duckdb")]
+pub fn run_query(
+    parquet_dir: &std::path::Path,
+    query: &ValidatedQuery,
+) -> Result<QueryResult, QueryError> {
+    let engine = |e: duckdb::Error| QueryError::Engine(e.to_string());
+    let conn = duckdb::Connection::open_in_memory().map_err(engine)?;
+
+    for table in QUERY_TABLES {
+        let pattern = parquet_dir.join("*").join(table.filename());
+        let pattern = pattern.to_string_lossy();
+        let has_files = glob::glob(&pattern)
+            .map(|mut paths| paths.next().is_some())
+            .unwrap_or(false);
+        if has_files {
+            conn.execute_batch(&format!(
+                "CREATE TABLE {} AS SELECT * FROM read_parquet('{}', union_by_name = true)",
+                table.name(),
+                pattern.replace('\'', "''")
+            ))
+            .map_err(engine)?;
+        }
+    }
+    conn.execute_batch("SET enable_external_access = false; SET lock_configuration = true;")
+        .map_err(engine)?;
+
+    let mut stmt = conn.prepare(&query.sql).map_err(engine)?;
+    let mut rows = Vec::new();
+    {
+        // One row past the limit is enough to know there are more
+        let mut result = stmt.query([]).map_err(engine)?;
+        while rows.len() <= query.limit {
+            let Some(row) = result.next().map_err(engine)? else {
+                break;
+            };
+            let values = (0..row.as_ref().column_count())
+                .map(|i| row.get::<_, duckdb::types::Value>(i).map(value_to_json))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(engine)?;
+            rows.push(values);
+        }
+    }
+    let columns = stmt.column_names();
+
+    let truncated = rows.len() > query.limit;
+    rows.truncate(query.limit);
+    Ok(QueryResult {
+        columns,
+        row_count: rows.len(),
+        rows,
+        truncated,
+        limit: query.limit,
+    })
+}
+
+#[cfg(feature = "duckdb")]
+fn value_to_json(value: duckdb::types::Value) -> serde_json::Value {
+    use duckdb::types::Value;
+    use serde_json::json;
+
+    match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Boolean(b) => json!(b),
+        Value::TinyInt(n) => json!(n),
+        Value::SmallInt(n) => json!(n),
+        Value::Int(n) => json!(n),
+        Value::BigInt(n) => json!(n),
+        Value::UTinyInt(n) => json!(n),
+        Value::USmallInt(n) => json!(n),
+        Value::UInt(n) => json!(n),
+        Value::UBigInt(n) => json!(n),
+        Value::Float(n) => json!(n),
+        Value::Double(n) => json!(n),
+        Value::Text(s) | Value::Enum(s) => json!(s),
+        Value::List(values) => {
+            serde_json::Value::Array(values.into_iter().map(value_to_json).collect())
+        }
+        other => json!(format!("{:?}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_query_accepts_selects() {
+        let q = validate_query(
+            "SELECT faction, count(*) AS n FROM placements WHERE rank <= 4 GROUP BY faction;",
+            None,
+        )
+        .unwrap();
+        assert_eq!(q.limit, DEFAULT_ROW_LIMIT);
+        assert!(q.sql.ends_with("LIMIT 1001"));
+
+        let q = validate_query(
+            "WITH top AS (SELECT * FROM placements WHERE rank = 1)
+             SELECT e.name FROM top JOIN events e ON e.id = top.event_id -- winners",
+            Some(50_000),
+        )
+        .unwrap();
+        assert_eq!(q.limit, MAX_ROW_LIMIT);
+        assert!(!q.sql.contains("winners"));
+        assert!(q.sql.ends_with("\n) AS q LIMIT 10001"));
+
+        // Keywords inside strings are data, not statements
+        assert!(validate_query("SELECT * FROM events WHERE name = 'Drop; Zone'", None).is_ok());
+        // Comment markers inside strings are kept
+        let q = validate_query("SELECT * FROM events WHERE name = 'a -- b'", None).unwrap();
+        assert!(q.sql.contains("'a -- b'"));
+        // Every table in a FROM list is checked, conditions may hold strings
+        assert!(validate_query(
+            "SELECT * FROM events e, placements p JOIN faction_stats f ON f.faction = 'Orks'
+             WHERE p.event_id = e.id AND e.name <> 'x'",
+            None,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_validate_query_keeps_the_limit() {
+        // Comments can't swallow the wrapper's closing parenthesis or limit
+        for sql in [
+            "SELECT * FROM placements --",
+            "SELECT * FROM placements /* unterminated",
+            "SELECT * FROM placements /* ) AS q */",
+        ] {
+            let q = validate_query(sql, Some(5)).unwrap();
+            assert!(!q.sql.contains("--") && !q.sql.contains("/*"), "{}", q.sql);
+            assert!(q.sql.ends_with("\n) AS q LIMIT 6"), "{}", q.sql);
+        }
+        assert!(matches!(
+            validate_query("SELECT * FROM placements) AS x --", None),
+            Err(QueryError::UnbalancedParentheses)
+        ));
+        assert!(matches!(
+            validate_query("SELECT * FROM (SELECT * FROM placements", None),
+            Err(QueryError::UnbalancedParentheses)
+        ));
+    }
+
+    #[cfg(feature = "duckdb")]
+    #[test]
+    fn test_run_query_commented_query_is_truncated() {
+        use crate::storage::parquet::{ParquetWriter, PlacementRecord};
+        use crate::storage::StorageConfig;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        let placements: Vec<PlacementRecord> = (1..=5)
+            .map(|rank| PlacementRecord {
+                id: format!("p{}", rank),
+                event_id: "gt".to_string(),
+                rank,
+                player_name: format!("Player {}", rank),
+                faction: "Aeldari".to_string(),
+                subfaction: None,
+                detachment: None,
+                wins: None,
+                losses: None,
+                draws: None,
+                battle_points: None,
+                epoch_id: "current".to_string(),
+            })
+            .collect();
+        ParquetWriter::new(storage.clone())
+            .write_placements("current", &placements)
+            .unwrap();
+
+        let query = validate_query("SELECT * FROM placements -- no limit please", Some(2)).unwrap();
+        let result = run_query(&storage.parquet_dir(), &query).unwrap();
+        assert!(result.truncated);
+        assert_eq!(result.row_count, 2);
+    }
+
+    #[test]
+    fn test_validate_query_rejects_unsafe() {
+        assert!(matches!(
+            validate_query("  ;", None),
+            Err(QueryError::Empty)
+        ));
+        assert!(matches!(
+            validate_query("SELECT 1; SELECT 2", None),
+            Err(QueryError::MultipleStatements)
+        ));
+        assert!(matches!(
+            validate_query("DELETE FROM events", None),
+            Err(QueryError::NotReadOnly)
+        ));
+        assert!(matches!(
+            validate_query("SELECT * FROM read_csv('/etc/passwd')", None),
+            Err(QueryError::Forbidden(_))
+        ));
+        assert!(matches!(
+            validate_query("SELECT * FROM '/tmp/other.parquet'", None),
+            Err(QueryError::Forbidden(_))
+        ));
+        assert!(matches!(
+            validate_query(
+                "WITH x AS (SELECT 1) SELECT * FROM x; COPY events TO 'out.csv'",
+                None
+            ),
+            Err(QueryError::MultipleStatements)
+        ));
+        assert!(matches!(
+            validate_query("SELECT * FROM duckdb_settings", None),
+            Err(QueryError::UnknownTable(t, _)) if t == "duckdb_settings"
+        ));
+        assert!(matches!(
+            validate_query("SELECT * FROM events, duckdb_settings", None),
+            Err(QueryError::UnknownTable(t, _)) if t == "duckdb_settings"
+        ));
+        assert!(matches!(
+            validate_query("SELECT * FROM events, 'x.parquet'", None),
+            Err(QueryError::Forbidden(_))
+        ));
+    }
+}
@@ -0,0 +1,485 @@
+//! In-memory entity indexes over the JSONL files.
+//!
+//! API routes read the same epoch files on nearly every request. Going
+//! through [`IndexedReader`] instead of [`JsonlReader`] parses a file once,
+//! deduplicates it by ID (the newest copy wins, as with [`merge_by_id`])
+//! and keeps it indexed by ID, event and player name. Like the
+//! significant-events cache, entries are keyed by epoch file and
+//! invalidated when any covered file's modification time or size changes,
+//! or when a source partition appears.
+//!
+//! [`merge_by_id`]: crate::api::merge_by_id
+
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use tracing::{debug, info};
+
+use super::jsonl::{entity_path, entity_paths, list_epochs, parse_lines, EntityType, JsonlReader};
+use super::store::{self, StorageBackend, Store};
+use super::{note_read, StorageConfig, StorageError};
+use crate::models::{ArmyList, BracketMatch, Event, Pairing, Placement, PointsChange, Timestamped};
+use crate::sync::normalize_player_name;
+
+/// An entity that can be held in an [`EntityIndex`].
+pub trait Indexed: DeserializeOwned + Clone + Send + Sync + 'static {
+    fn index_id(&self) -> &str;
+
+    /// Event the entity belongs to, if any.
+    fn index_event(&self) -> Option<&str> {
+        None
+    }
+
+    /// Players the entity involves.
+    fn index_players(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    /// When the record was last written, if it tracks that. Of several
+    /// copies of an ID the newest is indexed.
+    fn index_modified(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+}
+
+impl Indexed for Event {
+    fn index_id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn index_event(&self) -> Option<&str> {
+        Some(self.id.as_str())
+    }
+
+    fn index_modified(&self) -> Option<DateTime<Utc>> {
+        Some(self.last_modified())
+    }
+}
+
+impl Indexed for Placement {
+    fn index_id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn index_event(&self) -> Option<&str> {
+        Some(self.event_id.as_str())
+    }
+
+    fn index_players(&self) -> Vec<&str> {
+        vec![&self.player_name]
+    }
+
+    fn index_modified(&self) -> Option<DateTime<Utc>> {
+        Some(self.last_modified())
+    }
+}
+
+impl Indexed for ArmyList {
+    fn index_id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn index_event(&self) -> Option<&str> {
+        self.event_id.as_ref().map(|e| e.as_str())
+    }
+
+    fn index_players(&self) -> Vec<&str> {
+        self.player_name.as_deref().into_iter().collect()
+    }
+
+    fn index_modified(&self) -> Option<DateTime<Utc>> {
+        Some(self.last_modified())
+    }
+}
+
+impl Indexed for Pairing {
+    fn index_id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn index_event(&self) -> Option<&str> {
+        Some(self.event_id.as_str())
+    }
+
+    fn index_players(&self) -> Vec<&str> {
+        vec![&self.player1_name, &self.player2_name]
+    }
+
+    fn index_modified(&self) -> Option<DateTime<Utc>> {
+        Some(self.last_modified())
+    }
+}
+
+impl Indexed for BracketMatch {
+    fn index_id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn index_event(&self) -> Option<&str> {
+        Some(self.event_id.as_str())
+    }
+
+    fn index_players(&self) -> Vec<&str> {
+        vec![&self.player1_name, &self.player2_name]
+    }
+}
+
+impl Indexed for PointsChange {
+    fn index_id(&self) -> &str {
+        self.id.as_str()
+    }
+}
+
+/// Deduplicated entities of one type in one epoch, with lookup tables.
+#[derive(Debug)]
+pub struct EntityIndex<T> {
+    records: Vec<T>,
+    by_id: HashMap<String, usize>,
+    by_event: HashMap<String, Vec<usize>>,
+    by_player: HashMap<String, Vec<usize>>,
+}
+
+impl<T: Indexed> EntityIndex<T> {
+    /// Build an index, keeping the newest record for each ID (the first
+    /// on a tie) in the position of its first occurrence.
+    pub fn build(entities: Vec<T>) -> Self {
+        let mut records: Vec<T> = Vec::with_capacity(entities.len());
+        let mut by_id: HashMap<String, usize> = HashMap::new();
+        for entity in entities {
+            match by_id.get(entity.index_id()).copied() {
+                Some(pos) => {
+                    if entity.index_modified() > records[pos].index_modified() {
+                        records[pos] = entity;
+                    }
+                }
+                None => {
+                    by_id.insert(entity.index_id().to_string(), records.len());
+                    records.push(entity);
+                }
+            }
+        }
+
+        let mut index = Self {
+            records: Vec::with_capacity(records.len()),
+            by_id,
+            by_event: HashMap::new(),
+            by_player: HashMap::new(),
+        };
+        for entity in records {
+            let pos = index.records.len();
+            if let Some(event) = entity.index_event() {
+                index
+                    .by_event
+                    .entry(event.to_string())
+                    .or_default()
+                    .push(pos);
+            }
+            let players: HashSet<String> = entity
+                .index_players()
+                .into_iter()
+                .map(player_key)
+                .filter(|p| !p.is_empty())
+                .collect();
+            for player in players {
+                index.by_player.entry(player).or_default().push(pos);
+            }
+            index.records.push(entity);
+        }
+        index
+    }
+
+    /// All records, in file order.
+    pub fn all(&self) -> &[T] {
+        &self.records
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn get(&self, id: &str) -> Option<&T> {
+        self.by_id.get(id).map(|&i| &self.records[i])
+    }
+
+    /// Records belonging to an event.
+    pub fn for_event(&self, event_id: &str) -> Vec<&T> {
+        self.lookup(&self.by_event, event_id)
+    }
+
+    /// Records involving a player, matched case- and whitespace-insensitively.
+    pub fn for_player(&self, name: &str) -> Vec<&T> {
+        self.lookup(&self.by_player, &player_key(name))
+    }
+
+    fn lookup(&self, table: &HashMap<String, Vec<usize>>, key: &str) -> Vec<&T> {
+        table
+            .get(key)
+            .map(|positions| positions.iter().map(|&i| &self.records[i]).collect())
+            .unwrap_or_default()
+    }
+}
+
+fn player_key(name: &str) -> String {
+    normalize_player_name(name).to_lowercase()
+}
+
+/// Modification time and size of each file an index was built from.
+type Stamps = Vec<(PathBuf, Option<(SystemTime, u64)>)>;
+
+struct Entry {
+    stamps: Stamps,
+    index: Arc<dyn Any + Send + Sync>,
+}
+
+fn cache() -> &'static Mutex<HashMap<PathBuf, Entry>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Entry>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn stamps(paths: Vec<PathBuf>) -> Stamps {
+    paths
+        .into_iter()
+        .map(|path| {
+            let stamp = std::fs::metadata(&path)
+                .ok()
+                .and_then(|m| Some((m.modified().ok()?, m.len())));
+            (path, stamp)
+        })
+        .collect()
+}
+
+/// Reader with the same shape as [`JsonlReader`] that serves from the
+/// index cache.
+pub struct IndexedReader<T> {
+    config: StorageConfig,
+    entity: EntityType,
+    epoch_id: String,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Indexed> IndexedReader<T> {
+    pub fn for_entity(config: &StorageConfig, entity: EntityType, epoch_id: &str) -> Self {
+        Self {
+            config: config.clone(),
+            entity,
+            epoch_id: epoch_id.to_string(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The index for this epoch file, rebuilt only when a file changed.
+    /// Other backends have their own indexes and are read directly.
+    pub fn index(&self) -> Result<Arc<EntityIndex<T>>, StorageError> {
+        if self.config.backend != StorageBackend::Jsonl {
+            let entities = JsonlReader::<T>::for_entity(&self.config, self.entity, &self.epoch_id)
+                .read_all()?;
+            return Ok(Arc::new(EntityIndex::build(entities)));
+        }
+        let key = entity_path(&self.config, self.entity, &self.epoch_id);
+        let current = stamps(entity_paths(&self.config, self.entity, &self.epoch_id));
+
+        let cached = {
+            let cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+            cache
+                .get(&key)
+                .filter(|entry| entry.stamps == current)
+                .and_then(|entry| entry.index.clone().downcast::<EntityIndex<T>>().ok())
+        };
+        if let Some(index) = cached {
+            // Keep read tracking accurate for callers that rely on it
+            for (path, _) in &current {
+                note_read(path);
+            }
+            return Ok(index);
+        }
+
+        let entities =
+            JsonlReader::<T>::for_entity(&self.config, self.entity, &self.epoch_id).read_all()?;
+        let index = Arc::new(EntityIndex::build(entities));
+        debug!(
+            "Indexed {} {:?} records for epoch {}",
+            index.len(),
+            self.entity,
+            self.epoch_id
+        );
+        cache().lock().unwrap_or_else(|e| e.into_inner()).insert(
+            key,
+            Entry {
+                stamps: current,
+                index: index.clone(),
+            },
+        );
+        Ok(index)
+    }
+
+    /// The newest record with this ID. Database backends find it through
+    /// their ID index instead of reading the epoch.
+    pub fn get(&self, id: &str) -> Result<Option<T>, StorageError> {
+        match self.store_lookup(|store, key| store.find_by_id(key, id))? {
+            Some(found) => Ok(found.get(id).cloned()),
+            None => Ok(self.index()?.get(id).cloned()),
+        }
+    }
+
+    /// Deduplicated records belonging to an event, through the event index
+    /// on database backends.
+    pub fn for_event(&self, event_id: &str) -> Result<Vec<T>, StorageError> {
+        match self.store_lookup(|store, key| store.find_by_event(key, event_id))? {
+            Some(found) => Ok(found.all().to_vec()),
+            None => Ok(self
+                .index()?
+                .for_event(event_id)
+                .into_iter()
+                .cloned()
+                .collect()),
+        }
+    }
+
+    /// Records `lookup` finds under each of this epoch file's store keys,
+    /// or `None` for the JSONL backend.
+    fn store_lookup(
+        &self,
+        lookup: impl Fn(&dyn Store, &str) -> Result<Vec<String>, StorageError>,
+    ) -> Result<Option<EntityIndex<T>>, StorageError> {
+        if self.config.backend == StorageBackend::Jsonl {
+            return Ok(None);
+        }
+        let store = store::open(&self.config, self.config.backend)?;
+        let mut entities = Vec::new();
+        for key in store::entity_keys(store.as_ref(), self.entity, &self.epoch_id)? {
+            let lines = lookup(store.as_ref(), &key)?;
+            entities.extend(parse_lines(lines.iter().map(String::as_str), &key));
+        }
+        Ok(Some(EntityIndex::build(entities)))
+    }
+
+    /// Deduplicated records, cloned out of the index.
+    pub fn read_all(&self) -> Result<Vec<T>, StorageError> {
+        Ok(self.index()?.all().to_vec())
+    }
+
+    /// Check if any covered file exists.
+    pub fn exists(&self) -> bool {
+        !entity_paths(&self.config, self.entity, &self.epoch_id).is_empty()
+    }
+}
+
+/// Build the indexes routes use for every epoch, so the first requests
+/// after startup don't pay for parsing. Returns the number of records.
+pub fn warm(config: &StorageConfig) -> Result<usize, StorageError> {
+    fn load<T: Indexed>(
+        config: &StorageConfig,
+        entity: EntityType,
+        epoch: &str,
+    ) -> Result<usize, StorageError> {
+        Ok(IndexedReader::<T>::for_entity(config, entity, epoch)
+            .index()?
+            .len())
+    }
+
+    let mut records = 0;
+    let epochs = list_epochs(config)?;
+    for epoch in &epochs {
+        records += load::<Event>(config, EntityType::Event, epoch)?;
+        records += load::<Placement>(config, EntityType::Placement, epoch)?;
+        records += load::<ArmyList>(config, EntityType::ArmyList, epoch)?;
+        records += load::<Pairing>(config, EntityType::Pairing, epoch)?;
+    }
+    info!("Indexed {} records across {} epochs", records, epochs.len());
+    Ok(records)
+}
+
+/// Drop every cached index under this storage root.
+pub fn invalidate_indexes(config: &StorageConfig) {
+    let root = config.normalized_dir();
+    cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .retain(|path, _| !path.starts_with(&root));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::JsonlWriter;
+
+    fn placement(event: &str, rank: u32, player: &str) -> Placement {
+        Placement::new(
+            event.into(),
+            "current".into(),
+            rank,
+            player.to_string(),
+            "Aeldari".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_index_lookups_and_invalidation() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = StorageConfig::new(tmp.path().to_path_buf());
+        let writer = JsonlWriter::for_entity(&config, EntityType::Placement, "current");
+        let first = placement("e1", 1, "Jane  Doe");
+        writer
+            .write_all(&[
+                first.clone(),
+                first.clone(),
+                placement("e1", 2, "Bob"),
+                placement("e2", 1, "jane doe"),
+            ])
+            .unwrap();
+
+        let reader =
+            IndexedReader::<Placement>::for_entity(&config, EntityType::Placement, "current");
+        let index = reader.index().unwrap();
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.for_event("e1").len(), 2);
+        assert_eq!(index.for_player("JANE DOE").len(), 2);
+        assert!(index.get(first.id.as_str()).is_some());
+
+        // Served from cache until the file changes
+        assert!(Arc::ptr_eq(&index, &reader.index().unwrap()));
+        writer.append(&placement("e3", 1, "Cara")).unwrap();
+        let index = reader.index().unwrap();
+        assert_eq!(index.len(), 4);
+        assert_eq!(reader.read_all().unwrap().len(), 4);
+
+        invalidate_indexes(&config);
+        assert!(!Arc::ptr_eq(&index, &reader.index().unwrap()));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_lookups_use_store_indexes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = StorageConfig::new(tmp.path().to_path_buf())
+            .with_backend(StorageBackend::Sqlite)
+            .with_source_partitioning(true);
+        let first = placement("e1", 1, "Jane Doe");
+        let mut newer = first.clone();
+        newer.rank = 2;
+        newer.touch();
+        JsonlWriter::for_entity(&config, EntityType::Placement, "current")
+            .write_all(&[first.clone(), placement("e2", 1, "Bob")])
+            .unwrap();
+        JsonlWriter::for_source(&config, EntityType::Placement, "current", "bcp")
+            .write_all(&[newer])
+            .unwrap();
+
+        let reader =
+            IndexedReader::<Placement>::for_entity(&config, EntityType::Placement, "current");
+        assert_eq!(reader.get(first.id.as_str()).unwrap().unwrap().rank, 2);
+        assert!(reader.get("missing").unwrap().is_none());
+        assert_eq!(reader.for_event("e1").unwrap().len(), 1);
+        assert_eq!(reader.for_event("e2").unwrap()[0].player_name, "Bob");
+    }
+}
@@ -2,15 +2,23 @@
 //!
 //! JSONL is the source of truth for all normalized data.
 //! Each line is a valid JSON object representing one entity.
+//!
+//! Writes to a file are serialized per path within the process, so tasks
+//! appending to the same file concurrently never interleave partial lines.
+//! Full rewrites go to a temp file that is renamed over the original, so a
+//! crash mid-write leaves the previous version intact.
 
+use std::collections::HashMap;
 use std::fs::{self, File, OpenOptions};
-use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::io::{BufRead, BufReader, Cursor, Write};
 use std::marker::PhantomData;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use serde::{de::DeserializeOwned, Serialize};
 use tracing::{debug, info, warn};
 
+use super::store::{self, split_lines, StorageBackend};
 use super::{StorageConfig, StorageError};
 
 /// Entity types for JSONL storage.
@@ -22,6 +30,8 @@ pub enum EntityType {
     ArmyList,
     ReviewItem,
     Pairing,
+    BracketMatch,
+    PointsChange,
 }
 
 impl EntityType {
@@ -34,13 +44,98 @@ impl EntityType {
             EntityType::ArmyList => "army_lists.jsonl",
             EntityType::ReviewItem => "review_items.jsonl",
             EntityType::Pairing => "pairings.jsonl",
+            EntityType::BracketMatch => "bracket_matches.jsonl",
+            EntityType::PointsChange => "points_changes.jsonl",
         }
     }
 }
 
+/// Per-file write locks, keyed by absolute path.
+static FILE_LOCKS: OnceLock<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> = OnceLock::new();
+
+/// The process-wide lock for `path`.
+///
+/// Held only around the blocking file operation itself (never across an
+/// `.await`), so it is safe to take from concurrent tokio tasks.
+fn file_lock(path: &Path) -> Arc<Mutex<()>> {
+    let key = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut locks = FILE_LOCKS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    locks.entry(key).or_default().clone()
+}
+
+/// Serialize entities to newline-terminated JSON lines.
+fn to_lines<T: Serialize>(entities: &[T]) -> Result<String, StorageError> {
+    let mut buf = String::new();
+    for entity in entities {
+        buf.push_str(&serde_json::to_string(entity)?);
+        buf.push('\n');
+    }
+    Ok(buf)
+}
+
+/// Path of the `generation`th backup of `path`: `x.jsonl.bak` for the
+/// newest, then `x.jsonl.bak.2`, `x.jsonl.bak.3`, ...
+pub fn backup_path(path: &Path, generation: usize) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let suffix = if generation <= 1 {
+        ".bak".to_string()
+    } else {
+        format!(".bak.{}", generation)
+    };
+    path.with_file_name(format!("{}{}", name, suffix))
+}
+
+/// Shift `path`'s backups down one generation and make the current file
+/// the newest backup, keeping at most `keep`.
+fn rotate_backups(path: &Path, keep: usize) -> std::io::Result<()> {
+    if keep == 0 || !path.exists() {
+        return Ok(());
+    }
+    for generation in (1..keep).rev() {
+        let from = backup_path(path, generation);
+        if from.exists() {
+            fs::rename(&from, backup_path(path, generation + 1))?;
+        }
+    }
+    let newest = backup_path(path, 1);
+    if newest.exists() {
+        fs::remove_file(&newest)?;
+    }
+    // Hard link when possible: the original stays in place until the rename
+    if fs::hard_link(path, &newest).is_err() {
+        fs::copy(path, &newest)?;
+    }
+    Ok(())
+}
+
+/// An open store and a file's key in it.
+type BackingStore = (Arc<dyn store::Store>, String);
+
+/// Records per store key.
+type KeyedLines = Vec<(String, Vec<String>)>;
+
+/// Store key for a file under `normalized/`: its relative path.
+fn store_key(config: &StorageConfig, path: &Path) -> String {
+    path.strip_prefix(config.normalized_dir())
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
 /// JSONL file writer.
+///
+/// Writers created from a [`StorageConfig`] with a non-JSONL backend write
+/// to that [`store`] under the file's relative path instead.
 pub struct JsonlWriter<T> {
     path: PathBuf,
+    backups: usize,
+    store: Option<StorageConfig>,
     _marker: PhantomData<T>,
 }
 
@@ -49,6 +144,8 @@ impl<T: Serialize> JsonlWriter<T> {
     pub fn new(path: PathBuf) -> Self {
         Self {
             path,
+            backups: 0,
+            store: None,
             _marker: PhantomData,
         }
     }
@@ -59,7 +156,51 @@ impl<T: Serialize> JsonlWriter<T> {
             .normalized_dir()
             .join(epoch_id)
             .join(entity.filename());
-        Self::new(path)
+        Self::new(path).with_config(config)
+    }
+
+    /// Apply the backup policy and backend from `config`.
+    fn with_config(mut self, config: &StorageConfig) -> Self {
+        self.backups = config.backup_generations;
+        if config.backend != StorageBackend::Jsonl {
+            self.store = Some(config.clone());
+        }
+        self
+    }
+
+    /// The configured store and this file's key in it, for non-JSONL backends.
+    fn backing_store(&self) -> Result<Option<BackingStore>, StorageError> {
+        match &self.store {
+            Some(config) => Ok(Some((
+                store::open(config, config.backend)?,
+                store_key(config, &self.path),
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    /// Builder method to keep `generations` `.bak` copies of the file,
+    /// rotated on every `write_all`.
+    pub fn with_backups(mut self, generations: usize) -> Self {
+        self.backups = generations;
+        self
+    }
+
+    /// Create a writer for new data from one source.
+    ///
+    /// Writes to the source partition when `partition_by_source` is enabled,
+    /// otherwise to the shared epoch file.
+    pub fn for_source(
+        config: &StorageConfig,
+        entity: EntityType,
+        epoch_id: &str,
+        source: &str,
+    ) -> Self {
+        if config.partition_by_source {
+            Self::new(source_entity_path(config, entity, epoch_id, source)).with_config(config)
+        } else {
+            Self::for_entity(config, entity, epoch_id)
+        }
     }
 
     /// Ensure the parent directory exists.
@@ -70,76 +211,105 @@ impl<T: Serialize> JsonlWriter<T> {
         Ok(())
     }
 
-    /// Append a single entity to the file.
-    pub fn append(&self, entity: &T) -> Result<(), StorageError> {
+    /// Append pre-serialized lines under the file lock, in a single write.
+    fn append_lines(&self, lines: &str) -> Result<(), StorageError> {
+        if let Some((store, key)) = self.backing_store()? {
+            return store.append_lines(&key, &split_lines(lines));
+        }
         self.ensure_dir()?;
 
-        let file = OpenOptions::new()
+        let lock = file_lock(&self.path);
+        let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(&self.path)?;
+        file.write_all(lines.as_bytes())?;
+        file.flush()?;
+        Ok(())
+    }
 
-        let mut writer = BufWriter::new(file);
-        let json = serde_json::to_string(entity)?;
-        writeln!(writer, "{}", json)?;
-        writer.flush()?;
+    /// Append a single entity to the file.
+    pub fn append(&self, entity: &T) -> Result<(), StorageError> {
+        self.append_lines(&to_lines(std::slice::from_ref(entity))?)?;
 
         debug!("Appended entity to {:?}", self.path);
         Ok(())
     }
 
     /// Append multiple entities to the file.
+    ///
+    /// The batch is written as one unit: concurrent appends to the same file
+    /// land before or after it, never inside it.
     pub fn append_batch(&self, entities: &[T]) -> Result<usize, StorageError> {
         if entities.is_empty() {
             return Ok(0);
         }
 
-        self.ensure_dir()?;
-
-        let file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&self.path)?;
-
-        let mut writer = BufWriter::new(file);
-        let mut count = 0;
-
-        for entity in entities {
-            let json = serde_json::to_string(entity)?;
-            writeln!(writer, "{}", json)?;
-            count += 1;
-        }
+        self.append_lines(&to_lines(entities)?)?;
+        info!("Appended {} entities to {:?}", entities.len(), self.path);
 
-        writer.flush()?;
-        info!("Appended {} entities to {:?}", count, self.path);
-
-        Ok(count)
+        Ok(entities.len())
     }
 
     /// Write entities, replacing the entire file.
+    ///
+    /// The new contents are written and synced to a temp file next to the
+    /// original, which is then renamed over it. Readers see the old file or
+    /// the new one, never a partial write.
     pub fn write_all(&self, entities: &[T]) -> Result<usize, StorageError> {
-        self.ensure_dir()?;
+        self.replace_content(&to_lines(entities)?)?;
+        info!("Wrote {} entities to {:?}", entities.len(), self.path);
 
-        let file = File::create(&self.path)?;
-        let mut writer = BufWriter::new(file);
-        let mut count = 0;
+        Ok(entities.len())
+    }
 
-        for entity in entities {
-            let json = serde_json::to_string(entity)?;
-            writeln!(writer, "{}", json)?;
-            count += 1;
+    /// Replace the file with pre-serialized lines.
+    pub(crate) fn write_lines(&self, lines: &[String]) -> Result<(), StorageError> {
+        let mut content = String::new();
+        for line in lines {
+            content.push_str(line);
+            content.push('\n');
         }
+        self.replace_content(&content)
+    }
 
-        writer.flush()?;
-        info!("Wrote {} entities to {:?}", count, self.path);
+    fn replace_content(&self, lines: &str) -> Result<(), StorageError> {
+        if let Some((store, key)) = self.backing_store()? {
+            return store.replace_lines(&key, &split_lines(lines));
+        }
+        self.ensure_dir()?;
 
-        Ok(count)
+        let lock = file_lock(&self.path);
+        let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+        let tmp_path = self.path.with_file_name(format!(
+            ".{}.tmp",
+            self.path
+                .file_name()
+                .map(|n| n.to_string_lossy())
+                .unwrap_or_default()
+        ));
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(lines.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+
+        rotate_backups(&self.path, self.backups)?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
     }
 }
 
 /// JSONL file reader.
+///
+/// Readers created with `for_entity` merge the shared epoch file with any
+/// source partitions, shared file first, so `merge_by_id` keeps legacy
+/// records over partitioned copies. With a non-JSONL backend they read the
+/// same keys from the configured [`store`].
 pub struct JsonlReader<T> {
     path: PathBuf,
+    partitions: Vec<PathBuf>,
+    store: Option<StorageConfig>,
     _marker: PhantomData<T>,
 }
 
@@ -148,6 +318,8 @@ impl<T: DeserializeOwned> JsonlReader<T> {
     pub fn new(path: PathBuf) -> Self {
         Self {
             path,
+            partitions: Vec::new(),
+            store: None,
             _marker: PhantomData,
         }
     }
@@ -158,45 +330,87 @@ impl<T: DeserializeOwned> JsonlReader<T> {
             .normalized_dir()
             .join(epoch_id)
             .join(entity.filename());
-        Self::new(path)
+        let partitions = list_source_partitions(config, epoch_id)
+            .into_iter()
+            .map(|source| source_entity_path(config, entity, epoch_id, &source))
+            .filter(|p| p.exists())
+            .collect();
+        let store = (config.backend != StorageBackend::Jsonl).then(|| config.clone());
+        Self {
+            path,
+            partitions,
+            store,
+            _marker: PhantomData,
+        }
+    }
+
+    /// All files this reader covers, shared file first.
+    fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        std::iter::once(&self.path).chain(self.partitions.iter())
+    }
+
+    /// Records under each key this reader covers in the configured store,
+    /// shared key first; `None` for the JSONL backend.
+    fn store_lines(&self) -> Result<Option<KeyedLines>, StorageError> {
+        let Some(config) = &self.store else {
+            return Ok(None);
+        };
+        let store = store::open(config, config.backend)?;
+        let shared = store_key(config, &self.path);
+        // Partition keys are `<epoch>/<source>/<file>` next to `<epoch>/<file>`
+        let (epoch, file) = shared.rsplit_once('/').unwrap_or(("", shared.as_str()));
+        let partitions = store.keys()?.into_iter().filter(|key| {
+            key.strip_prefix(epoch)
+                .and_then(|rest| rest.strip_prefix('/'))
+                .and_then(|rest| rest.strip_suffix(file))
+                .is_some_and(|source| source.matches('/').count() == 1 && source.len() > 1)
+        });
+        std::iter::once(shared.clone())
+            .chain(partitions)
+            .map(|key| Ok((key.clone(), store.read_lines(&key)?)))
+            .collect::<Result<Vec<_>, StorageError>>()
+            .map(Some)
     }
 
     /// Check if the file exists.
     pub fn exists(&self) -> bool {
-        self.path.exists()
+        match self.store_lines() {
+            Ok(Some(keys)) => keys.iter().any(|(_, lines)| !lines.is_empty()),
+            _ => self.paths().any(|p| p.exists()),
+        }
     }
 
     /// Read all entities from the file.
     pub fn read_all(&self) -> Result<Vec<T>, StorageError> {
-        if !self.path.exists() {
-            return Ok(Vec::new());
-        }
-
-        let file = File::open(&self.path)?;
-        let reader = BufReader::new(file);
         let mut entities = Vec::new();
-        let mut line_num = 0;
-
-        for line in reader.lines() {
-            line_num += 1;
-            let line = line?;
-
-            if line.trim().is_empty() {
-                continue;
+        if let Some(keys) = self.store_lines()? {
+            for (key, lines) in keys {
+                entities.extend(parse_lines(lines.iter().map(String::as_str), &key));
             }
+            return Ok(entities);
+        }
+        for path in self.paths() {
+            entities.extend(Self::read_file(path)?);
+        }
+        Ok(entities)
+    }
 
-            match serde_json::from_str(&line) {
-                Ok(entity) => entities.push(entity),
-                Err(e) => {
-                    warn!(
-                        "Failed to parse line {} in {:?}: {}",
-                        line_num, self.path, e
-                    );
-                }
-            }
+    fn read_file(path: &PathBuf) -> Result<Vec<T>, StorageError> {
+        if !path.exists() {
+            return Ok(Vec::new());
         }
 
-        debug!("Read {} entities from {:?}", entities.len(), self.path);
+        super::note_read(path);
+        // Don't observe a half-written append from another task
+        let lock = file_lock(path);
+        let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+        let file = File::open(path)?;
+        let lines = BufReader::new(file)
+            .lines()
+            .collect::<Result<Vec<_>, _>>()?;
+        let entities = parse_lines(lines.iter().map(String::as_str), &path.display());
+
+        debug!("Read {} entities from {:?}", entities.len(), path);
         Ok(entities)
     }
 
@@ -211,28 +425,40 @@ impl<T: DeserializeOwned> JsonlReader<T> {
 
     /// Count entities in the file.
     pub fn count(&self) -> Result<usize, StorageError> {
-        if !self.path.exists() {
-            return Ok(0);
+        if let Some(keys) = self.store_lines()? {
+            return Ok(keys.iter().map(|(_, lines)| lines.len()).sum());
+        }
+        let mut count = 0;
+        for path in self.paths().filter(|p| p.exists()) {
+            let file = File::open(path)?;
+            let reader = BufReader::new(file);
+            count += reader.lines().filter(|l| l.is_ok()).count();
         }
-
-        let file = File::open(&self.path)?;
-        let reader = BufReader::new(file);
-        let count = reader.lines().filter(|l| l.is_ok()).count();
 
         Ok(count)
     }
 
     /// Create an iterator over the file.
     pub fn iter(&self) -> Result<JsonlIterator<T>, StorageError> {
-        if !self.path.exists() {
+        let mut files: Vec<Box<dyn BufRead + Send>> = Vec::new();
+        if let Some(keys) = self.store_lines()? {
+            for (_, lines) in keys.into_iter().filter(|(_, l)| !l.is_empty()) {
+                files.push(Box::new(Cursor::new(lines.join("\n").into_bytes())));
+            }
+        } else {
+            for path in self.paths().filter(|p| p.exists()) {
+                super::note_read(path);
+                files.push(Box::new(BufReader::new(File::open(path)?)));
+            }
+        }
+        if files.is_empty() {
             return Err(StorageError::PathNotFound(self.path.clone()));
         }
 
-        let file = File::open(&self.path)?;
-        let reader = BufReader::new(file);
-
+        let mut files = files.into_iter();
         Ok(JsonlIterator {
-            reader,
+            reader: files.next().unwrap(),
+            remaining: files,
             _marker: PhantomData,
         })
     }
@@ -240,7 +466,8 @@ impl<T: DeserializeOwned> JsonlReader<T> {
 
 /// Iterator over JSONL file entries.
 pub struct JsonlIterator<T> {
-    reader: BufReader<File>,
+    reader: Box<dyn BufRead + Send>,
+    remaining: std::vec::IntoIter<Box<dyn BufRead + Send>>,
     _marker: PhantomData<T>,
 }
 
@@ -253,7 +480,10 @@ impl<T: DeserializeOwned> Iterator for JsonlIterator<T> {
         loop {
             line.clear();
             match self.reader.read_line(&mut line) {
-                Ok(0) => return None, // EOF
+                Ok(0) => match self.remaining.next() {
+                    Some(next) => self.reader = next,
+                    None => return None, // EOF
+                },
                 Ok(_) => {
                     if line.trim().is_empty() {
                         continue;
@@ -266,14 +496,44 @@ impl<T: DeserializeOwned> Iterator for JsonlIterator<T> {
     }
 }
 
-/// Find all epoch directories.
+/// Parse JSON lines, skipping blank lines and logging unparseable ones.
+pub(super) fn parse_lines<'a, T: DeserializeOwned>(
+    lines: impl Iterator<Item = &'a str>,
+    source: &dyn std::fmt::Display,
+) -> Vec<T> {
+    let mut entities = Vec::new();
+    for (i, line) in lines.enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(line) {
+            Ok(entity) => entities.push(entity),
+            Err(e) => {
+                warn!("Failed to parse line {} in {}: {}", i + 1, source, e);
+            }
+        }
+    }
+    entities
+}
+
+/// Find all epoch directories (and, with a non-JSONL backend, every epoch
+/// holding records in the store).
 pub fn list_epochs(config: &StorageConfig) -> Result<Vec<String>, StorageError> {
+    let mut epochs = Vec::new();
+    if config.backend != StorageBackend::Jsonl {
+        for key in store::open(config, config.backend)?.keys()? {
+            if let Some((epoch, _)) = key.split_once('/') {
+                epochs.push(epoch.to_string());
+            }
+        }
+    }
     let dir = config.normalized_dir();
     if !dir.exists() {
-        return Ok(Vec::new());
+        epochs.sort();
+        epochs.dedup();
+        return Ok(epochs);
     }
 
-    let mut epochs = Vec::new();
     for entry in fs::read_dir(&dir)? {
         let entry = entry?;
         if entry.file_type()?.is_dir() {
@@ -284,6 +544,7 @@ pub fn list_epochs(config: &StorageConfig) -> Result<Vec<String>, StorageError>
     }
 
     epochs.sort();
+    epochs.dedup();
     Ok(epochs)
 }
 
@@ -295,6 +556,70 @@ pub fn entity_path(config: &StorageConfig, entity: EntityType, epoch_id: &str) -
         .join(entity.filename())
 }
 
+/// Get the path for one source's partition of an epoch's entity file.
+pub fn source_entity_path(
+    config: &StorageConfig,
+    entity: EntityType,
+    epoch_id: &str,
+    source: &str,
+) -> PathBuf {
+    config
+        .normalized_dir()
+        .join(epoch_id)
+        .join(source_dir_name(source))
+        .join(entity.filename())
+}
+
+/// Every existing file holding an epoch's entities: the shared file, then
+/// each source partition.
+pub fn entity_paths(config: &StorageConfig, entity: EntityType, epoch_id: &str) -> Vec<PathBuf> {
+    std::iter::once(entity_path(config, entity, epoch_id))
+        .chain(
+            list_source_partitions(config, epoch_id)
+                .into_iter()
+                .map(|source| source_entity_path(config, entity, epoch_id, &source)),
+        )
+        .filter(|p| p.exists())
+        .collect()
+}
+
+/// Find the source partitions present in an epoch directory.
+pub fn list_source_partitions(config: &StorageConfig, epoch_id: &str) -> Vec<String> {
+    let dir = config.normalized_dir().join(epoch_id);
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut sources: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|e| e.file_name().to_str().map(|s| s.to_string()))
+        .collect();
+    sources.sort();
+    sources
+}
+
+/// Filesystem-safe directory name for a source.
+fn source_dir_name(source: &str) -> String {
+    let name: String = source
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    if name.is_empty() {
+        "unknown".to_string()
+    } else {
+        name
+    }
+}
+
 /// Read significant events from the global file.
 pub fn read_significant_events(
     config: &StorageConfig,
@@ -310,7 +635,45 @@ pub fn write_significant_events(
 ) -> Result<usize, StorageError> {
     events.sort_by_key(|e| e.date);
     let writer = JsonlWriter::new(config.significant_events_path());
-    writer.write_all(events)
+    let written = writer.write_all(events)?;
+    super::cache::invalidate_cache(config);
+    Ok(written)
+}
+
+/// Read series definitions, falling back to the built-in defaults
+/// when none have been configured.
+pub fn read_series(config: &StorageConfig) -> Result<Vec<crate::models::Series>, StorageError> {
+    let reader = JsonlReader::new(config.series_path());
+    if !reader.exists() {
+        return Ok(crate::models::Series::defaults());
+    }
+    reader.read_all()
+}
+
+/// Write series definitions to the global file.
+pub fn write_series(
+    config: &StorageConfig,
+    series: &[crate::models::Series],
+) -> Result<usize, StorageError> {
+    let writer = JsonlWriter::new(config.series_path());
+    writer.write_all(series)
+}
+
+/// Read all review queue items.
+pub fn read_review_items(
+    config: &StorageConfig,
+) -> Result<Vec<crate::models::ReviewQueueItem>, StorageError> {
+    let reader = JsonlReader::new(config.review_items_path());
+    reader.read_all()
+}
+
+/// Write review queue items, replacing the queue file.
+pub fn write_review_items(
+    config: &StorageConfig,
+    items: &[crate::models::ReviewQueueItem],
+) -> Result<usize, StorageError> {
+    let writer = JsonlWriter::new(config.review_items_path());
+    writer.write_all(items)
 }
 
 #[cfg(test)]
@@ -330,6 +693,38 @@ mod tests {
         StorageConfig::new(temp_dir.path().to_path_buf())
     }
 
+    #[test]
+    fn test_write_all_is_atomic_and_rotates_backups() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test.jsonl");
+        let entity = |value| TestEntity {
+            id: "1".to_string(),
+            name: "Test".to_string(),
+            value,
+        };
+        let writer = JsonlWriter::new(path.clone()).with_backups(2);
+        for value in 1..=4 {
+            writer.write_all(&[entity(value)]).unwrap();
+        }
+
+        let read = |p: &Path| {
+            JsonlReader::<TestEntity>::new(p.to_path_buf())
+                .read_all()
+                .unwrap()
+        };
+        assert_eq!(read(&path)[0].value, 4);
+        assert_eq!(read(&backup_path(&path, 1))[0].value, 3);
+        assert_eq!(read(&backup_path(&path, 2))[0].value, 2);
+        assert!(!backup_path(&path, 3).exists());
+        // No temp file left behind
+        let files: Vec<_> = fs::read_dir(temp_dir.path()).unwrap().collect();
+        assert_eq!(files.len(), 3);
+        assert_eq!(
+            backup_path(&path, 2).file_name().unwrap(),
+            "test.jsonl.bak.2"
+        );
+    }
+
     #[test]
     fn test_jsonl_write_and_read() {
         let temp_dir = TempDir::new().unwrap();
@@ -723,6 +1118,26 @@ not-valid-json
         assert!(read[0].date <= read[1].date);
     }
 
+    #[test]
+    fn test_read_series_defaults_and_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = test_config(&temp_dir);
+
+        // No file yet: built-in defaults
+        let defaults = read_series(&config).unwrap();
+        assert!(defaults.iter().any(|s| s.name == "Las Vegas Open"));
+
+        let custom = vec![crate::models::Series::new(
+            "Brighton Doubles".to_string(),
+            vec!["brighton doubles".to_string()],
+        )];
+        write_series(&config, &custom).unwrap();
+
+        let read = read_series(&config).unwrap();
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].name, "Brighton Doubles");
+    }
+
     #[test]
     fn test_list_epochs_empty_dir() {
         let temp_dir = TempDir::new().unwrap();
@@ -748,4 +1163,99 @@ not-valid-json
         );
         assert_eq!(EntityType::ReviewItem.filename(), "review_items.jsonl");
     }
+
+    #[test]
+    fn test_source_partitioned_write_and_merged_read() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = test_config(&temp_dir).with_source_partitioning(true);
+
+        // Legacy shared file
+        JsonlWriter::for_entity(&config, EntityType::Event, "current")
+            .write_all(&[TestEntity {
+                id: "1".to_string(),
+                name: "legacy".to_string(),
+                value: 1,
+            }])
+            .unwrap();
+
+        // New data lands in the source partition
+        let writer = JsonlWriter::for_source(&config, EntityType::Event, "current", "BCP");
+        writer
+            .append_batch(&[
+                TestEntity {
+                    id: "1".to_string(),
+                    name: "partitioned copy".to_string(),
+                    value: 1,
+                },
+                TestEntity {
+                    id: "2".to_string(),
+                    name: "bcp".to_string(),
+                    value: 2,
+                },
+            ])
+            .unwrap();
+        assert!(temp_dir
+            .path()
+            .join("normalized/current/bcp/events.jsonl")
+            .exists());
+        assert_eq!(list_source_partitions(&config, "current"), vec!["bcp"]);
+        assert_eq!(entity_paths(&config, EntityType::Event, "current").len(), 2);
+
+        let reader = JsonlReader::<TestEntity>::for_entity(&config, EntityType::Event, "current");
+        let merged = reader.read_all().unwrap();
+        assert_eq!(merged.len(), 3);
+        // Shared file is read first, so it wins on dedup
+        assert_eq!(merged[0].name, "legacy");
+        assert_eq!(reader.count().unwrap(), 3);
+        assert_eq!(reader.iter().unwrap().count(), 3);
+
+        // Without partitioning, writes go to the shared file
+        let plain = test_config(&temp_dir);
+        let writer =
+            JsonlWriter::<TestEntity>::for_source(&plain, EntityType::Event, "current", "bcp");
+        assert_eq!(
+            writer.path,
+            entity_path(&plain, EntityType::Event, "current")
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_appends_do_not_interleave() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("concurrent.jsonl");
+
+        let tasks: Vec<_> = (0..8)
+            .map(|task| {
+                let path = path.clone();
+                tokio::spawn(async move {
+                    let writer: JsonlWriter<TestEntity> = JsonlWriter::new(path);
+                    for batch in 0..25 {
+                        let entities: Vec<TestEntity> = (0..20)
+                            .map(|i| TestEntity {
+                                id: format!("{}-{}-{}", task, batch, i),
+                                // Long enough that a batch spans several buffered writes
+                                name: "x".repeat(512),
+                                value: i,
+                            })
+                            .collect();
+                        writer.append_batch(&entities).unwrap();
+                        tokio::task::yield_now().await;
+                    }
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 8 * 25 * 20);
+        assert!(lines
+            .iter()
+            .all(|l| serde_json::from_str::<TestEntity>(l).is_ok()));
+
+        let reader: JsonlReader<TestEntity> = JsonlReader::new(path);
+        assert_eq!(reader.read_all().unwrap().len(), 8 * 25 * 20);
+    }
 }
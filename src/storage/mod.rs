@@ -3,20 +3,67 @@
 //! Handles reading and writing to the local data lake:
 //! - Raw content (HTML, PDFs)
 //! - Normalized JSONL files
-//! - Parquet analytics files
+//! - Parquet analytics files (and ad-hoc SQL over them)
 //! - State/cursor files
+//! - Snapshots for backup/restore
+//! - In-memory indexes for API reads
+//! - Integrity validation
+//! - Local overrides (faction groups, aliases, exclusions)
 
+pub mod backup;
+pub mod cache;
+pub mod compact;
+pub mod duckdb;
+pub mod index;
 pub mod jsonl;
+pub mod overrides;
 pub mod parquet;
+pub mod store;
+pub mod validate;
 
+pub use cache::{
+    cached_epoch_mapper, cached_significant_events, current_epoch_id, invalidate_cache,
+};
+pub use index::{invalidate_indexes, EntityIndex, Indexed, IndexedReader};
 pub use jsonl::{
-    read_significant_events, write_significant_events, EntityType, JsonlReader, JsonlWriter,
+    backup_path, read_review_items, read_series, read_significant_events, write_review_items,
+    write_series, write_significant_events, EntityType, JsonlReader, JsonlWriter,
 };
+pub use overrides::{Excludable, Overrides, OverridesError, OVERRIDES_FILE};
 pub use parquet::{ParquetReader, ParquetWriter, TableType};
+pub use store::{StorageBackend, Store};
 
-use std::path::PathBuf;
+use std::cell::RefCell;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use thiserror::Error;
 
+tokio::task_local! {
+    static FILES_READ: RefCell<Vec<PathBuf>>;
+}
+
+/// Run `fut`, collecting the data files it reads on this task (reads
+/// inside `spawn_blocking` or other tasks aren't seen).
+pub async fn track_reads<F: Future>(fut: F) -> (F::Output, Vec<PathBuf>) {
+    FILES_READ
+        .scope(RefCell::new(Vec::new()), async {
+            let output = fut.await;
+            (output, FILES_READ.with(|files| files.take()))
+        })
+        .await
+}
+
+/// Record a file read for [`track_reads`]; a no-op outside it.
+pub(crate) fn note_read(path: &Path) {
+    let _ = FILES_READ.try_with(|files| {
+        let mut files = files.borrow_mut();
+        if !files.iter().any(|f| f == path) {
+            files.push(path.to_path_buf());
+        }
+    });
+}
+
 /// Errors that can occur during storage operations.
 #[derive(Debug, Error)]
 pub enum StorageError {
@@ -31,17 +78,66 @@ pub enum StorageError {
 
     #[error("Invalid path: {0}")]
     InvalidPath(String),
+
+    #[error("Snapshot not found: {0}")]
+    SnapshotNotFound(String),
+
+    #[error("Storage backend error: {0}")]
+    Backend(String),
 }
 
 /// Configuration for storage paths.
 #[derive(Debug, Clone)]
 pub struct StorageConfig {
     pub data_dir: PathBuf,
+
+    /// Write new normalized data to `normalized/<epoch>/<source>/` instead of
+    /// the shared epoch files. Both layouts are always readable.
+    pub partition_by_source: bool,
+
+    /// `.bak` generations JSONL writers keep when rewriting a file (0 = none)
+    pub backup_generations: usize,
+
+    /// Where per-epoch entity records live
+    pub backend: StorageBackend,
+
+    /// Local house rules from `overrides.toml`
+    pub overrides: Arc<Overrides>,
 }
 
 impl StorageConfig {
     pub fn new(data_dir: PathBuf) -> Self {
-        Self { data_dir }
+        Self {
+            data_dir,
+            partition_by_source: false,
+            backup_generations: 0,
+            backend: StorageBackend::Jsonl,
+            overrides: Arc::default(),
+        }
+    }
+
+    /// Builder method to apply local overrides.
+    pub fn with_overrides(mut self, overrides: Arc<Overrides>) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Builder method to keep per-epoch records in another backend.
+    pub fn with_backend(mut self, backend: StorageBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Builder method to keep `.bak` copies of rewritten JSONL files.
+    pub fn with_backups(mut self, generations: usize) -> Self {
+        self.backup_generations = generations;
+        self
+    }
+
+    /// Builder method to enable source-partitioned writes.
+    pub fn with_source_partitioning(mut self, enabled: bool) -> Self {
+        self.partition_by_source = enabled;
+        self
     }
 
     pub fn raw_dir(&self) -> PathBuf {
@@ -68,16 +164,31 @@ impl StorageConfig {
         self.data_dir.join("logs")
     }
 
+    pub fn backups_dir(&self) -> PathBuf {
+        self.data_dir.join("backups")
+    }
+
     pub fn review_queue_dir(&self) -> PathBuf {
         self.data_dir.join("review_queue")
     }
 
+    /// Path to the review queue file (not per-epoch).
+    pub fn review_items_path(&self) -> PathBuf {
+        self.review_queue_dir()
+            .join(jsonl::EntityType::ReviewItem.filename())
+    }
+
     /// Path to the global significant_events file (not per-epoch).
     pub fn significant_events_path(&self) -> PathBuf {
         self.data_dir
             .join("normalized")
             .join("significant_events.jsonl")
     }
+
+    /// Path to the global series definitions file.
+    pub fn series_path(&self) -> PathBuf {
+        self.data_dir.join("normalized").join("series.jsonl")
+    }
 }
 
 impl Default for StorageConfig {
@@ -101,6 +212,28 @@ mod tests {
         assert_eq!(config.state_dir(), PathBuf::from("/data/state"));
     }
 
+    #[tokio::test]
+    async fn test_track_reads_collects_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = StorageConfig::new(tmp.path().to_path_buf());
+        JsonlWriter::for_entity(&config, EntityType::Event, "current")
+            .append(&serde_json::json!({"id": "a"}))
+            .unwrap();
+        let reader =
+            JsonlReader::<serde_json::Value>::for_entity(&config, EntityType::Event, "current");
+
+        let (rows, files) = track_reads(async {
+            reader.read_all().unwrap().len() + reader.read_all().unwrap().len()
+        })
+        .await;
+        assert_eq!(rows, 2);
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with("events.jsonl"));
+
+        // Outside a tracked scope reads are simply not recorded
+        assert_eq!(reader.read_all().unwrap().len(), 1);
+    }
+
     #[test]
     fn test_storage_config_default() {
         let config = StorageConfig::default();
@@ -0,0 +1,255 @@
+//! Local house rules from `overrides.toml` in the data directory.
+//!
+//! A community tracking its own scene can add faction aliases, group
+//! factions together (Imperial and Chaos Knights as "Knights", a homebrew
+//! faction under a label of its own), and leave events or players out of
+//! analytics without patching code:
+//!
+//! ```toml
+//! [aliases]
+//! "knights of the imperium" = "Imperial Knights"
+//!
+//! [groups]
+//! "Imperial Knights" = "Knights"
+//! "Chaos Knights" = "Knights"
+//!
+//! [exclude]
+//! events = ["2f0c6e1a9b3d4c7e"]
+//! players = ["Test Account"]
+//! ```
+//!
+//! Aliases and groups are applied to the installed faction taxonomy, so
+//! every normalized faction name respects them; stored records keep their
+//! canonical names. Exclusions travel with [`StorageConfig`] and are
+//! applied as analytics load records.
+//!
+//! [`StorageConfig`]: super::StorageConfig
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::models::{ArmyList, Event, Pairing, Placement};
+use crate::sync::normalize_player_name;
+
+/// File name of the local overrides, in the data directory.
+pub const OVERRIDES_FILE: &str = "overrides.toml";
+
+static INSTALLED: OnceLock<Arc<Overrides>> = OnceLock::new();
+
+/// Errors from loading overrides.
+#[derive(Debug, Error)]
+pub enum OverridesError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Parse error: {0}")]
+    Parse(#[from] toml::de::Error),
+}
+
+/// Events and players left out of analytics.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Exclusions {
+    /// Event IDs
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Player names, matched ignoring case and spacing
+    #[serde(default)]
+    pub players: Vec<String>,
+}
+
+/// Local faction aliases, faction groups and exclusions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Overrides {
+    /// Extra spellings, to the faction they name
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+    /// Factions, to the group reported in their place
+    #[serde(default)]
+    pub groups: BTreeMap<String, String>,
+    #[serde(default)]
+    pub exclude: Exclusions,
+    /// Normalized `exclude.players`
+    #[serde(skip)]
+    players: HashSet<String>,
+}
+
+impl Overrides {
+    /// Parse overrides from TOML.
+    pub fn parse(toml_text: &str) -> Result<Self, OverridesError> {
+        let mut overrides: Overrides = toml::from_str(toml_text)?;
+        overrides.players = overrides
+            .exclude
+            .players
+            .iter()
+            .map(|p| normalize_player_name(p))
+            .collect();
+        Ok(overrides)
+    }
+
+    /// `overrides.toml` under `data_dir`, or no overrides if it doesn't
+    /// exist.
+    pub fn load(data_dir: &Path) -> Result<Self, OverridesError> {
+        let path = data_dir.join(OVERRIDES_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::parse(&std::fs::read_to_string(&path)?)
+    }
+
+    /// Make these the overrides storage configs pick up. Only the first
+    /// install takes effect; returns false if some were already installed.
+    pub fn install(self) -> bool {
+        INSTALLED.set(Arc::new(self)).is_ok()
+    }
+
+    /// The installed overrides, or none.
+    pub fn shared() -> Arc<Overrides> {
+        INSTALLED.get().cloned().unwrap_or_default()
+    }
+
+    /// Whether anything is excluded.
+    pub fn excludes_any(&self) -> bool {
+        !self.exclude.events.is_empty() || !self.players.is_empty()
+    }
+
+    pub fn excludes_event(&self, event_id: &str) -> bool {
+        self.exclude.events.iter().any(|id| id == event_id)
+    }
+
+    pub fn excludes_player(&self, name: &str) -> bool {
+        !self.players.is_empty() && self.players.contains(&normalize_player_name(name))
+    }
+
+    /// Drop the records an exclusion covers.
+    pub fn retain<T: Excludable>(&self, records: &mut Vec<T>) {
+        if self.excludes_any() {
+            records.retain(|r| !r.excluded_by(self));
+        }
+    }
+}
+
+/// Records an exclusion can cover.
+pub trait Excludable {
+    fn excluded_by(&self, overrides: &Overrides) -> bool;
+}
+
+impl Excludable for Event {
+    fn excluded_by(&self, overrides: &Overrides) -> bool {
+        overrides.excludes_event(self.id.as_str())
+    }
+}
+
+impl Excludable for Placement {
+    fn excluded_by(&self, overrides: &Overrides) -> bool {
+        overrides.excludes_event(self.event_id.as_str())
+            || overrides.excludes_player(&self.player_name)
+    }
+}
+
+impl Excludable for ArmyList {
+    fn excluded_by(&self, overrides: &Overrides) -> bool {
+        self.event_id
+            .as_ref()
+            .is_some_and(|id| overrides.excludes_event(id.as_str()))
+            || self
+                .player_name
+                .as_deref()
+                .is_some_and(|p| overrides.excludes_player(p))
+    }
+}
+
+/// A game against an excluded player is dropped for both sides.
+impl Excludable for Pairing {
+    fn excluded_by(&self, overrides: &Overrides) -> bool {
+        overrides.excludes_event(self.event_id.as_str())
+            || overrides.excludes_player(&self.player1_name)
+            || overrides.excludes_player(&self.player2_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EntityId, FactionTaxonomy};
+
+    #[test]
+    fn test_overrides_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert_eq!(Overrides::load(tmp.path()).unwrap(), Overrides::default());
+        std::fs::write(
+            tmp.path().join(OVERRIDES_FILE),
+            r#"
+[aliases]
+"knights of the imperium" = "Imperial Knights"
+
+[groups]
+"Imperial Knights" = "Knights"
+"chaos knights" = "Knights"
+"Homebrew Squats" = "Homebrew"
+
+[exclude]
+events = ["narrative"]
+players = ["Test  Account"]
+"#,
+        )
+        .unwrap();
+        let overrides = Overrides::load(tmp.path()).unwrap();
+
+        let mut taxonomy = FactionTaxonomy::embedded().clone();
+        taxonomy.add_aliases(&overrides.aliases).unwrap();
+        taxonomy.add_groups(&overrides.groups);
+        assert_eq!(
+            taxonomy.normalize("Knights of the Imperium"),
+            "Knights".to_string()
+        );
+        assert_eq!(taxonomy.normalize("Chaos Knights"), "Knights");
+        // Names that get stored ignore groups
+        assert_eq!(taxonomy.canonical(" chaos knights "), "Chaos Knights");
+        assert_eq!(taxonomy.normalize("homebrew squats"), "Homebrew");
+        assert_eq!(taxonomy.normalize("Aeldari"), "Aeldari");
+        // Lookups still see the real faction
+        assert_eq!(
+            taxonomy
+                .lookup("knights of the imperium")
+                .unwrap()
+                .canonical_name,
+            "Imperial Knights"
+        );
+        let unknown = BTreeMap::from([("x".to_string(), "Not A Faction".to_string())]);
+        assert!(taxonomy.add_aliases(&unknown).is_err());
+
+        let epoch = EntityId::from("current");
+        let placement = |event: &str, player: &str| {
+            Placement::new(
+                EntityId::from(event),
+                epoch.clone(),
+                1,
+                player.to_string(),
+                "Aeldari".to_string(),
+            )
+        };
+        let mut placements = vec![
+            placement("gt", "Alice"),
+            placement("narrative", "Bob"),
+            placement("gt", "test account"),
+        ];
+        overrides.retain(&mut placements);
+        assert_eq!(placements.len(), 1);
+        assert_eq!(placements[0].player_name, "Alice");
+
+        let mut pairing = Pairing::new(
+            EntityId::from("gt"),
+            epoch,
+            1,
+            "Alice".to_string(),
+            "Test Account".to_string(),
+        );
+        assert!(pairing.excluded_by(&overrides));
+        pairing.player2_name = "Carol".to_string();
+        assert!(!pairing.excluded_by(&overrides));
+    }
+}
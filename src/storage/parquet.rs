@@ -28,6 +28,15 @@ pub enum TableType {
 }
 
 impl TableType {
+    /// Table name, as used in SQL queries.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TableType::Events => "events",
+            TableType::Placements => "placements",
+            TableType::FactionStats => "faction_stats",
+        }
+    }
+
     /// Get the filename for this table.
     pub fn filename(&self) -> &'static str {
         match self {
@@ -0,0 +1,583 @@
+//! Pluggable backing stores for the per-epoch entity files.
+//!
+//! [`JsonlReader`] and [`JsonlWriter`] created with `for_entity` or
+//! `for_source` go through the backend chosen in [`StorageConfig`]. A store
+//! holds newline-free JSON records under keys that mirror the JSONL layout
+//! (`<epoch>/events.jsonl`, `<epoch>/<source>/placements.jsonl`), so the
+//! same code reads either backend and data converts between them
+//! losslessly. Global files (significant events, series, the review queue)
+//! always stay JSONL.
+//!
+//! The SQLite store sits behind the `sqlite` feature. It keeps every record
+//! in one table with generated columns for the record and event IDs, which
+//! are indexed.
+//!
+//! [`JsonlReader`]: super::JsonlReader
+//! [`JsonlWriter`]: super::JsonlWriter
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::info;
+
+use super::jsonl::EntityType;
+use super::{StorageConfig, StorageError};
+
+/// Where per-epoch entity records are kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// `normalized/<epoch>/*.jsonl` files
+    #[default]
+    Jsonl,
+    /// A single `normalized/store.sqlite` database (`sqlite` feature)
+    Sqlite,
+}
+
+impl fmt::Display for StorageBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StorageBackend::Jsonl => "jsonl",
+            StorageBackend::Sqlite => "sqlite",
+        })
+    }
+}
+
+impl FromStr for StorageBackend {
+    type Err = StorageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "jsonl" => Ok(StorageBackend::Jsonl),
+            "sqlite" => Ok(StorageBackend::Sqlite),
+            other => Err(StorageError::Backend(format!(
+                "Unknown storage backend '{}' (expected jsonl or sqlite)",
+                other
+            ))),
+        }
+    }
+}
+
+/// A keyed store of JSON lines.
+pub trait Store: Send + Sync + fmt::Debug {
+    /// Records stored under `key`, in insertion order.
+    fn read_lines(&self, key: &str) -> Result<Vec<String>, StorageError>;
+
+    /// Add records after the existing ones.
+    fn append_lines(&self, key: &str, lines: &[String]) -> Result<(), StorageError>;
+
+    /// Replace every record under `key` in one transaction.
+    fn replace_lines(&self, key: &str, lines: &[String]) -> Result<(), StorageError>;
+
+    /// Every key holding records, sorted.
+    fn keys(&self) -> Result<Vec<String>, StorageError>;
+
+    /// Records under `key` with this ID, in insertion order. Database
+    /// stores answer from an index; the default scans the key.
+    fn find_by_id(&self, key: &str, id: &str) -> Result<Vec<String>, StorageError> {
+        Ok(self
+            .read_lines(key)?
+            .into_iter()
+            .filter(|line| field_is(line, "id", id))
+            .collect())
+    }
+
+    /// Records under `key` belonging to an event, in insertion order.
+    fn find_by_event(&self, key: &str, event_id: &str) -> Result<Vec<String>, StorageError> {
+        Ok(self
+            .read_lines(key)?
+            .into_iter()
+            .filter(|line| field_is(line, "event_id", event_id))
+            .collect())
+    }
+
+    /// A value that changes whenever the records under `key` do, cheaper to
+    /// read than the records themselves. Defaults to a hash of them.
+    fn stamp(&self, key: &str) -> Result<String, StorageError> {
+        let mut hasher = Sha256::new();
+        for line in self.read_lines(key)? {
+            hasher.update(line.as_bytes());
+            hasher.update(b"\n");
+        }
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// Push buffered writes to their files. JSONL appends are written
+    /// through, so by default there is nothing to do.
+    fn flush(&self) -> Result<(), StorageError> {
+        Ok(())
+    }
+}
+
+/// Whether a JSON record's string `field` equals `value`.
+fn field_is(line: &str, field: &str, value: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(line)
+        .is_ok_and(|record| record.get(field).and_then(|v| v.as_str()) == Some(value))
+}
+
+/// Keys holding an entity's records for one epoch: the shared
+/// `<epoch>/<file>` first, then any `<epoch>/<source>/<file>` partitions.
+pub fn entity_keys(
+    store: &dyn Store,
+    entity: EntityType,
+    epoch_id: &str,
+) -> Result<Vec<String>, StorageError> {
+    let mut keys: Vec<String> = store
+        .keys()?
+        .into_iter()
+        .filter(|key| {
+            let parts: Vec<&str> = key.split('/').collect();
+            parts.len() <= 3 && parts[0] == epoch_id && parts.last() == Some(&entity.filename())
+        })
+        .collect();
+    keys.sort_by_key(|key| key.matches('/').count());
+    Ok(keys)
+}
+
+/// Split serialized JSONL content into records.
+pub(crate) fn split_lines(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The JSONL layout under `normalized/`, as a [`Store`]. Used for
+/// converting to and from other backends.
+#[derive(Debug)]
+pub struct JsonlStore {
+    root: PathBuf,
+}
+
+impl JsonlStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn collect_keys(&self, dir: &Path, keys: &mut Vec<String>) -> Result<(), StorageError> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                self.collect_keys(&path, keys)?;
+            } else if path.extension().is_some_and(|e| e == "jsonl") {
+                let relative = path.strip_prefix(&self.root).unwrap_or(&path);
+                // Top-level files are the global JSONL-only ones
+                if relative.components().count() > 1 {
+                    keys.push(relative.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Store for JsonlStore {
+    fn read_lines(&self, key: &str) -> Result<Vec<String>, StorageError> {
+        match fs::read_to_string(self.root.join(key)) {
+            Ok(content) => Ok(split_lines(&content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn append_lines(&self, key: &str, lines: &[String]) -> Result<(), StorageError> {
+        let mut existing = self.read_lines(key)?;
+        existing.extend_from_slice(lines);
+        self.replace_lines(key, &existing)
+    }
+
+    fn replace_lines(&self, key: &str, lines: &[String]) -> Result<(), StorageError> {
+        super::JsonlWriter::<serde_json::Value>::new(self.root.join(key)).write_lines(lines)
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        let mut keys = Vec::new();
+        if self.root.is_dir() {
+            self.collect_keys(&self.root, &mut keys)?;
+        }
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+/// Path of the SQLite database for a storage root.
+pub fn sqlite_path(config: &StorageConfig) -> PathBuf {
+    config.normalized_dir().join("store.sqlite")
+}
+
+fn open_stores() -> &'static Mutex<HashMap<PathBuf, Arc<dyn Store>>> {
+    static STORES: OnceLock<Mutex<HashMap<PathBuf, Arc<dyn Store>>>> = OnceLock::new();
+    STORES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Flush every database store opened by this process, e.g. before exit.
+pub fn flush_all() -> Result<(), StorageError> {
+    let stores: Vec<Arc<dyn Store>> = open_stores()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .cloned()
+        .collect();
+    for store in stores {
+        store.flush()?;
+    }
+    Ok(())
+}
+
+/// The store for `backend` under this storage root. Database connections
+/// are opened once per process and shared.
+pub fn open(
+    config: &StorageConfig,
+    backend: StorageBackend,
+) -> Result<Arc<dyn Store>, StorageError> {
+    match backend {
+        StorageBackend::Jsonl => Ok(Arc::new(JsonlStore::new(config.normalized_dir()))),
+        StorageBackend::Sqlite => {
+            let path = sqlite_path(config);
+            let mut stores = open_stores().lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(store) = stores.get(&path) {
+                return Ok(store.clone());
+            }
+            let store = open_sqlite(&path)?;
+            stores.insert(path, store.clone());
+            Ok(store)
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+fn open_sqlite(path: &Path) -> Result<Arc<dyn Store>, StorageError> {
+    Ok(Arc::new(sqlite::SqliteStore::open(path)?))
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn open_sqlite(_path: &Path) -> Result<Arc<dyn Store>, StorageError> {
+    Err(StorageError::Backend(
+        "SQLite storage needs a build with the `sqlite` feature".to_string(),
+    ))
+}
+
+/// Copy every per-epoch record from one backend into another, replacing
+/// what the target holds for each key. Returns (keys, records) copied.
+pub fn convert(
+    config: &StorageConfig,
+    from: StorageBackend,
+    to: StorageBackend,
+) -> Result<(usize, usize), StorageError> {
+    if from == to {
+        return Err(StorageError::Backend(format!(
+            "Source and target backend are both {}",
+            from
+        )));
+    }
+    let source = open(config, from)?;
+    let target = open(config, to)?;
+    let (mut keys, mut records) = (0, 0);
+    for key in source.keys()? {
+        let lines = source.read_lines(&key)?;
+        target.replace_lines(&key, &lines)?;
+        keys += 1;
+        records += lines.len();
+    }
+    info!(
+        "Copied {} records in {} files from {} to {}",
+        records, keys, from, to
+    );
+    Ok((keys, records))
+}
+
+#[cfg(feature = "sqlite")]
+pub mod sqlite {
+    //! SQLite implementation of [`Store`].
+
+    use std::path::Path;
+    use std::sync::Mutex;
+
+    use rusqlite::{params, Connection};
+
+    use super::{StorageError, Store};
+
+    /// Schema migrations, applied in order and tracked in `user_version`.
+    const MIGRATIONS: &[&str] = &[
+        "CREATE TABLE records (
+            seq INTEGER PRIMARY KEY AUTOINCREMENT,
+            key TEXT NOT NULL,
+            body TEXT NOT NULL
+        );
+        CREATE INDEX records_key ON records (key, seq);",
+        "ALTER TABLE records ADD COLUMN record_id TEXT
+            GENERATED ALWAYS AS (json_extract(body, '$.id')) VIRTUAL;
+        ALTER TABLE records ADD COLUMN event_id TEXT
+            GENERATED ALWAYS AS (json_extract(body, '$.event_id')) VIRTUAL;
+        CREATE INDEX records_record_id ON records (key, record_id);
+        CREATE INDEX records_event_id ON records (key, event_id);",
+    ];
+
+    fn db(e: rusqlite::Error) -> StorageError {
+        StorageError::Backend(e.to_string())
+    }
+
+    /// Records in a single SQLite database.
+    #[derive(Debug)]
+    pub struct SqliteStore {
+        conn: Mutex<Connection>,
+    }
+
+    impl SqliteStore {
+        /// Open (creating if needed) and migrate the database at `path`.
+        pub fn open(path: &Path) -> Result<Self, StorageError> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let conn = Connection::open(path).map_err(db)?;
+            conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")
+                .map_err(db)?;
+            let store = Self {
+                conn: Mutex::new(conn),
+            };
+            store.migrate()?;
+            Ok(store)
+        }
+
+        /// Apply any migrations newer than the database's version.
+        fn migrate(&self) -> Result<(), StorageError> {
+            let mut conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+            let version: usize = conn
+                .query_row("PRAGMA user_version", [], |row| row.get(0))
+                .map_err(db)?;
+            for (i, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+                let tx = conn.transaction().map_err(db)?;
+                tx.execute_batch(migration).map_err(db)?;
+                tx.execute_batch(&format!("PRAGMA user_version = {}", i + 1))
+                    .map_err(db)?;
+                tx.commit().map_err(db)?;
+            }
+            Ok(())
+        }
+
+        /// The schema version after migrations.
+        pub fn schema_version(&self) -> Result<usize, StorageError> {
+            let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+            conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+                .map_err(db)
+        }
+    }
+
+    impl Store for SqliteStore {
+        /// Records with this ID under `key`, via the ID index.
+        fn find_by_id(&self, key: &str, id: &str) -> Result<Vec<String>, StorageError> {
+            let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+            let mut stmt = conn
+                .prepare_cached(
+                    "SELECT body FROM records WHERE key = ?1 AND record_id = ?2 ORDER BY seq",
+                )
+                .map_err(db)?;
+            let rows = stmt
+                .query_map(params![key, id], |row| row.get(0))
+                .map_err(db)?;
+            rows.collect::<Result<Vec<String>, _>>().map_err(db)
+        }
+
+        /// Records under `key` belonging to an event, via the event index.
+        fn find_by_event(&self, key: &str, event_id: &str) -> Result<Vec<String>, StorageError> {
+            let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+            let mut stmt = conn
+                .prepare_cached(
+                    "SELECT body FROM records WHERE key = ?1 AND event_id = ?2 ORDER BY seq",
+                )
+                .map_err(db)?;
+            let rows = stmt
+                .query_map(params![key, event_id], |row| row.get(0))
+                .map_err(db)?;
+            rows.collect::<Result<Vec<String>, _>>().map_err(db)
+        }
+
+        fn read_lines(&self, key: &str) -> Result<Vec<String>, StorageError> {
+            let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+            let mut stmt = conn
+                .prepare_cached("SELECT body FROM records WHERE key = ?1 ORDER BY seq")
+                .map_err(db)?;
+            let rows = stmt.query_map(params![key], |row| row.get(0)).map_err(db)?;
+            rows.collect::<Result<Vec<String>, _>>().map_err(db)
+        }
+
+        fn append_lines(&self, key: &str, lines: &[String]) -> Result<(), StorageError> {
+            let mut conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+            let tx = conn.transaction().map_err(db)?;
+            {
+                let mut stmt = tx
+                    .prepare_cached("INSERT INTO records (key, body) VALUES (?1, ?2)")
+                    .map_err(db)?;
+                for line in lines {
+                    stmt.execute(params![key, line]).map_err(db)?;
+                }
+            }
+            tx.commit().map_err(db)
+        }
+
+        fn replace_lines(&self, key: &str, lines: &[String]) -> Result<(), StorageError> {
+            let mut conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+            let tx = conn.transaction().map_err(db)?;
+            tx.execute("DELETE FROM records WHERE key = ?1", params![key])
+                .map_err(db)?;
+            {
+                let mut stmt = tx
+                    .prepare_cached("INSERT INTO records (key, body) VALUES (?1, ?2)")
+                    .map_err(db)?;
+                for line in lines {
+                    stmt.execute(params![key, line]).map_err(db)?;
+                }
+            }
+            tx.commit().map_err(db)
+        }
+
+        fn keys(&self) -> Result<Vec<String>, StorageError> {
+            let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+            let mut stmt = conn
+                .prepare_cached("SELECT DISTINCT key FROM records ORDER BY key")
+                .map_err(db)?;
+            let rows = stmt.query_map([], |row| row.get(0)).map_err(db)?;
+            rows.collect::<Result<Vec<String>, _>>().map_err(db)
+        }
+
+        /// Record count and newest sequence number. Sequence numbers are
+        /// never reused, so every append or replace changes the stamp.
+        fn stamp(&self, key: &str) -> Result<String, StorageError> {
+            let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+            let mut stmt = conn
+                .prepare_cached(
+                    "SELECT count(*), coalesce(max(seq), 0) FROM records WHERE key = ?1",
+                )
+                .map_err(db)?;
+            let (count, seq): (i64, i64) = stmt
+                .query_row(params![key], |row| Ok((row.get(0)?, row.get(1)?)))
+                .map_err(db)?;
+            Ok(format!("{}:{}", count, seq))
+        }
+
+        /// Checkpoint the WAL into the database file.
+        fn flush(&self) -> Result<(), StorageError> {
+            let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+            conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+                .map_err(db)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_sqlite_store_migrates_and_indexes() {
+            let tmp = tempfile::tempdir().unwrap();
+            let store = SqliteStore::open(&tmp.path().join("store.sqlite")).unwrap();
+            assert_eq!(store.schema_version().unwrap(), MIGRATIONS.len());
+
+            let key = "current/placements.jsonl";
+            store
+                .replace_lines(
+                    key,
+                    &[
+                        r#"{"id":"p1","event_id":"e1"}"#.to_string(),
+                        r#"{"id":"p2","event_id":"e2"}"#.to_string(),
+                    ],
+                )
+                .unwrap();
+            store
+                .append_lines(key, &[r#"{"id":"p3","event_id":"e1"}"#.to_string()])
+                .unwrap();
+
+            assert_eq!(store.read_lines(key).unwrap().len(), 3);
+            assert_eq!(store.find_by_event(key, "e1").unwrap().len(), 2);
+            assert_eq!(store.find_by_id(key, "p2").unwrap().len(), 1);
+            assert_eq!(store.keys().unwrap(), vec![key.to_string()]);
+
+            // Reopening doesn't re-run migrations
+            drop(store);
+            let store = SqliteStore::open(&tmp.path().join("store.sqlite")).unwrap();
+            assert_eq!(store.read_lines(key).unwrap().len(), 3);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{EntityType, JsonlReader, JsonlWriter};
+
+    #[test]
+    fn test_jsonl_store_keys_skip_global_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = StorageConfig::new(tmp.path().to_path_buf());
+        JsonlWriter::for_entity(&config, EntityType::Event, "current")
+            .append(&serde_json::json!({"id": "a"}))
+            .unwrap();
+        JsonlWriter::for_source(
+            &config.clone().with_source_partitioning(true),
+            EntityType::Placement,
+            "current",
+            "bcp",
+        )
+        .append(&serde_json::json!({"id": "p"}))
+        .unwrap();
+        std::fs::write(config.significant_events_path(), "{}\n").unwrap();
+
+        let store = open(&config, StorageBackend::Jsonl).unwrap();
+        assert_eq!(
+            store.keys().unwrap(),
+            vec!["current/bcp/placements.jsonl", "current/events.jsonl"]
+        );
+        store
+            .append_lines("current/events.jsonl", &[r#"{"id":"b"}"#.to_string()])
+            .unwrap();
+        let events =
+            JsonlReader::<serde_json::Value>::for_entity(&config, EntityType::Event, "current")
+                .read_all()
+                .unwrap();
+        assert_eq!(events.len(), 2);
+
+        assert_eq!(
+            "SQLite".parse::<StorageBackend>().unwrap(),
+            StorageBackend::Sqlite
+        );
+        assert!("csv".parse::<StorageBackend>().is_err());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_readers_and_writers_use_sqlite_backend() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = StorageConfig::new(tmp.path().to_path_buf());
+        JsonlWriter::for_entity(&config, EntityType::Event, "current")
+            .write_all(&[serde_json::json!({"id": "a"})])
+            .unwrap();
+
+        let sqlite = config.clone().with_backend(StorageBackend::Sqlite);
+        assert_eq!(
+            convert(&config, StorageBackend::Jsonl, StorageBackend::Sqlite).unwrap(),
+            (1, 1)
+        );
+        JsonlWriter::for_entity(&sqlite, EntityType::Event, "current")
+            .append(&serde_json::json!({"id": "b"}))
+            .unwrap();
+        let reader =
+            JsonlReader::<serde_json::Value>::for_entity(&sqlite, EntityType::Event, "current");
+        assert_eq!(reader.read_all().unwrap().len(), 2);
+        assert_eq!(reader.count().unwrap(), 2);
+        assert_eq!(
+            crate::storage::jsonl::list_epochs(&sqlite).unwrap(),
+            vec!["current"]
+        );
+        // The JSONL copy is untouched
+        let jsonl =
+            JsonlReader::<serde_json::Value>::for_entity(&config, EntityType::Event, "current");
+        assert_eq!(jsonl.read_all().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,540 @@
+//! Storage integrity checks.
+//!
+//! Walks every epoch, in JSONL files or the configured store backend, and
+//! verifies that records parse into their entity types, that references
+//! between entities resolve, and that IDs are unique. Produces a report
+//! suitable for CI or cron (JSON + exit code).
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::jsonl::{entity_paths, list_epochs, EntityType};
+use super::store;
+use super::{StorageBackend, StorageConfig, StorageError};
+use crate::models::{ArmyList, Event, Pairing, Placement};
+use crate::sync::normalize_player_name;
+
+/// How serious an issue is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Kind of integrity problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// Line is not valid JSON for its entity type
+    ParseError,
+    /// Same ID stored more than once
+    DuplicateId,
+    /// Placement or pairing references an event that doesn't exist
+    MissingEvent,
+    /// Placement references a list that doesn't exist
+    MissingList,
+    /// List references an event that doesn't exist
+    InvalidListEvent,
+    /// List not linked to any placement
+    OrphanList,
+    /// Event with no placements
+    EmptyEvent,
+}
+
+/// A single integrity problem.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    pub kind: IssueKind,
+    pub epoch_id: String,
+    pub entity: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// File and line, for parse errors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    pub message: String,
+}
+
+/// Entity counts for one epoch.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EpochSummary {
+    pub epoch_id: String,
+    pub events: usize,
+    pub placements: usize,
+    pub army_lists: usize,
+    pub pairings: usize,
+}
+
+/// Result of validating the whole data directory.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValidationReport {
+    pub epochs: Vec<EpochSummary>,
+    pub issues: Vec<ValidationIssue>,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+impl ValidationReport {
+    /// True if no errors were found (warnings allowed).
+    pub fn is_ok(&self) -> bool {
+        self.errors == 0
+    }
+
+    /// Process exit code: 1 on errors, or on warnings when `strict`.
+    pub fn exit_code(&self, strict: bool) -> i32 {
+        if self.errors > 0 || (strict && self.warnings > 0) {
+            1
+        } else {
+            0
+        }
+    }
+
+    fn push(&mut self, issue: ValidationIssue) {
+        match issue.severity {
+            Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+        }
+        self.issues.push(issue);
+    }
+}
+
+/// Entities loaded from one epoch.
+struct EpochData {
+    epoch_id: String,
+    events: Vec<Event>,
+    placements: Vec<Placement>,
+    lists: Vec<ArmyList>,
+    pairings: Vec<Pairing>,
+}
+
+/// Parse every record of an entity, reporting lines that fail. Non-JSONL
+/// backends are read through their [`Store`](super::Store), whose keys
+/// mirror the JSONL layout.
+fn scan<T: DeserializeOwned>(
+    config: &StorageConfig,
+    entity: EntityType,
+    epoch_id: &str,
+    report: &mut ValidationReport,
+) -> Result<Vec<T>, StorageError> {
+    let mut out = Vec::new();
+    if config.backend == StorageBackend::Jsonl {
+        for path in entity_paths(config, entity, epoch_id) {
+            let lines = BufReader::new(File::open(&path)?)
+                .lines()
+                .collect::<Result<Vec<_>, _>>()?;
+            scan_lines(
+                &path.display().to_string(),
+                &lines,
+                entity,
+                epoch_id,
+                &mut out,
+                report,
+            );
+        }
+        return Ok(out);
+    }
+    let store = store::open(config, config.backend)?;
+    for key in store::entity_keys(store.as_ref(), entity, epoch_id)? {
+        let lines = store.read_lines(&key)?;
+        scan_lines(&key, &lines, entity, epoch_id, &mut out, report);
+    }
+    Ok(out)
+}
+
+fn scan_lines<T: DeserializeOwned>(
+    location: &str,
+    lines: &[String],
+    entity: EntityType,
+    epoch_id: &str,
+    out: &mut Vec<T>,
+    report: &mut ValidationReport,
+) {
+    for (idx, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(line) {
+            Ok(item) => out.push(item),
+            Err(e) => report.push(ValidationIssue {
+                severity: Severity::Error,
+                kind: IssueKind::ParseError,
+                epoch_id: epoch_id.to_string(),
+                entity: entity.filename(),
+                id: None,
+                location: Some(format!("{}:{}", location, idx + 1)),
+                message: e.to_string(),
+            }),
+        }
+    }
+}
+
+/// Report IDs that appear more than once, within or across epochs.
+fn check_duplicates<'a>(
+    entity: EntityType,
+    ids: impl Iterator<Item = (&'a str, &'a str)>,
+    report: &mut ValidationReport,
+) {
+    let mut seen: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (epoch_id, id) in ids {
+        seen.entry(id).or_default().push(epoch_id);
+    }
+    let mut dups: Vec<(&str, Vec<&str>)> = seen.into_iter().filter(|(_, e)| e.len() > 1).collect();
+    dups.sort();
+    for (id, epochs) in dups {
+        let mut distinct: Vec<&str> = epochs.clone();
+        distinct.sort();
+        distinct.dedup();
+        report.push(ValidationIssue {
+            severity: Severity::Warning,
+            kind: IssueKind::DuplicateId,
+            epoch_id: distinct.join(","),
+            entity: entity.filename(),
+            id: Some(id.to_string()),
+            location: None,
+            message: format!("Stored {} times", epochs.len()),
+        });
+    }
+}
+
+/// Validate every epoch in the data directory.
+///
+/// Epoch directories renamed to `*.bak` are skipped. References are resolved
+/// across all epochs, since a list may be stored in a different epoch than
+/// its event.
+pub fn validate_storage(config: &StorageConfig) -> Result<ValidationReport, StorageError> {
+    let mut report = ValidationReport::default();
+
+    let mut data = Vec::new();
+    for epoch_id in list_epochs(config)? {
+        if epoch_id.ends_with(".bak") {
+            continue;
+        }
+        let events = scan::<Event>(config, EntityType::Event, &epoch_id, &mut report)?;
+        let placements = scan::<Placement>(config, EntityType::Placement, &epoch_id, &mut report)?;
+        let lists = scan::<ArmyList>(config, EntityType::ArmyList, &epoch_id, &mut report)?;
+        let pairings = scan::<Pairing>(config, EntityType::Pairing, &epoch_id, &mut report)?;
+        report.epochs.push(EpochSummary {
+            epoch_id: epoch_id.clone(),
+            events: events.len(),
+            placements: placements.len(),
+            army_lists: lists.len(),
+            pairings: pairings.len(),
+        });
+        data.push(EpochData {
+            epoch_id,
+            events,
+            placements,
+            lists,
+            pairings,
+        });
+    }
+
+    check_duplicates(
+        EntityType::Event,
+        data.iter().flat_map(|d| {
+            d.events
+                .iter()
+                .map(|e| (d.epoch_id.as_str(), e.id.as_str()))
+        }),
+        &mut report,
+    );
+    check_duplicates(
+        EntityType::Placement,
+        data.iter().flat_map(|d| {
+            d.placements
+                .iter()
+                .map(|p| (d.epoch_id.as_str(), p.id.as_str()))
+        }),
+        &mut report,
+    );
+    check_duplicates(
+        EntityType::ArmyList,
+        data.iter()
+            .flat_map(|d| d.lists.iter().map(|l| (d.epoch_id.as_str(), l.id.as_str()))),
+        &mut report,
+    );
+    check_duplicates(
+        EntityType::Pairing,
+        data.iter().flat_map(|d| {
+            d.pairings
+                .iter()
+                .map(|p| (d.epoch_id.as_str(), p.id.as_str()))
+        }),
+        &mut report,
+    );
+
+    let event_ids: HashSet<&str> = data
+        .iter()
+        .flat_map(|d| d.events.iter().map(|e| e.id.as_str()))
+        .collect();
+    let list_ids: HashSet<&str> = data
+        .iter()
+        .flat_map(|d| d.lists.iter().map(|l| l.id.as_str()))
+        .collect();
+    let linked_list_ids: HashSet<&str> = data
+        .iter()
+        .flat_map(|d| d.placements.iter().filter_map(|p| p.list_id.as_ref()))
+        .map(|id| id.as_str())
+        .collect();
+    let placement_players: HashSet<(&str, String)> = data
+        .iter()
+        .flat_map(|d| d.placements.iter())
+        .map(|p| (p.event_id.as_str(), normalize_player_name(&p.player_name)))
+        .collect();
+    let events_with_placements: HashSet<&str> = data
+        .iter()
+        .flat_map(|d| d.placements.iter().map(|p| p.event_id.as_str()))
+        .collect();
+
+    for d in &data {
+        let issue =
+            |severity, kind, entity: EntityType, id: &str, message: String| ValidationIssue {
+                severity,
+                kind,
+                epoch_id: d.epoch_id.clone(),
+                entity: entity.filename(),
+                id: Some(id.to_string()),
+                location: None,
+                message,
+            };
+
+        for p in &d.placements {
+            if !event_ids.contains(p.event_id.as_str()) {
+                report.push(issue(
+                    Severity::Error,
+                    IssueKind::MissingEvent,
+                    EntityType::Placement,
+                    p.id.as_str(),
+                    format!("event_id {} not found", p.event_id),
+                ));
+            }
+            if let Some(ref list_id) = p.list_id {
+                if !list_ids.contains(list_id.as_str()) {
+                    report.push(issue(
+                        Severity::Error,
+                        IssueKind::MissingList,
+                        EntityType::Placement,
+                        p.id.as_str(),
+                        format!("list_id {} not found", list_id),
+                    ));
+                }
+            }
+        }
+
+        for l in &d.lists {
+            match l.event_id {
+                Some(ref event_id) if !event_ids.contains(event_id.as_str()) => {
+                    report.push(issue(
+                        Severity::Error,
+                        IssueKind::InvalidListEvent,
+                        EntityType::ArmyList,
+                        l.id.as_str(),
+                        format!("event_id {} not found", event_id),
+                    ));
+                }
+                _ => {}
+            }
+
+            let linked_by_name = match (&l.event_id, &l.player_name) {
+                (Some(event_id), Some(player)) => {
+                    placement_players.contains(&(event_id.as_str(), normalize_player_name(player)))
+                }
+                _ => false,
+            };
+            if !linked_list_ids.contains(l.id.as_str()) && !linked_by_name {
+                report.push(issue(
+                    Severity::Warning,
+                    IssueKind::OrphanList,
+                    EntityType::ArmyList,
+                    l.id.as_str(),
+                    "Not linked to any placement".to_string(),
+                ));
+            }
+        }
+
+        for p in &d.pairings {
+            if !event_ids.contains(p.event_id.as_str()) {
+                report.push(issue(
+                    Severity::Error,
+                    IssueKind::MissingEvent,
+                    EntityType::Pairing,
+                    p.id.as_str(),
+                    format!("event_id {} not found", p.event_id),
+                ));
+            }
+        }
+
+        for e in &d.events {
+            if !events_with_placements.contains(e.id.as_str()) {
+                report.push(issue(
+                    Severity::Warning,
+                    IssueKind::EmptyEvent,
+                    EntityType::Event,
+                    e.id.as_str(),
+                    format!("{} has no placements", e.name),
+                ));
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::JsonlWriter;
+    use tempfile::TempDir;
+
+    fn make_event(name: &str) -> Event {
+        Event::new(
+            name.to_string(),
+            chrono::NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            format!("https://example.com/{}", name),
+            "test".to_string(),
+            "current".into(),
+        )
+    }
+
+    #[test]
+    fn test_validate_clean_storage() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = StorageConfig::new(temp_dir.path().to_path_buf());
+
+        let event = make_event("gt");
+        let list = ArmyList::new("Aeldari".to_string(), 2000, vec![], "raw".to_string())
+            .with_event_id(event.id.clone());
+        let mut placement = Placement::new(
+            event.id.clone(),
+            "current".into(),
+            1,
+            "Alice".to_string(),
+            "Aeldari".to_string(),
+        );
+        placement.list_id = Some(list.id.clone());
+
+        JsonlWriter::for_entity(&config, EntityType::Event, "current")
+            .write_all(&[event])
+            .unwrap();
+        JsonlWriter::for_entity(&config, EntityType::Placement, "current")
+            .write_all(&[placement])
+            .unwrap();
+        JsonlWriter::for_entity(&config, EntityType::ArmyList, "current")
+            .write_all(&[list])
+            .unwrap();
+
+        let report = validate_storage(&config).unwrap();
+        assert!(report.is_ok());
+        assert_eq!(report.warnings, 0);
+        assert_eq!(report.epochs[0].placements, 1);
+        assert_eq!(report.exit_code(true), 0);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_validate_sqlite_storage() {
+        use crate::storage::store::{convert, open};
+
+        let temp_dir = TempDir::new().unwrap();
+        let config = StorageConfig::new(temp_dir.path().to_path_buf());
+        let event = make_event("gt");
+        let placement = Placement::new(
+            event.id.clone(),
+            "current".into(),
+            1,
+            "Alice".to_string(),
+            "Aeldari".to_string(),
+        );
+        JsonlWriter::for_entity(&config, EntityType::Event, "current")
+            .write_all(&[event])
+            .unwrap();
+        JsonlWriter::for_entity(&config, EntityType::Placement, "current")
+            .write_all(&[placement])
+            .unwrap();
+        convert(&config, StorageBackend::Jsonl, StorageBackend::Sqlite).unwrap();
+        let sqlite = config.with_backend(StorageBackend::Sqlite);
+        open(&sqlite, StorageBackend::Sqlite)
+            .unwrap()
+            .append_lines("current/events.jsonl", &[r#"{"id":"x"}"#.to_string()])
+            .unwrap();
+
+        let report = validate_storage(&sqlite).unwrap();
+        assert_eq!(report.epochs[0].events, 1);
+        assert_eq!(report.epochs[0].placements, 1);
+        assert_eq!(report.errors, 1);
+        assert_eq!(
+            report.issues[0].location.as_deref(),
+            Some("current/events.jsonl:2")
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_problems() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = StorageConfig::new(temp_dir.path().to_path_buf());
+
+        let event = make_event("gt");
+        let empty_event = make_event("empty");
+        let mut dangling = Placement::new(
+            "missing-event".into(),
+            "current".into(),
+            1,
+            "Bob".to_string(),
+            "Orks".to_string(),
+        );
+        dangling.list_id = Some("missing-list".into());
+        let placement = Placement::new(
+            event.id.clone(),
+            "current".into(),
+            2,
+            "Carol".to_string(),
+            "Orks".to_string(),
+        );
+        let orphan = ArmyList::new("Orks".to_string(), 2000, vec![], "raw".to_string())
+            .with_event_id("missing-event".into());
+
+        JsonlWriter::for_entity(&config, EntityType::Event, "current")
+            .write_all(&[event.clone(), event, empty_event])
+            .unwrap();
+        JsonlWriter::for_entity(&config, EntityType::Placement, "current")
+            .write_all(&[dangling, placement])
+            .unwrap();
+        JsonlWriter::for_entity(&config, EntityType::ArmyList, "current")
+            .write_all(&[orphan])
+            .unwrap();
+        let lists_path =
+            crate::storage::jsonl::entity_path(&config, EntityType::ArmyList, "current");
+        let mut content = std::fs::read_to_string(&lists_path).unwrap();
+        content.push_str("{not json}\n");
+        std::fs::write(&lists_path, content).unwrap();
+
+        let report = validate_storage(&config).unwrap();
+        let kinds: Vec<IssueKind> = report.issues.iter().map(|i| i.kind).collect();
+        assert!(kinds.contains(&IssueKind::ParseError));
+        assert!(kinds.contains(&IssueKind::DuplicateId));
+        assert!(kinds.contains(&IssueKind::MissingEvent));
+        assert!(kinds.contains(&IssueKind::MissingList));
+        assert!(kinds.contains(&IssueKind::InvalidListEvent));
+        assert!(kinds.contains(&IssueKind::OrphanList));
+        assert!(kinds.contains(&IssueKind::EmptyEvent));
+        assert!(!report.is_ok());
+        assert_eq!(report.exit_code(false), 1);
+
+        let parse = report
+            .issues
+            .iter()
+            .find(|i| i.kind == IssueKind::ParseError)
+            .unwrap();
+        assert!(parse
+            .location
+            .as_ref()
+            .unwrap()
+            .ends_with("army_lists.jsonl:2"));
+    }
+}
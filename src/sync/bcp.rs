@@ -4,16 +4,15 @@
 //! All BCP API specifics are isolated in this module so endpoint changes
 //! are easy to fix.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use chrono::NaiveDate;
-use regex::Regex;
 use serde::Deserialize;
 use tracing::{info, warn};
 use url::Url;
 
 use crate::fetch::{FetchError, Fetcher};
-use crate::models::Unit;
+use crate::models::BracketStage;
 
 // ── Custom deserializers for nested BCP fields ──────────────────────────────
 
@@ -250,9 +249,41 @@ pub struct BcpEvent {
     /// Whether placings are hidden
     #[serde(alias = "hidePlacings")]
     pub hide_placings: Option<bool>,
+
+    /// Points limit, when the organiser set one
+    #[serde(default, alias = "pointsValue")]
+    pub points_limit: Option<u32>,
 }
 
 impl BcpEvent {
+    /// A finished event known only by what is stored for it, enough to
+    /// fetch its standings again.
+    pub fn finished(
+        id: &str,
+        name: &str,
+        date: NaiveDate,
+        player_count: Option<u32>,
+        round_count: Option<u32>,
+    ) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            start_date: Some(date.to_string()),
+            end_date: None,
+            venue: None,
+            city: None,
+            state: None,
+            country: None,
+            player_count,
+            round_count,
+            game_type: None,
+            ended: Some(true),
+            team_event: None,
+            hide_placings: None,
+            points_limit: None,
+        }
+    }
+
     /// Parse start_date string into NaiveDate.
     pub fn parsed_start_date(&self) -> Option<NaiveDate> {
         self.start_date.as_ref().and_then(|s| {
@@ -262,6 +293,19 @@ impl BcpEvent {
         })
     }
 
+    /// Parse end_date string into NaiveDate.
+    pub fn parsed_end_date(&self) -> Option<NaiveDate> {
+        self.end_date.as_ref().and_then(|s| {
+            let date_part = if s.len() >= 10 { &s[..10] } else { s };
+            NaiveDate::parse_from_str(date_part, "%Y-%m-%d").ok()
+        })
+    }
+
+    /// Whether the event is over: flagged as ended, or its end date has passed.
+    pub fn is_finished(&self, today: NaiveDate) -> bool {
+        self.ended == Some(true) || self.parsed_end_date().is_some_and(|d| d < today)
+    }
+
     /// Build a human-readable location string.
     pub fn location_string(&self) -> Option<String> {
         let parts: Vec<&str> = [
@@ -332,6 +376,14 @@ pub struct BcpStanding {
     /// Army list object ID (for fetching the full list)
     #[serde(alias = "armyListObjectId")]
     pub army_list_object_id: Option<String>,
+
+    /// Furthest top-cut stage reached (computed, not from BCP)
+    #[serde(default)]
+    pub bracket_stage: Option<BracketStage>,
+
+    /// Team or club the player entered under
+    #[serde(default, alias = "teamName")]
+    pub team: Option<String>,
 }
 
 /// An army list from BCP.
@@ -409,6 +461,14 @@ pub struct BcpPlayerV1 {
     /// Army/faction name directly on the player (some events)
     #[serde(alias = "armyName")]
     pub army_name: Option<String>,
+
+    /// Team, either a name or an object with one
+    #[serde(default)]
+    pub team: Option<serde_json::Value>,
+
+    /// Team name, on events that give it flat
+    #[serde(default, alias = "teamName")]
+    pub team_name: Option<String>,
 }
 
 impl BcpPlayerV1 {
@@ -431,6 +491,20 @@ impl BcpPlayerV1 {
             .and_then(|f| f.name.clone())
             .or_else(|| self.army_name.clone())
     }
+
+    /// Get the player's team name, if they entered with one.
+    pub fn team(&self) -> Option<String> {
+        let nested = match &self.team {
+            Some(serde_json::Value::String(name)) => Some(name.clone()),
+            Some(serde_json::Value::Object(team)) => {
+                team.get("name").and_then(|n| n.as_str()).map(String::from)
+            }
+            _ => None,
+        };
+        nested
+            .or_else(|| self.team_name.clone())
+            .filter(|t| !t.trim().is_empty())
+    }
 }
 
 /// Wrapper for v1 player list response: `{active: [...], deleted: [...]}`
@@ -560,6 +634,8 @@ struct PlayerStats {
     losses: u32,
     draws: u32,
     battle_points: u32,
+    bracket_stage: Option<BracketStage>,
+    team: Option<String>,
 }
 
 // ── BCP client implementation ───────────────────────────────────────────────
@@ -617,10 +693,14 @@ impl BcpClient {
         Ok(events)
     }
 
+    /// URL of an event's player list in the v1 API.
+    pub fn players_url(&self, event_id: &str) -> String {
+        format!("{}/events/{}/players?limit=500", self.api_base, event_id)
+    }
+
     /// Fetch players for an event from the v1 API.
     pub async fn fetch_players(&self, event_id: &str) -> Result<Vec<BcpPlayerV1>, FetchError> {
-        let url_str = format!("{}/events/{}/players?limit=500", self.api_base, event_id);
-        let url = Url::parse(&url_str)
+        let url = Url::parse(&self.players_url(event_id))
             .map_err(|e| FetchError::InvalidUrl(format!("Bad BCP players URL: {}", e)))?;
 
         info!("BCP: fetching players for event {}", event_id);
@@ -683,112 +763,13 @@ impl BcpClient {
 
     /// Compute standings from pairings data.
     ///
-    /// Aggregates W/L/D and battle points per player from pairings,
-    /// then sorts by wins desc, battle points desc to assign placing.
-    /// Also enriches with player/faction info from the players list.
+    /// See [`standings_from_pairings`].
     pub fn compute_standings(
         &self,
         pairings: &[BcpPairing],
         players: &[BcpPlayerV1],
     ) -> Vec<BcpStanding> {
-        let mut stats: HashMap<String, PlayerStats> = HashMap::new();
-
-        // Build player info lookup by ID
-        let player_info: HashMap<String, &BcpPlayerV1> = players
-            .iter()
-            .filter_map(|p| p.id.as_ref().map(|id| (id.clone(), p)))
-            .collect();
-
-        for pairing in pairings {
-            let meta = match &pairing.meta_data {
-                Some(m) => m,
-                None => continue,
-            };
-
-            // Process player 1
-            if let Some(ref p1) = pairing.player1 {
-                if let Some(ref p1_id) = p1.id {
-                    let entry = stats.entry(p1_id.clone()).or_insert_with(|| {
-                        let mut ps = PlayerStats {
-                            name: p1.full_name(),
-                            player_id: Some(p1_id.clone()),
-                            army_list_object_id: p1.army_list_object_id.clone(),
-                            ..Default::default()
-                        };
-                        // Get faction from player info or pairing
-                        ps.faction = player_info
-                            .get(p1_id)
-                            .and_then(|pi| pi.faction_name())
-                            .or_else(|| p1.army_name.clone());
-                        ps
-                    });
-
-                    match meta.p1_game_result {
-                        Some(2) => entry.wins += 1,
-                        Some(0) => entry.losses += 1,
-                        Some(1) => entry.draws += 1,
-                        _ => {}
-                    }
-                    if let Some(pts) = meta.p1_game_points {
-                        entry.battle_points += pts as u32;
-                    }
-                }
-            }
-
-            // Process player 2
-            if let Some(ref p2) = pairing.player2 {
-                if let Some(ref p2_id) = p2.id {
-                    let entry = stats.entry(p2_id.clone()).or_insert_with(|| {
-                        let mut ps = PlayerStats {
-                            name: p2.full_name(),
-                            player_id: Some(p2_id.clone()),
-                            army_list_object_id: p2.army_list_object_id.clone(),
-                            ..Default::default()
-                        };
-                        ps.faction = player_info
-                            .get(p2_id)
-                            .and_then(|pi| pi.faction_name())
-                            .or_else(|| p2.army_name.clone());
-                        ps
-                    });
-
-                    match meta.p2_game_result {
-                        Some(2) => entry.wins += 1,
-                        Some(0) => entry.losses += 1,
-                        Some(1) => entry.draws += 1,
-                        _ => {}
-                    }
-                    if let Some(pts) = meta.p2_game_points {
-                        entry.battle_points += pts as u32;
-                    }
-                }
-            }
-        }
-
-        // Sort by wins desc, then battle points desc
-        let mut player_stats: Vec<PlayerStats> = stats.into_values().collect();
-        player_stats.sort_by(|a, b| {
-            b.wins
-                .cmp(&a.wins)
-                .then(b.battle_points.cmp(&a.battle_points))
-        });
-
-        // Convert to BcpStanding with placing
-        player_stats
-            .into_iter()
-            .enumerate()
-            .map(|(i, ps)| BcpStanding {
-                placing: Some((i + 1) as u32),
-                player_name: Some(ps.name),
-                faction: ps.faction,
-                wins: Some(ps.wins),
-                losses: Some(ps.losses),
-                draws: Some(ps.draws),
-                total_battle_points: Some(ps.battle_points),
-                player_id: ps.player_id,
-                army_list_object_id: ps.army_list_object_id,
-            })
-            .collect()
+        standings_from_pairings(pairings, players)
     }
 
     /// Fetch standings for an event by fetching pairings and computing results.
@@ -875,429 +856,253 @@ impl BcpClient {
     }
 }
 
-/// Detect a specific Space Marine chapter from army list raw text.
-///
-/// BCP often returns "Space Marines" or "Space Marines (Astartes)" as the faction,
-/// but the raw text contains the actual chapter (Ultramarines, Salamanders, etc.).
-/// Returns `Some("Ultramarines")` etc. if detected, `None` if truly generic.
-pub fn detect_chapter_from_raw_text(raw_text: &str) -> Option<&'static str> {
-    static CHAPTERS: &[&str] = &[
-        "Ultramarines",
-        "Iron Hands",
-        "Salamanders",
-        "Imperial Fists",
-        "Raven Guard",
-        "White Scars",
-        "Crimson Fists",
-        "Flesh Tearers",
-    ];
-
-    // Pattern 1: "Space Marines\nChapterName\n"
-    let re_line = Regex::new(r"(?m)Space Marines\n(\w[\w\s]+)\n").unwrap();
-    if let Some(caps) = re_line.captures(raw_text) {
-        let candidate = caps[1].trim();
-        for &ch in CHAPTERS {
-            if candidate.eq_ignore_ascii_case(ch) {
-                return Some(ch);
-            }
+impl BcpPairing {
+    /// The player who won this game, if a result was reported.
+    pub fn winner(&self) -> Option<&BcpPairingPlayer> {
+        let meta = self.meta_data.as_ref()?;
+        match (meta.p1_game_result, meta.p2_game_result) {
+            (Some(2), _) | (_, Some(0)) => self.player1.as_ref(),
+            (_, Some(2)) | (Some(0), _) => self.player2.as_ref(),
+            _ => None,
         }
     }
 
-    // Pattern 2: "Adeptus Astartes - ChapterName"
-    let re_astartes =
-        Regex::new(r"(?i)Adeptus Astartes\s*-\s*(\w[\w\s]+?)(?:\s*-|\s*\n|\s*\[)").unwrap();
-    if let Some(caps) = re_astartes.captures(raw_text) {
-        let candidate = caps[1].trim();
-        for &ch in CHAPTERS {
-            if candidate.eq_ignore_ascii_case(ch) {
-                return Some(ch);
-            }
-        }
+    fn player_ids(&self) -> impl Iterator<Item = &str> {
+        [self.player1.as_ref(), self.player2.as_ref()]
+            .into_iter()
+            .flatten()
+            .filter_map(|p| p.id.as_deref())
     }
+}
 
-    // Pattern 3: "Space Marines (ChapterName)"
-    let re_parens = Regex::new(r"(?i)Space Marines\s*\((\w[\w\s]+?)\)").unwrap();
-    if let Some(caps) = re_parens.captures(raw_text) {
-        let candidate = caps[1].trim();
-        for &ch in CHAPTERS {
-            if candidate.eq_ignore_ascii_case(ch) {
-                return Some(ch);
-            }
+/// Detect rounds played as a single-elimination top cut after Swiss.
+///
+/// When the event's Swiss round count is known, any later round is a cut
+/// round. Otherwise trailing rounds count as a cut when they end in a
+/// single final, halve in size each round, are smaller than a full Swiss
+/// round, and every player in a round won their game in the round before.
+///
+/// Returns the cut round numbers in ascending order.
+pub fn detect_cut_rounds(pairings: &[BcpPairing], swiss_rounds: Option<u32>) -> Vec<u32> {
+    let mut by_round: BTreeMap<u32, Vec<&BcpPairing>> = BTreeMap::new();
+    for pairing in pairings {
+        if let Some(round) = pairing.round {
+            by_round.entry(round).or_default().push(pairing);
         }
     }
 
-    // Pattern 4: Chapter-specific detachments
-    static DET_MAP: &[(&str, &str)] = &[
-        ("Blade of Ultramar", "Ultramarines"),
-        ("Anvil Siege Force", "Iron Hands"),
-        ("Firestorm Assault Force", "Salamanders"),
-        ("Forgefather", "Salamanders"),
-        ("Stormlance Task Force", "White Scars"),
-        ("Emperor's Shield", "Imperial Fists"),
-    ];
-    let lower = raw_text.to_lowercase();
-    for &(det, ch) in DET_MAP {
-        if lower.contains(&det.to_lowercase()) {
-            return Some(ch);
+    if let Some(swiss) = swiss_rounds {
+        let later: Vec<u32> = by_round.keys().copied().filter(|r| *r > swiss).collect();
+        if !later.is_empty() {
+            return later;
         }
     }
 
-    // Pattern 5: Named characters unique to a chapter
-    static CHAR_MAP: &[(&str, &str)] = &[
-        ("Marneus Calgar", "Ultramarines"),
-        ("Cato Sicarius", "Ultramarines"),
-        ("Roboute Guilliman", "Ultramarines"),
-        ("Uriel Ventris", "Ultramarines"),
-        ("Kayvaan Shrike", "Raven Guard"),
-        ("Iron Father Feirros", "Iron Hands"),
-        ("Adrax Agatone", "Salamanders"),
-        ("Vulkan He'stan", "Salamanders"),
-        ("Tor Garadon", "Imperial Fists"),
-        ("Darnath Lysander", "Imperial Fists"),
-        ("Pedro Kantor", "Crimson Fists"),
-    ];
-    for &(name, ch) in CHAR_MAP {
-        if raw_text.contains(name) {
-            return Some(ch);
-        }
+    let rounds: Vec<u32> = by_round.keys().copied().collect();
+    let swiss_size = by_round.values().map(|p| p.len()).max().unwrap_or(0);
+    let Some(&last) = rounds.last() else {
+        return Vec::new();
+    };
+    if by_round[&last].len() != 1 || swiss_size <= 1 {
+        return Vec::new();
     }
 
-    None
-}
-
-/// Strip common line prefixes used in BCP army list formats.
-///
-/// Handles: `Char1:`, `EH1:`, `CH2:`, `BL3:`, `IN4:`, `VE1:`, `MO1:`, `BE1:`, `DT1:`.
-/// Returns `(stripped_line, prefix_hint)` where prefix_hint helps classify unit roles.
-fn strip_line_prefix(line: &str) -> (&str, Option<&str>) {
-    let re = Regex::new(r"^(?:(Char|EH|CH|BL|IN|VE|MO|BE|DT)\d+:\s*)").unwrap();
-    match re.captures(line) {
-        Some(caps) => {
-            let prefix = caps.get(1).map(|m| m.as_str());
-            (&line[caps.get(0).unwrap().end()..], prefix)
+    let mut cut = vec![last];
+    for window in rounds.windows(2).rev() {
+        let (prev, next) = (&by_round[&window[0]], &by_round[&window[1]]);
+        let winners: HashSet<&str> = prev
+            .iter()
+            .filter_map(|p| p.winner().and_then(|w| w.id.as_deref()))
+            .collect();
+        let advanced = next
+            .iter()
+            .flat_map(|p| p.player_ids())
+            .all(|id| winners.contains(id));
+        if prev.len() == next.len() * 2 && prev.len() < swiss_size && advanced {
+            cut.push(window[0]);
+        } else {
+            break;
         }
-        None => (line, None),
     }
-}
 
-/// Map a line prefix or section header to unit keywords.
-fn keywords_for_section(section: &str) -> Vec<String> {
-    match section {
-        "CHARACTERS" | "CHARACTER" | "Char" | "EH" | "CH" => {
-            vec!["Character".to_string()]
-        }
-        "BATTLELINE" | "BL" | "BE" => vec!["Battleline".to_string()],
-        "OTHER DATASHEETS" => vec!["Other".to_string()],
-        "IN" => vec!["Infantry".to_string()],
-        "DEDICATED TRANSPORTS" | "DT" => vec!["Dedicated Transport".to_string()],
-        "VE" => vec!["Vehicle".to_string()],
-        "MO" => vec!["Monster".to_string()],
-        "ALLIED UNITS" => vec!["Allied".to_string()],
-        "FORTIFICATIONS" => vec!["Fortification".to_string()],
-        _ => Vec::new(),
+    // A cut needs Swiss rounds before it
+    if cut.len() == rounds.len() {
+        return Vec::new();
     }
+    cut.reverse();
+    cut
 }
 
-/// Process buffered gear lines for a unit, classifying model types vs weapons.
-///
-/// Uses indentation depth to distinguish:
-/// - Shallow indent (≤ min+1): model components → sum into unit count
-/// - Deeper indent: weapons → add to wargear with "Nx" prefix when qty > 1
-///
-/// If all lines are at the same indent (flat), treats them all as weapons.
-fn flush_gear_buffer(buffer: &mut Vec<(usize, u32, String)>, units: &mut [Unit]) {
-    if buffer.is_empty() || units.is_empty() {
-        buffer.clear();
-        return;
-    }
-    let unit = units.last_mut().unwrap();
-
-    let min_indent = buffer.iter().map(|g| g.0).min().unwrap_or(0);
-    let max_indent = buffer.iter().map(|g| g.0).max().unwrap_or(0);
-    let has_sub_levels = max_indent > min_indent + 1;
-
-    if has_sub_levels {
-        // Multi-level: top-level items are model components, deeper are weapons
-        let mut model_count = 0u32;
-        for (indent, qty, name) in buffer.iter() {
-            if *indent <= min_indent + 1 {
-                // Model component → contributes to squad count
-                model_count += qty;
-            } else {
-                // Weapon line
-                let formatted = if *qty > 1 {
-                    format!("{}x {}", qty, name)
-                } else {
-                    name.clone()
-                };
-                unit.wargear.push(formatted);
-            }
-        }
-        if model_count > 0 {
-            unit.count = model_count;
+/// Furthest bracket stage per player ID, and the ID of the champion.
+fn bracket_results(
+    cut_pairings: &[&BcpPairing],
+    cut_rounds: &[u32],
+) -> (HashMap<String, BracketStage>, Option<String>) {
+    let mut stages: HashMap<String, BracketStage> = HashMap::new();
+    let mut champion = None;
+    let Some(&final_round) = cut_rounds.last() else {
+        return (stages, champion);
+    };
+
+    for pairing in cut_pairings {
+        let Some(round) = pairing.round else {
+            continue;
+        };
+        let stage = BracketStage::from_rounds_remaining(final_round.saturating_sub(round));
+        for id in pairing.player_ids() {
+            let entry = stages.entry(id.to_string()).or_insert(stage);
+            *entry = (*entry).min(stage);
         }
-    } else {
-        // Flat: all lines are direct weapons/gear
-        for (_, qty, name) in buffer.iter() {
-            if name.starts_with("Enhancement") || name.starts_with("Warlord") {
-                continue;
-            }
-            let formatted = if *qty > 1 {
-                format!("{}x {}", qty, name)
-            } else {
-                name.clone()
-            };
-            unit.wargear.push(formatted);
+        if round == final_round {
+            champion = pairing.winner().and_then(|w| w.id.clone());
         }
     }
-    buffer.clear();
+    (stages, champion)
 }
 
-/// Parse inline wargear from text after the points value.
+/// Compute standings from pairings data.
 ///
-/// Handles: `Unit (120 pts) 4x Multi-melta, 1x Inferno pistol`
-/// Returns wargear items like `["4x Multi-melta", "Inferno pistol"]`.
-fn parse_inline_wargear(text_after_points: &str) -> Vec<String> {
-    let re_item = Regex::new(r"(\d+)x\s+([^,]+)").unwrap();
-    let mut wargear = Vec::new();
-    for caps in re_item.captures_iter(text_after_points) {
-        let qty: u32 = caps[1].parse().unwrap_or(1);
-        let name = caps[2].trim().to_string();
-        if !name.is_empty() {
-            let formatted = if qty > 1 {
-                format!("{}x {}", qty, name)
-            } else {
-                name
-            };
-            wargear.push(formatted);
-        }
-    }
-    wargear
+/// Equivalent to [`standings_with_cut`] without a known Swiss round count.
+pub fn standings_from_pairings(
+    pairings: &[BcpPairing],
+    players: &[BcpPlayerV1],
+) -> Vec<BcpStanding> {
+    standings_with_cut(pairings, players, None)
 }
 
-/// Parse units from BCP army list raw text using regex.
-///
-/// Extracts unit names, points, counts, wargear (from bullet/indented lines
-/// and inline comma-separated gear), and keywords (from section headers / line
-/// prefixes).
-///
-/// Handles three BCP list formats:
-/// 1. **Parenthesized**: `Unit Name (XXpts)` — most common (~60%)
-/// 2. **Bracket**: `Unit Name [XXX pts]` — Russian tournament template (~20%)
-/// 3. **Dash**: `1 Unit Name - XXpts` — a few tournament organizers (~1%)
+/// Compute standings from pairings data, accounting for a top cut.
 ///
-/// Returns an empty Vec if no units could be parsed (signals AI fallback).
-pub fn parse_units_from_raw_text(raw_text: &str) -> Vec<Unit> {
-    // Format 1: "Unit Name (XXpts)" or "2x Unit Name (XX points)"
-    // Captures optional trailing text after the closing paren for inline wargear
-    let re_parens =
-        Regex::new(r"(?i)^(?:(\d+)x?\s+)?(.+?)\s*\((\d+)\s*(?:pts?|points?)\)(.*)").unwrap();
-    // Format 2: "Unit Name [XXX pts]" (bracket format, Russian template)
-    let re_bracket =
-        Regex::new(r"(?i)^(?:\((\d+)\)\s+)?(.+?)\s*\[(\d+)\s*(?:pts?|points?)\](.*)").unwrap();
-    // Format 3: "1 Unit Name - XXpts" (dash format)
-    let re_dash = Regex::new(r"(?i)^(\d+)\s+(.+?)\s*[-–]\s*(\d+)\s*(?:pts?|points?)(.*)").unwrap();
-    // Wargear line: "• 1x Storm bolter" or "  1x Bolt rifle" or "- 1x Weapon"
-    let re_wargear = Regex::new(r"(?:^[\u{2022}\u{25e6}\u{2013}*\-]\s*)?(\d+)x\s+(.+)").unwrap();
-    // Skip "N with ..." wargear description lines
-    let re_n_with = Regex::new(r"^\d+ with ").unwrap();
-    // Extract (N) model count from name, e.g. "Retributor Squad (5)" → count=5
-    let re_name_count = Regex::new(r"^(.+?)\s*\((\d+)\)\s*$").unwrap();
-
-    let section_headers: std::collections::HashSet<&str> = [
-        "CHARACTERS",
-        "BATTLELINE",
-        "OTHER DATASHEETS",
-        "ALLIED UNITS",
-        "CHARACTER",
-        "DEDICATED TRANSPORTS",
-        "FORTIFICATIONS",
-    ]
-    .into_iter()
-    .collect();
-
-    let skip_names: std::collections::HashSet<&str> =
-        ["strike force", "incursion", "onslaught", "army roster"]
-            .into_iter()
-            .collect();
+/// Aggregates W/L/D and battle points per player from the Swiss rounds,
+/// then sorts by wins desc, battle points desc to assign placing.
+/// If the event had a cut (see [`detect_cut_rounds`]), players in the
+/// bracket are placed first by how far they got (champion, finalist,
+/// semifinalists, ...), ties keeping their Swiss order.
+/// Also enriches with player/faction info from the players list.
+pub fn standings_with_cut(
+    pairings: &[BcpPairing],
+    players: &[BcpPlayerV1],
+    swiss_rounds: Option<u32>,
+) -> Vec<BcpStanding> {
+    let cut_rounds = detect_cut_rounds(pairings, swiss_rounds);
+    let (cut_pairings, swiss_pairings): (Vec<&BcpPairing>, Vec<&BcpPairing>) = pairings
+        .iter()
+        .partition(|p| p.round.is_some_and(|r| cut_rounds.contains(&r)));
 
-    let mut units: Vec<Unit> = Vec::new();
-    let mut current_section = String::new();
-    // Buffer for gear lines: (indent, qty, name)
-    let mut gear_buffer: Vec<(usize, u32, String)> = Vec::new();
+    let mut stats: HashMap<String, PlayerStats> = HashMap::new();
 
-    for raw_line in raw_text.lines() {
-        let indent = raw_line.len() - raw_line.trim_start().len();
-        let line = raw_line.trim();
-        if line.is_empty() {
-            continue;
-        }
+    // Build player info lookup by ID
+    let player_info: HashMap<String, &BcpPlayerV1> = players
+        .iter()
+        .filter_map(|p| p.id.as_ref().map(|id| (id.clone(), p)))
+        .collect();
 
-        // Track section headers for keyword assignment
-        if section_headers.contains(line) {
-            flush_gear_buffer(&mut gear_buffer, &mut units);
-            current_section = line.to_string();
-            continue;
-        }
-        // Also handle "Epic Hero:", "Character:", "Battleline:" labels
-        let stripped_colon = line.trim_end_matches(':');
-        if line.ends_with(':') && line.len() < 25 && !line.contains('[') && !line.contains('(') {
-            flush_gear_buffer(&mut gear_buffer, &mut units);
-            match stripped_colon {
-                "Epic Hero" | "Character" | "Characters" => {
-                    current_section = "CHARACTER".to_string();
-                }
-                "Battleline" => current_section = "BATTLELINE".to_string(),
-                "Other Datasheets" | "Other" => {
-                    current_section = "OTHER DATASHEETS".to_string();
+    for pairing in swiss_pairings {
+        let meta = match &pairing.meta_data {
+            Some(m) => m,
+            None => continue,
+        };
+
+        // Process player 1
+        if let Some(ref p1) = pairing.player1 {
+            if let Some(ref p1_id) = p1.id {
+                let entry = stats.entry(p1_id.clone()).or_insert_with(|| {
+                    let mut ps = PlayerStats {
+                        name: p1.full_name(),
+                        player_id: Some(p1_id.clone()),
+                        army_list_object_id: p1.army_list_object_id.clone(),
+                        ..Default::default()
+                    };
+                    // Get faction from player info or pairing
+                    ps.faction = player_info
+                        .get(p1_id)
+                        .and_then(|pi| pi.faction_name())
+                        .or_else(|| p1.army_name.clone());
+                    ps.team = player_info.get(p1_id).and_then(|pi| pi.team());
+                    ps
+                });
+
+                match meta.p1_game_result {
+                    Some(2) => entry.wins += 1,
+                    Some(0) => entry.losses += 1,
+                    Some(1) => entry.draws += 1,
+                    _ => {}
                 }
-                "Dedicated Transports" => {
-                    current_section = "DEDICATED TRANSPORTS".to_string();
+                if let Some(pts) = meta.p1_game_points {
+                    entry.battle_points += pts as u32;
                 }
-                _ => {}
             }
-            continue;
         }
 
-        // Gear line detection: bullet-prefixed OR indented Nx lines (without points)
-        let starts_with_bullet = line.starts_with('\u{2022}')
-            || line.starts_with('\u{25e6}')
-            || line.starts_with('\u{2013}')
-            || line.starts_with('*')
-            || line.starts_with("- ");
-        let has_points = line.contains("pts") || line.contains("points") || line.contains("Points");
-        let is_indented_nx =
-            indent >= 2 && !units.is_empty() && !has_points && re_wargear.is_match(line);
-
-        if starts_with_bullet || is_indented_nx {
-            if !units.is_empty() {
-                if let Some(caps) = re_wargear.captures(line) {
-                    let qty: u32 = caps[1].parse().unwrap_or(1);
-                    let name = caps[2].trim().to_string();
-                    if !name.is_empty() {
-                        gear_buffer.push((indent, qty, name));
-                    }
+        // Process player 2
+        if let Some(ref p2) = pairing.player2 {
+            if let Some(ref p2_id) = p2.id {
+                let entry = stats.entry(p2_id.clone()).or_insert_with(|| {
+                    let mut ps = PlayerStats {
+                        name: p2.full_name(),
+                        player_id: Some(p2_id.clone()),
+                        army_list_object_id: p2.army_list_object_id.clone(),
+                        ..Default::default()
+                    };
+                    ps.faction = player_info
+                        .get(p2_id)
+                        .and_then(|pi| pi.faction_name())
+                        .or_else(|| p2.army_name.clone());
+                    ps.team = player_info.get(p2_id).and_then(|pi| pi.team());
+                    ps
+                });
+
+                match meta.p2_game_result {
+                    Some(2) => entry.wins += 1,
+                    Some(0) => entry.losses += 1,
+                    Some(1) => entry.draws += 1,
+                    _ => {}
+                }
+                if let Some(pts) = meta.p2_game_points {
+                    entry.battle_points += pts as u32;
                 }
             }
-            continue;
-        }
-
-        // Skip non-unit lines
-        if line.starts_with("Enhancement:") || line.starts_with("Warlord") {
-            continue;
-        }
-        if line.starts_with("Exported with") || line == "undefined" {
-            continue;
-        }
-        if line.starts_with('+') || line.starts_with('#') {
-            continue;
-        }
-        if line.contains("(+") {
-            continue;
-        }
-        if line.starts_with("==") {
-            continue;
-        }
-        if re_n_with.is_match(line) {
-            continue;
         }
+    }
 
-        // Strip common line prefixes (Char1:, EH1:, CH2:, etc.)
-        let (stripped, prefix_hint) = strip_line_prefix(line);
-
-        // Try parenthesized format first, then bracket, then dash
-        let parsed = re_parens
-            .captures(stripped)
-            .map(|caps| {
-                let count: u32 = caps.get(1).map_or(1, |m| m.as_str().parse().unwrap_or(1));
-                let name = caps[2].trim().to_string();
-                let points: u32 = caps[3].parse().unwrap_or(0);
-                let trailing = caps.get(4).map_or("", |m| m.as_str());
-                (count, name, points, trailing.to_string())
-            })
-            .or_else(|| {
-                re_bracket.captures(stripped).map(|caps| {
-                    let count: u32 = caps.get(1).map_or(1, |m| m.as_str().parse().unwrap_or(1));
-                    let raw_name = caps[2].trim();
-                    let name = raw_name
-                        .split(',')
-                        .next()
-                        .unwrap_or(raw_name)
-                        .trim()
-                        .to_string();
-                    let points: u32 = caps[3].parse().unwrap_or(0);
-                    let trailing = caps.get(4).map_or("", |m| m.as_str());
-                    (count, name, points, trailing.to_string())
-                })
-            })
-            .or_else(|| {
-                re_dash.captures(stripped).map(|caps| {
-                    let count: u32 = caps[1].parse().unwrap_or(1);
-                    let name = caps[2].trim().to_string();
-                    let points: u32 = caps[3].parse().unwrap_or(0);
-                    let trailing = caps.get(4).map_or("", |m| m.as_str());
-                    (count, name, points, trailing.to_string())
-                })
-            });
-
-        if let Some((count, name, points, trailing)) = parsed {
-            if skip_names.contains(name.to_lowercase().as_str()) {
-                continue;
-            }
-            if name.is_empty() || points == 0 {
-                continue;
-            }
-            // Skip army total lines (no single unit costs 800+ pts)
-            if points >= 800 {
-                continue;
-            }
-            if name.starts_with("ENHANCEMENT") {
-                continue;
-            }
-
-            let (clean_name, count) = if let Some(nc) = re_name_count.captures(&name) {
-                let n = nc[1].trim().to_string();
-                let c: u32 = nc[2].parse().unwrap_or(count);
-                (n, c)
-            } else {
-                (
-                    name.replace(": Warlord", "")
-                        .replace(": ENHANCEMENT", "")
-                        .trim()
-                        .to_string(),
-                    count,
-                )
-            };
-
-            // Determine keywords from section header or line prefix
-            let keywords = if let Some(prefix) = prefix_hint {
-                keywords_for_section(prefix)
-            } else if !current_section.is_empty() {
-                keywords_for_section(&current_section)
-            } else {
-                Vec::new()
-            };
-
-            // Flush gear buffer for the previous unit before pushing new one
-            flush_gear_buffer(&mut gear_buffer, &mut units);
-
-            // Parse inline wargear from trailing text after points
-            let inline_wargear = parse_inline_wargear(&trailing);
+    // Sort by wins desc, then battle points desc
+    let mut player_stats: Vec<PlayerStats> = stats.into_values().collect();
+    player_stats.sort_by(|a, b| {
+        b.wins
+            .cmp(&a.wins)
+            .then(b.battle_points.cmp(&a.battle_points))
+    });
 
-            let mut unit = Unit::new(clean_name, count)
-                .with_points(points)
-                .with_keywords(keywords);
-            if !inline_wargear.is_empty() {
-                unit = unit.with_wargear(inline_wargear);
-            }
-            units.push(unit);
+    // Bracket results override Swiss order for players who made the cut
+    let (stages, champion) = bracket_results(&cut_pairings, &cut_rounds);
+    if !stages.is_empty() {
+        for ps in &mut player_stats {
+            ps.bracket_stage = ps.player_id.as_ref().and_then(|id| stages.get(id)).copied();
         }
+        player_stats.sort_by_key(|ps| match ps.bracket_stage {
+            Some(_) if ps.player_id.is_some() && ps.player_id == champion => 0,
+            Some(stage) => 1 + stage as u32,
+            None => u32::MAX,
+        });
     }
 
-    // Final flush for the last unit's gear lines
-    flush_gear_buffer(&mut gear_buffer, &mut units);
-
-    units
+    // Convert to BcpStanding with placing
+    player_stats
+        .into_iter()
+        .enumerate()
+        .map(|(i, ps)| BcpStanding {
+            placing: Some((i + 1) as u32),
+            player_name: Some(ps.name),
+            faction: ps.faction,
+            wins: Some(ps.wins),
+            losses: Some(ps.losses),
+            draws: Some(ps.draws),
+            total_battle_points: Some(ps.battle_points),
+            player_id: ps.player_id,
+            army_list_object_id: ps.army_list_object_id,
+            bracket_stage: ps.bracket_stage,
+            team: ps.team,
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -1321,6 +1126,7 @@ mod tests {
             ended: None,
             team_event: None,
             hide_placings: None,
+            points_limit: None,
         };
 
         assert_eq!(
@@ -1346,6 +1152,7 @@ mod tests {
             ended: None,
             team_event: None,
             hide_placings: None,
+            points_limit: None,
         };
 
         assert_eq!(
@@ -1371,6 +1178,7 @@ mod tests {
             ended: None,
             team_event: None,
             hide_placings: None,
+            points_limit: None,
         };
 
         assert_eq!(
@@ -1396,6 +1204,7 @@ mod tests {
             ended: None,
             team_event: None,
             hide_placings: None,
+            points_limit: None,
         };
 
         assert!(!event.should_skip());
@@ -1708,6 +1517,77 @@ mod tests {
         }
     }
 
+    fn cut_game(round: u32, a: usize, b: usize, a_wins: bool) -> BcpPairing {
+        let player = |i: usize| BcpPairingPlayer {
+            id: Some(format!("p{}", i)),
+            first_name: Some(format!("Player{}", i)),
+            last_name: None,
+            army_name: Some("Orks".to_string()),
+            army_list_object_id: None,
+        };
+        let (r1, r2) = if a_wins { (2, 0) } else { (0, 2) };
+        BcpPairing {
+            player1: Some(player(a)),
+            player2: Some(player(b)),
+            meta_data: Some(BcpPairingMeta {
+                p1_game_result: Some(r1),
+                p1_game_points: Some((90 - a) as f64),
+                p2_game_result: Some(r2),
+                p2_game_points: Some((90 - b) as f64),
+            }),
+            round: Some(round),
+        }
+    }
+
+    /// 8 players, 3 Swiss rounds (evens always win), then a top-4 cut.
+    fn swiss_then_top4() -> Vec<BcpPairing> {
+        let mut pairings = Vec::new();
+        for round in 1..=3 {
+            for a in [0, 2, 4, 6] {
+                pairings.push(cut_game(round, a, a + 1, true));
+            }
+        }
+        pairings.push(cut_game(4, 0, 6, false));
+        pairings.push(cut_game(4, 2, 4, true));
+        pairings.push(cut_game(5, 6, 2, true));
+        pairings
+    }
+
+    #[test]
+    fn test_detect_cut_rounds() {
+        let pairings = swiss_then_top4();
+        assert_eq!(detect_cut_rounds(&pairings, None), vec![4, 5]);
+        assert_eq!(detect_cut_rounds(&pairings, Some(3)), vec![4, 5]);
+
+        // Plain Swiss: last round is full-size, no cut
+        let swiss_only: Vec<BcpPairing> = pairings
+            .iter()
+            .filter(|p| p.round <= Some(3))
+            .cloned()
+            .collect();
+        assert!(detect_cut_rounds(&swiss_only, None).is_empty());
+
+        // Two-player event: every round is a single game, not a cut
+        let tiny = vec![cut_game(1, 0, 1, true), cut_game(2, 0, 1, false)];
+        assert!(detect_cut_rounds(&tiny, None).is_empty());
+    }
+
+    #[test]
+    fn test_standings_with_cut() {
+        let standings = standings_with_cut(&swiss_then_top4(), &[], None);
+        let order: Vec<&str> = standings
+            .iter()
+            .map(|s| s.player_id.as_deref().unwrap())
+            .collect();
+        // Champion, finalist, semifinal losers in Swiss order, then the rest
+        assert_eq!(order[..4], ["p6", "p2", "p0", "p4"]);
+        assert_eq!(standings[0].bracket_stage, Some(BracketStage::Final));
+        assert_eq!(standings[2].bracket_stage, Some(BracketStage::Semifinal));
+        assert_eq!(standings[4].bracket_stage, None);
+        // Records stay Swiss-only
+        assert_eq!((standings[0].wins, standings[0].losses), (Some(3), Some(0)));
+    }
+
     #[test]
     fn test_compute_standings_empty() {
         let fetcher = Fetcher::new(crate::fetch::FetcherConfig {
@@ -1742,6 +1622,8 @@ mod tests {
             list_id: Some("list-1".to_string()),
             dropped: None,
             army_name: None,
+            team: Some(serde_json::json!({"name": "Vanguard Tactics"})),
+            team_name: None,
         }];
 
         let pairings = vec![BcpPairing {
@@ -1776,6 +1658,7 @@ mod tests {
             .find(|s| s.player_name == Some("Alice A".to_string()))
             .unwrap();
         assert_eq!(alice.faction, Some("Necrons".to_string()));
+        assert_eq!(alice.team.as_deref(), Some("Vanguard Tactics"));
 
         // Bob gets faction from pairing
         let bob = standings
@@ -1784,621 +1667,4 @@ mod tests {
             .unwrap();
         assert_eq!(bob.faction, Some("Aeldari".to_string()));
     }
-
-    // ── parse_units_from_raw_text tests ────────────────────────────────────
-
-    #[test]
-    fn test_parse_standard_bcp_format() {
-        let raw = r#"GK Army (1975 points)
-
-Grey Knights
-Strike Force (2000 points)
-Teleport Strike Force
-
-
-CHARACTERS
-
-Brotherhood Librarian (150 points)
-  • 1x Combi-weapon
-    1x Nemesis force weapon
-  • Enhancement: Sigil of Exigence
-
-Castellan Crowe (90 points)
-  • 1x Black Blade of Antwyr
-    1x Storm bolter
-
-Grand Master Voldus (95 points)
-  • Warlord
-  • 1x Malleus Argyrum
-
-BATTLELINE
-
-Brotherhood Terminator Squad (200 points)
-  • 4x Brotherhood Terminator
-
-Brotherhood Terminator Squad (200 points)
-  • 4x Brotherhood Terminator
-
-OTHER DATASHEETS
-
-Nemesis Dreadknight (245 points)
-  • 1x Heavy psycannon
-
-Nemesis Dreadknight (245 points)
-  • 1x Heavy psycannon
-"#;
-        let units = parse_units_from_raw_text(raw);
-        // Should parse: Librarian(150), Crowe(90), Voldus(95), 2x Terminator(200),
-        //               2x Dreadknight(245) = 7 units minimum
-        // "Strike Force" and army name lines should be skipped
-        assert!(units.len() >= 7, "Expected >= 7 units, got {}", units.len());
-
-        // Check specific units
-        let librarian = units
-            .iter()
-            .find(|u| u.name.contains("Brotherhood Librarian"));
-        assert!(librarian.is_some(), "Should find Brotherhood Librarian");
-        assert_eq!(librarian.unwrap().points, Some(150));
-
-        let crowe = units.iter().find(|u| u.name.contains("Crowe"));
-        assert!(crowe.is_some(), "Should find Castellan Crowe");
-        assert_eq!(crowe.unwrap().points, Some(90));
-
-        // Strike Force should be skipped
-        let sf = units
-            .iter()
-            .find(|u| u.name.to_lowercase().contains("strike force"));
-        assert!(sf.is_none(), "Strike Force should be skipped");
-    }
-
-    #[test]
-    fn test_parse_char_prefix_pts_format() {
-        let raw = r#"+++++++++++++++++++++++++++++++++++++++++++++++
-+ FACTION KEYWORD: Xenos - Tyranids
-+ DETACHMENT: Crusher Stampede
-+ TOTAL ARMY POINTS: 2000pts
-+++++++++++++++++++++++++++++++++++++++++++++++
-
-CHARACTER
-
-Char1: 1x Old One Eye (150 pts)
-1 with Old One Eye's claws and talons
-
-Char2: 1x Hive Tyrant (235 pts)
-1 with Monstrous Bonesword and Lash Whip
-  • Warlord
-
-Char3: 1x Neurotyrant (105 pts)
-
-BATTLELINE
-
-10x Gargoyles (85 pts)
-
-OTHER DATASHEETS
-
-1x Biovores (50 pts)
-3x Screamer-Killers (375 pts)
-2x Carnifexes (180 pts)
-"#;
-        let units = parse_units_from_raw_text(raw);
-        assert!(units.len() >= 7, "Expected >= 7 units, got {}", units.len());
-
-        // Old One Eye with Char prefix
-        let ooe = units.iter().find(|u| u.name.contains("Old One Eye"));
-        assert!(ooe.is_some(), "Should find Old One Eye");
-        assert_eq!(ooe.unwrap().points, Some(150));
-        assert_eq!(ooe.unwrap().count, 1);
-
-        // 10x Gargoyles
-        let gargs = units.iter().find(|u| u.name.contains("Gargoyles"));
-        assert!(gargs.is_some(), "Should find Gargoyles");
-        assert_eq!(gargs.unwrap().count, 10);
-        assert_eq!(gargs.unwrap().points, Some(85));
-
-        // 3x Screamer-Killers
-        let sk = units.iter().find(|u| u.name.contains("Screamer-Killers"));
-        assert!(sk.is_some(), "Should find Screamer-Killers");
-        assert_eq!(sk.unwrap().count, 3);
-        assert_eq!(sk.unwrap().points, Some(375));
-    }
-
-    #[test]
-    fn test_parse_bracket_format() {
-        let raw = r#"+++++++++++++++++++++++++++++++++++++++++++++++
-+FACTION KEYWORD: Imperium - Adeptus Custodes
-+DETACHMENT: Lions of the Emperor
-+TOTAL ARMY POINTS: 2000 pts
-+++++++++++++++++++++++++++++++++++++++++++++++
-EH1: Trajann Valoris [140 pts]
-BL1: (4) Custodian Guard, Guardian Spear [150 pts]
-CH1: Blade Champion [120 pts]
-CH2: Shield-captain In Allarus Terminator Armour, Castellan axe, Admonimortis [140 pts]
-IN1: (2) Allarus Custodians, Guardian Spear [110 pts]
-IN2: (4) Custodian Wardens, Guardian Spear, Vexilla [210 pts]
-IN3: (5) Witchseekers, Witchseeker Flamer [55 pts]
-VE1: Venerable Land Raider, Hunter-killer missile [220 pts]
-"#;
-        let units = parse_units_from_raw_text(raw);
-        assert_eq!(units.len(), 8, "Expected 8 units, got {:?}", units);
-
-        // Trajann Valoris (EH prefix, no count)
-        let trajann = units.iter().find(|u| u.name.contains("Trajann Valoris"));
-        assert!(trajann.is_some(), "Should find Trajann Valoris");
-        assert_eq!(trajann.unwrap().points, Some(140));
-        assert_eq!(trajann.unwrap().count, 1);
-
-        // Custodian Guard with (4) count prefix
-        let guard = units.iter().find(|u| u.name.contains("Custodian Guard"));
-        assert!(guard.is_some(), "Should find Custodian Guard");
-        assert_eq!(guard.unwrap().count, 4);
-        assert_eq!(guard.unwrap().points, Some(150));
-
-        // Shield-captain — should strip wargear after comma
-        let sc = units.iter().find(|u| u.name.contains("Shield-captain"));
-        assert!(sc.is_some(), "Should find Shield-captain");
-        assert_eq!(sc.unwrap().points, Some(140));
-
-        // Allarus Custodians with (2) count
-        let allarus = units.iter().find(|u| u.name.contains("Allarus Custodians"));
-        assert!(allarus.is_some(), "Should find Allarus Custodians");
-        assert_eq!(allarus.unwrap().count, 2);
-    }
-
-    #[test]
-    fn test_parse_dash_format() {
-        let raw = r#"Sneaky Beaky Bois
-Detachment: Vanguard Spearhead
-
-1 Kayvaan Shrike - 100 pts
-- 1x Blackout
-- 1x The Raven's Talons
-
-1 Captain - 80 pts
-- 1x Master-crafted power sword
-
-1 Librarian in Phobos armour - 70 pts
-- 1x Smite
-- 1x Force weapon
-
-5 Vanguard Veterans - 110 pts
-- 5x Vanguard Veteran Weapon
-"#;
-        let units = parse_units_from_raw_text(raw);
-        assert_eq!(units.len(), 4, "Expected 4 units, got {:?}", units);
-
-        let shrike = units
-            .iter()
-            .find(|u| u.name.contains("Kayvaan Shrike"))
-            .unwrap();
-        assert_eq!(shrike.points, Some(100));
-        assert_eq!(shrike.count, 1);
-        assert_eq!(shrike.wargear, vec!["Blackout", "The Raven's Talons"]);
-
-        let captain = units.iter().find(|u| u.name == "Captain").unwrap();
-        assert_eq!(captain.wargear, vec!["Master-crafted power sword"]);
-
-        let librarian = units.iter().find(|u| u.name.contains("Librarian")).unwrap();
-        assert_eq!(librarian.wargear, vec!["Smite", "Force weapon"]);
-
-        let vets = units
-            .iter()
-            .find(|u| u.name.contains("Vanguard Veterans"))
-            .unwrap();
-        assert_eq!(vets.count, 5);
-        assert_eq!(vets.points, Some(110));
-        assert_eq!(vets.wargear, vec!["5x Vanguard Veteran Weapon"]);
-    }
-
-    #[test]
-    fn test_parse_bracket_format_with_warlord_suffix() {
-        // Some bracket-format lists have ": Warlord" after the unit name
-        let raw = r#"EH1: Mortarion: Warlord [380 pts]
-EH2: Typhus [100 pts]
-CH1: Daemon Prince of Nurgle [215 pts]
-IN1: Blightlord Terminators (10), 2x blight launcher [370 pts]
-IN2: Deathshroud Terminators (3), 1x Icon of Despair [160 pts]
-BL1: Poxwalkers (10) [65 pts]
-"#;
-        let units = parse_units_from_raw_text(raw);
-        assert_eq!(units.len(), 6, "Expected 6 units, got {:?}", units);
-
-        // Mortarion should have Warlord stripped from name
-        let mort = units.iter().find(|u| u.name.contains("Mortarion"));
-        assert!(mort.is_some(), "Should find Mortarion");
-        assert_eq!(mort.unwrap().points, Some(380));
-        assert!(
-            !mort.unwrap().name.contains("Warlord"),
-            "Warlord should be stripped from name"
-        );
-    }
-
-    #[test]
-    fn test_parse_inline_pts_on_same_line_as_wargear() {
-        // Russian format: unit name + model count in parens + points, wargear on same line
-        let raw = r#"Bjorn the Fell-Handed (160 points) 1x Multi-melta
-Iron Priest (80 points) Warlord Enhancement: Chariots of the Storm
-Logan Grimnar (110 points)
-Ragnar Blackmane (100 points)
-Blood Claws (20) (285 points) 1x Power weapon
-Intercessor Squad (5) (80 points) 1x Astartes grenade launcher
-Fenrisian Wolves (40 points)
-Gladiator Lancer (160 points) 2x Storm bolter
-"#;
-        let units = parse_units_from_raw_text(raw);
-        assert!(units.len() >= 6, "Expected >= 6 units, got {}", units.len());
-
-        let bjorn = units.iter().find(|u| u.name.contains("Bjorn"));
-        assert!(bjorn.is_some(), "Should find Bjorn");
-        assert_eq!(bjorn.unwrap().points, Some(160));
-
-        // Fenrisian Wolves (40 points) — number looks like count but it's points
-        let wolves = units.iter().find(|u| u.name.contains("Fenrisian Wolves"));
-        assert!(wolves.is_some(), "Should find Fenrisian Wolves");
-        assert_eq!(wolves.unwrap().points, Some(40));
-    }
-
-    #[test]
-    fn test_parse_skips_enhancement_lines() {
-        let raw = r#"CHARACTERS
-
-Brotherhood Librarian (150 points)
-  • Enhancement: Sigil of Exigence
-  • (+20 pts)
-
-OTHER DATASHEETS
-
-Nemesis Dreadknight (245 points)
-"#;
-        let units = parse_units_from_raw_text(raw);
-        // Should only find 2 units, not the enhancement line
-        assert_eq!(units.len(), 2, "Expected 2 units, got {:?}", units);
-        assert_eq!(units[0].keywords, vec!["Character"]);
-        assert_eq!(units[1].keywords, vec!["Other"]);
-    }
-
-    #[test]
-    fn test_parse_skips_wargear_bullet_lines() {
-        let raw = r#"Castellan Crowe (90 points)
-  • 1x Black Blade of Antwyr
-  - 1x Storm bolter
-Nemesis Dreadknight (245 points)
-  • 1x Heavy psycannon
-"#;
-        let units = parse_units_from_raw_text(raw);
-        // Should only find 2 units (Crowe, Dreadknight), not wargear lines
-        assert_eq!(units.len(), 2, "Expected 2 units, got {:?}", units);
-        // Wargear should be attached to the correct units
-        assert_eq!(
-            units[0].wargear,
-            vec!["Black Blade of Antwyr", "Storm bolter"]
-        );
-        assert_eq!(units[1].wargear, vec!["Heavy psycannon"]);
-    }
-
-    #[test]
-    fn test_parse_skips_header_lines() {
-        let raw = r#"+++++++++++++++++++++++++++++++++++++++++++++++
-+ FACTION KEYWORD: Space Marines
-+ DETACHMENT: Ironstorm
-+ TOTAL ARMY POINTS: 2000pts
-+++++++++++++++++++++++++++++++++++++++++++++++
-
-# Army Roster
-
-## Character [200 pts]
-
-== Ironstorm Army Roster ==
-
-Captain (80 points)
-"#;
-        let units = parse_units_from_raw_text(raw);
-        assert_eq!(units.len(), 1, "Expected 1 unit, got {:?}", units);
-        assert_eq!(units[0].name, "Captain");
-    }
-
-    #[test]
-    fn test_parse_returns_empty_for_no_points_format() {
-        // Lists without per-unit points should return empty (needs AI)
-        let raw = r#"Aeldari - Warhost
-2000 Points
-
-Maugan Ra
-- Maugetar
-
-5x Swooping Hawks
-- 4x Lasblasters
-
-10x Dark Reapers
-- 10x Reaper Launchers
-"#;
-        let units = parse_units_from_raw_text(raw);
-        assert!(
-            units.is_empty(),
-            "Should return empty for no-points format, got {:?}",
-            units
-        );
-    }
-
-    #[test]
-    fn test_parse_returns_empty_for_whitespace_tabulated() {
-        // Whitespace-aligned columns — impossible to parse with simple regex
-        let raw = r#"GREY KNIGHTS
-
-Brotherhood Strike
-
-GrandMaster                                                                95
-       Purity of purpose                                                   15
-
-Paladin Squad  (10)                                                       450
-
-Librarians  x2                                                 80        160
-"#;
-        let units = parse_units_from_raw_text(raw);
-        assert!(
-            units.is_empty(),
-            "Should return empty for whitespace-tabulated format, got {:?}",
-            units
-        );
-    }
-
-    #[test]
-    fn test_parse_total_points_accuracy() {
-        // Test that total points sum makes sense for a standard list
-        let raw = r#"CHARACTERS
-
-Captain (80 points)
-Librarian (70 points)
-
-BATTLELINE
-
-Intercessor Squad (75 points)
-Intercessor Squad (75 points)
-
-OTHER DATASHEETS
-
-Redemptor Dreadnought (210 points)
-Gladiator Lancer (160 points)
-"#;
-        let units = parse_units_from_raw_text(raw);
-        let total: u32 = units.iter().filter_map(|u| u.points).sum();
-        assert_eq!(total, 670, "Total should be 670pts, got {}", total);
-        assert_eq!(units.len(), 6);
-        // Verify section-based keyword assignment
-        assert_eq!(units[0].keywords, vec!["Character"]);
-        assert_eq!(units[1].keywords, vec!["Character"]);
-        assert_eq!(units[2].keywords, vec!["Battleline"]);
-        assert_eq!(units[3].keywords, vec!["Battleline"]);
-        assert_eq!(units[4].keywords, vec!["Other"]);
-        assert_eq!(units[5].keywords, vec!["Other"]);
-    }
-
-    #[test]
-    fn test_parse_mixed_count_formats() {
-        // Various ways counts appear
-        let raw = r#"1x Old One Eye (150 pts)
-3x Screamer-Killers (375 pts)
-10x Gargoyles (85 pts)
-Neurotyrant (105 pts)
-"#;
-        let units = parse_units_from_raw_text(raw);
-        assert_eq!(units.len(), 4);
-        assert_eq!(units[0].count, 1); // 1x
-        assert_eq!(units[1].count, 3); // 3x
-        assert_eq!(units[2].count, 10); // 10x
-        assert_eq!(units[3].count, 1); // no prefix = 1
-    }
-
-    #[test]
-    fn test_parse_bracket_count_in_parens() {
-        // Bracket format uses (N) before unit name for count
-        let raw = r#"EH1: Trajann Valoris [140 pts]
-BL1: (4) Custodian Guard, Guardian Spear [150 pts]
-IN1: (2) Allarus Custodians [110 pts]
-VE1: Venerable Land Raider [220 pts]
-"#;
-        let units = parse_units_from_raw_text(raw);
-        assert_eq!(units.len(), 4);
-
-        let trajann = units.iter().find(|u| u.name.contains("Trajann")).unwrap();
-        assert_eq!(trajann.count, 1);
-        assert_eq!(trajann.keywords, vec!["Character"]); // EH prefix
-
-        let guard = units
-            .iter()
-            .find(|u| u.name.contains("Custodian Guard"))
-            .unwrap();
-        assert_eq!(guard.count, 4);
-        assert_eq!(guard.keywords, vec!["Battleline"]); // BL prefix
-
-        let allarus = units.iter().find(|u| u.name.contains("Allarus")).unwrap();
-        assert_eq!(allarus.count, 2);
-        assert_eq!(allarus.keywords, vec!["Infantry"]); // IN prefix
-
-        let raider = units
-            .iter()
-            .find(|u| u.name.contains("Land Raider"))
-            .unwrap();
-        assert_eq!(raider.keywords, vec!["Vehicle"]); // VE prefix
-    }
-
-    #[test]
-    fn test_parse_does_not_include_game_size() {
-        let raw = r#"Strike Force (2000 points)
-Incursion (1000 points)
-Onslaught (3000 points)
-Captain (80 points)
-"#;
-        let units = parse_units_from_raw_text(raw);
-        assert_eq!(units.len(), 1);
-        assert_eq!(units[0].name, "Captain");
-    }
-
-    #[test]
-    fn test_strip_line_prefix() {
-        assert_eq!(
-            strip_line_prefix("Char1: 1x Old One Eye (150 pts)"),
-            ("1x Old One Eye (150 pts)", Some("Char"))
-        );
-        assert_eq!(
-            strip_line_prefix("EH1: Trajann Valoris [140 pts]"),
-            ("Trajann Valoris [140 pts]", Some("EH"))
-        );
-        assert_eq!(
-            strip_line_prefix("CH2: Shield-captain [140 pts]"),
-            ("Shield-captain [140 pts]", Some("CH"))
-        );
-        assert_eq!(
-            strip_line_prefix("BL1: (4) Custodian Guard [150 pts]"),
-            ("(4) Custodian Guard [150 pts]", Some("BL"))
-        );
-        assert_eq!(
-            strip_line_prefix("IN3: Poxwalkers (10) [65 pts]"),
-            ("Poxwalkers (10) [65 pts]", Some("IN"))
-        );
-        assert_eq!(
-            strip_line_prefix("VE1: Venerable Land Raider [220 pts]"),
-            ("Venerable Land Raider [220 pts]", Some("VE"))
-        );
-        assert_eq!(
-            strip_line_prefix("MO1: Carnifex [90 pts]"),
-            ("Carnifex [90 pts]", Some("MO"))
-        );
-        assert_eq!(
-            strip_line_prefix("Captain (80 points)"),
-            ("Captain (80 points)", None)
-        );
-    }
-
-    #[test]
-    fn test_detect_chapter_line_after_sm() {
-        let raw = "Army Name (2000 Points)\n\nSpace Marines\nUltramarines\nStrike Force (2000 Points)\nBlade of Ultramar\n";
-        assert_eq!(detect_chapter_from_raw_text(raw), Some("Ultramarines"));
-    }
-
-    #[test]
-    fn test_detect_chapter_astartes_dash() {
-        let raw = "+ FACTION KEYWORD: Imperium - Adeptus Astartes - Iron Hands\n+ DETACHMENT: Anvil Siege Force\n";
-        assert_eq!(detect_chapter_from_raw_text(raw), Some("Iron Hands"));
-    }
-
-    #[test]
-    fn test_detect_chapter_parens() {
-        let raw =
-            "Army Faction Used: Space Marines (Ultramarines)\nDetachment: Blade of Ultramar\n";
-        assert_eq!(detect_chapter_from_raw_text(raw), Some("Ultramarines"));
-    }
-
-    #[test]
-    fn test_detect_chapter_from_detachment() {
-        let raw = "FACTION KEYWORD: Adeptus Astartes\nDETACHMENT: Firestorm Assault Force\nCaptain (80 points)\n";
-        assert_eq!(detect_chapter_from_raw_text(raw), Some("Salamanders"));
-    }
-
-    #[test]
-    fn test_detect_chapter_from_character() {
-        let raw = "1 Kayvaan Shrike - 100 pts\n1 Captain - 80 pts\n";
-        assert_eq!(detect_chapter_from_raw_text(raw), Some("Raven Guard"));
-    }
-
-    #[test]
-    fn test_detect_chapter_generic_sm() {
-        // Truly generic SM list with no chapter markers
-        let raw = "FACTION KEYWORD: Space Marines\nDETACHMENT: Ironstorm Spearhead\nTechmarine (65 points)\n";
-        assert_eq!(detect_chapter_from_raw_text(raw), None);
-    }
-
-    #[test]
-    fn test_parse_multi_level_wargear_retributors() {
-        // BCP app format with model-type lines and sub-weapon lines
-        let raw = "OTHER DATASHEETS\n\nRetributor Squad (120 points)\n  \u{2022} 1x Retributor Superior\n    \u{2022} 1x Bolt pistol\n      1x Close combat weapon\n      1x Condemnor boltgun\n      1x Power weapon\n  \u{2022} 4x Retributor\n    \u{2022} 4x Bolt pistol\n      4x Close combat weapon\n      4x Multi-melta\n";
-        let units = parse_units_from_raw_text(raw);
-        assert_eq!(units.len(), 1, "Expected 1 unit, got {:?}", units);
-        let ret = &units[0];
-        assert_eq!(ret.name, "Retributor Squad");
-        assert_eq!(ret.count, 5, "Squad should be 5 models (1 superior + 4)");
-        assert!(
-            ret.wargear.iter().any(|w| w.contains("Multi-melta")),
-            "Should have Multi-melta in wargear: {:?}",
-            ret.wargear
-        );
-        assert!(
-            ret.wargear.iter().any(|w| w == "4x Multi-melta"),
-            "Should show 4x Multi-melta: {:?}",
-            ret.wargear
-        );
-        assert!(
-            ret.wargear.iter().any(|w| w.contains("Power weapon")),
-            "Should have Power weapon: {:?}",
-            ret.wargear
-        );
-    }
-
-    #[test]
-    fn test_parse_multi_level_wargear_paragons() {
-        let raw = "Paragon Warsuits (210 points)\n  \u{2022} 1x Paragon Superior\n    \u{2022} 1x Bolt pistol\n      1x Multi-melta\n      1x Paragon grenade launchers\n      1x Paragon war mace\n  \u{2022} 2x Paragon\n    \u{2022} 2x Bolt pistol\n      2x Multi-melta\n      2x Paragon grenade launchers\n      2x Paragon war mace\n";
-        let units = parse_units_from_raw_text(raw);
-        assert_eq!(units.len(), 1);
-        let para = &units[0];
-        assert_eq!(para.name, "Paragon Warsuits");
-        assert_eq!(para.count, 3, "Should be 3 models (1 superior + 2)");
-        assert!(
-            para.wargear.iter().any(|w| w.contains("war mace")),
-            "Should show Paragon war mace: {:?}",
-            para.wargear
-        );
-        assert!(
-            para.wargear.iter().any(|w| w.contains("Multi-melta")),
-            "Should show Multi-melta: {:?}",
-            para.wargear
-        );
-    }
-
-    #[test]
-    fn test_parse_inline_wargear_after_points() {
-        // Russian format with wargear on same line after points
-        let raw = "Retributor Squad (5) (120 pts) 4x Multi-melta, 1x Inferno pistol, 1x Power weapon\nParagon Warsuits (3) (210 pts) 3x Multi-melta, 3x Paragon war mace\n";
-        let units = parse_units_from_raw_text(raw);
-        assert_eq!(units.len(), 2, "Expected 2 units, got {:?}", units);
-
-        let ret = &units[0];
-        assert_eq!(ret.name, "Retributor Squad");
-        assert_eq!(ret.count, 5, "Count from (5) in name");
-        assert!(
-            ret.wargear.iter().any(|w| w == "4x Multi-melta"),
-            "Should have 4x Multi-melta: {:?}",
-            ret.wargear
-        );
-        assert!(
-            ret.wargear.iter().any(|w| w == "Inferno pistol"),
-            "Should have Inferno pistol: {:?}",
-            ret.wargear
-        );
-
-        let para = &units[1];
-        assert_eq!(para.name, "Paragon Warsuits");
-        assert_eq!(para.count, 3, "Count from (3) in name");
-        assert!(
-            para.wargear.iter().any(|w| w == "3x Multi-melta"),
-            "Should have 3x Multi-melta: {:?}",
-            para.wargear
-        );
-        assert!(
-            para.wargear.iter().any(|w| w.contains("war mace")),
-            "Should have war mace: {:?}",
-            para.wargear
-        );
-    }
-
-    #[test]
-    fn test_parse_flat_wargear_single_model() {
-        // Flat format: single-model unit, all gear at same indent
-        let raw = "Castellan Crowe (90 points)\n  \u{2022} 1x Black Blade of Antwyr\n  \u{2022} 1x Storm bolter\n";
-        let units = parse_units_from_raw_text(raw);
-        assert_eq!(units.len(), 1);
-        assert_eq!(units[0].count, 1, "Single model, count should stay 1");
-        assert_eq!(
-            units[0].wargear,
-            vec!["Black Blade of Antwyr", "Storm bolter"]
-        );
-    }
 }
@@ -0,0 +1,178 @@
+//! Detection of multi-bracket BCP listings.
+//!
+//! Some events run several game sizes (e.g. 2000pt and 1000pt) under one
+//! BCP listing. Players in different brackets never meet, so the pairing
+//! graph splits into disconnected groups; each large enough group is a
+//! bracket. Bracket game sizes are inferred from the players' list points.
+
+use std::collections::{BTreeSet, HashMap};
+
+use super::bcp::BcpPairing;
+
+/// Smallest player group treated as a bracket. Smaller islands are usually
+/// a late registration or a pairing data glitch, not a separate game size.
+pub const MIN_BRACKET_PLAYERS: usize = 4;
+
+/// Standard points limits, smallest first.
+const GAME_SIZES: &[u32] = &[500, 1000, 1500, 2000, 3000];
+
+/// One bracket: the BCP player IDs who played in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bracket {
+    pub player_ids: BTreeSet<String>,
+}
+
+impl Bracket {
+    /// Pairings played between this bracket's players.
+    pub fn pairings(&self, pairings: &[BcpPairing]) -> Vec<BcpPairing> {
+        pairings
+            .iter()
+            .filter(|p| {
+                pairing_ids(p)
+                    .iter()
+                    .any(|id| self.player_ids.contains(*id))
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+fn pairing_ids(pairing: &BcpPairing) -> Vec<&str> {
+    [&pairing.player1, &pairing.player2]
+        .into_iter()
+        .filter_map(|p| p.as_ref()?.id.as_deref())
+        .collect()
+}
+
+fn find(parent: &mut HashMap<String, String>, id: &str) -> String {
+    let mut root = id.to_string();
+    while let Some(next) = parent.get(&root).filter(|p| **p != root) {
+        root = next.clone();
+    }
+    parent.insert(id.to_string(), root.clone());
+    root
+}
+
+/// Split an event's pairings into brackets.
+///
+/// Returns an empty vec for an ordinary single-bracket event. Otherwise the
+/// brackets are ordered largest first (ties by smallest player ID), which
+/// keeps bracket indices stable across syncs.
+pub fn detect_brackets(pairings: &[BcpPairing]) -> Vec<Bracket> {
+    let mut parent: HashMap<String, String> = HashMap::new();
+    for pairing in pairings {
+        let ids = pairing_ids(pairing);
+        for id in &ids {
+            parent
+                .entry(id.to_string())
+                .or_insert_with(|| id.to_string());
+        }
+        if let [a, b] = ids[..] {
+            let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+            if ra != rb {
+                parent.insert(ra, rb);
+            }
+        }
+    }
+
+    let ids: Vec<String> = parent.keys().cloned().collect();
+    let mut groups: HashMap<String, BTreeSet<String>> = HashMap::new();
+    for id in ids {
+        let root = find(&mut parent, &id);
+        groups.entry(root).or_default().insert(id);
+    }
+
+    let mut brackets: Vec<Bracket> = groups
+        .into_values()
+        .filter(|g| g.len() >= MIN_BRACKET_PLAYERS)
+        .map(|player_ids| Bracket { player_ids })
+        .collect();
+    if brackets.len() < 2 {
+        return Vec::new();
+    }
+    brackets.sort_by(|a, b| {
+        b.player_ids
+            .len()
+            .cmp(&a.player_ids.len())
+            .then_with(|| a.player_ids.first().cmp(&b.player_ids.first()))
+    });
+    brackets
+}
+
+/// Infer a bracket's points limit from its list totals: the smallest
+/// standard size covering the median list. `None` without parsed lists.
+pub fn infer_game_size(list_points: &[u32]) -> Option<u32> {
+    let mut points: Vec<u32> = list_points.iter().copied().filter(|p| *p > 0).collect();
+    if points.is_empty() {
+        return None;
+    }
+    points.sort_unstable();
+    let median = points[points.len() / 2];
+    GAME_SIZES.iter().copied().find(|size| median <= *size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::bcp::BcpPairingPlayer;
+
+    fn pairing(a: &str, b: &str) -> BcpPairing {
+        let player = |id: &str| BcpPairingPlayer {
+            id: Some(id.to_string()),
+            first_name: Some(id.to_string()),
+            last_name: None,
+            army_name: None,
+            army_list_object_id: None,
+        };
+        BcpPairing {
+            player1: Some(player(a)),
+            player2: Some(player(b)),
+            meta_data: None,
+            round: Some(1),
+        }
+    }
+
+    #[test]
+    fn test_detect_brackets_splits_disconnected_groups() {
+        let pairings = vec![
+            // 2000pt bracket: six players
+            pairing("a1", "a2"),
+            pairing("a3", "a4"),
+            pairing("a5", "a6"),
+            pairing("a1", "a3"),
+            pairing("a2", "a5"),
+            pairing("a4", "a6"),
+            // 1000pt bracket: four players
+            pairing("b1", "b2"),
+            pairing("b3", "b4"),
+            pairing("b1", "b3"),
+            // Stray pair, too small to be a bracket
+            pairing("x1", "x2"),
+        ];
+        let brackets = detect_brackets(&pairings);
+        assert_eq!(brackets.len(), 2);
+        assert_eq!(brackets[0].player_ids.len(), 6);
+        assert_eq!(brackets[1].player_ids.len(), 4);
+        assert!(brackets[1].player_ids.contains("b4"));
+        assert_eq!(brackets[1].pairings(&pairings).len(), 3);
+    }
+
+    #[test]
+    fn test_detect_brackets_single_event() {
+        let pairings = vec![
+            pairing("a1", "a2"),
+            pairing("a3", "a4"),
+            pairing("a1", "a4"),
+            pairing("a2", "a3"),
+        ];
+        assert!(detect_brackets(&pairings).is_empty());
+    }
+
+    #[test]
+    fn test_infer_game_size() {
+        assert_eq!(infer_game_size(&[1995, 2000, 1980, 1000]), Some(2000));
+        assert_eq!(infer_game_size(&[990, 1000, 0, 985]), Some(1000));
+        assert_eq!(infer_game_size(&[0, 0]), None);
+        assert_eq!(infer_game_size(&[4000]), None);
+    }
+}
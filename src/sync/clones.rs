@@ -0,0 +1,160 @@
+//! Detect cloned army list submissions.
+//!
+//! Netlists are sometimes submitted verbatim by several players at the same
+//! event. Identical or near-identical `raw_text` within one event marks the
+//! later submissions as clones (`clone_of`) of the earliest one, so analytics
+//! can avoid counting them as independent data points.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::models::{ArmyList, ArmyListId, Timestamped};
+
+/// Line-set similarity at or above which two lists are considered clones.
+pub const CLONE_SIMILARITY_THRESHOLD: f64 = 0.9;
+
+/// Normalize raw list text into a set of comparable lines.
+///
+/// Lowercases, collapses whitespace, and drops blank lines so formatting
+/// differences from copy/paste don't hide a clone.
+fn normalized_lines(raw_text: &str) -> HashSet<String> {
+    raw_text
+        .lines()
+        .map(|l| {
+            l.split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase()
+        })
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+/// Jaccard similarity of the normalized lines of two raw list texts.
+pub fn raw_text_similarity(a: &str, b: &str) -> f64 {
+    let set_a = normalized_lines(a);
+    let set_b = normalized_lines(b);
+    if set_a.is_empty() || set_b.is_empty() {
+        return 0.0;
+    }
+    let intersection = set_a.intersection(&set_b).count();
+    let union = set_a.union(&set_b).count();
+    intersection as f64 / union as f64
+}
+
+/// Find the list `list` was cloned from among `candidates`.
+///
+/// Only candidates from the same event, by a different player, are
+/// considered. Returns the root of the clone chain so every clone points at
+/// the original submission.
+pub fn find_clone_source<'a>(
+    list: &ArmyList,
+    candidates: impl IntoIterator<Item = &'a ArmyList>,
+) -> Option<ArmyListId> {
+    let event_id = list.event_id.as_ref()?;
+    if list.raw_text.trim().is_empty() {
+        return None;
+    }
+    let player = list
+        .player_name
+        .as_deref()
+        .map(super::normalize_player_name);
+
+    candidates
+        .into_iter()
+        .filter(|c| c.id != list.id && c.event_id.as_ref() == Some(event_id))
+        .filter(|c| {
+            player.is_none() || c.player_name.as_deref().map(super::normalize_player_name) != player
+        })
+        .find(|c| raw_text_similarity(&list.raw_text, &c.raw_text) >= CLONE_SIMILARITY_THRESHOLD)
+        .map(|c| c.clone_of.clone().unwrap_or_else(|| c.id.clone()))
+}
+
+/// Flag clones across a set of lists, earliest submission per event first.
+///
+/// Existing `clone_of` values are recomputed. Returns the number of lists
+/// flagged as clones.
+pub fn flag_clones(lists: &mut [ArmyList]) -> usize {
+    let mut by_event: HashMap<String, Vec<usize>> = HashMap::new();
+    for (idx, list) in lists.iter().enumerate() {
+        if let Some(ref event_id) = list.event_id {
+            by_event
+                .entry(event_id.as_str().to_string())
+                .or_default()
+                .push(idx);
+        }
+    }
+
+    let mut flagged = 0;
+    for indices in by_event.values_mut() {
+        indices.sort_by_key(|&i| lists[i].created_at);
+        for (pos, &idx) in indices.iter().enumerate() {
+            let earlier: Vec<&ArmyList> = indices[..pos].iter().map(|&i| &lists[i]).collect();
+            let source = find_clone_source(&lists[idx], earlier);
+            if source.is_some() {
+                flagged += 1;
+            }
+            if lists[idx].clone_of != source {
+                lists[idx].clone_of = source;
+                lists[idx].touch();
+            }
+        }
+    }
+    flagged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EventId;
+
+    const NETLIST: &str = "++ Army Roster ++\nDetachment: Battle Host\n\nAvatar of Khaine (335 pts)\n10x Guardians (110 pts)\nWraithknight (435 pts)\nFarseer (70 pts)\n";
+
+    fn make_list(player: &str, event: &str, raw: &str, offset_secs: i64) -> ArmyList {
+        let mut list = ArmyList::new("Aeldari".to_string(), 2000, vec![], raw.to_string())
+            .with_player_name(player.to_string())
+            .with_event_id(EventId::from(event));
+        // Distinct IDs per player so lists don't collapse under dedup
+        list.id = crate::models::EntityId::generate(&[player, event]);
+        list.created_at += chrono::Duration::seconds(offset_secs);
+        list
+    }
+
+    #[test]
+    fn test_raw_text_similarity_ignores_formatting() {
+        let reformatted = NETLIST.replace('\n', "\n\n").to_uppercase();
+        assert_eq!(raw_text_similarity(NETLIST, &reformatted), 1.0);
+        assert!(raw_text_similarity(NETLIST, "Something else entirely") < 0.1);
+        assert_eq!(raw_text_similarity("", NETLIST), 0.0);
+    }
+
+    #[test]
+    fn test_flag_clones_within_event() {
+        let mut lists = vec![
+            make_list("Alice", "e1", NETLIST, 0),
+            make_list("Bob", "e1", NETLIST, 10),
+            make_list("Carol", "e1", &NETLIST.replace("Farseer", "Autarch"), 20),
+            make_list("Dave", "e2", NETLIST, 30),
+            make_list("Erin", "e1", "Orks\nBoyz (170 pts)\nWarboss (65 pts)", 40),
+        ];
+        let original = lists[0].id.clone();
+
+        let flagged = flag_clones(&mut lists);
+
+        assert_eq!(flagged, 1);
+        assert!(lists[0].clone_of.is_none());
+        assert_eq!(lists[1].clone_of, Some(original));
+        // One changed line of six drops below the threshold
+        assert!(lists[2].clone_of.is_none());
+        // Same text at a different event is not a clone
+        assert!(lists[3].clone_of.is_none());
+        assert!(lists[4].clone_of.is_none());
+    }
+
+    #[test]
+    fn test_same_player_not_a_clone() {
+        let first = make_list("Alice", "e1", NETLIST, 0);
+        let mut resubmitted = make_list("alice ", "e1", NETLIST, 10);
+        resubmitted.id = crate::models::EntityId::from("other");
+        assert!(find_clone_source(&resubmitted, [&first]).is_none());
+    }
+}
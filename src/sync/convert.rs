@@ -9,9 +9,11 @@ use crate::agents::event_scout::EventStub;
 use crate::agents::result_harvester::PlacementStub;
 use crate::agents::AgentOutput;
 use crate::models::{
-    ArmyList, ArmyListId, Confidence, EntityId, Event, EventId, Pairing, Placement,
+    game_size_from_name, ArmyList, ArmyListId, BracketMatch, BracketStage, Confidence, EntityId,
+    Event, EventId, Pairing, Placement,
 };
 use crate::sync::bcp::{BcpArmyList, BcpEvent, BcpPairing, BcpStanding};
+use crate::sync::t3::{T3Event, T3Standing};
 
 /// Convert an EventStub to an Event model entity.
 ///
@@ -45,6 +47,10 @@ pub fn event_from_stub(
     if let Some(count) = stub.data.round_count {
         event = event.with_round_count(count);
     }
+    event = event.with_mission_pack(stub.data.mission_pack.clone());
+    event = event.with_game_size(game_size_from_name(&stub.data.name));
+    event.extracted_by = stub.model.clone();
+    event.extraction_agent_version = stub.agent_version;
 
     event
 }
@@ -78,6 +84,9 @@ pub fn placement_from_stub(
     if let Some(bp) = stub.data.battle_points {
         placement = placement.with_battle_points(bp);
     }
+    placement = placement.with_team(stub.data.team.as_deref());
+    placement.extracted_by = stub.model.clone();
+    placement.extraction_agent_version = stub.agent_version;
 
     placement
 }
@@ -99,6 +108,12 @@ pub fn event_from_bcp(bcp_event: &BcpEvent, epoch_id: Option<EntityId>) -> Event
     )
     .with_confidence(Confidence::High);
 
+    event = event.with_place(
+        bcp_event.venue.clone(),
+        bcp_event.city.clone(),
+        bcp_event.state.clone(),
+        bcp_event.country.clone(),
+    );
     if let Some(location) = bcp_event.location_string() {
         event = event.with_location(location);
     }
@@ -108,6 +123,11 @@ pub fn event_from_bcp(bcp_event: &BcpEvent, epoch_id: Option<EntityId>) -> Event
     if let Some(count) = bcp_event.round_count {
         event = event.with_round_count(count);
     }
+    let game_size = bcp_event
+        .points_limit
+        .filter(|points| *points > 0)
+        .or_else(|| game_size_from_name(&bcp_event.name));
+    event = event.with_game_size(game_size);
 
     event
 }
@@ -142,6 +162,68 @@ pub fn placement_from_bcp(
     if let Some(lid) = list_id {
         placement = placement.with_list_id(lid);
     }
+    if let Some(stage) = standing.bracket_stage {
+        placement = placement.with_bracket_stage(stage);
+    }
+    placement = placement.with_team(standing.team.as_deref());
+
+    placement
+}
+
+/// Convert a T3Event to an Event model entity.
+pub fn event_from_t3(t3_event: &T3Event, base_url: &str, epoch_id: Option<EntityId>) -> Event {
+    let date = t3_event
+        .date
+        .unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let epoch_id = epoch_id.unwrap_or_else(|| EntityId::from("current"));
+
+    let mut event = Event::new(
+        t3_event.name.clone(),
+        date,
+        t3_event.event_url(base_url),
+        "t3".to_string(),
+        epoch_id,
+    )
+    .with_confidence(Confidence::High);
+
+    if let Some(ref location) = t3_event.location {
+        event = event.with_location(location.clone());
+    }
+    if let Some(count) = t3_event.player_count {
+        event = event.with_player_count(count);
+    }
+    event = event.with_game_size(game_size_from_name(&t3_event.name));
+
+    event
+}
+
+/// Convert a T3Standing to a Placement model entity.
+pub fn placement_from_t3(
+    standing: &T3Standing,
+    event_id: EventId,
+    epoch_id: Option<EntityId>,
+) -> Placement {
+    let epoch_id = epoch_id.unwrap_or_else(|| EntityId::from("current"));
+    let faction = standing
+        .faction
+        .clone()
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let mut placement = Placement::new(
+        event_id,
+        epoch_id,
+        standing.rank,
+        standing.player_name.clone(),
+        faction,
+    )
+    .with_confidence(Confidence::High);
+
+    if let (Some(w), Some(l), Some(d)) = (standing.wins, standing.losses, standing.draws) {
+        placement = placement.with_record(w, l, d);
+    }
+    if let Some(bp) = standing.battle_points {
+        placement = placement.with_battle_points(bp);
+    }
 
     placement
 }
@@ -282,6 +364,57 @@ pub fn pairings_from_bcp(
     result
 }
 
+/// Convert the top-cut rounds of BCP pairings into BracketMatch entities.
+///
+/// `cut_rounds` comes from [`crate::sync::bcp::detect_cut_rounds`]; pairings
+/// in other rounds are ignored.
+pub fn bracket_matches_from_bcp(
+    bcp_pairings: &[BcpPairing],
+    cut_rounds: &[u32],
+    event_id: &EventId,
+    epoch_id: Option<EntityId>,
+) -> Vec<BracketMatch> {
+    let epoch_id = epoch_id.unwrap_or_else(|| EntityId::from("current"));
+    let Some(&final_round) = cut_rounds.last() else {
+        return Vec::new();
+    };
+    let mut result = Vec::new();
+
+    for bp in bcp_pairings {
+        let Some(round) = bp.round.filter(|r| cut_rounds.contains(r)) else {
+            continue;
+        };
+        let (Some(p1), Some(p2)) = (&bp.player1, &bp.player2) else {
+            continue;
+        };
+        let (p1_name, p2_name) = (p1.full_name(), p2.full_name());
+        if p1_name.is_empty() || p2_name.is_empty() {
+            continue;
+        }
+
+        let stage = BracketStage::from_rounds_remaining(final_round - round);
+        let mut m = BracketMatch::new(
+            event_id.clone(),
+            epoch_id.clone(),
+            round,
+            stage,
+            p1_name,
+            p2_name,
+        );
+        m.player1_faction = p1.army_name.clone();
+        m.player2_faction = p2.army_name.clone();
+        m.winner_name = bp.winner().map(|w| w.full_name());
+        if let Some(ref meta) = bp.meta_data {
+            m.player1_game_points = meta.p1_game_points.map(|p| p as u32);
+            m.player2_game_points = meta.p2_game_points.map(|p| p as u32);
+        }
+        result.push(m);
+    }
+
+    result.sort_by_key(|m| m.round);
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -302,6 +435,7 @@ mod tests {
                 round_count: Some(5),
                 event_type: Some("GT".to_string()),
                 article_section: None,
+                mission_pack: None,
             },
             Confidence::High,
         )
@@ -312,12 +446,13 @@ mod tests {
         let article_date = NaiveDate::from_ymd_opt(2025, 6, 20).unwrap();
         let event_date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
 
-        let stub = make_event_stub(
+        let mut stub = make_event_stub(
             "London GT 2025",
             Some(event_date),
             Some("London, UK"),
             Some(96),
         );
+        stub.data.mission_pack = Some("GW Pariah Nexus missions".to_string());
 
         let event = event_from_stub(
             &stub,
@@ -332,6 +467,7 @@ mod tests {
         assert_eq!(event.location, Some("London, UK".to_string()));
         assert_eq!(event.player_count, Some(96));
         assert_eq!(event.round_count, Some(5));
+        assert_eq!(event.mission_pack.as_deref(), Some("Pariah Nexus"));
         assert_eq!(event.source_name, "goonhammer");
         assert_eq!(event.extraction_confidence, Confidence::High);
     }
@@ -371,6 +507,7 @@ mod tests {
                     draws: 0,
                 }),
                 battle_points: Some(94),
+                team: None,
             },
             Confidence::High,
         );
@@ -401,6 +538,7 @@ mod tests {
                 detachment: None,
                 record: None,
                 battle_points: None,
+                team: None,
             },
             Confidence::Medium,
         );
@@ -468,6 +606,7 @@ mod tests {
                     draws: 0,
                 }),
                 battle_points: None,
+                team: None,
             },
             Confidence::Medium,
         );
@@ -492,6 +631,7 @@ mod tests {
                 detachment: None,
                 record: None,
                 battle_points: None,
+                team: None,
             },
             Confidence::Low,
         );
@@ -522,6 +662,7 @@ mod tests {
             ended: None,
             team_event: None,
             hide_placings: None,
+            points_limit: None,
         };
 
         let event = event_from_bcp(&bcp_event, None);
@@ -529,6 +670,8 @@ mod tests {
         assert_eq!(event.name, "London GT 2026");
         assert_eq!(event.date, NaiveDate::from_ymd_opt(2026, 2, 1).unwrap());
         assert_eq!(event.location, Some("London, UK".to_string()));
+        assert_eq!(event.city.as_deref(), Some("London"));
+        assert_eq!(event.country.as_deref(), Some("GB"));
         assert_eq!(event.player_count, Some(96));
         assert_eq!(event.source_name, "bcp");
         assert_eq!(event.extraction_confidence, Confidence::High);
@@ -546,6 +689,8 @@ mod tests {
             total_battle_points: Some(94),
             player_id: Some("p1".to_string()),
             army_list_object_id: Some("list-1".to_string()),
+            bracket_stage: None,
+            team: Some("Vanguard Tactics".to_string()),
         };
 
         let event_id = EntityId::from("event-bcp-1");
@@ -557,6 +702,7 @@ mod tests {
         assert_eq!(placement.event_id, event_id);
         assert_eq!(placement.record.as_ref().unwrap().wins, 5);
         assert_eq!(placement.battle_points, Some(94));
+        assert_eq!(placement.team.as_deref(), Some("Vanguard Tactics"));
     }
 
     #[test]
@@ -847,4 +993,70 @@ mod tests {
         assert_eq!(result[0].player1_result, Some("loss".to_string()));
         assert_eq!(result[1].player1_result, Some("draw".to_string()));
     }
+
+    #[test]
+    fn test_bracket_matches_from_bcp() {
+        use crate::sync::bcp::{BcpPairingMeta, BcpPairingPlayer};
+        let player = |id: &str, name: &str| BcpPairingPlayer {
+            id: Some(id.to_string()),
+            first_name: Some(name.to_string()),
+            last_name: None,
+            army_name: Some("Necrons".to_string()),
+            army_list_object_id: None,
+        };
+        let game = |round, a, b| BcpPairing {
+            player1: Some(player(a, a)),
+            player2: Some(player(b, b)),
+            meta_data: Some(BcpPairingMeta {
+                p1_game_result: Some(0),
+                p1_game_points: Some(40.0),
+                p2_game_result: Some(2),
+                p2_game_points: Some(80.0),
+            }),
+            round: Some(round),
+        };
+        let pairings = vec![game(3, "a", "b"), game(5, "c", "d"), game(4, "a", "c")];
+
+        let matches =
+            bracket_matches_from_bcp(&pairings, &[4, 5], &EntityId::from("event-1"), None);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].stage, BracketStage::Semifinal);
+        assert_eq!(matches[1].stage, BracketStage::Final);
+        assert_eq!(matches[1].winner_name.as_deref(), Some("d"));
+        assert_eq!(matches[1].player2_game_points, Some(80));
+    }
+
+    #[test]
+    fn test_event_and_placement_from_t3() {
+        let t3_event = T3Event {
+            id: "34567".to_string(),
+            name: "Hamburger Hanse Cup".to_string(),
+            date: NaiveDate::from_ymd_opt(2025, 6, 14),
+            game_system: Some("Warhammer 40.000".to_string()),
+            location: Some("Hamburg".to_string()),
+            player_count: Some(38),
+        };
+        let event = event_from_t3(&t3_event, crate::sync::t3::T3_BASE_URL, None);
+        assert_eq!(event.source_name, "t3");
+        assert_eq!(event.date, NaiveDate::from_ymd_opt(2025, 6, 14).unwrap());
+        assert_eq!(event.location.as_deref(), Some("Hamburg"));
+        assert_eq!(event.player_count, Some(38));
+        assert!(event.source_url.ends_with("t3_tournament.php?tid=34567"));
+
+        let standing = T3Standing {
+            rank: 2,
+            player_name: "Lena Vogel".to_string(),
+            faction: None,
+            wins: Some(4),
+            draws: Some(0),
+            losses: Some(1),
+            battle_points: Some(82),
+            list_url: None,
+        };
+        let placement = placement_from_t3(&standing, event.id.clone(), None);
+        assert_eq!(placement.rank, 2);
+        assert_eq!(placement.faction, "Unknown");
+        assert_eq!(placement.record.as_ref().map(|r| r.losses), Some(1));
+        assert_eq!(placement.battle_points, Some(82));
+    }
 }
@@ -0,0 +1,167 @@
+//! Incremental sync cursor.
+//!
+//! Persists a per-source high-water mark (latest Goonhammer article date,
+//! latest finished BCP or T3 event date) in `state/sync_cursor.json`, so a
+//! repeated `sync --once` only fetches items at or after the mark instead of
+//! rescanning the whole date range.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::SyncSource;
+use crate::storage::{StorageConfig, StorageError};
+
+/// High-water mark for a single source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceCursor {
+    /// Latest item date fully processed for this source
+    pub high_water: NaiveDate,
+
+    /// When the mark last moved
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Per-source high-water marks, keyed by source name ([`Source::name`](super::Source::name)).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncCursor {
+    #[serde(default)]
+    pub sources: BTreeMap<String, SourceCursor>,
+}
+
+/// Key identifying a built-in source in the cursor file: its registered
+/// name.
+pub fn cursor_key(source: &SyncSource) -> &'static str {
+    match source {
+        SyncSource::Goonhammer { .. } => "goonhammer",
+        SyncSource::Bcp { .. } => "bcp",
+        SyncSource::WarhammerCommunity { .. } => "warhammer-community",
+        SyncSource::TabletopTournaments { .. } => "t3",
+    }
+}
+
+impl SyncCursor {
+    /// Path of the cursor file.
+    pub fn path(storage: &StorageConfig) -> PathBuf {
+        storage.state_dir().join("sync_cursor.json")
+    }
+
+    /// Load the cursor, or an empty one if none has been saved yet.
+    pub fn load(storage: &StorageConfig) -> Result<Self, StorageError> {
+        let path = Self::path(storage);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Write the cursor to the state directory.
+    pub fn save(&self, storage: &StorageConfig) -> Result<(), StorageError> {
+        let path = Self::path(storage);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// High-water mark for a source, if any.
+    pub fn get(&self, source: &str) -> Option<NaiveDate> {
+        self.sources.get(source).map(|c| c.high_water)
+    }
+
+    /// Move a source's mark forward to `date`. Never moves it backwards.
+    ///
+    /// Returns true if the mark changed.
+    pub fn advance(&mut self, source: &str, date: NaiveDate) -> bool {
+        let key = source.to_string();
+        match self.sources.get(&key) {
+            Some(existing) if existing.high_water >= date => false,
+            _ => {
+                self.sources.insert(
+                    key,
+                    SourceCursor {
+                        high_water: date,
+                        updated_at: Utc::now(),
+                    },
+                );
+                true
+            }
+        }
+    }
+}
+
+/// Tracks the dates of items processed in one run, to compute how far the
+/// cursor may safely advance.
+#[derive(Debug, Default)]
+pub struct HighWater {
+    latest_ok: Option<NaiveDate>,
+    earliest_failed: Option<NaiveDate>,
+}
+
+impl HighWater {
+    /// Record an item that was processed (or was already stored).
+    pub fn ok(&mut self, date: NaiveDate) {
+        self.latest_ok = self.latest_ok.max(Some(date));
+    }
+
+    /// Record an item that failed and must be retried next run.
+    pub fn failed(&mut self, date: NaiveDate) {
+        self.earliest_failed = Some(self.earliest_failed.map_or(date, |d| d.min(date)));
+    }
+
+    /// The new mark: the latest success, held back to the earliest failure
+    /// so failed items stay inside the next incremental window.
+    pub fn mark(&self) -> Option<NaiveDate> {
+        match (self.latest_ok, self.earliest_failed) {
+            (Some(ok), Some(failed)) => Some(ok.min(failed)),
+            (ok, None) => ok,
+            (None, Some(_)) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 6, d).unwrap()
+    }
+
+    #[test]
+    fn test_cursor_roundtrip_and_advance() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        let bcp = cursor_key(&SyncSource::default());
+
+        let mut cursor = SyncCursor::load(&storage).unwrap();
+        assert!(cursor.get(bcp).is_none());
+
+        assert!(cursor.advance(bcp, date(10)));
+        assert!(!cursor.advance(bcp, date(5)));
+        cursor.save(&storage).unwrap();
+
+        let loaded = SyncCursor::load(&storage).unwrap();
+        assert_eq!(loaded.get(bcp), Some(date(10)));
+        let goonhammer = SyncSource::Goonhammer {
+            base_url: "https://www.goonhammer.com".to_string(),
+        };
+        assert!(loaded.get(cursor_key(&goonhammer)).is_none());
+    }
+
+    #[test]
+    fn test_high_water_held_back_by_failures() {
+        let mut hw = HighWater::default();
+        assert_eq!(hw.mark(), None);
+        hw.ok(date(3));
+        hw.ok(date(12));
+        assert_eq!(hw.mark(), Some(date(12)));
+        hw.failed(date(8));
+        assert_eq!(hw.mark(), Some(date(8)));
+    }
+}
@@ -4,10 +4,11 @@
 
 use chrono::NaiveDate;
 use scraper::{Html, Selector};
+use serde::Serialize;
 use url::Url;
 
 /// A discovered article from a category page or RSS feed.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DiscoveredArticle {
     /// Full URL to the article
     pub url: Url,
@@ -0,0 +1,418 @@
+//! Correcting an event's date.
+//!
+//! An event extracted with the wrong date lands in the wrong epoch, along
+//! with everything recorded at it. [`fix_event_date`] sets the right date
+//! and, when that belongs to another epoch, moves the event, its
+//! placements, lists and pairings there. The event keeps its ID so
+//! references to it stay valid.
+
+use std::collections::HashSet;
+use std::fs;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::api::merge_by_id;
+use crate::models::{ArmyList, EntityId, Event, Pairing, Placement, Timestamped};
+use crate::storage::jsonl::{entity_path, entity_paths, list_epochs};
+use crate::storage::store;
+use crate::storage::{
+    cached_epoch_mapper, EntityType, JsonlReader, JsonlWriter, StorageBackend, StorageConfig,
+    StorageError,
+};
+
+/// Errors that stop an event fix.
+#[derive(Debug, Error)]
+pub enum FixEventError {
+    #[error("Event not found: {0}")]
+    NotFound(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// What a fix changed, or would change on a dry run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EventFix {
+    pub event_id: String,
+    pub name: String,
+    pub old_date: NaiveDate,
+    pub new_date: NaiveDate,
+    pub from_epoch: String,
+    pub to_epoch: String,
+    pub placements: usize,
+    pub lists: usize,
+    pub pairings: usize,
+}
+
+impl EventFix {
+    /// Whether the event changes epoch.
+    pub fn moved(&self) -> bool {
+        self.from_epoch != self.to_epoch
+    }
+}
+
+/// An epoch's records of one entity, split into those belonging to the
+/// event and the rest.
+fn split<T: serde::de::DeserializeOwned + Timestamped + Clone>(
+    storage: &StorageConfig,
+    entity: EntityType,
+    epoch_id: &str,
+    id: impl Fn(&T) -> &str,
+    belongs: impl Fn(&T) -> bool,
+) -> Result<(Vec<T>, Vec<T>), StorageError> {
+    let records = JsonlReader::<T>::for_entity(storage, entity, epoch_id).read_all()?;
+    Ok(merge_by_id(records, id).into_iter().partition(belongs))
+}
+
+/// Replace an epoch's records of one entity, folding any per-source files
+/// into the shared one. Each file is replaced atomically.
+pub(super) fn replace_entity<T: Serialize>(
+    storage: &StorageConfig,
+    entity: EntityType,
+    epoch_id: &str,
+    records: &[T],
+) -> Result<(), StorageError> {
+    let shared = entity_path(storage, entity, epoch_id);
+    let partitions: Vec<_> = entity_paths(storage, entity, epoch_id)
+        .into_iter()
+        .filter(|p| *p != shared)
+        .collect();
+    JsonlWriter::for_entity(storage, entity, epoch_id).write_all(records)?;
+    if storage.backend != StorageBackend::Jsonl {
+        // Partitions are keys in the store, not files
+        let store = store::open(storage, storage.backend)?;
+        let shared_key = format!("{}/{}", epoch_id, entity.filename());
+        for key in store::entity_keys(store.as_ref(), entity, epoch_id)? {
+            if key != shared_key {
+                store.replace_lines(&key, &[])?;
+            }
+        }
+        return Ok(());
+    }
+    for path in partitions {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Set an event's date and move it, with its placements, lists and
+/// pairings, to the epoch the date belongs to. Lists are matched by event
+/// ID, or by a placement's list ID. Unless `dry_run`, the affected files of
+/// both epochs are rewritten.
+pub fn fix_event_date(
+    storage: &StorageConfig,
+    event_id: &str,
+    date: NaiveDate,
+    dry_run: bool,
+) -> Result<EventFix, FixEventError> {
+    let mut found = None;
+    for epoch_id in list_epochs(storage)? {
+        let (mut matched, rest) = split::<Event>(
+            storage,
+            EntityType::Event,
+            &epoch_id,
+            |e| e.id.as_str(),
+            |e| e.id.as_str() == event_id,
+        )?;
+        if let Some(event) = matched.pop() {
+            found = Some((epoch_id, event, rest));
+            break;
+        }
+    }
+    let Some((from_epoch, mut event, other_events)) = found else {
+        return Err(FixEventError::NotFound(event_id.to_string()));
+    };
+
+    let mapper = cached_epoch_mapper(storage);
+    let to_epoch = if mapper.all_epochs().is_empty() {
+        from_epoch.clone()
+    } else {
+        mapper.get_epoch_id_for_date(date).as_str().to_string()
+    };
+
+    let (mut placements, other_placements) = split::<Placement>(
+        storage,
+        EntityType::Placement,
+        &from_epoch,
+        |p| p.id.as_str(),
+        |p| p.event_id == event.id,
+    )?;
+    // A shared source URL isn't enough: one article can cover several events
+    let placement_lists: HashSet<&EntityId> = placements
+        .iter()
+        .filter_map(|p| p.list_id.as_ref())
+        .collect();
+    let (mut lists, other_lists) = split::<ArmyList>(
+        storage,
+        EntityType::ArmyList,
+        &from_epoch,
+        |l| l.id.as_str(),
+        |l| l.event_id.as_ref() == Some(&event.id) || placement_lists.contains(&l.id),
+    )?;
+    let (mut pairings, other_pairings) = split::<Pairing>(
+        storage,
+        EntityType::Pairing,
+        &from_epoch,
+        |p| p.id.as_str(),
+        |p| p.event_id == event.id,
+    )?;
+
+    let fix = EventFix {
+        event_id: event_id.to_string(),
+        name: event.name.clone(),
+        old_date: event.date,
+        new_date: date,
+        from_epoch: from_epoch.clone(),
+        to_epoch: to_epoch.clone(),
+        placements: placements.len(),
+        lists: lists.len(),
+        pairings: pairings.len(),
+    };
+    if dry_run {
+        return Ok(fix);
+    }
+
+    let epoch = EntityId::from(to_epoch.as_str());
+    event.date = date;
+    event.epoch_id = epoch.clone();
+    event.touch();
+    for placement in &mut placements {
+        placement.epoch_id = epoch.clone();
+        placement.touch();
+    }
+    for list in &mut lists {
+        list.event_date = Some(date);
+        list.touch();
+    }
+    for pairing in &mut pairings {
+        pairing.epoch_id = epoch.clone();
+        pairing.touch();
+    }
+
+    if fix.moved() {
+        // Write the target first, so a failure part way leaves the records
+        // in both epochs rather than neither; the newer copy wins on read
+        move_into(storage, EntityType::Event, &to_epoch, &[event], |e| {
+            e.id.as_str()
+        })?;
+        move_into(
+            storage,
+            EntityType::Placement,
+            &to_epoch,
+            &placements,
+            |p| p.id.as_str(),
+        )?;
+        move_into(storage, EntityType::ArmyList, &to_epoch, &lists, |l| {
+            l.id.as_str()
+        })?;
+        move_into(storage, EntityType::Pairing, &to_epoch, &pairings, |p| {
+            p.id.as_str()
+        })?;
+        replace_entity(storage, EntityType::Event, &from_epoch, &other_events)?;
+        replace_entity(
+            storage,
+            EntityType::Placement,
+            &from_epoch,
+            &other_placements,
+        )?;
+        replace_entity(storage, EntityType::ArmyList, &from_epoch, &other_lists)?;
+        replace_entity(storage, EntityType::Pairing, &from_epoch, &other_pairings)?;
+    } else {
+        // Placements and pairings don't carry the date
+        let mut events = other_events;
+        events.push(event);
+        replace_entity(storage, EntityType::Event, &from_epoch, &events)?;
+        let mut all_lists = other_lists;
+        all_lists.extend(lists);
+        replace_entity(storage, EntityType::ArmyList, &from_epoch, &all_lists)?;
+    }
+    Ok(fix)
+}
+
+/// Add records to an epoch, replacing any copies already there.
+fn move_into<T>(
+    storage: &StorageConfig,
+    entity: EntityType,
+    epoch_id: &str,
+    records: &[T],
+    id: impl Fn(&T) -> &str,
+) -> Result<(), StorageError>
+where
+    T: Serialize + serde::de::DeserializeOwned + Timestamped + Clone,
+{
+    if records.is_empty() {
+        return Ok(());
+    }
+    let existing = JsonlReader::<T>::for_entity(storage, entity, epoch_id).read_all()?;
+    let merged = merge_by_id(
+        existing
+            .into_iter()
+            .chain(records.iter().cloned())
+            .collect(),
+        id,
+    );
+    replace_entity(storage, entity, epoch_id, &merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Confidence, SignificantEvent, SignificantEventType, Unit};
+    use crate::storage::write_significant_events;
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_replace_entity_drops_store_partitions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf())
+            .with_backend(StorageBackend::Sqlite)
+            .with_source_partitioning(true);
+        let pairing = |round: u32| {
+            Pairing::new(
+                "gt".into(),
+                "current".into(),
+                round,
+                "Alice".to_string(),
+                "Bob".to_string(),
+            )
+        };
+        JsonlWriter::for_entity(&storage, EntityType::Pairing, "current")
+            .write_all(&[pairing(1)])
+            .unwrap();
+        JsonlWriter::for_source(&storage, EntityType::Pairing, "current", "bcp")
+            .write_all(&[pairing(2)])
+            .unwrap();
+
+        replace_entity(&storage, EntityType::Pairing, "current", &[pairing(3)]).unwrap();
+        let pairings = JsonlReader::<Pairing>::for_entity(&storage, EntityType::Pairing, "current")
+            .read_all()
+            .unwrap();
+        assert_eq!(pairings.len(), 1);
+        assert_eq!(pairings[0].round, 3);
+    }
+
+    #[test]
+    fn test_fix_event_date_moves_epoch() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        let date = |m: u32, d: u32| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        write_significant_events(
+            &storage,
+            &mut [
+                SignificantEvent::new(
+                    SignificantEventType::BalanceUpdate,
+                    date(1, 1),
+                    "January Dataslate".to_string(),
+                    "https://example.com/jan".to_string(),
+                )
+                .with_confidence(Confidence::High),
+                SignificantEvent::new(
+                    SignificantEventType::BalanceUpdate,
+                    date(4, 1),
+                    "April Dataslate".to_string(),
+                    "https://example.com/apr".to_string(),
+                )
+                .with_confidence(Confidence::High),
+            ],
+        )
+        .unwrap();
+        let mapper = cached_epoch_mapper(&storage);
+        let january = mapper.get_epoch_id_for_date(date(2, 1));
+        let april = mapper.get_epoch_id_for_date(date(5, 1));
+        assert_ne!(january, april);
+
+        // Extracted as February, played in May
+        let event = Event::new(
+            "Spring GT".to_string(),
+            date(2, 10),
+            "https://example.com/spring".to_string(),
+            "bcp".to_string(),
+            january.clone(),
+        );
+        let other = Event::new(
+            "Winter GT".to_string(),
+            date(2, 20),
+            "https://example.com/winter".to_string(),
+            "bcp".to_string(),
+            january.clone(),
+        );
+        let mut placement = Placement::new(
+            event.id.clone(),
+            january.clone(),
+            1,
+            "Alice".to_string(),
+            "Aeldari".to_string(),
+        );
+        let army = |unit: &str| {
+            let mut list = ArmyList::new(
+                "Aeldari".to_string(),
+                2000,
+                vec![Unit::new(unit.to_string(), 1)],
+                String::new(),
+            );
+            list.source_url = Some(event.source_url.clone());
+            list
+        };
+        let mut list = army("Farseer");
+        list.event_id = Some(event.id.clone());
+        // Linked only through the placement
+        let placed = army("Autarch");
+        placement.list_id = Some(placed.id.clone());
+        // Same article, another event's list
+        let stray = army("Wraithlord");
+        let pairing = Pairing::new(
+            event.id.clone(),
+            january.clone(),
+            1,
+            "Alice".to_string(),
+            "Bob".to_string(),
+        );
+        let epoch = january.as_str();
+        JsonlWriter::<Event>::for_entity(&storage, EntityType::Event, epoch)
+            .write_all(&[event.clone(), other])
+            .unwrap();
+        JsonlWriter::<Placement>::for_entity(&storage, EntityType::Placement, epoch)
+            .write_all(&[placement])
+            .unwrap();
+        JsonlWriter::<ArmyList>::for_entity(&storage, EntityType::ArmyList, epoch)
+            .write_all(&[list, placed, stray])
+            .unwrap();
+        JsonlWriter::<Pairing>::for_entity(&storage, EntityType::Pairing, epoch)
+            .write_all(&[pairing])
+            .unwrap();
+
+        let dry = fix_event_date(&storage, event.id.as_str(), date(5, 2), true).unwrap();
+        assert_eq!((dry.placements, dry.lists, dry.pairings), (1, 2, 1));
+        assert_eq!(dry.to_epoch, april.as_str());
+        let read = |entity, epoch: &EntityId| {
+            JsonlReader::<serde_json::Value>::for_entity(&storage, entity, epoch.as_str())
+                .read_all()
+                .unwrap()
+        };
+        assert_eq!(read(EntityType::Event, &january).len(), 2);
+
+        let fix = fix_event_date(&storage, event.id.as_str(), date(5, 2), false).unwrap();
+        assert!(fix.moved());
+        assert_eq!(read(EntityType::Event, &january).len(), 1);
+        assert!(read(EntityType::Placement, &january).is_empty());
+        assert_eq!(read(EntityType::ArmyList, &january).len(), 1);
+        let moved = read(EntityType::Event, &april);
+        assert_eq!(moved[0]["date"], "2026-05-02");
+        assert_eq!(moved[0]["epoch_id"], april.as_str());
+        assert_eq!(
+            read(EntityType::Pairing, &april)[0]["epoch_id"],
+            april.as_str()
+        );
+        assert_eq!(
+            read(EntityType::ArmyList, &april)[0]["event_date"],
+            "2026-05-02"
+        );
+
+        assert!(matches!(
+            fix_event_date(&storage, "missing", date(5, 2), false),
+            Err(FixEventError::NotFound(_))
+        ));
+    }
+}
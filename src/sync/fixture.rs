@@ -0,0 +1,618 @@
+//! Offline parsing of saved source pages for debugging and regression fixtures.
+//!
+//! Detects what kind of page a fixture holds (Goonhammer article or listing,
+//! BCP API JSON, Warhammer Community post) and runs the matching
+//! discovery/parse path without an AI backend. Goonhammer articles are
+//! parsed with the same heuristics a human would use on the "Results" lists,
+//! so output is a rough preview of what the agents should extract.
+
+use std::sync::OnceLock;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+use url::Url;
+
+use crate::models::{
+    ArmyList, Confidence, EntityId, Event, Pairing, Placement, SignificantEvent,
+    SignificantEventType,
+};
+use crate::parsing::armylist::parse_units_from_raw_text;
+use crate::sync::bcp::{
+    standings_from_pairings, BcpArmyList, BcpEvent, BcpListResponse, BcpPairing, BcpPlayerV1,
+    BcpPlayersResponse,
+};
+use crate::sync::convert::{
+    army_list_from_bcp, event_from_bcp, pairings_from_bcp, placement_from_bcp,
+};
+use crate::sync::discovery::{
+    discover_from_rss, discover_goonhammer_articles, extract_text_from_html, DiscoveredArticle,
+};
+
+/// Errors that can occur while parsing a fixture.
+#[derive(Debug, Error)]
+pub enum FixtureError {
+    #[error("Unrecognized fixture format")]
+    Unrecognized,
+
+    #[error("Invalid BCP JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// The kind of source page a fixture contains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FixtureKind {
+    /// A Goonhammer "Competitive Innovations" style article
+    GoonhammerArticle,
+    /// A Goonhammer category page listing articles
+    GoonhammerListing,
+    /// A WordPress RSS feed
+    RssFeed,
+    /// BCP events listing (`/events`)
+    BcpEvents,
+    /// BCP event players (`/events/{id}/players`)
+    BcpPlayers,
+    /// BCP pairings (`/pairings?eventId=...`)
+    BcpPairings,
+    /// A single BCP / Listhammer army list
+    BcpArmyList,
+    /// A Warhammer Community balance / FAQ post
+    WarhammerCommunity,
+}
+
+impl std::fmt::Display for FixtureKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            FixtureKind::GoonhammerArticle => "goonhammer_article",
+            FixtureKind::GoonhammerListing => "goonhammer_listing",
+            FixtureKind::RssFeed => "rss_feed",
+            FixtureKind::BcpEvents => "bcp_events",
+            FixtureKind::BcpPlayers => "bcp_players",
+            FixtureKind::BcpPairings => "bcp_pairings",
+            FixtureKind::BcpArmyList => "bcp_army_list",
+            FixtureKind::WarhammerCommunity => "warhammer_community",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Everything parsed out of a single fixture.
+///
+/// `created_at` timestamps are pinned to the Unix epoch so emitted JSON is
+/// stable across runs and can be diffed as a regression fixture.
+#[derive(Debug, Serialize)]
+pub struct FixtureParse {
+    pub kind: FixtureKind,
+    pub articles: Vec<DiscoveredArticle>,
+    pub events: Vec<Event>,
+    pub placements: Vec<Placement>,
+    pub army_lists: Vec<ArmyList>,
+    pub pairings: Vec<Pairing>,
+    pub significant_events: Vec<SignificantEvent>,
+}
+
+impl FixtureParse {
+    fn new(kind: FixtureKind) -> Self {
+        Self {
+            kind,
+            articles: Vec::new(),
+            events: Vec::new(),
+            placements: Vec::new(),
+            army_lists: Vec::new(),
+            pairings: Vec::new(),
+            significant_events: Vec::new(),
+        }
+    }
+
+    fn pin_timestamps(&mut self) {
+        let epoch = DateTime::<Utc>::UNIX_EPOCH;
+        self.events.iter_mut().for_each(|e| e.created_at = epoch);
+        self.placements
+            .iter_mut()
+            .for_each(|p| p.created_at = epoch);
+        self.army_lists
+            .iter_mut()
+            .for_each(|l| l.created_at = epoch);
+        self.pairings.iter_mut().for_each(|p| p.created_at = epoch);
+        self.significant_events
+            .iter_mut()
+            .for_each(|s| s.created_at = epoch);
+    }
+}
+
+/// Detect the fixture kind from its content.
+pub fn detect_fixture_kind(content: &str) -> Option<FixtureKind> {
+    let trimmed = content.trim_start();
+    if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        let value: Value = serde_json::from_str(trimmed).ok()?;
+        return detect_bcp_kind(&value);
+    }
+
+    if trimmed.starts_with("<?xml") || trimmed.contains("<rss") {
+        return Some(FixtureKind::RssFeed);
+    }
+
+    let lower = content.to_lowercase();
+    if lower.contains("warhammer-community.com")
+        || lower.contains("balance dataslate")
+        || lower.contains("warhammer community")
+    {
+        return Some(FixtureKind::WarhammerCommunity);
+    }
+
+    if !lower.contains("<html") && !lower.contains("<article") {
+        return None;
+    }
+    let base = Url::parse("https://www.goonhammer.com/").expect("valid base URL");
+    if !discover_goonhammer_articles(content, &base).is_empty() {
+        Some(FixtureKind::GoonhammerListing)
+    } else {
+        Some(FixtureKind::GoonhammerArticle)
+    }
+}
+
+/// Classify BCP JSON by the shape of its records.
+fn detect_bcp_kind(value: &Value) -> Option<FixtureKind> {
+    if value.get("active").is_some() {
+        return Some(FixtureKind::BcpPlayers);
+    }
+    let records = match value {
+        Value::Array(items) => items.as_slice(),
+        Value::Object(map) => match map.get("data").or_else(|| map.get("results")) {
+            Some(Value::Array(items)) => items.as_slice(),
+            _ => {
+                let is_list = ["armyList", "armyListText", "list"]
+                    .iter()
+                    .any(|k| map.contains_key(*k));
+                return is_list.then_some(FixtureKind::BcpArmyList);
+            }
+        },
+        _ => return None,
+    };
+
+    let first = records.first()?.as_object()?;
+    if first.contains_key("player1") || first.contains_key("metaData") {
+        Some(FixtureKind::BcpPairings)
+    } else if first.contains_key("user") || first.contains_key("faction") {
+        Some(FixtureKind::BcpPlayers)
+    } else if first.contains_key("name") {
+        Some(FixtureKind::BcpEvents)
+    } else {
+        None
+    }
+}
+
+/// Parse a fixture without AI.
+///
+/// `origin` identifies the fixture (path or URL) and is used as the source
+/// URL, and as the event ID for BCP pairing/player dumps that carry no event
+/// record of their own.
+pub fn parse_fixture(content: &str, origin: &str) -> Result<FixtureParse, FixtureError> {
+    let kind = detect_fixture_kind(content).ok_or(FixtureError::Unrecognized)?;
+    let mut parsed = FixtureParse::new(kind);
+
+    match kind {
+        FixtureKind::GoonhammerArticle => parse_goonhammer_article(content, origin, &mut parsed),
+        FixtureKind::GoonhammerListing => {
+            let base = Url::parse("https://www.goonhammer.com/").expect("valid base URL");
+            parsed.articles = discover_goonhammer_articles(content, &base);
+        }
+        FixtureKind::RssFeed => parsed.articles = discover_from_rss(content),
+        FixtureKind::WarhammerCommunity => {
+            parsed
+                .significant_events
+                .extend(parse_warhammer_community(content, origin));
+        }
+        FixtureKind::BcpEvents => {
+            let events: Vec<BcpEvent> = bcp_records(content)?;
+            parsed.events = events.iter().map(|e| event_from_bcp(e, None)).collect();
+        }
+        FixtureKind::BcpPlayers => {
+            let players: Vec<BcpPlayerV1> =
+                match serde_json::from_str::<BcpPlayersResponse>(content) {
+                    Ok(response) if !response.active.is_empty() => response.active,
+                    _ => bcp_records(content)?,
+                };
+            let event_id = EntityId::from(origin);
+            parsed.placements = players
+                .iter()
+                .filter(|p| p.dropped != Some(true))
+                .enumerate()
+                .map(|(i, p)| {
+                    Placement::new(
+                        event_id.clone(),
+                        EntityId::from("current"),
+                        i as u32 + 1,
+                        p.full_name(),
+                        p.faction_name().unwrap_or_else(|| "Unknown".to_string()),
+                    )
+                    .with_confidence(Confidence::Low)
+                })
+                .collect();
+        }
+        FixtureKind::BcpPairings => {
+            let pairings: Vec<BcpPairing> = bcp_records(content)?;
+            let event_id = EntityId::from(origin);
+            parsed.placements = standings_from_pairings(&pairings, &[])
+                .iter()
+                .map(|s| placement_from_bcp(s, event_id.clone(), None, None))
+                .collect();
+            parsed.pairings = pairings_from_bcp(&pairings, &event_id, None);
+        }
+        FixtureKind::BcpArmyList => {
+            let list: BcpArmyList = serde_json::from_str(content)?;
+            let mut army_list = army_list_from_bcp(
+                &list,
+                EntityId::from(origin),
+                NaiveDate::default(),
+                origin,
+                None,
+            );
+            army_list.set_units(parse_units_from_raw_text(&army_list.raw_text));
+            if let Some(detachment) = list.detachment {
+                army_list = army_list.with_detachment(detachment);
+            }
+            parsed.army_lists.push(army_list);
+        }
+    }
+
+    parsed.pin_timestamps();
+    Ok(parsed)
+}
+
+/// Deserialize BCP records from either a paginated wrapper or a plain array.
+fn bcp_records<T: serde::de::DeserializeOwned>(content: &str) -> Result<Vec<T>, FixtureError> {
+    if let Ok(response) = serde_json::from_str::<BcpListResponse<T>>(content) {
+        return Ok(response.data);
+    }
+    Ok(serde_json::from_str::<Vec<T>>(content)?)
+}
+
+fn regex(cell: &'static OnceLock<Regex>, pattern: &str) -> &'static Regex {
+    cell.get_or_init(|| Regex::new(pattern).expect("valid fixture regex"))
+}
+
+/// Find a "June 23, 2025" or ISO date in text.
+fn find_date(text: &str) -> Option<NaiveDate> {
+    static LONG: OnceLock<Regex> = OnceLock::new();
+    static ISO: OnceLock<Regex> = OnceLock::new();
+    let long = regex(&LONG, r"([A-Z][a-z]+ \d{1,2}, \d{4})");
+    let iso = regex(&ISO, r"(\d{4}-\d{2}-\d{2})");
+
+    long.captures_iter(text)
+        .find_map(|c| NaiveDate::parse_from_str(&c[1], "%B %d, %Y").ok())
+        .or_else(|| {
+            iso.captures_iter(text)
+                .find_map(|c| NaiveDate::parse_from_str(&c[1], "%Y-%m-%d").ok())
+        })
+}
+
+fn element_text(el: &ElementRef) -> String {
+    el.text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A parsed "Name - Faction (Subfaction, Detachment) - W-L" results line.
+struct ResultLine {
+    player: String,
+    faction: String,
+    subfaction: Option<String>,
+    detachment: Option<String>,
+    record: Option<(u32, u32, u32)>,
+}
+
+fn parse_result_line(line: &str) -> Option<ResultLine> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = regex(
+        &RE,
+        r"^(?P<player>.+?)\s+[-–]\s+(?P<faction>[^()]+?)\s*(?:\((?P<detail>[^)]*)\))?\s*(?:[-–]\s*(?P<w>\d+)-(?P<l>\d+)(?:-(?P<d>\d+))?)?$",
+    );
+    let caps = re.captures(line.trim())?;
+
+    let detail: Vec<String> = caps
+        .name("detail")
+        .map(|d| {
+            d.as_str()
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    let (subfaction, detachment) = match detail.as_slice() {
+        [] => (None, None),
+        [detachment] => (None, Some(detachment.clone())),
+        [subfaction, .., detachment] => (Some(subfaction.clone()), Some(detachment.clone())),
+    };
+
+    let record = match (caps.name("w"), caps.name("l")) {
+        (Some(w), Some(l)) => Some((
+            w.as_str().parse().ok()?,
+            l.as_str().parse().ok()?,
+            caps.name("d")
+                .map_or(Some(0), |d| d.as_str().parse().ok())?,
+        )),
+        _ => None,
+    };
+
+    Some(ResultLine {
+        player: caps["player"].trim().to_string(),
+        faction: caps["faction"].trim().to_string(),
+        subfaction,
+        detachment,
+        record,
+    })
+}
+
+/// Heuristic parse of a Goonhammer results article.
+///
+/// Each `h2` starts an event; `ol > li` lines under it are placements in rank
+/// order; an `h4` naming a player followed by `div.army-list` is that
+/// player's list.
+fn parse_goonhammer_article(html: &str, origin: &str, parsed: &mut FixtureParse) {
+    static PLAYERS: OnceLock<Regex> = OnceLock::new();
+    static ROUNDS: OnceLock<Regex> = OnceLock::new();
+    static LOCATION: OnceLock<Regex> = OnceLock::new();
+    static LIST_HEADER: OnceLock<Regex> = OnceLock::new();
+    static DETACHMENT: OnceLock<Regex> = OnceLock::new();
+    static TOTAL: OnceLock<Regex> = OnceLock::new();
+    let players_re = regex(&PLAYERS, r"(\d+)[- ]player");
+    let rounds_re = regex(&ROUNDS, r"(\d+)[- ]round");
+    let location_re = regex(&LOCATION, r"\bin ((?:[A-Z][\w'-]*)(?:,? [A-Z][\w'-]*)*)");
+    let list_header_re = regex(&LIST_HEADER, r"(?i)place\s+[-–]\s+(.+?)\s+[-–]\s+(.+)$");
+    let detachment_re = regex(&DETACHMENT, r"(?im)^\s*detachment:\s*(.+?)\s*$");
+    let total_re = regex(&TOTAL, r"(?i)total:?\s*(\d+)\s*(?:pts|points)");
+
+    let document = Html::parse_document(html);
+    let date = find_date(&extract_text_from_html(html)).unwrap_or_else(|| {
+        tracing::warn!("No article date found in {}; using today", origin);
+        Utc::now().date_naive()
+    });
+    let blocks =
+        Selector::parse("h2, h4, p, ol > li, div.army-list").expect("valid fixture selector");
+
+    let mut current: Option<usize> = None;
+    let mut rank = 0;
+    let mut list_owner: Option<(String, String)> = None;
+
+    for el in document.select(&blocks) {
+        let text = element_text(&el);
+        match el.value().name() {
+            "h2" => {
+                let event = Event::new(
+                    text,
+                    date,
+                    origin.to_string(),
+                    "goonhammer".to_string(),
+                    EntityId::from("current"),
+                )
+                .with_confidence(Confidence::Low);
+                parsed.events.push(event);
+                current = Some(parsed.events.len() - 1);
+                rank = 0;
+            }
+            "p" => {
+                let Some(idx) = current else { continue };
+                let event = &mut parsed.events[idx];
+                if event.player_count.is_none() {
+                    event.player_count = players_re.captures(&text).and_then(|c| c[1].parse().ok());
+                }
+                if event.round_count.is_none() {
+                    event.round_count = rounds_re.captures(&text).and_then(|c| c[1].parse().ok());
+                }
+                if event.location.is_none() {
+                    event.location = location_re.captures(&text).map(|c| c[1].to_string());
+                }
+            }
+            "li" => {
+                let Some(idx) = current else { continue };
+                let Some(line) = parse_result_line(&text) else {
+                    continue;
+                };
+                rank += 1;
+                let event = &parsed.events[idx];
+                let mut placement = Placement::new(
+                    event.id.clone(),
+                    event.epoch_id.clone(),
+                    rank,
+                    line.player,
+                    line.faction,
+                )
+                .with_confidence(Confidence::Low);
+                if let Some(subfaction) = line.subfaction {
+                    placement = placement.with_subfaction(subfaction);
+                }
+                if let Some(detachment) = line.detachment {
+                    placement = placement.with_detachment(detachment);
+                }
+                if let Some((w, l, d)) = line.record {
+                    placement = placement.with_record(w, l, d);
+                }
+                parsed.placements.push(placement);
+            }
+            "h4" => {
+                list_owner = list_header_re
+                    .captures(&text)
+                    .map(|c| (c[1].trim().to_string(), c[2].trim().to_string()));
+            }
+            "div" => {
+                let Some(idx) = current else { continue };
+                let raw_text: String = el.text().collect::<String>().trim().to_string();
+                // Goonhammer lists bullet every unit with "- ", which the BCP
+                // parser would otherwise read as wargear lines
+                let unbulleted: String = raw_text
+                    .lines()
+                    .map(|l| l.trim_start().trim_start_matches("- "))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let units = parse_units_from_raw_text(&unbulleted);
+                let total = total_re
+                    .captures(&raw_text)
+                    .and_then(|c| c[1].parse().ok())
+                    .unwrap_or_else(|| units.iter().filter_map(|u| u.points).sum());
+                let (player, faction) = list_owner.take().unzip();
+                let event = &parsed.events[idx];
+
+                let mut list = ArmyList::new(
+                    faction.unwrap_or_else(|| "Unknown".to_string()),
+                    total,
+                    units,
+                    raw_text.clone(),
+                )
+                .with_event_id(event.id.clone())
+                .with_event_date(event.date)
+                .with_source_url(origin.to_string())
+                .with_confidence(Confidence::Low);
+                if let Some(detachment) = detachment_re.captures(&raw_text) {
+                    list = list.with_detachment(detachment[1].to_string());
+                }
+                if let Some(ref player) = player {
+                    list = list.with_player_name(player.clone());
+                    if let Some(placement) = parsed
+                        .placements
+                        .iter_mut()
+                        .find(|p| p.event_id == event.id && &p.player_name == player)
+                    {
+                        placement.list_id = Some(list.id.clone());
+                    }
+                }
+                parsed.army_lists.push(list);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Parse a Warhammer Community balance / FAQ post into a significant event.
+fn parse_warhammer_community(html: &str, origin: &str) -> Option<SignificantEvent> {
+    let document = Html::parse_document(html);
+    let title_sel = Selector::parse("h1, title").expect("valid fixture selector");
+    let pdf_sel = Selector::parse(r#"a[href$=".pdf"]"#).expect("valid fixture selector");
+    let item_sel = Selector::parse("li").expect("valid fixture selector");
+
+    let title = document
+        .select(&title_sel)
+        .map(|el| element_text(&el))
+        .find(|t| !t.is_empty())?;
+    let date = find_date(&extract_text_from_html(html))?;
+
+    let event_type = if title.to_lowercase().contains("edition") {
+        SignificantEventType::EditionRelease
+    } else {
+        SignificantEventType::BalanceUpdate
+    };
+    let mut event = SignificantEvent::new(event_type, date, title, origin.to_string())
+        .with_confidence(Confidence::Low);
+
+    if let Some(href) = document
+        .select(&pdf_sel)
+        .find_map(|a| a.value().attr("href"))
+    {
+        event = event.with_pdf_url(href.to_string());
+    }
+    let highlights: Vec<String> = document
+        .select(&item_sel)
+        .map(|el| element_text(&el))
+        .filter(|t| !t.is_empty())
+        .collect();
+    if !highlights.is_empty() {
+        event = event.with_summary(highlights.join("; "));
+    }
+
+    Some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GOONHAMMER: &str = include_str!("../../tests/fixtures/goonhammer_sample.html");
+    const WARCOM: &str = include_str!("../../tests/fixtures/warhammer_community_balance.html");
+
+    #[test]
+    fn test_detect_fixture_kind() {
+        assert_eq!(
+            detect_fixture_kind(GOONHAMMER),
+            Some(FixtureKind::GoonhammerArticle)
+        );
+        assert_eq!(
+            detect_fixture_kind(WARCOM),
+            Some(FixtureKind::WarhammerCommunity)
+        );
+        assert_eq!(
+            detect_fixture_kind(r#"{"data": [{"name": "GT", "eventDate": "2025-06-01"}]}"#),
+            Some(FixtureKind::BcpEvents)
+        );
+        assert_eq!(
+            detect_fixture_kind(r#"{"active": [], "deleted": []}"#),
+            Some(FixtureKind::BcpPlayers)
+        );
+        assert_eq!(
+            detect_fixture_kind(r#"{"armyListText": "Orks"}"#),
+            Some(FixtureKind::BcpArmyList)
+        );
+        assert_eq!(detect_fixture_kind("just some text"), None);
+    }
+
+    #[test]
+    fn test_parse_goonhammer_article() {
+        let parsed = parse_fixture(GOONHAMMER, "goonhammer_sample.html").unwrap();
+
+        assert_eq!(parsed.events.len(), 2);
+        let london = &parsed.events[0];
+        assert_eq!(london.name, "London GT 2025");
+        assert_eq!(london.date, NaiveDate::from_ymd_opt(2025, 6, 23).unwrap());
+        assert_eq!(london.player_count, Some(96));
+        assert_eq!(london.round_count, Some(5));
+        assert_eq!(london.location.as_deref(), Some("London, UK"));
+
+        assert_eq!(parsed.placements.len(), 5);
+        let jane = &parsed.placements[1];
+        assert_eq!(jane.rank, 2);
+        assert_eq!(jane.faction, "Space Marines");
+        assert_eq!(jane.subfaction.as_deref(), Some("Ultramarines"));
+        assert_eq!(jane.detachment.as_deref(), Some("Gladius Task Force"));
+        assert_eq!(jane.record.as_ref().map(|r| r.wins), Some(4));
+        assert_eq!(parsed.placements[3].event_id, parsed.events[1].id);
+
+        assert_eq!(parsed.army_lists.len(), 1);
+        let list = &parsed.army_lists[0];
+        assert_eq!(list.total_points, 2000);
+        assert_eq!(list.detachment.as_deref(), Some("Soulrender"));
+        assert!(list.units.iter().any(|u| u.name == "Yvraine"));
+        assert_eq!(parsed.placements[0].list_id, Some(list.id.clone()));
+    }
+
+    #[test]
+    fn test_parse_warhammer_community() {
+        let parsed = parse_fixture(WARCOM, "warcom.html").unwrap();
+        assert_eq!(parsed.significant_events.len(), 1);
+        let event = &parsed.significant_events[0];
+        assert_eq!(event.event_type, SignificantEventType::BalanceUpdate);
+        assert_eq!(event.date, NaiveDate::from_ymd_opt(2025, 3, 15).unwrap());
+        assert!(event.pdf_url.as_deref().unwrap().ends_with(".pdf"));
+    }
+
+    #[test]
+    fn test_parse_bcp_pairings() {
+        let json = r#"{"data": [
+            {"round": 1,
+             "player1": {"_id": "a", "firstName": "Ann", "lastName": "Lee", "armyName": "Orks"},
+             "player2": {"_id": "b", "firstName": "Bo", "lastName": "Kim", "armyName": "Necrons"},
+             "metaData": {"p1-gameResult": 2, "p2-gameResult": 0, "p1-gamePoints": 80, "p2-gamePoints": 40}}
+        ]}"#;
+        let parsed = parse_fixture(json, "event-123").unwrap();
+        assert_eq!(parsed.kind, FixtureKind::BcpPairings);
+        assert_eq!(parsed.pairings.len(), 1);
+        assert_eq!(parsed.placements[0].player_name, "Ann Lee");
+        assert_eq!(parsed.placements[0].rank, 1);
+        assert_eq!(parsed.placements[0].created_at, DateTime::<Utc>::UNIX_EPOCH);
+    }
+}
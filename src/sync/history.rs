@@ -0,0 +1,140 @@
+//! Rolling history of sync runs.
+//!
+//! Every finished [`SyncOrchestrator::sync_once`](super::SyncOrchestrator)
+//! appends one [`SyncRun`] to `state/sync_history.jsonl`, so a cron job
+//! that failed overnight, or a source whose counts dropped to zero, shows
+//! up without digging through logs. Only the newest [`HISTORY_LIMIT`] runs
+//! are kept.
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::SyncStatus;
+use crate::storage::{JsonlReader, JsonlWriter, StorageConfig, StorageError};
+
+/// Runs kept in the history file.
+pub const HISTORY_LIMIT: usize = 500;
+
+/// What one source contributed to a run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SourceRun {
+    /// Source key, as in the sync cursor (`bcp`, `goonhammer`, ...)
+    pub source: String,
+    pub events_synced: u32,
+    pub placements_synced: u32,
+    pub lists_normalized: u32,
+    pub items_for_review: u32,
+    /// Set when the source failed outright
+    #[serde(default)]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// One sync run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncRun {
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    /// `completed`, or `failed` when any source failed or the run was
+    /// cancelled
+    pub status: SyncStatus,
+    #[serde(default)]
+    pub cancelled: bool,
+    pub sources: Vec<SourceRun>,
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+impl SyncRun {
+    pub fn events_synced(&self) -> u32 {
+        self.sources.iter().map(|s| s.events_synced).sum()
+    }
+
+    pub fn placements_synced(&self) -> u32 {
+        self.sources.iter().map(|s| s.placements_synced).sum()
+    }
+
+    pub fn duration_ms(&self) -> u64 {
+        (self.finished_at - self.started_at)
+            .num_milliseconds()
+            .max(0) as u64
+    }
+}
+
+/// Path of the history file.
+pub fn history_path(storage: &StorageConfig) -> PathBuf {
+    storage.state_dir().join("sync_history.jsonl")
+}
+
+/// Append a run, dropping the oldest once over [`HISTORY_LIMIT`].
+pub fn record_run(storage: &StorageConfig, run: &SyncRun) -> Result<(), StorageError> {
+    let path = history_path(storage);
+    let writer = JsonlWriter::new(path.clone());
+    writer.append(run)?;
+
+    let reader = JsonlReader::<SyncRun>::new(path);
+    if reader.count()? > HISTORY_LIMIT {
+        let runs = reader.read_all()?;
+        writer.write_all(&runs[runs.len().saturating_sub(HISTORY_LIMIT)..])?;
+    }
+    Ok(())
+}
+
+/// Recorded runs, newest first.
+pub fn read_history(storage: &StorageConfig) -> Result<Vec<SyncRun>, StorageError> {
+    let mut runs: Vec<SyncRun> = JsonlReader::new(history_path(storage)).read_all()?;
+    runs.reverse();
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(minute: u32, events: u32, error: Option<&str>) -> SyncRun {
+        let started_at = DateTime::parse_from_rfc3339(&format!("2026-03-01T02:{:02}:00Z", minute))
+            .unwrap()
+            .with_timezone(&Utc);
+        SyncRun {
+            started_at,
+            finished_at: started_at + chrono::Duration::seconds(30),
+            status: if error.is_some() {
+                SyncStatus::Failed
+            } else {
+                SyncStatus::Completed
+            },
+            cancelled: false,
+            sources: vec![SourceRun {
+                source: "bcp".to_string(),
+                events_synced: events,
+                error: error.map(str::to_string),
+                ..Default::default()
+            }],
+            errors: error.into_iter().map(str::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn test_history_newest_first_and_capped() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        assert!(read_history(&storage).unwrap().is_empty());
+
+        record_run(&storage, &run(0, 4, None)).unwrap();
+        record_run(&storage, &run(1, 0, Some("BCP API timed out"))).unwrap();
+        let runs = read_history(&storage).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].status, SyncStatus::Failed);
+        assert_eq!(runs[1].events_synced(), 4);
+        assert_eq!(runs[1].duration_ms(), 30_000);
+
+        for i in 0..HISTORY_LIMIT as u32 {
+            record_run(&storage, &run(i % 60, i, None)).unwrap();
+        }
+        let runs = read_history(&storage).unwrap();
+        assert_eq!(runs.len(), HISTORY_LIMIT);
+        assert_eq!(runs[0].events_synced(), HISTORY_LIMIT as u32 - 1);
+    }
+}
@@ -0,0 +1,209 @@
+//! Ledger of ingest issues.
+//!
+//! Failed articles and events used to end up only as strings in the run's
+//! error list. The orchestrator now notes each failure (and each success)
+//! while it syncs and folds them into `state/ingest_issues.jsonl` when the
+//! run finishes, so failures can be listed and retried one by one with
+//! `meta-agent issues` or `GET /api/issues`.
+
+use std::cmp::Reverse;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+
+use super::SyncError;
+use crate::agents::AgentError;
+use crate::fetch::FetchError;
+use crate::models::{IngestIssue, IssueKind};
+use crate::storage::{JsonlReader, JsonlWriter, StorageConfig, StorageError};
+
+/// Issues kept in the ledger; the oldest resolved ones go first.
+pub const ISSUES_LIMIT: usize = 2000;
+
+/// Outcome of one item, noted during a run.
+#[derive(Debug, Clone)]
+pub enum IssueUpdate {
+    /// The item failed; `attempts` and `first_seen` carry over from an
+    /// existing issue, which reopens if it was resolved
+    Failed(Box<IngestIssue>),
+    /// The item imported; open issues for it are resolved
+    Succeeded { source: String, source_url: String },
+}
+
+/// Path of the ledger file.
+pub fn issues_path(storage: &StorageConfig) -> PathBuf {
+    storage.state_dir().join("ingest_issues.jsonl")
+}
+
+/// Every recorded issue, most recently seen first.
+pub fn read_issues(storage: &StorageConfig) -> Result<Vec<IngestIssue>, StorageError> {
+    let mut issues: Vec<IngestIssue> = JsonlReader::new(issues_path(storage)).read_all()?;
+    issues.sort_by_key(|i| Reverse(i.last_seen));
+    Ok(issues)
+}
+
+/// Fold a run's outcomes into the ledger.
+pub fn record_updates(
+    storage: &StorageConfig,
+    updates: Vec<IssueUpdate>,
+    now: DateTime<Utc>,
+) -> Result<(), StorageError> {
+    if updates.is_empty() {
+        return Ok(());
+    }
+    let path = issues_path(storage);
+    let mut issues: Vec<IngestIssue> = JsonlReader::new(path.clone()).read_all()?;
+
+    for update in updates {
+        match update {
+            IssueUpdate::Failed(mut issue) => match issues.iter_mut().find(|i| i.id == issue.id) {
+                Some(existing) => {
+                    issue.attempts = existing.attempts + 1;
+                    issue.first_seen = existing.first_seen;
+                    issue.raw_path = issue.raw_path.or(existing.raw_path.take());
+                    *existing = *issue;
+                }
+                None => issues.push(*issue),
+            },
+            IssueUpdate::Succeeded { source, source_url } => {
+                for issue in issues
+                    .iter_mut()
+                    .filter(|i| i.is_open() && i.source == source && i.source_url == source_url)
+                {
+                    issue.resolved_at = Some(now);
+                }
+            }
+        }
+    }
+
+    if issues.len() > ISSUES_LIMIT {
+        let mut excess = issues.len() - ISSUES_LIMIT;
+        let mut resolved: Vec<(DateTime<Utc>, usize)> = issues
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, i)| i.resolved_at.map(|at| (at, idx)))
+            .collect();
+        resolved.sort();
+        let mut drop: Vec<usize> = resolved.iter().take(excess).map(|(_, idx)| *idx).collect();
+        excess -= drop.len();
+        drop.sort_unstable();
+        for idx in drop.into_iter().rev() {
+            issues.remove(idx);
+        }
+        // Still over: drop the longest-open issues
+        if excess > 0 {
+            issues.sort_by_key(|i| i.last_seen);
+            issues.drain(..excess);
+        }
+    }
+
+    JsonlWriter::new(path).write_all(&issues)?;
+    Ok(())
+}
+
+/// Classify a sync error, returning its kind and whether a retry can help.
+pub fn classify(error: &SyncError) -> (IssueKind, bool) {
+    match error {
+        SyncError::Fetch(e) => classify_fetch(e),
+        SyncError::Agent(e) => match e {
+            AgentError::Fetch(e) => classify_fetch(e),
+            AgentError::RateLimited(_) => (IssueKind::RateLimited, true),
+            AgentError::ResponseParseError(_) => (IssueKind::Parse, true),
+            AgentError::Pdf(_) => (IssueKind::Parse, false),
+            AgentError::Io(_) => (IssueKind::Storage, true),
+            AgentError::BackendUnavailable(_) | AgentError::Timeout(_) => (IssueKind::Ai, true),
+            AgentError::ExtractionRefused(_) => (IssueKind::Ai, false),
+        },
+        SyncError::Storage(_) => (IssueKind::Storage, true),
+        SyncError::NoSources | SyncError::Cancelled | SyncError::NotCached(_) => {
+            (IssueKind::Other, true)
+        }
+    }
+}
+
+fn classify_fetch(error: &FetchError) -> (IssueKind, bool) {
+    match error {
+        FetchError::Http(_) | FetchError::Io(_) => (IssueKind::Network, true),
+        FetchError::RateLimited { .. } => (IssueKind::RateLimited, true),
+        FetchError::HttpStatus { status, .. } => {
+            (IssueKind::Network, *status == 429 || *status >= 500)
+        }
+        FetchError::InvalidUrl(_) | FetchError::Json(_) => (IssueKind::Parse, false),
+        FetchError::ContentTooLarge { .. } => (IssueKind::Other, false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{IngestStage, RetryTarget};
+    use chrono::NaiveDate;
+
+    fn failure(url: &str, at: DateTime<Utc>) -> IssueUpdate {
+        let error = SyncError::Fetch(FetchError::HttpStatus {
+            status: 503,
+            message: "unavailable".to_string(),
+        });
+        let (kind, retryable) = classify(&error);
+        IssueUpdate::Failed(Box::new(IngestIssue {
+            id: IngestIssue::issue_id("goonhammer", url, IngestStage::Fetch),
+            source: "goonhammer".to_string(),
+            source_url: url.to_string(),
+            stage: IngestStage::Fetch,
+            kind,
+            error: error.to_string(),
+            retryable,
+            raw_path: None,
+            target: RetryTarget::Article {
+                url: url.to_string(),
+                date: NaiveDate::from_ymd_opt(2026, 2, 1).unwrap(),
+            },
+            attempts: 1,
+            first_seen: at,
+            last_seen: at,
+            run_started_at: Some(at),
+            resolved_at: None,
+        }))
+    }
+
+    #[test]
+    fn test_ledger_tracks_attempts_and_resolution() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        let t0 = Utc::now();
+        let t1 = t0 + chrono::Duration::hours(6);
+
+        record_updates(
+            &storage,
+            vec![failure("https://a", t0), failure("https://b", t0)],
+            t0,
+        )
+        .unwrap();
+        record_updates(
+            &storage,
+            vec![
+                failure("https://a", t1),
+                IssueUpdate::Succeeded {
+                    source: "goonhammer".to_string(),
+                    source_url: "https://b".to_string(),
+                },
+            ],
+            t1,
+        )
+        .unwrap();
+
+        let issues = read_issues(&storage).unwrap();
+        assert_eq!(issues.len(), 2);
+        let a = issues.iter().find(|i| i.source_url == "https://a").unwrap();
+        assert_eq!(a.attempts, 2);
+        assert_eq!(a.first_seen, t0);
+        assert_eq!(a.last_seen, t1);
+        assert_eq!((a.kind, a.retryable), (IssueKind::Network, true));
+        assert!(a.is_open());
+        let b = issues.iter().find(|i| i.source_url == "https://b").unwrap();
+        assert_eq!(b.resolved_at, Some(t1));
+
+        let refused = SyncError::Agent(AgentError::ExtractionRefused("no table".into()));
+        assert_eq!(classify(&refused), (IssueKind::Ai, false));
+    }
+}
@@ -0,0 +1,717 @@
+//! Merging duplicate events.
+//!
+//! Sync-time dedup ([`find_duplicate_event`](super::convert::find_duplicate_event))
+//! needs the same date and a close name, so the same GT reported by two
+//! sources under different names slips through. [`merge_events`] folds one
+//! event into another: its placements, lists and pairings are re-pointed to
+//! the surviving event, records the survivor already has are reconciled
+//! rather than duplicated, and the duplicate is removed.
+//! [`suggest_duplicates`] lists likely pairs to review first.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use super::convert::event_name_similarity;
+use super::fix_event::replace_entity;
+use super::normalize_player_name;
+use crate::api::merge_by_id;
+use crate::models::{ArmyList, EntityId, EpochId, Event, Pairing, Placement, Timestamped};
+use crate::storage::jsonl::list_epochs;
+use crate::storage::{EntityType, JsonlReader, StorageConfig, StorageError};
+
+/// Lowest score [`suggest_duplicates`] reports.
+pub const SUGGESTION_THRESHOLD: f64 = 0.5;
+
+/// Errors that stop a merge.
+#[derive(Debug, Error)]
+pub enum MergeEventsError {
+    #[error("Event not found: {0}")]
+    NotFound(String),
+
+    #[error("Cannot merge an event into itself: {0}")]
+    SameEvent(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// What a merge changed, or would change on a dry run.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EventMerge {
+    pub keep_id: String,
+    pub remove_id: String,
+    pub keep_epoch: String,
+    pub remove_epoch: String,
+    /// Placements moved to the surviving event
+    pub placements_moved: usize,
+    /// Placements matched by player to one the survivor already had; their
+    /// missing fields are filled in and the duplicate dropped
+    pub placements_reconciled: usize,
+    pub lists_moved: usize,
+    pub lists_dropped: usize,
+    pub pairings_moved: usize,
+    pub pairings_dropped: usize,
+}
+
+/// A likely duplicate pair.
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateSuggestion {
+    /// The event with more placements, suggested to keep
+    pub keep: EventSummary,
+    pub remove: EventSummary,
+    /// Name similarity, plus a bonus for a matching location (0.0 to 1.0)
+    pub score: f64,
+}
+
+/// Enough of an event to tell duplicates apart.
+#[derive(Debug, Clone, Serialize)]
+pub struct EventSummary {
+    pub id: String,
+    pub name: String,
+    pub date: chrono::NaiveDate,
+    pub location: Option<String>,
+    pub source_name: String,
+    pub epoch_id: String,
+    pub placements: usize,
+}
+
+/// One epoch's records.
+struct EpochRecords {
+    epoch_id: String,
+    events: Vec<Event>,
+    placements: Vec<Placement>,
+    lists: Vec<ArmyList>,
+    pairings: Vec<Pairing>,
+}
+
+impl EpochRecords {
+    fn load(storage: &StorageConfig, epoch_id: &str) -> Result<Self, StorageError> {
+        Ok(Self {
+            epoch_id: epoch_id.to_string(),
+            events: read(storage, EntityType::Event, epoch_id, |e: &Event| {
+                e.id.as_str()
+            })?,
+            placements: read(storage, EntityType::Placement, epoch_id, |p: &Placement| {
+                p.id.as_str()
+            })?,
+            lists: read(storage, EntityType::ArmyList, epoch_id, |l: &ArmyList| {
+                l.id.as_str()
+            })?,
+            pairings: read(storage, EntityType::Pairing, epoch_id, |p: &Pairing| {
+                p.id.as_str()
+            })?,
+        })
+    }
+
+    fn save(&self, storage: &StorageConfig) -> Result<(), StorageError> {
+        replace_entity(storage, EntityType::Event, &self.epoch_id, &self.events)?;
+        replace_entity(
+            storage,
+            EntityType::Placement,
+            &self.epoch_id,
+            &self.placements,
+        )?;
+        replace_entity(storage, EntityType::ArmyList, &self.epoch_id, &self.lists)?;
+        replace_entity(storage, EntityType::Pairing, &self.epoch_id, &self.pairings)
+    }
+
+    fn has_event(&self, id: &str) -> bool {
+        self.events.iter().any(|e| e.id.as_str() == id)
+    }
+}
+
+fn read<T: serde::de::DeserializeOwned + Timestamped>(
+    storage: &StorageConfig,
+    entity: EntityType,
+    epoch_id: &str,
+    id: impl Fn(&T) -> &str,
+) -> Result<Vec<T>, StorageError> {
+    let records = JsonlReader::<T>::for_entity(storage, entity, epoch_id).read_all()?;
+    Ok(merge_by_id(records, id))
+}
+
+fn find_epoch(storage: &StorageConfig, event_id: &str) -> Result<Option<String>, StorageError> {
+    for epoch_id in list_epochs(storage)? {
+        let events =
+            JsonlReader::<Event>::for_entity(storage, EntityType::Event, &epoch_id).read_all()?;
+        if events.iter().any(|e| e.id.as_str() == event_id) {
+            return Ok(Some(epoch_id));
+        }
+    }
+    Ok(None)
+}
+
+/// Fold the event `remove_id` into `keep_id`. Unless `dry_run`, the
+/// affected epochs are rewritten.
+pub fn merge_events(
+    storage: &StorageConfig,
+    keep_id: &str,
+    remove_id: &str,
+    dry_run: bool,
+) -> Result<EventMerge, MergeEventsError> {
+    if keep_id == remove_id {
+        return Err(MergeEventsError::SameEvent(keep_id.to_string()));
+    }
+    let keep_epoch = find_epoch(storage, keep_id)?
+        .ok_or_else(|| MergeEventsError::NotFound(keep_id.to_string()))?;
+    let remove_epoch = find_epoch(storage, remove_id)?
+        .ok_or_else(|| MergeEventsError::NotFound(remove_id.to_string()))?;
+
+    let mut keep = EpochRecords::load(storage, &keep_epoch)?;
+    let mut other = if remove_epoch == keep_epoch {
+        None
+    } else {
+        Some(EpochRecords::load(storage, &remove_epoch)?)
+    };
+    let from = other.as_mut().unwrap_or(&mut keep);
+    debug_assert!(from.has_event(remove_id));
+
+    // Take the duplicate and everything recorded at it out of its epoch
+    let removed_event = take(&mut from.events, |e| e.id.as_str() == remove_id).remove(0);
+    let placements = take(&mut from.placements, |p| p.event_id.as_str() == remove_id);
+    // A list may be linked only through its placement, with no event of its own
+    let placement_lists: HashSet<&EntityId> = placements
+        .iter()
+        .filter_map(|p| p.list_id.as_ref())
+        .collect();
+    let lists = take(&mut from.lists, |l| {
+        l.event_id
+            .as_ref()
+            .is_some_and(|id| id.as_str() == remove_id)
+            || placement_lists.contains(&l.id)
+    });
+    let pairings = take(&mut from.pairings, |p| p.event_id.as_str() == remove_id);
+    for event in &mut from.events {
+        if event
+            .parent_id
+            .as_ref()
+            .is_some_and(|id| id.as_str() == remove_id)
+        {
+            event.parent_id = Some(EntityId::from(keep_id));
+            event.touch();
+        }
+    }
+
+    let mut merge = EventMerge {
+        keep_id: keep_id.to_string(),
+        remove_id: remove_id.to_string(),
+        keep_epoch: keep_epoch.clone(),
+        remove_epoch: remove_epoch.clone(),
+        ..Default::default()
+    };
+    absorb(
+        &mut keep,
+        keep_id,
+        removed_event,
+        placements,
+        lists,
+        pairings,
+        &mut merge,
+    );
+
+    if !dry_run {
+        keep.save(storage)?;
+        if let Some(other) = &other {
+            other.save(storage)?;
+        }
+    }
+    Ok(merge)
+}
+
+/// Remove and return the records matching `pred`.
+fn take<T>(records: &mut Vec<T>, pred: impl Fn(&T) -> bool) -> Vec<T> {
+    let (taken, kept) = std::mem::take(records).into_iter().partition(pred);
+    *records = kept;
+    taken
+}
+
+/// Move the duplicate's records into the surviving event's epoch.
+fn absorb(
+    epoch: &mut EpochRecords,
+    keep_id: &str,
+    removed: Event,
+    placements: Vec<Placement>,
+    lists: Vec<ArmyList>,
+    pairings: Vec<Pairing>,
+    merge: &mut EventMerge,
+) {
+    let event_id = EntityId::from(keep_id);
+    let epoch_id = EpochId::from(epoch.epoch_id.as_str());
+    let keep = epoch
+        .events
+        .iter_mut()
+        .find(|e| e.id.as_str() == keep_id)
+        .expect("surviving event is in its epoch");
+    if fill_event(keep, &removed) {
+        keep.touch();
+    }
+    let date = keep.date;
+
+    // Lists: one per player at the surviving event
+    let players_with_lists: HashMap<String, EntityId> = epoch
+        .lists
+        .iter()
+        .filter(|l| l.event_id.as_ref() == Some(&event_id))
+        .filter_map(|l| {
+            let player = normalize_player_name(l.player_name.as_deref()?);
+            Some((player, l.id.clone()))
+        })
+        .collect();
+    // Dropped list to the surviving list for the same player
+    let mut dropped_lists: HashMap<EntityId, EntityId> = HashMap::new();
+    for mut list in lists {
+        let player = list.player_name.as_deref().map(normalize_player_name);
+        if let Some(kept) = player.and_then(|p| players_with_lists.get(&p)) {
+            dropped_lists.insert(list.id.clone(), kept.clone());
+            merge.lists_dropped += 1;
+            continue;
+        }
+        list.event_id = Some(event_id.clone());
+        list.event_date = Some(date);
+        list.touch();
+        epoch.lists.push(list);
+        merge.lists_moved += 1;
+    }
+    epoch.lists = merge_by_id(std::mem::take(&mut epoch.lists), |l| l.id.as_str());
+
+    // Placements: matched by player name
+    let by_player: HashMap<String, usize> = epoch
+        .placements
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| p.event_id == event_id)
+        .map(|(i, p)| (normalize_player_name(&p.player_name), i))
+        .collect();
+    for mut placement in placements {
+        if let Some(kept) = placement
+            .list_id
+            .as_ref()
+            .and_then(|id| dropped_lists.get(id))
+        {
+            placement.list_id = Some(kept.clone());
+        }
+        match by_player.get(&normalize_player_name(&placement.player_name)) {
+            Some(&i) => {
+                let existing = &mut epoch.placements[i];
+                if fill_placement(existing, placement) {
+                    existing.touch();
+                }
+                merge.placements_reconciled += 1;
+            }
+            None => {
+                let mut moved = Placement::new(
+                    event_id.clone(),
+                    epoch_id.clone(),
+                    placement.rank,
+                    placement.player_name.clone(),
+                    placement.faction.clone(),
+                );
+                moved.created_at = placement.created_at;
+                placement.id = moved.id;
+                placement.event_id = event_id.clone();
+                placement.epoch_id = epoch_id.clone();
+                placement.touch();
+                epoch.placements.push(placement);
+                merge.placements_moved += 1;
+            }
+        }
+    }
+
+    // Pairings: one per round and pair of players
+    let game_key = |p: &Pairing| {
+        let mut players = [
+            normalize_player_name(&p.player1_name),
+            normalize_player_name(&p.player2_name),
+        ];
+        players.sort();
+        (p.round, players)
+    };
+    let games: HashSet<_> = epoch
+        .pairings
+        .iter()
+        .filter(|p| p.event_id == event_id)
+        .map(game_key)
+        .collect();
+    for mut pairing in pairings {
+        if games.contains(&game_key(&pairing)) {
+            merge.pairings_dropped += 1;
+            continue;
+        }
+        pairing.id = Pairing::new(
+            event_id.clone(),
+            epoch_id.clone(),
+            pairing.round,
+            pairing.player1_name.clone(),
+            pairing.player2_name.clone(),
+        )
+        .id;
+        pairing.event_id = event_id.clone();
+        pairing.epoch_id = epoch_id.clone();
+        pairing.touch();
+        epoch.pairings.push(pairing);
+        merge.pairings_moved += 1;
+    }
+}
+
+/// Fill the surviving event's missing details from the duplicate. Returns
+/// whether anything changed.
+fn fill_event(keep: &mut Event, removed: &Event) -> bool {
+    let mut changed = false;
+    let mut fill = |field: &mut Option<String>, from: &Option<String>| {
+        if field.is_none() && from.is_some() {
+            *field = from.clone();
+            changed = true;
+        }
+    };
+    fill(&mut keep.location, &removed.location);
+    fill(&mut keep.venue, &removed.venue);
+    fill(&mut keep.city, &removed.city);
+    fill(&mut keep.state, &removed.state);
+    fill(&mut keep.country, &removed.country);
+    fill(&mut keep.mission_pack, &removed.mission_pack);
+    for (field, from) in [
+        (&mut keep.player_count, removed.player_count),
+        (&mut keep.round_count, removed.round_count),
+        (&mut keep.game_size, removed.game_size),
+    ] {
+        if field.is_none() && from.is_some() {
+            *field = from;
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Fill a placement's missing details from the duplicate's placement for
+/// the same player. Returns whether anything changed.
+fn fill_placement(keep: &mut Placement, removed: Placement) -> bool {
+    let mut changed = false;
+    let mut fill = |field: &mut Option<String>, from: Option<String>| {
+        if field.is_none() && from.is_some() {
+            *field = from;
+            changed = true;
+        }
+    };
+    fill(&mut keep.subfaction, removed.subfaction);
+    fill(&mut keep.allegiance, removed.allegiance);
+    fill(&mut keep.detachment, removed.detachment);
+    fill(&mut keep.team, removed.team);
+    if keep.list_id.is_none() && removed.list_id.is_some() {
+        keep.list_id = removed.list_id;
+        changed = true;
+    }
+    if keep.record.is_none() && removed.record.is_some() {
+        keep.record = removed.record;
+        changed = true;
+    }
+    if keep.battle_points.is_none() && removed.battle_points.is_some() {
+        keep.battle_points = removed.battle_points;
+        changed = true;
+    }
+    changed
+}
+
+/// Likely duplicate events across every epoch, best match first: events
+/// within a day of each other whose names (and locations) are similar.
+/// Bracket events split from the same listing are never paired.
+pub fn suggest_duplicates(
+    storage: &StorageConfig,
+) -> Result<Vec<DuplicateSuggestion>, StorageError> {
+    let mut events = Vec::new();
+    for epoch_id in list_epochs(storage)? {
+        let records = EpochRecords::load(storage, &epoch_id)?;
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for placement in &records.placements {
+            *counts.entry(placement.event_id.as_str()).or_default() += 1;
+        }
+        for event in &records.events {
+            let placements = counts.get(event.id.as_str()).copied().unwrap_or(0);
+            events.push((event.clone(), placements));
+        }
+    }
+    events.sort_by_key(|a| a.0.date);
+
+    let mut suggestions = Vec::new();
+    for (i, (a, a_count)) in events.iter().enumerate() {
+        for (b, b_count) in &events[i + 1..] {
+            if (b.date - a.date).num_days() > 1 {
+                break;
+            }
+            if a.id == b.id || split_from_same_listing(a, b) {
+                continue;
+            }
+            let score = duplicate_score(a, b);
+            if score < SUGGESTION_THRESHOLD {
+                continue;
+            }
+            let (keep, remove) = if b_count > a_count {
+                ((b, *b_count), (a, *a_count))
+            } else {
+                ((a, *a_count), (b, *b_count))
+            };
+            suggestions.push(DuplicateSuggestion {
+                keep: summary(keep.0, keep.1),
+                remove: summary(remove.0, remove.1),
+                score,
+            });
+        }
+    }
+    suggestions.sort_by(|a, b| b.score.total_cmp(&a.score));
+    Ok(suggestions)
+}
+
+/// Name similarity scaled to 0.8, plus 0.2 when both events give the same
+/// location.
+fn duplicate_score(a: &Event, b: &Event) -> f64 {
+    let place = |e: &Event| {
+        e.city
+            .as_deref()
+            .or(e.location.as_deref())
+            .map(|s| s.trim().to_lowercase())
+    };
+    let same_place = matches!((place(a), place(b)), (Some(x), Some(y)) if x == y);
+    let score = event_name_similarity(&a.name, &b.name) * 0.8 + if same_place { 0.2 } else { 0.0 };
+    (score * 100.0).round() / 100.0
+}
+
+/// Whether two events are brackets of one listing, or played at different
+/// points limits.
+fn split_from_same_listing(a: &Event, b: &Event) -> bool {
+    let related = a.parent_id.as_ref() == Some(&b.id)
+        || b.parent_id.as_ref() == Some(&a.id)
+        || (a.parent_id.is_some() && a.parent_id == b.parent_id);
+    let sizes_differ = matches!((a.game_size, b.game_size), (Some(x), Some(y)) if x != y);
+    related || sizes_differ
+}
+
+fn summary(event: &Event, placements: usize) -> EventSummary {
+    EventSummary {
+        id: event.id.as_str().to_string(),
+        name: event.name.clone(),
+        date: event.date,
+        location: event.location.clone(),
+        source_name: event.source_name.clone(),
+        epoch_id: event.epoch_id.as_str().to_string(),
+        placements,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::JsonlWriter;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_merge_events_reconciles_placements() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        let date = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let epoch = EpochId::from("current");
+        let event = |name: &str, source: &str| {
+            let mut event = Event::new(
+                name.to_string(),
+                date,
+                format!("https://{}.example.com/gt", source),
+                source.to_string(),
+                epoch.clone(),
+            );
+            event.location = Some("London".to_string());
+            event
+        };
+        let mut keep = event("London GT 2026", "bcp");
+        keep.round_count = Some(5);
+        let mut remove = event("London Open", "goonhammer");
+        remove.player_count = Some(96);
+
+        let placement = |event: &Event, rank: u32, player: &str| {
+            Placement::new(
+                event.id.clone(),
+                epoch.clone(),
+                rank,
+                player.to_string(),
+                "Aeldari".to_string(),
+            )
+        };
+        let kept_alice = placement(&keep, 1, "Alice Smith");
+        let mut dup_alice = placement(&remove, 1, "alice  smith");
+        dup_alice.detachment = Some("Warhost".to_string());
+        let bob = placement(&remove, 2, "Bob Jones");
+        let mut pairing = Pairing::new(
+            remove.id.clone(),
+            epoch.clone(),
+            5,
+            "Alice Smith".to_string(),
+            "Bob Jones".to_string(),
+        );
+        pairing.player1_result = Some("win".to_string());
+
+        JsonlWriter::<Event>::for_entity(&storage, EntityType::Event, "current")
+            .write_all(&[keep.clone(), remove.clone()])
+            .unwrap();
+        JsonlWriter::<Placement>::for_entity(&storage, EntityType::Placement, "current")
+            .write_all(&[kept_alice.clone(), dup_alice, bob])
+            .unwrap();
+        JsonlWriter::<Pairing>::for_entity(&storage, EntityType::Pairing, "current")
+            .write_all(&[pairing])
+            .unwrap();
+
+        let suggestions = suggest_duplicates(&storage).unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].keep.id, remove.id.as_str());
+        assert!(suggestions[0].score >= SUGGESTION_THRESHOLD);
+
+        let merge = merge_events(&storage, keep.id.as_str(), remove.id.as_str(), false).unwrap();
+        assert_eq!(
+            (merge.placements_moved, merge.placements_reconciled),
+            (1, 1)
+        );
+        assert_eq!(merge.pairings_moved, 1);
+
+        let records = EpochRecords::load(&storage, "current").unwrap();
+        assert_eq!(records.events.len(), 1);
+        assert_eq!(records.events[0].player_count, Some(96));
+        assert_eq!(records.events[0].round_count, Some(5));
+        assert_eq!(records.placements.len(), 2);
+        assert!(records.placements.iter().all(|p| p.event_id == keep.id));
+        let alice = records.placements.iter().find(|p| p.rank == 1).unwrap();
+        assert_eq!(alice.id, kept_alice.id);
+        assert_eq!(alice.detachment.as_deref(), Some("Warhost"));
+        assert_eq!(records.pairings[0].event_id, keep.id);
+
+        assert!(matches!(
+            merge_events(&storage, keep.id.as_str(), remove.id.as_str(), false),
+            Err(MergeEventsError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_merge_events_repoints_dropped_list() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        let date = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let epoch = EpochId::from("current");
+        let keep = Event::new(
+            "London GT".to_string(),
+            date,
+            "https://bcp.example.com/gt".to_string(),
+            "bcp".to_string(),
+            epoch.clone(),
+        );
+        let remove = Event::new(
+            "London Open".to_string(),
+            date,
+            "https://goonhammer.example.com/gt".to_string(),
+            "goonhammer".to_string(),
+            epoch.clone(),
+        );
+        let list = |event: &Event, unit: &str| {
+            let mut list = ArmyList::new(
+                "Aeldari".to_string(),
+                2000,
+                vec![crate::models::Unit::new(unit.to_string(), 1)],
+                unit.to_string(),
+            );
+            list.event_id = Some(event.id.clone());
+            list.player_name = Some("Alice".to_string());
+            list
+        };
+        let kept_list = list(&keep, "Avatar of Khaine");
+        let dup_list = list(&remove, "Wraithknight");
+        let kept_alice = Placement::new(
+            keep.id.clone(),
+            epoch.clone(),
+            1,
+            "Alice".to_string(),
+            "Aeldari".to_string(),
+        );
+        let mut dup_alice = Placement::new(
+            remove.id.clone(),
+            epoch.clone(),
+            1,
+            "Alice".to_string(),
+            "Aeldari".to_string(),
+        );
+        dup_alice.list_id = Some(dup_list.id.clone());
+
+        JsonlWriter::<Event>::for_entity(&storage, EntityType::Event, "current")
+            .write_all(&[keep.clone(), remove.clone()])
+            .unwrap();
+        JsonlWriter::<Placement>::for_entity(&storage, EntityType::Placement, "current")
+            .write_all(&[kept_alice, dup_alice])
+            .unwrap();
+        JsonlWriter::<ArmyList>::for_entity(&storage, EntityType::ArmyList, "current")
+            .write_all(&[kept_list.clone(), dup_list])
+            .unwrap();
+
+        let merge = merge_events(&storage, keep.id.as_str(), remove.id.as_str(), false).unwrap();
+        assert_eq!(merge.lists_dropped, 1);
+
+        let records = EpochRecords::load(&storage, "current").unwrap();
+        assert_eq!(records.lists.len(), 1);
+        assert_eq!(records.placements.len(), 1);
+        assert_eq!(records.placements[0].list_id, Some(kept_list.id));
+    }
+
+    #[test]
+    fn test_merge_events_across_epochs_moves_placement_lists() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        let date = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let keep = Event::new(
+            "London GT".to_string(),
+            date,
+            "https://bcp.example.com/gt".to_string(),
+            "bcp".to_string(),
+            EpochId::from("current"),
+        );
+        let remove = Event::new(
+            "London Open".to_string(),
+            date,
+            "https://goonhammer.example.com/gt".to_string(),
+            "goonhammer".to_string(),
+            EpochId::from("previous"),
+        );
+        // Linked to the duplicate only through Bob's placement
+        let mut list = ArmyList::new(
+            "Orks".to_string(),
+            2000,
+            vec![crate::models::Unit::new("Ghazghkull Thraka".to_string(), 1)],
+            "Ghazghkull Thraka".to_string(),
+        );
+        list.player_name = Some("Bob".to_string());
+        let mut bob = Placement::new(
+            remove.id.clone(),
+            EpochId::from("previous"),
+            1,
+            "Bob".to_string(),
+            "Orks".to_string(),
+        );
+        bob.list_id = Some(list.id.clone());
+
+        JsonlWriter::<Event>::for_entity(&storage, EntityType::Event, "current")
+            .write_all(std::slice::from_ref(&keep))
+            .unwrap();
+        JsonlWriter::<Event>::for_entity(&storage, EntityType::Event, "previous")
+            .write_all(std::slice::from_ref(&remove))
+            .unwrap();
+        JsonlWriter::<Placement>::for_entity(&storage, EntityType::Placement, "previous")
+            .write_all(&[bob])
+            .unwrap();
+        JsonlWriter::<ArmyList>::for_entity(&storage, EntityType::ArmyList, "previous")
+            .write_all(&[list.clone()])
+            .unwrap();
+
+        let merge = merge_events(&storage, keep.id.as_str(), remove.id.as_str(), false).unwrap();
+        assert_eq!((merge.placements_moved, merge.lists_moved), (1, 1));
+
+        let previous = EpochRecords::load(&storage, "previous").unwrap();
+        assert!(previous.lists.is_empty());
+        let current = EpochRecords::load(&storage, "current").unwrap();
+        assert_eq!(current.lists.len(), 1);
+        assert_eq!(current.lists[0].event_id, Some(keep.id.clone()));
+        assert_eq!(current.placements[0].list_id, Some(list.id));
+    }
+}
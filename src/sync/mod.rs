@@ -3,13 +3,24 @@
 //! Coordinates the ingestion pipeline:
 //! 1. Fetch content from sources
 //! 2. Run AI agents to extract data
-//! 3. Validate with Fact Checker
+//! 3. Validate with Fact Checker (failing records go to the review queue)
 //! 4. Store in JSONL and Parquet
 
 pub mod bcp;
+pub mod brackets;
+pub mod clones;
 pub mod convert;
+pub mod cursor;
 pub mod discovery;
+pub mod fix_event;
+pub mod fixture;
+pub mod history;
+pub mod issues;
+pub mod merge_events;
 pub mod repartition;
+pub mod reprocess;
+pub mod source;
+pub mod t3;
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -24,16 +35,109 @@ use url::Url;
 
 use crate::agents::backend::AiBackend;
 use crate::agents::balance_watcher::{BalanceWatcherAgent, BalanceWatcherInput};
+use crate::agents::estimate::UsageSample;
 use crate::agents::event_scout::{EventScoutAgent, EventScoutInput};
+use crate::agents::execute_with_retry;
+use crate::agents::fact_checker::{self, Discrepancy};
 use crate::agents::list_normalizer::{ListNormalizerAgent, ListNormalizerInput};
 use crate::agents::result_harvester::{ResultHarvesterAgent, ResultHarvesterInput};
-use crate::agents::Agent;
+use crate::api::merge_by_id;
 use crate::fetch::Fetcher;
-use crate::models::{ArmyList, EpochMapper, Placement};
+use crate::models::{
+    ArmyList, EpochMapper, Event, EventStatus, HeldRecord, IngestIssue, IngestStage, Placement,
+    RetryTarget, ReviewQueueItem, ReviewReason, SeriesMatcher, Timestamped,
+};
+use crate::parsing::armylist::{detect_chapter_from_raw_text, parse_units_from_raw_text};
+use crate::storage::compact;
 use crate::storage::jsonl::EntityType;
 use crate::storage::{
-    read_significant_events, write_significant_events, JsonlWriter, StorageConfig,
+    cached_epoch_mapper, read_series, read_significant_events, write_significant_events,
+    JsonlWriter, StorageConfig,
 };
+use cursor::{HighWater, SyncCursor};
+pub use source::{Source, SourceRegistry};
+
+/// Review item holding a record that failed the fact checker's rule checks.
+fn held_for_review(
+    entity_type: crate::models::EntityType,
+    entity_id: crate::models::EntityId,
+    record: &impl Serialize,
+    discrepancies: &[Discrepancy],
+    epoch: &str,
+    source: &str,
+) -> ReviewQueueItem {
+    let details = discrepancies
+        .iter()
+        .filter(|d| d.severity.blocks_storage())
+        .map(|d| d.description.as_str())
+        .collect::<Vec<_>>()
+        .join("; ");
+    ReviewQueueItem::new(
+        entity_type,
+        entity_id,
+        ReviewReason::FactCheckFailed,
+        details,
+    )
+    .with_held(HeldRecord {
+        epoch: epoch.to_string(),
+        source: source.to_string(),
+        record: serde_json::to_value(record).unwrap_or_default(),
+    })
+}
+
+/// Source partition name for data synced from Best Coast Pairings.
+const BCP_SOURCE: &str = "bcp";
+
+/// Source partition name for data synced from Tabletop Tournaments.
+const T3_SOURCE: &str = "t3";
+
+/// Source name of events extracted from Goonhammer articles.
+const GOONHAMMER_SOURCE: &str = "goonhammer";
+
+/// How far ahead `sync --upcoming` looks when no end date is given.
+pub const UPCOMING_DAYS: u64 = 90;
+
+/// Days after its start date that a stored upcoming event is assumed over
+/// and its results fetched, leaving room for multi-day events.
+const UPCOMING_GRACE_DAYS: u64 = 3;
+
+/// WordPress REST API URL of a Goonhammer post.
+fn wp_post_url(post_id: u64) -> String {
+    format!("https://www.goonhammer.com/wp-json/wp/v2/posts/{}", post_id)
+}
+
+/// Rendered HTML and publish date of a WordPress REST API response, either
+/// a single post or a list of posts (the first is used).
+fn wp_rendered_content(json: &str) -> Option<(String, Option<NaiveDate>)> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    let post = match &value {
+        serde_json::Value::Array(posts) => posts.first()?,
+        post => post,
+    };
+    let content = post.get("content")?.get("rendered")?.as_str()?;
+    let date = post
+        .get("date")
+        .and_then(|d| d.as_str())
+        .and_then(|s| s.get(..10))
+        .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
+    Some((content.to_string(), date))
+}
+
+/// Retry target for the standings of a BCP event stored as `event_id`.
+fn bcp_retry_target(
+    bcp_event: &bcp::BcpEvent,
+    event_id: &crate::models::EventId,
+    date: NaiveDate,
+) -> RetryTarget {
+    RetryTarget::BcpEvent {
+        bcp_id: bcp_event.id.clone(),
+        name: bcp_event.name.clone(),
+        date: bcp_event.parsed_start_date().unwrap_or(date),
+        event_id: event_id.clone(),
+        player_count: bcp_event.player_count,
+        round_count: bcp_event.round_count,
+    }
+}
 
 /// Errors that can occur during sync.
 #[derive(Debug, Error)]
@@ -52,6 +156,9 @@ pub enum SyncError {
 
     #[error("Sync cancelled")]
     Cancelled,
+
+    #[error("No cached content for {0}")]
+    NotCached(String),
 }
 
 /// Source to sync from.
@@ -72,6 +179,10 @@ pub enum SyncSource {
         api_base_url: String,
         /// Game type ID (1 = Warhammer 40k)
         game_type: u32,
+        /// Split listings that host several game sizes into one event per
+        /// bracket
+        #[serde(default)]
+        split_brackets: bool,
     },
 
     /// Warhammer Community (for balance dataslates)
@@ -80,6 +191,15 @@ pub enum SyncSource {
         /// URL to monitor
         url: String,
     },
+
+    /// Tabletop Tournaments (tabletopturniere.de), scraped HTML
+    #[serde(rename = "t3")]
+    TabletopTournaments {
+        /// Site root
+        base_url: String,
+        /// Calendar game system filter (case-insensitive substring)
+        game_system: String,
+    },
 }
 
 impl Default for SyncSource {
@@ -87,6 +207,7 @@ impl Default for SyncSource {
         SyncSource::Bcp {
             api_base_url: "https://newprod-api.bestcoastpairings.com/v1".to_string(),
             game_type: 1,
+            split_brackets: false,
         }
     }
 }
@@ -95,7 +216,7 @@ impl Default for SyncSource {
 #[derive(Debug, Clone)]
 pub struct SyncConfig {
     /// Sources to sync from
-    pub sources: Vec<SyncSource>,
+    pub sources: Vec<Arc<dyn Source>>,
 
     /// Sync interval for periodic syncs
     pub interval: Duration,
@@ -107,6 +228,15 @@ pub struct SyncConfig {
     /// Dry run mode (fetch and parse but don't store)
     pub dry_run: bool,
 
+    /// Ignore the incremental cursor and rescan the whole date range
+    pub full: bool,
+
+    /// Store upcoming BCP events instead of syncing results
+    pub upcoming: bool,
+
+    /// Grace period given to discovered balance passes (`[epochs] grace_days`)
+    pub grace_days: u32,
+
     /// Storage configuration
     pub storage: StorageConfig,
 }
@@ -114,11 +244,14 @@ pub struct SyncConfig {
 impl Default for SyncConfig {
     fn default() -> Self {
         Self {
-            sources: vec![SyncSource::default()],
+            sources: vec![SyncSource::default().into_source()],
             interval: Duration::from_secs(6 * 3600), // 6 hours
             date_from: None,
             date_to: None,
             dry_run: false,
+            full: false,
+            upcoming: false,
+            grace_days: 0,
             storage: StorageConfig::default(),
         }
     }
@@ -184,6 +317,19 @@ pub struct SyncResult {
     pub duration: Duration,
 }
 
+/// Work run after each sync run that stored data, such as notifications.
+#[async_trait::async_trait]
+pub trait PostSyncHook: Send + Sync {
+    /// Called once the run started at `started_at` has finished; records it
+    /// stored were created at or after then. Failures are the hook's to log.
+    async fn after_sync(
+        &self,
+        storage: &StorageConfig,
+        started_at: DateTime<Utc>,
+        result: &SyncResult,
+    );
+}
+
 /// Normalize a player name for matching (lowercase, collapse whitespace).
 pub fn normalize_player_name(name: &str) -> String {
     name.split_whitespace()
@@ -230,6 +376,69 @@ pub struct SyncProgress {
     pub discovered_events: Vec<SyncEventProgress>,
 }
 
+/// Syncs running in this process, so a server shutting down can cancel
+/// them rather than abandon them mid-write.
+#[derive(Clone, Default)]
+pub struct ActiveSyncs {
+    running: Arc<RwLock<Vec<Arc<SyncOrchestrator>>>>,
+    shutting_down: Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl ActiveSyncs {
+    /// Run one sync, tracked until it returns. Refused with
+    /// [`SyncError::Cancelled`] once shutdown has begun.
+    pub async fn run(&self, orchestrator: SyncOrchestrator) -> Result<SyncResult, SyncError> {
+        if self.is_shutting_down() {
+            return Err(SyncError::Cancelled);
+        }
+        let orchestrator = Arc::new(orchestrator);
+        self.running.write().await.push(orchestrator.clone());
+        let result = orchestrator.sync_once().await;
+        self.running
+            .write()
+            .await
+            .retain(|o| !Arc::ptr_eq(o, &orchestrator));
+        result
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// Syncs currently running.
+    pub async fn len(&self) -> usize {
+        self.running.read().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+
+    /// Cancel every running sync and refuse new ones, waiting up to `drain`
+    /// for them to stop. Returns whether they all stopped in time.
+    pub async fn shutdown(&self, drain: Duration) -> bool {
+        self.shutting_down
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+        let deadline = tokio::time::Instant::now() + drain;
+        loop {
+            let running = self.running.read().await.clone();
+            if running.is_empty() {
+                return true;
+            }
+            // Repeated because a sync that was just starting resets its
+            // cancel token
+            for orchestrator in &running {
+                orchestrator.cancel().await;
+            }
+            if tokio::time::Instant::now() >= deadline {
+                warn!("{} sync(s) still running after {:?}", running.len(), drain);
+                return false;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+}
+
 /// Sync orchestrator.
 pub struct SyncOrchestrator {
     config: SyncConfig,
@@ -238,7 +447,13 @@ pub struct SyncOrchestrator {
     state: Arc<RwLock<SyncState>>,
     cancel_token: Arc<RwLock<bool>>,
     epoch_mapper: EpochMapper,
+    series_matcher: SeriesMatcher,
     on_progress: Option<Box<dyn Fn(SyncProgress) + Send + Sync>>,
+    /// Failures and successes noted since the ledger was last updated
+    issue_updates: std::sync::Mutex<Vec<issues::IssueUpdate>>,
+    /// Records the fact checker held for review since last taken
+    held_for_review: std::sync::atomic::AtomicU32,
+    post_sync: Vec<Arc<dyn PostSyncHook>>,
 }
 
 impl SyncOrchestrator {
@@ -247,16 +462,15 @@ impl SyncOrchestrator {
     /// Loads the epoch mapper from significant_events on disk (if any).
     pub fn new(config: SyncConfig, fetcher: Fetcher, backend: Arc<dyn AiBackend>) -> Self {
         // Load epoch mapper from stored significant events (empty = backward-compat)
-        let epoch_mapper = match read_significant_events(&config.storage) {
-            Ok(events) if !events.is_empty() => {
-                info!(
-                    "Loaded {} significant events for epoch mapping",
-                    events.len()
-                );
-                EpochMapper::from_significant_events(&events)
-            }
-            _ => EpochMapper::new(),
-        };
+        let epoch_mapper = EpochMapper::clone(&cached_epoch_mapper(&config.storage));
+        if !epoch_mapper.all_epochs().is_empty() {
+            info!(
+                "Loaded {} epochs for epoch mapping",
+                epoch_mapper.all_epochs().len()
+            );
+        }
+
+        let series_matcher = SeriesMatcher::new(read_series(&config.storage).unwrap_or_default());
 
         Self {
             config,
@@ -265,7 +479,63 @@ impl SyncOrchestrator {
             state: Arc::new(RwLock::new(SyncState::default())),
             cancel_token: Arc::new(RwLock::new(false)),
             epoch_mapper,
+            series_matcher,
             on_progress: None,
+            issue_updates: std::sync::Mutex::new(Vec::new()),
+            held_for_review: std::sync::atomic::AtomicU32::new(0),
+            post_sync: Vec::new(),
+        }
+    }
+
+    /// Attach the matching series (if any) to an event before it is stored.
+    fn link_series(&self, event: crate::models::Event) -> crate::models::Event {
+        let series = self
+            .series_matcher
+            .match_event(&event.name)
+            .map(|s| s.name.clone());
+        if let Some(ref name) = series {
+            info!("  Linked {} to series {}", event.name, name);
+        }
+        event.with_series(series)
+    }
+
+    /// The configured source of a built-in type, if any.
+    fn configured_source<T: Source + 'static>(&self) -> Option<&T> {
+        self.config
+            .sources
+            .iter()
+            .find_map(|s| s.as_any().downcast_ref::<T>())
+    }
+
+    /// Incremental lower bound for a source: its cursor, unless a full
+    /// rescan or an explicit start date was requested.
+    fn cursor_start(&self, source: &str) -> Option<NaiveDate> {
+        if self.config.full || self.config.date_from.is_some() {
+            return None;
+        }
+        match SyncCursor::load(&self.config.storage) {
+            Ok(cursor) => cursor.get(source),
+            Err(e) => {
+                warn!("Could not read sync cursor, rescanning: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Persist a source's new high-water mark after a completed run.
+    fn advance_cursor(&self, source: &str, high_water: &HighWater) {
+        if self.config.dry_run {
+            return;
+        }
+        let Some(mark) = high_water.mark() else {
+            return;
+        };
+        let mut cursor = SyncCursor::load(&self.config.storage).unwrap_or_default();
+        if cursor.advance(source, mark) {
+            match cursor.save(&self.config.storage) {
+                Ok(()) => info!("Sync cursor for {} advanced to {}", source, mark),
+                Err(e) => warn!("Failed to save sync cursor: {}", e),
+            }
         }
     }
 
@@ -278,6 +548,12 @@ impl SyncOrchestrator {
         self
     }
 
+    /// Run `hook` after each completed sync run. Dry runs skip hooks.
+    pub fn with_post_sync_hook(mut self, hook: Arc<dyn PostSyncHook>) -> Self {
+        self.post_sync.push(hook);
+        self
+    }
+
     /// Send a progress update to the callback if one is set.
     #[allow(clippy::too_many_arguments)]
     fn emit_progress(
@@ -377,6 +653,260 @@ impl SyncOrchestrator {
             .await
     }
 
+    /// Re-run extraction over an article's cached content, without network.
+    ///
+    /// Content is read from `raw_path` if given (an ingest issue's payload),
+    /// else from the cached WordPress API response for the article's slug,
+    /// else from the cached page itself, whatever their age. Stored events
+    /// are reused rather than duplicated, so this is safe to run after a
+    /// prompt or backend change. The outcome resolves or re-counts any
+    /// ingest issue for the article.
+    ///
+    /// With `replace`, the article's stored events, placements and lists
+    /// are removed once its content is found, so everything is extracted
+    /// afresh (see [`reprocess::clear_article`]). Returns (events,
+    /// placements, lists) added.
+    pub async fn reprocess_article(
+        &self,
+        article_url: &Url,
+        article_date: NaiveDate,
+        raw_path: Option<&std::path::Path>,
+        replace: bool,
+    ) -> Result<(u32, u32, u32), SyncError> {
+        let result = match self.cached_article_content(article_url, raw_path).await {
+            Some((html, date)) => {
+                if replace && !self.config.dry_run {
+                    let removed =
+                        reprocess::clear_article(&self.config.storage, article_url.as_str())?;
+                    info!("Cleared {} stored records from {}", removed, article_url);
+                }
+                self.extract_article(article_url, date.unwrap_or(article_date), &html, true)
+                    .await
+            }
+            None => Err(SyncError::NotCached(article_url.to_string())),
+        };
+        match &result {
+            Ok(_) => self.note_success(GOONHAMMER_SOURCE, article_url.as_str()),
+            Err(e) => self.note_failure(
+                GOONHAMMER_SOURCE,
+                article_url.as_str(),
+                IngestStage::Extract,
+                RetryTarget::Article {
+                    url: article_url.to_string(),
+                    date: article_date,
+                },
+                None,
+                e,
+            ),
+        }
+        self.record_issues(None);
+        result
+    }
+
+    /// Estimated AI calls for reprocessing a cached article that covers
+    /// `events` events and `lists` army lists: one Event Scout call, then a
+    /// Result Harvester call per event and a List Normalizer call per list.
+    /// `None` when the article isn't cached.
+    pub async fn estimate_reprocess(
+        &self,
+        article_url: &Url,
+        article_date: NaiveDate,
+        raw_path: Option<&std::path::Path>,
+        events: u32,
+        lists: u32,
+    ) -> Option<Vec<UsageSample>> {
+        let (html, date) = self.cached_article_content(article_url, raw_path).await?;
+        let text = discovery::extract_text_from_html(&html);
+        let mut samples = vec![EventScoutAgent::estimate_usage(
+            &text,
+            date.unwrap_or(article_date),
+            events,
+        )];
+        samples.extend((0..events).map(|_| ResultHarvesterAgent::estimate_usage(&text, events)));
+        if lists > 0 {
+            // A list is at most its share of the article
+            let share: String = text
+                .chars()
+                .take(text.chars().count() / lists as usize)
+                .collect();
+            samples
+                .extend((0..lists).map(|_| ListNormalizerAgent::estimate_usage(&share, None, &[])));
+        }
+        Some(samples)
+    }
+
+    /// Cached HTML of an article, with its publish date when the cached
+    /// payload is a WordPress API response.
+    async fn cached_article_content(
+        &self,
+        article_url: &Url,
+        raw_path: Option<&std::path::Path>,
+    ) -> Option<(String, Option<NaiveDate>)> {
+        let as_article = |body: String| match wp_rendered_content(&body) {
+            Some(content) => content,
+            None => (body, None),
+        };
+
+        if let Some(path) = raw_path {
+            let path = if path.is_absolute() {
+                path.to_path_buf()
+            } else {
+                self.config.storage.data_dir.join(path)
+            };
+            if let Ok(body) = tokio::fs::read_to_string(&path).await {
+                return Some(as_article(body));
+            }
+        }
+
+        let slug = article_url
+            .path()
+            .trim_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or("");
+        if !slug.is_empty() {
+            let api_url = Url::parse(&format!(
+                "https://www.goonhammer.com/wp-json/wp/v2/posts?slug={}",
+                slug
+            ))
+            .ok()?;
+            if let Some(body) = self.fetcher.read_cached_any_age(&api_url).await {
+                if let Some(content) = wp_rendered_content(&body) {
+                    return Some(content);
+                }
+            }
+        }
+
+        self.fetcher
+            .read_cached_any_age(article_url)
+            .await
+            .map(as_article)
+    }
+
+    /// BCP client with an authenticated fetcher, for standings and army
+    /// lists.
+    async fn bcp_standings_client(
+        &self,
+        api_base_url: &str,
+        game_type: u32,
+    ) -> Result<bcp::BcpClient, SyncError> {
+        let bcp_fetcher = Fetcher::new(crate::fetch::FetcherConfig {
+            cache_dir: self.config.storage.raw_dir(),
+            extra_headers: bcp::bcp_headers_authenticated().await,
+            request_delay: self.fetcher.config().request_delay,
+            ..Default::default()
+        })
+        .map_err(SyncError::Fetch)?;
+        Ok(bcp::BcpClient::new(
+            bcp_fetcher,
+            api_base_url.to_string(),
+            game_type,
+        ))
+    }
+
+    /// Re-run the article or event behind an ingest issue.
+    ///
+    /// The outcome goes into the issue ledger: success resolves the issue,
+    /// failure counts another attempt. BCP and T3 retries use the source as
+    /// configured for this orchestrator, so it must be among its sources.
+    /// Returns (events, placements, lists) imported.
+    pub async fn retry_issue(&self, issue: &IngestIssue) -> Result<(u32, u32, u32), SyncError> {
+        let result = self.retry_target(&issue.target).await;
+        match &result {
+            Ok(_) => self.note_success(&issue.source, &issue.source_url),
+            Err(e) => self.note_failure(
+                &issue.source,
+                &issue.source_url,
+                issue.stage,
+                issue.target.clone(),
+                None,
+                e,
+            ),
+        }
+        self.record_issues(None);
+        result
+    }
+
+    async fn retry_target(&self, target: &RetryTarget) -> Result<(u32, u32, u32), SyncError> {
+        let epoch_for = |date: NaiveDate| {
+            let epoch_id = if self.epoch_mapper.all_epochs().is_empty() {
+                None
+            } else {
+                Some(self.epoch_mapper.get_epoch_id_for_date(date))
+            };
+            let epoch_str = epoch_id
+                .as_ref()
+                .map(|e| e.as_str().to_string())
+                .unwrap_or_else(|| "current".to_string());
+            (epoch_id, epoch_str)
+        };
+
+        match target {
+            RetryTarget::Article { url, date } => {
+                let url = Url::parse(url).map_err(|e| {
+                    SyncError::Fetch(crate::fetch::FetchError::InvalidUrl(e.to_string()))
+                })?;
+                self.process_single_article(&url, *date, &self.config).await
+            }
+            RetryTarget::BcpEvent {
+                bcp_id,
+                name,
+                date,
+                event_id,
+                player_count,
+                round_count,
+            } => {
+                let Some(source) = self.configured_source::<source::BcpSource>() else {
+                    return Err(SyncError::NoSources);
+                };
+                let client = self
+                    .bcp_standings_client(&source.api_base_url, source.game_type)
+                    .await?;
+                let bcp_event =
+                    bcp::BcpEvent::finished(bcp_id, name, *date, *player_count, *round_count);
+                let (epoch_id, epoch_str) = epoch_for(*date);
+                let (p, l) = self
+                    .sync_bcp_standings(
+                        &client,
+                        &bcp_event,
+                        event_id,
+                        epoch_id,
+                        &epoch_str,
+                        source.split_brackets,
+                    )
+                    .await?;
+                Ok((0, p, l))
+            }
+            RetryTarget::T3Event {
+                t3_id,
+                name,
+                date,
+                event_id,
+            } => {
+                let Some(source) = self.configured_source::<source::T3Source>() else {
+                    return Err(SyncError::NoSources);
+                };
+                let fetcher =
+                    Fetcher::new(self.fetcher.config().clone()).map_err(SyncError::Fetch)?;
+                let client =
+                    t3::T3Client::new(fetcher, source.base_url.clone(), source.game_system.clone());
+                let t3_event = t3::T3Event {
+                    id: t3_id.clone(),
+                    name: name.clone(),
+                    date: Some(*date),
+                    game_system: None,
+                    location: None,
+                    player_count: None,
+                };
+                let (epoch_id, epoch_str) = epoch_for(*date);
+                let (p, l) = self
+                    .sync_t3_standings(&client, &t3_event, event_id, epoch_id, &epoch_str)
+                    .await?;
+                Ok((0, p, l))
+            }
+        }
+    }
+
     /// Run a single sync operation.
     pub async fn sync_once(&self) -> Result<SyncResult, SyncError> {
         if self.config.sources.is_empty() {
@@ -395,6 +925,7 @@ impl SyncOrchestrator {
         }
 
         let start = std::time::Instant::now();
+        let started_at = Utc::now();
         info!("Starting sync operation");
 
         let mut total_events = 0u32;
@@ -402,16 +933,31 @@ impl SyncOrchestrator {
         let mut total_lists = 0u32;
         let mut total_review = 0u32;
         let mut errors = Vec::new();
+        let mut source_runs = Vec::new();
 
         for source in &self.config.sources {
             // Check for cancellation
             if *self.cancel_token.read().await {
                 warn!("Sync cancelled");
+                crate::metrics::Metrics::global().record_sync_run("cancelled", start.elapsed());
+                errors.push(SyncError::Cancelled.to_string());
+                self.record_history(started_at, SyncStatus::Failed, true, source_runs, errors);
                 return Err(SyncError::Cancelled);
             }
 
-            match self.sync_source(source).await {
+            let source_start = std::time::Instant::now();
+            let result = self.sync_source(source).await;
+            let mut source_run = history::SourceRun {
+                source: source.name().to_string(),
+                duration_ms: source_start.elapsed().as_millis() as u64,
+                ..Default::default()
+            };
+            match result {
                 Ok(result) => {
+                    source_run.events_synced = result.events_synced;
+                    source_run.placements_synced = result.placements_synced;
+                    source_run.lists_normalized = result.lists_normalized;
+                    source_run.items_for_review = result.items_for_review;
                     total_events += result.events_synced;
                     total_placements += result.placements_synced;
                     total_lists += result.lists_normalized;
@@ -431,12 +977,16 @@ impl SyncOrchestrator {
                 }
                 Err(e) => {
                     error!("Error syncing source: {}", e);
+                    source_run.error = Some(e.to_string());
                     errors.push(e.to_string());
                 }
             }
+            source_runs.push(source_run);
         }
 
         let duration = start.elapsed();
+        crate::metrics::Metrics::global()
+            .record_sync_run(if errors.is_empty() { "ok" } else { "partial" }, duration);
 
         // Update final state
         {
@@ -457,137 +1007,467 @@ impl SyncOrchestrator {
             "Sync completed: {} events, {} placements, {} lists in {:?}",
             total_events, total_placements, total_lists, duration
         );
+        let status = if errors.is_empty() {
+            SyncStatus::Completed
+        } else {
+            SyncStatus::Failed
+        };
+        self.record_history(started_at, status, false, source_runs, errors.clone());
+        self.compact_after_large_sync(total_events + total_placements + total_lists);
 
-        Ok(SyncResult {
+        let result = SyncResult {
             events_synced: total_events,
             placements_synced: total_placements,
             lists_normalized: total_lists,
             items_for_review: total_review,
             errors,
             duration,
-        })
+        };
+        if !self.config.dry_run {
+            for hook in &self.post_sync {
+                hook.after_sync(&self.config.storage, started_at, &result)
+                    .await;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Compact the entity files once a run has stored enough records to
+    /// leave many superseded copies behind.
+    fn compact_after_large_sync(&self, records: u32) {
+        if self.config.dry_run || records < compact::AUTO_COMPACT_MIN_RECORDS {
+            return;
+        }
+        match compact::compact(&self.config.storage, None, false) {
+            Ok(report) => info!(
+                "Compacted {} files after sync, {} duplicate lines reclaimed",
+                report.files_rewritten(),
+                report.reclaimed()
+            ),
+            Err(e) => warn!("Compaction after sync failed: {}", e),
+        }
+    }
+
+    /// Append a finished run to the sync history. Dry runs store nothing.
+    fn record_history(
+        &self,
+        started_at: DateTime<Utc>,
+        status: SyncStatus,
+        cancelled: bool,
+        sources: Vec<history::SourceRun>,
+        errors: Vec<String>,
+    ) {
+        if self.config.dry_run {
+            return;
+        }
+        let run = history::SyncRun {
+            started_at,
+            finished_at: Utc::now(),
+            status,
+            cancelled,
+            sources,
+            errors,
+        };
+        if let Err(e) = history::record_run(&self.config.storage, &run) {
+            warn!("Failed to record sync history: {}", e);
+        }
+        self.record_issues(Some(started_at));
+    }
+
+    /// Note an item that failed to import for the issue ledger.
+    fn note_failure(
+        &self,
+        source: &str,
+        source_url: &str,
+        stage: IngestStage,
+        target: RetryTarget,
+        raw_url: Option<&str>,
+        error: &SyncError,
+    ) {
+        let (kind, retryable) = issues::classify(error);
+        let now = Utc::now();
+        let issue = IngestIssue {
+            id: IngestIssue::issue_id(source, source_url, stage),
+            source: source.to_string(),
+            source_url: source_url.to_string(),
+            stage,
+            kind,
+            error: error.to_string(),
+            retryable,
+            raw_path: raw_url.and_then(|url| self.raw_pointer(url)),
+            target,
+            attempts: 1,
+            first_seen: now,
+            last_seen: now,
+            run_started_at: None,
+            resolved_at: None,
+        };
+        self.issue_updates
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(issues::IssueUpdate::Failed(Box::new(issue)));
+    }
+
+    /// Note an item that imported, resolving any open issues for it.
+    fn note_success(&self, source: &str, source_url: &str) {
+        self.issue_updates
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(issues::IssueUpdate::Succeeded {
+                source: source.to_string(),
+                source_url: source_url.to_string(),
+            });
+    }
+
+    /// Cached raw payload of a URL, relative to the data directory, if it
+    /// was fetched.
+    fn raw_pointer(&self, url: &str) -> Option<String> {
+        let url = Url::parse(url).ok()?;
+        let path = self.fetcher.cache_path_for_url(&url);
+        if !path.exists() {
+            return None;
+        }
+        let relative = path
+            .strip_prefix(&self.config.storage.data_dir)
+            .unwrap_or(&path);
+        Some(relative.to_string_lossy().into_owned())
+    }
+
+    /// Fold the noted outcomes into the issue ledger. Dry runs store nothing.
+    fn record_issues(&self, run_started_at: Option<DateTime<Utc>>) {
+        let mut updates =
+            std::mem::take(&mut *self.issue_updates.lock().unwrap_or_else(|e| e.into_inner()));
+        if self.config.dry_run {
+            return;
+        }
+        for update in &mut updates {
+            if let issues::IssueUpdate::Failed(issue) = update {
+                issue.run_started_at = run_started_at;
+            }
+        }
+        if let Err(e) = issues::record_updates(&self.config.storage, updates, Utc::now()) {
+            warn!("Failed to record ingest issues: {}", e);
+        }
     }
 
     /// Sync from a specific source.
-    async fn sync_source(&self, source: &SyncSource) -> Result<SyncResult, SyncError> {
+    async fn sync_source(&self, source: &Arc<dyn Source>) -> Result<SyncResult, SyncError> {
+        source.sync(self).await
+    }
+
+    /// Run a source's stages over the sync window and store new events with
+    /// their placements and lists.
+    pub async fn sync_stages<S: Source + ?Sized>(
+        &self,
+        source: &S,
+    ) -> Result<SyncResult, SyncError> {
         let start = std::time::Instant::now();
+        let name = source.name();
+        let today = Utc::now().date_naive();
+        let ctx = source::SourceContext {
+            fetcher: &self.fetcher,
+            backend: self.backend.clone(),
+            date_from: self
+                .config
+                .date_from
+                .or_else(|| self.cursor_start(name))
+                .unwrap_or(today - chrono::Duration::days(30)),
+            date_to: self.config.date_to.unwrap_or(today),
+        };
+        info!(
+            "Syncing from {} ({} to {})",
+            name, ctx.date_from, ctx.date_to
+        );
 
-        match source {
-            SyncSource::Goonhammer { base_url } => {
-                info!("Syncing from Goonhammer: {}", base_url);
+        let items = source.discover(&ctx).await?;
+        info!("Discovered {} items from {}", items.len(), name);
+
+        let mut total_events = 0u32;
+        let mut total_placements = 0u32;
+        let mut total_lists = 0u32;
+        let mut errors = Vec::new();
+        let mut high_water = HighWater::default();
+        let mut cancelled = false;
+
+        for item in &items {
+            if *self.cancel_token.read().await {
+                cancelled = true;
+                break;
+            }
+
+            let extracted = match source.fetch(&ctx, item).await {
+                Ok(raw) => source.extract(&ctx, item, &raw).await,
+                Err(e) => Err(e),
+            };
+            let extracted = match extracted {
+                Ok(extracted) => extracted,
+                Err(e) => {
+                    let err = format!("Error processing {}: {}", item.url, e);
+                    warn!("{}", err);
+                    errors.push(err);
+                    if let Some(date) = item.date {
+                        high_water.failed(date);
+                    }
+                    continue;
+                }
+            };
 
-                // 1. Fetch RSS feed for article discovery (HTML pages are JS-rendered)
-                let base_with_slash = if base_url.ends_with('/') {
-                    base_url.clone()
+            for event in &extracted {
+                let item_date = event.event.data.date.or(item.date).unwrap_or(today);
+                let epoch_id = if self.epoch_mapper.all_epochs().is_empty() {
+                    None
                 } else {
-                    format!("{}/", base_url)
+                    Some(self.epoch_mapper.get_epoch_id_for_date(item_date))
                 };
-                let rss_url = format!("{}feed/", base_with_slash);
-                let rss_url = Url::parse(&rss_url).map_err(|e| {
-                    SyncError::Fetch(crate::fetch::FetchError::InvalidUrl(e.to_string()))
-                })?;
-
-                let fetch_result = self.fetcher.fetch(&rss_url).await?;
-                let rss_xml = self.fetcher.read_cached_text(&fetch_result).await?;
+                let epoch_str = epoch_id
+                    .as_ref()
+                    .map(|e| e.as_str().to_string())
+                    .unwrap_or_else(|| "current".to_string());
+                let converted = source.convert(item, event, epoch_id);
+                let event = self.link_series(converted.event);
 
-                // 2. Discover articles from RSS
-                let articles = discovery::discover_from_rss(&rss_xml);
-                info!("Discovered {} articles from RSS", articles.len());
+                if self.config.dry_run {
+                    total_events += 1;
+                    total_placements += converted.placements.len() as u32;
+                    total_lists += converted.lists.len() as u32;
+                    continue;
+                }
 
-                // 3. Filter by date range
-                let articles = discovery::filter_by_date_range(
-                    articles,
-                    self.config.date_from,
-                    self.config.date_to,
-                );
-                info!("{} articles after date filtering", articles.len());
-
-                // 4. Process each article
-                let mut total_events = 0u32;
-                let mut total_placements = 0u32;
-                let mut total_lists = 0u32;
-                let mut errors = Vec::new();
-
-                // Load all existing events across epochs to check which articles are already imported
-                let mut all_existing_source_urls: std::collections::HashSet<String> =
-                    std::collections::HashSet::new();
-                for epoch in self.epoch_mapper.all_epochs() {
-                    let reader = crate::storage::JsonlReader::<crate::models::Event>::for_entity(
+                let existing_events: Vec<crate::models::Event> =
+                    crate::storage::JsonlReader::for_entity(
                         &self.config.storage,
                         EntityType::Event,
-                        epoch.id.as_str(),
+                        &epoch_str,
+                    )
+                    .read_all()
+                    .unwrap_or_default();
+                if let Some(existing_id) = convert::find_duplicate_event(&event, &existing_events) {
+                    info!(
+                        "  {}: event {} already stored (matches {})",
+                        name, event.name, existing_id
                     );
-                    if let Ok(events) = reader.read_all() {
-                        for e in events {
-                            all_existing_source_urls.insert(e.source_url.clone());
-                        }
-                    }
-                }
-                // Also check "current" directory
-                if let Ok(events) = crate::storage::JsonlReader::<crate::models::Event>::for_entity(
-                    &self.config.storage,
-                    EntityType::Event,
-                    "current",
-                )
-                .read_all()
-                {
-                    for e in events {
-                        all_existing_source_urls.insert(e.source_url.clone());
-                    }
+                    continue;
                 }
 
-                for (article_idx, article) in articles.iter().enumerate() {
-                    if *self.cancel_token.read().await {
-                        break;
-                    }
+                let storage = &self.config.storage;
+                JsonlWriter::for_source(storage, EntityType::Event, &epoch_str, name)
+                    .append(&event)?;
+                JsonlWriter::for_source(storage, EntityType::Placement, &epoch_str, name)
+                    .append_batch(&converted.placements)?;
+                JsonlWriter::for_source(storage, EntityType::ArmyList, &epoch_str, name)
+                    .append_batch(&converted.lists)?;
+                total_events += 1;
+                total_placements += converted.placements.len() as u32;
+                total_lists += converted.lists.len() as u32;
+            }
+            if let Some(date) = item.date {
+                high_water.ok(date);
+            }
+        }
 
-                    // Skip articles that have already been imported (events exist with this source URL)
-                    let article_url_str = article.url.to_string();
-                    if all_existing_source_urls.contains(&article_url_str) {
-                        info!(
-                            "Skipping already-imported article: {} ({})",
-                            article.title, article_url_str
-                        );
-                        self.emit_progress(
-                            total_events,
-                            total_placements,
-                            total_lists,
-                            0,
-                            0,
-                            format!(
-                                "Skipping already-imported article {}/{}...",
-                                article_idx + 1,
-                                articles.len()
-                            ),
-                            Vec::new(),
-                        );
-                        continue;
-                    }
+        if !cancelled {
+            self.advance_cursor(name, &high_water);
+        }
 
-                    info!("Processing article: {}", article.title);
+        Ok(SyncResult {
+            events_synced: total_events,
+            placements_synced: total_placements,
+            lists_normalized: total_lists,
+            items_for_review: 0,
+            errors,
+            duration: start.elapsed(),
+        })
+    }
 
-                    // Fetch content via WP REST API if we have a post ID
-                    let content_result = if let Some(post_id) = article.wp_post_id {
-                        self.fetch_wp_article_content(post_id).await
-                    } else {
-                        // Fallback: fetch the page directly
-                        let fetch_result = self.fetcher.fetch(&article.url).await?;
-                        let html = self.fetcher.read_cached_text(&fetch_result).await?;
-                        Ok(html)
-                    };
+    /// Sync Goonhammer Competitive Innovations articles.
+    async fn sync_goonhammer(&self, name: &str, base_url: &str) -> Result<SyncResult, SyncError> {
+        let start = std::time::Instant::now();
+        info!("Syncing from Goonhammer: {}", base_url);
 
-                    let article_content = match content_result {
-                        Ok(content) => content,
-                        Err(e) => {
-                            let err = format!("Error fetching {}: {}", article.url, e);
-                            warn!("{}", err);
-                            errors.push(err);
-                            continue;
-                        }
-                    };
+        // 1. Fetch RSS feed for article discovery (HTML pages are JS-rendered)
+        let base_with_slash = if base_url.ends_with('/') {
+            base_url.to_string()
+        } else {
+            format!("{}/", base_url)
+        };
+        let rss_url = format!("{}feed/", base_with_slash);
+        let rss_url = Url::parse(&rss_url)
+            .map_err(|e| SyncError::Fetch(crate::fetch::FetchError::InvalidUrl(e.to_string())))?;
+
+        let fetch_result = self.fetcher.fetch(&rss_url).await?;
+        let rss_xml = self.fetcher.read_cached_text(&fetch_result).await?;
+
+        // 2. Discover articles from RSS
+        let articles = discovery::discover_from_rss(&rss_xml);
+        info!("Discovered {} articles from RSS", articles.len());
+
+        // 3. Filter by date range
+        let articles =
+            discovery::filter_by_date_range(articles, self.config.date_from, self.config.date_to);
+        info!("{} articles after date filtering", articles.len());
+
+        // Only articles at or after the cursor; same-day articles are
+        // re-checked and skipped by source URL below
+        let articles = match self.cursor_start(name) {
+            Some(since) => {
+                let articles = discovery::filter_by_date_range(articles, Some(since), None);
+                info!("{} articles at or after cursor {}", articles.len(), since);
+                articles
+            }
+            None => articles,
+        };
+
+        // 4. Process each article
+        let mut total_events = 0u32;
+        let mut total_placements = 0u32;
+        let mut total_lists = 0u32;
+        let mut errors = Vec::new();
+
+        if articles.is_empty() {
+            info!("No new Goonhammer articles");
+            return Ok(SyncResult {
+                events_synced: 0,
+                placements_synced: 0,
+                lists_normalized: 0,
+                items_for_review: 0,
+                errors,
+                duration: start.elapsed(),
+            });
+        }
+
+        let mut high_water = HighWater::default();
+        let mut cancelled = false;
+
+        // Load all existing events across epochs to check which articles are already imported
+        let mut all_existing_source_urls: std::collections::HashSet<String> =
+            std::collections::HashSet::new();
+        for epoch in self.epoch_mapper.all_epochs() {
+            let reader = crate::storage::JsonlReader::<crate::models::Event>::for_entity(
+                &self.config.storage,
+                EntityType::Event,
+                epoch.id.as_str(),
+            );
+            if let Ok(events) = reader.read_all() {
+                for e in events {
+                    all_existing_source_urls.insert(e.source_url.clone());
+                }
+            }
+        }
+        // Also check "current" directory
+        if let Ok(events) = crate::storage::JsonlReader::<crate::models::Event>::for_entity(
+            &self.config.storage,
+            EntityType::Event,
+            "current",
+        )
+        .read_all()
+        {
+            for e in events {
+                all_existing_source_urls.insert(e.source_url.clone());
+            }
+        }
+
+        for (article_idx, article) in articles.iter().enumerate() {
+            if *self.cancel_token.read().await {
+                cancelled = true;
+                break;
+            }
+
+            // Skip articles that have already been imported (events exist with this source URL)
+            let article_url_str = article.url.to_string();
+            if all_existing_source_urls.contains(&article_url_str) {
+                if let Some(date) = article.date {
+                    high_water.ok(date);
+                }
+                info!(
+                    "Skipping already-imported article: {} ({})",
+                    article.title, article_url_str
+                );
+                self.emit_progress(
+                    total_events,
+                    total_placements,
+                    total_lists,
+                    0,
+                    0,
+                    format!(
+                        "Skipping already-imported article {}/{}...",
+                        article_idx + 1,
+                        articles.len()
+                    ),
+                    Vec::new(),
+                );
+                continue;
+            }
+
+            info!("Processing article: {}", article.title);
+
+            // Fetch content via WP REST API if we have a post ID
+            let content_result = if let Some(post_id) = article.wp_post_id {
+                self.fetch_wp_article_content(post_id).await
+            } else {
+                // Fallback: fetch the page directly
+                let fetch_result = self.fetcher.fetch(&article.url).await?;
+                let html = self.fetcher.read_cached_text(&fetch_result).await?;
+                Ok(html)
+            };
+
+            let article_date = article.date.unwrap_or_else(|| Utc::now().date_naive());
+            let article_target = RetryTarget::Article {
+                url: article.url.to_string(),
+                date: article_date,
+            };
+            // Payload the article was extracted from
+            let raw_url = match article.wp_post_id {
+                Some(post_id) => wp_post_url(post_id),
+                None => article.url.to_string(),
+            };
 
-                    let article_date = article.date.unwrap_or_else(|| Utc::now().date_naive());
+            let article_content = match content_result {
+                Ok(content) => content,
+                Err(e) => {
+                    let err = format!("Error fetching {}: {}", article.url, e);
+                    warn!("{}", err);
+                    errors.push(err);
+                    self.note_failure(
+                        GOONHAMMER_SOURCE,
+                        article.url.as_str(),
+                        IngestStage::Fetch,
+                        article_target,
+                        Some(&raw_url),
+                        &e,
+                    );
+                    if let Some(date) = article.date {
+                        high_water.failed(date);
+                    }
+                    continue;
+                }
+            };
 
+            self.emit_progress(
+                total_events,
+                total_placements,
+                total_lists,
+                0,
+                0,
+                format!(
+                    "Processing Goonhammer article {}/{}...",
+                    article_idx + 1,
+                    articles.len()
+                ),
+                Vec::new(),
+            );
+
+            match self
+                .process_goonhammer_article_content(&article.url, article_date, &article_content)
+                .await
+            {
+                Ok((events, placements, lists)) => {
+                    if let Some(date) = article.date {
+                        high_water.ok(date);
+                    }
+                    self.note_success(GOONHAMMER_SOURCE, article.url.as_str());
+                    total_events += events;
+                    total_placements += placements;
+                    total_lists += lists;
                     self.emit_progress(
                         total_events,
                         total_placements,
@@ -595,291 +1475,276 @@ impl SyncOrchestrator {
                         0,
                         0,
                         format!(
-                            "Processing Goonhammer article {}/{}...",
+                            "Article {}/{}: {} events, {} placements, {} lists",
                             article_idx + 1,
-                            articles.len()
+                            articles.len(),
+                            events,
+                            placements,
+                            lists
                         ),
                         Vec::new(),
                     );
-
-                    match self
-                        .process_goonhammer_article_content(
-                            &article.url,
-                            article_date,
-                            &article_content,
-                        )
-                        .await
-                    {
-                        Ok((events, placements, lists)) => {
-                            total_events += events;
-                            total_placements += placements;
-                            total_lists += lists;
-                            self.emit_progress(
-                                total_events,
-                                total_placements,
-                                total_lists,
-                                0,
-                                0,
-                                format!(
-                                    "Article {}/{}: {} events, {} placements, {} lists",
-                                    article_idx + 1,
-                                    articles.len(),
-                                    events,
-                                    placements,
-                                    lists
-                                ),
-                                Vec::new(),
-                            );
-                        }
-                        Err(e) => {
-                            let err = format!("Error processing {}: {}", article.url, e);
-                            warn!("{}", err);
-                            errors.push(err);
-                        }
+                }
+                Err(e) => {
+                    let err = format!("Error processing {}: {}", article.url, e);
+                    warn!("{}", err);
+                    errors.push(err);
+                    self.note_failure(
+                        GOONHAMMER_SOURCE,
+                        article.url.as_str(),
+                        IngestStage::Extract,
+                        article_target,
+                        Some(&raw_url),
+                        &e,
+                    );
+                    if let Some(date) = article.date {
+                        high_water.failed(date);
                     }
                 }
-
-                Ok(SyncResult {
-                    events_synced: total_events,
-                    placements_synced: total_placements,
-                    lists_normalized: total_lists,
-                    items_for_review: 0,
-                    errors,
-                    duration: start.elapsed(),
-                })
             }
-            SyncSource::Bcp {
-                api_base_url,
-                game_type,
-            } => {
-                info!(
-                    "Syncing from BCP: {} (game_type={})",
-                    api_base_url, game_type
-                );
+        }
 
-                // Unauthenticated fetcher for event discovery (BCP rejects authed /events requests with 409)
-                let discovery_fetcher = Fetcher::new(crate::fetch::FetcherConfig {
-                    cache_dir: self.config.storage.raw_dir(),
-                    extra_headers: bcp::bcp_headers(),
-                    ..Default::default()
-                })
-                .map_err(SyncError::Fetch)?;
-                let discovery_client =
-                    bcp::BcpClient::new(discovery_fetcher, api_base_url.clone(), *game_type);
-
-                // Authenticated fetcher for standings and army list fetching
-                let bcp_fetcher = Fetcher::new(crate::fetch::FetcherConfig {
-                    cache_dir: self.config.storage.raw_dir(),
-                    extra_headers: bcp::bcp_headers_authenticated().await,
-                    ..Default::default()
-                })
-                .map_err(SyncError::Fetch)?;
-                let bcp_client = bcp::BcpClient::new(bcp_fetcher, api_base_url.clone(), *game_type);
+        // RSS lists newest first, so a cancelled run may have skipped
+        // older articles; only a complete pass moves the cursor
+        if !cancelled {
+            self.advance_cursor(name, &high_water);
+        }
 
-                let date_from = self.config.date_from.unwrap_or_else(|| {
-                    (chrono::Utc::now() - chrono::Duration::days(30)).date_naive()
-                });
-                let date_to = self
-                    .config
-                    .date_to
-                    .unwrap_or_else(|| chrono::Utc::now().date_naive());
+        Ok(SyncResult {
+            events_synced: total_events,
+            placements_synced: total_placements,
+            lists_normalized: total_lists,
+            items_for_review: self
+                .held_for_review
+                .swap(0, std::sync::atomic::Ordering::Relaxed),
+            errors,
+            duration: start.elapsed(),
+        })
+    }
 
-                let bcp_events = match discovery_client.discover_events(date_from, date_to).await {
-                    Ok(events) => events,
-                    Err(e) => {
-                        warn!("BCP event discovery failed: {}", e);
-                        return Ok(SyncResult {
-                            events_synced: 0,
-                            placements_synced: 0,
-                            lists_normalized: 0,
-                            items_for_review: 0,
-                            errors: vec![e.to_string()],
-                            duration: start.elapsed(),
-                        });
-                    }
-                };
+    /// Sync finished events and their standings from Best Coast Pairings.
+    async fn sync_bcp(
+        &self,
+        name: &str,
+        api_base_url: &str,
+        game_type: u32,
+        split_brackets: bool,
+    ) -> Result<SyncResult, SyncError> {
+        if self.config.upcoming {
+            return self.sync_bcp_upcoming(api_base_url, game_type).await;
+        }
+        let start = std::time::Instant::now();
+        info!(
+            "Syncing from BCP: {} (game_type={})",
+            api_base_url, game_type
+        );
 
-                let discovered_count = bcp_events.len() as u32;
-                info!("BCP: discovered {} events", discovered_count);
+        let discovery_client = self.bcp_discovery_client(api_base_url, game_type)?;
 
-                // Build per-event progress list
-                let mut event_progress: Vec<SyncEventProgress> = bcp_events
-                    .iter()
-                    .map(|e| {
-                        let date_str = e
-                            .parsed_start_date()
-                            .map(|d| d.to_string())
-                            .unwrap_or_default();
-                        SyncEventProgress {
-                            name: e.name.clone(),
-                            date: date_str,
-                            player_count: e.player_count.unwrap_or(0),
-                            status: if e.should_skip() {
-                                SyncEventStatus::Skipped
-                            } else {
-                                SyncEventStatus::Pending
-                            },
-                            placements_found: 0,
-                            lists_found: 0,
-                            detail: String::new(),
-                        }
-                    })
-                    .collect();
+        let bcp_client = self.bcp_standings_client(api_base_url, game_type).await?;
 
-                self.emit_progress(
-                    0,
-                    0,
-                    0,
-                    discovered_count,
-                    0,
-                    format!("BCP: found {} events, processing...", discovered_count),
-                    event_progress.clone(),
-                );
+        let cursor_start = self.cursor_start(name);
+        if let Some(since) = cursor_start {
+            info!("BCP: resuming from cursor {}", since);
+        }
+        let date_from = self
+            .config
+            .date_from
+            .or(cursor_start)
+            .unwrap_or_else(|| (chrono::Utc::now() - chrono::Duration::days(30)).date_naive());
+        let date_to = self
+            .config
+            .date_to
+            .unwrap_or_else(|| chrono::Utc::now().date_naive());
 
-                let mut total_events = 0u32;
-                let mut total_placements = 0u32;
-                let mut total_lists = 0u32;
-                let mut errors = Vec::new();
+        let bcp_events = match discovery_client.discover_events(date_from, date_to).await {
+            Ok(events) => events,
+            Err(e) => {
+                warn!("BCP event discovery failed: {}", e);
+                return Ok(SyncResult {
+                    events_synced: 0,
+                    placements_synced: 0,
+                    lists_normalized: 0,
+                    items_for_review: 0,
+                    errors: vec![e.to_string()],
+                    duration: start.elapsed(),
+                });
+            }
+        };
 
-                for (bcp_idx, bcp_event) in bcp_events.iter().enumerate() {
-                    if *self.cancel_token.read().await {
-                        break;
-                    }
+        let discovered_count = bcp_events.len() as u32;
+        info!("BCP: discovered {} events", discovered_count);
 
-                    // Skip team events and events with hidden placings
-                    if bcp_event.should_skip() {
-                        info!(
-                            "  BCP: skipping event: {} (team={:?}, hide_placings={:?})",
-                            bcp_event.name, bcp_event.team_event, bcp_event.hide_placings
-                        );
-                        continue;
-                    }
+        // Build per-event progress list
+        let mut event_progress: Vec<SyncEventProgress> = bcp_events
+            .iter()
+            .map(|e| {
+                let date_str = e
+                    .parsed_start_date()
+                    .map(|d| d.to_string())
+                    .unwrap_or_default();
+                SyncEventProgress {
+                    name: e.name.clone(),
+                    date: date_str,
+                    player_count: e.player_count.unwrap_or(0),
+                    status: if e.should_skip() {
+                        SyncEventStatus::Skipped
+                    } else {
+                        SyncEventStatus::Pending
+                    },
+                    placements_found: 0,
+                    lists_found: 0,
+                    detail: String::new(),
+                }
+            })
+            .collect();
 
-                    // Mark event as syncing
-                    event_progress[bcp_idx].status = SyncEventStatus::Syncing;
-                    event_progress[bcp_idx].detail = "Fetching standings...".to_string();
-                    self.emit_progress(
-                        total_events,
-                        total_placements,
-                        total_lists,
-                        discovered_count,
-                        (bcp_idx + 1) as u32,
-                        format!(
-                            "BCP {}/{}: {} ({} players)",
-                            bcp_idx + 1,
-                            bcp_events.len(),
-                            bcp_event.name,
-                            bcp_event.player_count.unwrap_or(0)
-                        ),
-                        event_progress.clone(),
-                    );
+        self.emit_progress(
+            0,
+            0,
+            0,
+            discovered_count,
+            0,
+            format!("BCP: found {} events, processing...", discovered_count),
+            event_progress.clone(),
+        );
 
-                    let event_date = bcp_event
-                        .parsed_start_date()
-                        .unwrap_or_else(|| chrono::Utc::now().date_naive());
+        let mut total_events = 0u32;
+        let mut total_placements = 0u32;
+        let mut total_lists = 0u32;
+        let mut errors = Vec::new();
+        let mut high_water = HighWater::default();
+        let mut cancelled = false;
+        let today = chrono::Utc::now().date_naive();
 
-                    // Determine epoch
-                    let epoch_id = if self.epoch_mapper.all_epochs().is_empty() {
-                        None
-                    } else {
-                        Some(self.epoch_mapper.get_epoch_id_for_date(event_date))
-                    };
-                    let epoch_str = epoch_id
-                        .as_ref()
-                        .map(|e| e.as_str().to_string())
-                        .unwrap_or_else(|| "current".to_string());
+        for (bcp_idx, bcp_event) in bcp_events.iter().enumerate() {
+            if *self.cancel_token.read().await {
+                cancelled = true;
+                break;
+            }
 
-                    // Convert to Event
-                    let event = convert::event_from_bcp(bcp_event, epoch_id.clone());
+            // Unfinished events hold the cursor at their start date so
+            // their final standings are picked up by a later run
+            if let Some(start_date) = bcp_event.parsed_start_date() {
+                if bcp_event.is_finished(today) {
+                    high_water.ok(bcp_event.parsed_end_date().unwrap_or(start_date));
+                } else {
+                    high_water.failed(start_date);
+                }
+            }
 
-                    if !self.config.dry_run {
-                        // Load existing events for dedup (both exact and fuzzy)
-                        let existing_events: Vec<crate::models::Event> =
-                            crate::storage::JsonlReader::for_entity(
-                                &self.config.storage,
-                                EntityType::Event,
-                                &epoch_str,
-                            )
-                            .read_all()
-                            .unwrap_or_default();
+            // Skip team events and events with hidden placings
+            if bcp_event.should_skip() {
+                info!(
+                    "  BCP: skipping event: {} (team={:?}, hide_placings={:?})",
+                    bcp_event.name, bcp_event.team_event, bcp_event.hide_placings
+                );
+                continue;
+            }
 
-                        if let Some(existing_id) =
-                            convert::find_duplicate_event(&event, &existing_events)
-                        {
-                            info!(
-                                "  BCP: skipping duplicate event: {} (matches {})",
-                                event.name, existing_id
-                            );
-                            // Still fetch standings using the EXISTING event ID
-                            // so placements link to the right event
-                            event_progress[bcp_idx].detail = "Fetching lists...".to_string();
-                            match self
-                                .sync_bcp_standings(
-                                    &bcp_client,
-                                    bcp_event,
-                                    &existing_id,
-                                    epoch_id.clone(),
-                                    &epoch_str,
-                                )
-                                .await
-                            {
-                                Ok((p, l)) => {
-                                    total_placements += p;
-                                    total_lists += l;
-                                    event_progress[bcp_idx].placements_found = p;
-                                    event_progress[bcp_idx].lists_found = l;
-                                }
-                                Err(e) => errors.push(e.to_string()),
-                            }
-                            event_progress[bcp_idx].status = SyncEventStatus::Done;
-                            event_progress[bcp_idx].detail = String::new();
-                            self.emit_progress(
-                                total_events,
-                                total_placements,
-                                total_lists,
-                                discovered_count,
-                                (bcp_idx + 1) as u32,
-                                format!("BCP {}/{}: done", bcp_idx + 1, bcp_events.len()),
-                                event_progress.clone(),
-                            );
-                            continue;
-                        }
+            // Mark event as syncing
+            event_progress[bcp_idx].status = SyncEventStatus::Syncing;
+            event_progress[bcp_idx].detail = "Fetching standings...".to_string();
+            self.emit_progress(
+                total_events,
+                total_placements,
+                total_lists,
+                discovered_count,
+                (bcp_idx + 1) as u32,
+                format!(
+                    "BCP {}/{}: {} ({} players)",
+                    bcp_idx + 1,
+                    bcp_events.len(),
+                    bcp_event.name,
+                    bcp_event.player_count.unwrap_or(0)
+                ),
+                event_progress.clone(),
+            );
 
-                        let event_writer = JsonlWriter::for_entity(
-                            &self.config.storage,
-                            EntityType::Event,
-                            &epoch_str,
-                        );
-                        event_writer.append(&event).map_err(SyncError::Storage)?;
-                    }
-                    total_events += 1;
+            let event_date = bcp_event
+                .parsed_start_date()
+                .unwrap_or_else(|| chrono::Utc::now().date_naive());
+
+            // Determine epoch
+            let epoch_id = if self.epoch_mapper.all_epochs().is_empty() {
+                None
+            } else {
+                Some(self.epoch_mapper.get_epoch_id_for_date(event_date))
+            };
+            let epoch_str = epoch_id
+                .as_ref()
+                .map(|e| e.as_str().to_string())
+                .unwrap_or_else(|| "current".to_string());
 
+            // Convert to Event
+            let status = if bcp_event.is_finished(today) {
+                EventStatus::Completed
+            } else {
+                EventStatus::Upcoming
+            };
+            let event = convert::event_from_bcp(bcp_event, epoch_id.clone()).with_status(status);
+            let event = self.link_series(event);
+
+            if !self.config.dry_run {
+                // Load existing events for dedup (both exact and fuzzy)
+                let existing_events: Vec<Event> = merge_by_id(
+                    crate::storage::JsonlReader::for_entity(
+                        &self.config.storage,
+                        EntityType::Event,
+                        &epoch_str,
+                    )
+                    .read_all()
+                    .unwrap_or_default(),
+                    |e: &Event| e.id.as_str(),
+                );
+
+                if let Some(existing_id) = convert::find_duplicate_event(&event, &existing_events) {
                     info!(
-                        "  BCP event: {} ({:?} players)",
-                        event.name, event.player_count
+                        "  BCP: skipping duplicate event: {} (matches {})",
+                        event.name, existing_id
                     );
-
-                    // Fetch standings for this event
+                    // Still fetch standings using the EXISTING event ID
+                    // so placements link to the right event
                     event_progress[bcp_idx].detail = "Fetching lists...".to_string();
                     match self
                         .sync_bcp_standings(
                             &bcp_client,
                             bcp_event,
-                            &event.id,
+                            &existing_id,
                             epoch_id.clone(),
                             &epoch_str,
+                            split_brackets,
                         )
                         .await
                     {
                         Ok((p, l)) => {
+                            self.note_success(BCP_SOURCE, &bcp_event.event_url());
                             total_placements += p;
                             total_lists += l;
                             event_progress[bcp_idx].placements_found = p;
                             event_progress[bcp_idx].lists_found = l;
+                            let stored = existing_events.iter().find(|e| e.id == existing_id);
+                            if let Some(stored) = stored.filter(|e| e.is_upcoming()) {
+                                if status == EventStatus::Completed {
+                                    self.mark_completed(stored, &epoch_str);
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            errors.push(e.to_string());
+                            self.note_failure(
+                                BCP_SOURCE,
+                                &bcp_event.event_url(),
+                                IngestStage::Standings,
+                                bcp_retry_target(bcp_event, &existing_id, event_date),
+                                Some(&bcp_client.players_url(&bcp_event.id)),
+                                &e,
+                            );
+                            if let Some(d) = bcp_event.parsed_start_date() {
+                                high_water.failed(d);
+                            }
                         }
-                        Err(e) => errors.push(e.to_string()),
                     }
-
                     event_progress[bcp_idx].status = SyncEventStatus::Done;
                     event_progress[bcp_idx].detail = String::new();
                     self.emit_progress(
@@ -891,213 +1756,469 @@ impl SyncOrchestrator {
                         format!("BCP {}/{}: done", bcp_idx + 1, bcp_events.len()),
                         event_progress.clone(),
                     );
+                    continue;
                 }
 
-                // Backfill: find existing BCP events with placements missing lists
-                // that weren't already processed in this sync (e.g. not in the 100-event discovery window)
-                if !self.config.dry_run {
-                    let processed_event_ids: std::collections::HashSet<String> = bcp_events
+                let event_writer = JsonlWriter::for_source(
+                    &self.config.storage,
+                    EntityType::Event,
+                    &epoch_str,
+                    &event.source_name,
+                );
+                event_writer.append(&event).map_err(SyncError::Storage)?;
+            }
+            total_events += 1;
+
+            info!(
+                "  BCP event: {} ({:?} players)",
+                event.name, event.player_count
+            );
+
+            // Fetch standings for this event
+            event_progress[bcp_idx].detail = "Fetching lists...".to_string();
+            match self
+                .sync_bcp_standings(
+                    &bcp_client,
+                    bcp_event,
+                    &event.id,
+                    epoch_id.clone(),
+                    &epoch_str,
+                    split_brackets,
+                )
+                .await
+            {
+                Ok((p, l)) => {
+                    self.note_success(BCP_SOURCE, &bcp_event.event_url());
+                    total_placements += p;
+                    total_lists += l;
+                    event_progress[bcp_idx].placements_found = p;
+                    event_progress[bcp_idx].lists_found = l;
+                }
+                Err(e) => {
+                    errors.push(e.to_string());
+                    self.note_failure(
+                        BCP_SOURCE,
+                        &bcp_event.event_url(),
+                        IngestStage::Standings,
+                        bcp_retry_target(bcp_event, &event.id, event_date),
+                        Some(&bcp_client.players_url(&bcp_event.id)),
+                        &e,
+                    );
+                    if let Some(d) = bcp_event.parsed_start_date() {
+                        high_water.failed(d);
+                    }
+                }
+            }
+
+            event_progress[bcp_idx].status = SyncEventStatus::Done;
+            event_progress[bcp_idx].detail = String::new();
+            self.emit_progress(
+                total_events,
+                total_placements,
+                total_lists,
+                discovered_count,
+                (bcp_idx + 1) as u32,
+                format!("BCP {}/{}: done", bcp_idx + 1, bcp_events.len()),
+                event_progress.clone(),
+            );
+        }
+
+        if !cancelled {
+            self.advance_cursor(name, &high_water);
+        }
+
+        // Backfill: find existing BCP events with placements missing lists
+        // that weren't already processed in this sync (e.g. not in the 100-event discovery window)
+        if !self.config.dry_run {
+            let processed_event_ids: std::collections::HashSet<String> = bcp_events
+                .iter()
+                .map(|e| {
+                    let event = convert::event_from_bcp(e, None);
+                    event.id.as_str().to_string()
+                })
+                .collect();
+
+            // Scan all epochs for BCP events needing list backfill
+            let epoch_dirs =
+                crate::storage::jsonl::list_epochs(&self.config.storage).unwrap_or_default();
+            for epoch_dir in &epoch_dirs {
+                if *self.cancel_token.read().await {
+                    break;
+                }
+
+                let events: Vec<Event> = merge_by_id(
+                    crate::storage::JsonlReader::for_entity(
+                        &self.config.storage,
+                        EntityType::Event,
+                        epoch_dir,
+                    )
+                    .read_all()
+                    .unwrap_or_default(),
+                    |e: &Event| e.id.as_str(),
+                );
+
+                let placements: Vec<Placement> = crate::storage::JsonlReader::for_entity(
+                    &self.config.storage,
+                    EntityType::Placement,
+                    epoch_dir,
+                )
+                .read_all()
+                .unwrap_or_default();
+
+                for event in &events {
+                    if *self.cancel_token.read().await {
+                        break;
+                    }
+
+                    // Only BCP events, and only ones not already processed this sync.
+                    // Bracket events are refreshed through their parent listing.
+                    if event.source_name != "bcp"
+                        || event.parent_id.is_some()
+                        || processed_event_ids.contains(event.id.as_str())
+                    {
+                        continue;
+                    }
+
+                    // Check if this event has placements needing lists
+                    let event_placements: Vec<&Placement> = placements
                         .iter()
-                        .map(|e| {
-                            let event = convert::event_from_bcp(e, None);
-                            event.id.as_str().to_string()
-                        })
+                        .filter(|p| p.event_id == event.id)
                         .collect();
+                    let without_lists = event_placements
+                        .iter()
+                        .filter(|p| p.list_id.is_none())
+                        .count();
+
+                    // Upcoming events that have since been played are
+                    // fetched whatever they hold
+                    let ended = event.is_upcoming()
+                        && event.date + chrono::Days::new(UPCOMING_GRACE_DAYS) < today;
+                    if !ended && (without_lists == 0 || event_placements.len() < 10) {
+                        continue;
+                    }
 
-                    // Scan all epochs for BCP events needing list backfill
-                    let epoch_dirs = crate::storage::jsonl::list_epochs(&self.config.storage)
-                        .unwrap_or_default();
-                    for epoch_dir in &epoch_dirs {
-                        if *self.cancel_token.read().await {
-                            break;
-                        }
+                    // Extract BCP event ID from source_url
+                    let bcp_event_id = match event
+                        .source_url
+                        .strip_prefix("https://www.bestcoastpairings.com/event/")
+                    {
+                        Some(id) if !id.is_empty() => id,
+                        _ => continue,
+                    };
 
-                        let events: Vec<crate::models::Event> =
-                            crate::storage::JsonlReader::for_entity(
-                                &self.config.storage,
-                                EntityType::Event,
-                                epoch_dir,
-                            )
-                            .read_all()
-                            .unwrap_or_default();
+                    if ended {
+                        info!("  BCP backfill: upcoming event {} has ended", event.name);
+                    } else {
+                        info!(
+                            "  BCP backfill: {} has {} placements without lists",
+                            event.name, without_lists
+                        );
+                    }
 
-                        let placements: Vec<Placement> = crate::storage::JsonlReader::for_entity(
-                            &self.config.storage,
-                            EntityType::Placement,
+                    // Reconstruct a minimal BcpEvent for the standings fetch
+                    let backfill_bcp_event = bcp::BcpEvent::finished(
+                        bcp_event_id,
+                        &event.name,
+                        event.date,
+                        event.player_count,
+                        event.round_count,
+                    );
+
+                    let epoch_id = if self.epoch_mapper.all_epochs().is_empty() {
+                        None
+                    } else {
+                        Some(self.epoch_mapper.get_epoch_id_for_date(event.date))
+                    };
+
+                    self.emit_progress(
+                        total_events,
+                        total_placements,
+                        total_lists,
+                        discovered_count,
+                        0,
+                        format!("Backfilling lists for {}...", event.name),
+                        event_progress.clone(),
+                    );
+
+                    match self
+                        .sync_bcp_standings(
+                            &bcp_client,
+                            &backfill_bcp_event,
+                            &event.id,
+                            epoch_id,
                             epoch_dir,
+                            split_brackets,
                         )
-                        .read_all()
-                        .unwrap_or_default();
-
-                        for event in &events {
-                            if *self.cancel_token.read().await {
-                                break;
+                        .await
+                    {
+                        Ok((p, l)) => {
+                            self.note_success(BCP_SOURCE, &event.source_url);
+                            total_placements += p;
+                            total_lists += l;
+                            if l > 0 {
+                                info!("  BCP backfill: {} new lists for {}", l, event.name);
                             }
-
-                            // Only BCP events, and only ones not already processed this sync
-                            if event.source_name != "bcp"
-                                || processed_event_ids.contains(event.id.as_str())
-                            {
-                                continue;
+                            if ended {
+                                self.mark_completed(event, epoch_dir);
                             }
+                        }
+                        Err(e) => {
+                            warn!("  BCP backfill failed for {}: {}", event.name, e);
+                            errors.push(e.to_string());
+                            self.note_failure(
+                                BCP_SOURCE,
+                                &event.source_url,
+                                IngestStage::Backfill,
+                                bcp_retry_target(&backfill_bcp_event, &event.id, event.date),
+                                Some(&bcp_client.players_url(bcp_event_id)),
+                                &e,
+                            );
+                        }
+                    }
+                }
+            }
+        }
 
-                            // Check if this event has placements needing lists
-                            let event_placements: Vec<&Placement> = placements
-                                .iter()
-                                .filter(|p| p.event_id == event.id)
-                                .collect();
-                            let without_lists = event_placements
-                                .iter()
-                                .filter(|p| p.list_id.is_none())
-                                .count();
-
-                            if without_lists == 0 || event_placements.len() < 10 {
-                                continue;
-                            }
+        Ok(SyncResult {
+            events_synced: total_events,
+            placements_synced: total_placements,
+            lists_normalized: total_lists,
+            items_for_review: 0,
+            errors,
+            duration: start.elapsed(),
+        })
+    }
+
+    /// Client for BCP event discovery. It is unauthenticated: BCP rejects
+    /// authed /events requests with 409.
+    fn bcp_discovery_client(
+        &self,
+        api_base_url: &str,
+        game_type: u32,
+    ) -> Result<bcp::BcpClient, SyncError> {
+        let discovery_fetcher = Fetcher::new(crate::fetch::FetcherConfig {
+            cache_dir: self.config.storage.raw_dir(),
+            extra_headers: bcp::bcp_headers(),
+            request_delay: self.fetcher.config().request_delay,
+            ..Default::default()
+        })
+        .map_err(SyncError::Fetch)?;
+        Ok(bcp::BcpClient::new(
+            discovery_fetcher,
+            api_base_url.to_string(),
+            game_type,
+        ))
+    }
+
+    /// Store BCP events that haven't been played yet, without standings.
+    /// Later syncs fetch their results and mark them completed once they
+    /// end.
+    async fn sync_bcp_upcoming(
+        &self,
+        api_base_url: &str,
+        game_type: u32,
+    ) -> Result<SyncResult, SyncError> {
+        let start = std::time::Instant::now();
+        let today = chrono::Utc::now().date_naive();
+        let date_from = self.config.date_from.unwrap_or(today);
+        let date_to = self
+            .config
+            .date_to
+            .unwrap_or(today + chrono::Days::new(UPCOMING_DAYS));
+        info!(
+            "Discovering upcoming BCP events {} to {}",
+            date_from, date_to
+        );
+
+        let client = self.bcp_discovery_client(api_base_url, game_type)?;
+        let bcp_events = match client.discover_events(date_from, date_to).await {
+            Ok(events) => events,
+            Err(e) => {
+                warn!("BCP event discovery failed: {}", e);
+                return Ok(SyncResult {
+                    events_synced: 0,
+                    placements_synced: 0,
+                    lists_normalized: 0,
+                    items_for_review: 0,
+                    errors: vec![e.to_string()],
+                    duration: start.elapsed(),
+                });
+            }
+        };
+
+        let mut stored = 0u32;
+        for bcp_event in &bcp_events {
+            if bcp_event.should_skip() || bcp_event.is_finished(today) {
+                continue;
+            }
+            let Some(event_date) = bcp_event.parsed_start_date() else {
+                continue;
+            };
+            let epoch_id = if self.epoch_mapper.all_epochs().is_empty() {
+                None
+            } else {
+                Some(self.epoch_mapper.get_epoch_id_for_date(event_date))
+            };
+            let epoch_str = epoch_id
+                .as_ref()
+                .map(|e| e.as_str().to_string())
+                .unwrap_or_else(|| "current".to_string());
+            let event =
+                convert::event_from_bcp(bcp_event, epoch_id).with_status(EventStatus::Upcoming);
+            let event = self.link_series(event);
+
+            if !self.config.dry_run {
+                let existing_events: Vec<Event> = crate::storage::JsonlReader::for_entity(
+                    &self.config.storage,
+                    EntityType::Event,
+                    &epoch_str,
+                )
+                .read_all()
+                .unwrap_or_default();
+                if convert::find_duplicate_event(&event, &existing_events).is_some() {
+                    continue;
+                }
+                JsonlWriter::for_source(
+                    &self.config.storage,
+                    EntityType::Event,
+                    &epoch_str,
+                    &event.source_name,
+                )
+                .append(&event)
+                .map_err(SyncError::Storage)?;
+            }
+            info!("  BCP upcoming: {} on {}", event.name, event.date);
+            stored += 1;
+        }
 
-                            // Extract BCP event ID from source_url
-                            let bcp_event_id = match event
-                                .source_url
-                                .strip_prefix("https://www.bestcoastpairings.com/event/")
-                            {
-                                Some(id) if !id.is_empty() => id,
-                                _ => continue,
-                            };
+        Ok(SyncResult {
+            events_synced: stored,
+            placements_synced: 0,
+            lists_normalized: 0,
+            items_for_review: 0,
+            errors: Vec::new(),
+            duration: start.elapsed(),
+        })
+    }
 
-                            info!(
-                                "  BCP backfill: {} has {} placements without lists",
-                                event.name, without_lists
-                            );
+    /// Record that an upcoming event has been played.
+    fn mark_completed(&self, event: &Event, epoch_str: &str) {
+        let mut event = event.clone();
+        event.status = EventStatus::Completed;
+        event.touch();
+        let writer = JsonlWriter::for_source(
+            &self.config.storage,
+            EntityType::Event,
+            epoch_str,
+            &event.source_name,
+        );
+        match writer.append(&event) {
+            Ok(()) => info!("  BCP: {} is now completed", event.name),
+            Err(e) => warn!("  BCP: could not mark {} completed: {}", event.name, e),
+        }
+    }
 
-                            // Reconstruct a minimal BcpEvent for the standings fetch
-                            let backfill_bcp_event = bcp::BcpEvent {
-                                id: bcp_event_id.to_string(),
-                                name: event.name.clone(),
-                                start_date: Some(event.date.to_string()),
-                                end_date: None,
-                                venue: None,
-                                city: None,
-                                state: None,
-                                country: None,
-                                player_count: event.player_count,
-                                round_count: event.round_count,
-                                game_type: None,
-                                ended: Some(true),
-                                team_event: None,
-                                hide_placings: None,
-                            };
-
-                            let epoch_id = if self.epoch_mapper.all_epochs().is_empty() {
-                                None
-                            } else {
-                                Some(self.epoch_mapper.get_epoch_id_for_date(event.date))
-                            };
-
-                            self.emit_progress(
-                                total_events,
-                                total_placements,
-                                total_lists,
-                                discovered_count,
-                                0,
-                                format!("Backfilling lists for {}...", event.name),
-                                event_progress.clone(),
-                            );
+    /// Give a stored event with no known points limit the one its lists
+    /// were built to.
+    fn record_game_size(
+        &self,
+        event_id: &crate::models::EventId,
+        epoch_str: &str,
+        lists: &[ArmyList],
+    ) {
+        if self.config.dry_run || lists.is_empty() {
+            return;
+        }
+        let points: Vec<u32> = lists.iter().map(|l| l.total_points).collect();
+        let Some(game_size) = brackets::infer_game_size(&points) else {
+            return;
+        };
+        let events = merge_by_id(
+            crate::storage::JsonlReader::<Event>::for_entity(
+                &self.config.storage,
+                EntityType::Event,
+                epoch_str,
+            )
+            .read_all()
+            .unwrap_or_default(),
+            |e: &Event| e.id.as_str(),
+        );
+        let Some(mut event) = events
+            .into_iter()
+            .find(|e| &e.id == event_id && e.game_size.is_none())
+        else {
+            return;
+        };
+        event.game_size = Some(game_size);
+        event.touch();
+        let writer = JsonlWriter::for_source(
+            &self.config.storage,
+            EntityType::Event,
+            epoch_str,
+            &event.source_name,
+        );
+        match writer.append(&event) {
+            Ok(()) => info!("  BCP: {} played at {}pts", event.name, game_size),
+            Err(e) => warn!("  BCP: could not record game size of {}: {}", event.name, e),
+        }
+    }
 
-                            match self
-                                .sync_bcp_standings(
-                                    &bcp_client,
-                                    &backfill_bcp_event,
-                                    &event.id,
-                                    epoch_id,
-                                    epoch_dir,
-                                )
-                                .await
-                            {
-                                Ok((p, l)) => {
-                                    total_placements += p;
-                                    total_lists += l;
-                                    if l > 0 {
-                                        info!("  BCP backfill: {} new lists for {}", l, event.name);
-                                    }
-                                }
-                                Err(e) => {
-                                    warn!("  BCP backfill failed for {}: {}", event.name, e);
-                                    errors.push(e.to_string());
-                                }
-                            }
-                        }
-                    }
-                }
+    /// Sync balance updates from a Warhammer Community page.
+    async fn sync_warhammer_community(&self, url: &str) -> Result<SyncResult, SyncError> {
+        let start = std::time::Instant::now();
+        info!("Syncing balance updates from: {}", url);
 
-                Ok(SyncResult {
-                    events_synced: total_events,
-                    placements_synced: total_placements,
-                    lists_normalized: total_lists,
-                    items_for_review: 0,
-                    errors,
-                    duration: start.elapsed(),
-                })
-            }
-            SyncSource::WarhammerCommunity { url } => {
-                info!("Syncing balance updates from: {}", url);
+        let page_url = Url::parse(url)
+            .map_err(|e| SyncError::Fetch(crate::fetch::FetchError::InvalidUrl(e.to_string())))?;
 
-                let page_url = Url::parse(url).map_err(|e| {
-                    SyncError::Fetch(crate::fetch::FetchError::InvalidUrl(e.to_string()))
-                })?;
+        // 1. Fetch page
+        let fetch_result = self.fetcher.fetch(&page_url).await?;
+        let html = self.fetcher.read_cached_text(&fetch_result).await?;
 
-                // 1. Fetch page
-                let fetch_result = self.fetcher.fetch(&page_url).await?;
-                let html = self.fetcher.read_cached_text(&fetch_result).await?;
+        // 2. Run BalanceWatcherAgent
+        let watcher =
+            BalanceWatcherAgent::new(self.backend.clone()).with_grace_days(self.config.grace_days);
+        let input = BalanceWatcherInput {
+            html_content: html,
+            source_url: url.to_string(),
+            known_event_ids: vec![],
+        };
 
-                // 2. Run BalanceWatcherAgent
-                let watcher = BalanceWatcherAgent::new(self.backend.clone());
-                let input = BalanceWatcherInput {
-                    html_content: html,
-                    source_url: url.clone(),
-                    known_event_ids: vec![],
-                };
+        let output = execute_with_retry(&watcher, input).await?;
+        let event_count = output.events.len() as u32;
 
-                let output = watcher.execute(input).await?;
-                let event_count = output.events.len() as u32;
-
-                // 3. Store SignificantEvent entities to global file
-                if !self.config.dry_run {
-                    let mut existing =
-                        read_significant_events(&self.config.storage).unwrap_or_default();
-                    let existing_ids: std::collections::HashSet<String> =
-                        existing.iter().map(|e| e.id.as_str().to_string()).collect();
-                    for event_output in &output.events {
-                        if !existing_ids.contains(event_output.data.id.as_str()) {
-                            existing.push(event_output.data.clone());
-                        }
-                    }
-                    write_significant_events(&self.config.storage, &mut existing)
-                        .map_err(SyncError::Storage)?;
+        // 3. Store SignificantEvent entities to global file
+        if !self.config.dry_run {
+            let mut existing = read_significant_events(&self.config.storage).unwrap_or_default();
+            let existing_ids: std::collections::HashSet<String> =
+                existing.iter().map(|e| e.id.as_str().to_string()).collect();
+            for event_output in &output.events {
+                if !existing_ids.contains(event_output.data.id.as_str()) {
+                    existing.push(event_output.data.clone());
                 }
-
-                info!("Balance watcher found {} events", event_count);
-
-                Ok(SyncResult {
-                    events_synced: event_count,
-                    placements_synced: 0,
-                    lists_normalized: 0,
-                    items_for_review: 0,
-                    errors: vec![],
-                    duration: start.elapsed(),
-                })
             }
+            write_significant_events(&self.config.storage, &mut existing)
+                .map_err(SyncError::Storage)?;
         }
+
+        info!("Balance watcher found {} events", event_count);
+
+        Ok(SyncResult {
+            events_synced: event_count,
+            placements_synced: 0,
+            lists_normalized: 0,
+            items_for_review: 0,
+            errors: vec![],
+            duration: start.elapsed(),
+        })
     }
 
     /// Fetch article content from WordPress REST API.
     ///
     /// Returns the rendered HTML content from the post's `content.rendered` field.
     async fn fetch_wp_article_content(&self, post_id: u64) -> Result<String, SyncError> {
-        let api_url = Url::parse(&format!(
-            "https://www.goonhammer.com/wp-json/wp/v2/posts/{}",
-            post_id
-        ))
-        .map_err(|e| SyncError::Fetch(crate::fetch::FetchError::InvalidUrl(e.to_string())))?;
+        let api_url = Url::parse(&wp_post_url(post_id))
+            .map_err(|e| SyncError::Fetch(crate::fetch::FetchError::InvalidUrl(e.to_string())))?;
 
         let fetch_result = self.fetcher.fetch(&api_url).await?;
         let json_text = self.fetcher.read_cached_text(&fetch_result).await?;
@@ -1148,6 +2269,23 @@ impl SyncOrchestrator {
         article_url: &Url,
         article_date: NaiveDate,
         html_content: &str,
+    ) -> Result<(u32, u32, u32), SyncError> {
+        self.extract_article(article_url, article_date, html_content, false)
+            .await
+    }
+
+    /// Run the extraction agents over an article's HTML and store the results.
+    ///
+    /// An event already stored under the same ID is skipped along with its
+    /// results, unless `reuse_events` is set: then it, or a stored event
+    /// matching by name and date, keeps its ID and only placements and
+    /// lists not yet stored are added to it.
+    async fn extract_article(
+        &self,
+        article_url: &Url,
+        article_date: NaiveDate,
+        html_content: &str,
+        reuse_events: bool,
     ) -> Result<(u32, u32, u32), SyncError> {
         // Strip HTML to clean text for the AI (saves ~50% tokens)
         let article_text = discovery::extract_text_from_html(html_content);
@@ -1165,7 +2303,7 @@ impl SyncOrchestrator {
             article_date,
         };
 
-        let scout_output = event_scout.execute(scout_input).await?;
+        let scout_output = execute_with_retry(&event_scout, scout_input).await?;
         info!("Event Scout found {} events", scout_output.events.len());
 
         let mut total_events = 0u32;
@@ -1190,9 +2328,11 @@ impl SyncOrchestrator {
                 event_stub,
                 article_url.as_str(),
                 article_date,
-                "goonhammer",
+                GOONHAMMER_SOURCE,
                 epoch_id.clone(),
             );
+            let mut event = self.link_series(event);
+            let mut is_new = true;
 
             if !self.config.dry_run {
                 // Dedup: load existing event IDs and skip if already present
@@ -1209,16 +2349,37 @@ impl SyncOrchestrator {
                     .map(|e| e.id.as_str().to_string())
                     .collect();
 
-                if existing_event_ids.contains(event.id.as_str()) {
-                    info!("  Skipping duplicate event: {} ({})", event.name, event.id);
-                    continue;
+                let existing_id = if existing_event_ids.contains(event.id.as_str()) {
+                    Some(event.id.clone())
+                } else if reuse_events {
+                    convert::find_duplicate_event(&event, &existing_events)
+                } else {
+                    None
+                };
+                match existing_id {
+                    Some(existing_id) if reuse_events => {
+                        info!("  Reusing stored event: {} ({})", event.name, existing_id);
+                        event.id = existing_id;
+                        is_new = false;
+                    }
+                    Some(_) => {
+                        info!("  Skipping duplicate event: {} ({})", event.name, event.id);
+                        continue;
+                    }
+                    None => {
+                        let event_writer = JsonlWriter::for_source(
+                            &self.config.storage,
+                            EntityType::Event,
+                            &epoch_str,
+                            &event.source_name,
+                        );
+                        event_writer.append(&event).map_err(SyncError::Storage)?;
+                    }
                 }
-
-                let event_writer =
-                    JsonlWriter::for_entity(&self.config.storage, EntityType::Event, &epoch_str);
-                event_writer.append(&event).map_err(SyncError::Storage)?;
             }
-            total_events += 1;
+            if is_new {
+                total_events += 1;
+            }
 
             info!("  Event: {} ({:?} players)", event.name, event.player_count);
 
@@ -1229,7 +2390,7 @@ impl SyncOrchestrator {
                 event_stub: event_stub.data.clone(),
             };
 
-            match harvester.execute(harvest_input).await {
+            match execute_with_retry(&harvester, harvest_input).await {
                 Ok(harvest_output) => {
                     let list_count = harvest_output.raw_lists.len() as u32;
                     total_lists += list_count;
@@ -1310,6 +2471,8 @@ impl SyncOrchestrator {
                             player_name: raw_list.player_name.clone(),
                         };
 
+                        let mut norm_model = None;
+                        let mut norm_agent_version = None;
                         let (
                             norm_faction,
                             norm_detachment,
@@ -1317,8 +2480,10 @@ impl SyncOrchestrator {
                             norm_points,
                             norm_units,
                             norm_confidence,
-                        ) = match normalizer.execute(norm_input).await {
+                        ) = match execute_with_retry(&normalizer, norm_input).await {
                             Ok(output) => {
+                                norm_model = output.list.model;
+                                norm_agent_version = output.list.agent_version;
                                 let d = output.list.data;
                                 info!(
                                     "    Normalized: {} - {} ({} units, {}pts)",
@@ -1368,88 +2533,532 @@ impl SyncOrchestrator {
                         .with_source_url(article_url.to_string())
                         .with_confidence(norm_confidence);
 
-                        if let Some(det) = norm_detachment {
-                            army_list = army_list.with_detachment(det);
-                        }
-                        if let Some(sub) = norm_subfaction {
-                            army_list = army_list.with_subfaction(sub);
-                        }
+                        if let Some(det) = norm_detachment {
+                            army_list = army_list.with_detachment(det);
+                        }
+                        if let Some(sub) = norm_subfaction {
+                            army_list = army_list.with_subfaction(sub);
+                        }
+                        army_list.extracted_by = norm_model;
+                        army_list.extraction_agent_version = norm_agent_version;
+
+                        if !self.config.dry_run && existing_list_ids.contains(army_list.id.as_str())
+                        {
+                            info!("    Skipping duplicate army list: {}", army_list.id);
+                            continue;
+                        }
+
+                        info!(
+                            "    Stored army list for #{} {} ({} chars, {} units)",
+                            raw_list.placement_rank,
+                            raw_list.player_name,
+                            raw_list.text.len(),
+                            army_list.units.len()
+                        );
+                        stored_lists.push(army_list);
+                    }
+
+                    // 7. Link placements to lists by player name
+                    let name_to_list_id: std::collections::HashMap<
+                        String,
+                        crate::models::ArmyListId,
+                    > = stored_lists
+                        .iter()
+                        .filter_map(|l| {
+                            l.player_name
+                                .as_ref()
+                                .map(|name| (normalize_player_name(name), l.id.clone()))
+                        })
+                        .collect();
+
+                    for placement in &mut buffered_placements {
+                        if placement.list_id.is_none() {
+                            let norm_name = normalize_player_name(&placement.player_name);
+                            if let Some(list_id) = name_to_list_id.get(&norm_name) {
+                                placement.list_id = Some(list_id.clone());
+                            }
+                        }
+                    }
+
+                    // 8. Rule-check the results; failures wait in the review queue
+                    let mut held: Vec<ReviewQueueItem> = Vec::new();
+                    let mut checks = fact_checker::check_placements(
+                        &article_text,
+                        event.player_count,
+                        event.round_count,
+                        &buffered_placements,
+                    )
+                    .into_iter();
+                    buffered_placements.retain(|placement| {
+                        let found = checks.next().unwrap_or_default();
+                        if !fact_checker::blocks_storage(&found) {
+                            return true;
+                        }
+                        warn!(
+                            "    Holding placement #{} {} for review",
+                            placement.rank, placement.player_name
+                        );
+                        held.push(held_for_review(
+                            crate::models::EntityType::Placement,
+                            placement.id.clone(),
+                            placement,
+                            &found,
+                            &epoch_str,
+                            &event.source_name,
+                        ));
+                        false
+                    });
+                    stored_lists.retain(|army_list| {
+                        let found = fact_checker::check_list(army_list);
+                        if !fact_checker::blocks_storage(&found) {
+                            return true;
+                        }
+                        warn!("    Holding army list {} for review", army_list.id);
+                        held.push(held_for_review(
+                            crate::models::EntityType::ArmyList,
+                            army_list.id.clone(),
+                            army_list,
+                            &found,
+                            &epoch_str,
+                            &event.source_name,
+                        ));
+                        false
+                    });
+                    self.held_for_review
+                        .fetch_add(held.len() as u32, std::sync::atomic::Ordering::Relaxed);
+
+                    // 9. Store placements and lists, and queue held records
+                    if !self.config.dry_run && !held.is_empty() {
+                        let mut items = crate::storage::read_review_items(&self.config.storage)
+                            .map_err(SyncError::Storage)?;
+                        items.extend(held);
+                        crate::storage::write_review_items(&self.config.storage, &items)
+                            .map_err(SyncError::Storage)?;
+                    }
+                    if !self.config.dry_run {
+                        let placement_writer = JsonlWriter::for_source(
+                            &self.config.storage,
+                            EntityType::Placement,
+                            &epoch_str,
+                            &event.source_name,
+                        );
+                        for placement in &buffered_placements {
+                            placement_writer
+                                .append(placement)
+                                .map_err(SyncError::Storage)?;
+                        }
+
+                        let list_writer = JsonlWriter::for_source(
+                            &self.config.storage,
+                            EntityType::ArmyList,
+                            &epoch_str,
+                            &event.source_name,
+                        );
+                        for army_list in &stored_lists {
+                            list_writer.append(army_list).map_err(SyncError::Storage)?;
+                        }
+                    }
+                    total_placements += buffered_placements.len() as u32;
+
+                    info!(
+                        "    {} placements, {} lists",
+                        buffered_placements.len(),
+                        list_count
+                    );
+                }
+                Err(e) => {
+                    warn!("Result Harvester error for {}: {}", event.name, e);
+                }
+            }
+        }
+
+        Ok((total_events, total_placements, total_lists))
+    }
+
+    /// Sync tournaments from Tabletop Tournaments (T3).
+    ///
+    /// Mirrors the BCP flow: discover events in the date range, skip ones
+    /// already stored from another source (by fuzzy name/date match, reusing
+    /// the existing event ID), then store standings and army lists.
+    async fn sync_t3(
+        &self,
+        name: &str,
+        base_url: &str,
+        game_system: &str,
+    ) -> Result<SyncResult, SyncError> {
+        let start = std::time::Instant::now();
+        info!("Syncing from T3: {} ({})", base_url, game_system);
+        // Own fetcher (same cache and rate limit) so the client can be moved around
+        let fetcher = Fetcher::new(self.fetcher.config().clone()).map_err(SyncError::Fetch)?;
+        let client = t3::T3Client::new(fetcher, base_url.to_string(), game_system.to_string());
+
+        let cursor_start = self.cursor_start(name);
+        if let Some(since) = cursor_start {
+            info!("T3: resuming from cursor {}", since);
+        }
+        let today = chrono::Utc::now().date_naive();
+        let date_from = self
+            .config
+            .date_from
+            .or(cursor_start)
+            .unwrap_or_else(|| today - chrono::Duration::days(30));
+        let date_to = self.config.date_to.unwrap_or(today);
+
+        let t3_events = match client.discover_events(date_from, date_to).await {
+            Ok(events) => events,
+            Err(e) => {
+                warn!("T3 event discovery failed: {}", e);
+                return Ok(SyncResult {
+                    events_synced: 0,
+                    placements_synced: 0,
+                    lists_normalized: 0,
+                    items_for_review: 0,
+                    errors: vec![e.to_string()],
+                    duration: start.elapsed(),
+                });
+            }
+        };
+        let discovered_count = t3_events.len() as u32;
+
+        let mut event_progress: Vec<SyncEventProgress> = t3_events
+            .iter()
+            .map(|e| SyncEventProgress {
+                name: e.name.clone(),
+                date: e.date.map(|d| d.to_string()).unwrap_or_default(),
+                player_count: e.player_count.unwrap_or(0),
+                status: SyncEventStatus::Pending,
+                placements_found: 0,
+                lists_found: 0,
+                detail: String::new(),
+            })
+            .collect();
+
+        let mut total_events = 0u32;
+        let mut total_placements = 0u32;
+        let mut total_lists = 0u32;
+        let mut errors = Vec::new();
+        let mut high_water = HighWater::default();
+        let mut cancelled = false;
+
+        for (idx, t3_event) in t3_events.iter().enumerate() {
+            if *self.cancel_token.read().await {
+                cancelled = true;
+                break;
+            }
+
+            let Some(event_date) = t3_event.date else {
+                event_progress[idx].status = SyncEventStatus::Skipped;
+                continue;
+            };
+            if !t3_event.is_finished(today) {
+                high_water.failed(event_date);
+                event_progress[idx].status = SyncEventStatus::Skipped;
+                continue;
+            }
+            high_water.ok(event_date);
+
+            event_progress[idx].status = SyncEventStatus::Syncing;
+            event_progress[idx].detail = "Fetching standings...".to_string();
+            self.emit_progress(
+                total_events,
+                total_placements,
+                total_lists,
+                discovered_count,
+                (idx + 1) as u32,
+                format!("T3 {}/{}: {}", idx + 1, t3_events.len(), t3_event.name),
+                event_progress.clone(),
+            );
+
+            let epoch_id = if self.epoch_mapper.all_epochs().is_empty() {
+                None
+            } else {
+                Some(self.epoch_mapper.get_epoch_id_for_date(event_date))
+            };
+            let epoch_str = epoch_id
+                .as_ref()
+                .map(|e| e.as_str().to_string())
+                .unwrap_or_else(|| "current".to_string());
+
+            let event = convert::event_from_t3(t3_event, client.base_url(), epoch_id.clone());
+            let event = self.link_series(event);
+
+            let mut event_id = event.id.clone();
+            if !self.config.dry_run {
+                let existing_events: Vec<crate::models::Event> =
+                    crate::storage::JsonlReader::for_entity(
+                        &self.config.storage,
+                        EntityType::Event,
+                        &epoch_str,
+                    )
+                    .read_all()
+                    .unwrap_or_default();
+
+                if let Some(existing_id) = convert::find_duplicate_event(&event, &existing_events) {
+                    info!(
+                        "  T3: event {} already stored (matches {})",
+                        event.name, existing_id
+                    );
+                    event_id = existing_id;
+                } else {
+                    JsonlWriter::for_source(
+                        &self.config.storage,
+                        EntityType::Event,
+                        &epoch_str,
+                        T3_SOURCE,
+                    )
+                    .append(&event)
+                    .map_err(SyncError::Storage)?;
+                    total_events += 1;
+                }
+            } else {
+                total_events += 1;
+            }
+
+            match self
+                .sync_t3_standings(&client, t3_event, &event_id, epoch_id, &epoch_str)
+                .await
+            {
+                Ok((p, l)) => {
+                    self.note_success(T3_SOURCE, &event.source_url);
+                    total_placements += p;
+                    total_lists += l;
+                    event_progress[idx].placements_found = p;
+                    event_progress[idx].lists_found = l;
+                }
+                Err(e) => {
+                    errors.push(e.to_string());
+                    self.note_failure(
+                        T3_SOURCE,
+                        &event.source_url,
+                        IngestStage::Standings,
+                        RetryTarget::T3Event {
+                            t3_id: t3_event.id.clone(),
+                            name: t3_event.name.clone(),
+                            date: event_date,
+                            event_id: event_id.clone(),
+                        },
+                        client
+                            .standings_url(t3_event)
+                            .ok()
+                            .as_ref()
+                            .map(Url::as_str),
+                        &e,
+                    );
+                    high_water.failed(event_date);
+                }
+            }
+
+            event_progress[idx].status = SyncEventStatus::Done;
+            event_progress[idx].detail = String::new();
+            self.emit_progress(
+                total_events,
+                total_placements,
+                total_lists,
+                discovered_count,
+                (idx + 1) as u32,
+                format!("T3 {}/{}: done", idx + 1, t3_events.len()),
+                event_progress.clone(),
+            );
+        }
 
-                        if !self.config.dry_run && existing_list_ids.contains(army_list.id.as_str())
-                        {
-                            info!("    Skipping duplicate army list: {}", army_list.id);
-                            continue;
-                        }
+        if !cancelled {
+            self.advance_cursor(name, &high_water);
+        }
 
-                        info!(
-                            "    Stored army list for #{} {} ({} chars, {} units)",
-                            raw_list.placement_rank,
-                            raw_list.player_name,
-                            raw_list.text.len(),
-                            army_list.units.len()
-                        );
-                        stored_lists.push(army_list);
-                    }
+        Ok(SyncResult {
+            events_synced: total_events,
+            placements_synced: total_placements,
+            lists_normalized: total_lists,
+            items_for_review: 0,
+            errors,
+            duration: start.elapsed(),
+        })
+    }
 
-                    // 7. Link placements to lists by player name
-                    let name_to_list_id: std::collections::HashMap<
-                        String,
-                        crate::models::ArmyListId,
-                    > = stored_lists
-                        .iter()
-                        .filter_map(|l| {
-                            l.player_name
-                                .as_ref()
-                                .map(|name| (normalize_player_name(name), l.id.clone()))
-                        })
-                        .collect();
+    /// Fetch and store T3 standings and published army lists for one event.
+    ///
+    /// Lists are parsed with the BCP regex parser first (T3 lists are
+    /// mostly app exports in the same format) and fall back to the AI
+    /// normalizer. Returns (placements_count, lists_count).
+    async fn sync_t3_standings(
+        &self,
+        client: &t3::T3Client,
+        t3_event: &t3::T3Event,
+        event_id: &crate::models::EventId,
+        epoch_id: Option<crate::models::EntityId>,
+        epoch_str: &str,
+    ) -> Result<(u32, u32), SyncError> {
+        let standings = client
+            .fetch_standings(t3_event)
+            .await
+            .map_err(SyncError::Fetch)?;
+        if standings.is_empty() {
+            info!("T3: no results for {} ({})", t3_event.name, t3_event.id);
+            return Ok((0, 0));
+        }
 
-                    for placement in &mut buffered_placements {
-                        if placement.list_id.is_none() {
-                            let norm_name = normalize_player_name(&placement.player_name);
-                            if let Some(list_id) = name_to_list_id.get(&norm_name) {
-                                placement.list_id = Some(list_id.clone());
+        let existing_placements: Vec<Placement> = if self.config.dry_run {
+            Vec::new()
+        } else {
+            crate::storage::JsonlReader::for_entity(
+                &self.config.storage,
+                EntityType::Placement,
+                epoch_str,
+            )
+            .read_all()
+            .unwrap_or_default()
+        };
+        let existing_ids: std::collections::HashSet<&str> =
+            existing_placements.iter().map(|p| p.id.as_str()).collect();
+        let existing_lists: std::collections::HashSet<String> = if self.config.dry_run {
+            std::collections::HashSet::new()
+        } else {
+            crate::storage::JsonlReader::<ArmyList>::for_entity(
+                &self.config.storage,
+                EntityType::ArmyList,
+                epoch_str,
+            )
+            .read_all()
+            .unwrap_or_default()
+            .iter()
+            .map(|l| l.id.as_str().to_string())
+            .collect()
+        };
+
+        let mut new_placements: Vec<Placement> = Vec::new();
+        let mut new_lists: Vec<ArmyList> = Vec::new();
+        for standing in &standings {
+            if *self.cancel_token.read().await {
+                break;
+            }
+            let mut placement =
+                convert::placement_from_t3(standing, event_id.clone(), epoch_id.clone());
+            if existing_ids.contains(placement.id.as_str()) {
+                continue;
+            }
+
+            if let Some(list_url) = &standing.list_url {
+                match client.fetch_army_list(list_url).await {
+                    Ok(Some(raw_text)) => {
+                        if let Some(list) = self
+                            .normalize_t3_list(raw_text, standing, t3_event, event_id, list_url)
+                            .await
+                        {
+                            placement.list_id = Some(list.id.clone());
+                            if placement.detachment.is_none() {
+                                placement.detachment = list.detachment.clone();
+                            }
+                            if placement.faction == "Unknown" {
+                                placement.faction = list.faction.clone();
+                            }
+                            if !existing_lists.contains(list.id.as_str()) {
+                                new_lists.push(list);
                             }
                         }
                     }
+                    Ok(None) => {}
+                    Err(e) => warn!(
+                        "  T3: failed to fetch list for {}: {}",
+                        standing.player_name, e
+                    ),
+                }
+            }
+            new_placements.push(placement);
+        }
 
-                    // 8. Store placements and lists
-                    if !self.config.dry_run {
-                        let placement_writer = JsonlWriter::for_entity(
-                            &self.config.storage,
-                            EntityType::Placement,
-                            &epoch_str,
-                        );
-                        for placement in &buffered_placements {
-                            placement_writer
-                                .append(placement)
-                                .map_err(SyncError::Storage)?;
-                        }
+        let counts = (new_placements.len() as u32, new_lists.len() as u32);
+        if !self.config.dry_run {
+            if !new_lists.is_empty() {
+                JsonlWriter::for_source(
+                    &self.config.storage,
+                    EntityType::ArmyList,
+                    epoch_str,
+                    T3_SOURCE,
+                )
+                .append_batch(&new_lists)
+                .map_err(SyncError::Storage)?;
+            }
+            if !new_placements.is_empty() {
+                JsonlWriter::for_source(
+                    &self.config.storage,
+                    EntityType::Placement,
+                    epoch_str,
+                    T3_SOURCE,
+                )
+                .append_batch(&new_placements)
+                .map_err(SyncError::Storage)?;
+            }
+        }
+        info!(
+            "  T3: {} placements, {} lists for {}",
+            counts.0, counts.1, t3_event.name
+        );
+        Ok(counts)
+    }
 
-                        let list_writer = JsonlWriter::for_entity(
-                            &self.config.storage,
-                            EntityType::ArmyList,
-                            &epoch_str,
-                        );
-                        for army_list in &stored_lists {
-                            list_writer.append(army_list).map_err(SyncError::Storage)?;
-                        }
+    /// Turn a T3 list page's text into an ArmyList, or None if it is too
+    /// short to be a real list or normalization fails.
+    async fn normalize_t3_list(
+        &self,
+        raw_text: String,
+        standing: &t3::T3Standing,
+        t3_event: &t3::T3Event,
+        event_id: &crate::models::EventId,
+        list_url: &str,
+    ) -> Option<ArmyList> {
+        if raw_text.trim().len() < 50 {
+            return None;
+        }
+        let faction_hint = standing.faction.clone();
+        let regex_units = parse_units_from_raw_text(&raw_text);
+
+        let mut list = if !regex_units.is_empty() {
+            let total_pts: u32 = regex_units.iter().filter_map(|u| u.points).sum();
+            let faction = faction_hint.unwrap_or_else(|| "Unknown".to_string());
+            ArmyList::new(faction, total_pts, regex_units, raw_text)
+                .with_confidence(crate::models::Confidence::High)
+        } else {
+            let normalizer = ListNormalizerAgent::new(self.backend.clone());
+            let input = ListNormalizerInput {
+                raw_text: raw_text.clone(),
+                faction_hint,
+                player_name: standing.player_name.clone(),
+            };
+            match execute_with_retry(&normalizer, input).await {
+                Ok(output) => {
+                    let model = output.list.model;
+                    let agent_version = output.list.agent_version;
+                    let d = output.list.data;
+                    let mut list = ArmyList::new(d.faction, d.total_points, d.units, raw_text)
+                        .with_confidence(output.list.confidence);
+                    if let Some(det) = d.detachment {
+                        list = list.with_detachment(det);
                     }
-                    total_placements += buffered_placements.len() as u32;
-
-                    info!(
-                        "    {} placements, {} lists",
-                        buffered_placements.len(),
-                        list_count
-                    );
+                    if let Some(sub) = d.subfaction {
+                        list = list.with_subfaction(sub);
+                    }
+                    list.extracted_by = model;
+                    list.extraction_agent_version = agent_version;
+                    list
                 }
                 Err(e) => {
-                    warn!("Result Harvester error for {}: {}", event.name, e);
+                    warn!(
+                        "    T3 list normalization failed for {}: {}",
+                        standing.player_name, e
+                    );
+                    return None;
                 }
             }
-        }
+        };
 
-        Ok((total_events, total_placements, total_lists))
+        list = list
+            .with_player_name(standing.player_name.clone())
+            .with_event_id(event_id.clone())
+            .with_source_url(list_url.to_string());
+        if let Some(date) = t3_event.date {
+            list = list.with_event_date(date);
+        }
+        Some(list)
     }
 
     /// Fetch and store BCP standings (placements + optional army lists) for one event.
@@ -1459,6 +3068,7 @@ impl SyncOrchestrator {
     /// Also persists pairings to pairings.jsonl.
     ///
     /// Returns (placements_count, lists_count).
+    #[allow(clippy::too_many_arguments)]
     async fn sync_bcp_standings(
         &self,
         bcp_client: &bcp::BcpClient,
@@ -1466,6 +3076,7 @@ impl SyncOrchestrator {
         event_id: &crate::models::EventId,
         epoch_id: Option<crate::models::EntityId>,
         epoch_str: &str,
+        split_brackets: bool,
     ) -> Result<(u32, u32), SyncError> {
         // Fetch players and pairings separately (instead of fetch_standings)
         // so we can persist pairings
@@ -1478,13 +3089,117 @@ impl SyncOrchestrator {
             .await
             .map_err(SyncError::Fetch)?;
 
+        let detected = if split_brackets {
+            brackets::detect_brackets(&bcp_pairings)
+        } else {
+            Vec::new()
+        };
+        if detected.is_empty() {
+            let (placements, lists, stored_lists) = self
+                .store_bcp_results(
+                    bcp_client,
+                    bcp_event,
+                    event_id,
+                    epoch_id,
+                    epoch_str,
+                    &players,
+                    &bcp_pairings,
+                )
+                .await?;
+            self.record_game_size(event_id, epoch_str, &stored_lists);
+            return Ok((placements, lists));
+        }
+
+        // Multi-bracket listing: results go to one child event per bracket so
+        // game sizes aren't mixed in analytics
+        info!(
+            "  BCP: {} has {} brackets, splitting",
+            bcp_event.name,
+            detected.len()
+        );
+        let mut parent = self.link_series(convert::event_from_bcp(bcp_event, epoch_id.clone()));
+        parent.id = event_id.clone();
+        let existing_event_ids: std::collections::HashSet<String> = if !self.config.dry_run {
+            crate::storage::JsonlReader::<crate::models::Event>::for_entity(
+                &self.config.storage,
+                EntityType::Event,
+                epoch_str,
+            )
+            .read_all()
+            .unwrap_or_default()
+            .iter()
+            .map(|e| e.id.as_str().to_string())
+            .collect()
+        } else {
+            std::collections::HashSet::new()
+        };
+
+        let mut placement_count = 0u32;
+        let mut list_count = 0u32;
+        for (index, bracket) in detected.iter().enumerate() {
+            let bracket_id = parent.bracket(index, None).id;
+            let (placements, lists, stored_lists) = self
+                .store_bcp_results(
+                    bcp_client,
+                    bcp_event,
+                    &bracket_id,
+                    epoch_id.clone(),
+                    epoch_str,
+                    &players,
+                    &bracket.pairings(&bcp_pairings),
+                )
+                .await?;
+            placement_count += placements;
+            list_count += lists;
+
+            let points: Vec<u32> = stored_lists.iter().map(|l| l.total_points).collect();
+            let event = parent
+                .bracket(index, brackets::infer_game_size(&points))
+                .with_player_count(bracket.player_ids.len() as u32);
+            info!(
+                "  BCP: bracket {} ({} players, {} placements)",
+                event.name,
+                bracket.player_ids.len(),
+                placements
+            );
+            if !self.config.dry_run && !existing_event_ids.contains(event.id.as_str()) {
+                JsonlWriter::for_source(
+                    &self.config.storage,
+                    EntityType::Event,
+                    epoch_str,
+                    &event.source_name,
+                )
+                .append(&event)
+                .map_err(SyncError::Storage)?;
+            }
+        }
+        Ok((placement_count, list_count))
+    }
+
+    /// Persist pairings, bracket matches, placements and army lists for one
+    /// event. Returns the placement and list counts plus the lists fetched.
+    #[allow(clippy::too_many_arguments)]
+    async fn store_bcp_results(
+        &self,
+        bcp_client: &bcp::BcpClient,
+        bcp_event: &bcp::BcpEvent,
+        event_id: &crate::models::EventId,
+        epoch_id: Option<crate::models::EntityId>,
+        epoch_str: &str,
+        players: &[bcp::BcpPlayerV1],
+        bcp_pairings: &[bcp::BcpPairing],
+    ) -> Result<(u32, u32, Vec<ArmyList>), SyncError> {
         // Persist pairings
         if !bcp_pairings.is_empty() && !self.config.dry_run {
             let model_pairings =
-                convert::pairings_from_bcp(&bcp_pairings, event_id, epoch_id.clone());
+                convert::pairings_from_bcp(bcp_pairings, event_id, epoch_id.clone());
             if !model_pairings.is_empty() {
-                let pairing_writer =
-                    JsonlWriter::for_entity(&self.config.storage, EntityType::Pairing, epoch_str);
+                let pairing_writer = JsonlWriter::for_source(
+                    &self.config.storage,
+                    EntityType::Pairing,
+                    epoch_str,
+                    BCP_SOURCE,
+                );
                 pairing_writer
                     .append_batch(&model_pairings)
                     .map_err(SyncError::Storage)?;
@@ -1496,15 +3211,41 @@ impl SyncOrchestrator {
             }
         }
 
-        // Compute standings from pairings
+        // Persist top-cut bracket, if the event had one
+        let cut_rounds = bcp::detect_cut_rounds(bcp_pairings, bcp_event.round_count);
+        if !cut_rounds.is_empty() && !self.config.dry_run {
+            let matches = convert::bracket_matches_from_bcp(
+                bcp_pairings,
+                &cut_rounds,
+                event_id,
+                epoch_id.clone(),
+            );
+            if !matches.is_empty() {
+                JsonlWriter::for_source(
+                    &self.config.storage,
+                    EntityType::BracketMatch,
+                    epoch_str,
+                    BCP_SOURCE,
+                )
+                .append_batch(&matches)
+                .map_err(SyncError::Storage)?;
+                info!(
+                    "  BCP: persisted {} bracket matches for {}",
+                    matches.len(),
+                    bcp_event.name
+                );
+            }
+        }
+
+        // Compute standings from pairings (Swiss record, bracket order)
         let standings = if bcp_pairings.is_empty() {
             info!(
                 "BCP: no pairings for event {} ({}), skipping",
                 bcp_event.name, bcp_event.id
             );
-            return Ok((0, 0));
+            return Ok((0, 0, Vec::new()));
         } else {
-            bcp_client.compute_standings(&bcp_pairings, &players)
+            bcp::standings_with_cut(bcp_pairings, players, bcp_event.round_count)
         };
 
         let event_date = bcp_event
@@ -1589,8 +3330,8 @@ impl SyncOrchestrator {
             );
         }
 
-        // Load existing army list IDs to avoid writing duplicates during backfill
-        let existing_bcp_list_ids: std::collections::HashSet<String> = if !self.config.dry_run {
+        // Load existing army lists to avoid writing duplicates during backfill
+        let existing_lists: Vec<ArmyList> = if !self.config.dry_run {
             crate::storage::JsonlReader::<ArmyList>::for_entity(
                 &self.config.storage,
                 EntityType::ArmyList,
@@ -1598,12 +3339,18 @@ impl SyncOrchestrator {
             )
             .read_all()
             .unwrap_or_default()
-            .iter()
-            .map(|l| l.id.as_str().to_string())
-            .collect()
         } else {
-            std::collections::HashSet::new()
+            Vec::new()
         };
+        let existing_bcp_list_ids: std::collections::HashSet<String> = existing_lists
+            .iter()
+            .map(|l| l.id.as_str().to_string())
+            .collect();
+        // Lists already stored for this event, for clone detection
+        let existing_event_lists: Vec<&ArmyList> = existing_lists
+            .iter()
+            .filter(|l| l.event_id.as_ref() == Some(event_id))
+            .collect();
 
         // Fetch army lists from Listhammer (no rate limiting needed)
         // Track player→chapter for post-fix of placement factions
@@ -1667,8 +3414,10 @@ impl SyncOrchestrator {
                 .or_else(|| standing.faction.clone());
 
             // Try regex parsing first (free), fall back to AI only if regex finds nothing
-            let regex_units = bcp::parse_units_from_raw_text(&raw_text);
+            let regex_units = parse_units_from_raw_text(&raw_text);
 
+            let mut norm_model = None;
+            let mut norm_agent_version = None;
             let (
                 norm_faction,
                 norm_detachment,
@@ -1688,7 +3437,7 @@ impl SyncOrchestrator {
                     || resolved_faction == "Space Marines"
                     || resolved_faction == "Adeptus Astartes";
                 if is_generic_sm {
-                    if let Some(chapter) = bcp::detect_chapter_from_raw_text(&raw_text) {
+                    if let Some(chapter) = detect_chapter_from_raw_text(&raw_text) {
                         info!("    Chapter detected: {} -> {}", resolved_faction, chapter);
                         resolved_faction = chapter.to_string();
                         player_chapter_fixes
@@ -1738,8 +3487,10 @@ impl SyncOrchestrator {
                     player_name: player_name.clone(),
                 };
 
-                match normalizer.execute(norm_input).await {
+                match execute_with_retry(&normalizer, norm_input).await {
                     Ok(output) => {
+                        norm_model = output.list.model;
+                        norm_agent_version = output.list.agent_version;
                         let d = output.list.data;
                         info!(
                             "    Normalized BCP list (AI): {} - {} ({} units, {}pts)",
@@ -1787,10 +3538,30 @@ impl SyncOrchestrator {
             if let Some(sub) = norm_subfaction {
                 army_list = army_list.with_subfaction(sub);
             }
+            army_list.extracted_by = norm_model;
+            army_list.extraction_agent_version = norm_agent_version;
+
+            army_list.clone_of = clones::find_clone_source(
+                &army_list,
+                existing_event_lists
+                    .iter()
+                    .copied()
+                    .chain(stored_lists.iter()),
+            );
+            if let Some(ref original) = army_list.clone_of {
+                info!(
+                    "    List for {} is a clone of {}",
+                    player_name_str, original
+                );
+            }
 
             if !self.config.dry_run && !existing_bcp_list_ids.contains(army_list.id.as_str()) {
-                let writer =
-                    JsonlWriter::for_entity(&self.config.storage, EntityType::ArmyList, epoch_str);
+                let writer = JsonlWriter::for_source(
+                    &self.config.storage,
+                    EntityType::ArmyList,
+                    epoch_str,
+                    BCP_SOURCE,
+                );
                 writer.append(&army_list).map_err(SyncError::Storage)?;
                 list_count += 1;
             }
@@ -1839,8 +3610,12 @@ impl SyncOrchestrator {
         // Write new placements and update existing ones with newly-fetched lists
         if !self.config.dry_run {
             if !new_placements.is_empty() {
-                let writer =
-                    JsonlWriter::for_entity(&self.config.storage, EntityType::Placement, epoch_str);
+                let writer = JsonlWriter::for_source(
+                    &self.config.storage,
+                    EntityType::Placement,
+                    epoch_str,
+                    BCP_SOURCE,
+                );
                 writer
                     .append_batch(&new_placements)
                     .map_err(SyncError::Storage)?;
@@ -1868,6 +3643,7 @@ impl SyncOrchestrator {
                                     p.detachment = Some(det.clone());
                                 }
                             }
+                            p.touch();
                             backfill_count += 1;
                         }
                     }
@@ -1908,7 +3684,7 @@ impl SyncOrchestrator {
             Vec::new(),
         );
 
-        Ok((placement_count, list_count))
+        Ok((placement_count, list_count, stored_lists))
     }
 
     /// Run periodic sync in the background.
@@ -1972,11 +3748,14 @@ mod tests {
 
     fn test_config(temp_dir: &TempDir) -> SyncConfig {
         SyncConfig {
-            sources: vec![SyncSource::default()],
+            sources: vec![SyncSource::default().into_source()],
             interval: Duration::from_secs(60),
             date_from: None,
             date_to: None,
             dry_run: true,
+            full: false,
+            upcoming: false,
+            grace_days: 0,
             storage: StorageConfig::new(temp_dir.path().to_path_buf()),
         }
     }
@@ -2047,6 +3826,7 @@ mod tests {
         let source = SyncSource::Bcp {
             api_base_url: "https://newprod-api.bestcoastpairings.com/v1".to_string(),
             game_type: 1,
+            split_brackets: false,
         };
 
         let json = serde_json::to_string(&source).unwrap();
@@ -2057,6 +3837,7 @@ mod tests {
             SyncSource::Bcp {
                 api_base_url,
                 game_type,
+                ..
             } => {
                 assert!(api_base_url.contains("bestcoastpairings"));
                 assert_eq!(game_type, 1);
@@ -2145,6 +3926,71 @@ mod tests {
         assert!(result.is_ok() || matches!(result, Err(SyncError::Cancelled)));
     }
 
+    #[tokio::test]
+    async fn test_active_syncs_refuse_after_shutdown() {
+        let temp_dir = TempDir::new().unwrap();
+        let fetcher = Fetcher::new(FetcherConfig {
+            cache_dir: temp_dir.path().join("cache"),
+            ..Default::default()
+        })
+        .unwrap();
+        let backend: Arc<dyn AiBackend> = Arc::new(MockBackend::new("{}"));
+        let orchestrator = SyncOrchestrator::new(test_config(&temp_dir), fetcher, backend);
+
+        let syncs = ActiveSyncs::default();
+        assert!(syncs.shutdown(Duration::from_millis(10)).await);
+        assert!(syncs.is_shutting_down());
+        let result = syncs.run(orchestrator).await;
+        assert!(matches!(result, Err(SyncError::Cancelled)));
+        assert!(syncs.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_reprocess_reads_cache_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = test_config(&temp_dir);
+        config.dry_run = false;
+        let fetcher = Fetcher::new(FetcherConfig {
+            cache_dir: temp_dir.path().join("raw"),
+            ..Default::default()
+        })
+        .unwrap();
+        let backend: Arc<dyn AiBackend> = Arc::new(MockBackend::new("{}"));
+        let orchestrator = SyncOrchestrator::new(config, fetcher, backend);
+        let article =
+            Url::parse("https://www.goonhammer.com/competitive-innovations-march/").unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+
+        // Nothing cached: no fetch is attempted and the failure is recorded
+        let result = orchestrator
+            .reprocess_article(&article, date, None, false)
+            .await;
+        assert!(matches!(result, Err(SyncError::NotCached(_))));
+        let issues = issues::read_issues(&orchestrator.config.storage).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].stage, IngestStage::Extract);
+        assert_eq!(issues[0].source_url, article.as_str());
+
+        // A cached WP API response by slug is found whatever its age
+        let api_url = Url::parse(
+            "https://www.goonhammer.com/wp-json/wp/v2/posts?slug=competitive-innovations-march",
+        )
+        .unwrap();
+        let path = orchestrator.fetcher.cache_path_for_url(&api_url);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            r#"[{"date":"2026-03-01T09:00:00","content":{"rendered":"<p>GT results</p>"}}]"#,
+        )
+        .unwrap();
+        let (html, published) = orchestrator
+            .cached_article_content(&article, None)
+            .await
+            .unwrap();
+        assert_eq!(html, "<p>GT results</p>");
+        assert_eq!(published, NaiveDate::from_ymd_opt(2026, 3, 1));
+    }
+
     #[tokio::test]
     async fn test_orchestrator_is_running() {
         let temp_dir = TempDir::new().unwrap();
@@ -2159,4 +4005,27 @@ mod tests {
         let orchestrator = SyncOrchestrator::new(config, fetcher, backend);
         assert!(!orchestrator.is_running().await);
     }
+
+    #[tokio::test]
+    async fn test_link_series() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = test_config(&temp_dir);
+        let fetcher = Fetcher::new(FetcherConfig {
+            cache_dir: temp_dir.path().join("cache"),
+            ..Default::default()
+        })
+        .unwrap();
+        let backend: Arc<dyn AiBackend> = Arc::new(MockBackend::new("{}"));
+        let orchestrator = SyncOrchestrator::new(config, fetcher, backend);
+
+        let event = crate::models::Event::new(
+            "LVO 2026".to_string(),
+            NaiveDate::from_ymd_opt(2026, 1, 30).unwrap(),
+            "https://example.com".to_string(),
+            "bcp".to_string(),
+            "current".into(),
+        );
+        let event = orchestrator.link_series(event);
+        assert_eq!(event.series.as_deref(), Some("Las Vegas Open"));
+    }
 }
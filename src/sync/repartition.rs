@@ -5,12 +5,15 @@
 
 use std::collections::HashMap;
 
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use tracing::info;
 
-use crate::api::dedup_by_id;
-use crate::models::{ArmyList, EpochMapper, Event, Placement};
+use crate::api::{dedup_by_id, merge_by_id};
+use crate::models::{ArmyList, Event, Placement};
 use crate::storage::{
-    read_significant_events, EntityType, JsonlReader, JsonlWriter, StorageConfig,
+    cached_epoch_mapper, cached_significant_events, EntityType, JsonlReader, JsonlWriter,
+    StorageConfig,
 };
 
 /// Result of a repartition operation.
@@ -31,13 +34,13 @@ pub fn repartition(
     keep_originals: bool,
 ) -> anyhow::Result<RepartitionResult> {
     // 1. Read significant events and build mapper
-    let sig_events = read_significant_events(storage)?;
-    if sig_events.is_empty() {
+    let sig_events = cached_significant_events(storage)?;
+    if !sig_events.iter().any(|e| e.event_type.is_epoch_boundary()) {
         anyhow::bail!(
             "No significant events found. Register balance passes first with `add-balance-pass`."
         );
     }
-    let mapper = EpochMapper::from_significant_events(&sig_events);
+    let mapper = cached_epoch_mapper(storage);
 
     info!(
         "Built epoch mapper with {} epochs from {} significant events",
@@ -47,15 +50,15 @@ pub fn repartition(
 
     // 2. Read all entities from source
     let event_reader = JsonlReader::<Event>::for_entity(storage, EntityType::Event, source_epoch);
-    let events = dedup_by_id(event_reader.read_all()?, |e| e.id.as_str());
+    let events = merge_by_id(event_reader.read_all()?, |e| e.id.as_str());
 
     let placement_reader =
         JsonlReader::<Placement>::for_entity(storage, EntityType::Placement, source_epoch);
-    let placements = dedup_by_id(placement_reader.read_all()?, |p| p.id.as_str());
+    let placements = merge_by_id(placement_reader.read_all()?, |p| p.id.as_str());
 
     let list_reader =
         JsonlReader::<ArmyList>::for_entity(storage, EntityType::ArmyList, source_epoch);
-    let lists = dedup_by_id(list_reader.read_all()?, |l| l.id.as_str());
+    let lists = merge_by_id(list_reader.read_all()?, |l| l.id.as_str());
 
     info!(
         "Read {} events, {} placements, {} lists from '{}'",
@@ -150,46 +153,48 @@ pub fn repartition(
         );
     }
 
-    // 7. Write (unless dry run)
+    // 7. Write (unless dry run), merging into whatever the target epochs
+    // already hold so repeated runs never drop data
     if !dry_run {
         for epoch_id in &all_epoch_ids {
             if let Some(evts) = events_by_epoch.get(epoch_id) {
-                let writer = JsonlWriter::<Event>::for_entity(storage, EntityType::Event, epoch_id);
-                writer.write_all(evts)?;
+                merge_into_epoch(storage, EntityType::Event, epoch_id, evts, |e| {
+                    e.id.as_str()
+                })?;
             }
             if let Some(plcs) = placements_by_epoch.get(epoch_id) {
-                let writer =
-                    JsonlWriter::<Placement>::for_entity(storage, EntityType::Placement, epoch_id);
-                writer.write_all(plcs)?;
+                merge_into_epoch(storage, EntityType::Placement, epoch_id, plcs, |p| {
+                    p.id.as_str()
+                })?;
             }
             if let Some(lsts) = lists_by_epoch.get(epoch_id) {
-                let writer =
-                    JsonlWriter::<ArmyList>::for_entity(storage, EntityType::ArmyList, epoch_id);
-                writer.write_all(lsts)?;
+                merge_into_epoch(storage, EntityType::ArmyList, epoch_id, lsts, |l| {
+                    l.id.as_str()
+                })?;
             }
         }
 
-        // Back up original directory (rename to source_epoch.bak)
+        // Back up original directory (rename to source_epoch.bak, or a
+        // timestamped name when an earlier backup exists)
         if !keep_originals {
             let src_dir = storage.normalized_dir().join(source_epoch);
-            let bak_dir = storage
-                .normalized_dir()
-                .join(format!("{}.bak", source_epoch));
             if src_dir.exists() {
-                // Don't overwrite an existing backup
-                if !bak_dir.exists() {
-                    std::fs::rename(&src_dir, &bak_dir)?;
-                    info!(
-                        "Backed up '{}' -> '{}'",
-                        src_dir.display(),
-                        bak_dir.display()
-                    );
-                } else {
-                    info!(
-                        "Backup already exists at '{}', skipping rename",
-                        bak_dir.display()
-                    );
+                let mut bak_dir = storage
+                    .normalized_dir()
+                    .join(format!("{}.bak", source_epoch));
+                if bak_dir.exists() {
+                    bak_dir = storage.normalized_dir().join(format!(
+                        "{}.bak.{}",
+                        source_epoch,
+                        chrono::Utc::now().format("%Y%m%d%H%M%S")
+                    ));
                 }
+                std::fs::rename(&src_dir, &bak_dir)?;
+                info!(
+                    "Backed up '{}' -> '{}'",
+                    src_dir.display(),
+                    bak_dir.display()
+                );
             }
         }
 
@@ -202,6 +207,24 @@ pub fn repartition(
     Ok(result)
 }
 
+/// Write `records` into an epoch, keeping existing records whose IDs aren't
+/// being replaced.
+fn merge_into_epoch<T>(
+    storage: &StorageConfig,
+    entity: EntityType,
+    epoch_id: &str,
+    records: &[T],
+    id: impl Fn(&T) -> &str,
+) -> anyhow::Result<()>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    let existing = JsonlReader::<T>::for_entity(storage, entity, epoch_id).read_all()?;
+    let merged = dedup_by_id(records.iter().cloned().chain(existing).collect(), id);
+    JsonlWriter::<T>::for_entity(storage, entity, epoch_id).write_all(&merged)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -383,4 +406,46 @@ mod tests {
         assert!(!event_epochs.is_empty());
         assert!(!placement_epochs.is_empty());
     }
+
+    #[test]
+    fn test_repartition_merges_into_existing_epoch() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = test_storage(&temp_dir);
+
+        let mut sig_events = vec![make_sig_event(
+            NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            "June Update",
+        )];
+        write_significant_events(&storage, &mut sig_events).unwrap();
+
+        let first = make_event(
+            "First GT",
+            NaiveDate::from_ymd_opt(2025, 7, 1).unwrap(),
+            "https://example.com/first",
+        );
+        JsonlWriter::<Event>::for_entity(&storage, EntityType::Event, "current")
+            .write_all(&[first])
+            .unwrap();
+        repartition(&storage, "current", false, false).unwrap();
+
+        // A later sync lands more data in "current"; repartitioning again
+        // must keep what the epoch already holds
+        let second = make_event(
+            "Second GT",
+            NaiveDate::from_ymd_opt(2025, 7, 8).unwrap(),
+            "https://example.com/second",
+        );
+        JsonlWriter::<Event>::for_entity(&storage, EntityType::Event, "current")
+            .write_all(&[second])
+            .unwrap();
+        let result = repartition(&storage, "current", false, false).unwrap();
+
+        let epoch_id = result.events_by_epoch.keys().next().unwrap().clone();
+        let events = JsonlReader::<Event>::for_entity(&storage, EntityType::Event, &epoch_id)
+            .read_all()
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        // Source moved aside both times
+        assert!(!storage.normalized_dir().join("current").exists());
+    }
 }
@@ -0,0 +1,222 @@
+//! Finding and clearing records extracted by outdated agents.
+//!
+//! Every AI-extracted event, placement and list records the version of the
+//! agent that produced it. After a prompt change bumps an agent's version,
+//! [`stale_articles`] finds the Goonhammer articles with records from older
+//! versions, and [`clear_article`] removes an article's records so
+//! `meta-agent reprocess --older-than-version` can extract it afresh.
+
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::NaiveDate;
+
+use super::GOONHAMMER_SOURCE;
+use crate::models::{ArmyList, Event, Placement};
+use crate::storage::jsonl::{entity_paths, list_epochs};
+use crate::storage::{EntityType, JsonlReader, JsonlWriter, StorageConfig, StorageError};
+
+/// An article with records extracted by an outdated agent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaleArticle {
+    pub url: String,
+    /// Earliest date of its events
+    pub date: NaiveDate,
+    /// Stale records from the article
+    pub events: usize,
+    pub placements: usize,
+    pub lists: usize,
+}
+
+/// Whether an AI-extracted record predates `min_version`. Records parsed
+/// without AI are never stale.
+pub fn is_stale(extracted_by: Option<&str>, agent_version: Option<u32>, min_version: u32) -> bool {
+    extracted_by.is_some() && agent_version.unwrap_or(0) < min_version
+}
+
+/// Goonhammer articles with any event, placement or list extracted by an
+/// agent older than `min_version`, oldest first.
+pub fn stale_articles(
+    storage: &StorageConfig,
+    min_version: u32,
+) -> Result<Vec<StaleArticle>, StorageError> {
+    let mut articles: BTreeMap<String, StaleArticle> = BTreeMap::new();
+    for epoch in list_epochs(storage)? {
+        let events: Vec<Event> =
+            JsonlReader::for_entity(storage, EntityType::Event, &epoch).read_all()?;
+        let placements: Vec<Placement> =
+            JsonlReader::for_entity(storage, EntityType::Placement, &epoch).read_all()?;
+        let lists: Vec<ArmyList> =
+            JsonlReader::for_entity(storage, EntityType::ArmyList, &epoch).read_all()?;
+
+        for event in events.iter().filter(|e| e.source_name == GOONHAMMER_SOURCE) {
+            let stale_event = is_stale(
+                event.extracted_by.as_deref(),
+                event.extraction_agent_version,
+                min_version,
+            );
+            let stale_placements = placements
+                .iter()
+                .filter(|p| p.event_id == event.id)
+                .filter(|p| {
+                    is_stale(
+                        p.extracted_by.as_deref(),
+                        p.extraction_agent_version,
+                        min_version,
+                    )
+                })
+                .count();
+            let stale_lists = lists
+                .iter()
+                .filter(|l| l.event_id.as_ref() == Some(&event.id))
+                .filter(|l| {
+                    is_stale(
+                        l.extracted_by.as_deref(),
+                        l.extraction_agent_version,
+                        min_version,
+                    )
+                })
+                .count();
+            if !stale_event && stale_placements == 0 && stale_lists == 0 {
+                continue;
+            }
+
+            let article =
+                articles
+                    .entry(event.source_url.clone())
+                    .or_insert_with(|| StaleArticle {
+                        url: event.source_url.clone(),
+                        date: event.date,
+                        events: 0,
+                        placements: 0,
+                        lists: 0,
+                    });
+            article.date = article.date.min(event.date);
+            article.events += usize::from(stale_event);
+            article.placements += stale_placements;
+            article.lists += stale_lists;
+        }
+    }
+
+    let mut articles: Vec<StaleArticle> = articles.into_values().collect();
+    articles.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.url.cmp(&b.url)));
+    Ok(articles)
+}
+
+/// Remove the events extracted from an article, with their placements and
+/// lists, from every epoch and source partition. Returns records removed.
+pub fn clear_article(storage: &StorageConfig, article_url: &str) -> Result<usize, StorageError> {
+    let mut removed = 0;
+    for epoch in list_epochs(storage)? {
+        let events: Vec<Event> =
+            JsonlReader::for_entity(storage, EntityType::Event, &epoch).read_all()?;
+        let event_ids: HashSet<String> = events
+            .iter()
+            .filter(|e| e.source_name == GOONHAMMER_SOURCE && e.source_url == article_url)
+            .map(|e| e.id.as_str().to_string())
+            .collect();
+
+        removed += retain_in_files::<Event>(storage, EntityType::Event, &epoch, |e| {
+            !event_ids.contains(e.id.as_str())
+        })?;
+        removed += retain_in_files::<Placement>(storage, EntityType::Placement, &epoch, |p| {
+            !event_ids.contains(p.event_id.as_str())
+        })?;
+        removed += retain_in_files::<ArmyList>(storage, EntityType::ArmyList, &epoch, |l| {
+            !l.event_id
+                .as_ref()
+                .is_some_and(|id| event_ids.contains(id.as_str()))
+                && l.source_url.as_deref() != Some(article_url)
+        })?;
+    }
+    Ok(removed)
+}
+
+/// Keep only matching records in each of an entity's files, rewriting only
+/// files that change. Returns records removed.
+fn retain_in_files<T>(
+    storage: &StorageConfig,
+    entity: EntityType,
+    epoch_id: &str,
+    keep: impl Fn(&T) -> bool,
+) -> Result<usize, StorageError>
+where
+    T: serde::de::DeserializeOwned + serde::Serialize,
+{
+    let mut removed = 0;
+    for path in entity_paths(storage, entity, epoch_id) {
+        let records: Vec<T> = JsonlReader::new(path.clone()).read_all()?;
+        let before = records.len();
+        let kept: Vec<T> = records.into_iter().filter(|r| keep(r)).collect();
+        if kept.len() < before {
+            removed += before - kept.len();
+            JsonlWriter::new(path).write_all(&kept)?;
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EntityId;
+
+    #[test]
+    fn test_stale_articles_and_clear() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        let date = NaiveDate::from_ymd_opt(2026, 2, 7).unwrap();
+        let event = |name: &str, url: &str, version: Option<u32>| {
+            let mut e = Event::new(
+                name.to_string(),
+                date,
+                url.to_string(),
+                GOONHAMMER_SOURCE.to_string(),
+                "current".into(),
+            );
+            e.extracted_by = Some("llama3.2:latest".to_string());
+            e.extraction_agent_version = version;
+            e
+        };
+        let old = event("Old GT", "https://gh/old", None);
+        let new = event("New GT", "https://gh/new", Some(2));
+        let mut placement = Placement::new(
+            new.id.clone(),
+            EntityId::from("current"),
+            1,
+            "Alice".to_string(),
+            "Aeldari".to_string(),
+        );
+        placement.extracted_by = Some("llama3.2:latest".to_string());
+        placement.extraction_agent_version = Some(1);
+        let kept = Placement::new(
+            old.id.clone(),
+            EntityId::from("current"),
+            2,
+            "Bob".to_string(),
+            "Orks".to_string(),
+        );
+
+        JsonlWriter::for_entity(&storage, EntityType::Event, "current")
+            .write_all(&[old.clone(), new.clone()])
+            .unwrap();
+        JsonlWriter::for_entity(&storage, EntityType::Placement, "current")
+            .write_all(&[placement, kept])
+            .unwrap();
+
+        assert!(stale_articles(&storage, 1)
+            .unwrap()
+            .iter()
+            .all(|a| a.url == "https://gh/old"));
+        let stale = stale_articles(&storage, 2).unwrap();
+        assert_eq!(stale.len(), 2);
+        let new_article = stale.iter().find(|a| a.url == "https://gh/new").unwrap();
+        assert_eq!((new_article.events, new_article.placements), (0, 1));
+
+        assert_eq!(clear_article(&storage, "https://gh/old").unwrap(), 2);
+        let events: Vec<Event> = JsonlReader::for_entity(&storage, EntityType::Event, "current")
+            .read_all()
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, new.id);
+    }
+}
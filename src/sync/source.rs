@@ -0,0 +1,495 @@
+//! Pluggable sync sources.
+//!
+//! A [`Source`] is one tournament platform. The orchestrator drives it
+//! through four stages: `discover` the items published in the sync window,
+//! `fetch` each one, `extract` events with their placements and lists, and
+//! `convert` those into records to store. Adding a platform is a module with
+//! a type implementing the stages, registered by name with
+//! [`register_source`]; `meta-agent sync --source <name>` then accepts it and
+//! `[sources.<name>] enabled = true` adds it to scheduled syncs.
+//!
+//! The built-in sources have flows the stages do not capture (cross-source
+//! dedup, top-cut brackets, list backfill, AI normalization), so they
+//! override [`Source::sync`] and run their own pipelines.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use url::Url;
+
+use super::{convert, SyncError, SyncOrchestrator, SyncResult, SyncSource};
+use crate::agents::backend::AiBackend;
+use crate::agents::event_scout::EventStub;
+use crate::agents::result_harvester::{PlacementStub, RawListText};
+use crate::agents::AgentOutput;
+use crate::config::SourcesConfig;
+use crate::fetch::Fetcher;
+use crate::models::{ArmyList, EntityId, Event, Placement};
+
+/// An item a source found in the sync window, such as an article or a
+/// tournament page.
+#[derive(Debug, Clone)]
+pub struct SourceItem {
+    pub url: Url,
+    /// Publish or event date; moves the source's sync cursor
+    pub date: Option<NaiveDate>,
+    pub title: Option<String>,
+}
+
+/// What a source's stages get to work with.
+pub struct SourceContext<'a> {
+    /// Shared fetcher (raw cache and rate limit)
+    pub fetcher: &'a Fetcher,
+    /// AI backend, for sources that extract with agents
+    pub backend: Arc<dyn AiBackend>,
+    /// Window to discover items in: the source's cursor or `--from`, up to
+    /// `--to` or today
+    pub date_from: NaiveDate,
+    pub date_to: NaiveDate,
+}
+
+/// An event extracted from an item, with its results.
+#[derive(Debug, Clone)]
+pub struct ExtractedEvent {
+    pub event: AgentOutput<EventStub>,
+    pub placements: Vec<AgentOutput<PlacementStub>>,
+    pub lists: Vec<RawListText>,
+}
+
+/// Records converted from an extracted event, ready to store.
+#[derive(Debug, Clone)]
+pub struct Converted {
+    pub event: Event,
+    pub placements: Vec<Placement>,
+    pub lists: Vec<ArmyList>,
+}
+
+/// Downcasting for sources, implemented for every sized type.
+pub trait AsAny {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// A tournament platform the orchestrator can sync from.
+#[async_trait]
+pub trait Source: AsAny + Send + Sync + std::fmt::Debug {
+    /// Registered name: the `--source` value, the sync cursor key and the
+    /// storage partition.
+    fn name(&self) -> &str;
+
+    /// Items published in the context's window.
+    async fn discover(&self, _ctx: &SourceContext<'_>) -> Result<Vec<SourceItem>, SyncError> {
+        Ok(Vec::new())
+    }
+
+    /// Raw content of an item. By default, the item's URL through the
+    /// shared fetcher.
+    async fn fetch(&self, ctx: &SourceContext<'_>, item: &SourceItem) -> Result<String, SyncError> {
+        let fetched = ctx.fetcher.fetch(&item.url).await?;
+        Ok(ctx.fetcher.read_cached_text(&fetched).await?)
+    }
+
+    /// Events, placements and lists in an item's content.
+    async fn extract(
+        &self,
+        _ctx: &SourceContext<'_>,
+        _item: &SourceItem,
+        _raw: &str,
+    ) -> Result<Vec<ExtractedEvent>, SyncError> {
+        Ok(Vec::new())
+    }
+
+    /// Records to store for an extracted event. By default, the stubs are
+    /// converted as for Goonhammer articles and each list is linked to the
+    /// placement with its rank.
+    fn convert(
+        &self,
+        item: &SourceItem,
+        extracted: &ExtractedEvent,
+        epoch_id: Option<EntityId>,
+    ) -> Converted {
+        let item_date = item.date.unwrap_or_else(|| chrono::Utc::now().date_naive());
+        let event = convert::event_from_stub(
+            &extracted.event,
+            item.url.as_str(),
+            item_date,
+            self.name(),
+            epoch_id.clone(),
+        );
+        let mut placements: Vec<Placement> = extracted
+            .placements
+            .iter()
+            .map(|p| convert::placement_from_stub(p, event.id.clone(), epoch_id.clone()))
+            .collect();
+        let lists: Vec<ArmyList> = extracted
+            .lists
+            .iter()
+            .map(|raw| {
+                let faction = placements
+                    .iter()
+                    .find(|p| p.rank == raw.placement_rank)
+                    .map(|p| p.faction.clone())
+                    .unwrap_or_default();
+                ArmyList::new(faction, 0, Vec::new(), raw.text.clone())
+                    .with_player_name(raw.player_name.clone())
+                    .with_event_date(event.date)
+                    .with_event_id(event.id.clone())
+                    .with_source_url(item.url.to_string())
+            })
+            .collect();
+        for placement in &mut placements {
+            if let Some(list) = extracted
+                .lists
+                .iter()
+                .position(|l| l.placement_rank == placement.rank)
+            {
+                placement.list_id = Some(lists[list].id.clone());
+            }
+        }
+        Converted {
+            event,
+            placements,
+            lists,
+        }
+    }
+
+    /// Sync the source: by default, run the stages over the window and store
+    /// new events with their placements and lists.
+    async fn sync(&self, orchestrator: &SyncOrchestrator) -> Result<SyncResult, SyncError> {
+        orchestrator.sync_stages(self).await
+    }
+}
+
+/// Goonhammer Competitive Innovations articles.
+#[derive(Debug, Clone)]
+pub struct GoonhammerSource {
+    pub base_url: String,
+}
+
+#[async_trait]
+impl Source for GoonhammerSource {
+    fn name(&self) -> &str {
+        "goonhammer"
+    }
+
+    async fn sync(&self, orchestrator: &SyncOrchestrator) -> Result<SyncResult, SyncError> {
+        orchestrator
+            .sync_goonhammer(self.name(), &self.base_url)
+            .await
+    }
+}
+
+/// Best Coast Pairings structured data.
+#[derive(Debug, Clone)]
+pub struct BcpSource {
+    pub api_base_url: String,
+    /// Game type ID (1 = Warhammer 40k)
+    pub game_type: u32,
+    /// Split listings that host several game sizes into one event per bracket
+    pub split_brackets: bool,
+}
+
+#[async_trait]
+impl Source for BcpSource {
+    fn name(&self) -> &str {
+        "bcp"
+    }
+
+    async fn sync(&self, orchestrator: &SyncOrchestrator) -> Result<SyncResult, SyncError> {
+        orchestrator
+            .sync_bcp(
+                self.name(),
+                &self.api_base_url,
+                self.game_type,
+                self.split_brackets,
+            )
+            .await
+    }
+}
+
+/// Warhammer Community balance dataslates.
+#[derive(Debug, Clone)]
+pub struct WarhammerCommunitySource {
+    pub url: String,
+}
+
+#[async_trait]
+impl Source for WarhammerCommunitySource {
+    fn name(&self) -> &str {
+        "warhammer-community"
+    }
+
+    async fn sync(&self, orchestrator: &SyncOrchestrator) -> Result<SyncResult, SyncError> {
+        orchestrator.sync_warhammer_community(&self.url).await
+    }
+}
+
+/// Tabletop Tournaments (tabletopturniere.de).
+#[derive(Debug, Clone)]
+pub struct T3Source {
+    pub base_url: String,
+    /// Calendar game system filter (case-insensitive substring)
+    pub game_system: String,
+}
+
+#[async_trait]
+impl Source for T3Source {
+    fn name(&self) -> &str {
+        "t3"
+    }
+
+    async fn sync(&self, orchestrator: &SyncOrchestrator) -> Result<SyncResult, SyncError> {
+        orchestrator
+            .sync_t3(self.name(), &self.base_url, &self.game_system)
+            .await
+    }
+}
+
+impl SyncSource {
+    /// The source this configuration describes.
+    pub fn into_source(self) -> Arc<dyn Source> {
+        match self {
+            SyncSource::Goonhammer { base_url } => Arc::new(GoonhammerSource { base_url }),
+            SyncSource::Bcp {
+                api_base_url,
+                game_type,
+                split_brackets,
+            } => Arc::new(BcpSource {
+                api_base_url,
+                game_type,
+                split_brackets,
+            }),
+            SyncSource::WarhammerCommunity { url } => Arc::new(WarhammerCommunitySource { url }),
+            SyncSource::TabletopTournaments {
+                base_url,
+                game_system,
+            } => Arc::new(T3Source {
+                base_url,
+                game_system,
+            }),
+        }
+    }
+}
+
+/// Builds a source from the `[sources]` config, or None if it is not
+/// configured.
+pub type SourceFactory = Arc<dyn Fn(&SourcesConfig) -> Option<Arc<dyn Source>> + Send + Sync>;
+
+/// Sources by name.
+#[derive(Clone, Default)]
+pub struct SourceRegistry {
+    factories: BTreeMap<String, SourceFactory>,
+}
+
+impl SourceRegistry {
+    /// A registry of the built-in sources.
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        for name in ["goonhammer", "bcp", "warhammer-community", "t3"] {
+            registry.register(name, move |config: &SourcesConfig| {
+                config.by_name(name).map(SyncSource::into_source)
+            });
+        }
+        registry
+    }
+
+    /// Add a source, replacing any registered under the same name.
+    pub fn register(
+        &mut self,
+        name: &str,
+        factory: impl Fn(&SourcesConfig) -> Option<Arc<dyn Source>> + Send + Sync + 'static,
+    ) {
+        self.factories.insert(name.to_string(), Arc::new(factory));
+    }
+
+    /// Registered names, sorted.
+    pub fn names(&self) -> Vec<String> {
+        self.factories.keys().cloned().collect()
+    }
+
+    /// Build a source by name, whether or not it is enabled.
+    pub fn build(&self, name: &str, config: &SourcesConfig) -> Option<Arc<dyn Source>> {
+        self.factories.get(name).and_then(|factory| factory(config))
+    }
+
+    /// Sources enabled in the config.
+    pub fn enabled(&self, config: &SourcesConfig) -> Vec<Arc<dyn Source>> {
+        self.factories
+            .iter()
+            .filter(|(name, _)| config.source_enabled(name))
+            .filter_map(|(_, factory)| factory(config))
+            .collect()
+    }
+}
+
+static REGISTRY: LazyLock<RwLock<SourceRegistry>> =
+    LazyLock::new(|| RwLock::new(SourceRegistry::builtin()));
+
+/// Register a source with the process-wide registry used by the CLI, the
+/// scheduler and the API. Call before running any command.
+pub fn register_source(
+    name: &str,
+    factory: impl Fn(&SourcesConfig) -> Option<Arc<dyn Source>> + Send + Sync + 'static,
+) {
+    REGISTRY
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .register(name, factory);
+}
+
+/// The process-wide registry: built-in sources plus any registered.
+pub fn registry() -> SourceRegistry {
+    REGISTRY.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::backend::MockBackend;
+    use crate::agents::result_harvester::PlacementStub;
+    use crate::fetch::FetcherConfig;
+    use crate::models::Confidence;
+    use crate::storage::{EntityType, JsonlReader, StorageConfig};
+    use crate::sync::cursor::SyncCursor;
+    use crate::sync::SyncConfig;
+
+    /// A platform with one tournament page, served from a fixed string.
+    #[derive(Debug)]
+    struct PageSource;
+
+    #[async_trait]
+    impl Source for PageSource {
+        fn name(&self) -> &str {
+            "example"
+        }
+
+        async fn discover(&self, _ctx: &SourceContext<'_>) -> Result<Vec<SourceItem>, SyncError> {
+            Ok(vec![SourceItem {
+                url: Url::parse("https://example.com/events/1").unwrap(),
+                date: NaiveDate::from_ymd_opt(2026, 3, 14),
+                title: None,
+            }])
+        }
+
+        async fn fetch(
+            &self,
+            _ctx: &SourceContext<'_>,
+            _item: &SourceItem,
+        ) -> Result<String, SyncError> {
+            Ok("Spring GT\n1. Alice - Aeldari\n2. Bob - Orks".to_string())
+        }
+
+        async fn extract(
+            &self,
+            _ctx: &SourceContext<'_>,
+            _item: &SourceItem,
+            raw: &str,
+        ) -> Result<Vec<ExtractedEvent>, SyncError> {
+            let mut lines = raw.lines();
+            let name = lines.next().unwrap_or_default().to_string();
+            let placements = lines
+                .filter_map(|line| {
+                    let (rank, rest) = line.split_once(". ")?;
+                    let (player, faction) = rest.split_once(" - ")?;
+                    Some(AgentOutput::new(
+                        PlacementStub {
+                            rank: rank.parse().ok()?,
+                            player_name: player.to_string(),
+                            faction: faction.to_string(),
+                            subfaction: None,
+                            detachment: None,
+                            record: None,
+                            battle_points: None,
+                            team: None,
+                        },
+                        Confidence::High,
+                    ))
+                })
+                .collect();
+            Ok(vec![ExtractedEvent {
+                event: AgentOutput::new(
+                    EventStub {
+                        name,
+                        date: None,
+                        location: None,
+                        player_count: Some(2),
+                        round_count: None,
+                        event_type: None,
+                        article_section: None,
+                        mission_pack: None,
+                    },
+                    Confidence::High,
+                ),
+                placements,
+                lists: vec![RawListText {
+                    placement_rank: 1,
+                    player_name: "Alice".to_string(),
+                    text: "Aeldari\nStrike Force (2000 points)".to_string(),
+                }],
+            }])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registered_source_runs_through_stages() {
+        let mut registry = SourceRegistry::builtin();
+        registry.register("example", |_| Some(Arc::new(PageSource) as Arc<dyn Source>));
+        assert_eq!(
+            registry.names(),
+            ["bcp", "example", "goonhammer", "t3", "warhammer-community"]
+        );
+        let config = SourcesConfig::default();
+        assert_eq!(registry.build("bcp", &config).unwrap().name(), "bcp");
+        // Only BCP is enabled by default
+        let enabled = registry.enabled(&config);
+        assert_eq!(enabled.len(), 1);
+        assert_eq!(enabled[0].name(), "bcp");
+
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = StorageConfig::new(tmp.path().to_path_buf());
+        let fetcher = Fetcher::new(FetcherConfig {
+            cache_dir: storage.raw_dir(),
+            ..Default::default()
+        })
+        .unwrap();
+        let orchestrator = SyncOrchestrator::new(
+            SyncConfig {
+                sources: vec![registry.build("example", &config).unwrap()],
+                storage: storage.clone(),
+                ..Default::default()
+            },
+            fetcher,
+            Arc::new(MockBackend::new("{}")),
+        );
+
+        let result = orchestrator.sync_once().await.unwrap();
+        assert_eq!((result.events_synced, result.placements_synced), (1, 2));
+
+        let events: Vec<Event> = JsonlReader::for_entity(&storage, EntityType::Event, "current")
+            .read_all()
+            .unwrap();
+        assert_eq!(events[0].name, "Spring GT");
+        assert_eq!(events[0].source_name, "example");
+        let placements: Vec<Placement> =
+            JsonlReader::for_entity(&storage, EntityType::Placement, "current")
+                .read_all()
+                .unwrap();
+        assert!(placements[0].list_id.is_some());
+        assert_eq!(
+            SyncCursor::load(&storage).unwrap().get("example"),
+            NaiveDate::from_ymd_opt(2026, 3, 14)
+        );
+
+        // A second run finds the event already stored
+        let result = orchestrator.sync_once().await.unwrap();
+        assert_eq!(result.events_synced, 0);
+    }
+}
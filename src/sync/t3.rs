@@ -0,0 +1,508 @@
+//! Tabletop Tournaments (T3) client.
+//!
+//! Most European events run on tabletopturniere.de rather than BCP. T3 has
+//! no public API, so this module scrapes the tournament calendar, results
+//! table and army list pages. Page paths and column headers are isolated
+//! here so layout changes are easy to fix; the parsers are pure functions
+//! over HTML so they can be tested against saved pages.
+
+use std::sync::OnceLock;
+
+use chrono::NaiveDate;
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+use tracing::info;
+use url::Url;
+
+use crate::fetch::{FetchError, Fetcher};
+
+/// Default T3 site root.
+pub const T3_BASE_URL: &str = "https://www.tabletopturniere.de";
+
+/// Calendar page listing past tournaments.
+const CALENDAR_PATH: &str = "t3_tournament_list.php";
+/// Tournament overview page, keyed by `tid`.
+const EVENT_PATH: &str = "t3_tournament.php";
+/// Final results table, keyed by `tid`.
+const RESULTS_PATH: &str = "t3_tournament_results.php";
+
+/// A tournament from the T3 calendar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct T3Event {
+    /// T3 tournament ID (`tid`)
+    pub id: String,
+    pub name: String,
+    pub date: Option<NaiveDate>,
+    /// Game system as shown in the calendar, e.g. "Warhammer 40.000"
+    pub game_system: Option<String>,
+    pub location: Option<String>,
+    /// Registered players
+    pub player_count: Option<u32>,
+}
+
+impl T3Event {
+    /// Public URL of the tournament page, used as the event source URL.
+    pub fn event_url(&self, base_url: &str) -> String {
+        format!(
+            "{}/{}?tid={}",
+            base_url.trim_end_matches('/'),
+            EVENT_PATH,
+            self.id
+        )
+    }
+
+    /// Whether the tournament day has passed. T3 tournaments are one or two
+    /// day events, so anything dated before today is treated as final.
+    pub fn is_finished(&self, today: NaiveDate) -> bool {
+        self.date.is_some_and(|d| d < today)
+    }
+}
+
+/// One row of a T3 results table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct T3Standing {
+    pub rank: u32,
+    pub player_name: String,
+    pub faction: Option<String>,
+    pub wins: Option<u32>,
+    pub draws: Option<u32>,
+    pub losses: Option<u32>,
+    /// Tournament points (T3 "TP" / "Punkte")
+    pub battle_points: Option<u32>,
+    /// Absolute URL of the player's army list page, if published
+    pub list_url: Option<String>,
+}
+
+/// T3 client.
+pub struct T3Client {
+    fetcher: Fetcher,
+    base_url: String,
+    game_system: String,
+}
+
+impl T3Client {
+    /// Create a new T3 client. Only calendar entries whose game system
+    /// contains `game_system` (case-insensitive) are returned.
+    pub fn new(fetcher: Fetcher, base_url: String, game_system: String) -> Self {
+        Self {
+            fetcher,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            game_system,
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Discover tournaments for the configured game system in a date range.
+    pub async fn discover_events(
+        &self,
+        date_from: NaiveDate,
+        date_to: NaiveDate,
+    ) -> Result<Vec<T3Event>, FetchError> {
+        let url = self.page_url(
+            CALENDAR_PATH,
+            &[
+                ("from", date_from.format("%d.%m.%Y").to_string()),
+                ("to", date_to.format("%d.%m.%Y").to_string()),
+            ],
+        )?;
+
+        info!("T3: discovering events {} to {}", date_from, date_to);
+        let html = self.fetch_text(&url).await?;
+        let events: Vec<T3Event> = parse_calendar(&html)
+            .into_iter()
+            .filter(|e| matches_game_system(e, &self.game_system))
+            .filter(|e| e.date.is_none_or(|d| d >= date_from && d <= date_to))
+            .collect();
+
+        info!("T3: found {} events", events.len());
+        Ok(events)
+    }
+
+    /// URL of a tournament's results page.
+    pub fn standings_url(&self, event: &T3Event) -> Result<Url, FetchError> {
+        self.page_url(RESULTS_PATH, &[("tid", event.id.clone())])
+    }
+
+    /// Fetch the final standings for a tournament.
+    pub async fn fetch_standings(&self, event: &T3Event) -> Result<Vec<T3Standing>, FetchError> {
+        let url = self.standings_url(event)?;
+        let html = self.fetch_text(&url).await?;
+        Ok(parse_standings(&html, &self.base_url))
+    }
+
+    /// Fetch a player's army list text. Returns None when the page has no
+    /// list (unpublished or hidden by the organiser).
+    pub async fn fetch_army_list(&self, list_url: &str) -> Result<Option<String>, FetchError> {
+        let url = Url::parse(list_url)
+            .map_err(|e| FetchError::InvalidUrl(format!("Bad T3 list URL: {}", e)))?;
+        let html = self.fetch_text(&url).await?;
+        Ok(parse_army_list(&html))
+    }
+
+    fn page_url(&self, path: &str, query: &[(&str, String)]) -> Result<Url, FetchError> {
+        let mut url = Url::parse(&format!("{}/{}", self.base_url, path))
+            .map_err(|e| FetchError::InvalidUrl(format!("Bad T3 URL: {}", e)))?;
+        url.query_pairs_mut()
+            .extend_pairs(query.iter().map(|(k, v)| (*k, v.as_str())));
+        Ok(url)
+    }
+
+    async fn fetch_text(&self, url: &Url) -> Result<String, FetchError> {
+        let result = self.fetcher.fetch(url).await?;
+        self.fetcher.read_cached_text(&result).await
+    }
+}
+
+fn matches_game_system(event: &T3Event, wanted: &str) -> bool {
+    let wanted = wanted.trim().to_lowercase();
+    wanted.is_empty()
+        || event
+            .game_system
+            .as_ref()
+            .is_some_and(|s| s.to_lowercase().contains(&wanted))
+}
+
+// ── Page parsers ────────────────────────────────────────────────────────────
+
+fn tid_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"[?&]tid=(\d+)").expect("valid tid regex"))
+}
+
+fn date_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\b(\d{1,2})\.(\d{1,2})\.(\d{4})\b").expect("valid date regex"))
+}
+
+fn players_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^(\d+)\s*/\s*\d+$").expect("valid players regex"))
+}
+
+fn record_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"^(\d+)\s*[/\-]\s*(\d+)\s*[/\-]\s*(\d+)$").expect("valid record regex")
+    })
+}
+
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).expect("valid T3 selector")
+}
+
+fn cell_text(el: &ElementRef) -> String {
+    el.text()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_t3_date(text: &str) -> Option<NaiveDate> {
+    let caps = date_regex().captures(text)?;
+    NaiveDate::from_ymd_opt(
+        caps[3].parse().ok()?,
+        caps[2].parse().ok()?,
+        caps[1].parse().ok()?,
+    )
+}
+
+/// Parse the tournament calendar. Each table row linking to a tournament
+/// page becomes an event; the date ("dd.mm.yyyy"), player count ("12/40")
+/// and game system / location cells are picked up by content.
+pub fn parse_calendar(html: &str) -> Vec<T3Event> {
+    let doc = Html::parse_document(html);
+    let row_sel = selector("tr");
+    let cell_sel = selector("td");
+    let link_sel = selector(r#"a[href*="tid="]"#);
+
+    let mut events: Vec<T3Event> = Vec::new();
+    for row in doc.select(&row_sel) {
+        let Some(link) = row.select(&link_sel).next() else {
+            continue;
+        };
+        let Some(id) = link
+            .value()
+            .attr("href")
+            .and_then(|h| tid_regex().captures(h))
+            .map(|c| c[1].to_string())
+        else {
+            continue;
+        };
+        if events.iter().any(|e| e.id == id) {
+            continue;
+        }
+        let name = cell_text(&link);
+        if name.is_empty() {
+            continue;
+        }
+
+        let mut event = T3Event {
+            id,
+            name: name.clone(),
+            date: None,
+            game_system: None,
+            location: None,
+            player_count: None,
+        };
+        for cell in row.select(&cell_sel) {
+            let text = cell_text(&cell);
+            if text.is_empty() || text.contains(&name) {
+                continue;
+            }
+            if event.date.is_none() {
+                if let Some(date) = parse_t3_date(&text) {
+                    event.date = Some(date);
+                    continue;
+                }
+            }
+            if let Some(caps) = players_regex().captures(&text) {
+                event.player_count = caps[1].parse().ok();
+            } else if event.game_system.is_none() && looks_like_game_system(&text) {
+                event.game_system = Some(text);
+            } else if event.location.is_none() && !text.chars().all(|c| c.is_ascii_digit()) {
+                event.location = Some(text);
+            }
+        }
+        events.push(event);
+    }
+    events
+}
+
+fn looks_like_game_system(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    [
+        "warhammer",
+        "40k",
+        "40.000",
+        "kill team",
+        "age of sigmar",
+        "horus heresy",
+    ]
+    .iter()
+    .any(|k| lower.contains(k))
+}
+
+/// Column positions in a results table, found from its header row.
+#[derive(Debug, Default)]
+struct ResultColumns {
+    rank: Option<usize>,
+    player: Option<usize>,
+    faction: Option<usize>,
+    record: Option<usize>,
+    wins: Option<usize>,
+    draws: Option<usize>,
+    losses: Option<usize>,
+    points: Option<usize>,
+}
+
+impl ResultColumns {
+    fn from_headers(headers: &[String]) -> Self {
+        let mut cols = Self::default();
+        for (i, h) in headers.iter().enumerate() {
+            let h = h.to_lowercase();
+            let slot = match h.as_str() {
+                "#" | "platz" | "rang" | "rank" | "pos" | "pos." => &mut cols.rank,
+                "spieler" | "player" | "name" | "teilnehmer" | "nick" => &mut cols.player,
+                "armee" | "army" | "fraktion" | "faction" | "volk" => &mut cols.faction,
+                "s/u/n" | "w/d/l" | "s-u-n" | "w-d-l" | "bilanz" | "record" => &mut cols.record,
+                "s" | "w" | "siege" | "wins" => &mut cols.wins,
+                "u" | "d" | "unentschieden" | "draws" => &mut cols.draws,
+                "n" | "l" | "niederlagen" | "losses" => &mut cols.losses,
+                "tp" | "punkte" | "points" | "turnierpunkte" | "bp" => &mut cols.points,
+                _ => continue,
+            };
+            slot.get_or_insert(i);
+        }
+        cols
+    }
+}
+
+/// Parse a T3 results table. Columns are mapped from the header row
+/// (German or English labels); rows whose rank cell is not a number are
+/// skipped. Army list links are resolved against `base_url`.
+pub fn parse_standings(html: &str, base_url: &str) -> Vec<T3Standing> {
+    let doc = Html::parse_document(html);
+    let table_sel = selector("table");
+    let row_sel = selector("tr");
+    let header_sel = selector("th");
+    let cell_sel = selector("td");
+    let link_sel = selector("a[href]");
+
+    for table in doc.select(&table_sel) {
+        let headers: Vec<String> = table.select(&header_sel).map(|h| cell_text(&h)).collect();
+        let cols = ResultColumns::from_headers(&headers);
+        let (Some(rank_col), Some(player_col)) = (cols.rank, cols.player) else {
+            continue;
+        };
+
+        let mut standings = Vec::new();
+        for row in table.select(&row_sel) {
+            let cells: Vec<ElementRef> = row.select(&cell_sel).collect();
+            let text_at = |i: Option<usize>| i.and_then(|i| cells.get(i)).map(cell_text);
+            let Some(rank) = text_at(Some(rank_col))
+                .and_then(|t| t.trim_end_matches('.').trim().parse::<u32>().ok())
+            else {
+                continue;
+            };
+            let Some(player_name) = text_at(Some(player_col)).filter(|n| !n.is_empty()) else {
+                continue;
+            };
+
+            let number_at = |i: Option<usize>| text_at(i).and_then(|t| t.parse::<u32>().ok());
+            let (mut wins, mut draws, mut losses) = (
+                number_at(cols.wins),
+                number_at(cols.draws),
+                number_at(cols.losses),
+            );
+            let record = text_at(cols.record).unwrap_or_default();
+            if let Some(caps) = record_regex().captures(&record) {
+                // T3 orders records win/draw/loss ("S/U/N")
+                wins = caps[1].parse().ok();
+                draws = caps[2].parse().ok();
+                losses = caps[3].parse().ok();
+            }
+
+            let list_url = row
+                .select(&link_sel)
+                .filter_map(|a| a.value().attr("href"))
+                .find(|h| {
+                    let h = h.to_lowercase();
+                    h.contains("armylist") || h.contains("armeeliste") || h.contains("list.php")
+                })
+                .and_then(|h| resolve_url(base_url, h));
+
+            standings.push(T3Standing {
+                rank,
+                player_name,
+                faction: text_at(cols.faction).filter(|f| !f.is_empty()),
+                wins,
+                draws,
+                losses,
+                battle_points: number_at(cols.points),
+                list_url,
+            });
+        }
+        if !standings.is_empty() {
+            standings.sort_by_key(|s| s.rank);
+            return standings;
+        }
+    }
+    Vec::new()
+}
+
+fn resolve_url(base_url: &str, href: &str) -> Option<String> {
+    let base = Url::parse(&format!("{}/", base_url.trim_end_matches('/'))).ok()?;
+    base.join(href).ok().map(|u| u.to_string())
+}
+
+/// Extract the army list text from a T3 list page: the first `pre` or
+/// `textarea` block, which is where T3 renders submitted lists.
+pub fn parse_army_list(html: &str) -> Option<String> {
+    let doc = Html::parse_document(html);
+    let sel = selector("pre, textarea");
+    doc.select(&sel)
+        .map(|el| el.text().collect::<String>().trim().to_string())
+        .find(|text| !text.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALENDAR: &str = r#"
+        <table>
+          <tr><th>Datum</th><th>Turnier</th><th>System</th><th>Ort</th><th>Spieler</th></tr>
+          <tr>
+            <td>14.06.2025</td>
+            <td><a href="t3_tournament.php?tid=34567">Hamburger Hanse Cup</a></td>
+            <td>Warhammer 40.000</td>
+            <td>Hamburg</td>
+            <td>38/40</td>
+          </tr>
+          <tr>
+            <td>15.06.2025</td>
+            <td><a href="t3_tournament.php?tid=34570">Old World Fest</a></td>
+            <td>Warhammer: The Old World</td>
+            <td>Berlin</td>
+            <td>20/24</td>
+          </tr>
+        </table>"#;
+
+    const RESULTS: &str = r#"
+        <table class="results">
+          <tr><th>Platz</th><th>Spieler</th><th>Armee</th><th>S/U/N</th><th>TP</th><th>Liste</th></tr>
+          <tr>
+            <td>2.</td><td>Lena  Vogel</td><td>Necrons</td><td>4/0/1</td><td>82</td>
+            <td><a href="t3_tournament_armylist.php?tid=34567&amp;pid=2">Liste</a></td>
+          </tr>
+          <tr>
+            <td>1.</td><td>Jonas Becker</td><td>Aeldari</td><td>5/0/0</td><td>94</td>
+            <td><a href="t3_tournament_armylist.php?tid=34567&amp;pid=1">Liste</a></td>
+          </tr>
+          <tr><td colspan="6">Nicht gewertet</td></tr>
+        </table>"#;
+
+    #[test]
+    fn test_parse_calendar() {
+        let events = parse_calendar(CALENDAR);
+        assert_eq!(events.len(), 2);
+
+        let e = &events[0];
+        assert_eq!(e.id, "34567");
+        assert_eq!(e.name, "Hamburger Hanse Cup");
+        assert_eq!(e.date, NaiveDate::from_ymd_opt(2025, 6, 14));
+        assert_eq!(e.game_system.as_deref(), Some("Warhammer 40.000"));
+        assert_eq!(e.location.as_deref(), Some("Hamburg"));
+        assert_eq!(e.player_count, Some(38));
+        assert_eq!(
+            e.event_url(T3_BASE_URL),
+            "https://www.tabletopturniere.de/t3_tournament.php?tid=34567"
+        );
+
+        let wanted: Vec<_> = events
+            .iter()
+            .filter(|e| matches_game_system(e, "Warhammer 40"))
+            .collect();
+        assert_eq!(wanted.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_standings() {
+        let standings = parse_standings(RESULTS, T3_BASE_URL);
+        assert_eq!(standings.len(), 2);
+
+        let winner = &standings[0];
+        assert_eq!(winner.rank, 1);
+        assert_eq!(winner.player_name, "Jonas Becker");
+        assert_eq!(winner.faction.as_deref(), Some("Aeldari"));
+        assert_eq!(
+            (winner.wins, winner.draws, winner.losses),
+            (Some(5), Some(0), Some(0))
+        );
+        assert_eq!(winner.battle_points, Some(94));
+        assert_eq!(
+            winner.list_url.as_deref(),
+            Some("https://www.tabletopturniere.de/t3_tournament_armylist.php?tid=34567&pid=1")
+        );
+
+        let second = &standings[1];
+        assert_eq!(second.player_name, "Lena Vogel");
+        assert_eq!(
+            (second.wins, second.draws, second.losses),
+            (Some(4), Some(0), Some(1))
+        );
+    }
+
+    #[test]
+    fn test_parse_army_list() {
+        let html = "<div><pre>\n+ FACTION KEYWORD: Necrons\nOverlord (85 pts)\n</pre></div>";
+        let text = parse_army_list(html).unwrap();
+        assert!(text.starts_with("+ FACTION KEYWORD: Necrons"));
+        assert_eq!(parse_army_list("<p>Keine Liste</p>"), None);
+    }
+}